@@ -6,13 +6,17 @@
 use std::sync::Arc;
 use actix_web::{middleware::Logger, web, App, HttpResponse, HttpServer};
 
-use crate::middleware::{cors::create_cors, security::SecurityMiddleware, auth::JwtAuth};
+use crate::middleware::{
+    cors::create_cors, security::SecurityMiddleware, auth::JwtAuth, request_trace::RequestTrace,
+};
 use crate::routes::auth::{
     send_code::send_code, 
     verify_code::verify_code, 
     select_type::select_type, 
     refresh::refresh as refresh_token, 
     logout::logout,
+    report_suspicious::report_suspicious,
+    sessions::{list_sessions, sign_out_session},
     AppState
 };
 
@@ -53,6 +57,7 @@ where
         
         // Add middleware (order matters: security first, then CORS, then logging)
         .wrap(Logger::default())
+        .wrap(RequestTrace::new())
         .wrap(cors)
         .wrap(security)
         
@@ -78,6 +83,21 @@ where
                                 .to(logout::<U, S, C, R, T>)
                                 .wrap(JwtAuth::new())
                         )
+                        .route("/report-suspicious",
+                            web::post()
+                                .to(report_suspicious::<U, S, C, R, T>)
+                                .wrap(JwtAuth::new())
+                        )
+                        .route("/sessions",
+                            web::get()
+                                .to(list_sessions::<U, S, C, R, T>)
+                                .wrap(JwtAuth::new())
+                        )
+                        .route("/sessions/{session_id}",
+                            web::delete()
+                                .to(sign_out_session::<U, S, C, R, T>)
+                                .wrap(JwtAuth::new())
+                        )
                 )
                 // API documentation endpoint
                 .route("/", web::get().to(api_documentation))
@@ -161,6 +181,41 @@ async fn api_documentation() -> HttpResponse {
                     "method": "POST",
                     "description": "Logout and invalidate tokens",
                     "status": "Coming soon"
+                },
+                "report_suspicious": {
+                    "path": "/api/v1/auth/report-suspicious",
+                    "method": "POST",
+                    "description": "Sign out a session from a login the user did not make",
+                    "requires_auth": true,
+                    "request_body": {
+                        "session_id": "string (from the suspicious login notification)"
+                    },
+                    "responses": {
+                        "200": "Session revoked",
+                        "401": "Authentication required",
+                        "404": "Session not found"
+                    }
+                },
+                "list_sessions": {
+                    "path": "/api/v1/auth/sessions",
+                    "method": "GET",
+                    "description": "List the devices the user is signed in on",
+                    "requires_auth": true,
+                    "responses": {
+                        "200": "Sessions with device, masked IP address and location",
+                        "401": "Authentication required"
+                    }
+                },
+                "sign_out_session": {
+                    "path": "/api/v1/auth/sessions/{session_id}",
+                    "method": "DELETE",
+                    "description": "Sign out one of the user's sessions",
+                    "requires_auth": true,
+                    "responses": {
+                        "200": "Session revoked",
+                        "401": "Authentication required",
+                        "404": "Session not found"
+                    }
                 }
             }
         }
@@ -0,0 +1,217 @@
+//! Cluster events received by this instance
+//!
+//! Every instance runs a background task subscribed to the cluster event
+//! bus and applies what it receives:
+//! - Revoked tokens are added to the token service's revocation cache
+//! - Reloaded configuration sections run the reloaders registered for them
+//! - Maintenance mode toggles switch the state read by the maintenance
+//!   middleware
+//! - Invalidated cache keys are dropped from the in-process cache tier
+//! - Log level overrides are applied to or cleared from the tracing filter
+//!
+//! ```ignore
+//! let dispatcher = Arc::new(
+//!     ClusterEventDispatcher::new(maintenance.clone())
+//!         .with_log_level(log_level_service.clone())
+//!         .with_revocation_cache(token_service.revocation_cache())
+//!         .with_reloader("ip_access", ip_access_service.clone())
+//!         .with_local_cache(tiered_cache.clone()),
+//! );
+//! cluster_events::start_subscriber(event_bus.clone(), dispatcher);
+//! ```
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use re_core::services::auth::{IpAccessControlService, IpAccessStoreTrait};
+use re_core::services::cluster::{ClusterEvent, ClusterEventHandlerTrait};
+use re_core::services::log_level::{LogFilterTrait, LogLevelService};
+use re_core::services::token::RevocationCache;
+use re_infra::cache::{RedisClusterEventBus, TieredCacheBackend};
+
+use crate::lifecycle::MaintenanceState;
+
+/// Delay before subscribing again after the connection drops
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Trait for configuration that can be reloaded at runtime
+#[async_trait]
+pub trait ConfigReloaderTrait: Send + Sync {
+    /// Load the configuration again from its source
+    async fn reload(&self) -> Result<(), String>;
+}
+
+#[async_trait]
+impl<S> ConfigReloaderTrait for IpAccessControlService<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    async fn reload(&self) -> Result<(), String> {
+        self.refresh().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Trait for runtime log level overrides announced by other instances
+pub trait LogLevelOverrideTrait: Send + Sync {
+    /// Apply an override until it expires, replacing any active one
+    fn apply_override(
+        &self,
+        level: Option<&str>,
+        targets: &BTreeMap<String, String>,
+        expires_at: DateTime<Utc>,
+        set_by: Option<Uuid>,
+    ) -> Result<(), String>;
+
+    /// Clear the active override
+    fn clear_override(&self) -> Result<(), String>;
+}
+
+impl<F> LogLevelOverrideTrait for LogLevelService<F>
+where
+    F: LogFilterTrait + 'static,
+{
+    fn apply_override(
+        &self,
+        level: Option<&str>,
+        targets: &BTreeMap<String, String>,
+        expires_at: DateTime<Utc>,
+        set_by: Option<Uuid>,
+    ) -> Result<(), String> {
+        // An override that expired in transit has nothing left to apply
+        let ttl = expires_at - Utc::now();
+        if ttl <= chrono::Duration::zero() {
+            return Ok(());
+        }
+
+        self.set_override(level, targets, Some(ttl), set_by)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn clear_override(&self) -> Result<(), String> {
+        LogLevelService::clear_override(self)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Applies cluster events to the state of this instance
+pub struct ClusterEventDispatcher {
+    maintenance: Arc<MaintenanceState>,
+    revocation_cache: Option<Arc<RevocationCache>>,
+    reloaders: HashMap<String, Vec<Arc<dyn ConfigReloaderTrait>>>,
+    local_cache: Option<Arc<TieredCacheBackend>>,
+    log_level: Option<Arc<dyn LogLevelOverrideTrait>>,
+}
+
+impl ClusterEventDispatcher {
+    /// Create a dispatcher switching the given maintenance state
+    pub fn new(maintenance: Arc<MaintenanceState>) -> Self {
+        Self {
+            maintenance,
+            revocation_cache: None,
+            reloaders: HashMap::new(),
+            local_cache: None,
+            log_level: None,
+        }
+    }
+
+    /// Add revoked tokens to a revocation cache
+    pub fn with_revocation_cache(mut self, cache: Arc<RevocationCache>) -> Self {
+        self.revocation_cache = Some(cache);
+        self
+    }
+
+    /// Run a reloader when a configuration section is reloaded
+    pub fn with_reloader(mut self, section: impl Into<String>, reloader: Arc<dyn ConfigReloaderTrait>) -> Self {
+        self.reloaders.entry(section.into()).or_default().push(reloader);
+        self
+    }
+
+    /// Drop invalidated keys from the in-process tier of a cache
+    pub fn with_local_cache(mut self, cache: Arc<TieredCacheBackend>) -> Self {
+        self.local_cache = Some(cache);
+        self
+    }
+
+    /// Apply log level overrides to this instance's tracing filter
+    pub fn with_log_level(mut self, log_level: Arc<dyn LogLevelOverrideTrait>) -> Self {
+        self.log_level = Some(log_level);
+        self
+    }
+}
+
+#[async_trait]
+impl ClusterEventHandlerTrait for ClusterEventDispatcher {
+    async fn handle(&self, event: ClusterEvent) {
+        match event {
+            ClusterEvent::TokenRevoked { revocation } => {
+                if let Some(cache) = &self.revocation_cache {
+                    cache.apply(&revocation);
+                }
+            }
+            ClusterEvent::ConfigReloaded { section } => {
+                let Some(reloaders) = self.reloaders.get(&section) else {
+                    warn!("No reloader registered for configuration section '{}'", section);
+                    return;
+                };
+
+                for reloader in reloaders {
+                    match reloader.reload().await {
+                        Ok(()) => info!("Reloaded configuration section '{}'", section),
+                        Err(e) => error!("Failed to reload configuration section '{}': {}", section, e),
+                    }
+                }
+            }
+            ClusterEvent::MaintenanceModeToggled { enabled, message, toggled_by } => {
+                if self.maintenance.set(enabled, message) {
+                    info!(
+                        "Maintenance mode switched {} by {}",
+                        if enabled { "on" } else { "off" },
+                        toggled_by.map_or_else(|| "the system".to_string(), |id| id.to_string())
+                    );
+                }
+            }
+            ClusterEvent::CacheInvalidated { keys } => {
+                if let Some(cache) = &self.local_cache {
+                    cache.invalidate_local(&keys).await;
+                }
+            }
+            ClusterEvent::LogLevelOverridden { level, targets, expires_at, set_by } => {
+                if let Some(log_level) = &self.log_level {
+                    if let Err(e) = log_level.apply_override(level.as_deref(), &targets, expires_at, set_by) {
+                        error!("Failed to apply log level override: {}", e);
+                    }
+                }
+            }
+            ClusterEvent::LogLevelCleared => {
+                if let Some(log_level) = &self.log_level {
+                    if let Err(e) = log_level.clear_override() {
+                        error!("Failed to clear log level override: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply the events published by any instance for the lifetime of the process
+///
+/// Spawns a task that subscribes to the event bus and subscribes again
+/// whenever the connection drops.
+pub fn start_subscriber(bus: Arc<RedisClusterEventBus>, dispatcher: Arc<ClusterEventDispatcher>) {
+    tokio::spawn(async move {
+        loop {
+            match bus.listen(dispatcher.as_ref()).await {
+                Ok(()) => warn!("Cluster event subscription closed, subscribing again"),
+                Err(e) => error!("Cluster event subscription failed: {}", e),
+            }
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    });
+}
@@ -7,15 +7,24 @@
 //! - Integrates with shared configuration types
 
 use re_shared::config::{
-    auth::AuthConfig,
+    auth::{AdminSsoConfig, AuthConfig},
     cache::{CacheConfig, CacheStrategyConfig},
-    database::DatabaseConfig,
+    database::{DatabaseConfig, DatabaseShardConfig, DatabaseSslMode},
     environment::{Environment, LoggingConfig, MonitoringConfig},
-    rate_limit::RateLimitConfig,
-    server::{CorsConfig, ServerConfig},
+    rate_limit::{RateLimitAlgorithm, RateLimitConfig},
+    server::{CorsConfig, ServerConfig, TlsConfig, UnknownFieldMode},
+    secret::SecretString,
+    sms::{SmsBudgetConfig, SmsMarketConfig, SmsProviderLimits, SmsRoute},
 };
+use re_core::domain::entities::admin::AdminRole;
+use re_core::services::auth::{AuthServiceConfig, PhoneHasher, TrustedTesterConfig};
+use re_core::services::tenant::TenantId;
+use re_infra::capabilities::ConfiguredServices;
 use serde::{Deserialize, Serialize};
-use std::{env, fmt, error::Error};
+use std::{collections::HashMap, env, fmt, error::Error};
+
+/// Minimum length of each phone hash pepper, in bytes
+const MIN_PHONE_HASH_PEPPER_LENGTH: usize = 32;
 
 /// Configuration errors
 #[derive(Debug)]
@@ -59,10 +68,10 @@ pub struct SmsConfig {
     pub provider: String,
 
     /// API key for the SMS service
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretString>,
 
     /// API secret for the SMS service (if required)
-    pub api_secret: Option<String>,
+    pub api_secret: Option<SecretString>,
 
     /// SMS sender ID or phone number
     pub sender_id: Option<String>,
@@ -75,6 +84,36 @@ pub struct SmsConfig {
 
     /// Use mock provider in development
     pub use_mock_in_dev: bool,
+
+    /// Phone hashes of trusted tester phones exempt from SMS rate limits, as
+    /// stored with their users under the configured phone hash pepper
+    #[serde(default)]
+    pub trusted_tester_phone_hashes: Vec<String>,
+
+    /// Fixed verification code for trusted testers, sent instead of an SMS
+    #[serde(default)]
+    pub trusted_tester_code: Option<String>,
+
+    /// Per-market sender IDs and branding, selected by recipient dialing code
+    #[serde(default)]
+    pub markets: Vec<SmsMarketConfig>,
+
+    /// Provider per recipient dialing code; without a default route the
+    /// remaining destinations use `provider`
+    #[serde(default)]
+    pub routes: Vec<SmsRoute>,
+
+    /// Per-provider concurrency and rate caps, keyed by provider name
+    #[serde(default)]
+    pub provider_limits: Vec<SmsProviderLimits>,
+
+    /// Estimated cost per provider and destination, and the daily budget
+    #[serde(default)]
+    pub budget: SmsBudgetConfig,
+
+    /// Record messages instead of calling the provider APIs, outside production
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl Default for SmsConfig {
@@ -87,6 +126,13 @@ impl Default for SmsConfig {
             template_id: None,
             enabled: true,
             use_mock_in_dev: true,
+            trusted_tester_phone_hashes: Vec::new(),
+            trusted_tester_code: None,
+            markets: Vec::new(),
+            routes: Vec::new(),
+            provider_limits: Vec::new(),
+            budget: SmsBudgetConfig::default(),
+            dry_run: false,
         }
     }
 }
@@ -118,6 +164,15 @@ impl SmsConfig {
                 env::var("AWS_SNS_SENDER_ID").ok()
                     .or_else(|| env::var("SMS_SENDER_ID").ok())
             )
+        } else if provider == "vonage" {
+            (
+                env::var("VONAGE_API_KEY").ok()
+                    .or_else(|| env::var("SMS_API_KEY").ok()),
+                env::var("VONAGE_API_SECRET").ok()
+                    .or_else(|| env::var("SMS_API_SECRET").ok()),
+                env::var("VONAGE_FROM").ok()
+                    .or_else(|| env::var("SMS_SENDER_ID").ok())
+            )
         } else {
             (
                 env::var("SMS_API_KEY").ok(),
@@ -135,18 +190,50 @@ impl SmsConfig {
             .unwrap_or_else(|_| "true".to_string())
             .parse()
             .unwrap_or(true);
+        let trusted_tester_phone_hashes = env::var("SMS_TRUSTED_TESTER_HASHES")
+            .map(|hashes| {
+                hashes
+                    .split(',')
+                    .map(|hash| hash.trim().to_string())
+                    .filter(|hash| !hash.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let trusted_tester_code = env::var("SMS_TRUSTED_TESTER_CODE").ok();
+        let markets = SmsMarketConfig::list_from_env();
+        let routes = SmsRoute::list_from_env();
+        let provider_limits = SmsProviderLimits::list_from_env();
+        let budget = SmsBudgetConfig::from_env();
+        let dry_run = env::var("SMS_DRY_RUN")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .unwrap_or(false);
 
         Self {
             provider,
-            api_key,
-            api_secret,
+            api_key: api_key.map(SecretString::from),
+            api_secret: api_secret.map(SecretString::from),
             sender_id,
             template_id,
             enabled,
             use_mock_in_dev,
+            trusted_tester_phone_hashes,
+            trusted_tester_code,
+            markets,
+            routes,
+            provider_limits,
+            budget,
+            dry_run,
         }
     }
 
+    /// Build the trusted tester allowlist for the authentication service
+    pub fn trusted_testers(&self) -> TrustedTesterConfig {
+        let mut config = TrustedTesterConfig::new(self.trusted_tester_phone_hashes.iter().cloned());
+        config.fixed_code = self.trusted_tester_code.clone();
+        config
+    }
+
     /// Check if using mock provider
     pub fn is_mock(&self) -> bool {
         self.provider == "mock"
@@ -154,6 +241,43 @@ impl SmsConfig {
 
     /// Validate SMS configuration
     pub fn validate(&self, environment: Environment) -> Result<(), ConfigError> {
+        if let Some(hash) = self
+            .trusted_tester_phone_hashes
+            .iter()
+            .find(|hash| hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(ConfigError::ValidationError(format!(
+                "SMS_TRUSTED_TESTER_HASHES entry '{}' is not a hex-encoded phone hash",
+                hash
+            )));
+        }
+        if let Some(ref code) = self.trusted_tester_code {
+            if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ConfigError::ValidationError(
+                    "SMS_TRUSTED_TESTER_CODE must be 6 digits".to_string()
+                ));
+            }
+        }
+        for (index, market) in self.markets.iter().enumerate() {
+            market.validate().map_err(ConfigError::ValidationError)?;
+            if self.markets[..index].iter().any(|other| other.country_code == market.country_code) {
+                return Err(ConfigError::ValidationError(format!(
+                    "SMS market {} is configured more than once",
+                    market.country_code
+                )));
+            }
+        }
+        SmsRoute::validate_all(&self.routes).map_err(ConfigError::ValidationError)?;
+        for limits in &self.provider_limits {
+            limits.validate().map_err(ConfigError::ValidationError)?;
+        }
+        self.budget.validate().map_err(ConfigError::ValidationError)?;
+        if self.dry_run && environment.is_production() {
+            return Err(ConfigError::ValidationError(
+                "SMS_DRY_RUN cannot be enabled in production".to_string()
+            ));
+        }
+
         // In production, require real SMS configuration unless explicitly using mock
         if environment.is_production() && !self.is_mock() && self.provider != "failover" {
             if self.api_key.is_none() {
@@ -184,6 +308,14 @@ impl SmsConfig {
                     // AWS SNS doesn't require a from number/sender ID in all regions
                     // so we don't validate it as required
                 }
+                "vonage" => {
+                    if self.api_secret.is_none() {
+                        return Err(ConfigError::MissingVar("VONAGE_API_SECRET or SMS_API_SECRET".to_string()));
+                    }
+                    if self.sender_id.is_none() {
+                        return Err(ConfigError::MissingVar("VONAGE_FROM or SMS_SENDER_ID".to_string()));
+                    }
+                }
                 "aliyun" => {
                     if self.template_id.is_none() {
                         return Err(ConfigError::MissingVar("SMS_TEMPLATE_ID".to_string()));
@@ -192,17 +324,19 @@ impl SmsConfig {
                 _ => {}
             }
         }
-        // For failover provider, validate both Twilio and AWS SNS configs separately
+        // For failover provider, validate the Twilio, AWS SNS and Vonage configs separately
         if self.provider == "failover" && environment.is_production() {
             // Check if at least one provider is configured
             let has_twilio = env::var("TWILIO_ACCOUNT_SID").is_ok() &&
                              env::var("TWILIO_AUTH_TOKEN").is_ok();
             let has_aws = env::var("AWS_ACCESS_KEY_ID").is_ok() &&
                          env::var("AWS_SECRET_ACCESS_KEY").is_ok();
+            let has_vonage = env::var("VONAGE_API_KEY").is_ok() &&
+                            env::var("VONAGE_API_SECRET").is_ok();
 
-            if !has_twilio && !has_aws {
+            if !has_twilio && !has_aws && !has_vonage {
                 return Err(ConfigError::ValidationError(
-                    "Failover SMS provider requires at least one of Twilio or AWS SNS to be configured".to_string()
+                    "Failover SMS provider requires at least one of Twilio, AWS SNS or Vonage to be configured".to_string()
                 ));
             }
         }
@@ -244,7 +378,7 @@ pub struct Config {
     pub monitoring: MonitoringConfig,
 
     /// Optional Google Maps API key for location services
-    pub google_maps_api_key: Option<String>,
+    pub google_maps_api_key: Option<SecretString>,
 }
 
 impl Config {
@@ -298,6 +432,7 @@ impl Config {
         config.logging = LoggingConfig::for_environment(environment);
         config.database = DatabaseConfig::new("mysql://staging-db:3306/renoveasy_staging")
             .with_max_connections(20);
+        config.server.unknown_field_mode = UnknownFieldMode::Strict;
         config
     }
 
@@ -330,6 +465,8 @@ impl Config {
                     ..Default::default()
                 },
                 oauth2: None,
+                admin_sso: None,
+                phone_hash: Default::default(),
             },
             server: ServerConfig::new("0.0.0.0", 8080),
             rate_limit: RateLimitConfig::production(),
@@ -342,7 +479,7 @@ impl Config {
                 tracing_enabled: true,
                 ..Default::default()
             },
-            google_maps_api_key: env::var("GOOGLE_MAPS_API_KEY").ok(),
+            google_maps_api_key: env::var("GOOGLE_MAPS_API_KEY").ok().map(SecretString::from),
         })
     }
 
@@ -350,7 +487,7 @@ impl Config {
     fn override_from_env(&mut self) -> Result<(), ConfigError> {
         // Override database configuration
         if let Ok(url) = env::var("DATABASE_URL") {
-            self.database.url = url;
+            self.database.url = SecretString::from(url);
         }
         if let Ok(max_conn) = env::var("DATABASE_MAX_CONNECTIONS") {
             self.database.max_connections = max_conn.parse()
@@ -359,17 +496,76 @@ impl Config {
                     value: max_conn,
                 })?;
         }
+        if let Ok(threshold) = env::var("DATABASE_SLOW_QUERY_THRESHOLD_MS") {
+            self.database.slow_query_threshold = threshold.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "DATABASE_SLOW_QUERY_THRESHOLD_MS".to_string(),
+                    value: threshold,
+                })?;
+        }
+        if let Ok(mode) = env::var("DATABASE_SSL_MODE") {
+            self.database.tls.ssl_mode = Some(DatabaseSslMode::from_str(&mode)
+                .ok_or(ConfigError::InvalidValue {
+                    key: "DATABASE_SSL_MODE".to_string(),
+                    value: mode,
+                })?);
+        }
+        if let Ok(path) = env::var("DATABASE_SSL_CA") {
+            self.database.tls.ca_cert_path = Some(path);
+        }
+        if let Ok(path) = env::var("DATABASE_SSL_CERT") {
+            self.database.tls.client_cert_path = Some(path);
+        }
+        if let Ok(path) = env::var("DATABASE_SSL_KEY") {
+            self.database.tls.client_key_path = Some(path);
+        }
+        if let Ok(names) = env::var("DATABASE_SHARDS") {
+            self.database.shards = names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    let prefix = format!("DATABASE_SHARD_{}", name.to_uppercase().replace('-', "_"));
+                    let url = env::var(format!("{}_URL", prefix))
+                        .map_err(|_| ConfigError::MissingVar(format!("{}_URL", prefix)))?;
+                    let country_codes = env::var(format!("{}_COUNTRY_CODES", prefix))
+                        .map_err(|_| ConfigError::MissingVar(format!("{}_COUNTRY_CODES", prefix)))?
+                        .split(',')
+                        .map(|code| code.trim().to_string())
+                        .filter(|code| !code.is_empty())
+                        .collect();
+                    Ok(DatabaseShardConfig {
+                        name: name.to_string(),
+                        url: SecretString::from(url),
+                        country_codes,
+                    })
+                })
+                .collect::<Result<_, ConfigError>>()?;
+        }
 
         // Override cache configuration
         if let Some(redis) = &mut self.cache.redis {
             if let Ok(url) = env::var("REDIS_URL") {
-                redis.url = url;
+                redis.url = SecretString::from(url);
+            }
+            if let Ok(sentinels) = env::var("REDIS_SENTINELS") {
+                redis.sentinels = sentinels
+                    .split(',')
+                    .map(|url| url.trim().to_string())
+                    .filter(|url| !url.is_empty())
+                    .collect();
+            }
+            if let Ok(master) = env::var("REDIS_SENTINEL_MASTER") {
+                redis.sentinel_master = Some(master);
+            }
+            if let Ok(prefix) = env::var("REDIS_KEY_PREFIX") {
+                redis.key_prefix = CacheConfig::parse_key_prefix(&prefix);
             }
         }
 
         // Override JWT configuration
         if let Ok(secret) = env::var("JWT_SECRET") {
-            self.auth.jwt.secret = secret;
+            self.auth.jwt.secret = SecretString::from(secret);
         }
         if let Ok(expiry) = env::var("JWT_ACCESS_TOKEN_EXPIRY") {
             self.auth.jwt.access_token_expiry = expiry.parse()
@@ -386,6 +582,25 @@ impl Config {
                 })?;
         }
 
+        // Override phone hashing configuration
+        if let Ok(peppers) = env::var("PHONE_HASH_PEPPERS") {
+            self.auth.phone_hash.peppers = peppers
+                .split(',')
+                .map(str::trim)
+                .filter(|pepper| !pepper.is_empty())
+                .map(SecretString::from)
+                .collect();
+        } else if let Ok(pepper) = env::var("PHONE_HASH_PEPPER") {
+            self.auth.phone_hash.peppers = vec![SecretString::from(pepper)];
+        }
+        if let Ok(accept) = env::var("PHONE_HASH_ACCEPT_UNPEPPERED") {
+            self.auth.phone_hash.accept_unpeppered = accept.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "PHONE_HASH_ACCEPT_UNPEPPERED".to_string(),
+                    value: accept,
+                })?;
+        }
+
         // Override server configuration
         if let Ok(host) = env::var("SERVER_HOST") {
             self.server.host = host;
@@ -398,12 +613,149 @@ impl Config {
                 })?;
         }
 
+        if let Ok(delay) = env::var("SERVER_DRAIN_DELAY_SECONDS") {
+            self.server.drain_delay_seconds = delay.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "SERVER_DRAIN_DELAY_SECONDS".to_string(),
+                    value: delay,
+                })?;
+        }
+        if let Ok(timeout) = env::var("SERVER_SHUTDOWN_TIMEOUT_SECONDS") {
+            self.server.shutdown_timeout_seconds = timeout.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "SERVER_SHUTDOWN_TIMEOUT_SECONDS".to_string(),
+                    value: timeout,
+                })?;
+        }
+        if let Ok(mode) = env::var("SERVER_UNKNOWN_FIELD_MODE") {
+            self.server.unknown_field_mode = match mode.to_lowercase().as_str() {
+                "compatible" => UnknownFieldMode::Compatible,
+                "strict" => UnknownFieldMode::Strict,
+                _ => return Err(ConfigError::InvalidValue {
+                    key: "SERVER_UNKNOWN_FIELD_MODE".to_string(),
+                    value: mode,
+                }),
+            };
+        }
+        if let Ok(rollouts) = env::var("SERVER_CANARY_ROLLOUTS") {
+            self.server.canary_rollouts = parse_pairs("SERVER_CANARY_ROLLOUTS", &rollouts)?
+                .into_iter()
+                .map(|(canary, percentage)| match percentage.parse::<u8>() {
+                    Ok(value) if value <= 100 => Ok((canary, value)),
+                    _ => Err(ConfigError::InvalidValue {
+                        key: "SERVER_CANARY_ROLLOUTS".to_string(),
+                        value: format!("{}={}", canary, percentage),
+                    }),
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Ok(enabled) = env::var("SERVER_LOAD_SHEDDING_ENABLED") {
+            self.server.load_shedding.enabled = enabled.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "SERVER_LOAD_SHEDDING_ENABLED".to_string(),
+                    value: enabled,
+                })?;
+        }
+        if let Ok(paths) = env::var("SERVER_LOAD_SHEDDING_LOW_PRIORITY_PATHS") {
+            self.server.load_shedding.low_priority_paths = paths
+                .split(',')
+                .map(|path| path.trim().to_string())
+                .filter(|path| !path.is_empty())
+                .collect();
+        }
+
+        if let Ok(hosts) = env::var("SERVER_TENANT_HOSTS") {
+            self.server.tenancy.hosts = parse_pairs("SERVER_TENANT_HOSTS", &hosts)?
+                .into_iter()
+                .map(|(host, tenant)| match TenantId::parse(&tenant) {
+                    Some(_) => Ok((host.to_lowercase(), tenant)),
+                    None => Err(ConfigError::InvalidValue {
+                        key: "SERVER_TENANT_HOSTS".to_string(),
+                        value: format!("{}={}", host, tenant),
+                    }),
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Ok(enabled) = env::var("SERVER_TENANT_HEADER_ENABLED") {
+            self.server.tenancy.header_enabled = enabled.parse()
+                .map_err(|_| ConfigError::InvalidValue {
+                    key: "SERVER_TENANT_HEADER_ENABLED".to_string(),
+                    value: enabled,
+                })?;
+        }
+
+        // Override TLS configuration
+        if let (Ok(cert_path), Ok(key_path)) = (env::var("TLS_CERT_PATH"), env::var("TLS_KEY_PATH")) {
+            let tls = self.server.tls.get_or_insert_with(TlsConfig::default);
+            tls.cert_path = cert_path;
+            tls.key_path = key_path;
+        }
+        if let Some(tls) = &mut self.server.tls {
+            if let Ok(ca_path) = env::var("TLS_CLIENT_CA_PATH") {
+                tls.ca_path = Some(ca_path);
+                tls.verify_client = true;
+            }
+            if let Ok(paths) = env::var("MTLS_REQUIRED_PATHS") {
+                tls.client_auth_paths = paths
+                    .split(',')
+                    .map(|path| path.trim().to_string())
+                    .filter(|path| !path.is_empty())
+                    .collect();
+            }
+            if let Ok(identities) = env::var("MTLS_CLIENT_IDENTITIES") {
+                tls.client_identities = parse_pairs("MTLS_CLIENT_IDENTITIES", &identities)?;
+            }
+        }
+
+        // Override admin single sign-on configuration
+        if let (Ok(issuer_url), Ok(client_id), Ok(client_secret), Ok(redirect_uri)) = (
+            env::var("ADMIN_SSO_ISSUER_URL"),
+            env::var("ADMIN_SSO_CLIENT_ID"),
+            env::var("ADMIN_SSO_CLIENT_SECRET"),
+            env::var("ADMIN_SSO_REDIRECT_URI"),
+        ) {
+            let mut sso = AdminSsoConfig {
+                issuer_url,
+                client_id,
+                client_secret: SecretString::from(client_secret),
+                redirect_uri,
+                scopes: vec!["openid".to_string(), "email".to_string(), "profile".to_string()],
+                groups_claim: "groups".to_string(),
+                role_mappings: HashMap::new(),
+                session_minutes: 60,
+            };
+            if let Ok(claim) = env::var("ADMIN_SSO_GROUPS_CLAIM") {
+                sso.groups_claim = claim;
+            }
+            if let Ok(mappings) = env::var("ADMIN_SSO_ROLE_MAPPINGS") {
+                sso.role_mappings = parse_pairs("ADMIN_SSO_ROLE_MAPPINGS", &mappings)?;
+            }
+            if let Ok(minutes) = env::var("ADMIN_SSO_SESSION_MINUTES") {
+                sso.session_minutes = minutes.parse()
+                    .map_err(|_| ConfigError::InvalidValue {
+                        key: "ADMIN_SSO_SESSION_MINUTES".to_string(),
+                        value: minutes,
+                    })?;
+            }
+            self.auth.admin_sso = Some(sso);
+        }
+
+        // Override rate limiting configuration
+        if let Ok(algorithm) = env::var("RATE_LIMIT_ALGORITHM") {
+            self.rate_limit.algorithm = RateLimitAlgorithm::from_str(&algorithm)
+                .ok_or(ConfigError::InvalidValue {
+                    key: "RATE_LIMIT_ALGORITHM".to_string(),
+                    value: algorithm,
+                })?;
+        }
+
         // Override SMS configuration
         self.sms = SmsConfig::from_env();
 
         // Override Google Maps API key
         if let Ok(key) = env::var("GOOGLE_MAPS_API_KEY") {
-            self.google_maps_api_key = Some(key);
+            self.google_maps_api_key = Some(SecretString::from(key));
         }
 
         Ok(())
@@ -418,16 +770,66 @@ impl Config {
             ));
         }
 
+        // Validate phone hashing configuration
+        let phone_hash = &self.auth.phone_hash;
+        if self.environment.is_production() && phone_hash.peppers.is_empty() {
+            return Err(ConfigError::ValidationError(
+                "PHONE_HASH_PEPPERS must be set in production".to_string()
+            ));
+        }
+        if phone_hash.peppers.iter().any(|pepper| pepper.expose_secret().len() < MIN_PHONE_HASH_PEPPER_LENGTH) {
+            return Err(ConfigError::ValidationError(format!(
+                "Every phone hash pepper must be at least {} characters", MIN_PHONE_HASH_PEPPER_LENGTH
+            )));
+        }
+        let mut peppers: Vec<&str> = phone_hash.peppers.iter().map(SecretString::expose_secret).collect();
+        peppers.sort_unstable();
+        if peppers.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(ConfigError::ValidationError(
+                "PHONE_HASH_PEPPERS must not repeat a pepper".to_string()
+            ));
+        }
+
         // Validate database configuration
         if self.environment.is_production() && !self.database.is_production() {
             return Err(ConfigError::ValidationError(
                 "Database URL appears to be localhost in production".to_string()
             ));
         }
+        self.database.tls.validate().map_err(ConfigError::ValidationError)?;
+        self.database.validate_shards().map_err(ConfigError::ValidationError)?;
+
+        // Validate cache configuration
+        if let Some(redis) = &self.cache.redis {
+            redis.validate_sentinels().map_err(ConfigError::ValidationError)?;
+        }
 
         // Validate SMS configuration
         self.sms.validate(self.environment)?;
 
+        // Validate client certificate authentication
+        if let Some(tls) = &self.server.tls {
+            if !tls.client_auth_paths.is_empty() && (!tls.verify_client || tls.ca_path.is_none()) {
+                return Err(ConfigError::ValidationError(
+                    "Client certificate paths require client verification with a CA certificate".to_string()
+                ));
+            }
+        }
+
+        // Validate admin single sign-on role mappings
+        if let Some(sso) = &self.auth.admin_sso {
+            if sso.role_mappings.is_empty() {
+                return Err(ConfigError::ValidationError(
+                    "Admin single sign-on requires at least one group to role mapping".to_string()
+                ));
+            }
+            if let Some(role) = sso.role_mappings.values().find(|role| AdminRole::from_str(role).is_none()) {
+                return Err(ConfigError::ValidationError(format!(
+                    "Unknown admin role in single sign-on mappings: {}", role
+                )));
+            }
+        }
+
         // Validate rate limiting is enabled in production
         if self.environment.is_production() && !self.rate_limit.enabled {
             return Err(ConfigError::ValidationError(
@@ -438,4 +840,49 @@ impl Config {
         Ok(())
     }
 
+    /// Configuration of the authentication service
+    ///
+    /// Trusted testers are matched with the same peppered hasher that
+    /// produces the phone hashes stored with users.
+    pub fn auth_service_config(&self) -> AuthServiceConfig {
+        AuthServiceConfig {
+            rate_limit: self.rate_limit.clone(),
+            trusted_testers: self.sms.trusted_testers(),
+            phone_hasher: PhoneHasher::new(&self.auth.phone_hash),
+            ..AuthServiceConfig::default()
+        }
+    }
+
+    /// Services this configuration enables, for capability discovery
+    pub fn configured_services(&self) -> ConfiguredServices {
+        ConfiguredServices {
+            database: !self.database.url.is_empty(),
+            redis: self.cache.enabled
+                && self.cache.redis.as_ref().is_some_and(|redis| !redis.url.is_empty()),
+            sms_provider: if self.sms.enabled {
+                self.sms.provider.clone()
+            } else {
+                "disabled".to_string()
+            },
+        }
+    }
+}
+
+/// Parse `key=value` pairs separated by commas, such as
+/// `fingerprint=identity` or `group=role`
+fn parse_pairs(name: &str, value: &str) -> Result<HashMap<String, String>, ConfigError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() && !value.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(ConfigError::InvalidValue {
+                key: name.to_string(),
+                value: pair.to_string(),
+            }),
+        })
+        .collect()
 }
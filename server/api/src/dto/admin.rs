@@ -0,0 +1,1474 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::lifecycle::{DrainStatus, DrainTrigger, MaintenanceStatus};
+use crate::middleware::compat_json::{UnknownFieldCount, UnknownFieldMetrics};
+use crate::middleware::load_shedding::{LoadLevel, LoadSheddingStatus};
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::attribution::AttributionCohort;
+use re_core::domain::entities::audit::AuditLog;
+use re_core::domain::entities::calendar::ClosureDate;
+use re_core::domain::entities::campaign::{
+    Campaign, CampaignAudience, CampaignChannel, CampaignContent, CampaignStats,
+};
+use re_core::domain::entities::credit_wallet::{CreditGrant, CreditSource};
+use re_core::domain::entities::export_job::{ExportJob, ExportKind, ExportStatus};
+use re_core::domain::entities::fee_schedule::{FeeSchedule, OrderFee};
+use re_core::domain::entities::oauth::{OAuthClient, OAuthGrantType};
+use re_core::domain::entities::payment_risk::{
+    PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction,
+};
+use re_core::domain::entities::reconciliation::{Discrepancy, ReconciliationReport};
+use re_core::domain::entities::security_webhook::{SecurityEventKind, SecurityWebhook};
+use re_core::domain::entities::sms_delivery::SmsDeliveryLog;
+use re_core::domain::entities::webhook_dead_letter::{
+    DeadLetterStatus, WebhookDeadLetter, WebhookDirection,
+};
+use re_core::services::admin_sso::AdminLoginResult;
+use re_core::services::auth::{IpAccessEntry, LockedAccount};
+use re_core::services::log_level::{LogLevelOverride, LogLevelStatus};
+use re_core::services::sms_cost::SmsSpendReport;
+use re_core::services::user_import::{ImportedUser, UserImportIssue, UserImportReport};
+use re_core::services::webhook_dead_letter::{BulkReplayResult, ReplayResult, ReplayStatus};
+use re_infra::capabilities::{Capabilities, Capability, CompiledFeatures};
+use re_shared::config::{Environment, UnknownFieldMode};
+use re_shared::types::response::{BatchError, BatchItem, BatchResponse, BatchSummary, ErrorDetail};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountLockResponse {
+    /// Phone number hash or user ID the lock applies to
+    pub id: String,
+    pub user_id: Option<Uuid>,
+    pub reason: String,
+    pub locked_at: DateTime<Utc>,
+    pub unlock_at: DateTime<Utc>,
+    pub remaining_seconds: Option<i64>,
+}
+
+impl From<LockedAccount> for AccountLockResponse {
+    fn from(lock: LockedAccount) -> Self {
+        Self {
+            id: lock.identifier,
+            user_id: lock.user_id,
+            reason: lock.reason,
+            locked_at: lock.locked_at,
+            unlock_at: lock.unlock_at,
+            remaining_seconds: lock.remaining_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountLockListResponse {
+    pub locks: Vec<AccountLockResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockAccountResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateClosureRequest {
+    /// Local date on which the market is closed (YYYY-MM-DD)
+    pub date: NaiveDate,
+
+    /// Reason shown to administrators
+    #[validate(length(min = 1, max = 255))]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosureQuery {
+    /// First date of the range (default: today)
+    pub from: Option<NaiveDate>,
+    /// Last date of the range (default: one year after `from`)
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosureResponse {
+    pub id: Uuid,
+    pub market: String,
+    pub date: NaiveDate,
+    pub reason: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ClosureDate> for ClosureResponse {
+    fn from(closure: ClosureDate) -> Self {
+        Self {
+            id: closure.id,
+            market: closure.market,
+            date: closure.date,
+            reason: closure.reason,
+            created_by: closure.created_by,
+            created_at: closure.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosureListResponse {
+    pub closures: Vec<ClosureResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteClosureResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateCampaignRequest {
+    /// Internal name shown to administrators
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+
+    /// Delivery channel, `push` or `sms` (default: push)
+    #[serde(default)]
+    pub channel: CampaignChannel,
+
+    /// Audience filters; omitted filters match everyone
+    #[serde(default)]
+    pub audience: CampaignAudience,
+
+    /// Localized content keyed by language code (e.g. "en", "zh")
+    pub content: HashMap<String, CampaignContent>,
+
+    /// Language used when a recipient's language has no content
+    #[validate(length(min = 2, max = 10))]
+    pub default_language: String,
+
+    /// Start time (default: now)
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignQuery {
+    /// Maximum number of campaigns to return (default: 50, maximum: 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub channel: String,
+    pub audience: CampaignAudience,
+    pub content: HashMap<String, CampaignContent>,
+    pub default_language: String,
+    pub scheduled_at: DateTime<Utc>,
+    pub status: String,
+    pub audience_size: u64,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<Campaign> for CampaignResponse {
+    fn from(campaign: Campaign) -> Self {
+        Self {
+            id: campaign.id,
+            name: campaign.name,
+            channel: campaign.channel.as_str().to_string(),
+            audience: campaign.audience,
+            content: campaign.content,
+            default_language: campaign.default_language,
+            scheduled_at: campaign.scheduled_at,
+            status: campaign.status.as_str().to_string(),
+            audience_size: campaign.audience_size,
+            created_by: campaign.created_by,
+            created_at: campaign.created_at,
+            updated_at: campaign.updated_at,
+            completed_at: campaign.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignStatsResponse {
+    pub pending: u64,
+    pub sent: u64,
+    pub failed: u64,
+    pub opted_out: u64,
+    pub opened: u64,
+    /// Share of sent messages that were opened, between 0.0 and 1.0
+    pub open_rate: f64,
+}
+
+impl From<CampaignStats> for CampaignStatsResponse {
+    fn from(stats: CampaignStats) -> Self {
+        Self {
+            pending: stats.pending,
+            sent: stats.sent,
+            failed: stats.failed,
+            opted_out: stats.opted_out,
+            opened: stats.opened,
+            open_rate: stats.open_rate(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignDetailResponse {
+    #[serde(flatten)]
+    pub campaign: CampaignResponse,
+    pub stats: CampaignStatsResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignListResponse {
+    pub campaigns: Vec<CampaignResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateIpAccessEntryRequest {
+    /// IP address or CIDR range, e.g. "203.0.113.0/24"
+    #[validate(length(min = 1, max = 64))]
+    pub cidr: String,
+
+    /// Why the entry is added
+    #[validate(length(min = 1, max = 255))]
+    pub reason: String,
+
+    /// Lifetime of the entry in seconds (default: permanent)
+    #[validate(range(min = 1))]
+    pub expires_in_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpAccessEntryQuery {
+    /// IP address or CIDR range of the entry
+    pub cidr: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpAccessEntryResponse {
+    pub cidr: String,
+    pub reason: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<IpAccessEntry> for IpAccessEntryResponse {
+    fn from(entry: IpAccessEntry) -> Self {
+        Self {
+            cidr: entry.cidr,
+            reason: entry.reason,
+            created_by: entry.created_by,
+            created_at: entry.created_at,
+            expires_at: entry.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpAccessListResponse {
+    pub list: String,
+    pub entries: Vec<IpAccessEntryResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteIpAccessEntryResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunReconciliationRequest {
+    /// UTC calendar day to reconcile
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationQuery {
+    /// Maximum number of reports to return (default: 30, maximum: 100)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReportResponse {
+    pub id: Uuid,
+    pub provider: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub ledger_count: usize,
+    pub provider_count: usize,
+    pub matched_count: usize,
+    pub discrepancy_count: usize,
+    /// Total discrepancy per currency in minor units
+    pub discrepancy_totals: BTreeMap<String, i64>,
+    pub balanced: bool,
+    pub alerted: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&ReconciliationReport> for ReconciliationReportResponse {
+    fn from(report: &ReconciliationReport) -> Self {
+        Self {
+            id: report.id,
+            provider: report.provider.clone(),
+            period_start: report.period_start,
+            period_end: report.period_end,
+            ledger_count: report.ledger_count,
+            provider_count: report.provider_count,
+            matched_count: report.matched_count,
+            discrepancy_count: report.discrepancies.len(),
+            discrepancy_totals: report.discrepancy_totals.clone(),
+            balanced: report.is_balanced(),
+            alerted: report.alerted,
+            created_at: report.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscrepancyResponse {
+    pub reference: String,
+    pub kind: String,
+    pub currency: String,
+    pub ledger_amount: Option<i64>,
+    pub provider_amount: Option<i64>,
+    pub difference: i64,
+}
+
+impl From<&Discrepancy> for DiscrepancyResponse {
+    fn from(discrepancy: &Discrepancy) -> Self {
+        Self {
+            reference: discrepancy.reference.clone(),
+            kind: discrepancy.kind.as_str().to_string(),
+            currency: discrepancy.currency.clone(),
+            ledger_amount: discrepancy.ledger_amount,
+            provider_amount: discrepancy.provider_amount,
+            difference: discrepancy.difference(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReportDetailResponse {
+    #[serde(flatten)]
+    pub report: ReconciliationReportResponse,
+    pub discrepancies: Vec<DiscrepancyResponse>,
+}
+
+impl From<ReconciliationReport> for ReconciliationReportDetailResponse {
+    fn from(report: ReconciliationReport) -> Self {
+        Self {
+            report: ReconciliationReportResponse::from(&report),
+            discrepancies: report.discrepancies.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReportListResponse {
+    pub reports: Vec<ReconciliationReportResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateFeeScheduleRequest {
+    /// Job category the schedule applies to; omitted for the whole market
+    #[validate(length(min = 1, max = 100))]
+    pub category: Option<String>,
+
+    /// Commission in basis points of the quoted amount (1000 = 10%)
+    #[validate(range(min = 0, max = 10000))]
+    pub commission_basis_points: i64,
+
+    /// Smallest fee charged per order in minor units
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub minimum_fee: i64,
+
+    /// ISO 4217 currency code
+    #[validate(length(equal = 3))]
+    pub currency: String,
+
+    /// When the schedule takes effect (default: immediately)
+    pub effective_from: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeScheduleResponse {
+    pub id: Uuid,
+    pub market: String,
+    pub category: Option<String>,
+    pub version: i32,
+    pub commission_basis_points: i64,
+    pub minimum_fee: i64,
+    pub currency: String,
+    pub effective_from: DateTime<Utc>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<FeeSchedule> for FeeScheduleResponse {
+    fn from(schedule: FeeSchedule) -> Self {
+        Self {
+            id: schedule.id,
+            market: schedule.market,
+            category: schedule.category,
+            version: schedule.version,
+            commission_basis_points: schedule.commission_basis_points,
+            minimum_fee: schedule.minimum_fee,
+            currency: schedule.currency,
+            effective_from: schedule.effective_from,
+            created_by: schedule.created_by,
+            created_at: schedule.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeScheduleListResponse {
+    pub schedules: Vec<FeeScheduleResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderFeeResponse {
+    pub order_id: Uuid,
+    pub schedule_id: Uuid,
+    pub schedule_version: i32,
+    pub market: String,
+    pub category: Option<String>,
+    pub commission_basis_points: i64,
+    pub minimum_fee: i64,
+    pub quote_amount: i64,
+    pub fee_amount: i64,
+    pub currency: String,
+    pub accepted_at: DateTime<Utc>,
+}
+
+impl From<OrderFee> for OrderFeeResponse {
+    fn from(fee: OrderFee) -> Self {
+        Self {
+            order_id: fee.order_id,
+            schedule_id: fee.schedule_id,
+            schedule_version: fee.schedule_version,
+            market: fee.market,
+            category: fee.category,
+            commission_basis_points: fee.commission_basis_points,
+            minimum_fee: fee.minimum_fee,
+            quote_amount: fee.quote_amount,
+            fee_amount: fee.fee_amount,
+            currency: fee.currency,
+            accepted_at: fee.accepted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct IssueCreditRequest {
+    /// Why the credit is granted
+    pub source: CreditSource,
+
+    /// Identifier of the granting event (e.g. a refund ID); repeated requests
+    /// with the same reference grant the credit once
+    #[validate(length(min = 1, max = 255))]
+    pub reference: String,
+
+    /// Amount in minor units
+    #[validate(range(min = 1))]
+    pub amount: i64,
+
+    /// ISO 4217 currency code
+    #[validate(length(equal = 3))]
+    pub currency: String,
+
+    /// When unspent credit expires (default: never, or the promotion expiry)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditGrantResponse {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub source: CreditSource,
+    pub reference: String,
+    pub amount: i64,
+    pub remaining: i64,
+    pub currency: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<CreditGrant> for CreditGrantResponse {
+    fn from(grant: CreditGrant) -> Self {
+        Self {
+            id: grant.id,
+            customer_id: grant.customer_id,
+            source: grant.source,
+            reference: grant.reference,
+            amount: grant.amount,
+            remaining: grant.remaining,
+            currency: grant.currency,
+            expires_at: grant.expires_at,
+            created_at: grant.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRiskReviewQuery {
+    /// Maximum number of assessments to return (default: 50, maximum: 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskReviewDecision {
+    Approve,
+    Reject,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct DecideRiskReviewRequest {
+    /// Whether the held payment may proceed
+    pub decision: RiskReviewDecision,
+
+    /// Reason for the decision, kept with the assessment
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRiskAssessmentResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub currency: String,
+    pub market: String,
+    pub billing_country: Option<String>,
+    pub triggered_rules: Vec<PaymentRiskRule>,
+    pub action: RiskAction,
+    pub review_status: ReviewStatus,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PaymentRiskAssessment> for PaymentRiskAssessmentResponse {
+    fn from(assessment: PaymentRiskAssessment) -> Self {
+        Self {
+            id: assessment.id,
+            user_id: assessment.user_id,
+            amount: assessment.amount,
+            currency: assessment.currency,
+            market: assessment.market,
+            billing_country: assessment.billing_country,
+            triggered_rules: assessment.triggered_rules,
+            action: assessment.action,
+            review_status: assessment.review_status,
+            reviewed_by: assessment.reviewed_by,
+            reviewed_at: assessment.reviewed_at,
+            review_note: assessment.review_note,
+            created_at: assessment.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRiskAssessmentListResponse {
+    pub assessments: Vec<PaymentRiskAssessmentResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RegisterOAuthClientRequest {
+    /// Partner name shown to users on the consent screen
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+
+    /// Redirect URIs for the authorization code grant, matched exactly
+    #[serde(default)]
+    #[validate(length(max = 10))]
+    pub redirect_uris: Vec<String>,
+
+    /// Scopes the partner may request
+    #[validate(length(min = 1))]
+    pub scopes: Vec<String>,
+
+    /// Grants the partner may use
+    #[validate(length(min = 1))]
+    pub grant_types: Vec<OAuthGrantType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateOAuthClientRequest {
+    /// Whether the partner may obtain new tokens
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthClientResponse {
+    pub client_id: String,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub scopes: Vec<String>,
+    pub grant_types: Vec<OAuthGrantType>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<OAuthClient> for OAuthClientResponse {
+    fn from(client: OAuthClient) -> Self {
+        Self {
+            client_id: client.client_id,
+            name: client.name,
+            redirect_uris: client.redirect_uris,
+            scopes: client.allowed_scopes,
+            grant_types: client.grant_types,
+            is_active: client.is_active,
+            created_at: client.created_at,
+            updated_at: client.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredOAuthClientResponse {
+    #[serde(flatten)]
+    pub client: OAuthClientResponse,
+    /// Secret to hand to the partner; it is not shown again
+    pub client_secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthClientListResponse {
+    pub clients: Vec<OAuthClientResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminSsoLoginResponse {
+    /// Identity provider URL to open
+    pub authorization_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AdminSsoCallbackRequest {
+    /// Authorization code returned by the identity provider
+    #[validate(length(min = 1))]
+    pub code: String,
+    /// State returned by the identity provider
+    #[validate(length(min = 1))]
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminSsoSessionResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub admin_id: Uuid,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub roles: Vec<AdminRole>,
+}
+
+impl From<AdminLoginResult> for AdminSsoSessionResponse {
+    fn from(result: AdminLoginResult) -> Self {
+        Self {
+            access_token: result.access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: result.expires_in,
+            admin_id: result.identity.id,
+            email: result.identity.email,
+            name: result.identity.name,
+            roles: result.identity.roles,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetLogLevelRequest {
+    /// Global level: trace, debug, info, warn, error or off (default: configured filter)
+    #[validate(length(min = 1, max = 16))]
+    pub level: Option<String>,
+
+    /// Level per target, e.g. {"re_core::services::auth": "debug"}
+    #[serde(default)]
+    pub targets: BTreeMap<String, String>,
+
+    /// Lifetime of the override in seconds (default from configuration)
+    #[validate(range(min = 1))]
+    pub ttl_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLevelOverrideResponse {
+    pub level: Option<String>,
+    pub targets: BTreeMap<String, String>,
+    pub filter: String,
+    pub set_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<LogLevelOverride> for LogLevelOverrideResponse {
+    fn from(active: LogLevelOverride) -> Self {
+        Self {
+            level: active.level,
+            targets: active.targets,
+            filter: active.filter,
+            set_by: active.set_by,
+            created_at: active.created_at,
+            expires_at: active.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLevelStatusResponse {
+    /// Filter currently applied
+    pub filter: String,
+    /// Filter restored when the override ends
+    pub default_filter: String,
+    pub active_override: Option<LogLevelOverrideResponse>,
+}
+
+impl From<LogLevelStatus> for LogLevelStatusResponse {
+    fn from(status: LogLevelStatus) -> Self {
+        Self {
+            filter: status.filter,
+            default_filter: status.default_filter,
+            active_override: status.active_override.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearLogLevelResponse {
+    pub message: String,
+    /// Whether an override was active
+    pub cleared: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct StartDrainRequest {
+    /// Why the instance is taken out of service
+    #[validate(length(min = 1, max = 255))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainStatusResponse {
+    pub draining: bool,
+    pub trigger: Option<DrainTrigger>,
+    pub since: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
+impl From<DrainStatus> for DrainStatusResponse {
+    fn from(status: DrainStatus) -> Self {
+        Self {
+            draining: status.draining,
+            trigger: status.trigger,
+            since: status.since,
+            reason: status.reason,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetMaintenanceRequest {
+    /// Whether client requests are rejected
+    pub enabled: bool,
+    /// Message shown to clients instead of the default one
+    #[validate(length(min = 1, max = 500))]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceStatusResponse {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl From<MaintenanceStatus> for MaintenanceStatusResponse {
+    fn from(status: MaintenanceStatus) -> Self {
+        Self {
+            enabled: status.enabled,
+            message: status.message,
+            since: status.since,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownFieldCountResponse {
+    /// Method and route pattern the field was sent to
+    pub route: String,
+    /// Path of the field in the request body
+    pub field: String,
+    pub count: u64,
+}
+
+impl From<UnknownFieldCount> for UnknownFieldCountResponse {
+    fn from(count: UnknownFieldCount) -> Self {
+        Self {
+            route: count.route,
+            field: count.field,
+            count: count.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownFieldMetricsResponse {
+    pub mode: UnknownFieldMode,
+    pub requests: u64,
+    pub rejected: u64,
+    pub fields: Vec<UnknownFieldCountResponse>,
+    pub client_versions: BTreeMap<String, u64>,
+}
+
+impl UnknownFieldMetricsResponse {
+    pub fn new(mode: UnknownFieldMode, metrics: UnknownFieldMetrics) -> Self {
+        Self {
+            mode,
+            requests: metrics.requests,
+            rejected: metrics.rejected,
+            fields: metrics.fields.into_iter().map(Into::into).collect(),
+            client_versions: metrics.client_versions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RegisterSecurityWebhookRequest {
+    /// HTTPS URL events are posted to
+    #[validate(length(min = 9, max = 2048))]
+    pub url: String,
+
+    /// Event kinds to deliver (default: all)
+    #[serde(default)]
+    pub events: Vec<SecurityEventKind>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityWebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub events: Vec<SecurityEventKind>,
+    pub is_active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SecurityWebhook> for SecurityWebhookResponse {
+    fn from(webhook: SecurityWebhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events,
+            is_active: webhook.is_active,
+            created_by: webhook.created_by,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredSecurityWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: SecurityWebhookResponse,
+    /// Signing secret to configure on the receiver; it is not shown again
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityWebhookListResponse {
+    pub webhooks: Vec<SecurityWebhookResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteSecurityWebhookResponse {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogQuery {
+    /// Page number, starting at 1 (default: 1)
+    pub page: Option<u32>,
+    /// Logs per page (default: 20, maximum: 100)
+    pub per_page: Option<u32>,
+    /// Only logs of this user
+    pub user_id: Option<Uuid>,
+    /// Only logs of this hashed phone number
+    pub phone_hash: Option<String>,
+    /// Comma-separated event types, e.g. "LOGIN_FAILURE,ACCOUNT_LOCKED"
+    pub event_types: Option<String>,
+    /// Only logs from this IP address
+    pub ip_address: Option<String>,
+    /// Only logs created at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only logs created at or before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogResponse {
+    pub id: Uuid,
+    pub event_type: String,
+    pub user_id: Option<Uuid>,
+    pub phone_masked: Option<String>,
+    pub phone_hash: Option<String>,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    pub device_info: Option<String>,
+    pub success: bool,
+    pub failure_reason: Option<String>,
+    pub rate_limit_type: Option<String>,
+    pub event_data: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditLog> for AuditLogResponse {
+    fn from(log: AuditLog) -> Self {
+        Self {
+            id: log.id,
+            event_type: log.event_type.as_str().to_string(),
+            user_id: log.user_id,
+            phone_masked: log.phone_masked,
+            phone_hash: log.phone_hash,
+            ip_address: log.ip_address,
+            user_agent: log.user_agent,
+            device_info: log.device_info,
+            success: log.success,
+            failure_reason: log.failure_reason,
+            rate_limit_type: log.rate_limit_type,
+            event_data: log.event_data,
+            created_at: log.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadSheddingResponse {
+    pub enabled: bool,
+    pub level: LoadLevel,
+    /// Latest samples; `null` until a signal has been sampled
+    pub event_loop_lag_ms: Option<u64>,
+    pub db_pool_utilization_percent: Option<u64>,
+    pub redis_latency_ms: Option<u64>,
+    /// Requests shed since the instance started, by priority
+    pub shed_low_priority: u64,
+    pub shed_normal_priority: u64,
+}
+
+impl From<LoadSheddingStatus> for LoadSheddingResponse {
+    fn from(status: LoadSheddingStatus) -> Self {
+        Self {
+            enabled: status.enabled,
+            level: status.level,
+            event_loop_lag_ms: status.event_loop_lag_ms,
+            db_pool_utilization_percent: status.db_pool_utilization_percent,
+            redis_latency_ms: status.redis_latency_ms,
+            shed_low_priority: status.shed_low_priority,
+            shed_normal_priority: status.shed_normal_priority,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionCohortQuery {
+    /// First registration date included (default: 12 weeks before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last registration date included (default: today)
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionCohortResponse {
+    pub cohort_week: NaiveDate,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub registrations: u64,
+    pub activated: u64,
+    pub activation_rate: f64,
+}
+
+impl From<AttributionCohort> for AttributionCohortResponse {
+    fn from(cohort: AttributionCohort) -> Self {
+        Self {
+            activation_rate: cohort.activation_rate(),
+            cohort_week: cohort.cohort_week,
+            utm_source: cohort.utm_source,
+            utm_medium: cohort.utm_medium,
+            utm_campaign: cohort.utm_campaign,
+            registrations: cohort.registrations,
+            activated: cohort.activated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionReportResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub cohorts: Vec<AttributionCohortResponse>,
+    pub total_registrations: u64,
+    pub total_activated: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct LegalTranslationRequest {
+    /// Language code, e.g. "en" or "zh-CN"
+    #[validate(length(min = 2, max = 35))]
+    pub language: String,
+
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+
+    /// Document text in Markdown
+    #[validate(length(min = 1))]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct PublishLegalDocumentRequest {
+    /// Market code (ISO 3166-1 alpha-2); omitted for every market
+    #[validate(length(equal = 2))]
+    pub market: Option<String>,
+
+    /// When the version takes effect (default: immediately)
+    pub effective_from: Option<DateTime<Utc>>,
+
+    /// Translations of the version, which must include English
+    #[validate(length(min = 1), nested)]
+    pub translations: Vec<LegalTranslationRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateExportRequest {
+    /// Data to export: "audit_logs" or "attribution_cohorts"
+    pub kind: ExportKind,
+    /// Start of the exported time range (default: unbounded)
+    pub from: Option<DateTime<Utc>>,
+    /// End of the exported time range (default: when the export is requested)
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJobResponse {
+    pub id: Uuid,
+    pub kind: ExportKind,
+    pub status: ExportStatus,
+    pub from: Option<DateTime<Utc>>,
+    pub to: DateTime<Utc>,
+    pub rows_exported: u64,
+    pub total_rows: Option<u64>,
+    /// Known once a worker has counted the rows
+    pub progress_percent: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Signed link to the file, once the export has completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_expires_at: Option<DateTime<Utc>>,
+}
+
+impl ExportJobResponse {
+    /// Describe a job, with the download link minted for it if it has completed
+    pub fn new(job: ExportJob, download: Option<(String, DateTime<Utc>)>) -> Self {
+        let (download_url, download_expires_at) = download.unzip();
+        Self {
+            progress_percent: job.progress_percent(),
+            id: job.id,
+            kind: job.kind,
+            status: job.status,
+            from: job.from,
+            to: job.to,
+            rows_exported: job.rows_exported,
+            total_rows: job.total_rows,
+            error: job.error,
+            created_at: job.created_at,
+            finished_at: job.finished_at,
+            download_url,
+            download_expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeadLetterQuery {
+    /// Only dead letters in this direction: "inbound" or "outbound"
+    pub direction: Option<WebhookDirection>,
+    /// Only dead letters from this source, e.g. "stripe" or "security_webhook"
+    pub source: Option<String>,
+    /// Only dead letters in this state: "pending", "replaying" or "resolved"
+    pub status: Option<DeadLetterStatus>,
+    /// Only dead letters that first failed at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only dead letters that first failed at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Maximum number of dead letters (default: 50, maximum: 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeadLetterResponse {
+    pub id: Uuid,
+    pub direction: WebhookDirection,
+    pub source: String,
+    pub event_id: String,
+    pub event_type: String,
+    /// Endpoint an outbound webhook was addressed to
+    pub target_id: Option<Uuid>,
+    pub error: String,
+    pub failures: u32,
+    pub status: DeadLetterStatus,
+    pub replays: u32,
+    pub replayed_by: Option<Uuid>,
+    pub replayed_at: Option<DateTime<Utc>>,
+    pub resolution: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// The full payload; only included when a single dead letter is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+}
+
+impl WebhookDeadLetterResponse {
+    /// Describe a dead letter, with its payload if requested
+    pub fn new(dead_letter: WebhookDeadLetter, include_payload: bool) -> Self {
+        Self {
+            id: dead_letter.id,
+            direction: dead_letter.direction,
+            source: dead_letter.source,
+            event_id: dead_letter.event_id,
+            event_type: dead_letter.event_type,
+            target_id: dead_letter.target_id,
+            error: dead_letter.error,
+            failures: dead_letter.failures,
+            status: dead_letter.status,
+            replays: dead_letter.replays,
+            replayed_by: dead_letter.replayed_by,
+            replayed_at: dead_letter.replayed_at,
+            resolution: dead_letter.resolution,
+            created_at: dead_letter.created_at,
+            updated_at: dead_letter.updated_at,
+            payload: include_payload.then_some(dead_letter.payload),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDeadLetterListResponse {
+    pub dead_letters: Vec<WebhookDeadLetterResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayResultResponse {
+    pub dead_letter_id: Uuid,
+    /// "replayed", "failed", "already_resolved" or "in_progress"
+    pub status: String,
+    /// How the webhook was handled, or why it failed again
+    pub detail: Option<String>,
+}
+
+impl From<ReplayResult> for ReplayResultResponse {
+    fn from(result: ReplayResult) -> Self {
+        Self {
+            dead_letter_id: result.dead_letter_id,
+            status: result.status.as_str().to_string(),
+            detail: result.detail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReplayRequest {
+    /// Only dead letters in this direction (default: both)
+    pub direction: Option<WebhookDirection>,
+    /// Start of the range of first failures, inclusive
+    pub from: DateTime<Utc>,
+    /// End of the range of first failures, inclusive
+    pub to: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkReplayResponse {
+    pub replayed: usize,
+    pub failed: usize,
+    /// Dead letters another replay was already handling
+    pub skipped: usize,
+    pub results: Vec<ReplayResultResponse>,
+    /// More pending dead letters remain in the range; repeat the request
+    pub has_more: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl From<BulkReplayResult> for BulkReplayResponse {
+    fn from(result: BulkReplayResult) -> Self {
+        Self {
+            replayed: result.count(ReplayStatus::Replayed),
+            failed: result.count(ReplayStatus::Failed),
+            skipped: result.count(ReplayStatus::InProgress) + result.count(ReplayStatus::AlreadyResolved),
+            results: result.results.into_iter().map(Into::into).collect(),
+            has_more: result.has_more,
+            errors: result.errors,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilitiesResponse {
+    pub environment: Environment,
+    /// Crate features the instance was built with
+    pub features: CompiledFeatures,
+    /// Selected SMS provider
+    pub sms_provider: String,
+    /// Capabilities the instance has
+    pub capabilities: Vec<Capability>,
+    /// Capabilities the environment requires
+    pub required: Vec<Capability>,
+}
+
+impl From<&Capabilities> for CapabilitiesResponse {
+    fn from(capabilities: &Capabilities) -> Self {
+        Self {
+            environment: capabilities.environment,
+            features: capabilities.features,
+            sms_provider: capabilities.sms_provider.clone(),
+            capabilities: capabilities.available.clone(),
+            required: capabilities.required(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserImportQuery {
+    /// Only validate the export, without creating users (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedUserResponse {
+    pub user_id: Uuid,
+    /// Market of the user, by dialing code
+    pub country_code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserImportResponse {
+    /// Whether nothing was written
+    pub dry_run: bool,
+    /// Records already handled by an earlier request for the same export
+    pub resumed_from: u64,
+    /// Users created, or to create, per market
+    pub markets: BTreeMap<String, u64>,
+    /// Result of each record handled by this request, identified by its
+    /// position in the export starting at 1
+    #[serde(flatten)]
+    pub records: BatchResponse<ImportedUserResponse>,
+}
+
+impl UserImportResponse {
+    /// Build the response from an import report and the time the import took
+    pub fn new(report: UserImportReport, duration_ms: u64) -> Self {
+        let successful: Vec<BatchItem<ImportedUserResponse>> =
+            report.users.into_iter().map(imported_user_item).collect();
+        let failed: Vec<BatchError> = report.issues.into_iter().map(import_issue_error).collect();
+
+        Self {
+            dry_run: report.dry_run,
+            resumed_from: report.resumed_from,
+            markets: report.markets,
+            records: BatchResponse {
+                summary: BatchSummary {
+                    total: (report.total_records - report.resumed_from) as usize,
+                    successful: successful.len(),
+                    failed: failed.len(),
+                    duration_ms: Some(duration_ms),
+                },
+                successful,
+                failed,
+            },
+        }
+    }
+}
+
+fn imported_user_item(user: ImportedUser) -> BatchItem<ImportedUserResponse> {
+    BatchItem {
+        id: user.record.to_string(),
+        result: ImportedUserResponse {
+            user_id: user.user_id,
+            country_code: user.country_code,
+        },
+    }
+}
+
+fn import_issue_error(issue: UserImportIssue) -> BatchError {
+    BatchError {
+        id: issue.record.to_string(),
+        error: ErrorDetail {
+            code: issue.kind.as_str().to_string(),
+            message: issue.message,
+            fields: None,
+            trace: None,
+            context: None,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsLogQuery {
+    /// Page number, starting at 1 (default: 1)
+    pub page: Option<u32>,
+    /// Messages per page (default: 20, maximum: 100)
+    pub per_page: Option<u32>,
+    /// Only messages to this phone number, in E.164 format; matched on its
+    /// masked form, so numbers sharing length and last 4 digits match too
+    pub phone: Option<String>,
+    /// Only messages sent through this provider, e.g. "Twilio"
+    pub provider: Option<String>,
+    /// Only messages in this status: sent, delivered, undelivered or failed
+    pub status: Option<String>,
+    /// Only messages rendered from this template, e.g. "verification_code"
+    pub template: Option<String>,
+    /// Only messages sent at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only messages sent at or before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsLogResponse {
+    pub id: Uuid,
+    pub trace_id: Option<String>,
+    pub provider: String,
+    pub provider_message_id: Option<String>,
+    pub phone_masked: String,
+    pub status: String,
+    pub error: Option<String>,
+    pub template: Option<String>,
+    /// Estimated cost in millionths of the budget currency
+    pub cost_micros: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<SmsDeliveryLog> for SmsLogResponse {
+    fn from(log: SmsDeliveryLog) -> Self {
+        Self {
+            id: log.id,
+            trace_id: log.trace_id,
+            provider: log.provider,
+            provider_message_id: log.provider_message_id,
+            phone_masked: log.phone_masked,
+            status: log.status.as_str().to_string(),
+            error: log.error,
+            template: log.template,
+            cost_micros: log.cost_micros,
+            created_at: log.created_at,
+            updated_at: log.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsSpendQuery {
+    /// First UTC day of the report (default: 29 days before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last UTC day of the report (default: today)
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsSpendBreakdownResponse {
+    pub provider: String,
+    /// Prefix of the rate the messages were priced with; empty without a rate
+    pub prefix: String,
+    pub messages: u64,
+    pub cost_micros: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsDaySpendResponse {
+    pub date: NaiveDate,
+    pub messages: u64,
+    pub cost_micros: u64,
+    pub breakdown: Vec<SmsSpendBreakdownResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmsSpendResponse {
+    /// Currency of every amount, which are in millionths of the currency
+    pub currency: String,
+    pub daily_budget_micros: Option<u64>,
+    /// What happens to messages once the budget is spent: `refuse` or `downgrade`
+    pub over_budget: String,
+    pub today_micros: u64,
+    /// Budget left for today, None without a budget
+    pub remaining_today_micros: Option<u64>,
+    pub total_messages: u64,
+    pub total_micros: u64,
+    /// Days with messages, oldest first
+    pub days: Vec<SmsDaySpendResponse>,
+}
+
+impl From<SmsSpendReport> for SmsSpendResponse {
+    fn from(report: SmsSpendReport) -> Self {
+        let mut days: Vec<SmsDaySpendResponse> = Vec::new();
+        for spend in report.days {
+            let breakdown = SmsSpendBreakdownResponse {
+                provider: spend.provider,
+                prefix: spend.prefix,
+                messages: spend.messages,
+                cost_micros: spend.cost_micros,
+            };
+            match days.last_mut().filter(|day| day.date == spend.day) {
+                Some(day) => {
+                    day.messages += breakdown.messages;
+                    day.cost_micros += breakdown.cost_micros;
+                    day.breakdown.push(breakdown);
+                }
+                None => days.push(SmsDaySpendResponse {
+                    date: spend.day,
+                    messages: breakdown.messages,
+                    cost_micros: breakdown.cost_micros,
+                    breakdown: vec![breakdown],
+                }),
+            }
+        }
+
+        Self {
+            currency: report.currency,
+            daily_budget_micros: report.daily_budget_micros,
+            over_budget: report.over_budget.as_str().to_string(),
+            today_micros: report.today_micros,
+            remaining_today_micros: report
+                .daily_budget_micros
+                .map(|budget| budget.saturating_sub(report.today_micros)),
+            total_messages: days.iter().map(|day| day.messages).sum(),
+            total_micros: days.iter().map(|day| day.cost_micros).sum(),
+            days,
+        }
+    }
+}
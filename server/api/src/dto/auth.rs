@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use re_core::domain::entities::compliance::ComplianceRequirement;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct SendCodeRequest {
     /// Phone number without country code, or full E.164 format with country code
@@ -27,6 +30,29 @@ pub struct VerifyCodeRequest {
     /// 6-digit verification code
     #[validate(length(equal = 6))]
     pub code: String,
+
+    /// Whether the user consented to marketing; attribution is discarded without it
+    #[serde(default)]
+    pub marketing_consent: Option<bool>,
+
+    /// Campaign the user arrived through, used when registering a new user
+    #[serde(default)]
+    pub attribution: Option<AttributionParams>,
+}
+
+/// UTM parameters and referrer captured by the client before registration
+///
+/// Every field can also be sent as a header (`X-Utm-Source`, ...,
+/// `X-Attribution-Referrer`); body values take precedence.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttributionParams {
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    /// URL or host of the page the user came from
+    pub referrer: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +72,13 @@ pub struct AuthResponse {
     pub expires_in: i64,
     pub user_type: Option<String>,
     pub requires_type_selection: bool,
+    /// Set when the login came from an unexpected country and the client
+    /// must complete step-up verification
+    #[serde(default)]
+    pub requires_step_up: bool,
+    /// Compliance checks the user's market requires that are not verified yet
+    #[serde(default)]
+    pub compliance_required: Vec<ComplianceRequirement>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,7 +87,58 @@ pub struct SendCodeResponse {
     pub resend_after: i64, // seconds until can resend
 }
 
+/// Proof-of-work challenge to solve before sending a code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofOfWorkChallengeResponse {
+    /// Nonce to send back in the `X-Pow-Nonce` header
+    pub nonce: String,
+    /// Leading zero bits `SHA-256("{nonce}:{solution}")` must have
+    pub difficulty: u8,
+    /// Whether send-code currently checks solutions
+    pub required: bool,
+    pub expires_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogoutResponse {
     pub message: String,
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ReportSuspiciousLoginRequest {
+    /// Session ID from the suspicious login notification
+    #[validate(length(min = 1, max = 64))]
+    pub session_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSuspiciousLoginResponse {
+    pub message: String,
+}
+
+/// A signed-in session in the user's device list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionResponse {
+    /// Session ID, used to sign the session out
+    pub session_id: String,
+    /// Device type and OS derived from the user agent
+    pub device: Option<String>,
+    /// IP address the session was started from, masked to its network part
+    pub ip_address: Option<String>,
+    /// Country the IP address resolved to, if known
+    pub location: Option<String>,
+    /// Whether this is the session making the request
+    pub current: bool,
+    pub created_at: DateTime<Utc>,
+    pub last_active_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionListResponse {
+    pub sessions: Vec<SessionResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignOutSessionResponse {
+    pub message: String,
+}
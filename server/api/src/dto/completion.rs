@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use re_core::domain::entities::completion::{
+    ChecklistItemSpec, ChecklistTemplate, CompletedItem, CompletionChecklist, CustomerSignOff,
+};
+use re_core::services::completion::ChecklistItemInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItemRequest {
+    /// Key of the template item
+    pub key: String,
+    /// Storage keys of the photos uploaded for the item
+    #[serde(default)]
+    pub photos: Vec<String>,
+    pub note: Option<String>,
+}
+
+impl From<ChecklistItemRequest> for ChecklistItemInput {
+    fn from(item: ChecklistItemRequest) -> Self {
+        Self {
+            key: item.key,
+            photos: item.photos,
+            note: item.note,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SubmitCompletionRequest {
+    pub customer_id: Uuid,
+    /// Job category choosing the checklist, the default checklist when absent
+    #[validate(length(max = 64))]
+    pub category: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub items: Vec<ChecklistItemRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SignOffRequest {
+    /// Full name the customer typed to sign
+    #[validate(length(min = 2, max = 100))]
+    pub signed_name: String,
+    /// Device description from the app; the User-Agent is used when absent
+    #[validate(length(max = 255))]
+    pub device_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignOffResponse {
+    pub signed_name: String,
+    pub signed_at: DateTime<Utc>,
+    pub device_info: Option<String>,
+    pub content_hash: String,
+}
+
+impl From<CustomerSignOff> for SignOffResponse {
+    fn from(sign_off: CustomerSignOff) -> Self {
+        Self {
+            signed_name: sign_off.signed_name,
+            signed_at: sign_off.signed_at,
+            device_info: sign_off.device_info,
+            content_hash: sign_off.content_hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionResponse {
+    pub order_id: Uuid,
+    pub worker_id: Uuid,
+    pub customer_id: Uuid,
+    pub template_version: i32,
+    pub items: Vec<CompletedItem>,
+    pub submitted_at: DateTime<Utc>,
+    pub sign_off: Option<SignOffResponse>,
+}
+
+impl From<CompletionChecklist> for CompletionResponse {
+    fn from(checklist: CompletionChecklist) -> Self {
+        Self {
+            order_id: checklist.order_id,
+            worker_id: checklist.worker_id,
+            customer_id: checklist.customer_id,
+            template_version: checklist.template_version,
+            items: checklist.items,
+            submitted_at: checklist.submitted_at,
+            sign_off: checklist.sign_off.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateChecklistTemplateRequest {
+    /// Job category, the default checklist when absent
+    #[validate(length(min = 1, max = 64))]
+    pub category: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub items: Vec<ChecklistItemSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChecklistTemplateQuery {
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistTemplateResponse {
+    pub id: Uuid,
+    pub category: Option<String>,
+    pub version: i32,
+    pub items: Vec<ChecklistItemSpec>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ChecklistTemplate> for ChecklistTemplateResponse {
+    fn from(template: ChecklistTemplate) -> Self {
+        Self {
+            id: template.id,
+            category: template.category,
+            version: template.version,
+            items: template.items,
+            created_by: template.created_by,
+            created_at: template.created_at,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use validator::Validate;
+
+use re_core::domain::entities::compliance::{
+    ComplianceAction, ComplianceRequirement, ComplianceStatus, ComplianceStep,
+};
+
+#[derive(Clone, Serialize, Deserialize, Validate)]
+pub struct SubmitComplianceRequest {
+    /// Name on the identity document, or the registered business name
+    #[validate(length(min = 1, max = 100))]
+    pub legal_name: String,
+    /// Identity document or ICP filing number
+    #[validate(length(min = 1, max = 64))]
+    pub document_number: String,
+}
+
+impl fmt::Debug for SubmitComplianceRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubmitComplianceRequest")
+            .field("legal_name", &"[REDACTED]")
+            .field("document_number", &"[REDACTED]")
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceStepResponse {
+    pub requirement: ComplianceRequirement,
+    pub status: ComplianceStatus,
+    /// Actions held back until the requirement is verified
+    pub gates: Vec<ComplianceAction>,
+    pub rejection_reason: Option<String>,
+    pub attempts_remaining: u32,
+    /// What the app should show next: `submit_compliance` or
+    /// `wait_for_compliance_review`; absent once verified
+    pub next_step: Option<String>,
+}
+
+impl From<ComplianceStep> for ComplianceStepResponse {
+    fn from(step: ComplianceStep) -> Self {
+        Self {
+            next_step: next_step(step.status).map(String::from),
+            requirement: step.requirement,
+            status: step.status,
+            gates: step.gates,
+            rejection_reason: step.rejection_reason,
+            attempts_remaining: step.attempts_remaining,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceStepsResponse {
+    pub steps: Vec<ComplianceStepResponse>,
+    /// Whether every requirement of the user's market is verified
+    pub complete: bool,
+}
+
+impl From<Vec<ComplianceStep>> for ComplianceStepsResponse {
+    fn from(steps: Vec<ComplianceStep>) -> Self {
+        Self {
+            complete: steps.iter().all(|step| step.status == ComplianceStatus::Verified),
+            steps: steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The screen the app opens for a step in the given state
+pub fn next_step(status: ComplianceStatus) -> Option<&'static str> {
+    match status {
+        ComplianceStatus::Required | ComplianceStatus::Rejected => Some("submit_compliance"),
+        ComplianceStatus::Pending => Some("wait_for_compliance_review"),
+        ComplianceStatus::Verified => None,
+    }
+}
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use re_core::domain::entities::verification_code::CODE_LENGTH;
+use re_infra::cache::metrics::LATENCY_BUCKETS_US;
+use re_infra::cache::CacheOperationMetrics;
+use re_infra::database::PoolStatistics;
+use re_infra::sms::DryRunMessage;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolStatisticsResponse {
+    /// Open connections, active and idle
+    pub connections: u32,
+    pub active_connections: u32,
+    pub idle_connections: u32,
+    pub max_connections: u32,
+    /// Active connections as a percentage of `max_connections`
+    pub utilization_percent: u32,
+    /// Time taken to acquire a connection, `null` if it could not be sampled
+    pub acquire_wait_ms: Option<f64>,
+    /// State of the circuit breaker: `closed`, `open` or `half_open`
+    pub circuit: String,
+}
+
+impl From<PoolStatistics> for PoolStatisticsResponse {
+    fn from(stats: PoolStatistics) -> Self {
+        let utilization_percent = stats
+            .active_connections
+            .saturating_mul(100)
+            .checked_div(stats.max_connections)
+            .unwrap_or(0);
+
+        Self {
+            connections: stats.connections,
+            active_connections: stats.active_connections,
+            idle_connections: stats.idle_connections as u32,
+            max_connections: stats.max_connections,
+            utilization_percent,
+            acquire_wait_ms: stats.acquire_wait.map(|wait| wait.as_secs_f64() * 1000.0),
+            circuit: stats.circuit.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyBucketResponse {
+    /// Upper bound of the bucket, `null` for the calls slower than every bound
+    pub le_ms: Option<f64>,
+    /// Calls that took longer than the previous bound and up to `le_ms`
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheOperationStatisticsResponse {
+    pub calls: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub errors: u64,
+    /// Hits as a share of hits and misses, `null` for operations that are not lookups
+    pub hit_ratio: Option<f64>,
+    pub average_duration_ms: f64,
+    pub max_duration_ms: f64,
+    pub latency_histogram: Vec<LatencyBucketResponse>,
+}
+
+impl From<CacheOperationMetrics> for CacheOperationStatisticsResponse {
+    fn from(metrics: CacheOperationMetrics) -> Self {
+        let bounds = LATENCY_BUCKETS_US.iter().map(|bound| Some(*bound as f64 / 1000.0));
+        let latency_histogram = bounds
+            .chain(std::iter::once(None))
+            .zip(metrics.latency_buckets.iter())
+            .map(|(le_ms, count)| LatencyBucketResponse { le_ms, count: *count })
+            .collect();
+
+        Self {
+            calls: metrics.calls,
+            hits: metrics.hits,
+            misses: metrics.misses,
+            errors: metrics.errors,
+            hit_ratio: metrics.hit_ratio(),
+            average_duration_ms: metrics.average_duration_us() as f64 / 1000.0,
+            max_duration_ms: metrics.max_duration_us as f64 / 1000.0,
+            latency_histogram,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheStatisticsResponse {
+    /// Operations of each cache, by cache name and operation name
+    pub caches: BTreeMap<String, BTreeMap<String, CacheOperationStatisticsResponse>>,
+}
+
+impl CacheStatisticsResponse {
+    /// Add the operations of a cache
+    pub fn with_cache(mut self, name: &str, metrics: HashMap<&'static str, CacheOperationMetrics>) -> Self {
+        let operations = metrics
+            .into_iter()
+            .map(|(operation, metrics)| (operation.to_string(), metrics.into()))
+            .collect();
+        self.caches.insert(name.to_string(), operations);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmsDryRunQuery {
+    /// Only report messages to this phone number, in E.164 format
+    pub phone: Option<String>,
+    /// Maximum number of messages reported (default: 20)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunMessageResponse {
+    pub message_id: String,
+    pub provider: String,
+    pub phone_number: String,
+    pub sender_id: String,
+    pub body: String,
+    /// Verification code found in the body, `null` for other messages
+    pub code: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl From<DryRunMessage> for DryRunMessageResponse {
+    fn from(message: DryRunMessage) -> Self {
+        Self {
+            code: verification_code_in(&message.body),
+            message_id: message.message_id,
+            provider: message.provider,
+            phone_number: message.phone_number,
+            sender_id: message.sender_id,
+            body: message.body,
+            recorded_at: message.recorded_at,
+        }
+    }
+}
+
+/// First run of exactly `CODE_LENGTH` digits in a message body
+fn verification_code_in(body: &str) -> Option<String> {
+    body.split(|c: char| !c.is_ascii_digit())
+        .find(|digits| digits.len() == CODE_LENGTH)
+        .map(str::to_string)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SmsDryRunResponse {
+    /// Recorded messages, newest first
+    pub messages: Vec<DryRunMessageResponse>,
+}
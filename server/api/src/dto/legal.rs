@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use re_core::domain::entities::legal_document::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalDocumentQuery {
+    /// Language code; defaults to the `Accept-Language` header, then English
+    pub lang: Option<String>,
+
+    /// Market code (ISO 3166-1 alpha-2); omitted for the version for every market
+    pub market: Option<String>,
+
+    /// Exact version to serve; omitted for the version in effect
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalDocumentResponse {
+    pub id: Uuid,
+    pub kind: LegalDocumentKind,
+    /// Market of the version, `null` if it applies to every market
+    pub market: Option<String>,
+    pub version: i32,
+    pub language: String,
+    pub title: String,
+    /// Document text in Markdown
+    pub body: String,
+    pub effective_from: DateTime<Utc>,
+}
+
+impl From<LegalDocument> for LegalDocumentResponse {
+    fn from(document: LegalDocument) -> Self {
+        Self {
+            id: document.id,
+            kind: document.kind,
+            market: document.market,
+            version: document.version,
+            language: document.language,
+            title: document.title,
+            body: document.body,
+            effective_from: document.effective_from,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingLegalDocumentsQuery {
+    /// Language code; defaults to the `Accept-Language` header, then English
+    pub lang: Option<String>,
+
+    /// Market code (ISO 3166-1 alpha-2) of the user
+    pub market: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalDocumentListResponse {
+    pub documents: Vec<LegalDocumentResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AcceptLegalDocumentRequest {
+    /// Version the app showed
+    #[validate(range(min = 1))]
+    pub version: i32,
+
+    /// Market code (ISO 3166-1 alpha-2) of the user
+    #[validate(length(equal = 2))]
+    pub market: Option<String>,
+
+    /// Language the document was shown in
+    #[validate(length(min = 2, max = 35))]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalAcceptanceResponse {
+    pub document_id: Uuid,
+    pub kind: LegalDocumentKind,
+    pub market: Option<String>,
+    pub version: i32,
+    pub accepted_at: DateTime<Utc>,
+}
+
+impl From<LegalAcceptance> for LegalAcceptanceResponse {
+    fn from(acceptance: LegalAcceptance) -> Self {
+        Self {
+            document_id: acceptance.document_id,
+            kind: acceptance.kind,
+            market: acceptance.market,
+            version: acceptance.version,
+            accepted_at: acceptance.accepted_at,
+        }
+    }
+}
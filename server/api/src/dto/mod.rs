@@ -1,3 +1,16 @@
+pub mod admin;
 pub mod auth;
+pub mod completion;
+pub mod compliance;
 pub mod error;
+pub mod internal;
+pub mod legal;
+pub mod notification;
+pub mod oauth;
+pub mod organization;
+pub mod payment;
+pub mod payout;
+pub mod user;
+pub mod validation;
+pub mod wallet;
 
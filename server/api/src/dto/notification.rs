@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use re_core::domain::entities::notification::{
+    MessageTemplate, NotificationCategory, ScheduledMessage, ScheduledMessageStatus,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleMessageRequest {
+    pub template: MessageTemplate,
+    /// Template parameters, such as `worker_name` and `arrival_time`
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    /// Requested delivery time; quiet hours may push it later
+    pub send_at: DateTime<Utc>,
+}
+
+/// A scheduled message as shown to its recipient
+///
+/// The phone number is left out; it is always the user's own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessageResponse {
+    pub id: Uuid,
+    pub template: MessageTemplate,
+    pub category: NotificationCategory,
+    pub params: HashMap<String, String>,
+    pub requested_for: DateTime<Utc>,
+    /// Time the message will be sent, after quiet hours were applied
+    pub deliver_at: DateTime<Utc>,
+    pub status: ScheduledMessageStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ScheduledMessage> for ScheduledMessageResponse {
+    fn from(message: ScheduledMessage) -> Self {
+        Self {
+            id: message.id,
+            template: message.template,
+            category: message.category,
+            params: message.params,
+            requested_for: message.requested_for,
+            deliver_at: message.deliver_at,
+            status: message.status,
+            created_at: message.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessagesResponse {
+    pub messages: Vec<ScheduledMessageResponse>,
+    pub total: usize,
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use re_core::domain::entities::oauth::{format_scope, OAuthConsent};
+use re_core::domain::entities::user::UserType;
+use re_core::services::oauth::{ConsentPrompt, OAuthError, PartnerAccessToken};
+
+/// Form body of the token endpoint
+///
+/// Clients authenticate with `client_id` and `client_secret` in the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRequest {
+    /// `client_credentials` or `authorization_code`
+    pub grant_type: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Space-delimited scopes, for the client credentials grant
+    pub scope: Option<String>,
+    /// Authorization code, for the authorization code grant
+    pub code: Option<String>,
+    /// Redirect URI of the authorization request, for the authorization code grant
+    pub redirect_uri: Option<String>,
+    /// PKCE verifier, when the authorization request had a challenge
+    pub code_verifier: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub scope: String,
+}
+
+impl From<PartnerAccessToken> for TokenResponse {
+    fn from(token: PartnerAccessToken) -> Self {
+        Self {
+            access_token: token.access_token,
+            token_type: token.token_type,
+            expires_in: token.expires_in,
+            scope: token.scope,
+        }
+    }
+}
+
+/// Error body of the token endpoint, as defined by RFC 6749
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
+
+impl From<&OAuthError> for OAuthErrorResponse {
+    fn from(error: &OAuthError) -> Self {
+        let error_description = match error {
+            // Storage and signing details are logged, not returned
+            OAuthError::Server(_) => None,
+            other => Some(other.to_string()),
+        };
+
+        Self {
+            error: error.error_code().to_string(),
+            error_description,
+        }
+    }
+}
+
+/// Query of an authorization request, as forwarded by the app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizeQuery {
+    /// Must be `code`
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentPromptResponse {
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    /// Whether the user already approved every requested scope
+    pub previously_granted: bool,
+}
+
+impl From<ConsentPrompt> for ConsentPromptResponse {
+    fn from(prompt: ConsentPrompt) -> Self {
+        Self {
+            client_id: prompt.client_id,
+            client_name: prompt.client_name,
+            scopes: prompt.scopes,
+            previously_granted: prompt.previously_granted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizeResponse {
+    /// Partner URL to open, carrying the authorization code and state
+    pub redirect_to: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentResponse {
+    pub client_id: String,
+    pub scope: String,
+    pub granted_at: DateTime<Utc>,
+}
+
+impl From<OAuthConsent> for ConsentResponse {
+    fn from(consent: OAuthConsent) -> Self {
+        Self {
+            scope: format_scope(&consent.scopes),
+            client_id: consent.client_id,
+            granted_at: consent.granted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentListResponse {
+    pub consents: Vec<ConsentResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartnerProfileResponse {
+    pub id: String,
+    pub user_type: Option<UserType>,
+    pub is_verified: bool,
+}
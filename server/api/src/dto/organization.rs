@@ -0,0 +1,200 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use re_core::domain::entities::organization::{
+    ConsolidatedInvoice, InvoiceLine, Organization, OrganizationMember, OrganizationPaymentMethod,
+    OrganizationRole,
+};
+use re_core::services::organization_billing::MemberSpend;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateOrganizationRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    /// Address invoices are sent to
+    #[validate(email)]
+    pub billing_email: String,
+
+    /// ISO 4217 currency code the organization is billed in
+    #[validate(length(equal = 3))]
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub billing_email: String,
+    pub currency: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Organization> for OrganizationResponse {
+    fn from(organization: Organization) -> Self {
+        Self {
+            id: organization.id,
+            name: organization.name,
+            billing_email: organization.billing_email,
+            currency: organization.currency,
+            created_at: organization.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SaveOrganizationMemberRequest {
+    pub role: OrganizationRole,
+
+    /// Most the member may spend per calendar month in minor units, omitted for no limit
+    #[validate(range(min = 0))]
+    pub monthly_spend_limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMemberResponse {
+    pub user_id: Uuid,
+    pub role: OrganizationRole,
+    pub monthly_spend_limit: Option<i64>,
+    /// Quotes accepted this calendar month in minor units, only included when listing members
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent_this_month: Option<i64>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl From<OrganizationMember> for OrganizationMemberResponse {
+    fn from(member: OrganizationMember) -> Self {
+        Self {
+            user_id: member.user_id,
+            role: member.role,
+            monthly_spend_limit: member.monthly_spend_limit,
+            spent_this_month: None,
+            added_at: member.added_at,
+        }
+    }
+}
+
+impl From<MemberSpend> for OrganizationMemberResponse {
+    fn from(spend: MemberSpend) -> Self {
+        Self {
+            spent_this_month: Some(spend.spent_this_month),
+            ..Self::from(spend.member)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMemberListResponse {
+    pub members: Vec<OrganizationMemberResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationPaymentMethodResponse {
+    pub id: Uuid,
+    /// Provider's identifier for the payment method
+    pub payment_method: String,
+    pub brand: String,
+    pub last4: String,
+    pub exp_month: u32,
+    pub exp_year: i32,
+    pub is_default: bool,
+    pub added_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OrganizationPaymentMethod> for OrganizationPaymentMethodResponse {
+    fn from(method: OrganizationPaymentMethod) -> Self {
+        Self {
+            id: method.id,
+            payment_method: method.provider_reference,
+            brand: method.brand,
+            last4: method.last4,
+            exp_month: method.exp_month,
+            exp_year: method.exp_year,
+            is_default: method.is_default,
+            added_by: method.added_by,
+            created_at: method.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationPaymentMethodListResponse {
+    pub payment_methods: Vec<OrganizationPaymentMethodResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceListQuery {
+    /// Maximum number of invoices to return (default: 12, maximum: 60)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceLineResponse {
+    pub order_id: Uuid,
+    pub user_id: Uuid,
+    pub amount: i64,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl From<InvoiceLine> for InvoiceLineResponse {
+    fn from(line: InvoiceLine) -> Self {
+        Self {
+            order_id: line.order_id,
+            user_id: line.user_id,
+            amount: line.amount,
+            completed_at: line.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberTotalResponse {
+    pub user_id: Uuid,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedInvoiceResponse {
+    pub id: Uuid,
+    /// First day of the invoiced month
+    pub period_start: NaiveDate,
+    pub currency: String,
+    pub total_amount: i64,
+    /// Totals per member, so spend can be allocated internally
+    pub member_totals: Vec<MemberTotalResponse>,
+    pub lines: Vec<InvoiceLineResponse>,
+    pub payment_method_id: Option<Uuid>,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl From<ConsolidatedInvoice> for ConsolidatedInvoiceResponse {
+    fn from(invoice: ConsolidatedInvoice) -> Self {
+        let member_totals = invoice
+            .member_totals()
+            .into_iter()
+            .map(|(user_id, amount)| MemberTotalResponse { user_id, amount })
+            .collect();
+
+        Self {
+            id: invoice.id,
+            period_start: invoice.period_start,
+            currency: invoice.currency,
+            total_amount: invoice.total_amount,
+            member_totals,
+            lines: invoice.lines.into_iter().map(Into::into).collect(),
+            payment_method_id: invoice.payment_method_id,
+            issued_at: invoice.issued_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedInvoiceListResponse {
+    pub invoices: Vec<ConsolidatedInvoiceResponse>,
+    pub total: usize,
+}
@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use re_core::domain::entities::payment::{
+    Payment, PaymentClientAction, PaymentStatus, PaymentTimelineEntry,
+};
+use re_core::domain::entities::payment_method::SavedPaymentMethod;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreatePaymentRequest {
+    /// Worker who is paid out for the job
+    pub worker_id: Uuid,
+
+    /// Amount in minor units
+    #[validate(range(min = 1))]
+    pub amount: i64,
+
+    /// ISO 4217 currency code
+    #[validate(length(equal = 3))]
+    pub currency: String,
+
+    /// Payment method created by the provider's SDK on the device (e.g. `pm_...`)
+    #[validate(length(min = 1, max = 255))]
+    pub payment_method: String,
+
+    /// Key chosen by the app; retrying with the same key returns the same payment
+    #[validate(length(min = 1, max = 255))]
+    pub idempotency_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentResponse {
+    pub id: Uuid,
+    pub status: PaymentStatus,
+    pub amount: i64,
+    pub currency: String,
+    /// What the app must do to authenticate the payment, when `status` is `requires_action`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_action: Option<PaymentClientAction>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PaymentResponse {
+    pub fn new(payment: Payment, client_action: Option<PaymentClientAction>) -> Self {
+        Self {
+            id: payment.id,
+            status: payment.status,
+            amount: payment.amount,
+            currency: payment.currency,
+            client_action,
+            created_at: payment.created_at,
+            updated_at: payment.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentTimelineEntryResponse {
+    pub status: PaymentStatus,
+    pub note: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<PaymentTimelineEntry> for PaymentTimelineEntryResponse {
+    fn from(entry: PaymentTimelineEntry) -> Self {
+        Self {
+            status: entry.status,
+            note: entry.note,
+            occurred_at: entry.occurred_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentTimelineResponse {
+    pub payment: PaymentResponse,
+    pub timeline: Vec<PaymentTimelineEntryResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AddPaymentMethodRequest {
+    /// Payment method created by the provider's SDK on the device (e.g. `pm_...`)
+    #[validate(length(min = 1, max = 255))]
+    pub payment_method: String,
+
+    /// Whether to make it the default; the first saved method always is
+    #[serde(default)]
+    pub make_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodResponse {
+    pub id: Uuid,
+    /// Provider's identifier, passed as `payment_method` when paying
+    pub payment_method: String,
+    pub brand: String,
+    pub last4: String,
+    pub exp_month: u32,
+    pub exp_year: i32,
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SavedPaymentMethod> for PaymentMethodResponse {
+    fn from(method: SavedPaymentMethod) -> Self {
+        Self {
+            id: method.id,
+            payment_method: method.provider_reference,
+            brand: method.brand,
+            last4: method.last4,
+            exp_month: method.exp_month,
+            exp_year: method.exp_year,
+            is_default: method.is_default,
+            created_at: method.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethodListResponse {
+    pub payment_methods: Vec<PaymentMethodResponse>,
+    pub total: usize,
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use re_core::domain::entities::payment::{Payout, PayoutStatus};
+use re_core::domain::entities::payout_method::{PayoutMethod, PayoutMethodStatus};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AddPayoutMethodRequest {
+    /// Bank account token created by the provider's SDK on the device (e.g. `btok_...`)
+    #[validate(length(min = 1, max = 255))]
+    pub bank_account: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct VerifyMicroDepositsRequest {
+    /// The two deposit amounts in cents, in any order
+    #[validate(length(min = 2, max = 2))]
+    pub amounts: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutMethodResponse {
+    pub id: Uuid,
+    pub bank_name: Option<String>,
+    pub account_last4: String,
+    pub currency: String,
+    pub status: PayoutMethodStatus,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PayoutMethod> for PayoutMethodResponse {
+    fn from(method: PayoutMethod) -> Self {
+        Self {
+            id: method.id,
+            bank_name: method.bank_name,
+            account_last4: method.account_last4,
+            currency: method.currency,
+            status: method.status,
+            verified_at: method.verified_at,
+            created_at: method.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutMethodListResponse {
+    pub payout_methods: Vec<PayoutMethodResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutResponse {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: i64,
+    pub currency: String,
+    pub status: PayoutStatus,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Payout> for PayoutResponse {
+    fn from(payout: Payout) -> Self {
+        Self {
+            id: payout.id,
+            payment_id: payout.payment_id,
+            amount: payout.amount,
+            currency: payout.currency,
+            status: payout.status,
+            updated_at: payout.updated_at,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use re_core::domain::entities::audit::{AuditEventType, AuditLog};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginHistoryQuery {
+    /// Maximum number of sign-ins to return (default: 20, maximum: 100)
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginHistoryEntryResponse {
+    pub id: Uuid,
+    pub event_type: AuditEventType,
+    pub success: bool,
+    /// IP address reduced to its network part
+    pub ip_address: String,
+    /// Device type and OS derived from the user agent
+    pub device: Option<String>,
+    /// Country the IP address resolved to, if known
+    pub location: Option<String>,
+    pub failure_reason: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<AuditLog> for LoginHistoryEntryResponse {
+    fn from(log: AuditLog) -> Self {
+        let location = log
+            .event_data
+            .as_ref()
+            .and_then(|data| data.get("country"))
+            .and_then(|country| country.as_str())
+            .map(String::from);
+
+        Self {
+            id: log.id,
+            event_type: log.event_type,
+            success: log.success,
+            ip_address: AuditLog::mask_ip(&log.ip_address),
+            device: log.device_info,
+            location,
+            failure_reason: log.failure_reason,
+            occurred_at: log.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginHistoryResponse {
+    pub logins: Vec<LoginHistoryEntryResponse>,
+    pub total: usize,
+}
@@ -0,0 +1,132 @@
+//! Catalog of the validation error codes request DTOs produce
+//!
+//! Every code is a key of the `validation.toml` locale files, so clients
+//! can map the `error` field of a 400 response to their own copy. The
+//! catalog also records which `validator` rules produce each code and the
+//! placeholders its message uses; tests keep it in sync with the DTOs and
+//! the locale files.
+
+use serde::Serialize;
+use validator::ValidationErrors;
+
+use re_core::errors::ValidationError as DomainValidationError;
+
+/// A validation error code clients may receive
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ValidationCode {
+    /// Error code and key in `validation.toml`
+    pub code: &'static str,
+    /// Placeholders of the localized message
+    pub params: &'static [&'static str],
+    /// `validator` rules reported with this code; empty for codes raised by services
+    pub rules: &'static [&'static str],
+    /// When the code is returned
+    pub description: &'static str,
+}
+
+/// Every validation error code, in the order of `validation.toml`
+pub const VALIDATION_CODES: &[ValidationCode] = &[
+    ValidationCode {
+        code: "required_field",
+        params: &["field"],
+        rules: &["required"],
+        description: "A required field is missing",
+    },
+    ValidationCode {
+        code: "invalid_format",
+        params: &["field"],
+        rules: &["custom", "contains", "does_not_contain", "must_match", "non_control_character"],
+        description: "A field is malformed; also reported for rules without a more specific code",
+    },
+    ValidationCode {
+        code: "out_of_range",
+        params: &["field", "min", "max"],
+        rules: &["range", "length"],
+        description: "A number, or the length of a text or list, is outside the allowed bounds",
+    },
+    ValidationCode {
+        code: "invalid_length",
+        params: &["field", "expected", "actual"],
+        rules: &["length"],
+        description: "A text or list must have an exact length, such as a 6-digit code",
+    },
+    ValidationCode {
+        code: "pattern_mismatch",
+        params: &["field"],
+        rules: &["regex"],
+        description: "A field does not match its required pattern",
+    },
+    ValidationCode {
+        code: "invalid_email",
+        params: &[],
+        rules: &["email"],
+        description: "An email address is malformed",
+    },
+    ValidationCode {
+        code: "invalid_url",
+        params: &[],
+        rules: &["url"],
+        description: "A URL is malformed",
+    },
+    ValidationCode {
+        code: "invalid_date",
+        params: &[],
+        rules: &[],
+        description: "A date cannot be parsed or is not allowed",
+    },
+    ValidationCode {
+        code: "duplicate_value",
+        params: &["field"],
+        rules: &[],
+        description: "A value that must be unique is already used",
+    },
+    ValidationCode {
+        code: "business_rule_violation",
+        params: &["rule"],
+        rules: &[],
+        description: "The request breaks a business rule",
+    },
+];
+
+/// Convert the first failed `validator` rule of a DTO to the domain error
+///
+/// `length` rules report `invalid_length` when an exact length is required
+/// and `out_of_range` otherwise; rules without a specific code report
+/// `invalid_format`.
+pub fn domain_validation_error(errors: &ValidationErrors) -> DomainValidationError {
+    let field_errors = errors.field_errors();
+    let Some((field, error)) = field_errors
+        .iter()
+        .min_by_key(|(field, _)| field.to_string())
+        .and_then(|(field, errors)| errors.first().map(|error| (field.to_string(), error)))
+    else {
+        return DomainValidationError::InvalidFormat { field: "request".to_string() };
+    };
+
+    let param = |name: &str| error.params.get(name).map(|value| value.to_string().trim_matches('"').to_string());
+    match error.code.as_ref() {
+        "required" => DomainValidationError::RequiredField { field },
+        "length" if error.params.contains_key("equal") => DomainValidationError::InvalidLength {
+            field,
+            expected: param("equal").and_then(|v| v.parse().ok()).unwrap_or_default(),
+            actual: error
+                .params
+                .get("value")
+                .map(|value| match value {
+                    serde_json::Value::String(text) => text.chars().count(),
+                    serde_json::Value::Array(items) => items.len(),
+                    _ => 0,
+                })
+                .unwrap_or_default(),
+        },
+        "length" | "range" => DomainValidationError::OutOfRange {
+            field,
+            min: param("min").unwrap_or_default(),
+            max: param("max").unwrap_or_default(),
+        },
+        "regex" => DomainValidationError::PatternMismatch { field },
+        "email" => DomainValidationError::InvalidEmail,
+        "url" => DomainValidationError::InvalidUrl,
+        _ => DomainValidationError::InvalidFormat { field },
+    }
+}
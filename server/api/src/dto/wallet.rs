@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use re_core::domain::entities::credit_wallet::{CreditTransaction, CreditTransactionKind};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBalanceQuery {
+    /// ISO 4217 currency code of the balance
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBalanceResponse {
+    /// Credit that can be spent, in minor units
+    pub balance: i64,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletHistoryQuery {
+    /// Maximum number of transactions to return (default: 50, maximum: 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditTransactionResponse {
+    pub id: Uuid,
+    pub kind: CreditTransactionKind,
+    pub amount: i64,
+    pub currency: String,
+    pub reference: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl From<CreditTransaction> for CreditTransactionResponse {
+    fn from(transaction: CreditTransaction) -> Self {
+        Self {
+            id: transaction.id,
+            kind: transaction.kind,
+            amount: transaction.amount,
+            currency: transaction.currency,
+            reference: transaction.reference,
+            occurred_at: transaction.occurred_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletHistoryResponse {
+    pub transactions: Vec<CreditTransactionResponse>,
+    pub total: usize,
+}
@@ -105,6 +105,10 @@ pub fn handle_domain_error_with_lang(error: &DomainError, lang: Language) -> Htt
             log::error!("Internal error: {}", message);
             handle_general_error("internal_error", None, lang)
         }
+        DomainError::Conflict { message } => {
+            log::warn!("Conflicting write: {}", message);
+            handle_general_error("conflict", None, lang)
+        }
     }
 }
 
@@ -132,6 +136,9 @@ fn handle_auth_error(auth_error: &AuthError, lang: Language) -> HttpResponse {
         AuthError::SessionExpired => ("session_expired", HashMap::new()),
         AuthError::RegistrationDisabled => ("registration_disabled", HashMap::new()),
         AuthError::UserBlocked => ("user_blocked", HashMap::new()),
+        AuthError::LandlinePhoneNumber => ("landline_phone_number", HashMap::new()),
+        AuthError::VoipPhoneNumber => ("voip_phone_number", HashMap::new()),
+        AuthError::UnreachablePhoneNumber => ("unreachable_phone_number", HashMap::new()),
     };
 
     create_error_response("auth", error_key, params, lang)
@@ -203,7 +210,9 @@ fn handle_token_error(token_error: &TokenError, lang: Language) -> HttpResponse
         TokenError::InvalidClaims => ("invalid_claims", HashMap::new()),
         TokenError::TokenRevoked => ("token_revoked", HashMap::new()),
         TokenError::RefreshTokenExpired => ("refresh_token_expired", HashMap::new()),
+        TokenError::SessionIdleTimeout => ("session_idle_timeout", HashMap::new()),
         TokenError::InvalidRefreshToken => ("invalid_refresh_token", HashMap::new()),
+        TokenError::DeviceMismatch => ("device_mismatch", HashMap::new()),
         TokenError::TokenGenerationFailed => ("token_generation_failed", HashMap::new()),
         TokenError::MissingClaim { claim } => {
             let mut params = HashMap::new();
@@ -154,6 +154,11 @@ pub fn to_standard_response(error: &DomainError, req: &HttpRequest) -> HttpRespo
             let msg = get_localized_message(lang, "internal_error", None);
             ("INTERNAL_ERROR".to_string(), msg, 500)
         }
+        DomainError::Conflict { message } => {
+            log::warn!("Conflicting write: {}", message);
+            let msg = get_localized_message(lang, "conflict", None);
+            ("CONFLICT".to_string(), msg, 409)
+        }
     };
     
     let response = DetailedResponse {
@@ -236,6 +241,15 @@ fn map_auth_error(auth_error: &AuthError, lang: Language) -> (String, String, u1
         AuthError::UserBlocked => {
             ("USER_BLOCKED", "user_blocked", HashMap::new())
         }
+        AuthError::LandlinePhoneNumber => {
+            ("LANDLINE_PHONE_NUMBER", "landline_phone_number", HashMap::new())
+        }
+        AuthError::VoipPhoneNumber => {
+            ("VOIP_PHONE_NUMBER", "voip_phone_number", HashMap::new())
+        }
+        AuthError::UnreachablePhoneNumber => {
+            ("UNREACHABLE_PHONE_NUMBER", "unreachable_phone_number", HashMap::new())
+        }
     };
     
     let (_, message, http_status) = get_error_message("auth", key, lang)
@@ -343,9 +357,15 @@ fn map_token_error(token_error: &TokenError, lang: Language) -> (String, String,
         TokenError::RefreshTokenExpired => {
             ("REFRESH_TOKEN_EXPIRED", "refresh_token_expired", HashMap::new())
         }
+        TokenError::SessionIdleTimeout => {
+            ("SESSION_IDLE_TIMEOUT", "session_idle_timeout", HashMap::new())
+        }
         TokenError::InvalidRefreshToken => {
             ("INVALID_REFRESH_TOKEN", "invalid_refresh_token", HashMap::new())
         }
+        TokenError::DeviceMismatch => {
+            ("DEVICE_MISMATCH", "device_mismatch", HashMap::new())
+        }
         TokenError::TokenGenerationFailed => {
             ("TOKEN_GENERATION_FAILED", "token_generation_failed", HashMap::new())
         }
@@ -1,8 +1,13 @@
 // Library exports for testing and external use
 
+pub mod cluster_events;
 pub mod config;
 pub mod dto;
 pub mod handlers;
 pub mod i18n;
+pub mod lifecycle;
+pub mod logging;
 pub mod middleware;
-pub mod routes;
\ No newline at end of file
+pub mod routes;
+pub mod seed;
+pub mod user_import;
\ No newline at end of file
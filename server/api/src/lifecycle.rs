@@ -0,0 +1,218 @@
+//! Server lifecycle and connection draining
+//!
+//! For zero-downtime rollouts an instance leaving service first reports
+//! not-ready on `/health/ready` while it keeps serving, so load balancers
+//! stop routing new requests to it. After the drain delay the server stops
+//! accepting connections and in-flight requests get the shutdown timeout to
+//! finish.
+//!
+//! Draining starts on SIGTERM or Ctrl-C, or from the admin drain endpoint.
+//! A drain started by an administrator can be cancelled; one started by a
+//! signal always ends in shutdown.
+//!
+//! Maintenance mode, unlike draining, applies to the whole cluster: it is
+//! toggled through a cluster event and every instance then answers client
+//! requests with 503 until it is switched off.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use actix_web::dev::ServerHandle;
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use re_core::errors::{DomainError, DomainResult};
+
+/// What started a drain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DrainTrigger {
+    /// SIGTERM or Ctrl-C; shutdown follows
+    Signal,
+    /// An administrator; the instance keeps running until stopped
+    Admin,
+}
+
+/// Draining state of this instance
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DrainStatus {
+    /// Whether the instance reports not-ready
+    pub draining: bool,
+    /// What started the drain
+    pub trigger: Option<DrainTrigger>,
+    /// Timestamp when the drain started
+    pub since: Option<DateTime<Utc>>,
+    /// Why the drain was started, if given
+    pub reason: Option<String>,
+}
+
+/// Shared draining flag read by the readiness endpoint
+#[derive(Debug, Default)]
+pub struct DrainState {
+    status: RwLock<DrainStatus>,
+}
+
+impl DrainState {
+    /// Create a state for an instance in service
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the instance is draining
+    pub fn is_draining(&self) -> bool {
+        self.status.read().unwrap().draining
+    }
+
+    /// Get the current draining state
+    pub fn status(&self) -> DrainStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Start draining
+    ///
+    /// A signal takes over a drain started by an administrator, so it can no
+    /// longer be cancelled.
+    ///
+    /// # Returns
+    /// * `true` if the instance was in service
+    pub fn start_draining(&self, trigger: DrainTrigger, reason: Option<String>) -> bool {
+        let mut status = self.status.write().unwrap();
+        if status.draining {
+            if trigger == DrainTrigger::Signal {
+                status.trigger = Some(DrainTrigger::Signal);
+            }
+            return false;
+        }
+
+        *status = DrainStatus {
+            draining: true,
+            trigger: Some(trigger),
+            since: Some(Utc::now()),
+            reason,
+        };
+        true
+    }
+
+    /// Put the instance back in service
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The drain was cancelled
+    /// * `Ok(false)` - The instance was not draining
+    /// * `Err(DomainError::BusinessRule)` - The instance is shutting down
+    pub fn cancel_draining(&self) -> DomainResult<bool> {
+        let mut status = self.status.write().unwrap();
+        if !status.draining {
+            return Ok(false);
+        }
+
+        if status.trigger == Some(DrainTrigger::Signal) {
+            return Err(DomainError::BusinessRule {
+                message: "The instance is shutting down".to_string(),
+            });
+        }
+
+        *status = DrainStatus::default();
+        Ok(true)
+    }
+}
+
+/// Maintenance mode of the platform, as last announced to this instance
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    /// Whether client requests are rejected
+    pub enabled: bool,
+    /// Message shown to clients instead of the default one
+    pub message: Option<String>,
+    /// Timestamp when maintenance mode was switched on
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Shared maintenance flag read by the maintenance middleware
+#[derive(Debug, Default)]
+pub struct MaintenanceState {
+    status: RwLock<MaintenanceStatus>,
+}
+
+impl MaintenanceState {
+    /// Create a state with maintenance mode off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether maintenance mode is on
+    pub fn is_enabled(&self) -> bool {
+        self.status.read().unwrap().enabled
+    }
+
+    /// Get the current maintenance state
+    pub fn status(&self) -> MaintenanceStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    /// Switch maintenance mode on or off
+    ///
+    /// Switching it on again only replaces the message.
+    ///
+    /// # Returns
+    /// * `true` if maintenance mode changed
+    pub fn set(&self, enabled: bool, message: Option<String>) -> bool {
+        let mut status = self.status.write().unwrap();
+        if !enabled {
+            let changed = status.enabled;
+            *status = MaintenanceStatus::default();
+            return changed;
+        }
+
+        let changed = !status.enabled;
+        status.enabled = true;
+        status.message = message;
+        if changed {
+            status.since = Some(Utc::now());
+        }
+        changed
+    }
+}
+
+/// Drain and stop the server once a shutdown signal arrives
+///
+/// The server must be built with `disable_signals()` so that this task,
+/// rather than actix, decides when it stops.
+pub async fn drain_on_shutdown_signal(
+    state: Arc<DrainState>,
+    server: ServerHandle,
+    drain_delay: Duration,
+) {
+    wait_for_shutdown_signal().await;
+
+    state.start_draining(DrainTrigger::Signal, None);
+    info!(
+        "Shutdown requested - reporting not ready for {} seconds before stopping",
+        drain_delay.as_secs()
+    );
+    tokio::time::sleep(drain_delay).await;
+
+    info!("Drain delay elapsed - waiting for in-flight requests and stopping");
+    server.stop(true).await;
+}
+
+/// Wait for SIGTERM or Ctrl-C
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = terminate.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                return;
+            }
+            Err(e) => log::warn!("Failed to listen for SIGTERM, only Ctrl-C drains: {}", e),
+        }
+    }
+
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,44 @@
+//! Tracing subscriber setup
+//!
+//! The subscriber's filter sits behind a reload layer so that the runtime
+//! log level service can swap it without restarting the server. Records
+//! emitted through the `log` crate are forwarded to the same subscriber.
+
+use re_core::services::log_level::LogFilterTrait;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// Handle to the filter of the global tracing subscriber
+pub struct ReloadableLogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogFilterTrait for ReloadableLogFilter {
+    fn apply(&self, filter: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(filter).map_err(|e| format!("Invalid log filter: {}", e))?;
+        self.handle
+            .reload(filter)
+            .map_err(|e| format!("Failed to reload log filter: {}", e))
+    }
+}
+
+/// Install the global tracing subscriber
+///
+/// # Arguments
+/// * `default_filter` - Initial filter, e.g. `info` or `info,sqlx=warn`;
+///   falls back to `info` if it cannot be parsed
+///
+/// # Panics
+/// Panics if a global subscriber is already installed
+pub fn init_tracing(default_filter: &str) -> ReloadableLogFilter {
+    let filter = EnvFilter::try_new(default_filter).unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .init();
+
+    ReloadableLogFilter { handle }
+}
@@ -1,15 +1,30 @@
 use actix_web::{middleware::Logger, web, App, HttpResponse, HttpServer};
 use dotenv::dotenv;
-use log::info;
+use log::{info, warn};
+use re_core::services::cluster::ClusterEventPublisherTrait;
+use re_core::services::log_level::{LogLevelConfig, LogLevelService};
+use re_infra::cache::{RedisClient, RedisClusterEventBus};
+use re_infra::capabilities::{Capabilities, Capability, CompiledFeatures};
+use re_infra::database::DatabasePool;
+use std::sync::Arc;
+use std::time::Duration;
 
 
 // mod app; // Will be used when dependencies are wired up
+mod cluster_events;
 mod config;
 mod dto;
 mod handlers;
 mod i18n;
+mod lifecycle;
+mod logging;
 mod middleware;
 mod routes;
+mod seed;
+mod user_import;
+
+/// Command-line flag that applies pending database migrations and exits
+const MIGRATE_ONLY_FLAG: &str = "--migrate-only";
 
 // For now, we'll create a simple example showing how to wire up the endpoint
 // In production, you would initialize real implementations of all the services
@@ -19,18 +34,96 @@ async fn main() -> std::io::Result<()> {
     // Load environment variables
     dotenv().ok();
     
-    // Initialize logger
-    env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
-    
-    info!("Starting RenovEasy API Server");
-    
     // Load configuration
     let config = config::Config::from_env()
         .expect("Failed to load configuration");
     
+    // Initialize logger; the filter can be changed at runtime by administrators
+    let default_filter = std::env::var("RUST_LOG").unwrap_or_else(|_| config.logging.level.clone());
+    let log_filter = Arc::new(logging::init_tracing(&default_filter));
+
+    // Deployment jobs apply pending migrations with `--migrate-only` before starting new instances
+    if std::env::args().any(|arg| arg == MIGRATE_ONLY_FLAG) {
+        return migrate_only(&config).await;
+    }
+
+    // Operators migrate users from the legacy system with `--import-users <export>`
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(import_args) = user_import::ImportArgs::from_args(&args) {
+        let import_args = import_args
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+        return user_import::run(&config, import_args).await;
+    }
+
+    // Demo environments and integration tests load fixture data with `--seed <fixture>`
+    if let Some(seed_args) = seed::SeedArgs::from_args(&args) {
+        let seed_args = seed_args
+            .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+        return seed::run(&config, seed_args).await;
+    }
+
+    let log_level_service = Arc::new(LogLevelService::new(
+        log_filter,
+        LogLevelConfig::new(default_filter),
+    ));
+    log_level_service.clone().start_background_task();
+
+    // Client requests are rejected with 503 while the platform is in maintenance
+    let maintenance_state = Arc::new(lifecycle::MaintenanceState::new());
+
+    // Maintenance toggles and log level overrides reach every instance through
+    // the cluster event bus; without Redis they only apply to this instance
+    let event_bus = connect_event_bus(&config).await?;
+    if let Some(event_bus) = &event_bus {
+        cluster_events::start_subscriber(event_bus.clone(), Arc::new(
+            cluster_events::ClusterEventDispatcher::new(maintenance_state.clone())
+                .with_log_level(log_level_service.clone()),
+        ));
+    }
+    let publisher = event_bus.map(|bus| bus as Arc<dyn ClusterEventPublisherTrait>);
+
+    let log_level_state = web::Data::new(routes::admin::log_level::LogLevelState {
+        log_level_service,
+        publisher: publisher.clone(),
+    });
+    
+    info!("Starting RenovEasy API Server");
+    
     let bind_address = format!("{}:{}", config.server.host, config.server.port);
     info!("Server will bind to: {}", bind_address);
     info!("Environment: {:?}", config.environment);
+
+    // Background jobs run once for every tenant the deployment serves
+    let tenants = middleware::tenant::TenantResolver::new(&config.server.tenancy).tenants();
+    info!(
+        "Tenants: {}",
+        tenants.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    );
+
+    // Refuse to start without the capabilities the environment requires, e.g. real SMS in production
+    let capabilities = Capabilities::detect(
+        config.environment,
+        CompiledFeatures::current(),
+        &config.configured_services(),
+    );
+    capabilities
+        .ensure_required()
+        .map_err(std::io::Error::other)?;
+    info!(
+        "Capabilities: {}",
+        capabilities.available.iter().map(Capability::as_str).collect::<Vec<_>>().join(", ")
+    );
+    let capabilities_state = web::Data::new(capabilities);
+
+    // The authentication service is built with this configuration; trusted
+    // testers skip SMS rate limits, so their presence is logged
+    let auth_config = config.auth_service_config();
+    if !auth_config.trusted_testers.phone_hashes.is_empty() {
+        warn!(
+            "{} trusted tester phone(s) are exempt from SMS rate limits",
+            auth_config.trusted_testers.phone_hashes.len()
+        );
+    }
     
     // Note: In a real implementation, you would:
     // 1. Initialize database connections
@@ -41,17 +134,80 @@ async fn main() -> std::io::Result<()> {
     // Example structure:
     // ```
     // let db_pool = create_database_pool().await?;
+    // // Refuse to start against a database missing migrations or altered by hand
+    // db_pool.verify_schema().await?;
+    // // Users of some countries live in regional databases
+    // let db_shards = ShardRouter::connect(DatabaseConfig::from_env()).await?;
     // let redis_client = create_redis_client().await?;
     // 
-    // let user_repo = Arc::new(MySqlUserRepository::new(db_pool.clone()));
-    // let token_repo = Arc::new(MySqlTokenRepository::new(db_pool.clone()));
+    // // User lookups on the token-refresh path are cached in-process and shared through Redis
+    // let user_repo = Arc::new(CachedUserRepository::new(
+    //     ShardedUserRepository::from_pools(&db_shards),
+    //     RepositoryCacheConfig::default(),
+    // )
+    // .with_shared_cache(Arc::new(RedisUserCacheStore::new(Arc::new(redis_client.clone())))));
+    // // Blacklist checks on every authenticated request go to Redis; MySQL keeps
+    // // a persistent copy that is read while Redis is down
+    // let token_repo = Arc::new(TokenBlacklistRepository::new(
+    //     SqlTokenRepository::new(db_pool.sql_database()),
+    //     Arc::new(RedisTokenBlacklistStore::new(Arc::new(redis_client.clone()))),
+    // )
+    // .with_persistent_fallback());
+    // // Without a MySQL server (`--features sqlite`), the authentication flow
+    // // runs on a local SQLite file instead:
+    // //     let sqlite = SqliteDatabase::new("sqlite://renoveasy_dev.db", 5).await?;
+    // //     let user_repo = Arc::new(SqlUserRepository::new(sqlite.database().clone()));
+    // //     let token_repo = Arc::new(SqlTokenRepository::new(sqlite.database().clone()));
+    // //     let audit_repo = Arc::new(SqlAuditLogRepository::new(sqlite.database().clone()));
+    // 
+    // // Sends to a phone and rotations of a session are serialized across instances
+    // let lock = DistributedLock::new(
+    //     Arc::new(RedisDistributedLock::new(redis_client.clone())),
+    //     DistributedLockConfig::default(),
+    // );
     // 
     // let sms_service = Arc::new(TwilioSmsService::new(config));
     // let cache_service = Arc::new(RedisCacheService::new(redis_client.clone()));
-    // let verification_service = Arc::new(VerificationService::new(sms_service, cache_service));
+    // // Codes are sent by a background worker, so a slow SMS provider does not
+    // // hold up /auth/send-code; failed sends are retried, then dead-lettered
+    // let sms_queue = Arc::new(SmsQueueService::with_defaults(
+    //     Arc::new(RedisSmsQueueStore::new(Arc::new(redis_client.clone()))),
+    //     sms_service.clone(),
+    // ));
+    // sms_queue.clone().start_background_task();
+    // // Landlines, VoIP and nonexistent numbers are rejected before a code is sent
+    // let number_lookup = Arc::new(TwilioNumberLookupService::from_env()?);
+    // let verification_service = Arc::new(
+    //     VerificationService::new(sms_service, cache_service)
+    //         .with_send_lock(lock.clone())
+    //         .with_sms_queue(sms_queue)
+    //         .with_number_lookup(number_lookup),
+    // );
     // 
+    // let session_store = Arc::new(RedisSessionStore::new(Arc::new(redis_client.clone())));
+    // // Revocations and configuration reloads reach every instance through the
+    // // cluster event bus connected above
+    // let event_bus = event_bus.expect("Redis is required");
+    // // Account locks checked on every authenticated call are read from memory for a few
+    // // seconds; lock changes are published so other instances drop their copies
+    // let lock_cache = Arc::new(
+    //     TieredCacheBackend::new(Arc::new(redis_client.clone()), TieredCacheConfig::default())
+    //         .with_invalidation_publisher(event_bus.clone()),
+    // );
+    // let account_lock_service = Arc::new(AccountLockService::with_defaults(
+    //     Arc::new(VerificationCache::with_backend(lock_cache.clone())),
+    // ));
     // let rate_limiter = Arc::new(RedisRateLimiter::new(redis_client));
-    // let token_service = Arc::new(TokenService::new(token_repo.clone(), config));
+    // let token_service = Arc::new(
+    //     TokenService::new(token_repo.clone(), config)?
+    //         .with_rotation_lock(lock)
+    //         .with_session_store(session_store)
+    //         .with_revocation_broadcaster(event_bus.clone()),
+    // );
+    // // Register with the dispatcher started above:
+    // //     .with_revocation_cache(token_service.revocation_cache())
+    // //     .with_reloader("ip_access", ip_access_service.clone())
+    // //     .with_local_cache(lock_cache)
     // 
     // let auth_service = Arc::new(AuthService::new(
     //     user_repo,
@@ -59,13 +215,178 @@ async fn main() -> std::io::Result<()> {
     //     rate_limiter,
     //     token_service,
     //     auth_config,
+    // // A login is saved on the shard its user is stored on
+    // ).with_unit_of_work(Arc::new(ShardedUnitOfWork::from_pools(&db_shards))));
+    //
+    // let phone_hash_rehash = Arc::new(PhoneHashRehashService::new(
+    //     Arc::new(MySqlPhoneHashRepository::new(db_pool.clone())),
+    //     PhoneHashRehashConfig { phone_hasher, ..PhoneHashRehashConfig::default() },
+    // ));
+    // phone_hash_rehash.start_background_task();
+    //
+    // let compliance_service = Arc::new(ComplianceService::new(
+    //     Arc::new(MySqlComplianceRepository::new(db_pool.clone())),
+    //     user_repo.clone(),
+    //     ComplianceConfig::default(),
+    // ).with_provider("+86", Arc::new(ChinaRealNameProvider::from_env()?)));
+    // let payout_method_service = payout_method_service.with_compliance(compliance_service.clone());
+    //
+    // // Audit writes are appended to a Redis Stream and flushed to MySQL in batches
+    // let mysql_audit_repo = Arc::new(SqlAuditLogRepository::new(db_pool.sql_database()));
+    // let audit_repo = Arc::new(RedisStreamAuditLogRepository::new(
+    //     Arc::new(redis_client.clone()),
+    //     mysql_audit_repo.clone(),
+    //     AuditStreamConfig::default(),
+    // ));
+    // Arc::new(AuditStreamWorker::new(
+    //     Arc::new(redis_client.clone()),
+    //     mysql_audit_repo,
+    //     AuditStreamConfig::default(),
+    // ))
+    // .start_background_task();
+    //
+    // let export_service = Arc::new(ExportService::new(
+    //     Arc::new(MySqlExportJobRepository::new(db_pool.clone())),
+    //     Arc::new(FileSystemObjectStorage::from_env()?),
+    //     signed_url_service.clone(),
+    //     ExportConfig::default(),
+    // )
+    // .with_source(Arc::new(AuditLogExportSource::new(audit_repo.clone())))
+    // .with_source(Arc::new(AttributionCohortExportSource::new(attribution_repo.clone())))
+    // .with_tenants(tenants.clone()));
+    // export_service.clone().start_background_task();
+    //
+    // // Audit logs past the retention period are archived (or deleted, dropping whole
+    // // monthly partitions) by a background job
+    // let audit_retention = Arc::new(AuditRetentionService::new(
+    //     audit_repo.clone(),
+    //     AuditRetentionConfig::default(),
+    // )
+    // .with_partition_manager(Arc::new(MySqlAuditPartitionManager::new(db_pool.clone()))));
+    // audit_retention.start_background_task();
+    //
+    // // Failed payment webhooks and given-up security webhook deliveries are dead-lettered
+    // let dead_letter_repo = Arc::new(MySqlWebhookDeadLetterRepository::new(db_pool.clone()));
+    // let payment_webhook_service = Arc::new(payment_webhook_service.with_dead_letters(dead_letter_repo.clone()));
+    // let security_webhook_service = Arc::new(security_webhook_service.with_dead_letters(dead_letter_repo.clone()));
+    // let dead_letter_service = Arc::new(WebhookDeadLetterService::new(
+    //     dead_letter_repo,
+    //     WebhookDeadLetterConfig::default(),
+    // )
+    // .with_replayer(payment_webhook_service.clone())
+    // .with_replayer(security_webhook_service.clone()));
+    //
+    // // Administrators import users from the legacy system over the API as well
+    // let user_import_state = web::Data::new(routes::admin::user_import::UserImportState {
+    //     user_import_service: Arc::new(UserImportService::new(
+    //         user_repo.clone(),
+    //         Arc::new(MySqlUserImportRepository::new(db_pool.clone())),
+    //         UserImportConfig { phone_hasher: phone_hasher.clone(), ..UserImportConfig::default() },
+    //     )),
+    // });
+    // // .service(web::resource("/admin/users/import")
+    // //     .app_data(web::PayloadConfig::new(routes::admin::user_import::MAX_EXPORT_BYTES))
+    // //     .route(web::post().to(routes::admin::user_import::import_users::<_, _>)))
+    //
+    // // Pool gauges are logged every minute; GET /internal/db-stats reports them on demand
+    // database_pool.start_statistics_export(Duration::from_secs(60));
+    // // .app_data(web::Data::new(database_pool.clone()))
+    // // .route("/internal/db-stats", web::get().to(routes::internal::db_stats))
+    //
+    // // Cache hits, misses and latency per operation are reported by GET /internal/cache-stats
+    // let cache_stats_state = web::Data::new(routes::internal::CacheStatsState::new()
+    //     .with_cache("redis", redis_client.metrics()));
+    // // .app_data(cache_stats_state.clone())
+    // // .route("/internal/cache-stats", web::get().to(routes::internal::cache_stats))
+    //
+    // // With SMS_DRY_RUN the providers record messages instead of sending them;
+    // // outside production GET /internal/sms/dry-run reports them with their codes
+    // if !config.environment.is_production() {
+    //     let sms_dry_run_state = web::Data::new(routes::internal::SmsDryRunState {
+    //         log: SmsDryRunLog::shared(),
+    //     });
+    //     // .app_data(sms_dry_run_state.clone())
+    //     // .route("/internal/sms/dry-run", web::get().to(routes::internal::sms_dry_run))
+    // }
+    //
+    // // Each SMS provider logs its sends; delivery receipts update the log and
+    // // steer failover away from providers whose messages stop arriving
+    // let sms_delivery_repo = Arc::new(MySqlSmsDeliveryLogRepository::new(db_pool.clone()));
+    // let sms_delivery_health = Arc::new(SmsDeliveryHealth::new(SmsDeliveryConfig::default()));
+    // // Each leg also adds its messages to the daily spend and keeps within the budget
+    // let sms_cost_service = Arc::new(SmsCostService::new(
+    //     Arc::new(MySqlSmsSpendRepository::new(db_pool.clone())),
+    //     Arc::new(RedisSmsSpendCounter::new(Arc::new(redis_client.clone()))),
+    //     config.sms.budget.clone(),
     // ));
+    // let sms_service = create_sms_service_with_delivery_tracking(&config.sms, Some(&SmsDeliveryTracking {
+    //     logs: sms_delivery_repo.clone(),
+    //     health: sms_delivery_health.clone(),
+    //     costs: Some(sms_cost_service.clone()),
+    // }))
+    // .await;
+    // let sms_webhook_state = web::Data::new(routes::webhooks::sms::SmsDeliveryWebhookState {
+    //     sms_delivery_service: Arc::new(SmsDeliveryService::new(sms_delivery_repo.clone(), sms_delivery_health)
+    //         .with_provider(Arc::new(TwilioReceiptProvider::from_env()?))
+    //         .with_provider(Arc::new(SnsReceiptProvider::from_env()?))),
+    // });
+    // // .app_data(sms_webhook_state.clone())
+    // // .route("/webhooks/sms/twilio", web::post().to(routes::webhooks::sms::receive_twilio_receipts::<_>))
+    // // .route("/webhooks/sms/sns", web::post().to(routes::webhooks::sms::receive_sns_receipts::<_>))
+    //
+    // // GET /admin/sms/spend reports the estimated spend against the daily budget
+    // let sms_spend_state = web::Data::new(routes::admin::sms_spend::SmsSpendState {
+    //     sms_cost_service: sms_cost_service.clone(),
+    // });
+    // // .app_data(sms_spend_state.clone())
+    // // .route("/admin/sms/spend", web::get().to(routes::admin::sms_spend::get_sms_spend))
+    //
+    // // GET /admin/sms/logs searches the logged sends for support tickets
+    // let sms_log_state = web::Data::new(routes::admin::sms_logs::SmsLogState {
+    //     sms_logs: sms_delivery_repo.clone(),
+    // });
+    // // .app_data(sms_log_state.clone())
+    // // .route("/admin/sms/logs", web::get().to(routes::admin::sms_logs::list_sms_logs::<MySqlSmsDeliveryLogRepository>))
     // ```
     
     // For now, we'll use the simplified version without real implementations
     // This allows the code to compile and demonstrates the structure
     
-    HttpServer::new(move || {
+    let tls = config.server.tls.clone().map(Arc::new);
+    let app_tls = tls.clone();
+
+    // Readiness reports not-ready while the instance drains before shutdown
+    let drain_state = web::Data::new(lifecycle::DrainState::new());
+    let app_drain_state = drain_state.clone();
+
+    // GET /health reports each dependency as healthy, degraded or down;
+    // once services are wired, register the database pool, e.g.
+    // HealthState::new().with_dependency("database", Arc::new(db_pool))
+    let health_state = web::Data::new(routes::health::HealthState::new());
+
+    let maintenance_admin_state = web::Data::new(routes::admin::maintenance::MaintenanceAdminState {
+        maintenance: maintenance_state.clone(),
+        publisher,
+    });
+
+    // Request fields unknown to this version are logged, or rejected in strict mode
+    let unknown_field_tracker = web::Data::new(
+        middleware::compat_json::UnknownFieldTracker::new(config.server.unknown_field_mode),
+    );
+
+    // Low-priority requests are shed with 503 while the instance is overloaded;
+    // once services are wired, also pass the database pool and Redis client
+    // as probes, e.g. vec![Arc::new(db_pool), Arc::new(redis_client)]
+    let load_monitor = Arc::new(middleware::load_shedding::LoadMonitor::new(
+        config.server.load_shedding.clone(),
+    ));
+    load_monitor.clone().start_sampling(Vec::new());
+    let app_load_monitor = web::Data::from(load_monitor.clone());
+
+    // Requests are served for the marketplace (tenant) their host is mapped to
+    let tenancy = config.server.tenancy.clone();
+
+    let server = HttpServer::new(move || {
         // Use the original simple app for now
         // When implementations are ready, switch to:
         // app::create_app(auth_service.clone())
@@ -73,13 +394,35 @@ async fn main() -> std::io::Result<()> {
         let cors = middleware::cors::create_cors();
         let security = middleware::security::SecurityMiddleware::new();
         
+        // Without TLS settings no route requires a client certificate
+        let mtls = middleware::mtls::MutualTls::new(app_tls.clone().unwrap_or_default());
+
+        // Shed requests are rejected before any other middleware runs
+        let load_shedding = middleware::load_shedding::LoadShedding::new(load_monitor.clone());
+
+        let tenant_resolution = middleware::tenant::TenantResolution::new(&tenancy);
+
+        let maintenance = middleware::maintenance::Maintenance::new(maintenance_state.clone());
+
         App::new()
+            .wrap(tenant_resolution)
+            .wrap(mtls)
             .wrap(Logger::default())
             .wrap(cors)
             .wrap(security)
+            .wrap(maintenance)
+            .wrap(load_shedding)
+            .app_data(log_level_state.clone())
+            .app_data(app_drain_state.clone())
+            .app_data(health_state.clone())
+            .app_data(maintenance_admin_state.clone())
+            .app_data(unknown_field_tracker.clone())
+            .app_data(app_load_monitor.clone())
+            .app_data(capabilities_state.clone())
             
-            // Health check endpoint
-            .route("/health", web::get().to(health_check))
+            // Health check endpoints
+            .route("/health", web::get().to(routes::health::health))
+            .route("/health/ready", web::get().to(routes::health::readiness))
             
             // API v1 routes
             .service(
@@ -91,6 +434,41 @@ async fn main() -> std::io::Result<()> {
                             // The send-code endpoint is ready to be wired when services are available
                             // .route("/send-code", web::post().to(routes::auth::send_code))
                     )
+                    .service(
+                        web::resource("/admin/log-level")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::log_level::get_log_level::<logging::ReloadableLogFilter>))
+                            .route(web::put().to(routes::admin::log_level::set_log_level::<logging::ReloadableLogFilter>))
+                            .route(web::delete().to(routes::admin::log_level::clear_log_level::<logging::ReloadableLogFilter>))
+                    )
+                    .service(
+                        web::resource("/admin/drain")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::drain::get_drain_status))
+                            .route(web::post().to(routes::admin::drain::start_drain))
+                            .route(web::delete().to(routes::admin::drain::cancel_drain))
+                    )
+                    .service(
+                        web::resource("/admin/maintenance")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::maintenance::get_maintenance))
+                            .route(web::put().to(routes::admin::maintenance::set_maintenance))
+                    )
+                    .service(
+                        web::resource("/admin/unknown-fields")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::unknown_fields::get_unknown_fields))
+                    )
+                    .service(
+                        web::resource("/admin/load-shedding")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::load_shedding::get_load_shedding))
+                    )
+                    .service(
+                        web::resource("/admin/capabilities")
+                            .wrap(middleware::auth::JwtAuth::new())
+                            .route(web::get().to(routes::admin::capabilities::get_capabilities))
+                    )
                     .route("/", web::get().to(api_info))
             )
             
@@ -102,18 +480,46 @@ async fn main() -> std::io::Result<()> {
                 }))
             }))
     })
-    .bind(&bind_address)?
-    .run()
-    .await
+    .on_connect(middleware::mtls::extract_peer_certificate)
+    // Shutdown signals are handled by the drain task below
+    .disable_signals()
+    .shutdown_timeout(config.server.shutdown_timeout_seconds);
+
+    let server = match tls {
+        Some(tls) => {
+            let rustls_config = middleware::mtls::load_rustls_config(&tls)?;
+            info!("TLS enabled (client verification: {})", tls.verify_client);
+            server.bind_rustls_0_23(&bind_address, rustls_config)?.run()
+        }
+        None => server.bind(&bind_address)?.run(),
+    };
+
+    actix_web::rt::spawn(lifecycle::drain_on_shutdown_signal(
+        drain_state.into_inner(),
+        server.handle(),
+        Duration::from_secs(config.server.drain_delay_seconds),
+    ));
+
+    server.await
 }
 
-async fn health_check() -> HttpResponse {
-    HttpResponse::Ok().json(serde_json::json!({
-        "status": "healthy",
-        "service": "renov-easy-api",
-        "version": env!("CARGO_PKG_VERSION"),
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-    }))
+/// Apply pending database migrations and exit without serving requests
+async fn migrate_only(config: &config::Config) -> std::io::Result<()> {
+    info!("Applying database migrations ({})", MIGRATE_ONLY_FLAG);
+
+    let pool = DatabasePool::new(config.database.clone())
+        .await
+        .map_err(std::io::Error::other)?;
+    let mut result = pool.run_migrations().await;
+    if result.is_ok() {
+        // Catches tables altered by hand that migrations do not repair
+        result = pool.verify_schema().await;
+    }
+    pool.close().await;
+    result.map_err(std::io::Error::other)?;
+
+    info!("Database migrations applied");
+    Ok(())
 }
 
 async fn api_info() -> HttpResponse {
@@ -121,6 +527,7 @@ async fn api_info() -> HttpResponse {
         "message": "RenovEasy API v1",
         "endpoints": {
             "health": "/health",
+            "ready": "/health/ready",
             "auth": {
                 "send_code": {
                     "path": "/api/v1/auth/send-code",
@@ -164,4 +571,30 @@ async fn api_info() -> HttpResponse {
             }
         }
     }))
-}
\ No newline at end of file
+}
+
+/// Connect the cluster event bus when Redis is configured
+///
+/// Production refuses to start without it; other environments fall back to
+/// applying changes to this instance only.
+async fn connect_event_bus(config: &config::Config) -> std::io::Result<Option<Arc<RedisClusterEventBus>>> {
+    let redis = match &config.cache.redis {
+        Some(redis) if config.cache.enabled && !redis.url.is_empty() => redis.clone(),
+        _ => {
+            info!("Redis is not configured; cluster events are disabled");
+            return Ok(None);
+        }
+    };
+
+    match RedisClient::new(redis).await {
+        Ok(redis_client) => {
+            info!("Cluster event bus connected");
+            Ok(Some(Arc::new(RedisClusterEventBus::new(Arc::new(redis_client)))))
+        }
+        Err(e) if config.environment.is_production() => Err(std::io::Error::other(e)),
+        Err(e) => {
+            warn!("Cluster events are disabled, Redis is unreachable: {}", e);
+            Ok(None)
+        }
+    }
+}
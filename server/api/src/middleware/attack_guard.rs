@@ -0,0 +1,198 @@
+//! Attack protection middleware
+//!
+//! This module rejects requests from IPs and subnets that the core
+//! `AttackGuard` has blocked or throttled, and reports failed requests back
+//! to the guard so they feed the next attack analysis. Blocks are decided by
+//! the guard's background evaluation, not per request. Authentication
+//! handlers name the phone a request targets with `record_target_phone`, so
+//! failures also feed per-phone credential stuffing detection.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::{ErrorForbidden, ErrorTooManyRequests},
+    http::StatusCode,
+    Error, HttpMessage, HttpRequest,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+};
+
+use re_core::repositories::AuditLogRepository;
+use re_core::services::auth::{mask_phone, AttackGuard, GuardDecision};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::middleware::rate_limit::get_client_ip;
+
+/// Request extension holding the masked phone an authentication request targets
+#[derive(Debug, Clone)]
+pub struct AuthTargetPhone(pub String);
+
+/// Name the phone number an authentication request targets
+///
+/// Failures of the request are then reported to the attack guard with the
+/// masked phone, the same way the audit log records it.
+pub fn record_target_phone(req: &HttpRequest, phone: &str) {
+    req.extensions_mut().insert(AuthTargetPhone(mask_phone(phone)));
+}
+
+/// Attack protection middleware factory
+pub struct AttackProtection<A>
+where
+    A: AuditLogRepository + 'static,
+{
+    guard: Arc<AttackGuard<A>>,
+}
+
+impl<A> AttackProtection<A>
+where
+    A: AuditLogRepository + 'static,
+{
+    /// Create attack protection backed by an attack guard
+    ///
+    /// The guard's background task should be started separately with
+    /// `AttackGuard::start_background_task`.
+    pub fn new(guard: Arc<AttackGuard<A>>) -> Self {
+        Self { guard }
+    }
+}
+
+impl<S, B, A> Transform<S, ServiceRequest> for AttackProtection<A>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    A: AuditLogRepository + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AttackProtectionMiddleware<S, A>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AttackProtectionMiddleware {
+            service: Rc::new(service),
+            guard: self.guard.clone(),
+        }))
+    }
+}
+
+/// Attack protection middleware service
+pub struct AttackProtectionMiddleware<S, A>
+where
+    A: AuditLogRepository + 'static,
+{
+    service: Rc<S>,
+    guard: Arc<AttackGuard<A>>,
+}
+
+impl<S, B, A> Service<ServiceRequest> for AttackProtectionMiddleware<S, A>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    A: AuditLogRepository + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let guard = self.guard.clone();
+
+        Box::pin(async move {
+            let ip = get_client_ip(&req);
+            let lang = extract_language(req.request());
+            let is_auth_route = req.path().contains("/auth/");
+
+            match guard.check(&ip) {
+                GuardDecision::Allow => {}
+                GuardDecision::Block { retry_after_seconds } => {
+                    log::warn!("Rejected request from blocked IP {}", ip);
+                    return Err(ErrorForbidden(rejection_body(
+                        "ip_blocked",
+                        lang,
+                        "Access from your network has been temporarily blocked due to suspicious activity",
+                        "由于检测到可疑活动，您的网络已被暂时禁止访问",
+                        retry_after_seconds,
+                    )));
+                }
+                GuardDecision::Throttle { retry_after_seconds } => {
+                    return Err(ErrorTooManyRequests(rejection_body(
+                        "suspicious_activity_throttled",
+                        lang,
+                        "Too many requests from your network. Please try again later",
+                        "您的网络请求过于频繁，请稍后重试",
+                        retry_after_seconds,
+                    )));
+                }
+            }
+
+            let result = service.call(req).await;
+
+            let status = match &result {
+                Ok(response) => response.status(),
+                Err(error) => error.as_response_error().status_code(),
+            };
+            if is_failure(status, is_auth_route) {
+                // Errors raised before a handler ran carry no target phone
+                let phone_masked = result
+                    .as_ref()
+                    .ok()
+                    .and_then(|response| response.request().extensions().get::<AuthTargetPhone>().cloned())
+                    .map(|phone| phone.0);
+                guard.record_failure(&ip, phone_masked.as_deref());
+            }
+
+            result
+        })
+    }
+}
+
+/// Whether a response status counts as a failure for attack detection
+///
+/// Authentication failures count everywhere; client errors and rate limit
+/// rejections only count on authentication routes.
+fn is_failure(status: StatusCode, is_auth_route: bool) -> bool {
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => true,
+        StatusCode::BAD_REQUEST | StatusCode::TOO_MANY_REQUESTS => is_auth_route,
+        _ => false,
+    }
+}
+
+/// Build a localized rejection body
+fn rejection_body(
+    error: &str,
+    lang: Language,
+    message_en: &str,
+    message_zh: &str,
+    retry_after_seconds: u64,
+) -> serde_json::Value {
+    let message = match lang {
+        Language::English => message_en,
+        Language::Chinese => message_zh,
+    };
+
+    let response = ErrorResponse::new(error.to_string(), message.to_string()).with_details(
+        HashMap::from([("retry_after_seconds".to_string(), json!(retry_after_seconds))]),
+    );
+
+    json!({
+        "error": response.error,
+        "message": response.message,
+        "details": response.details,
+        "timestamp": response.timestamp
+    })
+}
@@ -2,6 +2,9 @@
 //!
 //! This middleware extracts JWT tokens from the Authorization header,
 //! verifies their validity, and injects user context into requests.
+//! Access tokens carrying a device fingerprint hash are only accepted
+//! together with the matching `X-Device-Fingerprint` header, and access
+//! tokens are only accepted for the tenant (marketplace) that issued them.
 //! 
 //! The middleware can work in two modes:
 //! 1. Standalone mode: Uses jsonwebtoken directly for simple JWT verification
@@ -10,15 +13,17 @@
 use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
     error::ErrorUnauthorized,
-    http::header::AUTHORIZATION,
+    http::header::{HeaderMap, AUTHORIZATION},
     web, Error, FromRequest, HttpMessage, HttpRequest,
 };
 use re_core::{
-    domain::entities::token::Claims,
+    domain::entities::token::{Claims, JWT_AUDIENCE, JWT_ISSUER},
     errors::{DomainError, TokenError},
+    services::tenant::{TenantId, DEFAULT_TENANT},
     services::token::TokenService,
     repositories::TokenRepository,
 };
+use re_shared::config::SecretString;
 use futures_util::future::LocalBoxFuture;
 use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
 use std::{
@@ -29,6 +34,9 @@ use std::{
 };
 use uuid::Uuid;
 
+/// Header carrying the client's device fingerprint
+pub const DEVICE_FINGERPRINT_HEADER: &str = "X-Device-Fingerprint";
+
 /// User authentication context injected into requests
 #[derive(Debug, Clone)]
 pub struct AuthContext {
@@ -40,6 +48,12 @@ pub struct AuthContext {
     pub is_verified: bool,
     /// JWT ID for tracking
     pub jti: String,
+    /// Administrative roles, for administrators signed in through single sign-on
+    pub roles: Vec<String>,
+    /// Scopes the access token grants
+    pub scopes: Vec<String>,
+    /// Session (refresh-token family) the access token was issued to
+    pub session_id: Option<String>,
 }
 
 impl AuthContext {
@@ -52,28 +66,40 @@ impl AuthContext {
             user_type: claims.user_type,
             is_verified: claims.is_verified,
             jti: claims.jti,
+            roles: claims.roles,
+            scopes: claims
+                .scope
+                .as_deref()
+                .map(|scope| scope.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            session_id: claims.token_family,
         })
     }
+
+    /// Checks whether the access token grants a scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
 }
 
 /// JWT authentication middleware factory
 pub struct JwtAuth {
     /// Optional JWT secret for standalone mode
-    jwt_secret: Option<String>,
+    jwt_secret: Option<SecretString>,
 }
 
 impl JwtAuth {
     /// Creates a new JWT authentication middleware
     pub fn new() -> Self {
         Self {
-            jwt_secret: std::env::var("JWT_SECRET").ok(),
+            jwt_secret: std::env::var("JWT_SECRET").ok().map(SecretString::from),
         }
     }
     
     /// Creates a new JWT authentication middleware with a specific secret
     pub fn with_secret(secret: String) -> Self {
         Self {
-            jwt_secret: Some(secret),
+            jwt_secret: Some(SecretString::from(secret)),
         }
     }
 }
@@ -107,7 +133,7 @@ where
 /// JWT authentication middleware service
 pub struct JwtAuthMiddleware<S> {
     service: Rc<S>,
-    jwt_secret: Option<String>,
+    jwt_secret: Option<SecretString>,
 }
 
 impl<S, B> Service<ServiceRequest> for JwtAuthMiddleware<S>
@@ -139,27 +165,45 @@ where
 
             // Try to get TokenService from app data (if available)
             // This allows for integration with the core layer's TokenService
-            let auth_context = if let Some(token_service) = req.app_data::<web::Data<Arc<dyn TokenServiceWrapper>>>() {
+            let claims = if let Some(token_service) = req.app_data::<web::Data<Arc<dyn TokenServiceWrapper>>>() {
                 // Use the TokenService from core layer
                 match token_service.verify_access_token(&token) {
-                    Ok(claims) => {
-                        match AuthContext::from_claims(claims) {
-                            Ok(context) => context,
-                            Err(e) => return Err(ErrorUnauthorized(format!("Invalid token: {}", e))),
-                        }
-                    }
+                    Ok(claims) => claims,
                     Err(e) => return Err(ErrorUnauthorized(format!("Token verification failed: {}", e))),
                 }
             } else if let Some(secret) = jwt_secret {
                 // Fallback to standalone verification
-                match verify_token_standalone(&token, &secret) {
-                    Ok(context) => context,
+                match verify_token_standalone(&token, secret.expose_secret()) {
+                    Ok(claims) => claims,
                     Err(e) => return Err(ErrorUnauthorized(format!("Token verification failed: {}", e))),
                 }
             } else {
                 return Err(ErrorUnauthorized("JWT verification not configured"));
             };
 
+            // Reject tokens replayed from a device other than the one they were issued to
+            let fingerprint = extract_device_fingerprint(req.headers());
+            if !claims.is_bound_to_device(fingerprint.as_deref()) {
+                return Err(ErrorUnauthorized(format!(
+                    "Token verification failed: {}",
+                    TokenError::DeviceMismatch
+                )));
+            }
+
+            // Reject tokens issued by another marketplace of this deployment
+            let tenant = req.extensions().get::<TenantId>().cloned().unwrap_or_default();
+            if claims.tenant.as_deref().unwrap_or(DEFAULT_TENANT) != tenant.as_str() {
+                return Err(ErrorUnauthorized(format!(
+                    "Token verification failed: {}",
+                    TokenError::InvalidClaims
+                )));
+            }
+
+            let auth_context = match AuthContext::from_claims(claims) {
+                Ok(context) => context,
+                Err(e) => return Err(ErrorUnauthorized(format!("Invalid token: {}", e))),
+            };
+
             // Inject auth context into request extensions
             req.extensions_mut().insert(auth_context);
 
@@ -179,17 +223,32 @@ fn extract_bearer_token(req: &ServiceRequest) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Extracts the device fingerprint from request headers
+///
+/// Only an explicit `X-Device-Fingerprint` counts. Tokens issued to clients
+/// that do not send one stay unbound: a `User-Agent` changes with every
+/// browser update and is no proof of the device anyway. Login, refresh and
+/// this middleware must agree on the value.
+pub fn extract_device_fingerprint(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(DEVICE_FINGERPRINT_HEADER)?
+        .to_str()
+        .ok()
+        .map(|s| s.to_string())
+}
+
 /// Standalone token verification (for when TokenService is not available)
-fn verify_token_standalone(token: &str, secret: &str) -> Result<AuthContext, String> {
+fn verify_token_standalone(token: &str, secret: &str) -> Result<Claims, String> {
     let decoding_key = DecodingKey::from_secret(secret.as_bytes());
     let mut validation = Validation::new(Algorithm::HS256);
     validation.validate_exp = true;
+    validation.set_issuer(&[JWT_ISSUER]);
+    validation.set_audience(&[JWT_AUDIENCE]);
     
     let token_data = decode::<Claims>(token, &decoding_key, &validation)
         .map_err(|e| format!("Token decode error: {}", e))?;
     
-    AuthContext::from_claims(token_data.claims)
-        .map_err(|e| format!("Invalid claims: {}", e))
+    Ok(token_data.claims)
 }
 
 /// Trait for wrapping TokenService to allow dynamic dispatch
@@ -0,0 +1,184 @@
+//! Canary routing middleware
+//!
+//! Routes a percentage of users to a candidate implementation of an
+//! endpoint, registered side by side with the stable one:
+//!
+//! ```ignore
+//! web::resource("/quotes/price")
+//!     .wrap(CanaryRouting::new("pricing-engine-v2", config.server.canary_percentage("pricing-engine-v2")))
+//!     .wrap(JwtAuth::new())
+//!     .route(web::post().guard(in_canary("pricing-engine-v2")).to(pricing_v2::price_quote))
+//!     .route(web::post().to(pricing::price_quote))
+//! ```
+//!
+//! Users are assigned by a stable hash of the canary name and their user
+//! id, so a user stays in the same cohort across requests and instances,
+//! and raising the percentage only adds users to the canary. Each routed
+//! request is published as a `CanaryExposure` lifecycle event for the
+//! analytics hooks when a `LifecycleHookService` is registered as app data.
+//!
+//! The middleware reads the authentication context injected by `JwtAuth`,
+//! so `JwtAuth` must be registered after it (actix runs the last `wrap`
+//! first). Requests without one stay on the stable implementation.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    guard::{self, Guard},
+    web, Error, HttpMessage,
+};
+use chrono::Utc;
+use futures_util::future::LocalBoxFuture;
+use sha2::{Digest, Sha256};
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+use uuid::Uuid;
+
+use re_core::services::lifecycle::{LifecycleEvent, LifecycleHookService};
+
+use crate::middleware::auth::AuthContext;
+
+/// Cohort of a user in a canary rollout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanaryCohort {
+    /// Served by the stable implementation
+    Control,
+    /// Served by the candidate implementation
+    Canary,
+}
+
+impl CanaryCohort {
+    /// Name of the cohort, as reported to analytics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CanaryCohort::Control => "control",
+            CanaryCohort::Canary => "canary",
+        }
+    }
+}
+
+/// Request extension recording the cohort a request was assigned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanaryAssignment {
+    /// Name of the canary
+    pub canary: String,
+    /// Cohort of the requesting user
+    pub cohort: CanaryCohort,
+}
+
+/// Cohort of a user in a canary rolled out to `percentage` percent of users
+pub fn cohort_for(canary: &str, user_id: Uuid, percentage: u8) -> CanaryCohort {
+    let digest = Sha256::new()
+        .chain_update(canary.as_bytes())
+        .chain_update(b":")
+        .chain_update(user_id.as_bytes())
+        .finalize();
+    let bucket = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 100;
+
+    if bucket < u32::from(percentage) {
+        CanaryCohort::Canary
+    } else {
+        CanaryCohort::Control
+    }
+}
+
+/// Route guard matching requests assigned to the canary cohort of a canary
+pub fn in_canary(canary: &'static str) -> impl Guard {
+    guard::fn_guard(move |ctx| {
+        ctx.req_data()
+            .get::<CanaryAssignment>()
+            .is_some_and(|assignment| {
+                assignment.canary == canary && assignment.cohort == CanaryCohort::Canary
+            })
+    })
+}
+
+/// Canary routing middleware factory
+///
+/// Wraps the resource whose routes are split between the stable and the
+/// candidate implementation.
+#[derive(Debug, Clone)]
+pub struct CanaryRouting {
+    canary: Rc<str>,
+    percentage: u8,
+}
+
+impl CanaryRouting {
+    /// Route `percentage` percent of users (capped at 100) to the canary cohort
+    pub fn new(canary: &str, percentage: u8) -> Self {
+        Self {
+            canary: Rc::from(canary),
+            percentage: percentage.min(100),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CanaryRouting
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CanaryRoutingMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CanaryRoutingMiddleware {
+            service: Rc::new(service),
+            canary: self.canary.clone(),
+            percentage: self.percentage,
+        }))
+    }
+}
+
+/// Canary routing middleware service
+pub struct CanaryRoutingMiddleware<S> {
+    service: Rc<S>,
+    canary: Rc<str>,
+    percentage: u8,
+}
+
+impl<S, B> Service<ServiceRequest> for CanaryRoutingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let user_id = req.extensions().get::<AuthContext>().map(|auth| auth.user_id);
+
+        if let Some(user_id) = user_id {
+            let cohort = cohort_for(&self.canary, user_id, self.percentage);
+            log::debug!("Routing user {} to the {} cohort of {}", user_id, cohort.as_str(), self.canary);
+
+            if let Some(hooks) = req.app_data::<web::Data<LifecycleHookService>>() {
+                hooks.publish(LifecycleEvent::CanaryExposure {
+                    experiment: self.canary.to_string(),
+                    cohort: cohort.as_str().to_string(),
+                    user_id,
+                    exposed_at: Utc::now(),
+                });
+            }
+
+            req.extensions_mut().insert(CanaryAssignment {
+                canary: self.canary.to_string(),
+                cohort,
+            });
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { service.call(req).await })
+    }
+}
@@ -0,0 +1,246 @@
+//! JSON body extractor tolerant of schema drift
+//!
+//! Handlers whose request schema evolves take a [`CompatJson<T>`] argument
+//! instead of `web::Json<T>`:
+//!
+//! ```ignore
+//! async fn update_profile(request: CompatJson<UpdateProfileRequest>) -> HttpResponse {
+//!     // `request` derefs to `UpdateProfileRequest`
+//! }
+//!
+//! App::new()
+//!     .app_data(web::Data::new(UnknownFieldTracker::new(config.server.unknown_field_mode)))
+//!     .route("/api/v1/profile", web::put().to(update_profile))
+//! ```
+//!
+//! Fields the request type does not declare are neither dropped silently
+//! nor rejected: they are logged with the client's `X-Client-Version` and
+//! counted per route, so a field can be removed or renamed once no client
+//! in the field sends it any more. In strict mode, meant for staging, such
+//! requests are rejected with 400 Bad Request instead.
+
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::{web, Error, FromRequest, HttpRequest, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use re_shared::config::UnknownFieldMode;
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::i18n::{format_message, get_error_message};
+
+/// Header carrying the version of the client app
+pub const CLIENT_VERSION_HEADER: &str = "X-Client-Version";
+
+/// Client version recorded when the header is missing
+const UNKNOWN_CLIENT_VERSION: &str = "unknown";
+
+/// Longest client version kept, bounding the number of distinct counters
+const MAX_CLIENT_VERSION_LEN: usize = 32;
+
+/// How often an unknown field was sent to a route
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFieldCount {
+    /// Method and route pattern, e.g. `POST /api/v1/legal/{doc}/accept`
+    pub route: String,
+    /// Path of the field in the body, e.g. `device.model`
+    pub field: String,
+    pub count: u64,
+}
+
+/// Counts of unknown request fields since the server started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownFieldMetrics {
+    /// Requests carrying at least one unknown field
+    pub requests: u64,
+    /// Requests rejected in strict mode
+    pub rejected: u64,
+    /// Occurrences of each unknown field, by route then field
+    pub fields: Vec<UnknownFieldCount>,
+    /// Requests with unknown fields by client version
+    pub client_versions: BTreeMap<String, u64>,
+}
+
+/// Handling mode and counters for [`CompatJson`], registered as app data
+pub struct UnknownFieldTracker {
+    mode: UnknownFieldMode,
+    requests: AtomicU64,
+    rejected: AtomicU64,
+    fields: Mutex<HashMap<(String, String), u64>>,
+    client_versions: Mutex<HashMap<String, u64>>,
+}
+
+impl UnknownFieldTracker {
+    /// Create a tracker handling unknown fields in the given mode
+    pub fn new(mode: UnknownFieldMode) -> Self {
+        Self {
+            mode,
+            requests: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+            fields: Mutex::new(HashMap::new()),
+            client_versions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// How unknown fields are handled
+    pub fn mode(&self) -> UnknownFieldMode {
+        self.mode
+    }
+
+    /// Counts of unknown fields so far
+    pub fn metrics(&self) -> UnknownFieldMetrics {
+        let mut fields: Vec<UnknownFieldCount> = self
+            .fields
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((route, field), count)| UnknownFieldCount {
+                route: route.clone(),
+                field: field.clone(),
+                count: *count,
+            })
+            .collect();
+        fields.sort_by(|a, b| (&a.route, &a.field).cmp(&(&b.route, &b.field)));
+
+        UnknownFieldMetrics {
+            requests: self.requests.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            fields,
+            client_versions: self
+                .client_versions
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+        }
+    }
+
+    fn record(&self, route: &str, client_version: &str, fields: &[String]) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if self.mode == UnknownFieldMode::Strict {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let mut counts = self.fields.lock().unwrap();
+        for field in fields {
+            *counts.entry((route.to_string(), field.clone())).or_insert(0) += 1;
+        }
+        drop(counts);
+
+        *self
+            .client_versions
+            .lock()
+            .unwrap()
+            .entry(client_version.to_string())
+            .or_insert(0) += 1;
+    }
+}
+
+/// A JSON request body whose unknown fields were logged and counted
+#[derive(Debug, Clone)]
+pub struct CompatJson<T>(pub T);
+
+impl<T> CompatJson<T> {
+    /// Unwrap into the request body
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for CompatJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromRequest for CompatJson<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let req = req.clone();
+        let body = web::Bytes::from_request(&req, payload);
+
+        Box::pin(async move {
+            let body = body.await?;
+            parse_body(&req, &body)
+        })
+    }
+}
+
+/// Deserialize the body, collecting the paths of fields `T` does not declare
+fn parse_body<T: DeserializeOwned>(req: &HttpRequest, body: &[u8]) -> Result<CompatJson<T>, Error> {
+    let mut unknown = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_slice(body);
+    let value: T = serde_ignored::deserialize(&mut deserializer, |path| unknown.push(path.to_string()))
+        .and_then(|value| deserializer.end().map(|()| value))
+        .map_err(JsonPayloadError::Deserialize)?;
+
+    if unknown.is_empty() {
+        return Ok(CompatJson(value));
+    }
+
+    let tracker = req.app_data::<web::Data<UnknownFieldTracker>>();
+    let mode = tracker.map_or(UnknownFieldMode::Compatible, |tracker| tracker.mode());
+    let route = format!(
+        "{} {}",
+        req.method(),
+        req.match_pattern().unwrap_or_else(|| req.path().to_string())
+    );
+    let client_version = client_version(req);
+
+    log::warn!(
+        "{} received unknown fields [{}] from client version {}{}",
+        route,
+        unknown.join(", "),
+        client_version,
+        if mode == UnknownFieldMode::Strict { "; rejected" } else { "" }
+    );
+    if let Some(tracker) = tracker {
+        tracker.record(&route, &client_version, &unknown);
+    }
+
+    match mode {
+        UnknownFieldMode::Compatible => Ok(CompatJson(value)),
+        UnknownFieldMode::Strict => {
+            let response = unknown_fields_response(&unknown, extract_language(req));
+            Err(InternalError::from_response("unknown request fields", response).into())
+        }
+    }
+}
+
+/// The client version header, or `unknown` when missing or unusable
+fn client_version(req: &HttpRequest) -> String {
+    req.headers()
+        .get(CLIENT_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty() && v.len() <= MAX_CLIENT_VERSION_LEN)
+        .unwrap_or(UNKNOWN_CLIENT_VERSION)
+        .to_string()
+}
+
+/// Build the localized 400 response listing the rejected fields
+fn unknown_fields_response(fields: &[String], lang: Language) -> HttpResponse {
+    let mut params = HashMap::new();
+    params.insert("fields", fields.join(", "));
+
+    let (code, message) = match get_error_message("general", "unknown_fields", lang) {
+        Some((code, template, _)) => (code, format_message(&template, &params)),
+        None => ("unknown_fields".to_string(), format!("Unknown fields: {}", fields.join(", "))),
+    };
+
+    let details = HashMap::from([("fields".to_string(), serde_json::json!(fields))]);
+    HttpResponse::BadRequest().json(ErrorResponse::new(code, message).with_details(details))
+}
@@ -71,6 +71,17 @@ fn create_development_cors(max_age: usize) -> Cors {
             header::HeaderName::from_static("x-app-version"),
             header::HeaderName::from_static("x-platform"),
             header::HeaderName::from_static("x-device-id"),
+            header::HeaderName::from_static("x-device-fingerprint"),
+            header::HeaderName::from_static("x-request-id"),
+            header::HeaderName::from_static("x-marketing-consent"),
+            header::HeaderName::from_static("x-utm-source"),
+            header::HeaderName::from_static("x-utm-medium"),
+            header::HeaderName::from_static("x-utm-campaign"),
+            header::HeaderName::from_static("x-utm-term"),
+            header::HeaderName::from_static("x-utm-content"),
+            header::HeaderName::from_static("x-attribution-referrer"),
+            header::HeaderName::from_static("x-pow-nonce"),
+            header::HeaderName::from_static("x-pow-solution"),
         ])
         // Expose headers that clients might need to read
         .expose_headers(vec![
@@ -109,6 +120,17 @@ fn create_production_cors(max_age: usize) -> Cors {
             header::HeaderName::from_static("x-app-version"),
             header::HeaderName::from_static("x-platform"),
             header::HeaderName::from_static("x-device-id"),
+            header::HeaderName::from_static("x-device-fingerprint"),
+            header::HeaderName::from_static("x-request-id"),
+            header::HeaderName::from_static("x-marketing-consent"),
+            header::HeaderName::from_static("x-utm-source"),
+            header::HeaderName::from_static("x-utm-medium"),
+            header::HeaderName::from_static("x-utm-campaign"),
+            header::HeaderName::from_static("x-utm-term"),
+            header::HeaderName::from_static("x-utm-content"),
+            header::HeaderName::from_static("x-attribution-referrer"),
+            header::HeaderName::from_static("x-pow-nonce"),
+            header::HeaderName::from_static("x-pow-solution"),
         ])
         .expose_headers(vec![
             header::HeaderName::from_static("x-request-id"),
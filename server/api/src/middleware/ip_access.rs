@@ -0,0 +1,132 @@
+//! IP access control middleware
+//!
+//! This module rejects requests from IPs on the administrator-managed
+//! denylist (or, in allowlist-only mode, IPs missing from the allowlist)
+//! before they reach the wrapped routes. Register it on the authentication
+//! scope so denied ranges cannot trigger SMS sends.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorForbidden,
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+};
+
+use re_core::services::auth::{IpAccessControlService, IpAccessDecision, IpAccessStoreTrait};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::middleware::rate_limit::get_client_ip;
+
+/// Request extension marking a client IP that is on the allowlist
+///
+/// Later middleware and handlers can use it to relax limits for trusted
+/// networks.
+#[derive(Debug, Clone, Copy)]
+pub struct IpAllowlisted;
+
+/// IP access control middleware factory
+pub struct IpAccessControl<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    service: Arc<IpAccessControlService<S>>,
+}
+
+impl<S> IpAccessControl<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    /// Create IP access control backed by the access list service
+    ///
+    /// The service's background refresh should be started separately with
+    /// `IpAccessControlService::start_background_task`.
+    pub fn new(service: Arc<IpAccessControlService<S>>) -> Self {
+        Self { service }
+    }
+}
+
+impl<Svc, B, S> Transform<Svc, ServiceRequest> for IpAccessControl<S>
+where
+    Svc: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    Svc::Future: 'static,
+    B: 'static,
+    S: IpAccessStoreTrait + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = IpAccessControlMiddleware<Svc, S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: Svc) -> Self::Future {
+        ready(Ok(IpAccessControlMiddleware {
+            service: Rc::new(service),
+            access_control: self.service.clone(),
+        }))
+    }
+}
+
+/// IP access control middleware service
+pub struct IpAccessControlMiddleware<Svc, S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    service: Rc<Svc>,
+    access_control: Arc<IpAccessControlService<S>>,
+}
+
+impl<Svc, B, S> Service<ServiceRequest> for IpAccessControlMiddleware<Svc, S>
+where
+    Svc: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    Svc::Future: 'static,
+    B: 'static,
+    S: IpAccessStoreTrait + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let access_control = self.access_control.clone();
+
+        Box::pin(async move {
+            let ip = get_client_ip(&req);
+
+            match access_control.check(&ip) {
+                IpAccessDecision::Allowed => {}
+                IpAccessDecision::Allowlisted => {
+                    req.extensions_mut().insert(IpAllowlisted);
+                }
+                IpAccessDecision::Denied { reason } => {
+                    log::warn!("Rejected request from denied IP {}: {}", ip, reason);
+
+                    let message = match extract_language(req.request()) {
+                        Language::English => "Access from your network is not permitted",
+                        Language::Chinese => "您的网络无权访问此服务",
+                    };
+                    let response = ErrorResponse::new("ip_denied".to_string(), message.to_string());
+
+                    return Err(ErrorForbidden(json!({
+                        "error": response.error,
+                        "message": response.message,
+                        "timestamp": response.timestamp
+                    })));
+                }
+            }
+
+            service.call(req).await
+        })
+    }
+}
@@ -0,0 +1,377 @@
+//! Load shedding middleware
+//!
+//! Rejects low-priority requests with 503 Service Unavailable while the
+//! instance is overloaded, so the capacity left serves sign-ins and
+//! payments instead of queueing behind searches and analytics uploads:
+//!
+//! ```ignore
+//! let load_monitor = Arc::new(LoadMonitor::new(config.server.load_shedding.clone()));
+//! load_monitor.clone().start_sampling(vec![Arc::new(db_pool), Arc::new(redis_client)]);
+//!
+//! App::new()
+//!     .app_data(web::Data::from(load_monitor.clone()))
+//!     .wrap(LoadShedding::new(load_monitor.clone()))
+//! ```
+//!
+//! The monitor samples three health signals: the lag of the event loop it
+//! was started on, the share of database connections in use and the Redis
+//! round-trip time. The instance is overloaded when any signal exceeds its
+//! limit, and critically overloaded when the event loop lag or Redis
+//! latency exceed twice their limit or every database connection is in use.
+//!
+//! Overloaded instances shed requests to the configured low-priority
+//! paths; critically overloaded instances shed every request except those
+//! to protected paths such as authentication and payments. Shed requests
+//! carry a `Retry-After` header and are counted per priority.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    Error, HttpResponse,
+};
+use async_trait::async_trait;
+use futures_util::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use re_infra::cache::RedisClient;
+use re_infra::database::DatabasePool;
+use re_shared::config::LoadSheddingConfig;
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::extract_language;
+use crate::i18n::get_error_message;
+
+/// Recorded for signals that have not been sampled yet
+const NOT_SAMPLED: u64 = u64::MAX;
+
+/// How overloaded the instance is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadLevel {
+    /// Every request is served
+    Normal,
+    /// Low-priority requests are shed
+    Overloaded,
+    /// Only requests to protected paths are served
+    Critical,
+}
+
+/// How important a request is to keep serving under load
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestPriority {
+    /// Never shed, e.g. authentication and payments
+    Protected,
+    /// Shed only when critically overloaded
+    Normal,
+    /// Shed first, e.g. searches and analytics ingestion
+    Low,
+}
+
+/// Latest health signals and shed counts of the instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadSheddingStatus {
+    /// Whether requests are shed at all
+    pub enabled: bool,
+    pub level: LoadLevel,
+    /// Event loop lag of the latest sample in milliseconds
+    pub event_loop_lag_ms: Option<u64>,
+    /// Share of database connections in use (0-100)
+    pub db_pool_utilization_percent: Option<u64>,
+    /// Redis round-trip time of the latest sample in milliseconds
+    pub redis_latency_ms: Option<u64>,
+    /// Low-priority requests shed since the server started
+    pub shed_low_priority: u64,
+    /// Normal-priority requests shed since the server started
+    pub shed_normal_priority: u64,
+}
+
+/// Dependency whose health the load monitor samples
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// Sample the dependency and record the result on the monitor
+    async fn sample(&self, monitor: &LoadMonitor);
+}
+
+#[async_trait]
+impl HealthProbe for DatabasePool {
+    async fn sample(&self, monitor: &LoadMonitor) {
+        let stats = self.get_statistics();
+        monitor.record_db_pool(stats.active_connections, stats.max_connections);
+    }
+}
+
+#[async_trait]
+impl HealthProbe for RedisClient {
+    async fn sample(&self, monitor: &LoadMonitor) {
+        let started = Instant::now();
+        if let Err(e) = self.health_check().await {
+            log::warn!("Redis health probe failed: {}", e);
+        }
+        // A failing Redis counts with the time it took to fail
+        monitor.record_redis_latency(started.elapsed());
+    }
+}
+
+/// Health signals and shed counters shared by the middleware and the admin endpoint
+pub struct LoadMonitor {
+    config: LoadSheddingConfig,
+    event_loop_lag_ms: AtomicU64,
+    db_pool_utilization_percent: AtomicU64,
+    redis_latency_ms: AtomicU64,
+    shed_low_priority: AtomicU64,
+    shed_normal_priority: AtomicU64,
+}
+
+impl LoadMonitor {
+    /// Create a monitor with no signals sampled yet
+    pub fn new(config: LoadSheddingConfig) -> Self {
+        Self {
+            config,
+            event_loop_lag_ms: AtomicU64::new(NOT_SAMPLED),
+            db_pool_utilization_percent: AtomicU64::new(NOT_SAMPLED),
+            redis_latency_ms: AtomicU64::new(NOT_SAMPLED),
+            shed_low_priority: AtomicU64::new(0),
+            shed_normal_priority: AtomicU64::new(0),
+        }
+    }
+
+    /// Record how late a timer of the event loop fired
+    pub fn record_event_loop_lag(&self, lag: Duration) {
+        self.event_loop_lag_ms.store(lag.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Record how many of the pool's connections are in use
+    pub fn record_db_pool(&self, in_use: u32, max_connections: u32) {
+        let percent = u64::from(in_use) * 100 / u64::from(max_connections.max(1));
+        self.db_pool_utilization_percent.store(percent.min(100), Ordering::Relaxed);
+    }
+
+    /// Record the round-trip time of a Redis command
+    pub fn record_redis_latency(&self, latency: Duration) {
+        self.redis_latency_ms.store(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// How overloaded the instance is, judged by the latest samples
+    pub fn level(&self) -> LoadLevel {
+        let lag = sampled(&self.event_loop_lag_ms);
+        let pool = sampled(&self.db_pool_utilization_percent);
+        let redis = sampled(&self.redis_latency_ms);
+        let max_lag = self.config.max_event_loop_lag_ms;
+        let max_pool = u64::from(self.config.max_db_pool_utilization_percent);
+        let max_redis = self.config.max_redis_latency_ms;
+
+        let exceeds = |value: Option<u64>, limit: u64| value.is_some_and(|value| value > limit);
+        if exceeds(lag, max_lag.saturating_mul(2))
+            || exceeds(redis, max_redis.saturating_mul(2))
+            || pool == Some(100)
+        {
+            LoadLevel::Critical
+        } else if exceeds(lag, max_lag) || exceeds(pool, max_pool) || exceeds(redis, max_redis) {
+            LoadLevel::Overloaded
+        } else {
+            LoadLevel::Normal
+        }
+    }
+
+    /// Priority of a request to a path
+    ///
+    /// Protected paths take precedence over low-priority paths.
+    pub fn priority(&self, path: &str) -> RequestPriority {
+        if matches_prefix(&self.config.protected_paths, path) {
+            RequestPriority::Protected
+        } else if matches_prefix(&self.config.low_priority_paths, path) {
+            RequestPriority::Low
+        } else {
+            RequestPriority::Normal
+        }
+    }
+
+    /// Decide whether to serve a request, counting it if it is shed
+    ///
+    /// # Returns
+    /// * `true` if the request should be rejected
+    pub fn should_shed(&self, priority: RequestPriority) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+
+        let shed = match priority {
+            RequestPriority::Protected => false,
+            RequestPriority::Normal => self.level() == LoadLevel::Critical,
+            RequestPriority::Low => self.level() >= LoadLevel::Overloaded,
+        };
+        if shed {
+            let counter = match priority {
+                RequestPriority::Low => &self.shed_low_priority,
+                _ => &self.shed_normal_priority,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        shed
+    }
+
+    /// Seconds clients are asked to wait before retrying
+    pub fn retry_after_seconds(&self) -> u64 {
+        self.config.retry_after_seconds
+    }
+
+    /// Latest health signals and shed counts
+    pub fn status(&self) -> LoadSheddingStatus {
+        LoadSheddingStatus {
+            enabled: self.config.enabled,
+            level: self.level(),
+            event_loop_lag_ms: sampled(&self.event_loop_lag_ms),
+            db_pool_utilization_percent: sampled(&self.db_pool_utilization_percent),
+            redis_latency_ms: sampled(&self.redis_latency_ms),
+            shed_low_priority: self.shed_low_priority.load(Ordering::Relaxed),
+            shed_normal_priority: self.shed_normal_priority.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Start sampling health signals as a background task
+    ///
+    /// The event loop lag is measured on the runtime this is called from,
+    /// as how much later than requested the sampling timer fires.
+    pub fn start_sampling(self: Arc<Self>, probes: Vec<Arc<dyn HealthProbe>>) {
+        if !self.config.enabled {
+            log::warn!("Load shedding is disabled");
+            return;
+        }
+
+        let interval = Duration::from_millis(self.config.sample_interval_ms.max(1));
+
+        tokio::spawn(async move {
+            log::info!(
+                "Load monitor started - will sample health every {} ms",
+                interval.as_millis()
+            );
+
+            let mut previous = LoadLevel::Normal;
+            loop {
+                let started = Instant::now();
+                tokio::time::sleep(interval).await;
+                self.record_event_loop_lag(started.elapsed().saturating_sub(interval));
+
+                for probe in &probes {
+                    probe.sample(&self).await;
+                }
+
+                let level = self.level();
+                if level != previous {
+                    let status = self.status();
+                    if level > previous {
+                        log::warn!("Load level raised to {:?}, shedding requests: {:?}", level, status);
+                    } else {
+                        log::info!("Load level lowered to {:?}: {:?}", level, status);
+                    }
+                    previous = level;
+                }
+            }
+        });
+    }
+}
+
+/// Value of a signal, `None` until it is sampled
+fn sampled(signal: &AtomicU64) -> Option<u64> {
+    match signal.load(Ordering::Relaxed) {
+        NOT_SAMPLED => None,
+        value => Some(value),
+    }
+}
+
+/// Whether a path equals or lies below one of the prefixes
+fn matches_prefix(prefixes: &[String], path: &str) -> bool {
+    prefixes.iter().any(|prefix| {
+        path.strip_prefix(prefix.trim_end_matches('/'))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// Load shedding middleware factory
+///
+/// Wrap the whole application with it, so shed requests are rejected
+/// before any other middleware does work for them.
+#[derive(Clone)]
+pub struct LoadShedding {
+    monitor: Arc<LoadMonitor>,
+}
+
+impl LoadShedding {
+    /// Shed requests while the monitor reports the instance overloaded
+    pub fn new(monitor: Arc<LoadMonitor>) -> Self {
+        Self { monitor }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for LoadShedding
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = LoadSheddingMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LoadSheddingMiddleware {
+            service: Rc::new(service),
+            monitor: self.monitor.clone(),
+        }))
+    }
+}
+
+/// Load shedding middleware service
+pub struct LoadSheddingMiddleware<S> {
+    service: Rc<S>,
+    monitor: Arc<LoadMonitor>,
+}
+
+impl<S, B> Service<ServiceRequest> for LoadSheddingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let priority = self.monitor.priority(req.path());
+
+        if self.monitor.should_shed(priority) {
+            log::debug!("Shed {:?}-priority request to {}", priority, req.path());
+
+            let lang = extract_language(req.request());
+            let (code, message) = match get_error_message("general", "service_overloaded", lang) {
+                Some((code, message, _)) => (code, message),
+                None => ("service_overloaded".to_string(), "service_overloaded".to_string()),
+            };
+            let response = HttpResponse::ServiceUnavailable()
+                .insert_header(("Retry-After", self.monitor.retry_after_seconds().to_string()))
+                .json(ErrorResponse::new(code, message));
+
+            return Box::pin(ready(Err(
+                InternalError::from_response("Service overloaded", response).into(),
+            )));
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { service.call(req).await })
+    }
+}
@@ -0,0 +1,112 @@
+//! Maintenance mode middleware
+//!
+//! While the platform is in maintenance, client requests are rejected with
+//! 503 Service Unavailable. Health checks keep answering so load balancers
+//! keep the instance in rotation, and administration endpoints keep
+//! working so administrators can switch maintenance mode off again:
+//!
+//! ```ignore
+//! let maintenance = Arc::new(MaintenanceState::new());
+//!
+//! App::new()
+//!     .wrap(Maintenance::new(maintenance.clone()))
+//! ```
+//!
+//! The state is switched by `MaintenanceModeToggled` cluster events, so
+//! every instance enters and leaves maintenance together.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    Error, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::extract_language;
+use crate::i18n::get_error_message;
+use crate::lifecycle::MaintenanceState;
+
+/// Path prefixes served during maintenance
+const EXEMPT_PATH_PREFIXES: &[&str] = &["/health", "/api/v1/admin"];
+
+/// Middleware rejecting client requests during maintenance
+pub struct Maintenance {
+    state: Arc<MaintenanceState>,
+}
+
+impl Maintenance {
+    /// Create the middleware over a shared maintenance state
+    pub fn new(state: Arc<MaintenanceState>) -> Self {
+        Self { state }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Maintenance
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MaintenanceMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(MaintenanceMiddleware {
+            service: Rc::new(service),
+            state: self.state.clone(),
+        }))
+    }
+}
+
+/// Maintenance mode middleware service
+pub struct MaintenanceMiddleware<S> {
+    service: Rc<S>,
+    state: Arc<MaintenanceState>,
+}
+
+impl<S, B> Service<ServiceRequest> for MaintenanceMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let exempt = EXEMPT_PATH_PREFIXES
+            .iter()
+            .any(|prefix| req.path().starts_with(prefix));
+
+        if !exempt && self.state.is_enabled() {
+            let lang = extract_language(req.request());
+            let (code, default_message) = match get_error_message("general", "maintenance_mode", lang) {
+                Some((code, message, _)) => (code, message),
+                None => ("maintenance_mode".to_string(), "maintenance_mode".to_string()),
+            };
+            let message = self.state.status().message.unwrap_or(default_message);
+            let response = HttpResponse::ServiceUnavailable().json(ErrorResponse::new(code, message));
+
+            return Box::pin(ready(Err(
+                InternalError::from_response("Maintenance mode", response).into(),
+            )));
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { service.call(req).await })
+    }
+}
@@ -1,6 +1,18 @@
+pub mod attack_guard;
 pub mod auth;
+pub mod canary;
+pub mod compat_json;
 pub mod cors;
 pub mod error_handler;
+pub mod ip_access;
+pub mod load_shedding;
+pub mod maintenance;
+pub mod mtls;
+pub mod proof_of_work;
 pub mod rate_limit;
+pub mod request_trace;
+pub mod scope;
 pub mod security;
+pub mod signed_url;
+pub mod tenant;
 
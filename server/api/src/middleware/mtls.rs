@@ -0,0 +1,220 @@
+//! Mutual TLS middleware for internal endpoints
+//!
+//! When the server runs with TLS and client verification enabled, clients
+//! may present a certificate signed by the configured CA. Certificates are
+//! optional at the handshake so public routes keep working; this middleware
+//! rejects requests to the configured internal paths (admin, metrics,
+//! webhooks) unless the connection presented a certificate whose SHA-256
+//! fingerprint maps to a known service identity.
+
+use actix_web::{
+    dev::{Extensions, Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorForbidden,
+    rt::net::TcpStream,
+    Error, HttpMessage,
+};
+use actix_tls::accept::rustls_0_23::TlsStream;
+use futures_util::future::LocalBoxFuture;
+use rustls::{
+    crypto::aws_lc_rs,
+    pki_types::{CertificateDer, PrivateKeyDer},
+    server::WebPkiClientVerifier,
+    RootCertStore, ServerConfig,
+};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+    any::Any,
+    fs::File,
+    future::{ready, Ready},
+    io::{self, BufReader},
+    rc::Rc,
+    sync::Arc,
+};
+
+use re_shared::config::server::TlsConfig;
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+
+/// DER encoding of the certificate the client presented during the handshake
+///
+/// Stored as connection data by [`extract_peer_certificate`].
+#[derive(Debug, Clone)]
+pub struct PeerCertificate(pub Vec<u8>);
+
+/// Request extension carrying the service identity of an authenticated client certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceIdentity(pub String);
+
+/// SHA-256 fingerprint of a DER encoded certificate as lower-case hex
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    hex::encode(Sha256::digest(der))
+}
+
+/// Store the client certificate of a TLS connection as connection data
+///
+/// Pass this to `HttpServer::on_connect`.
+pub fn extract_peer_certificate(connection: &dyn Any, data: &mut Extensions) {
+    let Some(stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else {
+        return;
+    };
+
+    let (_, session) = stream.get_ref();
+    if let Some(certificate) = session.peer_certificates().and_then(|certs| certs.first()) {
+        data.insert(PeerCertificate(certificate.to_vec()));
+    }
+}
+
+/// Build the rustls server configuration from the TLS settings
+///
+/// With client verification enabled, certificates signed by the CA at
+/// `ca_path` are verified but not required, so [`MutualTls`] can decide per
+/// route.
+pub fn load_rustls_config(tls: &TlsConfig) -> io::Result<ServerConfig> {
+    let provider = Arc::new(aws_lc_rs::default_provider());
+    let versions: &[&rustls::SupportedProtocolVersion] = match tls.min_version.as_str() {
+        "1.3" => &[&rustls::version::TLS13],
+        _ => &[&rustls::version::TLS13, &rustls::version::TLS12],
+    };
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(versions)
+        .map_err(invalid_data)?;
+
+    let builder = match (tls.verify_client, tls.ca_path.as_deref()) {
+        (true, Some(ca_path)) => {
+            let mut roots = RootCertStore::empty();
+            for certificate in read_certificates(ca_path)? {
+                roots.add(certificate).map_err(invalid_data)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(invalid_data)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        (true, None) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Client verification requires a CA certificate",
+            ));
+        }
+        (false, _) => builder.with_no_client_auth(),
+    };
+
+    builder
+        .with_single_cert(read_certificates(&tls.cert_path)?, read_private_key(&tls.key_path)?)
+        .map_err(invalid_data)
+}
+
+/// Read all certificates from a PEM file
+fn read_certificates(path: &str) -> io::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::certs(&mut reader).collect()
+}
+
+/// Read the first private key from a PEM file
+fn read_private_key(path: &str) -> io::Result<PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("No private key found in {}", path))
+    })
+}
+
+fn invalid_data<E: std::error::Error + Send + Sync + 'static>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Mutual TLS middleware factory
+pub struct MutualTls {
+    config: Arc<TlsConfig>,
+}
+
+impl MutualTls {
+    /// Create the middleware from the server's TLS settings
+    pub fn new(config: Arc<TlsConfig>) -> Self {
+        Self { config }
+    }
+}
+
+impl<Svc, B> Transform<Svc, ServiceRequest> for MutualTls
+where
+    Svc: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    Svc::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MutualTlsMiddleware<Svc>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: Svc) -> Self::Future {
+        ready(Ok(MutualTlsMiddleware {
+            service: Rc::new(service),
+            config: self.config.clone(),
+        }))
+    }
+}
+
+/// Mutual TLS middleware service
+pub struct MutualTlsMiddleware<Svc> {
+    service: Rc<Svc>,
+    config: Arc<TlsConfig>,
+}
+
+impl<Svc, B> Service<ServiceRequest> for MutualTlsMiddleware<Svc>
+where
+    Svc: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    Svc::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let config = self.config.clone();
+
+        Box::pin(async move {
+            if config.requires_client_cert(req.path()) {
+                let identity = req
+                    .conn_data::<PeerCertificate>()
+                    .map(|certificate| certificate_fingerprint(&certificate.0))
+                    .and_then(|fingerprint| config.identity_for(&fingerprint).map(str::to_string));
+
+                match identity {
+                    Some(identity) => {
+                        req.extensions_mut().insert(ServiceIdentity(identity));
+                    }
+                    None => {
+                        log::warn!("Rejected request to {} without a known client certificate", req.path());
+
+                        let message = match extract_language(req.request()) {
+                            Language::English => "A trusted client certificate is required",
+                            Language::Chinese => "需要受信任的客户端证书",
+                        };
+                        let response = ErrorResponse::new(
+                            "client_certificate_required".to_string(),
+                            message.to_string(),
+                        );
+
+                        return Err(ErrorForbidden(json!({
+                            "error": response.error,
+                            "message": response.message,
+                            "timestamp": response.timestamp
+                        })));
+                    }
+                }
+            }
+
+            service.call(req).await
+        })
+    }
+}
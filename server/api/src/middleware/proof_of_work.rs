@@ -0,0 +1,173 @@
+//! Proof-of-work middleware
+//!
+//! This module rejects requests that do not carry a solved proof-of-work
+//! challenge in the `X-Pow-Nonce` and `X-Pow-Solution` headers. Wrap only
+//! the send-code resource with it, and register it inside the IP access
+//! control middleware so allowlisted networks skip the challenge:
+//!
+//! ```ignore
+//! web::resource("/send-code")
+//!     .wrap(ProofOfWork::new(proof_of_work_service))
+//!     .route(web::post().to(send_code))
+//! ```
+//!
+//! Nothing is checked while challenges are disabled for the environment.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::{ErrorForbidden, ErrorServiceUnavailable},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+};
+
+use re_core::services::auth::{ProofOfWorkDecision, ProofOfWorkService, ProofOfWorkStoreTrait};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::middleware::ip_access::IpAllowlisted;
+use crate::middleware::rate_limit::get_client_ip;
+
+/// Header carrying the nonce of a solved challenge
+pub const POW_NONCE_HEADER: &str = "X-Pow-Nonce";
+
+/// Header carrying the solution of a challenge
+pub const POW_SOLUTION_HEADER: &str = "X-Pow-Solution";
+
+/// Proof-of-work middleware factory
+pub struct ProofOfWork<P>
+where
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    service: Arc<ProofOfWorkService<P>>,
+}
+
+impl<P> ProofOfWork<P>
+where
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    /// Create proof-of-work protection backed by the challenge service
+    pub fn new(service: Arc<ProofOfWorkService<P>>) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B, P> Transform<S, ServiceRequest> for ProofOfWork<P>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ProofOfWorkMiddleware<S, P>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ProofOfWorkMiddleware {
+            service: Rc::new(service),
+            proof_of_work: self.service.clone(),
+        }))
+    }
+}
+
+/// Proof-of-work middleware service
+pub struct ProofOfWorkMiddleware<S, P>
+where
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    service: Rc<S>,
+    proof_of_work: Arc<ProofOfWorkService<P>>,
+}
+
+impl<S, B, P> Service<ServiceRequest> for ProofOfWorkMiddleware<S, P>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let proof_of_work = self.proof_of_work.clone();
+
+        Box::pin(async move {
+            if !proof_of_work.is_enabled() || req.extensions().get::<IpAllowlisted>().is_some() {
+                return service.call(req).await;
+            }
+
+            let lang = extract_language(req.request());
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+
+            let (Some(nonce), Some(solution)) = (header(POW_NONCE_HEADER), header(POW_SOLUTION_HEADER)) else {
+                return Err(ErrorForbidden(rejection_body(
+                    "proof_of_work_required",
+                    lang,
+                    "Please solve a challenge before requesting a code",
+                    "请先完成验证挑战再获取验证码",
+                )));
+            };
+
+            match proof_of_work.verify(&nonce, &solution).await {
+                Ok(decision) if decision.is_allowed() => {}
+                Ok(decision) => {
+                    if decision == ProofOfWorkDecision::InsufficientWork {
+                        log::warn!("Rejected insufficient proof of work from {}", get_client_ip(&req));
+                    }
+                    return Err(ErrorForbidden(rejection_body(
+                        "proof_of_work_invalid",
+                        lang,
+                        "The challenge is invalid or has expired. Please request a new one",
+                        "验证挑战无效或已过期，请重新获取",
+                    )));
+                }
+                Err(error) => {
+                    log::error!("Failed to verify proof of work: {}", error);
+                    return Err(ErrorServiceUnavailable(rejection_body(
+                        "service_unavailable",
+                        lang,
+                        "The service is temporarily unavailable. Please try again later",
+                        "服务暂时不可用，请稍后重试",
+                    )));
+                }
+            }
+
+            service.call(req).await
+        })
+    }
+}
+
+/// Build a localized rejection body
+fn rejection_body(error: &str, lang: Language, message_en: &str, message_zh: &str) -> serde_json::Value {
+    let message = match lang {
+        Language::English => message_en,
+        Language::Chinese => message_zh,
+    };
+    let response = ErrorResponse::new(error.to_string(), message.to_string());
+
+    json!({
+        "error": response.error,
+        "message": response.message,
+        "timestamp": response.timestamp
+    })
+}
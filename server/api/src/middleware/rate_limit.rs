@@ -20,6 +20,7 @@ use std::{
 };
 
 use crate::dto::error::ErrorResponse;
+use crate::middleware::security::{resolve_client_ip, trusted_proxies_from_env};
 
 /// Rate limit configuration for different actions
 #[derive(Debug, Clone)]
@@ -32,6 +33,9 @@ pub struct RateLimitConfig {
     pub api_calls_per_ip_per_minute: u32,
     /// Lock duration for phone numbers after max verification failures (in seconds)
     pub phone_lock_duration_seconds: u64,
+    /// Namespace put in front of every key as `{key_prefix}:`, as with
+    /// `CacheConfig::key_prefix`
+    pub key_prefix: Option<String>,
 }
 
 impl Default for RateLimitConfig {
@@ -41,6 +45,17 @@ impl Default for RateLimitConfig {
             verification_attempts_per_code: 3,
             api_calls_per_ip_per_minute: 60,
             phone_lock_duration_seconds: 1800, // 30 minutes
+            key_prefix: None,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Key as stored in Redis, under the key prefix
+    fn key(&self, key: &str) -> String {
+        match &self.key_prefix {
+            Some(prefix) => format!("{}:{}", prefix, key),
+            None => key.to_string(),
         }
     }
 }
@@ -79,7 +94,7 @@ impl RateLimiter {
         window_seconds: u64,
     ) -> Result<RateLimitStatus, redis::RedisError> {
         let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
-        let key = format!("rate_limit:{}:{}", action, identifier);
+        let key = self.config.key(&format!("rate_limit:{}:{}", action, identifier));
 
         // Get current count
         let count: Option<u32> = conn.get(&key).await?;
@@ -112,7 +127,7 @@ impl RateLimiter {
     /// Check if a phone number is temporarily locked
     async fn is_phone_locked(&self, phone: &str) -> Result<bool, redis::RedisError> {
         let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
-        let key = format!("phone_lock:{}", phone);
+        let key = self.config.key(&format!("phone_lock:{}", phone));
         let locked: bool = conn.exists(&key).await?;
         Ok(locked)
     }
@@ -120,7 +135,7 @@ impl RateLimiter {
     /// Lock a phone number temporarily
     async fn lock_phone(&self, phone: &str) -> Result<(), redis::RedisError> {
         let mut conn = self.redis_client.get_multiplexed_async_connection().await?;
-        let key = format!("phone_lock:{}", phone);
+        let key = self.config.key(&format!("phone_lock:{}", phone));
         conn.set_ex::<_, _, ()>(&key, "locked", self.config.phone_lock_duration_seconds).await?;
         Ok(())
     }
@@ -239,7 +254,7 @@ async fn check_sms_rate_limit(
         })?;
 
     // Check if phone is locked
-    let lock_key = format!("phone_lock:{}", phone);
+    let lock_key = config.key(&format!("phone_lock:{}", phone));
     let is_locked: bool = conn.exists(&lock_key).await.map_err(|e| {
         log::error!("Redis error checking phone lock: {:?}", e);
         ErrorResponse::new(
@@ -261,7 +276,7 @@ async fn check_sms_rate_limit(
     }
 
     // Check SMS rate limit
-    let key = format!("sms_limit:{}", phone);
+    let key = config.key(&format!("sms_limit:{}", phone));
     let count: Option<u32> = conn.get(&key).await.map_err(|e| {
         log::error!("Redis error getting SMS count: {:?}", e);
         ErrorResponse::new(
@@ -328,7 +343,7 @@ async fn check_verification_rate_limit(
         })?;
 
     // Check if phone is locked
-    let lock_key = format!("phone_lock:{}", phone);
+    let lock_key = config.key(&format!("phone_lock:{}", phone));
     let is_locked: bool = conn.exists(&lock_key).await.map_err(|e| {
         log::error!("Redis error checking phone lock: {:?}", e);
         ErrorResponse::new(
@@ -350,7 +365,7 @@ async fn check_verification_rate_limit(
     }
 
     // Check verification attempts
-    let key = format!("verify_attempts:{}", phone);
+    let key = config.key(&format!("verify_attempts:{}", phone));
     let count: Option<u32> = conn.get(&key).await.map_err(|e| {
         log::error!("Redis error getting verification count: {:?}", e);
         ErrorResponse::new(
@@ -425,7 +440,7 @@ async fn check_api_rate_limit(
             )
         })?;
 
-    let key = format!("api_limit:{}", ip);
+    let key = config.key(&format!("api_limit:{}", ip));
     let count: Option<u32> = conn.get(&key).await.map_err(|e| {
         log::error!("Redis error getting API count: {:?}", e);
         ErrorResponse::new(
@@ -483,27 +498,8 @@ async fn extract_phone_from_request(_req: &ServiceRequest) -> Result<String, Err
 }
 
 /// Get client IP address from request
-fn get_client_ip(req: &ServiceRequest) -> String {
-    // Try to get IP from X-Forwarded-For header (for reverse proxy scenarios)
-    if let Some(forwarded_for) = req.headers().get("X-Forwarded-For") {
-        if let Ok(forwarded_str) = forwarded_for.to_str() {
-            // Take the first IP from the comma-separated list
-            if let Some(ip) = forwarded_str.split(',').next() {
-                return ip.trim().to_string();
-            }
-        }
-    }
-
-    // Try to get IP from X-Real-IP header
-    if let Some(real_ip) = req.headers().get("X-Real-IP") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            return ip_str.to_string();
-        }
-    }
-
-    // Fall back to connection info
-    req.connection_info()
-        .peer_addr()
-        .unwrap_or("unknown")
-        .to_string()
+///
+/// Forwarding headers only count behind a proxy listed in `TRUSTED_PROXIES`.
+pub(crate) fn get_client_ip(req: &ServiceRequest) -> String {
+    resolve_client_ip(req, &trusted_proxies_from_env())
 }
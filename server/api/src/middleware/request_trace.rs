@@ -0,0 +1,106 @@
+//! Request trace middleware.
+//!
+//! Assigns every request a trace ID and makes it the trace context of the
+//! handler, so audit rows and SMS provider calls made while serving the
+//! request carry the same ID:
+//! - A well-formed `X-Request-ID` header from the client or an upstream
+//!   proxy is kept; anything else is replaced with a new ID
+//! - The ID is stored in the request extensions for error responses
+//! - The ID is echoed in the `X-Request-ID` response header
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use re_core::services::trace;
+
+/// Header carrying the trace ID
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Request trace middleware factory
+#[derive(Default)]
+pub struct RequestTrace;
+
+impl RequestTrace {
+    /// Creates the request trace middleware
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTrace
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestTraceService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTraceService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Request trace middleware service implementation
+pub struct RequestTraceService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTraceService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let trace_id = request_trace_id(&req);
+
+        // Stored as a plain String, where the error handlers look for it
+        req.extensions_mut().insert(trace_id.clone());
+
+        Box::pin(trace::scope(trace_id.clone(), async move {
+            let mut res = service.call(req).await?;
+
+            if let Ok(value) = HeaderValue::from_str(&trace_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+
+            Ok(res)
+        }))
+    }
+}
+
+/// Trace ID of a request: the client's `X-Request-ID` if well-formed,
+/// otherwise a newly generated one
+pub fn request_trace_id(req: &ServiceRequest) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| trace::is_valid_trace_id(value))
+        .map(String::from)
+        .unwrap_or_else(trace::new_trace_id)
+}
@@ -0,0 +1,105 @@
+//! Token scope guard middleware
+//!
+//! This module rejects requests whose access token does not grant the
+//! scope a group of routes requires, for example:
+//!
+//! ```ignore
+//! web::scope("/orders")
+//!     .wrap(RequireScope("orders:write"))
+//!     .wrap(JwtAuth::new())
+//! ```
+//!
+//! The guard reads the authentication context injected by `JwtAuth`, so
+//! `JwtAuth` must be registered after it (actix runs the last `wrap` first).
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::{ErrorForbidden, ErrorUnauthorized},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::middleware::auth::AuthContext;
+
+/// Scope guard middleware factory
+///
+/// Wraps routes that require the access token to grant a scope.
+#[derive(Debug, Clone, Copy)]
+pub struct RequireScope(pub &'static str);
+
+impl<S, B> Transform<S, ServiceRequest> for RequireScope
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequireScopeMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireScopeMiddleware {
+            service: Rc::new(service),
+            scope: self.0,
+        }))
+    }
+}
+
+/// Scope guard middleware service
+pub struct RequireScopeMiddleware<S> {
+    service: Rc<S>,
+    scope: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireScopeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let scope = self.scope;
+
+        Box::pin(async move {
+            let granted = match req.extensions().get::<AuthContext>() {
+                Some(auth) => auth.has_scope(scope),
+                None => return Err(ErrorUnauthorized("Authentication required")),
+            };
+
+            if !granted {
+                let message = match extract_language(req.request()) {
+                    Language::English => "Your access token does not permit this action",
+                    Language::Chinese => "您的访问令牌无权执行此操作",
+                };
+                let response = ErrorResponse::new("insufficient_scope".to_string(), message.to_string());
+
+                return Err(ErrorForbidden(json!({
+                    "error": response.error,
+                    "message": response.message,
+                    "required_scope": scope,
+                    "timestamp": response.timestamp
+                })));
+            }
+
+            service.call(req).await
+        })
+    }
+}
@@ -37,12 +37,7 @@ impl SecurityMiddleware {
         let enforce_https = environment == "production";
         let add_security_headers = environment == "production";
 
-        let trusted_proxies = env::var("TRUSTED_PROXIES")
-            .unwrap_or_default()
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let trusted_proxies = trusted_proxies_from_env();
 
         log::info!(
             "Security middleware configured: enforce_https={}, add_headers={}, trusted_proxies={:?}",
@@ -202,6 +197,48 @@ fn is_secure_request(req: &ServiceRequest, trusted_proxies: &[String]) -> bool {
     false
 }
 
+/// Reads the trusted proxy list from the comma-separated `TRUSTED_PROXIES`
+pub(crate) fn trusted_proxies_from_env() -> Vec<String> {
+    env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Resolves the client IP address of a request
+///
+/// `X-Forwarded-For` and `X-Real-IP` are only honoured when the connection
+/// comes from a trusted proxy; anyone else could put any address there.
+/// `X-Forwarded-For` is walked from the right, skipping further trusted
+/// proxies, so a client cannot prepend an address of its choosing either.
+pub(crate) fn resolve_client_ip(req: &ServiceRequest, trusted_proxies: &[String]) -> String {
+    let conn_info = req.connection_info();
+    let peer_addr = conn_info.peer_addr().unwrap_or("unknown");
+
+    if !is_trusted_proxy(peer_addr, trusted_proxies) {
+        return peer_addr.to_string();
+    }
+
+    if let Some(forwarded_for) = req.headers().get("X-Forwarded-For").and_then(|v| v.to_str().ok()) {
+        let client = forwarded_for
+            .rsplit(',')
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .find(|ip| !is_trusted_proxy(ip, trusted_proxies));
+        if let Some(ip) = client {
+            return ip.to_string();
+        }
+    }
+
+    if let Some(real_ip) = req.headers().get("X-Real-IP").and_then(|v| v.to_str().ok()) {
+        return real_ip.trim().to_string();
+    }
+
+    peer_addr.to_string()
+}
+
 /// Checks if the given IP address is in the trusted proxy list
 fn is_trusted_proxy(peer_addr: &str, trusted_proxies: &[String]) -> bool {
     // Extract IP from peer address (might be in format "ip:port")
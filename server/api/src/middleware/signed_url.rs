@@ -0,0 +1,75 @@
+//! Signed URL extractor
+//!
+//! Handlers serving links minted by `SignedUrlService` (photo downloads,
+//! invoice PDFs, verification deep links) take a [`SignedRequest`]
+//! argument instead of requiring an access token:
+//!
+//! ```ignore
+//! async fn download_invoice(signed: SignedRequest, path: web::Path<Uuid>) -> HttpResponse {
+//!     // `signed.0.expires_at` is when the link stops working
+//! }
+//!
+//! App::new()
+//!     .app_data(web::Data::new(signed_url_service))
+//!     .route("/api/v1/invoices/{id}.pdf", web::get().to(download_invoice))
+//! ```
+//!
+//! The extractor verifies the path and query exactly as received, so a
+//! link only opens the resource it was signed for.
+
+use actix_web::{error::InternalError, web, Error, FromRequest, HttpRequest, HttpResponse};
+use std::future::{ready, Ready};
+
+use re_core::errors::{DomainError, TokenError};
+use re_core::services::signed_url::{SignedUrlService, VerifiedUrl};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::{extract_language, Language};
+use crate::i18n::get_error_message;
+
+/// A request made through a valid, unexpired signed link
+#[derive(Debug, Clone)]
+pub struct SignedRequest(pub VerifiedUrl);
+
+impl FromRequest for SignedRequest {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        let Some(service) = req.app_data::<web::Data<SignedUrlService>>() else {
+            log::error!("SignedUrlService is not registered as app data");
+            return ready(Err(actix_web::error::ErrorInternalServerError(
+                "Signed links are not configured",
+            )));
+        };
+
+        let path_and_query = req.uri().path_and_query().map_or("", |pq| pq.as_str());
+        let result = service
+            .verify(path_and_query)
+            .map(SignedRequest)
+            .map_err(|error| {
+                let response = signed_url_error_response(&error, extract_language(req));
+                InternalError::from_response(error, response).into()
+            });
+
+        ready(result)
+    }
+}
+
+/// Build the localized 403 response for a rejected signed link
+///
+/// The error code is `signed_url_expired` for expired links and
+/// `signed_url_invalid` for anything else, so the app knows whether to
+/// request a fresh link.
+pub fn signed_url_error_response(error: &DomainError, lang: Language) -> HttpResponse {
+    let key = match error {
+        DomainError::Token(TokenError::TokenExpired) => "signed_url_expired",
+        _ => "signed_url_invalid",
+    };
+
+    let (code, message) = match get_error_message("general", key, lang) {
+        Some((code, message, _)) => (code, message),
+        None => (key.to_string(), key.to_string()),
+    };
+    HttpResponse::Forbidden().json(ErrorResponse::new(code, message))
+}
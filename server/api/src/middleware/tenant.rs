@@ -0,0 +1,190 @@
+//! Tenant resolution middleware.
+//!
+//! Resolves the white-labeled marketplace (tenant) a request is for and makes
+//! it the tenant context of the handler, so tenant-scoped repositories read
+//! and write the rows of that tenant only:
+//! - With the header enabled, `X-Tenant-ID` selects one of the configured
+//!   tenants; unknown or malformed tenants are rejected with 400
+//! - Otherwise the tenant the request's host is mapped to is used
+//! - Requests to unmapped hosts are served for the default tenant
+//!
+//! The tenant is also stored in the request extensions for handlers and for
+//! `JwtAuth`, which refuses access tokens issued by another tenant.
+
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorBadRequest,
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use re_core::services::tenant::{self, TenantId};
+use re_shared::config::TenancyConfig;
+
+use crate::dto::error::ErrorResponse;
+
+/// Header a trusted proxy selects the tenant with
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Tenants of a deployment and the hosts they are served at
+#[derive(Debug, Clone, Default)]
+pub struct TenantResolver {
+    hosts: HashMap<String, TenantId>,
+    header_enabled: bool,
+}
+
+impl TenantResolver {
+    /// Create a resolver from the tenancy configuration; mappings to
+    /// malformed tenant IDs are ignored
+    pub fn new(config: &TenancyConfig) -> Self {
+        let hosts = config
+            .hosts
+            .iter()
+            .filter_map(|(host, tenant)| Some((host.to_lowercase(), TenantId::parse(tenant)?)))
+            .collect();
+
+        Self {
+            hosts,
+            header_enabled: config.header_enabled,
+        }
+    }
+
+    /// Whether a tenant is served by this deployment
+    pub fn is_known(&self, tenant: &TenantId) -> bool {
+        tenant.is_default() || self.hosts.values().any(|known| known == tenant)
+    }
+
+    /// Every tenant served by this deployment, the default tenant first
+    ///
+    /// Background jobs run once for each of them.
+    pub fn tenants(&self) -> Vec<TenantId> {
+        let mut tenants: Vec<TenantId> = self
+            .hosts
+            .values()
+            .filter(|tenant| !tenant.is_default())
+            .cloned()
+            .collect();
+        tenants.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        tenants.dedup();
+        tenants.insert(0, TenantId::default());
+        tenants
+    }
+
+    /// Tenant of a request
+    ///
+    /// # Returns
+    /// * `Ok(tenant)` - The tenant selected by header or host
+    /// * `Err(value)` - The `X-Tenant-ID` header names no configured tenant
+    pub fn resolve(&self, req: &ServiceRequest) -> Result<TenantId, String> {
+        if self.header_enabled {
+            if let Some(value) = req.headers().get(TENANT_HEADER) {
+                let value = value.to_str().unwrap_or_default();
+                return TenantId::parse(value)
+                    .filter(|tenant| self.is_known(tenant))
+                    .ok_or_else(|| value.to_string());
+            }
+        }
+
+        Ok(self.tenant_for_host(req.connection_info().host()))
+    }
+
+    /// Tenant a host, with or without port, is mapped to
+    pub fn tenant_for_host(&self, host: &str) -> TenantId {
+        let hostname = host
+            .rsplit_once(':')
+            .filter(|(_, port)| port.chars().all(|c| c.is_ascii_digit()))
+            .map_or(host, |(hostname, _)| hostname);
+
+        self.hosts
+            .get(&hostname.to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Tenant resolution middleware factory
+pub struct TenantResolution {
+    resolver: Rc<TenantResolver>,
+}
+
+impl TenantResolution {
+    /// Creates the tenant resolution middleware
+    pub fn new(config: &TenancyConfig) -> Self {
+        Self {
+            resolver: Rc::new(TenantResolver::new(config)),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for TenantResolution
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = TenantResolutionService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TenantResolutionService {
+            service: Rc::new(service),
+            resolver: Rc::clone(&self.resolver),
+        }))
+    }
+}
+
+/// Tenant resolution middleware service implementation
+pub struct TenantResolutionService<S> {
+    service: Rc<S>,
+    resolver: Rc<TenantResolver>,
+}
+
+impl<S, B> Service<ServiceRequest> for TenantResolutionService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        let tenant = match self.resolver.resolve(&req) {
+            Ok(tenant) => tenant,
+            Err(value) => {
+                log::warn!("Rejected request for unknown tenant '{}'", value);
+
+                let response = ErrorResponse::new(
+                    "unknown_tenant".to_string(),
+                    "The requested marketplace does not exist".to_string(),
+                );
+                return Box::pin(ready(Err(ErrorBadRequest(json!({
+                    "error": response.error,
+                    "message": response.message,
+                    "timestamp": response.timestamp
+                })))));
+            }
+        };
+
+        req.extensions_mut().insert(tenant.clone());
+
+        Box::pin(tenant::scope(tenant, service.call(req)))
+    }
+}
@@ -0,0 +1,92 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::dto::admin::{AttributionCohortQuery, AttributionCohortResponse, AttributionReportResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::repositories::AttributionRepository;
+use re_core::services::attribution::AttributionService;
+
+use super::require_admin;
+
+/// Days reported when no start date is given
+const DEFAULT_REPORT_DAYS: i64 = 12 * 7;
+
+/// Application state for marketing attribution routes
+pub struct AttributionState<R>
+where
+    R: AttributionRepository + 'static,
+{
+    pub attribution_service: Arc<AttributionService<R>>,
+}
+
+/// Handler for GET /api/v1/admin/attribution/cohorts
+///
+/// Reports registrations and activations of weekly cohorts per campaign.
+/// Only users who consented to marketing when registering are counted.
+///
+/// # Query Parameters
+/// - `from`: First registration date included (default: 12 weeks before `to`)
+/// - `to`: Last registration date included (default: today)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "from": "2026-07-20",
+///     "to": "2026-10-11",
+///     "cohorts": [
+///         {
+///             "cohort_week": "2026-10-05",
+///             "utm_source": "google",
+///             "utm_medium": "cpc",
+///             "utm_campaign": "spring_kitchens",
+///             "registrations": 40,
+///             "activated": 26,
+///             "activation_rate": 0.65
+///         }
+///     ],
+///     "total_registrations": 40,
+///     "total_activated": 26
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The period is reversed or longer than a year
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn cohort_report<R>(
+    req: HttpRequest,
+    state: web::Data<AttributionState<R>>,
+    auth: AuthContext,
+    query: web::Query<AttributionCohortQuery>,
+) -> HttpResponse
+where
+    R: AttributionRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query.from.unwrap_or(to - Duration::days(DEFAULT_REPORT_DAYS - 1));
+
+    match state.attribution_service.cohort_report(from, to).await {
+        Ok(cohorts) => {
+            let cohorts: Vec<AttributionCohortResponse> = cohorts.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(AttributionReportResponse {
+                from,
+                to,
+                total_registrations: cohorts.iter().map(|cohort| cohort.registrations).sum(),
+                total_activated: cohorts.iter().map(|cohort| cohort.activated).sum(),
+                cohorts,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
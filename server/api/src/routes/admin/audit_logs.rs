@@ -0,0 +1,124 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::dto::admin::{AuditLogQuery, AuditLogResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::audit::{AuditEventType, AuditLogFilter};
+use re_core::errors::DomainError;
+use re_core::repositories::AuditLogRepository;
+use re_core::services::audit::AuditService;
+use re_shared::types::{DateRange, Pagination};
+
+use super::require_admin_role;
+
+/// Application state for audit log routes
+pub struct AuditLogState<R>
+where
+    R: AuditLogRepository + 'static,
+{
+    pub audit_service: Arc<AuditService<R>>,
+}
+
+/// Handler for GET /api/v1/admin/audit-logs
+///
+/// Searches the authentication audit log, newest first. All filters are
+/// optional and combined with AND.
+///
+/// # Query Parameters
+/// - `page`: Page number (default: 1)
+/// - `per_page`: Logs per page (default: 20, maximum: 100)
+/// - `user_id`: Only logs of this user
+/// - `phone_hash`: Only logs of this hashed phone number
+/// - `event_types`: Comma-separated event types, e.g. `LOGIN_FAILURE,ACCOUNT_LOCKED`
+/// - `ip_address`: Only logs from this IP address
+/// - `from`, `to`: RFC 3339 time range, both inclusive
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "data": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "event_type": "LOGIN_FAILURE",
+///             "user_id": null,
+///             "phone_masked": "****1234",
+///             "phone_hash": "3f2a9c...",
+///             "ip_address": "203.0.113.7",
+///             "success": false,
+///             "failure_reason": "Invalid verification code",
+///             "created_at": "2026-10-17T10:00:00Z",
+///             ...
+///         }
+///     ],
+///     "page": 1,
+///     "per_page": 20,
+///     "total": 1,
+///     "total_pages": 1,
+///     "has_next": false,
+///     "has_prev": false
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown event type, or `to` is before `from`
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `support` role
+pub async fn list_audit_logs<R>(
+    req: HttpRequest,
+    state: web::Data<AuditLogState<R>>,
+    auth: AuthContext,
+    query: web::Query<AuditLogQuery>,
+) -> HttpResponse
+where
+    R: AuditLogRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Support) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let query = query.into_inner();
+    let event_types = match parse_event_types(query.event_types.as_deref()) {
+        Ok(event_types) => event_types,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    let filter = AuditLogFilter {
+        user_id: query.user_id,
+        phone_hash: query.phone_hash,
+        event_types,
+        ip_address: query.ip_address,
+        date_range: DateRange::new(query.from, query.to),
+    };
+    let defaults = Pagination::default();
+    let pagination = Pagination::new(
+        query.page.unwrap_or(defaults.page),
+        query.per_page.unwrap_or(defaults.per_page),
+    );
+
+    match state.audit_service.query_logs(filter, pagination).await {
+        Ok(page) => HttpResponse::Ok().json(page.map(AuditLogResponse::from)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Parse a comma-separated list of event types
+fn parse_event_types(value: Option<&str>) -> Result<Vec<AuditEventType>, DomainError> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            AuditEventType::from_str(&name.to_ascii_uppercase()).ok_or_else(|| DomainError::Validation {
+                message: format!("Unknown audit event type: {}", name),
+            })
+        })
+        .collect()
+}
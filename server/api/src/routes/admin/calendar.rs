@@ -0,0 +1,191 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{
+    ClosureListResponse, ClosureQuery, ClosureResponse, CreateClosureRequest, DeleteClosureResponse,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::repositories::ClosureDateRepository;
+use re_core::services::calendar::BusinessCalendar;
+
+use super::require_admin;
+
+/// Application state for business calendar administration routes
+pub struct CalendarState<R>
+where
+    R: ClosureDateRepository + 'static,
+{
+    pub business_calendar: Arc<BusinessCalendar<R>>,
+}
+
+/// Handler for GET /api/v1/admin/calendar/{market}/closures
+///
+/// Lists ad-hoc closure dates for a market. Public holidays from
+/// configuration are not included.
+///
+/// # Query Parameters
+/// - `from`: First date of the range (default: today)
+/// - `to`: Last date of the range (default: one year after `from`)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "closures": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "market": "AU",
+///             "date": "2025-12-24",
+///             "reason": "Platform maintenance",
+///             "created_by": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///             "created_at": "2025-08-14T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unsupported market
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_closures<R>(
+    req: HttpRequest,
+    state: web::Data<CalendarState<R>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    query: web::Query<ClosureQuery>,
+) -> HttpResponse
+where
+    R: ClosureDateRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let from = query.from.unwrap_or_else(|| Utc::now().date_naive());
+    let to = query.to.unwrap_or(from + Duration::days(365));
+
+    match state.business_calendar.list_closures(&path, from, to).await {
+        Ok(closures) => {
+            let closures: Vec<ClosureResponse> = closures.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(ClosureListResponse {
+                total: closures.len(),
+                closures,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/calendar/{market}/closures
+///
+/// Adds an ad-hoc closure date to a market. The date is treated as a
+/// non-business day by SLA timers, slot generation and surge pricing.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "date": "2025-12-24",
+///     "reason": "Platform maintenance"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The created closure, in the same format as the list endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: Unsupported market, past date, empty reason or duplicate closure
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn create_closure<R>(
+    req: HttpRequest,
+    state: web::Data<CalendarState<R>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<CreateClosureRequest>,
+) -> HttpResponse
+where
+    R: ClosureDateRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if request.validate().is_err() {
+        let error = DomainError::ValidationErr(DomainValidationError::InvalidFormat {
+            field: "reason".to_string(),
+        });
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .business_calendar
+        .add_closure(&path, request.date, &request.reason, Some(auth.user_id))
+        .await
+    {
+        Ok(closure) => HttpResponse::Created().json(ClosureResponse::from(closure)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/admin/calendar/closures/{id}
+///
+/// Removes an ad-hoc closure date.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "Closure removed successfully"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No closure exists with this ID
+pub async fn delete_closure<R>(
+    req: HttpRequest,
+    state: web::Data<CalendarState<R>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    R: ClosureDateRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.business_calendar.remove_closure(path.into_inner()).await {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "Closure removed successfully",
+                Language::Chinese => "休业日已成功删除",
+            };
+
+            HttpResponse::Ok().json(DeleteClosureResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
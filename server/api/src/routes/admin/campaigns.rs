@@ -0,0 +1,264 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{
+    CampaignDetailResponse, CampaignListResponse, CampaignQuery, CampaignResponse,
+    CreateCampaignRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{CampaignRepository, NotificationPreferencesRepository};
+use re_core::services::campaign::{CampaignService, NewCampaign, NotificationSenderTrait};
+
+use super::require_admin;
+
+/// Default number of campaigns returned by the list endpoint
+const DEFAULT_CAMPAIGN_LIMIT: usize = 50;
+
+/// Maximum number of campaigns returned by the list endpoint
+const MAX_CAMPAIGN_LIMIT: usize = 200;
+
+/// Application state for notification campaign routes
+pub struct CampaignState<C, P, N>
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    pub campaign_service: Arc<CampaignService<C, P, N>>,
+}
+
+/// Handler for POST /api/v1/admin/campaigns
+///
+/// Creates a notification campaign. Sending starts at `scheduled_at` and
+/// proceeds in throttled batches; users who opted out of marketing are
+/// skipped and users inside quiet hours receive it once they end.
+///
+/// With `"channel": "sms"` the campaign is an SMS broadcast: each recipient
+/// gets the body in their language as a text message, paced below the SMS
+/// provider's rate limit. SMS bodies are limited to 320 characters.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "name": "Spring promotion",
+///     "channel": "push",
+///     "audience": {
+///         "user_type": "worker",
+///         "country_codes": ["+61"],
+///         "active_within_days": 30
+///     },
+///     "content": {
+///         "en": { "title": "Spring offer", "body": "10% off kitchen renovations" },
+///         "zh": { "title": "春季优惠", "body": "厨房翻新九折" }
+///     },
+///     "default_language": "en",
+///     "scheduled_at": "2025-09-01T00:00:00Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The created campaign, in the same format as the detail endpoint without `stats`
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid content, a start time in the past, or an SMS
+///   campaign when SMS sending is not configured
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn create_campaign<C, P, N>(
+    req: HttpRequest,
+    state: web::Data<CampaignState<C, P, N>>,
+    auth: AuthContext,
+    request: web::Json<CreateCampaignRequest>,
+) -> HttpResponse
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    let new_campaign = NewCampaign {
+        name: request.name,
+        channel: request.channel,
+        audience: request.audience,
+        content: request.content,
+        default_language: request.default_language,
+        scheduled_at: request.scheduled_at,
+    };
+
+    match state
+        .campaign_service
+        .create_campaign(new_campaign, auth.user_id)
+        .await
+    {
+        Ok(campaign) => HttpResponse::Created().json(CampaignResponse::from(campaign)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/campaigns
+///
+/// Lists campaigns, most recently created first.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of campaigns (default: 50, maximum: 200)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "campaigns": [ { "id": "...", "name": "Spring promotion", "status": "sending", ... } ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_campaigns<C, P, N>(
+    req: HttpRequest,
+    state: web::Data<CampaignState<C, P, N>>,
+    auth: AuthContext,
+    query: web::Query<CampaignQuery>,
+) -> HttpResponse
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_CAMPAIGN_LIMIT)
+        .min(MAX_CAMPAIGN_LIMIT);
+
+    match state.campaign_service.list_campaigns(limit).await {
+        Ok(campaigns) => {
+            let campaigns: Vec<CampaignResponse> = campaigns.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(CampaignListResponse {
+                total: campaigns.len(),
+                campaigns,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/campaigns/{id}
+///
+/// Returns a campaign with its delivery and open statistics.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "name": "Spring promotion",
+///     "channel": "push",
+///     "status": "sending",
+///     "audience_size": 1200,
+///     ...
+///     "stats": {
+///         "pending": 400,
+///         "sent": 760,
+///         "failed": 12,
+///         "opted_out": 28,
+///         "opened": 190,
+///         "open_rate": 0.25
+///     }
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No campaign exists with this ID
+pub async fn get_campaign<C, P, N>(
+    req: HttpRequest,
+    state: web::Data<CampaignState<C, P, N>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.campaign_service.get_campaign(path.into_inner()).await {
+        Ok((campaign, stats)) => HttpResponse::Ok().json(CampaignDetailResponse {
+            campaign: campaign.into(),
+            stats: stats.into(),
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/campaigns/{id}/cancel
+///
+/// Cancels a scheduled or sending campaign. Messages already sent are not
+/// recalled.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The cancelled campaign
+///
+/// ## Errors
+/// - 400 Bad Request: The campaign already finished
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No campaign exists with this ID
+pub async fn cancel_campaign<C, P, N>(
+    req: HttpRequest,
+    state: web::Data<CampaignState<C, P, N>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.campaign_service.cancel_campaign(path.into_inner()).await {
+        Ok(campaign) => HttpResponse::Ok().json(CampaignResponse::from(campaign)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
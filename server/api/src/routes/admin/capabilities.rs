@@ -0,0 +1,55 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::admin::CapabilitiesResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_infra::capabilities::Capabilities;
+
+use super::require_admin_role;
+
+/// Handler for GET /api/v1/admin/capabilities
+///
+/// Reports the crate features the instance serving the request was built
+/// with and the capabilities its configuration enables. Instances missing
+/// a capability their environment requires do not start, so every
+/// required capability is available.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "environment": "production",
+///     "features": {
+///         "mysql": true,
+///         "sqlite": false,
+///         "redis_cache": true,
+///         "twilio_sms": true,
+///         "aws_sns": true,
+///         "vonage_sms": true,
+///         "mock_services": false
+///     },
+///     "sms_provider": "failover",
+///     "capabilities": ["database", "redis_cache", "real_sms"],
+///     "required": ["database", "redis_cache", "real_sms"]
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn get_capabilities(
+    req: HttpRequest,
+    state: web::Data<Capabilities>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    HttpResponse::Ok().json(CapabilitiesResponse::from(state.get_ref()))
+}
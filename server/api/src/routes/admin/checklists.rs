@@ -0,0 +1,121 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use validator::Validate;
+
+use crate::dto::completion::{
+    ChecklistTemplateQuery, ChecklistTemplateResponse, CreateChecklistTemplateRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::orders::OrderState;
+
+use re_core::errors::DomainError;
+use re_core::repositories::CompletionChecklistRepository;
+
+use super::require_admin;
+
+/// Handler for GET /api/v1/admin/checklists?category={category}
+///
+/// Returns the completion checklist workers of a job category fill in: the
+/// category's latest version, or the default checklist when the category
+/// has none. Without `category` the default checklist is returned.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "category": "bathroom",
+///     "version": 2,
+///     "items": [
+///         { "key": "grout_sealed", "label": "Grout sealed", "min_photos": 2 },
+///         { "key": "site_cleaned", "label": "Site cleaned", "min_photos": 1 }
+///     ],
+///     "created_by": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "created_at": "2026-10-16T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: Neither the category nor a default checklist is configured
+pub async fn get_checklist_template<C>(
+    req: HttpRequest,
+    state: web::Data<OrderState<C>>,
+    auth: AuthContext,
+    query: web::Query<ChecklistTemplateQuery>,
+) -> HttpResponse
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.completion_service.template_for(query.category.as_deref()).await {
+        Ok(template) => HttpResponse::Ok().json(ChecklistTemplateResponse::from(template)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/checklists
+///
+/// Creates the next checklist version for a job category, or the default
+/// checklist when no category is given. Checklists already submitted keep
+/// the version they were filled in from.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "category": "bathroom",
+///     "items": [
+///         { "key": "grout_sealed", "label": "Grout sealed", "min_photos": 2 },
+///         { "key": "site_cleaned", "label": "Site cleaned", "min_photos": 1 }
+///     ]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The created checklist, in the same format as the get endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: No items, an item without key or label, or a duplicate key
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn create_checklist_template<C>(
+    req: HttpRequest,
+    state: web::Data<OrderState<C>>,
+    auth: AuthContext,
+    request: web::Json<CreateChecklistTemplateRequest>,
+) -> HttpResponse
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    match state
+        .completion_service
+        .create_template(request.category.as_deref(), request.items, Some(auth.user_id))
+        .await
+    {
+        Ok(template) => HttpResponse::Created().json(ChecklistTemplateResponse::from(template)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
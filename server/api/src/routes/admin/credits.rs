@@ -0,0 +1,92 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{CreditGrantResponse, IssueCreditRequest};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::wallet::credits::WalletState;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{CreditWalletRepository, JournalRepository};
+
+use super::require_admin;
+
+/// Handler for POST /api/v1/admin/credits/{customer_id}
+///
+/// Grants store credit to a customer, e.g. a refund paid as credit or a
+/// promotion. The credit is spent automatically on the customer's next
+/// jobs before their payment method is charged.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "source": "promotion",
+///     "reference": "spring_2026:6ba7b810",
+///     "amount": 1000,
+///     "currency": "AUD",
+///     "expires_at": "2026-12-31T23:59:59Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "customer_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "source": "promotion",
+///     "reference": "spring_2026:6ba7b810",
+///     "amount": 1000,
+///     "remaining": 1000,
+///     "currency": "AUD",
+///     "expires_at": "2026-12-31T23:59:59Z",
+///     "created_at": "2026-10-16T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid amount, currency or reference, or an expiry in the past
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn issue_credit<W, J>(
+    req: HttpRequest,
+    state: web::Data<WalletState<W, J>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<IssueCreditRequest>,
+) -> HttpResponse
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .credit_wallet_service
+        .issue(
+            path.into_inner(),
+            request.source,
+            &request.reference,
+            request.amount,
+            &request.currency,
+            request.expires_at,
+        )
+        .await
+    {
+        Ok(grant) => HttpResponse::Created().json(CreditGrantResponse::from(grant)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,123 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use validator::Validate;
+
+use crate::dto::admin::{DrainStatusResponse, StartDrainRequest};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::lifecycle::{DrainState, DrainTrigger};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::DomainError;
+
+use super::require_admin_role;
+
+/// Handler for GET /api/v1/admin/drain
+///
+/// Shows whether the instance serving the request is draining.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "draining": true,
+///     "trigger": "admin",
+///     "since": "2025-08-14T10:00:00Z",
+///     "reason": "Rolling deploy 2025.08.14"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn get_drain_status(
+    req: HttpRequest,
+    state: web::Data<DrainState>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    HttpResponse::Ok().json(DrainStatusResponse::from(state.status()))
+}
+
+/// Handler for POST /api/v1/admin/drain
+///
+/// Takes the instance serving the request out of load balancer rotation:
+/// `/health/ready` reports not-ready while requests are still served. The
+/// instance keeps running until it is stopped or the drain is cancelled.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "reason": "Rolling deploy 2025.08.14"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The draining state, in the same format as the status endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: Empty or overly long reason
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn start_drain(
+    req: HttpRequest,
+    state: web::Data<DrainState>,
+    auth: AuthContext,
+    request: web::Json<StartDrainRequest>,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if state.start_draining(DrainTrigger::Admin, request.into_inner().reason) {
+        log::warn!("Instance draining at the request of administrator {}", auth.user_id);
+    }
+
+    HttpResponse::Ok().json(DrainStatusResponse::from(state.status()))
+}
+
+/// Handler for DELETE /api/v1/admin/drain
+///
+/// Puts the instance serving the request back in rotation.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The draining state, in the same format as the status endpoint
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 400 Bad Request: The instance is shutting down
+pub async fn cancel_drain(
+    req: HttpRequest,
+    state: web::Data<DrainState>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.cancel_draining() {
+        Ok(_) => HttpResponse::Ok().json(DrainStatusResponse::from(state.status())),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,165 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dto::admin::{CreateExportRequest, ExportJobResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::middleware::signed_url::SignedRequest;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::export_job::ExportKind;
+use re_core::repositories::ExportJobRepository;
+use re_core::services::export::ExportService;
+
+use super::{require_admin, require_admin_role};
+
+/// Application state for export routes
+pub struct ExportState<J>
+where
+    J: ExportJobRepository + 'static,
+{
+    pub export_service: Arc<ExportService<J>>,
+}
+
+/// Handler for POST /api/v1/admin/exports
+///
+/// Queues an export that is written in the background. Poll the returned
+/// job until its status is `completed`, then download the file from its
+/// `download_url`. Audit log exports require the support role.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "kind": "audit_logs",
+///     "from": "2026-09-01T00:00:00Z",
+///     "to": "2026-10-01T00:00:00Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (202 Accepted)
+/// The queued job, in the same format as the status endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: The kind cannot be exported or the range is reversed
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator with the required role
+pub async fn create_export<J>(
+    req: HttpRequest,
+    state: web::Data<ExportState<J>>,
+    auth: AuthContext,
+    body: web::Json<CreateExportRequest>,
+) -> HttpResponse
+where
+    J: ExportJobRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let authorized = match body.kind {
+        ExportKind::AuditLogs => require_admin_role(&auth, AdminRole::Support),
+        ExportKind::AttributionCohorts => require_admin(&auth),
+    };
+    if let Err(error) = authorized {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let body = body.into_inner();
+    match state.export_service.create(auth.user_id, body.kind, body.from, body.to).await {
+        Ok(job) => HttpResponse::Accepted().json(ExportJobResponse::new(job, None)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/exports/{id}
+///
+/// Reports the progress of an export requested by the administrator. Every
+/// poll of a completed export mints a new download link, so an expired
+/// link can be replaced by polling again.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "5f0c...",
+///     "kind": "audit_logs",
+///     "status": "completed",
+///     "from": "2026-09-01T00:00:00Z",
+///     "to": "2026-10-01T00:00:00Z",
+///     "rows_exported": 48210,
+///     "total_rows": 48210,
+///     "progress_percent": 100,
+///     "created_at": "2026-10-17T09:00:00Z",
+///     "finished_at": "2026-10-17T09:02:41Z",
+///     "download_url": "/api/v1/admin/exports/5f0c.../download?exp=1792234561&sig=4f1c...",
+///     "download_expires_at": "2026-10-17T09:17:41Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: The administrator requested no export with this ID
+pub async fn get_export<J>(
+    req: HttpRequest,
+    state: web::Data<ExportState<J>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    J: ExportJobRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.export_service.get(path.into_inner(), auth.user_id).await {
+        Ok(job) => match state.export_service.download_url(&job) {
+            Ok(download) => HttpResponse::Ok().json(ExportJobResponse::new(job, download)),
+            Err(error) => handle_domain_error_with_lang(&error, lang),
+        },
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/exports/{id}/download
+///
+/// Downloads the CSV file of a completed export. Access is granted by the
+/// signed link returned by the status endpoint instead of an access token,
+/// so the link can be opened directly in a browser until it expires.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The CSV file as an attachment
+///
+/// ## Errors
+/// - 403 Forbidden: The link is invalid or has expired
+/// - 404 Not Found: No completed export exists with this ID
+pub async fn download_export<J>(
+    req: HttpRequest,
+    state: web::Data<ExportState<J>>,
+    _signed: SignedRequest,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    J: ExportJobRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.export_service.download(path.into_inner()).await {
+        Ok((bytes, filename)) => HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            ))
+            .body(bytes),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,201 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{
+    CreateFeeScheduleRequest, FeeScheduleListResponse, FeeScheduleResponse, OrderFeeResponse,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{FeeScheduleRepository, OrderFeeRepository};
+use re_core::services::fee_schedule::{FeeScheduleService, NewFeeSchedule};
+
+use super::require_admin;
+
+/// Application state for fee schedule administration routes
+pub struct FeeState<S, O>
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    pub fee_schedule_service: Arc<FeeScheduleService<S, O>>,
+}
+
+/// Handler for GET /api/v1/admin/fees/{market}/schedules
+///
+/// Lists every fee schedule version of a market, newest first, including
+/// schedules that have not taken effect yet.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "schedules": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "market": "AU",
+///             "category": "plumbing",
+///             "version": 2,
+///             "commission_basis_points": 1200,
+///             "minimum_fee": 500,
+///             "currency": "AUD",
+///             "effective_from": "2026-11-01T00:00:00Z",
+///             "created_by": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///             "created_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_fee_schedules<S, O>(
+    req: HttpRequest,
+    state: web::Data<FeeState<S, O>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+) -> HttpResponse
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.fee_schedule_service.list_schedules(&path).await {
+        Ok(schedules) => {
+            let schedules: Vec<FeeScheduleResponse> = schedules.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(FeeScheduleListResponse {
+                total: schedules.len(),
+                schedules,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/fees/{market}/schedules
+///
+/// Creates the next fee schedule version for a market, or for one job
+/// category in it. Orders whose quote was accepted before the schedule
+/// takes effect keep the fee they were quoted.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "category": "plumbing",
+///     "commission_basis_points": 1200,
+///     "minimum_fee": 500,
+///     "currency": "AUD",
+///     "effective_from": "2026-11-01T00:00:00Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The created schedule, in the same format as the list endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid commission, minimum fee or currency, or an
+///   effective date in the past
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn create_fee_schedule<S, O>(
+    req: HttpRequest,
+    state: web::Data<FeeState<S, O>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<CreateFeeScheduleRequest>,
+) -> HttpResponse
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    let input = NewFeeSchedule {
+        market: path.into_inner(),
+        category: request.category,
+        commission_basis_points: request.commission_basis_points,
+        minimum_fee: request.minimum_fee,
+        currency: request.currency,
+        effective_from: request.effective_from,
+    };
+
+    match state.fee_schedule_service.create_schedule(input, Some(auth.user_id)).await {
+        Ok(schedule) => HttpResponse::Created().json(FeeScheduleResponse::from(schedule)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/fees/orders/{order_id}
+///
+/// Shows the fee fixed on an order when its quote was accepted, with the
+/// schedule version it came from.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "order_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+///     "schedule_id": "550e8400-e29b-41d4-a716-446655440000",
+///     "schedule_version": 1,
+///     "market": "AU",
+///     "category": null,
+///     "commission_basis_points": 1000,
+///     "minimum_fee": 0,
+///     "quote_amount": 25000,
+///     "fee_amount": 2500,
+///     "currency": "AUD",
+///     "accepted_at": "2026-10-16T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: The order's quote has not been accepted
+pub async fn get_order_fee<S, O>(
+    req: HttpRequest,
+    state: web::Data<FeeState<S, O>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.fee_schedule_service.order_fee(path.into_inner()).await {
+        Ok(fee) => HttpResponse::Ok().json(OrderFeeResponse::from(fee)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,215 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::dto::admin::{
+    CreateIpAccessEntryRequest, DeleteIpAccessEntryResponse, IpAccessEntryQuery,
+    IpAccessEntryResponse, IpAccessListResponse,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::services::auth::{IpAccessControlService, IpAccessList, IpAccessStoreTrait};
+
+use super::require_admin;
+
+/// Application state for IP access list administration routes
+pub struct IpAccessState<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    pub ip_access_control: Arc<IpAccessControlService<S>>,
+}
+
+/// Handler for GET /api/v1/admin/ip-access/{list}
+///
+/// Lists the active entries on the `allow` or `deny` list, newest first.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "list": "deny",
+///     "entries": [
+///         {
+///             "cidr": "203.0.113.0/24",
+///             "reason": "SMS pumping",
+///             "created_by": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///             "created_at": "2025-08-14T10:00:00Z",
+///             "expires_at": null
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown list
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_entries<S>(
+    req: HttpRequest,
+    state: web::Data<IpAccessState<S>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+) -> HttpResponse
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let list = match parse_list(&path) {
+        Ok(list) => list,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    match state.ip_access_control.list_entries(list).await {
+        Ok(entries) => {
+            let entries: Vec<IpAccessEntryResponse> = entries.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(IpAccessListResponse {
+                list: list.as_str().to_string(),
+                total: entries.len(),
+                entries,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/ip-access/{list}
+///
+/// Adds an IP address or CIDR range to the `allow` or `deny` list. The
+/// change applies on this instance immediately and on other instances
+/// after their next refresh.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "cidr": "203.0.113.0/24",
+///     "reason": "SMS pumping",
+///     "expires_in_seconds": 86400
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The stored entry, in the same format as the list endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown list, invalid or overly broad range, or empty reason
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn add_entry<S>(
+    req: HttpRequest,
+    state: web::Data<IpAccessState<S>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<CreateIpAccessEntryRequest>,
+) -> HttpResponse
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let list = match parse_list(&path) {
+        Ok(list) => list,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .ip_access_control
+        .add_entry(
+            list,
+            &request.cidr,
+            &request.reason,
+            Some(auth.user_id),
+            request.expires_in_seconds,
+        )
+        .await
+    {
+        Ok(entry) => HttpResponse::Created().json(IpAccessEntryResponse::from(entry)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/admin/ip-access/{list}?cidr=203.0.113.0/24
+///
+/// Removes an entry from the `allow` or `deny` list. The CIDR is passed as
+/// a query parameter because it contains a slash.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "Entry removed successfully"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown list or invalid range
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: The list has no such entry
+pub async fn remove_entry<S>(
+    req: HttpRequest,
+    state: web::Data<IpAccessState<S>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    query: web::Query<IpAccessEntryQuery>,
+) -> HttpResponse
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let list = match parse_list(&path) {
+        Ok(list) => list,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    match state.ip_access_control.remove_entry(list, &query.cidr).await {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "Entry removed successfully",
+                Language::Chinese => "条目已成功删除",
+            };
+
+            HttpResponse::Ok().json(DeleteIpAccessEntryResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Parse the `{list}` path segment
+fn parse_list(list: &str) -> Result<IpAccessList, DomainError> {
+    IpAccessList::from_str(list).ok_or_else(|| {
+        DomainError::ValidationErr(DomainValidationError::InvalidFormat {
+            field: "list".to_string(),
+        })
+    })
+}
@@ -0,0 +1,109 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::dto::admin::PublishLegalDocumentRequest;
+use crate::dto::legal::{LegalDocumentListResponse, LegalDocumentResponse};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::legal::parse_kind;
+
+use re_core::errors::DomainError;
+use re_core::repositories::LegalDocumentRepository;
+use re_core::services::legal_document::{LegalDocumentService, LegalTranslation, NewLegalDocumentVersion};
+
+use super::require_admin;
+
+/// Application state for legal document administration routes
+pub struct LegalDocumentAdminState<L>
+where
+    L: LegalDocumentRepository + 'static,
+{
+    pub legal_document_service: Arc<LegalDocumentService<L>>,
+}
+
+/// Handler for POST /api/v1/admin/legal/{doc}/versions
+///
+/// Publishes the next version of the terms of service (`terms`) or privacy
+/// policy (`privacy`) in all of its languages. Once it takes effect, users
+/// who accepted an earlier version are asked to accept it.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "market": "AU",
+///     "effective_from": "2026-11-01T00:00:00Z",
+///     "translations": [
+///         { "language": "en", "title": "Terms of Service", "body": "# Terms of Service\n..." },
+///         { "language": "zh", "title": "服务条款", "body": "# 服务条款\n..." }
+///     ]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The translations of the version, in the same format as
+/// `GET /api/v1/legal/pending`
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid market, language or text, a duplicated or
+///   missing English translation, or an effective date in the past
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: Unknown document
+pub async fn publish_legal_document<L>(
+    req: HttpRequest,
+    state: web::Data<LegalDocumentAdminState<L>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<PublishLegalDocumentRequest>,
+) -> HttpResponse
+where
+    L: LegalDocumentRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let kind = match parse_kind(&path) {
+        Ok(kind) => kind,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    let request = request.into_inner();
+    let input = NewLegalDocumentVersion {
+        kind,
+        market: request.market,
+        effective_from: request.effective_from,
+        translations: request
+            .translations
+            .into_iter()
+            .map(|translation| LegalTranslation {
+                language: translation.language,
+                title: translation.title,
+                body: translation.body,
+            })
+            .collect(),
+    };
+
+    match state.legal_document_service.publish(input, Some(auth.user_id)).await {
+        Ok(documents) => {
+            let documents: Vec<LegalDocumentResponse> = documents.into_iter().map(Into::into).collect();
+            HttpResponse::Created().json(LegalDocumentListResponse {
+                total: documents.len(),
+                documents,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
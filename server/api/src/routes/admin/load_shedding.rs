@@ -0,0 +1,47 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::admin::LoadSheddingResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::middleware::load_shedding::LoadMonitor;
+
+use re_core::domain::entities::admin::AdminRole;
+
+use super::require_admin_role;
+
+/// Handler for GET /api/v1/admin/load-shedding
+///
+/// Reports the latest health signals of the instance serving the request
+/// and how many requests it has shed since it started.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "enabled": true,
+///     "level": "overloaded",
+///     "event_loop_lag_ms": 140,
+///     "db_pool_utilization_percent": 62,
+///     "redis_latency_ms": 3,
+///     "shed_low_priority": 1280,
+///     "shed_normal_priority": 0
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn get_load_shedding(
+    req: HttpRequest,
+    state: web::Data<LoadMonitor>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    HttpResponse::Ok().json(LoadSheddingResponse::from(state.status()))
+}
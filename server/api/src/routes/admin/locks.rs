@@ -0,0 +1,146 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::admin::{AccountLockListResponse, AccountLockResponse, UnlockAccountResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::repositories::AuditLogRepository;
+use re_core::services::verification::CacheServiceTrait;
+
+use super::{require_admin, AdminState};
+
+/// Handler for GET /api/v1/admin/locks
+///
+/// Lists accounts that are currently locked, most recently locked first.
+/// Requires an administrator access token.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "locks": [
+///         {
+///             "id": "3f2a9c...",
+///             "user_id": null,
+///             "reason": "Too many failed authentication attempts",
+///             "locked_at": "2025-08-14T10:00:00Z",
+///             "unlock_at": "2025-08-14T11:00:00Z",
+///             "remaining_seconds": 3240
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_locks<C, A>(
+    req: HttpRequest,
+    state: web::Data<AdminState<C, A>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    C: CacheServiceTrait + 'static,
+    A: AuditLogRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.account_lock_service.list_locked_accounts().await {
+        Ok(locked) => {
+            let locks: Vec<AccountLockResponse> = locked.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(AccountLockListResponse {
+                total: locks.len(),
+                locks,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/locks/{id}/unlock
+///
+/// Clears the lock on an account and records the administrator in the
+/// audit log. `{id}` is the lock identifier returned by `GET /admin/locks`.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "Account unlocked successfully"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: The account is not locked
+pub async fn unlock<C, A>(
+    req: HttpRequest,
+    state: web::Data<AdminState<C, A>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+) -> HttpResponse
+where
+    C: CacheServiceTrait + 'static,
+    A: AuditLogRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let identifier = path.into_inner();
+    let client_ip = extract_client_ip(&req);
+
+    match state
+        .account_lock_service
+        .admin_unlock_account(&identifier, auth.user_id, client_ip)
+        .await
+    {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "Account unlocked successfully",
+                Language::Chinese => "账户已成功解锁",
+            };
+
+            HttpResponse::Ok().json(UnlockAccountResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Extract client IP address from request
+fn extract_client_ip(req: &HttpRequest) -> String {
+    // Try to get IP from X-Forwarded-For header (for reverse proxy scenarios)
+    if let Some(forwarded_for) = req.headers().get("X-Forwarded-For") {
+        if let Ok(forwarded_str) = forwarded_for.to_str() {
+            // Take the first IP from the comma-separated list
+            if let Some(ip) = forwarded_str.split(',').next() {
+                return ip.trim().to_string();
+            }
+        }
+    }
+
+    // Try to get IP from X-Real-IP header
+    if let Some(real_ip) = req.headers().get("X-Real-IP") {
+        if let Ok(ip_str) = real_ip.to_str() {
+            return ip_str.to_string();
+        }
+    }
+
+    // Fall back to connection info
+    req.connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string()
+}
@@ -0,0 +1,210 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Duration;
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::dto::admin::{
+    ClearLogLevelResponse, LogLevelOverrideResponse, LogLevelStatusResponse, SetLogLevelRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::DomainError;
+use re_core::services::cluster::{ClusterEvent, ClusterEventPublisherTrait};
+use re_core::services::log_level::{LogFilterTrait, LogLevelService};
+
+use super::require_admin_role;
+
+/// Application state for runtime log level routes
+pub struct LogLevelState<F>
+where
+    F: LogFilterTrait + 'static,
+{
+    pub log_level_service: Arc<LogLevelService<F>>,
+    /// Announces overrides to the other instances; without it only the
+    /// instance serving the request is changed
+    pub publisher: Option<Arc<dyn ClusterEventPublisherTrait>>,
+}
+
+/// Announce a change to the other instances, if a publisher is configured
+async fn announce<F>(state: &LogLevelState<F>, event: ClusterEvent) -> Result<(), DomainError>
+where
+    F: LogFilterTrait + 'static,
+{
+    match &state.publisher {
+        Some(publisher) => publisher
+            .publish(&event)
+            .await
+            .map_err(|message| DomainError::Internal { message }),
+        None => Ok(()),
+    }
+}
+
+/// Handler for GET /api/v1/admin/log-level
+///
+/// Shows the log filter in effect on the instance serving the request.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "filter": "info,re_core::services::auth=debug",
+///     "default_filter": "info",
+///     "active_override": {
+///         "level": null,
+///         "targets": { "re_core::services::auth": "debug" },
+///         "filter": "info,re_core::services::auth=debug",
+///         "set_by": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///         "created_at": "2025-08-14T10:00:00Z",
+///         "expires_at": "2025-08-14T10:15:00Z"
+///     }
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn get_log_level<F>(
+    req: HttpRequest,
+    state: web::Data<LogLevelState<F>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    F: LogFilterTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.log_level_service.status() {
+        Ok(status) => HttpResponse::Ok().json(LogLevelStatusResponse::from(status)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for PUT /api/v1/admin/log-level
+///
+/// Raises the global level and/or the level of selected targets until the
+/// override expires, replacing any active override. The configured filter
+/// is restored automatically afterwards. The override is applied on every
+/// instance.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "targets": { "re_core::services::auth": "debug" },
+///     "ttl_seconds": 900
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The applied override, in the same format as `active_override` above
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown level, invalid target, empty override or lifetime out of range
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 500 Internal Server Error: The override could not be announced to the other instances
+pub async fn set_log_level<F>(
+    req: HttpRequest,
+    state: web::Data<LogLevelState<F>>,
+    auth: AuthContext,
+    request: web::Json<SetLogLevelRequest>,
+) -> HttpResponse
+where
+    F: LogFilterTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.log_level_service.set_override(
+        request.level.as_deref(),
+        &request.targets,
+        request.ttl_seconds.map(Duration::seconds),
+        Some(auth.user_id),
+    ) {
+        Ok(active) => {
+            let event = ClusterEvent::LogLevelOverridden {
+                level: active.level.clone(),
+                targets: active.targets.clone(),
+                expires_at: active.expires_at,
+                set_by: active.set_by,
+            };
+            match announce(&state, event).await {
+                Ok(()) => HttpResponse::Ok().json(LogLevelOverrideResponse::from(active)),
+                Err(error) => handle_domain_error_with_lang(&error, lang),
+            }
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/admin/log-level
+///
+/// Ends the active override early and restores the configured filter on
+/// every instance.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "Log level restored",
+///     "cleared": true
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 500 Internal Server Error: The change could not be announced to the other instances
+pub async fn clear_log_level<F>(
+    req: HttpRequest,
+    state: web::Data<LogLevelState<F>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    F: LogFilterTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let result = match state.log_level_service.clear_override() {
+        Ok(cleared) => announce(&state, ClusterEvent::LogLevelCleared).await.map(|()| cleared),
+        Err(error) => Err(error),
+    };
+
+    match result {
+        Ok(cleared) => {
+            let message = match lang {
+                Language::English => "Log level restored",
+                Language::Chinese => "日志级别已恢复",
+            };
+
+            HttpResponse::Ok().json(ClearLogLevelResponse {
+                message: message.to_string(),
+                cleared,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
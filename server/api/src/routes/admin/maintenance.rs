@@ -0,0 +1,123 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::dto::admin::{MaintenanceStatusResponse, SetMaintenanceRequest};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::lifecycle::MaintenanceState;
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::DomainError;
+use re_core::services::cluster::{ClusterEvent, ClusterEventPublisherTrait};
+
+use super::require_admin_role;
+
+/// Application state for maintenance mode routes
+pub struct MaintenanceAdminState {
+    pub maintenance: Arc<MaintenanceState>,
+    /// Announces toggles to the other instances; without it only the
+    /// instance serving the request is switched
+    pub publisher: Option<Arc<dyn ClusterEventPublisherTrait>>,
+}
+
+/// Handler for GET /api/v1/admin/maintenance
+///
+/// Shows whether the platform is in maintenance.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "enabled": true,
+///     "message": "Back at 02:00 UTC",
+///     "since": "2025-08-14T01:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn get_maintenance(
+    req: HttpRequest,
+    state: web::Data<MaintenanceAdminState>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    HttpResponse::Ok().json(MaintenanceStatusResponse::from(state.maintenance.status()))
+}
+
+/// Handler for PUT /api/v1/admin/maintenance
+///
+/// Switches maintenance mode on or off on every instance. While it is on,
+/// requests other than health checks and administration are answered with
+/// 503 Service Unavailable.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "enabled": true,
+///     "message": "Back at 02:00 UTC"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The maintenance state, in the same format as the status endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: Empty or overly long message
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 500 Internal Server Error: The toggle could not be announced to the other instances
+pub async fn set_maintenance(
+    req: HttpRequest,
+    state: web::Data<MaintenanceAdminState>,
+    auth: AuthContext,
+    request: web::Json<SetMaintenanceRequest>,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    if let Some(publisher) = &state.publisher {
+        let event = ClusterEvent::MaintenanceModeToggled {
+            enabled: request.enabled,
+            message: request.message.clone(),
+            toggled_by: Some(auth.user_id),
+        };
+        if let Err(e) = publisher.publish(&event).await {
+            let error = DomainError::Internal { message: e };
+            return handle_domain_error_with_lang(&error, lang);
+        }
+    }
+
+    // The published event reaches this instance too; switching right away
+    // makes the response reflect the new state
+    if state.maintenance.set(request.enabled, request.message) {
+        log::warn!(
+            "Maintenance mode switched {} by administrator {}",
+            if request.enabled { "on" } else { "off" },
+            auth.user_id
+        );
+    }
+
+    HttpResponse::Ok().json(MaintenanceStatusResponse::from(state.maintenance.status()))
+}
@@ -0,0 +1,106 @@
+//! Administration route handlers
+//!
+//! This module contains endpoints for platform administrators:
+//! - Listing and clearing account locks
+//! - Searching the authentication audit log
+//! - Managing business calendar closure dates
+//! - Versioning platform fee schedules and auditing order fees
+//! - Creating and monitoring notification campaigns
+//! - Versioning the completion checklists of job categories
+//! - Granting store credit to customers
+//! - Managing IP allowlists and denylists
+//! - Temporarily raising log levels to diagnose production issues
+//! - Draining instances out of load balancer rotation
+//! - Switching the platform in and out of maintenance mode
+//! - Reporting health signals and requests shed under load
+//! - Reporting the build features and capabilities of an instance
+//! - Reporting request fields sent by clients but unknown to the server
+//! - Registering webhooks that receive signed security events
+//! - Running and exporting payment reconciliation reports
+//! - Reviewing payments held by fraud rules
+//! - Registering partner applications for OAuth access
+//! - Signing administrators in through the company identity provider
+//! - Reporting weekly registration cohorts per marketing campaign
+//! - Publishing versions of the terms of service and privacy policy
+//! - Exporting audit logs and attribution cohorts in the background
+//! - Inspecting and replaying webhooks that failed to be handled
+//! - Importing users from the legacy system
+//! - Reporting estimated SMS spend against the daily budget
+//! - Searching the outbound SMS log for support tickets
+//!
+//! All handlers except single sign-on require an authenticated user whose
+//! type is `admin`.
+
+pub mod attribution;
+pub mod audit_logs;
+pub mod calendar;
+pub mod campaigns;
+pub mod capabilities;
+pub mod checklists;
+pub mod credits;
+pub mod drain;
+pub mod exports;
+pub mod fees;
+pub mod ip_access;
+pub mod legal_documents;
+pub mod load_shedding;
+pub mod locks;
+pub mod log_level;
+pub mod maintenance;
+pub mod oauth_clients;
+pub mod payment_risk;
+pub mod reconciliation;
+pub mod security_webhooks;
+pub mod sms_logs;
+pub mod sms_spend;
+pub mod sso;
+pub mod unknown_fields;
+pub mod user_import;
+pub mod webhook_dead_letters;
+
+use std::sync::Arc;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::{AuthError, DomainError};
+use re_core::repositories::AuditLogRepository;
+use re_core::services::auth::AccountLockService;
+use re_core::services::verification::CacheServiceTrait;
+
+use crate::middleware::auth::AuthContext;
+
+/// User type carried in the access token of administrators
+pub const ADMIN_USER_TYPE: &str = "admin";
+
+/// Application state for administration routes
+pub struct AdminState<C, A>
+where
+    C: CacheServiceTrait,
+    A: AuditLogRepository,
+{
+    pub account_lock_service: Arc<AccountLockService<C, A>>,
+}
+
+/// Ensure the authenticated user is an administrator
+pub fn require_admin(auth: &AuthContext) -> Result<(), DomainError> {
+    match auth.user_type.as_deref() {
+        Some(ADMIN_USER_TYPE) => Ok(()),
+        _ => Err(DomainError::Auth(AuthError::InsufficientPermissions)),
+    }
+}
+
+/// Ensure the authenticated administrator holds a role
+///
+/// The `admin` role grants every other role.
+pub fn require_admin_role(auth: &AuthContext, role: AdminRole) -> Result<(), DomainError> {
+    require_admin(auth)?;
+
+    let granted = auth
+        .roles
+        .iter()
+        .any(|r| r == role.as_str() || r == AdminRole::Admin.as_str());
+    if granted {
+        Ok(())
+    } else {
+        Err(DomainError::Auth(AuthError::InsufficientPermissions))
+    }
+}
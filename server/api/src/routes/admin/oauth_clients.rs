@@ -0,0 +1,185 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use validator::Validate;
+
+use crate::dto::admin::{
+    OAuthClientListResponse, OAuthClientResponse, RegisterOAuthClientRequest,
+    RegisteredOAuthClientResponse, UpdateOAuthClientRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::oauth::OAuthState;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{OAuthRepository, TokenRepository};
+use re_core::services::oauth::NewOAuthClient;
+
+use super::require_admin;
+
+/// Handler for GET /api/v1/admin/oauth/clients
+///
+/// Lists registered partner applications, newest first.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "clients": [
+///         {
+///             "client_id": "rec_5f0c6a1d2b3e4f5a6b7c8d9e0f1a2b3c",
+///             "name": "Tile Supplies Pty Ltd",
+///             "redirect_uris": ["https://tiles.example.com/renoveasy/callback"],
+///             "scopes": ["profile:read", "orders:read"],
+///             "grant_types": ["authorization_code"],
+///             "is_active": true,
+///             "created_at": "2026-10-16T10:00:00Z",
+///             "updated_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_clients<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.oauth_service.list_clients().await {
+        Ok(clients) => {
+            let clients: Vec<OAuthClientResponse> = clients.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(OAuthClientListResponse {
+                total: clients.len(),
+                clients,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/oauth/clients
+///
+/// Registers a partner application. The response is the only time the
+/// client secret is shown.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "name": "Tile Supplies Pty Ltd",
+///     "redirect_uris": ["https://tiles.example.com/renoveasy/callback"],
+///     "scopes": ["profile:read", "orders:read"],
+///     "grant_types": ["authorization_code"]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The client, in the same format as the list, with a `client_secret` field
+///
+/// ## Errors
+/// - 400 Bad Request: Missing name, scopes or grants, an unsupported scope,
+///   or a redirect URI that is not HTTPS
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn register_client<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+    request: web::Json<RegisterOAuthClientRequest>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    let new_client = NewOAuthClient {
+        name: request.name,
+        redirect_uris: request.redirect_uris,
+        scopes: request.scopes,
+        grant_types: request.grant_types,
+    };
+
+    match state.oauth_service.register_client(new_client).await {
+        Ok(registered) => HttpResponse::Created().json(RegisteredOAuthClientResponse {
+            client: registered.client.into(),
+            client_secret: registered.client_secret,
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for PATCH /api/v1/admin/oauth/clients/{client_id}
+///
+/// Enables or disables a partner application. A disabled partner cannot
+/// obtain new tokens; tokens already issued expire normally.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "is_active": false
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The client, in the same format as the list
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No client exists with this ID
+pub async fn update_client<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<UpdateOAuthClientRequest>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .oauth_service
+        .set_client_active(&path.into_inner(), request.is_active)
+        .await
+    {
+        Ok(client) => HttpResponse::Ok().json(OAuthClientResponse::from(client)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,197 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{
+    DecideRiskReviewRequest, PaymentRiskAssessmentListResponse, PaymentRiskAssessmentResponse,
+    PaymentRiskReviewQuery, RiskReviewDecision,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::repositories::PaymentRiskRepository;
+use re_core::services::payment_risk::PaymentRiskService;
+
+use super::require_admin;
+
+/// Default number of assessments returned by the review queue
+const DEFAULT_REVIEW_LIMIT: usize = 50;
+
+/// Maximum number of assessments returned by the review queue
+const MAX_REVIEW_LIMIT: usize = 200;
+
+/// Application state for payment risk review routes
+pub struct PaymentRiskState<R>
+where
+    R: PaymentRiskRepository + 'static,
+{
+    pub payment_risk_service: Arc<PaymentRiskService<R>>,
+}
+
+/// Handler for GET /api/v1/admin/payment-risk/reviews
+///
+/// Lists payment attempts held for manual review, oldest first.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of assessments (default: 50, maximum: 200)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "assessments": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "user_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///             "amount": 250000,
+///             "currency": "AUD",
+///             "market": "AU",
+///             "billing_country": "AU",
+///             "triggered_rules": ["new_account_high_amount"],
+///             "action": "review",
+///             "review_status": "pending",
+///             "reviewed_by": null,
+///             "reviewed_at": null,
+///             "review_note": null,
+///             "created_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_reviews<R>(
+    req: HttpRequest,
+    state: web::Data<PaymentRiskState<R>>,
+    auth: AuthContext,
+    query: web::Query<PaymentRiskReviewQuery>,
+) -> HttpResponse
+where
+    R: PaymentRiskRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_REVIEW_LIMIT)
+        .min(MAX_REVIEW_LIMIT);
+
+    match state.payment_risk_service.pending_reviews(limit).await {
+        Ok(assessments) => {
+            let assessments: Vec<PaymentRiskAssessmentResponse> =
+                assessments.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(PaymentRiskAssessmentListResponse {
+                total: assessments.len(),
+                assessments,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/payment-risk/assessments/{id}
+///
+/// Returns the risk assessment of a payment attempt.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The assessment, in the same format as the review queue
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No assessment exists with this ID
+pub async fn get_assessment<R>(
+    req: HttpRequest,
+    state: web::Data<PaymentRiskState<R>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    R: PaymentRiskRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.payment_risk_service.get(path.into_inner()).await {
+        Ok(assessment) => HttpResponse::Ok().json(PaymentRiskAssessmentResponse::from(assessment)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/payment-risk/reviews/{id}
+///
+/// Approves or rejects a payment attempt held for review. An approved
+/// attempt may be retried by the customer and proceeds to payment.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "decision": "approve",
+///     "note": "Customer verified by phone"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The decided assessment, in the same format as the review queue
+///
+/// ## Errors
+/// - 400 Bad Request: Note too long, or the assessment is not pending review
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No assessment exists with this ID
+pub async fn decide_review<R>(
+    req: HttpRequest,
+    state: web::Data<PaymentRiskState<R>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<DecideRiskReviewRequest>,
+) -> HttpResponse
+where
+    R: PaymentRiskRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if request.validate().is_err() {
+        let error = DomainError::ValidationErr(DomainValidationError::InvalidFormat {
+            field: "note".to_string(),
+        });
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    match state
+        .payment_risk_service
+        .decide_review(
+            path.into_inner(),
+            request.decision == RiskReviewDecision::Approve,
+            auth.user_id,
+            request.note,
+        )
+        .await
+    {
+        Ok(assessment) => HttpResponse::Ok().json(PaymentRiskAssessmentResponse::from(assessment)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
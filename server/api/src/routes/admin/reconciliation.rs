@@ -0,0 +1,285 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dto::admin::{
+    ReconciliationQuery, ReconciliationReportDetailResponse, ReconciliationReportListResponse,
+    ReconciliationReportResponse, RunReconciliationRequest,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::reconciliation::ReconciliationReport;
+use re_core::repositories::{PaymentLedgerRepository, ReconciliationReportRepository};
+use re_core::services::reconciliation::{
+    PaymentReportProviderTrait, ReconciliationAlertTrait, ReconciliationService,
+};
+
+use super::require_admin;
+
+/// Default number of reports returned by the list endpoint
+const DEFAULT_REPORT_LIMIT: usize = 30;
+
+/// Maximum number of reports returned by the list endpoint
+const MAX_REPORT_LIMIT: usize = 100;
+
+/// Application state for financial reconciliation routes
+pub struct ReconciliationState<L, R, P, A>
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    pub reconciliation_service: Arc<ReconciliationService<L, R, P, A>>,
+}
+
+/// Handler for POST /api/v1/admin/reconciliation/reports
+///
+/// Reconciles one UTC day immediately, e.g. to re-check a day after the
+/// ledger was corrected. The nightly job keeps running independently.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "date": "2025-08-01"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The new report, in the same format as the detail endpoint
+///
+/// ## Errors
+/// - 400 Bad Request: The day has not ended yet
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 500 Internal Server Error: The provider report could not be fetched
+pub async fn run_reconciliation<L, R, P, A>(
+    req: HttpRequest,
+    state: web::Data<ReconciliationState<L, R, P, A>>,
+    auth: AuthContext,
+    request: web::Json<RunReconciliationRequest>,
+) -> HttpResponse
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.reconciliation_service.reconcile_day(request.date).await {
+        Ok(report) => HttpResponse::Created().json(ReconciliationReportDetailResponse::from(report)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/reconciliation/reports
+///
+/// Lists reports, most recent period first.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of reports (default: 30, maximum: 100)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "reports": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "provider": "stripe",
+///             "period_start": "2025-08-01T00:00:00Z",
+///             "period_end": "2025-08-02T00:00:00Z",
+///             "ledger_count": 412,
+///             "provider_count": 413,
+///             "matched_count": 410,
+///             "discrepancy_count": 2,
+///             "discrepancy_totals": { "AUD": 2500 },
+///             "balanced": false,
+///             "alerted": true,
+///             "created_at": "2025-08-02T03:00:04Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn list_reports<L, R, P, A>(
+    req: HttpRequest,
+    state: web::Data<ReconciliationState<L, R, P, A>>,
+    auth: AuthContext,
+    query: web::Query<ReconciliationQuery>,
+) -> HttpResponse
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_REPORT_LIMIT).min(MAX_REPORT_LIMIT);
+
+    match state.reconciliation_service.list_reports(limit).await {
+        Ok(reports) => {
+            let reports: Vec<ReconciliationReportResponse> = reports.iter().map(Into::into).collect();
+            HttpResponse::Ok().json(ReconciliationReportListResponse {
+                total: reports.len(),
+                reports,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/reconciliation/reports/{id}
+///
+/// Returns a report with every discrepancy.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "provider": "stripe",
+///     ...
+///     "discrepancies": [
+///         {
+///             "reference": "ch_3Nx...",
+///             "kind": "missing_in_ledger",
+///             "currency": "AUD",
+///             "ledger_amount": null,
+///             "provider_amount": 2500,
+///             "difference": 2500
+///         }
+///     ]
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No report exists with this ID
+pub async fn get_report<L, R, P, A>(
+    req: HttpRequest,
+    state: web::Data<ReconciliationState<L, R, P, A>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.reconciliation_service.get_report(path.into_inner()).await {
+        Ok(report) => HttpResponse::Ok().json(ReconciliationReportDetailResponse::from(report)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/reconciliation/reports/{id}/export
+///
+/// Downloads a report's discrepancies as CSV for finance tooling.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```text
+/// reference,kind,currency,ledger_amount,provider_amount,difference
+/// ch_3Nx...,missing_in_ledger,AUD,,2500,2500
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+/// - 404 Not Found: No report exists with this ID
+pub async fn export_report<L, R, P, A>(
+    req: HttpRequest,
+    state: web::Data<ReconciliationState<L, R, P, A>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.reconciliation_service.get_report(path.into_inner()).await {
+        Ok(report) => {
+            let filename = format!(
+                "reconciliation-{}-{}.csv",
+                report.provider,
+                report.period_start.format("%Y-%m-%d")
+            );
+
+            HttpResponse::Ok()
+                .content_type("text/csv; charset=utf-8")
+                .insert_header((
+                    "Content-Disposition",
+                    format!("attachment; filename=\"{}\"", filename),
+                ))
+                .body(report_to_csv(&report))
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Render a report's discrepancies as CSV
+fn report_to_csv(report: &ReconciliationReport) -> String {
+    let mut csv = String::from("reference,kind,currency,ledger_amount,provider_amount,difference\n");
+
+    for discrepancy in &report.discrepancies {
+        let amount = |amount: Option<i64>| amount.map(|a| a.to_string()).unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&discrepancy.reference),
+            discrepancy.kind.as_str(),
+            csv_field(&discrepancy.currency),
+            amount(discrepancy.ledger_amount),
+            amount(discrepancy.provider_amount),
+            discrepancy.difference(),
+        ));
+    }
+
+    csv
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
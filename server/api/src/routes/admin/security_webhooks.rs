@@ -0,0 +1,198 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::admin::{
+    DeleteSecurityWebhookResponse, RegisterSecurityWebhookRequest,
+    RegisteredSecurityWebhookResponse, SecurityWebhookListResponse, SecurityWebhookResponse,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::DomainError;
+use re_core::repositories::SecurityWebhookRepository;
+use re_core::services::security_webhook::{SecurityWebhookService, WebhookSenderTrait};
+
+use super::require_admin_role;
+
+/// Application state for security webhook routes
+pub struct SecurityWebhookState<R, S>
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    pub security_webhook_service: Arc<SecurityWebhookService<R, S>>,
+}
+
+/// Handler for GET /api/v1/admin/security-webhooks
+///
+/// Lists registered security webhooks, newest first. Signing secrets are
+/// never included.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "webhooks": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "url": "https://siem.example.com/renoveasy",
+///             "events": ["account_locked", "refresh_token_reuse"],
+///             "is_active": true,
+///             "created_by": "...",
+///             "created_at": "2026-10-17T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn list_webhooks<R, S>(
+    req: HttpRequest,
+    state: web::Data<SecurityWebhookState<R, S>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.security_webhook_service.list_webhooks().await {
+        Ok(webhooks) => {
+            let webhooks: Vec<SecurityWebhookResponse> = webhooks.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(SecurityWebhookListResponse {
+                total: webhooks.len(),
+                webhooks,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/security-webhooks
+///
+/// Registers an endpoint for rate-limit violations, account locks,
+/// refresh-token reuse and administrator actions. Each request carries an
+/// `X-RenovEasy-Signature: t=<timestamp>,v1=<hex>` header, the HMAC-SHA256
+/// of `<timestamp>.<body>` keyed with the secret. The response is the only
+/// time the secret is shown.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "url": "https://siem.example.com/renoveasy",
+///     "events": ["account_locked", "refresh_token_reuse"]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The webhook, in the same format as the list, with a `secret` field
+///
+/// ## Errors
+/// - 400 Bad Request: The URL is not HTTPS or the webhook limit is reached
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn register_webhook<R, S>(
+    req: HttpRequest,
+    state: web::Data<SecurityWebhookState<R, S>>,
+    auth: AuthContext,
+    request: web::Json<RegisterSecurityWebhookRequest>,
+) -> HttpResponse
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    match state
+        .security_webhook_service
+        .register_webhook(request.url, request.events, auth.user_id)
+        .await
+    {
+        Ok(webhook) => {
+            let secret = webhook.secret.clone();
+            HttpResponse::Created().json(RegisteredSecurityWebhookResponse {
+                webhook: webhook.into(),
+                secret,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/admin/security-webhooks/{id}
+///
+/// Deletes a webhook. Events still queued for it are dropped.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "Security webhook deleted successfully"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 404 Not Found: No webhook exists with this ID
+pub async fn delete_webhook<R, S>(
+    req: HttpRequest,
+    state: web::Data<SecurityWebhookState<R, S>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .security_webhook_service
+        .delete_webhook(path.into_inner())
+        .await
+    {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "Security webhook deleted successfully",
+                Language::Chinese => "安全 Webhook 已成功删除",
+            };
+
+            HttpResponse::Ok().json(DeleteSecurityWebhookResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
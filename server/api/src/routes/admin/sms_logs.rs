@@ -0,0 +1,136 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::dto::admin::{SmsLogQuery, SmsLogResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::sms_delivery::{SmsDeliveryStatus, SmsLogFilter};
+use re_core::errors::DomainError;
+use re_core::repositories::SmsLogRepository;
+use re_infra::sms::mask_phone_number;
+use re_shared::types::{DateRange, Pagination, PaginatedResponse};
+
+use super::require_admin_role;
+
+/// Application state for SMS log routes
+pub struct SmsLogState<R>
+where
+    R: SmsLogRepository + 'static,
+{
+    pub sms_logs: Arc<R>,
+}
+
+/// Handler for GET /api/v1/admin/sms/logs
+///
+/// Searches the outbound SMS log, newest first, to answer "I never got my
+/// code" tickets: every attempt to hand a message to a provider is logged,
+/// including attempts the provider rejected and messages failover moved on
+/// from. All filters are optional and combined with AND.
+///
+/// # Query Parameters
+/// - `page`: Page number (default: 1)
+/// - `per_page`: Messages per page (default: 20, maximum: 100)
+/// - `phone`: Only messages to this E.164 phone number, matched on its masked
+///   form (same length and last 4 digits)
+/// - `provider`: Only messages sent through this provider, e.g. `Twilio`
+/// - `status`: `sent`, `delivered`, `undelivered` or `failed`
+/// - `template`: Only messages rendered from this template, e.g. `verification_code`
+/// - `from`, `to`: RFC 3339 time range, both inclusive
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "data": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "trace_id": "4bf92f3577b34da6a3ce929d0e0e4736",
+///             "provider": "Twilio",
+///             "provider_message_id": "SM123",
+///             "phone_masked": "+*******5678",
+///             "status": "undelivered",
+///             "error": "30003: Unreachable destination handset",
+///             "template": "verification_code",
+///             "cost_micros": 51500,
+///             "created_at": "2026-10-17T10:00:00Z",
+///             "updated_at": "2026-10-17T10:00:05Z"
+///         }
+///     ],
+///     "page": 1,
+///     "per_page": 20,
+///     "total": 1,
+///     "total_pages": 1,
+///     "has_next": false,
+///     "has_prev": false
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown status, or `to` is before `from`
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `support` role
+pub async fn list_sms_logs<R>(
+    req: HttpRequest,
+    state: web::Data<SmsLogState<R>>,
+    auth: AuthContext,
+    query: web::Query<SmsLogQuery>,
+) -> HttpResponse
+where
+    R: SmsLogRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Support) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let query = query.into_inner();
+    let filter = match build_filter(&query) {
+        Ok(filter) => filter,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+    let defaults = Pagination::default();
+    let pagination = Pagination::new(
+        query.page.unwrap_or(defaults.page),
+        query.per_page.unwrap_or(defaults.per_page),
+    )
+    .validate();
+
+    match state.sms_logs.query(&filter, &pagination).await {
+        Ok((logs, total)) => HttpResponse::Ok().json(
+            PaginatedResponse::new(logs, pagination, total).map(SmsLogResponse::from),
+        ),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Build the log filter from the query parameters
+fn build_filter(query: &SmsLogQuery) -> Result<SmsLogFilter, DomainError> {
+    let status = match query.status.as_deref() {
+        Some(status) => Some(SmsDeliveryStatus::from_str(&status.to_ascii_lowercase()).ok_or_else(|| {
+            DomainError::Validation {
+                message: format!("Unknown SMS status: {}", status),
+            }
+        })?),
+        None => None,
+    };
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err(DomainError::Validation {
+                message: "Date range must not end before it starts".to_string(),
+            });
+        }
+    }
+
+    Ok(SmsLogFilter {
+        phone_masked: query.phone.as_deref().map(str::trim).map(mask_phone_number),
+        provider: query.provider.clone(),
+        status,
+        template: query.template.clone(),
+        date_range: DateRange::new(query.from, query.to),
+    })
+}
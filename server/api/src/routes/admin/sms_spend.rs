@@ -0,0 +1,79 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use crate::dto::admin::{SmsSpendQuery, SmsSpendResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::services::sms_cost::SmsCostService;
+
+use super::require_admin;
+
+/// Number of days reported when no period is given
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+/// Application state for SMS spend routes
+pub struct SmsSpendState {
+    pub sms_cost_service: Arc<SmsCostService>,
+}
+
+/// Handler for GET /api/v1/admin/sms/spend
+///
+/// Reports the estimated SMS spend per UTC day, broken down by provider and
+/// destination prefix, with today's spend against the daily budget.
+/// Amounts are in millionths of the budget currency.
+///
+/// # Query Parameters
+/// - `from`: First day of the period (default: 29 days before `to`)
+/// - `to`: Last day of the period (default: today)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "currency": "USD",
+///     "daily_budget_micros": 50000000,
+///     "over_budget": "downgrade",
+///     "today_micros": 6180000,
+///     "remaining_today_micros": 43820000,
+///     "total_messages": 120,
+///     "total_micros": 6180000,
+///     "days": [
+///         {
+///             "date": "2026-10-17",
+///             "messages": 120,
+///             "cost_micros": 6180000,
+///             "breakdown": [
+///                 { "provider": "twilio", "prefix": "+61", "messages": 120, "cost_micros": 6180000 }
+///             ]
+///         }
+///     ]
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: `from` is after `to`, or the period exceeds a year
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn get_sms_spend(
+    req: HttpRequest,
+    state: web::Data<SmsSpendState>,
+    auth: AuthContext,
+    query: web::Query<SmsSpendQuery>,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query.from.unwrap_or(to - Duration::days(DEFAULT_REPORT_DAYS - 1));
+
+    match state.sms_cost_service.spend_report(from, to).await {
+        Ok(report) => HttpResponse::Ok().json(SmsSpendResponse::from(report)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
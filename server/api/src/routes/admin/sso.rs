@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use validator::Validate;
+
+use crate::dto::admin::{AdminSsoCallbackRequest, AdminSsoLoginResponse, AdminSsoSessionResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::repositories::{AdminIdentityRepository, TokenRepository};
+use re_core::services::admin_sso::{AdminSsoService, OidcProviderTrait};
+
+/// Application state for admin single sign-on routes
+pub struct AdminSsoState<A, R, P>
+where
+    A: AdminIdentityRepository + 'static,
+    R: TokenRepository + 'static,
+    P: OidcProviderTrait + 'static,
+{
+    pub admin_sso_service: Arc<AdminSsoService<A, R, P>>,
+}
+
+/// Handler for GET /api/v1/admin/sso/login
+///
+/// Starts a login with the company identity provider. The admin portal
+/// opens the returned URL; the provider redirects back to the portal with
+/// a code and state, which the portal posts to the callback.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "authorization_url": "https://renoveasy.okta.com/oauth2/v1/authorize?response_type=code&..."
+/// }
+/// ```
+///
+/// ## Errors
+/// - 500 Internal Server Error: The identity provider could not be reached
+pub async fn start_login<A, R, P>(
+    req: HttpRequest,
+    state: web::Data<AdminSsoState<A, R, P>>,
+) -> HttpResponse
+where
+    A: AdminIdentityRepository + 'static,
+    R: TokenRepository + 'static,
+    P: OidcProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.admin_sso_service.start_login().await {
+        Ok(redirect) => HttpResponse::Ok().json(AdminSsoLoginResponse {
+            authorization_url: redirect.authorization_url,
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/sso/callback
+///
+/// Completes a login with the code and state the identity provider
+/// returned, and issues an admin session token carrying the roles mapped
+/// from the administrator's groups. No refresh token is issued; the
+/// administrator signs in again when the session expires.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "code": "Y2Fub25pY2Fs...",
+///     "state": "af0ifjsldkj..."
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "access_token": "eyJ...",
+///     "token_type": "Bearer",
+///     "expires_in": 3600,
+///     "admin_id": "550e8400-e29b-41d4-a716-446655440000",
+///     "email": "ops@renoveasy.com",
+///     "name": "Ops Team",
+///     "roles": ["support", "finance"]
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Missing code or state
+/// - 401 Unauthorized: Unknown or reused state, or the identity provider rejected the login
+/// - 403 Forbidden: None of the administrator's groups grants access to the portal
+pub async fn complete_login<A, R, P>(
+    req: HttpRequest,
+    state: web::Data<AdminSsoState<A, R, P>>,
+    request: web::Json<AdminSsoCallbackRequest>,
+) -> HttpResponse
+where
+    A: AdminIdentityRepository + 'static,
+    R: TokenRepository + 'static,
+    P: OidcProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let field = errors
+            .field_errors()
+            .keys()
+            .next()
+            .map(|field| field.to_string())
+            .unwrap_or_else(|| "code".to_string());
+        let error = DomainError::ValidationErr(DomainValidationError::RequiredField { field });
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .admin_sso_service
+        .complete_login(&request.state, &request.code)
+        .await
+    {
+        Ok(result) => HttpResponse::Ok().json(AdminSsoSessionResponse::from(result)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
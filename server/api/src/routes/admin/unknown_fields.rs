@@ -0,0 +1,48 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::admin::UnknownFieldMetricsResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::middleware::compat_json::UnknownFieldTracker;
+
+use re_core::domain::entities::admin::AdminRole;
+
+use super::require_admin_role;
+
+/// Handler for GET /api/v1/admin/unknown-fields
+///
+/// Reports the request fields clients sent that the instance serving the
+/// request does not know, since it started. A field can be dropped from a
+/// request schema once no supported client version sends it.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "mode": "compatible",
+///     "requests": 42,
+///     "rejected": 0,
+///     "fields": [
+///         { "route": "POST /api/v1/auth/send-code", "field": "device_model", "count": 42 }
+///     ],
+///     "client_versions": { "1.3.0": 40, "unknown": 2 }
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an administrator
+pub async fn get_unknown_fields(
+    req: HttpRequest,
+    state: web::Data<UnknownFieldTracker>,
+    auth: AuthContext,
+) -> HttpResponse {
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    HttpResponse::Ok().json(UnknownFieldMetricsResponse::new(state.mode(), state.metrics()))
+}
@@ -0,0 +1,140 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::dto::admin::{UserImportQuery, UserImportResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::errors::DomainError;
+use re_core::repositories::{UserImportRepository, UserRepository};
+use re_core::services::user_import::{parse_export, ExportFormat, UserImportService};
+
+use super::require_admin_role;
+
+/// Largest export accepted by the import endpoint; register the route with
+/// `web::PayloadConfig::new(MAX_EXPORT_BYTES)`. Larger exports are imported
+/// with `re_api --import-users`.
+pub const MAX_EXPORT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Application state for user import routes
+pub struct UserImportState<U, C>
+where
+    U: UserRepository + 'static,
+    C: UserImportRepository + 'static,
+{
+    pub user_import_service: Arc<UserImportService<U, C>>,
+}
+
+/// Handler for POST /api/v1/admin/users/import
+///
+/// Imports users from an export of the legacy system, sent as the request
+/// body with `Content-Type: text/csv` or `application/json`. Records are
+/// validated like `re_api --import-users` does: invalid records, duplicates
+/// within the export and users already registered are skipped and reported,
+/// the others are created in batches. An import that fails part-way resumes
+/// where it stopped when the same export is sent again.
+///
+/// # Query Parameters
+/// - `dry_run`: Only validate the export, without creating users (default: false)
+///
+/// # Request Body
+///
+/// ```text
+/// phone,country,user_type,registered_at,blocked
+/// 0412 345 678,AU,worker,2019-03-04 05:06:07,false
+/// 13800138000,CN,customer,2020-01-01T00:00:00Z,true
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "dry_run": false,
+///     "resumed_from": 0,
+///     "markets": { "+61": 1 },
+///     "successful": [
+///         {
+///             "id": "1",
+///             "result": {
+///                 "user_id": "550e8400-e29b-41d4-a716-446655440000",
+///                 "country_code": "+61"
+///             }
+///         }
+///     ],
+///     "failed": [
+///         {
+///             "id": "2",
+///             "error": {
+///                 "code": "already_registered",
+///                 "message": "A user with phone number 138****8000 is already registered"
+///             }
+///         }
+///     ],
+///     "summary": { "total": 2, "successful": 1, "failed": 1, "duration_ms": 84 }
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unsupported content type, malformed export, or the
+///   same export was already imported
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 413 Payload Too Large: The export exceeds `MAX_EXPORT_BYTES`
+pub async fn import_users<U, C>(
+    req: HttpRequest,
+    state: web::Data<UserImportState<U, C>>,
+    auth: AuthContext,
+    query: web::Query<UserImportQuery>,
+    body: web::Bytes,
+) -> HttpResponse
+where
+    U: UserRepository + 'static,
+    C: UserImportRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let format = match req.content_type() {
+        "text/csv" => ExportFormat::Csv,
+        "application/json" => ExportFormat::Json,
+        other => {
+            let error = DomainError::Validation {
+                message: format!("Exports must be sent as text/csv or application/json, not {}", other),
+            };
+            return handle_domain_error_with_lang(&error, lang);
+        }
+    };
+    let records = match parse_export(format, &body) {
+        Ok(records) => records,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    let started = Instant::now();
+    let service = &state.user_import_service;
+    let result = if query.dry_run {
+        service.dry_run(&records).await
+    } else {
+        let import_id = UserImportService::<U, C>::import_id(&body);
+        log::info!(
+            "Administrator {} importing {} users as import {}",
+            auth.user_id,
+            records.len(),
+            import_id
+        );
+        service.import(&import_id, &records).await
+    };
+
+    match result {
+        Ok(report) => HttpResponse::Ok().json(UserImportResponse::new(
+            report,
+            started.elapsed().as_millis() as u64,
+        )),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
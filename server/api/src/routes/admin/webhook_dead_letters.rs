@@ -0,0 +1,263 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dto::admin::{
+    BulkReplayRequest, BulkReplayResponse, ReplayResultResponse, WebhookDeadLetterListResponse,
+    WebhookDeadLetterQuery, WebhookDeadLetterResponse,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::webhook_dead_letter::DeadLetterFilter;
+use re_core::errors::DomainError;
+use re_core::repositories::WebhookDeadLetterRepository;
+use re_core::services::webhook_dead_letter::WebhookDeadLetterService;
+
+use super::require_admin_role;
+
+/// Default number of dead letters listed
+const DEFAULT_LIST_LIMIT: usize = 50;
+
+/// Application state for webhook dead-letter routes
+pub struct WebhookDeadLetterState<R>
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    pub dead_letter_service: Arc<WebhookDeadLetterService<R>>,
+}
+
+/// Handler for GET /api/v1/admin/webhook-dead-letters
+///
+/// Lists webhooks that failed to be handled, most recent failure first.
+/// Payloads are only included when a single dead letter is requested.
+///
+/// # Query Parameters
+/// - `direction`: `inbound` (received from a provider) or `outbound`
+///   (security webhook deliveries)
+/// - `source`: e.g. `stripe` or `security_webhook`
+/// - `status`: `pending`, `replaying` or `resolved`
+/// - `from`, `to`: RFC 3339 range of first failures, both inclusive
+/// - `limit`: Maximum number of dead letters (default: 50, maximum: 200)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "dead_letters": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "direction": "inbound",
+///             "source": "stripe",
+///             "event_id": "evt_1NqB2c",
+///             "event_type": "charge.refunded",
+///             "target_id": null,
+///             "error": "Refund exceeds the amount paid",
+///             "failures": 1,
+///             "status": "pending",
+///             "replays": 0,
+///             "created_at": "2026-10-17T10:00:00Z",
+///             ...
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown direction or status, or `to` is before `from`
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn list_dead_letters<R>(
+    req: HttpRequest,
+    state: web::Data<WebhookDeadLetterState<R>>,
+    auth: AuthContext,
+    query: web::Query<WebhookDeadLetterQuery>,
+) -> HttpResponse
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let query = query.into_inner();
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            let error = DomainError::Validation {
+                message: "Period must not end before it starts".to_string(),
+            };
+            return handle_domain_error_with_lang(&error, lang);
+        }
+    }
+
+    let filter = DeadLetterFilter {
+        direction: query.direction,
+        source: query.source,
+        status: query.status,
+        from: query.from,
+        to: query.to,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+
+    match state.dead_letter_service.list(&filter, limit).await {
+        Ok(dead_letters) => {
+            let dead_letters: Vec<WebhookDeadLetterResponse> = dead_letters
+                .into_iter()
+                .map(|dead_letter| WebhookDeadLetterResponse::new(dead_letter, false))
+                .collect();
+            HttpResponse::Ok().json(WebhookDeadLetterListResponse {
+                total: dead_letters.len(),
+                dead_letters,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/admin/webhook-dead-letters/{id}
+///
+/// Returns a dead letter with its full payload.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The dead letter, in the same format as the list, with a `payload` field
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 404 Not Found: No dead letter exists with this ID
+pub async fn get_dead_letter<R>(
+    req: HttpRequest,
+    state: web::Data<WebhookDeadLetterState<R>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.dead_letter_service.get(path.into_inner()).await {
+        Ok(dead_letter) => HttpResponse::Ok().json(WebhookDeadLetterResponse::new(dead_letter, true)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/webhook-dead-letters/{id}/replay
+///
+/// Handles a dead-lettered webhook again. Resolved dead letters are not
+/// replayed, and a dead letter already being replayed is not replayed a
+/// second time. A replay that fails again leaves the dead letter pending.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "dead_letter_id": "550e8400-e29b-41d4-a716-446655440000",
+///     "status": "replayed",
+///     "detail": "refund_recorded"
+/// }
+/// ```
+///
+/// `status` is one of `replayed`, `failed`, `already_resolved` or
+/// `in_progress`.
+///
+/// ## Errors
+/// - 400 Bad Request: Webhooks from this source cannot be replayed
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+/// - 404 Not Found: No dead letter exists with this ID
+pub async fn replay_dead_letter<R>(
+    req: HttpRequest,
+    state: web::Data<WebhookDeadLetterState<R>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .dead_letter_service
+        .replay(path.into_inner(), auth.user_id)
+        .await
+    {
+        Ok(result) => HttpResponse::Ok().json(ReplayResultResponse::from(result)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/admin/webhook-dead-letters/replay
+///
+/// Replays the pending dead letters that first failed within a time range,
+/// oldest first and at most 100 per request. Repeat the request while
+/// `has_more` is true.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "direction": "inbound",
+///     "from": "2026-10-17T08:00:00Z",
+///     "to": "2026-10-17T10:00:00Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "replayed": 12,
+///     "failed": 1,
+///     "skipped": 0,
+///     "results": [ ... ],
+///     "has_more": false
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: `to` is before `from`
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user does not hold the `admin` role
+pub async fn replay_dead_letters<R>(
+    req: HttpRequest,
+    state: web::Data<WebhookDeadLetterState<R>>,
+    auth: AuthContext,
+    request: web::Json<BulkReplayRequest>,
+) -> HttpResponse
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_admin_role(&auth, AdminRole::Admin) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    match state
+        .dead_letter_service
+        .replay_range(request.direction, request.from, request.to, auth.user_id)
+        .await
+    {
+        Ok(result) => HttpResponse::Ok().json(BulkReplayResponse::from(result)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::dto::auth::ProofOfWorkChallengeResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::services::auth::{ProofOfWorkService, ProofOfWorkStoreTrait};
+
+/// Application state for proof-of-work challenges
+pub struct ProofOfWorkState<P>
+where
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    pub proof_of_work_service: Arc<ProofOfWorkService<P>>,
+}
+
+/// Handler for POST /api/v1/auth/challenge
+///
+/// Issues a proof-of-work challenge. The client searches for a solution
+/// whose `SHA-256("{nonce}:{solution}")` starts with `difficulty` zero bits,
+/// then sends the nonce and solution with its send-code request in the
+/// `X-Pow-Nonce` and `X-Pow-Solution` headers. Each challenge is good for
+/// one request.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "nonce": "9f86d081884c7d659a2feaa0c55ad015",
+///     "difficulty": 18,
+///     "required": true,
+///     "expires_at": "2026-10-17T10:02:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 500 Internal Server Error: The challenge could not be stored
+pub async fn issue_challenge<P>(
+    req: HttpRequest,
+    state: web::Data<ProofOfWorkState<P>>,
+) -> HttpResponse
+where
+    P: ProofOfWorkStoreTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.proof_of_work_service.issue_challenge().await {
+        Ok(challenge) => HttpResponse::Ok().json(ProofOfWorkChallengeResponse {
+            nonce: challenge.nonce,
+            difficulty: challenge.difficulty,
+            required: state.proof_of_work_service.is_enabled(),
+            expires_at: challenge.expires_at,
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
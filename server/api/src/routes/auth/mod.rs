@@ -2,14 +2,20 @@
 //!
 //! This module contains all authentication-related endpoints including:
 //! - Phone verification (sending and verifying codes)
+//! - Proof-of-work challenges guarding code sends
 //! - User type selection
 //! - Token refresh
 //! - Logout
+//! - Reporting suspicious logins
+//! - Listing and signing out signed-in devices
 
+pub mod challenge;
 pub mod send_code;
 pub mod verify_code;
 pub mod select_type;
 pub mod refresh;
 pub mod logout;
+pub mod report_suspicious;
+pub mod sessions;
 
 pub use send_code::AppState;
@@ -2,6 +2,7 @@ use actix_web::{web, HttpRequest, HttpResponse};
 
 use crate::dto::auth::{RefreshTokenRequest, AuthResponse as DtoAuthResponse};
 use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::extract_device_fingerprint;
 
 use re_core::repositories::{UserRepository, TokenRepository};
 use re_core::services::verification::{SmsServiceTrait, CacheServiceTrait};
@@ -57,8 +58,11 @@ where
     let client_ip = extract_client_ip(&req);
     let user_agent = extract_user_agent(&req);
     
+    // Extract device fingerprint so the new access token stays bound to the device
+    let device_fingerprint = extract_device_fingerprint(req.headers());
+    
     // Call the auth service to refresh the token
-    match state.auth_service.refresh_token(&request.refresh_token, Some(client_ip), user_agent, None).await {
+    match state.auth_service.refresh_token(&request.refresh_token, Some(client_ip), user_agent, device_fingerprint).await {
         Ok(auth_response) => {
             // Convert the domain AuthResponse to DTO AuthResponse
             let response = DtoAuthResponse {
@@ -67,6 +71,8 @@ where
                 expires_in: auth_response.expires_in,
                 user_type: auth_response.user_type.map(|ut| ut.to_string()),
                 requires_type_selection: auth_response.requires_type_selection,
+                requires_step_up: auth_response.requires_step_up,
+                compliance_required: auth_response.compliance_required,
             };
             
             HttpResponse::Ok().json(response)
@@ -0,0 +1,117 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use validator::Validate;
+
+use crate::dto::auth::{ReportSuspiciousLoginRequest, ReportSuspiciousLoginResponse};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{UserRepository, TokenRepository};
+use re_core::services::verification::{SmsServiceTrait, CacheServiceTrait};
+use re_core::services::auth::RateLimiterTrait;
+
+use super::AppState;
+
+/// Handler for POST /api/v1/auth/report-suspicious
+///
+/// Reports a login the user was notified about but did not make. The
+/// reported session is revoked immediately and can no longer be refreshed.
+/// Requires authentication via Bearer token in Authorization header.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "session_id": "550e8400-e29b-41d4-a716-446655440000"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "The session has been signed out"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Missing or malformed session ID
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: The user has no session with this ID
+pub async fn report_suspicious<U, S, C, R, T>(
+    req: HttpRequest,
+    state: web::Data<AppState<U, S, C, R, T>>,
+    auth: AuthContext,
+    request: web::Json<ReportSuspiciousLoginRequest>,
+) -> HttpResponse
+where
+    U: UserRepository + 'static,
+    S: SmsServiceTrait + 'static,
+    C: CacheServiceTrait + 'static,
+    R: RateLimiterTrait + 'static,
+    T: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let client_ip = extract_client_ip(&req);
+    let user_agent = extract_user_agent(&req);
+
+    match state
+        .auth_service
+        .report_suspicious_login(auth.user_id, &request.session_id, Some(client_ip), user_agent)
+        .await
+    {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "The session has been signed out",
+                Language::Chinese => "该会话已被登出",
+            };
+
+            HttpResponse::Ok().json(ReportSuspiciousLoginResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Extract client IP address from request
+fn extract_client_ip(req: &HttpRequest) -> String {
+    // Try to get IP from X-Forwarded-For header (for reverse proxy scenarios)
+    if let Some(forwarded_for) = req.headers().get("X-Forwarded-For") {
+        if let Ok(forwarded_str) = forwarded_for.to_str() {
+            // Take the first IP from the comma-separated list
+            if let Some(ip) = forwarded_str.split(',').next() {
+                return ip.trim().to_string();
+            }
+        }
+    }
+
+    // Try to get IP from X-Real-IP header
+    if let Some(real_ip) = req.headers().get("X-Real-IP") {
+        if let Ok(ip_str) = real_ip.to_str() {
+            return ip_str.to_string();
+        }
+    }
+
+    // Fall back to connection info
+    req.connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Extract user agent from request headers
+fn extract_user_agent(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("User-Agent")
+        .and_then(|ua| ua.to_str().ok())
+        .map(|s| s.to_string())
+}
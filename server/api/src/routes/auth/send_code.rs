@@ -5,6 +5,7 @@ use uuid::Uuid;
 
 use crate::dto::auth::{SendCodeRequest, SendCodeResponse};
 use crate::handlers::error_standard::{StandardApiError, to_standard_response, extract_language};
+use crate::middleware::attack_guard::record_target_phone;
 use crate::middleware::error_handler::ErrorHandlingExt;
 
 use re_core::services::auth::AuthService;
@@ -157,6 +158,7 @@ where
     } else {
         format!("{}{}", request.country_code, request.phone)
     };
+    record_target_phone(&req, &phone);
 
     // Validate E.164 format
     if !phone.starts_with('+') || phone.len() < 8 || phone.len() > 16 {
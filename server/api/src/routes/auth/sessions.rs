@@ -0,0 +1,169 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::auth::{SessionListResponse, SessionResponse, SignOutSessionResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, Language, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::audit::AuditLog;
+use re_core::repositories::{UserRepository, TokenRepository};
+use re_core::services::verification::{SmsServiceTrait, CacheServiceTrait};
+use re_core::services::auth::RateLimiterTrait;
+
+use super::AppState;
+
+/// Handler for GET /api/v1/auth/sessions
+///
+/// Lists the devices the user is signed in on, most recently active
+/// first, so they can sign out the ones they no longer use. IP addresses
+/// are masked to their network part.
+/// Requires authentication via Bearer token in Authorization header.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "sessions": [
+///         {
+///             "session_id": "550e8400-e29b-41d4-a716-446655440000",
+///             "device": "Mobile/iOS",
+///             "ip_address": "203.0.*.*",
+///             "location": "AU",
+///             "current": true,
+///             "created_at": "2026-10-17T10:00:00Z",
+///             "last_active_at": "2026-10-17T12:30:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn list_sessions<U, S, C, R, T>(
+    req: HttpRequest,
+    state: web::Data<AppState<U, S, C, R, T>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    U: UserRepository + 'static,
+    S: SmsServiceTrait + 'static,
+    C: CacheServiceTrait + 'static,
+    R: RateLimiterTrait + 'static,
+    T: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.auth_service.list_sessions(auth.user_id).await {
+        Ok(sessions) => {
+            let sessions: Vec<SessionResponse> = sessions
+                .into_iter()
+                .map(|session| SessionResponse {
+                    current: auth.session_id.as_deref() == Some(session.token_family.as_str()),
+                    session_id: session.token_family,
+                    device: session.device,
+                    ip_address: session.ip_address.as_deref().map(AuditLog::mask_ip),
+                    location: session.country,
+                    created_at: session.created_at,
+                    last_active_at: session.last_activity_at,
+                })
+                .collect();
+
+            HttpResponse::Ok().json(SessionListResponse {
+                total: sessions.len(),
+                sessions,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/auth/sessions/{session_id}
+///
+/// Signs one of the user's sessions out. Its refresh tokens are revoked at
+/// once and its access tokens are rejected from then on.
+/// Requires authentication via Bearer token in Authorization header.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "message": "The session has been signed out"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: The user has no session with this ID
+pub async fn sign_out_session<U, S, C, R, T>(
+    req: HttpRequest,
+    state: web::Data<AppState<U, S, C, R, T>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+) -> HttpResponse
+where
+    U: UserRepository + 'static,
+    S: SmsServiceTrait + 'static,
+    C: CacheServiceTrait + 'static,
+    R: RateLimiterTrait + 'static,
+    T: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+    let session_id = path.into_inner();
+
+    let client_ip = extract_client_ip(&req);
+    let user_agent = extract_user_agent(&req);
+
+    match state
+        .auth_service
+        .sign_out_session(auth.user_id, &session_id, Some(client_ip), user_agent)
+        .await
+    {
+        Ok(()) => {
+            let message = match lang {
+                Language::English => "The session has been signed out",
+                Language::Chinese => "该会话已被登出",
+            };
+
+            HttpResponse::Ok().json(SignOutSessionResponse {
+                message: message.to_string(),
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Extract client IP address from request
+fn extract_client_ip(req: &HttpRequest) -> String {
+    // Try to get IP from X-Forwarded-For header (for reverse proxy scenarios)
+    if let Some(forwarded_for) = req.headers().get("X-Forwarded-For") {
+        if let Ok(forwarded_str) = forwarded_for.to_str() {
+            // Take the first IP from the comma-separated list
+            if let Some(ip) = forwarded_str.split(',').next() {
+                return ip.trim().to_string();
+            }
+        }
+    }
+
+    // Try to get IP from X-Real-IP header
+    if let Some(real_ip) = req.headers().get("X-Real-IP") {
+        if let Ok(ip_str) = real_ip.to_str() {
+            return ip_str.to_string();
+        }
+    }
+
+    // Fall back to connection info
+    req.connection_info()
+        .peer_addr()
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Extract user agent from request headers
+fn extract_user_agent(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("User-Agent")
+        .and_then(|ua| ua.to_str().ok())
+        .map(|s| s.to_string())
+}
@@ -3,14 +3,17 @@ use validator::Validate;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::dto::auth::{VerifyCodeRequest, AuthResponse};
+use crate::dto::auth::{AttributionParams, VerifyCodeRequest, AuthResponse};
 use crate::handlers::error_standard::{to_standard_response, extract_language};
+use crate::middleware::attack_guard::record_target_phone;
+use crate::middleware::auth::extract_device_fingerprint;
 use crate::middleware::error_handler::ErrorHandlingExt;
 
 use re_core::services::auth::AuthService;
 use re_core::repositories::{UserRepository, TokenRepository};
 use re_core::services::verification::{SmsServiceTrait, CacheServiceTrait};
 use re_core::services::auth::{RateLimiterTrait, mask_phone};
+use re_core::domain::entities::attribution::RegistrationAttribution;
 use re_core::errors::DomainError;
 use re_shared::types::response::{DetailedResponse, ResponseStatus, ResponseMeta};
 use chrono::Utc;
@@ -20,12 +23,23 @@ use std::collections::HashMap;
 ///
 /// Verifies the OTP code sent to the phone number and returns authentication tokens.
 ///
+/// When the code registers a new user who consented to marketing, the UTM
+/// parameters and referrer are recorded to attribute the registration to a
+/// campaign. They can be sent in the body or as `X-Utm-*`,
+/// `X-Attribution-Referrer` and `X-Marketing-Consent` headers.
+///
 /// # Request Body
 /// 
 /// ```json
 /// {
 ///     "phone": "+1234567890",
-///     "code": "123456"
+///     "code": "123456",
+///     "marketing_consent": true,
+///     "attribution": {
+///         "utm_source": "google",
+///         "utm_medium": "cpc",
+///         "utm_campaign": "spring_kitchens"
+///     }
 /// }
 /// ```
 ///
@@ -149,6 +163,7 @@ where
     } else {
         request.phone.clone()
     };
+    record_target_phone(&req, &phone);
 
     // Log verification attempt for security audit
     log::info!(
@@ -159,8 +174,21 @@ where
         device_info
     );
 
+    let attribution = extract_attribution(&req, &request);
+
     // Call the auth service to verify the code
-    match state.auth_service.verify_code(&phone, &request.code, Some(client_ip.clone()), user_agent, device_info.clone()).await {
+    match state
+        .auth_service
+        .verify_code_with_attribution(
+            &phone,
+            &request.code,
+            Some(client_ip.clone()),
+            user_agent,
+            device_info.clone(),
+            Some(attribution),
+        )
+        .await
+    {
         Ok(auth_result) => {
             // Log successful verification
             log::info!(
@@ -179,6 +207,8 @@ where
                     expires_in: auth_result.expires_in,
                     user_type: auth_result.user_type,
                     requires_type_selection: auth_result.requires_type_selection,
+                    requires_step_up: auth_result.requires_step_up,
+                    compliance_required: auth_result.compliance_required,
                 }),
                 meta: ResponseMeta {
                     timestamp: Utc::now(),
@@ -267,14 +297,32 @@ fn extract_user_agent(req: &HttpRequest) -> Option<String> {
 }
 
 /// Extract device information from request headers
+///
+/// Uses the same fingerprint the auth middleware checks access tokens against.
 fn extract_device_info(req: &HttpRequest) -> Option<String> {
-    // Try to get device info from custom header first
-    if let Some(device_header) = req.headers().get("X-Device-Info") {
-        if let Ok(device_str) = device_header.to_str() {
-            return Some(device_str.to_string());
-        }
+    extract_device_fingerprint(req.headers())
+}
+/// Extract registration attribution from the request body, falling back to headers
+fn extract_attribution(req: &HttpRequest, request: &VerifyCodeRequest) -> RegistrationAttribution {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    let params = request.attribution.clone().unwrap_or_default();
+    let AttributionParams { utm_source, utm_medium, utm_campaign, utm_term, utm_content, referrer } = params;
+
+    RegistrationAttribution {
+        utm_source: utm_source.or_else(|| header("X-Utm-Source")),
+        utm_medium: utm_medium.or_else(|| header("X-Utm-Medium")),
+        utm_campaign: utm_campaign.or_else(|| header("X-Utm-Campaign")),
+        utm_term: utm_term.or_else(|| header("X-Utm-Term")),
+        utm_content: utm_content.or_else(|| header("X-Utm-Content")),
+        referrer: referrer.or_else(|| header("X-Attribution-Referrer")),
+        marketing_consent: request
+            .marketing_consent
+            .or_else(|| header("X-Marketing-Consent").map(|value| value.eq_ignore_ascii_case("true")))
+            .unwrap_or(false),
     }
-    
-    // Fall back to User-Agent if no specific device info
-    extract_user_agent(req)
-}
\ No newline at end of file
+}
@@ -0,0 +1,9 @@
+//! Development route handlers
+//!
+//! This module contains endpoints that help client teams during
+//! development:
+//! - Listing the validation error codes with their localized messages
+//!
+//! The handlers answer 404 in production.
+
+pub mod validation_codes;
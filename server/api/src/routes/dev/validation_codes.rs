@@ -0,0 +1,86 @@
+use actix_web::{web, HttpResponse};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use re_shared::config::environment::Environment;
+
+use crate::dto::validation::{ValidationCode, VALIDATION_CODES};
+use crate::handlers::error::Language;
+use crate::i18n::get_error_message;
+
+/// A validation error code with its status and message in every locale
+#[derive(Debug, Serialize)]
+pub struct ValidationCodeEntry {
+    #[serde(flatten)]
+    pub code: ValidationCode,
+    pub http_status: Option<u16>,
+    /// Message template per locale code, with `{param}` placeholders
+    pub messages: BTreeMap<&'static str, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationCodeListResponse {
+    pub validation_codes: Vec<ValidationCodeEntry>,
+    pub total: usize,
+}
+
+/// Build the catalog entries with their localized message templates
+pub fn validation_code_entries() -> Vec<ValidationCodeEntry> {
+    VALIDATION_CODES
+        .iter()
+        .map(|code| {
+            let mut http_status = None;
+            let mut messages = BTreeMap::new();
+            for lang in [Language::English, Language::Chinese] {
+                if let Some((_, message, status)) = get_error_message("validation", code.code, lang) {
+                    http_status = Some(status);
+                    messages.insert(lang.locale_code(), message);
+                }
+            }
+            ValidationCodeEntry { code: *code, http_status, messages }
+        })
+        .collect()
+}
+
+/// Handler for GET /api/v1/dev/validation-codes
+///
+/// Lists every validation error code request bodies can fail with, the
+/// `validator` rules producing it, the placeholders of its message and the
+/// message template in each locale, so mobile apps can map codes to their
+/// own copy. Not available in production.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "validation_codes": [
+///         {
+///             "code": "out_of_range",
+///             "params": ["field", "min", "max"],
+///             "rules": ["range", "length"],
+///             "description": "A number, or the length of a text or list, is outside the allowed bounds",
+///             "http_status": 400,
+///             "messages": {
+///                 "en-US": "Field {field} out of range (min: {min}, max: {max})",
+///                 "zh-CN": "字段{field}超出范围（最小值：{min}，最大值：{max}）"
+///             }
+///         }
+///     ],
+///     "total": 10
+/// }
+/// ```
+///
+/// ## Errors
+/// - 404 Not Found: The server runs in production
+pub async fn list_validation_codes(environment: web::Data<Environment>) -> HttpResponse {
+    if environment.is_production() {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let validation_codes = validation_code_entries();
+    HttpResponse::Ok().json(ValidationCodeListResponse {
+        total: validation_codes.len(),
+        validation_codes,
+    })
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use async_trait::async_trait;
+use chrono::Utc;
+
+use re_infra::database::{DatabaseHealthConfig, DatabasePool};
+use re_shared::types::{HealthResponse, HealthStatus, ServiceHealth};
+
+use crate::lifecycle::DrainState;
+
+/// A dependency reported by the health endpoint
+#[async_trait]
+pub trait DependencyHealth: Send + Sync {
+    /// Check the dependency
+    async fn health(&self) -> ServiceHealth;
+}
+
+#[async_trait]
+impl DependencyHealth for DatabasePool {
+    async fn health(&self) -> ServiceHealth {
+        self.health_check_with(&DatabaseHealthConfig::from_env()).await.into()
+    }
+}
+
+/// Dependencies checked by GET /health, by name
+#[derive(Default)]
+pub struct HealthState {
+    dependencies: Vec<(String, Arc<dyn DependencyHealth>)>,
+}
+
+impl HealthState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report a dependency under the given name
+    pub fn with_dependency(mut self, name: impl Into<String>, dependency: Arc<dyn DependencyHealth>) -> Self {
+        self.dependencies.push((name.into(), dependency));
+        self
+    }
+}
+
+/// Handler for GET /health
+///
+/// Checks every dependency and reports the worst status as the overall
+/// status, so load balancers can tell a slow dependency from a missing
+/// one. A degraded instance still answers 200 OK; an unhealthy one
+/// answers 503 Service Unavailable.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "status": "degraded",
+///     "services": {
+///         "database": {
+///             "status": "degraded",
+///             "message": "Query took 412 ms, above 250 ms",
+///             "response_time_ms": 412
+///         }
+///     },
+///     "timestamp": "2025-08-14T10:00:00Z",
+///     "version": "0.1.0"
+/// }
+/// ```
+///
+/// ## Unhealthy (503 Service Unavailable)
+/// Same body, with `"status": "unhealthy"`
+pub async fn health(state: web::Data<HealthState>) -> HttpResponse {
+    let mut services = HashMap::new();
+    for (name, dependency) in &state.dependencies {
+        services.insert(name.clone(), dependency.health().await);
+    }
+
+    let status = services
+        .values()
+        .map(|service| service.status)
+        .max_by_key(|status| severity(*status))
+        .unwrap_or(HealthStatus::Healthy);
+
+    let response = HealthResponse {
+        status,
+        services,
+        timestamp: Utc::now(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+
+    let mut builder = match status {
+        HealthStatus::Unhealthy => HttpResponse::ServiceUnavailable(),
+        _ => HttpResponse::Ok(),
+    };
+    builder
+        .insert_header(("Cache-Control", "no-store"))
+        .json(response)
+}
+
+fn severity(status: HealthStatus) -> u8 {
+    match status {
+        HealthStatus::Healthy => 0,
+        HealthStatus::Degraded => 1,
+        HealthStatus::Unhealthy => 2,
+    }
+}
+
+/// Handler for GET /health/ready
+///
+/// Tells load balancers whether to route new requests to this instance.
+/// It reports not-ready as soon as the instance starts draining, while
+/// in-flight and straggling requests are still served.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "status": "ready"
+/// }
+/// ```
+///
+/// ## Draining (503 Service Unavailable)
+/// ```json
+/// {
+///     "status": "draining",
+///     "since": "2025-08-14T10:00:00Z"
+/// }
+/// ```
+pub async fn readiness(state: web::Data<DrainState>) -> HttpResponse {
+    let status = state.status();
+    if status.draining {
+        return HttpResponse::ServiceUnavailable()
+            .insert_header(("Cache-Control", "no-store"))
+            .json(serde_json::json!({
+                "status": "draining",
+                "since": status.since,
+            }));
+    }
+
+    HttpResponse::Ok()
+        .insert_header(("Cache-Control", "no-store"))
+        .json(serde_json::json!({
+            "status": "ready",
+        }))
+}
@@ -0,0 +1,171 @@
+use actix_web::{web, HttpResponse};
+use std::sync::Arc;
+
+use re_infra::cache::CacheMetrics;
+use re_infra::database::DatabasePool;
+use re_infra::sms::SmsDryRunLog;
+
+use crate::dto::internal::{
+    CacheStatisticsResponse, DryRunMessageResponse, PoolStatisticsResponse, SmsDryRunQuery,
+    SmsDryRunResponse,
+};
+
+/// Handler for GET /internal/db-stats
+///
+/// Reports the state of the database connection pool, so `max_connections`
+/// can be tuned from data. A connection is acquired on every call to
+/// measure the current acquire wait. Internal endpoints are only reachable
+/// by other services and must be listed in `MTLS_REQUIRED_PATHS`.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "connections": 12,
+///     "active_connections": 9,
+///     "idle_connections": 3,
+///     "max_connections": 20,
+///     "utilization_percent": 45,
+///     "acquire_wait_ms": 0.41,
+///     "circuit": "closed"
+/// }
+/// ```
+///
+/// `acquire_wait_ms` is `null` if no connection could be acquired.
+/// `circuit` is `open` while database access fails fast after repeated
+/// connection failures, and `half_open` while a trial request is running.
+pub async fn db_stats(pool: web::Data<DatabasePool>) -> HttpResponse {
+    let acquire_wait = pool.sample_acquire_wait().await;
+    if let Err(e) = &acquire_wait {
+        tracing::warn!("Failed to sample database acquire wait: {}", e);
+    }
+
+    let mut stats = pool.get_statistics();
+    stats.acquire_wait = acquire_wait.ok();
+
+    HttpResponse::Ok()
+        .insert_header(("Cache-Control", "no-store"))
+        .json(PoolStatisticsResponse::from(stats))
+}
+
+/// Caches reported by GET /internal/cache-stats
+#[derive(Default)]
+pub struct CacheStatsState {
+    caches: Vec<(&'static str, Arc<CacheMetrics>)>,
+}
+
+impl CacheStatsState {
+    /// Create a state reporting no cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report the metrics of a cache under a name, e.g. `redis`
+    pub fn with_cache(mut self, name: &'static str, metrics: Arc<CacheMetrics>) -> Self {
+        self.caches.push((name, metrics));
+        self
+    }
+}
+
+/// Handler for GET /internal/cache-stats
+///
+/// Reports the calls, hits, misses, errors and latency of every cache
+/// operation run by this instance since it started, so TTLs and Redis
+/// pool sizes can be tuned from data. Internal endpoints are only
+/// reachable by other services and must be listed in `MTLS_REQUIRED_PATHS`.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "caches": {
+///         "redis": {
+///             "get": {
+///                 "calls": 1200,
+///                 "hits": 1100,
+///                 "misses": 100,
+///                 "errors": 0,
+///                 "hit_ratio": 0.9166,
+///                 "average_duration_ms": 0.42,
+///                 "max_duration_ms": 12.5,
+///                 "latency_histogram": [
+///                     { "le_ms": 0.5, "count": 950 },
+///                     { "le_ms": 1.0, "count": 230 },
+///                     { "le_ms": null, "count": 0 }
+///                 ]
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// `hit_ratio` is `null` for operations that are not lookups. Each bucket
+/// of `latency_histogram` counts the calls slower than the previous bound
+/// and no slower than `le_ms`; the last one counts the slower calls.
+pub async fn cache_stats(state: web::Data<CacheStatsState>) -> HttpResponse {
+    let response = state
+        .caches
+        .iter()
+        .fold(CacheStatisticsResponse::default(), |response, (name, metrics)| {
+            response.with_cache(name, metrics.snapshot())
+        });
+
+    HttpResponse::Ok()
+        .insert_header(("Cache-Control", "no-store"))
+        .json(response)
+}
+
+/// Number of messages reported by GET /internal/sms/dry-run by default
+const DEFAULT_DRY_RUN_LIMIT: usize = 20;
+
+/// Messages recorded by SMS providers in dry-run mode
+pub struct SmsDryRunState {
+    pub log: Arc<SmsDryRunLog>,
+}
+
+/// Handler for GET /internal/sms/dry-run
+///
+/// Reports the messages the SMS providers recorded instead of sending them
+/// while `SMS_DRY_RUN` is set, so staging test suites can complete phone
+/// verification. The route must only be registered outside production.
+///
+/// # Query Parameters
+/// - `phone`: only report messages to this number, in E.164 format
+/// - `limit`: maximum number of messages (default: 20)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "messages": [
+///         {
+///             "message_id": "dry-run-6f1c...",
+///             "provider": "Twilio",
+///             "phone_number": "+61412345678",
+///             "sender_id": "+15005550006",
+///             "body": "Your RenovEasy verification code is: 123456. ...",
+///             "code": "123456",
+///             "recorded_at": "2026-10-17T09:30:00Z"
+///         }
+///     ]
+/// }
+/// ```
+pub async fn sms_dry_run(
+    state: web::Data<SmsDryRunState>,
+    query: web::Query<SmsDryRunQuery>,
+) -> HttpResponse {
+    let limit = query.limit.unwrap_or(DEFAULT_DRY_RUN_LIMIT);
+    let messages = state
+        .log
+        .recent(query.phone.as_deref(), limit)
+        .into_iter()
+        .map(DryRunMessageResponse::from)
+        .collect();
+
+    HttpResponse::Ok()
+        .insert_header(("Cache-Control", "no-store"))
+        .json(SmsDryRunResponse { messages })
+}
@@ -0,0 +1,143 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use validator::Validate;
+
+use crate::dto::legal::{
+    AcceptLegalDocumentRequest, LegalAcceptanceResponse, LegalDocumentListResponse,
+    LegalDocumentResponse, PendingLegalDocumentsQuery,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::LegalDocumentRepository;
+
+use super::{parse_kind, requested_language, LegalDocumentState};
+
+/// Handler for GET /api/v1/legal/pending
+///
+/// Lists the documents in effect for the user's market that the signed-in
+/// user has not accepted, in the exact version they must accept. The app
+/// shows these before letting the user continue.
+///
+/// # Query Parameters
+/// - `lang`: Language code (default: the `Accept-Language` header, then `en`)
+/// - `market`: Market code (ISO 3166-1 alpha-2) of the user
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "documents": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "kind": "privacy",
+///             "market": null,
+///             "version": 2,
+///             "language": "en",
+///             "title": "Privacy Policy",
+///             "body": "# Privacy Policy\n...",
+///             "effective_from": "2026-11-01T00:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn list_pending<L>(
+    req: HttpRequest,
+    state: web::Data<LegalDocumentState<L>>,
+    auth: AuthContext,
+    query: web::Query<PendingLegalDocumentsQuery>,
+) -> HttpResponse
+where
+    L: LegalDocumentRepository + 'static,
+{
+    let lang = extract_language(&req);
+    let language = requested_language(&req, query.lang.as_deref());
+
+    match state
+        .legal_document_service
+        .pending(auth.user_id, query.market.as_deref(), &language, Utc::now())
+        .await
+    {
+        Ok(documents) => {
+            let documents: Vec<LegalDocumentResponse> = documents.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(LegalDocumentListResponse {
+                total: documents.len(),
+                documents,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/legal/{doc}/accept
+///
+/// Records that the signed-in user accepted the version of a document in
+/// effect for their market. Accepting the same version again is harmless.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "version": 3,
+///     "market": "AU",
+///     "language": "en"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// ```json
+/// {
+///     "document_id": "550e8400-e29b-41d4-a716-446655440000",
+///     "kind": "terms",
+///     "market": "AU",
+///     "version": 3,
+///     "accepted_at": "2026-11-01T09:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The version is not the one in effect; the app should
+///   fetch and show the current version
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: Unknown document, or no version in effect
+pub async fn accept_document<L>(
+    req: HttpRequest,
+    state: web::Data<LegalDocumentState<L>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<AcceptLegalDocumentRequest>,
+) -> HttpResponse
+where
+    L: LegalDocumentRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let kind = match parse_kind(&path) {
+        Ok(kind) => kind,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+    let language = requested_language(&req, request.language.as_deref());
+
+    match state
+        .legal_document_service
+        .accept(auth.user_id, kind, request.market.as_deref(), request.version, &language)
+        .await
+    {
+        Ok(acceptance) => HttpResponse::Created().json(LegalAcceptanceResponse::from(acceptance)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
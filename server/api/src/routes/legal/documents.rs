@@ -0,0 +1,109 @@
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+
+use crate::dto::legal::{LegalDocumentQuery, LegalDocumentResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::repositories::LegalDocumentRepository;
+
+use super::{parse_kind, requested_language, LegalDocumentState};
+
+/// Cache-Control of the version in effect, short so new versions are picked up soon after taking effect
+const CURRENT_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// Cache-Control of an exact version, whose text never changes
+const PINNED_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Handler for GET /api/v1/legal/{doc}
+///
+/// Serves the terms of service (`terms`) or privacy policy (`privacy`).
+/// Without `version`, the version in effect for the market is served,
+/// falling back to the version for every market. The translation falls
+/// back to English. The response carries an `ETag` identifying the exact
+/// text and honours `If-None-Match`.
+///
+/// # Query Parameters
+/// - `lang`: Language code (default: the `Accept-Language` header, then `en`)
+/// - `market`: Market code (ISO 3166-1 alpha-2)
+/// - `version`: Exact version to serve; `market` must then be the version's market
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "kind": "terms",
+///     "market": "AU",
+///     "version": 3,
+///     "language": "en",
+///     "title": "Terms of Service",
+///     "body": "# Terms of Service\n...",
+///     "effective_from": "2026-11-01T00:00:00Z"
+/// }
+/// ```
+///
+/// ## Not Modified (304)
+/// The `If-None-Match` header matches the document's `ETag`
+///
+/// ## Errors
+/// - 404 Not Found: Unknown document, or no such version in effect
+pub async fn get_document<L>(
+    req: HttpRequest,
+    state: web::Data<LegalDocumentState<L>>,
+    path: web::Path<String>,
+    query: web::Query<LegalDocumentQuery>,
+) -> HttpResponse
+where
+    L: LegalDocumentRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let kind = match parse_kind(&path) {
+        Ok(kind) => kind,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+    let language = requested_language(&req, query.lang.as_deref());
+    let market = query.market.as_deref();
+
+    let (result, cache_control) = match query.version {
+        Some(version) => (
+            state.legal_document_service.version(kind, market, version, &language).await,
+            PINNED_CACHE_CONTROL,
+        ),
+        None => (
+            state.legal_document_service.current(kind, market, &language, Utc::now()).await,
+            CURRENT_CACHE_CONTROL,
+        ),
+    };
+
+    let document = match result {
+        Ok(document) => document,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    let etag = document.etag();
+    let not_modified = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"));
+
+    let mut response = if not_modified {
+        HttpResponse::NotModified()
+    } else {
+        HttpResponse::Ok()
+    };
+    response
+        .insert_header((header::ETAG, etag))
+        .insert_header((header::CACHE_CONTROL, cache_control))
+        .insert_header((header::CONTENT_LANGUAGE, document.language.clone()))
+        .insert_header((header::VARY, "Accept-Language"));
+
+    if not_modified {
+        response.finish()
+    } else {
+        response.json(LegalDocumentResponse::from(document))
+    }
+}
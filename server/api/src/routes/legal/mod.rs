@@ -0,0 +1,49 @@
+//! Legal document route handlers
+//!
+//! This module contains endpoints for terms of service and privacy policies:
+//! - Serving the version in effect, or an exact version, in the user's language
+//! - Listing the documents a signed-in user still has to accept
+//! - Recording that a user accepted the version they were shown
+//!
+//! `/api/v1/legal/pending` must be registered before `/api/v1/legal/{doc}`.
+
+pub mod acceptances;
+pub mod documents;
+
+use actix_web::HttpRequest;
+use std::sync::Arc;
+
+use re_core::domain::entities::legal_document::{normalize_language, LegalDocumentKind, DEFAULT_LEGAL_LANGUAGE};
+use re_core::errors::DomainError;
+use re_core::repositories::LegalDocumentRepository;
+use re_core::services::legal_document::LegalDocumentService;
+
+/// Application state for legal document routes
+pub struct LegalDocumentState<L>
+where
+    L: LegalDocumentRepository + 'static,
+{
+    pub legal_document_service: Arc<LegalDocumentService<L>>,
+}
+
+/// Parse the document kind of a path segment
+pub fn parse_kind(doc: &str) -> Result<LegalDocumentKind, DomainError> {
+    LegalDocumentKind::from_str(doc).ok_or_else(|| DomainError::NotFound {
+        resource: format!("Legal document {}", doc),
+    })
+}
+
+/// Language a document is requested in
+///
+/// An explicit `lang` parameter wins over the first `Accept-Language` entry.
+pub fn requested_language(req: &HttpRequest, lang: Option<&str>) -> String {
+    let header = req
+        .headers()
+        .get("Accept-Language")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split([',', ';']).next());
+
+    lang.and_then(normalize_language)
+        .or_else(|| header.and_then(normalize_language))
+        .unwrap_or_else(|| DEFAULT_LEGAL_LANGUAGE.to_string())
+}
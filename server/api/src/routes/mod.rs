@@ -1 +1,16 @@
-pub mod auth;
\ No newline at end of file
+pub mod admin;
+pub mod auth;
+pub mod dev;
+pub mod health;
+pub mod internal;
+pub mod legal;
+pub mod notifications;
+pub mod oauth;
+pub mod orders;
+pub mod organizations;
+pub mod partner;
+pub mod payments;
+pub mod payouts;
+pub mod users;
+pub mod webhooks;
+pub mod wallet;
@@ -0,0 +1,45 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::admin::campaigns::CampaignState;
+
+use re_core::repositories::{CampaignRepository, NotificationPreferencesRepository};
+use re_core::services::campaign::NotificationSenderTrait;
+
+/// Handler for POST /api/v1/notifications/campaigns/{delivery_id}/open
+///
+/// Records that the signed-in user opened a campaign notification.
+/// `{delivery_id}` is the ID attached to the notification when it was sent.
+/// Repeated calls are accepted but only the first open is counted.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: No delivery with this ID belongs to the user
+pub async fn record_open<C, P, N>(
+    req: HttpRequest,
+    state: web::Data<CampaignState<C, P, N>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .campaign_service
+        .record_open(path.into_inner(), auth.user_id)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
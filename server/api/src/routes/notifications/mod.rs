@@ -0,0 +1,8 @@
+//! Notification route handlers for signed-in users
+//!
+//! This module contains endpoints that notification clients call:
+//! - Reporting that a campaign notification was opened
+//! - Scheduling, listing and cancelling reminders to the user's own phone
+
+pub mod campaigns;
+pub mod scheduled_messages;
@@ -0,0 +1,188 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::dto::notification::{
+    ScheduleMessageRequest, ScheduledMessageResponse, ScheduledMessagesResponse,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{NotificationPreferencesRepository, ScheduledMessageRepository};
+use re_core::services::campaign::UserPhoneDirectoryTrait;
+use re_core::services::notification::MessageScheduler;
+use re_core::services::verification::SmsServiceTrait;
+
+/// Application state for scheduled message routes
+pub struct ScheduledMessageState<M, P, S>
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    pub scheduler: Arc<MessageScheduler<M, P, S>>,
+    /// Reveals the signed-in user's number, which is only stored hashed
+    pub phones: Arc<dyn UserPhoneDirectoryTrait>,
+}
+
+/// Handler for POST /api/v1/notifications/scheduled-messages
+///
+/// Schedules a reminder to the signed-in user's own phone. The delivery
+/// time is moved out of the user's quiet hours, so the response may show a
+/// later `deliver_at` than the requested `send_at`.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "template": "worker_arrival_reminder",
+///     "params": { "worker_name": "Sam", "arrival_time": "9:00am" },
+///     "send_at": "2026-10-18T08:00:00Z"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "template": "worker_arrival_reminder",
+///     "category": "reminder",
+///     "params": { "worker_name": "Sam", "arrival_time": "9:00am" },
+///     "requested_for": "2026-10-18T08:00:00Z",
+///     "deliver_at": "2026-10-18T08:00:00Z",
+///     "status": "pending",
+///     "created_at": "2026-10-17T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: `send_at` is not in the future, a template parameter
+///   is missing, no phone number is on file or reminders are turned off
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn schedule_message<M, P, S>(
+    req: HttpRequest,
+    state: web::Data<ScheduledMessageState<M, P, S>>,
+    auth: AuthContext,
+    request: web::Json<ScheduleMessageRequest>,
+) -> HttpResponse
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    let lang = extract_language(&req);
+    let request = request.into_inner();
+
+    if request.send_at <= Utc::now() {
+        let error = DomainError::Validation {
+            message: "send_at must be in the future".to_string(),
+        };
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let phone = match state.phones.phone_for(auth.user_id).await {
+        Ok(Some(phone)) => phone,
+        Ok(None) => {
+            let error = DomainError::BusinessRule {
+                message: "No phone number is on file for this account".to_string(),
+            };
+            return handle_domain_error_with_lang(&error, lang);
+        }
+        Err(e) => {
+            let error = DomainError::Internal {
+                message: format!("Failed to look up phone number: {}", e),
+            };
+            return handle_domain_error_with_lang(&error, lang);
+        }
+    };
+
+    match state
+        .scheduler
+        .schedule(auth.user_id, &phone, request.template, request.params, request.send_at)
+        .await
+    {
+        Ok(message) => HttpResponse::Created().json(ScheduledMessageResponse::from(message)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/notifications/scheduled-messages
+///
+/// Lists the signed-in user's messages that have not been sent yet,
+/// soonest first.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "messages": [ { "id": "...", "status": "pending", ... } ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn list_scheduled_messages<M, P, S>(
+    req: HttpRequest,
+    state: web::Data<ScheduledMessageState<M, P, S>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.scheduler.list_pending(auth.user_id).await {
+        Ok(mut messages) => {
+            messages.sort_by_key(|message| message.deliver_at);
+            let messages: Vec<ScheduledMessageResponse> = messages.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(ScheduledMessagesResponse {
+                total: messages.len(),
+                messages,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/notifications/scheduled-messages/{id}
+///
+/// Cancels one of the signed-in user's pending messages. Messages that
+/// were already sent or cancelled are left as they are.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: The user has no message with this ID
+pub async fn cancel_scheduled_message<M, P, S>(
+    req: HttpRequest,
+    state: web::Data<ScheduledMessageState<M, P, S>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .scheduler
+        .cancel_for_user(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
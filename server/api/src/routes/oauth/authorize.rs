@@ -0,0 +1,198 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::oauth::{
+    AuthorizeQuery, AuthorizeResponse, ConsentListResponse, ConsentPromptResponse, ConsentResponse,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::repositories::{OAuthRepository, TokenRepository};
+use re_core::services::oauth::AuthorizationRequest;
+
+use super::OAuthState;
+
+/// Convert the forwarded query into an authorization request
+fn authorization_request(query: AuthorizeQuery) -> Result<AuthorizationRequest, DomainError> {
+    if query.response_type != "code" {
+        return Err(DomainError::ValidationErr(DomainValidationError::InvalidFormat {
+            field: "response_type".to_string(),
+        }));
+    }
+
+    Ok(AuthorizationRequest {
+        client_id: query.client_id,
+        redirect_uri: query.redirect_uri,
+        scope: query.scope,
+        state: query.state,
+        code_challenge: query.code_challenge,
+        code_challenge_method: query.code_challenge_method,
+    })
+}
+
+/// Handler for GET /api/v1/oauth/authorize
+///
+/// Partners send users to the app with a standard authorization request;
+/// the app forwards its query here to show the consent screen. Nothing is
+/// granted until the user approves.
+///
+/// # Query Parameters
+/// - `response_type`: Must be `code`
+/// - `client_id`, `redirect_uri`, `scope`, `state`: As sent by the partner
+/// - `code_challenge`, `code_challenge_method`: Optional PKCE challenge; only `S256`
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "client_id": "rec_5f0c6a1d2b3e4f5a6b7c8d9e0f1a2b3c",
+///     "client_name": "Tile Supplies Pty Ltd",
+///     "scopes": ["profile:read", "orders:read"],
+///     "previously_granted": false
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown client, unregistered redirect URI, or scopes not allowed
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn consent_prompt<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+    query: web::Query<AuthorizeQuery>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let request = match authorization_request(query.into_inner()) {
+        Ok(request) => request,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    match state.oauth_service.prepare_authorization(auth.user_id, &request).await {
+        Ok(prompt) => HttpResponse::Ok().json(ConsentPromptResponse::from(prompt)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/oauth/authorize
+///
+/// Records the user's approval and returns the partner URL to open, which
+/// carries a single-use authorization code valid for ten minutes. Takes
+/// the same query parameters as the consent prompt.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "redirect_to": "https://tiles.example.com/renoveasy/callback?code=Zm9v...&state=af0ifjsldkj"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Unknown client, unregistered redirect URI, or scopes not allowed
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn approve<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+    query: web::Query<AuthorizeQuery>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let request = match authorization_request(query.into_inner()) {
+        Ok(request) => request,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+
+    match state.oauth_service.approve_authorization(auth.user_id, &request).await {
+        Ok(grant) => HttpResponse::Ok().json(AuthorizeResponse {
+            redirect_to: grant.redirect_url(),
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/oauth/consents
+///
+/// Lists the partner applications the user has approved.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "consents": [
+///         {
+///             "client_id": "rec_5f0c6a1d2b3e4f5a6b7c8d9e0f1a2b3c",
+///             "scope": "profile:read orders:read",
+///             "granted_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn list_consents<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.oauth_service.user_consents(auth.user_id).await {
+        Ok(consents) => {
+            let consents: Vec<ConsentResponse> = consents.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(ConsentListResponse {
+                total: consents.len(),
+                consents,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/oauth/consents/{client_id}
+///
+/// Revokes the user's approval of a partner application. Codes not yet
+/// exchanged become unusable; access tokens already issued expire normally.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: The user has not approved this partner
+pub async fn revoke_consent<O, R>(
+    req: HttpRequest,
+    state: web::Data<OAuthState<O, R>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.oauth_service.revoke_consent(auth.user_id, &path.into_inner()).await {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
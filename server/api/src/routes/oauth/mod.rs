@@ -0,0 +1,26 @@
+//! OAuth2 authorization server route handlers
+//!
+//! This module contains endpoints for partner integrations:
+//! - The token endpoint for the client credentials and authorization code grants
+//! - The consent prompt and approval used by the app on behalf of the signed-in user
+//! - Listing and revoking the partners a user has approved
+//!
+//! Partner applications are registered by administrators; see
+//! [`crate::routes::admin::oauth_clients`].
+
+pub mod authorize;
+pub mod token;
+
+use std::sync::Arc;
+
+use re_core::repositories::{OAuthRepository, TokenRepository};
+use re_core::services::oauth::OAuthService;
+
+/// Application state for OAuth routes
+pub struct OAuthState<O, R>
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    pub oauth_service: Arc<OAuthService<O, R>>,
+}
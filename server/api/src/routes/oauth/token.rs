@@ -0,0 +1,111 @@
+use actix_web::{http::header, web, HttpResponse};
+
+use crate::dto::oauth::{OAuthErrorResponse, TokenRequest, TokenResponse};
+
+use re_core::repositories::{OAuthRepository, TokenRepository};
+use re_core::services::oauth::OAuthError;
+
+use super::OAuthState;
+
+/// Handler for POST /api/v1/oauth/token
+///
+/// Issues a scope-limited access token to a partner application. The body
+/// is form encoded and carries the client credentials. Tokens are only
+/// accepted by the partner API under `/api/v1/partner`.
+///
+/// # Request Body
+///
+/// Client credentials grant:
+/// ```text
+/// grant_type=client_credentials&client_id=rec_5f0c...&client_secret=...&scope=orders:read
+/// ```
+///
+/// Authorization code grant:
+/// ```text
+/// grant_type=authorization_code&client_id=rec_5f0c...&client_secret=...
+///     &code=...&redirect_uri=https://tiles.example.com/renoveasy/callback&code_verifier=...
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "access_token": "eyJhbGciOiJSUzI1NiIs...",
+///     "token_type": "Bearer",
+///     "expires_in": 3600,
+///     "scope": "profile:read orders:read"
+/// }
+/// ```
+///
+/// ## Errors
+/// Errors use the RFC 6749 format, e.g. `{"error": "invalid_grant", "error_description": "..."}`
+/// - 400 Bad Request: `invalid_request`, `invalid_grant`, `unauthorized_client`,
+///   `invalid_scope` or `unsupported_grant_type`
+/// - 401 Unauthorized: `invalid_client`
+/// - 500 Internal Server Error: `server_error`
+pub async fn token<O, R>(
+    state: web::Data<OAuthState<O, R>>,
+    form: web::Form<TokenRequest>,
+) -> HttpResponse
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let form = form.into_inner();
+    let (client_id, client_secret) = match (form.client_id.as_deref(), form.client_secret.as_deref()) {
+        (Some(id), Some(secret)) => (id, secret),
+        _ => return oauth_error(&OAuthError::InvalidClient),
+    };
+
+    let result = match form.grant_type.as_str() {
+        "client_credentials" => {
+            state
+                .oauth_service
+                .client_credentials(client_id, client_secret, form.scope.as_deref())
+                .await
+        }
+        "authorization_code" => {
+            let (Some(code), Some(redirect_uri)) = (form.code.as_deref(), form.redirect_uri.as_deref()) else {
+                return oauth_error(&OAuthError::InvalidRequest(
+                    "code and redirect_uri are required".to_string(),
+                ));
+            };
+            state
+                .oauth_service
+                .exchange_code(client_id, client_secret, code, redirect_uri, form.code_verifier.as_deref())
+                .await
+        }
+        _ => {
+            return HttpResponse::BadRequest()
+                .insert_header((header::CACHE_CONTROL, "no-store"))
+                .json(OAuthErrorResponse {
+                    error: "unsupported_grant_type".to_string(),
+                    error_description: None,
+                })
+        }
+    };
+
+    match result {
+        Ok(token) => HttpResponse::Ok()
+            .insert_header((header::CACHE_CONTROL, "no-store"))
+            .json(TokenResponse::from(token)),
+        Err(error) => oauth_error(&error),
+    }
+}
+
+/// Build an RFC 6749 error response
+fn oauth_error(error: &OAuthError) -> HttpResponse {
+    let mut response = match error {
+        OAuthError::InvalidClient => HttpResponse::Unauthorized(),
+        OAuthError::Server(e) => {
+            log::error!("OAuth token request failed: {}", e);
+            HttpResponse::InternalServerError()
+        }
+        _ => HttpResponse::BadRequest(),
+    };
+
+    response
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(OAuthErrorResponse::from(error))
+}
@@ -0,0 +1,204 @@
+use actix_web::{http::header::USER_AGENT, web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::completion::{CompletionResponse, SignOffRequest, SubmitCompletionRequest};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::CompletionChecklistRepository;
+use re_core::services::completion::ChecklistSubmission;
+
+use super::{require_user_type, OrderState, CUSTOMER_USER_TYPE, WORKER_USER_TYPE};
+
+/// Handler for PUT /api/v1/orders/{id}/completion
+///
+/// Submits the worker's completion checklist for the job. Every item of the
+/// category's checklist must be present with its required photos, which
+/// the app uploads beforehand. The checklist can be resubmitted until the
+/// customer signs off.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "customer_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "category": "bathroom",
+///     "items": [
+///         {
+///             "key": "grout_sealed",
+///             "photos": ["uploads/orders/550e8400/grout-1.jpg"],
+///             "note": "Sealed twice around the shower"
+///         }
+///     ]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "order_id": "550e8400-e29b-41d4-a716-446655440000",
+///     "worker_id": "7c9e6679-7425-40de-944b-e07fc1f90ae7",
+///     "customer_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "template_version": 2,
+///     "items": [
+///         {
+///             "key": "grout_sealed",
+///             "label": "Grout sealed",
+///             "photos": ["uploads/orders/550e8400/grout-1.jpg"],
+///             "note": "Sealed twice around the shower"
+///         }
+///     ],
+///     "submitted_at": "2026-10-16T10:00:00Z",
+///     "sign_off": null
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: A missing or unknown item, too few photos, or the
+///   customer already signed off
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a worker
+/// - 404 Not Found: No checklist is configured, or another worker submitted
+///   the order's checklist
+pub async fn submit_completion<C>(
+    req: HttpRequest,
+    state: web::Data<OrderState<C>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<SubmitCompletionRequest>,
+) -> HttpResponse
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_user_type(&auth, WORKER_USER_TYPE) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    let submission = ChecklistSubmission {
+        order_id: path.into_inner(),
+        customer_id: request.customer_id,
+        category: request.category,
+        items: request.items.into_iter().map(Into::into).collect(),
+    };
+
+    match state.completion_service.submit(auth.user_id, submission).await {
+        Ok(checklist) => HttpResponse::Ok().json(CompletionResponse::from(checklist)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/orders/{id}/completion
+///
+/// Returns the order's completion checklist to its worker or customer,
+/// including the sign-off once given.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The checklist, in the same format as the submission response
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: No checklist was submitted, or the user is not a party to the order
+pub async fn get_completion<C>(
+    req: HttpRequest,
+    state: web::Data<OrderState<C>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.completion_service.find(auth.user_id, path.into_inner()).await {
+        Ok(checklist) => HttpResponse::Ok().json(CompletionResponse::from(checklist)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/orders/{id}/completion/sign-off
+///
+/// Records the customer's approval of the submitted checklist. The typed
+/// name, time and device are stored with a hash of the approved content
+/// and cannot be changed afterwards.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "signed_name": "Jane Citizen",
+///     "device_info": "iPhone 15 / iOS 18.1 / RenovEasy 2.4.0"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The checklist with its `sign_off`:
+/// ```json
+/// {
+///     "sign_off": {
+///         "signed_name": "Jane Citizen",
+///         "signed_at": "2026-10-16T10:05:00Z",
+///         "device_info": "iPhone 15 / iOS 18.1 / RenovEasy 2.4.0",
+///         "content_hash": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+///     }
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Missing name, or the completion was already signed off
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a customer
+/// - 404 Not Found: No checklist was submitted for the customer's order
+pub async fn sign_off_completion<C>(
+    req: HttpRequest,
+    state: web::Data<OrderState<C>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<SignOffRequest>,
+) -> HttpResponse
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_user_type(&auth, CUSTOMER_USER_TYPE) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let device_info = request.device_info.clone().or_else(|| {
+        req.headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    });
+
+    match state
+        .completion_service
+        .sign_off(auth.user_id, path.into_inner(), &request.signed_name, device_info.as_deref())
+        .await
+    {
+        Ok(checklist) => HttpResponse::Ok().json(CompletionResponse::from(checklist)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
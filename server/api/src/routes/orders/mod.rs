@@ -0,0 +1,41 @@
+//! Order route handlers for signed-in users
+//!
+//! This module contains endpoints for finishing a job:
+//! - The worker submitting the completion checklist with photos
+//! - The customer signing off the completion
+//!
+//! A signed-off completion cannot be changed and is what the invoice is
+//! generated from.
+
+pub mod completion;
+
+use std::sync::Arc;
+
+use re_core::errors::{AuthError, DomainError};
+use re_core::repositories::CompletionChecklistRepository;
+use re_core::services::completion::CompletionService;
+
+use crate::middleware::auth::AuthContext;
+
+/// User type carried in the access token of workers
+pub const WORKER_USER_TYPE: &str = "worker";
+
+/// User type carried in the access token of customers
+pub const CUSTOMER_USER_TYPE: &str = "customer";
+
+/// Application state for order routes
+pub struct OrderState<C>
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    pub completion_service: Arc<CompletionService<C>>,
+}
+
+/// Ensure the authenticated user has the given user type
+pub fn require_user_type(auth: &AuthContext, user_type: &str) -> Result<(), DomainError> {
+    if auth.user_type.as_deref() == Some(user_type) {
+        Ok(())
+    } else {
+        Err(DomainError::Auth(AuthError::InsufficientPermissions))
+    }
+}
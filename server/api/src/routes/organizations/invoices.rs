@@ -0,0 +1,132 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+use crate::dto::organization::{
+    ConsolidatedInvoiceListResponse, ConsolidatedInvoiceResponse, InvoiceListQuery,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::repositories::OrganizationRepository;
+use re_core::services::payment_method::PaymentMethodProviderTrait;
+
+use super::OrganizationBillingState;
+
+/// Default number of invoices returned by the invoice list endpoint
+const DEFAULT_INVOICE_LIMIT: usize = 12;
+
+/// Maximum number of invoices returned by the invoice list endpoint
+const MAX_INVOICE_LIMIT: usize = 60;
+
+/// Handler for GET /api/v1/organizations/{id}/invoices
+///
+/// Lists the organization's consolidated monthly invoices, most recent
+/// month first. Each invoice lists the orders completed by members in that
+/// month, with totals per member.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of invoices (default: 12, maximum: 60)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "invoices": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "period_start": "2026-09-01",
+///             "currency": "AUD",
+///             "total_amount": 120000,
+///             "member_totals": [
+///                 { "user_id": "660e8400-e29b-41d4-a716-446655440000", "amount": 120000 }
+///             ],
+///             "lines": [
+///                 {
+///                     "order_id": "770e8400-e29b-41d4-a716-446655440000",
+///                     "user_id": "660e8400-e29b-41d4-a716-446655440000",
+///                     "amount": 120000,
+///                     "completed_at": "2026-09-14T15:30:00Z"
+///                 }
+///             ],
+///             "payment_method_id": "880e8400-e29b-41d4-a716-446655440000",
+///             "issued_at": "2026-10-01T01:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization
+pub async fn list_invoices<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    query: web::Query<InvoiceListQuery>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_INVOICE_LIMIT)
+        .min(MAX_INVOICE_LIMIT);
+
+    match state
+        .organization_billing_service
+        .invoices(auth.user_id, path.into_inner(), limit)
+        .await
+    {
+        Ok(invoices) => {
+            let invoices: Vec<ConsolidatedInvoiceResponse> = invoices.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(ConsolidatedInvoiceListResponse {
+                total: invoices.len(),
+                invoices,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/organizations/{id}/invoices/{invoice_id}
+///
+/// Gets one consolidated invoice.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The invoice, in the same format as the list
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization, or no such invoice
+pub async fn get_invoice<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<(Uuid, Uuid)>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    let (organization_id, invoice_id) = path.into_inner();
+    match state
+        .organization_billing_service
+        .invoice(auth.user_id, organization_id, invoice_id)
+        .await
+    {
+        Ok(invoice) => HttpResponse::Ok().json(ConsolidatedInvoiceResponse::from(invoice)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
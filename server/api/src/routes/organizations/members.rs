@@ -0,0 +1,222 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::organization::{
+    CreateOrganizationRequest, OrganizationMemberListResponse, OrganizationMemberResponse,
+    OrganizationResponse, SaveOrganizationMemberRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::OrganizationRepository;
+use re_core::services::payment_method::PaymentMethodProviderTrait;
+
+use super::OrganizationBillingState;
+
+/// Handler for POST /api/v1/organizations
+///
+/// Creates an organization with the signed-in user as its first admin.
+/// A user belongs to at most one organization.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "name": "Acme Property Group",
+///     "billing_email": "accounts@acme.example",
+///     "currency": "AUD"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "name": "Acme Property Group",
+///     "billing_email": "accounts@acme.example",
+///     "currency": "AUD",
+///     "created_at": "2026-10-17T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid name, billing email or currency, or the user
+///   already belongs to an organization
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn create_organization<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    request: web::Json<CreateOrganizationRequest>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .organization_billing_service
+        .create_organization(auth.user_id, &request.name, &request.billing_email, &request.currency)
+        .await
+    {
+        Ok(organization) => HttpResponse::Created().json(OrganizationResponse::from(organization)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/organizations/{id}/members
+///
+/// Lists an organization's members with what each accepted this calendar month.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "members": [
+///         {
+///             "user_id": "550e8400-e29b-41d4-a716-446655440000",
+///             "role": "member",
+///             "monthly_spend_limit": 500000,
+///             "spent_this_month": 120000,
+///             "added_at": "2026-10-17T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization
+pub async fn list_members<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .organization_billing_service
+        .members(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(members) => {
+            let members: Vec<OrganizationMemberResponse> = members.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(OrganizationMemberListResponse {
+                total: members.len(),
+                members,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for PUT /api/v1/organizations/{id}/members/{user_id}
+///
+/// Adds a user to the organization, or changes a member's role and
+/// monthly spend limit. A lowered limit applies to quotes accepted from
+/// then on; quotes already accepted stay charged.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "role": "member",
+///     "monthly_spend_limit": 500000
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The member, in the same format as the list but without `spent_this_month`
+///
+/// ## Errors
+/// - 400 Bad Request: Negative spend limit, the user belongs to another
+///   organization, the organization is full, or the last admin would be demoted
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization
+pub async fn save_member<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<(Uuid, Uuid)>,
+    request: web::Json<SaveOrganizationMemberRequest>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let (organization_id, user_id) = path.into_inner();
+    match state
+        .organization_billing_service
+        .save_member(auth.user_id, organization_id, user_id, request.role, request.monthly_spend_limit)
+        .await
+    {
+        Ok(member) => HttpResponse::Ok().json(OrganizationMemberResponse::from(member)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/organizations/{id}/members/{user_id}
+///
+/// Removes a member. Charges the member already incurred stay with the
+/// organization and are still invoiced.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 400 Bad Request: The member is the last admin
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user or the member does not belong to the organization
+pub async fn remove_member<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<(Uuid, Uuid)>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    let (organization_id, user_id) = path.into_inner();
+    match state
+        .organization_billing_service
+        .remove_member(auth.user_id, organization_id, user_id)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
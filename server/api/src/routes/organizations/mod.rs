@@ -0,0 +1,28 @@
+//! Organization route handlers for company accounts
+//!
+//! This module contains endpoints for organization billing:
+//! - Creating an organization, with the creator as its first admin
+//! - Managing members, their roles and monthly spend limits
+//! - Saving the payment methods the organization is billed to
+//! - Viewing consolidated monthly invoices
+//!
+//! Apart from creation, every endpoint is limited to the organization's admins.
+
+pub mod invoices;
+pub mod members;
+pub mod payment_methods;
+
+use std::sync::Arc;
+
+use re_core::repositories::OrganizationRepository;
+use re_core::services::organization_billing::OrganizationBillingService;
+use re_core::services::payment_method::PaymentMethodProviderTrait;
+
+/// Application state for organization routes
+pub struct OrganizationBillingState<O, V>
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    pub organization_billing_service: Arc<OrganizationBillingService<O, V>>,
+}
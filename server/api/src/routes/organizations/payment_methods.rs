@@ -0,0 +1,165 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::organization::{OrganizationPaymentMethodListResponse, OrganizationPaymentMethodResponse};
+use crate::dto::payment::AddPaymentMethodRequest;
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::OrganizationRepository;
+use re_core::services::payment_method::PaymentMethodProviderTrait;
+
+use super::OrganizationBillingState;
+
+/// Handler for GET /api/v1/organizations/{id}/payment-methods
+///
+/// Lists the cards the organization is billed to, newest first. Invoices
+/// are charged to the default one.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "payment_methods": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "payment_method": "pm_1Q2w3E4r5T6y",
+///             "brand": "visa",
+///             "last4": "4242",
+///             "exp_month": 12,
+///             "exp_year": 2028,
+///             "is_default": true,
+///             "added_by": "660e8400-e29b-41d4-a716-446655440000",
+///             "created_at": "2026-10-17T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization
+pub async fn list_payment_methods<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .organization_billing_service
+        .payment_methods(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(methods) => {
+            let payment_methods: Vec<OrganizationPaymentMethodResponse> =
+                methods.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(OrganizationPaymentMethodListResponse {
+                total: payment_methods.len(),
+                payment_methods,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/organizations/{id}/payment-methods
+///
+/// Saves a card the app tokenized with the provider's SDK for the
+/// organization. Card numbers are refused.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "payment_method": "pm_1Q2w3E4r5T6y",
+///     "make_default": true
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The payment method, in the same format as the list
+///
+/// ## Errors
+/// - 400 Bad Request: Missing payment method, a card number, an expired card,
+///   or the maximum number of saved methods reached
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization
+/// - 500 Internal Server Error: The payment provider rejected or could not attach the method
+pub async fn add_payment_method<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<AddPaymentMethodRequest>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .organization_billing_service
+        .add_payment_method(auth.user_id, path.into_inner(), &request.payment_method, request.make_default)
+        .await
+    {
+        Ok(method) => HttpResponse::Created().json(OrganizationPaymentMethodResponse::from(method)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/organizations/{id}/payment-methods/{method_id}
+///
+/// Removes a payment method. When the default method is removed, the
+/// newest remaining method becomes the default.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not an admin of the organization
+/// - 404 Not Found: The user does not belong to the organization, or no such payment method
+pub async fn delete_payment_method<O, V>(
+    req: HttpRequest,
+    state: web::Data<OrganizationBillingState<O, V>>,
+    auth: AuthContext,
+    path: web::Path<(Uuid, Uuid)>,
+) -> HttpResponse
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    let (organization_id, method_id) = path.into_inner();
+    match state
+        .organization_billing_service
+        .remove_payment_method(auth.user_id, organization_id, method_id)
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
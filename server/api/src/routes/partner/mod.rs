@@ -0,0 +1,57 @@
+//! Partner API route handlers
+//!
+//! This module contains the limited API that partner applications call
+//! with tokens from the OAuth token endpoint:
+//! - Reading the profile of the user who approved the partner
+//!
+//! Partner tokens are verified with the partner audience and must carry
+//! the scope each endpoint requires; user access tokens are not accepted.
+
+pub mod profile;
+
+use actix_web::{http::header::AUTHORIZATION, HttpRequest};
+use std::sync::Arc;
+
+use re_core::domain::entities::token::Claims;
+use re_core::errors::{AuthError, DomainError};
+use re_core::repositories::{TokenRepository, UserRepository};
+use re_core::services::token::TokenService;
+
+/// Application state for partner routes
+pub struct PartnerState<U, R>
+where
+    U: UserRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    pub user_repository: Arc<U>,
+    pub token_service: Arc<TokenService<R>>,
+}
+
+/// Verify the partner token of a request and check it grants a scope
+///
+/// # Returns
+/// * `Err(DomainError::Unauthorized)` - Missing bearer token
+/// * `Err(DomainError::Token)` - Invalid, expired or revoked token, or a user access token
+/// * `Err(DomainError::Auth(InsufficientPermissions))` - The token lacks the scope
+pub async fn authorize_partner<R>(
+    req: &HttpRequest,
+    token_service: &TokenService<R>,
+    scope: &str,
+) -> Result<Claims, DomainError>
+where
+    R: TokenRepository + 'static,
+{
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(DomainError::Unauthorized)?;
+
+    let claims = token_service.verify_partner_token(token).await?;
+    if !claims.has_scope(scope) {
+        return Err(DomainError::Auth(AuthError::InsufficientPermissions));
+    }
+
+    Ok(claims)
+}
@@ -0,0 +1,64 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+
+use crate::dto::oauth::PartnerProfileResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::domain::entities::oauth::SCOPE_PROFILE_READ;
+use re_core::errors::{AuthError, DomainError};
+use re_core::repositories::{TokenRepository, UserRepository};
+
+use super::{authorize_partner, PartnerState};
+
+/// Handler for GET /api/v1/partner/me
+///
+/// Returns the basic profile of the user who approved the partner. Requires
+/// a token from the authorization code grant with the `profile:read` scope;
+/// client credentials tokens do not act for a user and are refused.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "user_type": "customer",
+///     "is_verified": true
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing, invalid or expired partner token
+/// - 403 Forbidden: The token lacks `profile:read` or does not act for a user
+/// - 404 Not Found: The user no longer exists
+pub async fn get_profile<U, R>(
+    req: HttpRequest,
+    state: web::Data<PartnerState<U, R>>,
+) -> HttpResponse
+where
+    U: UserRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let claims = match authorize_partner(&req, &state.token_service, SCOPE_PROFILE_READ).await {
+        Ok(claims) => claims,
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    };
+    let Ok(user_id) = claims.user_id() else {
+        let error = DomainError::Auth(AuthError::InsufficientPermissions);
+        return handle_domain_error_with_lang(&error, lang);
+    };
+
+    match state.user_repository.find_by_id(user_id).await {
+        Ok(Some(user)) => HttpResponse::Ok().json(PartnerProfileResponse {
+            id: user.id.to_string(),
+            user_type: user.user_type,
+            is_verified: user.is_verified,
+        }),
+        Ok(None) => {
+            let error = DomainError::NotFound { resource: "User".to_string() };
+            handle_domain_error_with_lang(&error, lang)
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,174 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::payment::{
+    CreatePaymentRequest, PaymentResponse, PaymentTimelineEntryResponse, PaymentTimelineResponse,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::users::compliance::compliance_blocked_response;
+
+use re_core::domain::entities::compliance::ComplianceAction;
+use re_core::errors::DomainError;
+use re_core::repositories::{JournalRepository, PaymentRepository};
+use re_core::services::compliance::ComplianceGateTrait;
+use re_core::services::payment_intent::{NewPayment, PaymentIntentProviderTrait, PaymentIntentService};
+
+/// Application state for payment routes
+pub struct PaymentState<P, J, V>
+where
+    P: PaymentRepository + 'static,
+    J: JournalRepository + 'static,
+    V: PaymentIntentProviderTrait + 'static,
+{
+    pub payment_intent_service: Arc<PaymentIntentService<P, J, V>>,
+    /// Checks holding back orders in markets with compliance requirements
+    pub compliance: Option<Arc<dyn ComplianceGateTrait>>,
+}
+
+/// Handler for POST /api/v1/payments
+///
+/// Pays for a job with a payment method created on the device. When the
+/// card issuer requires 3-D Secure, the payment is returned with status
+/// `requires_action` and a `client_action`: the app hands the client secret
+/// to the Stripe SDK (`use_sdk`) or opens the bank's page (`redirect_to_url`).
+/// The payment moves to `succeeded` or `failed` once the provider confirms
+/// the outcome; poll the timeline endpoint to follow it.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "worker_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "amount": 25000,
+///     "currency": "AUD",
+///     "payment_method": "pm_1Q2w3E4r5T6y",
+///     "idempotency_key": "order_7c9e6679"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "status": "requires_action",
+///     "amount": 25000,
+///     "currency": "AUD",
+///     "client_action": {
+///         "type": "use_sdk",
+///         "client_secret": "pi_3Q2w3E_secret_4r5T6y"
+///     },
+///     "created_at": "2026-10-16T10:00:00Z",
+///     "updated_at": "2026-10-16T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid amount, currency, payment method or idempotency key
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: A compliance check of the customer's market is not
+///   complete; `details.next_step` is `submit_compliance` or
+///   `wait_for_compliance_review` and `details.requirement` names the check
+/// - 500 Internal Server Error: The payment provider could not be reached
+pub async fn create_payment<P, J, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentState<P, J, V>>,
+    auth: AuthContext,
+    request: web::Json<CreatePaymentRequest>,
+) -> HttpResponse
+where
+    P: PaymentRepository + 'static,
+    J: JournalRepository + 'static,
+    V: PaymentIntentProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Some(ref compliance) = state.compliance {
+        match compliance.blocking_step(auth.user_id, ComplianceAction::Order).await {
+            Ok(Some(step)) => return compliance_blocked_response(&step, lang),
+            Ok(None) => {}
+            Err(error) => return handle_domain_error_with_lang(&error, lang),
+        }
+    }
+
+    let request = request.into_inner();
+    let new_payment = NewPayment {
+        customer_id: auth.user_id,
+        worker_id: request.worker_id,
+        amount: request.amount,
+        currency: request.currency,
+        payment_method: request.payment_method,
+        // Scoped to the customer so keys chosen by different apps cannot collide
+        idempotency_key: format!("{}:{}", auth.user_id, request.idempotency_key),
+        require_three_ds: false,
+    };
+
+    match state.payment_intent_service.create_payment(new_payment).await {
+        Ok(result) => HttpResponse::Created().json(PaymentResponse::new(result.payment, result.client_action)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/payments/{payment_id}/timeline
+///
+/// Returns a payment with its status changes, oldest first. Only the
+/// customer and the worker of the payment can see it.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "payment": {
+///         "id": "550e8400-e29b-41d4-a716-446655440000",
+///         "status": "succeeded",
+///         "amount": 25000,
+///         "currency": "AUD",
+///         "created_at": "2026-10-16T10:00:00Z",
+///         "updated_at": "2026-10-16T10:01:30Z"
+///     },
+///     "timeline": [
+///         { "status": "requires_action", "note": null, "occurred_at": "2026-10-16T10:00:00Z" },
+///         { "status": "succeeded", "note": null, "occurred_at": "2026-10-16T10:01:30Z" }
+///     ]
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: No such payment for this user
+pub async fn get_payment_timeline<P, J, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentState<P, J, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    P: PaymentRepository + 'static,
+    J: JournalRepository + 'static,
+    V: PaymentIntentProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .payment_intent_service
+        .timeline(path.into_inner(), auth.user_id)
+        .await
+    {
+        Ok((payment, timeline)) => HttpResponse::Ok().json(PaymentTimelineResponse {
+            payment: PaymentResponse::new(payment, None),
+            timeline: timeline.into_iter().map(PaymentTimelineEntryResponse::from).collect(),
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,205 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::payment::{AddPaymentMethodRequest, PaymentMethodListResponse, PaymentMethodResponse};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::DomainError;
+use re_core::repositories::{PaymentMethodRepository, PaymentRepository, PayoutRepository};
+use re_core::services::payment_method::{PaymentMethodProviderTrait, PaymentMethodService};
+
+/// Application state for saved payment method routes
+pub struct PaymentMethodState<M, P, O, V>
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    pub payment_method_service: Arc<PaymentMethodService<M, P, O, V>>,
+}
+
+/// Handler for GET /api/v1/payment-methods
+///
+/// Lists the signed-in customer's saved payment methods, newest first.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "payment_methods": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "payment_method": "pm_1Q2w3E4r5T6y",
+///             "brand": "visa",
+///             "last4": "4242",
+///             "exp_month": 12,
+///             "exp_year": 2028,
+///             "is_default": true,
+///             "created_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn list_payment_methods<M, P, O, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentMethodState<M, P, O, V>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.payment_method_service.list(auth.user_id).await {
+        Ok(methods) => {
+            let payment_methods: Vec<PaymentMethodResponse> = methods.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(PaymentMethodListResponse {
+                total: payment_methods.len(),
+                payment_methods,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/payment-methods
+///
+/// Saves a card the app tokenized with the provider's SDK. Card numbers
+/// are refused; only the provider's identifier and display details are
+/// stored. Saving the same payment method again returns it unchanged.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "payment_method": "pm_1Q2w3E4r5T6y",
+///     "make_default": false
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The payment method, in the same format as the list
+///
+/// ## Errors
+/// - 400 Bad Request: Missing payment method, a card number, an expired card,
+///   or the maximum number of saved methods reached
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 500 Internal Server Error: The payment provider rejected or could not attach the method
+pub async fn add_payment_method<M, P, O, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentMethodState<M, P, O, V>>,
+    auth: AuthContext,
+    request: web::Json<AddPaymentMethodRequest>,
+) -> HttpResponse
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .payment_method_service
+        .add(auth.user_id, &request.payment_method, request.make_default)
+        .await
+    {
+        Ok(method) => HttpResponse::Created().json(PaymentMethodResponse::from(method)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for PUT /api/v1/payment-methods/{id}/default
+///
+/// Makes a saved payment method the default.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The payment method, in the same format as the list
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: No such payment method for this customer
+pub async fn set_default_payment_method<M, P, O, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentMethodState<M, P, O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .payment_method_service
+        .set_default(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(method) => HttpResponse::Ok().json(PaymentMethodResponse::from(method)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for DELETE /api/v1/payment-methods/{id}
+///
+/// Removes a saved payment method. A method cannot be removed while a
+/// payment charged to it is held in escrow, i.e. until the worker is paid
+/// out or the payment is refunded. When the default method is removed, the
+/// newest remaining method becomes the default.
+///
+/// # Response
+///
+/// ## Success (204 No Content)
+///
+/// ## Errors
+/// - 400 Bad Request: A payment charged to the method is held in escrow
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: No such payment method for this customer
+pub async fn delete_payment_method<M, P, O, V>(
+    req: HttpRequest,
+    state: web::Data<PaymentMethodState<M, P, O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .payment_method_service
+        .delete(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,9 @@
+//! Payment route handlers for signed-in users
+//!
+//! This module contains endpoints for customer payments:
+//! - Creating a payment, returning the 3-D Secure action when one is required
+//! - Viewing a payment's status timeline
+//! - Saving, listing and removing payment methods
+
+pub mod intents;
+pub mod methods;
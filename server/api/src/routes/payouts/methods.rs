@@ -0,0 +1,184 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::payout::{
+    AddPayoutMethodRequest, PayoutMethodListResponse, PayoutMethodResponse, VerifyMicroDepositsRequest,
+};
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::errors::{DomainError, ValidationError as DomainValidationError};
+use re_core::repositories::{PayoutMethodRepository, PayoutRepository};
+use re_core::services::payout_method::PayoutMethodProviderTrait;
+
+use super::{require_payout_scope, PayoutState};
+
+/// Handler for GET /api/v1/payout-methods
+///
+/// Lists the signed-in worker's bank accounts, newest first.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "payout_methods": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "bank_name": "ANZ",
+///             "account_last4": "6789",
+///             "currency": "AUD",
+///             "status": "awaiting_micro_deposits",
+///             "verified_at": null,
+///             "created_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a worker
+pub async fn list_payout_methods<M, O, V>(
+    req: HttpRequest,
+    state: web::Data<PayoutState<M, O, V>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_payout_scope(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.payout_method_service.list(auth.user_id).await {
+        Ok(methods) => {
+            let payout_methods: Vec<PayoutMethodResponse> = methods.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(PayoutMethodListResponse {
+                total: payout_methods.len(),
+                payout_methods,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/payout-methods
+///
+/// Registers a bank account the app tokenized with the provider's SDK. The
+/// provider verifies the account holder against the worker's identity; if
+/// it cannot, it sends two micro-deposits and the account is returned with
+/// the `awaiting_micro_deposits` status.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "bank_account": "btok_1Q2w3E4r5T6y"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (201 Created)
+/// The payout method, in the same format as the list
+///
+/// ## Errors
+/// - 400 Bad Request: Missing bank account, or the maximum number of accounts reached
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a worker
+/// - 500 Internal Server Error: The payment provider rejected or could not register the account
+pub async fn add_payout_method<M, O, V>(
+    req: HttpRequest,
+    state: web::Data<PayoutState<M, O, V>>,
+    auth: AuthContext,
+    request: web::Json<AddPayoutMethodRequest>,
+) -> HttpResponse
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_payout_scope(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state.payout_method_service.add(auth.user_id, &request.bank_account).await {
+        Ok(method) => HttpResponse::Created().json(PayoutMethodResponse::from(method)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/payout-methods/{id}/verify
+///
+/// Confirms the two micro-deposit amounts shown on the worker's bank
+/// statement. After three wrong attempts the account fails verification
+/// and has to be added again.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "amounts": [32, 45]
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// The verified payout method, in the same format as the list
+///
+/// ## Errors
+/// - 400 Bad Request: Not two amounts between 1 and 99, amounts that do not
+///   match, or an account not awaiting micro-deposits
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a worker
+/// - 404 Not Found: No such payout method for this worker
+pub async fn verify_micro_deposits<M, O, V>(
+    req: HttpRequest,
+    state: web::Data<PayoutState<M, O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+    request: web::Json<VerifyMicroDepositsRequest>,
+) -> HttpResponse
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_payout_scope(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if request.validate().is_err() {
+        let error = DomainError::ValidationErr(DomainValidationError::InvalidFormat {
+            field: "amounts".to_string(),
+        });
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    match state
+        .payout_method_service
+        .verify_micro_deposits(auth.user_id, path.into_inner(), &request.amounts)
+        .await
+    {
+        Ok(method) => HttpResponse::Ok().json(PayoutMethodResponse::from(method)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,89 @@
+//! Payout route handlers for signed-in workers
+//!
+//! This module contains endpoints for worker payouts:
+//! - Registering and verifying the bank accounts payouts are sent to
+//! - Requesting the transfer of a pending payout
+//!
+//! Payout requests are refused until the worker has a verified bank
+//! account and has completed the compliance checks of their market; the
+//! error tells the worker what to do next.
+
+pub mod methods;
+pub mod requests;
+
+use actix_web::HttpResponse;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use re_core::domain::entities::token::SCOPE_PAYOUTS_WRITE;
+use re_core::errors::{AuthError, DomainError};
+use re_core::repositories::{PayoutMethodRepository, PayoutRepository};
+use re_core::services::compliance::ComplianceGateTrait;
+use re_core::services::payout_method::{
+    PayoutEligibility, PayoutMethodProviderTrait, PayoutMethodService,
+};
+
+use crate::dto::error::ErrorResponse;
+use crate::handlers::error::Language;
+use crate::i18n::{format_message, get_error_message};
+use crate::middleware::auth::AuthContext;
+
+/// Application state for payout routes
+pub struct PayoutState<M, O, V>
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    pub payout_method_service: Arc<PayoutMethodService<M, O, V>>,
+    /// Checks holding back payouts in markets with compliance requirements
+    pub compliance: Option<Arc<dyn ComplianceGateTrait>>,
+}
+
+/// Ensure the access token allows managing payouts, i.e. belongs to a worker
+pub fn require_payout_scope(auth: &AuthContext) -> Result<(), DomainError> {
+    if auth.has_scope(SCOPE_PAYOUTS_WRITE) {
+        Ok(())
+    } else {
+        Err(DomainError::Auth(AuthError::InsufficientPermissions))
+    }
+}
+
+/// Build the localized 403 response for a worker who cannot request payouts
+///
+/// The body carries a `next_step` detail the app uses to open the right
+/// screen: `add_payout_method`, `wait_for_verification` or
+/// `verify_micro_deposits`.
+///
+/// # Returns
+/// * `None` - The worker has a verified bank account
+pub fn payout_blocked_response(eligibility: &PayoutEligibility, lang: Language) -> Option<HttpResponse> {
+    let (key, next_step, method) = match eligibility {
+        PayoutEligibility::Ready(_) => return None,
+        PayoutEligibility::NoPayoutMethod => ("payout_method_missing", "add_payout_method", None),
+        PayoutEligibility::PendingVerification(method) => {
+            ("payout_method_pending_verification", "wait_for_verification", Some(method))
+        }
+        PayoutEligibility::AwaitingMicroDeposits(method) => {
+            ("payout_method_awaiting_micro_deposits", "verify_micro_deposits", Some(method))
+        }
+        PayoutEligibility::VerificationFailed(method) => {
+            ("payout_method_verification_failed", "add_payout_method", Some(method))
+        }
+    };
+
+    let mut params = HashMap::new();
+    let mut details = HashMap::from([("next_step".to_string(), json!(next_step))]);
+    if let Some(method) = method {
+        params.insert("last4", method.account_last4.clone());
+        details.insert("payout_method_id".to_string(), json!(method.id));
+    }
+
+    let (code, message) = match get_error_message("general", key, lang) {
+        Some((code, template, _)) => (code, format_message(&template, &params)),
+        None => (key.to_string(), key.to_string()),
+    };
+    let response = ErrorResponse::new(code, message).with_details(details);
+    Some(HttpResponse::Forbidden().json(response))
+}
@@ -0,0 +1,86 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use uuid::Uuid;
+
+use crate::dto::payout::PayoutResponse;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+use crate::routes::users::compliance::compliance_blocked_response;
+
+use re_core::domain::entities::compliance::ComplianceAction;
+use re_core::repositories::{PayoutMethodRepository, PayoutRepository};
+use re_core::services::payout_method::PayoutMethodProviderTrait;
+
+use super::{payout_blocked_response, require_payout_scope, PayoutState};
+
+/// Handler for POST /api/v1/payouts/{id}/request
+///
+/// Transfers a pending payout to the worker's verified bank account.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "id": "550e8400-e29b-41d4-a716-446655440000",
+///     "payment_id": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+///     "amount": 8500,
+///     "currency": "AUD",
+///     "status": "paid",
+///     "updated_at": "2026-10-16T10:00:00Z"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The payout is frozen or already paid, or in another
+///   currency than the bank account
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 403 Forbidden: The user is not a worker, has not completed a compliance
+///   check of their market, or has no verified bank account; the localized
+///   message explains what to do and `details.next_step` is one of
+///   `submit_compliance`, `wait_for_compliance_review`, `add_payout_method`,
+///   `wait_for_verification` or `verify_micro_deposits`
+/// - 404 Not Found: No such payout for this worker
+/// - 500 Internal Server Error: The payment provider could not transfer the payout
+pub async fn request_payout<M, O, V>(
+    req: HttpRequest,
+    state: web::Data<PayoutState<M, O, V>>,
+    auth: AuthContext,
+    path: web::Path<Uuid>,
+) -> HttpResponse
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    let lang = extract_language(&req);
+
+    if let Err(error) = require_payout_scope(&auth) {
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    if let Some(ref compliance) = state.compliance {
+        match compliance.blocking_step(auth.user_id, ComplianceAction::Payout).await {
+            Ok(Some(step)) => return compliance_blocked_response(&step, lang),
+            Ok(None) => {}
+            Err(error) => return handle_domain_error_with_lang(&error, lang),
+        }
+    }
+
+    match state.payout_method_service.payout_eligibility(auth.user_id).await {
+        Ok(eligibility) => {
+            if let Some(response) = payout_blocked_response(&eligibility, lang) {
+                return response;
+            }
+        }
+        Err(error) => return handle_domain_error_with_lang(&error, lang),
+    }
+
+    match state
+        .payout_method_service
+        .request_payout(auth.user_id, path.into_inner())
+        .await
+    {
+        Ok(payout) => HttpResponse::Ok().json(PayoutResponse::from(payout)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
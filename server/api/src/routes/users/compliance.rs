@@ -0,0 +1,162 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use validator::Validate;
+
+use crate::dto::compliance::{next_step, ComplianceStepResponse, ComplianceStepsResponse, SubmitComplianceRequest};
+use crate::dto::error::ErrorResponse;
+use crate::dto::validation::domain_validation_error;
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language, Language};
+use crate::i18n::{format_message, get_error_message};
+use crate::middleware::auth::AuthContext;
+
+use re_core::domain::entities::compliance::{ComplianceRequirement, ComplianceStep};
+use re_core::errors::DomainError;
+use re_core::repositories::{ComplianceRepository, UserRepository};
+use re_core::services::compliance::{ComplianceService, ComplianceSubmission};
+
+/// Application state for compliance routes
+pub struct ComplianceState<C, U>
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    pub compliance_service: Arc<ComplianceService<C, U>>,
+}
+
+/// Build the localized 403 response for an action a compliance step holds back
+///
+/// The body carries `next_step` (`submit_compliance` or
+/// `wait_for_compliance_review`) and the `requirement` the app should open.
+pub fn compliance_blocked_response(step: &ComplianceStep, lang: Language) -> HttpResponse {
+    let key = format!("compliance_{}_{}", step.requirement.as_str(), step.status.as_str());
+    let (code, message) = match get_error_message("general", &key, lang) {
+        Some((code, template, _)) => (code, format_message(&template, &HashMap::new())),
+        None => (key.clone(), key),
+    };
+
+    let details = HashMap::from([
+        ("requirement".to_string(), json!(step.requirement)),
+        ("status".to_string(), json!(step.status)),
+        ("next_step".to_string(), json!(next_step(step.status))),
+        ("attempts_remaining".to_string(), json!(step.attempts_remaining)),
+    ]);
+    HttpResponse::Forbidden().json(ErrorResponse::new(code, message).with_details(details))
+}
+
+/// Handler for GET /api/v1/users/me/compliance
+///
+/// Lists the checks the signed-in user's market requires, such as
+/// real-name verification in China, and their progress. Markets without
+/// requirements return no steps.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "steps": [
+///         {
+///             "requirement": "real_name",
+///             "status": "required",
+///             "gates": ["order", "payout"],
+///             "rejection_reason": null,
+///             "attempts_remaining": 5,
+///             "next_step": "submit_compliance"
+///         }
+///     ],
+///     "complete": false
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn get_compliance_steps<C, U>(
+    req: HttpRequest,
+    state: web::Data<ComplianceState<C, U>>,
+    auth: AuthContext,
+) -> HttpResponse
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state.compliance_service.steps(auth.user_id).await {
+        Ok(steps) => HttpResponse::Ok().json(ComplianceStepsResponse::from(steps)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for POST /api/v1/users/me/compliance/{requirement}
+///
+/// Submits the signed-in user's details for a requirement of their market
+/// (`real_name` or `icp_filing`). The details go to the market's
+/// verification provider and are not stored; the result is usually
+/// immediate, otherwise the step stays `pending` until the provider reports.
+///
+/// # Request Body
+///
+/// ```json
+/// {
+///     "legal_name": "张三",
+///     "document_number": "11010519491231002X"
+/// }
+/// ```
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "requirement": "real_name",
+///     "status": "verified",
+///     "gates": ["order", "payout"],
+///     "rejection_reason": null,
+///     "attempts_remaining": 4,
+///     "next_step": null
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Missing details, the market does not require this, a
+///   submission is still being checked, or no submissions are left
+/// - 401 Unauthorized: Missing or invalid access token
+/// - 404 Not Found: Unknown requirement
+/// - 500 Internal Server Error: The verification provider could not be reached
+pub async fn submit_compliance<C, U>(
+    req: HttpRequest,
+    state: web::Data<ComplianceState<C, U>>,
+    auth: AuthContext,
+    path: web::Path<String>,
+    request: web::Json<SubmitComplianceRequest>,
+) -> HttpResponse
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let Some(requirement) = ComplianceRequirement::from_str(&path.into_inner()) else {
+        let error = DomainError::NotFound {
+            resource: "Compliance requirement".to_string(),
+        };
+        return handle_domain_error_with_lang(&error, lang);
+    };
+    if let Err(errors) = request.validate() {
+        let error = DomainError::ValidationErr(domain_validation_error(&errors));
+        return handle_domain_error_with_lang(&error, lang);
+    }
+
+    let request = request.into_inner();
+    let submission = ComplianceSubmission {
+        legal_name: request.legal_name,
+        document_number: request.document_number,
+    };
+
+    match state.compliance_service.submit(auth.user_id, requirement, submission).await {
+        Ok(step) => HttpResponse::Ok().json(ComplianceStepResponse::from(step)),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::dto::user::{LoginHistoryEntryResponse, LoginHistoryQuery, LoginHistoryResponse};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::repositories::AuditLogRepository;
+use re_core::services::audit::AuditService;
+
+/// Default number of sign-ins returned by the login history endpoint
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+
+/// Maximum number of sign-ins returned by the login history endpoint
+const MAX_HISTORY_LIMIT: u32 = 100;
+
+/// Application state for account routes
+pub struct UserAccountState<R>
+where
+    R: AuditLogRepository + 'static,
+{
+    pub audit_service: Arc<AuditService<R>>,
+}
+
+/// Handler for GET /api/v1/users/me/login-history
+///
+/// Lists the signed-in user's recent successful and failed sign-ins,
+/// newest first, so they can spot activity that was not theirs. IP
+/// addresses are masked to their network part.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of sign-ins (default: 20, maximum: 100)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "logins": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "event_type": "LOGIN_SUCCESS",
+///             "success": true,
+///             "ip_address": "203.0.*.*",
+///             "device": "Mobile/iOS",
+///             "location": "AU",
+///             "failure_reason": null,
+///             "occurred_at": "2026-10-17T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn get_login_history<R>(
+    req: HttpRequest,
+    state: web::Data<UserAccountState<R>>,
+    auth: AuthContext,
+    query: web::Query<LoginHistoryQuery>,
+) -> HttpResponse
+where
+    R: AuditLogRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+
+    match state.audit_service.login_history(auth.user_id, limit).await {
+        Ok(logs) => {
+            let logins: Vec<LoginHistoryEntryResponse> = logs.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(LoginHistoryResponse {
+                total: logins.len(),
+                logins,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,11 @@
+//! Account route handlers for signed-in users
+//!
+//! This module contains endpoints for a user's own account:
+//! - Viewing recent successful and failed sign-ins
+//! - Completing the compliance checks of the user's market
+//!
+//! Actions held back by an unverified compliance check are refused with a
+//! localized 403 telling the user which check to complete.
+
+pub mod compliance;
+pub mod login_history;
@@ -0,0 +1,132 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+
+use crate::dto::wallet::{
+    CreditTransactionResponse, WalletBalanceQuery, WalletBalanceResponse, WalletHistoryQuery,
+    WalletHistoryResponse,
+};
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+use crate::middleware::auth::AuthContext;
+
+use re_core::repositories::{CreditWalletRepository, JournalRepository};
+use re_core::services::credit_wallet::CreditWalletService;
+
+/// Default number of transactions returned by the history endpoint
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Maximum number of transactions returned by the history endpoint
+const MAX_HISTORY_LIMIT: usize = 200;
+
+/// Application state for credit wallet routes
+pub struct WalletState<W, J>
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    pub credit_wallet_service: Arc<CreditWalletService<W, J>>,
+}
+
+/// Handler for GET /api/v1/wallet/balance
+///
+/// Returns the store credit the signed-in user can spend in a currency.
+/// Expired credit is not included.
+///
+/// # Query Parameters
+/// - `currency`: ISO 4217 currency code (e.g. `AUD`)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "balance": 2500,
+///     "currency": "AUD"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: Invalid currency code
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn get_balance<W, J>(
+    req: HttpRequest,
+    state: web::Data<WalletState<W, J>>,
+    auth: AuthContext,
+    query: web::Query<WalletBalanceQuery>,
+) -> HttpResponse
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    match state
+        .credit_wallet_service
+        .balance(auth.user_id, &query.currency)
+        .await
+    {
+        Ok(balance) => HttpResponse::Ok().json(WalletBalanceResponse {
+            balance,
+            currency: query.currency.trim().to_ascii_uppercase(),
+        }),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
+
+/// Handler for GET /api/v1/wallet/history
+///
+/// Lists the signed-in user's issued, redeemed and expired credit,
+/// newest first.
+///
+/// # Query Parameters
+/// - `limit`: Maximum number of transactions (default: 50, maximum: 200)
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "transactions": [
+///         {
+///             "id": "550e8400-e29b-41d4-a716-446655440000",
+///             "kind": "redeemed",
+///             "amount": 1500,
+///             "currency": "AUD",
+///             "reference": "order_7c9e6679",
+///             "occurred_at": "2026-10-16T10:00:00Z"
+///         }
+///     ],
+///     "total": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 401 Unauthorized: Missing or invalid access token
+pub async fn get_history<W, J>(
+    req: HttpRequest,
+    state: web::Data<WalletState<W, J>>,
+    auth: AuthContext,
+    query: web::Query<WalletHistoryQuery>,
+) -> HttpResponse
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    let lang = extract_language(&req);
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT);
+
+    match state.credit_wallet_service.history(auth.user_id, limit).await {
+        Ok(transactions) => {
+            let transactions: Vec<CreditTransactionResponse> =
+                transactions.into_iter().map(Into::into).collect();
+            HttpResponse::Ok().json(WalletHistoryResponse {
+                total: transactions.len(),
+                transactions,
+            })
+        }
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
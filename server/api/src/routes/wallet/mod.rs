@@ -0,0 +1,7 @@
+//! Credit wallet route handlers for signed-in users
+//!
+//! This module contains endpoints for a user's own store credit:
+//! - Viewing the spendable balance
+//! - Viewing the wallet history
+
+pub mod credits;
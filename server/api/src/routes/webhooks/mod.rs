@@ -0,0 +1,9 @@
+//! Webhook route handlers for external providers
+//!
+//! This module contains endpoints that third parties call, authenticated by
+//! the provider's request signature instead of an access token:
+//! - Payment provider refund and chargeback events
+//! - SMS provider delivery receipts
+
+pub mod payments;
+pub mod sms;
@@ -0,0 +1,85 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::repositories::{
+    DisputeRepository, JournalRepository, PaymentLedgerRepository, PaymentRepository,
+    PaymentWebhookEventRepository, PayoutRepository,
+};
+use re_core::services::campaign::NotificationSenderTrait;
+use re_core::services::payment_webhook::{PaymentWebhookProviderTrait, PaymentWebhookService};
+
+/// Application state for payment webhook routes
+#[allow(clippy::type_complexity)]
+pub struct PaymentWebhookState<P, Y, L, J, D, W, V, N>
+where
+    P: PaymentRepository + 'static,
+    Y: PayoutRepository + 'static,
+    L: PaymentLedgerRepository + 'static,
+    J: JournalRepository + 'static,
+    D: DisputeRepository + 'static,
+    W: PaymentWebhookEventRepository + 'static,
+    V: PaymentWebhookProviderTrait + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    pub payment_webhook_service: Arc<PaymentWebhookService<P, Y, L, J, D, W, V, N>>,
+}
+
+/// Handler for POST /api/v1/webhooks/payments
+///
+/// Receives payment confirmation, refund and chargeback events from the
+/// payment provider, including the outcome of 3-D Secure authentication. The
+/// raw body is verified against the provider's signature header (e.g.
+/// `Stripe-Signature`) before it is decoded, so it must not be altered by
+/// middleware. Redelivered events are acknowledged without being applied
+/// again.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "received": true,
+///     "outcome": "dispute_opened"
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The payload could not be decoded, or the event conflicts
+///   with the payment (e.g. an over-refund)
+/// - 401 Unauthorized: Missing or invalid signature
+/// - 500 Internal Server Error: The event could not be stored; the provider retries
+#[allow(clippy::type_complexity)]
+pub async fn receive_webhook<P, Y, L, J, D, W, V, N>(
+    req: HttpRequest,
+    state: web::Data<PaymentWebhookState<P, Y, L, J, D, W, V, N>>,
+    body: web::Bytes,
+) -> HttpResponse
+where
+    P: PaymentRepository + 'static,
+    Y: PayoutRepository + 'static,
+    L: PaymentLedgerRepository + 'static,
+    J: JournalRepository + 'static,
+    D: DisputeRepository + 'static,
+    W: PaymentWebhookEventRepository + 'static,
+    V: PaymentWebhookProviderTrait + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    let lang = extract_language(&req);
+    let service = &state.payment_webhook_service;
+
+    let signature = req
+        .headers()
+        .get(service.signature_header())
+        .and_then(|value| value.to_str().ok());
+
+    match service.handle_webhook(&body, signature).await {
+        Ok(outcome) => HttpResponse::Ok().json(json!({
+            "received": true,
+            "outcome": outcome.as_str(),
+        })),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,117 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::handlers::error::{handle_domain_error_with_lang, extract_language};
+
+use re_core::repositories::SmsDeliveryLogRepository;
+use re_core::services::sms_delivery::SmsDeliveryService;
+use re_infra::sms::receipts::{SNS_PROVIDER, TWILIO_PROVIDER};
+
+/// Application state for SMS delivery webhook routes
+pub struct SmsDeliveryWebhookState<R>
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    pub sms_delivery_service: Arc<SmsDeliveryService<R>>,
+}
+
+/// Handler for POST /api/v1/webhooks/sms/twilio
+///
+/// Receives the status callbacks of messages sent through Twilio. The form
+/// body is verified against the `X-Twilio-Signature` header, computed over
+/// the URL Twilio called, so the URL configured as the status callback must
+/// be the public URL of this endpoint.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "received": true,
+///     "applied": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The payload could not be decoded
+/// - 401 Unauthorized: Missing or invalid signature
+/// - 404 Not Found: Twilio receipts are not configured
+/// - 500 Internal Server Error: The delivery log could not be updated; Twilio retries
+pub async fn receive_twilio_receipts<R>(
+    req: HttpRequest,
+    state: web::Data<SmsDeliveryWebhookState<R>>,
+    body: web::Bytes,
+) -> HttpResponse
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    receive_receipts(TWILIO_PROVIDER, &req, &state, &body).await
+}
+
+/// Handler for POST /api/v1/webhooks/sms/sns
+///
+/// Receives the SMS delivery status logs of AWS SNS, forwarded by an SNS
+/// topic subscribed to this endpoint over HTTPS. The subscription URL must
+/// carry the configured `token` query parameter. Subscription confirmation
+/// requests are acknowledged and their `SubscribeURL` logged.
+///
+/// # Response
+///
+/// ## Success (200 OK)
+/// ```json
+/// {
+///     "received": true,
+///     "applied": 1
+/// }
+/// ```
+///
+/// ## Errors
+/// - 400 Bad Request: The payload could not be decoded
+/// - 401 Unauthorized: Missing or invalid token
+/// - 404 Not Found: SNS receipts are not configured
+/// - 500 Internal Server Error: The delivery log could not be updated; SNS retries
+pub async fn receive_sns_receipts<R>(
+    req: HttpRequest,
+    state: web::Data<SmsDeliveryWebhookState<R>>,
+    body: web::Bytes,
+) -> HttpResponse
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    receive_receipts(SNS_PROVIDER, &req, &state, &body).await
+}
+
+/// Apply the receipts of one provider's callback
+async fn receive_receipts<R>(
+    provider: &str,
+    req: &HttpRequest,
+    state: &SmsDeliveryWebhookState<R>,
+    body: &[u8],
+) -> HttpResponse
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    let lang = extract_language(req);
+    let service = &state.sms_delivery_service;
+
+    // Providers sign the public URL, as seen through any forwarding proxy
+    let url = {
+        let connection = req.connection_info();
+        format!("{}://{}{}", connection.scheme(), connection.host(), req.uri())
+    };
+
+    let signature = service
+        .provider(provider)
+        .and_then(|adapter| adapter.signature_header())
+        .and_then(|header| req.headers().get(header))
+        .and_then(|value| value.to_str().ok());
+
+    match service.handle_callback(provider, &url, body, signature).await {
+        Ok(summary) => HttpResponse::Ok().json(json!({
+            "received": true,
+            "applied": summary.applied,
+        })),
+        Err(error) => handle_domain_error_with_lang(&error, lang),
+    }
+}
@@ -0,0 +1,95 @@
+//! Command-line seeding of demo and test data
+//!
+//! ```text
+//! re_api --seed fixtures/demo.toml
+//! re_api --seed fixtures/integration.json
+//! ```
+//!
+//! The fixture is read as TOML or JSON by its extension and applied through
+//! the repositories; the report is printed to stdout as JSON. Seeding is
+//! idempotent and refused in production. Verification codes are only seeded
+//! when Redis is configured. Migrations must have been applied with
+//! `--migrate-only` first.
+
+use log::info;
+use std::io;
+use std::sync::Arc;
+
+use re_core::services::auth::PhoneHasher;
+use re_infra::cache::{RedisClient, VerificationCache};
+use re_infra::database::{DatabasePool, MySqlAdminIdentityRepository, SqlUserRepository};
+use re_infra::seeder::{parse_fixture, FixtureFormat, Seeder};
+use re_shared::config::environment::Environment;
+
+use crate::config::Config;
+
+/// Command-line flag that seeds a fixture and exits
+pub const SEED_FLAG: &str = "--seed";
+
+/// Options of a command-line seed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedArgs {
+    /// Path of the fixture
+    pub path: String,
+}
+
+impl SeedArgs {
+    /// Read the seed options from command-line arguments
+    ///
+    /// # Returns
+    /// * `None` - The arguments do not ask for seeding
+    /// * `Some(Err(message))` - The seed options are invalid
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        let position = args.iter().position(|arg| arg == SEED_FLAG)?;
+
+        let Some(path) = args.get(position + 1).filter(|path| !path.starts_with("--")) else {
+            return Some(Err(format!("{} requires the path of a fixture", SEED_FLAG)));
+        };
+
+        Some(Ok(Self { path: path.clone() }))
+    }
+}
+
+/// Seed a fixture and print the report
+pub async fn run(config: &Config, args: SeedArgs) -> io::Result<()> {
+    if config.environment == Environment::Production {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Seeding demo data is not allowed in production",
+        ));
+    }
+
+    let format = FixtureFormat::from_path(&args.path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "Fixtures must be .toml or .json files")
+    })?;
+    let content = std::fs::read_to_string(&args.path)?;
+    let fixture = parse_fixture(format, &content).map_err(io::Error::other)?;
+    info!(
+        "Read {} users, {} administrators and {} verification codes from {}",
+        fixture.users.len(),
+        fixture.admins.len(),
+        fixture.verification_codes.len(),
+        args.path
+    );
+
+    let pool = DatabasePool::new(config.database.clone())
+        .await
+        .map_err(io::Error::other)?;
+
+    let mut seeder = Seeder::new(
+        Arc::new(SqlUserRepository::new(pool.sql_database())),
+        PhoneHasher::new(&config.auth.phone_hash),
+    )
+    .with_admin_repository(Arc::new(MySqlAdminIdentityRepository::new(pool.clone())));
+    if let Some(redis) = config.cache.redis.clone().filter(|_| config.cache.enabled) {
+        let client = RedisClient::new(redis).await.map_err(io::Error::other)?;
+        seeder = seeder.with_verification_store(Arc::new(VerificationCache::new(client)));
+    }
+
+    let result = seeder.seed(&fixture).await;
+    pool.close().await;
+
+    let report = result.map_err(io::Error::other)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Command-line import of users from the legacy system
+//!
+//! ```text
+//! re_api --import-users users.csv --dry-run      # validate and print the report
+//! re_api --import-users users.csv                # import, resuming if interrupted
+//! re_api --import-users users.json --batch-size 200
+//! ```
+//!
+//! The export is read as CSV or JSON by its extension. The report is
+//! printed to stdout as JSON; progress is logged. An import that fails
+//! part-way is resumed by running the same command again with the same
+//! file, since imports are identified by the file's content. Migrations
+//! must have been applied with `--migrate-only` first.
+
+use log::info;
+use std::io;
+use std::sync::Arc;
+
+use re_core::services::auth::PhoneHasher;
+use re_core::services::user_import::{parse_export, ExportFormat, UserImportConfig, UserImportService};
+use re_infra::database::{DatabasePool, MySqlUserImportRepository, SqlUserRepository};
+
+use crate::config::Config;
+
+/// Command-line flag that imports users from a legacy export and exits
+pub const IMPORT_USERS_FLAG: &str = "--import-users";
+
+/// Flag validating the export without writing anything
+const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// Flag overriding the number of users created per batch
+const BATCH_SIZE_FLAG: &str = "--batch-size";
+
+/// Options of a command-line import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportArgs {
+    /// Path of the legacy export
+    pub path: String,
+    /// Whether to only validate the export
+    pub dry_run: bool,
+    /// Users created per batch, if not the default
+    pub batch_size: Option<usize>,
+}
+
+impl ImportArgs {
+    /// Read the import options from command-line arguments
+    ///
+    /// # Returns
+    /// * `None` - The arguments do not ask for an import
+    /// * `Some(Err(message))` - The import options are invalid
+    pub fn from_args(args: &[String]) -> Option<Result<Self, String>> {
+        let position = args.iter().position(|arg| arg == IMPORT_USERS_FLAG)?;
+
+        let Some(path) = args.get(position + 1).filter(|path| !path.starts_with("--")) else {
+            return Some(Err(format!("{} requires the path of an export", IMPORT_USERS_FLAG)));
+        };
+
+        let batch_size = match args.iter().position(|arg| arg == BATCH_SIZE_FLAG) {
+            None => None,
+            Some(index) => match args.get(index + 1).and_then(|size| size.parse().ok()) {
+                Some(size) if size > 0 => Some(size),
+                _ => return Some(Err(format!("{} requires a positive number", BATCH_SIZE_FLAG))),
+            },
+        };
+
+        Some(Ok(Self {
+            path: path.clone(),
+            dry_run: args.iter().any(|arg| arg == DRY_RUN_FLAG),
+            batch_size,
+        }))
+    }
+}
+
+/// Run an import and print its report
+pub async fn run(config: &Config, args: ImportArgs) -> io::Result<()> {
+    let format = ExportFormat::from_path(&args.path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "Exports must be .csv or .json files")
+    })?;
+    let content = std::fs::read(&args.path)?;
+    let records = parse_export(format, &content).map_err(io::Error::other)?;
+    info!("Read {} records from {}", records.len(), args.path);
+
+    let pool = DatabasePool::new(config.database.clone())
+        .await
+        .map_err(io::Error::other)?;
+
+    let mut import_config = UserImportConfig {
+        phone_hasher: PhoneHasher::new(&config.auth.phone_hash),
+        ..UserImportConfig::default()
+    };
+    if let Some(batch_size) = args.batch_size {
+        import_config.batch_size = batch_size;
+    }
+    let service = UserImportService::new(
+        Arc::new(SqlUserRepository::new(pool.sql_database())),
+        Arc::new(MySqlUserImportRepository::new(pool.clone())),
+        import_config,
+    );
+
+    let result = if args.dry_run {
+        service.dry_run(&records).await
+    } else {
+        let import_id = UserImportService::<SqlUserRepository, MySqlUserImportRepository>::import_id(&content);
+        info!("Importing users as import {}", import_id);
+        service.import(&import_id, &records).await
+    };
+    pool.close().await;
+
+    let report = result.map_err(|e| {
+        if !args.dry_run {
+            log::error!("User import failed; run the same command again to resume: {}", e);
+        }
+        io::Error::other(e)
+    })?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
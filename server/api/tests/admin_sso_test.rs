@@ -0,0 +1,46 @@
+//! Tests for admin single sign-on roles
+
+#[cfg(test)]
+mod tests {
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::admin::require_admin_role;
+    use re_core::domain::entities::admin::AdminRole;
+    use re_core::domain::entities::token::Claims;
+    use uuid::Uuid;
+
+    fn admin_context(roles: &[&str]) -> AuthContext {
+        let claims = Claims::new_admin_token(
+            Uuid::new_v4(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            3600,
+        );
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    #[test]
+    fn test_admin_roles_are_carried_from_token() {
+        let auth = admin_context(&["support"]);
+
+        assert_eq!(auth.user_type.as_deref(), Some("admin"));
+        assert_eq!(auth.roles, vec!["support".to_string()]);
+        assert!(require_admin_role(&auth, AdminRole::Support).is_ok());
+        assert!(require_admin_role(&auth, AdminRole::Finance).is_err());
+    }
+
+    #[test]
+    fn test_admin_role_grants_every_role() {
+        let auth = admin_context(&["admin"]);
+
+        assert!(require_admin_role(&auth, AdminRole::Finance).is_ok());
+        assert!(require_admin_role(&auth, AdminRole::Support).is_ok());
+    }
+
+    #[test]
+    fn test_customer_token_has_no_admin_role() {
+        let claims = Claims::new_access_token(Uuid::new_v4(), Some("customer".to_string()), true, None, None);
+        let auth = AuthContext::from_claims(claims).unwrap();
+
+        assert!(auth.roles.is_empty());
+        assert!(require_admin_role(&auth, AdminRole::Support).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Tests for reporting failed authentication requests to the attack guard
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::StatusCode,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpRequest, HttpResponse,
+    };
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use re_api::middleware::attack_guard::{record_target_phone, AttackProtection};
+    use re_core::repositories::audit::MockAuditLogRepository;
+    use re_core::services::auth::{
+        AttackDetector, AttackGuard, AttackGuardConfig, GuardDecision, RecommendedAction,
+    };
+
+    fn guard() -> Arc<AttackGuard<MockAuditLogRepository>> {
+        let detector = Arc::new(AttackDetector::with_defaults(Arc::new(MockAuditLogRepository::new())));
+        Arc::new(AttackGuard::new(detector, AttackGuardConfig::default()))
+    }
+
+    /// Send a rejected verification for `+61412345678` from `peer`
+    async fn fail_verification(
+        guard: &Arc<AttackGuard<MockAuditLogRepository>>,
+        peer: &str,
+        headers: &[(&'static str, &'static str)],
+    ) -> StatusCode {
+        let app = actix_test::init_service(
+            App::new().wrap(AttackProtection::new(guard.clone())).route(
+                "/api/v1/auth/verify-code",
+                web::post().to(|req: HttpRequest| async move {
+                    record_target_phone(&req, "+61412345678");
+                    HttpResponse::Unauthorized().finish()
+                }),
+            ),
+        )
+        .await;
+
+        let mut request = TestRequest::post()
+            .uri("/api/v1/auth/verify-code")
+            .peer_addr(peer.parse::<SocketAddr>().unwrap());
+        for header in headers {
+            request = request.insert_header(*header);
+        }
+        match actix_test::try_call_service(&app, request.to_request()).await {
+            Ok(response) => response.status(),
+            Err(e) => e.as_response_error().status_code(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_failures_against_one_phone_are_detected_as_credential_stuffing() {
+        let guard = guard();
+        for peer in ["1.1.1.1:4000", "2.2.2.2:4000", "3.3.3.3:4000", "4.4.4.4:4000", "5.5.5.5:4000"] {
+            assert_eq!(fail_verification(&guard, peer, &[]).await, StatusCode::UNAUTHORIZED);
+        }
+
+        let result = guard.evaluate().await.unwrap().unwrap();
+        assert!(matches!(result.recommended_action, RecommendedAction::EnableCaptcha));
+        assert_eq!(result.targeted_phones, vec!["***5678".to_string()]);
+    }
+
+    #[actix_web::test]
+    async fn test_failures_are_recorded_against_the_peer_not_a_spoofed_header() {
+        let guard = guard();
+        // An attacker names victims in X-Forwarded-For from addresses in one subnet
+        for (peer, victim) in [
+            ("10.0.0.1:4000", "198.51.100.1"),
+            ("10.0.0.2:4000", "198.51.100.2"),
+            ("10.0.0.3:4000", "198.51.100.3"),
+        ] {
+            fail_verification(&guard, peer, &[("X-Forwarded-For", victim)]).await;
+        }
+
+        let result = guard.evaluate().await.unwrap().unwrap();
+        assert!(matches!(result.recommended_action, RecommendedAction::BlockSubnet(_)));
+        assert!(matches!(guard.check("10.0.0.99"), GuardDecision::Block { .. }));
+        assert_eq!(guard.check("198.51.100.1"), GuardDecision::Allow);
+    }
+}
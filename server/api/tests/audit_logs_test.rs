@@ -0,0 +1,122 @@
+//! Tests for the admin audit log search endpoint
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage,
+    };
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::admin::audit_logs::{list_audit_logs, AuditLogState};
+    use re_core::domain::entities::audit::{AuditEventType, AuditLog};
+    use re_core::domain::entities::token::Claims;
+    use re_core::repositories::audit::MockAuditLogRepository;
+    use re_core::repositories::AuditLogRepository;
+    use re_core::services::audit::{AuditService, AuditServiceConfig};
+
+    fn admin_context(roles: &[&str]) -> AuthContext {
+        let claims = Claims::new_admin_token(
+            Uuid::new_v4(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            3600,
+        );
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    async fn state() -> web::Data<AuditLogState<MockAuditLogRepository>> {
+        let repository = Arc::new(MockAuditLogRepository::new());
+        let logs = [
+            AuditLog::new(AuditEventType::LoginFailure, "203.0.113.7".to_string()),
+            AuditLog::new(AuditEventType::LoginFailure, "198.51.100.2".to_string()),
+            AuditLog::new(AuditEventType::AccountLocked, "system".to_string()),
+        ];
+        for log in &logs {
+            repository.create(log).await.unwrap();
+        }
+
+        web::Data::new(AuditLogState {
+            audit_service: Arc::new(AuditService::new(repository, AuditServiceConfig::default())),
+        })
+    }
+
+    #[actix_web::test]
+    async fn test_support_can_filter_audit_logs() {
+        let auth = admin_context(&["support"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state().await)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route(
+                    "/api/v1/admin/audit-logs",
+                    web::get().to(list_audit_logs::<MockAuditLogRepository>),
+                ),
+        )
+        .await;
+
+        let request = TestRequest::get()
+            .uri("/api/v1/admin/audit-logs?event_types=LOGIN_FAILURE&ip_address=203.0.113.7&per_page=10")
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request).await;
+
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["per_page"], 10);
+        assert_eq!(body["data"][0]["event_type"], "LOGIN_FAILURE");
+        assert_eq!(body["data"][0]["ip_address"], "203.0.113.7");
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_event_type_is_rejected() {
+        let auth = admin_context(&["admin"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state().await)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route(
+                    "/api/v1/admin/audit-logs",
+                    web::get().to(list_audit_logs::<MockAuditLogRepository>),
+                ),
+        )
+        .await;
+
+        let request = TestRequest::get()
+            .uri("/api/v1/admin/audit-logs?event_types=LOGIN_FAILURE,NOT_AN_EVENT")
+            .to_request();
+        let response = actix_test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_finance_role_is_forbidden() {
+        let auth = admin_context(&["finance"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state().await)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route(
+                    "/api/v1/admin/audit-logs",
+                    web::get().to(list_audit_logs::<MockAuditLogRepository>),
+                ),
+        )
+        .await;
+
+        let request = TestRequest::get().uri("/api/v1/admin/audit-logs").to_request();
+        let response = actix_test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
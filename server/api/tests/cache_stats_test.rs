@@ -0,0 +1,58 @@
+//! Tests for the cache statistics endpoint
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::test::{call_and_read_body_json, init_service, TestRequest};
+    use actix_web::{web, App};
+
+    use re_api::dto::internal::{CacheOperationStatisticsResponse, CacheStatisticsResponse};
+    use re_api::routes::internal::{cache_stats, CacheStatsState};
+    use re_infra::cache::CacheMetrics;
+
+    #[test]
+    fn test_response_reports_latency_in_milliseconds() {
+        let metrics = CacheMetrics::new();
+        metrics.record_call("get", Duration::from_micros(300), false, Some(true));
+        metrics.record_call("get", Duration::from_millis(2), false, Some(false));
+        metrics.record_call("get", Duration::from_secs(3), true, None);
+
+        let response = CacheOperationStatisticsResponse::from(metrics.snapshot()["get"].clone());
+        assert_eq!((response.calls, response.hits, response.misses, response.errors), (3, 1, 1, 1));
+        assert_eq!(response.hit_ratio, Some(0.5));
+        assert_eq!(response.max_duration_ms, 3000.0);
+
+        let first = &response.latency_histogram[0];
+        assert_eq!((first.le_ms, first.count), (Some(0.5), 1));
+        let last = response.latency_histogram.last().unwrap();
+        assert_eq!((last.le_ms, last.count), (None, 1));
+        assert_eq!(response.latency_histogram.iter().map(|bucket| bucket.count).sum::<u64>(), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_cache_stats_reports_every_cache() {
+        let redis = Arc::new(CacheMetrics::new());
+        redis.record_call("exists", Duration::from_micros(100), false, Some(true));
+        let verification = Arc::new(CacheMetrics::new());
+        verification.record_call("store_code", Duration::from_micros(100), false, None);
+
+        let state = CacheStatsState::new()
+            .with_cache("redis", redis)
+            .with_cache("verification", verification);
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .route("/internal/cache-stats", web::get().to(cache_stats)),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/internal/cache-stats").to_request();
+        let response: CacheStatisticsResponse = call_and_read_body_json(&app, req).await;
+
+        assert_eq!(response.caches["redis"]["exists"].hit_ratio, Some(1.0));
+        assert_eq!(response.caches["verification"]["store_code"].calls, 1);
+        assert_eq!(response.caches["verification"]["store_code"].hit_ratio, None);
+    }
+}
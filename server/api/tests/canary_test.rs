@@ -0,0 +1,141 @@
+//! Tests for canary routing by user cohort
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage, HttpResponse,
+    };
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::middleware::canary::{cohort_for, in_canary, CanaryCohort, CanaryRouting};
+    use re_core::domain::entities::token::Claims;
+    use re_core::services::lifecycle::{
+        LifecycleEvent, LifecycleHookConfig, LifecycleHookService, LifecycleHookTrait,
+    };
+
+    const CANARY: &str = "pricing-engine-v2";
+
+    /// Analytics hook recording the exposures it received
+    #[derive(Default)]
+    struct ExposureRecorder {
+        exposures: Mutex<Vec<(Uuid, String)>>,
+    }
+
+    #[async_trait]
+    impl LifecycleHookTrait for ExposureRecorder {
+        fn name(&self) -> &str {
+            "analytics"
+        }
+
+        async fn handle(&self, event: &LifecycleEvent) -> Result<(), String> {
+            if let LifecycleEvent::CanaryExposure { user_id, cohort, .. } = event {
+                self.exposures.lock().unwrap().push((*user_id, cohort.clone()));
+            }
+            Ok(())
+        }
+    }
+
+    fn auth_context(user_id: Uuid) -> AuthContext {
+        AuthContext::from_claims(Claims::new_access_token(user_id, Some("customer".to_string()), true, None, None)).unwrap()
+    }
+
+    /// A user id in the given cohort of the canary at the given percentage
+    fn user_in(cohort: CanaryCohort, percentage: u8) -> Uuid {
+        std::iter::repeat_with(Uuid::new_v4)
+            .find(|user_id| cohort_for(CANARY, *user_id, percentage) == cohort)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cohorts_are_stable_and_proportional() {
+        let users: Vec<Uuid> = (0..2000).map(|_| Uuid::new_v4()).collect();
+
+        for user_id in &users {
+            assert_eq!(cohort_for(CANARY, *user_id, 0), CanaryCohort::Control);
+            assert_eq!(cohort_for(CANARY, *user_id, 100), CanaryCohort::Canary);
+            assert_eq!(cohort_for(CANARY, *user_id, 25), cohort_for(CANARY, *user_id, 25));
+            if cohort_for(CANARY, *user_id, 10) == CanaryCohort::Canary {
+                assert_eq!(cohort_for(CANARY, *user_id, 30), CanaryCohort::Canary);
+            }
+        }
+
+        let in_canary = users
+            .iter()
+            .filter(|user_id| cohort_for(CANARY, **user_id, 20) == CanaryCohort::Canary)
+            .count();
+        assert!((250..550).contains(&in_canary), "{} users in a 20% canary", in_canary);
+    }
+
+    #[actix_web::test]
+    async fn test_canary_cohort_reaches_candidate_handler() {
+        let recorder = Arc::new(ExposureRecorder::default());
+        let hooks = Arc::new(
+            LifecycleHookService::new(LifecycleHookConfig {
+                base_retry_delay_ms: 1,
+                max_retry_delay_ms: 5,
+                ..LifecycleHookConfig::default()
+            })
+            .with_hook(recorder.clone()),
+        );
+        hooks.clone().start_background_task();
+
+        let canary_user = user_in(CanaryCohort::Canary, 30);
+        let control_user = user_in(CanaryCohort::Control, 30);
+
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::from(hooks.clone()))
+                .wrap_fn(|req, srv| {
+                    let user_id = req
+                        .headers()
+                        .get("X-Test-User")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| Uuid::parse_str(v).ok());
+                    if let Some(user_id) = user_id {
+                        req.extensions_mut().insert(auth_context(user_id));
+                    }
+                    srv.call(req)
+                })
+                .service(
+                    web::resource("/quotes/price")
+                        .wrap(CanaryRouting::new(CANARY, 30))
+                        .route(web::post().guard(in_canary(CANARY)).to(|| async { HttpResponse::Ok().body("v2") }))
+                        .route(web::post().to(|| async { HttpResponse::Ok().body("v1") })),
+                ),
+        )
+        .await;
+
+        for (user_id, expected) in [(canary_user, "v2"), (control_user, "v1"), (canary_user, "v2")] {
+            let req = TestRequest::post()
+                .uri("/quotes/price")
+                .insert_header(("X-Test-User", user_id.to_string()))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(actix_test::read_body(resp).await, expected);
+        }
+
+        let req = TestRequest::post().uri("/quotes/price").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(actix_test::read_body(resp).await, "v1");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut exposures = recorder.exposures.lock().unwrap().clone();
+        exposures.sort();
+        let mut expected = vec![
+            (canary_user, "canary".to_string()),
+            (canary_user, "canary".to_string()),
+            (control_user, "control".to_string()),
+        ];
+        expected.sort();
+        assert_eq!(exposures, expected);
+    }
+}
@@ -0,0 +1,84 @@
+//! Tests for capability discovery
+
+#[cfg(test)]
+mod tests {
+    use re_api::dto::admin::CapabilitiesResponse;
+    use re_infra::capabilities::{Capabilities, Capability, CompiledFeatures, ConfiguredServices};
+    use re_shared::config::Environment;
+
+    fn features(twilio_sms: bool, aws_sns: bool) -> CompiledFeatures {
+        CompiledFeatures {
+            mysql: true,
+            sqlite: false,
+            redis_cache: true,
+            twilio_sms,
+            aws_sns,
+            vonage_sms: false,
+            mock_services: false,
+        }
+    }
+
+    fn configured(sms_provider: &str) -> ConfiguredServices {
+        ConfiguredServices {
+            database: true,
+            redis: true,
+            sms_provider: sms_provider.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_real_sms_needs_selected_provider_compiled_in() {
+        let twilio = Capabilities::detect(Environment::Production, features(true, false), &configured("twilio"));
+        assert!(twilio.has(Capability::RealSms));
+        assert!(twilio.ensure_required().is_ok());
+
+        let not_compiled = Capabilities::detect(Environment::Production, features(false, true), &configured("twilio"));
+        assert!(!not_compiled.has(Capability::RealSms));
+
+        let failover = Capabilities::detect(Environment::Production, features(false, true), &configured("failover"));
+        assert!(failover.has(Capability::RealSms));
+
+        let vonage_only = CompiledFeatures {
+            vonage_sms: true,
+            ..features(false, false)
+        };
+        assert!(Capabilities::detect(Environment::Production, vonage_only, &configured("vonage")).has(Capability::RealSms));
+        assert!(Capabilities::detect(Environment::Production, vonage_only, &configured("failover")).has(Capability::RealSms));
+        assert!(!Capabilities::detect(Environment::Production, features(true, true), &configured("vonage")).has(Capability::RealSms));
+    }
+
+    #[test]
+    fn test_production_refuses_mock_sms() {
+        let capabilities = Capabilities::detect(Environment::Production, features(true, true), &configured("mock"));
+
+        assert_eq!(capabilities.missing(), vec![Capability::RealSms]);
+        let error = capabilities.ensure_required().unwrap_err().to_string();
+        assert!(error.contains("real_sms"), "{}", error);
+
+        let development = Capabilities::detect(Environment::Development, features(true, true), &configured("mock"));
+        assert!(development.ensure_required().is_ok());
+    }
+
+    #[test]
+    fn test_staging_requires_database_and_redis() {
+        let configured = ConfiguredServices {
+            redis: false,
+            ..configured("mock")
+        };
+        let capabilities = Capabilities::detect(Environment::Staging, features(true, true), &configured);
+
+        assert_eq!(capabilities.missing(), vec![Capability::RedisCache]);
+    }
+
+    #[test]
+    fn test_response_lists_features_and_capabilities() {
+        let capabilities = Capabilities::detect(Environment::Production, features(true, false), &configured("twilio"));
+
+        let body = serde_json::to_value(CapabilitiesResponse::from(&capabilities)).unwrap();
+        assert_eq!(body["environment"], "production");
+        assert_eq!(body["features"]["twilio_sms"], true);
+        assert_eq!(body["features"]["aws_sns"], false);
+        assert_eq!(body["capabilities"], serde_json::json!(["database", "redis_cache", "real_sms"]));
+        assert_eq!(body["required"], body["capabilities"]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Tests for the unknown-field tolerant JSON extractor
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage, HttpResponse,
+    };
+    use serde::Deserialize;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::middleware::compat_json::{CompatJson, UnknownFieldTracker, CLIENT_VERSION_HEADER};
+    use re_api::routes::admin::unknown_fields::get_unknown_fields;
+    use re_core::domain::entities::token::Claims;
+    use re_shared::config::UnknownFieldMode;
+
+    #[derive(Debug, Deserialize)]
+    struct Device {
+        platform: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct RegisterDeviceRequest {
+        name: String,
+        device: Device,
+    }
+
+    async fn register_device(request: CompatJson<RegisterDeviceRequest>) -> HttpResponse {
+        HttpResponse::Ok().json(serde_json::json!({
+            "name": request.name,
+            "platform": request.device.platform,
+        }))
+    }
+
+    fn admin_context() -> AuthContext {
+        let claims = Claims::new_admin_token(Uuid::new_v4(), vec!["admin".to_string()], 3600);
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_compatible_mode_accepts_and_counts_unknown_fields() {
+        let tracker = web::Data::new(UnknownFieldTracker::new(UnknownFieldMode::Compatible));
+        let auth = admin_context();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(tracker.clone())
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route("/api/v1/devices", web::post().to(register_device))
+                .route("/api/v1/admin/unknown-fields", web::get().to(get_unknown_fields)),
+        )
+        .await;
+
+        for version in ["1.2.0", "1.2.0"] {
+            let req = TestRequest::post()
+                .uri("/api/v1/devices")
+                .insert_header((CLIENT_VERSION_HEADER, version))
+                .set_json(serde_json::json!({
+                    "name": "phone",
+                    "nickname": "mine",
+                    "device": { "platform": "ios", "model": "15" }
+                }))
+                .to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body: serde_json::Value = actix_test::read_body_json(resp).await;
+            assert_eq!(body["platform"], "ios");
+        }
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .set_json(serde_json::json!({ "name": "tablet", "device": { "platform": "android" } }))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .set_json(serde_json::json!({ "name": "tablet", "color": "red", "device": { "platform": "android" } }))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.requests, 3);
+        assert_eq!(metrics.rejected, 0);
+        assert_eq!(metrics.client_versions.get("1.2.0"), Some(&2));
+        assert_eq!(metrics.client_versions.get("unknown"), Some(&1));
+        let fields: Vec<(&str, &str, u64)> = metrics
+            .fields
+            .iter()
+            .map(|f| (f.route.as_str(), f.field.as_str(), f.count))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("POST /api/v1/devices", "color", 1),
+                ("POST /api/v1/devices", "device.model", 2),
+                ("POST /api/v1/devices", "nickname", 2),
+            ]
+        );
+
+        let req = TestRequest::get().uri("/api/v1/admin/unknown-fields").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["mode"], "compatible");
+        assert_eq!(body["requests"], 3);
+        assert_eq!(body["fields"][1]["field"], "device.model");
+        assert_eq!(body["client_versions"]["1.2.0"], 2);
+    }
+
+    #[actix_web::test]
+    async fn test_strict_mode_rejects_unknown_fields() {
+        let tracker = web::Data::new(UnknownFieldTracker::new(UnknownFieldMode::Strict));
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(tracker.clone())
+                .route("/api/v1/devices", web::post().to(register_device)),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .set_json(serde_json::json!({ "name": "phone", "nickname": "mine", "device": { "platform": "ios" } }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert_eq!(body["error"], "unknown_fields");
+        assert_eq!(body["details"]["fields"], serde_json::json!(["nickname"]));
+        assert!(body["message"].as_str().unwrap().contains("nickname"));
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .insert_header(("Accept-Language", "zh-CN"))
+            .set_json(serde_json::json!({ "name": "phone", "nickname": "mine", "device": { "platform": "ios" } }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = actix_test::read_body_json(resp).await;
+        assert!(!body["message"].as_str().unwrap().is_ascii());
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .set_json(serde_json::json!({ "name": "phone", "device": { "platform": "ios" } }))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.requests, 2);
+        assert_eq!(metrics.rejected, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_body_is_rejected() {
+        let app = actix_test::init_service(
+            App::new().route("/api/v1/devices", web::post().to(register_device)),
+        )
+        .await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .insert_header(("Content-Type", "application/json"))
+            .set_payload("{\"name\": \"phone\"")
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+
+        let req = TestRequest::post()
+            .uri("/api/v1/devices")
+            .set_json(serde_json::json!({ "name": "phone" }))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
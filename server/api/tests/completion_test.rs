@@ -0,0 +1,68 @@
+//! Tests for order completion requests and responses
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use re_api::dto::completion::{CompletionResponse, SignOffRequest, SubmitCompletionRequest};
+    use re_core::domain::entities::completion::{CompletedItem, CompletionChecklist};
+    use uuid::Uuid;
+    use validator::Validate;
+
+    #[test]
+    fn test_submission_requires_items() {
+        let body = serde_json::json!({
+            "customer_id": Uuid::new_v4(),
+            "category": "bathroom",
+            "items": [{ "key": "grout_sealed", "photos": ["uploads/grout-1.jpg"] }]
+        });
+        let request: SubmitCompletionRequest = serde_json::from_value(body).unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.items[0].note, None);
+
+        let empty: SubmitCompletionRequest = serde_json::from_value(serde_json::json!({
+            "customer_id": Uuid::new_v4(),
+            "items": []
+        }))
+        .unwrap();
+        assert!(empty.validate().is_err());
+
+        let unsigned = SignOffRequest {
+            signed_name: "J".to_string(),
+            device_info: None,
+        };
+        assert!(unsigned.validate().is_err());
+    }
+
+    #[test]
+    fn test_response_includes_sign_off() {
+        let mut checklist = CompletionChecklist {
+            order_id: Uuid::new_v4(),
+            worker_id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            template_id: Uuid::new_v4(),
+            template_version: 1,
+            items: vec![CompletedItem {
+                key: "site_cleaned".to_string(),
+                label: "Site cleaned".to_string(),
+                photos: vec!["uploads/site-1.jpg".to_string()],
+                note: None,
+            }],
+            submitted_at: Utc::now(),
+            sign_off: None,
+        };
+
+        let unsigned = serde_json::to_value(CompletionResponse::from(checklist.clone())).unwrap();
+        assert!(unsigned["sign_off"].is_null());
+        assert!(unsigned.get("template_id").is_none());
+
+        checklist
+            .sign("Jane Citizen", Some("Pixel 9".to_string()), Utc::now())
+            .unwrap();
+        let hash = checklist.content_hash();
+        let signed = serde_json::to_value(CompletionResponse::from(checklist)).unwrap();
+        assert_eq!(signed["sign_off"]["signed_name"], "Jane Citizen");
+        assert_eq!(signed["sign_off"]["device_info"], "Pixel 9");
+        assert_eq!(signed["sign_off"]["content_hash"], hash);
+        assert_eq!(signed["items"][0]["photos"][0], "uploads/site-1.jpg");
+    }
+}
@@ -0,0 +1,63 @@
+//! Tests for compliance guidance responses
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use re_api::dto::compliance::ComplianceStepResponse;
+    use re_api::handlers::error::Language;
+    use re_api::routes::users::compliance::compliance_blocked_response;
+    use re_core::domain::entities::compliance::{
+        ComplianceAction, ComplianceRequirement, ComplianceStatus, ComplianceStep,
+    };
+
+    fn step(status: ComplianceStatus) -> ComplianceStep {
+        ComplianceStep {
+            requirement: ComplianceRequirement::RealName,
+            market: "+86".to_string(),
+            status,
+            gates: vec![ComplianceAction::Order, ComplianceAction::Payout],
+            rejection_reason: None,
+            attempts_remaining: 4,
+        }
+    }
+
+    async fn body(step: &ComplianceStep, lang: Language) -> serde_json::Value {
+        let response = compliance_blocked_response(step, lang);
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_blocked_action_explains_next_step() {
+        let required = step(ComplianceStatus::Required);
+
+        let english = body(&required, Language::English).await;
+        assert_eq!(english["error"], "compliance_real_name_required");
+        assert_eq!(english["details"]["requirement"], "real_name");
+        assert_eq!(english["details"]["next_step"], "submit_compliance");
+        assert_eq!(english["details"]["attempts_remaining"], 4);
+
+        let chinese = body(&required, Language::Chinese).await;
+        assert_eq!(chinese["error"], english["error"]);
+        assert_ne!(chinese["message"], english["message"]);
+
+        let pending = body(&step(ComplianceStatus::Pending), Language::English).await;
+        assert_eq!(pending["error"], "compliance_real_name_pending");
+        assert_eq!(pending["details"]["next_step"], "wait_for_compliance_review");
+
+        let rejected = body(&step(ComplianceStatus::Rejected), Language::English).await;
+        assert_eq!(rejected["details"]["next_step"], "submit_compliance");
+    }
+
+    #[test]
+    fn test_verified_step_has_no_next_step() {
+        let mut verified = step(ComplianceStatus::Verified);
+        verified.attempts_remaining = 5;
+
+        let body = serde_json::to_value(ComplianceStepResponse::from(verified)).unwrap();
+        assert_eq!(body["status"], "verified");
+        assert!(body["next_step"].is_null());
+        assert!(body.get("market").is_none());
+    }
+}
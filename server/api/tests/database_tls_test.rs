@@ -0,0 +1,53 @@
+//! Tests for database TLS configuration
+
+#[cfg(test)]
+mod tests {
+    use re_shared::config::{DatabaseSslMode, DatabaseTlsConfig};
+
+    #[test]
+    fn test_ssl_mode_accepts_mysql_spellings() {
+        assert_eq!(DatabaseSslMode::from_str("VERIFY_CA"), Some(DatabaseSslMode::VerifyCa));
+        assert_eq!(DatabaseSslMode::from_str("verify-identity"), Some(DatabaseSslMode::VerifyIdentity));
+        assert_eq!(DatabaseSslMode::from_str(" required "), Some(DatabaseSslMode::Required));
+        assert_eq!(DatabaseSslMode::from_str("strict"), None);
+
+        for mode in [
+            DatabaseSslMode::Disabled,
+            DatabaseSslMode::Preferred,
+            DatabaseSslMode::Required,
+            DatabaseSslMode::VerifyCa,
+            DatabaseSslMode::VerifyIdentity,
+        ] {
+            assert_eq!(DatabaseSslMode::from_str(mode.as_str()), Some(mode));
+        }
+    }
+
+    #[test]
+    fn test_validation_rejects_inconsistent_settings() {
+        assert!(DatabaseTlsConfig::default().validate().is_ok());
+
+        let verify_without_ca = DatabaseTlsConfig {
+            ssl_mode: Some(DatabaseSslMode::VerifyIdentity),
+            ..Default::default()
+        };
+        assert!(verify_without_ca.validate().unwrap_err().contains("DATABASE_SSL_CA"));
+
+        let verify = DatabaseTlsConfig {
+            ca_cert_path: Some("/etc/ssl/rds-ca.pem".to_string()),
+            ..verify_without_ca
+        };
+        assert!(verify.validate().is_ok());
+
+        let cert_without_key = DatabaseTlsConfig {
+            client_cert_path: Some("/etc/ssl/client.pem".to_string()),
+            ..verify.clone()
+        };
+        assert!(cert_without_key.validate().is_err());
+
+        let disabled_with_ca = DatabaseTlsConfig {
+            ssl_mode: Some(DatabaseSslMode::Disabled),
+            ..verify
+        };
+        assert!(disabled_with_ca.validate().is_err());
+    }
+}
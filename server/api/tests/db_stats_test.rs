@@ -0,0 +1,56 @@
+//! Tests for the database pool statistics endpoint
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use re_api::dto::internal::PoolStatisticsResponse;
+    use re_infra::database::{CircuitState, PoolStatistics};
+
+    fn stats(active: u32, idle: usize, max: u32) -> PoolStatistics {
+        PoolStatistics {
+            connections: active + idle as u32,
+            idle_connections: idle,
+            active_connections: active,
+            max_connections: max,
+            acquire_wait: None,
+            circuit: CircuitState::Closed,
+        }
+    }
+
+    #[test]
+    fn test_response_reports_utilization_of_active_connections() {
+        let response = PoolStatisticsResponse::from(stats(9, 3, 20));
+
+        assert_eq!(response.connections, 12);
+        assert_eq!(response.active_connections, 9);
+        assert_eq!(response.idle_connections, 3);
+        assert_eq!(response.utilization_percent, 45);
+        assert_eq!(response.acquire_wait_ms, None);
+    }
+
+    #[test]
+    fn test_response_reports_acquire_wait_in_milliseconds() {
+        let mut sampled = stats(1, 0, 10);
+        sampled.acquire_wait = Some(Duration::from_micros(1500));
+
+        let json = serde_json::to_value(PoolStatisticsResponse::from(sampled)).unwrap();
+        assert_eq!(json["acquire_wait_ms"], 1.5);
+    }
+
+    #[test]
+    fn test_response_reports_circuit_state() {
+        let mut failing = stats(0, 0, 10);
+        failing.circuit = CircuitState::HalfOpen;
+
+        let json = serde_json::to_value(PoolStatisticsResponse::from(failing)).unwrap();
+        assert_eq!(json["circuit"], "half_open");
+        assert_eq!(serde_json::to_value(PoolStatisticsResponse::from(stats(1, 0, 10))).unwrap()["circuit"], "closed");
+    }
+
+    #[test]
+    fn test_response_handles_pool_without_connection_limit() {
+        let response = PoolStatisticsResponse::from(stats(0, 0, 0));
+        assert_eq!(response.utilization_percent, 0);
+    }
+}
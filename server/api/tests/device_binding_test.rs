@@ -0,0 +1,87 @@
+//! Tests for binding access tokens to the device they were issued to
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header::HeaderMap, StatusCode},
+        test::{self as actix_test, TestRequest},
+        web, App, HttpResponse,
+    };
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::{extract_device_fingerprint, JwtAuth, DEVICE_FINGERPRINT_HEADER};
+    use re_core::domain::entities::token::Claims;
+
+    const SECRET: &str = "device-binding-test-secret";
+
+    /// Access token issued with the fingerprint the client sent at login
+    fn token(device_fingerprint: Option<&str>) -> String {
+        let mut claims = Claims::new_access_token(Uuid::new_v4(), Some("customer".to_string()), true, None, None);
+        claims.device_fingerprint = device_fingerprint.map(Claims::hash_device_fingerprint);
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut request = TestRequest::default();
+        for pair in pairs {
+            request = request.insert_header(*pair);
+        }
+        request.to_http_request().headers().clone()
+    }
+
+    async fn status_for(token: &str, extra: &[(&'static str, &'static str)]) -> StatusCode {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(JwtAuth::with_secret(SECRET.to_string()))
+                .route("/api/v1/users/me", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let mut request = TestRequest::get()
+            .uri("/api/v1/users/me")
+            .insert_header(("Authorization", format!("Bearer {}", token)));
+        for pair in extra {
+            request = request.insert_header(*pair);
+        }
+        match actix_test::try_call_service(&app, request.to_request()).await {
+            Ok(resp) => resp.status(),
+            Err(e) => e.as_response_error().status_code(),
+        }
+    }
+
+    #[test]
+    fn test_only_the_fingerprint_header_is_a_fingerprint() {
+        assert_eq!(
+            extract_device_fingerprint(&headers(&[(DEVICE_FINGERPRINT_HEADER, "device-abc"), ("User-Agent", "app/1.0")])),
+            Some("device-abc".to_string())
+        );
+        assert_eq!(
+            extract_device_fingerprint(&headers(&[("User-Agent", "app/1.0"), ("X-Device-Info", "iPhone 15")])),
+            None
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_bound_token_requires_the_matching_fingerprint() {
+        let token = token(Some("device-abc"));
+
+        assert_eq!(status_for(&token, &[(DEVICE_FINGERPRINT_HEADER, "device-abc")]).await, StatusCode::OK);
+        assert_eq!(
+            status_for(&token, &[(DEVICE_FINGERPRINT_HEADER, "device-xyz")]).await,
+            StatusCode::UNAUTHORIZED
+        );
+        // A User-Agent equal to the fingerprint does not stand in for the header
+        assert_eq!(status_for(&token, &[("User-Agent", "device-abc")]).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_token_issued_without_fingerprint_ignores_user_agent() {
+        // The login sent only a User-Agent, so the token was left unbound
+        let login_fingerprint = extract_device_fingerprint(&headers(&[("User-Agent", "Mozilla/5.0 (Macintosh)")]));
+        let token = token(login_fingerprint.as_deref());
+
+        assert_eq!(status_for(&token, &[("User-Agent", "Mozilla/5.0 (Windows NT 10.0)")]).await, StatusCode::OK);
+        assert_eq!(status_for(&token, &[]).await, StatusCode::OK);
+    }
+}
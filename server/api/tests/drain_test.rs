@@ -0,0 +1,113 @@
+//! Tests for connection draining and the readiness endpoint
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage,
+    };
+    use uuid::Uuid;
+
+    use re_api::lifecycle::{DrainState, DrainTrigger};
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::admin::drain::{cancel_drain, get_drain_status, start_drain};
+    use re_api::routes::health::readiness;
+    use re_core::domain::entities::token::Claims;
+
+    fn admin_context(roles: &[&str]) -> AuthContext {
+        let claims = Claims::new_admin_token(
+            Uuid::new_v4(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            3600,
+        );
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    #[test]
+    fn test_signal_drain_cannot_be_cancelled() {
+        let state = DrainState::new();
+        assert!(!state.cancel_draining().unwrap());
+
+        assert!(state.start_draining(DrainTrigger::Admin, Some("deploy".to_string())));
+        assert!(!state.start_draining(DrainTrigger::Admin, None));
+        assert_eq!(state.status().reason.as_deref(), Some("deploy"));
+        assert!(state.cancel_draining().unwrap());
+        assert!(!state.is_draining());
+
+        state.start_draining(DrainTrigger::Admin, None);
+        state.start_draining(DrainTrigger::Signal, None);
+        assert_eq!(state.status().trigger, Some(DrainTrigger::Signal));
+        assert!(state.cancel_draining().is_err());
+        assert!(state.is_draining());
+    }
+
+    #[actix_web::test]
+    async fn test_admin_drain_flips_readiness() {
+        let state = web::Data::new(DrainState::new());
+        let auth = admin_context(&["admin"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .wrap_fn(move |req, srv| {
+                    if req.path().starts_with("/api") {
+                        req.extensions_mut().insert(auth.clone());
+                    }
+                    srv.call(req)
+                })
+                .route("/health/ready", web::get().to(readiness))
+                .service(
+                    web::resource("/api/v1/admin/drain")
+                        .route(web::get().to(get_drain_status))
+                        .route(web::post().to(start_drain))
+                        .route(web::delete().to(cancel_drain)),
+                ),
+        )
+        .await;
+
+        let ready = || TestRequest::get().uri("/health/ready").to_request();
+        assert_eq!(actix_test::call_service(&app, ready()).await.status(), StatusCode::OK);
+
+        let start = TestRequest::post()
+            .uri("/api/v1/admin/drain")
+            .set_json(serde_json::json!({ "reason": "Rolling deploy" }))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, start).await;
+        assert_eq!(body["draining"], true);
+        assert_eq!(body["trigger"], "admin");
+
+        let response = actix_test::call_service(&app, ready()).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = actix_test::read_body_json(response).await;
+        assert_eq!(body["status"], "draining");
+
+        let cancel = TestRequest::delete().uri("/api/v1/admin/drain").to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, cancel).await;
+        assert_eq!(body["draining"], false);
+        assert_eq!(actix_test::call_service(&app, ready()).await.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_drain_requires_admin_role() {
+        let state = web::Data::new(DrainState::new());
+        let auth = admin_context(&["support"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route("/api/v1/admin/drain", web::post().to(start_drain)),
+        )
+        .await;
+
+        let start = TestRequest::post()
+            .uri("/api/v1/admin/drain")
+            .set_json(serde_json::json!({}))
+            .to_request();
+        assert_eq!(actix_test::call_service(&app, start).await.status(), StatusCode::FORBIDDEN);
+        assert!(!state.is_draining());
+    }
+}
@@ -0,0 +1,50 @@
+//! Tests for export job responses
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use re_api::dto::admin::{CreateExportRequest, ExportJobResponse};
+    use re_core::domain::entities::export_job::{ExportJob, ExportKind};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_running_export_reports_progress_without_link() {
+        let mut job = ExportJob::new(ExportKind::AuditLogs, Uuid::new_v4(), None, None);
+        job.start();
+        job.total_rows = Some(400);
+        job.record_progress(100);
+
+        let body = serde_json::to_value(ExportJobResponse::new(job, None)).unwrap();
+        assert_eq!(body["kind"], "audit_logs");
+        assert_eq!(body["status"], "running");
+        assert_eq!(body["progress_percent"], 25);
+        assert!(body.get("download_url").is_none());
+        assert!(body.get("error").is_none());
+    }
+
+    #[test]
+    fn test_completed_export_includes_signed_link() {
+        let mut job = ExportJob::new(ExportKind::AttributionCohorts, Uuid::new_v4(), None, None);
+        job.start();
+        job.complete("exports/attribution_cohorts/1.csv");
+        let expires_at = Utc::now() + Duration::minutes(15);
+        let url = format!("/api/v1/admin/exports/{}/download?exp=1&sig=ab", job.id);
+
+        let body = serde_json::to_value(ExportJobResponse::new(job, Some((url.clone(), expires_at)))).unwrap();
+        assert_eq!(body["status"], "completed");
+        assert_eq!(body["progress_percent"], 100);
+        assert_eq!(body["download_url"], url);
+        assert!(body["download_expires_at"].is_string());
+        assert!(body.get("object_key").is_none());
+    }
+
+    #[test]
+    fn test_create_request_rejects_unknown_kind() {
+        let request: CreateExportRequest =
+            serde_json::from_str(r#"{"kind": "attribution_cohorts", "from": "2026-09-01T00:00:00Z"}"#).unwrap();
+        assert_eq!(request.kind, ExportKind::AttributionCohorts);
+        assert!(request.to.is_none());
+
+        assert!(serde_json::from_str::<CreateExportRequest>(r#"{"kind": "users"}"#).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Tests for the dependency health endpoint
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::http::StatusCode;
+    use actix_web::{test as actix_test, web, App};
+    use async_trait::async_trait;
+
+    use re_api::routes::health::{health, DependencyHealth, HealthState};
+    use re_shared::types::{HealthStatus, ServiceHealth};
+
+    struct FixedHealth(HealthStatus);
+
+    #[async_trait]
+    impl DependencyHealth for FixedHealth {
+        async fn health(&self) -> ServiceHealth {
+            ServiceHealth {
+                status: self.0,
+                message: None,
+                response_time_ms: Some(12),
+            }
+        }
+    }
+
+    async fn get_health(state: HealthState) -> (StatusCode, serde_json::Value) {
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .route("/health", web::get().to(health)),
+        )
+        .await;
+
+        let resp = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/health").to_request()).await;
+        let status = resp.status();
+        assert_eq!(resp.headers().get("Cache-Control").unwrap(), "no-store");
+        (status, actix_test::read_body_json(resp).await)
+    }
+
+    #[actix_web::test]
+    async fn test_degraded_dependency_reports_degraded_with_200() {
+        let state = HealthState::new()
+            .with_dependency("database", Arc::new(FixedHealth(HealthStatus::Degraded)))
+            .with_dependency("redis", Arc::new(FixedHealth(HealthStatus::Healthy)));
+
+        let (status, body) = get_health(state).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["services"]["database"]["status"], "degraded");
+        assert_eq!(body["services"]["database"]["response_time_ms"], 12);
+        assert_eq!(body["services"]["redis"]["status"], "healthy");
+    }
+
+    #[actix_web::test]
+    async fn test_down_dependency_reports_unhealthy_with_503() {
+        let state = HealthState::new()
+            .with_dependency("database", Arc::new(FixedHealth(HealthStatus::Unhealthy)));
+
+        let (status, body) = get_health(state).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unhealthy");
+    }
+
+    #[actix_web::test]
+    async fn test_no_dependencies_is_healthy() {
+        let (status, body) = get_health(HealthState::new()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "healthy");
+    }
+}
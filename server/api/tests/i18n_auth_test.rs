@@ -27,6 +27,9 @@ mod tests {
             "session_expired",
             "registration_disabled",
             "user_blocked",
+            "landline_phone_number",
+            "voip_phone_number",
+            "unreachable_phone_number",
             "phone_locked",
             "sms_rate_limit_exceeded",
             "account_locked",
@@ -240,6 +243,7 @@ mod tests {
             ("auth", "invalid_phone_format", 400),
             ("auth", "rate_limit_exceeded", 429),
             ("auth", "sms_service_failure", 503),
+            ("auth", "landline_phone_number", 400),
             ("auth", "user_not_found", 404),
             ("auth", "user_already_exists", 409),
             ("auth", "authentication_failed", 401),
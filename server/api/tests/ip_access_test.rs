@@ -0,0 +1,129 @@
+//! Tests for resolving the client IP the IP access lists are checked against
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::StatusCode,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage, HttpRequest, HttpResponse,
+    };
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use re_api::middleware::ip_access::{IpAccessControl, IpAllowlisted};
+    use re_core::services::auth::{
+        IpAccessControlConfig, IpAccessControlService, IpAccessEntry, IpAccessList, IpAccessStoreTrait,
+    };
+
+    #[derive(Default)]
+    struct MemoryIpAccessStore {
+        entries: Mutex<HashMap<(IpAccessList, String), IpAccessEntry>>,
+    }
+
+    #[async_trait]
+    impl IpAccessStoreTrait for MemoryIpAccessStore {
+        async fn add_entry(&self, list: IpAccessList, entry: &IpAccessEntry) -> Result<(), String> {
+            self.entries.lock().unwrap().insert((list, entry.cidr.clone()), entry.clone());
+            Ok(())
+        }
+
+        async fn remove_entry(&self, list: IpAccessList, cidr: &str) -> Result<bool, String> {
+            Ok(self.entries.lock().unwrap().remove(&(list, cidr.to_string())).is_some())
+        }
+
+        async fn list_entries(&self, list: IpAccessList) -> Result<Vec<IpAccessEntry>, String> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|((entry_list, _), _)| *entry_list == list)
+                .map(|(_, entry)| entry.clone())
+                .collect())
+        }
+    }
+
+    async fn access_control() -> Arc<IpAccessControlService<MemoryIpAccessStore>> {
+        let service = Arc::new(IpAccessControlService::new(
+            Arc::new(MemoryIpAccessStore::default()),
+            IpAccessControlConfig::default(),
+        ));
+        service
+            .add_entry(IpAccessList::Deny, "203.0.113.0/24", "SMS pumping", None, None)
+            .await
+            .unwrap();
+        service
+            .add_entry(IpAccessList::Allow, "10.0.0.0/8", "Office network", None, None)
+            .await
+            .unwrap();
+        service
+    }
+
+    /// Status of the request and whether it was marked as allowlisted
+    async fn call(peer: &str, headers: &[(&'static str, &'static str)]) -> (StatusCode, bool) {
+        let app = actix_test::init_service(
+            App::new().wrap(IpAccessControl::new(access_control().await)).route(
+                "/api/v1/auth/send-code",
+                web::post().to(|req: HttpRequest| async move {
+                    let allowlisted = req.extensions().get::<IpAllowlisted>().is_some();
+                    HttpResponse::Ok().json(allowlisted)
+                }),
+            ),
+        )
+        .await;
+
+        let mut request = TestRequest::post()
+            .uri("/api/v1/auth/send-code")
+            .peer_addr(peer.parse::<SocketAddr>().unwrap());
+        for header in headers {
+            request = request.insert_header(*header);
+        }
+        match actix_test::try_call_service(&app, request.to_request()).await {
+            Ok(response) => {
+                let status = response.status();
+                let allowlisted: bool = actix_test::read_body_json(response).await;
+                (status, allowlisted)
+            }
+            Err(e) => (e.as_response_error().status_code(), false),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_spoofed_forwarding_headers_do_not_escape_the_denylist() {
+        assert_eq!(call("203.0.113.9:4000", &[]).await.0, StatusCode::FORBIDDEN);
+        assert_eq!(
+            call("203.0.113.9:4000", &[("X-Forwarded-For", "198.51.100.1")]).await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call("203.0.113.9:4000", &[("X-Real-IP", "198.51.100.1")]).await.0,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_spoofed_forwarding_headers_do_not_claim_the_allowlist() {
+        assert_eq!(
+            call("198.51.100.7:4000", &[("X-Forwarded-For", "10.1.2.3")]).await,
+            (StatusCode::OK, false)
+        );
+        assert_eq!(call("10.1.2.3:4000", &[]).await, (StatusCode::OK, true));
+    }
+
+    #[actix_web::test]
+    async fn test_forwarding_headers_from_a_trusted_proxy_are_honoured() {
+        std::env::set_var("TRUSTED_PROXIES", "192.0.2.10");
+
+        // The client prepended an allowlisted address; the proxy appended the real one
+        assert_eq!(
+            call("192.0.2.10:4000", &[("X-Forwarded-For", "10.1.2.3, 203.0.113.9")]).await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call("192.0.2.10:4000", &[("X-Forwarded-For", "10.1.2.3")]).await,
+            (StatusCode::OK, true)
+        );
+    }
+}
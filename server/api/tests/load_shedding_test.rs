@@ -0,0 +1,111 @@
+//! Tests for load shedding under degraded health
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::{test as actix_test, web, App, HttpResponse};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use re_api::middleware::load_shedding::{LoadLevel, LoadMonitor, LoadShedding, RequestPriority};
+    use re_shared::config::LoadSheddingConfig;
+
+    #[test]
+    fn test_load_level_follows_worst_signal() {
+        let monitor = LoadMonitor::new(LoadSheddingConfig::default());
+        assert_eq!(monitor.level(), LoadLevel::Normal);
+
+        monitor.record_event_loop_lag(Duration::from_millis(20));
+        monitor.record_db_pool(50, 100);
+        monitor.record_redis_latency(Duration::from_millis(5));
+        assert_eq!(monitor.level(), LoadLevel::Normal);
+
+        monitor.record_db_pool(95, 100);
+        assert_eq!(monitor.level(), LoadLevel::Overloaded);
+
+        monitor.record_db_pool(100, 100);
+        assert_eq!(monitor.level(), LoadLevel::Critical);
+
+        monitor.record_db_pool(10, 100);
+        monitor.record_redis_latency(Duration::from_millis(120));
+        assert_eq!(monitor.level(), LoadLevel::Critical);
+
+        monitor.record_redis_latency(Duration::from_millis(5));
+        assert_eq!(monitor.level(), LoadLevel::Normal);
+    }
+
+    #[test]
+    fn test_request_priority_by_path() {
+        let monitor = LoadMonitor::new(LoadSheddingConfig::default());
+
+        assert_eq!(monitor.priority("/api/v1/auth/send-code"), RequestPriority::Protected);
+        assert_eq!(monitor.priority("/api/v1/payments/intents"), RequestPriority::Protected);
+        assert_eq!(monitor.priority("/api/v1/search"), RequestPriority::Low);
+        assert_eq!(monitor.priority("/api/v1/analytics/events"), RequestPriority::Low);
+        assert_eq!(monitor.priority("/api/v1/searches"), RequestPriority::Normal);
+        assert_eq!(monitor.priority("/api/v1/orders"), RequestPriority::Normal);
+    }
+
+    #[actix_web::test]
+    async fn test_low_priority_requests_are_shed_first() {
+        let monitor = Arc::new(LoadMonitor::new(LoadSheddingConfig::default()));
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(LoadShedding::new(monitor.clone()))
+                .default_service(web::to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+        let status = |path: &'static str| {
+            let request = actix_test::TestRequest::get().uri(path).to_request();
+            let app = &app;
+            async move {
+                match actix_test::try_call_service(app, request).await {
+                    Ok(response) => response.status(),
+                    Err(e) => e.as_response_error().status_code(),
+                }
+            }
+        };
+
+        assert_eq!(status("/api/v1/search").await, StatusCode::OK);
+
+        monitor.record_event_loop_lag(Duration::from_millis(150));
+        let request = actix_test::TestRequest::get().uri("/api/v1/search").to_request();
+        let response = actix_test::try_call_service(&app, request).await.unwrap_err().error_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(response.headers().get("Retry-After").unwrap(), "5");
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["error"], "service_overloaded");
+        assert_eq!(status("/api/v1/orders").await, StatusCode::OK);
+
+        monitor.record_event_loop_lag(Duration::from_millis(500));
+        assert_eq!(status("/api/v1/orders").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status("/api/v1/auth/verify-code").await, StatusCode::OK);
+        assert_eq!(status("/health/ready").await, StatusCode::OK);
+
+        let shed = monitor.status();
+        assert_eq!(shed.level, LoadLevel::Critical);
+        assert_eq!(shed.shed_low_priority, 1);
+        assert_eq!(shed.shed_normal_priority, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_disabled_load_shedding_serves_everything() {
+        let config = LoadSheddingConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let monitor = Arc::new(LoadMonitor::new(config));
+        monitor.record_event_loop_lag(Duration::from_secs(5));
+
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(LoadShedding::new(monitor.clone()))
+                .default_service(web::to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+        let response = actix_test::call_service(&app, actix_test::TestRequest::get().uri("/api/v1/search").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(monitor.status().shed_low_priority, 0);
+    }
+}
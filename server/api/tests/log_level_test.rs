@@ -0,0 +1,31 @@
+//! Tests for the reloadable tracing filter
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use re_api::logging::init_tracing;
+    use re_core::services::log_level::{LogFilterTrait, LogLevelConfig, LogLevelService};
+    use tracing::Level;
+
+    // The global subscriber can only be installed once per test binary
+    #[test]
+    fn test_override_changes_enabled_levels_until_cleared() {
+        let filter = Arc::new(init_tracing("info"));
+        let service = LogLevelService::new(filter.clone(), LogLevelConfig::new("info"));
+
+        assert!(!tracing::enabled!(target: "re_core::services::auth", Level::DEBUG));
+
+        let targets = BTreeMap::from([("re_core::services::auth".to_string(), "debug".to_string())]);
+        service.set_override(None, &targets, None, None).unwrap();
+        assert!(tracing::enabled!(target: "re_core::services::auth::service", Level::DEBUG));
+        assert!(!tracing::enabled!(target: "re_core::services::ledger", Level::DEBUG));
+
+        service.clear_override().unwrap();
+        assert!(!tracing::enabled!(target: "re_core::services::auth", Level::DEBUG));
+        assert!(tracing::enabled!(target: "re_core::services::auth", Level::INFO));
+
+        assert!(filter.apply("info,re_core=loud").is_err());
+    }
+}
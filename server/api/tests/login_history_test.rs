@@ -0,0 +1,76 @@
+//! Tests for the end-user login history endpoint
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage,
+    };
+    use serde_json::json;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::users::login_history::{get_login_history, UserAccountState};
+    use re_core::domain::entities::audit::{AuditEventType, AuditLog};
+    use re_core::domain::entities::token::Claims;
+    use re_core::repositories::audit::MockAuditLogRepository;
+    use re_core::repositories::AuditLogRepository;
+    use re_core::services::audit::{AuditService, AuditServiceConfig};
+
+    #[actix_web::test]
+    async fn test_login_history_lists_own_sign_ins_masked() {
+        let user_id = Uuid::new_v4();
+        let repository = Arc::new(MockAuditLogRepository::new());
+        let logs = [
+            AuditLog::new(AuditEventType::LoginSuccess, "203.0.113.7")
+                .with_user(user_id)
+                .with_event_data(json!({ "country": "AU" }))
+                .with_device_info("Mobile/iOS"),
+            AuditLog::new(AuditEventType::LoginFailure, "198.51.100.2")
+                .with_user(user_id)
+                .with_failure_reason("Invalid verification code"),
+            AuditLog::new(AuditEventType::TokenGenerated, "203.0.113.7").with_user(user_id),
+            AuditLog::new(AuditEventType::LoginSuccess, "192.0.2.1").with_user(Uuid::new_v4()),
+        ];
+        for log in &logs {
+            repository.create(log).await.unwrap();
+        }
+        let state = web::Data::new(UserAccountState {
+            audit_service: Arc::new(AuditService::new(repository, AuditServiceConfig::default())),
+        });
+
+        let claims = Claims::new_access_token(user_id, Some("customer".to_string()), true, None, None);
+        let auth = AuthContext::from_claims(claims).unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .route(
+                    "/api/v1/users/me/login-history",
+                    web::get().to(get_login_history::<MockAuditLogRepository>),
+                ),
+        )
+        .await;
+
+        let request = TestRequest::get().uri("/api/v1/users/me/login-history").to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request).await;
+
+        assert_eq!(body["total"], 2);
+        let logins = body["logins"].as_array().unwrap();
+        assert!(logins.iter().all(|login| login["event_type"] != "TOKEN_GENERATED"));
+
+        let success = logins.iter().find(|login| login["success"] == true).unwrap();
+        assert_eq!(success["ip_address"], "203.0.*.*");
+        assert_eq!(success["location"], "AU");
+        assert_eq!(success["device"], "Mobile/iOS");
+
+        let failure = logins.iter().find(|login| login["success"] == false).unwrap();
+        assert_eq!(failure["ip_address"], "198.51.*.*");
+        assert_eq!(failure["failure_reason"], "Invalid verification code");
+    }
+}
@@ -0,0 +1,247 @@
+//! Tests for cluster event handling and maintenance mode
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage, HttpResponse,
+    };
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use re_api::cluster_events::{ClusterEventDispatcher, ConfigReloaderTrait};
+    use re_api::lifecycle::MaintenanceState;
+    use re_api::middleware::auth::AuthContext;
+    use re_api::middleware::maintenance::Maintenance;
+    use re_api::routes::admin::maintenance::{get_maintenance, set_maintenance, MaintenanceAdminState};
+    use re_core::domain::entities::token::Claims;
+    use re_core::services::cluster::{ClusterEvent, ClusterEventHandlerTrait, ClusterEventPublisherTrait};
+    use re_core::services::log_level::{LogFilterTrait, LogLevelConfig, LogLevelService};
+    use re_core::services::token::{RevocationCache, TokenRevocation};
+    use re_infra::cache::{CacheBackend, InMemoryCacheBackend, TieredCacheBackend, TieredCacheConfig};
+
+    /// Publisher recording the events instead of sending them
+    #[derive(Default)]
+    struct RecordingPublisher {
+        events: Mutex<Vec<ClusterEvent>>,
+    }
+
+    #[async_trait]
+    impl ClusterEventPublisherTrait for RecordingPublisher {
+        async fn publish(&self, event: &ClusterEvent) -> Result<(), String> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    /// Reloader counting how often it ran
+    #[derive(Default)]
+    struct CountingReloader {
+        reloads: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl ConfigReloaderTrait for CountingReloader {
+        async fn reload(&self) -> Result<(), String> {
+            *self.reloads.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    /// Log filter remembering the last filter applied
+    #[derive(Default)]
+    struct RecordingFilter {
+        filter: Mutex<String>,
+    }
+
+    impl LogFilterTrait for RecordingFilter {
+        fn apply(&self, filter: &str) -> Result<(), String> {
+            *self.filter.lock().unwrap() = filter.to_string();
+            Ok(())
+        }
+    }
+
+    fn admin_context(roles: &[&str]) -> AuthContext {
+        let claims = Claims::new_admin_token(
+            Uuid::new_v4(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            3600,
+        );
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_applies_events() {
+        let maintenance = Arc::new(MaintenanceState::new());
+        let cache = Arc::new(RevocationCache::new(Duration::seconds(30), Duration::minutes(15)));
+        let reloader = Arc::new(CountingReloader::default());
+        let dispatcher = ClusterEventDispatcher::new(maintenance.clone())
+            .with_revocation_cache(cache.clone())
+            .with_reloader("ip_access", reloader.clone());
+
+        dispatcher
+            .handle(ClusterEvent::TokenRevoked {
+                revocation: TokenRevocation::TokenFamily {
+                    token_family: "family-1".to_string(),
+                },
+            })
+            .await;
+        assert!(cache.is_family_revoked("family-1"));
+
+        dispatcher
+            .handle(ClusterEvent::ConfigReloaded { section: "ip_access".to_string() })
+            .await;
+        dispatcher
+            .handle(ClusterEvent::ConfigReloaded { section: "unknown".to_string() })
+            .await;
+        assert_eq!(*reloader.reloads.lock().unwrap(), 1);
+
+        dispatcher
+            .handle(ClusterEvent::MaintenanceModeToggled {
+                enabled: true,
+                message: Some("Back at 02:00 UTC".to_string()),
+                toggled_by: None,
+            })
+            .await;
+        let status = maintenance.status();
+        assert!(status.enabled);
+        assert_eq!(status.message.as_deref(), Some("Back at 02:00 UTC"));
+        assert!(status.since.is_some());
+
+        dispatcher
+            .handle(ClusterEvent::MaintenanceModeToggled {
+                enabled: false,
+                message: None,
+                toggled_by: None,
+            })
+            .await;
+        assert!(!maintenance.is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_invalidates_local_cache() {
+        let shared = Arc::new(InMemoryCacheBackend::default());
+        let config = TieredCacheConfig {
+            cached_prefixes: vec!["lock:".to_string()],
+            ..TieredCacheConfig::default()
+        };
+        let local_cache = Arc::new(TieredCacheBackend::new(shared.clone(), config));
+        let dispatcher =
+            ClusterEventDispatcher::new(Arc::new(MaintenanceState::new())).with_local_cache(local_cache.clone());
+
+        assert!(!local_cache.exists("lock:user-1").await.unwrap());
+        // Locked through another instance
+        shared.set("lock:user-1", "locked", 60).await.unwrap();
+        assert!(!local_cache.exists("lock:user-1").await.unwrap());
+
+        dispatcher
+            .handle(ClusterEvent::CacheInvalidated { keys: vec!["lock:user-1".to_string()] })
+            .await;
+        assert!(local_cache.exists("lock:user-1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_applies_log_level_overrides() {
+        let filter = Arc::new(RecordingFilter::default());
+        let service = Arc::new(LogLevelService::new(filter.clone(), LogLevelConfig::new("info")));
+        let dispatcher =
+            ClusterEventDispatcher::new(Arc::new(MaintenanceState::new())).with_log_level(service.clone());
+        let targets = BTreeMap::from([("re_core::services::auth".to_string(), "debug".to_string())]);
+
+        // Overrides that expired in transit are ignored
+        dispatcher
+            .handle(ClusterEvent::LogLevelOverridden {
+                level: None,
+                targets: targets.clone(),
+                expires_at: Utc::now() - Duration::seconds(1),
+                set_by: None,
+            })
+            .await;
+        assert!(service.status().unwrap().active_override.is_none());
+
+        dispatcher
+            .handle(ClusterEvent::LogLevelOverridden {
+                level: None,
+                targets,
+                expires_at: Utc::now() + Duration::minutes(15),
+                set_by: None,
+            })
+            .await;
+        assert_eq!(*filter.filter.lock().unwrap(), "info,re_core::services::auth=debug");
+        assert!(service.status().unwrap().active_override.is_some());
+
+        dispatcher.handle(ClusterEvent::LogLevelCleared).await;
+        assert_eq!(*filter.filter.lock().unwrap(), "info");
+        assert!(service.status().unwrap().active_override.is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_maintenance_rejects_client_requests() {
+        let maintenance = Arc::new(MaintenanceState::new());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let state = web::Data::new(MaintenanceAdminState {
+            maintenance: maintenance.clone(),
+            publisher: Some(publisher.clone()),
+        });
+        let auth = admin_context(&["admin"]);
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state.clone())
+                .wrap(Maintenance::new(maintenance.clone()))
+                .wrap_fn(move |req, srv| {
+                    if req.path().starts_with("/api") {
+                        req.extensions_mut().insert(auth.clone());
+                    }
+                    srv.call(req)
+                })
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/api/v1/orders", web::get().to(HttpResponse::Ok))
+                .service(
+                    web::resource("/api/v1/admin/maintenance")
+                        .route(web::get().to(get_maintenance))
+                        .route(web::put().to(set_maintenance)),
+                ),
+        )
+        .await;
+
+        let orders = || TestRequest::get().uri("/api/v1/orders").to_request();
+        assert_eq!(actix_test::call_service(&app, orders()).await.status(), StatusCode::OK);
+
+        let enable = TestRequest::put()
+            .uri("/api/v1/admin/maintenance")
+            .set_json(serde_json::json!({ "enabled": true, "message": "Back at 02:00 UTC" }))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, enable).await;
+        assert_eq!(body["enabled"], true);
+        assert!(matches!(
+            publisher.events.lock().unwrap().as_slice(),
+            [ClusterEvent::MaintenanceModeToggled { enabled: true, .. }]
+        ));
+
+        let response = actix_test::try_call_service(&app, orders()).await.unwrap_err().error_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value = serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["error"], "maintenance_mode");
+        assert_eq!(body["message"], "Back at 02:00 UTC");
+
+        let health = TestRequest::get().uri("/health").to_request();
+        assert_eq!(actix_test::call_service(&app, health).await.status(), StatusCode::OK);
+        let status = TestRequest::get().uri("/api/v1/admin/maintenance").to_request();
+        assert_eq!(actix_test::call_service(&app, status).await.status(), StatusCode::OK);
+
+        let disable = TestRequest::put()
+            .uri("/api/v1/admin/maintenance")
+            .set_json(serde_json::json!({ "enabled": false }))
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, disable).await;
+        assert_eq!(body["enabled"], false);
+        assert_eq!(actix_test::call_service(&app, orders()).await.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,80 @@
+//! Tests for client certificate authentication on internal routes
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::{self as actix_test, TestRequest}, web, App, HttpResponse};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use re_api::middleware::mtls::{certificate_fingerprint, MutualTls};
+    use re_shared::config::server::TlsConfig;
+
+    fn tls_config() -> TlsConfig {
+        TlsConfig {
+            cert_path: "server.crt".to_string(),
+            key_path: "server.key".to_string(),
+            ca_path: Some("ca.crt".to_string()),
+            verify_client: true,
+            client_auth_paths: vec!["/api/v1/admin".to_string(), "/metrics".to_string()],
+            client_identities: HashMap::from([(
+                "AB:CD:EF:01".to_string(),
+                "billing-worker".to_string(),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_client_auth_path_matching() {
+        let config = tls_config();
+
+        assert!(config.requires_client_cert("/api/v1/admin"));
+        assert!(config.requires_client_cert("/api/v1/admin/payments/reviews"));
+        assert!(config.requires_client_cert("/metrics"));
+        assert!(!config.requires_client_cert("/api/v1/administrators"));
+        assert!(!config.requires_client_cert("/api/v1/auth/send-code"));
+
+        let disabled = TlsConfig { verify_client: false, ..tls_config() };
+        assert!(!disabled.requires_client_cert("/api/v1/admin"));
+    }
+
+    #[test]
+    fn test_identity_lookup_ignores_fingerprint_format() {
+        let config = tls_config();
+
+        assert_eq!(config.identity_for("abcdef01"), Some("billing-worker"));
+        assert_eq!(config.identity_for("ab:cd:ef:01"), Some("billing-worker"));
+        assert_eq!(config.identity_for("abcdef02"), None);
+    }
+
+    #[test]
+    fn test_certificate_fingerprint_is_sha256_hex() {
+        let fingerprint = certificate_fingerprint(b"certificate");
+
+        assert_eq!(fingerprint.len(), 64);
+        assert!(fingerprint.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    }
+
+    #[actix_web::test]
+    async fn test_internal_route_rejected_without_certificate() {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(MutualTls::new(Arc::new(tls_config())))
+                .route("/api/v1/admin/fees", web::get().to(HttpResponse::Ok))
+                .route("/health", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/api/v1/admin/fees").to_request();
+        let resp = actix_test::try_call_service(&app, req).await;
+        let status = match resp {
+            Ok(resp) => resp.status(),
+            Err(e) => e.as_response_error().status_code(),
+        };
+        assert_eq!(status, actix_web::http::StatusCode::FORBIDDEN);
+
+        let req = TestRequest::get().uri("/health").to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+}
@@ -0,0 +1,44 @@
+//! Tests for the OAuth token endpoint responses
+
+#[cfg(test)]
+mod tests {
+    use re_api::dto::oauth::{OAuthErrorResponse, TokenRequest};
+    use re_core::errors::DomainError;
+    use re_core::services::oauth::OAuthError;
+
+    #[test]
+    fn test_error_response_uses_rfc_6749_codes() {
+        let response = OAuthErrorResponse::from(&OAuthError::InvalidGrant("Authorization code expired".to_string()));
+        assert_eq!(response.error, "invalid_grant");
+        assert_eq!(response.error_description.as_deref(), Some("Invalid grant: Authorization code expired"));
+
+        assert_eq!(OAuthErrorResponse::from(&OAuthError::InvalidClient).error, "invalid_client");
+        assert_eq!(OAuthErrorResponse::from(&OAuthError::InvalidScope).error, "invalid_scope");
+        assert_eq!(OAuthErrorResponse::from(&OAuthError::UnauthorizedClient).error, "unauthorized_client");
+    }
+
+    #[test]
+    fn test_server_error_details_are_not_returned() {
+        let error = OAuthError::Server(DomainError::Internal {
+            message: "Failed to save authorization code: connection refused".to_string(),
+        });
+
+        let body = serde_json::to_value(OAuthErrorResponse::from(&error)).unwrap();
+
+        assert_eq!(body, serde_json::json!({ "error": "server_error" }));
+    }
+
+    #[test]
+    fn test_token_request_accepts_form_fields() {
+        let request: TokenRequest = serde_json::from_value(serde_json::json!({
+            "grant_type": "client_credentials",
+            "client_id": "rec_1",
+            "client_secret": "secret"
+        }))
+        .unwrap();
+
+        assert_eq!(request.grant_type, "client_credentials");
+        assert_eq!(request.scope, None);
+        assert_eq!(request.code, None);
+    }
+}
@@ -0,0 +1,29 @@
+//! Tests for saved payment method request and response bodies
+
+#[cfg(test)]
+mod tests {
+    use re_api::dto::payment::{AddPaymentMethodRequest, PaymentMethodResponse};
+    use re_core::domain::entities::payment_method::SavedPaymentMethod;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_make_default_is_optional() {
+        let request: AddPaymentMethodRequest =
+            serde_json::from_value(serde_json::json!({ "payment_method": "pm_1" })).unwrap();
+
+        assert_eq!(request.payment_method, "pm_1");
+        assert!(!request.make_default);
+    }
+
+    #[test]
+    fn test_response_shows_display_details_only() {
+        let method = SavedPaymentMethod::new(Uuid::new_v4(), "stripe", "pm_1", "visa", "4242", 12, 2030).unwrap();
+
+        let body = serde_json::to_value(PaymentMethodResponse::from(method)).unwrap();
+
+        assert_eq!(body["payment_method"], "pm_1");
+        assert_eq!(body["last4"], "4242");
+        assert!(body.get("customer_id").is_none());
+        assert!(body.get("provider").is_none());
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests for payout method verification responses
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use re_api::dto::payout::PayoutMethodResponse;
+    use re_api::handlers::error::Language;
+    use re_api::routes::payouts::payout_blocked_response;
+    use re_core::domain::entities::payout_method::PayoutMethod;
+    use re_core::services::payout_method::PayoutEligibility;
+    use uuid::Uuid;
+
+    fn method() -> PayoutMethod {
+        let mut method = PayoutMethod::new(Uuid::new_v4(), "stripe", "acct_1", None, "6789", "AUD").unwrap();
+        method.await_micro_deposits();
+        method
+    }
+
+    async fn body(eligibility: &PayoutEligibility, lang: Language) -> serde_json::Value {
+        let response = payout_blocked_response(eligibility, lang).unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_blocked_payout_explains_next_step() {
+        let method = method();
+        let eligibility = PayoutEligibility::AwaitingMicroDeposits(method.clone());
+
+        let english = body(&eligibility, Language::English).await;
+        assert_eq!(english["error"], "payout_method_awaiting_micro_deposits");
+        assert!(english["message"].as_str().unwrap().contains("6789"));
+        assert_eq!(english["details"]["next_step"], "verify_micro_deposits");
+        assert_eq!(english["details"]["payout_method_id"], method.id.to_string());
+
+        let chinese = body(&eligibility, Language::Chinese).await;
+        assert_ne!(chinese["message"], english["message"]);
+
+        let missing = body(&PayoutEligibility::NoPayoutMethod, Language::English).await;
+        assert_eq!(missing["details"]["next_step"], "add_payout_method");
+        assert!(missing["details"].get("payout_method_id").is_none());
+    }
+
+    #[test]
+    fn test_verified_method_is_not_blocked() {
+        let mut method = method();
+        method.mark_verified();
+
+        assert!(payout_blocked_response(&PayoutEligibility::Ready(method.clone()), Language::English).is_none());
+
+        let body = serde_json::to_value(PayoutMethodResponse::from(method)).unwrap();
+        assert_eq!(body["status"], "verified");
+        assert!(body.get("provider_reference").is_none());
+    }
+}
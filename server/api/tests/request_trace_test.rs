@@ -0,0 +1,56 @@
+//! Tests for request trace ID propagation
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::{self as actix_test, TestRequest}, web, App, HttpResponse};
+
+    use re_api::middleware::request_trace::RequestTrace;
+    use re_core::services::trace::{current_trace_id, is_valid_trace_id};
+
+    /// Handler echoing the trace ID visible to services
+    async fn echo_trace_id() -> HttpResponse {
+        HttpResponse::Ok().body(current_trace_id().unwrap_or_default())
+    }
+
+    #[actix_web::test]
+    async fn test_client_request_id_propagated_to_handler_and_response() {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(RequestTrace::new())
+                .route("/trace", web::get().to(echo_trace_id)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/trace")
+            .insert_header(("X-Request-ID", "req-abc-123"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        assert_eq!(resp.headers().get("x-request-id").unwrap(), "req-abc-123");
+        let body = actix_test::read_body(resp).await;
+        assert_eq!(body, "req-abc-123");
+    }
+
+    #[actix_web::test]
+    async fn test_malformed_request_id_replaced() {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(RequestTrace::new())
+                .route("/trace", web::get().to(echo_trace_id)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/trace")
+            .insert_header(("X-Request-ID", "not a valid id"))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+
+        let header = resp.headers().get("x-request-id").unwrap().to_str().unwrap().to_string();
+        assert_ne!(header, "not a valid id");
+        assert!(is_valid_trace_id(&header));
+        let body = actix_test::read_body(resp).await;
+        assert_eq!(body, header.as_bytes());
+    }
+}
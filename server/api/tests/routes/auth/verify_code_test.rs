@@ -73,6 +73,8 @@ mod tests {
                 phone: "1234567890".to_string(),
                 country_code: "+1".to_string(),
                 code: "123".to_string(), // Invalid: too short
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -86,6 +88,8 @@ mod tests {
                 phone: "1234567890".to_string(),
                 country_code: "+1".to_string(),
                 code: "1234567".to_string(), // Invalid: too long
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -116,6 +120,8 @@ mod tests {
                 phone: "".to_string(),
                 country_code: "+1".to_string(),
                 code: "123456".to_string(),
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -129,6 +135,8 @@ mod tests {
                 phone: "12345678901234567890".to_string(), // Too long
                 country_code: "+1".to_string(),
                 code: "123456".to_string(),
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -160,6 +168,8 @@ mod tests {
                 phone: "".to_string(), // Invalid to trigger error
                 country_code: "+86".to_string(),
                 code: "123456".to_string(),
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -177,6 +187,8 @@ mod tests {
                 phone: "".to_string(), // Invalid to trigger error
                 country_code: "+1".to_string(),
                 code: "123456".to_string(),
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
@@ -214,6 +226,8 @@ mod tests {
                 phone: "+11234567890".to_string(), // Already includes country code
                 country_code: "+1".to_string(),
                 code: "123456".to_string(),
+                marketing_consent: None,
+                attribution: None,
             })
             .to_request();
         
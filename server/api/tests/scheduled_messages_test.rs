@@ -0,0 +1,222 @@
+//! Tests for the endpoints users schedule reminders to their own phone with
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        dev::{Service, ServiceResponse},
+        http::StatusCode,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage,
+    };
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::notifications::scheduled_messages::{
+        cancel_scheduled_message, list_scheduled_messages, schedule_message, ScheduledMessageState,
+    };
+    use re_core::domain::entities::notification::{
+        MessageTemplate, NotificationPreferences, ScheduledMessage, ScheduledMessageStatus,
+    };
+    use re_core::domain::entities::token::Claims;
+    use re_core::errors::DomainError;
+    use re_core::repositories::{NotificationPreferencesRepository, ScheduledMessageRepository};
+    use re_core::services::campaign::UserPhoneDirectoryTrait;
+    use re_core::services::notification::{MessageScheduler, MessageSchedulerConfig};
+    use re_core::services::verification::SmsServiceTrait;
+
+    #[derive(Default)]
+    struct MemoryMessageRepository {
+        messages: Mutex<HashMap<Uuid, ScheduledMessage>>,
+    }
+
+    #[async_trait]
+    impl ScheduledMessageRepository for MemoryMessageRepository {
+        async fn save(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+            self.messages.lock().unwrap().insert(message.id, message.clone());
+            Ok(message)
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<ScheduledMessage>, DomainError> {
+            Ok(self.messages.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn claim_due(
+            &self,
+            _now: DateTime<Utc>,
+            _limit: usize,
+            _claim_until: DateTime<Utc>,
+        ) -> Result<Vec<ScheduledMessage>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_pending_by_user(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, DomainError> {
+            Ok(self
+                .messages
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|m| m.user_id == user_id && m.status == ScheduledMessageStatus::Pending)
+                .cloned()
+                .collect())
+        }
+
+        async fn update(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+            self.messages.lock().unwrap().insert(message.id, message.clone());
+            Ok(message)
+        }
+    }
+
+    struct NoPreferences;
+
+    #[async_trait]
+    impl NotificationPreferencesRepository for NoPreferences {
+        async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Option<NotificationPreferences>, DomainError> {
+            Ok(None)
+        }
+
+        async fn upsert(&self, preferences: NotificationPreferences) -> Result<NotificationPreferences, DomainError> {
+            Ok(preferences)
+        }
+    }
+
+    struct NoopSms;
+
+    #[async_trait]
+    impl SmsServiceTrait for NoopSms {
+        async fn send_verification_code(&self, _phone: &str, _code: &str) -> Result<String, String> {
+            Ok("msg".to_string())
+        }
+
+        fn is_valid_phone_number(&self, phone: &str) -> bool {
+            phone.starts_with('+')
+        }
+    }
+
+    struct Directory(HashMap<Uuid, String>);
+
+    #[async_trait]
+    impl UserPhoneDirectoryTrait for Directory {
+        async fn phone_for(&self, user_id: Uuid) -> Result<Option<String>, String> {
+            Ok(self.0.get(&user_id).cloned())
+        }
+    }
+
+    type State = ScheduledMessageState<MemoryMessageRepository, NoPreferences, NoopSms>;
+
+    fn state(phones: HashMap<Uuid, String>) -> (web::Data<State>, Arc<MemoryMessageRepository>) {
+        let messages = Arc::new(MemoryMessageRepository::default());
+        let config = MessageSchedulerConfig {
+            market_quiet_hours: HashMap::new(),
+            ..MessageSchedulerConfig::default()
+        };
+        let scheduler = MessageScheduler::new(messages.clone(), Arc::new(NoPreferences), Arc::new(NoopSms), config);
+        let state = web::Data::new(ScheduledMessageState {
+            scheduler: Arc::new(scheduler),
+            phones: Arc::new(Directory(phones)),
+        });
+        (state, messages)
+    }
+
+    /// Send `request` to the scheduled message routes as `user_id`
+    async fn call(state: web::Data<State>, user_id: Uuid, request: TestRequest) -> ServiceResponse {
+        let claims = Claims::new_access_token(user_id, Some("customer".to_string()), true, None, None);
+        let auth = AuthContext::from_claims(claims).unwrap();
+        let app = actix_test::init_service(
+            App::new()
+                .app_data(state)
+                .wrap_fn(move |req, srv| {
+                    req.extensions_mut().insert(auth.clone());
+                    srv.call(req)
+                })
+                .service(
+                    web::resource("/api/v1/notifications/scheduled-messages")
+                        .route(web::post().to(schedule_message::<MemoryMessageRepository, NoPreferences, NoopSms>))
+                        .route(web::get().to(list_scheduled_messages::<MemoryMessageRepository, NoPreferences, NoopSms>)),
+                )
+                .route(
+                    "/api/v1/notifications/scheduled-messages/{id}",
+                    web::delete().to(cancel_scheduled_message::<MemoryMessageRepository, NoPreferences, NoopSms>),
+                ),
+        )
+        .await;
+        actix_test::call_service(&app, request.to_request()).await
+    }
+
+    fn arrival_reminder(send_at: DateTime<Utc>) -> serde_json::Value {
+        json!({
+            "template": "worker_arrival_reminder",
+            "params": { "worker_name": "Sam", "arrival_time": "9:00am" },
+            "send_at": send_at,
+        })
+    }
+
+    #[actix_web::test]
+    async fn test_reminder_is_scheduled_to_the_users_own_phone() {
+        let user_id = Uuid::new_v4();
+        let (state, messages) = state(HashMap::from([(user_id, "+61412345678".to_string())]));
+
+        let request = TestRequest::post()
+            .uri("/api/v1/notifications/scheduled-messages")
+            .set_json(arrival_reminder(Utc::now() + Duration::hours(12)));
+        let response = call(state.clone(), user_id, request).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body: serde_json::Value = actix_test::read_body_json(response).await;
+        assert_eq!(body["category"], "reminder");
+        assert_eq!(body["status"], "pending");
+        assert!(body.get("phone").is_none());
+
+        let id: Uuid = body["id"].as_str().unwrap().parse().unwrap();
+        let stored = messages.find_by_id(id).await.unwrap().unwrap();
+        assert_eq!(stored.user_id, user_id);
+        assert_eq!(stored.phone, "+61412345678");
+
+        let request = TestRequest::get().uri("/api/v1/notifications/scheduled-messages");
+        let body: serde_json::Value = actix_test::read_body_json(call(state, user_id, request).await).await;
+        assert_eq!(body["total"], 1);
+    }
+
+    #[actix_web::test]
+    async fn test_reminder_in_the_past_or_without_a_phone_is_rejected() {
+        let user_id = Uuid::new_v4();
+        let (state, _) = state(HashMap::new());
+
+        for send_at in [Utc::now() - Duration::minutes(1), Utc::now() + Duration::hours(1)] {
+            let request = TestRequest::post()
+                .uri("/api/v1/notifications/scheduled-messages")
+                .set_json(arrival_reminder(send_at));
+            let response = call(state.clone(), user_id, request).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_users_cannot_cancel_each_others_reminders() {
+        let owner = Uuid::new_v4();
+        let (state, messages) = state(HashMap::new());
+        let message = state
+            .scheduler
+            .schedule(
+                owner,
+                "+61412345678",
+                MessageTemplate::ReviewRequest,
+                HashMap::from([("worker_name".to_string(), "Sam".to_string())]),
+                Utc::now() + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        let uri = format!("/api/v1/notifications/scheduled-messages/{}", message.id);
+
+        let response = call(state.clone(), Uuid::new_v4(), TestRequest::delete().uri(&uri)).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(messages.find_by_id(message.id).await.unwrap().unwrap().status, ScheduledMessageStatus::Pending);
+
+        let response = call(state, owner, TestRequest::delete().uri(&uri)).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(messages.find_by_id(message.id).await.unwrap().unwrap().status, ScheduledMessageStatus::Cancelled);
+    }
+}
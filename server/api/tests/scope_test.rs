@@ -0,0 +1,57 @@
+//! Tests for token scope guards
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage, HttpResponse,
+    };
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::middleware::scope::RequireScope;
+    use re_core::domain::entities::token::Claims;
+
+    fn context(user_type: &str) -> AuthContext {
+        let claims = Claims::new_access_token(Uuid::new_v4(), Some(user_type.to_string()), true, None, None);
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    async fn status_for(auth: Option<AuthContext>) -> actix_web::http::StatusCode {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(RequireScope("payments:write"))
+                .wrap_fn(move |req, srv| {
+                    if let Some(auth) = auth.clone() {
+                        req.extensions_mut().insert(auth);
+                    }
+                    srv.call(req)
+                })
+                .route("/api/v1/payments/intents", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = TestRequest::post().uri("/api/v1/payments/intents").to_request();
+        match actix_test::try_call_service(&app, req).await {
+            Ok(resp) => resp.status(),
+            Err(e) => e.as_response_error().status_code(),
+        }
+    }
+
+    #[test]
+    fn test_auth_context_carries_token_scopes() {
+        let auth = context("worker");
+
+        assert!(auth.has_scope("orders:write"));
+        assert!(auth.has_scope("payouts:read"));
+        assert!(!auth.has_scope("payments:write"));
+    }
+
+    #[actix_web::test]
+    async fn test_require_scope_guards_routes() {
+        assert_eq!(status_for(Some(context("customer"))).await, actix_web::http::StatusCode::OK);
+        assert_eq!(status_for(Some(context("worker"))).await, actix_web::http::StatusCode::FORBIDDEN);
+        assert_eq!(status_for(None).await, actix_web::http::StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,39 @@
+//! Tests for the command-line options of fixture seeding
+
+#[cfg(test)]
+mod tests {
+    use re_api::seed::SeedArgs;
+    use re_infra::seeder::{parse_fixture, FixtureFormat};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once("re_api").chain(args.iter().copied()).map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_seed_options() {
+        assert_eq!(SeedArgs::from_args(&args(&["--migrate-only"])), None);
+
+        assert_eq!(
+            SeedArgs::from_args(&args(&["--seed", "fixtures/demo.toml"])),
+            Some(Ok(SeedArgs {
+                path: "fixtures/demo.toml".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_invalid_seed_options() {
+        assert!(matches!(SeedArgs::from_args(&args(&["--seed"])), Some(Err(_))));
+        assert!(matches!(SeedArgs::from_args(&args(&["--seed", "--migrate-only"])), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_demo_fixture_is_valid() {
+        let content = include_str!("../../fixtures/demo.toml");
+        let fixture = parse_fixture(FixtureFormat::Toml, content).unwrap();
+
+        assert_eq!(fixture.users.len(), 4);
+        assert_eq!(fixture.admins.len(), 2);
+        assert_eq!(fixture.verification_codes.len(), 2);
+    }
+}
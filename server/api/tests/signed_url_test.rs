@@ -0,0 +1,65 @@
+//! Tests for the signed URL extractor
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{test, web, App, HttpResponse};
+    use chrono::{Duration, Utc};
+    use re_api::middleware::signed_url::SignedRequest;
+    use re_core::services::signed_url::{SignedUrlConfig, SignedUrlService};
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef";
+
+    async fn download(signed: SignedRequest) -> HttpResponse {
+        HttpResponse::Ok().body(signed.0.path)
+    }
+
+    #[actix_web::test]
+    async fn test_signed_link_opens_only_its_resource() {
+        let service = web::Data::new(SignedUrlService::new(SignedUrlConfig::new(KEY)).unwrap());
+        let link = service.sign("/files/abc.jpg?size=large", None).unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/files/{name}", web::get().to(download)),
+        )
+        .await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri(&link).to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "/files/abc.jpg");
+
+        let other = link.replace("abc.jpg", "xyz.jpg");
+        let response = test::call_service(&app, test::TestRequest::get().uri(&other).to_request()).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["error"], "signed_url_invalid");
+
+        let unsigned = test::TestRequest::get().uri("/files/abc.jpg").to_request();
+        assert_eq!(test::call_service(&app, unsigned).await.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn test_expired_link_asks_for_a_new_one() {
+        let service = web::Data::new(SignedUrlService::new(SignedUrlConfig::new(KEY)).unwrap());
+        let link = service
+            .sign_until("/files/abc.jpg", Utc::now() - Duration::minutes(1))
+            .unwrap();
+        let app = test::init_service(
+            App::new()
+                .app_data(service.clone())
+                .route("/files/{name}", web::get().to(download)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri(&link)
+            .insert_header(("Accept-Language", "zh-CN"))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["error"], "signed_url_expired");
+        assert!(body["message"].as_str().unwrap().contains("过期"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Tests for the SMS delivery receipt webhooks
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::{web, App};
+    use async_trait::async_trait;
+    use serde_json::Value;
+
+    use re_api::routes::webhooks::sms::{
+        receive_sns_receipts, receive_twilio_receipts, SmsDeliveryWebhookState,
+    };
+    use re_core::domain::entities::sms_delivery::{SmsDeliveryLog, SmsDeliveryStatus};
+    use re_core::errors::DomainResult;
+    use re_core::repositories::SmsDeliveryLogRepository;
+    use re_core::services::sms_delivery::{SmsDeliveryHealth, SmsDeliveryService};
+    use re_infra::sms::{SnsReceiptProvider, TwilioReceiptProvider};
+    use re_shared::config::SecretString;
+
+    /// In-memory delivery log, keyed by provider message ID
+    #[derive(Default)]
+    struct InMemorySmsDeliveryLogRepository {
+        logs: Mutex<HashMap<String, SmsDeliveryLog>>,
+    }
+
+    #[async_trait]
+    impl SmsDeliveryLogRepository for InMemorySmsDeliveryLogRepository {
+        async fn save(&self, log: SmsDeliveryLog) -> DomainResult<SmsDeliveryLog> {
+            let key = log.provider_message_id.clone().unwrap_or_default();
+            self.logs.lock().unwrap().insert(key, log.clone());
+            Ok(log)
+        }
+
+        async fn find_by_provider_message_id(
+            &self,
+            provider: &str,
+            provider_message_id: &str,
+        ) -> DomainResult<Option<SmsDeliveryLog>> {
+            Ok(self
+                .logs
+                .lock()
+                .unwrap()
+                .get(provider_message_id)
+                .filter(|log| log.provider == provider)
+                .cloned())
+        }
+
+        async fn find_by_trace_id(&self, trace_id: &str) -> DomainResult<Vec<SmsDeliveryLog>> {
+            Ok(self
+                .logs
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|log| log.trace_id.as_deref() == Some(trace_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn update(&self, log: SmsDeliveryLog) -> DomainResult<SmsDeliveryLog> {
+            self.save(log).await
+        }
+    }
+
+    fn state(logs: Arc<InMemorySmsDeliveryLogRepository>) -> SmsDeliveryWebhookState<InMemorySmsDeliveryLogRepository> {
+        let service = SmsDeliveryService::new(logs, Arc::new(SmsDeliveryHealth::default()))
+            .with_provider(Arc::new(TwilioReceiptProvider::new(SecretString::from("auth-token"))))
+            .with_provider(Arc::new(SnsReceiptProvider::new(SecretString::from("sns-token"))));
+        SmsDeliveryWebhookState {
+            sms_delivery_service: Arc::new(service),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_twilio_receipt_updates_delivery_log() {
+        let logs = Arc::new(InMemorySmsDeliveryLogRepository::default());
+        logs.save(SmsDeliveryLog::sent(None, "Twilio", "SM123", "****5678".to_string()))
+            .await
+            .unwrap();
+
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(state(logs.clone())))
+                .route("/webhooks/sms/twilio", web::post().to(receive_twilio_receipts::<InMemorySmsDeliveryLogRepository>)),
+        )
+        .await;
+
+        let payload = "MessageSid=SM123&MessageStatus=undelivered&ErrorCode=30003";
+        let signature = TwilioReceiptProvider::new(SecretString::from("auth-token"))
+            .sign("http://localhost:8080/webhooks/sms/twilio", payload.as_bytes());
+
+        let req = TestRequest::post()
+            .uri("/webhooks/sms/twilio")
+            .insert_header(("X-Twilio-Signature", "invalid"))
+            .set_payload(payload)
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+
+        let req = TestRequest::post()
+            .uri("/webhooks/sms/twilio")
+            .insert_header(("X-Twilio-Signature", signature))
+            .set_payload(payload)
+            .to_request();
+        let response = call_service(&app, req).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = read_body_json(response).await;
+        assert_eq!(body["applied"], 1);
+
+        let log = logs.find_by_provider_message_id("Twilio", "SM123").await.unwrap().unwrap();
+        assert_eq!(log.status, SmsDeliveryStatus::Undelivered);
+    }
+
+    #[actix_web::test]
+    async fn test_sns_receipt_requires_token() {
+        let logs = Arc::new(InMemorySmsDeliveryLogRepository::default());
+        logs.save(SmsDeliveryLog::sent(None, "AWS SNS", "b1c3a2d4", "****5678".to_string()))
+            .await
+            .unwrap();
+
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(state(logs.clone())))
+                .route("/webhooks/sms/sns", web::post().to(receive_sns_receipts::<InMemorySmsDeliveryLogRepository>)),
+        )
+        .await;
+
+        let message = serde_json::json!({
+            "notification": { "messageId": "b1c3a2d4" },
+            "status": "SUCCESS"
+        });
+        let payload = serde_json::json!({ "Type": "Notification", "Message": message.to_string() }).to_string();
+
+        let req = TestRequest::post()
+            .uri("/webhooks/sms/sns?token=wrong")
+            .set_payload(payload.clone())
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+
+        let req = TestRequest::post()
+            .uri("/webhooks/sms/sns?token=sns-token")
+            .set_payload(payload)
+            .to_request();
+        assert_eq!(call_service(&app, req).await.status(), StatusCode::OK);
+
+        let log = logs.find_by_provider_message_id("AWS SNS", "b1c3a2d4").await.unwrap().unwrap();
+        assert_eq!(log.status, SmsDeliveryStatus::Delivered);
+    }
+}
@@ -0,0 +1,42 @@
+//! Tests for the SMS dry-run debug endpoint
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::test::{call_and_read_body_json, init_service, TestRequest};
+    use actix_web::{web, App};
+
+    use re_api::dto::internal::SmsDryRunResponse;
+    use re_api::routes::internal::{sms_dry_run, SmsDryRunState};
+    use re_infra::sms::SmsDryRunLog;
+
+    #[actix_web::test]
+    async fn test_reports_recorded_messages_with_codes() {
+        let log = Arc::new(SmsDryRunLog::new(10));
+        log.record("Twilio", "+15551234567", "+61412345678", "Your RenovEasy verification code is: 482910. This code will expire in 5 minutes.");
+        log.record("Twilio", "+15551234567", "+8613812345678", "Your RenovEasy verification code is: 111111.");
+        log.record("Twilio", "+15551234567", "+61412345678", "Your job starts at 9:00 tomorrow");
+
+        let app = init_service(
+            App::new()
+                .app_data(web::Data::new(SmsDryRunState { log }))
+                .route("/internal/sms/dry-run", web::get().to(sms_dry_run)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/internal/sms/dry-run?phone=%2B61412345678")
+            .to_request();
+        let response: SmsDryRunResponse = call_and_read_body_json(&app, req).await;
+
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(response.messages[0].code, None);
+        assert_eq!(response.messages[1].code.as_deref(), Some("482910"));
+        assert_eq!(response.messages[1].provider, "Twilio");
+
+        let req = TestRequest::get().uri("/internal/sms/dry-run?limit=1").to_request();
+        let response: SmsDryRunResponse = call_and_read_body_json(&app, req).await;
+        assert_eq!(response.messages.len(), 1);
+    }
+}
@@ -0,0 +1,138 @@
+//! Tests for the admin SMS log search endpoint
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::{
+        dev::Service,
+        test::{self as actix_test, TestRequest},
+        web, App, HttpMessage,
+    };
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::AuthContext;
+    use re_api::routes::admin::sms_logs::{list_sms_logs, SmsLogState};
+    use re_core::domain::entities::sms_delivery::{SmsDeliveryLog, SmsLogFilter};
+    use re_core::domain::entities::token::Claims;
+    use re_core::errors::DomainError;
+    use re_core::repositories::SmsLogRepository;
+    use re_shared::types::Pagination;
+
+    /// SMS log kept in memory, newest entry last
+    struct InMemorySmsLogRepository {
+        logs: Vec<SmsDeliveryLog>,
+    }
+
+    #[async_trait]
+    impl SmsLogRepository for InMemorySmsLogRepository {
+        async fn query(
+            &self,
+            filter: &SmsLogFilter,
+            pagination: &Pagination,
+        ) -> Result<(Vec<SmsDeliveryLog>, u64), DomainError> {
+            let matching: Vec<SmsDeliveryLog> =
+                self.logs.iter().rev().filter(|log| filter.matches(log)).cloned().collect();
+            let total = matching.len() as u64;
+            let page = matching
+                .into_iter()
+                .skip(pagination.offset_i64() as usize)
+                .take(pagination.limit_i64() as usize)
+                .collect();
+            Ok((page, total))
+        }
+    }
+
+    fn admin_context(roles: &[&str]) -> AuthContext {
+        let claims = Claims::new_admin_token(
+            Uuid::new_v4(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            3600,
+        );
+        AuthContext::from_claims(claims).unwrap()
+    }
+
+    fn state() -> web::Data<SmsLogState<InMemorySmsLogRepository>> {
+        let logs = vec![
+            SmsDeliveryLog::failed(None, "Twilio", "+*******5678", "Request timed out")
+                .with_template("verification_code"),
+            SmsDeliveryLog::sent(None, "AWS SNS", "sns-1", "+*******5678")
+                .with_template("verification_code")
+                .with_cost_micros(38600),
+            SmsDeliveryLog::sent(None, "Twilio", "SM123", "+*******9999"),
+        ];
+
+        web::Data::new(SmsLogState {
+            sms_logs: Arc::new(InMemorySmsLogRepository { logs }),
+        })
+    }
+
+    macro_rules! app {
+        ($roles:expr) => {{
+            let auth = admin_context($roles);
+            actix_test::init_service(
+                App::new()
+                    .app_data(state())
+                    .wrap_fn(move |req, srv| {
+                        req.extensions_mut().insert(auth.clone());
+                        srv.call(req)
+                    })
+                    .route(
+                        "/api/v1/admin/sms/logs",
+                        web::get().to(list_sms_logs::<InMemorySmsLogRepository>),
+                    ),
+            )
+            .await
+        }};
+    }
+
+    #[actix_web::test]
+    async fn test_support_finds_attempts_to_a_phone_number() {
+        let app = app!(&["support"]);
+
+        let request = TestRequest::get()
+            .uri("/api/v1/admin/sms/logs?phone=%2B61412345678&template=verification_code")
+            .to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request).await;
+
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["data"][0]["provider"], "AWS SNS");
+        assert_eq!(body["data"][0]["status"], "sent");
+        assert_eq!(body["data"][0]["cost_micros"], 38600);
+        assert_eq!(body["data"][1]["provider"], "Twilio");
+        assert_eq!(body["data"][1]["status"], "failed");
+        assert_eq!(body["data"][1]["error"], "Request timed out");
+    }
+
+    #[actix_web::test]
+    async fn test_filters_by_status() {
+        let app = app!(&["admin"]);
+
+        let request = TestRequest::get().uri("/api/v1/admin/sms/logs?status=FAILED").to_request();
+        let body: serde_json::Value = actix_test::call_and_read_body_json(&app, request).await;
+
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["data"][0]["provider"], "Twilio");
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_status_is_rejected() {
+        let app = app!(&["support"]);
+
+        let request = TestRequest::get().uri("/api/v1/admin/sms/logs?status=lost").to_request();
+        let response = actix_test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_finance_role_is_forbidden() {
+        let app = app!(&["finance"]);
+
+        let request = TestRequest::get().uri("/api/v1/admin/sms/logs").to_request();
+        let response = actix_test::call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
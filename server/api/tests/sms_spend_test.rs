@@ -0,0 +1,60 @@
+//! Tests for the SMS spend report
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use re_api::dto::admin::SmsSpendResponse;
+    use re_core::domain::entities::sms_spend::SmsDailySpend;
+    use re_core::services::sms_cost::SmsSpendReport;
+    use re_shared::config::SmsOverBudgetAction;
+
+    fn spend(day: u32, provider: &str, prefix: &str, messages: u64, cost_micros: u64) -> SmsDailySpend {
+        SmsDailySpend {
+            day: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            provider: provider.to_string(),
+            prefix: prefix.to_string(),
+            messages,
+            cost_micros,
+        }
+    }
+
+    #[test]
+    fn test_groups_spend_by_day() {
+        let response = SmsSpendResponse::from(SmsSpendReport {
+            currency: "USD".to_string(),
+            daily_budget_micros: Some(1_000_000),
+            over_budget: SmsOverBudgetAction::Downgrade,
+            today_micros: 1_250_000,
+            days: vec![
+                spend(16, "twilio", "+61", 10, 515_000),
+                spend(17, "aws-sns", "+61", 5, 193_000),
+                spend(17, "twilio", "+", 2, 160_000),
+            ],
+        });
+
+        assert_eq!(response.days.len(), 2);
+        assert_eq!((response.days[1].messages, response.days[1].cost_micros), (7, 353_000));
+        assert_eq!(response.days[1].breakdown[1].provider, "twilio");
+        assert_eq!((response.total_messages, response.total_micros), (17, 868_000));
+        assert_eq!(response.remaining_today_micros, Some(0));
+
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["over_budget"], "downgrade");
+        assert_eq!(body["days"][0]["date"], "2026-10-16");
+    }
+
+    #[test]
+    fn test_no_remaining_budget_without_cap() {
+        let response = SmsSpendResponse::from(SmsSpendReport {
+            currency: "AUD".to_string(),
+            daily_budget_micros: None,
+            over_budget: SmsOverBudgetAction::Refuse,
+            today_micros: 0,
+            days: Vec::new(),
+        });
+
+        assert_eq!(response.remaining_today_micros, None);
+        assert!(response.days.is_empty());
+    }
+}
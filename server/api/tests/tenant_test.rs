@@ -0,0 +1,149 @@
+//! Tests for tenant resolution from host and header
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test::{self as actix_test, TestRequest}, web, App, HttpResponse};
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    use re_api::middleware::auth::JwtAuth;
+    use re_api::middleware::tenant::{TenantResolution, TenantResolver};
+    use re_core::domain::entities::token::Claims;
+    use re_core::services::tenant::current_tenant;
+    use re_shared::config::TenancyConfig;
+
+    /// Handler echoing the tenant visible to repositories
+    async fn echo_tenant() -> HttpResponse {
+        HttpResponse::Ok().body(current_tenant().to_string())
+    }
+
+    fn tenancy(header_enabled: bool) -> TenancyConfig {
+        TenancyConfig {
+            hosts: HashMap::from([
+                ("build.example.com".to_string(), "buildco".to_string()),
+                ("reno.example.com".to_string(), "renoco".to_string()),
+            ]),
+            header_enabled,
+        }
+    }
+
+    async fn tenant_of(config: &TenancyConfig, req: TestRequest) -> (StatusCode, String) {
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(TenantResolution::new(config))
+                .route("/tenant", web::get().to(echo_tenant)),
+        )
+        .await;
+
+        let resp = actix_test::call_service(&app, req.uri("/tenant").to_request()).await;
+        let status = resp.status();
+        let body = actix_test::read_body(resp).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn test_tenant_resolved_from_host() {
+        let config = tenancy(false);
+
+        let req = TestRequest::get().insert_header(("Host", "Build.Example.com:8443"));
+        assert_eq!(tenant_of(&config, req).await, (StatusCode::OK, "buildco".to_string()));
+
+        let req = TestRequest::get().insert_header(("Host", "api.renoveasy.com"));
+        assert_eq!(tenant_of(&config, req).await, (StatusCode::OK, "default".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn test_header_ignored_unless_enabled() {
+        let req = TestRequest::get()
+            .insert_header(("Host", "build.example.com"))
+            .insert_header(("X-Tenant-ID", "renoco"));
+
+        assert_eq!(tenant_of(&tenancy(false), req).await, (StatusCode::OK, "buildco".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn test_header_selects_configured_tenant() {
+        let config = tenancy(true);
+
+        let req = TestRequest::get()
+            .insert_header(("Host", "build.example.com"))
+            .insert_header(("X-Tenant-ID", "renoco"));
+        assert_eq!(tenant_of(&config, req).await, (StatusCode::OK, "renoco".to_string()));
+
+        let req = TestRequest::get().insert_header(("X-Tenant-ID", "default"));
+        assert_eq!(tenant_of(&config, req).await, (StatusCode::OK, "default".to_string()));
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_header_tenant_rejected() {
+        let config = tenancy(true);
+
+        for value in ["otherco", "Not A Tenant"] {
+            let app = actix_test::init_service(
+                App::new()
+                    .wrap(TenantResolution::new(&config))
+                    .route("/tenant", web::get().to(echo_tenant)),
+            )
+            .await;
+            let req = TestRequest::get()
+                .uri("/tenant")
+                .insert_header(("X-Tenant-ID", value))
+                .to_request();
+
+            let resp = actix_test::try_call_service(&app, req).await;
+            let error = resp.expect_err("unknown tenant must be rejected");
+            assert_eq!(error.as_response_error().status_code(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn test_every_served_tenant_listed_once() {
+        let mut config = tenancy(false);
+        config.hosts.insert("www.build.example.com".to_string(), "buildco".to_string());
+        config.hosts.insert("api.example.com".to_string(), "default".to_string());
+
+        let tenants: Vec<String> = TenantResolver::new(&config)
+            .tenants()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(tenants, ["default", "buildco", "renoco"]);
+    }
+
+    /// Status of a signed-in request with an access token issued by `tenant`
+    async fn signed_in_status(host: &str, tenant: Option<&str>) -> StatusCode {
+        const SECRET: &str = "tenant-test-secret";
+        let mut claims = Claims::new_access_token(Uuid::new_v4(), Some("customer".to_string()), true, None, None);
+        claims.tenant = tenant.map(str::to_string);
+        let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap();
+
+        let app = actix_test::init_service(
+            App::new()
+                .wrap(JwtAuth::with_secret(SECRET.to_string()))
+                .wrap(TenantResolution::new(&tenancy(false)))
+                .route("/api/v1/users/me", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let req = TestRequest::get()
+            .uri("/api/v1/users/me")
+            .insert_header(("Host", host))
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+
+        match actix_test::try_call_service(&app, req).await {
+            Ok(resp) => resp.status(),
+            Err(e) => e.as_response_error().status_code(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_access_tokens_only_accepted_by_the_issuing_tenant() {
+        assert_eq!(signed_in_status("build.example.com", Some("buildco")).await, StatusCode::OK);
+        assert_eq!(signed_in_status("api.renoveasy.com", None).await, StatusCode::OK);
+
+        assert_eq!(signed_in_status("reno.example.com", Some("buildco")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(signed_in_status("api.renoveasy.com", Some("buildco")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(signed_in_status("build.example.com", None).await, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,42 @@
+//! Tests for the command-line options of legacy user imports
+
+#[cfg(test)]
+mod tests {
+    use re_api::user_import::ImportArgs;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        std::iter::once("re_api").chain(args.iter().copied()).map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_import_options() {
+        assert_eq!(ImportArgs::from_args(&args(&["--migrate-only"])), None);
+
+        assert_eq!(
+            ImportArgs::from_args(&args(&["--import-users", "users.csv"])),
+            Some(Ok(ImportArgs {
+                path: "users.csv".to_string(),
+                dry_run: false,
+                batch_size: None,
+            }))
+        );
+        assert_eq!(
+            ImportArgs::from_args(&args(&["--dry-run", "--import-users", "users.json", "--batch-size", "200"])),
+            Some(Ok(ImportArgs {
+                path: "users.json".to_string(),
+                dry_run: true,
+                batch_size: Some(200),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_invalid_import_options() {
+        assert!(matches!(ImportArgs::from_args(&args(&["--import-users"])), Some(Err(_))));
+        assert!(matches!(ImportArgs::from_args(&args(&["--import-users", "--dry-run"])), Some(Err(_))));
+        assert!(matches!(
+            ImportArgs::from_args(&args(&["--import-users", "users.csv", "--batch-size", "0"])),
+            Some(Err(_))
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+//! Tests for user import responses
+
+#[cfg(test)]
+mod tests {
+    use re_api::dto::admin::{UserImportQuery, UserImportResponse};
+    use re_core::services::user_import::{
+        ImportedUser, UserImportIssue, UserImportIssueKind, UserImportReport,
+    };
+    use std::collections::BTreeMap;
+    use uuid::Uuid;
+
+    fn report() -> UserImportReport {
+        UserImportReport {
+            dry_run: false,
+            total_records: 5,
+            resumed_from: 2,
+            imported: 1,
+            already_registered: 1,
+            invalid: 1,
+            markets: BTreeMap::from([("+61".to_string(), 1)]),
+            users: vec![ImportedUser {
+                record: 3,
+                user_id: Uuid::new_v4(),
+                country_code: "+61".to_string(),
+            }],
+            issues: vec![
+                UserImportIssue {
+                    record: 4,
+                    kind: UserImportIssueKind::AlreadyRegistered,
+                    message: "A user with phone number 412****678 is already registered".to_string(),
+                },
+                UserImportIssue {
+                    record: 5,
+                    kind: UserImportIssueKind::InvalidDate,
+                    message: "Invalid registration time yesterday".to_string(),
+                },
+            ],
+            ..UserImportReport::default()
+        }
+    }
+
+    #[test]
+    fn test_records_are_reported_per_row() {
+        let body = serde_json::to_value(UserImportResponse::new(report(), 12)).unwrap();
+
+        assert_eq!(body["resumed_from"], 2);
+        assert_eq!(body["markets"]["+61"], 1);
+        assert_eq!(body["successful"][0]["id"], "3");
+        assert_eq!(body["successful"][0]["result"]["country_code"], "+61");
+        assert_eq!(body["failed"][0]["id"], "4");
+        assert_eq!(body["failed"][0]["error"]["code"], "already_registered");
+        assert_eq!(body["failed"][1]["error"]["code"], "invalid_date");
+        assert_eq!(body["summary"]["total"], 3);
+        assert_eq!(body["summary"]["successful"], 1);
+        assert_eq!(body["summary"]["failed"], 2);
+        assert_eq!(body["summary"]["duration_ms"], 12);
+    }
+
+    #[test]
+    fn test_dry_run_defaults_to_false() {
+        let query: UserImportQuery = serde_json::from_str("{}").unwrap();
+        assert!(!query.dry_run);
+    }
+}
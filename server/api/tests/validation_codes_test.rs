@@ -0,0 +1,138 @@
+//! Tests keeping the validation error code catalog in sync with the DTOs and locale files
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::{web, App};
+    use re_api::dto::validation::{domain_validation_error, VALIDATION_CODES};
+    use re_api::routes::dev::validation_codes::list_validation_codes;
+    use re_core::errors::ValidationError as DomainValidationError;
+    use re_shared::config::environment::Environment;
+    use std::collections::{BTreeSet, HashMap};
+    use std::fs;
+    use std::path::Path;
+    use validator::Validate;
+
+    const LOCALES: [&str; 2] = ["en-US", "zh-CN"];
+
+    fn crate_path(relative: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(relative)
+    }
+
+    #[test]
+    fn test_every_code_is_translated_in_every_locale() {
+        for locale in LOCALES {
+            let content = fs::read_to_string(crate_path(&format!("src/i18n/locales/{}/validation.toml", locale))).unwrap();
+            let table: HashMap<String, toml::Value> = toml::from_str(&content).unwrap();
+
+            for code in VALIDATION_CODES {
+                let entry = table
+                    .get(code.code)
+                    .unwrap_or_else(|| panic!("{} is missing from {}/validation.toml", code.code, locale));
+                assert_eq!(entry["code"].as_str(), Some(code.code), "{} in {}", code.code, locale);
+
+                let message = entry["message"].as_str().unwrap();
+                let used: BTreeSet<&str> = message
+                    .split('{')
+                    .skip(1)
+                    .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+                    .collect();
+                let declared: BTreeSet<&str> = code.params.iter().copied().collect();
+                assert_eq!(used, declared, "placeholders of {} in {}", code.code, locale);
+            }
+
+            for key in table.keys() {
+                assert!(
+                    VALIDATION_CODES.iter().any(|code| code.code == key),
+                    "{} in {}/validation.toml is not in the catalog",
+                    key,
+                    locale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_rule_used_by_dtos_is_catalogued() {
+        for entry in fs::read_dir(crate_path("src/dto")).unwrap() {
+            let path = entry.unwrap().path();
+            let source = fs::read_to_string(&path).unwrap();
+            for attribute in source.split("#[validate(").skip(1) {
+                let name: String = attribute.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+                let name = name.as_str();
+                if name == "nested" || name == "schema" {
+                    continue;
+                }
+                assert!(
+                    VALIDATION_CODES.iter().any(|code| code.rules.contains(&name)),
+                    "rule `{}` used in {} has no validation code",
+                    name,
+                    path.display()
+                );
+            }
+        }
+    }
+
+    #[derive(Validate)]
+    struct Sample {
+        #[validate(length(equal = 6))]
+        code: String,
+        #[validate(range(min = 1, max = 5))]
+        quantity: i64,
+        #[validate(length(min = 2, max = 10))]
+        name: String,
+    }
+
+    #[test]
+    fn test_rules_map_to_catalogued_codes() {
+        let exact = Sample { code: "123".to_string(), quantity: 1, name: "ok".to_string() };
+        match domain_validation_error(&exact.validate().unwrap_err()) {
+            DomainValidationError::InvalidLength { field, expected, actual } => {
+                assert_eq!((field.as_str(), expected, actual), ("code", 6, 3));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let range = Sample { code: "123456".to_string(), quantity: 9, name: "ok".to_string() };
+        match domain_validation_error(&range.validate().unwrap_err()) {
+            DomainValidationError::OutOfRange { field, min, max } => {
+                assert_eq!((field.as_str(), min.as_str(), max.as_str()), ("quantity", "1", "5"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let length = Sample { code: "123456".to_string(), quantity: 1, name: "x".to_string() };
+        assert!(matches!(
+            domain_validation_error(&length.validate().unwrap_err()),
+            DomainValidationError::OutOfRange { field, .. } if field == "name"
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_catalog_endpoint_is_hidden_in_production() {
+        for (environment, status) in [
+            (Environment::Development, StatusCode::OK),
+            (Environment::Production, StatusCode::NOT_FOUND),
+        ] {
+            let app = init_service(
+                App::new()
+                    .app_data(web::Data::new(environment))
+                    .route("/dev/validation-codes", web::get().to(list_validation_codes)),
+            )
+            .await;
+            let response = call_service(&app, TestRequest::get().uri("/dev/validation-codes").to_request()).await;
+            assert_eq!(response.status(), status);
+
+            if status == StatusCode::OK {
+                let body: serde_json::Value = read_body_json(response).await;
+                assert_eq!(body["total"], VALIDATION_CODES.len());
+                let first = &body["validation_codes"][0];
+                assert_eq!(first["code"], "required_field");
+                assert_eq!(first["http_status"], 400);
+                assert_eq!(first["messages"]["en-US"], "Required field: {field}");
+                assert!(first["messages"]["zh-CN"].is_string());
+            }
+        }
+    }
+}
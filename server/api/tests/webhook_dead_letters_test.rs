@@ -0,0 +1,73 @@
+//! Tests for webhook dead-letter responses
+
+#[cfg(test)]
+mod tests {
+    use re_api::dto::admin::{BulkReplayRequest, BulkReplayResponse, WebhookDeadLetterResponse};
+    use re_core::domain::entities::webhook_dead_letter::{WebhookDeadLetter, WebhookDirection};
+    use re_core::services::webhook_dead_letter::{BulkReplayResult, ReplayResult, ReplayStatus};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn dead_letter() -> WebhookDeadLetter {
+        WebhookDeadLetter::inbound(
+            "stripe",
+            "evt_1",
+            "charge.refunded",
+            json!({ "id": "evt_1", "type": "charge.refunded" }),
+            "Refund exceeds the amount paid",
+        )
+    }
+
+    #[test]
+    fn test_payload_is_only_included_in_detail() {
+        let summary = serde_json::to_value(WebhookDeadLetterResponse::new(dead_letter(), false)).unwrap();
+        assert_eq!(summary["direction"], "inbound");
+        assert_eq!(summary["status"], "pending");
+        assert_eq!(summary["failures"], 1);
+        assert!(summary.get("payload").is_none());
+
+        let detail = serde_json::to_value(WebhookDeadLetterResponse::new(dead_letter(), true)).unwrap();
+        assert_eq!(detail["payload"]["id"], "evt_1");
+    }
+
+    #[test]
+    fn test_bulk_replay_counts_results() {
+        let result = |status| ReplayResult {
+            dead_letter_id: Uuid::new_v4(),
+            status,
+            detail: None,
+        };
+        let response = BulkReplayResponse::from(BulkReplayResult {
+            results: vec![
+                result(ReplayStatus::Replayed),
+                result(ReplayStatus::Replayed),
+                result(ReplayStatus::Failed),
+                result(ReplayStatus::InProgress),
+            ],
+            has_more: true,
+            errors: Vec::new(),
+        });
+
+        assert_eq!(response.replayed, 2);
+        assert_eq!(response.failed, 1);
+        assert_eq!(response.skipped, 1);
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["results"][2]["status"], "failed");
+        assert!(body.get("errors").is_none());
+    }
+
+    #[test]
+    fn test_bulk_replay_request_direction_is_optional() {
+        let request: BulkReplayRequest = serde_json::from_str(
+            r#"{"from": "2026-10-17T08:00:00Z", "to": "2026-10-17T10:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(request.direction.is_none());
+
+        let request: BulkReplayRequest = serde_json::from_str(
+            r#"{"direction": "outbound", "from": "2026-10-17T08:00:00Z", "to": "2026-10-17T10:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.direction, Some(WebhookDirection::Outbound));
+    }
+}
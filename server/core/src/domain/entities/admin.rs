@@ -0,0 +1,99 @@
+//! Administrator entities for the admin portal.
+//!
+//! Administrators do not have phone-based accounts. They sign in through the
+//! company's OpenID Connect provider, and the groups the provider reports
+//! are mapped to internal administrative roles on every login.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Administrative role granted through single sign-on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminRole {
+    /// Full access to the admin portal
+    Admin,
+    /// Customer support: users, orders and disputes
+    Support,
+    /// Finance: payments, payouts and reconciliation
+    Finance,
+}
+
+impl AdminRole {
+    /// Convert to string representation for tokens and storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::Support => "support",
+            Self::Finance => "finance",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "admin" => Some(Self::Admin),
+            "support" => Some(Self::Support),
+            "finance" => Some(Self::Finance),
+            _ => None,
+        }
+    }
+}
+
+/// An administrator known from the identity provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AdminIdentity {
+    /// Unique identifier, used as the token subject
+    pub id: Uuid,
+    /// Issuer URL of the identity provider
+    pub issuer: String,
+    /// Subject identifier at the identity provider
+    pub subject: String,
+    /// Email address reported by the provider
+    pub email: Option<String>,
+    /// Display name reported by the provider
+    pub name: Option<String>,
+    /// Roles granted at the last login
+    pub roles: Vec<AdminRole>,
+    /// When the administrator first signed in
+    pub created_at: DateTime<Utc>,
+    /// When the administrator last signed in
+    pub last_login_at: DateTime<Utc>,
+}
+
+impl AdminIdentity {
+    /// Create an identity for an administrator signing in for the first time
+    pub fn new(issuer: String, subject: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            issuer,
+            subject,
+            email: None,
+            name: None,
+            roles: Vec::new(),
+            created_at: now,
+            last_login_at: now,
+        }
+    }
+
+    /// Refresh the profile and roles reported at a login
+    pub fn record_login(&mut self, email: Option<String>, name: Option<String>, roles: Vec<AdminRole>) {
+        self.email = email;
+        self.name = name;
+        self.roles = roles;
+        self.last_login_at = Utc::now();
+    }
+
+    /// Checks whether the administrator holds a role
+    pub fn has_role(&self, role: AdminRole) -> bool {
+        self.roles.contains(&role)
+    }
+
+    /// Role names as carried in access tokens
+    pub fn role_names(&self) -> Vec<String> {
+        self.roles.iter().map(|role| role.as_str().to_string()).collect()
+    }
+}
@@ -0,0 +1,145 @@
+//! Marketing attribution entities for evaluating acquisition campaigns.
+//!
+//! Attribution is only kept for users who consented to marketing when they
+//! registered, and referrers are reduced to their host so full URLs, which
+//! may carry personal data in their query strings, are never stored.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest attribution value kept; longer values are truncated
+pub const MAX_ATTRIBUTION_VALUE_LENGTH: usize = 100;
+
+/// UTM parameters and referrer presented when a user registered
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrationAttribution {
+    /// Campaign source, e.g. `google` or `newsletter`
+    pub utm_source: Option<String>,
+
+    /// Marketing medium, e.g. `cpc` or `email`
+    pub utm_medium: Option<String>,
+
+    /// Campaign name, e.g. `spring_kitchens`
+    pub utm_campaign: Option<String>,
+
+    /// Paid search keyword
+    pub utm_term: Option<String>,
+
+    /// Variant of the ad or link that was clicked
+    pub utm_content: Option<String>,
+
+    /// Host of the page the user came from
+    pub referrer: Option<String>,
+
+    /// Whether the user consented to marketing when registering
+    pub marketing_consent: bool,
+}
+
+impl RegistrationAttribution {
+    /// Normalize the values for grouping
+    ///
+    /// Values are trimmed and truncated, empty ones dropped, source, medium
+    /// and campaign lowercased, and the referrer reduced to its host.
+    pub fn normalized(self) -> Self {
+        Self {
+            utm_source: normalize_value(self.utm_source, true),
+            utm_medium: normalize_value(self.utm_medium, true),
+            utm_campaign: normalize_value(self.utm_campaign, true),
+            utm_term: normalize_value(self.utm_term, false),
+            utm_content: normalize_value(self.utm_content, false),
+            referrer: self.referrer.as_deref().and_then(referrer_host),
+            marketing_consent: self.marketing_consent,
+        }
+    }
+
+    /// Whether any attribution value is present
+    pub fn has_values(&self) -> bool {
+        self.utm_source.is_some()
+            || self.utm_medium.is_some()
+            || self.utm_campaign.is_some()
+            || self.utm_term.is_some()
+            || self.utm_content.is_some()
+            || self.referrer.is_some()
+    }
+
+    /// The normalized attribution, if the user consented and it has values
+    pub fn consented(self) -> Option<Self> {
+        if !self.marketing_consent {
+            return None;
+        }
+        Some(self.normalized()).filter(Self::has_values)
+    }
+}
+
+/// Attribution stored for a registered user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserAttribution {
+    /// The registered user
+    pub user_id: Uuid,
+
+    /// Normalized attribution presented at registration
+    pub attribution: RegistrationAttribution,
+
+    /// When the user registered
+    pub registered_at: DateTime<Utc>,
+}
+
+/// Registrations of one weekly cohort arriving through one campaign
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttributionCohort {
+    /// Monday of the week the users registered in (UTC)
+    pub cohort_week: NaiveDate,
+
+    /// Campaign source, `None` for users arriving without one
+    pub utm_source: Option<String>,
+
+    /// Marketing medium
+    pub utm_medium: Option<String>,
+
+    /// Campaign name
+    pub utm_campaign: Option<String>,
+
+    /// Users who registered
+    pub registrations: u64,
+
+    /// Users who went on to select whether they are a customer or worker
+    pub activated: u64,
+}
+
+impl AttributionCohort {
+    /// Share of registered users who activated, between 0 and 1
+    pub fn activation_rate(&self) -> f64 {
+        if self.registrations == 0 {
+            return 0.0;
+        }
+        self.activated as f64 / self.registrations as f64
+    }
+}
+
+fn normalize_value(value: Option<String>, lowercase: bool) -> Option<String> {
+    let value = value?;
+    let value: String = value.trim().chars().take(MAX_ATTRIBUTION_VALUE_LENGTH).collect();
+    let value = value.trim_end();
+    if value.is_empty() {
+        None
+    } else if lowercase {
+        Some(value.to_lowercase())
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Host of a referrer URL, without scheme, credentials, port, path or query
+fn referrer_host(referrer: &str) -> Option<String> {
+    let referrer = referrer.trim();
+    let without_scheme = referrer.split_once("://").map_or(referrer, |(_, rest)| rest);
+    let authority = without_scheme.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_and_port = authority.rsplit('@').next().unwrap_or_default();
+    let host = host_and_port.split(':').next().unwrap_or_default().to_lowercase();
+
+    let valid = !host.is_empty()
+        && host.len() <= MAX_ATTRIBUTION_VALUE_LENGTH
+        && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    valid.then_some(host)
+}
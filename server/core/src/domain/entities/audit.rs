@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use re_shared::types::DateRange;
+
 /// Event types for comprehensive authentication auditing
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -47,11 +49,18 @@ pub enum AuditEventType {
     // Security events
     SuspiciousActivity,
     InvalidTokenUsage,
+    GeoAnomalyDetected,
+    SuspiciousLoginDetected,
+    SuspiciousLoginReported,
     
     // Refresh token events
     RefreshTokenAttempt,
     RefreshTokenSuccess,
     RefreshTokenFailure,
+    RefreshTokenReuseDetected,
+    
+    // Administration events
+    AdminAction,
 }
 
 impl AuditEventType {
@@ -81,9 +90,14 @@ impl AuditEventType {
             Self::SessionExpired => "SESSION_EXPIRED",
             Self::SuspiciousActivity => "SUSPICIOUS_ACTIVITY",
             Self::InvalidTokenUsage => "INVALID_TOKEN_USAGE",
+            Self::GeoAnomalyDetected => "GEO_ANOMALY_DETECTED",
+            Self::SuspiciousLoginDetected => "SUSPICIOUS_LOGIN_DETECTED",
+            Self::SuspiciousLoginReported => "SUSPICIOUS_LOGIN_REPORTED",
             Self::RefreshTokenAttempt => "REFRESH_TOKEN_ATTEMPT",
             Self::RefreshTokenSuccess => "REFRESH_TOKEN_SUCCESS",
             Self::RefreshTokenFailure => "REFRESH_TOKEN_FAILURE",
+            Self::RefreshTokenReuseDetected => "REFRESH_TOKEN_REUSE_DETECTED",
+            Self::AdminAction => "ADMIN_ACTION",
         }
     }
     
@@ -113,9 +127,14 @@ impl AuditEventType {
             "SESSION_EXPIRED" => Some(Self::SessionExpired),
             "SUSPICIOUS_ACTIVITY" => Some(Self::SuspiciousActivity),
             "INVALID_TOKEN_USAGE" => Some(Self::InvalidTokenUsage),
+            "GEO_ANOMALY_DETECTED" => Some(Self::GeoAnomalyDetected),
+            "SUSPICIOUS_LOGIN_DETECTED" => Some(Self::SuspiciousLoginDetected),
+            "SUSPICIOUS_LOGIN_REPORTED" => Some(Self::SuspiciousLoginReported),
             "REFRESH_TOKEN_ATTEMPT" => Some(Self::RefreshTokenAttempt),
             "REFRESH_TOKEN_SUCCESS" => Some(Self::RefreshTokenSuccess),
             "REFRESH_TOKEN_FAILURE" => Some(Self::RefreshTokenFailure),
+            "REFRESH_TOKEN_REUSE_DETECTED" => Some(Self::RefreshTokenReuseDetected),
+            "ADMIN_ACTION" => Some(Self::AdminAction),
             _ => None,
         }
     }
@@ -160,6 +179,10 @@ pub struct AuditLog {
     /// Rate limit type if applicable
     pub rate_limit_type: Option<String>,
     
+    /// Trace ID of the API request that produced the event
+    #[serde(default)]
+    pub trace_id: Option<String>,
+    
     /// Whether the action succeeded (kept for backward compatibility)
     pub success: bool,
     
@@ -194,6 +217,7 @@ impl AuditLog {
                 | AuditEventType::TokenGenerated
                 | AuditEventType::TokenRefreshed
                 | AuditEventType::RefreshTokenSuccess
+                | AuditEventType::AdminAction
         );
         
         Self {
@@ -209,6 +233,7 @@ impl AuditLog {
             failure_reason: None,
             token_id: None,
             rate_limit_type: None,
+            trace_id: None,
             success,
             action,
             error_message: None,
@@ -239,6 +264,7 @@ impl AuditLog {
             failure_reason: None,
             token_id: None,
             rate_limit_type: None,
+            trace_id: None,
             success,
             action: action_str,
             error_message: None,
@@ -286,6 +312,12 @@ impl AuditLog {
         self
     }
     
+    /// Add the trace ID of the API request that produced the event
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+    
     /// Add device information
     pub fn with_device_info(mut self, device_info: impl Into<String>) -> Self {
         self.device_info = Some(device_info.into());
@@ -344,6 +376,25 @@ impl AuditLog {
         format!("****{}", last_four)
     }
     
+    /// Mask an IP address down to its network part
+    ///
+    /// IPv4 addresses keep the first two octets (`203.0.*.*`) and IPv6
+    /// addresses the first three groups (`2001:db8:85a3::*`). Anything that
+    /// is not an IP address is fully masked.
+    pub fn mask_ip(ip_address: &str) -> String {
+        match ip_address.parse::<std::net::IpAddr>() {
+            Ok(std::net::IpAddr::V4(ip)) => {
+                let octets = ip.octets();
+                format!("{}.{}.*.*", octets[0], octets[1])
+            }
+            Ok(std::net::IpAddr::V6(ip)) => {
+                let segments = ip.segments();
+                format!("{:x}:{:x}:{:x}::*", segments[0], segments[1], segments[2])
+            }
+            Err(_) => "*".to_string(),
+        }
+    }
+    
     /// Extract device information from user agent string
     pub fn extract_device_info(user_agent: &str) -> String {
         // Simple extraction - can be enhanced with a proper user agent parser
@@ -389,6 +440,49 @@ impl AuditLog {
     }
 }
 
+/// Criteria for querying audit logs
+///
+/// Unset criteria match every log; set criteria must all match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    /// Only logs of this user
+    pub user_id: Option<Uuid>,
+    /// Only logs of this hashed phone number
+    pub phone_hash: Option<String>,
+    /// Only logs of these event types; empty matches all types
+    pub event_types: Vec<AuditEventType>,
+    /// Only logs from this IP address
+    pub ip_address: Option<String>,
+    /// Only logs created within this range
+    pub date_range: DateRange,
+}
+
+impl Default for AuditLogFilter {
+    fn default() -> Self {
+        Self {
+            user_id: None,
+            phone_hash: None,
+            event_types: Vec::new(),
+            ip_address: None,
+            date_range: DateRange::new(None, None),
+        }
+    }
+}
+
+impl AuditLogFilter {
+    /// Whether a log matches every set criterion
+    pub fn matches(&self, log: &AuditLog) -> bool {
+        self.user_id.is_none_or(|user_id| log.user_id == Some(user_id))
+            && self
+                .phone_hash
+                .as_deref()
+                .is_none_or(|hash| log.phone_hash.as_deref() == Some(hash))
+            && (self.event_types.is_empty() || self.event_types.contains(&log.event_type))
+            && self.ip_address.as_deref().is_none_or(|ip| log.ip_address == ip)
+            && self.date_range.contains(&log.created_at)
+    }
+}
+
 /// Common audit log actions
 pub mod actions {
     /// User attempts to send verification code
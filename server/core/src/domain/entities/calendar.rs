@@ -0,0 +1,52 @@
+//! Business calendar entities for ad-hoc closure dates.
+//!
+//! Public holidays and regular business hours come from configuration;
+//! closures are one-off non-business days added by administrators, such as
+//! extreme weather or a platform-wide shutdown.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An ad-hoc date on which a market is closed for business
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClosureDate {
+    /// Unique identifier for the closure
+    pub id: Uuid,
+
+    /// Market code the closure applies to (ISO 3166-1 alpha-2, e.g. "AU")
+    pub market: String,
+
+    /// Local date on which the market is closed
+    pub date: NaiveDate,
+
+    /// Reason shown to administrators
+    pub reason: String,
+
+    /// Administrator who added the closure
+    pub created_by: Option<Uuid>,
+
+    /// Timestamp when the closure was added
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClosureDate {
+    /// Create a new closure date
+    ///
+    /// The market code is normalized to upper case.
+    pub fn new(market: &str, date: NaiveDate, reason: String, created_by: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            market: normalize_market(market),
+            date,
+            reason,
+            created_by,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Normalize a market code for comparison and storage
+pub fn normalize_market(market: &str) -> String {
+    market.trim().to_ascii_uppercase()
+}
@@ -0,0 +1,476 @@
+//! Notification campaign entities for bulk messages sent by administrators.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::user::{User, UserType};
+
+/// Maximum length of a campaign title
+pub const MAX_CAMPAIGN_TITLE_LENGTH: usize = 100;
+
+/// Maximum length of a campaign body
+pub const MAX_CAMPAIGN_BODY_LENGTH: usize = 1000;
+
+/// Maximum length of an SMS campaign body, about two concatenated segments
+pub const MAX_SMS_CAMPAIGN_BODY_LENGTH: usize = 320;
+
+/// Lifecycle status of a campaign
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignStatus {
+    /// Waiting for its scheduled start time
+    Scheduled,
+    /// Claimed by a dispatcher that is enqueueing its audience
+    Dispatching,
+    /// Audience resolved, deliveries are being sent
+    Sending,
+    /// Every delivery has been processed
+    Completed,
+    /// Cancelled by an administrator
+    Cancelled,
+}
+
+impl CampaignStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "scheduled",
+            Self::Dispatching => "dispatching",
+            Self::Sending => "sending",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "scheduled" => Some(Self::Scheduled),
+            "dispatching" => Some(Self::Dispatching),
+            "sending" => Some(Self::Sending),
+            "completed" => Some(Self::Completed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Channel a campaign is delivered through
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignChannel {
+    /// In-app push notification with a title and body
+    #[default]
+    Push,
+    /// Text message with the body only, sent to the user's phone number
+    Sms,
+}
+
+impl CampaignChannel {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Push => "push",
+            Self::Sms => "sms",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "push" => Some(Self::Push),
+            "sms" => Some(Self::Sms),
+            _ => None,
+        }
+    }
+}
+
+/// Filters selecting which users receive a campaign
+///
+/// Empty or unset filters match everyone. Blocked users never match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignAudience {
+    /// Only users of this type
+    pub user_type: Option<UserType>,
+
+    /// Only users from these markets, by country code (e.g. "+61")
+    #[serde(default)]
+    pub country_codes: Vec<String>,
+
+    /// Only users who logged in within this many days
+    pub active_within_days: Option<u32>,
+}
+
+impl CampaignAudience {
+    /// Checks whether a user belongs to the audience at the given time
+    pub fn matches(&self, user: &User, now: DateTime<Utc>) -> bool {
+        if user.is_blocked {
+            return false;
+        }
+
+        if let Some(user_type) = self.user_type {
+            if user.user_type != Some(user_type) {
+                return false;
+            }
+        }
+
+        if !self.country_codes.is_empty() && !self.country_codes.contains(&user.country_code) {
+            return false;
+        }
+
+        match (self.active_within_days, user.last_login_at) {
+            (Some(days), Some(last_login)) => last_login >= now - Duration::days(days as i64),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+/// Title and body of a campaign in one language
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignContent {
+    pub title: String,
+    pub body: String,
+}
+
+/// A bulk notification sent to an audience of users
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Campaign {
+    /// Unique identifier for the campaign
+    pub id: Uuid,
+
+    /// Internal name shown to administrators
+    pub name: String,
+
+    /// Channel the campaign is delivered through
+    #[serde(default)]
+    pub channel: CampaignChannel,
+
+    /// Users the campaign is sent to
+    pub audience: CampaignAudience,
+
+    /// Localized content keyed by language code (e.g. "en", "zh")
+    pub content: HashMap<String, CampaignContent>,
+
+    /// Language used when a recipient's language has no content
+    pub default_language: String,
+
+    /// Time at which sending starts
+    pub scheduled_at: DateTime<Utc>,
+
+    /// Current lifecycle status
+    pub status: CampaignStatus,
+
+    /// Number of recipients, known once sending starts
+    pub audience_size: u64,
+
+    /// Administrator who created the campaign
+    pub created_by: Uuid,
+
+    /// Timestamp when the campaign was created
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp when the campaign was last updated
+    pub updated_at: DateTime<Utc>,
+
+    /// Timestamp when the campaign completed
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl Campaign {
+    /// Creates a new scheduled campaign
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - A valid campaign
+    /// * `Err(String)` - Why the campaign was rejected
+    pub fn new(
+        name: String,
+        audience: CampaignAudience,
+        content: HashMap<String, CampaignContent>,
+        default_language: String,
+        scheduled_at: DateTime<Utc>,
+        created_by: Uuid,
+    ) -> Result<Self, String> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err("Campaign name must not be empty".to_string());
+        }
+
+        if !content.contains_key(&default_language) {
+            return Err(format!("Content is missing the default language '{}'", default_language));
+        }
+
+        for (language, content) in &content {
+            if content.title.trim().is_empty() || content.title.chars().count() > MAX_CAMPAIGN_TITLE_LENGTH {
+                return Err(format!(
+                    "Title for '{}' must be 1-{} characters",
+                    language, MAX_CAMPAIGN_TITLE_LENGTH
+                ));
+            }
+            if content.body.trim().is_empty() || content.body.chars().count() > MAX_CAMPAIGN_BODY_LENGTH {
+                return Err(format!(
+                    "Body for '{}' must be 1-{} characters",
+                    language, MAX_CAMPAIGN_BODY_LENGTH
+                ));
+            }
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            channel: CampaignChannel::Push,
+            audience,
+            content,
+            default_language,
+            scheduled_at,
+            status: CampaignStatus::Scheduled,
+            audience_size: 0,
+            created_by,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        })
+    }
+
+    /// Sets the delivery channel
+    ///
+    /// SMS campaigns only send the body, which must fit in
+    /// [`MAX_SMS_CAMPAIGN_BODY_LENGTH`] characters in every language.
+    ///
+    /// # Returns
+    /// * `Err(String)` - A body is too long for the channel
+    pub fn with_channel(mut self, channel: CampaignChannel) -> Result<Self, String> {
+        if channel == CampaignChannel::Sms {
+            for (language, content) in &self.content {
+                if content.body.chars().count() > MAX_SMS_CAMPAIGN_BODY_LENGTH {
+                    return Err(format!(
+                        "SMS body for '{}' must be at most {} characters",
+                        language, MAX_SMS_CAMPAIGN_BODY_LENGTH
+                    ));
+                }
+            }
+        }
+
+        self.channel = channel;
+        Ok(self)
+    }
+
+    /// Returns the content for a language, falling back to the default language
+    pub fn content_for(&self, language: &str) -> Option<&CampaignContent> {
+        self.content
+            .get(language)
+            .or_else(|| self.content.get(&self.default_language))
+    }
+
+    /// Checks whether the campaign is scheduled and its start time has passed
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == CampaignStatus::Scheduled && self.scheduled_at <= now
+    }
+
+    /// Marks the campaign as sending to a resolved audience
+    pub fn start(&mut self, audience_size: u64) {
+        self.status = CampaignStatus::Sending;
+        self.audience_size = audience_size;
+        self.updated_at = Utc::now();
+    }
+
+    /// Marks the campaign as completed
+    pub fn complete(&mut self) {
+        let now = Utc::now();
+        self.status = CampaignStatus::Completed;
+        self.completed_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Cancels the campaign if it has not finished yet
+    ///
+    /// # Returns
+    /// * `true` if the campaign is now cancelled
+    pub fn cancel(&mut self) -> bool {
+        match self.status {
+            CampaignStatus::Scheduled | CampaignStatus::Dispatching | CampaignStatus::Sending => {
+                self.status = CampaignStatus::Cancelled;
+                self.updated_at = Utc::now();
+                true
+            }
+            CampaignStatus::Completed | CampaignStatus::Cancelled => false,
+        }
+    }
+}
+
+/// Delivery status of a campaign message to one recipient
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting to be sent
+    Pending,
+    /// Claimed by a dispatcher that is sending it
+    Processing,
+    /// Delivered to the notification channel
+    Sent,
+    /// The notification channel rejected the message
+    Failed,
+    /// Skipped because the recipient opted out of marketing messages
+    OptedOut,
+}
+
+impl DeliveryStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Processing => "processing",
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::OptedOut => "opted_out",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "processing" => Some(Self::Processing),
+            "sent" => Some(Self::Sent),
+            "failed" => Some(Self::Failed),
+            "opted_out" => Some(Self::OptedOut),
+            _ => None,
+        }
+    }
+}
+
+/// A campaign message addressed to one recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignDelivery {
+    /// Unique identifier for the delivery
+    pub id: Uuid,
+
+    /// Campaign the delivery belongs to
+    pub campaign_id: Uuid,
+
+    /// Recipient user
+    pub user_id: Uuid,
+
+    /// Recipient's country code, used to pick the content language
+    pub country_code: String,
+
+    /// Current delivery status
+    pub status: DeliveryStatus,
+
+    /// Earliest time the delivery may be attempted
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// Channel error for failed deliveries
+    pub error: Option<String>,
+
+    /// Channel message ID once sent
+    pub provider_message_id: Option<String>,
+
+    /// Timestamp when the message was sent
+    pub sent_at: Option<DateTime<Utc>>,
+
+    /// Timestamp when the recipient first opened the message
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
+impl CampaignDelivery {
+    /// Creates a new pending delivery
+    pub fn new(campaign_id: Uuid, user_id: Uuid, country_code: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            campaign_id,
+            user_id,
+            country_code,
+            status: DeliveryStatus::Pending,
+            next_attempt_at: Utc::now(),
+            error: None,
+            provider_message_id: None,
+            sent_at: None,
+            opened_at: None,
+        }
+    }
+
+    /// Claims a pending delivery for sending
+    ///
+    /// # Returns
+    /// * `true` if the delivery was pending and is now processing
+    pub fn claim(&mut self) -> bool {
+        if self.status == DeliveryStatus::Pending {
+            self.status = DeliveryStatus::Processing;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns a claimed delivery to pending, so the dispatcher holding the
+    /// claim can send, defer or skip it like any pending delivery
+    pub fn release(&mut self) {
+        if self.status == DeliveryStatus::Processing {
+            self.status = DeliveryStatus::Pending;
+        }
+    }
+
+    /// Marks the delivery as sent
+    pub fn mark_sent(&mut self, provider_message_id: String) {
+        self.status = DeliveryStatus::Sent;
+        self.provider_message_id = Some(provider_message_id);
+        self.sent_at = Some(Utc::now());
+    }
+
+    /// Marks the delivery as failed
+    pub fn mark_failed(&mut self, error: String) {
+        self.status = DeliveryStatus::Failed;
+        self.error = Some(error);
+    }
+
+    /// Marks the delivery as skipped because the recipient opted out
+    pub fn mark_opted_out(&mut self) {
+        self.status = DeliveryStatus::OptedOut;
+    }
+
+    /// Records the first open of a sent message
+    ///
+    /// # Returns
+    /// * `true` if this was the first open
+    pub fn mark_opened(&mut self) -> bool {
+        if self.status != DeliveryStatus::Sent || self.opened_at.is_some() {
+            return false;
+        }
+        self.opened_at = Some(Utc::now());
+        true
+    }
+}
+
+/// Delivery and open statistics for a campaign
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignStats {
+    /// Deliveries not attempted yet, including those a dispatcher is sending
+    pub pending: u64,
+    pub sent: u64,
+    pub failed: u64,
+    pub opted_out: u64,
+    pub opened: u64,
+}
+
+impl CampaignStats {
+    /// Total number of deliveries
+    pub fn total(&self) -> u64 {
+        self.pending + self.sent + self.failed + self.opted_out
+    }
+
+    /// Share of sent messages that were opened, between 0.0 and 1.0
+    pub fn open_rate(&self) -> f64 {
+        if self.sent == 0 {
+            0.0
+        } else {
+            self.opened as f64 / self.sent as f64
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Order completion checklist entities.
+//!
+//! Administrators configure a checklist per job category. When a job is
+//! done the worker fills in every item with photos, and the customer signs
+//! off by typing their name. The sign-off records a hash of the checklist
+//! it approved, so the completion used for invoicing and disputes cannot be
+//! changed afterwards without detection.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::fee_schedule::normalize_category;
+
+/// An item the worker must complete, as configured for a category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistItemSpec {
+    /// Stable key of the item (e.g. "grout_sealed")
+    pub key: String,
+
+    /// Label shown to the worker and customer
+    pub label: String,
+
+    /// Photos the worker must attach to the item
+    pub min_photos: u32,
+}
+
+/// A version of the completion checklist for a job category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChecklistTemplate {
+    /// Unique identifier for the template
+    pub id: Uuid,
+
+    /// Job category the template applies to, `None` for every category without its own
+    pub category: Option<String>,
+
+    /// Version number, increasing per category
+    pub version: i32,
+
+    /// Items in display order
+    pub items: Vec<ChecklistItemSpec>,
+
+    /// Administrator who created the template
+    pub created_by: Option<Uuid>,
+
+    /// When the template was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChecklistTemplate {
+    /// Creates a new checklist template version
+    ///
+    /// The category is normalized to lower case and item keys are trimmed.
+    ///
+    /// # Returns
+    /// * `Err(String)` - If there are no items, a key or label is empty, or
+    ///   two items share a key
+    pub fn new(
+        category: Option<&str>,
+        version: i32,
+        items: Vec<ChecklistItemSpec>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, String> {
+        if items.is_empty() {
+            return Err("A checklist needs at least one item".to_string());
+        }
+
+        let mut normalized: Vec<ChecklistItemSpec> = Vec::with_capacity(items.len());
+        for item in items {
+            let key = item.key.trim().to_ascii_lowercase();
+            let label = item.label.trim().to_string();
+            if key.is_empty() || label.is_empty() {
+                return Err("Checklist items need a key and a label".to_string());
+            }
+            if normalized.iter().any(|existing| existing.key == key) {
+                return Err(format!("Duplicate checklist item: {}", key));
+            }
+            normalized.push(ChecklistItemSpec { key, label, min_photos: item.min_photos });
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            category: category.map(normalize_category).filter(|c| !c.is_empty()),
+            version,
+            items: normalized,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// An item as completed by the worker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedItem {
+    /// Key of the template item
+    pub key: String,
+
+    /// Label of the template item when the checklist was filled in
+    pub label: String,
+
+    /// Storage keys of the photos the worker uploaded
+    pub photos: Vec<String>,
+
+    /// Optional remark by the worker
+    pub note: Option<String>,
+}
+
+/// The customer's approval of a completed checklist
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomerSignOff {
+    /// Name the customer typed to sign
+    pub signed_name: String,
+
+    /// When the customer signed
+    pub signed_at: DateTime<Utc>,
+
+    /// Device the customer signed on, as reported by the app
+    pub device_info: Option<String>,
+
+    /// Hash of the checklist content the customer approved
+    pub content_hash: String,
+}
+
+/// The completion checklist of an order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletionChecklist {
+    /// Order the checklist completes
+    pub order_id: Uuid,
+
+    /// Worker who did the job
+    pub worker_id: Uuid,
+
+    /// Customer who signs off
+    pub customer_id: Uuid,
+
+    /// Template the checklist was filled in from
+    pub template_id: Uuid,
+
+    /// Version of that template, kept for disputes
+    pub template_version: i32,
+
+    /// Completed items in template order
+    pub items: Vec<CompletedItem>,
+
+    /// When the worker last submitted the checklist
+    pub submitted_at: DateTime<Utc>,
+
+    /// The customer's sign-off, once given
+    pub sign_off: Option<CustomerSignOff>,
+}
+
+impl CompletionChecklist {
+    /// Whether the customer signed off
+    pub fn is_signed(&self) -> bool {
+        self.sign_off.is_some()
+    }
+
+    /// SHA-256 hash of the content the customer approves, hex encoded
+    ///
+    /// Covers the order, the parties, the template version and every item
+    /// with its photos and note.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.order_id.as_bytes());
+        hasher.update(self.worker_id.as_bytes());
+        hasher.update(self.customer_id.as_bytes());
+        hasher.update(self.template_id.as_bytes());
+        hasher.update(self.template_version.to_be_bytes());
+        for item in &self.items {
+            for value in [&item.key, &item.label]
+                .into_iter()
+                .chain(&item.photos)
+                .chain(item.note.as_ref())
+            {
+                hasher.update((value.len() as u64).to_be_bytes());
+                hasher.update(value.as_bytes());
+            }
+            hasher.update([0xff]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Whether the checklist still matches what the customer signed
+    ///
+    /// # Returns
+    /// * `false` - The checklist is unsigned or was changed after signing
+    pub fn matches_sign_off(&self) -> bool {
+        self.sign_off
+            .as_ref()
+            .is_some_and(|sign_off| sign_off.content_hash == self.content_hash())
+    }
+
+    /// Records the customer's sign-off over the current content
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the checklist was already signed
+    pub fn sign(
+        &mut self,
+        signed_name: impl Into<String>,
+        device_info: Option<String>,
+        signed_at: DateTime<Utc>,
+    ) -> Result<&CustomerSignOff, String> {
+        if self.is_signed() {
+            return Err("Checklist is already signed off".to_string());
+        }
+
+        let content_hash = self.content_hash();
+        Ok(self.sign_off.insert(CustomerSignOff {
+            signed_name: signed_name.into(),
+            signed_at,
+            device_info,
+            content_hash,
+        }))
+    }
+}
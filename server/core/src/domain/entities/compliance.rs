@@ -0,0 +1,272 @@
+//! Market compliance entities.
+//!
+//! Some markets require users to complete regulatory checks before they can
+//! trade on the platform, such as real-name verification of residents in
+//! China or the ICP filing of a business publishing a website there. Each
+//! market lists its requirements as rules; a user's progress on each is kept
+//! as a compliance check.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::user::UserType;
+
+/// A regulatory check a market can require
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceRequirement {
+    /// The user's legal name matches their national identity document
+    RealName,
+    /// The business has an ICP filing for its website
+    IcpFiling,
+}
+
+impl ComplianceRequirement {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RealName => "real_name",
+            Self::IcpFiling => "icp_filing",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "real_name" => Some(Self::RealName),
+            "icp_filing" => Some(Self::IcpFiling),
+            _ => None,
+        }
+    }
+}
+
+/// Progress of a user on a compliance requirement
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceStatus {
+    /// Nothing has been submitted yet
+    Required,
+    /// The provider is still checking the submission
+    Pending,
+    /// The requirement is met
+    Verified,
+    /// The provider rejected the submission; the user may submit again
+    Rejected,
+}
+
+impl ComplianceStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Required => "required",
+            Self::Pending => "pending",
+            Self::Verified => "verified",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "required" => Some(Self::Required),
+            "pending" => Some(Self::Pending),
+            "verified" => Some(Self::Verified),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// An action a compliance requirement can hold back until it is met
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceAction {
+    /// Paying for an order
+    Order,
+    /// Requesting a payout
+    Payout,
+}
+
+impl ComplianceAction {
+    /// Convert to string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Order => "order",
+            Self::Payout => "payout",
+        }
+    }
+}
+
+/// A requirement of a market and whom it applies to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceRule {
+    /// The check users have to complete
+    pub requirement: ComplianceRequirement,
+    /// User types the rule applies to; empty for all users
+    #[serde(default)]
+    pub user_types: Vec<UserType>,
+    /// Actions held back until the check is verified
+    pub gates: Vec<ComplianceAction>,
+}
+
+impl ComplianceRule {
+    /// Create a rule applying to all users
+    pub fn new(requirement: ComplianceRequirement, gates: Vec<ComplianceAction>) -> Self {
+        Self {
+            requirement,
+            user_types: Vec::new(),
+            gates,
+        }
+    }
+
+    /// Restrict the rule to the given user types
+    pub fn for_user_types(mut self, user_types: Vec<UserType>) -> Self {
+        self.user_types = user_types;
+        self
+    }
+
+    /// Whether the rule applies to a user of the given type
+    ///
+    /// Rules restricted to user types do not apply before a type is selected.
+    pub fn applies_to(&self, user_type: Option<UserType>) -> bool {
+        self.user_types.is_empty() || user_type.is_some_and(|t| self.user_types.contains(&t))
+    }
+
+    /// Whether the rule holds back the given action
+    pub fn gates(&self, action: ComplianceAction) -> bool {
+        self.gates.contains(&action)
+    }
+}
+
+/// A user's submission for a compliance requirement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceCheck {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// User the check belongs to
+    pub user_id: Uuid,
+
+    /// Requirement being checked
+    pub requirement: ComplianceRequirement,
+
+    /// Market that required the check, as a country calling code (e.g. "+86")
+    pub market: String,
+
+    /// Verification provider handling the submission
+    pub provider: String,
+
+    /// Provider's identifier for the latest submission
+    pub provider_reference: Option<String>,
+
+    /// Last four characters of the submitted document number
+    pub document_last4: Option<String>,
+
+    /// Progress on the requirement
+    pub status: ComplianceStatus,
+
+    /// Why the provider rejected the latest submission
+    pub rejection_reason: Option<String>,
+
+    /// Submissions made so far
+    pub attempts: u32,
+
+    /// When the requirement was met
+    pub verified_at: Option<DateTime<Utc>>,
+
+    /// When the first submission was made
+    pub created_at: DateTime<Utc>,
+
+    /// When the check was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ComplianceCheck {
+    /// Creates a check for a requirement nothing has been submitted for
+    pub fn new(
+        user_id: Uuid,
+        requirement: ComplianceRequirement,
+        market: impl Into<String>,
+        provider: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            requirement,
+            market: market.into(),
+            provider: provider.into(),
+            provider_reference: None,
+            document_last4: None,
+            status: ComplianceStatus::Required,
+            rejection_reason: None,
+            attempts: 0,
+            verified_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Record a submission sent to the provider, awaiting its outcome
+    ///
+    /// Only the last four characters of the document number are kept.
+    pub fn submit(&mut self, provider_reference: impl Into<String>, document_number: &str) {
+        let chars: Vec<char> = document_number.trim().chars().collect();
+        let last4: String = chars[chars.len().saturating_sub(4)..].iter().collect();
+
+        self.provider_reference = Some(provider_reference.into());
+        self.document_last4 = Some(last4);
+        self.status = ComplianceStatus::Pending;
+        self.rejection_reason = None;
+        self.attempts += 1;
+        self.updated_at = Utc::now();
+    }
+
+    /// Mark the requirement as met
+    pub fn verify(&mut self) {
+        let now = Utc::now();
+        self.status = ComplianceStatus::Verified;
+        self.rejection_reason = None;
+        self.verified_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Mark the latest submission as rejected
+    pub fn reject(&mut self, reason: impl Into<String>) {
+        self.status = ComplianceStatus::Rejected;
+        self.rejection_reason = Some(reason.into());
+        self.updated_at = Utc::now();
+    }
+
+    /// Whether the requirement is met
+    pub fn is_verified(&self) -> bool {
+        self.status == ComplianceStatus::Verified
+    }
+}
+
+/// A user's progress on one requirement of their market
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceStep {
+    /// The requirement
+    pub requirement: ComplianceRequirement,
+    /// Market that requires it
+    pub market: String,
+    /// Progress on the requirement
+    pub status: ComplianceStatus,
+    /// Actions held back until the requirement is met
+    pub gates: Vec<ComplianceAction>,
+    /// Why the latest submission was rejected
+    pub rejection_reason: Option<String>,
+    /// Submissions the user has left
+    pub attempts_remaining: u32,
+}
+
+impl ComplianceStep {
+    /// Whether the step holds back the given action
+    pub fn blocks(&self, action: ComplianceAction) -> bool {
+        self.status != ComplianceStatus::Verified && self.gates.contains(&action)
+    }
+}
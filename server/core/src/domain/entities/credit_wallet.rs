@@ -0,0 +1,204 @@
+//! Customer credit wallet entities.
+//!
+//! Credit is granted in separate grants so each can carry its own expiry.
+//! Grants are spent soonest-expiring first; the money itself is tracked in
+//! the double-entry journal, the grants only record what is left to spend.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::payment::normalize_currency;
+
+/// Why credit was granted to a customer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditSource {
+    /// Refund paid out as credit instead of to the payment method
+    Refund,
+    /// Promotional credit, e.g. a sign-up or referral bonus
+    Promotion,
+    /// Credit granted by support as compensation
+    Goodwill,
+}
+
+impl CreditSource {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Refund => "refund",
+            Self::Promotion => "promotion",
+            Self::Goodwill => "goodwill",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "refund" => Some(Self::Refund),
+            "promotion" => Some(Self::Promotion),
+            "goodwill" => Some(Self::Goodwill),
+            _ => None,
+        }
+    }
+}
+
+/// An amount of credit granted to a customer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditGrant {
+    /// Unique identifier for the grant
+    pub id: Uuid,
+
+    /// Customer the credit belongs to
+    pub customer_id: Uuid,
+
+    /// Why the credit was granted
+    pub source: CreditSource,
+
+    /// Identifier of the granting event, unique across grants
+    pub reference: String,
+
+    /// Granted amount in minor units
+    pub amount: i64,
+
+    /// Amount not yet spent or expired in minor units
+    pub remaining: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// When the unspent credit expires, `None` if it never does
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// When the credit was granted
+    pub created_at: DateTime<Utc>,
+}
+
+impl CreditGrant {
+    /// Creates a new grant with its full amount remaining
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the amount is not positive or the currency is invalid
+    pub fn new(
+        customer_id: Uuid,
+        source: CreditSource,
+        reference: impl Into<String>,
+        amount: i64,
+        currency: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, String> {
+        if amount <= 0 {
+            return Err(format!("Credit amount must be positive: {}", amount));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            customer_id,
+            source,
+            reference: reference.into(),
+            amount,
+            remaining: amount,
+            currency: normalize_currency(currency)?,
+            expires_at,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Whether the grant has passed its expiry
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether the grant can still be spent
+    pub fn is_spendable(&self, now: DateTime<Utc>) -> bool {
+        self.remaining > 0 && !self.is_expired(now)
+    }
+
+    /// Spends up to `amount` from the grant, returning what was taken
+    pub fn consume(&mut self, amount: i64) -> i64 {
+        let taken = amount.clamp(0, self.remaining);
+        self.remaining -= taken;
+        taken
+    }
+}
+
+/// Type of change to a customer's wallet
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditTransactionKind {
+    /// Credit was granted
+    Issued,
+    /// Credit was spent on a job
+    Redeemed,
+    /// Unspent credit expired
+    Expired,
+}
+
+impl CreditTransactionKind {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Issued => "issued",
+            Self::Redeemed => "redeemed",
+            Self::Expired => "expired",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "issued" => Some(Self::Issued),
+            "redeemed" => Some(Self::Redeemed),
+            "expired" => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// A change to a customer's wallet, as shown in the wallet history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditTransaction {
+    /// Unique identifier for the transaction
+    pub id: Uuid,
+
+    /// Customer whose wallet changed
+    pub customer_id: Uuid,
+
+    /// Type of change
+    pub kind: CreditTransactionKind,
+
+    /// Positive amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Grant or order reference of the change, unique per kind
+    pub reference: String,
+
+    /// When the change happened
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl CreditTransaction {
+    /// Creates a new wallet transaction happening now
+    pub fn new(
+        customer_id: Uuid,
+        kind: CreditTransactionKind,
+        amount: i64,
+        currency: &str,
+        reference: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            customer_id,
+            kind,
+            amount,
+            currency: currency.to_string(),
+            reference: reference.into(),
+            occurred_at: Utc::now(),
+        }
+    }
+}
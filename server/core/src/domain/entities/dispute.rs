@@ -0,0 +1,119 @@
+//! Dispute entities tracking chargebacks opened by customers' banks.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::payment::Payment;
+
+/// State of a dispute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeStatus {
+    /// Waiting for evidence or the bank's decision
+    Open,
+    /// Decided in the platform's favour; the funds are returned
+    Won,
+    /// Decided in the customer's favour; the funds are lost
+    Lost,
+}
+
+impl DisputeStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Won => "won",
+            Self::Lost => "lost",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(Self::Open),
+            "won" => Some(Self::Won),
+            "lost" => Some(Self::Lost),
+            _ => None,
+        }
+    }
+}
+
+/// A chargeback against a payment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dispute {
+    /// Unique identifier for the dispute
+    pub id: Uuid,
+
+    /// Disputed payment
+    pub payment_id: Uuid,
+
+    /// Payment provider that reported the dispute
+    pub provider: String,
+
+    /// Provider's identifier for the dispute (e.g. a Stripe dispute ID)
+    pub provider_dispute_id: String,
+
+    /// Reason given by the bank (e.g. "fraudulent")
+    pub reason: String,
+
+    /// Disputed amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Current state
+    pub status: DisputeStatus,
+
+    /// When the dispute was opened
+    pub opened_at: DateTime<Utc>,
+
+    /// When the dispute was decided
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Dispute {
+    /// Opens a dispute against a payment
+    pub fn open(
+        payment: &Payment,
+        provider_dispute_id: impl Into<String>,
+        reason: impl Into<String>,
+        amount: i64,
+        opened_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            payment_id: payment.id,
+            provider: payment.provider.clone(),
+            provider_dispute_id: provider_dispute_id.into(),
+            reason: reason.into(),
+            amount,
+            currency: payment.currency.clone(),
+            status: DisputeStatus::Open,
+            opened_at,
+            resolved_at: None,
+        }
+    }
+
+    /// Checks whether the dispute is still waiting for a decision
+    pub fn is_open(&self) -> bool {
+        self.status == DisputeStatus::Open
+    }
+
+    /// Records the bank's decision
+    ///
+    /// # Returns
+    /// * `true` - The dispute was open and is now resolved
+    /// * `false` - The dispute was already resolved
+    pub fn resolve(&mut self, won: bool, resolved_at: DateTime<Utc>) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.status = if won { DisputeStatus::Won } else { DisputeStatus::Lost };
+        self.resolved_at = Some(resolved_at);
+        true
+    }
+}
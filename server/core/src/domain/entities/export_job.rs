@@ -0,0 +1,219 @@
+//! Export job entities.
+//!
+//! Large admin exports are produced in the background instead of within the
+//! request that asks for them. Each request becomes an export job, which a
+//! worker picks up, writes page by page to object storage and marks
+//! completed; the administrator polls the job for its progress and then
+//! downloads the file through a signed link.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Data an export job produces
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+    /// Entries of the authentication audit log
+    AuditLogs,
+    /// Weekly registration cohorts per marketing campaign
+    AttributionCohorts,
+}
+
+impl ExportKind {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AuditLogs => "audit_logs",
+            Self::AttributionCohorts => "attribution_cohorts",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "audit_logs" => Some(Self::AuditLogs),
+            "attribution_cohorts" => Some(Self::AttributionCohorts),
+            _ => None,
+        }
+    }
+}
+
+/// Progress of an export job
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    /// Waiting for a worker
+    Queued,
+    /// A worker is writing the file
+    Running,
+    /// The file is ready to download
+    Completed,
+    /// The export gave up; see the job's error
+    Failed,
+}
+
+impl ExportStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "queued" => Some(Self::Queued),
+            "running" => Some(Self::Running),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// An export requested by an administrator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportJob {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Data being exported
+    pub kind: ExportKind,
+
+    /// Administrator who requested the export
+    pub requested_by: Uuid,
+
+    /// Start of the exported time range, inclusive
+    pub from: Option<DateTime<Utc>>,
+
+    /// End of the exported time range, inclusive; fixed when the job is
+    /// created so rows arriving later do not shift the pages being written
+    pub to: DateTime<Utc>,
+
+    /// Progress of the job
+    pub status: ExportStatus,
+
+    /// Rows written so far
+    pub rows_exported: u64,
+
+    /// Rows the export will contain, known once a worker starts it
+    pub total_rows: Option<u64>,
+
+    /// Key of the finished file in object storage
+    pub object_key: Option<String>,
+
+    /// Why the export failed
+    pub error: Option<String>,
+
+    /// Times a worker started the job
+    pub attempts: u32,
+
+    /// When the job was requested
+    pub created_at: DateTime<Utc>,
+
+    /// When a worker last started the job
+    pub started_at: Option<DateTime<Utc>>,
+
+    /// When the job completed or failed
+    pub finished_at: Option<DateTime<Utc>>,
+
+    /// When the job was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ExportJob {
+    /// Creates a queued export job
+    pub fn new(
+        kind: ExportKind,
+        requested_by: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            requested_by,
+            from,
+            to: to.unwrap_or(now),
+            status: ExportStatus::Queued,
+            rows_exported: 0,
+            total_rows: None,
+            object_key: None,
+            error: None,
+            attempts: 0,
+            created_at: now,
+            started_at: None,
+            finished_at: None,
+            updated_at: now,
+        }
+    }
+
+    /// Mark the job as picked up by a worker, starting from the first row
+    pub fn start(&mut self) {
+        let now = Utc::now();
+        self.status = ExportStatus::Running;
+        self.rows_exported = 0;
+        self.total_rows = None;
+        self.attempts += 1;
+        self.started_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Record the rows written so far
+    pub fn record_progress(&mut self, rows_exported: u64) {
+        self.rows_exported = rows_exported;
+        self.updated_at = Utc::now();
+    }
+
+    /// Mark the file as written to object storage
+    pub fn complete(&mut self, object_key: impl Into<String>) {
+        let now = Utc::now();
+        self.status = ExportStatus::Completed;
+        self.object_key = Some(object_key.into());
+        self.error = None;
+        self.finished_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Put the job back in the queue after a failed attempt
+    pub fn retry(&mut self, error: impl Into<String>) {
+        self.status = ExportStatus::Queued;
+        self.error = Some(error.into());
+        self.updated_at = Utc::now();
+    }
+
+    /// Give up on the job
+    pub fn fail(&mut self, error: impl Into<String>) {
+        let now = Utc::now();
+        self.status = ExportStatus::Failed;
+        self.error = Some(error.into());
+        self.finished_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Percentage of rows written, once the total is known
+    ///
+    /// Completed jobs report 100 even when they contain no rows.
+    pub fn progress_percent(&self) -> Option<u8> {
+        if self.status == ExportStatus::Completed {
+            return Some(100);
+        }
+        match self.total_rows {
+            Some(0) | None => None,
+            Some(total) => Some((self.rows_exported.min(total) * 100 / total) as u8),
+        }
+    }
+
+    /// Whether the job has finished, successfully or not
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, ExportStatus::Completed | ExportStatus::Failed)
+    }
+}
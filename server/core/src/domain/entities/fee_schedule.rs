@@ -0,0 +1,156 @@
+//! Platform fee schedule entities.
+//!
+//! Fee schedules are versioned per market and optional job category and
+//! take effect at a point in time. The schedule in effect when a customer
+//! accepts a quote is copied onto the order, so later fee changes never
+//! apply to orders that were already agreed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::calendar::normalize_market;
+use super::payment::normalize_currency;
+
+/// A version of the platform fee for a market and optional category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Unique identifier for the schedule
+    pub id: Uuid,
+
+    /// Market code the schedule applies to (ISO 3166-1 alpha-2, e.g. "AU")
+    pub market: String,
+
+    /// Job category the schedule applies to, `None` for the whole market
+    pub category: Option<String>,
+
+    /// Version number, increasing per market and category
+    pub version: i32,
+
+    /// Commission in basis points of the quoted amount
+    pub commission_basis_points: i64,
+
+    /// Smallest fee charged per order in minor units
+    pub minimum_fee: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// When the schedule takes effect
+    pub effective_from: DateTime<Utc>,
+
+    /// Administrator who created the schedule
+    pub created_by: Option<Uuid>,
+
+    /// When the schedule was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl FeeSchedule {
+    /// Creates a new fee schedule version
+    ///
+    /// The market is normalized to upper case and the category to lower case.
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the commission is outside 0-10000 basis points, the
+    ///   minimum fee is negative or the currency is not a 3-letter code
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        market: &str,
+        category: Option<&str>,
+        version: i32,
+        commission_basis_points: i64,
+        minimum_fee: i64,
+        currency: &str,
+        effective_from: DateTime<Utc>,
+        created_by: Option<Uuid>,
+    ) -> Result<Self, String> {
+        if !(0..=10_000).contains(&commission_basis_points) {
+            return Err(format!(
+                "Commission must be between 0 and 10000 basis points: {}",
+                commission_basis_points
+            ));
+        }
+        if minimum_fee < 0 {
+            return Err("Minimum fee must not be negative".to_string());
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            market: normalize_market(market),
+            category: category.map(normalize_category).filter(|c| !c.is_empty()),
+            version,
+            commission_basis_points,
+            minimum_fee,
+            currency: normalize_currency(currency)?,
+            effective_from,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Fee for a quoted amount, never more than the amount itself
+    pub fn fee_for(&self, amount: i64) -> i64 {
+        let commission = amount * self.commission_basis_points / 10_000;
+        commission.max(self.minimum_fee).min(amount.max(0))
+    }
+
+    /// Copies the schedule onto an order whose quote was accepted
+    pub fn apply(&self, order_id: Uuid, quote_amount: i64, accepted_at: DateTime<Utc>) -> OrderFee {
+        OrderFee {
+            order_id,
+            schedule_id: self.id,
+            schedule_version: self.version,
+            market: self.market.clone(),
+            category: self.category.clone(),
+            commission_basis_points: self.commission_basis_points,
+            minimum_fee: self.minimum_fee,
+            quote_amount,
+            fee_amount: self.fee_for(quote_amount),
+            currency: self.currency.clone(),
+            accepted_at,
+        }
+    }
+}
+
+/// The fee agreed for an order, fixed when its quote was accepted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderFee {
+    /// Order the fee applies to
+    pub order_id: Uuid,
+
+    /// Schedule that was in effect at acceptance
+    pub schedule_id: Uuid,
+
+    /// Version of that schedule, kept for audits
+    pub schedule_version: i32,
+
+    /// Market of the schedule
+    pub market: String,
+
+    /// Category of the schedule, `None` if it covered the whole market
+    pub category: Option<String>,
+
+    /// Commission of the schedule in basis points
+    pub commission_basis_points: i64,
+
+    /// Minimum fee of the schedule in minor units
+    pub minimum_fee: i64,
+
+    /// Accepted quote in minor units
+    pub quote_amount: i64,
+
+    /// Platform fee for the order in minor units
+    pub fee_amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// When the quote was accepted
+    pub accepted_at: DateTime<Utc>,
+}
+
+/// Normalize a job category for comparison and storage
+pub fn normalize_category(category: &str) -> String {
+    category.trim().to_ascii_lowercase()
+}
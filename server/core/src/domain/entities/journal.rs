@@ -0,0 +1,412 @@
+//! Double-entry bookkeeping entities for the platform's own books.
+//!
+//! Every money movement is recorded as a journal entry whose debit and
+//! credit lines balance per currency. Balances of workers, customers and
+//! the platform are derived from the lines instead of being stored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::payment::normalize_currency;
+
+/// Side of a journal line
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EntrySide {
+    Debit,
+    Credit,
+}
+
+impl EntrySide {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Debit => "debit",
+            Self::Credit => "credit",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "debit" => Some(Self::Debit),
+            "credit" => Some(Self::Credit),
+            _ => None,
+        }
+    }
+}
+
+/// Account in the chart of accounts
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountType {
+    /// Money held at the payment provider (asset)
+    ProviderCash,
+    /// Earnings owed to a worker (liability, per worker)
+    WorkerPayable,
+    /// Store credit owed to a customer (liability, per customer)
+    CustomerCredit,
+    /// Platform commission earned on payments (revenue)
+    PlatformRevenue,
+    /// Processing fees charged by the provider (expense)
+    ProcessingFees,
+    /// Goodwill and promotional credit granted to customers (expense)
+    CustomerIncentives,
+}
+
+impl AccountType {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProviderCash => "provider_cash",
+            Self::WorkerPayable => "worker_payable",
+            Self::CustomerCredit => "customer_credit",
+            Self::PlatformRevenue => "platform_revenue",
+            Self::ProcessingFees => "processing_fees",
+            Self::CustomerIncentives => "customer_incentives",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "provider_cash" => Some(Self::ProviderCash),
+            "worker_payable" => Some(Self::WorkerPayable),
+            "customer_credit" => Some(Self::CustomerCredit),
+            "platform_revenue" => Some(Self::PlatformRevenue),
+            "processing_fees" => Some(Self::ProcessingFees),
+            "customer_incentives" => Some(Self::CustomerIncentives),
+            _ => None,
+        }
+    }
+
+    /// Side that increases the account's balance
+    pub fn normal_side(&self) -> EntrySide {
+        match self {
+            Self::ProviderCash | Self::ProcessingFees | Self::CustomerIncentives => EntrySide::Debit,
+            Self::WorkerPayable | Self::CustomerCredit | Self::PlatformRevenue => EntrySide::Credit,
+        }
+    }
+
+    /// Whether the account is kept per worker or customer
+    pub fn is_per_owner(&self) -> bool {
+        matches!(self, Self::WorkerPayable | Self::CustomerCredit)
+    }
+}
+
+/// An account of one owner in one currency
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AccountKey {
+    /// Account in the chart of accounts
+    pub account_type: AccountType,
+
+    /// Worker or customer owning the account, `None` for platform accounts
+    pub owner_id: Option<Uuid>,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+}
+
+impl AccountKey {
+    /// Creates a platform account key
+    pub fn platform(account_type: AccountType, currency: &str) -> Self {
+        Self {
+            account_type,
+            owner_id: None,
+            currency: currency.to_string(),
+        }
+    }
+
+    /// Creates the payable account of a worker
+    pub fn worker_payable(worker_id: Uuid, currency: &str) -> Self {
+        Self {
+            account_type: AccountType::WorkerPayable,
+            owner_id: Some(worker_id),
+            currency: currency.to_string(),
+        }
+    }
+
+    /// Creates the store credit account of a customer
+    pub fn customer_credit(customer_id: Uuid, currency: &str) -> Self {
+        Self {
+            account_type: AccountType::CustomerCredit,
+            owner_id: Some(customer_id),
+            currency: currency.to_string(),
+        }
+    }
+}
+
+/// A debit or credit to one account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalLine {
+    /// Account being debited or credited
+    pub account: AccountKey,
+
+    /// Whether the line is a debit or a credit
+    pub side: EntrySide,
+
+    /// Positive amount in minor units
+    pub amount: i64,
+}
+
+impl JournalLine {
+    /// Creates a debit line
+    pub fn debit(account: AccountKey, amount: i64) -> Self {
+        Self { account, side: EntrySide::Debit, amount }
+    }
+
+    /// Creates a credit line
+    pub fn credit(account: AccountKey, amount: i64) -> Self {
+        Self { account, side: EntrySide::Credit, amount }
+    }
+}
+
+/// Business event a journal entry records
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalTransactionType {
+    /// Customer payment for a job, split between worker and platform
+    Payment,
+    /// Tip from a customer to a worker
+    Tip,
+    /// Processing fee charged by the provider
+    Fee,
+    /// Store credit granted to a customer
+    CreditIssued,
+    /// Store credit spent by a customer on a job
+    CreditRedeemed,
+    /// Unspent store credit that expired
+    CreditExpired,
+    /// Transfer of earnings to a worker
+    Payout,
+    /// Money returned to a customer
+    Refund,
+    /// Money withdrawn by the customer's bank
+    Chargeback,
+    /// Chargeback decided in the platform's favour
+    ChargebackReversal,
+}
+
+impl JournalTransactionType {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Payment => "payment",
+            Self::Tip => "tip",
+            Self::Fee => "fee",
+            Self::CreditIssued => "credit_issued",
+            Self::CreditRedeemed => "credit_redeemed",
+            Self::CreditExpired => "credit_expired",
+            Self::Payout => "payout",
+            Self::Refund => "refund",
+            Self::Chargeback => "chargeback",
+            Self::ChargebackReversal => "chargeback_reversal",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "payment" => Some(Self::Payment),
+            "tip" => Some(Self::Tip),
+            "fee" => Some(Self::Fee),
+            "credit_issued" => Some(Self::CreditIssued),
+            "credit_redeemed" => Some(Self::CreditRedeemed),
+            "credit_expired" => Some(Self::CreditExpired),
+            "payout" => Some(Self::Payout),
+            "refund" => Some(Self::Refund),
+            "chargeback" => Some(Self::Chargeback),
+            "chargeback_reversal" => Some(Self::ChargebackReversal),
+            _ => None,
+        }
+    }
+}
+
+/// A balanced set of journal lines recording one business event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique identifier for the entry
+    pub id: Uuid,
+
+    /// Business event the entry records
+    pub transaction_type: JournalTransactionType,
+
+    /// Identifier of the event, unique per transaction type (e.g. a payment ID)
+    pub reference: String,
+
+    /// Optional free-form description
+    pub description: Option<String>,
+
+    /// Debit and credit lines
+    pub lines: Vec<JournalLine>,
+
+    /// When the event happened
+    pub occurred_at: DateTime<Utc>,
+
+    /// When the entry was recorded
+    pub created_at: DateTime<Utc>,
+}
+
+impl JournalEntry {
+    /// Creates a journal entry after checking the bookkeeping invariants
+    ///
+    /// Lines with a zero amount are dropped, so callers can pass optional
+    /// splits such as a zero platform fee.
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty, a line is negative, has an
+    ///   invalid currency or the wrong owner, fewer than two lines remain, or
+    ///   debits and credits differ in any currency
+    pub fn new(
+        transaction_type: JournalTransactionType,
+        reference: impl Into<String>,
+        lines: Vec<JournalLine>,
+        occurred_at: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let reference = reference.into();
+        if reference.trim().is_empty() {
+            return Err("Journal reference must not be empty".to_string());
+        }
+
+        let mut checked = Vec::with_capacity(lines.len());
+        for mut line in lines {
+            if line.amount < 0 {
+                return Err(format!("Journal line amount must not be negative: {}", line.amount));
+            }
+            if line.amount == 0 {
+                continue;
+            }
+            if line.account.account_type.is_per_owner() != line.account.owner_id.is_some() {
+                return Err(format!(
+                    "Account {} has the wrong owner",
+                    line.account.account_type.as_str()
+                ));
+            }
+            line.account.currency = normalize_currency(&line.account.currency)?;
+            checked.push(line);
+        }
+
+        if checked.len() < 2 {
+            return Err("Journal entry needs at least two lines".to_string());
+        }
+
+        let entry = Self {
+            id: Uuid::new_v4(),
+            transaction_type,
+            reference,
+            description: None,
+            lines: checked,
+            occurred_at,
+            created_at: Utc::now(),
+        };
+
+        if let Some((currency, (debits, credits))) = entry
+            .totals_by_currency()
+            .into_iter()
+            .find(|(_, (debits, credits))| debits != credits)
+        {
+            return Err(format!(
+                "Journal entry is unbalanced in {}: debits {} != credits {}",
+                currency, debits, credits
+            ));
+        }
+
+        Ok(entry)
+    }
+
+    /// Sets the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Total debits and credits per currency
+    pub fn totals_by_currency(&self) -> BTreeMap<String, (i64, i64)> {
+        let mut totals: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for line in &self.lines {
+            let total = totals.entry(line.account.currency.clone()).or_default();
+            match line.side {
+                EntrySide::Debit => total.0 += line.amount,
+                EntrySide::Credit => total.1 += line.amount,
+            }
+        }
+        totals
+    }
+}
+
+/// Debit and credit totals of an account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    /// The account
+    pub account: AccountKey,
+
+    /// Sum of debit lines in minor units
+    pub debits: i64,
+
+    /// Sum of credit lines in minor units
+    pub credits: i64,
+}
+
+impl AccountBalance {
+    /// Creates an empty balance for an account
+    pub fn empty(account: AccountKey) -> Self {
+        Self { account, debits: 0, credits: 0 }
+    }
+
+    /// Balance on the account's normal side, e.g. what a worker is owed
+    pub fn balance(&self) -> i64 {
+        match self.account.account_type.normal_side() {
+            EntrySide::Debit => self.debits - self.credits,
+            EntrySide::Credit => self.credits - self.debits,
+        }
+    }
+}
+
+/// Result of checking that the books balance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerVerification {
+    /// Total debits and credits per currency across all accounts
+    pub totals: BTreeMap<String, (i64, i64)>,
+
+    /// Entries whose own lines do not balance
+    pub unbalanced_entries: Vec<Uuid>,
+
+    /// Owner accounts with a negative balance, e.g. a worker paid more than earned
+    pub negative_accounts: Vec<AccountBalance>,
+
+    /// When the check ran
+    pub checked_at: DateTime<Utc>,
+}
+
+impl LedgerVerification {
+    /// Builds the verification result from a trial balance
+    pub fn from_trial_balance(balances: &[AccountBalance], unbalanced_entries: Vec<Uuid>) -> Self {
+        let mut totals: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for balance in balances {
+            let total = totals.entry(balance.account.currency.clone()).or_default();
+            total.0 += balance.debits;
+            total.1 += balance.credits;
+        }
+
+        Self {
+            totals,
+            unbalanced_entries,
+            negative_accounts: balances
+                .iter()
+                .filter(|b| b.account.account_type.is_per_owner() && b.balance() < 0)
+                .cloned()
+                .collect(),
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Whether debits equal credits in every currency and every entry balances
+    pub fn is_balanced(&self) -> bool {
+        self.unbalanced_entries.is_empty() && self.totals.values().all(|(d, c)| d == c)
+    }
+}
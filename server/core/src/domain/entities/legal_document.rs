@@ -0,0 +1,146 @@
+//! Legal document entities for terms of service and privacy policies.
+//!
+//! Documents are versioned per kind and market. A version is published in
+//! one or more languages at once and takes effect at a point in time;
+//! versions without a market apply wherever no market-specific version is
+//! in effect. Users accept one exact version, which is recorded so the app
+//! can ask them again when a newer version takes effect.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Language every version must be published in, used when a translation is missing
+pub const DEFAULT_LEGAL_LANGUAGE: &str = "en";
+
+/// Kind of legal document users accept
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LegalDocumentKind {
+    /// Terms of service
+    Terms,
+    /// Privacy policy
+    Privacy,
+}
+
+impl LegalDocumentKind {
+    /// Every kind, in the order the app presents them
+    pub const ALL: [Self; 2] = [Self::Terms, Self::Privacy];
+
+    /// Convert to string representation for database storage and URLs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Terms => "terms",
+            Self::Privacy => "privacy",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "terms" => Some(Self::Terms),
+            "privacy" => Some(Self::Privacy),
+            _ => None,
+        }
+    }
+}
+
+/// One translation of a legal document version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalDocument {
+    /// Unique identifier, never reused so it identifies the exact text
+    pub id: Uuid,
+
+    /// Kind of document
+    pub kind: LegalDocumentKind,
+
+    /// Market the version applies to (ISO 3166-1 alpha-2), `None` for every market
+    pub market: Option<String>,
+
+    /// Version number, increasing per kind and market
+    pub version: i32,
+
+    /// ISO 639-1 language code in lower case
+    pub language: String,
+
+    /// Title shown above the document
+    pub title: String,
+
+    /// Document text in Markdown
+    pub body: String,
+
+    /// When the version takes effect
+    pub effective_from: DateTime<Utc>,
+
+    /// Administrator who published the version
+    pub created_by: Option<Uuid>,
+
+    /// When the version was published
+    pub created_at: DateTime<Utc>,
+}
+
+impl LegalDocument {
+    /// Entity tag of the text, for conditional requests
+    ///
+    /// Documents are never edited, so the identifier is a strong validator.
+    pub fn etag(&self) -> String {
+        format!("\"{}\"", self.id.simple())
+    }
+}
+
+/// A user's acceptance of an exact legal document version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegalAcceptance {
+    /// User who accepted
+    pub user_id: Uuid,
+
+    /// Translation the user was shown
+    pub document_id: Uuid,
+
+    /// Kind of document
+    pub kind: LegalDocumentKind,
+
+    /// Market of the accepted version, `None` for the version for every market
+    pub market: Option<String>,
+
+    /// Version accepted
+    pub version: i32,
+
+    /// When the user accepted
+    pub accepted_at: DateTime<Utc>,
+}
+
+impl LegalAcceptance {
+    /// Records a user accepting a document
+    pub fn new(user_id: Uuid, document: &LegalDocument) -> Self {
+        Self {
+            user_id,
+            document_id: document.id,
+            kind: document.kind,
+            market: document.market.clone(),
+            version: document.version,
+            accepted_at: Utc::now(),
+        }
+    }
+
+    /// Whether this acceptance covers a document version in any language
+    pub fn covers(&self, document: &LegalDocument) -> bool {
+        self.kind == document.kind && self.market == document.market && self.version == document.version
+    }
+}
+
+/// Normalize a language tag to its ISO 639-1 primary subtag
+///
+/// `"zh-CN"` and `"ZH_hant"` both become `"zh"`.
+///
+/// # Returns
+/// * `None` - If the tag does not start with a two or three letter code
+pub fn normalize_language(tag: &str) -> Option<String> {
+    let primary = tag.trim().split(['-', '_']).next().unwrap_or_default();
+    if (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some(primary.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
@@ -1,9 +1,37 @@
 //! Domain entities representing core business objects.
 
+pub mod admin;
+pub mod attribution;
 pub mod audit;
+pub mod calendar;
+pub mod campaign;
+pub mod completion;
+pub mod compliance;
+pub mod credit_wallet;
+pub mod dispute;
+pub mod export_job;
+pub mod fee_schedule;
+pub mod journal;
+pub mod legal_document;
+pub mod notification;
+pub mod oauth;
+pub mod organization;
+pub mod payment;
+pub mod payment_method;
+pub mod payment_risk;
+pub mod payment_webhook;
+pub mod payout_method;
+pub mod reconciliation;
+pub mod security_webhook;
+pub mod service_area;
+pub mod sms_delivery;
+pub mod sms_job;
+pub mod sms_spend;
 pub mod token;
 pub mod user;
+pub mod user_import;
 pub mod verification_code;
+pub mod webhook_dead_letter;
 
 #[cfg(test)]
 mod tests;
@@ -13,11 +41,61 @@ mod tests;
 // pub mod order;
 
 // Re-export commonly used types
-pub use audit::{AuditLog, actions as audit_actions};
+pub use admin::{AdminIdentity, AdminRole};
+pub use attribution::{AttributionCohort, RegistrationAttribution, UserAttribution};
+pub use audit::{AuditLog, AuditLogFilter, actions as audit_actions};
+pub use calendar::ClosureDate;
+pub use campaign::{
+    Campaign, CampaignAudience, CampaignContent, CampaignDelivery, CampaignStats, CampaignStatus,
+    DeliveryStatus,
+};
+pub use compliance::{
+    ComplianceAction, ComplianceCheck, ComplianceRequirement, ComplianceRule, ComplianceStatus,
+    ComplianceStep,
+};
+pub use credit_wallet::{CreditGrant, CreditSource, CreditTransaction, CreditTransactionKind};
+pub use dispute::{Dispute, DisputeStatus};
+pub use export_job::{ExportJob, ExportKind, ExportStatus};
+pub use fee_schedule::{FeeSchedule, OrderFee};
+pub use journal::{
+    AccountBalance, AccountKey, AccountType, EntrySide, JournalEntry, JournalLine,
+    JournalTransactionType, LedgerVerification,
+};
+pub use legal_document::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+pub use notification::{
+    MessageTemplate, NotificationCategory, NotificationPreferences, ScheduledMessage,
+    ScheduledMessageStatus,
+};
+pub use oauth::{OAuthAuthorizationCode, OAuthClient, OAuthConsent, OAuthGrantType};
+pub use organization::{
+    ConsolidatedInvoice, InvoiceLine, Organization, OrganizationCharge, OrganizationMember,
+    OrganizationPaymentMethod, OrganizationRole,
+};
+pub use payment::{
+    LedgerEntry, LedgerEntryKind, Payment, PaymentClientAction, PaymentStatus, PaymentTimelineEntry,
+    Payout, PayoutStatus,
+};
+pub use payment_method::SavedPaymentMethod;
+pub use payment_risk::{PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction};
+pub use payment_webhook::{ParsedWebhookEvent, PaymentWebhookAction, PaymentWebhookEvent};
+pub use reconciliation::{
+    Discrepancy, DiscrepancyKind, ProviderTransaction, ReconciliationReport,
+};
+pub use security_webhook::{
+    SecurityEventKind, SecurityWebhook, WebhookDelivery, WebhookDeliveryStatus,
+};
+pub use service_area::ServiceArea;
+pub use sms_delivery::{SmsDeliveryLog, SmsDeliveryStatus};
+pub use sms_job::{SmsJob, SmsJobPayload};
+pub use sms_spend::SmsDailySpend;
 pub use token::{
     Claims, RefreshToken, TokenPair,
     ACCESS_TOKEN_EXPIRY_MINUTES, REFRESH_TOKEN_EXPIRY_DAYS,
-    JWT_ISSUER, JWT_AUDIENCE
+    JWT_ISSUER, JWT_AUDIENCE, JWT_PARTNER_AUDIENCE
 };
 pub use user::{User, UserType};
-pub use verification_code::{VerificationCode, MAX_ATTEMPTS, CODE_LENGTH, DEFAULT_EXPIRATION_MINUTES};
\ No newline at end of file
+pub use user_import::UserImportCheckpoint;
+pub use verification_code::{VerificationCode, MAX_ATTEMPTS, CODE_LENGTH, DEFAULT_EXPIRATION_MINUTES};
+pub use webhook_dead_letter::{
+    DeadLetterFilter, DeadLetterStatus, WebhookDeadLetter, WebhookDirection, SECURITY_WEBHOOK_SOURCE,
+};
\ No newline at end of file
@@ -0,0 +1,435 @@
+//! Notification entities for scheduled messages, reminders and per-user delivery preferences.
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Maximum number of delivery attempts before a scheduled message is marked failed
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Category of a notification, deciding which preferences and quiet hours apply
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// One-time passcode for sign-in or verification
+    Otp,
+    /// Security alert such as a sign-in from a new device
+    SecurityAlert,
+    /// Reminder about an appointment or job
+    Reminder,
+    /// Marketing or promotional message
+    Marketing,
+    /// Periodic digest of activity
+    Digest,
+}
+
+impl NotificationCategory {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Otp => "otp",
+            Self::SecurityAlert => "security_alert",
+            Self::Reminder => "reminder",
+            Self::Marketing => "marketing",
+            Self::Digest => "digest",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "otp" => Some(Self::Otp),
+            "security_alert" => Some(Self::SecurityAlert),
+            "reminder" => Some(Self::Reminder),
+            "marketing" => Some(Self::Marketing),
+            "digest" => Some(Self::Digest),
+            _ => None,
+        }
+    }
+
+    /// Whether messages of this category are delivered immediately,
+    /// ignoring quiet hours and opt-outs
+    pub fn is_critical(&self) -> bool {
+        matches!(self, Self::Otp | Self::SecurityAlert)
+    }
+}
+
+/// Message templates available for scheduled messages and reminders
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageTemplate {
+    /// Reminder that a worker is arriving at an appointment
+    WorkerArrivalReminder,
+    /// Request for the customer to leave a review after a job
+    ReviewRequest,
+    /// Free-form message supplied by the caller in the `body` parameter
+    Custom,
+}
+
+impl MessageTemplate {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WorkerArrivalReminder => "worker_arrival_reminder",
+            Self::ReviewRequest => "review_request",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "worker_arrival_reminder" => Some(Self::WorkerArrivalReminder),
+            "review_request" => Some(Self::ReviewRequest),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+
+    /// Raw template text with `{placeholder}` parameters
+    pub fn text(&self) -> &'static str {
+        match self {
+            Self::WorkerArrivalReminder => {
+                "RenovEasy reminder: {worker_name} is scheduled to arrive at {arrival_time}."
+            }
+            Self::ReviewRequest => {
+                "How did {worker_name} do? Leave a review for your RenovEasy job in the app."
+            }
+            Self::Custom => "{body}",
+        }
+    }
+
+    /// Parameters that must be supplied to render this template
+    pub fn required_params(&self) -> &'static [&'static str] {
+        match self {
+            Self::WorkerArrivalReminder => &["worker_name", "arrival_time"],
+            Self::ReviewRequest => &["worker_name"],
+            Self::Custom => &["body"],
+        }
+    }
+
+    /// Category a message rendered from this template belongs to by default
+    pub fn category(&self) -> NotificationCategory {
+        match self {
+            Self::WorkerArrivalReminder | Self::ReviewRequest | Self::Custom => {
+                NotificationCategory::Reminder
+            }
+        }
+    }
+
+    /// Render the template by substituting the given parameters
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The rendered message
+    /// * `Err(String)` - Name of the first missing required parameter
+    pub fn render(&self, params: &HashMap<String, String>) -> Result<String, String> {
+        if let Some(missing) = self
+            .required_params()
+            .iter()
+            .find(|name| !params.contains_key(**name))
+        {
+            return Err(missing.to_string());
+        }
+
+        let mut message = self.text().to_string();
+        for (name, value) in params {
+            message = message.replace(&format!("{{{}}}", name), value);
+        }
+        Ok(message)
+    }
+}
+
+/// Delivery status of a scheduled message
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledMessageStatus {
+    /// Waiting for its delivery time
+    Pending,
+    /// Claimed by a dispatcher that is delivering it
+    Processing,
+    /// Delivered to the SMS provider
+    Sent,
+    /// Gave up after repeated delivery failures
+    Failed,
+    /// Cancelled before delivery
+    Cancelled,
+}
+
+impl ScheduledMessageStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Processing => "processing",
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "processing" => Some(Self::Processing),
+            "sent" => Some(Self::Sent),
+            "failed" => Some(Self::Failed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// A message scheduled for delivery to a user at a later time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    /// Unique identifier for the scheduled message
+    pub id: Uuid,
+
+    /// User the message is addressed to
+    pub user_id: Uuid,
+
+    /// Recipient phone number in E.164 format
+    pub phone: String,
+
+    /// Template used to render the message body
+    pub template: MessageTemplate,
+
+    /// Category deciding which preferences and quiet hours apply
+    pub category: NotificationCategory,
+
+    /// Template parameters
+    pub params: HashMap<String, String>,
+
+    /// Time requested by the caller
+    pub requested_for: DateTime<Utc>,
+
+    /// Time the message becomes due, after quiet hours were applied
+    pub deliver_at: DateTime<Utc>,
+
+    /// Current delivery status
+    pub status: ScheduledMessageStatus,
+
+    /// Number of delivery attempts made so far
+    pub attempts: u32,
+
+    /// Last delivery error, if any
+    pub last_error: Option<String>,
+
+    /// Provider message ID once sent
+    pub provider_message_id: Option<String>,
+
+    /// Timestamp when the message was scheduled
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp when the message was sent
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+impl ScheduledMessage {
+    /// Creates a new pending scheduled message
+    pub fn new(
+        user_id: Uuid,
+        phone: String,
+        template: MessageTemplate,
+        params: HashMap<String, String>,
+        requested_for: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            phone,
+            template,
+            category: template.category(),
+            params,
+            requested_for,
+            deliver_at: requested_for,
+            status: ScheduledMessageStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            provider_message_id: None,
+            created_at: Utc::now(),
+            sent_at: None,
+        }
+    }
+
+    /// Sets the category of the message
+    pub fn with_category(mut self, category: NotificationCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Checks whether the message is pending and due at the given time
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == ScheduledMessageStatus::Pending && self.deliver_at <= now
+    }
+
+    /// Claims a due message for delivery
+    ///
+    /// # Returns
+    /// * `true` if the message was pending and is now processing
+    pub fn claim(&mut self) -> bool {
+        if self.status == ScheduledMessageStatus::Pending {
+            self.status = ScheduledMessageStatus::Processing;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns a claimed message to pending, so the dispatcher holding the
+    /// claim can send, defer or cancel it like any pending message
+    pub fn release(&mut self) {
+        if self.status == ScheduledMessageStatus::Processing {
+            self.status = ScheduledMessageStatus::Pending;
+        }
+    }
+
+    /// Marks the message as sent
+    pub fn mark_sent(&mut self, provider_message_id: String) {
+        self.status = ScheduledMessageStatus::Sent;
+        self.attempts += 1;
+        self.provider_message_id = Some(provider_message_id);
+        self.last_error = None;
+        self.sent_at = Some(Utc::now());
+    }
+
+    /// Records a failed delivery attempt, marking the message failed once
+    /// `MAX_DELIVERY_ATTEMPTS` is reached
+    pub fn record_failure(&mut self, error: String) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        if self.attempts >= MAX_DELIVERY_ATTEMPTS {
+            self.status = ScheduledMessageStatus::Failed;
+        }
+    }
+
+    /// Cancels the message if it has not been delivered yet
+    ///
+    /// # Returns
+    /// * `true` if the message was pending and is now cancelled
+    pub fn cancel(&mut self) -> bool {
+        if self.status == ScheduledMessageStatus::Pending {
+            self.status = ScheduledMessageStatus::Cancelled;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Per-user notification preferences
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    /// User the preferences belong to
+    pub user_id: Uuid,
+
+    /// Offset of the user's local time from UTC, in minutes (e.g. 600 for UTC+10)
+    pub utc_offset_minutes: i32,
+
+    /// Local time at which quiet hours start
+    pub quiet_hours_start: Option<NaiveTime>,
+
+    /// Local time at which quiet hours end
+    pub quiet_hours_end: Option<NaiveTime>,
+
+    /// Whether the user wants to receive reminders at all
+    pub reminders_enabled: bool,
+
+    /// Whether the user accepts marketing campaigns
+    pub marketing_enabled: bool,
+
+    /// Timestamp when the preferences were last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationPreferences {
+    /// Creates default preferences: UTC, no quiet hours, reminders and
+    /// marketing campaigns enabled
+    pub fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            utc_offset_minutes: 0,
+            quiet_hours_start: None,
+            quiet_hours_end: None,
+            reminders_enabled: true,
+            marketing_enabled: true,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Sets the quiet hours window in the user's local time
+    pub fn with_quiet_hours(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.quiet_hours_start = Some(start);
+        self.quiet_hours_end = Some(end);
+        self
+    }
+
+    /// Sets the user's UTC offset in minutes
+    pub fn with_utc_offset_minutes(mut self, offset: i32) -> Self {
+        self.utc_offset_minutes = offset;
+        self
+    }
+
+    /// Whether the user accepts messages of the given category
+    ///
+    /// Critical categories are always accepted.
+    pub fn accepts(&self, category: NotificationCategory) -> bool {
+        match category {
+            NotificationCategory::Otp | NotificationCategory::SecurityAlert => true,
+            NotificationCategory::Reminder => self.reminders_enabled,
+            NotificationCategory::Marketing | NotificationCategory::Digest => self.marketing_enabled,
+        }
+    }
+
+    /// Whether quiet hours are configured
+    pub fn has_quiet_hours(&self) -> bool {
+        matches!(
+            (self.quiet_hours_start, self.quiet_hours_end),
+            (Some(start), Some(end)) if start != end
+        )
+    }
+
+    /// Checks whether the given instant falls inside the user's quiet hours
+    ///
+    /// Windows that wrap past midnight (e.g. 22:00-07:00) are supported.
+    pub fn is_quiet_at(&self, at: DateTime<Utc>) -> bool {
+        let (start, end) = match (self.quiet_hours_start, self.quiet_hours_end) {
+            (Some(start), Some(end)) if start != end => (start, end),
+            _ => return false,
+        };
+
+        let local = self.to_local(at).time();
+        if start < end {
+            local >= start && local < end
+        } else {
+            local >= start || local < end
+        }
+    }
+
+    /// Returns the earliest instant at or after `at` that is outside quiet hours
+    pub fn next_allowed_time(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        if !self.is_quiet_at(at) {
+            return at;
+        }
+
+        // Quiet hours are active, so both bounds are set
+        let end = self.quiet_hours_end.unwrap_or_default();
+        let local = self.to_local(at);
+        let mut local_end = local.date_naive().and_time(end).and_utc();
+        if local_end <= local {
+            local_end += Duration::days(1);
+        }
+
+        local_end - Duration::minutes(self.utc_offset_minutes as i64)
+    }
+
+    /// Shift a UTC instant into the user's local wall-clock time
+    fn to_local(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        at + Duration::minutes(self.utc_offset_minutes as i64)
+    }
+}
@@ -0,0 +1,266 @@
+//! OAuth2 entities for partner integrations.
+//!
+//! Partners such as renovation-material suppliers are registered as OAuth
+//! clients. A client obtains tokens for its own account with the client
+//! credentials grant, or acts on behalf of a user with the authorization
+//! code grant once the user has consented to the requested scopes.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Scope to read a user's basic profile
+pub const SCOPE_PROFILE_READ: &str = "profile:read";
+
+/// Scope to read a user's orders
+pub const SCOPE_ORDERS_READ: &str = "orders:read";
+
+/// Scope to read the partner's material catalogue listing
+pub const SCOPE_MATERIALS_READ: &str = "materials:read";
+
+/// Scope to publish material quotes for a user's jobs
+pub const SCOPE_MATERIALS_WRITE: &str = "materials:write";
+
+/// OAuth2 grant type a client may use
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthGrantType {
+    /// The client authenticates as itself
+    ClientCredentials,
+    /// The client exchanges a code a user approved
+    AuthorizationCode,
+}
+
+impl OAuthGrantType {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ClientCredentials => "client_credentials",
+            Self::AuthorizationCode => "authorization_code",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "client_credentials" => Some(Self::ClientCredentials),
+            "authorization_code" => Some(Self::AuthorizationCode),
+            _ => None,
+        }
+    }
+}
+
+/// Split a space-delimited scope parameter into distinct scopes
+pub fn parse_scope(scope: &str) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for s in scope.split_whitespace() {
+        if !scopes.iter().any(|existing| existing == s) {
+            scopes.push(s.to_string());
+        }
+    }
+    scopes
+}
+
+/// Join scopes into a space-delimited scope parameter
+pub fn format_scope(scopes: &[String]) -> String {
+    scopes.join(" ")
+}
+
+/// Hash a client secret or authorization code for storage
+pub fn hash_oauth_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// A registered partner application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuthClient {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Public client identifier sent with every request
+    pub client_id: String,
+    /// SHA-256 hash of the client secret
+    pub client_secret_hash: String,
+    /// Partner name shown on the consent screen
+    pub name: String,
+    /// Redirect URIs the authorization code may be sent to, matched exactly
+    pub redirect_uris: Vec<String>,
+    /// Scopes the client may request
+    pub allowed_scopes: Vec<String>,
+    /// Grants the client may use
+    pub grant_types: Vec<OAuthGrantType>,
+    /// Whether the client may obtain tokens
+    pub is_active: bool,
+    /// When the client was registered
+    pub created_at: DateTime<Utc>,
+    /// When the client was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OAuthClient {
+    /// Create a new active client
+    ///
+    /// # Returns
+    /// * `Err(String)` - Missing name, grants or scopes, or no redirect URI
+    ///   for a client using the authorization code grant
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret_hash: impl Into<String>,
+        name: impl Into<String>,
+        redirect_uris: Vec<String>,
+        allowed_scopes: Vec<String>,
+        grant_types: Vec<OAuthGrantType>,
+    ) -> Result<Self, String> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err("Client name is required".to_string());
+        }
+        if grant_types.is_empty() {
+            return Err("At least one grant type is required".to_string());
+        }
+        if allowed_scopes.is_empty() {
+            return Err("At least one scope is required".to_string());
+        }
+        if grant_types.contains(&OAuthGrantType::AuthorizationCode) && redirect_uris.is_empty() {
+            return Err("The authorization code grant requires a redirect URI".to_string());
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            client_id: client_id.into(),
+            client_secret_hash: client_secret_hash.into(),
+            name,
+            redirect_uris,
+            allowed_scopes,
+            grant_types,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Whether the client may use a grant
+    pub fn allows_grant(&self, grant: OAuthGrantType) -> bool {
+        self.is_active && self.grant_types.contains(&grant)
+    }
+
+    /// Whether a redirect URI is registered for the client
+    pub fn allows_redirect_uri(&self, redirect_uri: &str) -> bool {
+        self.redirect_uris.iter().any(|uri| uri == redirect_uri)
+    }
+
+    /// Whether every scope is allowed for the client
+    pub fn allows_scopes(&self, scopes: &[String]) -> bool {
+        scopes.iter().all(|scope| self.allowed_scopes.contains(scope))
+    }
+}
+
+/// A user's consent for a client to act on their behalf
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuthConsent {
+    /// Consenting user
+    pub user_id: Uuid,
+    /// Public identifier of the client
+    pub client_id: String,
+    /// Scopes the user approved
+    pub scopes: Vec<String>,
+    /// When the consent was first given or last extended
+    pub granted_at: DateTime<Utc>,
+}
+
+impl OAuthConsent {
+    /// Create a consent for the given scopes
+    pub fn new(user_id: Uuid, client_id: impl Into<String>, scopes: Vec<String>) -> Self {
+        Self {
+            user_id,
+            client_id: client_id.into(),
+            scopes,
+            granted_at: Utc::now(),
+        }
+    }
+
+    /// Whether the consent covers every requested scope
+    pub fn covers(&self, scopes: &[String]) -> bool {
+        scopes.iter().all(|scope| self.scopes.contains(scope))
+    }
+
+    /// Add scopes approved later, keeping those already granted
+    pub fn extend(&mut self, scopes: &[String]) {
+        for scope in scopes {
+            if !self.scopes.contains(scope) {
+                self.scopes.push(scope.clone());
+            }
+        }
+        self.granted_at = Utc::now();
+    }
+}
+
+/// A single-use code issued after a user approved a client
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuthAuthorizationCode {
+    /// SHA-256 hash of the code
+    pub code_hash: String,
+    /// Public identifier of the client the code was issued to
+    pub client_id: String,
+    /// User who approved the client
+    pub user_id: Uuid,
+    /// Redirect URI the code was sent to; must be repeated on exchange
+    pub redirect_uri: String,
+    /// Approved scopes
+    pub scopes: Vec<String>,
+    /// PKCE S256 code challenge, when the client sent one
+    pub code_challenge: Option<String>,
+    /// When the code stops being exchangeable
+    pub expires_at: DateTime<Utc>,
+    /// When the code was exchanged
+    pub used_at: Option<DateTime<Utc>>,
+    /// When the code was issued
+    pub created_at: DateTime<Utc>,
+}
+
+impl OAuthAuthorizationCode {
+    /// Create a code valid for the given number of seconds
+    pub fn new(
+        code_hash: impl Into<String>,
+        client_id: impl Into<String>,
+        user_id: Uuid,
+        redirect_uri: impl Into<String>,
+        scopes: Vec<String>,
+        code_challenge: Option<String>,
+        ttl_seconds: i64,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            code_hash: code_hash.into(),
+            client_id: client_id.into(),
+            user_id,
+            redirect_uri: redirect_uri.into(),
+            scopes,
+            code_challenge,
+            expires_at: now + Duration::seconds(ttl_seconds),
+            used_at: None,
+            created_at: now,
+        }
+    }
+
+    /// Whether the code is unused and not expired
+    pub fn is_valid(&self) -> bool {
+        self.used_at.is_none() && Utc::now() < self.expires_at
+    }
+
+    /// Check a PKCE code verifier against the stored challenge
+    ///
+    /// Codes issued without a challenge accept no verifier only.
+    pub fn verifies(&self, code_verifier: Option<&str>) -> bool {
+        match (&self.code_challenge, code_verifier) {
+            (None, None) => true,
+            (Some(challenge), Some(verifier)) => {
+                URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())) == *challenge
+            }
+            _ => false,
+        }
+    }
+}
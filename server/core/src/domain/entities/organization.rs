@@ -0,0 +1,301 @@
+//! Organization billing entities for company accounts.
+//!
+//! Members of an organization book jobs as themselves, but accepted quotes
+//! are charged to the organization. Each accepted quote becomes a charge;
+//! once the job is completed the charge is collected on the organization's
+//! consolidated invoice for the month it was completed in.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::payment::normalize_currency;
+
+/// Longest organization name accepted
+pub const MAX_ORGANIZATION_NAME_LENGTH: usize = 100;
+
+/// Role of a member within an organization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRole {
+    /// Manages members, payment methods and invoices
+    Admin,
+    /// Books jobs charged to the organization
+    Member,
+}
+
+impl OrganizationRole {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::Member => "member",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "admin" => Some(Self::Admin),
+            "member" => Some(Self::Member),
+            _ => None,
+        }
+    }
+}
+
+/// A company account billed for its members' jobs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Organization {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Company name shown on invoices
+    pub name: String,
+
+    /// Address invoices are sent to
+    pub billing_email: String,
+
+    /// ISO 4217 currency the organization is billed in
+    pub currency: String,
+
+    /// When the organization was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl Organization {
+    /// Creates a new organization
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the name is empty or too long, the email is not
+    ///   an email address or the currency is invalid
+    pub fn new(name: &str, billing_email: &str, currency: &str) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_ORGANIZATION_NAME_LENGTH {
+            return Err(format!(
+                "Organization name must be between 1 and {} characters",
+                MAX_ORGANIZATION_NAME_LENGTH
+            ));
+        }
+        let billing_email = billing_email.trim();
+        let valid_email = billing_email
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.'));
+        if !valid_email {
+            return Err("Billing email must be an email address".to_string());
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            billing_email: billing_email.to_ascii_lowercase(),
+            currency: normalize_currency(currency)?,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// A user belonging to an organization
+///
+/// A user belongs to at most one organization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrganizationMember {
+    /// The organization
+    pub organization_id: Uuid,
+
+    /// The member
+    pub user_id: Uuid,
+
+    /// What the member may do
+    pub role: OrganizationRole,
+
+    /// Most the member may spend per calendar month in minor units, `None` for no limit
+    pub monthly_spend_limit: Option<i64>,
+
+    /// When the member was added
+    pub added_at: DateTime<Utc>,
+}
+
+impl OrganizationMember {
+    /// Whether the member manages the organization
+    pub fn is_admin(&self) -> bool {
+        self.role == OrganizationRole::Admin
+    }
+}
+
+/// A card an organization is billed to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrganizationPaymentMethod {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Organization the card belongs to
+    pub organization_id: Uuid,
+
+    /// Payment provider holding the card (e.g. "stripe")
+    pub provider: String,
+
+    /// Provider's identifier for the payment method
+    pub provider_reference: String,
+
+    /// Card brand (e.g. "visa")
+    pub brand: String,
+
+    /// Last four digits of the card number
+    pub last4: String,
+
+    /// Expiry month, 1 to 12
+    pub exp_month: u32,
+
+    /// Expiry year, four digits
+    pub exp_year: i32,
+
+    /// Whether invoices are charged to this method
+    pub is_default: bool,
+
+    /// Organization admin who added the method
+    pub added_by: Uuid,
+
+    /// When the method was added
+    pub created_at: DateTime<Utc>,
+}
+
+impl OrganizationPaymentMethod {
+    /// Whether the card expired before `now`
+    ///
+    /// Cards are valid until the end of their expiry month.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        (now.year(), now.month()) > (self.exp_year, self.exp_month)
+    }
+}
+
+/// An accepted quote charged to an organization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrganizationCharge {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Organization charged
+    pub organization_id: Uuid,
+
+    /// Member who accepted the quote
+    pub user_id: Uuid,
+
+    /// The order, charged at most once
+    pub order_id: Uuid,
+
+    /// Quoted amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// When the quote was accepted; counts towards that month's spend limit
+    pub accepted_at: DateTime<Utc>,
+
+    /// When the job was completed, `None` while it is in progress
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Invoice the charge was collected on
+    pub invoice_id: Option<Uuid>,
+}
+
+/// A charge listed on a consolidated invoice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvoiceLine {
+    /// The order
+    pub order_id: Uuid,
+
+    /// Member who accepted the quote
+    pub user_id: Uuid,
+
+    /// Amount in minor units
+    pub amount: i64,
+
+    /// When the job was completed
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Monthly invoice of all completed jobs of an organization's members
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsolidatedInvoice {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Organization invoiced
+    pub organization_id: Uuid,
+
+    /// First day of the invoiced month
+    pub period_start: NaiveDate,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Charges collected, in order of completion
+    pub lines: Vec<InvoiceLine>,
+
+    /// Sum of the lines in minor units
+    pub total_amount: i64,
+
+    /// Payment method the invoice is charged to, `None` if the organization has none
+    pub payment_method_id: Option<Uuid>,
+
+    /// When the invoice was issued
+    pub issued_at: DateTime<Utc>,
+}
+
+impl ConsolidatedInvoice {
+    /// Creates an invoice collecting completed charges
+    pub fn new(
+        organization: &Organization,
+        period_start: NaiveDate,
+        charges: &[OrganizationCharge],
+        payment_method_id: Option<Uuid>,
+    ) -> Self {
+        let mut lines: Vec<InvoiceLine> = charges
+            .iter()
+            .filter_map(|charge| {
+                charge.completed_at.map(|completed_at| InvoiceLine {
+                    order_id: charge.order_id,
+                    user_id: charge.user_id,
+                    amount: charge.amount,
+                    completed_at,
+                })
+            })
+            .collect();
+        lines.sort_by_key(|line| line.completed_at);
+
+        Self {
+            id: Uuid::new_v4(),
+            organization_id: organization.id,
+            period_start,
+            currency: organization.currency.clone(),
+            total_amount: lines.iter().map(|line| line.amount).sum(),
+            lines,
+            payment_method_id,
+            issued_at: Utc::now(),
+        }
+    }
+
+    /// Total invoiced per member in minor units
+    pub fn member_totals(&self) -> BTreeMap<Uuid, i64> {
+        let mut totals = BTreeMap::new();
+        for line in &self.lines {
+            *totals.entry(line.user_id).or_insert(0) += line.amount;
+        }
+        totals
+    }
+}
+
+/// Start and end (exclusive) of the calendar month containing a time, in UTC
+pub fn month_bounds(at: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = month_start(at.date_naive());
+    let end = start + Months::new(1);
+    (start.and_hms_opt(0, 0, 0).unwrap().and_utc(), end.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+/// First day of the month containing a date
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
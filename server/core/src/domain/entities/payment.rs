@@ -0,0 +1,575 @@
+//! Payment ledger entities recording money movements with payment providers.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Type of money movement recorded in the ledger
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerEntryKind {
+    /// Customer payment captured by the provider
+    Charge,
+    /// Money returned to a customer
+    Refund,
+    /// Processing fee charged by the provider
+    Fee,
+    /// Transfer from the provider balance to a bank account
+    Payout,
+    /// Any other balance change, e.g. a dispute or manual correction
+    Adjustment,
+}
+
+impl LedgerEntryKind {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Charge => "charge",
+            Self::Refund => "refund",
+            Self::Fee => "fee",
+            Self::Payout => "payout",
+            Self::Adjustment => "adjustment",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "charge" => Some(Self::Charge),
+            "refund" => Some(Self::Refund),
+            "fee" => Some(Self::Fee),
+            "payout" => Some(Self::Payout),
+            "adjustment" => Some(Self::Adjustment),
+            _ => None,
+        }
+    }
+}
+
+/// A money movement as recorded by the platform
+///
+/// Amounts are signed and in the currency's minor unit (cents, fen), so
+/// money leaving the provider balance is negative.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Unique identifier for the entry
+    pub id: Uuid,
+
+    /// Payment provider that moved the money (e.g. "stripe")
+    pub provider: String,
+
+    /// Provider's identifier for the movement, used for reconciliation
+    pub provider_reference: String,
+
+    /// Type of movement
+    pub kind: LedgerEntryKind,
+
+    /// Signed amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case (e.g. "AUD")
+    pub currency: String,
+
+    /// Optional free-form description
+    pub description: Option<String>,
+
+    /// When the movement happened at the provider
+    pub occurred_at: DateTime<Utc>,
+
+    /// When the entry was recorded
+    pub created_at: DateTime<Utc>,
+}
+
+impl LedgerEntry {
+    /// Creates a new ledger entry
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty or the currency is not a 3-letter code
+    pub fn new(
+        provider: impl Into<String>,
+        provider_reference: impl Into<String>,
+        kind: LedgerEntryKind,
+        amount: i64,
+        currency: &str,
+        occurred_at: DateTime<Utc>,
+    ) -> Result<Self, String> {
+        let provider_reference = provider_reference.into();
+        if provider_reference.trim().is_empty() {
+            return Err("Provider reference must not be empty".to_string());
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            provider: provider.into(),
+            provider_reference,
+            kind,
+            amount,
+            currency: normalize_currency(currency)?,
+            description: None,
+            occurred_at,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Normalizes an ISO 4217 currency code to upper case
+///
+/// # Returns
+/// * `Err(String)` - If the code is not three ASCII letters
+pub fn normalize_currency(currency: &str) -> Result<String, String> {
+    let currency = currency.trim();
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid currency code: {}", currency));
+    }
+
+    Ok(currency.to_ascii_uppercase())
+}
+
+/// Lifecycle state of a customer payment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    /// Waiting for the customer to authenticate the payment (3-D Secure)
+    RequiresAction,
+    /// Authentication or the charge failed; nothing was captured
+    Failed,
+    /// Captured and not refunded
+    Succeeded,
+    /// Part of the amount was returned to the customer
+    PartiallyRefunded,
+    /// The full amount was returned to the customer
+    Refunded,
+    /// The customer's bank opened a chargeback
+    Disputed,
+}
+
+impl PaymentStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequiresAction => "requires_action",
+            Self::Failed => "failed",
+            Self::Succeeded => "succeeded",
+            Self::PartiallyRefunded => "partially_refunded",
+            Self::Refunded => "refunded",
+            Self::Disputed => "disputed",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "requires_action" => Some(Self::RequiresAction),
+            "failed" => Some(Self::Failed),
+            "succeeded" => Some(Self::Succeeded),
+            "partially_refunded" => Some(Self::PartiallyRefunded),
+            "refunded" => Some(Self::Refunded),
+            "disputed" => Some(Self::Disputed),
+            _ => None,
+        }
+    }
+}
+
+/// A customer payment for a job, captured through a payment provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payment {
+    /// Unique identifier for the payment
+    pub id: Uuid,
+
+    /// Payment provider that captured the payment (e.g. "stripe")
+    pub provider: String,
+
+    /// Provider's identifier for the payment (e.g. a Stripe charge ID)
+    ///
+    /// Until an intent is captured this is the intent's identifier.
+    pub provider_reference: String,
+
+    /// Provider's identifier for the payment intent, if created through one
+    pub intent_reference: Option<String>,
+
+    /// Provider's identifier for the payment method charged, if known
+    pub payment_method: Option<String>,
+
+    /// Customer who paid
+    pub customer_id: Uuid,
+
+    /// Worker who is paid out for the job
+    pub worker_id: Uuid,
+
+    /// Captured amount in minor units
+    pub amount: i64,
+
+    /// Total refunded so far in minor units
+    pub refunded_amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Current state
+    pub status: PaymentStatus,
+
+    /// When the payment was captured
+    pub created_at: DateTime<Utc>,
+
+    /// When the payment was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Payment {
+    /// Creates a new succeeded payment
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty, the amount is not positive
+    ///   or the currency is not a 3-letter code
+    pub fn new(
+        provider: impl Into<String>,
+        provider_reference: impl Into<String>,
+        customer_id: Uuid,
+        worker_id: Uuid,
+        amount: i64,
+        currency: &str,
+    ) -> Result<Self, String> {
+        let provider_reference = provider_reference.into();
+        if provider_reference.trim().is_empty() {
+            return Err("Provider reference must not be empty".to_string());
+        }
+        if amount <= 0 {
+            return Err("Payment amount must be positive".to_string());
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            provider: provider.into(),
+            provider_reference,
+            intent_reference: None,
+            payment_method: None,
+            customer_id,
+            worker_id,
+            amount,
+            refunded_amount: 0,
+            currency: normalize_currency(currency)?,
+            status: PaymentStatus::Succeeded,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Creates a payment whose intent waits for the customer to authenticate
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty, the amount is not positive
+    ///   or the currency is not a 3-letter code
+    pub fn awaiting_authentication(
+        provider: impl Into<String>,
+        intent_reference: impl Into<String>,
+        customer_id: Uuid,
+        worker_id: Uuid,
+        amount: i64,
+        currency: &str,
+    ) -> Result<Self, String> {
+        let intent_reference = intent_reference.into();
+        let mut payment = Self::new(provider, intent_reference.clone(), customer_id, worker_id, amount, currency)?
+            .with_intent(intent_reference);
+        payment.status = PaymentStatus::RequiresAction;
+        Ok(payment)
+    }
+
+    /// Records the payment intent the payment was captured through
+    pub fn with_intent(mut self, intent_reference: impl Into<String>) -> Self {
+        self.intent_reference = Some(intent_reference.into());
+        self
+    }
+
+    /// Records the payment method the payment was charged to
+    pub fn with_payment_method(mut self, payment_method: impl Into<String>) -> Self {
+        self.payment_method = Some(payment_method.into());
+        self
+    }
+
+    /// Whether the platform still holds the customer's money in escrow
+    ///
+    /// Money is held from the moment a payment is attempted until it is
+    /// refunded in full, or until the worker has been paid out. A disputed
+    /// payment stays in escrow until the dispute is resolved.
+    ///
+    /// # Arguments
+    /// * `payouts` - The payouts created for this payment
+    pub fn holds_escrow(&self, payouts: &[Payout]) -> bool {
+        match self.status {
+            PaymentStatus::RequiresAction | PaymentStatus::Disputed => true,
+            PaymentStatus::Failed | PaymentStatus::Refunded => false,
+            PaymentStatus::Succeeded | PaymentStatus::PartiallyRefunded => {
+                payouts.is_empty() || payouts.iter().any(|payout| payout.status != PayoutStatus::Paid)
+            }
+        }
+    }
+
+    /// Whether the provider captured the money
+    pub fn is_captured(&self) -> bool {
+        !matches!(self.status, PaymentStatus::RequiresAction | PaymentStatus::Failed)
+    }
+
+    /// Marks an authenticated payment as captured
+    ///
+    /// A failed attempt can still be captured, since the customer may retry
+    /// authentication on the same intent.
+    ///
+    /// # Arguments
+    /// * `charge_reference` - Provider's identifier for the capture, which
+    ///   replaces the intent's identifier as the provider reference
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the payment was already captured
+    pub fn confirm(&mut self, charge_reference: Option<String>) -> Result<(), String> {
+        if self.is_captured() {
+            return Err(format!("Payment is already captured: {}", self.status.as_str()));
+        }
+
+        if let Some(charge_reference) = charge_reference.filter(|r| !r.trim().is_empty()) {
+            self.provider_reference = charge_reference;
+        }
+        self.status = PaymentStatus::Succeeded;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Marks a payment waiting for authentication as failed
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the payment is not waiting for authentication
+    pub fn mark_failed(&mut self) -> Result<(), String> {
+        if self.status != PaymentStatus::RequiresAction {
+            return Err(format!("Payment is not awaiting authentication: {}", self.status.as_str()));
+        }
+
+        self.status = PaymentStatus::Failed;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Amount that has not been refunded
+    pub fn remaining_amount(&self) -> i64 {
+        self.amount - self.refunded_amount
+    }
+
+    /// Records a refund of `amount` minor units
+    ///
+    /// A disputed payment stays disputed; otherwise the status becomes
+    /// partially or fully refunded.
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the payment was not captured, or the amount is not
+    ///   positive or exceeds the remaining amount
+    pub fn apply_refund(&mut self, amount: i64) -> Result<(), String> {
+        if !self.is_captured() {
+            return Err("Payment has not been captured".to_string());
+        }
+        if amount <= 0 {
+            return Err("Refund amount must be positive".to_string());
+        }
+        if amount > self.remaining_amount() {
+            return Err(format!(
+                "Refund of {} exceeds remaining amount {}",
+                amount,
+                self.remaining_amount()
+            ));
+        }
+
+        self.refunded_amount += amount;
+        if self.status != PaymentStatus::Disputed {
+            self.status = if self.refunded_amount == self.amount {
+                PaymentStatus::Refunded
+            } else {
+                PaymentStatus::PartiallyRefunded
+            };
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Marks the payment as disputed by the customer's bank
+    pub fn mark_disputed(&mut self) {
+        self.status = PaymentStatus::Disputed;
+        self.updated_at = Utc::now();
+    }
+
+    /// Restores the refund-based status after a dispute was won
+    pub fn clear_dispute(&mut self) {
+        self.status = match self.refunded_amount {
+            0 => PaymentStatus::Succeeded,
+            refunded if refunded == self.amount => PaymentStatus::Refunded,
+            _ => PaymentStatus::PartiallyRefunded,
+        };
+        self.updated_at = Utc::now();
+    }
+}
+
+/// How the mobile app completes authentication of a payment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentClientAction {
+    /// Hand the client secret to the provider's SDK, which shows the bank's challenge
+    UseSdk {
+        /// Secret that lets the app act on this one payment intent
+        client_secret: String,
+    },
+    /// Open the bank's challenge page, which returns to the app's return URL
+    RedirectToUrl {
+        /// Secret that lets the app act on this one payment intent
+        client_secret: String,
+        /// Page to open
+        url: String,
+    },
+}
+
+/// A status change of a payment, shown on the order timeline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentTimelineEntry {
+    /// Unique identifier for the entry
+    pub id: Uuid,
+
+    /// Payment whose status changed
+    pub payment_id: Uuid,
+
+    /// Status the payment moved to
+    pub status: PaymentStatus,
+
+    /// Optional explanation, e.g. why authentication failed
+    pub note: Option<String>,
+
+    /// When the change happened
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl PaymentTimelineEntry {
+    /// Creates an entry recording the payment's current status
+    pub fn new(payment: &Payment, note: Option<String>, occurred_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            payment_id: payment.id,
+            status: payment.status,
+            note,
+            occurred_at,
+        }
+    }
+}
+
+/// State of a payout to a worker
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutStatus {
+    /// Waiting to be transferred
+    Pending,
+    /// Held back, e.g. while a chargeback is investigated
+    Frozen,
+    /// Transferred to the worker
+    Paid,
+}
+
+impl PayoutStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Frozen => "frozen",
+            Self::Paid => "paid",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "frozen" => Some(Self::Frozen),
+            "paid" => Some(Self::Paid),
+            _ => None,
+        }
+    }
+}
+
+/// A worker's earnings from a payment, waiting to be or already paid out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    /// Unique identifier for the payout
+    pub id: Uuid,
+
+    /// Payment the earnings come from
+    pub payment_id: Uuid,
+
+    /// Worker receiving the payout
+    pub worker_id: Uuid,
+
+    /// Amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Current state
+    pub status: PayoutStatus,
+
+    /// Why the payout was frozen
+    pub frozen_reason: Option<String>,
+
+    /// Provider's identifier for the transfer, once paid
+    pub transfer_reference: Option<String>,
+
+    /// When the payout was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the payout was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Payout {
+    /// Creates a new pending payout for a payment
+    pub fn new(payment: &Payment, amount: i64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            payment_id: payment.id,
+            worker_id: payment.worker_id,
+            amount,
+            currency: payment.currency.clone(),
+            status: PayoutStatus::Pending,
+            frozen_reason: None,
+            transfer_reference: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Freezes a pending payout
+    ///
+    /// # Returns
+    /// * `true` - The payout was pending and is now frozen
+    /// * `false` - The payout was already frozen or paid
+    pub fn freeze(&mut self, reason: impl Into<String>) -> bool {
+        if self.status != PayoutStatus::Pending {
+            return false;
+        }
+
+        self.status = PayoutStatus::Frozen;
+        self.frozen_reason = Some(reason.into());
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Marks a pending payout as transferred to the worker
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the payout is frozen or already paid
+    pub fn mark_paid(&mut self, transfer_reference: impl Into<String>) -> Result<(), String> {
+        if self.status != PayoutStatus::Pending {
+            return Err(format!("Payout is not pending: {}", self.status.as_str()));
+        }
+
+        self.status = PayoutStatus::Paid;
+        self.transfer_reference = Some(transfer_reference.into());
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
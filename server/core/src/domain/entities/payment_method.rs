@@ -0,0 +1,103 @@
+//! Saved payment method entities.
+//!
+//! Cards are tokenized by the payment provider's SDK on the device. The
+//! platform only keeps the provider's identifier for the payment method and
+//! the details needed to show it to the customer; card numbers never reach
+//! the server.
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A payment method a customer saved for future payments
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedPaymentMethod {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Customer who saved the method
+    pub customer_id: Uuid,
+
+    /// Payment provider holding the card (e.g. "stripe")
+    pub provider: String,
+
+    /// Provider's identifier for the payment method, charged when paying
+    pub provider_reference: String,
+
+    /// Card brand (e.g. "visa")
+    pub brand: String,
+
+    /// Last four digits of the card number
+    pub last4: String,
+
+    /// Expiry month, 1 to 12
+    pub exp_month: u32,
+
+    /// Expiry year, four digits
+    pub exp_year: i32,
+
+    /// Whether payments use this method unless another is chosen
+    pub is_default: bool,
+
+    /// When the method was saved
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedPaymentMethod {
+    /// Creates a new saved payment method
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty, the last four digits are
+    ///   not four digits or the expiry month is out of range
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        customer_id: Uuid,
+        provider: impl Into<String>,
+        provider_reference: impl Into<String>,
+        brand: impl Into<String>,
+        last4: impl Into<String>,
+        exp_month: u32,
+        exp_year: i32,
+    ) -> Result<Self, String> {
+        let provider_reference = provider_reference.into();
+        if provider_reference.trim().is_empty() {
+            return Err("Provider reference must not be empty".to_string());
+        }
+        let last4 = last4.into();
+        if last4.len() != 4 || !last4.chars().all(|c| c.is_ascii_digit()) {
+            return Err("Last four digits must be four digits".to_string());
+        }
+        if !(1..=12).contains(&exp_month) {
+            return Err(format!("Invalid expiry month: {}", exp_month));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            customer_id,
+            provider: provider.into(),
+            provider_reference,
+            brand: brand.into().to_ascii_lowercase(),
+            last4,
+            exp_month,
+            exp_year,
+            is_default: false,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Whether the card expired before `now`
+    ///
+    /// Cards are valid until the end of their expiry month.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        (now.year(), now.month()) > (self.exp_year, self.exp_month)
+    }
+}
+
+/// Whether a value looks like a raw card number rather than a provider token
+///
+/// Used to refuse card numbers sent by mistake, so they are never forwarded
+/// to the provider as a token or written to logs.
+pub fn looks_like_card_number(value: &str) -> bool {
+    let digits: Vec<char> = value.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+    (12..=19).contains(&digits.len()) && digits.iter().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,200 @@
+//! Payment risk entities for fraud checks run before a payment is created.
+//!
+//! Each payment attempt is assessed against a set of rules; the most severe
+//! action of the triggered rules decides whether the payment may proceed,
+//! must pass 3-D Secure, waits for manual review or is blocked.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A payment risk rule
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentRiskRule {
+    /// A recently created account paying a high amount
+    NewAccountHighAmount,
+    /// An account using more distinct cards than allowed in the window
+    TooManyCards,
+    /// The card's billing country differs from the job's market
+    BillingCountryMismatch,
+}
+
+impl PaymentRiskRule {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NewAccountHighAmount => "new_account_high_amount",
+            Self::TooManyCards => "too_many_cards",
+            Self::BillingCountryMismatch => "billing_country_mismatch",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "new_account_high_amount" => Some(Self::NewAccountHighAmount),
+            "too_many_cards" => Some(Self::TooManyCards),
+            "billing_country_mismatch" => Some(Self::BillingCountryMismatch),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to a payment attempt, from least to most severe
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskAction {
+    /// The payment may proceed
+    Allow,
+    /// The payment may proceed only after 3-D Secure authentication
+    RequireThreeDs,
+    /// The payment waits for an administrator's decision
+    Review,
+    /// The payment is refused
+    Block,
+}
+
+impl RiskAction {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::RequireThreeDs => "require_3ds",
+            Self::Review => "review",
+            Self::Block => "block",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(Self::Allow),
+            "require_3ds" => Some(Self::RequireThreeDs),
+            "review" => Some(Self::Review),
+            "block" => Some(Self::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Manual review state of an assessment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    /// The assessment did not need a review
+    NotRequired,
+    /// Waiting for an administrator
+    Pending,
+    /// The administrator let the payment proceed
+    Approved,
+    /// The administrator refused the payment
+    Rejected,
+}
+
+impl ReviewStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NotRequired => "not_required",
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "not_required" => Some(Self::NotRequired),
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+
+/// The risk assessment of one payment attempt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentRiskAssessment {
+    /// Unique identifier for the assessment
+    pub id: Uuid,
+
+    /// Paying customer
+    pub user_id: Uuid,
+
+    /// Amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// Market of the job (ISO 3166-1 alpha-2)
+    pub market: String,
+
+    /// Billing country of the card, if known
+    pub billing_country: Option<String>,
+
+    /// Provider fingerprint identifying the card, if known
+    pub card_fingerprint: Option<String>,
+
+    /// Rules the attempt triggered
+    pub triggered_rules: Vec<PaymentRiskRule>,
+
+    /// Most severe action of the triggered rules
+    pub action: RiskAction,
+
+    /// Manual review state
+    pub review_status: ReviewStatus,
+
+    /// Administrator who decided the review
+    pub reviewed_by: Option<Uuid>,
+
+    /// When the review was decided
+    pub reviewed_at: Option<DateTime<Utc>>,
+
+    /// Note left by the reviewer
+    pub review_note: Option<String>,
+
+    /// When the attempt was assessed
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaymentRiskAssessment {
+    /// Whether the payment may be created now, possibly with 3-D Secure
+    pub fn permits_payment(&self) -> bool {
+        match self.action {
+            RiskAction::Allow | RiskAction::RequireThreeDs => true,
+            RiskAction::Review => self.review_status == ReviewStatus::Approved,
+            RiskAction::Block => false,
+        }
+    }
+
+    /// Whether the payment must pass 3-D Secure authentication
+    pub fn requires_three_ds(&self) -> bool {
+        self.action == RiskAction::RequireThreeDs
+    }
+
+    /// Records an administrator's decision on a pending review
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the assessment is not waiting for review
+    pub fn decide(&mut self, approved: bool, reviewer: Uuid, note: Option<String>) -> Result<(), String> {
+        if self.review_status != ReviewStatus::Pending {
+            return Err(format!(
+                "Assessment is not pending review: {}",
+                self.review_status.as_str()
+            ));
+        }
+
+        self.review_status = if approved { ReviewStatus::Approved } else { ReviewStatus::Rejected };
+        self.reviewed_by = Some(reviewer);
+        self.reviewed_at = Some(Utc::now());
+        self.review_note = note;
+        Ok(())
+    }
+}
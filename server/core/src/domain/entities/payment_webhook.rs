@@ -0,0 +1,130 @@
+//! Payment provider webhook entities.
+//!
+//! Every verified webhook is stored with its full payload so the handling
+//! of a confirmation, refund or chargeback can be audited against what the provider
+//! actually sent.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a provider webhook asks the platform to do
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PaymentWebhookAction {
+    /// Money was returned to the customer
+    Refund {
+        /// Provider's identifier for the refunded payment
+        payment_reference: String,
+        /// Provider's identifier for the refund
+        refund_reference: String,
+        /// Refunded amount in minor units
+        amount: i64,
+        /// ISO 4217 currency code in upper case
+        currency: String,
+    },
+    /// The customer's bank opened a chargeback
+    DisputeOpened {
+        /// Provider's identifier for the disputed payment
+        payment_reference: String,
+        /// Provider's identifier for the dispute
+        dispute_reference: String,
+        /// Disputed amount in minor units
+        amount: i64,
+        /// ISO 4217 currency code in upper case
+        currency: String,
+        /// Reason given by the bank
+        reason: String,
+    },
+    /// The bank decided a chargeback
+    DisputeClosed {
+        /// Provider's identifier for the dispute
+        dispute_reference: String,
+        /// Whether the dispute was decided in the platform's favour
+        won: bool,
+    },
+    /// A payment intent was captured, e.g. after the customer passed 3-D Secure
+    PaymentSucceeded {
+        /// Provider's identifier for the payment intent
+        intent_reference: String,
+        /// Provider's identifier for the capture, if reported
+        charge_reference: Option<String>,
+    },
+    /// A payment intent could not be captured, e.g. authentication failed
+    PaymentFailed {
+        /// Provider's identifier for the payment intent
+        intent_reference: String,
+        /// Why the payment failed
+        reason: String,
+    },
+    /// An event type the platform does not act on
+    Ignored,
+}
+
+/// A webhook decoded by a payment provider adapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedWebhookEvent {
+    /// Provider's event identifier, used to drop redeliveries
+    pub event_id: String,
+
+    /// Provider's event type (e.g. "charge.dispute.created")
+    pub event_type: String,
+
+    /// When the provider created the event
+    pub occurred_at: DateTime<Utc>,
+
+    /// What the event asks the platform to do
+    pub action: PaymentWebhookAction,
+
+    /// The full decoded payload
+    pub payload: serde_json::Value,
+}
+
+/// A received provider webhook, kept for audit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentWebhookEvent {
+    /// Unique identifier for the record
+    pub id: Uuid,
+
+    /// Payment provider that sent the webhook
+    pub provider: String,
+
+    /// Provider's event identifier
+    pub event_id: String,
+
+    /// Provider's event type
+    pub event_type: String,
+
+    /// The full payload as sent by the provider
+    pub payload: serde_json::Value,
+
+    /// Short description of how the event was handled, set once processed
+    pub outcome: Option<String>,
+
+    /// When the webhook was received
+    pub received_at: DateTime<Utc>,
+
+    /// When the webhook was fully processed
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl PaymentWebhookEvent {
+    /// Creates a record for a newly received webhook
+    pub fn received(provider: impl Into<String>, event: &ParsedWebhookEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            provider: provider.into(),
+            event_id: event.event_id.clone(),
+            event_type: event.event_type.clone(),
+            payload: event.payload.clone(),
+            outcome: None,
+            received_at: Utc::now(),
+            processed_at: None,
+        }
+    }
+
+    /// Checks whether the webhook was already fully processed
+    pub fn is_processed(&self) -> bool {
+        self.processed_at.is_some()
+    }
+}
@@ -0,0 +1,171 @@
+//! Payout method entities.
+//!
+//! Workers are paid out to a bank account registered with the payment
+//! provider. Before the first payout the account must be verified, either
+//! by the provider matching the account holder to the worker's identity or
+//! by the worker confirming the two small deposits the provider sent.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::payment::normalize_currency;
+
+/// Verification state of a payout method
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutMethodStatus {
+    /// The provider is checking the account holder against the worker's identity
+    PendingVerification,
+    /// The provider sent micro-deposits the worker has to confirm
+    AwaitingMicroDeposits,
+    /// Payouts may be sent to the account
+    Verified,
+    /// The account could not be verified and cannot receive payouts
+    VerificationFailed,
+}
+
+impl PayoutMethodStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PendingVerification => "pending_verification",
+            Self::AwaitingMicroDeposits => "awaiting_micro_deposits",
+            Self::Verified => "verified",
+            Self::VerificationFailed => "verification_failed",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending_verification" => Some(Self::PendingVerification),
+            "awaiting_micro_deposits" => Some(Self::AwaitingMicroDeposits),
+            "verified" => Some(Self::Verified),
+            "verification_failed" => Some(Self::VerificationFailed),
+            _ => None,
+        }
+    }
+}
+
+/// A bank account a worker registered to receive payouts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutMethod {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Worker receiving payouts
+    pub worker_id: Uuid,
+
+    /// Payment provider holding the bank account (e.g. "stripe")
+    pub provider: String,
+
+    /// Provider's identifier for the bank account, paid out to
+    pub provider_reference: String,
+
+    /// Name of the bank, when the provider reports it
+    pub bank_name: Option<String>,
+
+    /// Last four digits of the account number
+    pub account_last4: String,
+
+    /// ISO 4217 currency code of the account in upper case
+    pub currency: String,
+
+    /// Verification state
+    pub status: PayoutMethodStatus,
+
+    /// Wrong micro-deposit amounts entered so far
+    pub failed_attempts: u32,
+
+    /// When the account was verified
+    pub verified_at: Option<DateTime<Utc>>,
+
+    /// When the account was registered
+    pub created_at: DateTime<Utc>,
+
+    /// When the account was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PayoutMethod {
+    /// Creates a new payout method waiting for the provider's verification
+    ///
+    /// # Returns
+    /// * `Err(String)` - If the reference is empty, the last four digits are
+    ///   not four digits or the currency is not a 3-letter code
+    pub fn new(
+        worker_id: Uuid,
+        provider: impl Into<String>,
+        provider_reference: impl Into<String>,
+        bank_name: Option<String>,
+        account_last4: impl Into<String>,
+        currency: &str,
+    ) -> Result<Self, String> {
+        let provider_reference = provider_reference.into();
+        if provider_reference.trim().is_empty() {
+            return Err("Provider reference must not be empty".to_string());
+        }
+        let account_last4 = account_last4.into();
+        if account_last4.len() != 4 || !account_last4.chars().all(|c| c.is_ascii_digit()) {
+            return Err("Last four digits must be four digits".to_string());
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            worker_id,
+            provider: provider.into(),
+            provider_reference,
+            bank_name,
+            account_last4,
+            currency: normalize_currency(currency)?,
+            status: PayoutMethodStatus::PendingVerification,
+            failed_attempts: 0,
+            verified_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Whether payouts may be sent to the account
+    pub fn is_verified(&self) -> bool {
+        self.status == PayoutMethodStatus::Verified
+    }
+
+    /// Marks the account as waiting for the worker to confirm micro-deposits
+    pub fn await_micro_deposits(&mut self) {
+        self.status = PayoutMethodStatus::AwaitingMicroDeposits;
+        self.updated_at = Utc::now();
+    }
+
+    /// Marks the account as verified
+    pub fn mark_verified(&mut self) {
+        let now = Utc::now();
+        self.status = PayoutMethodStatus::Verified;
+        self.verified_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Marks the account as failing verification
+    pub fn mark_failed(&mut self) {
+        self.status = PayoutMethodStatus::VerificationFailed;
+        self.updated_at = Utc::now();
+    }
+
+    /// Records wrong micro-deposit amounts
+    ///
+    /// # Returns
+    /// * `true` - The attempts are used up and the account failed verification
+    /// * `false` - The worker may try again
+    pub fn record_failed_attempt(&mut self, max_attempts: u32) -> bool {
+        self.failed_attempts += 1;
+        self.updated_at = Utc::now();
+        if self.failed_attempts >= max_attempts {
+            self.status = PayoutMethodStatus::VerificationFailed;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,247 @@
+//! Reconciliation entities comparing the payment ledger with provider reports.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use uuid::Uuid;
+
+use super::payment::{LedgerEntry, LedgerEntryKind};
+
+/// A money movement as reported by the payment provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderTransaction {
+    /// Provider's identifier for the movement
+    pub reference: String,
+
+    /// Type of movement
+    pub kind: LedgerEntryKind,
+
+    /// Signed amount in minor units
+    pub amount: i64,
+
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+
+    /// When the movement happened at the provider
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Why a reference did not reconcile
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    /// The provider reported a movement the ledger does not have
+    MissingInLedger,
+    /// The ledger has a movement the provider did not report
+    MissingAtProvider,
+    /// Both sides have the movement with different amounts
+    AmountMismatch,
+    /// Both sides have the movement in different currencies
+    CurrencyMismatch,
+}
+
+impl DiscrepancyKind {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingInLedger => "missing_in_ledger",
+            Self::MissingAtProvider => "missing_at_provider",
+            Self::AmountMismatch => "amount_mismatch",
+            Self::CurrencyMismatch => "currency_mismatch",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "missing_in_ledger" => Some(Self::MissingInLedger),
+            "missing_at_provider" => Some(Self::MissingAtProvider),
+            "amount_mismatch" => Some(Self::AmountMismatch),
+            "currency_mismatch" => Some(Self::CurrencyMismatch),
+            _ => None,
+        }
+    }
+}
+
+/// A single reference that did not reconcile
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Discrepancy {
+    /// Provider reference of the movement
+    pub reference: String,
+
+    /// Why the reference did not reconcile
+    pub kind: DiscrepancyKind,
+
+    /// Currency of the provider's record, or the ledger's when the provider has none
+    pub currency: String,
+
+    /// Net ledger amount for the reference, if the ledger has it
+    pub ledger_amount: Option<i64>,
+
+    /// Net provider amount for the reference, if the provider reported it
+    pub provider_amount: Option<i64>,
+}
+
+impl Discrepancy {
+    /// Absolute amount by which the two sides disagree, in minor units of `currency`
+    ///
+    /// For a currency mismatch the whole provider amount is counted, since
+    /// the ledger amount is in another currency.
+    pub fn difference(&self) -> i64 {
+        match self.kind {
+            DiscrepancyKind::CurrencyMismatch => self.provider_amount.unwrap_or(0).abs(),
+            _ => (self.provider_amount.unwrap_or(0) - self.ledger_amount.unwrap_or(0)).abs(),
+        }
+    }
+}
+
+/// Outcome of reconciling one provider over one period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    /// Unique identifier for the report
+    pub id: Uuid,
+
+    /// Payment provider that was reconciled (e.g. "stripe")
+    pub provider: String,
+
+    /// Start of the reconciled period (inclusive)
+    pub period_start: DateTime<Utc>,
+
+    /// End of the reconciled period (exclusive)
+    pub period_end: DateTime<Utc>,
+
+    /// Number of ledger entries in the period
+    pub ledger_count: usize,
+
+    /// Number of provider transactions in the period
+    pub provider_count: usize,
+
+    /// Number of references that reconciled exactly
+    pub matched_count: usize,
+
+    /// References that did not reconcile, ordered by reference
+    pub discrepancies: Vec<Discrepancy>,
+
+    /// Sum of discrepancy differences per currency, in minor units
+    pub discrepancy_totals: BTreeMap<String, i64>,
+
+    /// Whether administrators were alerted about this report
+    pub alerted: bool,
+
+    /// When the report was produced
+    pub created_at: DateTime<Utc>,
+}
+
+/// Net amount of all records sharing a reference
+struct ReferenceTotal {
+    currency: String,
+    amount: i64,
+    mixed_currency: bool,
+}
+
+fn add_to_totals(totals: &mut BTreeMap<String, ReferenceTotal>, reference: &str, currency: &str, amount: i64) {
+    totals
+        .entry(reference.to_string())
+        .and_modify(|total| {
+            total.amount += amount;
+            total.mixed_currency |= total.currency != currency;
+        })
+        .or_insert_with(|| ReferenceTotal {
+            currency: currency.to_string(),
+            amount,
+            mixed_currency: false,
+        });
+}
+
+impl ReconciliationReport {
+    /// Reconciles ledger entries against provider transactions for a period
+    ///
+    /// Records are matched by provider reference. Several records with the
+    /// same reference (e.g. a charge and its fee) are netted before
+    /// comparing, so only the net movement per reference has to agree.
+    pub fn reconcile(
+        provider: impl Into<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        ledger: &[LedgerEntry],
+        transactions: &[ProviderTransaction],
+    ) -> Self {
+        let mut ledger_totals = BTreeMap::new();
+        for entry in ledger {
+            add_to_totals(&mut ledger_totals, &entry.provider_reference, &entry.currency, entry.amount);
+        }
+
+        let mut provider_totals = BTreeMap::new();
+        for transaction in transactions {
+            add_to_totals(&mut provider_totals, &transaction.reference, &transaction.currency, transaction.amount);
+        }
+
+        let references: BTreeSet<&String> = ledger_totals.keys().chain(provider_totals.keys()).collect();
+        let mut matched_count = 0;
+        let mut discrepancies = Vec::new();
+
+        for reference in references {
+            let ledger_total = ledger_totals.get(reference);
+            let provider_total = provider_totals.get(reference);
+
+            let kind = match (ledger_total, provider_total) {
+                (None, Some(_)) => DiscrepancyKind::MissingInLedger,
+                (Some(_), None) => DiscrepancyKind::MissingAtProvider,
+                (Some(l), Some(p)) if l.mixed_currency || p.mixed_currency || l.currency != p.currency => {
+                    DiscrepancyKind::CurrencyMismatch
+                }
+                (Some(l), Some(p)) if l.amount != p.amount => DiscrepancyKind::AmountMismatch,
+                _ => {
+                    matched_count += 1;
+                    continue;
+                }
+            };
+
+            let currency = provider_total
+                .or(ledger_total)
+                .map(|total| total.currency.clone())
+                .unwrap_or_default();
+
+            discrepancies.push(Discrepancy {
+                reference: reference.clone(),
+                kind,
+                currency,
+                ledger_amount: ledger_total.map(|total| total.amount),
+                provider_amount: provider_total.map(|total| total.amount),
+            });
+        }
+
+        let mut discrepancy_totals = BTreeMap::new();
+        for discrepancy in &discrepancies {
+            *discrepancy_totals.entry(discrepancy.currency.clone()).or_insert(0) += discrepancy.difference();
+        }
+
+        Self {
+            id: Uuid::new_v4(),
+            provider: provider.into(),
+            period_start,
+            period_end,
+            ledger_count: ledger.len(),
+            provider_count: transactions.len(),
+            matched_count,
+            discrepancies,
+            discrepancy_totals,
+            alerted: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Checks whether every reference reconciled
+    pub fn is_balanced(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    /// Checks whether the total discrepancy in any currency exceeds the threshold
+    ///
+    /// # Arguments
+    /// * `threshold` - Largest tolerated total in minor units
+    pub fn exceeds_threshold(&self, threshold: i64) -> bool {
+        self.discrepancy_totals.values().any(|total| *total > threshold)
+    }
+}
@@ -0,0 +1,235 @@
+//! Security webhook entities for forwarding audit events to operator endpoints.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use super::audit::AuditEventType;
+
+/// Category of security event a webhook can subscribe to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    /// A phone number or IP address exceeded a rate limit
+    RateLimitViolation,
+    /// An account was locked after repeated failures
+    AccountLocked,
+    /// A revoked refresh token was presented again
+    RefreshTokenReuse,
+    /// An administrator changed platform state
+    AdminAction,
+}
+
+impl SecurityEventKind {
+    /// All kinds, used when a webhook subscribes to everything
+    pub const ALL: [SecurityEventKind; 4] = [
+        Self::RateLimitViolation,
+        Self::AccountLocked,
+        Self::RefreshTokenReuse,
+        Self::AdminAction,
+    ];
+
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimitViolation => "rate_limit_violation",
+            Self::AccountLocked => "account_locked",
+            Self::RefreshTokenReuse => "refresh_token_reuse",
+            Self::AdminAction => "admin_action",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "rate_limit_violation" => Some(Self::RateLimitViolation),
+            "account_locked" => Some(Self::AccountLocked),
+            "refresh_token_reuse" => Some(Self::RefreshTokenReuse),
+            "admin_action" => Some(Self::AdminAction),
+            _ => None,
+        }
+    }
+
+    /// Kind of an audit event, if it is forwarded to webhooks at all
+    pub fn from_event_type(event_type: AuditEventType) -> Option<Self> {
+        match event_type {
+            AuditEventType::RateLimitExceeded
+            | AuditEventType::RateLimitPhoneExceeded
+            | AuditEventType::RateLimitIpExceeded => Some(Self::RateLimitViolation),
+            AuditEventType::AccountLocked => Some(Self::AccountLocked),
+            AuditEventType::RefreshTokenReuseDetected => Some(Self::RefreshTokenReuse),
+            AuditEventType::AdminAction => Some(Self::AdminAction),
+            _ => None,
+        }
+    }
+}
+
+/// An operator endpoint receiving security events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityWebhook {
+    /// Unique identifier for the webhook
+    pub id: Uuid,
+
+    /// HTTPS URL events are posted to
+    pub url: String,
+
+    /// Shared secret used to sign payloads
+    pub secret: String,
+
+    /// Event kinds delivered to the endpoint
+    pub events: Vec<SecurityEventKind>,
+
+    /// Whether new events are delivered
+    pub is_active: bool,
+
+    /// Administrator who registered the webhook
+    pub created_by: Uuid,
+
+    /// Timestamp when the webhook was registered
+    pub created_at: DateTime<Utc>,
+}
+
+impl SecurityWebhook {
+    /// Creates a new active webhook
+    pub fn new(url: String, secret: String, events: Vec<SecurityEventKind>, created_by: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            url,
+            secret,
+            events,
+            is_active: true,
+            created_by,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Whether the webhook receives events of this kind
+    pub fn subscribes_to(&self, kind: SecurityEventKind) -> bool {
+        self.is_active && self.events.contains(&kind)
+    }
+}
+
+/// Delivery status of an event to one webhook
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// Accepted by the endpoint
+    Delivered,
+    /// Gave up after the maximum number of attempts
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "delivered" => Some(Self::Delivered),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// A security event addressed to one webhook
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    /// Unique identifier for the delivery, sent to the endpoint for deduplication
+    pub id: Uuid,
+
+    /// Webhook the event is delivered to
+    pub webhook_id: Uuid,
+
+    /// Kind of the event
+    pub event_kind: SecurityEventKind,
+
+    /// JSON body posted to the endpoint
+    pub payload: JsonValue,
+
+    /// Current delivery status
+    pub status: WebhookDeliveryStatus,
+
+    /// Number of attempts made so far
+    pub attempts: u32,
+
+    /// Earliest time of the next attempt
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// Error of the most recent failed attempt
+    pub last_error: Option<String>,
+
+    /// Timestamp when the delivery was enqueued
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp when the endpoint accepted the event
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    /// Creates a new pending delivery, due immediately
+    pub fn new(webhook_id: Uuid, event_kind: SecurityEventKind, payload: JsonValue) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            webhook_id,
+            event_kind,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    /// Marks the delivery as accepted by the endpoint
+    pub fn mark_delivered(&mut self) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Delivered;
+        self.last_error = None;
+        self.delivered_at = Some(Utc::now());
+    }
+
+    /// Records a failed attempt and schedules a retry with exponential backoff
+    ///
+    /// The delay doubles after every attempt, starting at `base_delay` and
+    /// capped at `max_delay`. The delivery fails permanently once
+    /// `max_attempts` attempts have been made.
+    pub fn record_failure(
+        &mut self,
+        error: String,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+    ) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+
+        if self.attempts >= max_attempts {
+            self.status = WebhookDeliveryStatus::Failed;
+            return;
+        }
+
+        let factor = 2i32.saturating_pow(self.attempts.saturating_sub(1).min(30));
+        let delay = base_delay
+            .checked_mul(factor)
+            .map_or(max_delay, |delay| delay.min(max_delay));
+        self.next_attempt_at = now + delay;
+    }
+}
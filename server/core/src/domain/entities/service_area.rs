@@ -0,0 +1,317 @@
+//! Worker service-area polygons.
+//!
+//! A service area is a simple polygon (no holes) in WGS 84 longitude and
+//! latitude, exchanged as GeoJSON. Polygons let workers describe coverage
+//! that follows rivers, bays and suburbs, which a radius cannot.
+
+use chrono::{DateTime, Utc};
+use re_shared::types::common::Coordinate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use uuid::Uuid;
+
+/// Maximum number of vertices accepted in an uploaded polygon
+pub const MAX_POLYGON_VERTICES: usize = 1000;
+
+/// A polygon describing where a worker accepts jobs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceArea {
+    /// Unique identifier for the area
+    pub id: Uuid,
+
+    /// Worker the area belongs to
+    pub worker_id: Uuid,
+
+    /// Optional display name (e.g. "Inner West")
+    pub name: Option<String>,
+
+    /// Closed exterior ring in counter-clockwise order; the first and last
+    /// vertices are equal
+    pub ring: Vec<Coordinate>,
+
+    /// Timestamp when the area was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl ServiceArea {
+    /// Create a service area from a GeoJSON geometry
+    ///
+    /// Accepts a `Polygon` geometry or a `Feature` wrapping one. The ring is
+    /// validated, then simplified with the given tolerance (in degrees; `0.0`
+    /// disables simplification) and normalized to counter-clockwise order.
+    ///
+    /// # Returns
+    /// * `Ok(ServiceArea)` - A valid service area
+    /// * `Err(String)` - Why the polygon was rejected
+    pub fn from_geojson(
+        worker_id: Uuid,
+        name: Option<String>,
+        geojson: &JsonValue,
+        tolerance: f64,
+    ) -> Result<Self, String> {
+        let ring = parse_polygon(geojson)?;
+        validate_ring(&ring)?;
+
+        let mut ring = simplify_ring(&ring, tolerance);
+        if signed_area(&ring) < 0.0 {
+            ring.reverse();
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            worker_id,
+            name,
+            ring,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Check whether a point lies inside the area
+    ///
+    /// Uses ray casting on longitude/latitude, which is accurate for the
+    /// city-scale polygons service areas describe.
+    pub fn contains(&self, point: &Coordinate) -> bool {
+        let mut inside = false;
+        let (x, y) = (point.longitude, point.latitude);
+
+        for edge in self.ring.windows(2) {
+            let (a, b) = (&edge[0], &edge[1]);
+            let crosses = (a.latitude > y) != (b.latitude > y);
+            if crosses {
+                let intersect_x = a.longitude
+                    + (y - a.latitude) / (b.latitude - a.latitude) * (b.longitude - a.longitude);
+                if x < intersect_x {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// Convert the area to a GeoJSON `Polygon` geometry
+    pub fn to_geojson(&self) -> JsonValue {
+        let positions: Vec<[f64; 2]> = self
+            .ring
+            .iter()
+            .map(|c| [c.longitude, c.latitude])
+            .collect();
+        json!({ "type": "Polygon", "coordinates": [positions] })
+    }
+
+    /// Convert the area to WKT with longitude-first axis order
+    pub fn to_wkt(&self) -> String {
+        let positions: Vec<String> = self
+            .ring
+            .iter()
+            .map(|c| format!("{} {}", c.longitude, c.latitude))
+            .collect();
+        format!("POLYGON(({}))", positions.join(", "))
+    }
+}
+
+/// Extract the exterior ring from a GeoJSON Polygon or Feature
+fn parse_polygon(geojson: &JsonValue) -> Result<Vec<Coordinate>, String> {
+    let geometry = match geojson.get("type").and_then(|t| t.as_str()) {
+        Some("Feature") => geojson
+            .get("geometry")
+            .ok_or_else(|| "Feature has no geometry".to_string())?,
+        Some("Polygon") => geojson,
+        Some(other) => return Err(format!("Unsupported geometry type: {}", other)),
+        None => return Err("Missing GeoJSON type".to_string()),
+    };
+
+    if geometry.get("type").and_then(|t| t.as_str()) != Some("Polygon") {
+        return Err("Geometry must be a Polygon".to_string());
+    }
+
+    let rings = geometry
+        .get("coordinates")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| "Polygon has no coordinates".to_string())?;
+
+    match rings.len() {
+        0 => return Err("Polygon has no rings".to_string()),
+        1 => {}
+        _ => return Err("Polygons with holes are not supported".to_string()),
+    }
+
+    let positions = rings[0]
+        .as_array()
+        .ok_or_else(|| "Polygon ring must be an array".to_string())?;
+
+    if positions.len() > MAX_POLYGON_VERTICES {
+        return Err(format!(
+            "Polygon has {} vertices (maximum {})",
+            positions.len(),
+            MAX_POLYGON_VERTICES
+        ));
+    }
+
+    positions
+        .iter()
+        .map(|position| {
+            let pair = position
+                .as_array()
+                .filter(|p| p.len() >= 2)
+                .ok_or_else(|| "Position must be [longitude, latitude]".to_string())?;
+            let longitude = pair[0].as_f64().ok_or_else(|| "Longitude must be a number".to_string())?;
+            let latitude = pair[1].as_f64().ok_or_else(|| "Latitude must be a number".to_string())?;
+            Ok(Coordinate::new(latitude, longitude))
+        })
+        .collect()
+}
+
+/// Validate a closed, simple ring within WGS 84 bounds
+fn validate_ring(ring: &[Coordinate]) -> Result<(), String> {
+    if ring.len() < 4 {
+        return Err("Polygon ring needs at least 4 positions".to_string());
+    }
+
+    for c in ring {
+        if !(-180.0..=180.0).contains(&c.longitude) || !(-90.0..=90.0).contains(&c.latitude) {
+            return Err(format!(
+                "Position [{}, {}] is out of range",
+                c.longitude, c.latitude
+            ));
+        }
+    }
+
+    if !same_point(&ring[0], &ring[ring.len() - 1]) {
+        return Err("Polygon ring must be closed".to_string());
+    }
+
+    if signed_area(ring).abs() < f64::EPSILON {
+        return Err("Polygon has no area".to_string());
+    }
+
+    if is_self_intersecting(ring) {
+        return Err("Polygon must not intersect itself".to_string());
+    }
+
+    Ok(())
+}
+
+/// Simplify a closed ring with the Douglas-Peucker algorithm
+///
+/// Falls back to the original ring if simplification would leave fewer than
+/// three distinct vertices or make the ring self-intersecting.
+fn simplify_ring(ring: &[Coordinate], tolerance: f64) -> Vec<Coordinate> {
+    if tolerance <= 0.0 || ring.len() <= 4 {
+        return ring.to_vec();
+    }
+
+    let mut keep = vec![false; ring.len()];
+    keep[0] = true;
+    keep[ring.len() - 1] = true;
+
+    // A closed ring has identical endpoints, so split it at the vertex
+    // farthest from the start and simplify both halves
+    let split = (1..ring.len() - 1)
+        .max_by(|&a, &b| {
+            distance(&ring[0], &ring[a])
+                .partial_cmp(&distance(&ring[0], &ring[b]))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(ring.len() / 2);
+    keep[split] = true;
+
+    douglas_peucker(ring, 0, split, tolerance, &mut keep);
+    douglas_peucker(ring, split, ring.len() - 1, tolerance, &mut keep);
+
+    let simplified: Vec<Coordinate> = ring
+        .iter()
+        .zip(keep)
+        .filter(|(_, kept)| *kept)
+        .map(|(c, _)| *c)
+        .collect();
+
+    if simplified.len() < 4 || is_self_intersecting(&simplified) {
+        ring.to_vec()
+    } else {
+        simplified
+    }
+}
+
+fn douglas_peucker(ring: &[Coordinate], start: usize, end: usize, tolerance: f64, keep: &mut [bool]) {
+    if end <= start + 1 {
+        return;
+    }
+
+    let (index, max_distance) = (start + 1..end)
+        .map(|i| (i, perpendicular_distance(&ring[i], &ring[start], &ring[end])))
+        .fold((start, 0.0), |best, current| if current.1 > best.1 { current } else { best });
+
+    if max_distance > tolerance {
+        keep[index] = true;
+        douglas_peucker(ring, start, index, tolerance, keep);
+        douglas_peucker(ring, index, end, tolerance, keep);
+    }
+}
+
+fn perpendicular_distance(point: &Coordinate, start: &Coordinate, end: &Coordinate) -> f64 {
+    let dx = end.longitude - start.longitude;
+    let dy = end.latitude - start.latitude;
+    let length = (dx * dx + dy * dy).sqrt();
+
+    if length == 0.0 {
+        return distance(point, start);
+    }
+
+    (dy * point.longitude - dx * point.latitude + end.longitude * start.latitude
+        - end.latitude * start.longitude)
+        .abs()
+        / length
+}
+
+fn distance(a: &Coordinate, b: &Coordinate) -> f64 {
+    let dx = a.longitude - b.longitude;
+    let dy = a.latitude - b.latitude;
+    (dx * dx + dy * dy).sqrt()
+}
+
+fn same_point(a: &Coordinate, b: &Coordinate) -> bool {
+    a.longitude == b.longitude && a.latitude == b.latitude
+}
+
+/// Signed area of a closed ring; positive for counter-clockwise
+fn signed_area(ring: &[Coordinate]) -> f64 {
+    ring.windows(2)
+        .map(|edge| edge[0].longitude * edge[1].latitude - edge[1].longitude * edge[0].latitude)
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Check whether any two non-adjacent edges of a closed ring intersect
+fn is_self_intersecting(ring: &[Coordinate]) -> bool {
+    let edges = ring.len() - 1;
+
+    for i in 0..edges {
+        for j in i + 1..edges {
+            // Skip edges that share a vertex, including the closing edge
+            if j == i + 1 || (i == 0 && j == edges - 1) {
+                continue;
+            }
+            if segments_intersect(&ring[i], &ring[i + 1], &ring[j], &ring[j + 1]) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn segments_intersect(p1: &Coordinate, p2: &Coordinate, q1: &Coordinate, q2: &Coordinate) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+}
+
+fn orientation(a: &Coordinate, b: &Coordinate, c: &Coordinate) -> f64 {
+    (b.longitude - a.longitude) * (c.latitude - a.latitude)
+        - (b.latitude - a.latitude) * (c.longitude - a.longitude)
+}
@@ -0,0 +1,233 @@
+//! SMS delivery log entity linking provider messages to the requests that sent them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use re_shared::types::DateRange;
+
+/// Delivery status of an SMS as last reported by the provider
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsDeliveryStatus {
+    /// Accepted by the provider, no receipt yet
+    Sent,
+    /// The provider reported delivery to the handset
+    Delivered,
+    /// The provider reported the message could not be delivered
+    Undelivered,
+    /// The provider rejected the message
+    Failed,
+}
+
+impl SmsDeliveryStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Delivered => "delivered",
+            Self::Undelivered => "undelivered",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "sent" => Some(Self::Sent),
+            "delivered" => Some(Self::Delivered),
+            "undelivered" => Some(Self::Undelivered),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One SMS handed to a provider, with the trace ID of the request that sent it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmsDeliveryLog {
+    /// Unique identifier for the log entry
+    pub id: Uuid,
+
+    /// Trace ID of the API request that sent the message, if known
+    pub trace_id: Option<String>,
+
+    /// Provider the message was sent through (e.g. "Twilio")
+    pub provider: String,
+
+    /// Provider message ID, used to match delivery receipts
+    pub provider_message_id: Option<String>,
+
+    /// Masked recipient phone number (e.g. "+*******5678")
+    pub phone_masked: String,
+
+    /// Current delivery status
+    pub status: SmsDeliveryStatus,
+
+    /// Error reported by the provider, if any
+    pub error: Option<String>,
+
+    /// Template the message was rendered from (e.g. "verification_code")
+    pub template: Option<String>,
+
+    /// Estimated cost in millionths of the budget currency, if costs are tracked
+    pub cost_micros: Option<u64>,
+
+    /// Timestamp when the message was handed to the provider
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp of the last status change
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SmsDeliveryLog {
+    /// Creates a log entry for a message the provider accepted
+    pub fn sent(
+        trace_id: Option<String>,
+        provider: impl Into<String>,
+        provider_message_id: impl Into<String>,
+        phone_masked: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            trace_id,
+            provider.into(),
+            Some(provider_message_id.into()),
+            phone_masked.into(),
+            SmsDeliveryStatus::Sent,
+            None,
+        )
+    }
+
+    /// Creates a log entry for a message the provider rejected
+    pub fn failed(
+        trace_id: Option<String>,
+        provider: impl Into<String>,
+        phone_masked: impl Into<String>,
+        error: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            trace_id,
+            provider.into(),
+            None,
+            phone_masked.into(),
+            SmsDeliveryStatus::Failed,
+            Some(error.into()),
+        )
+    }
+
+    fn new(
+        trace_id: Option<String>,
+        provider: String,
+        provider_message_id: Option<String>,
+        phone_masked: String,
+        status: SmsDeliveryStatus,
+        error: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            trace_id,
+            provider,
+            provider_message_id,
+            phone_masked,
+            status,
+            error,
+            template: None,
+            cost_micros: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Sets the template the message was rendered from
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Sets the estimated cost of the message
+    pub fn with_cost_micros(mut self, cost_micros: u64) -> Self {
+        self.cost_micros = Some(cost_micros);
+        self
+    }
+
+    /// Applies a delivery receipt from the provider
+    ///
+    /// Receipts may arrive out of order, so a final status (delivered,
+    /// undelivered or failed) is never replaced by `Sent`.
+    ///
+    /// # Returns
+    /// * `true` if the status changed
+    pub fn apply_receipt(&mut self, status: SmsDeliveryStatus, error: Option<String>) -> bool {
+        if status == self.status || status == SmsDeliveryStatus::Sent {
+            return false;
+        }
+
+        self.status = status;
+        self.error = error;
+        self.updated_at = Utc::now();
+        true
+    }
+}
+
+/// Delivery receipt reported by a provider for one message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmsDeliveryReceipt {
+    /// Provider message ID the receipt is for
+    pub provider_message_id: String,
+
+    /// Status reported by the provider
+    pub status: SmsDeliveryStatus,
+
+    /// Error reported by the provider, if any
+    pub error: Option<String>,
+}
+
+impl SmsDeliveryStatus {
+    /// Whether the provider will not report on the message again
+    pub fn is_final(&self) -> bool {
+        !matches!(self, Self::Sent)
+    }
+}
+
+/// Criteria for searching the outbound SMS log
+#[derive(Debug, Clone)]
+pub struct SmsLogFilter {
+    /// Only messages to this masked phone number (e.g. "+*******5678")
+    pub phone_masked: Option<String>,
+    /// Only messages sent through this provider (e.g. "Twilio")
+    pub provider: Option<String>,
+    /// Only messages in this delivery status
+    pub status: Option<SmsDeliveryStatus>,
+    /// Only messages rendered from this template
+    pub template: Option<String>,
+    /// Only messages sent within this range
+    pub date_range: DateRange,
+}
+
+impl Default for SmsLogFilter {
+    fn default() -> Self {
+        Self {
+            phone_masked: None,
+            provider: None,
+            status: None,
+            template: None,
+            date_range: DateRange::new(None, None),
+        }
+    }
+}
+
+impl SmsLogFilter {
+    /// Whether a log entry matches every set criterion
+    pub fn matches(&self, log: &SmsDeliveryLog) -> bool {
+        self.phone_masked.as_deref().is_none_or(|phone| log.phone_masked == phone)
+            && self.provider.as_deref().is_none_or(|provider| log.provider == provider)
+            && self.status.is_none_or(|status| log.status == status)
+            && self
+                .template
+                .as_deref()
+                .is_none_or(|template| log.template.as_deref() == Some(template))
+            && self.date_range.contains(&log.created_at)
+    }
+}
@@ -0,0 +1,119 @@
+//! Queued SMS entities sent in the background by the SMS queue worker.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Placeholder replacing verification codes in dead letters
+pub const REDACTED_CODE: &str = "[redacted]";
+
+/// Content of a queued SMS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SmsJobPayload {
+    /// A verification code, sent with the provider's verification template
+    VerificationCode { code: String },
+    /// An arbitrary text message
+    Message { text: String },
+}
+
+/// An SMS waiting to be accepted by a provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmsJob {
+    /// Unique identifier for the job, reported as the message ID when queued
+    pub id: Uuid,
+
+    /// Recipient phone number in E.164 format
+    pub phone: String,
+
+    /// Content of the message
+    pub payload: SmsJobPayload,
+
+    /// Number of attempts made so far
+    pub attempts: u32,
+
+    /// Earliest time of the next attempt
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// Time after which sending the message is pointless, such as the
+    /// expiry of a verification code
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Error of the most recent failed attempt
+    pub last_error: Option<String>,
+
+    /// Timestamp when the job was enqueued
+    pub created_at: DateTime<Utc>,
+}
+
+impl SmsJob {
+    /// Creates a job sending a verification code, due immediately
+    pub fn verification_code(phone: String, code: String, expires_at: DateTime<Utc>) -> Self {
+        Self::new(phone, SmsJobPayload::VerificationCode { code }, Some(expires_at))
+    }
+
+    /// Creates a job sending a text message, due immediately
+    pub fn message(phone: String, text: String) -> Self {
+        Self::new(phone, SmsJobPayload::Message { text }, None)
+    }
+
+    fn new(phone: String, payload: SmsJobPayload, expires_at: Option<DateTime<Utc>>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            phone,
+            payload,
+            attempts: 0,
+            next_attempt_at: now,
+            expires_at,
+            last_error: None,
+            created_at: now,
+        }
+    }
+
+    /// Whether the message is no longer worth sending
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Records a failed attempt and schedules a retry with exponential backoff
+    ///
+    /// The delay doubles after every attempt, starting at `base_delay` and
+    /// capped at `max_delay`.
+    ///
+    /// # Returns
+    /// * `true` - A retry was scheduled
+    /// * `false` - `max_attempts` attempts have been made and the job is given up
+    pub fn record_failure(
+        &mut self,
+        error: String,
+        now: DateTime<Utc>,
+        max_attempts: u32,
+        base_delay: Duration,
+        max_delay: Duration,
+    ) -> bool {
+        self.attempts += 1;
+        self.last_error = Some(error);
+
+        if self.attempts >= max_attempts {
+            return false;
+        }
+
+        let factor = 2i32.saturating_pow(self.attempts.saturating_sub(1).min(30));
+        let delay = base_delay
+            .checked_mul(factor)
+            .map_or(max_delay, |delay| delay.min(max_delay));
+        self.next_attempt_at = now + delay;
+        true
+    }
+
+    /// Copy of the job safe to keep after it is given up, with any
+    /// verification code replaced by [`REDACTED_CODE`]
+    pub fn redacted(&self) -> Self {
+        let mut job = self.clone();
+        if let SmsJobPayload::VerificationCode { ref mut code } = job.payload {
+            *code = REDACTED_CODE.to_string();
+        }
+        job
+    }
+}
@@ -0,0 +1,25 @@
+//! Daily SMS spend entity aggregating estimated message costs.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Estimated spend on the messages sent through a provider to a
+/// destination prefix during one UTC day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmsDailySpend {
+    /// UTC day the messages were sent
+    pub day: NaiveDate,
+
+    /// Provider name used in the SMS configuration (e.g. `twilio`)
+    pub provider: String,
+
+    /// Prefix of the rate the messages were priced with, empty when no
+    /// rate matched
+    pub prefix: String,
+
+    /// Number of messages sent
+    pub messages: u64,
+
+    /// Estimated cost in millionths of the budget currency
+    pub cost_micros: u64,
+}
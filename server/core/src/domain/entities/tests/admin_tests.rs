@@ -0,0 +1,42 @@
+//! Unit tests for administrator entities
+
+use crate::domain::entities::admin::{AdminIdentity, AdminRole};
+use crate::domain::entities::token::{Claims, JWT_AUDIENCE};
+
+#[test]
+fn test_admin_role_round_trip() {
+    for role in [AdminRole::Admin, AdminRole::Support, AdminRole::Finance] {
+        assert_eq!(AdminRole::from_str(role.as_str()), Some(role));
+    }
+    assert_eq!(AdminRole::from_str("superuser"), None);
+}
+
+#[test]
+fn test_login_replaces_roles_and_profile() {
+    let mut identity = AdminIdentity::new("https://login.example.com".to_string(), "00u1".to_string());
+    identity.record_login(
+        Some("ops@renoveasy.com".to_string()),
+        None,
+        vec![AdminRole::Support, AdminRole::Finance],
+    );
+    assert!(identity.has_role(AdminRole::Finance));
+    assert_eq!(identity.role_names(), vec!["support".to_string(), "finance".to_string()]);
+
+    identity.record_login(None, Some("Ops".to_string()), vec![AdminRole::Support]);
+    assert!(!identity.has_role(AdminRole::Finance));
+    assert_eq!(identity.email, None);
+    assert!(identity.last_login_at >= identity.created_at);
+}
+
+#[test]
+fn test_admin_token_claims() {
+    let identity = AdminIdentity::new("https://login.example.com".to_string(), "00u1".to_string());
+    let claims = Claims::new_admin_token(identity.id, vec!["support".to_string()], 3600);
+
+    assert_eq!(claims.user_id().unwrap(), identity.id);
+    assert_eq!(claims.user_type.as_deref(), Some("admin"));
+    assert_eq!(claims.aud, JWT_AUDIENCE);
+    assert!(claims.has_role("support"));
+    assert!(!claims.has_role("admin"));
+    assert_eq!(claims.exp - claims.iat, 3600);
+}
@@ -0,0 +1,71 @@
+//! Unit tests for marketing attribution entities
+
+use chrono::NaiveDate;
+
+use crate::domain::entities::attribution::{AttributionCohort, RegistrationAttribution};
+
+fn attribution(source: &str, referrer: &str, consent: bool) -> RegistrationAttribution {
+    RegistrationAttribution {
+        utm_source: Some(source.to_string()),
+        utm_campaign: Some(" Spring_Kitchens ".to_string()),
+        referrer: Some(referrer.to_string()),
+        marketing_consent: consent,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_normalized_lowercases_and_reduces_referrer_to_host() {
+    let normalized = attribution("Google", "https://user:pw@WWW.Example.com:8443/p?email=a@b.c", true).normalized();
+
+    assert_eq!(normalized.utm_source.as_deref(), Some("google"));
+    assert_eq!(normalized.utm_campaign.as_deref(), Some("spring_kitchens"));
+    assert_eq!(normalized.referrer.as_deref(), Some("www.example.com"));
+}
+
+#[test]
+fn test_normalized_drops_blank_and_truncates_long_values() {
+    let normalized = RegistrationAttribution {
+        utm_medium: Some("   ".to_string()),
+        utm_term: Some("x".repeat(500)),
+        referrer: Some("not a url".to_string()),
+        ..Default::default()
+    }
+    .normalized();
+
+    assert_eq!(normalized.utm_medium, None);
+    assert_eq!(normalized.utm_term.map(|term| term.len()), Some(100));
+    assert_eq!(normalized.referrer, None);
+}
+
+#[test]
+fn test_consented_requires_consent_and_values() {
+    assert!(attribution("google", "example.com", false).consented().is_none());
+
+    let empty = RegistrationAttribution {
+        utm_source: Some(" ".to_string()),
+        marketing_consent: true,
+        ..Default::default()
+    };
+    assert!(empty.consented().is_none());
+
+    let consented = attribution("Google", "example.com", true).consented().unwrap();
+    assert_eq!(consented.utm_source.as_deref(), Some("google"));
+}
+
+#[test]
+fn test_cohort_activation_rate() {
+    let mut cohort = AttributionCohort {
+        cohort_week: NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(),
+        utm_source: Some("google".to_string()),
+        utm_medium: None,
+        utm_campaign: None,
+        registrations: 8,
+        activated: 2,
+    };
+    assert_eq!(cohort.activation_rate(), 0.25);
+
+    cohort.registrations = 0;
+    cohort.activated = 0;
+    assert_eq!(cohort.activation_rate(), 0.0);
+}
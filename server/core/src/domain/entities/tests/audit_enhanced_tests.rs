@@ -29,6 +29,13 @@ fn test_phone_masking() {
     assert_eq!(AuditLog::mask_phone(""), "");
 }
 
+#[test]
+fn test_ip_masking() {
+    assert_eq!(AuditLog::mask_ip("203.0.113.7"), "203.0.*.*");
+    assert_eq!(AuditLog::mask_ip("2001:db8:85a3::8a2e:370:7334"), "2001:db8:85a3::*");
+    assert_eq!(AuditLog::mask_ip("unknown"), "*");
+}
+
 #[test]
 fn test_device_info_extraction() {
     // Test mobile detection
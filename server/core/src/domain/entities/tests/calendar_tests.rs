@@ -0,0 +1,24 @@
+//! Unit tests for business calendar entities
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::{normalize_market, ClosureDate};
+
+#[test]
+fn test_closure_normalizes_market() {
+    let date = NaiveDate::from_ymd_opt(2025, 12, 24).unwrap();
+    let admin_id = Uuid::new_v4();
+
+    let closure = ClosureDate::new(" au ", date, "Platform maintenance".to_string(), Some(admin_id));
+
+    assert_eq!(closure.market, "AU");
+    assert_eq!(closure.date, date);
+    assert_eq!(closure.created_by, Some(admin_id));
+}
+
+#[test]
+fn test_normalize_market() {
+    assert_eq!(normalize_market("cn"), "CN");
+    assert_eq!(normalize_market("AU"), "AU");
+}
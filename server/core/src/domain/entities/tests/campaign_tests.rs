@@ -0,0 +1,170 @@
+//! Unit tests for notification campaign entities
+
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::campaign::{
+    Campaign, CampaignAudience, CampaignChannel, CampaignContent, CampaignStats, CampaignStatus, CampaignDelivery,
+    DeliveryStatus,
+};
+use crate::domain::entities::user::{User, UserType};
+
+fn content(title: &str, body: &str) -> CampaignContent {
+    CampaignContent {
+        title: title.to_string(),
+        body: body.to_string(),
+    }
+}
+
+fn campaign() -> Campaign {
+    let content = HashMap::from([
+        ("en".to_string(), content("Spring offer", "10% off kitchen renovations")),
+        ("zh".to_string(), content("春季优惠", "厨房翻新九折")),
+    ]);
+
+    Campaign::new(
+        "Spring promotion".to_string(),
+        CampaignAudience::default(),
+        content,
+        "en".to_string(),
+        Utc::now(),
+        Uuid::new_v4(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_campaign_requires_default_language_content() {
+    let content = HashMap::from([("zh".to_string(), content("春季优惠", "厨房翻新九折"))]);
+
+    let result = Campaign::new(
+        "Spring promotion".to_string(),
+        CampaignAudience::default(),
+        content,
+        "en".to_string(),
+        Utc::now(),
+        Uuid::new_v4(),
+    );
+
+    assert!(result.unwrap_err().contains("default language"));
+}
+
+#[test]
+fn test_campaign_rejects_empty_body() {
+    let content = HashMap::from([("en".to_string(), content("Spring offer", "  "))]);
+
+    let result = Campaign::new(
+        "Spring promotion".to_string(),
+        CampaignAudience::default(),
+        content,
+        "en".to_string(),
+        Utc::now(),
+        Uuid::new_v4(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_content_falls_back_to_default_language() {
+    let campaign = campaign();
+
+    assert_eq!(campaign.content_for("zh").unwrap().title, "春季优惠");
+    assert_eq!(campaign.content_for("fr").unwrap().title, "Spring offer");
+}
+
+#[test]
+fn test_sms_channel_limits_body_length() {
+    assert_eq!(campaign().channel, CampaignChannel::Push);
+
+    let campaign = campaign().with_channel(CampaignChannel::Sms).unwrap();
+    assert_eq!(campaign.channel, CampaignChannel::Sms);
+
+    let mut long = campaign.clone();
+    long.content.insert("en".to_string(), content("Spring offer", &"a".repeat(321)));
+    assert!(long.with_channel(CampaignChannel::Sms).unwrap_err().contains("SMS body for 'en'"));
+}
+
+#[test]
+fn test_campaign_lifecycle() {
+    let mut campaign = campaign();
+    assert!(campaign.is_due(Utc::now()));
+
+    campaign.start(42);
+    assert_eq!(campaign.status, CampaignStatus::Sending);
+    assert_eq!(campaign.audience_size, 42);
+    assert!(!campaign.is_due(Utc::now()));
+
+    campaign.complete();
+    assert_eq!(campaign.status, CampaignStatus::Completed);
+    assert!(campaign.completed_at.is_some());
+    assert!(!campaign.cancel());
+}
+
+#[test]
+fn test_audience_filters() {
+    let mut worker = User::new("hash".to_string(), "+61".to_string());
+    worker.set_user_type(UserType::Worker);
+    worker.update_last_login();
+
+    let audience = CampaignAudience {
+        user_type: Some(UserType::Worker),
+        country_codes: vec!["+61".to_string()],
+        active_within_days: Some(30),
+    };
+    assert!(audience.matches(&worker, Utc::now()));
+
+    // Inactive for longer than the window
+    assert!(!audience.matches(&worker, Utc::now() + Duration::days(31)));
+
+    let mut customer = worker.clone();
+    customer.set_user_type(UserType::Customer);
+    assert!(!audience.matches(&customer, Utc::now()));
+
+    let mut blocked = worker.clone();
+    blocked.block();
+    assert!(!CampaignAudience::default().matches(&blocked, Utc::now()));
+}
+
+#[test]
+fn test_delivery_open_only_counts_once_after_send() {
+    let mut delivery = CampaignDelivery::new(Uuid::new_v4(), Uuid::new_v4(), "+86".to_string());
+    assert!(!delivery.mark_opened());
+
+    delivery.mark_sent("msg-1".to_string());
+    assert_eq!(delivery.status, DeliveryStatus::Sent);
+    assert!(delivery.mark_opened());
+    assert!(!delivery.mark_opened());
+}
+
+#[test]
+fn test_stats_open_rate() {
+    let stats = CampaignStats {
+        pending: 1,
+        sent: 4,
+        failed: 1,
+        opted_out: 2,
+        opened: 1,
+    };
+
+    assert_eq!(stats.total(), 8);
+    assert_eq!(stats.open_rate(), 0.25);
+    assert_eq!(CampaignStats::default().open_rate(), 0.0);
+}
+
+#[test]
+fn test_delivery_claim() {
+    let mut delivery = CampaignDelivery::new(Uuid::new_v4(), Uuid::new_v4(), "+61".to_string());
+
+    assert!(delivery.claim());
+    assert_eq!(delivery.status, DeliveryStatus::Processing);
+    assert!(!delivery.claim());
+
+    delivery.release();
+    assert_eq!(delivery.status, DeliveryStatus::Pending);
+
+    delivery.mark_sent("msg-1".to_string());
+    delivery.release();
+    assert_eq!(delivery.status, DeliveryStatus::Sent);
+}
@@ -0,0 +1,64 @@
+//! Unit tests for order completion checklist entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::completion::{
+    ChecklistItemSpec, ChecklistTemplate, CompletedItem, CompletionChecklist,
+};
+
+fn spec(key: &str, min_photos: u32) -> ChecklistItemSpec {
+    ChecklistItemSpec { key: key.to_string(), label: format!("{} done", key), min_photos }
+}
+
+fn checklist() -> CompletionChecklist {
+    CompletionChecklist {
+        order_id: Uuid::new_v4(),
+        worker_id: Uuid::new_v4(),
+        customer_id: Uuid::new_v4(),
+        template_id: Uuid::new_v4(),
+        template_version: 1,
+        items: vec![CompletedItem {
+            key: "grout_sealed".to_string(),
+            label: "Grout sealed".to_string(),
+            photos: vec!["completion/1.jpg".to_string()],
+            note: None,
+        }],
+        submitted_at: Utc::now(),
+        sign_off: None,
+    }
+}
+
+#[test]
+fn test_template_normalizes_and_validates_items() {
+    let template = ChecklistTemplate::new(Some(" Tiling "), 1, vec![spec(" Grout_Sealed ", 2)], None).unwrap();
+    assert_eq!(template.category.as_deref(), Some("tiling"));
+    assert_eq!(template.items[0].key, "grout_sealed");
+
+    assert!(ChecklistTemplate::new(None, 1, vec![], None).is_err());
+    assert!(ChecklistTemplate::new(None, 1, vec![spec("a", 0), spec("A", 1)], None).is_err());
+    assert!(ChecklistTemplate::new(None, 1, vec![spec(" ", 0)], None).is_err());
+}
+
+#[test]
+fn test_sign_off_detects_later_changes() {
+    let mut checklist = checklist();
+    assert!(!checklist.matches_sign_off());
+
+    checklist.sign("Jane Citizen", Some("iPhone 15".to_string()), Utc::now()).unwrap();
+    assert!(checklist.matches_sign_off());
+    assert!(checklist.sign("Someone Else", None, Utc::now()).is_err());
+
+    checklist.items[0].photos.push("completion/2.jpg".to_string());
+    assert!(!checklist.matches_sign_off());
+}
+
+#[test]
+fn test_content_hash_separates_fields() {
+    let mut a = checklist();
+    let mut b = a.clone();
+    a.items[0].photos = vec!["ab".to_string(), "c".to_string()];
+    b.items[0].photos = vec!["a".to_string(), "bc".to_string()];
+
+    assert_ne!(a.content_hash(), b.content_hash());
+}
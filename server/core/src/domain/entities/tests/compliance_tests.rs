@@ -0,0 +1,59 @@
+//! Unit tests for market compliance entities
+
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::{
+    ComplianceAction, ComplianceCheck, ComplianceRequirement, ComplianceRule, ComplianceStatus,
+};
+use crate::domain::entities::user::UserType;
+
+#[test]
+fn test_rule_applies_to_listed_user_types() {
+    let everyone = ComplianceRule::new(ComplianceRequirement::RealName, vec![ComplianceAction::Order]);
+    assert!(everyone.applies_to(None));
+    assert!(everyone.applies_to(Some(UserType::Customer)));
+
+    let workers = ComplianceRule::new(ComplianceRequirement::IcpFiling, vec![ComplianceAction::Payout])
+        .for_user_types(vec![UserType::Worker]);
+    assert!(workers.applies_to(Some(UserType::Worker)));
+    assert!(!workers.applies_to(Some(UserType::Customer)));
+    assert!(!workers.applies_to(None));
+    assert!(workers.gates(ComplianceAction::Payout));
+    assert!(!workers.gates(ComplianceAction::Order));
+}
+
+#[test]
+fn test_check_keeps_only_last_four_document_characters() {
+    let mut check = ComplianceCheck::new(Uuid::new_v4(), ComplianceRequirement::RealName, "+86", "mock");
+    assert_eq!(check.status, ComplianceStatus::Required);
+
+    check.submit("req_1", "11010519491231002X");
+    assert_eq!(check.status, ComplianceStatus::Pending);
+    assert_eq!(check.document_last4.as_deref(), Some("002X"));
+    assert_eq!(check.attempts, 1);
+
+    check.reject("Name does not match");
+    assert_eq!(check.status, ComplianceStatus::Rejected);
+
+    check.submit("req_2", "123");
+    assert_eq!(check.document_last4.as_deref(), Some("123"));
+    assert!(check.rejection_reason.is_none());
+    check.verify();
+    assert!(check.is_verified());
+    assert!(check.verified_at.is_some());
+}
+
+#[test]
+fn test_requirement_and_status_round_trip() {
+    for requirement in [ComplianceRequirement::RealName, ComplianceRequirement::IcpFiling] {
+        assert_eq!(ComplianceRequirement::from_str(requirement.as_str()), Some(requirement));
+    }
+    for status in [
+        ComplianceStatus::Required,
+        ComplianceStatus::Pending,
+        ComplianceStatus::Verified,
+        ComplianceStatus::Rejected,
+    ] {
+        assert_eq!(ComplianceStatus::from_str(status.as_str()), Some(status));
+    }
+}
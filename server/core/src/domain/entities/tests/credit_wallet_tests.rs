@@ -0,0 +1,60 @@
+//! Unit tests for credit wallet entities
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::credit_wallet::{CreditGrant, CreditSource, CreditTransactionKind};
+
+#[test]
+fn test_new_grant_validates_amount_and_currency() {
+    let customer_id = Uuid::new_v4();
+
+    let grant = CreditGrant::new(customer_id, CreditSource::Refund, "re_1", 1_000, "aud", None).unwrap();
+    assert_eq!(grant.remaining, 1_000);
+    assert_eq!(grant.currency, "AUD");
+
+    assert!(CreditGrant::new(customer_id, CreditSource::Refund, "re_2", 0, "AUD", None).is_err());
+    assert!(CreditGrant::new(customer_id, CreditSource::Refund, "re_3", 100, "AU", None).is_err());
+}
+
+#[test]
+fn test_consume_never_takes_more_than_remaining() {
+    let mut grant =
+        CreditGrant::new(Uuid::new_v4(), CreditSource::Goodwill, "gw_1", 1_000, "AUD", None).unwrap();
+
+    assert_eq!(grant.consume(400), 400);
+    assert_eq!(grant.consume(900), 600);
+    assert_eq!(grant.remaining, 0);
+    assert!(!grant.is_spendable(Utc::now()));
+}
+
+#[test]
+fn test_expired_grant_is_not_spendable() {
+    let now = Utc::now();
+    let grant = CreditGrant::new(
+        Uuid::new_v4(),
+        CreditSource::Promotion,
+        "promo_1",
+        500,
+        "AUD",
+        Some(now + Duration::days(1)),
+    )
+    .unwrap();
+
+    assert!(grant.is_spendable(now));
+    assert!(!grant.is_spendable(now + Duration::days(1)));
+}
+
+#[test]
+fn test_kinds_round_trip_through_strings() {
+    for source in [CreditSource::Refund, CreditSource::Promotion, CreditSource::Goodwill] {
+        assert_eq!(CreditSource::from_str(source.as_str()), Some(source));
+    }
+    for kind in [
+        CreditTransactionKind::Issued,
+        CreditTransactionKind::Redeemed,
+        CreditTransactionKind::Expired,
+    ] {
+        assert_eq!(CreditTransactionKind::from_str(kind.as_str()), Some(kind));
+    }
+}
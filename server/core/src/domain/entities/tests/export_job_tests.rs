@@ -0,0 +1,49 @@
+//! Unit tests for export job entities
+
+use uuid::Uuid;
+
+use crate::domain::entities::export_job::{ExportJob, ExportKind, ExportStatus};
+
+#[test]
+fn test_kind_and_status_round_trip() {
+    for kind in [ExportKind::AuditLogs, ExportKind::AttributionCohorts] {
+        assert_eq!(ExportKind::from_str(kind.as_str()), Some(kind));
+    }
+    for status in [ExportStatus::Queued, ExportStatus::Running, ExportStatus::Completed, ExportStatus::Failed] {
+        assert_eq!(ExportStatus::from_str(status.as_str()), Some(status));
+    }
+    assert_eq!(ExportKind::from_str("users"), None);
+}
+
+#[test]
+fn test_new_job_fixes_end_of_range() {
+    let job = ExportJob::new(ExportKind::AuditLogs, Uuid::new_v4(), None, None);
+    assert_eq!(job.status, ExportStatus::Queued);
+    assert_eq!(job.to, job.created_at);
+    assert_eq!(job.progress_percent(), None);
+    assert!(!job.is_finished());
+}
+
+#[test]
+fn test_progress_through_completion() {
+    let mut job = ExportJob::new(ExportKind::AuditLogs, Uuid::new_v4(), None, None);
+    job.start();
+    assert_eq!(job.status, ExportStatus::Running);
+    assert_eq!(job.attempts, 1);
+
+    job.total_rows = Some(400);
+    job.record_progress(100);
+    assert_eq!(job.progress_percent(), Some(25));
+
+    job.retry("Connection reset");
+    assert_eq!(job.status, ExportStatus::Queued);
+    job.start();
+    assert_eq!(job.attempts, 2);
+    assert_eq!(job.rows_exported, 0);
+
+    job.total_rows = Some(0);
+    job.complete("exports/audit_logs/job.csv");
+    assert_eq!(job.progress_percent(), Some(100));
+    assert!(job.is_finished());
+    assert!(job.error.is_none());
+}
@@ -0,0 +1,54 @@
+//! Unit tests for fee schedule entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::fee_schedule::FeeSchedule;
+
+fn schedule(commission_basis_points: i64, minimum_fee: i64) -> FeeSchedule {
+    FeeSchedule::new("au", Some(" Plumbing "), 1, commission_basis_points, minimum_fee, "aud", Utc::now(), None)
+        .unwrap()
+}
+
+#[test]
+fn test_new_schedule_is_normalized() {
+    let schedule = schedule(1_000, 0);
+
+    assert_eq!(schedule.market, "AU");
+    assert_eq!(schedule.category.as_deref(), Some("plumbing"));
+    assert_eq!(schedule.currency, "AUD");
+}
+
+#[test]
+fn test_new_schedule_rejects_invalid_values() {
+    let now = Utc::now();
+
+    assert!(FeeSchedule::new("AU", None, 1, 10_001, 0, "AUD", now, None).is_err());
+    assert!(FeeSchedule::new("AU", None, 1, -1, 0, "AUD", now, None).is_err());
+    assert!(FeeSchedule::new("AU", None, 1, 1_000, -1, "AUD", now, None).is_err());
+    assert!(FeeSchedule::new("AU", None, 1, 1_000, 0, "AUDD", now, None).is_err());
+}
+
+#[test]
+fn test_fee_applies_the_minimum_but_never_exceeds_the_amount() {
+    let schedule = schedule(1_000, 500);
+
+    assert_eq!(schedule.fee_for(20_000), 2_000);
+    assert_eq!(schedule.fee_for(3_000), 500);
+    assert_eq!(schedule.fee_for(300), 300);
+}
+
+#[test]
+fn test_apply_records_the_schedule_version_on_the_order() {
+    let schedule = schedule(1_500, 0);
+    let order_id = Uuid::new_v4();
+    let accepted_at = Utc::now();
+
+    let fee = schedule.apply(order_id, 10_000, accepted_at);
+
+    assert_eq!(fee.order_id, order_id);
+    assert_eq!(fee.schedule_id, schedule.id);
+    assert_eq!(fee.schedule_version, 1);
+    assert_eq!(fee.fee_amount, 1_500);
+    assert_eq!(fee.accepted_at, accepted_at);
+}
@@ -0,0 +1,134 @@
+//! Unit tests for double-entry journal entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::journal::{
+    AccountBalance, AccountKey, AccountType, JournalEntry, JournalLine, JournalTransactionType,
+    LedgerVerification,
+};
+
+fn cash(currency: &str) -> AccountKey {
+    AccountKey::platform(AccountType::ProviderCash, currency)
+}
+
+#[test]
+fn test_balanced_entry_is_accepted() {
+    let worker_id = Uuid::new_v4();
+    let entry = JournalEntry::new(
+        JournalTransactionType::Tip,
+        "tip_1",
+        vec![
+            JournalLine::debit(cash("aud"), 500),
+            JournalLine::credit(AccountKey::worker_payable(worker_id, "AUD"), 500),
+        ],
+        Utc::now(),
+    )
+    .unwrap();
+
+    assert_eq!(entry.lines[0].account.currency, "AUD");
+    assert_eq!(entry.totals_by_currency()["AUD"], (500, 500));
+}
+
+#[test]
+fn test_unbalanced_entry_is_rejected() {
+    let result = JournalEntry::new(
+        JournalTransactionType::Fee,
+        "fee_1",
+        vec![
+            JournalLine::debit(AccountKey::platform(AccountType::ProcessingFees, "AUD"), 300),
+            JournalLine::credit(cash("AUD"), 299),
+        ],
+        Utc::now(),
+    );
+
+    assert!(result.unwrap_err().contains("unbalanced"));
+}
+
+#[test]
+fn test_entry_must_balance_per_currency() {
+    let result = JournalEntry::new(
+        JournalTransactionType::Fee,
+        "fee_1",
+        vec![
+            JournalLine::debit(AccountKey::platform(AccountType::ProcessingFees, "AUD"), 300),
+            JournalLine::credit(cash("CNY"), 300),
+        ],
+        Utc::now(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_zero_lines_are_dropped_and_negative_lines_rejected() {
+    let worker_id = Uuid::new_v4();
+    let entry = JournalEntry::new(
+        JournalTransactionType::Payment,
+        "pay_1",
+        vec![
+            JournalLine::debit(cash("AUD"), 1_000),
+            JournalLine::credit(AccountKey::worker_payable(worker_id, "AUD"), 1_000),
+            JournalLine::credit(AccountKey::platform(AccountType::PlatformRevenue, "AUD"), 0),
+        ],
+        Utc::now(),
+    )
+    .unwrap();
+    assert_eq!(entry.lines.len(), 2);
+
+    let result = JournalEntry::new(
+        JournalTransactionType::Payment,
+        "pay_2",
+        vec![
+            JournalLine::debit(cash("AUD"), -1_000),
+            JournalLine::credit(AccountKey::worker_payable(worker_id, "AUD"), -1_000),
+        ],
+        Utc::now(),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_owner_accounts_require_an_owner() {
+    let mut unowned = AccountKey::worker_payable(Uuid::new_v4(), "AUD");
+    unowned.owner_id = None;
+
+    let result = JournalEntry::new(
+        JournalTransactionType::Tip,
+        "tip_1",
+        vec![JournalLine::debit(cash("AUD"), 500), JournalLine::credit(unowned, 500)],
+        Utc::now(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_account_balance_follows_normal_side() {
+    let cash = AccountBalance { account: cash("AUD"), debits: 1_000, credits: 300 };
+    let payable = AccountBalance {
+        account: AccountKey::worker_payable(Uuid::new_v4(), "AUD"),
+        debits: 300,
+        credits: 1_000,
+    };
+
+    assert_eq!(cash.balance(), 700);
+    assert_eq!(payable.balance(), 700);
+}
+
+#[test]
+fn test_verification_from_trial_balance() {
+    let balances = vec![
+        AccountBalance { account: cash("AUD"), debits: 1_000, credits: 0 },
+        AccountBalance {
+            account: AccountKey::worker_payable(Uuid::new_v4(), "AUD"),
+            debits: 0,
+            credits: 900,
+        },
+    ];
+
+    let verification = LedgerVerification::from_trial_balance(&balances, Vec::new());
+
+    assert!(!verification.is_balanced());
+    assert_eq!(verification.totals["AUD"], (1_000, 900));
+}
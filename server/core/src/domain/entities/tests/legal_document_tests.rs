@@ -0,0 +1,54 @@
+//! Unit tests for legal document entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::legal_document::{
+    normalize_language, LegalAcceptance, LegalDocument, LegalDocumentKind,
+};
+
+fn document(market: Option<&str>, version: i32) -> LegalDocument {
+    LegalDocument {
+        id: Uuid::new_v4(),
+        kind: LegalDocumentKind::Terms,
+        market: market.map(str::to_string),
+        version,
+        language: "en".to_string(),
+        title: "Terms of Service".to_string(),
+        body: "# Terms".to_string(),
+        effective_from: Utc::now(),
+        created_by: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_normalize_language() {
+    assert_eq!(normalize_language("zh-CN").as_deref(), Some("zh"));
+    assert_eq!(normalize_language(" EN_au ").as_deref(), Some("en"));
+    assert_eq!(normalize_language("*"), None);
+    assert_eq!(normalize_language("english"), None);
+}
+
+#[test]
+fn test_acceptance_covers_every_translation_of_the_version() {
+    let accepted = document(Some("AU"), 2);
+    let acceptance = LegalAcceptance::new(Uuid::new_v4(), &accepted);
+
+    let translation = LegalDocument {
+        id: Uuid::new_v4(),
+        language: "zh".to_string(),
+        ..accepted.clone()
+    };
+    assert!(acceptance.covers(&translation));
+    assert!(!acceptance.covers(&document(Some("AU"), 3)));
+    assert!(!acceptance.covers(&document(None, 2)));
+}
+
+#[test]
+fn test_kind_round_trips() {
+    for kind in LegalDocumentKind::ALL {
+        assert_eq!(LegalDocumentKind::from_str(kind.as_str()), Some(kind));
+    }
+    assert_eq!(LegalDocumentKind::from_str("cookies"), None);
+}
@@ -1,12 +1,56 @@
 //! Tests for domain entities
 
+#[cfg(test)]
+pub mod admin_tests;
+#[cfg(test)]
+pub mod attribution_tests;
 #[cfg(test)]
 pub mod audit_tests;
 #[cfg(test)]
 pub mod audit_enhanced_tests;
 #[cfg(test)]
+pub mod calendar_tests;
+#[cfg(test)]
+pub mod campaign_tests;
+#[cfg(test)]
+pub mod completion_tests;
+#[cfg(test)]
+pub mod compliance_tests;
+#[cfg(test)]
+pub mod credit_wallet_tests;
+#[cfg(test)]
+pub mod export_job_tests;
+#[cfg(test)]
+pub mod fee_schedule_tests;
+#[cfg(test)]
+pub mod journal_tests;
+#[cfg(test)]
+pub mod legal_document_tests;
+#[cfg(test)]
+pub mod notification_tests;
+#[cfg(test)]
+pub mod oauth_tests;
+#[cfg(test)]
+pub mod organization_tests;
+#[cfg(test)]
+pub mod payment_method_tests;
+#[cfg(test)]
+pub mod payment_risk_tests;
+#[cfg(test)]
+pub mod payment_tests;
+#[cfg(test)]
+pub mod payout_method_tests;
+#[cfg(test)]
+pub mod reconciliation_tests;
+#[cfg(test)]
+pub mod service_area_tests;
+#[cfg(test)]
+pub mod sms_delivery_tests;
+#[cfg(test)]
 pub mod token_tests;
 #[cfg(test)]
 pub mod user_tests;
 #[cfg(test)]
-pub mod verification_code_tests;
\ No newline at end of file
+pub mod verification_code_tests;
+#[cfg(test)]
+pub mod webhook_dead_letter_tests;
\ No newline at end of file
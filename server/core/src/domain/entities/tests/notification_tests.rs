@@ -0,0 +1,152 @@
+//! Unit tests for notification entities
+
+use chrono::{NaiveTime, TimeZone, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::notification::{
+    MessageTemplate, NotificationCategory, NotificationPreferences, ScheduledMessage,
+    ScheduledMessageStatus, MAX_DELIVERY_ATTEMPTS,
+};
+
+fn time(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+#[test]
+fn test_template_render() {
+    let mut params = HashMap::new();
+    params.insert("worker_name".to_string(), "Alex".to_string());
+    params.insert("arrival_time".to_string(), "9:00 AM".to_string());
+
+    let message = MessageTemplate::WorkerArrivalReminder.render(&params).unwrap();
+    assert_eq!(
+        message,
+        "RenovEasy reminder: Alex is scheduled to arrive at 9:00 AM."
+    );
+}
+
+#[test]
+fn test_template_render_missing_param() {
+    let params = HashMap::new();
+    let result = MessageTemplate::ReviewRequest.render(&params);
+    assert_eq!(result, Err("worker_name".to_string()));
+}
+
+#[test]
+fn test_template_string_round_trip() {
+    for template in [
+        MessageTemplate::WorkerArrivalReminder,
+        MessageTemplate::ReviewRequest,
+        MessageTemplate::Custom,
+    ] {
+        assert_eq!(MessageTemplate::from_str(template.as_str()), Some(template));
+    }
+    assert_eq!(MessageTemplate::from_str("unknown"), None);
+}
+
+#[test]
+fn test_preferences_accept_critical_categories_after_opt_out() {
+    let mut prefs = NotificationPreferences::new(Uuid::new_v4());
+    prefs.reminders_enabled = false;
+    prefs.marketing_enabled = false;
+
+    assert!(prefs.accepts(NotificationCategory::Otp));
+    assert!(prefs.accepts(NotificationCategory::SecurityAlert));
+    assert!(!prefs.accepts(NotificationCategory::Reminder));
+    assert!(!prefs.accepts(NotificationCategory::Marketing));
+    assert!(!prefs.accepts(NotificationCategory::Digest));
+}
+
+#[test]
+fn test_quiet_hours_same_day_window() {
+    let prefs = NotificationPreferences::new(Uuid::new_v4()).with_quiet_hours(time(12, 0), time(14, 0));
+
+    assert!(prefs.is_quiet_at(Utc.with_ymd_and_hms(2025, 9, 1, 13, 0, 0).unwrap()));
+    assert!(!prefs.is_quiet_at(Utc.with_ymd_and_hms(2025, 9, 1, 14, 0, 0).unwrap()));
+    assert!(!prefs.is_quiet_at(Utc.with_ymd_and_hms(2025, 9, 1, 11, 59, 0).unwrap()));
+}
+
+#[test]
+fn test_quiet_hours_overnight_window_with_offset() {
+    // 22:00-07:00 local time in UTC+10
+    let prefs = NotificationPreferences::new(Uuid::new_v4())
+        .with_quiet_hours(time(22, 0), time(7, 0))
+        .with_utc_offset_minutes(600);
+
+    // 13:00 UTC is 23:00 local
+    let at = Utc.with_ymd_and_hms(2025, 9, 1, 13, 0, 0).unwrap();
+    assert!(prefs.is_quiet_at(at));
+
+    // Next allowed time is 07:00 local on the following day, i.e. 21:00 UTC
+    let next = prefs.next_allowed_time(at);
+    assert_eq!(next, Utc.with_ymd_and_hms(2025, 9, 1, 21, 0, 0).unwrap());
+    assert!(!prefs.is_quiet_at(next));
+}
+
+#[test]
+fn test_next_allowed_time_outside_quiet_hours() {
+    let prefs = NotificationPreferences::new(Uuid::new_v4()).with_quiet_hours(time(22, 0), time(7, 0));
+    let at = Utc.with_ymd_and_hms(2025, 9, 1, 9, 0, 0).unwrap();
+    assert_eq!(prefs.next_allowed_time(at), at);
+}
+
+#[test]
+fn test_scheduled_message_lifecycle() {
+    let at = Utc.with_ymd_and_hms(2025, 9, 1, 9, 0, 0).unwrap();
+    let mut message = ScheduledMessage::new(
+        Uuid::new_v4(),
+        "+61412345678".to_string(),
+        MessageTemplate::ReviewRequest,
+        HashMap::new(),
+        at,
+    );
+
+    assert_eq!(message.status, ScheduledMessageStatus::Pending);
+    assert!(message.is_due(at));
+    assert!(!message.is_due(at - chrono::Duration::minutes(1)));
+
+    message.mark_sent("msg_1".to_string());
+    assert_eq!(message.status, ScheduledMessageStatus::Sent);
+    assert!(!message.cancel());
+}
+
+#[test]
+fn test_scheduled_message_claim() {
+    let mut message = ScheduledMessage::new(
+        Uuid::new_v4(),
+        "+61412345678".to_string(),
+        MessageTemplate::ReviewRequest,
+        HashMap::new(),
+        Utc::now(),
+    );
+
+    assert!(message.claim());
+    assert_eq!(message.status, ScheduledMessageStatus::Processing);
+    assert!(!message.claim());
+    assert!(!message.is_due(Utc::now()));
+    // Users cannot cancel a message that is being delivered
+    assert!(!message.cancel());
+
+    message.release();
+    assert_eq!(message.status, ScheduledMessageStatus::Pending);
+}
+
+#[test]
+fn test_scheduled_message_fails_after_max_attempts() {
+    let mut message = ScheduledMessage::new(
+        Uuid::new_v4(),
+        "+61412345678".to_string(),
+        MessageTemplate::ReviewRequest,
+        HashMap::new(),
+        Utc::now(),
+    );
+
+    for _ in 0..MAX_DELIVERY_ATTEMPTS - 1 {
+        message.record_failure("provider error".to_string());
+        assert_eq!(message.status, ScheduledMessageStatus::Pending);
+    }
+    message.record_failure("provider error".to_string());
+    assert_eq!(message.status, ScheduledMessageStatus::Failed);
+    assert_eq!(message.last_error.as_deref(), Some("provider error"));
+}
@@ -0,0 +1,113 @@
+//! Unit tests for OAuth entities
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{
+    format_scope, parse_scope, OAuthAuthorizationCode, OAuthClient, OAuthConsent, OAuthGrantType,
+};
+use crate::domain::entities::token::{Claims, JWT_PARTNER_AUDIENCE};
+
+fn scopes(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_grant_type_round_trip() {
+    for grant in [OAuthGrantType::ClientCredentials, OAuthGrantType::AuthorizationCode] {
+        assert_eq!(OAuthGrantType::from_str(grant.as_str()), Some(grant));
+    }
+    assert_eq!(OAuthGrantType::from_str("password"), None);
+}
+
+#[test]
+fn test_scope_parsing_drops_duplicates() {
+    let parsed = parse_scope("  profile:read orders:read profile:read ");
+
+    assert_eq!(parsed, scopes(&["profile:read", "orders:read"]));
+    assert_eq!(format_scope(&parsed), "profile:read orders:read");
+    assert!(parse_scope(" ").is_empty());
+}
+
+#[test]
+fn test_client_requires_redirect_uri_for_code_grant() {
+    let result = OAuthClient::new(
+        "rec_1",
+        "hash",
+        "Supplier",
+        Vec::new(),
+        scopes(&["profile:read"]),
+        vec![OAuthGrantType::AuthorizationCode],
+    );
+    assert!(result.is_err());
+
+    let client = OAuthClient::new(
+        "rec_1",
+        "hash",
+        "Supplier",
+        Vec::new(),
+        scopes(&["profile:read"]),
+        vec![OAuthGrantType::ClientCredentials],
+    )
+    .unwrap();
+    assert!(client.allows_grant(OAuthGrantType::ClientCredentials));
+    assert!(!client.allows_grant(OAuthGrantType::AuthorizationCode));
+    assert!(client.allows_scopes(&scopes(&["profile:read"])));
+    assert!(!client.allows_scopes(&scopes(&["profile:read", "orders:read"])));
+}
+
+#[test]
+fn test_consent_extension_keeps_earlier_scopes() {
+    let mut consent = OAuthConsent::new(Uuid::new_v4(), "rec_1", scopes(&["profile:read"]));
+    assert!(!consent.covers(&scopes(&["orders:read"])));
+
+    consent.extend(&scopes(&["orders:read", "profile:read"]));
+
+    assert_eq!(consent.scopes, scopes(&["profile:read", "orders:read"]));
+    assert!(consent.covers(&scopes(&["orders:read", "profile:read"])));
+}
+
+#[test]
+fn test_authorization_code_pkce_verification() {
+    // Example from RFC 7636, appendix B
+    let code = OAuthAuthorizationCode::new(
+        "hash",
+        "rec_1",
+        Uuid::new_v4(),
+        "https://supplier.example.com/callback",
+        scopes(&["profile:read"]),
+        Some("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM".to_string()),
+        600,
+    );
+
+    assert!(code.is_valid());
+    assert!(code.verifies(Some("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")));
+    assert!(!code.verifies(Some("wrong")));
+    assert!(!code.verifies(None));
+
+    let mut plain = OAuthAuthorizationCode { code_challenge: None, ..code };
+    assert!(plain.verifies(None));
+    assert!(!plain.verifies(Some("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")));
+
+    plain.expires_at = Utc::now() - Duration::seconds(1);
+    assert!(!plain.is_valid());
+}
+
+#[test]
+fn test_partner_token_claims() {
+    let claims = Claims::new_partner_token(
+        "rec_1".to_string(),
+        "rec_1".to_string(),
+        &scopes(&["profile:read", "orders:read"]),
+        3600,
+    );
+
+    assert_eq!(claims.aud, JWT_PARTNER_AUDIENCE);
+    assert!(claims.has_scope("orders:read"));
+    assert!(!claims.has_scope("orders"));
+    assert_eq!(claims.exp - claims.iat, 3600);
+
+    let user_claims = Claims::new_access_token(Uuid::new_v4(), None, true, None, None);
+    assert!(!user_claims.has_scope("orders:read"));
+    assert!(!serde_json::to_string(&user_claims).unwrap().contains("client_id"));
+}
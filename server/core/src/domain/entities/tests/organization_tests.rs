@@ -0,0 +1,63 @@
+//! Unit tests for organization billing entities
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::organization::{
+    month_bounds, ConsolidatedInvoice, Organization, OrganizationCharge,
+};
+
+fn charge(organization: &Organization, user_id: Uuid, amount: i64, completed_day: Option<u32>) -> OrganizationCharge {
+    OrganizationCharge {
+        id: Uuid::new_v4(),
+        organization_id: organization.id,
+        user_id,
+        order_id: Uuid::new_v4(),
+        amount,
+        currency: organization.currency.clone(),
+        accepted_at: Utc.with_ymd_and_hms(2026, 9, 1, 9, 0, 0).unwrap(),
+        completed_at: completed_day.map(|day| Utc.with_ymd_and_hms(2026, 9, day, 9, 0, 0).unwrap()),
+        invoice_id: None,
+    }
+}
+
+#[test]
+fn test_new_organization_validates_input() {
+    assert!(Organization::new(" ", "billing@acme.example", "AUD").is_err());
+    assert!(Organization::new("Acme", "not-an-email", "AUD").is_err());
+    assert!(Organization::new("Acme", "billing@acme.example", "dollars").is_err());
+
+    let organization = Organization::new(" Acme ", "Billing@Acme.example", "aud").unwrap();
+    assert_eq!(organization.name, "Acme");
+    assert_eq!(organization.billing_email, "billing@acme.example");
+    assert_eq!(organization.currency, "AUD");
+}
+
+#[test]
+fn test_invoice_lists_completed_charges_in_completion_order() {
+    let organization = Organization::new("Acme", "billing@acme.example", "AUD").unwrap();
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    let charges = vec![
+        charge(&organization, alice, 3_000, Some(20)),
+        charge(&organization, bob, 5_000, Some(5)),
+        charge(&organization, alice, 2_000, Some(12)),
+        charge(&organization, bob, 9_000, None),
+    ];
+
+    let invoice = ConsolidatedInvoice::new(&organization, NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(), &charges, None);
+
+    assert_eq!(invoice.lines.len(), 3);
+    assert_eq!(invoice.lines[0].amount, 5_000);
+    assert_eq!(invoice.total_amount, 10_000);
+    let totals = invoice.member_totals();
+    assert_eq!(totals[&alice], 5_000);
+    assert_eq!(totals[&bob], 5_000);
+}
+
+#[test]
+fn test_month_bounds() {
+    let (start, end) = month_bounds(Utc.with_ymd_and_hms(2026, 12, 31, 23, 59, 59).unwrap());
+    assert_eq!(start, Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap());
+    assert_eq!(end, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+}
@@ -0,0 +1,33 @@
+//! Unit tests for saved payment method entities
+
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::payment_method::{looks_like_card_number, SavedPaymentMethod};
+
+#[test]
+fn test_new_payment_method_validates_card_details() {
+    let method = SavedPaymentMethod::new(Uuid::new_v4(), "stripe", "pm_1", "Visa", "4242", 12, 2030).unwrap();
+    assert_eq!(method.brand, "visa");
+    assert!(!method.is_default);
+
+    assert!(SavedPaymentMethod::new(Uuid::new_v4(), "stripe", " ", "visa", "4242", 12, 2030).is_err());
+    assert!(SavedPaymentMethod::new(Uuid::new_v4(), "stripe", "pm_1", "visa", "424", 12, 2030).is_err());
+    assert!(SavedPaymentMethod::new(Uuid::new_v4(), "stripe", "pm_1", "visa", "4242", 13, 2030).is_err());
+}
+
+#[test]
+fn test_card_is_valid_until_end_of_expiry_month() {
+    let method = SavedPaymentMethod::new(Uuid::new_v4(), "stripe", "pm_1", "visa", "4242", 10, 2026).unwrap();
+
+    assert!(!method.is_expired(Utc.with_ymd_and_hms(2026, 10, 31, 23, 0, 0).unwrap()));
+    assert!(method.is_expired(Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap()));
+}
+
+#[test]
+fn test_card_numbers_are_recognised() {
+    assert!(looks_like_card_number("4242 4242 4242 4242"));
+    assert!(looks_like_card_number("4242-4242-4242-4242"));
+    assert!(!looks_like_card_number("pm_1Q2w3E4r5T6y"));
+    assert!(!looks_like_card_number("4242"));
+}
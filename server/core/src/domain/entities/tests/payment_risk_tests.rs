@@ -0,0 +1,70 @@
+//! Unit tests for payment risk entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::payment_risk::{
+    PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction,
+};
+
+fn assessment(action: RiskAction, review_status: ReviewStatus) -> PaymentRiskAssessment {
+    PaymentRiskAssessment {
+        id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        amount: 10_000,
+        currency: "AUD".to_string(),
+        market: "AU".to_string(),
+        billing_country: None,
+        card_fingerprint: None,
+        triggered_rules: Vec::new(),
+        action,
+        review_status,
+        reviewed_by: None,
+        reviewed_at: None,
+        review_note: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_actions_are_ordered_by_severity() {
+    assert!(RiskAction::Allow < RiskAction::RequireThreeDs);
+    assert!(RiskAction::RequireThreeDs < RiskAction::Review);
+    assert!(RiskAction::Review < RiskAction::Block);
+}
+
+#[test]
+fn test_only_pending_reviews_can_be_decided() {
+    let reviewer = Uuid::new_v4();
+    let mut pending = assessment(RiskAction::Review, ReviewStatus::Pending);
+
+    pending.decide(false, reviewer, Some("Stolen card".to_string())).unwrap();
+    assert_eq!(pending.review_status, ReviewStatus::Rejected);
+    assert!(!pending.permits_payment());
+    assert!(pending.decide(true, reviewer, None).is_err());
+
+    let mut allowed = assessment(RiskAction::Allow, ReviewStatus::NotRequired);
+    assert!(allowed.decide(true, reviewer, None).is_err());
+}
+
+#[test]
+fn test_values_round_trip_through_strings() {
+    for rule in [
+        PaymentRiskRule::NewAccountHighAmount,
+        PaymentRiskRule::TooManyCards,
+        PaymentRiskRule::BillingCountryMismatch,
+    ] {
+        assert_eq!(PaymentRiskRule::from_str(rule.as_str()), Some(rule));
+    }
+    for action in [RiskAction::Allow, RiskAction::RequireThreeDs, RiskAction::Review, RiskAction::Block] {
+        assert_eq!(RiskAction::from_str(action.as_str()), Some(action));
+    }
+    for status in [
+        ReviewStatus::NotRequired,
+        ReviewStatus::Pending,
+        ReviewStatus::Approved,
+        ReviewStatus::Rejected,
+    ] {
+        assert_eq!(ReviewStatus::from_str(status.as_str()), Some(status));
+    }
+}
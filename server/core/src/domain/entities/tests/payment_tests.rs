@@ -0,0 +1,165 @@
+//! Unit tests for payment, payout and dispute entities
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::entities::dispute::{Dispute, DisputeStatus};
+use crate::domain::entities::payment::{Payment, PaymentStatus, Payout, PayoutStatus};
+
+fn payment() -> Payment {
+    Payment::new("stripe", "ch_1", Uuid::new_v4(), Uuid::new_v4(), 10_000, "aud").unwrap()
+}
+
+#[test]
+fn test_new_payment_validates_input() {
+    let payment = payment();
+    assert_eq!(payment.currency, "AUD");
+    assert_eq!(payment.status, PaymentStatus::Succeeded);
+    assert_eq!(payment.remaining_amount(), 10_000);
+
+    assert!(Payment::new("stripe", " ", Uuid::new_v4(), Uuid::new_v4(), 100, "AUD").is_err());
+    assert!(Payment::new("stripe", "ch_1", Uuid::new_v4(), Uuid::new_v4(), 0, "AUD").is_err());
+    assert!(Payment::new("stripe", "ch_1", Uuid::new_v4(), Uuid::new_v4(), 100, "AU").is_err());
+}
+
+#[test]
+fn test_apply_refund_tracks_partial_and_full_refunds() {
+    let mut payment = payment();
+
+    payment.apply_refund(4_000).unwrap();
+    assert_eq!(payment.status, PaymentStatus::PartiallyRefunded);
+
+    assert!(payment.apply_refund(6_001).is_err());
+    assert!(payment.apply_refund(0).is_err());
+
+    payment.apply_refund(6_000).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Refunded);
+    assert_eq!(payment.remaining_amount(), 0);
+}
+
+#[test]
+fn test_refund_keeps_disputed_status_until_cleared() {
+    let mut payment = payment();
+    payment.mark_disputed();
+
+    payment.apply_refund(1_000).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Disputed);
+
+    payment.clear_dispute();
+    assert_eq!(payment.status, PaymentStatus::PartiallyRefunded);
+}
+
+#[test]
+fn test_authenticated_payment_is_captured_under_its_charge() {
+    let mut payment =
+        Payment::awaiting_authentication("stripe", "pi_1", Uuid::new_v4(), Uuid::new_v4(), 10_000, "AUD").unwrap();
+    assert_eq!(payment.status, PaymentStatus::RequiresAction);
+    assert_eq!(payment.provider_reference, "pi_1");
+    assert_eq!(payment.intent_reference.as_deref(), Some("pi_1"));
+    assert!(!payment.is_captured());
+    assert!(payment.apply_refund(1_000).is_err());
+
+    payment.confirm(Some("ch_1".to_string())).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Succeeded);
+    assert_eq!(payment.provider_reference, "ch_1");
+    assert_eq!(payment.intent_reference.as_deref(), Some("pi_1"));
+    assert!(payment.confirm(None).is_err());
+}
+
+#[test]
+fn test_failed_authentication_can_be_retried() {
+    let mut payment =
+        Payment::awaiting_authentication("stripe", "pi_1", Uuid::new_v4(), Uuid::new_v4(), 10_000, "AUD").unwrap();
+
+    payment.mark_failed().unwrap();
+    assert_eq!(payment.status, PaymentStatus::Failed);
+    assert!(payment.mark_failed().is_err());
+
+    payment.confirm(None).unwrap();
+    assert_eq!(payment.status, PaymentStatus::Succeeded);
+    assert_eq!(payment.provider_reference, "pi_1");
+}
+
+#[test]
+fn test_only_pending_payouts_can_be_frozen() {
+    let payment = payment();
+    let mut payout = Payout::new(&payment, 8_500);
+    assert_eq!(payout.worker_id, payment.worker_id);
+    assert_eq!(payout.currency, "AUD");
+
+    assert!(payout.freeze("Chargeback dp_1"));
+    assert_eq!(payout.status, PayoutStatus::Frozen);
+    assert!(!payout.freeze("Refund re_1"));
+    assert_eq!(payout.frozen_reason.as_deref(), Some("Chargeback dp_1"));
+
+    let mut paid = Payout::new(&payment, 8_500);
+    paid.status = PayoutStatus::Paid;
+    assert!(!paid.freeze("Chargeback dp_1"));
+}
+
+#[test]
+fn test_only_pending_payouts_can_be_paid() {
+    let payment = payment();
+    let mut payout = Payout::new(&payment, 8_500);
+
+    payout.mark_paid("tr_1").unwrap();
+    assert_eq!(payout.status, PayoutStatus::Paid);
+    assert_eq!(payout.transfer_reference.as_deref(), Some("tr_1"));
+    assert!(payout.mark_paid("tr_2").is_err());
+
+    let mut frozen = Payout::new(&payment, 8_500);
+    frozen.freeze("Chargeback dp_1");
+    assert!(frozen.mark_paid("tr_3").is_err());
+}
+
+#[test]
+fn test_dispute_resolves_once() {
+    let payment = payment();
+    let mut dispute = Dispute::open(&payment, "dp_1", "fraudulent", 10_000, Utc::now());
+    assert!(dispute.is_open());
+    assert_eq!(dispute.currency, "AUD");
+
+    assert!(dispute.resolve(false, Utc::now()));
+    assert_eq!(dispute.status, DisputeStatus::Lost);
+    assert!(dispute.resolved_at.is_some());
+    assert!(!dispute.resolve(true, Utc::now()));
+    assert_eq!(dispute.status, DisputeStatus::Lost);
+}
+
+#[test]
+fn test_statuses_round_trip_through_strings() {
+    for status in [
+        PaymentStatus::RequiresAction,
+        PaymentStatus::Failed,
+        PaymentStatus::Succeeded,
+        PaymentStatus::PartiallyRefunded,
+        PaymentStatus::Refunded,
+        PaymentStatus::Disputed,
+    ] {
+        assert_eq!(PaymentStatus::from_str(status.as_str()), Some(status));
+    }
+    for status in [PayoutStatus::Pending, PayoutStatus::Frozen, PayoutStatus::Paid] {
+        assert_eq!(PayoutStatus::from_str(status.as_str()), Some(status));
+    }
+    for status in [DisputeStatus::Open, DisputeStatus::Won, DisputeStatus::Lost] {
+        assert_eq!(DisputeStatus::from_str(status.as_str()), Some(status));
+    }
+}
+
+#[test]
+fn test_escrow_is_held_until_refund_or_payout() {
+    let mut payment = payment();
+    assert!(payment.holds_escrow(&[]));
+
+    let mut payout = Payout::new(&payment, 9_000);
+    assert!(payment.holds_escrow(std::slice::from_ref(&payout)));
+    payout.status = PayoutStatus::Paid;
+    assert!(!payment.holds_escrow(std::slice::from_ref(&payout)));
+
+    payment.mark_disputed();
+    assert!(payment.holds_escrow(&[payout]));
+
+    let mut refunded = self::payment();
+    refunded.apply_refund(10_000).unwrap();
+    assert!(!refunded.holds_escrow(&[]));
+}
@@ -0,0 +1,40 @@
+//! Unit tests for payout method entities
+
+use uuid::Uuid;
+
+use crate::domain::entities::payout_method::{PayoutMethod, PayoutMethodStatus};
+
+#[test]
+fn test_new_payout_method_awaits_verification() {
+    let method = PayoutMethod::new(Uuid::new_v4(), "stripe", "ba_1", Some("ANZ".to_string()), "6789", "aud").unwrap();
+    assert_eq!(method.status, PayoutMethodStatus::PendingVerification);
+    assert_eq!(method.currency, "AUD");
+    assert!(!method.is_verified());
+
+    assert!(PayoutMethod::new(Uuid::new_v4(), "stripe", "", None, "6789", "AUD").is_err());
+    assert!(PayoutMethod::new(Uuid::new_v4(), "stripe", "ba_1", None, "67a9", "AUD").is_err());
+    assert!(PayoutMethod::new(Uuid::new_v4(), "stripe", "ba_1", None, "6789", "AU").is_err());
+}
+
+#[test]
+fn test_micro_deposit_attempts_are_limited() {
+    let mut method = PayoutMethod::new(Uuid::new_v4(), "stripe", "ba_1", None, "6789", "AUD").unwrap();
+    method.await_micro_deposits();
+
+    assert!(!method.record_failed_attempt(2));
+    assert_eq!(method.status, PayoutMethodStatus::AwaitingMicroDeposits);
+    assert!(method.record_failed_attempt(2));
+    assert_eq!(method.status, PayoutMethodStatus::VerificationFailed);
+}
+
+#[test]
+fn test_status_round_trips() {
+    for status in [
+        PayoutMethodStatus::PendingVerification,
+        PayoutMethodStatus::AwaitingMicroDeposits,
+        PayoutMethodStatus::Verified,
+        PayoutMethodStatus::VerificationFailed,
+    ] {
+        assert_eq!(PayoutMethodStatus::from_str(status.as_str()), Some(status));
+    }
+}
@@ -0,0 +1,144 @@
+//! Unit tests for payment ledger and reconciliation entities
+
+use chrono::{Duration, Utc};
+
+use crate::domain::entities::payment::{LedgerEntry, LedgerEntryKind};
+use crate::domain::entities::reconciliation::{
+    DiscrepancyKind, ProviderTransaction, ReconciliationReport,
+};
+
+fn ledger(reference: &str, kind: LedgerEntryKind, amount: i64, currency: &str) -> LedgerEntry {
+    LedgerEntry::new("stripe", reference, kind, amount, currency, Utc::now()).unwrap()
+}
+
+fn transaction(reference: &str, kind: LedgerEntryKind, amount: i64, currency: &str) -> ProviderTransaction {
+    ProviderTransaction {
+        reference: reference.to_string(),
+        kind,
+        amount,
+        currency: currency.to_string(),
+        occurred_at: Utc::now(),
+    }
+}
+
+fn reconcile(ledger: &[LedgerEntry], transactions: &[ProviderTransaction]) -> ReconciliationReport {
+    let end = Utc::now();
+    ReconciliationReport::reconcile("stripe", end - Duration::days(1), end, ledger, transactions)
+}
+
+#[test]
+fn test_ledger_entry_validates_reference_and_currency() {
+    let now = Utc::now();
+
+    let entry = LedgerEntry::new("stripe", "txn_1", LedgerEntryKind::Charge, 5000, "aud", now).unwrap();
+    assert_eq!(entry.currency, "AUD");
+
+    assert!(LedgerEntry::new("stripe", " ", LedgerEntryKind::Charge, 5000, "AUD", now).is_err());
+    assert!(LedgerEntry::new("stripe", "txn_1", LedgerEntryKind::Charge, 5000, "AU", now).is_err());
+    assert!(LedgerEntry::new("stripe", "txn_1", LedgerEntryKind::Charge, 5000, "A1D", now).is_err());
+}
+
+#[test]
+fn test_ledger_entry_kind_round_trip() {
+    for kind in [
+        LedgerEntryKind::Charge,
+        LedgerEntryKind::Refund,
+        LedgerEntryKind::Fee,
+        LedgerEntryKind::Payout,
+        LedgerEntryKind::Adjustment,
+    ] {
+        assert_eq!(LedgerEntryKind::from_str(kind.as_str()), Some(kind));
+    }
+    assert_eq!(LedgerEntryKind::from_str("transfer"), None);
+}
+
+#[test]
+fn test_matching_records_are_balanced() {
+    let report = reconcile(
+        &[
+            ledger("txn_1", LedgerEntryKind::Charge, 5000, "AUD"),
+            ledger("txn_2", LedgerEntryKind::Refund, -1200, "AUD"),
+        ],
+        &[
+            transaction("txn_1", LedgerEntryKind::Charge, 5000, "AUD"),
+            transaction("txn_2", LedgerEntryKind::Refund, -1200, "AUD"),
+        ],
+    );
+
+    assert!(report.is_balanced());
+    assert_eq!(report.matched_count, 2);
+    assert_eq!(report.ledger_count, 2);
+    assert_eq!(report.provider_count, 2);
+    assert!(report.discrepancy_totals.is_empty());
+    assert!(!report.exceeds_threshold(0));
+}
+
+#[test]
+fn test_records_with_the_same_reference_are_netted() {
+    let report = reconcile(
+        &[
+            ledger("txn_1", LedgerEntryKind::Charge, 5000, "AUD"),
+            ledger("txn_1", LedgerEntryKind::Fee, -175, "AUD"),
+        ],
+        &[transaction("txn_1", LedgerEntryKind::Charge, 4825, "AUD")],
+    );
+
+    assert!(report.is_balanced());
+    assert_eq!(report.matched_count, 1);
+}
+
+#[test]
+fn test_discrepancies_are_classified() {
+    let report = reconcile(
+        &[
+            ledger("txn_amount", LedgerEntryKind::Charge, 5000, "AUD"),
+            ledger("txn_currency", LedgerEntryKind::Charge, 3000, "AUD"),
+            ledger("txn_ledger_only", LedgerEntryKind::Refund, -800, "CNY"),
+        ],
+        &[
+            transaction("txn_amount", LedgerEntryKind::Charge, 4500, "AUD"),
+            transaction("txn_currency", LedgerEntryKind::Charge, 3000, "CNY"),
+            transaction("txn_provider_only", LedgerEntryKind::Charge, 2000, "AUD"),
+        ],
+    );
+
+    assert!(!report.is_balanced());
+    assert_eq!(report.matched_count, 0);
+
+    let kinds: Vec<_> = report
+        .discrepancies
+        .iter()
+        .map(|d| (d.reference.as_str(), d.kind, d.difference()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("txn_amount", DiscrepancyKind::AmountMismatch, 500),
+            ("txn_currency", DiscrepancyKind::CurrencyMismatch, 3000),
+            ("txn_ledger_only", DiscrepancyKind::MissingAtProvider, 800),
+            ("txn_provider_only", DiscrepancyKind::MissingInLedger, 2000),
+        ]
+    );
+
+    // Totals are kept per currency
+    assert_eq!(report.discrepancy_totals.get("AUD"), Some(&2500));
+    assert_eq!(report.discrepancy_totals.get("CNY"), Some(&3800));
+}
+
+#[test]
+fn test_threshold_applies_per_currency() {
+    let report = reconcile(
+        &[
+            ledger("txn_1", LedgerEntryKind::Charge, 5000, "AUD"),
+            ledger("txn_2", LedgerEntryKind::Charge, 5000, "CNY"),
+        ],
+        &[
+            transaction("txn_1", LedgerEntryKind::Charge, 4400, "AUD"),
+            transaction("txn_2", LedgerEntryKind::Charge, 4400, "CNY"),
+        ],
+    );
+
+    // 600 in each currency, never 1200 combined
+    assert!(report.exceeds_threshold(599));
+    assert!(!report.exceeds_threshold(600));
+}
@@ -0,0 +1,78 @@
+//! Unit tests for worker service-area polygons
+
+use re_shared::types::common::Coordinate;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::domain::entities::service_area::ServiceArea;
+
+/// Clockwise square around (0, 0) with an extra vertex on one edge
+fn square() -> serde_json::Value {
+    json!({
+        "type": "Polygon",
+        "coordinates": [[[-1.0, -1.0], [-1.0, 1.0], [1.0, 1.0], [1.0, 0.0], [1.0, -1.0], [-1.0, -1.0]]]
+    })
+}
+
+#[test]
+fn test_from_geojson_simplifies_and_orients_ring() {
+    let area = ServiceArea::from_geojson(Uuid::new_v4(), None, &square(), 0.001).unwrap();
+
+    // The collinear vertex is dropped and the ring is counter-clockwise
+    assert_eq!(area.ring.len(), 5);
+    let area_sign: f64 = area
+        .ring
+        .windows(2)
+        .map(|e| e[0].longitude * e[1].latitude - e[1].longitude * e[0].latitude)
+        .sum();
+    assert!(area_sign > 0.0);
+}
+
+#[test]
+fn test_from_geojson_accepts_feature() {
+    let feature = json!({ "type": "Feature", "properties": {}, "geometry": square() });
+
+    assert!(ServiceArea::from_geojson(Uuid::new_v4(), None, &feature, 0.0).is_ok());
+}
+
+#[test]
+fn test_contains() {
+    let area = ServiceArea::from_geojson(Uuid::new_v4(), None, &square(), 0.0).unwrap();
+
+    assert!(area.contains(&Coordinate::new(0.5, 0.5)));
+    assert!(!area.contains(&Coordinate::new(2.0, 0.0)));
+}
+
+#[test]
+fn test_rejects_invalid_polygons() {
+    let worker_id = Uuid::new_v4();
+    let cases = [
+        json!({ "type": "Point", "coordinates": [0.0, 0.0] }),
+        json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]] }),
+        json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]]] }),
+        json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [200.0, 0.0], [1.0, 1.0], [0.0, 0.0]]] }),
+        // Bow tie
+        json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]] }),
+        // Polygon with a hole
+        json!({ "type": "Polygon", "coordinates": [
+            [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0], [0.0, 0.0]],
+            [[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 1.0]]
+        ] }),
+    ];
+
+    for case in cases {
+        assert!(
+            ServiceArea::from_geojson(worker_id, None, &case, 0.0).is_err(),
+            "expected rejection: {}",
+            case
+        );
+    }
+}
+
+#[test]
+fn test_to_wkt_and_geojson_use_longitude_first() {
+    let area = ServiceArea::from_geojson(Uuid::new_v4(), None, &square(), 0.001).unwrap();
+
+    assert!(area.to_wkt().starts_with("POLYGON((-1 -1, 1 -1"));
+    assert_eq!(area.to_geojson()["coordinates"][0][1], json!([1.0, -1.0]));
+}
@@ -0,0 +1,38 @@
+//! Unit tests for SMS delivery log entities
+
+use crate::domain::entities::sms_delivery::{SmsDeliveryLog, SmsDeliveryStatus};
+
+#[test]
+fn test_sms_delivery_status_round_trip() {
+    for status in [
+        SmsDeliveryStatus::Sent,
+        SmsDeliveryStatus::Delivered,
+        SmsDeliveryStatus::Undelivered,
+        SmsDeliveryStatus::Failed,
+    ] {
+        assert_eq!(SmsDeliveryStatus::from_str(status.as_str()), Some(status));
+    }
+    assert_eq!(SmsDeliveryStatus::from_str("queued"), None);
+}
+
+#[test]
+fn test_sent_log_keeps_trace_and_provider_ids() {
+    let log = SmsDeliveryLog::sent(Some("req-1".to_string()), "Twilio", "SM123", "****5678");
+
+    assert_eq!(log.trace_id.as_deref(), Some("req-1"));
+    assert_eq!(log.provider_message_id.as_deref(), Some("SM123"));
+    assert_eq!(log.status, SmsDeliveryStatus::Sent);
+    assert!(log.error.is_none());
+}
+
+#[test]
+fn test_apply_receipt_ignores_late_sent_status() {
+    let mut log = SmsDeliveryLog::sent(None, "AWS SNS", "msg-1", "****5678");
+
+    assert!(log.apply_receipt(SmsDeliveryStatus::Delivered, None));
+    assert!(!log.apply_receipt(SmsDeliveryStatus::Sent, None));
+    assert_eq!(log.status, SmsDeliveryStatus::Delivered);
+
+    assert!(log.apply_receipt(SmsDeliveryStatus::Undelivered, Some("30003".to_string())));
+    assert_eq!(log.error.as_deref(), Some("30003"));
+}
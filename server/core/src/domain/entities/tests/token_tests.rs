@@ -5,8 +5,11 @@ use chrono::{Duration, Utc};
 use crate::domain::entities::token::{
     Claims, RefreshToken, TokenPair,
     ACCESS_TOKEN_EXPIRY_MINUTES, REFRESH_TOKEN_EXPIRY_DAYS,
-    JWT_ISSUER, JWT_AUDIENCE
+    JWT_ISSUER, JWT_AUDIENCE, scopes_for_admin_roles,
+    SCOPE_ORDERS_WRITE, SCOPE_PAYMENTS_WRITE, SCOPE_PAYOUTS_READ, SCOPE_PAYOUTS_WRITE,
+    SCOPE_PROFILE_WRITE,
 };
+use crate::domain::entities::oauth::SCOPE_ORDERS_READ;
 
 #[test]
 fn test_access_token_claims() {
@@ -256,4 +259,56 @@ fn test_claims_with_phone_hash() {
     assert_eq!(claims.phone_hash, phone_hash);
     assert_eq!(claims.device_fingerprint, None);
     assert_eq!(claims.token_family, None);
-}
\ No newline at end of file
+}
+#[test]
+fn test_claims_bound_to_device() {
+    let claims = Claims::new_access_token(
+        Uuid::new_v4(),
+        None,
+        true,
+        None,
+        Some(Claims::hash_device_fingerprint("device_abc")),
+    );
+    
+    assert!(claims.is_bound_to_device(Some("device_abc")));
+    assert!(!claims.is_bound_to_device(Some("device_xyz")));
+    assert!(!claims.is_bound_to_device(None));
+}
+
+#[test]
+fn test_claims_without_device_are_unbound() {
+    let claims = Claims::new_access_token(Uuid::new_v4(), None, true, None, None);
+    
+    assert!(claims.is_bound_to_device(Some("device_abc")));
+    assert!(claims.is_bound_to_device(None));
+}
+
+#[test]
+fn test_access_token_scopes_follow_user_type() {
+    let customer = Claims::new_access_token(Uuid::new_v4(), Some("customer".to_string()), true, None, None);
+    assert!(customer.has_scope(SCOPE_ORDERS_WRITE));
+    assert!(customer.has_scope(SCOPE_PAYMENTS_WRITE));
+    assert!(!customer.has_scope(SCOPE_PAYOUTS_WRITE));
+
+    let worker = Claims::new_access_token(Uuid::new_v4(), Some("worker".to_string()), true, None, None);
+    assert!(worker.has_scope(SCOPE_ORDERS_WRITE));
+    assert!(worker.has_scope(SCOPE_PAYOUTS_READ));
+    assert!(!worker.has_scope(SCOPE_PAYMENTS_WRITE));
+
+    let unassigned = Claims::new_access_token(Uuid::new_v4(), None, true, None, None);
+    assert!(unassigned.has_scope(SCOPE_PROFILE_WRITE));
+    assert!(!unassigned.has_scope(SCOPE_ORDERS_WRITE));
+}
+
+#[test]
+fn test_admin_token_scopes_follow_roles() {
+    let support = Claims::new_admin_token(Uuid::new_v4(), vec!["support".to_string()], 3600);
+    assert!(support.has_scope(SCOPE_ORDERS_WRITE));
+    assert!(!support.has_scope(SCOPE_PAYOUTS_WRITE));
+
+    let both = scopes_for_admin_roles(&["support".to_string(), "finance".to_string()]);
+    assert!(both.contains(&SCOPE_PAYOUTS_WRITE));
+    assert_eq!(both.iter().filter(|s| **s == SCOPE_ORDERS_READ).count(), 1);
+
+    assert!(scopes_for_admin_roles(&["auditor".to_string()]).is_empty());
+}
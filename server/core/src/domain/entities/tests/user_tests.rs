@@ -15,6 +15,7 @@ fn test_new_user_creation() {
     assert!(!user.is_verified);
     assert!(!user.is_blocked);
     assert!(user.last_login_at.is_none());
+    assert!(!user.is_deleted());
 }
 
 #[test]
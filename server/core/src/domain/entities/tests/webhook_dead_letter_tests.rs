@@ -0,0 +1,60 @@
+//! Unit tests for webhook dead-letter entities
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::domain::entities::security_webhook::{SecurityEventKind, WebhookDelivery};
+use crate::domain::entities::webhook_dead_letter::{
+    DeadLetterStatus, WebhookDeadLetter, WebhookDirection, SECURITY_WEBHOOK_SOURCE,
+};
+
+#[test]
+fn test_direction_and_status_round_trip() {
+    for direction in [WebhookDirection::Inbound, WebhookDirection::Outbound] {
+        assert_eq!(WebhookDirection::from_str(direction.as_str()), Some(direction));
+    }
+    for status in [DeadLetterStatus::Pending, DeadLetterStatus::Replaying, DeadLetterStatus::Resolved] {
+        assert_eq!(DeadLetterStatus::from_str(status.as_str()), Some(status));
+    }
+    assert_eq!(WebhookDirection::from_str("sideways"), None);
+}
+
+#[test]
+fn test_outbound_dead_letter_keeps_delivery_and_endpoint() {
+    let webhook_id = Uuid::new_v4();
+    let mut delivery = WebhookDelivery::new(webhook_id, SecurityEventKind::AccountLocked, json!({ "id": "evt" }));
+    delivery.last_error = Some("HTTP 503".to_string());
+
+    let dead_letter = WebhookDeadLetter::outbound(&delivery);
+    assert_eq!(dead_letter.direction, WebhookDirection::Outbound);
+    assert_eq!(dead_letter.source, SECURITY_WEBHOOK_SOURCE);
+    assert_eq!(dead_letter.event_id, delivery.id.to_string());
+    assert_eq!(dead_letter.event_type, "account_locked");
+    assert_eq!(dead_letter.target_id, Some(webhook_id));
+    assert_eq!(dead_letter.error, "HTTP 503");
+    assert_eq!(dead_letter.failures, 1);
+    assert!(dead_letter.is_pending());
+}
+
+#[test]
+fn test_replay_failure_and_resolution() {
+    let admin = Uuid::new_v4();
+    let mut dead_letter =
+        WebhookDeadLetter::inbound("stripe", "evt_1", "charge.refunded", json!({}), "Over-refund");
+
+    dead_letter.start_replay(admin);
+    assert_eq!(dead_letter.status, DeadLetterStatus::Replaying);
+    assert_eq!(dead_letter.replays, 1);
+    assert_eq!(dead_letter.replayed_by, Some(admin));
+
+    dead_letter.record_failure("Payment not found");
+    assert!(dead_letter.is_pending());
+    assert_eq!(dead_letter.failures, 2);
+    assert_eq!(dead_letter.error, "Payment not found");
+
+    dead_letter.start_replay(admin);
+    dead_letter.resolve("refund_recorded");
+    assert_eq!(dead_letter.status, DeadLetterStatus::Resolved);
+    assert_eq!(dead_letter.replays, 2);
+    assert_eq!(dead_letter.resolution.as_deref(), Some("refund_recorded"));
+}
@@ -2,8 +2,12 @@
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::admin::AdminRole;
+use super::oauth::{SCOPE_ORDERS_READ, SCOPE_PROFILE_READ};
+
 /// Access token expiration time (15 minutes)
 pub const ACCESS_TOKEN_EXPIRY_MINUTES: i64 = 15;
 
@@ -16,6 +20,92 @@ pub const JWT_ISSUER: &str = "renov-easy";
 /// JWT audience
 pub const JWT_AUDIENCE: &str = "renov-easy-api";
 
+/// JWT audience of tokens issued to partner applications
+///
+/// Partner tokens are rejected by the main API and only accepted by the
+/// scope-limited partner API.
+pub const JWT_PARTNER_AUDIENCE: &str = "renov-easy-partner-api";
+
+/// Scope to update the user's own profile
+pub const SCOPE_PROFILE_WRITE: &str = "profile:write";
+
+/// Scope to create orders and change their status
+pub const SCOPE_ORDERS_WRITE: &str = "orders:write";
+
+/// Scope to read payments and saved payment methods
+pub const SCOPE_PAYMENTS_READ: &str = "payments:read";
+
+/// Scope to pay for orders and manage saved payment methods
+pub const SCOPE_PAYMENTS_WRITE: &str = "payments:write";
+
+/// Scope to read payouts and payout methods
+pub const SCOPE_PAYOUTS_READ: &str = "payouts:read";
+
+/// Scope to manage payout methods and release payouts
+pub const SCOPE_PAYOUTS_WRITE: &str = "payouts:write";
+
+/// Scopes granted to a user access token
+///
+/// Users who have not chosen a type yet may only manage their profile.
+pub fn scopes_for_user_type(user_type: Option<&str>) -> Vec<&'static str> {
+    let mut scopes = vec![SCOPE_PROFILE_READ, SCOPE_PROFILE_WRITE];
+    match user_type {
+        Some("customer") => scopes.extend([
+            SCOPE_ORDERS_READ,
+            SCOPE_ORDERS_WRITE,
+            SCOPE_PAYMENTS_READ,
+            SCOPE_PAYMENTS_WRITE,
+        ]),
+        Some("worker") => scopes.extend([
+            SCOPE_ORDERS_READ,
+            SCOPE_ORDERS_WRITE,
+            SCOPE_PAYOUTS_READ,
+            SCOPE_PAYOUTS_WRITE,
+        ]),
+        _ => {}
+    }
+    scopes
+}
+
+/// Scopes granted to an administrator holding the given roles
+///
+/// Unknown role names grant nothing.
+pub fn scopes_for_admin_roles(roles: &[String]) -> Vec<&'static str> {
+    let mut scopes = Vec::new();
+    for role in roles.iter().filter_map(|role| AdminRole::from_str(role)) {
+        let granted: &[&'static str] = match role {
+            AdminRole::Admin => &[
+                SCOPE_PROFILE_READ,
+                SCOPE_ORDERS_READ,
+                SCOPE_ORDERS_WRITE,
+                SCOPE_PAYMENTS_READ,
+                SCOPE_PAYMENTS_WRITE,
+                SCOPE_PAYOUTS_READ,
+                SCOPE_PAYOUTS_WRITE,
+            ],
+            AdminRole::Support => &[
+                SCOPE_PROFILE_READ,
+                SCOPE_ORDERS_READ,
+                SCOPE_ORDERS_WRITE,
+                SCOPE_PAYMENTS_READ,
+            ],
+            AdminRole::Finance => &[
+                SCOPE_ORDERS_READ,
+                SCOPE_PAYMENTS_READ,
+                SCOPE_PAYMENTS_WRITE,
+                SCOPE_PAYOUTS_READ,
+                SCOPE_PAYOUTS_WRITE,
+            ],
+        };
+        for scope in granted {
+            if !scopes.contains(scope) {
+                scopes.push(*scope);
+            }
+        }
+    }
+    scopes
+}
+
 /// Claims structure for JWT payload
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Claims {
@@ -54,11 +144,29 @@ pub struct Claims {
     
     /// Token family ID for rotation tracking
     pub token_family: Option<String>,
+
+    /// Space-delimited scopes the token grants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// OAuth client the token was issued to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+
+    /// Administrative roles, for tokens issued through admin single sign-on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+
+    /// Marketplace (tenant) the token was issued by; absent for the default tenant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
 }
 
 impl Claims {
     /// Creates new claims for an access token
     ///
+    /// The token's scopes are derived from the user type.
+    ///
     /// # Arguments
     ///
     /// * `user_id` - The user's UUID
@@ -79,6 +187,7 @@ impl Claims {
     ) -> Self {
         let now = Utc::now();
         let expiry = now + Duration::minutes(ACCESS_TOKEN_EXPIRY_MINUTES);
+        let scope = scopes_for_user_type(user_type.as_deref()).join(" ");
         
         Self {
             sub: user_id.to_string(),
@@ -93,6 +202,10 @@ impl Claims {
             phone_hash,
             device_fingerprint,
             token_family: None,
+            scope: Some(scope),
+            client_id: None,
+            roles: Vec::new(),
+            tenant: None,
         }
     }
     
@@ -128,8 +241,103 @@ impl Claims {
             phone_hash: None,
             device_fingerprint,
             token_family,
+            scope: None,
+            client_id: None,
+            roles: Vec::new(),
+            tenant: None,
+        }
+    }
+
+    /// Creates new claims for a partner access token
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - The user the client acts for, or the client itself
+    /// * `client_id` - The OAuth client the token is issued to
+    /// * `scopes` - The scopes the token grants
+    /// * `expires_in_seconds` - Lifetime of the token
+    ///
+    /// # Returns
+    ///
+    /// A new `Claims` instance for the partner API
+    pub fn new_partner_token(
+        subject: String,
+        client_id: String,
+        scopes: &[String],
+        expires_in_seconds: i64,
+    ) -> Self {
+        let now = Utc::now();
+        let expiry = now + Duration::seconds(expires_in_seconds);
+
+        Self {
+            sub: subject,
+            iat: now.timestamp(),
+            exp: expiry.timestamp(),
+            nbf: now.timestamp(),
+            iss: JWT_ISSUER.to_string(),
+            aud: JWT_PARTNER_AUDIENCE.to_string(),
+            jti: Uuid::new_v4().to_string(),
+            user_type: None,
+            is_verified: false,
+            phone_hash: None,
+            device_fingerprint: None,
+            token_family: None,
+            scope: Some(scopes.join(" ")),
+            client_id: Some(client_id),
+            roles: Vec::new(),
+            tenant: None,
+        }
+    }
+
+    /// Creates new claims for an administrator signed in through single sign-on
+    ///
+    /// The token's scopes are derived from the roles.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_id` - The administrator's identity ID
+    /// * `roles` - The administrative roles granted by the identity provider
+    /// * `expires_in_seconds` - Lifetime of the token
+    ///
+    /// # Returns
+    ///
+    /// A new `Claims` instance with the `admin` user type
+    pub fn new_admin_token(admin_id: Uuid, roles: Vec<String>, expires_in_seconds: i64) -> Self {
+        let now = Utc::now();
+        let expiry = now + Duration::seconds(expires_in_seconds);
+        let scope = scopes_for_admin_roles(&roles).join(" ");
+
+        Self {
+            sub: admin_id.to_string(),
+            iat: now.timestamp(),
+            exp: expiry.timestamp(),
+            nbf: now.timestamp(),
+            iss: JWT_ISSUER.to_string(),
+            aud: JWT_AUDIENCE.to_string(),
+            jti: Uuid::new_v4().to_string(),
+            user_type: Some("admin".to_string()),
+            is_verified: true,
+            phone_hash: None,
+            device_fingerprint: None,
+            token_family: None,
+            scope: Some(scope),
+            client_id: None,
+            roles,
+            tenant: None,
         }
     }
+
+    /// Checks whether the token carries an administrative role
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    /// Checks whether the token grants a scope
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope
+            .as_deref()
+            .is_some_and(|granted| granted.split_whitespace().any(|s| s == scope))
+    }
     
     /// Checks if the claims have expired
     ///
@@ -159,6 +367,43 @@ impl Claims {
     pub fn user_id(&self) -> Result<Uuid, uuid::Error> {
         Uuid::parse_str(&self.sub)
     }
+    
+    /// Hashes a device fingerprint for embedding into access token claims
+    ///
+    /// Access tokens are readable by anyone holding them, so only the hash
+    /// of the fingerprint is carried in the payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `fingerprint` - The raw device fingerprint sent by the client
+    ///
+    /// # Returns
+    ///
+    /// The hex-encoded SHA-256 hash of the fingerprint
+    pub fn hash_device_fingerprint(fingerprint: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(fingerprint.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+    
+    /// Checks whether the claims may be used from the given device
+    ///
+    /// Claims without a device fingerprint are not bound to any device.
+    ///
+    /// # Arguments
+    ///
+    /// * `fingerprint` - The raw device fingerprint presented with the token
+    ///
+    /// # Returns
+    ///
+    /// `true` if the claims are unbound or the fingerprint hash matches, `false` otherwise
+    pub fn is_bound_to_device(&self, fingerprint: Option<&str>) -> bool {
+        match (&self.device_fingerprint, fingerprint) {
+            (None, _) => true,
+            (Some(expected), Some(provided)) => *expected == Self::hash_device_fingerprint(provided),
+            (Some(_), None) => false,
+        }
+    }
 }
 
 /// Refresh token entity stored in the database
@@ -23,6 +23,10 @@ pub struct User {
     /// Hashed phone number for security
     #[serde(rename = "phone_hash")]
     pub phone_hash: String,
+
+    /// Pepper version the phone hash was computed with; 0 for bare SHA-256
+    #[serde(default)]
+    pub phone_hash_version: u16,
     
     /// Country code (e.g., +86, +61)
     pub country_code: String,
@@ -39,11 +43,19 @@ pub struct User {
     /// Timestamp of the user's last login
     pub last_login_at: Option<DateTime<Utc>>,
     
+    /// ISO 3166-1 alpha-2 country of the IP address used for the last login, if known
+    #[serde(default)]
+    pub last_login_country: Option<String>,
+    
     /// Whether the user's phone number has been verified
     pub is_verified: bool,
     
     /// Whether the user account is blocked
     pub is_blocked: bool,
+
+    /// Timestamp when the account was deleted; it can be restored until purged
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl User {
@@ -56,13 +68,16 @@ impl User {
         Self {
             id: Uuid::new_v4(),
             phone_hash,
+            phone_hash_version: 0,
             country_code,
             user_type: None,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            last_login_country: None,
             is_verified: false,
             is_blocked: false,
+            deleted_at: None,
         }
     }
     
@@ -110,4 +125,9 @@ impl User {
     pub fn is_worker(&self) -> bool {
         matches!(self.user_type, Some(UserType::Worker))
     }
+
+    /// Checks if the account has been deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 }
\ No newline at end of file
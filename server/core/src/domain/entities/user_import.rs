@@ -0,0 +1,39 @@
+//! Entities for importing users from the legacy system.
+//!
+//! An import is identified by a digest of the export it reads, so running
+//! the same export again resumes from the last saved checkpoint instead of
+//! starting over.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Progress of an import, saved after every committed batch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserImportCheckpoint {
+    /// SHA-256 of the export being imported, as lower-case hex
+    pub import_id: String,
+    /// Records handled so far, in export order
+    pub records_processed: u64,
+    /// Users created so far
+    pub imported: u64,
+    /// Records skipped so far as invalid or duplicated
+    pub skipped: u64,
+    /// Whether every record has been handled
+    pub completed: bool,
+    /// When the checkpoint was saved
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserImportCheckpoint {
+    /// Create the checkpoint of an import that has not started
+    pub fn new(import_id: String) -> Self {
+        Self {
+            import_id,
+            records_processed: 0,
+            imported: 0,
+            skipped: 0,
+            completed: false,
+            updated_at: Utc::now(),
+        }
+    }
+}
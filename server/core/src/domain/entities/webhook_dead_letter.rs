@@ -0,0 +1,243 @@
+//! Webhook dead-letter entities.
+//!
+//! A webhook that could not be handled, whether received from a provider or
+//! delivered to an operator endpoint, is kept as a dead letter with its full
+//! payload. Administrators inspect dead letters and replay them once the
+//! cause of the failure is fixed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use super::security_webhook::WebhookDelivery;
+
+/// Source of outbound dead letters
+pub const SECURITY_WEBHOOK_SOURCE: &str = "security_webhook";
+
+/// Whether a webhook was received or sent by the platform
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDirection {
+    /// Received from a provider, e.g. a payment provider event
+    Inbound,
+    /// Delivered to an operator endpoint, e.g. a security event
+    Outbound,
+}
+
+impl WebhookDirection {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Inbound => "inbound",
+            Self::Outbound => "outbound",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "inbound" => Some(Self::Inbound),
+            "outbound" => Some(Self::Outbound),
+            _ => None,
+        }
+    }
+}
+
+/// Replay state of a dead letter
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadLetterStatus {
+    /// Waiting to be replayed
+    Pending,
+    /// A replay is in progress
+    Replaying,
+    /// Handled by a replay or by a later redelivery from the provider
+    Resolved,
+}
+
+impl DeadLetterStatus {
+    /// Convert to string representation for database storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Replaying => "replaying",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "replaying" => Some(Self::Replaying),
+            "resolved" => Some(Self::Resolved),
+            _ => None,
+        }
+    }
+}
+
+/// A webhook that failed to be handled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDeadLetter {
+    /// Unique identifier
+    pub id: Uuid,
+
+    /// Whether the webhook was received or sent
+    pub direction: WebhookDirection,
+
+    /// Provider that sent an inbound webhook (e.g. "stripe"), or the kind of
+    /// outbound webhook (e.g. "security_webhook")
+    pub source: String,
+
+    /// Provider's event identifier for inbound webhooks, delivery ID for
+    /// outbound webhooks; unique per direction and source
+    pub event_id: String,
+
+    /// Provider's event type or the kind of outbound event
+    pub event_type: String,
+
+    /// Endpoint an outbound webhook was addressed to
+    pub target_id: Option<Uuid>,
+
+    /// The full payload
+    pub payload: JsonValue,
+
+    /// Error of the most recent failure
+    pub error: String,
+
+    /// Times handling the webhook failed, including failed replays
+    pub failures: u32,
+
+    /// Replay state
+    pub status: DeadLetterStatus,
+
+    /// Times the webhook was replayed
+    pub replays: u32,
+
+    /// Administrator who started the most recent replay
+    pub replayed_by: Option<Uuid>,
+
+    /// When the most recent replay started
+    pub replayed_at: Option<DateTime<Utc>>,
+
+    /// Outcome of the replay or redelivery that handled the webhook
+    pub resolution: Option<String>,
+
+    /// When the webhook first failed
+    pub created_at: DateTime<Utc>,
+
+    /// When the dead letter was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDeadLetter {
+    /// Creates a dead letter for a received webhook that could not be processed
+    pub fn inbound(
+        source: impl Into<String>,
+        event_id: impl Into<String>,
+        event_type: impl Into<String>,
+        payload: JsonValue,
+        error: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            WebhookDirection::Inbound,
+            source.into(),
+            event_id.into(),
+            event_type.into(),
+            None,
+            payload,
+            error.into(),
+        )
+    }
+
+    /// Creates a dead letter for a security webhook delivery that was given up
+    pub fn outbound(delivery: &WebhookDelivery) -> Self {
+        Self::new(
+            WebhookDirection::Outbound,
+            SECURITY_WEBHOOK_SOURCE.to_string(),
+            delivery.id.to_string(),
+            delivery.event_kind.as_str().to_string(),
+            Some(delivery.webhook_id),
+            delivery.payload.clone(),
+            delivery.last_error.clone().unwrap_or_default(),
+        )
+    }
+
+    fn new(
+        direction: WebhookDirection,
+        source: String,
+        event_id: String,
+        event_type: String,
+        target_id: Option<Uuid>,
+        payload: JsonValue,
+        error: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            direction,
+            source,
+            event_id,
+            event_type,
+            target_id,
+            payload,
+            error,
+            failures: 1,
+            status: DeadLetterStatus::Pending,
+            replays: 0,
+            replayed_by: None,
+            replayed_at: None,
+            resolution: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Mark a replay by an administrator as started
+    pub fn start_replay(&mut self, replayed_by: Uuid) {
+        let now = Utc::now();
+        self.status = DeadLetterStatus::Replaying;
+        self.replays += 1;
+        self.replayed_by = Some(replayed_by);
+        self.replayed_at = Some(now);
+        self.updated_at = now;
+    }
+
+    /// Mark the webhook as handled
+    pub fn resolve(&mut self, resolution: impl Into<String>) {
+        self.status = DeadLetterStatus::Resolved;
+        self.resolution = Some(resolution.into());
+        self.updated_at = Utc::now();
+    }
+
+    /// Record a failed replay, leaving the webhook to be replayed again
+    pub fn record_failure(&mut self, error: impl Into<String>) {
+        self.status = DeadLetterStatus::Pending;
+        self.error = error.into();
+        self.failures += 1;
+        self.updated_at = Utc::now();
+    }
+
+    /// Whether the dead letter may be replayed
+    pub fn is_pending(&self) -> bool {
+        self.status == DeadLetterStatus::Pending
+    }
+}
+
+/// Criteria for listing dead letters
+#[derive(Debug, Clone, Default)]
+pub struct DeadLetterFilter {
+    /// Only dead letters in this direction
+    pub direction: Option<WebhookDirection>,
+    /// Only dead letters from this source
+    pub source: Option<String>,
+    /// Only dead letters in this state
+    pub status: Option<DeadLetterStatus>,
+    /// Only dead letters that first failed at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only dead letters that first failed at or before this time
+    pub to: Option<DateTime<Utc>>,
+}
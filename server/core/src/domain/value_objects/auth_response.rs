@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::entities::compliance::ComplianceRequirement;
+
 /// Authentication response containing tokens and user metadata
 ///
 /// This response is returned after successful authentication and contains:
@@ -9,6 +11,8 @@ use serde::{Deserialize, Serialize};
 /// - Token expiration times
 /// - User type (if selected)
 /// - Flag indicating if user type selection is required
+/// - Flag indicating if step-up verification is required
+/// - Compliance checks the user's market still requires
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AuthResponse {
     /// JWT access token for API authentication
@@ -25,6 +29,14 @@ pub struct AuthResponse {
     
     /// Whether the user needs to select their type
     pub requires_type_selection: bool,
+    
+    /// Whether the login looked anomalous and the client must complete step-up verification
+    #[serde(default)]
+    pub requires_step_up: bool,
+
+    /// Compliance checks of the user's market that are not verified yet
+    #[serde(default)]
+    pub compliance_required: Vec<ComplianceRequirement>,
 }
 
 impl AuthResponse {
@@ -54,6 +66,8 @@ impl AuthResponse {
             expires_in,
             user_type,
             requires_type_selection,
+            requires_step_up: false,
+            compliance_required: Vec::new(),
         }
     }
 
@@ -84,6 +98,8 @@ impl AuthResponse {
             expires_in: token_pair.access_expires_in,
             user_type: user_type_str,
             requires_type_selection,
+            requires_step_up: false,
+            compliance_required: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -10,7 +10,7 @@ pub use types::{
 use thiserror::Error;
 
 /// Core domain errors (general purpose)
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum DomainError {
     #[error("Validation error: {message}")]
     Validation { message: String },
@@ -27,6 +27,11 @@ pub enum DomainError {
     #[error("Internal error: {message}")]
     Internal { message: String },
 
+    /// A write lost a conflict with a concurrent write, such as a deadlock;
+    /// retrying it may succeed
+    #[error("Conflicting concurrent write: {message}")]
+    Conflict { message: String },
+
     // Bridge to specific error types
     #[error(transparent)]
     Auth(#[from] AuthError),
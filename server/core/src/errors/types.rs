@@ -13,7 +13,7 @@ pub use re_shared::types::response::ErrorResponse as DomainErrorResponse;
 /// 
 /// These errors represent various authentication failure scenarios.
 /// Error messages are configured in the presentation layer for i18n support.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AuthError {
     #[error("Invalid phone format: {phone}")]
     InvalidPhoneFormat { phone: String },
@@ -56,13 +56,22 @@ pub enum AuthError {
     
     #[error("User blocked")]
     UserBlocked,
+
+    #[error("Landline phone number")]
+    LandlinePhoneNumber,
+
+    #[error("VoIP phone number")]
+    VoipPhoneNumber,
+
+    #[error("Unreachable phone number")]
+    UnreachablePhoneNumber,
 }
 
 /// Token-related errors
 /// 
 /// These errors represent various token validation and management failures.
 /// Error messages are configured in the presentation layer for i18n support.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum TokenError {
     #[error("Token expired")]
     TokenExpired,
@@ -85,9 +94,15 @@ pub enum TokenError {
     #[error("Refresh token expired")]
     RefreshTokenExpired,
 
+    #[error("Session expired due to inactivity")]
+    SessionIdleTimeout,
+
     #[error("Invalid refresh token")]
     InvalidRefreshToken,
 
+    #[error("Token bound to a different device")]
+    DeviceMismatch,
+
     #[error("Token generation failed")]
     TokenGenerationFailed,
 
@@ -102,7 +117,7 @@ pub enum TokenError {
 /// 
 /// These errors represent input validation failures.
 /// Error messages are configured in the presentation layer for i18n support.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum ValidationError {
     #[error("Required field: {field}")]
     RequiredField { field: String },
@@ -0,0 +1,7 @@
+//! Administrator identity repository module.
+
+mod r#trait;
+pub use r#trait::AdminIdentityRepository;
+
+mod repository;
+pub use repository::MySqlAdminIdentityRepository;
@@ -0,0 +1,24 @@
+//! Repository trait for administrators signed in through single sign-on.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::admin::AdminIdentity;
+use crate::errors::DomainError;
+
+/// Repository trait for administrator identity persistence operations
+#[async_trait]
+pub trait AdminIdentityRepository: Send + Sync {
+    /// Find an administrator by the identity provider's issuer and subject
+    async fn find_by_subject(&self, issuer: &str, subject: &str) -> Result<Option<AdminIdentity>, DomainError>;
+
+    /// Find an administrator by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<AdminIdentity>, DomainError>;
+
+    /// Create or update an administrator
+    ///
+    /// # Returns
+    /// * `Ok(AdminIdentity)` - The saved administrator
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, identity: AdminIdentity) -> Result<AdminIdentity, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Marketing attribution repository module.
+
+mod r#trait;
+pub use r#trait::AttributionRepository;
+
+mod repository;
+pub use repository::MySqlAttributionRepository;
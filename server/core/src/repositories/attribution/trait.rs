@@ -0,0 +1,39 @@
+//! Repository trait for marketing attribution captured at registration.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::attribution::{AttributionCohort, UserAttribution};
+use crate::errors::DomainError;
+
+/// Repository trait for UserAttribution persistence operations
+#[async_trait]
+pub trait AttributionRepository: Send + Sync {
+    /// Save the attribution of a user, replacing any already saved
+    async fn save(&self, attribution: &UserAttribution) -> Result<(), DomainError>;
+
+    /// Find the attribution of a user
+    ///
+    /// # Returns
+    /// * `Ok(Some(UserAttribution))` - If the user's attribution was saved
+    /// * `Ok(None)` - If none was saved
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<UserAttribution>, DomainError>;
+
+    /// Delete the attribution of a user
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If an attribution was deleted
+    /// * `Ok(false)` - If none was saved
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<bool, DomainError>;
+
+    /// Count registrations by week and campaign
+    ///
+    /// # Arguments
+    /// * `from` - Earliest registration time included
+    /// * `to` - Registration time at which counting stops (exclusive)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<AttributionCohort>)` - Cohorts ordered by week, largest first within a week
+    async fn cohorts(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<AttributionCohort>, DomainError>;
+}
@@ -5,7 +5,9 @@ use chrono::{DateTime, Utc};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType};
+use re_shared::types::Pagination;
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter};
 use crate::errors::DomainError;
 
 use super::AuditLogRepository;
@@ -196,7 +198,54 @@ impl AuditLogRepository for MockAuditLogRepository {
         
         Ok(deleted_count)
     }
-    
+
+    async fn archive_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        if *self.should_fail.lock().unwrap() {
+            return Err(DomainError::Internal {
+                message: "Mock repository error".to_string(),
+            });
+        }
+
+        let mut logs = self.logs.lock().unwrap();
+        let mut expired: Vec<&mut AuditLog> = logs
+            .iter_mut()
+            .filter(|log| log.created_at < before && !log.archived)
+            .collect();
+        expired.sort_by_key(|log| log.created_at);
+
+        let now = Utc::now();
+        let mut archived_count = 0;
+        for log in expired.into_iter().take(limit) {
+            log.archived = true;
+            log.archived_at = Some(now);
+            archived_count += 1;
+        }
+
+        Ok(archived_count)
+    }
+
+    async fn delete_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        if *self.should_fail.lock().unwrap() {
+            return Err(DomainError::Internal {
+                message: "Mock repository error".to_string(),
+            });
+        }
+
+        let mut logs = self.logs.lock().unwrap();
+        let mut expired: Vec<(DateTime<Utc>, Uuid)> = logs
+            .iter()
+            .filter(|log| log.created_at < before)
+            .map(|log| (log.created_at, log.id))
+            .collect();
+        expired.sort();
+        expired.truncate(limit);
+
+        let initial_count = logs.len();
+        logs.retain(|log| !expired.iter().any(|(_, id)| *id == log.id));
+
+        Ok(initial_count - logs.len())
+    }
+
     async fn find_by_event_types(
         &self,
         event_types: Vec<AuditEventType>,
@@ -229,4 +278,34 @@ impl AuditLogRepository for MockAuditLogRepository {
         
         Ok(result)
     }
-}
\ No newline at end of file
+
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        if *self.should_fail.lock().unwrap() {
+            return Err(DomainError::Internal {
+                message: "Mock repository error".to_string(),
+            });
+        }
+
+        let logs = self.logs.lock().unwrap();
+        let mut result: Vec<AuditLog> = logs
+            .iter()
+            .filter(|log| filter.matches(log))
+            .cloned()
+            .collect();
+
+        result.sort_by_key(|l| std::cmp::Reverse(l.created_at));
+        let total = result.len() as u64;
+
+        let page = result
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(pagination.limit() as usize)
+            .collect();
+
+        Ok((page, total))
+    }
+}
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType};
+use re_shared::types::Pagination;
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter};
 use crate::errors::DomainError;
 use super::AuditLogRepository;
 
@@ -73,6 +75,16 @@ impl AuditLogRepository for NoOpAuditLogRepository {
         // No-op - return 0 deleted
         Ok(0)
     }
+
+    async fn archive_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        // No-op - return 0 archived
+        Ok(0)
+    }
+
+    async fn delete_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        // No-op - return 0 deleted
+        Ok(0)
+    }
     
     async fn find_by_event_types(
         &self,
@@ -84,6 +96,14 @@ impl AuditLogRepository for NoOpAuditLogRepository {
         // No-op - return empty list
         Ok(Vec::new())
     }
+
+    async fn query(
+        &self,
+        _filter: &AuditLogFilter,
+        _pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        Ok((Vec::new(), 0))
+    }
 }
 
 // Also implement for () to allow simple type defaults
@@ -134,7 +154,15 @@ impl AuditLogRepository for () {
     async fn delete_archived_logs(&self) -> Result<usize, DomainError> {
         Ok(0)
     }
-    
+
+    async fn archive_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
     async fn find_by_event_types(
         &self,
         _event_types: Vec<AuditEventType>,
@@ -144,4 +172,12 @@ impl AuditLogRepository for () {
     ) -> Result<Vec<AuditLog>, DomainError> {
         Ok(Vec::new())
     }
-}
\ No newline at end of file
+
+    async fn query(
+        &self,
+        _filter: &AuditLogFilter,
+        _pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        Ok((Vec::new(), 0))
+    }
+}
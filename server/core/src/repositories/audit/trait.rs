@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType};
+use re_shared::types::Pagination;
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter};
 use crate::errors::DomainError;
 
 /// Repository trait for AuditLog entity persistence operations
@@ -24,6 +26,24 @@ pub trait AuditLogRepository: Send + Sync {
     /// * `Err(DomainError)` if the operation fails
     async fn create(&self, audit_log: &AuditLog) -> Result<(), DomainError>;
 
+    /// Create several audit log entries
+    ///
+    /// Used by writers flushing buffered entries. The default
+    /// implementation creates them one by one and stops at the first
+    /// failure; implementations able to write them together should do so.
+    ///
+    /// # Arguments
+    /// * `audit_logs` - The audit log entries to persist
+    ///
+    /// # Returns
+    /// * Number of entries created
+    async fn create_batch(&self, audit_logs: &[AuditLog]) -> Result<usize, DomainError> {
+        for audit_log in audit_logs {
+            self.create(audit_log).await?;
+        }
+        Ok(audit_logs.len())
+    }
+
     /// Find audit logs by user ID
     ///
     /// # Arguments
@@ -101,7 +121,29 @@ pub trait AuditLogRepository: Send + Sync {
     /// # Returns
     /// * Number of records deleted
     async fn delete_archived_logs(&self) -> Result<usize, DomainError>;
-    
+
+    /// Archive a batch of audit logs created before a cutoff
+    ///
+    /// # Arguments
+    /// * `before` - Only logs created before this time are archived
+    /// * `limit` - Maximum number of logs to archive, oldest first
+    ///
+    /// # Returns
+    /// * Number of records archived; fewer than `limit` once none are left
+    async fn archive_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError>;
+
+    /// Delete a batch of audit logs created before a cutoff
+    ///
+    /// Logs are deleted whether or not they have been archived.
+    ///
+    /// # Arguments
+    /// * `before` - Only logs created before this time are deleted
+    /// * `limit` - Maximum number of logs to delete, oldest first
+    ///
+    /// # Returns
+    /// * Number of records deleted; fewer than `limit` once none are left
+    async fn delete_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError>;
+
     /// Find audit logs by event types within a time range
     ///
     /// # Arguments
@@ -119,4 +161,19 @@ pub trait AuditLogRepository: Send + Sync {
         to: DateTime<Utc>,
         limit: Option<usize>,
     ) -> Result<Vec<AuditLog>, DomainError>;
-}
\ No newline at end of file
+
+    /// Query audit logs page by page
+    ///
+    /// # Arguments
+    /// * `filter` - Criteria the logs must match
+    /// * `pagination` - Page to return
+    ///
+    /// # Returns
+    /// * The page of matching logs ordered by created_at descending, and
+    ///   the total number of matching logs
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Business calendar closure date repository module.
+
+mod r#trait;
+pub use r#trait::ClosureDateRepository;
+
+mod repository;
+pub use repository::MySqlClosureDateRepository;
@@ -0,0 +1,10 @@
+//! Closure date repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlClosureDateRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/closure_date_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlClosureDateRepository;
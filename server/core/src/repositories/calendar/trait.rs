@@ -0,0 +1,45 @@
+//! Repository trait for ad-hoc business closure dates.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::ClosureDate;
+use crate::errors::DomainError;
+
+/// Repository trait for ClosureDate persistence operations
+#[async_trait]
+pub trait ClosureDateRepository: Send + Sync {
+    /// Save a new closure date
+    ///
+    /// # Arguments
+    /// * `closure` - The closure date to persist
+    ///
+    /// # Returns
+    /// * `Ok(ClosureDate)` - The saved closure
+    /// * `Err(DomainError::Validation)` - If the market already has a closure on this date
+    async fn save(&self, closure: ClosureDate) -> Result<ClosureDate, DomainError>;
+
+    /// Find closures for a market within an inclusive date range
+    ///
+    /// # Arguments
+    /// * `market` - Normalized market code
+    /// * `from` - First date of the range
+    /// * `to` - Last date of the range
+    ///
+    /// # Returns
+    /// * Closures ordered by date ascending
+    async fn find_by_market(
+        &self,
+        market: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ClosureDate>, DomainError>;
+
+    /// Delete a closure by its ID
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the closure was deleted
+    /// * `Ok(false)` - If no closure exists with this ID
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
+}
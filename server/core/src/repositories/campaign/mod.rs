@@ -0,0 +1,7 @@
+//! Notification campaign repository module.
+
+mod r#trait;
+pub use r#trait::CampaignRepository;
+
+mod repository;
+pub use repository::MySqlCampaignRepository;
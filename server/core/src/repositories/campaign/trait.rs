@@ -0,0 +1,99 @@
+//! Repository trait for notification campaigns and their per-recipient deliveries.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::campaign::{Campaign, CampaignDelivery, CampaignStats, CampaignStatus};
+use crate::errors::DomainError;
+
+/// Repository trait for Campaign persistence operations
+#[async_trait]
+pub trait CampaignRepository: Send + Sync {
+    /// Save a new campaign
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - The saved campaign
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, campaign: Campaign) -> Result<Campaign, DomainError>;
+
+    /// Find a campaign by its ID
+    ///
+    /// # Returns
+    /// * `Ok(Some(Campaign))` - If found
+    /// * `Ok(None)` - If no campaign exists with this ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Campaign>, DomainError>;
+
+    /// List campaigns, most recently created first
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of campaigns to return
+    async fn list_recent(&self, limit: usize) -> Result<Vec<Campaign>, DomainError>;
+
+    /// Find campaigns with the given status, oldest scheduled first
+    async fn find_by_status(&self, status: CampaignStatus) -> Result<Vec<Campaign>, DomainError>;
+
+    /// Update a campaign's status, audience size and timestamps
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - The updated campaign
+    /// * `Err(DomainError::NotFound)` - If the campaign does not exist
+    async fn update(&self, campaign: Campaign) -> Result<Campaign, DomainError>;
+
+    /// Claim a due campaign for enqueueing its audience
+    ///
+    /// Moves the campaign from scheduled to dispatching in one conditional
+    /// update, so only one of several dispatchers starts it. A campaign
+    /// left dispatching since before `stale_before` (its dispatcher stopped
+    /// before starting it) may be claimed again.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The caller holds the claim
+    /// * `Ok(false)` - Another dispatcher holds it, or the campaign is no
+    ///   longer scheduled
+    async fn claim_start(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError>;
+
+    /// Create a pending delivery for every user matching the campaign audience
+    ///
+    /// Must be idempotent: users who already have a delivery for the
+    /// campaign are not enqueued again.
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Total number of deliveries for the campaign
+    async fn enqueue_deliveries(&self, campaign: &Campaign) -> Result<u64, DomainError>;
+
+    /// Claim pending deliveries of a campaign that may be attempted at `now`
+    ///
+    /// Claimed deliveries move to processing atomically, so dispatchers
+    /// running on several instances never receive the same delivery. A
+    /// claim lasts until `claim_until`; deliveries still processing after
+    /// that are claimed again. [`Self::update_delivery`] releases the claim.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - The campaign's unique identifier
+    /// * `now` - Reference time compared against `next_attempt_at`
+    /// * `limit` - Maximum number of deliveries to claim
+    /// * `claim_until` - When the claim expires
+    async fn claim_pending_deliveries(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        limit: usize,
+        claim_until: DateTime<Utc>,
+    ) -> Result<Vec<CampaignDelivery>, DomainError>;
+
+    /// Find a delivery by its ID
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<CampaignDelivery>, DomainError>;
+
+    /// Update a delivery's status, next attempt time, error and timestamps,
+    /// releasing any claim
+    async fn update_delivery(&self, delivery: CampaignDelivery) -> Result<CampaignDelivery, DomainError>;
+
+    /// Aggregate delivery and open statistics for a campaign
+    async fn stats(&self, campaign_id: Uuid) -> Result<CampaignStats, DomainError>;
+}
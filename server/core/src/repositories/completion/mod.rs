@@ -0,0 +1,7 @@
+//! Order completion checklist repository module.
+
+mod r#trait;
+pub use r#trait::CompletionChecklistRepository;
+
+mod repository;
+pub use repository::MySqlCompletionChecklistRepository;
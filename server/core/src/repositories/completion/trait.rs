@@ -0,0 +1,37 @@
+//! Repository trait for checklist templates and the completion checklists of orders.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::completion::{ChecklistTemplate, CompletionChecklist, CustomerSignOff};
+use crate::errors::DomainError;
+
+/// Repository trait for completion checklist persistence operations
+#[async_trait]
+pub trait CompletionChecklistRepository: Send + Sync {
+    /// Record a new checklist template version
+    async fn save_template(&self, template: ChecklistTemplate) -> Result<ChecklistTemplate, DomainError>;
+
+    /// Find the newest template version for exactly the given category
+    ///
+    /// # Arguments
+    /// * `category` - Normalized category, `None` for the default template
+    async fn latest_template(&self, category: Option<&str>) -> Result<Option<ChecklistTemplate>, DomainError>;
+
+    /// Store a worker's checklist, replacing an earlier submission for the order
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The checklist was stored
+    /// * `Ok(false)` - The order's checklist is already signed off and was left unchanged
+    async fn save_checklist(&self, checklist: &CompletionChecklist) -> Result<bool, DomainError>;
+
+    /// Find the checklist of an order
+    async fn find_checklist(&self, order_id: Uuid) -> Result<Option<CompletionChecklist>, DomainError>;
+
+    /// Record the customer's sign-off of an order's checklist
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The sign-off was recorded
+    /// * `Ok(false)` - The checklist was already signed off
+    async fn record_sign_off(&self, order_id: Uuid, sign_off: &CustomerSignOff) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Compliance check repository module.
+
+mod r#trait;
+pub use r#trait::ComplianceRepository;
+
+mod repository;
+pub use repository::MySqlComplianceRepository;
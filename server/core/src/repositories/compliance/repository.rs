@@ -0,0 +1,10 @@
+//! Compliance check repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlComplianceRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/compliance_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlComplianceRepository;
@@ -0,0 +1,39 @@
+//! Repository trait for users' market compliance checks.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::ComplianceCheck;
+use crate::errors::DomainError;
+use crate::services::tenant::TenantId;
+
+/// Repository trait for compliance check persistence operations
+#[async_trait]
+pub trait ComplianceRepository: Send + Sync {
+    /// Save a compliance check, replacing the user's check for the same requirement
+    ///
+    /// # Returns
+    /// * `Ok(ComplianceCheck)` - The saved check
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, check: ComplianceCheck) -> Result<ComplianceCheck, DomainError>;
+
+    /// List a user's compliance checks
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ComplianceCheck>, DomainError>;
+
+    /// Find a compliance check by the provider's identifier for its latest submission
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<ComplianceCheck>, DomainError>;
+
+    /// Find the tenant of the compliance check with a provider identifier
+    ///
+    /// Provider results are received for every tenant, so this lookup is not
+    /// scoped to the current tenant.
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Credit wallet repository module.
+
+mod r#trait;
+pub use r#trait::CreditWalletRepository;
+
+mod repository;
+pub use repository::MySqlCreditWalletRepository;
@@ -0,0 +1,66 @@
+//! Repository trait for customer credit grants and wallet history.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::credit_wallet::{
+    CreditGrant, CreditTransaction, CreditTransactionKind,
+};
+use crate::errors::DomainError;
+
+/// Repository trait for credit wallet persistence operations
+#[async_trait]
+pub trait CreditWalletRepository: Send + Sync {
+    /// Record a new credit grant
+    ///
+    /// # Returns
+    /// * `Ok(CreditGrant)` - The saved grant
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save_grant(&self, grant: CreditGrant) -> Result<CreditGrant, DomainError>;
+
+    /// Update the remaining amount of a grant
+    async fn update_grant(&self, grant: &CreditGrant) -> Result<(), DomainError>;
+
+    /// Find a grant by the reference of its granting event
+    async fn find_grant_by_reference(&self, reference: &str) -> Result<Option<CreditGrant>, DomainError>;
+
+    /// Find a customer's grants that can still be spent at `now`
+    ///
+    /// Grants are ordered by expiry, soonest first and non-expiring last,
+    /// then by creation time.
+    async fn find_spendable_grants(
+        &self,
+        customer_id: Uuid,
+        currency: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CreditGrant>, DomainError>;
+
+    /// Find grants of any customer that expired with credit remaining
+    ///
+    /// # Arguments
+    /// * `now` - Grants expiring at or before this time are returned
+    /// * `limit` - Maximum number of grants to return
+    async fn find_expired_grants(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<CreditGrant>, DomainError>;
+
+    /// Record a change to a customer's wallet
+    async fn save_transaction(&self, transaction: CreditTransaction) -> Result<CreditTransaction, DomainError>;
+
+    /// Find a wallet transaction by kind and reference
+    async fn find_transaction(
+        &self,
+        kind: CreditTransactionKind,
+        reference: &str,
+    ) -> Result<Option<CreditTransaction>, DomainError>;
+
+    /// Find a customer's most recent wallet transactions, newest first
+    async fn find_transactions(
+        &self,
+        customer_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<CreditTransaction>, DomainError>;
+}
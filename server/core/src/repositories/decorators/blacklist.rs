@@ -0,0 +1,218 @@
+//! Access token blacklist kept outside the database
+//!
+//! Every authenticated request checks whether its access token was
+//! blacklisted. Keeping the blacklist in a store such as Redis, where an
+//! entry expires together with its token, spares each of those checks a
+//! database round trip. The wrapped repository can still be written as a
+//! persistent copy of the blacklist, which is read while the store is down.
+//! Writes that fail to reach the store are retried in the background until
+//! they land or the token expires, so a token blacklisted during an outage
+//! is still rejected once checks go back to the store.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::entities::token::RefreshToken;
+use crate::errors::DomainError;
+use crate::repositories::TokenRepository;
+
+/// Delay before the first retry of a failed store write
+const STORE_RETRY_INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between retries of a failed store write
+const STORE_RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Store holding the JWT IDs of blacklisted access tokens
+///
+/// Errors are reported as strings. Entries only need to be kept until the
+/// token expires, after which it is rejected anyway.
+#[async_trait]
+pub trait TokenBlacklistStoreTrait: Send + Sync {
+    /// Blacklist a token until it expires
+    async fn add(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), String>;
+
+    /// Whether a token is blacklisted
+    async fn contains(&self, token_jti: &str) -> Result<bool, String>;
+}
+
+/// Token repository decorator keeping the access token blacklist in a store
+///
+/// Blacklist checks and writes go to the store; every other call goes to
+/// the wrapped repository. With the persistent fallback enabled,
+/// blacklisted tokens are also written to the wrapped repository, checks
+/// are answered from it when the store fails, and failed store writes are
+/// retried until the store has them.
+pub struct TokenBlacklistRepository<R> {
+    inner: R,
+    store: Arc<dyn TokenBlacklistStoreTrait>,
+    persistent_fallback: bool,
+}
+
+impl<R: TokenRepository> TokenBlacklistRepository<R> {
+    /// Wrap a token repository, keeping the blacklist only in `store`
+    pub fn new(inner: R, store: Arc<dyn TokenBlacklistStoreTrait>) -> Self {
+        Self {
+            inner,
+            store,
+            persistent_fallback: false,
+        }
+    }
+
+    /// Also write blacklisted tokens to the wrapped repository, and read
+    /// them from it when the store fails
+    pub fn with_persistent_fallback(mut self) -> Self {
+        self.persistent_fallback = true;
+        self
+    }
+
+    /// The wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Keep retrying store writes that failed, with exponential backoff,
+    /// until each lands in the store or its token expires
+    fn retry_in_background(&self, mut pending: Vec<(String, DateTime<Utc>)>) {
+        let store = Arc::clone(&self.store);
+
+        tokio::spawn(async move {
+            let mut delay = STORE_RETRY_INITIAL_DELAY;
+            while !pending.is_empty() {
+                tokio::time::sleep(delay).await;
+
+                let mut failed = Vec::new();
+                for (token_jti, expires_at) in pending {
+                    if expires_at <= Utc::now() {
+                        continue;
+                    }
+                    if store.add(&token_jti, expires_at).await.is_err() {
+                        failed.push((token_jti, expires_at));
+                    }
+                }
+
+                pending = failed;
+                delay = (delay * 2).min(STORE_RETRY_MAX_DELAY);
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<R: TokenRepository> TokenRepository for TokenBlacklistRepository<R> {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        self.inner.save_refresh_token(token).await
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        self.inner.find_refresh_token(token_hash).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        self.inner.find_by_user_id(user_id).await
+    }
+
+    async fn find_by_token_family(&self, token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        self.inner.find_by_token_family(token_family).await
+    }
+
+    async fn revoke_token_family(&self, token_family: &str) -> Result<usize, DomainError> {
+        self.inner.revoke_token_family(token_family).await
+    }
+
+    async fn is_token_blacklisted(&self, token_jti: &str) -> Result<bool, DomainError> {
+        match self.store.contains(token_jti).await {
+            Ok(blacklisted) => Ok(blacklisted),
+            Err(e) if self.persistent_fallback => {
+                warn!(error = %e, "Token blacklist store unavailable, checking the database");
+                self.inner.is_token_blacklisted(token_jti).await
+            }
+            Err(e) => Err(DomainError::Internal {
+                message: format!("Failed to check token blacklist: {}", e),
+            }),
+        }
+    }
+
+    async fn blacklist_token(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        let stored = self.store.add(token_jti, expires_at).await;
+
+        if self.persistent_fallback {
+            self.inner.blacklist_token(token_jti, expires_at).await?;
+            if let Err(e) = stored {
+                // Checks read the database until the store is back
+                warn!(error = %e, "Failed to blacklist token in the store, retrying in the background");
+                self.retry_in_background(vec![(token_jti.to_string(), expires_at)]);
+            }
+            return Ok(());
+        }
+
+        stored.map_err(|e| DomainError::Internal {
+            message: format!("Failed to blacklist token: {}", e),
+        })
+    }
+
+    async fn revoke_token(&self, token_hash: &str) -> Result<bool, DomainError> {
+        self.inner.revoke_token(token_hash).await
+    }
+
+    async fn revoke_all_user_tokens(&self, user_id: Uuid) -> Result<usize, DomainError> {
+        self.inner.revoke_all_user_tokens(user_id).await
+    }
+
+    async fn save_refresh_tokens_batch(&self, tokens: Vec<RefreshToken>) -> Result<usize, DomainError> {
+        self.inner.save_refresh_tokens_batch(tokens).await
+    }
+
+    async fn revoke_tokens_batch(&self, user_ids: &[Uuid]) -> Result<usize, DomainError> {
+        self.inner.revoke_tokens_batch(user_ids).await
+    }
+
+    async fn blacklist_tokens_batch(&self, tokens: &[(String, DateTime<Utc>)]) -> Result<(), DomainError> {
+        let mut failure = None;
+        let mut pending = Vec::new();
+        for (token_jti, expires_at) in tokens {
+            if let Err(e) = self.store.add(token_jti, *expires_at).await {
+                failure = Some(e);
+                pending.push((token_jti.clone(), *expires_at));
+            }
+        }
+
+        if self.persistent_fallback {
+            self.inner.blacklist_tokens_batch(tokens).await?;
+            if let Some(e) = failure {
+                warn!(error = %e, "Failed to blacklist tokens in the store, retrying in the background");
+                self.retry_in_background(pending);
+            }
+            return Ok(());
+        }
+
+        match failure {
+            Some(e) => Err(DomainError::Internal {
+                message: format!("Failed to blacklist tokens: {}", e),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        self.inner.delete_expired_tokens().await
+    }
+
+    async fn archive_expired_tokens(&self) -> Result<usize, DomainError> {
+        self.inner.archive_expired_tokens().await
+    }
+
+    /// Entries in the store expire on their own; only the persistent copy
+    /// needs cleaning up
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        self.inner.cleanup_blacklist().await
+    }
+}
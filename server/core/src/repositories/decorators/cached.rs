@@ -0,0 +1,355 @@
+//! Caching of user lookups
+//!
+//! Lookups are cached in-process and, when a shared store such as Redis is
+//! attached, in the shared store as well, so a user loaded by one instance
+//! is served to every instance without reaching the database. Writes drop
+//! the user from both caches. Entries are kept per tenant, so a lookup never
+//! returns a user of another marketplace.
+//!
+//! Concurrent misses for the same user are coalesced into a single lookup,
+//! and hot users can be served stale while one request refreshes them, so
+//! an expiring entry does not send every waiting request to the database.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::stream::BoxStream;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::UserRepository;
+use crate::services::singleflight::SingleFlight;
+use crate::services::tenant::{current_tenant, TenantId};
+
+/// Configuration for caching repository lookups
+#[derive(Debug, Clone)]
+pub struct RepositoryCacheConfig {
+    /// How long a lookup is reused (in seconds)
+    pub ttl_seconds: i64,
+    /// Number of cached entries above which expired ones are dropped
+    pub max_entries: usize,
+    /// How long a lookup is kept in the shared store (in seconds)
+    pub shared_ttl_seconds: u64,
+    /// How long after expiring a lookup is still served while another
+    /// request refreshes it (in seconds); 0 to always wait for the refresh
+    pub stale_seconds: i64,
+}
+
+impl Default for RepositoryCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 30,
+            max_entries: 10_000,
+            shared_ttl_seconds: 300,
+            stale_seconds: 0,
+        }
+    }
+}
+
+/// Store sharing cached users between instances
+///
+/// Errors are reported as strings; the cache treats a failing store as
+/// empty and falls back to the wrapped repository. Stores keep users per
+/// tenant, the tenant being [`current_tenant`] of the call.
+#[async_trait]
+pub trait UserCacheStoreTrait: Send + Sync {
+    /// Get a cached user by ID
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<User>, String>;
+
+    /// Get a cached user by phone number hash and country code
+    async fn get_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, String>;
+
+    /// Cache a user under its ID and phone number
+    async fn put(&self, user: &User, ttl_seconds: u64) -> Result<(), String>;
+
+    /// Drop a cached user, under whichever key
+    async fn remove(&self, id: Uuid) -> Result<(), String>;
+}
+
+/// Cache hit and miss counts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepositoryCacheMetrics {
+    /// Lookups served from the cache
+    pub hits: u64,
+    /// Lookups passed to the inner repository
+    pub misses: u64,
+    /// Hits served from the shared store rather than in-process
+    pub shared_hits: u64,
+    /// Hits served an expired entry while it was being refreshed
+    pub stale_hits: u64,
+    /// Misses served the result of a concurrent miss for the same user
+    pub coalesced: u64,
+}
+
+/// Key of a cached lookup
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Id { tenant: TenantId, id: Uuid },
+    Phone { tenant: TenantId, phone_hash: String, country_code: String },
+}
+
+impl CacheKey {
+    /// Key of a lookup by ID for the current tenant
+    fn id(id: Uuid) -> Self {
+        Self::Id { tenant: current_tenant(), id }
+    }
+
+    /// Key of a lookup by phone for the current tenant
+    fn phone(phone_hash: &str, country_code: &str) -> Self {
+        Self::Phone {
+            tenant: current_tenant(),
+            phone_hash: phone_hash.to_string(),
+            country_code: country_code.to_string(),
+        }
+    }
+}
+
+struct CachedUser {
+    user: User,
+    valid_until: DateTime<Utc>,
+}
+
+/// An in-process entry found for a lookup
+enum LocalEntry {
+    Fresh(User),
+    /// Expired but within the stale window
+    Stale(User),
+}
+
+/// User repository decorator that caches lookups by ID and phone
+///
+/// Only users that were found are cached, and every write through this
+/// repository drops the cached entries of the user, in-process and in the
+/// shared store. Other instances keep their in-process entries until they
+/// expire, so keep the in-process TTL short.
+pub struct CachedUserRepository<R> {
+    inner: R,
+    config: RepositoryCacheConfig,
+    entries: RwLock<HashMap<CacheKey, CachedUser>>,
+    shared: Option<Arc<dyn UserCacheStoreTrait>>,
+    lookups: SingleFlight<CacheKey, Option<User>, DomainError>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    shared_hits: AtomicU64,
+    stale_hits: AtomicU64,
+}
+
+impl<R: UserRepository> CachedUserRepository<R> {
+    /// Wrap a user repository
+    pub fn new(inner: R, config: RepositoryCacheConfig) -> Self {
+        Self {
+            inner,
+            config,
+            entries: RwLock::new(HashMap::new()),
+            shared: None,
+            lookups: SingleFlight::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            shared_hits: AtomicU64::new(0),
+            stale_hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Share cached users with other instances through a store
+    pub fn with_shared_cache(mut self, store: Arc<dyn UserCacheStoreTrait>) -> Self {
+        self.shared = Some(store);
+        self
+    }
+
+    /// The wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Cache hit and miss counts so far
+    pub fn metrics(&self) -> RepositoryCacheMetrics {
+        RepositoryCacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            shared_hits: self.shared_hits.load(Ordering::Relaxed),
+            stale_hits: self.stale_hits.load(Ordering::Relaxed),
+            coalesced: self.lookups.coalesced(),
+        }
+    }
+
+    /// Look a user up in-process, then in the shared store, then with `fetch`
+    ///
+    /// A stale entry is served while another lookup of the same key
+    /// refreshes it; otherwise this lookup refreshes it. Concurrent lookups
+    /// missing the same key share one refresh.
+    async fn lookup<F>(&self, key: CacheKey, fetch: F) -> Result<Option<User>, DomainError>
+    where
+        F: Future<Output = Result<Option<User>, DomainError>>,
+    {
+        match self.get_local(&key) {
+            Some(LocalEntry::Fresh(user)) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(user));
+            }
+            Some(LocalEntry::Stale(user)) if self.lookups.is_in_flight(&key) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                self.stale_hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(Some(user));
+            }
+            _ => {}
+        }
+
+        self.lookups.run(key.clone(), self.refresh(&key, fetch)).await
+    }
+
+    /// Load a user from the shared store or, failing that, with `fetch`
+    async fn refresh<F>(&self, key: &CacheKey, fetch: F) -> Result<Option<User>, DomainError>
+    where
+        F: Future<Output = Result<Option<User>, DomainError>>,
+    {
+        if let Some(user) = self.get_shared(key).await {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            self.shared_hits.fetch_add(1, Ordering::Relaxed);
+            self.insert_local(&user);
+            return Ok(Some(user));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let user = fetch.await?;
+        if let Some(ref user) = user {
+            self.insert(user).await;
+        }
+        Ok(user)
+    }
+
+    fn get_local(&self, key: &CacheKey) -> Option<LocalEntry> {
+        let entries = self.entries.read().ok()?;
+        let entry = entries.get(key)?;
+
+        let now = Utc::now();
+        if entry.valid_until > now {
+            Some(LocalEntry::Fresh(entry.user.clone()))
+        } else if entry.valid_until + Duration::seconds(self.config.stale_seconds) > now {
+            Some(LocalEntry::Stale(entry.user.clone()))
+        } else {
+            None
+        }
+    }
+
+    async fn get_shared(&self, key: &CacheKey) -> Option<User> {
+        let store = self.shared.as_ref()?;
+        let result = match key {
+            CacheKey::Id { id, .. } => store.get_by_id(*id).await,
+            CacheKey::Phone { phone_hash, country_code, .. } => store.get_by_phone(phone_hash, country_code).await,
+        };
+
+        result.unwrap_or_else(|e| {
+            warn!(error = %e, "Shared user cache lookup failed");
+            None
+        })
+    }
+
+    async fn insert(&self, user: &User) {
+        self.insert_local(user);
+
+        let Some(store) = self.shared.as_ref() else {
+            return;
+        };
+        if self.config.shared_ttl_seconds == 0 {
+            return;
+        }
+        if let Err(e) = store.put(user, self.config.shared_ttl_seconds).await {
+            warn!(user_id = %user.id, error = %e, "Failed to share cached user");
+        }
+    }
+
+    fn insert_local(&self, user: &User) {
+        if self.config.ttl_seconds <= 0 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.write() else {
+            return;
+        };
+
+        let now = Utc::now();
+        if entries.len() >= self.config.max_entries {
+            let stale = Duration::seconds(self.config.stale_seconds);
+            entries.retain(|_, entry| entry.valid_until + stale > now);
+        }
+
+        let valid_until = now + Duration::seconds(self.config.ttl_seconds);
+        let keys = [CacheKey::id(user.id), CacheKey::phone(&user.phone_hash, &user.country_code)];
+        for key in keys {
+            entries.insert(key, CachedUser { user: user.clone(), valid_until });
+        }
+    }
+
+    /// Drop every cached entry of a user, under whichever key
+    async fn invalidate(&self, id: Uuid) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|_, entry| entry.user.id != id);
+        }
+
+        if let Some(store) = self.shared.as_ref() {
+            if let Err(e) = store.remove(id).await {
+                warn!(user_id = %id, error = %e, "Failed to drop user from shared cache");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: UserRepository> UserRepository for CachedUserRepository<R> {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        let key = CacheKey::phone(phone_hash, country_code);
+        self.lookup(key, self.inner.find_by_phone(phone_hash, country_code)).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.lookup(CacheKey::id(id), self.inner.find_by_id(id)).await
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.inner.create(user).await
+    }
+
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        self.inner.create_many(users).await
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        let id = user.id;
+        let result = self.inner.update(user).await;
+        self.invalidate(id).await;
+        result
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = self.inner.delete(id).await;
+        self.invalidate(id).await;
+        result
+    }
+
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.inner.find_including_deleted(id).await
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = self.inner.restore(id).await;
+        self.invalidate(id).await;
+        result
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        Ok(self.find_by_phone(phone_hash, country_code).await?.is_some())
+    }
+
+    async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError> {
+        self.inner.count_by_type(user_type).await
+    }
+
+    fn find_all_stream(&self, user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        self.inner.find_all_stream(user_type)
+    }
+}
@@ -0,0 +1,493 @@
+//! Tracing, metrics and retries around any repository
+//!
+//! Every call runs in a `repository` span recording the repository, the
+//! operation, its duration and the rows it returned or affected. Calls
+//! slower than the slow-call threshold are logged as warnings.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use tracing::{debug, field, warn, Instrument, Span};
+use uuid::Uuid;
+
+use re_shared::types::Pagination;
+
+use crate::domain::entities::audit::{AuditEventType, AuditLog, AuditLogFilter};
+use crate::domain::entities::token::RefreshToken;
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::{AuditLogRepository, TokenRepository, UserRepository};
+
+/// Retry policy for failed reads
+///
+/// Only reads are retried: a write that failed may still have been applied,
+/// and repeating it could apply it twice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per read, the first one included
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further one
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+        }
+    }
+
+    /// Delay before the retry following the given attempt
+    fn delay_after(&self, attempt: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+}
+
+/// Default duration above which a call is logged as slow
+pub const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Rows a repository call returned or affected, as recorded in traces
+trait RowCount {
+    fn row_count(&self) -> Option<u64>;
+}
+
+impl RowCount for () {
+    fn row_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+impl RowCount for bool {
+    fn row_count(&self) -> Option<u64> {
+        Some(u64::from(*self))
+    }
+}
+
+impl RowCount for usize {
+    fn row_count(&self) -> Option<u64> {
+        Some(*self as u64)
+    }
+}
+
+impl RowCount for u64 {
+    fn row_count(&self) -> Option<u64> {
+        Some(*self)
+    }
+}
+
+impl RowCount for User {
+    fn row_count(&self) -> Option<u64> {
+        Some(1)
+    }
+}
+
+impl RowCount for RefreshToken {
+    fn row_count(&self) -> Option<u64> {
+        Some(1)
+    }
+}
+
+impl<T> RowCount for Option<T> {
+    fn row_count(&self) -> Option<u64> {
+        Some(u64::from(self.is_some()))
+    }
+}
+
+impl<T> RowCount for Vec<T> {
+    fn row_count(&self) -> Option<u64> {
+        Some(self.len() as u64)
+    }
+}
+
+/// A page of results and the total it was taken from
+impl<T> RowCount for (Vec<T>, u64) {
+    fn row_count(&self) -> Option<u64> {
+        self.0.row_count()
+    }
+}
+
+/// Metrics of one repository operation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// Number of calls
+    pub calls: u64,
+    /// Calls that failed, after any retries
+    pub errors: u64,
+    /// Retries made
+    pub retries: u64,
+    /// Total duration of all calls, retries included, in milliseconds
+    pub total_duration_ms: u64,
+    /// Duration of the slowest call, in milliseconds
+    pub max_duration_ms: u64,
+    /// Calls slower than the slow-call threshold
+    pub slow_calls: u64,
+    /// Rows returned or affected by all successful calls
+    pub rows: u64,
+}
+
+impl OperationMetrics {
+    /// Average duration of a call, in milliseconds
+    pub fn average_duration_ms(&self) -> u64 {
+        self.total_duration_ms.checked_div(self.calls).unwrap_or(0)
+    }
+}
+
+/// Repository decorator that traces calls, records metrics and retries reads
+///
+/// Implements every repository trait its inner repository implements. Reads
+/// failing with an internal error, such as a lost database connection, are
+/// retried according to the retry policy.
+pub struct InstrumentedRepository<R> {
+    inner: R,
+    name: &'static str,
+    retry_policy: RetryPolicy,
+    slow_threshold: Duration,
+    metrics: Mutex<HashMap<&'static str, OperationMetrics>>,
+}
+
+impl<R> InstrumentedRepository<R> {
+    /// Wrap a repository, naming it in traces
+    pub fn new(inner: R, name: &'static str) -> Self {
+        Self {
+            inner,
+            name,
+            retry_policy: RetryPolicy::default(),
+            slow_threshold: DEFAULT_SLOW_THRESHOLD,
+            metrics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the retry policy for failed reads
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the duration above which a call is logged as slow, such as the
+    /// database's `slow_query_threshold`
+    pub fn with_slow_threshold(mut self, slow_threshold: Duration) -> Self {
+        self.slow_threshold = slow_threshold;
+        self
+    }
+
+    /// The wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Metrics of each operation called so far
+    pub fn metrics(&self) -> HashMap<&'static str, OperationMetrics> {
+        self.metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .unwrap_or_default()
+    }
+
+    /// Run a read, retrying it if it fails with an internal error
+    async fn read<T, F, Fut>(&self, operation: &'static str, mut call: F) -> Result<T, DomainError>
+    where
+        T: RowCount,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, DomainError>>,
+    {
+        let span = self.span(operation);
+        async {
+            let started = Instant::now();
+            let mut attempt = 1;
+            let result = loop {
+                match call().await {
+                    Err(e @ DomainError::Internal { .. }) if attempt < self.retry_policy.max_attempts => {
+                        warn!(attempt, error = %e, "Repository read failed, retrying");
+                        tokio::time::sleep(self.retry_policy.delay_after(attempt)).await;
+                        attempt += 1;
+                    }
+                    result => break result,
+                }
+            };
+            self.record(operation, started.elapsed(), attempt - 1, &result);
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Run a write, which is never retried
+    async fn write<T, Fut>(&self, operation: &'static str, call: Fut) -> Result<T, DomainError>
+    where
+        T: RowCount,
+        Fut: Future<Output = Result<T, DomainError>>,
+    {
+        let span = self.span(operation);
+        async {
+            let started = Instant::now();
+            let result = call.await;
+            self.record(operation, started.elapsed(), 0, &result);
+            result
+        }
+        .instrument(span)
+        .await
+    }
+
+    fn span(&self, operation: &'static str) -> Span {
+        tracing::info_span!(
+            "repository",
+            repository = self.name,
+            operation,
+            duration_ms = field::Empty,
+            rows = field::Empty,
+        )
+    }
+
+    /// Record a finished call in the current span, the log and the metrics
+    fn record<T: RowCount>(
+        &self,
+        operation: &'static str,
+        elapsed: Duration,
+        retries: u32,
+        result: &Result<T, DomainError>,
+    ) {
+        let duration_ms = elapsed.as_millis() as u64;
+        let rows = result.as_ref().ok().and_then(RowCount::row_count);
+
+        let span = Span::current();
+        span.record("duration_ms", duration_ms);
+        if let Some(rows) = rows {
+            span.record("rows", rows);
+        }
+
+        match result {
+            Ok(_) => debug!(duration_ms, retries, rows, "Repository call succeeded"),
+            Err(e) => warn!(duration_ms, retries, error = %e, "Repository call failed"),
+        }
+        let slow = elapsed > self.slow_threshold;
+        if slow {
+            warn!(
+                repository = self.name,
+                operation,
+                duration_ms,
+                threshold_ms = self.slow_threshold.as_millis() as u64,
+                rows,
+                "Slow repository call"
+            );
+        }
+
+        if let Ok(mut metrics) = self.metrics.lock() {
+            let entry = metrics.entry(operation).or_default();
+            entry.calls += 1;
+            entry.retries += u64::from(retries);
+            entry.total_duration_ms += duration_ms;
+            entry.max_duration_ms = entry.max_duration_ms.max(duration_ms);
+            entry.rows += rows.unwrap_or(0);
+            if slow {
+                entry.slow_calls += 1;
+            }
+            if result.is_err() {
+                entry.errors += 1;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: UserRepository> UserRepository for InstrumentedRepository<R> {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        self.read("find_by_phone", || self.inner.find_by_phone(phone_hash, country_code)).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.read("find_by_id", || self.inner.find_by_id(id)).await
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.write("create", self.inner.create(user)).await
+    }
+
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        self.write("create_many", self.inner.create_many(users)).await
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        self.write("update", self.inner.update(user)).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        self.write("delete", self.inner.delete(id)).await
+    }
+
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.read("find_including_deleted", || self.inner.find_including_deleted(id)).await
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        self.write("restore", self.inner.restore(id)).await
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        self.read("exists_by_phone", || self.inner.exists_by_phone(phone_hash, country_code)).await
+    }
+
+    async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError> {
+        self.read("count_by_type", || self.inner.count_by_type(user_type)).await
+    }
+
+    fn find_all_stream(&self, user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        // Streams last as long as the job reading them, so they are not timed
+        self.inner.find_all_stream(user_type)
+    }
+}
+
+#[async_trait]
+impl<R: TokenRepository> TokenRepository for InstrumentedRepository<R> {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        self.write("save_refresh_token", self.inner.save_refresh_token(token)).await
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        self.read("find_refresh_token", || self.inner.find_refresh_token(token_hash)).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        self.read("find_by_id", || self.inner.find_by_id(id)).await
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        self.read("find_by_user_id", || self.inner.find_by_user_id(user_id)).await
+    }
+
+    async fn find_by_token_family(&self, token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        self.read("find_by_token_family", || self.inner.find_by_token_family(token_family)).await
+    }
+
+    async fn revoke_token_family(&self, token_family: &str) -> Result<usize, DomainError> {
+        self.write("revoke_token_family", self.inner.revoke_token_family(token_family)).await
+    }
+
+    async fn is_token_blacklisted(&self, token_jti: &str) -> Result<bool, DomainError> {
+        self.read("is_token_blacklisted", || self.inner.is_token_blacklisted(token_jti)).await
+    }
+
+    async fn blacklist_token(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        self.write("blacklist_token", self.inner.blacklist_token(token_jti, expires_at)).await
+    }
+
+    async fn revoke_token(&self, token_hash: &str) -> Result<bool, DomainError> {
+        self.write("revoke_token", self.inner.revoke_token(token_hash)).await
+    }
+
+    async fn revoke_all_user_tokens(&self, user_id: Uuid) -> Result<usize, DomainError> {
+        self.write("revoke_all_user_tokens", self.inner.revoke_all_user_tokens(user_id)).await
+    }
+
+    async fn save_refresh_tokens_batch(&self, tokens: Vec<RefreshToken>) -> Result<usize, DomainError> {
+        self.write("save_refresh_tokens_batch", self.inner.save_refresh_tokens_batch(tokens)).await
+    }
+
+    async fn revoke_tokens_batch(&self, user_ids: &[Uuid]) -> Result<usize, DomainError> {
+        self.write("revoke_tokens_batch", self.inner.revoke_tokens_batch(user_ids)).await
+    }
+
+    async fn blacklist_tokens_batch(&self, tokens: &[(String, DateTime<Utc>)]) -> Result<(), DomainError> {
+        self.write("blacklist_tokens_batch", self.inner.blacklist_tokens_batch(tokens)).await
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        self.write("delete_expired_tokens", self.inner.delete_expired_tokens()).await
+    }
+
+    async fn archive_expired_tokens(&self) -> Result<usize, DomainError> {
+        self.write("archive_expired_tokens", self.inner.archive_expired_tokens()).await
+    }
+
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        self.write("cleanup_blacklist", self.inner.cleanup_blacklist()).await
+    }
+}
+
+#[async_trait]
+impl<R: AuditLogRepository> AuditLogRepository for InstrumentedRepository<R> {
+    async fn create(&self, audit_log: &AuditLog) -> Result<(), DomainError> {
+        self.write("create", self.inner.create(audit_log)).await
+    }
+
+    async fn find_by_user(&self, user_id: Uuid, limit: usize) -> Result<Vec<AuditLog>, DomainError> {
+        self.read("find_by_user", || self.inner.find_by_user(user_id, limit)).await
+    }
+
+    async fn find_by_phone_hash(&self, phone_hash: &str, limit: usize) -> Result<Vec<AuditLog>, DomainError> {
+        self.read("find_by_phone_hash", || self.inner.find_by_phone_hash(phone_hash, limit)).await
+    }
+
+    async fn count_failed_attempts(
+        &self,
+        action: &str,
+        phone_hash: Option<&str>,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<usize, DomainError> {
+        self.read("count_failed_attempts", || {
+            self.inner.count_failed_attempts(action, phone_hash, ip_address, since)
+        })
+        .await
+    }
+
+    async fn find_suspicious_activity(
+        &self,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        self.read("find_suspicious_activity", || self.inner.find_suspicious_activity(ip_address, since)).await
+    }
+
+    async fn archive_old_logs(&self) -> Result<usize, DomainError> {
+        self.write("archive_old_logs", self.inner.archive_old_logs()).await
+    }
+
+    async fn delete_archived_logs(&self) -> Result<usize, DomainError> {
+        self.write("delete_archived_logs", self.inner.delete_archived_logs()).await
+    }
+
+    async fn archive_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        self.write("archive_logs_before", self.inner.archive_logs_before(before, limit)).await
+    }
+
+    async fn delete_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        self.write("delete_logs_before", self.inner.delete_logs_before(before, limit)).await
+    }
+
+    async fn find_by_event_types(
+        &self,
+        event_types: Vec<AuditEventType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        self.read("find_by_event_types", || {
+            self.inner.find_by_event_types(event_types.clone(), from, to, limit)
+        })
+        .await
+    }
+
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        self.read("query", || self.inner.query(filter, pagination)).await
+    }
+}
@@ -0,0 +1,25 @@
+//! Repository decorators for cross-cutting concerns
+//!
+//! Each decorator wraps any implementation of a repository trait and
+//! implements the same trait, so decorators stack and services take them
+//! in place of the MySQL repositories:
+//!
+//! - `InstrumentedRepository` traces calls with their duration and row count,
+//!   logs slow calls, records per-operation metrics and retries failed reads
+//! - `CachedUserRepository` caches user lookups in-process and, optionally,
+//!   in a store shared between instances
+//! - `TokenBlacklistRepository` keeps the access token blacklist in a store
+//!   such as Redis, optionally with a persistent copy in the database
+
+mod blacklist;
+mod cached;
+mod instrumented;
+
+#[cfg(test)]
+mod tests;
+
+pub use blacklist::{TokenBlacklistRepository, TokenBlacklistStoreTrait};
+pub use cached::{
+    CachedUserRepository, RepositoryCacheConfig, RepositoryCacheMetrics, UserCacheStoreTrait,
+};
+pub use instrumented::{InstrumentedRepository, OperationMetrics, RetryPolicy, DEFAULT_SLOW_THRESHOLD};
@@ -0,0 +1,86 @@
+//! Tests for the token blacklist decorator
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+
+use crate::repositories::decorators::TokenBlacklistRepository;
+use crate::repositories::TokenRepository;
+
+use super::mocks::{BlacklistTokenRepository, MemoryBlacklistStore};
+
+#[tokio::test]
+async fn test_blacklist_is_kept_in_the_store() {
+    let store = Arc::new(MemoryBlacklistStore::default());
+    let repository = TokenBlacklistRepository::new(BlacklistTokenRepository::default(), store.clone());
+
+    repository
+        .blacklist_token("jti-1", Utc::now() + Duration::minutes(15))
+        .await
+        .unwrap();
+    repository
+        .blacklist_token("jti-expired", Utc::now() - Duration::minutes(1))
+        .await
+        .unwrap();
+
+    assert!(repository.is_token_blacklisted("jti-1").await.unwrap());
+    assert!(!repository.is_token_blacklisted("jti-2").await.unwrap());
+    assert!(!repository.is_token_blacklisted("jti-expired").await.unwrap());
+    assert!(!repository.inner().contains("jti-1"));
+    assert_eq!(repository.inner().checks.load(Ordering::SeqCst), 0);
+
+    store.unavailable.store(true, Ordering::SeqCst);
+    assert!(repository.is_token_blacklisted("jti-1").await.is_err());
+    assert!(repository
+        .blacklist_token("jti-3", Utc::now() + Duration::minutes(15))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_persistent_fallback_answers_while_the_store_is_down() {
+    let store = Arc::new(MemoryBlacklistStore::default());
+    let repository = TokenBlacklistRepository::new(BlacklistTokenRepository::default(), store.clone())
+        .with_persistent_fallback();
+    let expires_at = Utc::now() + Duration::minutes(15);
+
+    repository.blacklist_token("jti-1", expires_at).await.unwrap();
+    assert!(repository.inner().contains("jti-1"));
+    assert!(repository.is_token_blacklisted("jti-1").await.unwrap());
+    assert_eq!(repository.inner().checks.load(Ordering::SeqCst), 0);
+
+    store.unavailable.store(true, Ordering::SeqCst);
+    repository
+        .blacklist_tokens_batch(&[("jti-2".to_string(), expires_at)])
+        .await
+        .unwrap();
+    assert!(repository.is_token_blacklisted("jti-1").await.unwrap());
+    assert!(repository.is_token_blacklisted("jti-2").await.unwrap());
+    assert_eq!(repository.inner().checks.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_tokens_blacklisted_during_an_outage_reach_the_store_once_it_recovers() {
+    let store = Arc::new(MemoryBlacklistStore::default());
+    let repository = TokenBlacklistRepository::new(BlacklistTokenRepository::default(), store.clone())
+        .with_persistent_fallback();
+    let expires_at = Utc::now() + Duration::minutes(15);
+
+    store.unavailable.store(true, Ordering::SeqCst);
+    repository.blacklist_token("jti-1", expires_at).await.unwrap();
+    repository
+        .blacklist_tokens_batch(&[("jti-2".to_string(), expires_at)])
+        .await
+        .unwrap();
+
+    // Still down at the first retry
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    store.unavailable.store(false, Ordering::SeqCst);
+    tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+
+    // Answered by the store again, without reading the database
+    assert!(repository.is_token_blacklisted("jti-1").await.unwrap());
+    assert!(repository.is_token_blacklisted("jti-2").await.unwrap());
+    assert_eq!(repository.inner().checks.load(Ordering::SeqCst), 0);
+}
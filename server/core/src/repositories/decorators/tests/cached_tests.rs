@@ -0,0 +1,270 @@
+//! Tests for the cached user repository decorator
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::entities::user::User;
+use crate::repositories::decorators::{CachedUserRepository, RepositoryCacheConfig, UserCacheStoreTrait};
+use crate::repositories::UserRepository;
+use crate::services::tenant::{self, TenantId};
+
+use super::mocks::{FlakyUserRepository, MemoryUserCacheStore};
+
+#[tokio::test]
+async fn test_lookups_served_from_cache() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+
+    repository.find_by_phone("hash", "+61").await.unwrap();
+    let by_id = repository.find_by_id(user.id).await.unwrap();
+    let by_phone = repository.find_by_phone("hash", "+61").await.unwrap();
+
+    assert_eq!(by_id.map(|u| u.id), Some(user.id));
+    assert_eq!(by_phone.map(|u| u.id), Some(user.id));
+    assert_eq!(repository.inner().calls(), 1);
+    let metrics = repository.metrics();
+    assert_eq!((metrics.hits, metrics.misses), (2, 1));
+}
+
+#[tokio::test]
+async fn test_lookups_are_cached_per_tenant() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+
+    repository.find_by_id(user.id).await.unwrap();
+    let brand = TenantId::parse("brand-a").unwrap();
+    tenant::scope(brand.clone(), async {
+        repository.find_by_id(user.id).await.unwrap();
+        repository.find_by_phone("hash", "+61").await.unwrap();
+    })
+    .await;
+    assert_eq!(repository.inner().calls(), 2);
+
+    tenant::scope(brand, repository.find_by_id(user.id)).await.unwrap();
+    repository.find_by_phone("hash", "+61").await.unwrap();
+    assert_eq!(repository.inner().calls(), 2);
+}
+
+#[tokio::test]
+async fn test_missing_users_are_not_cached() {
+    let repository = CachedUserRepository::new(FlakyUserRepository::default(), RepositoryCacheConfig::default());
+
+    assert!(repository.find_by_phone("hash", "+61").await.unwrap().is_none());
+    let user = repository.create(User::new("hash".to_string(), "+61".to_string())).await.unwrap();
+
+    let found = repository.find_by_phone("hash", "+61").await.unwrap();
+    assert_eq!(found.map(|u| u.id), Some(user.id));
+}
+
+#[tokio::test]
+async fn test_update_drops_entries_under_old_phone_hash() {
+    let user = User::new("old-hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+    repository.find_by_phone("old-hash", "+61").await.unwrap();
+
+    let mut rehashed = user.clone();
+    rehashed.phone_hash = "new-hash".to_string();
+    repository.update(rehashed).await.unwrap();
+
+    assert!(repository.find_by_phone("old-hash", "+61").await.unwrap().is_none());
+    let found = repository.find_by_id(user.id).await.unwrap().unwrap();
+    assert_eq!(found.phone_hash, "new-hash");
+}
+
+#[tokio::test]
+async fn test_deleted_users_are_not_served_from_cache() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert!(repository.delete(user.id).await.unwrap());
+    assert!(repository.find_by_id(user.id).await.unwrap().is_none());
+    assert!(repository.find_by_phone("hash", "+61").await.unwrap().is_none());
+    let deleted = repository.find_including_deleted(user.id).await.unwrap().unwrap();
+    assert!(deleted.is_deleted());
+
+    assert!(repository.restore(user.id).await.unwrap());
+    assert!(!repository.restore(user.id).await.unwrap());
+    let restored = repository.find_by_id(user.id).await.unwrap().unwrap();
+    assert!(!restored.is_deleted());
+}
+
+#[tokio::test]
+async fn test_zero_ttl_disables_caching() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig {
+            ttl_seconds: 0,
+            ..RepositoryCacheConfig::default()
+        },
+    );
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(repository.inner().calls(), 2);
+}
+
+/// A cache without an in-process layer, so every lookup reaches the shared store
+fn shared_only(inner: FlakyUserRepository, store: &Arc<MemoryUserCacheStore>) -> CachedUserRepository<FlakyUserRepository> {
+    CachedUserRepository::new(
+        inner,
+        RepositoryCacheConfig {
+            ttl_seconds: 0,
+            ..RepositoryCacheConfig::default()
+        },
+    )
+    .with_shared_cache(store.clone())
+}
+
+#[tokio::test]
+async fn test_shared_cache_serves_other_instances() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let store = Arc::new(MemoryUserCacheStore::default());
+    let loading = shared_only(FlakyUserRepository::with_user(user.clone()), &store);
+    let other = shared_only(FlakyUserRepository::default(), &store);
+
+    loading.find_by_id(user.id).await.unwrap();
+    let by_id = other.find_by_id(user.id).await.unwrap();
+    let by_phone = other.find_by_phone("hash", "+61").await.unwrap();
+
+    assert_eq!(by_id.map(|u| u.id), Some(user.id));
+    assert_eq!(by_phone.map(|u| u.id), Some(user.id));
+    assert_eq!(other.inner().calls(), 0);
+    assert_eq!(other.metrics().shared_hits, 2);
+}
+
+#[tokio::test]
+async fn test_writes_drop_user_from_shared_cache() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let store = Arc::new(MemoryUserCacheStore::default());
+    let repository = shared_only(FlakyUserRepository::with_user(user.clone()), &store);
+    repository.find_by_id(user.id).await.unwrap();
+
+    let mut verified = user.clone();
+    verified.verify();
+    repository.update(verified).await.unwrap();
+    assert!(store.get_by_id(user.id).await.unwrap().is_none());
+
+    let found = repository.find_by_id(user.id).await.unwrap().unwrap();
+    assert!(found.is_verified);
+    repository.delete(user.id).await.unwrap();
+    assert!(repository.find_by_phone("hash", "+61").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_unavailable_shared_cache_falls_back_to_repository() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let store = Arc::new(MemoryUserCacheStore::default());
+    store.unavailable.store(true, Ordering::SeqCst);
+    let repository = shared_only(FlakyUserRepository::with_user(user.clone()), &store);
+
+    let found = repository.find_by_id(user.id).await.unwrap();
+    assert_eq!(found.map(|u| u.id), Some(user.id));
+    assert!(repository.delete(user.id).await.unwrap());
+    assert_eq!(repository.metrics().misses, 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_misses_share_one_lookup() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+    repository.inner().set_latency(Duration::from_millis(20));
+
+    let (first, second, third) = tokio::join!(
+        repository.find_by_id(user.id),
+        repository.find_by_id(user.id),
+        repository.find_by_id(user.id),
+    );
+
+    for found in [first, second, third] {
+        assert_eq!(found.unwrap().map(|u| u.id), Some(user.id));
+    }
+    assert_eq!(repository.inner().calls(), 1);
+    let metrics = repository.metrics();
+    assert_eq!((metrics.misses, metrics.coalesced), (1, 2));
+}
+
+#[tokio::test]
+async fn test_concurrent_misses_share_a_failed_lookup() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig::default(),
+    );
+    repository.inner().set_latency(Duration::from_millis(20));
+    repository.inner().fail_next(1);
+
+    let (first, second) = tokio::join!(repository.find_by_id(user.id), repository.find_by_id(user.id));
+
+    assert!(first.is_err() && second.is_err());
+    assert_eq!(repository.inner().calls(), 1);
+    assert!(repository.find_by_id(user.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_stale_entry_served_while_refreshing() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig {
+            ttl_seconds: 1,
+            stale_seconds: 60,
+            ..RepositoryCacheConfig::default()
+        },
+    );
+    repository.find_by_id(user.id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+    repository.inner().set_latency(Duration::from_millis(50));
+
+    // The first lookup refreshes the entry; the second is served it stale
+    let refreshing = repository.find_by_id(user.id);
+    let stale = async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        repository.find_by_id(user.id).await
+    };
+    let (refreshed, stale) = tokio::join!(refreshing, stale);
+
+    assert_eq!(refreshed.unwrap().map(|u| u.id), Some(user.id));
+    assert_eq!(stale.unwrap().map(|u| u.id), Some(user.id));
+    assert_eq!(repository.inner().calls(), 2);
+    assert_eq!(repository.metrics().stale_hits, 1);
+}
+
+#[tokio::test]
+async fn test_stale_entry_is_refreshed_when_no_refresh_is_running() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = CachedUserRepository::new(
+        FlakyUserRepository::with_user(user.clone()),
+        RepositoryCacheConfig {
+            ttl_seconds: 1,
+            stale_seconds: 60,
+            ..RepositoryCacheConfig::default()
+        },
+    );
+    repository.find_by_id(user.id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1_100)).await;
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(repository.inner().calls(), 2);
+    assert_eq!(repository.metrics().stale_hits, 0);
+}
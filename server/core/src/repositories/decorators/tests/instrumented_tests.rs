@@ -0,0 +1,97 @@
+//! Tests for the instrumented repository decorator
+
+use std::time::Duration;
+
+use crate::domain::entities::user::User;
+use crate::errors::DomainError;
+use crate::repositories::decorators::{InstrumentedRepository, RetryPolicy};
+use crate::repositories::UserRepository;
+
+use super::mocks::FlakyUserRepository;
+
+fn fast_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(1),
+    }
+}
+
+#[tokio::test]
+async fn test_failed_read_is_retried() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = InstrumentedRepository::new(FlakyUserRepository::with_user(user.clone()), "users")
+        .with_retry_policy(fast_retries(3));
+    repository.inner().fail_next(2);
+
+    let found = repository.find_by_id(user.id).await.unwrap();
+
+    assert_eq!(found.map(|u| u.id), Some(user.id));
+    assert_eq!(repository.inner().calls(), 3);
+    let metrics = &repository.metrics()["find_by_id"];
+    assert_eq!(metrics.calls, 1);
+    assert_eq!(metrics.retries, 2);
+    assert_eq!(metrics.errors, 0);
+}
+
+#[tokio::test]
+async fn test_read_fails_once_attempts_run_out() {
+    let repository = InstrumentedRepository::new(FlakyUserRepository::default(), "users")
+        .with_retry_policy(fast_retries(2));
+    repository.inner().fail_next(5);
+
+    let result = repository.find_by_phone("hash", "+61").await;
+
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert_eq!(repository.inner().calls(), 2);
+    let metrics = &repository.metrics()["find_by_phone"];
+    assert_eq!(metrics.errors, 1);
+    assert_eq!(metrics.retries, 1);
+}
+
+#[tokio::test]
+async fn test_failed_write_is_not_retried() {
+    let repository = InstrumentedRepository::new(FlakyUserRepository::default(), "users")
+        .with_retry_policy(fast_retries(3));
+    repository.inner().fail_next(1);
+
+    let result = repository.create(User::new("hash".to_string(), "+61".to_string())).await;
+
+    assert!(result.is_err());
+    assert_eq!(repository.inner().calls(), 1);
+    assert_eq!(repository.metrics()["create"].errors, 1);
+}
+
+#[tokio::test]
+async fn test_not_found_is_not_retried() {
+    let repository = InstrumentedRepository::new(FlakyUserRepository::default(), "users")
+        .with_retry_policy(fast_retries(3));
+
+    let result = repository.update(User::new("hash".to_string(), "+61".to_string())).await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    assert_eq!(repository.inner().calls(), 1);
+}
+
+#[tokio::test]
+async fn test_rows_and_slow_calls_are_recorded() {
+    let user = User::new("hash".to_string(), "+61".to_string());
+    let repository = InstrumentedRepository::new(FlakyUserRepository::with_user(user.clone()), "users")
+        .with_slow_threshold(Duration::ZERO);
+
+    repository.find_by_id(user.id).await.unwrap();
+    repository.find_by_id(uuid::Uuid::new_v4()).await.unwrap();
+
+    let metrics = &repository.metrics()["find_by_id"];
+    assert_eq!(metrics.calls, 2);
+    assert_eq!(metrics.rows, 1);
+    assert_eq!(metrics.slow_calls, 2);
+}
+
+#[tokio::test]
+async fn test_fast_calls_are_not_slow() {
+    let repository = InstrumentedRepository::new(FlakyUserRepository::default(), "users");
+
+    repository.count_by_type(None).await.unwrap();
+
+    assert_eq!(repository.metrics()["count_by_type"].slow_calls, 0);
+}
@@ -0,0 +1,297 @@
+//! In-memory user repository that counts calls and can be made to fail,
+//! an in-memory shared user cache, and an in-memory token blacklist with
+//! its store
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::token::RefreshToken;
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::decorators::{TokenBlacklistStoreTrait, UserCacheStoreTrait};
+use crate::repositories::{TokenRepository, UserRepository};
+
+#[derive(Default)]
+pub struct FlakyUserRepository {
+    users: Mutex<Vec<User>>,
+    /// Calls that reached the repository
+    pub calls: AtomicUsize,
+    /// Number of upcoming calls that fail with an internal error
+    failures: AtomicU32,
+    /// How long lookups take, in milliseconds
+    latency_ms: AtomicU64,
+}
+
+impl FlakyUserRepository {
+    pub fn with_user(user: User) -> Self {
+        let repository = Self::default();
+        repository.users.lock().unwrap().push(user);
+        repository
+    }
+
+    pub fn fail_next(&self, calls: u32) {
+        self.failures.store(calls, Ordering::SeqCst);
+    }
+
+    pub fn set_latency(&self, latency: Duration) {
+        self.latency_ms.store(latency.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn call(&self) -> Result<(), DomainError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let failing = self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| failures.checked_sub(1))
+            .is_ok();
+        if failing {
+            return Err(DomainError::Internal {
+                message: "Connection reset".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn lookup(&self) -> Result<(), DomainError> {
+        let latency = self.latency_ms.load(Ordering::SeqCst);
+        if latency > 0 {
+            tokio::time::sleep(Duration::from_millis(latency)).await;
+        }
+        self.call()
+    }
+}
+
+#[async_trait]
+impl UserRepository for FlakyUserRepository {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        self.lookup().await?;
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .find(|u| u.phone_hash == phone_hash && u.country_code == country_code && !u.is_deleted())
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.lookup().await?;
+        Ok(self.users.lock().unwrap().iter().find(|u| u.id == id && !u.is_deleted()).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.call()?;
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        self.call()?;
+        let mut users = self.users.lock().unwrap();
+        match users.iter_mut().find(|u| u.id == user.id) {
+            Some(existing) => {
+                *existing = user.clone();
+                Ok(user)
+            }
+            None => Err(DomainError::NotFound {
+                resource: "User".to_string(),
+            }),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        self.call()?;
+        let mut users = self.users.lock().unwrap();
+        match users.iter_mut().find(|u| u.id == id && !u.is_deleted()) {
+            Some(user) => {
+                user.deleted_at = Some(Utc::now());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.call()?;
+        Ok(self.users.lock().unwrap().iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        self.call()?;
+        let mut users = self.users.lock().unwrap();
+        match users.iter_mut().find(|u| u.id == id && u.is_deleted()) {
+            Some(user) => {
+                user.deleted_at = None;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        Ok(self.find_by_phone(phone_hash, country_code).await?.is_some())
+    }
+
+    async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError> {
+        self.call()?;
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .filter(|u| !u.is_deleted() && (user_type.is_none() || u.user_type == user_type))
+            .count() as u64)
+    }
+}
+
+/// Shared user cache kept in memory; can be made unavailable
+#[derive(Default)]
+pub struct MemoryUserCacheStore {
+    users: Mutex<HashMap<Uuid, User>>,
+    /// Whether every call fails as if the store were down
+    pub unavailable: AtomicBool,
+}
+
+impl MemoryUserCacheStore {
+    fn check(&self) -> Result<(), String> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err("Connection refused".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UserCacheStoreTrait for MemoryUserCacheStore {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<User>, String> {
+        self.check()?;
+        Ok(self.users.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn get_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, String> {
+        self.check()?;
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .values()
+            .find(|u| u.phone_hash == phone_hash && u.country_code == country_code)
+            .cloned())
+    }
+
+    async fn put(&self, user: &User, _ttl_seconds: u64) -> Result<(), String> {
+        self.check()?;
+        self.users.lock().unwrap().insert(user.id, user.clone());
+        Ok(())
+    }
+
+    async fn remove(&self, id: Uuid) -> Result<(), String> {
+        self.check()?;
+        self.users.lock().unwrap().remove(&id);
+        Ok(())
+    }
+}
+
+/// Token repository holding only a blacklist, counting blacklist checks
+#[derive(Default)]
+pub struct BlacklistTokenRepository {
+    blacklist: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Number of blacklist checks made
+    pub checks: AtomicU32,
+}
+
+impl BlacklistTokenRepository {
+    pub fn contains(&self, token_jti: &str) -> bool {
+        self.blacklist.lock().unwrap().contains_key(token_jti)
+    }
+}
+
+#[async_trait]
+impl TokenRepository for BlacklistTokenRepository {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        Ok(token)
+    }
+
+    async fn find_refresh_token(&self, _token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn find_by_token_family(&self, _token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn revoke_token_family(&self, _token_family: &str) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn is_token_blacklisted(&self, token_jti: &str) -> Result<bool, DomainError> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        Ok(self.contains(token_jti))
+    }
+
+    async fn blacklist_token(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        self.blacklist.lock().unwrap().insert(token_jti.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn revoke_token(&self, _token_hash: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn revoke_all_user_tokens(&self, _user_id: Uuid) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        let mut blacklist = self.blacklist.lock().unwrap();
+        let before = blacklist.len();
+        blacklist.retain(|_, expires_at| *expires_at > Utc::now());
+        Ok(before - blacklist.len())
+    }
+}
+
+/// Token blacklist store kept in memory; can be made unavailable
+#[derive(Default)]
+pub struct MemoryBlacklistStore {
+    entries: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Whether every call fails as if the store were down
+    pub unavailable: AtomicBool,
+}
+
+#[async_trait]
+impl TokenBlacklistStoreTrait for MemoryBlacklistStore {
+    async fn add(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), String> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err("Connection refused".to_string());
+        }
+        self.entries.lock().unwrap().insert(token_jti.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn contains(&self, token_jti: &str) -> Result<bool, String> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err("Connection refused".to_string());
+        }
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .get(token_jti)
+            .is_some_and(|expires_at| *expires_at > Utc::now()))
+    }
+}
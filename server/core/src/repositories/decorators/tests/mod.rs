@@ -0,0 +1,13 @@
+//! Tests for repository decorators
+
+#[cfg(test)]
+mod blacklist_tests;
+
+#[cfg(test)]
+mod cached_tests;
+
+#[cfg(test)]
+mod instrumented_tests;
+
+#[cfg(test)]
+mod mocks;
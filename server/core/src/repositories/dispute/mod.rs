@@ -0,0 +1,7 @@
+//! Payment dispute repository module.
+
+mod r#trait;
+pub use r#trait::DisputeRepository;
+
+mod repository;
+pub use repository::MySqlDisputeRepository;
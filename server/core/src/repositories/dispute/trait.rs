@@ -0,0 +1,46 @@
+//! Repository trait for payment disputes.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::dispute::Dispute;
+use crate::errors::DomainError;
+use crate::services::tenant::TenantId;
+
+/// Repository trait for dispute persistence operations
+#[async_trait]
+pub trait DisputeRepository: Send + Sync {
+    /// Record a new dispute
+    ///
+    /// # Returns
+    /// * `Ok(Dispute)` - The saved dispute
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, dispute: Dispute) -> Result<Dispute, DomainError>;
+
+    /// Find a dispute by the provider's dispute identifier
+    ///
+    /// # Arguments
+    /// * `provider` - Payment provider name (e.g. "stripe")
+    /// * `provider_dispute_id` - Provider's identifier for the dispute
+    async fn find_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<Dispute>, DomainError>;
+
+    /// Find the tenant of the dispute with a provider dispute identifier
+    ///
+    /// Like [`crate::repositories::PaymentRepository::find_tenant_by_reference`],
+    /// this lookup is not scoped to the current tenant.
+    async fn find_tenant_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<TenantId>, DomainError>;
+
+    /// Find the disputes opened against a payment
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Dispute>, DomainError>;
+
+    /// Update the status of a dispute
+    async fn update(&self, dispute: &Dispute) -> Result<(), DomainError>;
+}
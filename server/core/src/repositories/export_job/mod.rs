@@ -0,0 +1,7 @@
+//! Export job repository module.
+
+mod r#trait;
+pub use r#trait::ExportJobRepository;
+
+mod repository;
+pub use repository::MySqlExportJobRepository;
@@ -0,0 +1,10 @@
+//! Export job repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlExportJobRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/export_job_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlExportJobRepository;
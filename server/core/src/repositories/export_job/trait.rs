@@ -0,0 +1,46 @@
+//! Repository trait for admin export jobs.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::export_job::ExportJob;
+use crate::errors::DomainError;
+
+/// Repository trait for export job persistence operations
+#[async_trait]
+pub trait ExportJobRepository: Send + Sync {
+    /// Save a new export job
+    ///
+    /// # Returns
+    /// * `Ok(ExportJob)` - The saved job
+    /// * `Err(DomainError)` - If the operation fails
+    async fn create(&self, job: ExportJob) -> Result<ExportJob, DomainError>;
+
+    /// Find an export job by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ExportJob>, DomainError>;
+
+    /// List queued jobs, oldest first
+    async fn find_queued(&self, limit: usize) -> Result<Vec<ExportJob>, DomainError>;
+
+    /// Record that a worker started a queued job
+    ///
+    /// Only one worker can start a job: the job is saved only if it is
+    /// still queued.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The job was claimed by this worker
+    /// * `Ok(false)` - Another worker claimed it first
+    async fn claim(&self, job: &ExportJob) -> Result<bool, DomainError>;
+
+    /// Save the progress, result or failure of a job
+    async fn update(&self, job: &ExportJob) -> Result<(), DomainError>;
+
+    /// Put running jobs not updated since the given time back in the queue
+    ///
+    /// Recovers jobs whose worker stopped mid-export, e.g. during a deployment.
+    ///
+    /// # Returns
+    /// * `Ok(count)` - Number of jobs queued again
+    async fn requeue_stale(&self, updated_before: DateTime<Utc>) -> Result<u64, DomainError>;
+}
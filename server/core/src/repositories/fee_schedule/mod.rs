@@ -0,0 +1,7 @@
+//! Fee schedule repository module.
+
+mod r#trait;
+pub use r#trait::{FeeScheduleRepository, OrderFeeRepository};
+
+mod repository;
+pub use repository::MySqlFeeScheduleRepository;
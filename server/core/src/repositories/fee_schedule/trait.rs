@@ -0,0 +1,54 @@
+//! Repository traits for platform fee schedules and the fees applied to orders.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::fee_schedule::{FeeSchedule, OrderFee};
+use crate::errors::DomainError;
+
+/// Repository trait for fee schedule persistence operations
+#[async_trait]
+pub trait FeeScheduleRepository: Send + Sync {
+    /// Record a new fee schedule version
+    ///
+    /// # Returns
+    /// * `Ok(FeeSchedule)` - The saved schedule
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, schedule: FeeSchedule) -> Result<FeeSchedule, DomainError>;
+
+    /// Find every schedule version of a market, newest first
+    async fn find_by_market(&self, market: &str) -> Result<Vec<FeeSchedule>, DomainError>;
+
+    /// Get the highest version number for a market and category
+    ///
+    /// # Arguments
+    /// * `market` - Normalized market code
+    /// * `category` - Normalized category, `None` for market-wide schedules
+    async fn latest_version(
+        &self,
+        market: &str,
+        category: Option<&str>,
+    ) -> Result<Option<i32>, DomainError>;
+
+    /// Find the schedule in effect at a point in time
+    ///
+    /// Returns the schedule with the latest `effective_from` not after `at`
+    /// for exactly the given category (`None` matches market-wide schedules).
+    async fn find_effective(
+        &self,
+        market: &str,
+        category: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<FeeSchedule>, DomainError>;
+}
+
+/// Repository trait for the fees fixed on orders
+#[async_trait]
+pub trait OrderFeeRepository: Send + Sync {
+    /// Record the fee of an order
+    async fn save(&self, fee: OrderFee) -> Result<OrderFee, DomainError>;
+
+    /// Find the fee of an order
+    async fn find_by_order(&self, order_id: Uuid) -> Result<Option<OrderFee>, DomainError>;
+}
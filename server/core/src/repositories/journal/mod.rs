@@ -0,0 +1,7 @@
+//! Double-entry journal repository module.
+
+mod r#trait;
+pub use r#trait::JournalRepository;
+
+mod repository;
+pub use repository::MySqlJournalRepository;
@@ -0,0 +1,42 @@
+//! Repository trait for the double-entry journal.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::journal::{
+    AccountBalance, AccountKey, JournalEntry, JournalTransactionType,
+};
+use crate::errors::DomainError;
+
+/// Repository trait for journal persistence operations
+#[async_trait]
+pub trait JournalRepository: Send + Sync {
+    /// Record a journal entry with all of its lines atomically
+    ///
+    /// # Returns
+    /// * `Ok(JournalEntry)` - The saved entry
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, entry: JournalEntry) -> Result<JournalEntry, DomainError>;
+
+    /// Find the entry recording a business event
+    ///
+    /// # Arguments
+    /// * `transaction_type` - Type of the business event
+    /// * `reference` - Identifier of the event (e.g. a payment ID)
+    async fn find_by_reference(
+        &self,
+        transaction_type: JournalTransactionType,
+        reference: &str,
+    ) -> Result<Option<JournalEntry>, DomainError>;
+
+    /// Get the debit and credit totals of an account
+    ///
+    /// Accounts without lines have an empty balance.
+    async fn account_balance(&self, account: &AccountKey) -> Result<AccountBalance, DomainError>;
+
+    /// Get the totals of every account that has lines
+    async fn trial_balance(&self) -> Result<Vec<AccountBalance>, DomainError>;
+
+    /// Find entries whose debits and credits differ in any currency
+    async fn find_unbalanced_entries(&self) -> Result<Vec<Uuid>, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Legal document repository module.
+
+mod r#trait;
+pub use r#trait::LegalDocumentRepository;
+
+mod repository;
+pub use repository::MySqlLegalDocumentRepository;
@@ -0,0 +1,10 @@
+//! Legal document repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlLegalDocumentRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/legal_document_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlLegalDocumentRepository;
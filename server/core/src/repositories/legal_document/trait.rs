@@ -0,0 +1,49 @@
+//! Repository trait for legal documents and users' acceptances of them.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::legal_document::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+use crate::errors::DomainError;
+
+/// Repository trait for legal document persistence operations
+///
+/// Markets are normalized codes; `None` matches versions for every market only.
+#[async_trait]
+pub trait LegalDocumentRepository: Send + Sync {
+    /// Record every translation of a new version atomically
+    async fn save_version(&self, translations: &[LegalDocument]) -> Result<(), DomainError>;
+
+    /// Get the highest version number for a kind and market
+    async fn latest_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+    ) -> Result<Option<i32>, DomainError>;
+
+    /// Find every translation of the version in effect at a point in time
+    ///
+    /// Returns the translations of the version with the latest
+    /// `effective_from` not after `at`, or an empty list.
+    async fn find_effective(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<LegalDocument>, DomainError>;
+
+    /// Find every translation of a version
+    async fn find_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        version: i32,
+    ) -> Result<Vec<LegalDocument>, DomainError>;
+
+    /// Record an acceptance; accepting the same version again is a no-op
+    async fn save_acceptance(&self, acceptance: &LegalAcceptance) -> Result<(), DomainError>;
+
+    /// Find every version a user accepted
+    async fn find_acceptances(&self, user_id: Uuid) -> Result<Vec<LegalAcceptance>, DomainError>;
+}
@@ -1,7 +1,69 @@
+pub mod admin_identity;
+pub mod attribution;
 pub mod audit;
+pub mod calendar;
+pub mod campaign;
+pub mod completion;
+pub mod compliance;
+pub mod credit_wallet;
+pub mod decorators;
+pub mod dispute;
+pub mod export_job;
+pub mod fee_schedule;
+pub mod journal;
+pub mod legal_document;
+pub mod notification;
+pub mod oauth;
+pub mod organization;
+pub mod payment;
+pub mod payment_method;
+pub mod payment_risk;
+pub mod payout_method;
+pub mod phone_hash;
+pub mod reconciliation;
+pub mod security_webhook;
+pub mod service_area;
+pub mod sms_delivery;
+pub mod sms_spend;
 pub mod token;
+pub mod unit_of_work;
 pub mod user;
+pub mod user_import;
+pub mod webhook_dead_letter;
 
+pub use admin_identity::{AdminIdentityRepository, MySqlAdminIdentityRepository};
+pub use attribution::{AttributionRepository, MySqlAttributionRepository};
 pub use audit::{AuditLogRepository, MySqlAuditLogRepository};
+pub use calendar::{ClosureDateRepository, MySqlClosureDateRepository};
+pub use campaign::{CampaignRepository, MySqlCampaignRepository};
+pub use completion::{CompletionChecklistRepository, MySqlCompletionChecklistRepository};
+pub use compliance::{ComplianceRepository, MySqlComplianceRepository};
+pub use credit_wallet::{CreditWalletRepository, MySqlCreditWalletRepository};
+pub use dispute::{DisputeRepository, MySqlDisputeRepository};
+pub use export_job::{ExportJobRepository, MySqlExportJobRepository};
+pub use fee_schedule::{FeeScheduleRepository, MySqlFeeScheduleRepository, OrderFeeRepository};
+pub use journal::{JournalRepository, MySqlJournalRepository};
+pub use legal_document::{LegalDocumentRepository, MySqlLegalDocumentRepository};
+pub use notification::{
+    MySqlScheduledMessageRepository, NotificationPreferencesRepository, ScheduledMessageRepository,
+};
+pub use oauth::{MySqlOAuthRepository, OAuthRepository};
+pub use organization::{MySqlOrganizationRepository, OrganizationRepository};
+pub use payment::{
+    MySqlPaymentLedgerRepository, PaymentLedgerRepository, PaymentRepository,
+    PaymentWebhookEventRepository, PayoutRepository,
+};
+pub use payment_method::{MySqlPaymentMethodRepository, PaymentMethodRepository};
+pub use payment_risk::{MySqlPaymentRiskRepository, PaymentRiskRepository};
+pub use payout_method::{MySqlPayoutMethodRepository, PayoutMethodRepository};
+pub use phone_hash::{MySqlPhoneHashRepository, PhoneHashRepository};
+pub use reconciliation::{MySqlReconciliationReportRepository, ReconciliationReportRepository};
+pub use security_webhook::{MySqlSecurityWebhookRepository, SecurityWebhookRepository};
+pub use service_area::{MySqlServiceAreaRepository, ServiceAreaRepository};
+pub use sms_delivery::{MySqlSmsDeliveryLogRepository, SmsDeliveryLogRepository, SmsLogRepository};
+pub use sms_spend::{MySqlSmsSpendRepository, SmsSpendRepository};
 pub use token::{TokenRepository, MySqlTokenRepository};
-pub use user::{UserRepository, MySqlUserRepository};
\ No newline at end of file
+pub use unit_of_work::{MySqlUnitOfWork, UnitOfWork, UnitOfWorkTransaction};
+pub use user::{UserRepository, MySqlUserRepository};
+pub use user_import::{MySqlUserImportRepository, UserImportRepository};
+pub use webhook_dead_letter::{MySqlWebhookDeadLetterRepository, WebhookDeadLetterRepository};
\ No newline at end of file
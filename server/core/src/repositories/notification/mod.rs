@@ -0,0 +1,7 @@
+//! Scheduled message and notification preference repository module.
+
+mod r#trait;
+pub use r#trait::{NotificationPreferencesRepository, ScheduledMessageRepository};
+
+mod repository;
+pub use repository::MySqlScheduledMessageRepository;
@@ -0,0 +1,10 @@
+//! Scheduled message repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlScheduledMessageRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/scheduled_message_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlScheduledMessageRepository;
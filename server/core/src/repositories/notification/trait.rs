@@ -0,0 +1,93 @@
+//! Repository traits for scheduled messages and per-user notification preferences.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::notification::{NotificationPreferences, ScheduledMessage};
+use crate::errors::DomainError;
+
+/// Repository trait for ScheduledMessage persistence operations
+#[async_trait]
+pub trait ScheduledMessageRepository: Send + Sync {
+    /// Save a newly scheduled message
+    ///
+    /// # Arguments
+    /// * `message` - The scheduled message to persist
+    ///
+    /// # Returns
+    /// * `Ok(ScheduledMessage)` - The saved message
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError>;
+
+    /// Find a scheduled message by its ID
+    ///
+    /// # Arguments
+    /// * `id` - The message's unique identifier
+    ///
+    /// # Returns
+    /// * `Ok(Some(ScheduledMessage))` - If found
+    /// * `Ok(None)` - If no message exists with this ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ScheduledMessage>, DomainError>;
+
+    /// Claim pending messages whose delivery time is at or before `now`
+    ///
+    /// Claimed messages move to processing atomically, so dispatchers
+    /// running on several instances never receive the same message. A
+    /// claim lasts until `claim_until`; messages still processing after
+    /// that (their dispatcher stopped before updating them) are claimed
+    /// again. [`Self::update`] releases the claim.
+    ///
+    /// # Arguments
+    /// * `now` - Reference time for the due check
+    /// * `limit` - Maximum number of messages to claim
+    /// * `claim_until` - When the claim expires
+    ///
+    /// # Returns
+    /// * Claimed messages ordered by delivery time ascending
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        claim_until: DateTime<Utc>,
+    ) -> Result<Vec<ScheduledMessage>, DomainError>;
+
+    /// Find pending messages scheduled for a user
+    ///
+    /// # Arguments
+    /// * `user_id` - The user's unique identifier
+    ///
+    /// # Returns
+    /// * Pending messages ordered by delivery time ascending
+    async fn find_pending_by_user(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, DomainError>;
+
+    /// Update a scheduled message's delivery state
+    ///
+    /// Persists status, delivery time, attempt count, last error,
+    /// provider message ID and sent timestamp, and releases any claim.
+    ///
+    /// # Returns
+    /// * `Ok(ScheduledMessage)` - The updated message
+    /// * `Err(DomainError::NotFound)` - If the message does not exist
+    async fn update(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError>;
+}
+
+/// Repository trait for NotificationPreferences persistence operations
+#[async_trait]
+pub trait NotificationPreferencesRepository: Send + Sync {
+    /// Find notification preferences for a user
+    ///
+    /// # Returns
+    /// * `Ok(Some(NotificationPreferences))` - If the user has saved preferences
+    /// * `Ok(None)` - If the user relies on defaults
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>, DomainError>;
+
+    /// Create or replace notification preferences for a user
+    async fn upsert(
+        &self,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! OAuth client, consent and authorization code repository module.
+
+mod r#trait;
+pub use r#trait::OAuthRepository;
+
+mod repository;
+pub use repository::MySqlOAuthRepository;
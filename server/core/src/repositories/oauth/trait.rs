@@ -0,0 +1,61 @@
+//! Repository trait for OAuth clients, user consents and authorization codes.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{OAuthAuthorizationCode, OAuthClient, OAuthConsent};
+use crate::errors::DomainError;
+
+/// Repository trait for OAuth persistence operations
+#[async_trait]
+pub trait OAuthRepository: Send + Sync {
+    /// Register a new client
+    ///
+    /// # Returns
+    /// * `Ok(OAuthClient)` - The saved client
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save_client(&self, client: OAuthClient) -> Result<OAuthClient, DomainError>;
+
+    /// Update a client's settings and active flag
+    async fn update_client(&self, client: &OAuthClient) -> Result<(), DomainError>;
+
+    /// Find a client by its public client identifier
+    async fn find_client(&self, client_id: &str) -> Result<Option<OAuthClient>, DomainError>;
+
+    /// List all clients, newest first
+    async fn list_clients(&self) -> Result<Vec<OAuthClient>, DomainError>;
+
+    /// Create or replace a user's consent for a client
+    async fn save_consent(&self, consent: &OAuthConsent) -> Result<(), DomainError>;
+
+    /// Find a user's consent for a client
+    async fn find_consent(&self, user_id: Uuid, client_id: &str) -> Result<Option<OAuthConsent>, DomainError>;
+
+    /// Find every consent a user has given
+    async fn find_consents_by_user(&self, user_id: Uuid) -> Result<Vec<OAuthConsent>, DomainError>;
+
+    /// Delete a user's consent for a client
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The consent was deleted
+    /// * `Ok(false)` - The user had not consented to the client
+    async fn delete_consent(&self, user_id: Uuid, client_id: &str) -> Result<bool, DomainError>;
+
+    /// Store a newly issued authorization code
+    async fn save_authorization_code(&self, code: &OAuthAuthorizationCode) -> Result<(), DomainError>;
+
+    /// Mark an unused authorization code as used and return it
+    ///
+    /// Marking must be atomic so that a code is exchanged at most once,
+    /// even by concurrent requests.
+    ///
+    /// # Returns
+    /// * `Ok(Some(code))` - The code, now marked used at `used_at`
+    /// * `Ok(None)` - No such code, or it was already used
+    async fn consume_authorization_code(
+        &self,
+        code_hash: &str,
+        used_at: DateTime<Utc>,
+    ) -> Result<Option<OAuthAuthorizationCode>, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Organization repository module.
+
+mod r#trait;
+pub use r#trait::OrganizationRepository;
+
+mod repository;
+pub use repository::MySqlOrganizationRepository;
@@ -0,0 +1,122 @@
+//! Repository trait for organizations, their members, payment methods,
+//! charges and consolidated invoices.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::organization::{
+    ConsolidatedInvoice, Organization, OrganizationCharge, OrganizationMember, OrganizationPaymentMethod,
+};
+use crate::errors::DomainError;
+
+/// Repository trait for organization billing persistence operations
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    /// Create an organization together with its first admin
+    async fn create(&self, organization: &Organization, admin: &OrganizationMember) -> Result<(), DomainError>;
+
+    /// Find an organization by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, DomainError>;
+
+    /// Find the membership of a user in any organization
+    async fn find_membership(&self, user_id: Uuid) -> Result<Option<OrganizationMember>, DomainError>;
+
+    /// List the members of an organization, oldest first
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, DomainError>;
+
+    /// Add a member, or update the role and spend limit of an existing one
+    async fn save_member(&self, member: &OrganizationMember) -> Result<(), DomainError>;
+
+    /// Remove a member
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The member was removed
+    /// * `Ok(false)` - The user was not a member
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, DomainError>;
+
+    /// Save a new payment method
+    async fn save_payment_method(&self, method: &OrganizationPaymentMethod) -> Result<(), DomainError>;
+
+    /// List an organization's payment methods, newest first
+    async fn find_payment_methods(&self, organization_id: Uuid) -> Result<Vec<OrganizationPaymentMethod>, DomainError>;
+
+    /// Make one payment method the default and clear the flag on the others
+    async fn set_default_payment_method(&self, organization_id: Uuid, id: Uuid) -> Result<(), DomainError>;
+
+    /// Delete a payment method
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The method was deleted
+    /// * `Ok(false)` - No such method
+    async fn delete_payment_method(&self, id: Uuid) -> Result<bool, DomainError>;
+
+    /// Find the charge of an order
+    async fn find_charge_by_order(&self, order_id: Uuid) -> Result<Option<OrganizationCharge>, DomainError>;
+
+    /// Save a charge unless it takes the member over their spend limit
+    ///
+    /// The member's charges accepted within the window are summed and the
+    /// charge saved atomically, so concurrent acceptances cannot together
+    /// exceed the limit.
+    ///
+    /// # Arguments
+    /// * `limit` - Most the member may spend within the window, `None` for no limit
+    /// * `from`, `to` - Window of acceptance times counted, `to` exclusive
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The charge was saved
+    /// * `Ok(false)` - The charge would exceed the limit and was not saved
+    async fn save_charge_within_limit(
+        &self,
+        charge: &OrganizationCharge,
+        limit: Option<i64>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<bool, DomainError>;
+
+    /// Mark the charge of an order as completed, if it is not yet
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The charge was marked completed
+    /// * `Ok(false)` - The order has no charge or it was already completed
+    async fn complete_charge(&self, order_id: Uuid, completed_at: DateTime<Utc>) -> Result<bool, DomainError>;
+
+    /// Total accepted per member of an organization within a window, `to` exclusive
+    async fn member_spend(
+        &self,
+        organization_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<Uuid, i64>, DomainError>;
+
+    /// Find completed charges not yet invoiced
+    ///
+    /// # Arguments
+    /// * `completed_before` - Only charges completed before this time
+    async fn find_uninvoiced_charges(
+        &self,
+        organization_id: Uuid,
+        completed_before: DateTime<Utc>,
+    ) -> Result<Vec<OrganizationCharge>, DomainError>;
+
+    /// Find organizations with completed charges not yet invoiced
+    async fn find_organizations_to_invoice(&self, completed_before: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError>;
+
+    /// Save an invoice and link the charges on its lines to it
+    async fn save_invoice(&self, invoice: &ConsolidatedInvoice) -> Result<(), DomainError>;
+
+    /// Find an invoice by ID
+    async fn find_invoice(&self, id: Uuid) -> Result<Option<ConsolidatedInvoice>, DomainError>;
+
+    /// Find the invoice of an organization for a month
+    async fn find_invoice_for_period(
+        &self,
+        organization_id: Uuid,
+        period_start: NaiveDate,
+    ) -> Result<Option<ConsolidatedInvoice>, DomainError>;
+
+    /// List an organization's invoices, most recent period first
+    async fn find_invoices(&self, organization_id: Uuid, limit: usize) -> Result<Vec<ConsolidatedInvoice>, DomainError>;
+}
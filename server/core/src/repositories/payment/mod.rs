@@ -0,0 +1,9 @@
+//! Payment ledger, payment, payout and provider webhook repository module.
+
+mod r#trait;
+pub use r#trait::{
+    PaymentLedgerRepository, PaymentRepository, PaymentWebhookEventRepository, PayoutRepository,
+};
+
+mod repository;
+pub use repository::MySqlPaymentLedgerRepository;
@@ -0,0 +1,10 @@
+//! Payment ledger repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlPaymentLedgerRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/payment_ledger_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlPaymentLedgerRepository;
@@ -0,0 +1,139 @@
+//! Repository traits for the payment ledger, payments, payouts and provider webhooks.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::payment::{LedgerEntry, Payment, PaymentTimelineEntry, Payout};
+use crate::domain::entities::payment_webhook::PaymentWebhookEvent;
+use crate::errors::DomainError;
+use crate::services::tenant::TenantId;
+
+/// Repository trait for payment ledger persistence operations
+#[async_trait]
+pub trait PaymentLedgerRepository: Send + Sync {
+    /// Record a new ledger entry
+    ///
+    /// # Returns
+    /// * `Ok(LedgerEntry)` - The saved entry
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, entry: LedgerEntry) -> Result<LedgerEntry, DomainError>;
+
+    /// Find a provider's ledger entries that occurred within a period
+    ///
+    /// # Arguments
+    /// * `provider` - Payment provider name (e.g. "stripe")
+    /// * `start` - Start of the period (inclusive)
+    /// * `end` - End of the period (exclusive)
+    async fn find_by_period(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, DomainError>;
+}
+
+/// Repository trait for customer payments
+#[async_trait]
+pub trait PaymentRepository: Send + Sync {
+    /// Record a new payment
+    async fn save(&self, payment: Payment) -> Result<Payment, DomainError>;
+
+    /// Find a payment by its provider reference
+    ///
+    /// # Arguments
+    /// * `provider` - Payment provider name (e.g. "stripe")
+    /// * `provider_reference` - Provider's identifier for the payment
+    async fn find_by_provider_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<Payment>, DomainError>;
+
+    /// Find a payment by the provider's identifier for its payment intent
+    async fn find_by_intent_reference(
+        &self,
+        provider: &str,
+        intent_reference: &str,
+    ) -> Result<Option<Payment>, DomainError>;
+
+    /// Find a payment by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, DomainError>;
+
+    /// Find the tenant of the payment with a provider or intent reference
+    ///
+    /// Provider webhooks are received for every tenant, so this lookup is
+    /// not scoped to the current tenant; the webhook is then handled in the
+    /// tenant returned.
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        reference: &str,
+    ) -> Result<Option<TenantId>, DomainError>;
+
+    /// Find a customer's payments charged to a payment method
+    ///
+    /// # Arguments
+    /// * `customer_id` - Paying customer
+    /// * `payment_method` - Provider's identifier for the payment method
+    async fn find_by_payment_method(
+        &self,
+        customer_id: Uuid,
+        payment_method: &str,
+    ) -> Result<Vec<Payment>, DomainError>;
+
+    /// Update the provider reference, refunded amount and status of a payment
+    async fn update(&self, payment: &Payment) -> Result<(), DomainError>;
+
+    /// Record a status change on a payment's timeline
+    async fn add_timeline_entry(
+        &self,
+        entry: PaymentTimelineEntry,
+    ) -> Result<PaymentTimelineEntry, DomainError>;
+
+    /// Find a payment's timeline, oldest first
+    async fn find_timeline(&self, payment_id: Uuid) -> Result<Vec<PaymentTimelineEntry>, DomainError>;
+}
+
+/// Repository trait for worker payouts
+#[async_trait]
+pub trait PayoutRepository: Send + Sync {
+    /// Record a new payout
+    async fn save(&self, payout: Payout) -> Result<Payout, DomainError>;
+
+    /// Find a payout by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payout>, DomainError>;
+
+    /// Find the payouts funded by a payment
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Payout>, DomainError>;
+
+    /// Update the status and transfer reference of a payout
+    async fn update(&self, payout: &Payout) -> Result<(), DomainError>;
+}
+
+/// Repository trait for received payment provider webhooks
+#[async_trait]
+pub trait PaymentWebhookEventRepository: Send + Sync {
+    /// Record a received webhook
+    async fn save(&self, event: PaymentWebhookEvent) -> Result<PaymentWebhookEvent, DomainError>;
+
+    /// Find a webhook by the provider's event identifier
+    async fn find_by_event_id(
+        &self,
+        provider: &str,
+        event_id: &str,
+    ) -> Result<Option<PaymentWebhookEvent>, DomainError>;
+
+    /// Record how a webhook was handled
+    ///
+    /// # Arguments
+    /// * `id` - Record ID
+    /// * `outcome` - Short description of the handling
+    /// * `processed_at` - When processing finished
+    async fn mark_processed(
+        &self,
+        id: Uuid,
+        outcome: &str,
+        processed_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+}
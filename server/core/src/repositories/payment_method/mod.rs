@@ -0,0 +1,7 @@
+//! Saved payment method repository module.
+
+mod r#trait;
+pub use r#trait::PaymentMethodRepository;
+
+mod repository;
+pub use repository::MySqlPaymentMethodRepository;
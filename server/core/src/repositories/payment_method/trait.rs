@@ -0,0 +1,42 @@
+//! Repository trait for customers' saved payment methods.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::payment_method::SavedPaymentMethod;
+use crate::errors::DomainError;
+
+/// Repository trait for saved payment method persistence operations
+#[async_trait]
+pub trait PaymentMethodRepository: Send + Sync {
+    /// Save a new payment method
+    ///
+    /// # Returns
+    /// * `Ok(SavedPaymentMethod)` - The saved method
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, method: SavedPaymentMethod) -> Result<SavedPaymentMethod, DomainError>;
+
+    /// Find a payment method by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SavedPaymentMethod>, DomainError>;
+
+    /// Find a customer's payment method by the provider's identifier
+    async fn find_by_reference(
+        &self,
+        customer_id: Uuid,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<SavedPaymentMethod>, DomainError>;
+
+    /// List a customer's payment methods, newest first
+    async fn find_by_customer(&self, customer_id: Uuid) -> Result<Vec<SavedPaymentMethod>, DomainError>;
+
+    /// Make one payment method the customer's default and clear the flag on the others
+    async fn set_default(&self, customer_id: Uuid, id: Uuid) -> Result<(), DomainError>;
+
+    /// Delete a payment method
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The method was deleted
+    /// * `Ok(false)` - No such method
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Payment risk assessment repository module.
+
+mod r#trait;
+pub use r#trait::PaymentRiskRepository;
+
+mod repository;
+pub use repository::MySqlPaymentRiskRepository;
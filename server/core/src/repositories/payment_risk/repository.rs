@@ -0,0 +1,10 @@
+//! Payment risk repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlPaymentRiskRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/payment_risk_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlPaymentRiskRepository;
@@ -0,0 +1,38 @@
+//! Repository trait for payment risk assessments and the review queue.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::payment_risk::PaymentRiskAssessment;
+use crate::errors::DomainError;
+
+/// Repository trait for payment risk persistence operations
+#[async_trait]
+pub trait PaymentRiskRepository: Send + Sync {
+    /// Record a new assessment
+    ///
+    /// # Returns
+    /// * `Ok(PaymentRiskAssessment)` - The saved assessment
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, assessment: PaymentRiskAssessment) -> Result<PaymentRiskAssessment, DomainError>;
+
+    /// Update the review fields of an assessment
+    async fn update_review(&self, assessment: &PaymentRiskAssessment) -> Result<(), DomainError>;
+
+    /// Find an assessment by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentRiskAssessment>, DomainError>;
+
+    /// Find assessments waiting for review, oldest first
+    async fn find_pending_reviews(&self, limit: usize) -> Result<Vec<PaymentRiskAssessment>, DomainError>;
+
+    /// Find the distinct cards a user attempted to pay with since a point in time
+    ///
+    /// Attempts that were blocked are included, so cycling through cards
+    /// after a refusal still counts.
+    async fn find_card_fingerprints(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<String>, DomainError>;
+}
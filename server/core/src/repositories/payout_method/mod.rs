@@ -0,0 +1,7 @@
+//! Worker payout method repository module.
+
+mod r#trait;
+pub use r#trait::PayoutMethodRepository;
+
+mod repository;
+pub use repository::MySqlPayoutMethodRepository;
@@ -0,0 +1,10 @@
+//! Payout method repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlPayoutMethodRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/payout_method_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlPayoutMethodRepository;
@@ -0,0 +1,45 @@
+//! Repository trait for workers' payout methods.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::payout_method::PayoutMethod;
+use crate::errors::DomainError;
+use crate::services::tenant::TenantId;
+
+/// Repository trait for payout method persistence operations
+#[async_trait]
+pub trait PayoutMethodRepository: Send + Sync {
+    /// Save a new payout method
+    ///
+    /// # Returns
+    /// * `Ok(PayoutMethod)` - The saved method
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, method: PayoutMethod) -> Result<PayoutMethod, DomainError>;
+
+    /// Find a payout method by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PayoutMethod>, DomainError>;
+
+    /// Find a payout method by the provider's identifier
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<PayoutMethod>, DomainError>;
+
+    /// Find the tenant of the payout method with a provider identifier
+    ///
+    /// Provider verification results are received for every tenant, so this
+    /// lookup is not scoped to the current tenant.
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError>;
+
+    /// List a worker's payout methods, newest first
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<PayoutMethod>, DomainError>;
+
+    /// Update the verification state of a payout method
+    async fn update(&self, method: &PayoutMethod) -> Result<(), DomainError>;
+}
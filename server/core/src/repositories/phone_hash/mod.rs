@@ -0,0 +1,7 @@
+//! Phone hash repository module.
+
+mod r#trait;
+pub use r#trait::PhoneHashRepository;
+
+mod repository;
+pub use repository::MySqlPhoneHashRepository;
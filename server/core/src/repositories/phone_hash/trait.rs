@@ -0,0 +1,43 @@
+//! Repository trait for rehashing stored phone hashes after a pepper rotation.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::user::User;
+use crate::errors::DomainError;
+
+/// Repository trait for the phone hashes of users
+#[async_trait]
+pub trait PhoneHashRepository: Send + Sync {
+    /// Find users whose phone hash is peppered but older than `current_version`
+    ///
+    /// Bare SHA-256 hashes (version 0) are not returned, since only the phone
+    /// number can be rehashed from them.
+    ///
+    /// # Arguments
+    /// * `current_version` - Version of the hashes written now
+    /// * `limit` - Maximum number of users to return
+    async fn find_outdated(&self, current_version: u16, limit: usize) -> Result<Vec<User>, DomainError>;
+
+    /// Count users whose phone hash is peppered but older than `current_version`
+    async fn count_outdated(&self, current_version: u16) -> Result<u64, DomainError>;
+
+    /// Replace a user's phone hash, unless it changed since it was read
+    ///
+    /// # Arguments
+    /// * `user_id` - The user whose hash is replaced
+    /// * `old_hash` - The hash read by [`Self::find_outdated`]
+    /// * `new_hash` - The hash at `new_version`
+    /// * `new_version` - Version of `new_hash`
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The hash was replaced
+    /// * `Ok(false)` - The user is gone or was rehashed in the meantime
+    async fn replace_phone_hash(
+        &self,
+        user_id: Uuid,
+        old_hash: &str,
+        new_hash: &str,
+        new_version: u16,
+    ) -> Result<bool, DomainError>;
+}
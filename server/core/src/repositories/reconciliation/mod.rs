@@ -0,0 +1,7 @@
+//! Reconciliation report repository module.
+
+mod r#trait;
+pub use r#trait::ReconciliationReportRepository;
+
+mod repository;
+pub use repository::MySqlReconciliationReportRepository;
@@ -0,0 +1,31 @@
+//! Repository trait for reconciliation reports.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::reconciliation::ReconciliationReport;
+use crate::errors::DomainError;
+
+/// Repository trait for ReconciliationReport persistence operations
+#[async_trait]
+pub trait ReconciliationReportRepository: Send + Sync {
+    /// Save a report together with its discrepancies
+    ///
+    /// # Returns
+    /// * `Ok(ReconciliationReport)` - The saved report
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, report: ReconciliationReport) -> Result<ReconciliationReport, DomainError>;
+
+    /// Find a report by its ID, including its discrepancies
+    ///
+    /// # Returns
+    /// * `Ok(Some(ReconciliationReport))` - If found
+    /// * `Ok(None)` - If no report exists with this ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ReconciliationReport>, DomainError>;
+
+    /// List reports with their discrepancies, most recent period first
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of reports to return
+    async fn list_recent(&self, limit: usize) -> Result<Vec<ReconciliationReport>, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Security webhook repository module.
+
+mod r#trait;
+pub use r#trait::SecurityWebhookRepository;
+
+mod repository;
+pub use repository::MySqlSecurityWebhookRepository;
@@ -0,0 +1,10 @@
+//! Security webhook repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlSecurityWebhookRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/security_webhook_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlSecurityWebhookRepository;
@@ -0,0 +1,63 @@
+//! Repository trait for security webhooks and their queued event deliveries.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::security_webhook::{SecurityEventKind, SecurityWebhook, WebhookDelivery};
+use crate::errors::DomainError;
+use crate::services::tenant::TenantId;
+
+/// Repository trait for SecurityWebhook persistence operations
+#[async_trait]
+pub trait SecurityWebhookRepository: Send + Sync {
+    /// Save a new webhook
+    ///
+    /// # Returns
+    /// * `Ok(SecurityWebhook)` - The saved webhook
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, webhook: SecurityWebhook) -> Result<SecurityWebhook, DomainError>;
+
+    /// Find a webhook by its ID
+    ///
+    /// # Returns
+    /// * `Ok(Some(SecurityWebhook))` - If found
+    /// * `Ok(None)` - If no webhook exists with this ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SecurityWebhook>, DomainError>;
+
+    /// Find the tenant a webhook belongs to
+    ///
+    /// Dead letters are replayed for every tenant, so this lookup is not
+    /// scoped to the current tenant.
+    async fn find_tenant_by_id(&self, id: Uuid) -> Result<Option<TenantId>, DomainError>;
+
+    /// List all webhooks, most recently registered first
+    async fn list(&self) -> Result<Vec<SecurityWebhook>, DomainError>;
+
+    /// Find active webhooks subscribed to an event kind
+    async fn find_subscribed(&self, kind: SecurityEventKind) -> Result<Vec<SecurityWebhook>, DomainError>;
+
+    /// Delete a webhook together with its pending deliveries
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the webhook was deleted
+    /// * `Ok(false)` - If no webhook exists with this ID
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
+
+    /// Enqueue deliveries of one event
+    async fn save_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), DomainError>;
+
+    /// Find pending deliveries that may be attempted at `now`, oldest first
+    ///
+    /// # Arguments
+    /// * `now` - Reference time compared against `next_attempt_at`
+    /// * `limit` - Maximum number of deliveries to return
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, DomainError>;
+
+    /// Update a delivery's status, attempt count, next attempt time and error
+    async fn update_delivery(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Worker service-area repository module.
+
+mod r#trait;
+pub use r#trait::ServiceAreaRepository;
+
+mod repository;
+pub use repository::MySqlServiceAreaRepository;
@@ -0,0 +1,10 @@
+//! Service area repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlServiceAreaRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/service_area_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlServiceAreaRepository;
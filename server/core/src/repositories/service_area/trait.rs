@@ -0,0 +1,46 @@
+//! Repository trait for worker service-area polygons.
+
+use async_trait::async_trait;
+use re_shared::types::common::Coordinate;
+use uuid::Uuid;
+
+use crate::domain::entities::service_area::ServiceArea;
+use crate::errors::DomainError;
+
+/// Repository trait for ServiceArea persistence and spatial matching
+#[async_trait]
+pub trait ServiceAreaRepository: Send + Sync {
+    /// Replace all service areas of a worker
+    ///
+    /// Existing areas are removed and the given areas saved atomically.
+    ///
+    /// # Arguments
+    /// * `worker_id` - The worker's unique identifier
+    /// * `areas` - The new service areas (may be empty)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ServiceArea>)` - The saved areas
+    /// * `Err(DomainError)` - If the operation fails
+    async fn replace_for_worker(
+        &self,
+        worker_id: Uuid,
+        areas: Vec<ServiceArea>,
+    ) -> Result<Vec<ServiceArea>, DomainError>;
+
+    /// Find all service areas of a worker
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<ServiceArea>, DomainError>;
+
+    /// Find workers with a service area containing a point
+    ///
+    /// # Arguments
+    /// * `point` - Job location
+    /// * `limit` - Maximum number of workers to return
+    ///
+    /// # Returns
+    /// * IDs of matching workers, each at most once
+    async fn find_workers_containing(
+        &self,
+        point: Coordinate,
+        limit: usize,
+    ) -> Result<Vec<Uuid>, DomainError>;
+}
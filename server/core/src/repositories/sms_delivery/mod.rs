@@ -0,0 +1,7 @@
+//! SMS delivery log repository module.
+
+mod r#trait;
+pub use r#trait::{SmsDeliveryLogRepository, SmsLogRepository};
+
+mod repository;
+pub use repository::MySqlSmsDeliveryLogRepository;
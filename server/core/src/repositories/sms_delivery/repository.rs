@@ -0,0 +1,10 @@
+//! SMS delivery log repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlSmsDeliveryLogRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/sms_delivery_log_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlSmsDeliveryLogRepository;
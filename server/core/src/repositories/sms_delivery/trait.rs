@@ -0,0 +1,59 @@
+//! Repository trait for SMS delivery logs.
+
+use async_trait::async_trait;
+
+use crate::domain::entities::sms_delivery::{SmsDeliveryLog, SmsLogFilter};
+use crate::errors::DomainError;
+use re_shared::types::Pagination;
+
+/// Repository trait for SmsDeliveryLog persistence operations
+#[async_trait]
+pub trait SmsDeliveryLogRepository: Send + Sync {
+    /// Save a new delivery log entry
+    ///
+    /// # Returns
+    /// * `Ok(SmsDeliveryLog)` - The saved entry
+    /// * `Err(DomainError)` - If the operation fails
+    async fn save(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError>;
+
+    /// Find the entry of a provider message, to apply its delivery receipt
+    ///
+    /// # Returns
+    /// * `Ok(Some(SmsDeliveryLog))` - If found
+    /// * `Ok(None)` - If no message with this ID was sent through the provider
+    async fn find_by_provider_message_id(
+        &self,
+        provider: &str,
+        provider_message_id: &str,
+    ) -> Result<Option<SmsDeliveryLog>, DomainError>;
+
+    /// Find all messages sent while serving a request, oldest first
+    async fn find_by_trace_id(&self, trace_id: &str) -> Result<Vec<SmsDeliveryLog>, DomainError>;
+
+    /// Update an entry's status and error after a delivery receipt
+    async fn update(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError>;
+}
+
+/// Repository trait for searching the outbound SMS log
+///
+/// Every attempt to hand an SMS to a provider is saved through
+/// [`SmsDeliveryLogRepository`], sent or failed, so support can tell
+/// whether a code was sent, by which provider and what the provider
+/// reported back.
+#[async_trait]
+pub trait SmsLogRepository: Send + Sync {
+    /// Query the SMS log page by page
+    ///
+    /// # Arguments
+    /// * `filter` - Criteria the entries must match
+    /// * `pagination` - Page to return
+    ///
+    /// # Returns
+    /// * The page of matching entries ordered by created_at descending, and
+    ///   the total number of matching entries
+    async fn query(
+        &self,
+        filter: &SmsLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<SmsDeliveryLog>, u64), DomainError>;
+}
@@ -0,0 +1,7 @@
+//! Daily SMS spend repository module.
+
+mod r#trait;
+pub use r#trait::SmsSpendRepository;
+
+mod repository;
+pub use repository::MySqlSmsSpendRepository;
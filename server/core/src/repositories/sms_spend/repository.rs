@@ -0,0 +1,10 @@
+//! Daily SMS spend repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlSmsSpendRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/sms_spend_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlSmsSpendRepository;
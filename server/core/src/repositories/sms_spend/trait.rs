@@ -0,0 +1,28 @@
+//! Repository trait for daily SMS spend.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::domain::entities::sms_spend::SmsDailySpend;
+use crate::errors::DomainError;
+
+/// Repository trait for the daily SMS spend aggregate
+#[async_trait]
+pub trait SmsSpendRepository: Send + Sync {
+    /// Add one message to the spend of a day, provider and prefix
+    ///
+    /// # Returns
+    /// * `Ok(())` - The message was counted
+    /// * `Err(DomainError)` - If the operation fails
+    async fn add(
+        &self,
+        day: NaiveDate,
+        provider: &str,
+        prefix: &str,
+        cost_micros: u64,
+    ) -> Result<(), DomainError>;
+
+    /// Find the spend of every day between `from` and `to` inclusive,
+    /// oldest day first
+    async fn find_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<SmsDailySpend>, DomainError>;
+}
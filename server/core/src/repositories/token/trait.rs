@@ -195,6 +195,67 @@ pub trait TokenRepository: Send + Sync {
     /// ```
     async fn revoke_all_user_tokens(&self, user_id: Uuid) -> Result<usize, DomainError>;
 
+    /// Save several refresh tokens at once
+    ///
+    /// Implementations backed by a database insert the tokens with as few
+    /// statements as possible, in a single transaction. The default
+    /// implementation saves them one by one.
+    ///
+    /// # Arguments
+    /// * `tokens` - The refresh tokens to save
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of tokens saved
+    /// * `Err(DomainError)` - Saving failed (e.g., duplicate token hash)
+    async fn save_refresh_tokens_batch(&self, tokens: Vec<RefreshToken>) -> Result<usize, DomainError> {
+        let count = tokens.len();
+        for token in tokens {
+            self.save_refresh_token(token).await?;
+        }
+        Ok(count)
+    }
+
+    /// Revoke all refresh tokens of several users, e.g. to sign out every
+    /// user of a type
+    ///
+    /// Implementations backed by a database revoke the tokens of many users
+    /// per statement. The default implementation revokes them user by user.
+    ///
+    /// # Arguments
+    /// * `user_ids` - The UUIDs of the users
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of tokens revoked
+    /// * `Err(DomainError)` - Revocation failed
+    async fn revoke_tokens_batch(&self, user_ids: &[Uuid]) -> Result<usize, DomainError> {
+        let mut revoked = 0;
+        for user_id in user_ids {
+            revoked += self.revoke_all_user_tokens(*user_id).await?;
+        }
+        Ok(revoked)
+    }
+
+    /// Add several access tokens to the blacklist
+    ///
+    /// Implementations backed by a database insert many entries per
+    /// statement. The default implementation blacklists them one by one.
+    ///
+    /// # Arguments
+    /// * `tokens` - JWT IDs to blacklist, each with when its entry expires
+    ///
+    /// # Returns
+    /// * `Ok(())` - Tokens blacklisted successfully
+    /// * `Err(DomainError)` - Blacklisting failed
+    async fn blacklist_tokens_batch(
+        &self,
+        tokens: &[(String, chrono::DateTime<chrono::Utc>)],
+    ) -> Result<(), DomainError> {
+        for (token_jti, expires_at) in tokens {
+            self.blacklist_token(token_jti, *expires_at).await?;
+        }
+        Ok(())
+    }
+
     /// Delete expired refresh tokens from the repository
     ///
     /// This method should be called periodically to clean up expired tokens.
@@ -214,6 +275,21 @@ pub trait TokenRepository: Send + Sync {
     /// ```
     async fn delete_expired_tokens(&self) -> Result<usize, DomainError>;
 
+    /// Move the tokens `delete_expired_tokens` would delete to the archive
+    ///
+    /// Archived tokens are kept in `refresh_tokens_archive` so their history
+    /// remains available to security investigations after cleanup.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of tokens archived and deleted
+    /// * `Err(DomainError)` - Archival failed or is not supported; nothing
+    ///   was deleted
+    async fn archive_expired_tokens(&self) -> Result<usize, DomainError> {
+        Err(DomainError::Internal {
+            message: "Refresh token archival is not supported by this repository".to_string(),
+        })
+    }
+
     /// Check if a token exists and is valid
     ///
     /// # Arguments
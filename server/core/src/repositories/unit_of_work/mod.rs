@@ -0,0 +1,7 @@
+//! Unit of work module for writes spanning several repositories.
+
+mod r#trait;
+pub use r#trait::{UnitOfWork, UnitOfWorkTransaction};
+
+mod repository;
+pub use repository::MySqlUnitOfWork;
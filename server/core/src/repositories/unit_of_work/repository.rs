@@ -0,0 +1,10 @@
+//! Unit of work implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlUnitOfWork) is in the infrastructure layer
+// at server/infra/src/database/mysql/unit_of_work_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlUnitOfWork;
@@ -0,0 +1,65 @@
+//! Unit of work traits for writing to several repositories atomically.
+
+use async_trait::async_trait;
+
+use crate::domain::entities::audit::AuditLog;
+use crate::domain::entities::token::RefreshToken;
+use crate::domain::entities::user::User;
+use crate::errors::DomainError;
+
+/// Factory of transactions spanning the user, token and audit log stores
+///
+/// Writes made through a transaction are only visible once it is committed,
+/// and are discarded if it is rolled back or dropped, so a failure part-way
+/// through a multi-step operation leaves nothing behind.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    /// Begin a new transaction
+    ///
+    /// # Returns
+    /// * `Ok(Box<dyn UnitOfWorkTransaction>)` - The open transaction
+    /// * `Err(DomainError)` - No transaction could be started
+    async fn begin(&self) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError>;
+
+    /// Begin a new transaction writing the users of a country
+    ///
+    /// Stores keeping some countries' users in separate databases open the
+    /// transaction on the database of the country; others ignore it.
+    ///
+    /// # Arguments
+    /// * `country_code` - Country calling code of the user written (e.g., "+86")
+    async fn begin_for_country(&self, country_code: &str) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        let _ = country_code;
+        self.begin().await
+    }
+}
+
+/// An open transaction of a [`UnitOfWork`]
+#[async_trait]
+pub trait UnitOfWorkTransaction: Send {
+    /// Insert a new user
+    ///
+    /// # Returns
+    /// * `Ok(User)` - The created user
+    /// * `Err(DomainError)` - Creation failed (e.g., phone already registered)
+    async fn create_user(&mut self, user: User) -> Result<User, DomainError>;
+
+    /// Update an existing user
+    ///
+    /// # Returns
+    /// * `Ok(User)` - The updated user
+    /// * `Err(DomainError::NotFound)` - No user has the user's id
+    async fn update_user(&mut self, user: User) -> Result<User, DomainError>;
+
+    /// Save a new refresh token
+    async fn save_refresh_token(&mut self, token: RefreshToken) -> Result<RefreshToken, DomainError>;
+
+    /// Write an audit log entry
+    async fn create_audit_log(&mut self, audit_log: &AuditLog) -> Result<(), DomainError>;
+
+    /// Make every write of the transaction visible
+    async fn commit(self: Box<Self>) -> Result<(), DomainError>;
+
+    /// Discard every write of the transaction
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError>;
+}
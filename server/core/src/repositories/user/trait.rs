@@ -5,6 +5,7 @@
 //! uses Result types for proper error handling.
 
 use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream};
 use uuid::Uuid;
 
 use crate::domain::entities::user::{User, UserType};
@@ -46,6 +47,8 @@ use crate::errors::DomainError;
 pub trait UserRepository: Send + Sync {
     /// Find a user by their phone number hash and country code
     ///
+    /// Deleted users are not returned.
+    ///
     /// # Arguments
     /// * `phone_hash` - SHA-256 hash of the phone number
     /// * `country_code` - International country code (e.g., "+86", "+61")
@@ -77,6 +80,9 @@ pub trait UserRepository: Send + Sync {
 
     /// Find a user by their unique identifier
     ///
+    /// Deleted users are not returned; see
+    /// [`find_including_deleted`](Self::find_including_deleted).
+    ///
     /// # Arguments
     /// * `id` - The UUID of the user
     ///
@@ -126,6 +132,26 @@ pub trait UserRepository: Send + Sync {
     /// ```
     async fn create(&self, user: User) -> Result<User, DomainError>;
 
+    /// Create several users at once, for bulk imports
+    ///
+    /// Implementations backed by a database insert the users in a single
+    /// transaction, so either every user is created or none is. The default
+    /// implementation creates them one by one.
+    ///
+    /// # Arguments
+    /// * `users` - The users to create, none of whose phone numbers is registered
+    ///
+    /// # Returns
+    /// * `Ok(count)` - Number of users created
+    /// * `Err(DomainError)` - Creation failed (e.g., duplicate phone number)
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        let count = users.len();
+        for user in users {
+            self.create(user).await?;
+        }
+        Ok(count)
+    }
+
     /// Update an existing user in the repository
     ///
     /// # Arguments
@@ -157,12 +183,16 @@ pub trait UserRepository: Send + Sync {
 
     /// Delete a user from the repository
     ///
+    /// Database-backed implementations soft-delete the user by setting
+    /// `deleted_at`: the user is hidden from every other lookup but can be
+    /// restored with [`restore`](Self::restore) during the deletion grace period.
+    ///
     /// # Arguments
     /// * `id` - The UUID of the user to delete
     ///
     /// # Returns
     /// * `Ok(true)` - User was deleted
-    /// * `Ok(false)` - User not found
+    /// * `Ok(false)` - User not found or already deleted
     /// * `Err(DomainError)` - Deletion failed
     ///
     /// # Example
@@ -182,8 +212,42 @@ pub trait UserRepository: Send + Sync {
     /// ```
     async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
 
+    /// Find a user by their unique identifier, including deleted users
+    ///
+    /// The default implementation is for repositories that delete users
+    /// outright and falls back to [`find_by_id`](Self::find_by_id).
+    ///
+    /// # Arguments
+    /// * `id` - The UUID of the user
+    ///
+    /// # Returns
+    /// * `Ok(Some(User))` - User found; `deleted_at` is set if it was deleted
+    /// * `Ok(None)` - No user found with given ID
+    /// * `Err(DomainError)` - Database or other error occurred
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.find_by_id(id).await
+    }
+
+    /// Restore a soft-deleted user
+    ///
+    /// The default implementation is for repositories that delete users
+    /// outright, which have nothing to restore.
+    ///
+    /// # Arguments
+    /// * `id` - The UUID of the deleted user
+    ///
+    /// # Returns
+    /// * `Ok(true)` - User was restored
+    /// * `Ok(false)` - No deleted user found with given ID
+    /// * `Err(DomainError)` - Restoring failed
+    async fn restore(&self, _id: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
     /// Check if a user exists with the given phone number
     ///
+    /// Deleted users are not counted.
+    ///
     /// # Arguments
     /// * `phone_hash` - SHA-256 hash of the phone number
     /// * `country_code` - International country code
@@ -214,6 +278,8 @@ pub trait UserRepository: Send + Sync {
 
     /// Count total users by type
     ///
+    /// Deleted users are not counted.
+    ///
     /// # Arguments
     /// * `user_type` - Optional filter by user type (None counts all users)
     ///
@@ -236,4 +302,35 @@ pub trait UserRepository: Send + Sync {
     /// # }
     /// ```
     async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError>;
+
+    /// Stream every user, for exports and analytics jobs
+    ///
+    /// Users are read as the stream is polled rather than loaded at once, so
+    /// the whole table never has to fit in memory. Deleted users are skipped.
+    /// Implementations backed by a database hold a connection until the
+    /// stream is dropped. The default implementation yields a single error,
+    /// for repositories that cannot stream.
+    ///
+    /// # Arguments
+    /// * `user_type` - Optional filter by user type (None streams all users)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use futures_util::TryStreamExt;
+    /// # use renov_core::repositories::UserRepository;
+    /// # async fn example(repo: &impl UserRepository) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut users = repo.find_all_stream(None);
+    /// while let Some(user) = users.try_next().await? {
+    ///     println!("{} registered at {}", user.id, user.created_at);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn find_all_stream(&self, _user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        Box::pin(stream::once(async {
+            Err(DomainError::Internal {
+                message: "Streaming users is not supported by this repository".to_string(),
+            })
+        }))
+    }
 }
\ No newline at end of file
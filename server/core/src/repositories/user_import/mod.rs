@@ -0,0 +1,7 @@
+//! User import repository module.
+
+mod r#trait;
+pub use r#trait::UserImportRepository;
+
+mod repository;
+pub use repository::MySqlUserImportRepository;
@@ -0,0 +1,10 @@
+//! User import repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlUserImportRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/user_import_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlUserImportRepository;
@@ -0,0 +1,16 @@
+//! Repository trait for the progress of legacy user imports.
+
+use async_trait::async_trait;
+
+use crate::domain::entities::user_import::UserImportCheckpoint;
+use crate::errors::DomainError;
+
+/// Repository trait for user import checkpoints
+#[async_trait]
+pub trait UserImportRepository: Send + Sync {
+    /// Find the checkpoint of an import, if it ever committed a batch
+    async fn find_checkpoint(&self, import_id: &str) -> Result<Option<UserImportCheckpoint>, DomainError>;
+
+    /// Insert or replace the checkpoint of an import
+    async fn save_checkpoint(&self, checkpoint: &UserImportCheckpoint) -> Result<(), DomainError>;
+}
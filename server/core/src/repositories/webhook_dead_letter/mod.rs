@@ -0,0 +1,7 @@
+//! Webhook dead-letter repository module.
+
+mod r#trait;
+pub use r#trait::WebhookDeadLetterRepository;
+
+mod repository;
+pub use repository::MySqlWebhookDeadLetterRepository;
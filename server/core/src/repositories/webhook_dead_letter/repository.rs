@@ -0,0 +1,10 @@
+//! Webhook dead-letter repository implementation placeholder
+//! 
+//! This module provides a placeholder for the concrete implementation
+//! which is actually located in the infrastructure layer.
+
+// The actual implementation (MySqlWebhookDeadLetterRepository) is in the infrastructure layer
+// at server/infra/src/database/mysql/webhook_dead_letter_repository_impl.rs
+
+// Placeholder struct for compilation purposes
+pub struct MySqlWebhookDeadLetterRepository;
@@ -0,0 +1,83 @@
+//! Repository trait for webhooks that failed to be handled.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::webhook_dead_letter::{
+    DeadLetterFilter, WebhookDeadLetter, WebhookDirection,
+};
+use crate::errors::DomainError;
+
+/// Repository trait for WebhookDeadLetter persistence operations
+#[async_trait]
+pub trait WebhookDeadLetterRepository: Send + Sync {
+    /// Record a failed webhook
+    ///
+    /// A webhook that already has a dead letter, e.g. one the provider
+    /// redelivered and that failed again, keeps its dead letter: the error
+    /// is replaced, the failure count incremented and a resolved dead
+    /// letter becomes pending again.
+    ///
+    /// # Returns
+    /// * `Ok(WebhookDeadLetter)` - The stored dead letter
+    /// * `Err(DomainError)` - If the operation fails
+    async fn record(&self, dead_letter: WebhookDeadLetter) -> Result<WebhookDeadLetter, DomainError>;
+
+    /// Find a dead letter by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDeadLetter>, DomainError>;
+
+    /// List dead letters matching a filter, most recent failure first
+    async fn list(
+        &self,
+        filter: &DeadLetterFilter,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError>;
+
+    /// Find pending dead letters that first failed within a time range,
+    /// oldest first so replayed events are applied in their original order
+    ///
+    /// # Arguments
+    /// * `direction` - Only dead letters in this direction, if given
+    /// * `from` - Start of the range, inclusive
+    /// * `to` - End of the range, inclusive
+    /// * `limit` - Maximum number of dead letters to return
+    async fn find_pending(
+        &self,
+        direction: Option<WebhookDirection>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError>;
+
+    /// Record that a replay of a dead letter started
+    ///
+    /// Only one replay can run at a time: the dead letter is saved only if
+    /// it is pending, or if a previous replay has not updated it since
+    /// `stale_before` and is assumed to have stopped.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The replay was claimed by this caller
+    /// * `Ok(false)` - The dead letter is resolved or being replayed
+    async fn claim(
+        &self,
+        dead_letter: &WebhookDeadLetter,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError>;
+
+    /// Save the result of a replay
+    async fn update(&self, dead_letter: &WebhookDeadLetter) -> Result<(), DomainError>;
+
+    /// Resolve the pending dead letter of a webhook that was handled later
+    ///
+    /// # Returns
+    /// * `Ok(true)` - A pending dead letter was resolved
+    /// * `Ok(false)` - The webhook has no pending dead letter
+    async fn resolve_event(
+        &self,
+        direction: WebhookDirection,
+        source: &str,
+        event_id: &str,
+        resolution: &str,
+    ) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,26 @@
+//! Configuration for the admin single sign-on service
+
+use std::collections::HashMap;
+
+use crate::domain::entities::admin::AdminRole;
+
+/// Configuration for the admin single sign-on service
+#[derive(Debug, Clone)]
+pub struct AdminSsoServiceConfig {
+    /// Internal role granted for each identity provider group
+    pub role_mappings: HashMap<String, AdminRole>,
+    /// Lifetime of admin session tokens, in minutes
+    pub session_minutes: i64,
+    /// How long a started login can be completed, in seconds
+    pub login_ttl_seconds: u64,
+}
+
+impl Default for AdminSsoServiceConfig {
+    fn default() -> Self {
+        Self {
+            role_mappings: HashMap::new(),
+            session_minutes: 60,
+            login_ttl_seconds: 600,
+        }
+    }
+}
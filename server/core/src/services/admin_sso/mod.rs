@@ -0,0 +1,18 @@
+//! Admin single sign-on module for the admin portal
+//!
+//! This module handles:
+//! - Starting an OpenID Connect login with state, nonce and PKCE
+//! - Completing the login with the code returned by the identity provider
+//! - Mapping the provider's group claims to internal administrative roles
+//! - Recording administrators and issuing admin session tokens
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AdminSsoServiceConfig;
+pub use service::{AdminLoginRedirect, AdminLoginResult, AdminSsoService};
+pub use traits::{OidcIdentity, OidcProviderTrait, PendingSsoLogin, SsoLoginStoreTrait};
@@ -0,0 +1,181 @@
+//! Admin single sign-on service
+//!
+//! A login starts with a random state, nonce and PKCE verifier, stored until
+//! the identity provider redirects back. The callback state can only be used
+//! once, the ID token must carry the stored nonce, and the administrator's
+//! roles are recomputed from the provider's groups on every login, so
+//! removing someone from a group takes effect at their next sign-in.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use constant_time_eq::constant_time_eq;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::domain::entities::admin::{AdminIdentity, AdminRole};
+use crate::errors::{AuthError, DomainError, DomainResult};
+use crate::repositories::{AdminIdentityRepository, TokenRepository};
+use crate::services::token::TokenService;
+
+use super::config::AdminSsoServiceConfig;
+use super::traits::{OidcIdentity, OidcProviderTrait, PendingSsoLogin, SsoLoginStoreTrait};
+
+/// Where to send the administrator to sign in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminLoginRedirect {
+    /// Identity provider URL carrying the state, nonce and PKCE challenge
+    pub authorization_url: String,
+    /// State the callback must return
+    pub state: String,
+}
+
+/// A completed admin login
+#[derive(Debug, Clone)]
+pub struct AdminLoginResult {
+    /// The administrator, with the roles granted at this login
+    pub identity: AdminIdentity,
+    /// Admin session token
+    pub access_token: String,
+    /// Lifetime of the token, in seconds
+    pub expires_in: i64,
+}
+
+/// Service authenticating administrators against an OpenID Connect provider
+pub struct AdminSsoService<A, R, P>
+where
+    A: AdminIdentityRepository + 'static,
+    R: TokenRepository + 'static,
+    P: OidcProviderTrait + 'static,
+{
+    identities: Arc<A>,
+    tokens: Arc<TokenService<R>>,
+    provider: Arc<P>,
+    logins: Arc<dyn SsoLoginStoreTrait>,
+    config: AdminSsoServiceConfig,
+}
+
+impl<A, R, P> AdminSsoService<A, R, P>
+where
+    A: AdminIdentityRepository + 'static,
+    R: TokenRepository + 'static,
+    P: OidcProviderTrait + 'static,
+{
+    /// Create a new admin single sign-on service
+    pub fn new(
+        identities: Arc<A>,
+        tokens: Arc<TokenService<R>>,
+        provider: Arc<P>,
+        logins: Arc<dyn SsoLoginStoreTrait>,
+        config: AdminSsoServiceConfig,
+    ) -> Self {
+        Self { identities, tokens, provider, logins, config }
+    }
+
+    /// Start a login with the identity provider
+    ///
+    /// # Returns
+    /// * `Ok(AdminLoginRedirect)` - The provider URL to open
+    /// * `Err(DomainError::Internal)` - The pending login could not be stored
+    pub async fn start_login(&self) -> DomainResult<AdminLoginRedirect> {
+        let state = random_token();
+        let login = PendingSsoLogin {
+            nonce: random_token(),
+            code_verifier: random_token(),
+        };
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(login.code_verifier.as_bytes()));
+
+        self.logins
+            .save(&state, &login, self.config.login_ttl_seconds)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        let authorization_url = self
+            .provider
+            .authorization_url(&state, &login.nonce, &code_challenge)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        Ok(AdminLoginRedirect { authorization_url, state })
+    }
+
+    /// Complete a login with the code the identity provider returned
+    ///
+    /// # Returns
+    /// * `Ok(AdminLoginResult)` - The administrator and an admin session token
+    /// * `Err(AuthError::AuthenticationFailed)` - Unknown or reused state, or
+    ///   the code or ID token was rejected
+    /// * `Err(AuthError::InsufficientPermissions)` - None of the administrator's
+    ///   groups maps to a role
+    pub async fn complete_login(&self, state: &str, code: &str) -> DomainResult<AdminLoginResult> {
+        let login = self
+            .logins
+            .take(state)
+            .await
+            .map_err(|message| DomainError::Internal { message })?
+            .ok_or(DomainError::Auth(AuthError::AuthenticationFailed))?;
+
+        let identity = self
+            .provider
+            .exchange_code(code, &login.code_verifier)
+            .await
+            .map_err(|message| {
+                warn!("Admin single sign-on code exchange failed: {}", message);
+                DomainError::Auth(AuthError::AuthenticationFailed)
+            })?;
+
+        let nonce_matches = identity
+            .nonce
+            .as_deref()
+            .is_some_and(|nonce| constant_time_eq(nonce.as_bytes(), login.nonce.as_bytes()));
+        if !nonce_matches {
+            warn!("Admin single sign-on ID token nonce mismatch for subject {}", identity.subject);
+            return Err(DomainError::Auth(AuthError::AuthenticationFailed));
+        }
+
+        let roles = self.map_roles(&identity);
+        if roles.is_empty() {
+            warn!(
+                "Admin single sign-on denied for subject {}: no group maps to a role",
+                identity.subject
+            );
+            return Err(DomainError::Auth(AuthError::InsufficientPermissions));
+        }
+
+        let mut admin = self
+            .identities
+            .find_by_subject(&identity.issuer, &identity.subject)
+            .await?
+            .unwrap_or_else(|| AdminIdentity::new(identity.issuer.clone(), identity.subject.clone()));
+        admin.record_login(identity.email, identity.name, roles);
+        let admin = self.identities.save(admin).await?;
+
+        let expires_in = self.config.session_minutes * 60;
+        let access_token = self
+            .tokens
+            .generate_admin_token(admin.id, admin.role_names(), expires_in)?;
+
+        info!("Administrator {} signed in with roles {:?}", admin.id, admin.role_names());
+
+        Ok(AdminLoginResult { identity: admin, access_token, expires_in })
+    }
+
+    /// Map the provider's groups to distinct roles, in a stable order
+    fn map_roles(&self, identity: &OidcIdentity) -> Vec<AdminRole> {
+        let mut roles: Vec<AdminRole> = identity
+            .groups
+            .iter()
+            .filter_map(|group| self.config.role_mappings.get(group).copied())
+            .collect();
+        roles.sort();
+        roles.dedup();
+        roles
+    }
+}
+
+/// Generate a random URL-safe value for the state, nonce and PKCE verifier
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
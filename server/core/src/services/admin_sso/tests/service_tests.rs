@@ -0,0 +1,261 @@
+//! Unit tests for the admin single sign-on service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use jsonwebtoken::Algorithm;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::admin::{AdminIdentity, AdminRole};
+use crate::domain::entities::token::RefreshToken;
+use crate::errors::{AuthError, DomainError};
+use crate::repositories::{AdminIdentityRepository, TokenRepository};
+use crate::services::admin_sso::{
+    AdminSsoService, AdminSsoServiceConfig, OidcIdentity, OidcProviderTrait, PendingSsoLogin,
+    SsoLoginStoreTrait,
+};
+use crate::services::token::{TokenService, TokenServiceConfig};
+
+const ISSUER: &str = "https://renoveasy.okta.com";
+
+#[derive(Default)]
+struct MockAdminIdentityRepository {
+    identities: Mutex<Vec<AdminIdentity>>,
+}
+
+#[async_trait]
+impl AdminIdentityRepository for MockAdminIdentityRepository {
+    async fn find_by_subject(&self, issuer: &str, subject: &str) -> Result<Option<AdminIdentity>, DomainError> {
+        Ok(self
+            .identities
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|i| i.issuer == issuer && i.subject == subject)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<AdminIdentity>, DomainError> {
+        Ok(self.identities.lock().unwrap().iter().find(|i| i.id == id).cloned())
+    }
+
+    async fn save(&self, identity: AdminIdentity) -> Result<AdminIdentity, DomainError> {
+        let mut identities = self.identities.lock().unwrap();
+        identities.retain(|i| i.id != identity.id);
+        identities.push(identity.clone());
+        Ok(identity)
+    }
+}
+
+/// Provider that signs in one administrator with the nonce of the last login started
+#[derive(Default)]
+struct MockOidcProvider {
+    groups: Mutex<Vec<String>>,
+    last_nonce: Mutex<Option<String>>,
+    last_challenge: Mutex<Option<String>>,
+}
+
+#[async_trait]
+impl OidcProviderTrait for MockOidcProvider {
+    async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String, String> {
+        *self.last_nonce.lock().unwrap() = Some(nonce.to_string());
+        *self.last_challenge.lock().unwrap() = Some(code_challenge.to_string());
+        Ok(format!("{}/oauth2/v1/authorize?state={}", ISSUER, state))
+    }
+
+    async fn exchange_code(&self, code: &str, _code_verifier: &str) -> Result<OidcIdentity, String> {
+        if code != "valid-code" {
+            return Err("invalid_grant".to_string());
+        }
+        Ok(OidcIdentity {
+            issuer: ISSUER.to_string(),
+            subject: "00u1abcd".to_string(),
+            email: Some("ops@renoveasy.com".to_string()),
+            name: Some("Ops Team".to_string()),
+            groups: self.groups.lock().unwrap().clone(),
+            nonce: self.last_nonce.lock().unwrap().clone(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct MemoryLoginStore {
+    logins: Mutex<HashMap<String, PendingSsoLogin>>,
+}
+
+#[async_trait]
+impl SsoLoginStoreTrait for MemoryLoginStore {
+    async fn save(&self, state: &str, login: &PendingSsoLogin, _ttl_seconds: u64) -> Result<(), String> {
+        self.logins.lock().unwrap().insert(state.to_string(), login.clone());
+        Ok(())
+    }
+
+    async fn take(&self, state: &str) -> Result<Option<PendingSsoLogin>, String> {
+        Ok(self.logins.lock().unwrap().remove(state))
+    }
+}
+
+/// Token repository without refresh tokens or blacklisted tokens
+struct NoopTokenRepository;
+
+#[async_trait]
+impl TokenRepository for NoopTokenRepository {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        Ok(token)
+    }
+
+    async fn find_refresh_token(&self, _token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn revoke_token(&self, _token_hash: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn revoke_all_user_tokens(&self, _user_id: Uuid) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn count_user_tokens(&self, _user_id: Uuid) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn find_by_token_family(&self, _token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn revoke_token_family(&self, _token_family: &str) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn is_token_blacklisted(&self, _token_jti: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn blacklist_token(&self, _token_jti: &str, _expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+}
+
+struct Fixture {
+    service: AdminSsoService<MockAdminIdentityRepository, NoopTokenRepository, MockOidcProvider>,
+    identities: Arc<MockAdminIdentityRepository>,
+    provider: Arc<MockOidcProvider>,
+    tokens: Arc<TokenService<NoopTokenRepository>>,
+}
+
+fn fixture(groups: &[&str]) -> Fixture {
+    let identities = Arc::new(MockAdminIdentityRepository::default());
+    let provider = Arc::new(MockOidcProvider::default());
+    *provider.groups.lock().unwrap() = groups.iter().map(|g| g.to_string()).collect();
+    // Use HS256 for tests to avoid needing key files
+    let mut token_config = TokenServiceConfig::default();
+    token_config.algorithm = Algorithm::HS256;
+    let tokens = Arc::new(TokenService::new(NoopTokenRepository, token_config).unwrap());
+
+    let config = AdminSsoServiceConfig {
+        role_mappings: HashMap::from([
+            ("renoveasy-admins".to_string(), AdminRole::Admin),
+            ("renoveasy-support".to_string(), AdminRole::Support),
+            ("renoveasy-finance".to_string(), AdminRole::Finance),
+        ]),
+        ..Default::default()
+    };
+    let service = AdminSsoService::new(
+        identities.clone(),
+        tokens.clone(),
+        provider.clone(),
+        Arc::new(MemoryLoginStore::default()),
+        config,
+    );
+
+    Fixture { service, identities, provider, tokens }
+}
+
+#[tokio::test]
+async fn test_login_maps_groups_to_roles_and_issues_admin_token() {
+    let f = fixture(&["everyone", "renoveasy-finance", "renoveasy-support"]);
+
+    let redirect = f.service.start_login().await.unwrap();
+    assert!(redirect.authorization_url.contains(&redirect.state));
+    assert!(f.provider.last_challenge.lock().unwrap().is_some());
+
+    let result = f.service.complete_login(&redirect.state, "valid-code").await.unwrap();
+    assert_eq!(result.identity.roles, vec![AdminRole::Support, AdminRole::Finance]);
+    assert_eq!(result.expires_in, 3600);
+
+    let claims = f.tokens.verify_access_token(&result.access_token).await.unwrap();
+    assert_eq!(claims.user_id().unwrap(), result.identity.id);
+    assert_eq!(claims.user_type.as_deref(), Some("admin"));
+    assert!(claims.has_role("finance"));
+    assert!(!claims.has_role("admin"));
+}
+
+#[tokio::test]
+async fn test_repeat_login_reuses_identity_and_refreshes_roles() {
+    let f = fixture(&["renoveasy-admins"]);
+    let redirect = f.service.start_login().await.unwrap();
+    let first = f.service.complete_login(&redirect.state, "valid-code").await.unwrap();
+
+    *f.provider.groups.lock().unwrap() = vec!["renoveasy-support".to_string()];
+    let redirect = f.service.start_login().await.unwrap();
+    let second = f.service.complete_login(&redirect.state, "valid-code").await.unwrap();
+
+    assert_eq!(first.identity.id, second.identity.id);
+    assert_eq!(f.identities.identities.lock().unwrap().len(), 1);
+    let stored = f.identities.find_by_id(first.identity.id).await.unwrap().unwrap();
+    assert_eq!(stored.roles, vec![AdminRole::Support]);
+}
+
+#[tokio::test]
+async fn test_login_without_mapped_group_is_forbidden() {
+    let f = fixture(&["everyone"]);
+    let redirect = f.service.start_login().await.unwrap();
+
+    let result = f.service.complete_login(&redirect.state, "valid-code").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::InsufficientPermissions))));
+    assert!(f.identities.identities.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_state_is_single_use_and_checked() {
+    let f = fixture(&["renoveasy-admins"]);
+
+    let result = f.service.complete_login("unknown", "valid-code").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::AuthenticationFailed))));
+
+    let redirect = f.service.start_login().await.unwrap();
+    let result = f.service.complete_login(&redirect.state, "bad-code").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::AuthenticationFailed))));
+
+    // A failed callback consumes the state as well
+    let result = f.service.complete_login(&redirect.state, "valid-code").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::AuthenticationFailed))));
+}
+
+#[tokio::test]
+async fn test_id_token_must_carry_login_nonce() {
+    let f = fixture(&["renoveasy-admins"]);
+    let redirect = f.service.start_login().await.unwrap();
+    *f.provider.last_nonce.lock().unwrap() = Some("replayed-nonce".to_string());
+
+    let result = f.service.complete_login(&redirect.state, "valid-code").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::AuthenticationFailed))));
+}
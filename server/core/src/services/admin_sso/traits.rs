@@ -0,0 +1,59 @@
+//! Traits for the OpenID Connect provider and pending login storage
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Administrator identity asserted by a verified ID token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcIdentity {
+    /// Issuer URL of the identity provider
+    pub issuer: String,
+    /// Subject identifier at the identity provider
+    pub subject: String,
+    /// Email address, if released by the provider
+    pub email: Option<String>,
+    /// Display name, if released by the provider
+    pub name: Option<String>,
+    /// Groups the administrator belongs to
+    pub groups: Vec<String>,
+    /// Nonce carried by the ID token
+    pub nonce: Option<String>,
+}
+
+/// Trait for an OpenID Connect identity provider such as Okta or Azure AD
+#[async_trait]
+pub trait OidcProviderTrait: Send + Sync {
+    /// Build the provider URL the administrator is sent to
+    ///
+    /// # Arguments
+    /// * `state` - Opaque value matched on the callback
+    /// * `nonce` - Value the ID token must carry
+    /// * `code_challenge` - PKCE S256 challenge
+    async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String, String>;
+
+    /// Exchange an authorization code and verify the returned ID token
+    ///
+    /// # Returns
+    /// * `Ok(OidcIdentity)` - The identity from a token with a valid signature, issuer and audience
+    /// * `Err(String)` - The exchange failed or the ID token was rejected
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<OidcIdentity, String>;
+}
+
+/// A login started but not yet completed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSsoLogin {
+    /// Nonce the ID token must carry
+    pub nonce: String,
+    /// PKCE verifier for the code exchange
+    pub code_verifier: String,
+}
+
+/// Trait for storing logins between the redirect and the callback
+#[async_trait]
+pub trait SsoLoginStoreTrait: Send + Sync {
+    /// Store a pending login under its state
+    async fn save(&self, state: &str, login: &PendingSsoLogin, ttl_seconds: u64) -> Result<(), String>;
+
+    /// Remove and return a pending login, so each state can only be used once
+    async fn take(&self, state: &str) -> Result<Option<PendingSsoLogin>, String>;
+}
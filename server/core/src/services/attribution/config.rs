@@ -0,0 +1,19 @@
+//! Configuration for the attribution service
+
+/// Configuration for the attribution service
+#[derive(Debug, Clone)]
+pub struct AttributionConfig {
+    /// Longest period a cohort report may cover (in days)
+    pub max_report_days: i64,
+    /// Whether to record attribution at all
+    pub enabled: bool,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            max_report_days: 366,
+            enabled: true,
+        }
+    }
+}
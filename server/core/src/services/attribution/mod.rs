@@ -0,0 +1,16 @@
+//! Marketing attribution service module for evaluating acquisition campaigns
+//!
+//! This module handles:
+//! - Recording the UTM parameters and referrer users registered with, only
+//!   when they consented to marketing
+//! - Deleting recorded attribution when consent is withdrawn
+//! - Weekly cohort reports of registrations and activations per campaign
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::AttributionConfig;
+pub use service::{AttributionService, ATTRIBUTION_HOOK_NAME};
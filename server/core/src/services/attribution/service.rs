@@ -0,0 +1,141 @@
+//! Attribution service for acquisition campaigns
+//!
+//! Registrations reach this service as `UserRegistered` lifecycle events,
+//! so recording attribution never slows down or fails a sign-in. The
+//! authentication service only puts attribution on the event when the user
+//! consented to marketing, and this service checks the consent again before
+//! saving anything.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::entities::attribution::{AttributionCohort, RegistrationAttribution, UserAttribution};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::AttributionRepository;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookTrait};
+
+use super::config::AttributionConfig;
+
+/// Name of the lifecycle hook recording attribution
+pub const ATTRIBUTION_HOOK_NAME: &str = "marketing_attribution";
+
+/// Service for recording and reporting marketing attribution
+pub struct AttributionService<R>
+where
+    R: AttributionRepository + 'static,
+{
+    repository: Arc<R>,
+    config: AttributionConfig,
+}
+
+impl<R> AttributionService<R>
+where
+    R: AttributionRepository + 'static,
+{
+    /// Create a new attribution service
+    pub fn new(repository: Arc<R>, config: AttributionConfig) -> Self {
+        Self { repository, config }
+    }
+
+    /// Create a new attribution service with default configuration
+    pub fn with_defaults(repository: Arc<R>) -> Self {
+        Self::new(repository, AttributionConfig::default())
+    }
+
+    /// Record the attribution a user registered with
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The attribution was saved
+    /// * `Ok(false)` - Nothing was saved: recording is disabled, the user did
+    ///   not consent to marketing, or the attribution has no values
+    pub async fn record_registration(
+        &self,
+        user_id: Uuid,
+        registered_at: DateTime<Utc>,
+        attribution: RegistrationAttribution,
+    ) -> DomainResult<bool> {
+        if !self.config.enabled {
+            return Ok(false);
+        }
+        let Some(attribution) = attribution.consented() else {
+            return Ok(false);
+        };
+
+        self.repository
+            .save(&UserAttribution {
+                user_id,
+                attribution,
+                registered_at,
+            })
+            .await?;
+        Ok(true)
+    }
+
+    /// Delete a user's attribution after they withdrew marketing consent
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The attribution was deleted
+    /// * `Ok(false)` - None was recorded
+    pub async fn withdraw_consent(&self, user_id: Uuid) -> DomainResult<bool> {
+        self.repository.delete_by_user(user_id).await
+    }
+
+    /// Registrations and activations per weekly cohort and campaign
+    ///
+    /// # Arguments
+    /// * `from` - First day of registrations included (UTC)
+    /// * `to` - Last day of registrations included (UTC)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<AttributionCohort>)` - Cohorts ordered by week
+    /// * `Err(DomainError::Validation)` - The period is reversed or too long
+    pub async fn cohort_report(&self, from: NaiveDate, to: NaiveDate) -> DomainResult<Vec<AttributionCohort>> {
+        if from > to {
+            return Err(DomainError::Validation {
+                message: "Report period must not end before it starts".to_string(),
+            });
+        }
+        if (to - from).num_days() + 1 > self.config.max_report_days {
+            return Err(DomainError::Validation {
+                message: format!("Report period must not exceed {} days", self.config.max_report_days),
+            });
+        }
+
+        let start = from.and_time(NaiveTime::MIN).and_utc();
+        let end = to.and_time(NaiveTime::MIN).and_utc() + Duration::days(1);
+        self.repository.cohorts(start, end).await
+    }
+}
+
+#[async_trait]
+impl<R> LifecycleHookTrait for AttributionService<R>
+where
+    R: AttributionRepository + 'static,
+{
+    fn name(&self) -> &str {
+        ATTRIBUTION_HOOK_NAME
+    }
+
+    fn handles(&self, event: &LifecycleEvent) -> bool {
+        matches!(event, LifecycleEvent::UserRegistered { attribution: Some(_), .. })
+    }
+
+    async fn handle(&self, event: &LifecycleEvent) -> Result<(), String> {
+        let LifecycleEvent::UserRegistered {
+            user_id,
+            registered_at,
+            attribution: Some(attribution),
+            ..
+        } = event
+        else {
+            return Ok(());
+        };
+
+        self.record_registration(*user_id, *registered_at, attribution.clone())
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
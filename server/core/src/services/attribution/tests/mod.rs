@@ -0,0 +1,4 @@
+//! Tests for the attribution service
+
+#[cfg(test)]
+mod service_tests;
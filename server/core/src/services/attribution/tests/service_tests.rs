@@ -0,0 +1,149 @@
+//! Unit tests for the attribution service
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::attribution::{AttributionCohort, RegistrationAttribution, UserAttribution};
+use crate::errors::DomainError;
+use crate::repositories::AttributionRepository;
+use crate::services::attribution::{AttributionConfig, AttributionService};
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookTrait};
+
+#[derive(Default)]
+struct MockAttributionRepository {
+    attributions: Mutex<HashMap<Uuid, UserAttribution>>,
+    cohort_ranges: Mutex<Vec<(DateTime<Utc>, DateTime<Utc>)>>,
+}
+
+#[async_trait]
+impl AttributionRepository for MockAttributionRepository {
+    async fn save(&self, attribution: &UserAttribution) -> Result<(), DomainError> {
+        self.attributions.lock().unwrap().insert(attribution.user_id, attribution.clone());
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<UserAttribution>, DomainError> {
+        Ok(self.attributions.lock().unwrap().get(&user_id).cloned())
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<bool, DomainError> {
+        Ok(self.attributions.lock().unwrap().remove(&user_id).is_some())
+    }
+
+    async fn cohorts(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<AttributionCohort>, DomainError> {
+        self.cohort_ranges.lock().unwrap().push((from, to));
+        Ok(Vec::new())
+    }
+}
+
+fn service() -> (AttributionService<MockAttributionRepository>, Arc<MockAttributionRepository>) {
+    let repository = Arc::new(MockAttributionRepository::default());
+    (AttributionService::with_defaults(repository.clone()), repository)
+}
+
+fn attribution(consent: bool) -> RegistrationAttribution {
+    RegistrationAttribution {
+        utm_source: Some("Google".to_string()),
+        utm_medium: Some("cpc".to_string()),
+        utm_campaign: Some("spring_kitchens".to_string()),
+        referrer: Some("https://www.google.com/search?q=renovation".to_string()),
+        marketing_consent: consent,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_record_registration_saves_normalized_attribution_with_consent() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+
+    assert!(service.record_registration(user_id, Utc::now(), attribution(true)).await.unwrap());
+
+    let saved = repository.find_by_user(user_id).await.unwrap().unwrap();
+    assert_eq!(saved.attribution.utm_source.as_deref(), Some("google"));
+    assert_eq!(saved.attribution.referrer.as_deref(), Some("www.google.com"));
+}
+
+#[tokio::test]
+async fn test_record_registration_skips_without_consent() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+
+    assert!(!service.record_registration(user_id, Utc::now(), attribution(false)).await.unwrap());
+    assert!(repository.find_by_user(user_id).await.unwrap().is_none());
+
+    let disabled = AttributionService::new(
+        repository.clone(),
+        AttributionConfig {
+            enabled: false,
+            ..AttributionConfig::default()
+        },
+    );
+    assert!(!disabled.record_registration(user_id, Utc::now(), attribution(true)).await.unwrap());
+    assert!(repository.find_by_user(user_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_withdraw_consent_deletes_attribution() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    service.record_registration(user_id, Utc::now(), attribution(true)).await.unwrap();
+
+    assert!(service.withdraw_consent(user_id).await.unwrap());
+    assert!(!service.withdraw_consent(user_id).await.unwrap());
+    assert!(repository.find_by_user(user_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_hook_records_attributed_registrations() {
+    let (service, repository) = service();
+    let user_id = Uuid::new_v4();
+    let registered = |attribution| LifecycleEvent::UserRegistered {
+        user_id,
+        country_code: "+61".to_string(),
+        registered_at: Utc::now(),
+        attribution,
+    };
+
+    assert!(!service.handles(&registered(None)));
+
+    let event = registered(Some(attribution(true)));
+    assert!(service.handles(&event));
+    service.handle(&event).await.unwrap();
+    assert!(repository.find_by_user(user_id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_cohort_report_covers_whole_days() {
+    let (service, repository) = service();
+    let from = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+    let to = NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+
+    service.cohort_report(from, to).await.unwrap();
+
+    let ranges = repository.cohort_ranges.lock().unwrap().clone();
+    assert_eq!(
+        ranges,
+        vec![(
+            Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap(),
+        )]
+    );
+}
+
+#[tokio::test]
+async fn test_cohort_report_rejects_invalid_periods() {
+    let (service, _) = service();
+    let day = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+
+    let reversed = service.cohort_report(day, day.pred_opt().unwrap()).await;
+    assert!(matches!(reversed, Err(DomainError::Validation { .. })));
+
+    let too_long = service.cohort_report(day, day + chrono::Duration::days(366)).await;
+    assert!(matches!(too_long, Err(DomainError::Validation { .. })));
+
+    assert!(service.cohort_report(day, day + chrono::Duration::days(365)).await.is_ok());
+}
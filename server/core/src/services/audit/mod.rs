@@ -1,8 +1,10 @@
 //! Audit service module for recording authentication attempts and security events.
 
 mod service;
+mod traits;
 
 pub use service::{AuditService, AuditServiceConfig};
+pub use traits::AuditEventPublisherTrait;
 
 #[cfg(test)]
 mod tests;
\ No newline at end of file
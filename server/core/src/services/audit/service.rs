@@ -5,14 +5,28 @@
 //! blocking the main authentication flow.
 
 use std::sync::Arc;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde_json::{json, Value as JsonValue};
 use tokio::task;
 use uuid::Uuid;
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType, actions};
-use crate::errors::DomainResult;
+use re_shared::types::{PaginatedResponse, Pagination};
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter, actions};
+use crate::errors::{DomainError, DomainResult};
 use crate::repositories::AuditLogRepository;
+use crate::services::tenant::{current_tenant, scope};
+use crate::services::trace::current_trace_id;
+
+use super::traits::AuditEventPublisherTrait;
+
+/// Event types listed in a user's login history
+const LOGIN_HISTORY_EVENTS: [AuditEventType; 4] = [
+    AuditEventType::LoginSuccess,
+    AuditEventType::LoginFailure,
+    AuditEventType::VerifyCodeFailure,
+    AuditEventType::AccountLocked,
+];
 
 /// Configuration for the audit service
 #[derive(Debug, Clone)]
@@ -45,6 +59,7 @@ where
 {
     repository: Arc<R>,
     config: AuditServiceConfig,
+    publisher: Option<Arc<dyn AuditEventPublisherTrait>>,
 }
 
 impl<R> AuditService<R>
@@ -53,7 +68,19 @@ where
 {
     /// Create a new audit service
     pub fn new(repository: Arc<R>, config: AuditServiceConfig) -> Self {
-        Self { repository, config }
+        Self {
+            repository,
+            config,
+            publisher: None,
+        }
+    }
+
+    /// Notify a publisher of every recorded event
+    ///
+    /// Used to forward security events to operator webhooks.
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn AuditEventPublisherTrait>) -> Self {
+        self.publisher = Some(publisher);
+        self
     }
 
     /// Log an authentication attempt (backward compatibility)
@@ -220,6 +247,9 @@ where
     }
 
     /// Enhanced: Log login success with comprehensive details
+    ///
+    /// `country` is the country the client IP resolved to, if known.
+    #[allow(clippy::too_many_arguments)]
     pub async fn log_login_success(
         &self,
         user_id: Uuid,
@@ -228,28 +258,45 @@ where
         ip_address: String,
         user_agent: Option<String>,
         token_id: Uuid,
+        country: Option<String>,
     ) -> DomainResult<()> {
+        let audit_log =
+            Self::login_success_log(user_id, phone, phone_hash, ip_address, user_agent, token_id, country);
+        self.write_log(audit_log).await
+    }
+
+    /// Build the login success entry without writing it
+    ///
+    /// Used when the entry is written as part of a larger transaction; pass
+    /// it to [`Self::publish_log`] once the transaction is committed.
+    pub(crate) fn login_success_log(
+        user_id: Uuid,
+        phone: &str,
+        phone_hash: &str,
+        ip_address: String,
+        user_agent: Option<String>,
+        token_id: Uuid,
+        country: Option<String>,
+    ) -> AuditLog {
         let event_data = json!({
             "token_id": token_id.to_string(),
-            "login_method": "passwordless"
+            "login_method": "passwordless",
+            "country": country,
         });
 
-        let audit_log = AuditLog::new(AuditEventType::LoginSuccess, ip_address)
+        let mut audit_log = AuditLog::new(AuditEventType::LoginSuccess, ip_address)
             .with_user(user_id)
             .with_phone(phone, phone_hash)
             .with_token_id(token_id)
             .with_event_data(event_data);
+        audit_log.trace_id = current_trace_id();
 
-        let audit_log = if let Some(ua) = user_agent {
-            let mut log = audit_log;
-            log.user_agent = Some(ua.clone());
-            log.device_info = Some(AuditLog::extract_device_info(&ua));
-            log
-        } else {
-            audit_log
-        };
+        if let Some(ua) = user_agent {
+            audit_log.device_info = Some(AuditLog::extract_device_info(&ua));
+            audit_log.user_agent = Some(ua);
+        }
 
-        self.write_log(audit_log).await
+        audit_log
     }
 
     /// Enhanced: Log login failure with detailed reason
@@ -351,6 +398,65 @@ where
         self.write_log(audit_log).await
     }
 
+    /// Log a revoked refresh token being presented again
+    ///
+    /// Reuse of a rotated token indicates it may have been stolen, so the
+    /// event is forwarded to security webhooks.
+    pub async fn log_refresh_token_reuse(
+        &self,
+        user_id: Option<Uuid>,
+        token_family: Option<&str>,
+        ip_address: String,
+        user_agent: Option<String>,
+    ) -> DomainResult<()> {
+        let mut audit_log = AuditLog::new(AuditEventType::RefreshTokenReuseDetected, ip_address)
+            .with_failure_reason("Revoked refresh token presented")
+            .with_event_data(json!({
+                "token_family": token_family,
+                "detection_time": Utc::now().to_rfc3339()
+            }));
+
+        if let Some(uid) = user_id {
+            audit_log = audit_log.with_user(uid);
+        }
+
+        if let Some(ua) = user_agent {
+            audit_log.user_agent = Some(ua.clone());
+            audit_log.device_info = Some(AuditLog::extract_device_info(&ua));
+        }
+
+        self.write_log(audit_log).await
+    }
+
+    /// Log an action taken by an administrator
+    ///
+    /// # Arguments
+    /// * `admin_id` - ID of the administrator
+    /// * `action` - What was done, e.g. `unlock_account`
+    /// * `target` - Optional identifier of the affected resource
+    /// * `ip_address` - IP address the request came from
+    /// * `details` - Optional action-specific data
+    pub async fn log_admin_action(
+        &self,
+        admin_id: Uuid,
+        action: &str,
+        target: Option<&str>,
+        ip_address: String,
+        details: Option<JsonValue>,
+    ) -> DomainResult<()> {
+        let event_data = json!({
+            "admin_action": action,
+            "target": target,
+            "details": details,
+        });
+
+        let audit_log = AuditLog::new(AuditEventType::AdminAction, ip_address)
+            .with_user(admin_id)
+            .with_event_data(event_data);
+
+        self.write_log(audit_log).await
+    }
+
     /// Log suspicious activity detection
     pub async fn log_suspicious_activity(
         &self,
@@ -431,6 +537,22 @@ where
         self.repository.find_by_user(user_id, limit).await
     }
 
+    /// Get a user's recent successful and failed sign-ins, newest first
+    ///
+    /// Failed attempts are only attributed to a user when the phone number
+    /// belonged to an existing account at the time of the attempt.
+    pub async fn login_history(&self, user_id: Uuid, limit: u32) -> DomainResult<Vec<AuditLog>> {
+        let filter = AuditLogFilter {
+            user_id: Some(user_id),
+            event_types: LOGIN_HISTORY_EVENTS.to_vec(),
+            ..Default::default()
+        };
+
+        let pagination = Pagination::new(1, limit);
+        let (logs, _) = self.repository.query(&filter, &pagination).await?;
+        Ok(logs)
+    }
+
     /// Get recent audit logs for a phone number
     pub async fn get_phone_audit_logs(
         &self,
@@ -440,6 +562,43 @@ where
         self.repository.find_by_phone_hash(phone_hash, limit).await
     }
 
+    /// Get audit logs of the given event types within a time range
+    ///
+    /// # Returns
+    /// * Matching logs ordered by creation time descending
+    pub async fn find_events(
+        &self,
+        event_types: Vec<AuditEventType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> DomainResult<Vec<AuditLog>> {
+        self.repository.find_by_event_types(event_types, from, to, limit).await
+    }
+
+    /// Query audit logs page by page, newest first
+    ///
+    /// # Returns
+    /// * `Ok(PaginatedResponse<AuditLog>)` - The requested page and the total match count
+    /// * `Err(DomainError::Validation)` - The date range ends before it starts
+    pub async fn query_logs(
+        &self,
+        filter: AuditLogFilter,
+        pagination: Pagination,
+    ) -> DomainResult<PaginatedResponse<AuditLog>> {
+        if let (Some(from), Some(to)) = (filter.date_range.from, filter.date_range.to) {
+            if from > to {
+                return Err(DomainError::Validation {
+                    message: "Date range must not end before it starts".to_string(),
+                });
+            }
+        }
+
+        let pagination = pagination.validate();
+        let (logs, total) = self.repository.query(&filter, &pagination).await?;
+        Ok(PaginatedResponse::new(logs, pagination, total))
+    }
+
     /// Archive old audit logs based on retention policy (90 days)
     ///
     /// This method should be called periodically (e.g., daily) to archive
@@ -464,15 +623,25 @@ where
 
     /// Internal method to write audit logs
     ///
-    /// If async_writes is enabled, the write happens in a background task
-    /// to avoid blocking the main flow.
-    async fn write_log(&self, audit_log: AuditLog) -> DomainResult<()> {
+    /// The log is stamped with the trace ID of the request being served
+    /// before any background task is spawned, since spawned tasks do not
+    /// inherit the trace context. If async_writes is enabled, the write
+    /// happens in a background task to avoid blocking the main flow, run
+    /// in the tenant scope of the request for the same reason.
+    async fn write_log(&self, mut audit_log: AuditLog) -> DomainResult<()> {
+        if audit_log.trace_id.is_none() {
+            audit_log.trace_id = current_trace_id();
+        }
+
+        self.publish_log(&audit_log);
+
         if self.config.async_writes {
             let repository = Arc::clone(&self.repository);
+            let tenant = current_tenant();
 
             // Spawn a background task for async write
             task::spawn(async move {
-                if let Err(e) = repository.create(&audit_log).await {
+                if let Err(e) = scope(tenant, repository.create(&audit_log)).await {
                     // Log the error but don't fail the main operation
                     eprintln!("Failed to write audit log: {:?}", e);
                 }
@@ -484,4 +653,19 @@ where
             self.repository.create(&audit_log).await
         }
     }
+
+    /// Publish an entry to the external publisher, if any, in the background
+    ///
+    /// Runs in the tenant scope of the request, like the async write.
+    pub(crate) fn publish_log(&self, audit_log: &AuditLog) {
+        if let Some(publisher) = self.publisher.clone() {
+            let event = audit_log.clone();
+            let tenant = current_tenant();
+            task::spawn(async move {
+                if let Err(e) = scope(tenant, publisher.publish(&event)).await {
+                    tracing::warn!(error = %e, "Failed to publish audit event");
+                }
+            });
+        }
+    }
 }
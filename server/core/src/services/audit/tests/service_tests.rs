@@ -6,14 +6,18 @@ use uuid::Uuid;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType, actions};
+use re_shared::types::{DateRange, Pagination};
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter, actions};
 use crate::errors::DomainError;
 use crate::repositories::AuditLogRepository;
-use crate::services::audit::{AuditService, AuditServiceConfig};
+use crate::services::audit::{AuditEventPublisherTrait, AuditService, AuditServiceConfig};
+use crate::services::tenant::{current_tenant, scope, TenantId};
 
 /// Mock implementation of AuditLogRepository for testing
 struct MockAuditLogRepository {
     logs: Arc<Mutex<Vec<AuditLog>>>,
+    tenants: Arc<Mutex<Vec<TenantId>>>,
     should_fail: Arc<Mutex<bool>>,
 }
 
@@ -21,6 +25,7 @@ impl MockAuditLogRepository {
     fn new() -> Self {
         Self {
             logs: Arc::new(Mutex::new(Vec::new())),
+            tenants: Arc::new(Mutex::new(Vec::new())),
             should_fail: Arc::new(Mutex::new(false)),
         }
     }
@@ -32,6 +37,25 @@ impl MockAuditLogRepository {
     fn get_all_logs(&self) -> Vec<AuditLog> {
         self.logs.lock().unwrap().clone()
     }
+
+    /// Tenants the logs were written for, in order
+    fn get_tenants(&self) -> Vec<TenantId> {
+        self.tenants.lock().unwrap().clone()
+    }
+}
+
+/// Publisher recording the tenant each event was published in
+#[derive(Default)]
+struct TenantRecordingPublisher {
+    tenants: Mutex<Vec<TenantId>>,
+}
+
+#[async_trait]
+impl AuditEventPublisherTrait for TenantRecordingPublisher {
+    async fn publish(&self, _audit_log: &AuditLog) -> Result<(), String> {
+        self.tenants.lock().unwrap().push(current_tenant());
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -41,6 +65,7 @@ impl AuditLogRepository for MockAuditLogRepository {
             return Err(DomainError::Internal { message: "Mock failure".to_string() });
         }
         self.logs.lock().unwrap().push(audit_log.clone());
+        self.tenants.lock().unwrap().push(current_tenant());
         Ok(())
     }
 
@@ -142,6 +167,14 @@ impl AuditLogRepository for MockAuditLogRepository {
         // Mock implementation - just return 0
         Ok(0)
     }
+
+    async fn archive_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
     
     async fn find_by_event_types(
         &self,
@@ -156,6 +189,29 @@ impl AuditLogRepository for MockAuditLogRepository {
         // Mock implementation - return empty list
         Ok(Vec::new())
     }
+
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        let mut logs: Vec<AuditLog> = self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|log| filter.matches(log))
+            .cloned()
+            .collect();
+        logs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        let total = logs.len() as u64;
+        let page = logs
+            .into_iter()
+            .skip(pagination.offset() as usize)
+            .take(pagination.limit() as usize)
+            .collect();
+        Ok((page, total))
+    }
 }
 
 #[tokio::test]
@@ -566,6 +622,66 @@ async fn test_async_writes_enabled() {
     assert_eq!(logs[0].action, actions::LOGIN_ATTEMPT);
 }
 
+#[tokio::test]
+async fn test_async_writes_keep_the_request_tenant() {
+    let repo = Arc::new(MockAuditLogRepository::new());
+    let config = AuditServiceConfig {
+        async_writes: true,
+        ..Default::default()
+    };
+    let service = AuditService::new(Arc::clone(&repo), config);
+    let tenant = TenantId::parse("acme").unwrap();
+
+    let result = scope(
+        tenant.clone(),
+        service.log_login(
+            None,
+            Some("phone_hash_tenant".to_string()),
+            true,
+            Some("10.0.0.11".to_string()),
+            None,
+            None,
+        ),
+    )
+    .await;
+    assert!(result.is_ok());
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(repo.get_tenants(), vec![tenant]);
+}
+
+#[tokio::test]
+async fn test_published_events_keep_the_request_tenant() {
+    let repo = Arc::new(MockAuditLogRepository::new());
+    let publisher = Arc::new(TenantRecordingPublisher::default());
+    let config = AuditServiceConfig {
+        async_writes: false,
+        ..Default::default()
+    };
+    let service = AuditService::new(Arc::clone(&repo), config)
+        .with_event_publisher(publisher.clone());
+    let tenant = TenantId::parse("acme").unwrap();
+
+    let result = scope(
+        tenant.clone(),
+        service.log_login(
+            None,
+            Some("phone_hash_tenant".to_string()),
+            true,
+            Some("10.0.0.12".to_string()),
+            None,
+            None,
+        ),
+    )
+    .await;
+    assert!(result.is_ok());
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(*publisher.tenants.lock().unwrap(), vec![tenant]);
+}
+
 #[tokio::test]
 async fn test_log_login_success() {
     let repo = Arc::new(MockAuditLogRepository::new());
@@ -650,4 +766,59 @@ async fn test_repository_error_handling() {
         .await;
 
     assert!(result.is_err());
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_query_logs_filters_and_paginates() {
+    let repo = Arc::new(MockAuditLogRepository::new());
+    let config = AuditServiceConfig {
+        async_writes: false,
+        ..Default::default()
+    };
+    let service = AuditService::new(Arc::clone(&repo), config);
+
+    for _ in 0..3 {
+        service
+            .log_rate_limit_violation("ip", None, Some("phone_hash"), "10.0.0.1".to_string(), None)
+            .await
+            .unwrap();
+    }
+    service
+        .log_rate_limit_violation("ip", None, Some("phone_hash"), "10.0.0.2".to_string(), None)
+        .await
+        .unwrap();
+    service
+        .log_login_failure("other_hash", "10.0.0.1".to_string(), None, "Invalid code")
+        .await
+        .unwrap();
+
+    let filter = AuditLogFilter {
+        phone_hash: Some("phone_hash".to_string()),
+        event_types: vec![AuditEventType::RateLimitIpExceeded],
+        ip_address: Some("10.0.0.1".to_string()),
+        ..Default::default()
+    };
+    let page = service
+        .query_logs(filter, Pagination::new(2, 2))
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 3);
+    assert_eq!(page.total_pages, 2);
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.has_next, Some(false));
+}
+
+#[tokio::test]
+async fn test_query_logs_rejects_inverted_date_range() {
+    let repo = Arc::new(MockAuditLogRepository::new());
+    let service = AuditService::new(repo, AuditServiceConfig::default());
+
+    let now = Utc::now();
+    let filter = AuditLogFilter {
+        date_range: DateRange::new(Some(now), Some(now - chrono::Duration::hours(1))),
+        ..Default::default()
+    };
+
+    let result = service.query_logs(filter, Pagination::default()).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
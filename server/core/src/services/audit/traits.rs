@@ -0,0 +1,19 @@
+//! Traits for receiving audit events as they are recorded
+
+use async_trait::async_trait;
+
+use crate::domain::entities::audit::AuditLog;
+
+/// Trait for components notified of every audit event
+///
+/// Publishers run after the event is handed to the repository and must not
+/// block for long; slow work such as network delivery should be queued.
+#[async_trait]
+pub trait AuditEventPublisherTrait: Send + Sync {
+    /// Publish a recorded audit event
+    ///
+    /// # Returns
+    /// * `Err(String)` - Why the event could not be published; the audit
+    ///   write itself is unaffected
+    async fn publish(&self, audit_log: &AuditLog) -> Result<(), String>;
+}
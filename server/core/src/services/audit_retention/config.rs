@@ -0,0 +1,63 @@
+//! Configuration for the audit retention service
+
+/// What happens to audit logs older than the retention period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditRetentionAction {
+    /// Mark logs as archived, keeping them until they are exported and deleted
+    Archive,
+    /// Delete logs, dropping whole monthly partitions where possible
+    Delete,
+}
+
+impl AuditRetentionAction {
+    /// Convert to string representation for configuration and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::Delete => "delete",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "archive" => Some(Self::Archive),
+            "delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for the audit retention service
+#[derive(Debug, Clone)]
+pub struct AuditRetentionConfig {
+    /// Age after which audit logs are archived or deleted (in days)
+    pub retention_days: i64,
+    /// What happens to expired audit logs
+    pub action: AuditRetentionAction,
+    /// Logs archived or deleted per query
+    pub batch_size: usize,
+    /// Maximum number of batches per run; the rest waits for the next run
+    pub max_batches_per_run: usize,
+    /// Months of partitions kept created ahead of the current one
+    pub partition_months_ahead: u32,
+    /// How often the background task runs (in seconds)
+    pub interval_seconds: u64,
+    /// Whether to enable the background task
+    pub enabled: bool,
+}
+
+impl Default for AuditRetentionConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: 90,
+            action: AuditRetentionAction::Archive,
+            batch_size: 1000,
+            max_batches_per_run: 100,
+            partition_months_ahead: 3,
+            interval_seconds: 3600,
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Audit log retention module
+//!
+//! This module handles:
+//! - Archiving or deleting audit logs older than the retention period, in
+//!   batches so a large backlog does not hold long locks
+//! - Keeping monthly audit log partitions created ahead of time and
+//!   dropping months that have passed the retention period
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{AuditRetentionAction, AuditRetentionConfig};
+pub use service::{AuditRetentionResult, AuditRetentionService};
+pub use traits::AuditPartitionManagerTrait;
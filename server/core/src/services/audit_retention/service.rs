@@ -0,0 +1,183 @@
+//! Audit retention service
+//!
+//! Audit logs are written for every authentication attempt, so the table
+//! grows without bound unless old logs are removed. A background task
+//! periodically archives or deletes the logs older than `retention_days`,
+//! oldest first and `batch_size` rows per query so that a large backlog is
+//! worked off over several runs rather than in one long statement.
+//!
+//! When a partition manager is attached, each run also creates the monthly
+//! partitions for the coming `partition_months_ahead` months and, when logs
+//! are deleted, drops whole months that have passed the retention period,
+//! which is far cheaper than deleting their rows. Rows of the month the
+//! cutoff falls in are still deleted batch by batch.
+
+use chrono::{DateTime, Duration, Months, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::errors::DomainResult;
+use crate::repositories::AuditLogRepository;
+
+use super::config::{AuditRetentionAction, AuditRetentionConfig};
+use super::traits::AuditPartitionManagerTrait;
+
+/// Service for archiving or deleting expired audit logs
+pub struct AuditRetentionService<R>
+where
+    R: AuditLogRepository + 'static,
+{
+    repository: Arc<R>,
+    partitions: Option<Arc<dyn AuditPartitionManagerTrait>>,
+    config: AuditRetentionConfig,
+}
+
+impl<R> AuditRetentionService<R>
+where
+    R: AuditLogRepository + 'static,
+{
+    /// Create a new audit retention service without partition maintenance
+    pub fn new(repository: Arc<R>, config: AuditRetentionConfig) -> Self {
+        Self {
+            repository,
+            partitions: None,
+            config,
+        }
+    }
+
+    /// Maintain monthly partitions of the audit log table on every run
+    pub fn with_partition_manager(mut self, partitions: Arc<dyn AuditPartitionManagerTrait>) -> Self {
+        self.partitions = Some(partitions);
+        self
+    }
+
+    /// Logs created before this time have passed the retention period
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - Duration::days(self.config.retention_days)
+    }
+
+    /// Archive or delete the audit logs that have passed the retention period
+    ///
+    /// Partition maintenance failures are recorded in the result without
+    /// stopping the run, since expired rows can still be removed one batch
+    /// at a time.
+    ///
+    /// # Returns
+    /// * `Ok(AuditRetentionResult)` - What the run archived, deleted, created and dropped
+    /// * `Err(DomainError)` - Expired logs could not be archived or deleted
+    pub async fn run(&self) -> DomainResult<AuditRetentionResult> {
+        let now = Utc::now();
+        let cutoff = self.cutoff(now);
+        let mut result = AuditRetentionResult::default();
+
+        if let Some(partitions) = &self.partitions {
+            let through = now
+                .checked_add_months(Months::new(self.config.partition_months_ahead))
+                .unwrap_or(now);
+            match partitions.ensure_partitions(through).await {
+                Ok(created) => result.partitions_created = created,
+                Err(e) => result.errors.push(format!("Failed to create audit partitions: {}", e)),
+            }
+
+            if self.config.action == AuditRetentionAction::Delete {
+                match partitions.drop_partitions_before(cutoff).await {
+                    Ok(dropped) => result.partitions_dropped = dropped,
+                    Err(e) => result.errors.push(format!("Failed to drop audit partitions: {}", e)),
+                }
+            }
+        }
+
+        let batch_size = self.config.batch_size.max(1);
+        for batch in 0..self.config.max_batches_per_run {
+            let removed = match self.config.action {
+                AuditRetentionAction::Archive => {
+                    let archived = self.repository.archive_logs_before(cutoff, batch_size).await?;
+                    result.archived += archived;
+                    archived
+                }
+                AuditRetentionAction::Delete => {
+                    let deleted = self.repository.delete_logs_before(cutoff, batch_size).await?;
+                    result.deleted += deleted;
+                    deleted
+                }
+            };
+
+            if removed < batch_size {
+                break;
+            }
+            if batch + 1 == self.config.max_batches_per_run {
+                result.has_more = true;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Start the retention job as a background task
+    ///
+    /// This spawns a tokio task that runs the job at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Audit retention is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Audit retention started - will {} logs older than {} days every {} seconds",
+                self.config.action.as_str(),
+                self.config.retention_days,
+                self.config.interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                match self.run().await {
+                    Ok(result) => {
+                        if result.total_processed() > 0 || !result.partitions_dropped.is_empty() {
+                            info!(
+                                "Audit retention archived {} and deleted {} logs, dropped partitions {:?}",
+                                result.archived, result.deleted, result.partitions_dropped
+                            );
+                        }
+                        if !result.errors.is_empty() {
+                            warn!("Audit retention completed with errors: {:?}", result.errors);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Audit retention run failed: {}", e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Result of an audit retention run
+#[derive(Debug, Default)]
+pub struct AuditRetentionResult {
+    /// Number of logs marked as archived
+    pub archived: usize,
+    /// Number of logs deleted row by row
+    pub deleted: usize,
+    /// Partitions created ahead of time
+    pub partitions_created: Vec<String>,
+    /// Partitions dropped because all their logs had expired
+    pub partitions_dropped: Vec<String>,
+    /// Whether expired logs are left for the next run
+    pub has_more: bool,
+    /// Any errors encountered while maintaining partitions
+    pub errors: Vec<String>,
+}
+
+impl AuditRetentionResult {
+    /// Get total number of logs archived or deleted row by row
+    pub fn total_processed(&self) -> usize {
+        self.archived + self.deleted
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for audit retention service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,169 @@
+//! Unit tests for audit retention service
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::entities::audit::{AuditEventType, AuditLog};
+use crate::repositories::audit::MockAuditLogRepository;
+use crate::repositories::AuditLogRepository;
+use crate::services::audit_retention::{
+    AuditPartitionManagerTrait, AuditRetentionAction, AuditRetentionConfig, AuditRetentionService,
+};
+
+/// Partition manager recording the cutoffs it was called with
+#[derive(Default)]
+struct RecordingPartitionManager {
+    ensured: Mutex<Vec<DateTime<Utc>>>,
+    dropped: Mutex<Vec<DateTime<Utc>>>,
+    fail: bool,
+}
+
+#[async_trait]
+impl AuditPartitionManagerTrait for RecordingPartitionManager {
+    async fn ensure_partitions(&self, through: DateTime<Utc>) -> Result<Vec<String>, String> {
+        if self.fail {
+            return Err("table is not partitioned".to_string());
+        }
+        self.ensured.lock().unwrap().push(through);
+        Ok(vec!["p202701".to_string()])
+    }
+
+    async fn drop_partitions_before(&self, before: DateTime<Utc>) -> Result<Vec<String>, String> {
+        self.dropped.lock().unwrap().push(before);
+        Ok(vec!["p_before_202610".to_string()])
+    }
+}
+
+fn config(action: AuditRetentionAction) -> AuditRetentionConfig {
+    AuditRetentionConfig {
+        retention_days: 30,
+        action,
+        batch_size: 2,
+        max_batches_per_run: 10,
+        ..Default::default()
+    }
+}
+
+/// Store logs created the given number of days ago
+async fn repository_with_logs(ages_in_days: &[i64]) -> Arc<MockAuditLogRepository> {
+    let repository = Arc::new(MockAuditLogRepository::new());
+    for days in ages_in_days {
+        let mut log = AuditLog::new(AuditEventType::LoginSuccess, "192.168.1.1");
+        log.created_at = Utc::now() - Duration::days(*days);
+        repository.create(&log).await.unwrap();
+    }
+    repository
+}
+
+#[tokio::test]
+async fn test_archives_expired_logs_in_batches() {
+    let repository = repository_with_logs(&[1, 29, 31, 45, 60, 365, 400]).await;
+    let service = AuditRetentionService::new(repository.clone(), config(AuditRetentionAction::Archive));
+
+    let result = service.run().await.unwrap();
+
+    assert_eq!(result.archived, 5);
+    assert_eq!(result.deleted, 0);
+    assert!(!result.has_more);
+    let logs = repository.get_all_logs();
+    assert_eq!(logs.len(), 7);
+    let cutoff = Utc::now() - Duration::days(30);
+    assert!(logs.iter().all(|log| log.archived == (log.created_at < cutoff)));
+}
+
+#[tokio::test]
+async fn test_deletes_expired_logs() {
+    let repository = repository_with_logs(&[1, 29, 31, 45, 60]).await;
+    let service = AuditRetentionService::new(repository.clone(), config(AuditRetentionAction::Delete));
+
+    let result = service.run().await.unwrap();
+
+    assert_eq!(result.deleted, 3);
+    assert_eq!(result.archived, 0);
+    assert_eq!(repository.get_all_logs().len(), 2);
+}
+
+#[tokio::test]
+async fn test_leaves_backlog_beyond_batch_limit_for_next_run() {
+    let repository = repository_with_logs(&[31, 32, 33, 34, 35]).await;
+    let service = AuditRetentionService::new(
+        repository.clone(),
+        AuditRetentionConfig {
+            max_batches_per_run: 2,
+            ..config(AuditRetentionAction::Delete)
+        },
+    );
+
+    let result = service.run().await.unwrap();
+    assert_eq!(result.deleted, 4);
+    assert!(result.has_more);
+
+    // The oldest logs go first
+    let remaining = repository.get_all_logs();
+    assert_eq!(remaining.len(), 1);
+    assert!(remaining[0].created_at > Utc::now() - Duration::days(32));
+
+    let result = service.run().await.unwrap();
+    assert_eq!(result.deleted, 1);
+    assert!(!result.has_more);
+}
+
+#[tokio::test]
+async fn test_drops_partitions_only_when_deleting() {
+    let repository = repository_with_logs(&[]).await;
+    let partitions = Arc::new(RecordingPartitionManager::default());
+
+    let archive = AuditRetentionService::new(repository.clone(), config(AuditRetentionAction::Archive))
+        .with_partition_manager(partitions.clone());
+    let result = archive.run().await.unwrap();
+    assert_eq!(result.partitions_created, vec!["p202701".to_string()]);
+    assert!(result.partitions_dropped.is_empty());
+    assert!(partitions.dropped.lock().unwrap().is_empty());
+
+    let delete = AuditRetentionService::new(repository, config(AuditRetentionAction::Delete))
+        .with_partition_manager(partitions.clone());
+    let result = delete.run().await.unwrap();
+    assert_eq!(result.partitions_dropped, vec!["p_before_202610".to_string()]);
+
+    // Partitions are created months ahead and dropped at the retention cutoff
+    let ensured = partitions.ensured.lock().unwrap()[1];
+    assert!(ensured > Utc::now() + Duration::days(80));
+    let dropped = partitions.dropped.lock().unwrap()[0];
+    assert!((dropped - delete.cutoff(Utc::now())).num_seconds().abs() < 5);
+}
+
+#[tokio::test]
+async fn test_partition_failure_does_not_stop_row_deletion() {
+    let repository = repository_with_logs(&[1, 31]).await;
+    let partitions = Arc::new(RecordingPartitionManager {
+        fail: true,
+        ..Default::default()
+    });
+    let service = AuditRetentionService::new(repository.clone(), config(AuditRetentionAction::Delete))
+        .with_partition_manager(partitions);
+
+    let result = service.run().await.unwrap();
+
+    assert_eq!(result.deleted, 1);
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].contains("not partitioned"));
+}
+
+#[tokio::test]
+async fn test_repository_failure_fails_run() {
+    let repository = repository_with_logs(&[31]).await;
+    repository.set_should_fail(true);
+    let service = AuditRetentionService::new(repository, config(AuditRetentionAction::Archive));
+
+    assert!(service.run().await.is_err());
+}
+
+#[test]
+fn test_action_round_trips_through_string() {
+    for action in [AuditRetentionAction::Archive, AuditRetentionAction::Delete] {
+        assert_eq!(AuditRetentionAction::from_str(action.as_str()), Some(action));
+    }
+    assert_eq!(AuditRetentionAction::from_str("truncate"), None);
+}
@@ -0,0 +1,24 @@
+//! Traits for audit log partition maintenance
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Trait for maintaining monthly partitions of the audit log table
+///
+/// Each partition holds the logs of one calendar month (UTC).
+#[async_trait]
+pub trait AuditPartitionManagerTrait: Send + Sync {
+    /// Create the monthly partitions up to and including the month of `through`
+    ///
+    /// # Returns
+    /// * `Ok(names)` - Partitions created; empty if they all existed
+    /// * `Err(String)` - Why the partitions could not be created
+    async fn ensure_partitions(&self, through: DateTime<Utc>) -> Result<Vec<String>, String>;
+
+    /// Drop the partitions holding only logs created before `before`
+    ///
+    /// # Returns
+    /// * `Ok(names)` - Partitions dropped
+    /// * `Err(String)` - Why the partitions could not be dropped
+    async fn drop_partitions_before(&self, before: DateTime<Utc>) -> Result<Vec<String>, String>;
+}
@@ -3,14 +3,26 @@
 //! This service provides functionality to lock accounts after failed authentication
 //! attempts and automatically unlock them after a specified duration.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::{info, warn};
+use uuid::Uuid;
 
+use crate::domain::entities::audit::AuditEventType;
 use crate::errors::{DomainError, DomainResult};
+use crate::repositories::AuditLogRepository;
+use crate::services::audit::AuditService;
 use crate::services::verification::CacheServiceTrait;
 
+/// Lock reason recorded when an account is locked by failed attempts
+pub const DEFAULT_LOCK_REASON: &str = "Too many failed authentication attempts";
+
+/// IP address recorded for lock events raised by the service itself
+const SYSTEM_IP_ADDRESS: &str = "system";
+
 /// Account lock information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountLockInfo {
@@ -26,6 +38,23 @@ pub struct AccountLockInfo {
     pub remaining_seconds: Option<i64>,
 }
 
+/// A currently locked account as reported to administrators
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedAccount {
+    /// Phone number hash or user ID the lock applies to
+    pub identifier: String,
+    /// User ID if the identifier is a user ID
+    pub user_id: Option<Uuid>,
+    /// Reason recorded when the account was locked
+    pub reason: String,
+    /// Timestamp when the account was locked
+    pub locked_at: DateTime<Utc>,
+    /// Timestamp when the lock expires
+    pub unlock_at: DateTime<Utc>,
+    /// Remaining time in seconds until unlock
+    pub remaining_seconds: Option<i64>,
+}
+
 /// Configuration for account lock service
 #[derive(Debug, Clone)]
 pub struct AccountLockConfig {
@@ -54,24 +83,52 @@ impl Default for AccountLockConfig {
 }
 
 /// Service for managing account locks and brute force protection
-pub struct AccountLockService<C>
+///
+/// When constructed with an audit service, every lock and unlock is recorded
+/// as an audit event. The audit trail is also what backs
+/// [`list_locked_accounts`](Self::list_locked_accounts), since the cache
+/// cannot enumerate lock keys.
+pub struct AccountLockService<C, A = crate::repositories::audit::NoOpAuditLogRepository>
 where
     C: CacheServiceTrait,
+    A: AuditLogRepository,
 {
     /// Cache service for Redis operations
     cache_service: Arc<C>,
+    /// Audit service for recording lock and unlock events
+    audit_service: Option<Arc<AuditService<A>>>,
     /// Configuration for the lock service
     config: AccountLockConfig,
 }
 
-impl<C> AccountLockService<C>
+impl<C, A> AccountLockService<C, A>
 where
     C: CacheServiceTrait,
+    A: AuditLogRepository + 'static,
 {
     /// Create a new account lock service
     pub fn new(cache_service: Arc<C>, config: AccountLockConfig) -> Self {
         Self {
             cache_service,
+            audit_service: None,
+            config,
+        }
+    }
+
+    /// Create a new account lock service with audit logging
+    ///
+    /// # Arguments
+    /// * `cache_service` - Cache service for lock storage
+    /// * `audit_service` - Service for audit logging
+    /// * `config` - Service configuration
+    pub fn with_audit(
+        cache_service: Arc<C>,
+        audit_service: Arc<AuditService<A>>,
+        config: AccountLockConfig,
+    ) -> Self {
+        Self {
+            cache_service,
+            audit_service: Some(audit_service),
             config,
         }
     }
@@ -100,6 +157,19 @@ where
     /// * `Ok(())` - Account successfully locked
     /// * `Err(DomainError)` - If locking fails
     pub async fn lock_account(&self, identifier: &str) -> DomainResult<()> {
+        self.lock_account_with_reason(identifier, DEFAULT_LOCK_REASON).await
+    }
+
+    /// Lock an account and record why it was locked
+    ///
+    /// # Arguments
+    /// * `identifier` - Phone number hash or user ID to lock
+    /// * `reason` - Human readable reason shown to administrators
+    ///
+    /// # Returns
+    /// * `Ok(())` - Account successfully locked
+    /// * `Err(DomainError)` - If locking fails
+    pub async fn lock_account_with_reason(&self, identifier: &str, reason: &str) -> DomainResult<()> {
         let lock_key = self.get_lock_key(identifier);
         let lock_info = LockData {
             locked_at: Utc::now(),
@@ -116,10 +186,28 @@ where
 
         info!(
             identifier = identifier,
+            reason = reason,
             duration_seconds = self.config.lock_duration_seconds,
-            "Account locked due to failed authentication attempts"
+            "Account locked"
         );
 
+        if let Some(ref audit_service) = self.audit_service {
+            let (user_id, phone_hash) = split_identifier(identifier);
+            let _ = audit_service.log_auth_event(
+                AuditEventType::AccountLocked,
+                SYSTEM_IP_ADDRESS.to_string(),
+                user_id,
+                None,
+                phone_hash,
+                None,
+                Some(reason.to_string()),
+                Some(json!({
+                    "identifier": identifier,
+                    "lock_duration_seconds": self.config.lock_duration_seconds,
+                })),
+            ).await;
+        }
+
         // Clear the attempt counter since the account is now locked
         let attempt_key = self.get_attempt_key(identifier);
         let _ = self.delete_key(&attempt_key).await;
@@ -169,6 +257,116 @@ where
         Ok(())
     }
 
+    /// Unlock an account on behalf of an administrator
+    ///
+    /// Unlike [`unlock_account`](Self::unlock_account) this fails if the
+    /// account is not locked, and records who cleared the lock.
+    ///
+    /// # Arguments
+    /// * `identifier` - Phone number hash or user ID to unlock
+    /// * `admin_id` - ID of the administrator clearing the lock
+    /// * `ip_address` - IP address the request came from
+    ///
+    /// # Returns
+    /// * `Ok(())` - Account successfully unlocked
+    /// * `Err(DomainError::NotFound)` - If the account is not locked
+    /// * `Err(DomainError)` - If unlocking fails
+    pub async fn admin_unlock_account(
+        &self,
+        identifier: &str,
+        admin_id: Uuid,
+        ip_address: String,
+    ) -> DomainResult<()> {
+        if !self.is_locked(identifier).await? {
+            return Err(DomainError::NotFound {
+                resource: "Account lock".to_string(),
+            });
+        }
+
+        self.unlock_account(identifier).await?;
+
+        info!(
+            identifier = identifier,
+            admin_id = %admin_id,
+            "Account unlocked by administrator"
+        );
+
+        if let Some(ref audit_service) = self.audit_service {
+            let (user_id, phone_hash) = split_identifier(identifier);
+            let _ = audit_service.log_auth_event(
+                AuditEventType::AccountUnlocked,
+                ip_address,
+                user_id,
+                None,
+                phone_hash,
+                None,
+                None,
+                Some(json!({
+                    "identifier": identifier,
+                    "unlocked_by": admin_id,
+                })),
+            ).await;
+        }
+
+        Ok(())
+    }
+
+    /// List accounts that are currently locked
+    ///
+    /// Lock events from the audit log within the last lock duration are
+    /// checked against the cache, so expired and manually cleared locks are
+    /// left out. Requires the service to be constructed with an audit service.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<LockedAccount>)` - Locked accounts, most recently locked first
+    /// * `Err(DomainError::BusinessRule)` - If audit logging is not configured
+    pub async fn list_locked_accounts(&self) -> DomainResult<Vec<LockedAccount>> {
+        let audit_service = self.audit_service.as_ref().ok_or_else(|| DomainError::BusinessRule {
+            message: "Listing account locks requires audit logging".to_string(),
+        })?;
+
+        let now = Utc::now();
+        let lock_duration = Duration::seconds(self.config.lock_duration_seconds as i64);
+        let mut events = audit_service
+            .find_events(vec![AuditEventType::AccountLocked], now - lock_duration, now, None)
+            .await?;
+        events.sort_by_key(|event| std::cmp::Reverse(event.created_at));
+
+        let mut seen = HashSet::new();
+        let mut locked = Vec::new();
+
+        for event in events {
+            // Lock events written by other services carry no identifier
+            let identifier = match event
+                .event_data
+                .as_ref()
+                .and_then(|data| data.get("identifier"))
+                .and_then(|value| value.as_str())
+            {
+                Some(identifier) => identifier.to_string(),
+                None => continue,
+            };
+
+            if !seen.insert(identifier.clone()) || !self.is_locked(&identifier).await? {
+                continue;
+            }
+
+            let remaining_seconds = self.get_ttl(&self.get_lock_key(&identifier)).await?;
+            locked.push(LockedAccount {
+                user_id: event.user_id,
+                reason: event
+                    .failure_reason
+                    .unwrap_or_else(|| DEFAULT_LOCK_REASON.to_string()),
+                locked_at: event.created_at,
+                unlock_at: event.created_at + lock_duration,
+                remaining_seconds,
+                identifier,
+            });
+        }
+
+        Ok(locked)
+    }
+
     /// Get detailed lock information for an account
     ///
     /// # Arguments
@@ -343,6 +541,14 @@ where
     }
 }
 
+/// Split a lock identifier into a user ID or a phone hash for audit records
+fn split_identifier(identifier: &str) -> (Option<Uuid>, Option<String>) {
+    match Uuid::parse_str(identifier) {
+        Ok(user_id) => (Some(user_id), None),
+        Err(_) => (None, Some(identifier.to_string())),
+    }
+}
+
 /// Internal structure for lock data storage
 #[derive(Debug, Serialize, Deserialize)]
 struct LockData {
@@ -3,9 +3,9 @@
 //! This service analyzes authentication patterns to detect and prevent
 //! distributed attacks such as credential stuffing and botnet attacks.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::IpAddr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Duration, Utc};
 use tracing::{warn, error};
 use ipnetwork::{Ipv4Network, Ipv6Network};
@@ -30,6 +30,8 @@ pub struct AttackDetectorConfig {
     pub ipv6_subnet_mask: u8,
     /// Enable geographic anomaly detection
     pub enable_geo_detection: bool,
+    /// Maximum number of directly recorded failures kept in memory
+    pub max_recorded_failures: usize,
 }
 
 impl Default for AttackDetectorConfig {
@@ -42,6 +44,7 @@ impl Default for AttackDetectorConfig {
             ipv4_subnet_mask: 24,          // /24 subnet
             ipv6_subnet_mask: 48,          // /48 subnet
             enable_geo_detection: false,   // Disabled by default (requires GeoIP)
+            max_recorded_failures: 1000,
         }
     }
 }
@@ -102,6 +105,8 @@ where
 {
     /// Audit log repository for querying events
     audit_repository: Arc<A>,
+    /// Failures reported directly, e.g. by request middleware
+    recorded_failures: Mutex<VecDeque<AuditLog>>,
     /// Configuration
     config: AttackDetectorConfig,
 }
@@ -114,6 +119,7 @@ where
     pub fn new(audit_repository: Arc<A>, config: AttackDetectorConfig) -> Self {
         Self {
             audit_repository,
+            recorded_failures: Mutex::new(VecDeque::new()),
             config,
         }
     }
@@ -123,6 +129,32 @@ where
         Self::new(audit_repository, AttackDetectorConfig::default())
     }
 
+    /// Get the detector configuration
+    pub fn config(&self) -> &AttackDetectorConfig {
+        &self.config
+    }
+
+    /// Record a failed request for the next analysis
+    ///
+    /// Failures recorded here are analyzed together with the audit log, so
+    /// request middleware can report failures that never reach an audited
+    /// service (e.g. rejected tokens). Events outside the analysis window are
+    /// dropped, and the buffer is capped at `max_recorded_failures`.
+    ///
+    /// # Arguments
+    /// * `ip_address` - Client IP address
+    /// * `phone_masked` - Masked phone number targeted, if known
+    pub fn record_failure(&self, ip_address: &str, phone_masked: Option<&str>) {
+        let mut event = AuditLog::new(AuditEventType::LoginFailure, ip_address.to_string());
+        event.phone_masked = phone_masked.map(|p| p.to_string());
+
+        let mut failures = self.recorded_failures.lock().unwrap();
+        failures.push_back(event);
+        while failures.len() > self.config.max_recorded_failures {
+            failures.pop_front();
+        }
+    }
+
     /// Detect distributed attack patterns
     pub async fn detect_attack(&self) -> DomainResult<AttackDetectionResult> {
         let since = Utc::now() - Duration::minutes(self.config.analysis_window_minutes);
@@ -429,12 +461,18 @@ where
             AuditEventType::AccountLocked,
         ];
 
-        self.audit_repository
+        let mut events = self.audit_repository
             .find_by_event_types(event_types, since, Utc::now(), Some(1000))
             .await
             .map_err(|e| DomainError::Internal {
                 message: format!("Failed to query audit logs: {}", e),
-            })
+            })?;
+
+        let mut failures = self.recorded_failures.lock().unwrap();
+        failures.retain(|event| event.created_at >= since);
+        events.extend(failures.iter().cloned());
+
+        Ok(events)
     }
 
     /// Check if an IP is in a suspicious range
@@ -0,0 +1,328 @@
+//! Automatic mitigation for attacks found by the attack detector
+//!
+//! The guard periodically runs the [`AttackDetector`] and turns its
+//! recommended actions into in-memory blocks and throttles that request
+//! middleware checks before handling a request:
+//!
+//! - `BlockSubnet` blocks the whole subnet
+//! - `SystemLockdown` blocks every suspicious IP
+//! - `EnableCaptcha` throttles suspicious IPs, since no CAPTCHA exists yet
+//! - `AlertAdmins` only records the finding
+//!
+//! Every detected attack is recorded in the audit log.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use ipnetwork::IpNetwork;
+use serde_json::json;
+use tracing::{error, info, warn};
+
+use crate::domain::entities::audit::AuditEventType;
+use crate::errors::DomainResult;
+use crate::repositories::AuditLogRepository;
+use crate::services::audit::AuditService;
+
+use super::attack_detector::{AttackDetectionResult, AttackDetector, RecommendedAction};
+
+/// IP address recorded for findings raised by the guard itself
+const SYSTEM_IP_ADDRESS: &str = "system";
+
+/// Configuration for the attack guard
+#[derive(Debug, Clone)]
+pub struct AttackGuardConfig {
+    /// How often the background task runs detection (in seconds)
+    pub evaluation_interval_seconds: u64,
+    /// How long blocked IPs and subnets stay blocked (in seconds)
+    pub block_duration_seconds: i64,
+    /// How long suspicious IPs stay throttled (in seconds)
+    pub throttle_duration_seconds: i64,
+    /// Requests per minute allowed for a throttled IP
+    pub throttle_requests_per_minute: u32,
+    /// Whether to enable the guard
+    pub enabled: bool,
+}
+
+impl Default for AttackGuardConfig {
+    fn default() -> Self {
+        Self {
+            evaluation_interval_seconds: 30,
+            block_duration_seconds: 900,     // 15 minutes
+            throttle_duration_seconds: 600,  // 10 minutes
+            throttle_requests_per_minute: 5,
+            enabled: true,
+        }
+    }
+}
+
+/// Decision for an incoming request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardDecision {
+    /// The request may proceed
+    Allow,
+    /// The client is throttled and has used up its allowance
+    Throttle { retry_after_seconds: u64 },
+    /// The client is blocked
+    Block { retry_after_seconds: u64 },
+}
+
+/// Request counter for a throttled IP
+#[derive(Debug)]
+struct Throttle {
+    until: DateTime<Utc>,
+    window_start: DateTime<Utc>,
+    requests: u32,
+}
+
+/// Active mitigations
+#[derive(Debug, Default)]
+struct Mitigations {
+    blocked_networks: Vec<(IpNetwork, DateTime<Utc>)>,
+    blocked_ips: HashMap<IpAddr, DateTime<Utc>>,
+    throttled_ips: HashMap<IpAddr, Throttle>,
+}
+
+/// Service that applies and enforces attack mitigations
+pub struct AttackGuard<A>
+where
+    A: AuditLogRepository + 'static,
+{
+    detector: Arc<AttackDetector<A>>,
+    audit_service: Option<Arc<AuditService<A>>>,
+    mitigations: Mutex<Mitigations>,
+    config: AttackGuardConfig,
+}
+
+impl<A> AttackGuard<A>
+where
+    A: AuditLogRepository + 'static,
+{
+    /// Create a new attack guard
+    pub fn new(detector: Arc<AttackDetector<A>>, config: AttackGuardConfig) -> Self {
+        Self {
+            detector,
+            audit_service: None,
+            mitigations: Mutex::new(Mitigations::default()),
+            config,
+        }
+    }
+
+    /// Create a new attack guard that records findings in the audit log
+    pub fn with_audit(
+        detector: Arc<AttackDetector<A>>,
+        audit_service: Arc<AuditService<A>>,
+        config: AttackGuardConfig,
+    ) -> Self {
+        Self {
+            detector,
+            audit_service: Some(audit_service),
+            mitigations: Mutex::new(Mitigations::default()),
+            config,
+        }
+    }
+
+    /// Check whether a request from an IP may proceed
+    ///
+    /// Throttled IPs consume one request from their per-minute allowance.
+    /// Unparseable addresses are always allowed.
+    pub fn check(&self, ip_address: &str) -> GuardDecision {
+        let ip = match ip_address.parse::<IpAddr>() {
+            Ok(ip) => ip,
+            Err(_) => return GuardDecision::Allow,
+        };
+
+        let now = Utc::now();
+        let mut mitigations = self.mitigations.lock().unwrap();
+
+        let blocked_until = mitigations
+            .blocked_ips
+            .get(&ip)
+            .copied()
+            .into_iter()
+            .chain(
+                mitigations
+                    .blocked_networks
+                    .iter()
+                    .filter(|(network, _)| network.contains(ip))
+                    .map(|(_, until)| *until),
+            )
+            .filter(|until| *until > now)
+            .max();
+
+        if let Some(until) = blocked_until {
+            return GuardDecision::Block {
+                retry_after_seconds: Self::seconds_until(now, until),
+            };
+        }
+
+        if let Some(throttle) = mitigations.throttled_ips.get_mut(&ip) {
+            if throttle.until <= now {
+                mitigations.throttled_ips.remove(&ip);
+                return GuardDecision::Allow;
+            }
+
+            if now - throttle.window_start >= Duration::minutes(1) {
+                throttle.window_start = now;
+                throttle.requests = 0;
+            }
+
+            if throttle.requests >= self.config.throttle_requests_per_minute {
+                return GuardDecision::Throttle {
+                    retry_after_seconds: Self::seconds_until(
+                        now,
+                        throttle.window_start + Duration::minutes(1),
+                    ),
+                };
+            }
+            throttle.requests += 1;
+        }
+
+        GuardDecision::Allow
+    }
+
+    /// Report a failed request to the detector
+    pub fn record_failure(&self, ip_address: &str, phone_masked: Option<&str>) {
+        self.detector.record_failure(ip_address, phone_masked);
+    }
+
+    /// Run detection once and apply the recommended mitigation
+    ///
+    /// # Returns
+    /// * `Ok(Some(result))` - An attack was detected and handled
+    /// * `Ok(None)` - No attack detected
+    /// * `Err(DomainError)` - If detection fails
+    pub async fn evaluate(&self) -> DomainResult<Option<AttackDetectionResult>> {
+        let result = self.detector.detect_attack().await?;
+        self.purge_expired();
+
+        if !result.is_attack_detected {
+            return Ok(None);
+        }
+
+        self.apply(&result);
+        self.record_finding(&result).await;
+
+        Ok(Some(result))
+    }
+
+    /// Apply the recommended action of a detection result
+    fn apply(&self, result: &AttackDetectionResult) {
+        let now = Utc::now();
+        let block_until = now + Duration::seconds(self.config.block_duration_seconds);
+        let mut mitigations = self.mitigations.lock().unwrap();
+
+        match &result.recommended_action {
+            RecommendedAction::BlockSubnet(subnet) => {
+                match self.subnet_network(subnet) {
+                    Some(network) => {
+                        mitigations.blocked_networks.retain(|(n, _)| *n != network);
+                        mitigations.blocked_networks.push((network, block_until));
+                        warn!(subnet = %network, "Blocking subnet after detected attack");
+                    }
+                    None => error!(subnet = subnet, "Cannot block unparseable subnet"),
+                }
+            }
+            RecommendedAction::SystemLockdown => {
+                for ip in Self::parse_ips(&result.suspicious_ips) {
+                    mitigations.blocked_ips.insert(ip, block_until);
+                }
+                warn!(ips = result.suspicious_ips.len(), "Blocking suspicious IPs after detected attack");
+            }
+            RecommendedAction::EnableCaptcha => {
+                let until = now + Duration::seconds(self.config.throttle_duration_seconds);
+                for ip in Self::parse_ips(&result.suspicious_ips) {
+                    mitigations
+                        .throttled_ips
+                        .entry(ip)
+                        .and_modify(|throttle| throttle.until = until)
+                        .or_insert(Throttle {
+                            until,
+                            window_start: now,
+                            requests: 0,
+                        });
+                }
+                warn!(ips = result.suspicious_ips.len(), "Throttling suspicious IPs after detected attack");
+            }
+            RecommendedAction::AlertAdmins | RecommendedAction::None => {}
+        }
+    }
+
+    /// Record a detection result in the audit log
+    async fn record_finding(&self, result: &AttackDetectionResult) {
+        if let Some(ref audit_service) = self.audit_service {
+            let _ = audit_service.log_auth_event(
+                AuditEventType::SuspiciousActivity,
+                SYSTEM_IP_ADDRESS.to_string(),
+                None,
+                None,
+                None,
+                None,
+                Some(result.analysis_details.clone()),
+                Some(json!({
+                    "pattern": result.attack_pattern.as_ref().map(|p| format!("{:?}", p)),
+                    "recommended_action": format!("{:?}", result.recommended_action),
+                    "confidence_score": result.confidence_score,
+                    "suspicious_ips": result.suspicious_ips,
+                    "targeted_phones": result.targeted_phones,
+                })),
+            ).await;
+        }
+    }
+
+    /// Drop mitigations that have expired
+    fn purge_expired(&self) {
+        let now = Utc::now();
+        let mut mitigations = self.mitigations.lock().unwrap();
+        mitigations.blocked_networks.retain(|(_, until)| *until > now);
+        mitigations.blocked_ips.retain(|_, until| *until > now);
+        mitigations.throttled_ips.retain(|_, throttle| throttle.until > now);
+    }
+
+    /// Build the network for a subnet address reported by the detector
+    fn subnet_network(&self, subnet: &str) -> Option<IpNetwork> {
+        let address = subnet.parse::<IpAddr>().ok()?;
+        let prefix = match address {
+            IpAddr::V4(_) => self.detector.config().ipv4_subnet_mask,
+            IpAddr::V6(_) => self.detector.config().ipv6_subnet_mask,
+        };
+        IpNetwork::new(address, prefix).ok()
+    }
+
+    fn parse_ips(ips: &[String]) -> impl Iterator<Item = IpAddr> + '_ {
+        ips.iter().filter_map(|ip| ip.parse().ok())
+    }
+
+    fn seconds_until(now: DateTime<Utc>, until: DateTime<Utc>) -> u64 {
+        (until - now).num_seconds().max(1) as u64
+    }
+
+    /// Start the guard as a background task
+    ///
+    /// This spawns a tokio task that runs detection at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Attack guard is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.evaluation_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Attack guard started - will evaluate every {} seconds",
+                self.config.evaluation_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                if let Err(e) = self.evaluate().await {
+                    error!("Attack evaluation failed: {}", e);
+                }
+            }
+        });
+    }
+}
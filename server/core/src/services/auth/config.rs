@@ -1,7 +1,11 @@
 //! Configuration for the authentication service
 
+use std::collections::HashSet;
+
 use re_shared::config::rate_limit::RateLimitConfig;
 
+use super::phone_utils::PhoneHasher;
+
 /// Configuration for the authentication service
 #[derive(Debug, Clone)]
 pub struct AuthServiceConfig {
@@ -11,6 +15,10 @@ pub struct AuthServiceConfig {
     pub allow_registration: bool,
     /// Whether to require user type selection immediately after registration
     pub require_immediate_user_type: bool,
+    /// Phones of QA staff and app store reviewers exempt from SMS rate limits
+    pub trusted_testers: TrustedTesterConfig,
+    /// Hasher for the phone hashes stored with users and audit logs
+    pub phone_hasher: PhoneHasher,
 }
 
 impl Default for AuthServiceConfig {
@@ -19,6 +27,8 @@ impl Default for AuthServiceConfig {
             rate_limit: RateLimitConfig::default(),
             allow_registration: true,
             require_immediate_user_type: false,
+            trusted_testers: TrustedTesterConfig::default(),
+            phone_hasher: PhoneHasher::default(),
         }
     }
 }
@@ -33,4 +43,44 @@ impl AuthServiceConfig {
     pub fn rate_limit_window_seconds(&self) -> i64 {
         self.rate_limit.window_seconds() as i64
     }
-}
\ No newline at end of file
+}
+
+/// Allowlist of trusted tester phones
+///
+/// Phones are identified by the hash a [`PhoneHasher`] stores with their
+/// user (`users.phone_hash`), so the configuration holds no phone numbers.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedTesterConfig {
+    /// Hex-encoded phone hashes of the trusted phone numbers
+    pub phone_hashes: HashSet<String>,
+    /// Fixed 6-digit code for trusted phones; when set no SMS is sent
+    pub fixed_code: Option<String>,
+}
+
+impl TrustedTesterConfig {
+    /// Create an allowlist from phone hashes, without a fixed code
+    pub fn new<I, H>(phone_hashes: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+        H: Into<String>,
+    {
+        Self {
+            phone_hashes: phone_hashes
+                .into_iter()
+                .map(|hash| hash.into().trim().to_ascii_lowercase())
+                .collect(),
+            fixed_code: None,
+        }
+    }
+
+    /// Checks whether a phone number is on the allowlist
+    ///
+    /// Hashes listed under a previous pepper still match.
+    pub fn is_trusted(&self, phone: &str, hasher: &PhoneHasher) -> bool {
+        !self.phone_hashes.is_empty()
+            && hasher
+                .lookup_hashes(phone)
+                .iter()
+                .any(|hash| self.phone_hashes.contains(hash))
+    }
+}
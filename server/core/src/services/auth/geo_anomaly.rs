@@ -0,0 +1,162 @@
+//! Geo-IP anomaly detection for logins
+//!
+//! Resolves the country of the IP address a verification comes from and
+//! flags it when it differs from the country of the phone number or from
+//! the country of the user's previous login. Lookups are best-effort: an
+//! unknown IP or a failing lookup never blocks a login.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Trait for resolving the country of an IP address
+#[async_trait]
+pub trait GeoIpLookupTrait: Send + Sync {
+    /// Look up the country of an IP address
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - ISO 3166-1 alpha-2 country code in upper case
+    /// * `Ok(None)` - The address is not in the database (e.g. private ranges)
+    /// * `Err(String)` - The lookup failed
+    async fn lookup_country(&self, ip: IpAddr) -> Result<Option<String>, String>;
+}
+
+/// Configuration for geo-IP anomaly detection
+#[derive(Debug, Clone)]
+pub struct GeoAnomalyConfig {
+    /// Countries expected for each phone calling code; unlisted codes are not checked
+    pub calling_code_countries: HashMap<String, Vec<String>>,
+    /// Whether anomalous logins must complete step-up verification
+    pub require_step_up: bool,
+    /// Whether to enable detection
+    pub enabled: bool,
+}
+
+impl Default for GeoAnomalyConfig {
+    fn default() -> Self {
+        let countries = |codes: &[&str]| codes.iter().map(|c| c.to_string()).collect();
+
+        Self {
+            calling_code_countries: HashMap::from([
+                ("+86".to_string(), countries(&["CN"])),
+                ("+61".to_string(), countries(&["AU"])),
+                ("+1".to_string(), countries(&["US", "CA"])),
+                ("+44".to_string(), countries(&["GB"])),
+                ("+7".to_string(), countries(&["RU", "KZ"])),
+            ]),
+            require_step_up: false, // Flag only until clients support step-up
+            enabled: true,
+        }
+    }
+}
+
+/// A reason a login looks anomalous
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeoAnomaly {
+    /// The IP country is not a country of the phone's calling code
+    PhoneCountryMismatch {
+        phone_country_code: String,
+        ip_country: String,
+    },
+    /// The IP country differs from the country of the previous login
+    LoginCountryChanged {
+        previous_country: String,
+        ip_country: String,
+    },
+}
+
+/// Outcome of assessing a login
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeoAssessment {
+    /// Country of the client IP, if it could be resolved
+    pub country: Option<String>,
+    /// Anomalies found, empty for an ordinary login
+    pub anomalies: Vec<GeoAnomaly>,
+    /// Whether the client must complete step-up verification
+    pub step_up_required: bool,
+}
+
+impl GeoAssessment {
+    /// Checks whether any anomaly was found
+    pub fn is_anomalous(&self) -> bool {
+        !self.anomalies.is_empty()
+    }
+}
+
+/// Detector for logins from unexpected countries
+pub struct GeoAnomalyDetector {
+    lookup: Arc<dyn GeoIpLookupTrait>,
+    config: GeoAnomalyConfig,
+}
+
+impl GeoAnomalyDetector {
+    /// Create a new geo-IP anomaly detector
+    pub fn new(lookup: Arc<dyn GeoIpLookupTrait>, config: GeoAnomalyConfig) -> Self {
+        Self { lookup, config }
+    }
+
+    /// Create a new geo-IP anomaly detector with default configuration
+    pub fn with_defaults(lookup: Arc<dyn GeoIpLookupTrait>) -> Self {
+        Self::new(lookup, GeoAnomalyConfig::default())
+    }
+
+    /// Assess a verification attempt
+    ///
+    /// # Arguments
+    /// * `client_ip` - Client IP address as reported by the API layer
+    /// * `phone_country_code` - Calling code of the phone number (e.g. "+61")
+    /// * `last_login_country` - Country of the user's previous login, `None` for new users
+    pub async fn assess(
+        &self,
+        client_ip: Option<&str>,
+        phone_country_code: &str,
+        last_login_country: Option<&str>,
+    ) -> GeoAssessment {
+        if !self.config.enabled {
+            return GeoAssessment::default();
+        }
+
+        let Some(ip) = client_ip.and_then(|ip| ip.parse::<IpAddr>().ok()) else {
+            return GeoAssessment::default();
+        };
+
+        let country = match self.lookup.lookup_country(ip).await {
+            Ok(Some(country)) => country.to_ascii_uppercase(),
+            Ok(None) => return GeoAssessment::default(),
+            Err(e) => {
+                warn!(error = %e, "Geo-IP lookup failed");
+                return GeoAssessment::default();
+            }
+        };
+
+        let mut anomalies = Vec::new();
+
+        if let Some(expected) = self.config.calling_code_countries.get(phone_country_code) {
+            if !expected.iter().any(|c| c.eq_ignore_ascii_case(&country)) {
+                anomalies.push(GeoAnomaly::PhoneCountryMismatch {
+                    phone_country_code: phone_country_code.to_string(),
+                    ip_country: country.clone(),
+                });
+            }
+        }
+
+        if let Some(previous) = last_login_country {
+            if !previous.eq_ignore_ascii_case(&country) {
+                anomalies.push(GeoAnomaly::LoginCountryChanged {
+                    previous_country: previous.to_string(),
+                    ip_country: country.clone(),
+                });
+            }
+        }
+
+        GeoAssessment {
+            step_up_required: self.config.require_step_up && !anomalies.is_empty(),
+            country: Some(country),
+            anomalies,
+        }
+    }
+}
@@ -0,0 +1,448 @@
+//! IP allowlist and denylist management
+//!
+//! Administrators maintain two lists of IP addresses and CIDR ranges:
+//!
+//! - The denylist rejects requests, e.g. from SMS-pumping ranges
+//! - The allowlist always admits requests and takes precedence over the
+//!   denylist; with `allowlist_only` it becomes the only way in
+//!
+//! Entries live in a shared store so every instance sees them. Each instance
+//! keeps an in-memory snapshot for per-request checks, updated immediately on
+//! local changes and refreshed from the store in the background.
+
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ipnetwork::IpNetwork;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::errors::{DomainError, DomainResult};
+
+/// Which list an entry belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpAccessList {
+    Allow,
+    Deny,
+}
+
+impl IpAccessList {
+    /// Convert to string representation for storage keys and URLs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(Self::Allow),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// An IP address or CIDR range on an access list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IpAccessEntry {
+    /// Normalized CIDR range (single addresses use a /32 or /128 prefix)
+    pub cidr: String,
+
+    /// Why the entry was added
+    pub reason: String,
+
+    /// Administrator who added the entry
+    pub created_by: Option<Uuid>,
+
+    /// Timestamp when the entry was added
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp after which the entry no longer applies
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl IpAccessEntry {
+    /// Checks whether the entry has expired at the given time
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Shared storage for access list entries
+#[async_trait]
+pub trait IpAccessStoreTrait: Send + Sync {
+    /// Add an entry, replacing any entry with the same CIDR on the list
+    async fn add_entry(&self, list: IpAccessList, entry: &IpAccessEntry) -> Result<(), String>;
+
+    /// Remove an entry by CIDR
+    ///
+    /// # Returns
+    /// * `Ok(true)` if the entry existed
+    async fn remove_entry(&self, list: IpAccessList, cidr: &str) -> Result<bool, String>;
+
+    /// List all entries on a list, including expired ones
+    async fn list_entries(&self, list: IpAccessList) -> Result<Vec<IpAccessEntry>, String>;
+}
+
+/// Configuration for IP access control
+#[derive(Debug, Clone)]
+pub struct IpAccessControlConfig {
+    /// How often each instance reloads the lists from the store (in seconds)
+    pub refresh_interval_seconds: u64,
+    /// Reject every IP that is not on the allowlist
+    pub allowlist_only: bool,
+    /// Shortest IPv4 prefix accepted, to prevent blocking huge ranges by mistake
+    pub min_ipv4_prefix: u8,
+    /// Shortest IPv6 prefix accepted
+    pub min_ipv6_prefix: u8,
+    /// Maximum number of entries per list
+    pub max_entries_per_list: usize,
+    /// Whether to enforce the lists
+    pub enabled: bool,
+}
+
+impl Default for IpAccessControlConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_seconds: 15,
+            allowlist_only: false,
+            min_ipv4_prefix: 8,
+            min_ipv6_prefix: 32,
+            max_entries_per_list: 10_000,
+            enabled: true,
+        }
+    }
+}
+
+/// Decision for a client IP
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpAccessDecision {
+    /// The IP is on the allowlist
+    Allowlisted,
+    /// The IP is on neither list
+    Allowed,
+    /// The IP is denied
+    Denied { reason: String },
+}
+
+impl IpAccessDecision {
+    /// Whether the request may proceed
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, Self::Denied { .. })
+    }
+}
+
+/// In-memory copy of both lists
+#[derive(Debug, Default)]
+struct Snapshot {
+    allow: Vec<(IpNetwork, IpAccessEntry)>,
+    deny: Vec<(IpNetwork, IpAccessEntry)>,
+}
+
+impl Snapshot {
+    fn list_mut(&mut self, list: IpAccessList) -> &mut Vec<(IpNetwork, IpAccessEntry)> {
+        match list {
+            IpAccessList::Allow => &mut self.allow,
+            IpAccessList::Deny => &mut self.deny,
+        }
+    }
+}
+
+/// Service that manages and enforces IP access lists
+pub struct IpAccessControlService<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    store: Arc<S>,
+    snapshot: RwLock<Snapshot>,
+    config: IpAccessControlConfig,
+}
+
+impl<S> IpAccessControlService<S>
+where
+    S: IpAccessStoreTrait + 'static,
+{
+    /// Create a new IP access control service
+    ///
+    /// The lists start empty; call [`refresh`](Self::refresh) or start the
+    /// background task to load them from the store.
+    pub fn new(store: Arc<S>, config: IpAccessControlConfig) -> Self {
+        Self {
+            store,
+            snapshot: RwLock::new(Snapshot::default()),
+            config,
+        }
+    }
+
+    /// Check whether requests from an IP may proceed
+    ///
+    /// Unparseable addresses are treated as being on neither list.
+    pub fn check(&self, ip_address: &str) -> IpAccessDecision {
+        if !self.config.enabled {
+            return IpAccessDecision::Allowed;
+        }
+
+        let ip = ip_address.parse::<IpAddr>().ok();
+        let now = Utc::now();
+        let snapshot = self.snapshot.read().unwrap();
+
+        let find = |entries: &[(IpNetwork, IpAccessEntry)]| {
+            ip.and_then(|ip| {
+                entries
+                    .iter()
+                    .find(|(network, entry)| network.contains(ip) && !entry.is_expired(now))
+                    .map(|(_, entry)| entry.clone())
+            })
+        };
+
+        if find(&snapshot.allow).is_some() {
+            return IpAccessDecision::Allowlisted;
+        }
+
+        if let Some(entry) = find(&snapshot.deny) {
+            return IpAccessDecision::Denied { reason: entry.reason };
+        }
+
+        if self.config.allowlist_only {
+            return IpAccessDecision::Denied {
+                reason: "IP address is not on the allowlist".to_string(),
+            };
+        }
+
+        IpAccessDecision::Allowed
+    }
+
+    /// Add an IP address or CIDR range to a list
+    ///
+    /// # Arguments
+    /// * `list` - The list to add to
+    /// * `cidr` - An IP address or CIDR range, e.g. `203.0.113.0/24`
+    /// * `reason` - Why the entry is added
+    /// * `created_by` - Administrator adding the entry
+    /// * `expires_in_seconds` - Optional lifetime of the entry
+    ///
+    /// # Returns
+    /// * `Ok(IpAccessEntry)` - The stored entry with a normalized CIDR
+    /// * `Err(DomainError::Validation)` - Invalid or overly broad range, or empty reason
+    /// * `Err(DomainError::BusinessRule)` - The list is full
+    pub async fn add_entry(
+        &self,
+        list: IpAccessList,
+        cidr: &str,
+        reason: &str,
+        created_by: Option<Uuid>,
+        expires_in_seconds: Option<i64>,
+    ) -> DomainResult<IpAccessEntry> {
+        let network = self.parse_network(cidr)?;
+
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Reason must not be empty".to_string(),
+            });
+        }
+
+        if expires_in_seconds.is_some_and(|seconds| seconds <= 0) {
+            return Err(DomainError::Validation {
+                message: "Expiry must be in the future".to_string(),
+            });
+        }
+
+        let cidr = network.to_string();
+        {
+            let snapshot = self.snapshot.read().unwrap();
+            let entries = match list {
+                IpAccessList::Allow => &snapshot.allow,
+                IpAccessList::Deny => &snapshot.deny,
+            };
+            let is_new = !entries.iter().any(|(_, entry)| entry.cidr == cidr);
+            if is_new && entries.len() >= self.config.max_entries_per_list {
+                return Err(DomainError::BusinessRule {
+                    message: format!(
+                        "The {} list already has {} entries",
+                        list.as_str(),
+                        self.config.max_entries_per_list
+                    ),
+                });
+            }
+        }
+
+        let now = Utc::now();
+        let entry = IpAccessEntry {
+            cidr: cidr.clone(),
+            reason: reason.to_string(),
+            created_by,
+            created_at: now,
+            expires_at: expires_in_seconds.map(|seconds| now + Duration::seconds(seconds)),
+        };
+
+        self.store
+            .add_entry(list, &entry)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        {
+            let mut snapshot = self.snapshot.write().unwrap();
+            let entries = snapshot.list_mut(list);
+            entries.retain(|(_, existing)| existing.cidr != cidr);
+            entries.push((network, entry.clone()));
+        }
+
+        info!(
+            list = list.as_str(),
+            cidr = %cidr,
+            created_by = ?created_by,
+            "IP access entry added"
+        );
+
+        Ok(entry)
+    }
+
+    /// Remove an IP address or CIDR range from a list
+    ///
+    /// # Returns
+    /// * `Ok(())` - The entry was removed
+    /// * `Err(DomainError::Validation)` - Invalid CIDR
+    /// * `Err(DomainError::NotFound)` - The list has no such entry
+    pub async fn remove_entry(&self, list: IpAccessList, cidr: &str) -> DomainResult<()> {
+        let cidr = cidr
+            .parse::<IpNetwork>()
+            .map(normalize)
+            .map_err(|_| DomainError::Validation {
+                message: "Invalid IP address or CIDR range".to_string(),
+            })?
+            .to_string();
+
+        let removed = self
+            .store
+            .remove_entry(list, &cidr)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        self.snapshot
+            .write()
+            .unwrap()
+            .list_mut(list)
+            .retain(|(_, entry)| entry.cidr != cidr);
+
+        if !removed {
+            return Err(DomainError::NotFound {
+                resource: "IP access entry".to_string(),
+            });
+        }
+
+        info!(list = list.as_str(), cidr = %cidr, "IP access entry removed");
+        Ok(())
+    }
+
+    /// List the active entries on a list, newest first
+    pub async fn list_entries(&self, list: IpAccessList) -> DomainResult<Vec<IpAccessEntry>> {
+        let now = Utc::now();
+        let mut entries: Vec<IpAccessEntry> = self
+            .store
+            .list_entries(list)
+            .await
+            .map_err(|message| DomainError::Internal { message })?
+            .into_iter()
+            .filter(|entry| !entry.is_expired(now))
+            .collect();
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+        Ok(entries)
+    }
+
+    /// Reload both lists from the store and purge expired entries
+    pub async fn refresh(&self) -> DomainResult<()> {
+        let now = Utc::now();
+        let mut snapshot = Snapshot::default();
+
+        for list in [IpAccessList::Allow, IpAccessList::Deny] {
+            let entries = self
+                .store
+                .list_entries(list)
+                .await
+                .map_err(|message| DomainError::Internal { message })?;
+
+            for entry in entries {
+                if entry.is_expired(now) {
+                    if let Err(e) = self.store.remove_entry(list, &entry.cidr).await {
+                        warn!(cidr = %entry.cidr, error = %e, "Failed to purge expired IP access entry");
+                    }
+                    continue;
+                }
+
+                match entry.cidr.parse::<IpNetwork>() {
+                    Ok(network) => snapshot.list_mut(list).push((network, entry)),
+                    Err(_) => warn!(cidr = %entry.cidr, "Ignoring unparseable IP access entry"),
+                }
+            }
+        }
+
+        *self.snapshot.write().unwrap() = snapshot;
+        Ok(())
+    }
+
+    /// Parse and validate a CIDR range for a new entry
+    fn parse_network(&self, cidr: &str) -> DomainResult<IpNetwork> {
+        let network = cidr
+            .trim()
+            .parse::<IpNetwork>()
+            .map(normalize)
+            .map_err(|_| DomainError::Validation {
+                message: "Invalid IP address or CIDR range".to_string(),
+            })?;
+
+        let min_prefix = match network {
+            IpNetwork::V4(_) => self.config.min_ipv4_prefix,
+            IpNetwork::V6(_) => self.config.min_ipv6_prefix,
+        };
+        if network.prefix() < min_prefix {
+            return Err(DomainError::Validation {
+                message: format!("CIDR prefix must be at least /{}", min_prefix),
+            });
+        }
+
+        Ok(network)
+    }
+
+    /// Start refreshing the lists as a background task
+    ///
+    /// This spawns a tokio task that reloads the lists at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("IP access control is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.refresh_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "IP access control started - will refresh every {} seconds",
+                self.config.refresh_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                if let Err(e) = self.refresh().await {
+                    error!("IP access list refresh failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Zero the host bits so `203.0.113.7/24` and `203.0.113.0/24` are the same entry
+fn normalize(network: IpNetwork) -> IpNetwork {
+    IpNetwork::new(network.network(), network.prefix()).unwrap_or(network)
+}
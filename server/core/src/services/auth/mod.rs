@@ -5,29 +5,55 @@
 //! - User registration and login
 //! - Token generation and refresh
 //! - User type selection
-//! - Rate limiting
+//! - Rate limiting, with an allowlist of trusted testers
 //! - Account locking for brute force protection
+//! - IP allowlists and denylists
+//! - Proof-of-work challenges before sending codes
+//! - Geo-IP anomaly detection on login
+//! - Notifications of logins from new devices or countries
 
 mod account_lock;
 mod attack_detector;
+mod attack_guard;
 mod config;
 mod delay_response;
+mod geo_anomaly;
+mod ip_access_control;
 mod phone_utils;
+mod proof_of_work;
 mod rate_limiter;
 mod service;
+mod suspicious_login;
 
 #[cfg(test)]
 mod tests;
 
-pub use account_lock::{AccountLockService, AccountLockConfig, AccountLockInfo};
+pub use account_lock::{
+    AccountLockService, AccountLockConfig, AccountLockInfo, LockedAccount, DEFAULT_LOCK_REASON
+};
 pub use attack_detector::{
     AttackDetector, AttackDetectorConfig, AttackDetectionResult, 
     AttackPattern, RecommendedAction, AttackTrendAnalysis
 };
-pub use config::AuthServiceConfig;
+pub use attack_guard::{AttackGuard, AttackGuardConfig, GuardDecision};
+pub use config::{AuthServiceConfig, TrustedTesterConfig};
 pub use delay_response::{DelayResponseService, DelayResponseConfig, DelayInfo};
+pub use geo_anomaly::{
+    GeoAnomaly, GeoAnomalyConfig, GeoAnomalyDetector, GeoAssessment, GeoIpLookupTrait,
+};
+pub use ip_access_control::{
+    IpAccessControlService, IpAccessControlConfig, IpAccessDecision, IpAccessEntry, IpAccessList,
+    IpAccessStoreTrait,
+};
+pub use proof_of_work::{
+    meets_difficulty, ProofOfWorkChallenge, ProofOfWorkDecision, ProofOfWorkService, ProofOfWorkStoreTrait,
+    MAX_PROOF_OF_WORK_DIFFICULTY,
+};
 pub use rate_limiter::RateLimiterTrait;
 pub use service::AuthService;
+pub use suspicious_login::{
+    SmsSuspiciousLoginNotifier, SuspiciousLogin, SuspiciousLoginNotifierTrait, SuspiciousLoginReason,
+};
 
 // Export selected phone utilities for public use
 pub use phone_utils::{
@@ -35,6 +61,8 @@ pub use phone_utils::{
     validate_australian_phone,
     validate_phone_with_country,
     normalize_to_e164,
+    extract_country_code,
     mask_phone,
     CountryCode,
+    PhoneHasher,
 };
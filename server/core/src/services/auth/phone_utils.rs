@@ -3,10 +3,18 @@
 //! This module provides comprehensive phone number validation and manipulation
 //! utilities supporting E.164 format and country-specific validation rules.
 
+use std::fmt;
+
+use hmac::{Hmac, Mac};
 use sha2::{Sha256, Digest};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use re_shared::config::auth::PhoneHashConfig;
+use re_shared::config::SecretString;
+
+type HmacSha256 = Hmac<Sha256>;
+
 /// Regular expression for valid E.164 format
 /// E.164 format: + followed by 1-3 digit country code (no leading 0) and up to 14 total digits
 static E164_REGEX: Lazy<Regex> = Lazy::new(|| {
@@ -294,6 +302,10 @@ pub fn mask_phone(phone: &str) -> String {
 
 /// Hash a phone number using SHA-256
 ///
+/// The hash is unpeppered, so it only suits short-lived keys such as rate
+/// limit counters and configured allowlists. Hashes stored with user and
+/// audit rows are produced by a [`PhoneHasher`].
+///
 /// # Arguments
 ///
 /// * `phone` - Phone number to hash (without country code)
@@ -308,6 +320,103 @@ pub fn hash_phone(phone: &str) -> String {
     format!("{:x}", result)
 }
 
+/// Hashes phone numbers with HMAC-SHA256 under versioned server-side peppers
+///
+/// There are few enough phone numbers that a bare SHA-256 hash is reversed
+/// by hashing all of them, so stored hashes are keyed with a secret pepper.
+/// Version 1 is the HMAC of the phone number under the first pepper, and
+/// every later version the HMAC of the previous version's hash under its own
+/// pepper. A hash is thus brought to the current version with the peppers
+/// alone, and lookups try each version a phone number may be stored under.
+#[derive(Clone)]
+pub struct PhoneHasher {
+    peppers: Vec<SecretString>,
+    accept_unpeppered: bool,
+}
+
+impl PhoneHasher {
+    /// Create a hasher from the phone hashing configuration
+    pub fn new(config: &PhoneHashConfig) -> Self {
+        Self {
+            peppers: config.peppers.clone(),
+            accept_unpeppered: config.accept_unpeppered,
+        }
+    }
+
+    /// Version of the hashes written now; 0 for bare SHA-256
+    pub fn current_version(&self) -> u16 {
+        self.peppers.len() as u16
+    }
+
+    /// Hash a phone number for storage, at the current version
+    ///
+    /// Falls back to [`hash_phone`] when no pepper is configured.
+    pub fn hash(&self, phone: &str) -> String {
+        self.version_hashes(phone).pop().unwrap_or_else(|| hash_phone(phone))
+    }
+
+    /// Every hash a phone number may be stored under, current one first
+    pub fn lookup_hashes(&self, phone: &str) -> Vec<String> {
+        let mut hashes = self.version_hashes(phone);
+        hashes.reverse();
+        if hashes.is_empty() || self.accept_unpeppered {
+            hashes.push(hash_phone(phone));
+        }
+        hashes.dedup();
+        hashes
+    }
+
+    /// Bring a hash stored at `version` to the current version
+    ///
+    /// # Returns
+    /// * `Some(hash)` - The hash at the current version
+    /// * `None` - The hash is already current, or is a bare SHA-256 hash,
+    ///   which only the phone number can be rehashed from
+    pub fn rehash(&self, hash: &str, version: u16) -> Option<String> {
+        let version = usize::from(version);
+        if version == 0 || version >= self.peppers.len() {
+            return None;
+        }
+
+        let rehashed = self.peppers[version..]
+            .iter()
+            .fold(hash.to_string(), |hash, pepper| hmac_phone(pepper.expose_secret(), &hash));
+        Some(rehashed)
+    }
+
+    /// Hashes of a phone number at every version, oldest first
+    fn version_hashes(&self, phone: &str) -> Vec<String> {
+        let mut hashes: Vec<String> = Vec::with_capacity(self.peppers.len());
+        for pepper in &self.peppers {
+            let input = hashes.last().map_or(phone, String::as_str);
+            hashes.push(hmac_phone(pepper.expose_secret(), input));
+        }
+        hashes
+    }
+}
+
+impl Default for PhoneHasher {
+    fn default() -> Self {
+        Self::new(&PhoneHashConfig::default())
+    }
+}
+
+impl fmt::Debug for PhoneHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PhoneHasher")
+            .field("version", &self.current_version())
+            .field("accept_unpeppered", &self.accept_unpeppered)
+            .finish()
+    }
+}
+
+/// Hex-encoded HMAC-SHA256 of a phone number, or of the hash of an earlier version
+fn hmac_phone(pepper: &str, phone: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(pepper.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(phone.as_bytes());
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
 /// Extract country code from a full phone number
 ///
 /// # Arguments
@@ -0,0 +1,174 @@
+//! Proof-of-work challenges for sending verification codes
+//!
+//! Before requesting a code the client fetches a challenge, a random nonce
+//! and a difficulty, and searches for a solution such that
+//! `SHA-256("{nonce}:{solution}")` starts with `difficulty` zero bits. The
+//! server checks a solution with a single hash. Challenges live in a shared
+//! store and are removed when a solution is submitted, so each one pays for
+//! exactly one attempt.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use re_shared::config::rate_limit::ProofOfWorkConfig;
+
+use crate::errors::{DomainError, DomainResult};
+
+/// Highest difficulty accepted, to keep challenges solvable on phones
+pub const MAX_PROOF_OF_WORK_DIFFICULTY: u8 = 32;
+
+/// Longest solution accepted
+const MAX_SOLUTION_LENGTH: usize = 64;
+
+/// A challenge issued to a client
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofOfWorkChallenge {
+    /// Random hex nonce the solution is computed for
+    pub nonce: String,
+    /// Leading zero bits the solution hash must have
+    pub difficulty: u8,
+    /// When the challenge can no longer be solved
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Outcome of checking a submitted solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofOfWorkDecision {
+    /// Challenges are disabled in this environment
+    NotRequired,
+    /// The solution meets the difficulty of a live challenge
+    Accepted,
+    /// The challenge is unknown, expired or already used
+    UnknownChallenge,
+    /// The solution does not meet the difficulty
+    InsufficientWork,
+}
+
+impl ProofOfWorkDecision {
+    /// Whether the request may proceed
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::NotRequired | Self::Accepted)
+    }
+}
+
+/// Shared storage for issued challenges
+#[async_trait]
+pub trait ProofOfWorkStoreTrait: Send + Sync {
+    /// Store the difficulty of a challenge under its nonce
+    async fn save(&self, nonce: &str, difficulty: u8, ttl_seconds: u64) -> Result<(), String>;
+
+    /// Remove and return the difficulty of a challenge, so each nonce can
+    /// only be used once
+    async fn take(&self, nonce: &str) -> Result<Option<u8>, String>;
+}
+
+/// Service issuing and checking proof-of-work challenges
+pub struct ProofOfWorkService<S>
+where
+    S: ProofOfWorkStoreTrait + 'static,
+{
+    store: Arc<S>,
+    config: ProofOfWorkConfig,
+}
+
+impl<S> ProofOfWorkService<S>
+where
+    S: ProofOfWorkStoreTrait + 'static,
+{
+    /// Create a new proof-of-work service
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Internal)` - The difficulty exceeds 32 bits
+    pub fn new(store: Arc<S>, config: ProofOfWorkConfig) -> DomainResult<Self> {
+        if config.difficulty > MAX_PROOF_OF_WORK_DIFFICULTY {
+            return Err(DomainError::Internal {
+                message: format!(
+                    "Proof-of-work difficulty must not exceed {} bits",
+                    MAX_PROOF_OF_WORK_DIFFICULTY
+                ),
+            });
+        }
+
+        Ok(Self { store, config })
+    }
+
+    /// Whether clients must solve a challenge before sending a code
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Issue a new challenge
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Internal)` - The challenge could not be stored
+    pub async fn issue_challenge(&self) -> DomainResult<ProofOfWorkChallenge> {
+        let mut bytes = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let nonce = hex::encode(bytes);
+
+        self.store
+            .save(&nonce, self.config.difficulty, self.config.challenge_ttl_seconds)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        Ok(ProofOfWorkChallenge {
+            nonce,
+            difficulty: self.config.difficulty,
+            expires_at: Utc::now() + Duration::seconds(self.config.challenge_ttl_seconds as i64),
+        })
+    }
+
+    /// Check a submitted solution, using up its challenge
+    ///
+    /// The difficulty stored with the challenge applies, so raising the
+    /// configured difficulty does not invalidate challenges being solved.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Internal)` - The store could not be reached
+    pub async fn verify(&self, nonce: &str, solution: &str) -> DomainResult<ProofOfWorkDecision> {
+        if !self.config.enabled {
+            return Ok(ProofOfWorkDecision::NotRequired);
+        }
+        if solution.is_empty() || solution.len() > MAX_SOLUTION_LENGTH {
+            return Ok(ProofOfWorkDecision::InsufficientWork);
+        }
+
+        let difficulty = self
+            .store
+            .take(nonce)
+            .await
+            .map_err(|message| DomainError::Internal { message })?;
+
+        Ok(match difficulty {
+            None => ProofOfWorkDecision::UnknownChallenge,
+            Some(difficulty) if meets_difficulty(nonce, solution, difficulty) => ProofOfWorkDecision::Accepted,
+            Some(_) => ProofOfWorkDecision::InsufficientWork,
+        })
+    }
+}
+
+/// Whether `SHA-256("{nonce}:{solution}")` starts with `difficulty` zero bits
+pub fn meets_difficulty(nonce: &str, solution: &str, difficulty: u8) -> bool {
+    let hash = Sha256::new()
+        .chain_update(nonce.as_bytes())
+        .chain_update(b":")
+        .chain_update(solution.as_bytes())
+        .finalize();
+
+    let mut remaining = u32::from(difficulty);
+    for byte in hash {
+        if remaining == 0 {
+            return true;
+        }
+        let zeros = byte.leading_zeros().min(remaining);
+        if zeros < remaining.min(8) {
+            return false;
+        }
+        remaining -= zeros;
+    }
+    remaining == 0
+}
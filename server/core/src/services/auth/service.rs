@@ -3,21 +3,29 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use serde_json;
+use crate::domain::entities::attribution::RegistrationAttribution;
+use crate::domain::entities::audit::AuditLog;
+use crate::domain::entities::compliance::ComplianceRequirement;
+use crate::domain::entities::token::RefreshToken;
 use crate::domain::entities::user::User;
 use crate::domain::value_objects::AuthResponse;
-use crate::errors::{AuthError, DomainError, DomainResult, ValidationError};
-use crate::repositories::{UserRepository, TokenRepository, AuditLogRepository};
+use crate::errors::{AuthError, DomainError, DomainResult, TokenError, ValidationError};
+use crate::repositories::{UserRepository, TokenRepository, AuditLogRepository, UnitOfWork};
 use crate::services::verification::{
     VerificationService, SmsServiceTrait, CacheServiceTrait, SendCodeResult,
 };
-use crate::services::token::TokenService;
+use crate::services::token::{SessionInfo, SessionMetadata, TokenService};
 use crate::services::audit::AuditService;
+use crate::services::compliance::ComplianceGateTrait;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookService};
 
 use super::config::AuthServiceConfig;
+use super::geo_anomaly::{GeoAnomalyDetector, GeoAssessment};
 use super::phone_utils::{
-    mask_phone, hash_phone, extract_country_code, validate_phone_with_country
+    mask_phone, extract_country_code, validate_phone_with_country
 };
 use super::rate_limiter::RateLimiterTrait;
+use super::suspicious_login::{SuspiciousLogin, SuspiciousLoginNotifierTrait, SuspiciousLoginReason};
 
 /// Authentication service for managing the complete authentication flow
 pub struct AuthService<U, S, C, R, T, A = crate::repositories::audit::NoOpAuditLogRepository> 
@@ -39,6 +47,16 @@ where
     token_service: Arc<TokenService<T>>,
     /// Optional audit service for logging security events
     audit_service: Option<Arc<AuditService<A>>>,
+    /// Optional detector for logins from unexpected countries
+    geo_anomaly_detector: Option<Arc<GeoAnomalyDetector>>,
+    /// Optional notifier for logins from new devices or countries
+    suspicious_login_notifier: Option<Arc<dyn SuspiciousLoginNotifierTrait>>,
+    /// Optional hooks run when a user registers
+    lifecycle_hooks: Option<Arc<LifecycleHookService>>,
+    /// Optional unit of work saving a login's writes in one transaction
+    unit_of_work: Option<Arc<dyn UnitOfWork>>,
+    /// Optional checks reporting the compliance steps a user's market requires
+    compliance: Option<Arc<dyn ComplianceGateTrait>>,
     /// Service configuration
     config: AuthServiceConfig,
 }
@@ -74,6 +92,11 @@ where
             rate_limiter,
             token_service,
             audit_service: None,
+            geo_anomaly_detector: None,
+            suspicious_login_notifier: None,
+            lifecycle_hooks: None,
+            unit_of_work: None,
+            compliance: None,
             config,
         }
     }
@@ -102,10 +125,55 @@ where
             rate_limiter,
             token_service,
             audit_service: Some(audit_service),
+            geo_anomaly_detector: None,
+            suspicious_login_notifier: None,
+            lifecycle_hooks: None,
+            unit_of_work: None,
+            compliance: None,
             config,
         }
     }
 
+    /// Enable geo-IP anomaly detection on successful verifications
+    ///
+    /// Anomalous logins are recorded in the audit log and, if the detector
+    /// requires it, flagged for step-up verification in the response.
+    pub fn with_geo_anomaly_detector(mut self, detector: Arc<GeoAnomalyDetector>) -> Self {
+        self.geo_anomaly_detector = Some(detector);
+        self
+    }
+
+    /// Notify users of logins from a new device or a new country
+    ///
+    /// Such logins are recorded in the audit log whether or not a notifier
+    /// is set; the notifier only adds the message to the user.
+    pub fn with_suspicious_login_notifier(mut self, notifier: Arc<dyn SuspiciousLoginNotifierTrait>) -> Self {
+        self.suspicious_login_notifier = Some(notifier);
+        self
+    }
+
+    /// Run lifecycle hooks when a user registers
+    pub fn with_lifecycle_hooks(mut self, hooks: Arc<LifecycleHookService>) -> Self {
+        self.lifecycle_hooks = Some(hooks);
+        self
+    }
+
+    /// Save the user, refresh token and login audit entry of a successful
+    /// verification in one transaction
+    ///
+    /// Without a unit of work they are written one after the other, so a
+    /// failure in between can leave a registered user without a session.
+    pub fn with_unit_of_work(mut self, unit_of_work: Arc<dyn UnitOfWork>) -> Self {
+        self.unit_of_work = Some(unit_of_work);
+        self
+    }
+
+    /// Report the compliance steps a user still has to complete with each login
+    pub fn with_compliance(mut self, compliance: Arc<dyn ComplianceGateTrait>) -> Self {
+        self.compliance = Some(compliance);
+        self
+    }
+
     /// Send a verification code to a phone number
     ///
     /// This method:
@@ -113,6 +181,9 @@ where
     /// 2. Checks rate limiting (3 requests per hour per phone, 10 per hour per IP)
     /// 3. Delegates to verification service for code generation and sending
     /// 4. Increments rate limit counter
+    ///
+    /// Trusted testers skip steps 2 and 4, and get the configured fixed code
+    /// instead of an SMS if there is one.
     /// 5. Logs all authentication events to audit log
     ///
     /// # Arguments
@@ -153,8 +224,11 @@ where
             }));
         }
 
+        // QA staff and app store reviewers are exempt from rate limiting
+        let trusted_tester = self.config.trusted_testers.is_trusted(phone, &self.config.phone_hasher);
+
         // Step 2: Check phone-based rate limiting (3 times per hour per phone number)
-        let phone_rate_limit_exceeded = self.rate_limiter
+        let phone_rate_limit_exceeded = !trusted_tester && self.rate_limiter
             .check_sms_rate_limit(phone)
             .await
             .map_err(|e| {
@@ -172,7 +246,7 @@ where
             // Log to audit service if available (Requirement 7.4)
             if let Some(ref audit_service) = self.audit_service {
                 let phone_masked = mask_phone(phone);
-                let phone_hash = hash_phone(phone);
+                let phone_hash = self.config.phone_hasher.hash(phone);
                 let _ = audit_service.log_rate_limit_violation(
                     "phone",
                     Some(&phone_masked),
@@ -196,7 +270,7 @@ where
         
         // Step 3: Check IP-based rate limiting if IP is provided (10 attempts per hour per IP)
         if let Some(ref ip) = client_ip {
-            let ip_rate_limit_exceeded = self.rate_limiter
+            let ip_rate_limit_exceeded = !trusted_tester && self.rate_limiter
                 .check_ip_verification_limit(ip)
                 .await
                 .map_err(|e| {
@@ -214,7 +288,7 @@ where
                 // Log to audit service if available (Requirement 7.4)
                 if let Some(ref audit_service) = self.audit_service {
                     let phone_masked = mask_phone(phone);
-                    let phone_hash = hash_phone(phone);
+                    let phone_hash = self.config.phone_hasher.hash(phone);
                     let _ = audit_service.log_rate_limit_violation(
                         "ip",
                         Some(&phone_masked),
@@ -238,16 +312,19 @@ where
         }
 
         // Step 4: Delegate to verification service to send the code
-        let send_result = match self.verification_service
-            .send_verification_code(phone)
-            .await
-        {
+        let send_result = match &self.config.trusted_testers.fixed_code {
+            Some(code) if trusted_tester => {
+                self.verification_service.store_fixed_code(phone, code).await
+            }
+            _ => self.verification_service.send_verification_code(phone).await,
+        };
+        let send_result = match send_result {
             Ok(result) => result,
             Err(e) => {
                 // Log SMS send failure to audit service
                 if let Some(ref audit_service) = self.audit_service {
                     let phone_masked = mask_phone(phone);
-                    let phone_hash = hash_phone(phone);
+                    let phone_hash = self.config.phone_hasher.hash(phone);
                     let failure_reason = e.to_string();
                     
                     let event_type = if failure_reason.contains("Rate limit") {
@@ -286,23 +363,25 @@ where
         };
 
         // Step 5: Increment rate limit counters after successful send
-        let _phone_count = self.rate_limiter
-            .increment_sms_counter(phone)
-            .await
-            .unwrap_or(1);
-        
-        // Increment IP counter if IP is provided
-        if let Some(ref ip) = client_ip {
-            let _ip_count = self.rate_limiter
-                .increment_ip_verification_counter(ip)
+        if !trusted_tester {
+            let _phone_count = self.rate_limiter
+                .increment_sms_counter(phone)
                 .await
                 .unwrap_or(1);
+
+            // Increment IP counter if IP is provided
+            if let Some(ref ip) = client_ip {
+                let _ip_count = self.rate_limiter
+                    .increment_ip_verification_counter(ip)
+                    .await
+                    .unwrap_or(1);
+            }
         }
         
         // Log successful code send to audit service
         if let Some(ref audit_service) = self.audit_service {
             let phone_masked = mask_phone(phone);
-            let phone_hash = hash_phone(phone);
+            let phone_hash = self.config.phone_hasher.hash(phone);
             let _ = audit_service.log_auth_event(
                 crate::domain::entities::audit::AuditEventType::SendCodeSuccess,
                 client_ip.unwrap_or_else(|| "unknown".to_string()),
@@ -314,6 +393,7 @@ where
                 Some(serde_json::json!({
                     "message_id": send_result.message_id,
                     "phone_masked": phone_masked,
+                    "trusted_tester": trusted_tester,
                 })),
             ).await;
         }
@@ -369,6 +449,24 @@ where
         client_ip: Option<String>,
         user_agent: Option<String>,
         device_fingerprint: Option<String>,
+    ) -> DomainResult<AuthResponse> {
+        self.verify_code_with_attribution(phone, code, client_ip, user_agent, device_fingerprint, None)
+            .await
+    }
+
+    /// Verify a verification code, passing on the campaign a new user arrived through
+    ///
+    /// Works like [`Self::verify_code`]. When the code registers a new user,
+    /// the attribution is added to the `UserRegistered` lifecycle event, but
+    /// only if the user consented to marketing.
+    pub async fn verify_code_with_attribution(
+        &self,
+        phone: &str,
+        code: &str,
+        client_ip: Option<String>,
+        user_agent: Option<String>,
+        device_fingerprint: Option<String>,
+        mut attribution: Option<RegistrationAttribution>,
     ) -> DomainResult<AuthResponse> {
         // Step 1: Validate phone number format with country-specific rules
         if !validate_phone_with_country(phone) {
@@ -397,7 +495,7 @@ where
                 // Log to audit service if available (Requirement 7.4)
                 if let Some(ref audit_service) = self.audit_service {
                     let phone_masked = mask_phone(phone);
-                    let phone_hash = hash_phone(phone);
+                    let phone_hash = self.config.phone_hasher.hash(phone);
                     let _ = audit_service.log_rate_limit_violation(
                         "ip",
                         Some(&phone_masked),
@@ -430,7 +528,7 @@ where
                 // Log verification failure to audit service
                 if let Some(ref audit_service) = self.audit_service {
                     let phone_masked = mask_phone(phone);
-                    let phone_hash = hash_phone(phone);
+                    let phone_hash = self.config.phone_hasher.hash(phone);
                     let failure_reason = e.to_string();
                     
                     let event_type = if failure_reason.contains("expired") {
@@ -472,11 +570,12 @@ where
             let (country_code, phone_without_code) = extract_country_code(phone);
             
             // Hash the phone number for storage
-            let phone_hash = hash_phone(&phone_without_code);
+            let phone_hash = self.config.phone_hasher.hash(&phone_without_code);
             
             // Step 5: Look up existing user or create new one
-            let mut user = match self.user_repository
-                .find_by_phone(&phone_hash, &country_code)
+            let mut is_new_user = false;
+            let mut user = match self
+                .find_user_by_phone(&phone_without_code, &country_code)
                 .await
                 .map_err(|e| {
                     DomainError::Internal {
@@ -498,75 +597,211 @@ where
                     }
                     
                     // Create new user
+                    is_new_user = true;
                     let mut new_user = User::new(phone_hash.clone(), country_code.clone());
+                    new_user.phone_hash_version = self.config.phone_hasher.current_version();
                     new_user.verify(); // Mark as verified since they completed phone verification
                     
-                    // Save the new user to the repository
-                    self.user_repository
-                        .create(new_user)
-                        .await
-                        .map_err(|e| {
-                            DomainError::Internal {
-                                message: format!("Failed to create user: {}", e),
-                            }
-                        })?
+                    if self.unit_of_work.is_some() {
+                        // Saved with the login below
+                        new_user
+                    } else {
+                        // Save the new user to the repository
+                        self.user_repository
+                            .create(new_user)
+                            .await
+                            .map_err(|e| {
+                                DomainError::Internal {
+                                    message: format!("Failed to create user: {}", e),
+                                }
+                            })?
+                    }
                 }
             };
+
+            if is_new_user && self.unit_of_work.is_none() {
+                self.publish_registration(&user, attribution.take());
+            }
             
-            // Step 6: Update user state
+            // Step 6: Compare the login location with the phone and the previous login
+            let geo_assessment = match self.geo_anomaly_detector {
+                Some(ref detector) => {
+                    detector
+                        .assess(
+                            client_ip.as_deref(),
+                            &country_code,
+                            user.last_login_country.as_deref(),
+                        )
+                        .await
+                }
+                None => GeoAssessment::default(),
+            };
+
+            // Step 7: Update user state
             // Mark as verified if not already (for existing users who may not have been verified)
             if !user.is_verified {
                 user.verify();
             }
             
-            // Update last login timestamp
+            // Update last login timestamp and, when known, its country
             user.update_last_login();
+            if geo_assessment.country.is_some() {
+                user.last_login_country = geo_assessment.country.clone();
+            }
             
-            // Save the updated user
-            let _updated_user = self.user_repository
-                .update(user)
-                .await
-                .map_err(|e| {
-                    DomainError::Internal {
-                        message: format!("Failed to update user: {}", e),
-                    }
-                })?;
+            // Save the updated user, unless it is saved with the login below
+            let _updated_user = if self.unit_of_work.is_some() {
+                user
+            } else {
+                self.user_repository
+                    .update(user)
+                    .await
+                    .map_err(|e| {
+                        DomainError::Internal {
+                            message: format!("Failed to update user: {}", e),
+                        }
+                    })?
+            };
             
             // Clear the verification code from cache now that it's been used
             let _ = self.verification_service
                 .clear_verification(phone)
                 .await;
             
-            // Step 7: Generate JWT tokens with phone hash and device fingerprint
-            let token_pair = self.token_service
-                .generate_tokens(
+            // Step 8: Check whether an existing user signs in from a new device,
+            // before the new refresh token records it
+            let new_device = match (is_new_user, device_fingerprint.as_deref()) {
+                (false, Some(fingerprint)) => matches!(
+                    self.token_service.is_known_device(_updated_user.id, fingerprint).await,
+                    Ok(Some(false))
+                ),
+                _ => false,
+            };
+
+            // Step 9: Generate JWT tokens with phone hash and device fingerprint
+            let (_updated_user, token_pair, login_log) = match self.unit_of_work {
+                Some(ref unit_of_work) => {
+                    let (token_pair, refresh_token) = self.token_service.issue_tokens(
+                        _updated_user.id,
+                        _updated_user.user_type,
+                        _updated_user.is_verified,
+                        Some(phone_hash.clone()),
+                        device_fingerprint.clone(),
+                    )?;
+                    // The login is tracked by the id of its refresh token
+                    let login_log = self.audit_service.as_ref().map(|_| {
+                        AuditService::<A>::login_success_log(
+                            _updated_user.id,
+                            phone,
+                            &phone_hash,
+                            client_ip.clone().unwrap_or_else(|| "unknown".to_string()),
+                            user_agent.clone(),
+                            refresh_token.id,
+                            geo_assessment.country.clone(),
+                        )
+                    });
+                    let user = self
+                        .commit_login(
+                            unit_of_work.as_ref(),
+                            _updated_user,
+                            is_new_user,
+                            refresh_token,
+                            login_log.as_ref(),
+                        )
+                        .await?;
+
+                    self.token_service.start_session(&token_pair).await;
+                    if is_new_user {
+                        self.publish_registration(&user, attribution);
+                    }
+                    (user, token_pair, login_log)
+                }
+                None => {
+                    let token_pair = self.token_service
+                        .generate_tokens(
+                            _updated_user.id,
+                            _updated_user.user_type,
+                            _updated_user.is_verified,
+                            Some(phone_hash.clone()),
+                            device_fingerprint.clone(),
+                        )
+                        .await?;
+                    (_updated_user, token_pair, None)
+                }
+            };
+            
+            // Record the device and location the session was started from
+            self.token_service
+                .record_session(
                     _updated_user.id,
-                    _updated_user.user_type.clone(),
-                    _updated_user.is_verified,
-                    Some(phone_hash.clone()),
-                    device_fingerprint.clone(),
+                    &token_pair,
+                    SessionMetadata {
+                        user_agent: user_agent.clone(),
+                        ip_address: client_ip.clone(),
+                        country: geo_assessment.country.clone(),
+                    },
                 )
-                .await?;
-            
+                .await;
+
+            // Tell the user about logins from a new device or country
+            let reasons = SuspiciousLoginReason::collect(new_device, &geo_assessment);
+            if let (false, Some(session_id)) = (reasons.is_empty(), token_pair.token_family.clone()) {
+                let login = SuspiciousLogin {
+                    user_id: _updated_user.id,
+                    session_id,
+                    reasons,
+                    country: geo_assessment.country.clone(),
+                    ip_address: client_ip.clone(),
+                    occurred_at: chrono::Utc::now(),
+                };
+                self.notify_suspicious_login(phone, &phone_hash, &login, user_agent.clone()).await;
+            }
+
             // Log successful login to audit service (Requirement 7.3)
             if let Some(ref audit_service) = self.audit_service {
-                // Generate a token ID from the access token for tracking
-                let token_id = Uuid::new_v4();
-                let _ = audit_service.log_login_success(
-                    _updated_user.id,
-                    phone,
-                    &phone_hash,
-                    client_ip.unwrap_or_else(|| "unknown".to_string()),
-                    user_agent,
-                    token_id,
-                ).await;
+                if geo_assessment.is_anomalous() {
+                    let phone_masked = mask_phone(phone);
+                    let _ = audit_service.log_auth_event(
+                        crate::domain::entities::audit::AuditEventType::GeoAnomalyDetected,
+                        client_ip.clone().unwrap_or_else(|| "unknown".to_string()),
+                        Some(_updated_user.id),
+                        Some(&phone_masked),
+                        Some(phone_hash.clone()),
+                        user_agent.clone(),
+                        None,
+                        Some(serde_json::json!({
+                            "ip_country": geo_assessment.country,
+                            "anomalies": geo_assessment.anomalies,
+                            "step_up_required": geo_assessment.step_up_required,
+                        })),
+                    ).await;
+                }
+
+                if let Some(ref login_log) = login_log {
+                    // Already written with the login
+                    audit_service.publish_log(login_log);
+                } else {
+                    // Generate a token ID from the access token for tracking
+                    let token_id = Uuid::new_v4();
+                    let _ = audit_service.log_login_success(
+                        _updated_user.id,
+                        phone,
+                        &phone_hash,
+                        client_ip.unwrap_or_else(|| "unknown".to_string()),
+                        user_agent,
+                        token_id,
+                        geo_assessment.country.clone(),
+                    ).await;
+                }
             }
             
-            // Step 8: Create and return authentication response
-            let auth_response = AuthResponse::from_token_pair(
+            // Step 10: Create and return authentication response
+            let mut auth_response = AuthResponse::from_token_pair(
                 token_pair,
                 _updated_user.user_type,
             );
+            auth_response.requires_step_up = geo_assessment.step_up_required;
+            auth_response.compliance_required = self.compliance_required(&_updated_user).await;
             
             Ok(auth_response)
         } else {
@@ -619,11 +854,22 @@ where
             // Log login failure to audit service (Requirement 7.2)
             if let Some(ref audit_service) = self.audit_service {
                 let phone_masked = mask_phone(phone);
-                let phone_hash = hash_phone(phone);
+                let phone_hash = self.config.phone_hasher.hash(phone);
+
+                // Attribute the attempt to the account it targeted, so it
+                // shows up in that user's login history
+                let (country_code, phone_without_code) = extract_country_code(phone);
+                let user_id = self
+                    .find_user_by_phone(&phone_without_code, &country_code)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|user| user.id);
+
                 let _ = audit_service.log_auth_event(
                     event_type,
                     client_ip.unwrap_or_else(|| "unknown".to_string()),
-                    None,
+                    user_id,
                     Some(&phone_masked),
                     Some(phone_hash),
                     user_agent,
@@ -755,6 +1001,17 @@ where
             Err(e) => {
                 // Log refresh token failure to audit service
                 if let Some(ref audit_service) = self.audit_service {
+                    // A revoked token presented again suggests it was stolen
+                    if matches!(e, DomainError::Token(TokenError::TokenRevoked)) {
+                        let _ = audit_service.log_refresh_token_reuse(
+                            None,
+                            None,
+                            client_ip.clone().unwrap_or_else(|| "unknown".to_string()),
+                            user_agent.clone(),
+                        ).await;
+                        return Err(e);
+                    }
+
                     let failure_reason = e.to_string();
                     let _ = audit_service.log_auth_event(
                         crate::domain::entities::audit::AuditEventType::RefreshTokenFailure,
@@ -823,10 +1080,11 @@ where
         }
         
         // Step 6: Create and return authentication response
-        let auth_response = AuthResponse::from_token_pair(
+        let mut auth_response = AuthResponse::from_token_pair(
             token_pair,
             user.user_type,
         );
+        auth_response.compliance_required = self.compliance_required(&user).await;
 
         Ok(auth_response)
     }
@@ -903,4 +1161,244 @@ where
         
         Ok(())
     }
+
+    /// List the user's signed-in sessions, most recently active first
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<SessionInfo>)` - The sessions with their device and location;
+    ///   empty when sessions are not recorded
+    pub async fn list_sessions(&self, user_id: Uuid) -> DomainResult<Vec<SessionInfo>> {
+        self.token_service.list_sessions(user_id).await
+    }
+
+    /// Sign one of the user's sessions out, e.g. from the device list
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user
+    /// * `session_id` - ID of the session, as listed by [`Self::list_sessions`]
+    /// * `client_ip` - Optional client IP address for audit logging
+    /// * `user_agent` - Optional user agent for audit logging
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The session was signed out
+    /// * `Err(DomainError::NotFound)` - The user has no session with this ID
+    pub async fn sign_out_session(
+        &self,
+        user_id: Uuid,
+        session_id: &str,
+        client_ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> DomainResult<()> {
+        if !self.token_service.revoke_session(user_id, session_id).await? {
+            return Err(DomainError::NotFound {
+                resource: "Session".to_string(),
+            });
+        }
+
+        if let Some(ref audit_service) = self.audit_service {
+            let _ = audit_service.log_auth_event(
+                crate::domain::entities::audit::AuditEventType::Logout,
+                client_ip.unwrap_or_else(|| "unknown".to_string()),
+                Some(user_id),
+                None,
+                None,
+                user_agent,
+                None,
+                Some(serde_json::json!({
+                    "logout_type": "session",
+                    "session_id": session_id,
+                })),
+            ).await;
+        }
+
+        Ok(())
+    }
+
+    /// Revoke a session the user did not start
+    ///
+    /// Called when the user reports a login they were notified about. The
+    /// session's refresh tokens are revoked at once, so it cannot be
+    /// renewed; its current access token expires within minutes.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the reporting user
+    /// * `session_id` - ID of the reported session, as sent in the notification
+    /// * `client_ip` - Optional client IP address for audit logging
+    /// * `user_agent` - Optional user agent for audit logging
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The session was revoked
+    /// * `Err(DomainError::NotFound)` - The user has no session with this ID
+    pub async fn report_suspicious_login(
+        &self,
+        user_id: Uuid,
+        session_id: &str,
+        client_ip: Option<String>,
+        user_agent: Option<String>,
+    ) -> DomainResult<()> {
+        if !self.token_service.revoke_session(user_id, session_id).await? {
+            return Err(DomainError::NotFound {
+                resource: "Session".to_string(),
+            });
+        }
+
+        if let Some(ref audit_service) = self.audit_service {
+            let _ = audit_service.log_auth_event(
+                crate::domain::entities::audit::AuditEventType::SuspiciousLoginReported,
+                client_ip.unwrap_or_else(|| "unknown".to_string()),
+                Some(user_id),
+                None,
+                None,
+                user_agent,
+                None,
+                Some(serde_json::json!({
+                    "session_id": session_id,
+                })),
+            ).await;
+        }
+
+        Ok(())
+    }
+
+    /// Run the lifecycle hooks of a newly registered user
+    fn publish_registration(&self, user: &User, attribution: Option<RegistrationAttribution>) {
+        if let Some(ref hooks) = self.lifecycle_hooks {
+            hooks.publish(LifecycleEvent::UserRegistered {
+                user_id: user.id,
+                country_code: user.country_code.clone(),
+                registered_at: user.created_at,
+                attribution: attribution.and_then(RegistrationAttribution::consented),
+            });
+        }
+    }
+
+    /// Save the user, a new refresh token and the login audit entry in one
+    /// transaction, rolling it back if any write fails
+    async fn commit_login(
+        &self,
+        unit_of_work: &dyn UnitOfWork,
+        user: User,
+        is_new_user: bool,
+        refresh_token: RefreshToken,
+        login_log: Option<&AuditLog>,
+    ) -> DomainResult<User> {
+        let mut transaction = unit_of_work.begin_for_country(&user.country_code).await?;
+        let written: DomainResult<User> = async {
+            let user = if is_new_user {
+                transaction.create_user(user).await.map_err(|e| DomainError::Internal {
+                    message: format!("Failed to create user: {}", e),
+                })?
+            } else {
+                transaction.update_user(user).await.map_err(|e| DomainError::Internal {
+                    message: format!("Failed to update user: {}", e),
+                })?
+            };
+            transaction
+                .save_refresh_token(refresh_token)
+                .await
+                .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))?;
+            if let Some(login_log) = login_log {
+                transaction.create_audit_log(login_log).await?;
+            }
+            Ok(user)
+        }
+        .await;
+
+        match written {
+            Ok(user) => {
+                transaction.commit().await?;
+                Ok(user)
+            }
+            Err(e) => {
+                if let Err(rollback_error) = transaction.rollback().await {
+                    tracing::warn!(error = %rollback_error, "Failed to roll back login transaction");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Find the user of a phone number under any hash it may be stored with
+    ///
+    /// A user found under an outdated hash is given the current one, which
+    /// is saved with the next update of the user.
+    async fn find_user_by_phone(
+        &self,
+        phone_without_code: &str,
+        country_code: &str,
+    ) -> DomainResult<Option<User>> {
+        let phone_hashes = self.config.phone_hasher.lookup_hashes(phone_without_code);
+        for phone_hash in &phone_hashes {
+            if let Some(mut user) = self.user_repository.find_by_phone(phone_hash, country_code).await? {
+                user.phone_hash = phone_hashes[0].clone();
+                user.phone_hash_version = self.config.phone_hasher.current_version();
+                return Ok(Some(user));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Compliance requirements of the user's market that are not verified yet
+    ///
+    /// Failures are logged and otherwise ignored, so they never fail the login.
+    async fn compliance_required(&self, user: &User) -> Vec<ComplianceRequirement> {
+        let Some(ref compliance) = self.compliance else {
+            return Vec::new();
+        };
+
+        match compliance.outstanding_steps(user).await {
+            Ok(steps) => steps.into_iter().map(|step| step.requirement).collect(),
+            Err(e) => {
+                tracing::warn!(user_id = %user.id, error = %e, "Failed to load compliance steps");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Record a suspicious login and notify the user about it
+    ///
+    /// Failures are logged and otherwise ignored, so they never fail the login.
+    async fn notify_suspicious_login(
+        &self,
+        phone: &str,
+        phone_hash: &str,
+        login: &SuspiciousLogin,
+        user_agent: Option<String>,
+    ) {
+        let notified = match self.suspicious_login_notifier {
+            Some(ref notifier) => match notifier.notify(phone, login).await {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!(user_id = %login.user_id, error = %e, "Failed to send suspicious login notification");
+                    false
+                }
+            },
+            None => false,
+        };
+
+        if let Some(ref audit_service) = self.audit_service {
+            let phone_masked = mask_phone(phone);
+            let _ = audit_service.log_auth_event(
+                crate::domain::entities::audit::AuditEventType::SuspiciousLoginDetected,
+                login.ip_address.clone().unwrap_or_else(|| "unknown".to_string()),
+                Some(login.user_id),
+                Some(&phone_masked),
+                Some(phone_hash.to_string()),
+                user_agent,
+                None,
+                Some(serde_json::json!({
+                    "session_id": login.session_id,
+                    "reasons": login.reasons,
+                    "country": login.country,
+                    "notified": notified,
+                })),
+            ).await;
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+//! Suspicious login notifications
+//!
+//! A login is suspicious when it comes from a device fingerprint the user
+//! has not signed in with before, or from a different country than the
+//! previous login. The user is told about it out of band so they can report
+//! the session if it was not them. Notifications are informational: a
+//! failing notifier never blocks a login.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::services::verification::SmsServiceTrait;
+
+use super::geo_anomaly::{GeoAnomaly, GeoAssessment};
+
+/// A reason a login is reported to the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SuspiciousLoginReason {
+    /// The device fingerprint was never used by the user before
+    NewDevice,
+    /// The login country differs from the country of the previous login
+    NewCountry {
+        previous_country: String,
+        country: String,
+    },
+}
+
+impl SuspiciousLoginReason {
+    /// Reasons for a login, given whether its device is new and its geo assessment
+    pub fn collect(new_device: bool, geo_assessment: &GeoAssessment) -> Vec<Self> {
+        let mut reasons = Vec::new();
+
+        if new_device {
+            reasons.push(Self::NewDevice);
+        }
+
+        for anomaly in &geo_assessment.anomalies {
+            if let GeoAnomaly::LoginCountryChanged { previous_country, ip_country } = anomaly {
+                reasons.push(Self::NewCountry {
+                    previous_country: previous_country.clone(),
+                    country: ip_country.clone(),
+                });
+            }
+        }
+
+        reasons
+    }
+}
+
+/// A login the user is notified about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspiciousLogin {
+    /// User who signed in
+    pub user_id: Uuid,
+    /// Session created by the login, the token family to report
+    pub session_id: String,
+    /// Why the login is suspicious, never empty
+    pub reasons: Vec<SuspiciousLoginReason>,
+    /// Country the login came from, if it could be resolved
+    pub country: Option<String>,
+    /// Client IP address, if known
+    pub ip_address: Option<String>,
+    /// Timestamp of the login
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Trait for telling a user about a suspicious login
+#[async_trait]
+pub trait SuspiciousLoginNotifierTrait: Send + Sync {
+    /// Notify the user who owns the phone number
+    ///
+    /// # Arguments
+    /// * `phone` - The user's phone number (E.164 format)
+    /// * `login` - The login to report
+    async fn notify(&self, phone: &str, login: &SuspiciousLogin) -> Result<(), String>;
+}
+
+/// Notifier sending an informational SMS
+pub struct SmsSuspiciousLoginNotifier<S: SmsServiceTrait> {
+    sms_service: Arc<S>,
+}
+
+impl<S: SmsServiceTrait> SmsSuspiciousLoginNotifier<S> {
+    /// Create a new SMS notifier
+    pub fn new(sms_service: Arc<S>) -> Self {
+        Self { sms_service }
+    }
+
+    /// Text of the notification
+    pub fn message(login: &SuspiciousLogin) -> String {
+        let location = match login.country {
+            Some(ref country) => format!(" from {}", country),
+            None => String::new(),
+        };
+        let device = if login.reasons.contains(&SuspiciousLoginReason::NewDevice) {
+            " on a new device"
+        } else {
+            ""
+        };
+
+        format!(
+            "RenovEasy: new sign-in to your account{}{} at {} UTC. \
+             If this wasn't you, open the app and report it to sign that session out.",
+            device,
+            location,
+            login.occurred_at.format("%Y-%m-%d %H:%M"),
+        )
+    }
+}
+
+#[async_trait]
+impl<S: SmsServiceTrait> SuspiciousLoginNotifierTrait for SmsSuspiciousLoginNotifier<S> {
+    async fn notify(&self, phone: &str, login: &SuspiciousLogin) -> Result<(), String> {
+        self.sms_service
+            .send_message(phone, &Self::message(login))
+            .await
+            .map(|_| ())
+    }
+}
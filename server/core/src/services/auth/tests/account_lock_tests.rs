@@ -0,0 +1,174 @@
+//! Tests for account lock administration
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::audit::AuditEventType;
+use crate::errors::DomainError;
+use crate::services::audit::{AuditService, AuditServiceConfig};
+use crate::services::auth::{AccountLockConfig, AccountLockService, DEFAULT_LOCK_REASON};
+use crate::services::verification::CacheServiceTrait;
+
+use super::audit_integration_tests::MockAuditLogRepository;
+
+/// In-memory cache that remembers stored keys
+#[derive(Default)]
+struct MockLockCache {
+    entries: Mutex<HashMap<String, String>>,
+}
+
+#[async_trait]
+impl CacheServiceTrait for MockLockCache {
+    async fn store_code(&self, key: &str, value: &str) -> Result<(), String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn verify_code(&self, _key: &str, _code: &str) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    async fn get_remaining_attempts(&self, key: &str) -> Result<i64, String> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .get(key)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(-1))
+    }
+
+    async fn code_exists(&self, key: &str) -> Result<bool, String> {
+        Ok(self.entries.lock().unwrap().contains_key(key))
+    }
+
+    async fn get_code_ttl(&self, key: &str) -> Result<Option<i64>, String> {
+        Ok(self.entries.lock().unwrap().get(key).map(|_| 3600))
+    }
+
+    async fn clear_verification(&self, key: &str) -> Result<(), String> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+type TestLockService = AccountLockService<MockLockCache, MockAuditLogRepository>;
+
+fn create_service() -> (TestLockService, Arc<MockAuditLogRepository>) {
+    let audit_repo = Arc::new(MockAuditLogRepository::new());
+    let audit_service = Arc::new(AuditService::new(
+        audit_repo.clone(),
+        AuditServiceConfig {
+            async_writes: false,
+            ..AuditServiceConfig::default()
+        },
+    ));
+    let service = AccountLockService::with_audit(
+        Arc::new(MockLockCache::default()),
+        audit_service,
+        AccountLockConfig::default(),
+    );
+    (service, audit_repo)
+}
+
+#[tokio::test]
+async fn test_lock_records_audit_event_with_reason() {
+    let (service, audit_repo) = create_service();
+
+    service
+        .lock_account_with_reason("phone_hash_1", "Reported by support")
+        .await
+        .unwrap();
+
+    assert!(service.is_locked("phone_hash_1").await.unwrap());
+    let log = audit_repo.get_last_log().unwrap();
+    assert_eq!(log.event_type, AuditEventType::AccountLocked);
+    assert_eq!(log.phone_hash.as_deref(), Some("phone_hash_1"));
+    assert_eq!(log.failure_reason.as_deref(), Some("Reported by support"));
+}
+
+#[tokio::test]
+async fn test_list_locked_accounts() {
+    let (service, _) = create_service();
+    let user_id = Uuid::new_v4();
+
+    service.lock_account("phone_hash_1").await.unwrap();
+    service
+        .lock_account_with_reason(&user_id.to_string(), "Fraud review")
+        .await
+        .unwrap();
+    service.lock_account("phone_hash_2").await.unwrap();
+    service.unlock_account("phone_hash_2").await.unwrap();
+
+    let locked = service.list_locked_accounts().await.unwrap();
+
+    assert_eq!(locked.len(), 2);
+    let by_user = locked.iter().find(|l| l.user_id == Some(user_id)).unwrap();
+    assert_eq!(by_user.reason, "Fraud review");
+    let by_phone = locked.iter().find(|l| l.identifier == "phone_hash_1").unwrap();
+    assert_eq!(by_phone.reason, DEFAULT_LOCK_REASON);
+    assert!(by_phone.unlock_at > by_phone.locked_at);
+}
+
+#[tokio::test]
+async fn test_list_locked_accounts_deduplicates_relocks() {
+    let (service, _) = create_service();
+
+    service.lock_account("phone_hash_1").await.unwrap();
+    service
+        .lock_account_with_reason("phone_hash_1", "Locked again")
+        .await
+        .unwrap();
+
+    let locked = service.list_locked_accounts().await.unwrap();
+
+    assert_eq!(locked.len(), 1);
+}
+
+#[tokio::test]
+async fn test_admin_unlock_records_audit_event() {
+    let (service, audit_repo) = create_service();
+    let admin_id = Uuid::new_v4();
+
+    service.lock_account("phone_hash_1").await.unwrap();
+    service
+        .admin_unlock_account("phone_hash_1", admin_id, "10.0.0.1".to_string())
+        .await
+        .unwrap();
+
+    assert!(!service.is_locked("phone_hash_1").await.unwrap());
+    assert!(service.list_locked_accounts().await.unwrap().is_empty());
+
+    let log = audit_repo.get_last_log().unwrap();
+    assert_eq!(log.event_type, AuditEventType::AccountUnlocked);
+    assert_eq!(log.ip_address, "10.0.0.1");
+    let unlocked_by = log.event_data.unwrap()["unlocked_by"].as_str().unwrap().to_string();
+    assert_eq!(unlocked_by, admin_id.to_string());
+}
+
+#[tokio::test]
+async fn test_admin_unlock_rejects_unlocked_account() {
+    let (service, audit_repo) = create_service();
+
+    let result = service
+        .admin_unlock_account("phone_hash_1", Uuid::new_v4(), "10.0.0.1".to_string())
+        .await;
+
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    assert_eq!(audit_repo.count_by_event_type(AuditEventType::AccountUnlocked), 0);
+}
+
+#[tokio::test]
+async fn test_list_requires_audit_service() {
+    let service: AccountLockService<MockLockCache> =
+        AccountLockService::with_defaults(Arc::new(MockLockCache::default()));
+
+    let result = service.list_locked_accounts().await;
+
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
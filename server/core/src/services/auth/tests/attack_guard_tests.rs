@@ -0,0 +1,89 @@
+//! Tests for automatic attack mitigation
+
+use std::sync::Arc;
+
+use crate::domain::entities::audit::AuditEventType;
+use crate::services::audit::{AuditService, AuditServiceConfig};
+use crate::services::auth::{
+    AttackDetector, AttackGuard, AttackGuardConfig, GuardDecision, RecommendedAction,
+};
+
+use super::audit_integration_tests::MockAuditLogRepository;
+
+fn create_guard() -> (AttackGuard<MockAuditLogRepository>, Arc<MockAuditLogRepository>) {
+    let audit_repo = Arc::new(MockAuditLogRepository::new());
+    let detector = Arc::new(AttackDetector::with_defaults(audit_repo.clone()));
+    let audit_service = Arc::new(AuditService::new(
+        audit_repo.clone(),
+        AuditServiceConfig {
+            async_writes: false,
+            ..AuditServiceConfig::default()
+        },
+    ));
+    let guard = AttackGuard::with_audit(detector, audit_service, AttackGuardConfig::default());
+    (guard, audit_repo)
+}
+
+#[tokio::test]
+async fn test_no_attack_allows_requests() {
+    let (guard, audit_repo) = create_guard();
+    guard.record_failure("203.0.113.5", None);
+
+    assert!(guard.evaluate().await.unwrap().is_none());
+    assert_eq!(guard.check("203.0.113.5"), GuardDecision::Allow);
+    assert_eq!(audit_repo.count_by_event_type(AuditEventType::SuspiciousActivity), 0);
+}
+
+#[tokio::test]
+async fn test_subnet_attack_blocks_subnet() {
+    let (guard, audit_repo) = create_guard();
+    for ip in ["10.0.0.1", "10.0.0.2", "10.0.0.3"] {
+        guard.record_failure(ip, None);
+    }
+
+    let result = guard.evaluate().await.unwrap().unwrap();
+
+    assert!(matches!(result.recommended_action, RecommendedAction::BlockSubnet(_)));
+    assert!(matches!(guard.check("10.0.0.99"), GuardDecision::Block { .. }));
+    assert_eq!(guard.check("10.0.1.1"), GuardDecision::Allow);
+    assert_eq!(audit_repo.count_by_event_type(AuditEventType::SuspiciousActivity), 1);
+}
+
+#[tokio::test]
+async fn test_credential_stuffing_throttles_suspicious_ips() {
+    let (guard, _) = create_guard();
+    for ip in ["1.1.1.1", "2.2.2.2", "3.3.3.3", "4.4.4.4", "5.5.5.5"] {
+        guard.record_failure(ip, Some("****1234"));
+    }
+
+    let result = guard.evaluate().await.unwrap().unwrap();
+    assert!(matches!(result.recommended_action, RecommendedAction::EnableCaptcha));
+
+    let allowance = AttackGuardConfig::default().throttle_requests_per_minute;
+    for _ in 0..allowance {
+        assert_eq!(guard.check("1.1.1.1"), GuardDecision::Allow);
+    }
+    assert!(matches!(guard.check("1.1.1.1"), GuardDecision::Throttle { .. }));
+    assert_eq!(guard.check("6.6.6.6"), GuardDecision::Allow);
+}
+
+#[tokio::test]
+async fn test_mixed_attack_blocks_suspicious_ips() {
+    let (guard, _) = create_guard();
+    for ip in ["10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5"] {
+        guard.record_failure(ip, Some("****1234"));
+    }
+
+    let result = guard.evaluate().await.unwrap().unwrap();
+
+    assert!(matches!(result.recommended_action, RecommendedAction::SystemLockdown));
+    assert!(matches!(guard.check("10.0.0.1"), GuardDecision::Block { .. }));
+    assert_eq!(guard.check("10.0.0.99"), GuardDecision::Allow);
+}
+
+#[tokio::test]
+async fn test_unparseable_ip_is_allowed() {
+    let (guard, _) = create_guard();
+
+    assert_eq!(guard.check("unknown"), GuardDecision::Allow);
+}
@@ -6,7 +6,9 @@ use uuid::Uuid;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 
-use crate::domain::entities::audit::{AuditLog, AuditEventType};
+use re_shared::types::Pagination;
+
+use crate::domain::entities::audit::{AuditLog, AuditEventType, AuditLogFilter};
 use crate::domain::entities::user::User;
 use crate::errors::{DomainError};
 use crate::repositories::AuditLogRepository;
@@ -132,6 +134,14 @@ impl AuditLogRepository for MockAuditLogRepository {
         logs.retain(|log| !log.archived);
         Ok(before_count - logs.len())
     }
+
+    async fn archive_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_logs_before(&self, _before: DateTime<Utc>, _limit: usize) -> Result<usize, DomainError> {
+        Ok(0)
+    }
     
     async fn find_by_event_types(
         &self,
@@ -159,6 +169,14 @@ impl AuditLogRepository for MockAuditLogRepository {
         
         Ok(result)
     }
+
+    async fn query(
+        &self,
+        _filter: &AuditLogFilter,
+        _pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        Ok((Vec::new(), 0))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,115 @@
+//! Unit tests for geo-IP anomaly detection
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::services::auth::{
+    GeoAnomaly, GeoAnomalyConfig, GeoAnomalyDetector, GeoIpLookupTrait,
+};
+
+/// Resolves addresses from a fixed table
+pub struct StaticGeoIpLookup {
+    countries: HashMap<IpAddr, String>,
+}
+
+impl StaticGeoIpLookup {
+    pub fn new(entries: &[(&str, &str)]) -> Self {
+        Self {
+            countries: entries
+                .iter()
+                .map(|(ip, country)| (ip.parse().unwrap(), country.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl GeoIpLookupTrait for StaticGeoIpLookup {
+    async fn lookup_country(&self, ip: IpAddr) -> Result<Option<String>, String> {
+        Ok(self.countries.get(&ip).cloned())
+    }
+}
+
+struct FailingGeoIpLookup;
+
+#[async_trait]
+impl GeoIpLookupTrait for FailingGeoIpLookup {
+    async fn lookup_country(&self, _ip: IpAddr) -> Result<Option<String>, String> {
+        Err("database not loaded".to_string())
+    }
+}
+
+fn detector(require_step_up: bool) -> GeoAnomalyDetector {
+    let lookup = StaticGeoIpLookup::new(&[("1.128.0.1", "AU"), ("36.110.0.1", "CN"), ("81.2.69.1", "gb")]);
+    GeoAnomalyDetector::new(
+        Arc::new(lookup),
+        GeoAnomalyConfig {
+            require_step_up,
+            ..GeoAnomalyConfig::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn test_login_from_phone_country_is_not_anomalous() {
+    let assessment = detector(true).assess(Some("1.128.0.1"), "+61", Some("AU")).await;
+
+    assert_eq!(assessment.country.as_deref(), Some("AU"));
+    assert!(!assessment.is_anomalous());
+    assert!(!assessment.step_up_required);
+}
+
+#[tokio::test]
+async fn test_phone_country_mismatch_is_flagged() {
+    let assessment = detector(false).assess(Some("36.110.0.1"), "+61", None).await;
+
+    assert_eq!(
+        assessment.anomalies,
+        vec![GeoAnomaly::PhoneCountryMismatch {
+            phone_country_code: "+61".to_string(),
+            ip_country: "CN".to_string(),
+        }]
+    );
+    // Step-up is off, so the login is only flagged
+    assert!(!assessment.step_up_required);
+}
+
+#[tokio::test]
+async fn test_changed_login_country_is_flagged_and_requires_step_up() {
+    let assessment = detector(true).assess(Some("81.2.69.1"), "+44", Some("AU")).await;
+
+    // Lookup results are normalized to upper case
+    assert_eq!(assessment.country.as_deref(), Some("GB"));
+    assert_eq!(
+        assessment.anomalies,
+        vec![GeoAnomaly::LoginCountryChanged {
+            previous_country: "AU".to_string(),
+            ip_country: "GB".to_string(),
+        }]
+    );
+    assert!(assessment.step_up_required);
+}
+
+#[tokio::test]
+async fn test_unknown_calling_code_only_checks_previous_login() {
+    let assessment = detector(true).assess(Some("1.128.0.1"), "+64", None).await;
+
+    assert!(!assessment.is_anomalous());
+}
+
+#[tokio::test]
+async fn test_unresolvable_addresses_are_not_anomalous() {
+    let detector = detector(true);
+
+    for ip in [None, Some("not-an-ip"), Some("10.0.0.1")] {
+        let assessment = detector.assess(ip, "+61", Some("CN")).await;
+        assert!(!assessment.is_anomalous());
+        assert_eq!(assessment.country, None);
+    }
+
+    let failing = GeoAnomalyDetector::with_defaults(Arc::new(FailingGeoIpLookup));
+    let assessment = failing.assess(Some("36.110.0.1"), "+61", Some("AU")).await;
+    assert!(!assessment.is_anomalous());
+}
@@ -0,0 +1,185 @@
+//! Tests for IP allowlist and denylist enforcement
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::errors::DomainError;
+use crate::services::auth::{
+    IpAccessControlConfig, IpAccessControlService, IpAccessDecision, IpAccessEntry, IpAccessList,
+    IpAccessStoreTrait,
+};
+
+#[derive(Default)]
+struct MockIpAccessStore {
+    entries: Mutex<HashMap<(IpAccessList, String), IpAccessEntry>>,
+}
+
+#[async_trait]
+impl IpAccessStoreTrait for MockIpAccessStore {
+    async fn add_entry(&self, list: IpAccessList, entry: &IpAccessEntry) -> Result<(), String> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert((list, entry.cidr.clone()), entry.clone());
+        Ok(())
+    }
+
+    async fn remove_entry(&self, list: IpAccessList, cidr: &str) -> Result<bool, String> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .remove(&(list, cidr.to_string()))
+            .is_some())
+    }
+
+    async fn list_entries(&self, list: IpAccessList) -> Result<Vec<IpAccessEntry>, String> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((entry_list, _), _)| *entry_list == list)
+            .map(|(_, entry)| entry.clone())
+            .collect())
+    }
+}
+
+fn create_service(
+    config: IpAccessControlConfig,
+) -> (IpAccessControlService<MockIpAccessStore>, Arc<MockIpAccessStore>) {
+    let store = Arc::new(MockIpAccessStore::default());
+    (IpAccessControlService::new(store.clone(), config), store)
+}
+
+#[tokio::test]
+async fn test_denylisted_range_is_rejected() {
+    let (service, _) = create_service(IpAccessControlConfig::default());
+
+    let entry = service
+        .add_entry(IpAccessList::Deny, "203.0.113.7/24", "SMS pumping", Some(Uuid::new_v4()), None)
+        .await
+        .unwrap();
+    assert_eq!(entry.cidr, "203.0.113.0/24");
+
+    assert_eq!(
+        service.check("203.0.113.200"),
+        IpAccessDecision::Denied { reason: "SMS pumping".to_string() }
+    );
+    assert_eq!(service.check("198.51.100.1"), IpAccessDecision::Allowed);
+}
+
+#[tokio::test]
+async fn test_allowlist_takes_precedence_over_denylist() {
+    let (service, _) = create_service(IpAccessControlConfig::default());
+
+    service
+        .add_entry(IpAccessList::Deny, "10.0.0.0/8", "Abusive range", None, None)
+        .await
+        .unwrap();
+    service
+        .add_entry(IpAccessList::Allow, "10.1.2.3", "Office", None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(service.check("10.1.2.3"), IpAccessDecision::Allowlisted);
+    assert!(!service.check("10.1.2.4").is_allowed());
+}
+
+#[tokio::test]
+async fn test_allowlist_only_mode_rejects_unknown_ips() {
+    let config = IpAccessControlConfig {
+        allowlist_only: true,
+        ..Default::default()
+    };
+    let (service, _) = create_service(config);
+
+    service
+        .add_entry(IpAccessList::Allow, "2001:db8::/48", "Partner network", None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(service.check("2001:db8::1"), IpAccessDecision::Allowlisted);
+    assert!(!service.check("192.0.2.1").is_allowed());
+}
+
+#[tokio::test]
+async fn test_add_entry_validation() {
+    let (service, _) = create_service(IpAccessControlConfig::default());
+
+    let invalid = service
+        .add_entry(IpAccessList::Deny, "not-an-ip", "Reason", None, None)
+        .await;
+    assert!(matches!(invalid, Err(DomainError::Validation { .. })));
+
+    let too_broad = service
+        .add_entry(IpAccessList::Deny, "0.0.0.0/0", "Reason", None, None)
+        .await;
+    assert!(matches!(too_broad, Err(DomainError::Validation { .. })));
+
+    let no_reason = service
+        .add_entry(IpAccessList::Deny, "192.0.2.1", "  ", None, None)
+        .await;
+    assert!(matches!(no_reason, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_remove_entry() {
+    let (service, _) = create_service(IpAccessControlConfig::default());
+
+    service
+        .add_entry(IpAccessList::Deny, "192.0.2.1", "Abuse", None, None)
+        .await
+        .unwrap();
+    service.remove_entry(IpAccessList::Deny, "192.0.2.1/32").await.unwrap();
+
+    assert_eq!(service.check("192.0.2.1"), IpAccessDecision::Allowed);
+
+    let missing = service.remove_entry(IpAccessList::Deny, "192.0.2.1").await;
+    assert!(matches!(missing, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_refresh_loads_shared_entries_and_purges_expired() {
+    let (service, store) = create_service(IpAccessControlConfig::default());
+
+    // Entries added by another instance
+    let now = Utc::now();
+    store
+        .add_entry(
+            IpAccessList::Deny,
+            &IpAccessEntry {
+                cidr: "198.51.100.0/24".to_string(),
+                reason: "Added elsewhere".to_string(),
+                created_by: None,
+                created_at: now,
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    store
+        .add_entry(
+            IpAccessList::Deny,
+            &IpAccessEntry {
+                cidr: "192.0.2.0/24".to_string(),
+                reason: "Expired".to_string(),
+                created_by: None,
+                created_at: now - Duration::hours(2),
+                expires_at: Some(now - Duration::hours(1)),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(service.check("198.51.100.9"), IpAccessDecision::Allowed);
+
+    service.refresh().await.unwrap();
+
+    assert!(!service.check("198.51.100.9").is_allowed());
+    assert_eq!(service.check("192.0.2.9"), IpAccessDecision::Allowed);
+    assert_eq!(store.list_entries(IpAccessList::Deny).await.unwrap().len(), 1);
+}
@@ -9,4 +9,16 @@ mod rate_limiter_tests;
 #[cfg(test)]
 mod audit_integration_tests;
 #[cfg(test)]
-mod delay_response_tests;
\ No newline at end of file
+mod delay_response_tests;
+#[cfg(test)]
+mod account_lock_tests;
+#[cfg(test)]
+mod attack_guard_tests;
+#[cfg(test)]
+pub(crate) mod geo_anomaly_tests;
+#[cfg(test)]
+mod ip_access_control_tests;
+#[cfg(test)]
+mod proof_of_work_tests;
+#[cfg(test)]
+mod unit_of_work_tests;
@@ -0,0 +1,103 @@
+//! Tests for proof-of-work challenges
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use re_shared::config::rate_limit::ProofOfWorkConfig;
+
+use crate::services::auth::{meets_difficulty, ProofOfWorkDecision, ProofOfWorkService, ProofOfWorkStoreTrait};
+
+#[derive(Default)]
+struct MockProofOfWorkStore {
+    challenges: Mutex<HashMap<String, u8>>,
+}
+
+#[async_trait]
+impl ProofOfWorkStoreTrait for MockProofOfWorkStore {
+    async fn save(&self, nonce: &str, difficulty: u8, _ttl_seconds: u64) -> Result<(), String> {
+        self.challenges.lock().unwrap().insert(nonce.to_string(), difficulty);
+        Ok(())
+    }
+
+    async fn take(&self, nonce: &str) -> Result<Option<u8>, String> {
+        Ok(self.challenges.lock().unwrap().remove(nonce))
+    }
+}
+
+fn create_service(difficulty: u8) -> ProofOfWorkService<MockProofOfWorkStore> {
+    let config = ProofOfWorkConfig {
+        enabled: true,
+        difficulty,
+        ..Default::default()
+    };
+    ProofOfWorkService::new(Arc::new(MockProofOfWorkStore::default()), config).unwrap()
+}
+
+fn solve(nonce: &str, difficulty: u8) -> String {
+    (0u64..)
+        .map(|candidate| candidate.to_string())
+        .find(|candidate| meets_difficulty(nonce, candidate, difficulty))
+        .unwrap()
+}
+
+#[test]
+fn test_meets_difficulty_counts_leading_zero_bits() {
+    // SHA-256("abc:181") starts with 0x0036, ten zero bits
+    assert!(meets_difficulty("abc", "181", 0));
+    assert!(meets_difficulty("abc", "181", 10));
+    assert!(!meets_difficulty("abc", "181", 11));
+    assert!(!meets_difficulty("abc", "0", 2));
+}
+
+#[tokio::test]
+async fn test_valid_solution_is_accepted_once() {
+    let service = create_service(8);
+    let challenge = service.issue_challenge().await.unwrap();
+    assert_eq!(challenge.difficulty, 8);
+    assert_eq!(challenge.nonce.len(), 32);
+
+    let solution = solve(&challenge.nonce, challenge.difficulty);
+    assert_eq!(service.verify(&challenge.nonce, &solution).await.unwrap(), ProofOfWorkDecision::Accepted);
+    assert_eq!(
+        service.verify(&challenge.nonce, &solution).await.unwrap(),
+        ProofOfWorkDecision::UnknownChallenge
+    );
+}
+
+#[tokio::test]
+async fn test_insufficient_solution_uses_up_challenge() {
+    let service = create_service(12);
+    let challenge = service.issue_challenge().await.unwrap();
+
+    let wrong = (0u64..)
+        .map(|candidate| candidate.to_string())
+        .find(|candidate| !meets_difficulty(&challenge.nonce, candidate, 12))
+        .unwrap();
+    assert_eq!(service.verify(&challenge.nonce, &wrong).await.unwrap(), ProofOfWorkDecision::InsufficientWork);
+
+    let solution = solve(&challenge.nonce, 12);
+    assert_eq!(
+        service.verify(&challenge.nonce, &solution).await.unwrap(),
+        ProofOfWorkDecision::UnknownChallenge
+    );
+}
+
+#[tokio::test]
+async fn test_disabled_service_requires_nothing() {
+    let service = ProofOfWorkService::new(Arc::new(MockProofOfWorkStore::default()), ProofOfWorkConfig::default()).unwrap();
+
+    let decision = service.verify("unknown", "").await.unwrap();
+    assert_eq!(decision, ProofOfWorkDecision::NotRequired);
+    assert!(decision.is_allowed());
+}
+
+#[test]
+fn test_excessive_difficulty_is_rejected() {
+    let config = ProofOfWorkConfig {
+        enabled: true,
+        difficulty: 40,
+        ..Default::default()
+    };
+    assert!(ProofOfWorkService::new(Arc::new(MockProofOfWorkStore::default()), config).is_err());
+}
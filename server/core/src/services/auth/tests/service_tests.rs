@@ -5,17 +5,26 @@ use std::sync::Mutex;
 use uuid::Uuid;
 use async_trait::async_trait;
 
+use crate::domain::entities::attribution::RegistrationAttribution;
 use crate::domain::entities::user::{User, UserType};
 use crate::domain::entities::token::RefreshToken;
 use crate::errors::{AuthError, DomainError};
 use crate::repositories::{UserRepository, TokenRepository};
 use crate::repositories::audit::NoOpAuditLogRepository;
-use crate::services::auth::{AuthService, AuthServiceConfig};
+use crate::services::auth::{
+    AuthService, AuthServiceConfig, GeoAnomalyConfig, GeoAnomalyDetector, SuspiciousLogin,
+    PhoneHasher, SuspiciousLoginNotifierTrait, SuspiciousLoginReason, TrustedTesterConfig,
+};
 use crate::services::auth::phone_utils::hash_phone;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookConfig, LifecycleHookService, LifecycleHookTrait};
 use crate::services::token::{TokenService, TokenServiceConfig};
-use crate::services::verification::{VerificationService, VerificationServiceConfig};
+use crate::services::verification::{
+    VerificationService, VerificationServiceConfig, FIXED_CODE_MESSAGE_ID,
+};
 use jsonwebtoken::Algorithm;
+use re_shared::config::auth::PhoneHashConfig;
 
+use super::geo_anomaly_tests::StaticGeoIpLookup;
 use super::mocks::*;
 
 /// Mock implementation of TokenRepository for testing
@@ -249,6 +258,116 @@ async fn test_send_verification_code_rate_limit() {
     }
 }
 
+/// Peppered hasher the trusted tester allowlists are hashed with
+fn tester_phone_hasher() -> PhoneHasher {
+    PhoneHasher::new(&PhoneHashConfig {
+        peppers: vec!["tester-pepper-0123456789abcdef012345".into()],
+        accept_unpeppered: false,
+    })
+}
+
+#[test]
+fn test_trusted_testers_match_peppered_hashes_only() {
+    let tester = "+61412345678";
+    let phone_hasher = tester_phone_hasher();
+
+    assert!(TrustedTesterConfig::new([phone_hasher.hash(tester)]).is_trusted(tester, &phone_hasher));
+    assert!(!TrustedTesterConfig::new([hash_phone(tester)]).is_trusted(tester, &phone_hasher));
+    assert!(!TrustedTesterConfig::default().is_trusted(tester, &phone_hasher));
+}
+
+#[tokio::test]
+async fn test_send_verification_code_trusted_tester_bypasses_rate_limit() {
+    let user_repo = Arc::new(MockUserRepository::new());
+    let sms_service = Arc::new(MockSmsService);
+    let cache_service = Arc::new(MockCacheService::new_success());
+    let verification_service = Arc::new(VerificationService::new(
+        sms_service,
+        cache_service,
+        VerificationServiceConfig::default(),
+    ));
+    let rate_limiter = Arc::new(MockRateLimiter::new(3));
+    let token_repo = MockTokenRepository::new();
+    let token_service = create_test_token_service(token_repo);
+    let tester = "+61412345678";
+    let phone_hasher = tester_phone_hasher();
+    let config = AuthServiceConfig {
+        trusted_testers: TrustedTesterConfig::new([phone_hasher.hash(tester).to_uppercase()]),
+        phone_hasher,
+        ..AuthServiceConfig::default()
+    };
+
+    let auth_service = AuthService::<MockUserRepository, MockSmsService, MockCacheService, MockRateLimiter, MockTokenRepository, NoOpAuditLogRepository>::new(
+        user_repo,
+        verification_service,
+        rate_limiter.clone(),
+        token_service,
+        config,
+    );
+
+    for _ in 0..5 {
+        let result = auth_service
+            .send_verification_code(tester, Some("192.168.1.1".to_string()), None)
+            .await;
+        assert!(result.unwrap().message_id.starts_with("mock-message"));
+    }
+    assert!(rate_limiter.phone_counters.lock().unwrap().is_empty());
+    assert!(rate_limiter.ip_counters.lock().unwrap().is_empty());
+
+    // Other phones are still limited
+    let phone = "+8613812345678";
+    for _ in 0..3 {
+        assert!(auth_service.send_verification_code(phone, None, None).await.is_ok());
+    }
+    assert!(matches!(
+        auth_service.send_verification_code(phone, None, None).await,
+        Err(DomainError::Auth(AuthError::RateLimitExceeded { .. }))
+    ));
+}
+
+#[tokio::test]
+async fn test_send_verification_code_trusted_tester_fixed_code() {
+    let user_repo = Arc::new(MockUserRepository::new());
+    let sms_service = Arc::new(MockSmsService);
+    let cache_service = Arc::new(MockCacheService::new_success());
+    let verification_service = Arc::new(VerificationService::new(
+        sms_service,
+        cache_service,
+        VerificationServiceConfig::default(),
+    ));
+    let rate_limiter = Arc::new(MockRateLimiter::new(3));
+    let token_repo = MockTokenRepository::new();
+    let token_service = create_test_token_service(token_repo);
+    let tester = "+61412345678";
+    let phone_hasher = tester_phone_hasher();
+    let mut trusted_testers = TrustedTesterConfig::new([phone_hasher.hash(tester)]);
+    trusted_testers.fixed_code = Some("246810".to_string());
+    let config = AuthServiceConfig {
+        trusted_testers,
+        phone_hasher,
+        ..AuthServiceConfig::default()
+    };
+
+    let auth_service = AuthService::<MockUserRepository, MockSmsService, MockCacheService, MockRateLimiter, MockTokenRepository, NoOpAuditLogRepository>::new(
+        user_repo,
+        verification_service,
+        rate_limiter,
+        token_service,
+        config,
+    );
+
+    let send_result = auth_service.send_verification_code(tester, None, None).await.unwrap();
+    assert_eq!(send_result.message_id, FIXED_CODE_MESSAGE_ID);
+    assert_eq!(send_result.verification_code.code, "246810");
+
+    // Phones off the allowlist still get a random code by SMS
+    let send_result = auth_service
+        .send_verification_code("+8613812345678", None, None)
+        .await
+        .unwrap();
+    assert!(send_result.message_id.starts_with("mock-message"));
+}
+
 #[tokio::test]
 async fn test_verify_code_success() {
     let user_repo = Arc::new(MockUserRepository::new());
@@ -283,6 +402,218 @@ async fn test_verify_code_success() {
     assert_eq!(auth_response.user_type, None);
 }
 
+#[tokio::test]
+async fn test_verify_code_flags_login_from_new_country() {
+    let mut existing_user = User::new(hash_phone("412345678"), "+61".to_string());
+    existing_user.verify();
+    existing_user.last_login_country = Some("AU".to_string());
+    let user_id = existing_user.id;
+
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user));
+    let sms_service = Arc::new(MockSmsService);
+    let cache_service = Arc::new(MockCacheService::new_success());
+    let verification_service = Arc::new(VerificationService::new(
+        sms_service,
+        cache_service,
+        VerificationServiceConfig::default(),
+    ));
+    let rate_limiter = Arc::new(MockRateLimiter::new(3));
+    let token_service = create_test_token_service(MockTokenRepository::new());
+    let detector = GeoAnomalyDetector::new(
+        Arc::new(StaticGeoIpLookup::new(&[("36.110.0.1", "CN")])),
+        GeoAnomalyConfig {
+            require_step_up: true,
+            ..GeoAnomalyConfig::default()
+        },
+    );
+
+    let auth_service = AuthService::<MockUserRepository, MockSmsService, MockCacheService, MockRateLimiter, MockTokenRepository, NoOpAuditLogRepository>::new(
+        user_repo.clone(),
+        verification_service,
+        rate_limiter,
+        token_service,
+        AuthServiceConfig::default(),
+    )
+    .with_geo_anomaly_detector(Arc::new(detector));
+
+    let auth_response = auth_service
+        .verify_code("+61412345678", "123456", Some("36.110.0.1".to_string()), None, None)
+        .await
+        .unwrap();
+
+    assert!(auth_response.requires_step_up);
+    let user = user_repo.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(user.last_login_country.as_deref(), Some("CN"));
+}
+
+fn create_peppered_auth_service(
+    user_repo: Arc<MockUserRepository>,
+    phone_hash: PhoneHashConfig,
+) -> AuthService<MockUserRepository, MockSmsService, MockCacheService, MockRateLimiter, MockTokenRepository, NoOpAuditLogRepository> {
+    let verification_service = Arc::new(VerificationService::new(
+        Arc::new(MockSmsService),
+        Arc::new(MockCacheService::new_success()),
+        VerificationServiceConfig::default(),
+    ));
+    let config = AuthServiceConfig {
+        phone_hasher: PhoneHasher::new(&phone_hash),
+        ..AuthServiceConfig::default()
+    };
+
+    AuthService::new(
+        user_repo,
+        verification_service,
+        Arc::new(MockRateLimiter::new(3)),
+        create_test_token_service(MockTokenRepository::new()),
+        config,
+    )
+}
+
+#[tokio::test]
+async fn test_verify_code_rehashes_user_stored_under_previous_pepper() {
+    let old = PhoneHashConfig {
+        peppers: vec!["old-pepper-0123456789abcdef0123456789".into()],
+        ..PhoneHashConfig::default()
+    };
+    let current = PhoneHashConfig {
+        peppers: vec![
+            "old-pepper-0123456789abcdef0123456789".into(),
+            "new-pepper-0123456789abcdef0123456789".into(),
+        ],
+        accept_unpeppered: false,
+    };
+    let mut existing_user = User::new(PhoneHasher::new(&old).hash("412345678"), "+61".to_string());
+    existing_user.phone_hash_version = 1;
+    existing_user.verify();
+    let user_id = existing_user.id;
+
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user));
+    let auth_service = create_peppered_auth_service(user_repo.clone(), current.clone());
+
+    auth_service
+        .verify_code("+61412345678", "123456", None, None, None)
+        .await
+        .unwrap();
+
+    let user = user_repo.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(user.phone_hash, PhoneHasher::new(&current).hash("412345678"));
+    assert_eq!(user.phone_hash_version, 2);
+}
+
+#[tokio::test]
+async fn test_verify_code_finds_unpeppered_user_only_when_accepted() {
+    let existing_user = User::new(hash_phone("412345678"), "+61".to_string());
+    let user_id = existing_user.id;
+    let peppered = PhoneHashConfig {
+        peppers: vec!["pepper-0123456789abcdef0123456789abcd".into()],
+        ..PhoneHashConfig::default()
+    };
+
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user.clone()));
+    let auth_service = create_peppered_auth_service(user_repo.clone(), peppered.clone());
+    auth_service
+        .verify_code("+61412345678", "123456", None, None, None)
+        .await
+        .unwrap();
+    let user = user_repo.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(user.phone_hash, PhoneHasher::new(&peppered).hash("412345678"));
+
+    // Without the fallback the old row is not found and a new user registers
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user));
+    let auth_service = create_peppered_auth_service(
+        user_repo.clone(),
+        PhoneHashConfig { accept_unpeppered: false, ..peppered.clone() },
+    );
+    auth_service
+        .verify_code("+61412345678", "123456", None, None, None)
+        .await
+        .unwrap();
+    let registered = user_repo
+        .find_by_phone(&PhoneHasher::new(&peppered).hash("412345678"), "+61")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(registered.id, user_id);
+}
+
+/// Lifecycle hook recording the events published to it
+#[derive(Default)]
+pub(super) struct PublishedEvents {
+    pub(super) events: Mutex<Vec<LifecycleEvent>>,
+}
+
+#[async_trait]
+impl LifecycleHookTrait for PublishedEvents {
+    fn name(&self) -> &str {
+        "published_events"
+    }
+
+    fn handles(&self, event: &LifecycleEvent) -> bool {
+        self.events.lock().unwrap().push(event.clone());
+        true
+    }
+
+    async fn handle(&self, _event: &LifecycleEvent) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_verify_code_publishes_registration_of_new_users_only() {
+    let existing_user = User::new(hash_phone("412345678"), "+61".to_string());
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user));
+    let published = Arc::new(PublishedEvents::default());
+    let hooks = Arc::new(LifecycleHookService::new(LifecycleHookConfig::default()).with_hook(published.clone()));
+    let auth_service = create_peppered_auth_service(user_repo, PhoneHashConfig::default())
+        .with_lifecycle_hooks(hooks.clone());
+
+    auth_service.verify_code("+61412345678", "123456", None, None, None).await.unwrap();
+    assert_eq!(hooks.metrics().queued, 0);
+
+    auth_service.verify_code("+61412345679", "123456", None, None, None).await.unwrap();
+    assert_eq!(hooks.metrics().queued, 1);
+    assert!(published.events.lock().unwrap().iter().all(|event| matches!(
+        event,
+        LifecycleEvent::UserRegistered { attribution: None, .. }
+    )));
+}
+
+#[tokio::test]
+async fn test_verify_code_passes_on_attribution_only_with_consent() {
+    let published = Arc::new(PublishedEvents::default());
+    let hooks = Arc::new(LifecycleHookService::new(LifecycleHookConfig::default()).with_hook(published.clone()));
+    let auth_service = create_peppered_auth_service(Arc::new(MockUserRepository::new()), PhoneHashConfig::default())
+        .with_lifecycle_hooks(hooks);
+    let attribution = |marketing_consent| RegistrationAttribution {
+        utm_source: Some("Google".to_string()),
+        marketing_consent,
+        ..Default::default()
+    };
+
+    auth_service
+        .verify_code_with_attribution("+61412345678", "123456", None, None, None, Some(attribution(false)))
+        .await
+        .unwrap();
+    auth_service
+        .verify_code_with_attribution("+61412345679", "123456", None, None, None, Some(attribution(true)))
+        .await
+        .unwrap();
+
+    let attributions: Vec<_> = published
+        .events
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|event| match event {
+            LifecycleEvent::UserRegistered { attribution, .. } => attribution.clone(),
+            _ => panic!("Unexpected event: {:?}", event),
+        })
+        .collect();
+    assert_eq!(attributions.len(), 2);
+    assert_eq!(attributions[0], None);
+    assert_eq!(attributions[1].as_ref().and_then(|a| a.utm_source.as_deref()), Some("google"));
+}
+
 #[tokio::test]
 async fn test_verify_code_invalid_phone() {
     let user_repo = Arc::new(MockUserRepository::new());
@@ -703,3 +1034,116 @@ async fn test_logout_success() {
     // Since we're using a mock, the revoke_tokens method is called which sets tokens as revoked
     // The next verification attempt should fail (mock behavior)
 }
+
+/// Notifier that records the logins it was told about
+#[derive(Default)]
+struct RecordingNotifier {
+    logins: Mutex<Vec<SuspiciousLogin>>,
+}
+
+#[async_trait]
+impl SuspiciousLoginNotifierTrait for RecordingNotifier {
+    async fn notify(&self, _phone: &str, login: &SuspiciousLogin) -> Result<(), String> {
+        self.logins.lock().unwrap().push(login.clone());
+        Ok(())
+    }
+}
+
+/// Auth service for an existing user who signed in before on `device-a`
+fn create_service_with_known_device(
+    notifier: Arc<RecordingNotifier>,
+) -> (
+    AuthService<MockUserRepository, MockSmsService, MockCacheService, MockRateLimiter, MockTokenRepository, NoOpAuditLogRepository>,
+    Arc<Mutex<Vec<RefreshToken>>>,
+    Uuid,
+) {
+    let mut existing_user = User::new(hash_phone("412345678"), "+61".to_string());
+    existing_user.verify();
+    let user_id = existing_user.id;
+
+    let token_repo = MockTokenRepository::new();
+    let tokens = token_repo.tokens.clone();
+    tokens.lock().unwrap().push(RefreshToken::new_with_metadata(
+        user_id,
+        "previous-hash".to_string(),
+        Some("previous-family".to_string()),
+        Some("device-a".to_string()),
+        None,
+    ));
+
+    let verification_service = Arc::new(VerificationService::new(
+        Arc::new(MockSmsService),
+        Arc::new(MockCacheService::new_success()),
+        VerificationServiceConfig::default(),
+    ));
+    let auth_service = AuthService::new(
+        Arc::new(MockUserRepository::with_existing_user(existing_user)),
+        verification_service,
+        Arc::new(MockRateLimiter::new(3)),
+        create_test_token_service(token_repo),
+        AuthServiceConfig::default(),
+    )
+    .with_suspicious_login_notifier(notifier);
+
+    (auth_service, tokens, user_id)
+}
+
+#[tokio::test]
+async fn test_verify_code_notifies_login_from_new_device() {
+    let notifier = Arc::new(RecordingNotifier::default());
+    let (auth_service, tokens, user_id) = create_service_with_known_device(notifier.clone());
+
+    auth_service
+        .verify_code("+61412345678", "123456", None, None, Some("device-b".to_string()))
+        .await
+        .unwrap();
+
+    let logins = notifier.logins.lock().unwrap();
+    assert_eq!(logins.len(), 1);
+    assert_eq!(logins[0].user_id, user_id);
+    assert_eq!(logins[0].reasons, vec![SuspiciousLoginReason::NewDevice]);
+
+    // The reported session is the one created for the new device
+    let tokens = tokens.lock().unwrap();
+    let session = tokens
+        .iter()
+        .find(|token| token.device_fingerprint.as_deref() == Some("device-b"))
+        .unwrap();
+    assert_eq!(session.token_family.as_deref(), Some(logins[0].session_id.as_str()));
+}
+
+#[tokio::test]
+async fn test_verify_code_does_not_notify_known_device() {
+    let notifier = Arc::new(RecordingNotifier::default());
+    let (auth_service, _tokens, _user_id) = create_service_with_known_device(notifier.clone());
+
+    auth_service
+        .verify_code("+61412345678", "123456", None, None, Some("device-a".to_string()))
+        .await
+        .unwrap();
+
+    assert!(notifier.logins.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_report_suspicious_login_revokes_session() {
+    let notifier = Arc::new(RecordingNotifier::default());
+    let (auth_service, tokens, user_id) = create_service_with_known_device(notifier);
+
+    // Another user cannot revoke the session
+    let result = auth_service
+        .report_suspicious_login(Uuid::new_v4(), "previous-family", None, None)
+        .await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+
+    auth_service
+        .report_suspicious_login(user_id, "previous-family", None, None)
+        .await
+        .unwrap();
+    assert!(tokens.lock().unwrap().iter().all(|token| token.is_revoked));
+
+    let result = auth_service
+        .report_suspicious_login(user_id, "unknown-family", None, None)
+        .await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
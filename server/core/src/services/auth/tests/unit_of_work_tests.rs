@@ -0,0 +1,245 @@
+//! Tests for saving a login through a unit of work
+
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+
+use crate::domain::entities::audit::{AuditEventType, AuditLog};
+use crate::domain::entities::token::RefreshToken;
+use crate::domain::entities::user::User;
+use crate::errors::{DomainError, TokenError};
+use crate::repositories::audit::MockAuditLogRepository;
+use crate::repositories::{UnitOfWork, UnitOfWorkTransaction};
+use crate::services::audit::{AuditService, AuditServiceConfig};
+use crate::services::auth::phone_utils::hash_phone;
+use crate::services::auth::{AuthService, AuthServiceConfig};
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookConfig, LifecycleHookService};
+use crate::services::token::{TokenService, TokenServiceConfig};
+use crate::services::verification::{VerificationService, VerificationServiceConfig};
+use jsonwebtoken::Algorithm;
+
+use super::audit_integration_tests::MockTokenRepository;
+use super::mocks::*;
+use super::service_tests::PublishedEvents;
+
+/// Writes made visible by committed transactions
+#[derive(Default)]
+struct Committed {
+    tokens: Vec<RefreshToken>,
+    audit_logs: Vec<AuditLog>,
+    rollbacks: usize,
+}
+
+/// In-memory unit of work sharing its users with a `MockUserRepository`
+struct MemoryUnitOfWork {
+    users: Arc<Mutex<Vec<User>>>,
+    committed: Arc<Mutex<Committed>>,
+    fail_token_save: bool,
+    /// Country of each transaction begun for a country
+    countries: Mutex<Vec<String>>,
+}
+
+impl MemoryUnitOfWork {
+    fn new(users: &MockUserRepository, fail_token_save: bool) -> Self {
+        Self {
+            users: users.users.clone(),
+            committed: Arc::new(Mutex::new(Committed::default())),
+            fail_token_save,
+            countries: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for MemoryUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        Ok(Box::new(MemoryTransaction {
+            users: self.users.clone(),
+            committed: self.committed.clone(),
+            fail_token_save: self.fail_token_save,
+            pending_users: Vec::new(),
+            pending_tokens: Vec::new(),
+            pending_logs: Vec::new(),
+        }))
+    }
+
+    async fn begin_for_country(&self, country_code: &str) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        self.countries.lock().unwrap().push(country_code.to_string());
+        self.begin().await
+    }
+}
+
+struct MemoryTransaction {
+    users: Arc<Mutex<Vec<User>>>,
+    committed: Arc<Mutex<Committed>>,
+    fail_token_save: bool,
+    pending_users: Vec<User>,
+    pending_tokens: Vec<RefreshToken>,
+    pending_logs: Vec<AuditLog>,
+}
+
+#[async_trait]
+impl UnitOfWorkTransaction for MemoryTransaction {
+    async fn create_user(&mut self, user: User) -> Result<User, DomainError> {
+        self.pending_users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User, DomainError> {
+        self.pending_users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn save_refresh_token(&mut self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        if self.fail_token_save {
+            return Err(DomainError::Internal { message: "Connection lost".to_string() });
+        }
+        self.pending_tokens.push(token.clone());
+        Ok(token)
+    }
+
+    async fn create_audit_log(&mut self, audit_log: &AuditLog) -> Result<(), DomainError> {
+        self.pending_logs.push(audit_log.clone());
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        let mut users = self.users.lock().unwrap();
+        for user in self.pending_users {
+            users.retain(|u| u.id != user.id);
+            users.push(user);
+        }
+
+        let mut committed = self.committed.lock().unwrap();
+        committed.tokens.extend(self.pending_tokens);
+        committed.audit_logs.extend(self.pending_logs);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.committed.lock().unwrap().rollbacks += 1;
+        Ok(())
+    }
+}
+
+type TestAuthService = AuthService<
+    MockUserRepository,
+    MockSmsService,
+    MockCacheService,
+    MockRateLimiter,
+    MockTokenRepository,
+    MockAuditLogRepository,
+>;
+
+fn create_auth_service(
+    user_repo: Arc<MockUserRepository>,
+    audit_repo: Arc<MockAuditLogRepository>,
+    unit_of_work: Arc<MemoryUnitOfWork>,
+) -> TestAuthService {
+    let verification_service = Arc::new(VerificationService::new(
+        Arc::new(MockSmsService),
+        Arc::new(MockCacheService::new_success()),
+        VerificationServiceConfig::default(),
+    ));
+    let token_config = TokenServiceConfig {
+        algorithm: Algorithm::HS256,
+        rs256_config: None,
+        ..TokenServiceConfig::default()
+    };
+    let audit_config = AuditServiceConfig {
+        async_writes: false,
+        ..AuditServiceConfig::default()
+    };
+
+    AuthService::with_audit(
+        user_repo,
+        verification_service,
+        Arc::new(MockRateLimiter::new(3)),
+        Arc::new(TokenService::new(MockTokenRepository, token_config).unwrap()),
+        Arc::new(AuditService::new(audit_repo, audit_config)),
+        AuthServiceConfig::default(),
+    )
+    .with_unit_of_work(unit_of_work)
+}
+
+#[tokio::test]
+async fn test_verify_code_commits_new_user_with_token_and_audit_log() {
+    let user_repo = Arc::new(MockUserRepository::new());
+    let audit_repo = Arc::new(MockAuditLogRepository::new());
+    let unit_of_work = Arc::new(MemoryUnitOfWork::new(&user_repo, false));
+    let published = Arc::new(PublishedEvents::default());
+    let hooks = Arc::new(LifecycleHookService::new(LifecycleHookConfig::default()).with_hook(published.clone()));
+    let auth_service = create_auth_service(user_repo.clone(), audit_repo.clone(), unit_of_work.clone())
+        .with_lifecycle_hooks(hooks);
+
+    auth_service.verify_code("+61412345678", "123456", None, None, None).await.unwrap();
+
+    let users = user_repo.users.lock().unwrap().clone();
+    assert_eq!(users.len(), 1);
+    assert!(users[0].is_verified);
+    assert!(users[0].last_login_at.is_some());
+
+    let committed = unit_of_work.committed.lock().unwrap();
+    assert_eq!(committed.tokens.len(), 1);
+    assert_eq!(committed.tokens[0].user_id, users[0].id);
+    assert_eq!(committed.audit_logs.len(), 1);
+    assert_eq!(committed.audit_logs[0].event_type, AuditEventType::LoginSuccess);
+    assert_eq!(committed.audit_logs[0].token_id, Some(committed.tokens[0].id));
+    // The transaction is opened where the user's country is stored
+    assert_eq!(*unit_of_work.countries.lock().unwrap(), vec!["+61".to_string()]);
+
+    assert!(matches!(
+        published.events.lock().unwrap().as_slice(),
+        [LifecycleEvent::UserRegistered { user_id, .. }] if *user_id == users[0].id
+    ));
+
+    // The login entry is not written a second time outside the transaction
+    assert!(audit_repo
+        .get_all_logs()
+        .iter()
+        .all(|log| log.event_type != AuditEventType::LoginSuccess));
+}
+
+#[tokio::test]
+async fn test_verify_code_updates_existing_user_in_transaction() {
+    let existing_user = User::new(hash_phone("412345678"), "+61".to_string());
+    let user_id = existing_user.id;
+    let user_repo = Arc::new(MockUserRepository::with_existing_user(existing_user));
+    let unit_of_work = Arc::new(MemoryUnitOfWork::new(&user_repo, false));
+    let auth_service = create_auth_service(
+        user_repo.clone(),
+        Arc::new(MockAuditLogRepository::new()),
+        unit_of_work.clone(),
+    );
+
+    auth_service.verify_code("+61412345678", "123456", None, None, None).await.unwrap();
+
+    let users = user_repo.users.lock().unwrap().clone();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id, user_id);
+    assert!(users[0].last_login_at.is_some());
+    assert_eq!(unit_of_work.committed.lock().unwrap().tokens[0].user_id, user_id);
+}
+
+#[tokio::test]
+async fn test_verify_code_leaves_no_user_when_token_save_fails() {
+    let user_repo = Arc::new(MockUserRepository::new());
+    let unit_of_work = Arc::new(MemoryUnitOfWork::new(&user_repo, true));
+    let published = Arc::new(PublishedEvents::default());
+    let hooks = Arc::new(LifecycleHookService::new(LifecycleHookConfig::default()).with_hook(published.clone()));
+    let auth_service = create_auth_service(
+        user_repo.clone(),
+        Arc::new(MockAuditLogRepository::new()),
+        unit_of_work.clone(),
+    )
+    .with_lifecycle_hooks(hooks);
+
+    let result = auth_service.verify_code("+61412345678", "123456", None, None, None).await;
+    assert!(matches!(result, Err(DomainError::Token(TokenError::TokenGenerationFailed))));
+
+    assert!(user_repo.users.lock().unwrap().is_empty());
+    let committed = unit_of_work.committed.lock().unwrap();
+    assert!(committed.tokens.is_empty());
+    assert!(committed.audit_logs.is_empty());
+    assert_eq!(committed.rollbacks, 1);
+    assert!(published.events.lock().unwrap().is_empty());
+}
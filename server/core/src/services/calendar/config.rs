@@ -0,0 +1,77 @@
+//! Configuration for the business calendar
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::domain::entities::calendar::normalize_market;
+
+/// Business hours and public holidays for a single market
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketCalendar {
+    /// Market timezone as an offset from UTC in minutes (e.g. 600 for UTC+10)
+    pub utc_offset_minutes: i32,
+    /// Local time business opens
+    pub opens_at: NaiveTime,
+    /// Local time business closes
+    pub closes_at: NaiveTime,
+    /// Days of the week the market is open
+    pub working_days: Vec<Weekday>,
+    /// Public holidays in local dates
+    pub holidays: Vec<NaiveDate>,
+}
+
+impl MarketCalendar {
+    /// Create a market calendar open 09:00-17:00, Monday to Friday
+    pub fn weekdays(utc_offset_minutes: i32) -> Self {
+        Self {
+            utc_offset_minutes,
+            opens_at: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            closes_at: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            working_days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            holidays: Vec::new(),
+        }
+    }
+
+    /// Add public holidays to the calendar
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+}
+
+/// Configuration for the business calendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusinessCalendarConfig {
+    /// Calendars keyed by market code (ISO 3166-1 alpha-2)
+    pub markets: HashMap<String, MarketCalendar>,
+    /// Maximum number of days searched when looking for the next open time
+    pub max_search_days: i64,
+}
+
+impl BusinessCalendarConfig {
+    /// Add or replace a market calendar
+    pub fn with_market(mut self, market: &str, calendar: MarketCalendar) -> Self {
+        self.markets.insert(normalize_market(market), calendar);
+        self
+    }
+}
+
+impl Default for BusinessCalendarConfig {
+    fn default() -> Self {
+        let mut markets = HashMap::new();
+        markets.insert("CN".to_string(), MarketCalendar::weekdays(480)); // UTC+8
+        markets.insert("AU".to_string(), MarketCalendar::weekdays(600)); // UTC+10 (AEST)
+
+        Self {
+            markets,
+            max_search_days: 366, // Never search more than a year ahead
+        }
+    }
+}
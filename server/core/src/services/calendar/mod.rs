@@ -0,0 +1,16 @@
+//! Business calendar service module
+//!
+//! This module handles:
+//! - Per-market business hours and public holidays from configuration
+//! - Ad-hoc closure dates added by administrators
+//! - Business-time arithmetic for SLA timers
+//! - Bookable slot generation for availability
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{BusinessCalendarConfig, MarketCalendar};
+pub use service::{BusinessCalendar, TimeSlot};
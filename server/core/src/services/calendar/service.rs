@@ -0,0 +1,330 @@
+//! Business calendar for per-market business hours, holidays and closures
+//!
+//! Business hours and public holidays come from configuration, while ad-hoc
+//! closures are persisted so administrators can add them at runtime. All
+//! calculations happen in the market's local time and results are returned
+//! in UTC.
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::{normalize_market, ClosureDate};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::ClosureDateRepository;
+
+use super::config::{BusinessCalendarConfig, MarketCalendar};
+
+/// A bookable time slot within business hours
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSlot {
+    /// Slot start time
+    pub start: DateTime<Utc>,
+    /// Slot end time
+    pub end: DateTime<Utc>,
+}
+
+/// Service answering business-day and business-hour questions per market
+pub struct BusinessCalendar<R>
+where
+    R: ClosureDateRepository + 'static,
+{
+    closures: Arc<R>,
+    config: BusinessCalendarConfig,
+}
+
+impl<R> BusinessCalendar<R>
+where
+    R: ClosureDateRepository + 'static,
+{
+    /// Create a new business calendar
+    pub fn new(closures: Arc<R>, config: BusinessCalendarConfig) -> Self {
+        Self { closures, config }
+    }
+
+    /// Create a new business calendar with default configuration
+    pub fn with_defaults(closures: Arc<R>) -> Self {
+        Self::new(closures, BusinessCalendarConfig::default())
+    }
+
+    /// Check whether a local date is a business day in a market
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The market is open on this day
+    /// * `Ok(false)` - Weekend, public holiday or closure
+    /// * `Err(DomainError::Validation)` - Unknown market
+    pub async fn is_business_day(&self, market: &str, date: NaiveDate) -> DomainResult<bool> {
+        let (market, calendar) = self.market_calendar(market)?;
+        let closed = self.closed_dates(&market, calendar, date, date).await?;
+        Ok(Self::is_open_day(calendar, date, &closed))
+    }
+
+    /// Check whether a local date is a public holiday or closure in a market
+    ///
+    /// Used by surge pricing, which applies holiday rates regardless of weekday.
+    pub async fn is_holiday(&self, market: &str, date: NaiveDate) -> DomainResult<bool> {
+        let (market, calendar) = self.market_calendar(market)?;
+        let closed = self.closed_dates(&market, calendar, date, date).await?;
+        Ok(closed.contains(&date))
+    }
+
+    /// Check whether a market is within business hours at a given instant
+    pub async fn is_open_at(&self, market: &str, at: DateTime<Utc>) -> DomainResult<bool> {
+        let (market, calendar) = self.market_calendar(market)?;
+        let local = Self::to_local(calendar, at);
+        let date = local.date();
+        let closed = self.closed_dates(&market, calendar, date, date).await?;
+
+        Ok(Self::is_open_day(calendar, date, &closed)
+            && local.time() >= calendar.opens_at
+            && local.time() < calendar.closes_at)
+    }
+
+    /// Find the first instant at or after `from` that falls within business hours
+    ///
+    /// # Returns
+    /// * `Ok(DateTime<Utc>)` - `from` itself if the market is open, otherwise the next opening
+    /// * `Err(DomainError::BusinessRule)` - If the market never opens within the search window
+    pub async fn next_open_time(&self, market: &str, from: DateTime<Utc>) -> DomainResult<DateTime<Utc>> {
+        self.add_business_time(market, from, Duration::zero()).await
+    }
+
+    /// Add an amount of business time to an instant
+    ///
+    /// Time outside business hours, on non-working days, public holidays and
+    /// closures does not count. Used for SLA deadlines, e.g. "respond within
+    /// 8 business hours".
+    ///
+    /// # Returns
+    /// * `Ok(DateTime<Utc>)` - The instant at which the business time has elapsed
+    /// * `Err(DomainError::BusinessRule)` - If the deadline falls beyond the search window
+    pub async fn add_business_time(
+        &self,
+        market: &str,
+        start: DateTime<Utc>,
+        duration: Duration,
+    ) -> DomainResult<DateTime<Utc>> {
+        let (market, calendar) = self.market_calendar(market)?;
+        let local_start = Self::to_local(calendar, start);
+        let first_day = local_start.date();
+        let last_day = first_day + Duration::days(self.config.max_search_days);
+        let closed = self.closed_dates(&market, calendar, first_day, last_day).await?;
+
+        let mut remaining = duration;
+        let mut date = first_day;
+
+        while date <= last_day {
+            if Self::is_open_day(calendar, date, &closed) {
+                let opens = date.and_time(calendar.opens_at);
+                let closes = date.and_time(calendar.closes_at);
+                let day_start = opens.max(local_start);
+
+                if day_start < closes {
+                    let available = closes - day_start;
+                    if remaining < available {
+                        return Ok(Self::to_utc(calendar, day_start + remaining));
+                    }
+                    remaining -= available;
+                }
+            }
+            date += Duration::days(1);
+        }
+
+        Err(DomainError::BusinessRule {
+            message: format!(
+                "No business hours in market {} within {} days",
+                market, self.config.max_search_days
+            ),
+        })
+    }
+
+    /// Generate bookable slots of a fixed length for a local date
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TimeSlot>)` - Slots covering business hours, empty on non-business days
+    /// * `Err(DomainError::Validation)` - Unknown market or non-positive slot length
+    pub async fn slots(
+        &self,
+        market: &str,
+        date: NaiveDate,
+        slot_minutes: i64,
+    ) -> DomainResult<Vec<TimeSlot>> {
+        if slot_minutes <= 0 {
+            return Err(DomainError::Validation {
+                message: "Slot length must be positive".to_string(),
+            });
+        }
+
+        let (market, calendar) = self.market_calendar(market)?;
+        let closed = self.closed_dates(&market, calendar, date, date).await?;
+        if !Self::is_open_day(calendar, date, &closed) {
+            return Ok(Vec::new());
+        }
+
+        let slot_length = Duration::minutes(slot_minutes);
+        let closes = date.and_time(calendar.closes_at);
+        let mut start = date.and_time(calendar.opens_at);
+        let mut slots = Vec::new();
+
+        while start + slot_length <= closes {
+            slots.push(TimeSlot {
+                start: Self::to_utc(calendar, start),
+                end: Self::to_utc(calendar, start + slot_length),
+            });
+            start += slot_length;
+        }
+
+        Ok(slots)
+    }
+
+    /// Add an ad-hoc closure date to a market
+    ///
+    /// # Arguments
+    /// * `market` - Market code
+    /// * `date` - Local date to close
+    /// * `reason` - Reason shown to administrators
+    /// * `created_by` - Administrator adding the closure
+    ///
+    /// # Returns
+    /// * `Ok(ClosureDate)` - The persisted closure
+    /// * `Err(DomainError::Validation)` - Unknown market, empty reason, past date or duplicate closure
+    pub async fn add_closure(
+        &self,
+        market: &str,
+        date: NaiveDate,
+        reason: &str,
+        created_by: Option<Uuid>,
+    ) -> DomainResult<ClosureDate> {
+        let (market, calendar) = self.market_calendar(market)?;
+
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Closure reason is required".to_string(),
+            });
+        }
+
+        let today = Self::to_local(calendar, Utc::now()).date();
+        if date < today {
+            return Err(DomainError::Validation {
+                message: "Closure date must not be in the past".to_string(),
+            });
+        }
+
+        if !self.closures.find_by_market(&market, date, date).await?.is_empty() {
+            return Err(DomainError::Validation {
+                message: format!("Market {} already has a closure on {}", market, date),
+            });
+        }
+
+        let closure = self
+            .closures
+            .save(ClosureDate::new(&market, date, reason.to_string(), created_by))
+            .await?;
+
+        info!(
+            closure_id = %closure.id,
+            market = %closure.market,
+            date = %closure.date,
+            "Business closure added"
+        );
+
+        Ok(closure)
+    }
+
+    /// List closures for a market within an inclusive local date range
+    pub async fn list_closures(
+        &self,
+        market: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ClosureDate>> {
+        let (market, _) = self.market_calendar(market)?;
+        self.closures.find_by_market(&market, from, to).await
+    }
+
+    /// Remove a closure
+    ///
+    /// # Returns
+    /// * `Ok(())` - The closure was removed
+    /// * `Err(DomainError::NotFound)` - No closure exists with this ID
+    pub async fn remove_closure(&self, id: Uuid) -> DomainResult<()> {
+        if !self.closures.delete(id).await? {
+            return Err(DomainError::NotFound {
+                resource: "Closure date".to_string(),
+            });
+        }
+
+        info!(closure_id = %id, "Business closure removed");
+        Ok(())
+    }
+
+    /// Look up the calendar for a market
+    fn market_calendar(&self, market: &str) -> DomainResult<(String, &MarketCalendar)> {
+        let market = normalize_market(market);
+        let calendar = self
+            .config
+            .markets
+            .get(&market)
+            .ok_or_else(|| DomainError::Validation {
+                message: format!("Unsupported market: {}", market),
+            })?;
+
+        if calendar.opens_at >= calendar.closes_at {
+            return Err(DomainError::Internal {
+                message: format!("Invalid business hours configured for market {}", market),
+            });
+        }
+
+        Ok((market, calendar))
+    }
+
+    /// Collect public holidays and closures within an inclusive date range
+    async fn closed_dates(
+        &self,
+        market: &str,
+        calendar: &MarketCalendar,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<HashSet<NaiveDate>> {
+        let mut closed: HashSet<NaiveDate> = calendar
+            .holidays
+            .iter()
+            .copied()
+            .filter(|date| *date >= from && *date <= to)
+            .collect();
+
+        closed.extend(
+            self.closures
+                .find_by_market(market, from, to)
+                .await?
+                .into_iter()
+                .map(|closure| closure.date),
+        );
+
+        Ok(closed)
+    }
+
+    fn is_open_day(calendar: &MarketCalendar, date: NaiveDate, closed: &HashSet<NaiveDate>) -> bool {
+        calendar.working_days.contains(&date.weekday()) && !closed.contains(&date)
+    }
+
+    fn offset(calendar: &MarketCalendar) -> FixedOffset {
+        FixedOffset::east_opt(calendar.utc_offset_minutes * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+    }
+
+    fn to_local(calendar: &MarketCalendar, at: DateTime<Utc>) -> NaiveDateTime {
+        at.with_timezone(&Self::offset(calendar)).naive_local()
+    }
+
+    fn to_utc(calendar: &MarketCalendar, local: NaiveDateTime) -> DateTime<Utc> {
+        Self::offset(calendar)
+            .from_local_datetime(&local)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+}
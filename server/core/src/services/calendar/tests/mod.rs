@@ -0,0 +1,4 @@
+//! Tests for the business calendar service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,184 @@
+//! Unit tests for the business calendar service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::ClosureDate;
+use crate::errors::DomainError;
+use crate::repositories::ClosureDateRepository;
+use crate::services::calendar::{BusinessCalendar, BusinessCalendarConfig, MarketCalendar};
+
+#[derive(Default)]
+struct MockClosureRepository {
+    closures: Mutex<Vec<ClosureDate>>,
+}
+
+#[async_trait]
+impl ClosureDateRepository for MockClosureRepository {
+    async fn save(&self, closure: ClosureDate) -> Result<ClosureDate, DomainError> {
+        self.closures.lock().unwrap().push(closure.clone());
+        Ok(closure)
+    }
+
+    async fn find_by_market(
+        &self,
+        market: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ClosureDate>, DomainError> {
+        let mut found: Vec<_> = self
+            .closures
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.market == market && c.date >= from && c.date <= to)
+            .cloned()
+            .collect();
+        found.sort_by_key(|c| c.date);
+        Ok(found)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let mut closures = self.closures.lock().unwrap();
+        let before = closures.len();
+        closures.retain(|c| c.id != id);
+        Ok(closures.len() != before)
+    }
+}
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+/// Calendar with a UTC market "XX" and the default "AU" market (UTC+10).
+/// 2030-01-04 is a Friday and 2030-01-07 is a Monday.
+fn create_calendar() -> BusinessCalendar<MockClosureRepository> {
+    let config = BusinessCalendarConfig::default().with_market(
+        "xx",
+        MarketCalendar::weekdays(0).with_holidays(vec![date(2030, 1, 1)]),
+    );
+    BusinessCalendar::new(Arc::new(MockClosureRepository::default()), config)
+}
+
+#[tokio::test]
+async fn test_business_days_exclude_weekends_and_holidays() {
+    let calendar = create_calendar();
+
+    assert!(calendar.is_business_day("XX", date(2030, 1, 4)).await.unwrap());
+    assert!(!calendar.is_business_day("XX", date(2030, 1, 5)).await.unwrap());
+    assert!(!calendar.is_business_day("XX", date(2030, 1, 1)).await.unwrap());
+    assert!(calendar.is_holiday("XX", date(2030, 1, 1)).await.unwrap());
+    assert!(!calendar.is_holiday("XX", date(2030, 1, 5)).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_unknown_market_rejected() {
+    let calendar = create_calendar();
+
+    let result = calendar.is_business_day("ZZ", date(2030, 1, 4)).await;
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_is_open_at_uses_market_timezone() {
+    let calendar = create_calendar();
+
+    // 09:30 and 08:30 on Monday 2030-01-07 in UTC+10
+    assert!(calendar.is_open_at("AU", utc(2030, 1, 6, 23, 30)).await.unwrap());
+    assert!(!calendar.is_open_at("AU", utc(2030, 1, 6, 22, 30)).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_add_business_time_skips_weekend_and_closures() {
+    let calendar = create_calendar();
+    let friday_afternoon = utc(2030, 1, 4, 16, 0);
+
+    let deadline = calendar
+        .add_business_time("XX", friday_afternoon, Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(deadline, utc(2030, 1, 7, 10, 0));
+
+    calendar
+        .add_closure("XX", date(2030, 1, 7), "Storm warning", None)
+        .await
+        .unwrap();
+    let deadline = calendar
+        .add_business_time("XX", friday_afternoon, Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(deadline, utc(2030, 1, 8, 10, 0));
+}
+
+#[tokio::test]
+async fn test_next_open_time() {
+    let calendar = create_calendar();
+
+    let saturday = utc(2030, 1, 5, 12, 0);
+    assert_eq!(
+        calendar.next_open_time("XX", saturday).await.unwrap(),
+        utc(2030, 1, 7, 9, 0)
+    );
+
+    let open = utc(2030, 1, 4, 11, 15);
+    assert_eq!(calendar.next_open_time("XX", open).await.unwrap(), open);
+}
+
+#[tokio::test]
+async fn test_slots() {
+    let calendar = create_calendar();
+
+    let slots = calendar.slots("XX", date(2030, 1, 4), 90).await.unwrap();
+    assert_eq!(slots.len(), 5);
+    assert_eq!(slots[0].start, utc(2030, 1, 4, 9, 0));
+    assert_eq!(slots[4].end, utc(2030, 1, 4, 16, 30));
+
+    assert!(calendar.slots("XX", date(2030, 1, 5), 60).await.unwrap().is_empty());
+
+    let invalid = calendar.slots("XX", date(2030, 1, 4), 0).await;
+    assert!(matches!(invalid, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_add_closure_validation() {
+    let calendar = create_calendar();
+    let admin_id = Uuid::new_v4();
+
+    let closure = calendar
+        .add_closure("xx", date(2030, 1, 7), "Storm warning", Some(admin_id))
+        .await
+        .unwrap();
+    assert_eq!(closure.market, "XX");
+    assert_eq!(closure.created_by, Some(admin_id));
+
+    let duplicate = calendar.add_closure("XX", date(2030, 1, 7), "Again", None).await;
+    assert!(matches!(duplicate, Err(DomainError::Validation { .. })));
+
+    let past = calendar.add_closure("XX", date(2020, 1, 7), "Too late", None).await;
+    assert!(matches!(past, Err(DomainError::Validation { .. })));
+
+    let empty_reason = calendar.add_closure("XX", date(2030, 1, 8), "  ", None).await;
+    assert!(matches!(empty_reason, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_remove_closure() {
+    let calendar = create_calendar();
+    let closure = calendar
+        .add_closure("XX", date(2030, 1, 7), "Storm warning", None)
+        .await
+        .unwrap();
+
+    calendar.remove_closure(closure.id).await.unwrap();
+
+    assert!(calendar.is_business_day("XX", date(2030, 1, 7)).await.unwrap());
+    let missing = calendar.remove_closure(closure.id).await;
+    assert!(matches!(missing, Err(DomainError::NotFound { .. })));
+}
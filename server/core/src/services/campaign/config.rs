@@ -0,0 +1,47 @@
+//! Configuration for the campaign service
+
+use std::collections::HashMap;
+
+/// Configuration for the campaign service
+#[derive(Debug, Clone)]
+pub struct CampaignServiceConfig {
+    /// How often the background task dispatches deliveries (in seconds)
+    pub dispatch_interval_seconds: u64,
+    /// Maximum number of deliveries attempted per cycle across all campaigns
+    pub max_sends_per_cycle: usize,
+    /// Maximum number of SMS deliveries per cycle, within `max_sends_per_cycle`
+    ///
+    /// Kept below the provider's rate limit so broadcasts leave room for
+    /// verification codes.
+    pub max_sms_per_cycle: usize,
+    /// How long a dispatcher's claim on a campaign or delivery lasts before
+    /// another instance may claim it again (in seconds)
+    pub claim_lease_seconds: i64,
+    /// Content language for each market, keyed by country code
+    pub market_languages: HashMap<String, String>,
+    /// Whether to enable background dispatch
+    pub enabled: bool,
+}
+
+impl CampaignServiceConfig {
+    /// Content language for a recipient's country code, if configured
+    pub fn language_for(&self, country_code: &str) -> Option<&str> {
+        self.market_languages.get(country_code).map(String::as_str)
+    }
+}
+
+impl Default for CampaignServiceConfig {
+    fn default() -> Self {
+        Self {
+            dispatch_interval_seconds: 30,
+            max_sends_per_cycle: 200, // At most 400 sends per minute
+            max_sms_per_cycle: 30,    // At most 60 SMS per minute
+            claim_lease_seconds: 600,
+            market_languages: HashMap::from([
+                ("+86".to_string(), "zh".to_string()),
+                ("+61".to_string(), "en".to_string()),
+            ]),
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Notification campaign service module for bulk messages sent by administrators
+//!
+//! This module handles:
+//! - Creating campaigns with an audience, localized content and a schedule
+//! - Delivering campaigns as push notifications or SMS broadcasts
+//! - Fanning out deliveries in throttled background batches
+//! - Honouring marketing opt-outs and quiet hours
+//! - Per-campaign delivery and open statistics
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::CampaignServiceConfig;
+pub use service::{CampaignDispatchResult, CampaignService, NewCampaign};
+pub use traits::{NotificationSenderTrait, UserPhoneDirectoryTrait};
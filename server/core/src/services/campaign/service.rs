@@ -0,0 +1,490 @@
+//! Campaign service for bulk notifications
+//!
+//! Campaigns are created by administrators and started by a background task
+//! once their scheduled time passes. Starting a campaign enqueues one
+//! delivery per audience member; each cycle then attempts a bounded number
+//! of deliveries so large audiences are sent gradually. Dispatchers claim a
+//! campaign before starting it and each batch of deliveries before sending
+//! it, so several instances can run the background task at once. Preferences are
+//! checked at send time: opted-out users are skipped and users inside quiet
+//! hours are retried once their quiet period ends.
+//!
+//! SMS campaigns send the localized body to each recipient's phone number.
+//! They have their own, smaller per-cycle budget so a broadcast stays well
+//! under the provider's rate limit, and are only accepted when an SMS
+//! channel has been configured with [`CampaignService::with_sms_channel`].
+//!
+//! Campaigns belong to a tenant. The background task dispatches those of
+//! each tenant given to [`CampaignService::with_tenants`] in turn.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::campaign::{
+    Campaign, CampaignAudience, CampaignChannel, CampaignContent, CampaignDelivery, CampaignStats,
+    CampaignStatus,
+};
+use crate::domain::entities::notification::NotificationPreferences;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{CampaignRepository, NotificationPreferencesRepository};
+use crate::services::tenant::{self, TenantId};
+use crate::services::verification::SmsServiceTrait;
+
+use super::config::CampaignServiceConfig;
+use super::traits::{NotificationSenderTrait, UserPhoneDirectoryTrait};
+
+/// A campaign as submitted by an administrator
+#[derive(Debug, Clone)]
+pub struct NewCampaign {
+    /// Internal name shown to administrators
+    pub name: String,
+    /// Channel the campaign is delivered through
+    pub channel: CampaignChannel,
+    /// Users the campaign is sent to
+    pub audience: CampaignAudience,
+    /// Localized content keyed by language code
+    pub content: HashMap<String, CampaignContent>,
+    /// Language used when a recipient's language has no content
+    pub default_language: String,
+    /// Start time; `None` starts with the next dispatch cycle
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+/// Service for creating and dispatching notification campaigns
+pub struct CampaignService<C, P, N>
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    campaigns: Arc<C>,
+    preferences: Arc<P>,
+    sender: Arc<N>,
+    sms: Option<SmsChannel>,
+    config: CampaignServiceConfig,
+    tenants: Vec<TenantId>,
+}
+
+/// SMS service and phone directory used by SMS campaigns
+struct SmsChannel {
+    sms_service: Arc<dyn SmsServiceTrait>,
+    phones: Arc<dyn UserPhoneDirectoryTrait>,
+}
+
+impl<C, P, N> CampaignService<C, P, N>
+where
+    C: CampaignRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    /// Create a new campaign service
+    pub fn new(
+        campaigns: Arc<C>,
+        preferences: Arc<P>,
+        sender: Arc<N>,
+        config: CampaignServiceConfig,
+    ) -> Self {
+        Self {
+            campaigns,
+            preferences,
+            sender,
+            sms: None,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Enable SMS campaigns
+    ///
+    /// Without an SMS channel, campaigns on the SMS channel are rejected
+    /// when they are created.
+    pub fn with_sms_channel(
+        mut self,
+        sms_service: Arc<dyn SmsServiceTrait>,
+        phones: Arc<dyn UserPhoneDirectoryTrait>,
+    ) -> Self {
+        self.sms = Some(SmsChannel { sms_service, phones });
+        self
+    }
+
+    /// Dispatch the campaigns of these tenants in the background task,
+    /// instead of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Create a new campaign service with default configuration
+    pub fn with_defaults(campaigns: Arc<C>, preferences: Arc<P>, sender: Arc<N>) -> Self {
+        Self::new(campaigns, preferences, sender, CampaignServiceConfig::default())
+    }
+
+    /// Create a scheduled campaign
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - The saved campaign
+    /// * `Err(DomainError::Validation)` - Invalid content, a start time in the
+    ///   past, or an SMS campaign without a configured SMS channel
+    pub async fn create_campaign(
+        &self,
+        request: NewCampaign,
+        created_by: Uuid,
+    ) -> DomainResult<Campaign> {
+        let now = Utc::now();
+        let scheduled_at = request.scheduled_at.unwrap_or(now);
+
+        // Allow a little clock skew between the admin client and the server
+        if scheduled_at < now - Duration::minutes(1) {
+            return Err(DomainError::Validation {
+                message: "Scheduled time must not be in the past".to_string(),
+            });
+        }
+
+        if request.channel == CampaignChannel::Sms && self.sms.is_none() {
+            return Err(DomainError::Validation {
+                message: "SMS campaigns are not available".to_string(),
+            });
+        }
+
+        let campaign = Campaign::new(
+            request.name,
+            request.audience,
+            request.content,
+            request.default_language,
+            scheduled_at,
+            created_by,
+        )
+        .and_then(|campaign| campaign.with_channel(request.channel))
+        .map_err(|message| DomainError::Validation { message })?;
+
+        let campaign = self.campaigns.save(campaign).await?;
+        info!(
+            campaign_id = %campaign.id,
+            created_by = %created_by,
+            channel = campaign.channel.as_str(),
+            scheduled_at = %campaign.scheduled_at,
+            "Campaign created"
+        );
+
+        Ok(campaign)
+    }
+
+    /// Get a campaign with its delivery statistics
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No campaign exists with this ID
+    pub async fn get_campaign(&self, id: Uuid) -> DomainResult<(Campaign, CampaignStats)> {
+        let campaign = self.find_campaign(id).await?;
+        let stats = self.campaigns.stats(id).await?;
+        Ok((campaign, stats))
+    }
+
+    /// List the most recently created campaigns
+    pub async fn list_campaigns(&self, limit: usize) -> DomainResult<Vec<Campaign>> {
+        self.campaigns.list_recent(limit).await
+    }
+
+    /// Cancel a scheduled or sending campaign
+    ///
+    /// Deliveries that were already sent are unaffected.
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - The cancelled campaign
+    /// * `Err(DomainError::NotFound)` - No campaign exists with this ID
+    /// * `Err(DomainError::BusinessRule)` - The campaign already finished
+    pub async fn cancel_campaign(&self, id: Uuid) -> DomainResult<Campaign> {
+        let mut campaign = self.find_campaign(id).await?;
+
+        if !campaign.cancel() {
+            return Err(DomainError::BusinessRule {
+                message: "Campaign has already finished".to_string(),
+            });
+        }
+
+        let campaign = self.campaigns.update(campaign).await?;
+        info!(campaign_id = %id, "Campaign cancelled");
+        Ok(campaign)
+    }
+
+    /// Record that a user opened a campaign notification
+    ///
+    /// Repeated opens are ignored so statistics count each recipient once.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No delivery with this ID belongs to the user
+    pub async fn record_open(&self, delivery_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        let mut delivery = self
+            .campaigns
+            .find_delivery(delivery_id)
+            .await?
+            .filter(|delivery| delivery.user_id == user_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Campaign delivery".to_string(),
+            })?;
+
+        if delivery.mark_opened() {
+            self.campaigns.update_delivery(delivery).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Start due campaigns and send the next batch of deliveries
+    ///
+    /// At most `max_sends_per_cycle` deliveries are attempted per call,
+    /// oldest campaign first, of which at most `max_sms_per_cycle` are SMS.
+    /// A campaign completes once it has no pending deliveries left.
+    ///
+    /// Campaigns and deliveries are claimed before they are started or
+    /// sent, so concurrent dispatchers never send a delivery twice. A
+    /// campaign whose audience could not be enqueued keeps its claim and is
+    /// retried once the claim lease expires.
+    ///
+    /// # Returns
+    /// * `Ok(CampaignDispatchResult)` - Summary of the dispatch cycle
+    /// * `Err(DomainError)` - If campaigns could not be loaded
+    pub async fn dispatch(&self) -> DomainResult<CampaignDispatchResult> {
+        let now = Utc::now();
+        let lease = Duration::seconds(self.config.claim_lease_seconds);
+        let mut result = CampaignDispatchResult::default();
+
+        let mut due: Vec<Campaign> = self
+            .campaigns
+            .find_by_status(CampaignStatus::Scheduled)
+            .await?
+            .into_iter()
+            .filter(|campaign| campaign.is_due(now))
+            .collect();
+        due.extend(
+            self.campaigns
+                .find_by_status(CampaignStatus::Dispatching)
+                .await?
+                .into_iter()
+                .filter(|campaign| campaign.updated_at <= now - lease),
+        );
+
+        for mut campaign in due {
+            if !self.campaigns.claim_start(campaign.id, now, now - lease).await? {
+                continue;
+            }
+
+            match self.campaigns.enqueue_deliveries(&campaign).await {
+                Ok(audience_size) => {
+                    campaign.start(audience_size);
+                    self.campaigns.update(campaign).await?;
+                    result.started += 1;
+                }
+                Err(e) => {
+                    error!(campaign_id = %campaign.id, error = %e, "Failed to enqueue campaign audience");
+                    result.errors.push(format!("{}: {}", campaign.id, e));
+                }
+            }
+        }
+
+        // Deliveries enqueued above become eligible from the time they were created
+        let now = Utc::now();
+        let mut budget = self.config.max_sends_per_cycle;
+        let mut sms_budget = self.config.max_sms_per_cycle;
+
+        for mut campaign in self.campaigns.find_by_status(CampaignStatus::Sending).await? {
+            if budget == 0 {
+                break;
+            }
+
+            let limit = match campaign.channel {
+                CampaignChannel::Push => budget,
+                CampaignChannel::Sms => budget.min(sms_budget),
+            };
+            if limit == 0 {
+                continue;
+            }
+
+            let deliveries = self
+                .campaigns
+                .claim_pending_deliveries(campaign.id, now, limit, now + lease)
+                .await?;
+            budget -= deliveries.len();
+            if campaign.channel == CampaignChannel::Sms {
+                sms_budget -= deliveries.len();
+            }
+
+            for delivery in deliveries {
+                let delivery_id = delivery.id;
+                if let Err(e) = self.dispatch_one(&campaign, delivery, now, &mut result).await {
+                    error!(delivery_id = %delivery_id, error = %e, "Failed to dispatch campaign delivery");
+                    result.errors.push(format!("{}: {}", delivery_id, e));
+                }
+            }
+
+            if self.campaigns.stats(campaign.id).await?.pending == 0 {
+                campaign.complete();
+                let campaign = self.campaigns.update(campaign).await?;
+                info!(campaign_id = %campaign.id, "Campaign completed");
+                result.completed += 1;
+            }
+        }
+
+        if result.total_processed() > 0 || result.started > 0 {
+            info!(
+                started = result.started,
+                sent = result.sent,
+                failed = result.failed,
+                opted_out = result.opted_out,
+                deferred = result.deferred,
+                completed = result.completed,
+                "Campaign dispatch completed"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Attempt a single delivery and persist its new state
+    async fn dispatch_one(
+        &self,
+        campaign: &Campaign,
+        mut delivery: CampaignDelivery,
+        now: DateTime<Utc>,
+        result: &mut CampaignDispatchResult,
+    ) -> DomainResult<()> {
+        delivery.release();
+
+        let preferences = self
+            .preferences
+            .find_by_user_id(delivery.user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::new(delivery.user_id));
+
+        if !preferences.marketing_enabled {
+            delivery.mark_opted_out();
+            result.opted_out += 1;
+        } else if preferences.is_quiet_at(now) {
+            delivery.next_attempt_at = preferences.next_allowed_time(now);
+            result.deferred += 1;
+        } else {
+            let language = self
+                .config
+                .language_for(&delivery.country_code)
+                .unwrap_or(&campaign.default_language);
+
+            let sent = match campaign.content_for(language) {
+                Some(content) => match campaign.channel {
+                    CampaignChannel::Push => {
+                        self.sender
+                            .send_notification(delivery.user_id, delivery.id, &content.title, &content.body)
+                            .await
+                    }
+                    CampaignChannel::Sms => self.send_sms(delivery.user_id, &content.body).await,
+                },
+                None => Err("Campaign has no content".to_string()),
+            };
+
+            match sent {
+                Ok(provider_message_id) => {
+                    delivery.mark_sent(provider_message_id);
+                    result.sent += 1;
+                }
+                Err(e) => {
+                    warn!(delivery_id = %delivery.id, error = %e, "Campaign delivery failed");
+                    delivery.mark_failed(e);
+                    result.failed += 1;
+                }
+            }
+        }
+
+        self.campaigns.update_delivery(delivery).await?;
+        Ok(())
+    }
+
+    /// Send an SMS campaign body to a user's phone number
+    async fn send_sms(&self, user_id: Uuid, body: &str) -> Result<String, String> {
+        let sms = self
+            .sms
+            .as_ref()
+            .ok_or_else(|| "SMS channel is not configured".to_string())?;
+
+        match sms.phones.phone_for(user_id).await? {
+            Some(phone) => sms.sms_service.send_message(&phone, body).await,
+            None => Err("No phone number on file".to_string()),
+        }
+    }
+
+    async fn find_campaign(&self, id: Uuid) -> DomainResult<Campaign> {
+        self.campaigns
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Campaign".to_string(),
+            })
+    }
+
+    /// Start the campaign dispatcher as a background task
+    ///
+    /// This spawns a tokio task that dispatches campaigns at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Campaign dispatcher is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.dispatch_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Campaign dispatcher started - will dispatch every {} seconds",
+                self.config.dispatch_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                for tenant in &self.tenants {
+                    match tenant::scope(tenant.clone(), self.dispatch()).await {
+                        Ok(result) => {
+                            if !result.errors.is_empty() {
+                                warn!(
+                                    "Campaign dispatch for tenant {} completed with errors: {:?}",
+                                    tenant, result.errors
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("Campaign dispatch cycle for tenant {} failed: {}", tenant, e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Result of a campaign dispatch cycle
+#[derive(Debug, Default)]
+pub struct CampaignDispatchResult {
+    /// Number of campaigns whose audience was enqueued
+    pub started: usize,
+    /// Number of deliveries sent
+    pub sent: usize,
+    /// Number of deliveries rejected by the channel
+    pub failed: usize,
+    /// Number of deliveries skipped because the user opted out
+    pub opted_out: usize,
+    /// Number of deliveries deferred past quiet hours
+    pub deferred: usize,
+    /// Number of campaigns completed
+    pub completed: usize,
+    /// Any errors encountered during dispatch
+    pub errors: Vec<String>,
+}
+
+impl CampaignDispatchResult {
+    /// Get total number of deliveries processed
+    pub fn total_processed(&self) -> usize {
+        self.sent + self.failed + self.opted_out + self.deferred
+    }
+}
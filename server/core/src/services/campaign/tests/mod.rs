@@ -0,0 +1,4 @@
+//! Tests for the campaign service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,564 @@
+//! Unit tests for the campaign service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::campaign::{
+    Campaign, CampaignAudience, CampaignChannel, CampaignContent, CampaignDelivery, CampaignStats, CampaignStatus,
+    DeliveryStatus,
+};
+use crate::domain::entities::notification::NotificationPreferences;
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::{CampaignRepository, NotificationPreferencesRepository};
+use crate::services::campaign::{
+    CampaignService, CampaignServiceConfig, NewCampaign, NotificationSenderTrait,
+    UserPhoneDirectoryTrait,
+};
+use crate::services::verification::SmsServiceTrait;
+
+struct MockCampaignRepository {
+    users: Vec<User>,
+    campaigns: Mutex<HashMap<Uuid, Campaign>>,
+    deliveries: Mutex<Vec<CampaignDelivery>>,
+}
+
+impl MockCampaignRepository {
+    fn new(users: Vec<User>) -> Self {
+        Self {
+            users,
+            campaigns: Mutex::new(HashMap::new()),
+            deliveries: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn deliveries(&self) -> Vec<CampaignDelivery> {
+        self.deliveries.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl CampaignRepository for MockCampaignRepository {
+    async fn save(&self, campaign: Campaign) -> Result<Campaign, DomainError> {
+        self.campaigns.lock().unwrap().insert(campaign.id, campaign.clone());
+        Ok(campaign)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Campaign>, DomainError> {
+        Ok(self.campaigns.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list_recent(&self, limit: usize) -> Result<Vec<Campaign>, DomainError> {
+        let mut campaigns: Vec<_> = self.campaigns.lock().unwrap().values().cloned().collect();
+        campaigns.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        campaigns.truncate(limit);
+        Ok(campaigns)
+    }
+
+    async fn find_by_status(&self, status: CampaignStatus) -> Result<Vec<Campaign>, DomainError> {
+        let mut campaigns: Vec<_> = self
+            .campaigns
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|c| c.status == status)
+            .cloned()
+            .collect();
+        campaigns.sort_by_key(|c| c.scheduled_at);
+        Ok(campaigns)
+    }
+
+    async fn update(&self, campaign: Campaign) -> Result<Campaign, DomainError> {
+        self.save(campaign).await
+    }
+
+    async fn claim_start(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let mut campaigns = self.campaigns.lock().unwrap();
+        let Some(campaign) = campaigns.get_mut(&campaign_id) else {
+            return Ok(false);
+        };
+        let claimable = campaign.status == CampaignStatus::Scheduled
+            || (campaign.status == CampaignStatus::Dispatching && campaign.updated_at <= stale_before);
+        if claimable {
+            campaign.status = CampaignStatus::Dispatching;
+            campaign.updated_at = now;
+        }
+        Ok(claimable)
+    }
+
+    async fn enqueue_deliveries(&self, campaign: &Campaign) -> Result<u64, DomainError> {
+        let now = Utc::now();
+        let mut deliveries = self.deliveries.lock().unwrap();
+        for user in self.users.iter().filter(|u| campaign.audience.matches(u, now)) {
+            let exists = deliveries
+                .iter()
+                .any(|d| d.campaign_id == campaign.id && d.user_id == user.id);
+            if !exists {
+                deliveries.push(CampaignDelivery::new(campaign.id, user.id, user.country_code.clone()));
+            }
+        }
+        Ok(deliveries.iter().filter(|d| d.campaign_id == campaign.id).count() as u64)
+    }
+
+    async fn claim_pending_deliveries(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        limit: usize,
+        _claim_until: DateTime<Utc>,
+    ) -> Result<Vec<CampaignDelivery>, DomainError> {
+        Ok(self
+            .deliveries
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|d| {
+                d.campaign_id == campaign_id
+                    && d.status == DeliveryStatus::Pending
+                    && d.next_attempt_at <= now
+            })
+            .take(limit)
+            .map(|d| {
+                d.claim();
+                d.clone()
+            })
+            .collect())
+    }
+
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<CampaignDelivery>, DomainError> {
+        Ok(self.deliveries.lock().unwrap().iter().find(|d| d.id == id).cloned())
+    }
+
+    async fn update_delivery(&self, delivery: CampaignDelivery) -> Result<CampaignDelivery, DomainError> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        let existing = deliveries
+            .iter_mut()
+            .find(|d| d.id == delivery.id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Campaign delivery".to_string(),
+            })?;
+        *existing = delivery.clone();
+        Ok(delivery)
+    }
+
+    async fn stats(&self, campaign_id: Uuid) -> Result<CampaignStats, DomainError> {
+        let mut stats = CampaignStats::default();
+        for delivery in self.deliveries.lock().unwrap().iter().filter(|d| d.campaign_id == campaign_id) {
+            match delivery.status {
+                DeliveryStatus::Pending | DeliveryStatus::Processing => stats.pending += 1,
+                DeliveryStatus::Sent => stats.sent += 1,
+                DeliveryStatus::Failed => stats.failed += 1,
+                DeliveryStatus::OptedOut => stats.opted_out += 1,
+            }
+            if delivery.opened_at.is_some() {
+                stats.opened += 1;
+            }
+        }
+        Ok(stats)
+    }
+}
+
+#[derive(Default)]
+struct MockPreferencesRepository {
+    preferences: Mutex<HashMap<Uuid, NotificationPreferences>>,
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for MockPreferencesRepository {
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>, DomainError> {
+        Ok(self.preferences.lock().unwrap().get(&user_id).cloned())
+    }
+
+    async fn upsert(
+        &self,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, DomainError> {
+        self.preferences
+            .lock()
+            .unwrap()
+            .insert(preferences.user_id, preferences.clone());
+        Ok(preferences)
+    }
+}
+
+#[derive(Default)]
+struct MockNotificationSender {
+    sent: Mutex<Vec<(Uuid, String)>>,
+    failing_users: Vec<Uuid>,
+}
+
+#[async_trait]
+impl NotificationSenderTrait for MockNotificationSender {
+    async fn send_notification(
+        &self,
+        user_id: Uuid,
+        _delivery_id: Uuid,
+        title: &str,
+        _body: &str,
+    ) -> Result<String, String> {
+        // Let concurrent dispatchers interleave at the send
+        tokio::task::yield_now().await;
+        if self.failing_users.contains(&user_id) {
+            return Err("Device not registered".to_string());
+        }
+        self.sent.lock().unwrap().push((user_id, title.to_string()));
+        Ok(format!("mock-notification-{}", Uuid::new_v4()))
+    }
+}
+
+#[derive(Default)]
+struct MockSmsService {
+    sent: Mutex<Vec<(String, String)>>,
+}
+
+#[async_trait]
+impl SmsServiceTrait for MockSmsService {
+    async fn send_verification_code(&self, _phone: &str, _code: &str) -> Result<String, String> {
+        Err("Not used by campaigns".to_string())
+    }
+
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        self.sent.lock().unwrap().push((phone.to_string(), message.to_string()));
+        Ok(format!("mock-sms-{}", Uuid::new_v4()))
+    }
+
+    fn is_valid_phone_number(&self, phone: &str) -> bool {
+        phone.starts_with('+')
+    }
+}
+
+#[derive(Default)]
+struct MockPhoneDirectory {
+    phones: HashMap<Uuid, String>,
+}
+
+#[async_trait]
+impl UserPhoneDirectoryTrait for MockPhoneDirectory {
+    async fn phone_for(&self, user_id: Uuid) -> Result<Option<String>, String> {
+        Ok(self.phones.get(&user_id).cloned())
+    }
+}
+
+type TestService =
+    CampaignService<MockCampaignRepository, MockPreferencesRepository, MockNotificationSender>;
+
+fn user(country_code: &str, user_type: UserType) -> User {
+    let mut user = User::new(format!("hash-{}", Uuid::new_v4()), country_code.to_string());
+    user.set_user_type(user_type);
+    user
+}
+
+fn create_service(
+    users: Vec<User>,
+    sender: MockNotificationSender,
+    config: CampaignServiceConfig,
+) -> (
+    TestService,
+    Arc<MockCampaignRepository>,
+    Arc<MockPreferencesRepository>,
+    Arc<MockNotificationSender>,
+) {
+    let campaigns = Arc::new(MockCampaignRepository::new(users));
+    let preferences = Arc::new(MockPreferencesRepository::default());
+    let sender = Arc::new(sender);
+    let service = CampaignService::new(campaigns.clone(), preferences.clone(), sender.clone(), config);
+    (service, campaigns, preferences, sender)
+}
+
+fn new_campaign(audience: CampaignAudience) -> NewCampaign {
+    NewCampaign {
+        name: "Spring promotion".to_string(),
+        channel: CampaignChannel::Push,
+        audience,
+        content: HashMap::from([
+            (
+                "en".to_string(),
+                CampaignContent {
+                    title: "Spring offer".to_string(),
+                    body: "10% off kitchen renovations".to_string(),
+                },
+            ),
+            (
+                "zh".to_string(),
+                CampaignContent {
+                    title: "春季优惠".to_string(),
+                    body: "厨房翻新九折".to_string(),
+                },
+            ),
+        ]),
+        default_language: "en".to_string(),
+        scheduled_at: None,
+    }
+}
+
+#[tokio::test]
+async fn test_create_campaign_rejects_past_schedule() {
+    let (service, _, _, _) = create_service(vec![], MockNotificationSender::default(), CampaignServiceConfig::default());
+
+    let mut request = new_campaign(CampaignAudience::default());
+    request.scheduled_at = Some(Utc::now() - Duration::hours(1));
+
+    let result = service.create_campaign(request, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_dispatch_sends_localized_content_to_audience() {
+    let au_worker = user("+61", UserType::Worker);
+    let cn_worker = user("+86", UserType::Worker);
+    let customer = user("+61", UserType::Customer);
+    let (service, _, _, sender) = create_service(
+        vec![au_worker.clone(), cn_worker.clone(), customer.clone()],
+        MockNotificationSender::default(),
+        CampaignServiceConfig::default(),
+    );
+
+    let audience = CampaignAudience {
+        user_type: Some(UserType::Worker),
+        ..Default::default()
+    };
+    let campaign = service.create_campaign(new_campaign(audience), Uuid::new_v4()).await.unwrap();
+
+    let result = service.dispatch().await.unwrap();
+    assert_eq!(result.started, 1);
+    assert_eq!(result.sent, 2);
+    assert_eq!(result.completed, 1);
+
+    let sent = sender.sent.lock().unwrap().clone();
+    assert!(sent.contains(&(au_worker.id, "Spring offer".to_string())));
+    assert!(sent.contains(&(cn_worker.id, "春季优惠".to_string())));
+    assert!(!sent.iter().any(|(id, _)| *id == customer.id));
+
+    let (campaign, stats) = service.get_campaign(campaign.id).await.unwrap();
+    assert_eq!(campaign.status, CampaignStatus::Completed);
+    assert_eq!(campaign.audience_size, 2);
+    assert_eq!(stats.sent, 2);
+}
+
+#[tokio::test]
+async fn test_dispatch_throttles_sends_per_cycle() {
+    let users: Vec<User> = (0..5).map(|_| user("+61", UserType::Customer)).collect();
+    let config = CampaignServiceConfig {
+        max_sends_per_cycle: 2,
+        ..Default::default()
+    };
+    let (service, _, _, _) = create_service(users, MockNotificationSender::default(), config);
+    let campaign = service
+        .create_campaign(new_campaign(CampaignAudience::default()), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    assert_eq!(service.dispatch().await.unwrap().sent, 2);
+    assert_eq!(service.dispatch().await.unwrap().sent, 2);
+
+    let (campaign_state, stats) = service.get_campaign(campaign.id).await.unwrap();
+    assert_eq!(campaign_state.status, CampaignStatus::Sending);
+    assert_eq!(stats.pending, 1);
+
+    let result = service.dispatch().await.unwrap();
+    assert_eq!(result.sent, 1);
+    assert_eq!(result.completed, 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_dispatchers_send_each_delivery_once() {
+    let users: Vec<User> = (0..6).map(|_| user("+61", UserType::Customer)).collect();
+    let (first, campaigns, preferences, sender) =
+        create_service(users, MockNotificationSender::default(), CampaignServiceConfig::default());
+    let second = CampaignService::new(
+        campaigns.clone(),
+        preferences.clone(),
+        sender.clone(),
+        CampaignServiceConfig::default(),
+    );
+    first
+        .create_campaign(new_campaign(CampaignAudience::default()), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let (a, b) = tokio::join!(first.dispatch(), second.dispatch());
+    let (a, b) = (a.unwrap(), b.unwrap());
+
+    assert_eq!(a.started + b.started, 1);
+    assert_eq!(a.sent + b.sent, 6);
+    let mut recipients: Vec<Uuid> = sender.sent.lock().unwrap().iter().map(|(id, _)| *id).collect();
+    recipients.sort();
+    recipients.dedup();
+    assert_eq!(recipients.len(), 6);
+    assert_eq!(sender.sent.lock().unwrap().len(), 6);
+}
+
+#[tokio::test]
+async fn test_dispatch_honours_opt_out_quiet_hours_and_failures() {
+    let opted_out = user("+61", UserType::Customer);
+    let quiet = user("+61", UserType::Customer);
+    let failing = user("+61", UserType::Customer);
+    let sender = MockNotificationSender {
+        failing_users: vec![failing.id],
+        ..Default::default()
+    };
+    let (service, campaigns, preferences, _) = create_service(
+        vec![opted_out.clone(), quiet.clone(), failing.clone()],
+        sender,
+        CampaignServiceConfig::default(),
+    );
+
+    let mut opt_out_preferences = NotificationPreferences::new(opted_out.id);
+    opt_out_preferences.marketing_enabled = false;
+    preferences.upsert(opt_out_preferences).await.unwrap();
+
+    // Quiet hours from an hour ago until an hour from now
+    let now = Utc::now().time();
+    let quiet_preferences = NotificationPreferences::new(quiet.id)
+        .with_quiet_hours(now - Duration::hours(1), now + Duration::hours(1));
+    preferences.upsert(quiet_preferences).await.unwrap();
+
+    let campaign = service
+        .create_campaign(new_campaign(CampaignAudience::default()), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    let result = service.dispatch().await.unwrap();
+    assert_eq!(result.opted_out, 1);
+    assert_eq!(result.deferred, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.completed, 0);
+
+    let deferred = campaigns
+        .deliveries()
+        .into_iter()
+        .find(|d| d.user_id == quiet.id)
+        .unwrap();
+    assert_eq!(deferred.status, DeliveryStatus::Pending);
+    assert!(deferred.next_attempt_at > Utc::now());
+
+    let (_, stats) = service.get_campaign(campaign.id).await.unwrap();
+    assert_eq!(stats.opted_out, 1);
+    assert_eq!(stats.failed, 1);
+    assert_eq!(stats.pending, 1);
+}
+
+#[tokio::test]
+async fn test_record_open_counts_once_for_owner() {
+    let recipient = user("+61", UserType::Customer);
+    let (service, campaigns, _, _) = create_service(
+        vec![recipient.clone()],
+        MockNotificationSender::default(),
+        CampaignServiceConfig::default(),
+    );
+    let campaign = service
+        .create_campaign(new_campaign(CampaignAudience::default()), Uuid::new_v4())
+        .await
+        .unwrap();
+    service.dispatch().await.unwrap();
+
+    let delivery = campaigns.deliveries().pop().unwrap();
+
+    let result = service.record_open(delivery.id, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+
+    service.record_open(delivery.id, recipient.id).await.unwrap();
+    service.record_open(delivery.id, recipient.id).await.unwrap();
+
+    let (_, stats) = service.get_campaign(campaign.id).await.unwrap();
+    assert_eq!(stats.opened, 1);
+    assert_eq!(stats.open_rate(), 1.0);
+}
+
+#[tokio::test]
+async fn test_cancel_campaign_stops_dispatch() {
+    let (service, campaigns, _, sender) = create_service(
+        vec![user("+61", UserType::Customer)],
+        MockNotificationSender::default(),
+        CampaignServiceConfig::default(),
+    );
+    let mut request = new_campaign(CampaignAudience::default());
+    request.scheduled_at = Some(Utc::now() + Duration::hours(1));
+    let campaign = service.create_campaign(request, Uuid::new_v4()).await.unwrap();
+
+    let cancelled = service.cancel_campaign(campaign.id).await.unwrap();
+    assert_eq!(cancelled.status, CampaignStatus::Cancelled);
+
+    let result = service.cancel_campaign(campaign.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    service.dispatch().await.unwrap();
+    assert!(campaigns.deliveries().is_empty());
+    assert!(sender.sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sms_campaign_requires_sms_channel() {
+    let (service, _, _, _) = create_service(vec![], MockNotificationSender::default(), CampaignServiceConfig::default());
+
+    let mut request = new_campaign(CampaignAudience::default());
+    request.channel = CampaignChannel::Sms;
+
+    let result = service.create_campaign(request, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_sms_campaign_sends_body_to_phone_numbers() {
+    let au_customer = user("+61", UserType::Customer);
+    let cn_customer = user("+86", UserType::Customer);
+    let opted_out = user("+61", UserType::Customer);
+    let no_phone = user("+61", UserType::Customer);
+    let config = CampaignServiceConfig {
+        max_sms_per_cycle: 2,
+        ..Default::default()
+    };
+    let (service, _, preferences, sender) = create_service(
+        vec![au_customer.clone(), cn_customer.clone(), opted_out.clone(), no_phone.clone()],
+        MockNotificationSender::default(),
+        config,
+    );
+
+    let sms = Arc::new(MockSmsService::default());
+    let phones = Arc::new(MockPhoneDirectory {
+        phones: HashMap::from([
+            (au_customer.id, "+61412345678".to_string()),
+            (cn_customer.id, "+8613812345678".to_string()),
+            (opted_out.id, "+61487654321".to_string()),
+        ]),
+    });
+    let service = service.with_sms_channel(sms.clone(), phones);
+
+    let mut opt_out_preferences = NotificationPreferences::new(opted_out.id);
+    opt_out_preferences.marketing_enabled = false;
+    preferences.upsert(opt_out_preferences).await.unwrap();
+
+    let mut request = new_campaign(CampaignAudience::default());
+    request.channel = CampaignChannel::Sms;
+    let campaign = service.create_campaign(request, Uuid::new_v4()).await.unwrap();
+    assert_eq!(campaign.channel, CampaignChannel::Sms);
+
+    // Two SMS per cycle, however large the overall budget
+    let first = service.dispatch().await.unwrap();
+    assert_eq!(first.total_processed(), 2);
+    let second = service.dispatch().await.unwrap();
+    assert_eq!(second.total_processed(), 2);
+    assert_eq!(second.completed, 1);
+
+    let sent = sms.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 2);
+    assert!(sent.contains(&("+61412345678".to_string(), "10% off kitchen renovations".to_string())));
+    assert!(sent.contains(&("+8613812345678".to_string(), "厨房翻新九折".to_string())));
+    assert!(sender.sent.lock().unwrap().is_empty());
+
+    let (_, stats) = service.get_campaign(campaign.id).await.unwrap();
+    assert_eq!(stats.sent, 2);
+    assert_eq!(stats.opted_out, 1);
+    assert_eq!(stats.failed, 1);
+}
@@ -0,0 +1,42 @@
+//! Traits for campaign delivery channels
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Trait for channels that deliver campaign notifications to users
+///
+/// Users are addressed by ID because phone numbers are only stored as
+/// hashes; the channel resolves its own device or inbox for the user.
+#[async_trait]
+pub trait NotificationSenderTrait: Send + Sync {
+    /// Send a notification to a user
+    ///
+    /// `delivery_id` should be attached to the notification so the client
+    /// can report when it is opened.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Channel message ID
+    /// * `Err(String)` - Why the channel rejected the message
+    async fn send_notification(
+        &self,
+        user_id: Uuid,
+        delivery_id: Uuid,
+        title: &str,
+        body: &str,
+    ) -> Result<String, String>;
+}
+
+/// Trait for looking up the phone number SMS campaigns are sent to
+///
+/// The users table only keeps phone hashes, so SMS campaigns need a
+/// directory that can reveal a user's number at send time.
+#[async_trait]
+pub trait UserPhoneDirectoryTrait: Send + Sync {
+    /// Phone number of a user in E.164 format
+    ///
+    /// # Returns
+    /// * `Ok(Some(String))` - The user's phone number
+    /// * `Ok(None)` - No number is on file for the user
+    /// * `Err(String)` - The directory could not be reached
+    async fn phone_for(&self, user_id: Uuid) -> Result<Option<String>, String>;
+}
@@ -0,0 +1,68 @@
+//! Typed cluster events
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::services::token::TokenRevocation;
+
+/// Notification sent from one API instance to all of them
+///
+/// Events are encoded as JSON tagged with their `type`, so instances
+/// running an older release ignore event types they don't know.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClusterEvent {
+    /// A token or session was revoked
+    TokenRevoked {
+        /// What was revoked
+        revocation: TokenRevocation,
+    },
+    /// A configuration section was changed and must be reloaded
+    ConfigReloaded {
+        /// Name of the section, e.g. `ip_access` or `log_level`
+        section: String,
+    },
+    /// Maintenance mode was switched on or off
+    MaintenanceModeToggled {
+        /// Whether the platform is in maintenance
+        enabled: bool,
+        /// Message shown to clients while in maintenance
+        message: Option<String>,
+        /// Administrator who toggled maintenance mode
+        toggled_by: Option<Uuid>,
+    },
+    /// Cache entries were changed and copies kept in process memory are stale
+    CacheInvalidated {
+        /// Keys of the changed entries
+        keys: Vec<String>,
+    },
+    /// A runtime log level override was applied
+    LogLevelOverridden {
+        /// Global level, if the override sets one
+        level: Option<String>,
+        /// Level per target
+        targets: BTreeMap<String, String>,
+        /// Time the override reverts to the configured filter
+        expires_at: DateTime<Utc>,
+        /// Administrator who applied the override
+        set_by: Option<Uuid>,
+    },
+    /// The runtime log level override was cleared
+    LogLevelCleared,
+}
+
+impl ClusterEvent {
+    /// Name of the event type, as used in the JSON encoding
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::TokenRevoked { .. } => "token_revoked",
+            Self::ConfigReloaded { .. } => "config_reloaded",
+            Self::MaintenanceModeToggled { .. } => "maintenance_mode_toggled",
+            Self::CacheInvalidated { .. } => "cache_invalidated",
+            Self::LogLevelOverridden { .. } => "log_level_overridden",
+            Self::LogLevelCleared => "log_level_cleared",
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Notifications exchanged between API instances
+//!
+//! Some changes made on one instance must reach every other instance of
+//! the cluster: a revoked token has to be rejected everywhere, reloaded
+//! configuration applied everywhere, and maintenance mode switched on
+//! everywhere. This module provides:
+//! - `ClusterEvent`, the typed notifications sent between instances
+//! - A trait for publishing events, implemented over Redis pub/sub in the
+//!   infrastructure layer
+//! - A trait for handling the events an instance receives
+
+mod events;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use events::ClusterEvent;
+pub use traits::{ClusterEventHandlerTrait, ClusterEventPublisherTrait};
@@ -0,0 +1,78 @@
+//! Tests for the encoding of cluster events
+
+use chrono::{TimeZone, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::services::cluster::ClusterEvent;
+use crate::services::token::TokenRevocation;
+
+#[test]
+fn test_events_round_trip_through_json() {
+    let events = vec![
+        ClusterEvent::TokenRevoked {
+            revocation: TokenRevocation::AccessToken {
+                jti: "jti-1".to_string(),
+                expires_at: Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap(),
+            },
+        },
+        ClusterEvent::TokenRevoked {
+            revocation: TokenRevocation::TokenFamily {
+                token_family: "family-1".to_string(),
+            },
+        },
+        ClusterEvent::ConfigReloaded {
+            section: "ip_access".to_string(),
+        },
+        ClusterEvent::MaintenanceModeToggled {
+            enabled: true,
+            message: Some("Back at 02:00 UTC".to_string()),
+            toggled_by: Some(Uuid::new_v4()),
+        },
+        ClusterEvent::CacheInvalidated {
+            keys: vec!["verification:code:account_lock:user-1".to_string()],
+        },
+        ClusterEvent::LogLevelOverridden {
+            level: None,
+            targets: BTreeMap::from([("re_core::services::auth".to_string(), "debug".to_string())]),
+            expires_at: Utc.with_ymd_and_hms(2030, 1, 1, 0, 15, 0).unwrap(),
+            set_by: Some(Uuid::new_v4()),
+        },
+        ClusterEvent::LogLevelCleared,
+    ];
+
+    for event in events {
+        let json = serde_json::to_string(&event).unwrap();
+        let decoded: ClusterEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
+
+#[test]
+fn test_event_type_matches_json_tag() {
+    let event = ClusterEvent::MaintenanceModeToggled {
+        enabled: false,
+        message: None,
+        toggled_by: None,
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["type"], event.event_type());
+    assert_eq!(json["enabled"], false);
+
+    let event = ClusterEvent::ConfigReloaded {
+        section: "log_level".to_string(),
+    };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["type"], "config_reloaded");
+    assert_eq!(json["section"], "log_level");
+
+    let json = serde_json::to_value(ClusterEvent::LogLevelCleared).unwrap();
+    assert_eq!(json["type"], ClusterEvent::LogLevelCleared.event_type());
+}
+
+#[test]
+fn test_unknown_event_types_are_rejected() {
+    let result = serde_json::from_str::<ClusterEvent>(r#"{"type":"cache_flushed"}"#);
+    assert!(result.is_err());
+}
@@ -0,0 +1,4 @@
+//! Tests for cluster events
+
+#[cfg(test)]
+pub(crate) mod events_tests;
@@ -0,0 +1,23 @@
+//! Traits for publishing and handling cluster events
+
+use async_trait::async_trait;
+
+use super::events::ClusterEvent;
+
+/// Trait for sending events to every instance of the cluster
+#[async_trait]
+pub trait ClusterEventPublisherTrait: Send + Sync {
+    /// Publish an event
+    ///
+    /// Delivery is best effort: instances that are disconnected when the
+    /// event is published never receive it. The publishing instance
+    /// receives its own events too.
+    async fn publish(&self, event: &ClusterEvent) -> Result<(), String>;
+}
+
+/// Trait for applying the events received by an instance
+#[async_trait]
+pub trait ClusterEventHandlerTrait: Send + Sync {
+    /// Apply an event
+    async fn handle(&self, event: ClusterEvent);
+}
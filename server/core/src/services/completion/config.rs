@@ -0,0 +1,22 @@
+//! Configuration for the order completion service
+
+/// Configuration for the order completion service
+#[derive(Debug, Clone)]
+pub struct CompletionConfig {
+    /// Most photos the worker can attach to one item
+    pub max_photos_per_item: usize,
+    /// Longest note the worker can add to an item, in characters
+    pub max_note_length: usize,
+    /// Longest device description stored with a sign-off, in characters
+    pub max_device_info_length: usize,
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        Self {
+            max_photos_per_item: 10,
+            max_note_length: 500,
+            max_device_info_length: 255,
+        }
+    }
+}
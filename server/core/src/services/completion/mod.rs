@@ -0,0 +1,15 @@
+//! Order completion service module
+//!
+//! This module handles:
+//! - Versioning completion checklists per job category
+//! - Checking the worker's checklist against the template, with photos per item
+//! - Capturing the customer's sign-off, which fixes the checklist for invoicing
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::CompletionConfig;
+pub use service::{ChecklistItemInput, ChecklistSubmission, CompletionService};
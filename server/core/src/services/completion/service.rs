@@ -0,0 +1,363 @@
+//! Order completion service
+//!
+//! Checklist templates are never edited: a change is a new version for the
+//! category, and each checklist keeps the version it was filled in from.
+//! The worker may resubmit until the customer signs off; the sign-off is
+//! stored once and never replaced, and invoices only use a checklist whose
+//! content still matches the hash the customer signed.
+
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::domain::entities::completion::{
+    ChecklistItemSpec, ChecklistTemplate, CompletedItem, CompletionChecklist,
+};
+use crate::domain::entities::fee_schedule::normalize_category;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::CompletionChecklistRepository;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookService};
+
+use super::config::CompletionConfig;
+
+/// A checklist item as filled in by the worker
+#[derive(Debug, Clone)]
+pub struct ChecklistItemInput {
+    /// Key of the template item
+    pub key: String,
+    /// Storage keys of the uploaded photos
+    pub photos: Vec<String>,
+    /// Optional remark
+    pub note: Option<String>,
+}
+
+/// A worker's checklist for a finished job
+#[derive(Debug, Clone)]
+pub struct ChecklistSubmission {
+    /// Order the checklist completes
+    pub order_id: Uuid,
+    /// Customer who signs off
+    pub customer_id: Uuid,
+    /// Job category, choosing the template
+    pub category: Option<String>,
+    /// Every item of the template
+    pub items: Vec<ChecklistItemInput>,
+}
+
+/// Service managing completion checklists and customer sign-off
+pub struct CompletionService<C>
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    checklists: Arc<C>,
+    config: CompletionConfig,
+    lifecycle_hooks: Option<Arc<LifecycleHookService>>,
+}
+
+impl<C> CompletionService<C>
+where
+    C: CompletionChecklistRepository + 'static,
+{
+    /// Create a new order completion service
+    pub fn new(checklists: Arc<C>, config: CompletionConfig) -> Self {
+        Self {
+            checklists,
+            config,
+            lifecycle_hooks: None,
+        }
+    }
+
+    /// Run lifecycle hooks when a customer signs off an order
+    pub fn with_lifecycle_hooks(mut self, hooks: Arc<LifecycleHookService>) -> Self {
+        self.lifecycle_hooks = Some(hooks);
+        self
+    }
+
+    /// Create the next checklist template version for a category
+    ///
+    /// # Arguments
+    /// * `category` - Job category, `None` for the default template
+    /// * `items` - Items in display order
+    /// * `created_by` - Administrator creating the version
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - No items, an empty key or label, or a duplicate key
+    pub async fn create_template(
+        &self,
+        category: Option<&str>,
+        items: Vec<ChecklistItemSpec>,
+        created_by: Option<Uuid>,
+    ) -> DomainResult<ChecklistTemplate> {
+        let category = normalized_category(category);
+        let version = self
+            .checklists
+            .latest_template(category.as_deref())
+            .await?
+            .map_or(1, |latest| latest.version + 1);
+
+        let template = ChecklistTemplate::new(category.as_deref(), version, items, created_by)
+            .map_err(|message| DomainError::Validation { message })?;
+        let template = self.checklists.save_template(template).await?;
+
+        info!(
+            template_id = %template.id,
+            category = ?template.category,
+            version = template.version,
+            "Completion checklist template created"
+        );
+
+        Ok(template)
+    }
+
+    /// Resolve the checklist template for a job category
+    ///
+    /// A template for the category takes precedence over the default one.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - Neither a category nor a default template exists
+    pub async fn template_for(&self, category: Option<&str>) -> DomainResult<ChecklistTemplate> {
+        if let Some(category) = normalized_category(category) {
+            if let Some(template) = self.checklists.latest_template(Some(&category)).await? {
+                return Ok(template);
+            }
+        }
+
+        self.checklists
+            .latest_template(None)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Completion checklist template".to_string(),
+            })
+    }
+
+    /// Submit the worker's checklist for a finished job
+    ///
+    /// Every template item must be present with at least its minimum
+    /// number of photos. Resubmitting replaces the earlier checklist until
+    /// the customer signs off.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - A missing, unknown or duplicate item,
+    ///   too few or too many photos, or a note that is too long
+    /// * `Err(DomainError::NotFound)` - No template applies, or another
+    ///   worker submitted the order's checklist
+    /// * `Err(DomainError::BusinessRule)` - The customer already signed off
+    pub async fn submit(
+        &self,
+        worker_id: Uuid,
+        submission: ChecklistSubmission,
+    ) -> DomainResult<CompletionChecklist> {
+        if let Some(existing) = self.checklists.find_checklist(submission.order_id).await? {
+            if existing.worker_id != worker_id {
+                return Err(DomainError::NotFound {
+                    resource: "Order".to_string(),
+                });
+            }
+            if existing.is_signed() {
+                return Err(already_signed());
+            }
+        }
+
+        let template = self.template_for(submission.category.as_deref()).await?;
+        let items = self.completed_items(&template, submission.items)?;
+
+        let checklist = CompletionChecklist {
+            order_id: submission.order_id,
+            worker_id,
+            customer_id: submission.customer_id,
+            template_id: template.id,
+            template_version: template.version,
+            items,
+            submitted_at: Utc::now(),
+            sign_off: None,
+        };
+        if !self.checklists.save_checklist(&checklist).await? {
+            return Err(already_signed());
+        }
+
+        info!(
+            order_id = %checklist.order_id,
+            worker_id = %worker_id,
+            template_version = checklist.template_version,
+            "Completion checklist submitted"
+        );
+
+        Ok(checklist)
+    }
+
+    /// Find an order's checklist for its worker or customer
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No checklist, or the user is not a party to the order
+    pub async fn find(&self, user_id: Uuid, order_id: Uuid) -> DomainResult<CompletionChecklist> {
+        self.checklists
+            .find_checklist(order_id)
+            .await?
+            .filter(|c| c.worker_id == user_id || c.customer_id == user_id)
+            .ok_or_else(checklist_not_found)
+    }
+
+    /// Record the customer's sign-off of the submitted checklist
+    ///
+    /// # Arguments
+    /// * `signed_name` - Full name the customer typed
+    /// * `device_info` - Device description sent by the app
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - The name is shorter than two characters or longer than 100
+    /// * `Err(DomainError::NotFound)` - No checklist, or the order belongs to another customer
+    /// * `Err(DomainError::BusinessRule)` - The checklist was already signed off
+    pub async fn sign_off(
+        &self,
+        customer_id: Uuid,
+        order_id: Uuid,
+        signed_name: &str,
+        device_info: Option<&str>,
+    ) -> DomainResult<CompletionChecklist> {
+        let signed_name = signed_name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !(2..=100).contains(&signed_name.chars().count()) {
+            return Err(DomainError::Validation {
+                message: "Type your full name to sign off".to_string(),
+            });
+        }
+        let device_info = device_info
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(|d| d.chars().take(self.config.max_device_info_length).collect::<String>());
+
+        let mut checklist = self
+            .checklists
+            .find_checklist(order_id)
+            .await?
+            .filter(|c| c.customer_id == customer_id)
+            .ok_or_else(checklist_not_found)?;
+
+        let signed_at = Utc::now();
+        let sign_off = checklist
+            .sign(signed_name, device_info, signed_at)
+            .map_err(|message| DomainError::BusinessRule { message })?;
+        if !self.checklists.record_sign_off(order_id, sign_off).await? {
+            return Err(already_signed());
+        }
+
+        info!(order_id = %order_id, customer_id = %customer_id, "Completion signed off");
+        if let Some(ref hooks) = self.lifecycle_hooks {
+            hooks.publish(LifecycleEvent::OrderCompleted {
+                order_id,
+                customer_id,
+                worker_id: checklist.worker_id,
+                completed_at: signed_at,
+            });
+        }
+        Ok(checklist)
+    }
+
+    /// The signed checklist of an order, for invoicing
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - The order has no checklist
+    /// * `Err(DomainError::BusinessRule)` - The customer has not signed off
+    /// * `Err(DomainError::Internal)` - The stored checklist no longer matches the sign-off
+    pub async fn signed_completion(&self, order_id: Uuid) -> DomainResult<CompletionChecklist> {
+        let checklist = self
+            .checklists
+            .find_checklist(order_id)
+            .await?
+            .ok_or_else(checklist_not_found)?;
+
+        if !checklist.is_signed() {
+            return Err(DomainError::BusinessRule {
+                message: "The customer has not signed off the completion".to_string(),
+            });
+        }
+        if !checklist.matches_sign_off() {
+            error!(order_id = %order_id, "Completion checklist changed after sign-off");
+            return Err(DomainError::Internal {
+                message: format!("Completion checklist of order {} does not match its sign-off", order_id),
+            });
+        }
+
+        Ok(checklist)
+    }
+
+    /// Check the submitted items against the template, in template order
+    fn completed_items(
+        &self,
+        template: &ChecklistTemplate,
+        mut inputs: Vec<ChecklistItemInput>,
+    ) -> DomainResult<Vec<CompletedItem>> {
+        let invalid = |message: String| DomainError::Validation { message };
+
+        for input in &mut inputs {
+            input.key = input.key.trim().to_ascii_lowercase();
+        }
+        if let Some(unknown) = inputs.iter().find(|i| !template.items.iter().any(|s| s.key == i.key)) {
+            return Err(invalid(format!("Unknown checklist item: {}", unknown.key)));
+        }
+
+        let mut items = Vec::with_capacity(template.items.len());
+        for spec in &template.items {
+            let mut matching = inputs.iter().filter(|i| i.key == spec.key);
+            let input = matching
+                .next()
+                .ok_or_else(|| invalid(format!("Checklist item is missing: {}", spec.key)))?;
+            if matching.next().is_some() {
+                return Err(invalid(format!("Duplicate checklist item: {}", spec.key)));
+            }
+
+            let photos: Vec<String> = input
+                .photos
+                .iter()
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            if photos.len() < spec.min_photos as usize {
+                return Err(invalid(format!(
+                    "Checklist item {} needs at least {} photos",
+                    spec.key, spec.min_photos
+                )));
+            }
+            if photos.len() > self.config.max_photos_per_item {
+                return Err(invalid(format!(
+                    "Checklist item {} has more than {} photos",
+                    spec.key, self.config.max_photos_per_item
+                )));
+            }
+
+            let note = input.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+            if note.is_some_and(|n| n.chars().count() > self.config.max_note_length) {
+                return Err(invalid(format!(
+                    "Note of checklist item {} is longer than {} characters",
+                    spec.key, self.config.max_note_length
+                )));
+            }
+
+            items.push(CompletedItem {
+                key: spec.key.clone(),
+                label: spec.label.clone(),
+                photos,
+                note: note.map(str::to_string),
+            });
+        }
+
+        Ok(items)
+    }
+}
+
+/// Normalize an optional category, treating blank as the default template
+fn normalized_category(category: Option<&str>) -> Option<String> {
+    category.map(normalize_category).filter(|c| !c.is_empty())
+}
+
+fn checklist_not_found() -> DomainError {
+    DomainError::NotFound {
+        resource: "Completion checklist".to_string(),
+    }
+}
+
+fn already_signed() -> DomainError {
+    DomainError::BusinessRule {
+        message: "The customer already signed off the completion".to_string(),
+    }
+}
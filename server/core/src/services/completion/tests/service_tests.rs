@@ -0,0 +1,227 @@
+//! Unit tests for the order completion service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::completion::{
+    ChecklistItemSpec, ChecklistTemplate, CompletionChecklist, CustomerSignOff,
+};
+use crate::errors::DomainError;
+use crate::repositories::CompletionChecklistRepository;
+use crate::services::completion::{
+    ChecklistItemInput, ChecklistSubmission, CompletionConfig, CompletionService,
+};
+
+#[derive(Default)]
+struct MockChecklists {
+    templates: Mutex<Vec<ChecklistTemplate>>,
+    checklists: Mutex<Vec<CompletionChecklist>>,
+}
+
+#[async_trait]
+impl CompletionChecklistRepository for MockChecklists {
+    async fn save_template(&self, template: ChecklistTemplate) -> Result<ChecklistTemplate, DomainError> {
+        self.templates.lock().unwrap().push(template.clone());
+        Ok(template)
+    }
+
+    async fn latest_template(&self, category: Option<&str>) -> Result<Option<ChecklistTemplate>, DomainError> {
+        Ok(self
+            .templates
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|t| t.category.as_deref() == category)
+            .max_by_key(|t| t.version)
+            .cloned())
+    }
+
+    async fn save_checklist(&self, checklist: &CompletionChecklist) -> Result<bool, DomainError> {
+        let mut checklists = self.checklists.lock().unwrap();
+        match checklists.iter_mut().find(|c| c.order_id == checklist.order_id) {
+            Some(existing) if existing.is_signed() => return Ok(false),
+            Some(existing) => *existing = checklist.clone(),
+            None => checklists.push(checklist.clone()),
+        }
+        Ok(true)
+    }
+
+    async fn find_checklist(&self, order_id: Uuid) -> Result<Option<CompletionChecklist>, DomainError> {
+        Ok(self.checklists.lock().unwrap().iter().find(|c| c.order_id == order_id).cloned())
+    }
+
+    async fn record_sign_off(&self, order_id: Uuid, sign_off: &CustomerSignOff) -> Result<bool, DomainError> {
+        let mut checklists = self.checklists.lock().unwrap();
+        match checklists.iter_mut().find(|c| c.order_id == order_id && !c.is_signed()) {
+            Some(existing) => {
+                existing.sign_off = Some(sign_off.clone());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+fn spec(key: &str, min_photos: u32) -> ChecklistItemSpec {
+    ChecklistItemSpec {
+        key: key.to_string(),
+        label: key.replace('_', " "),
+        min_photos,
+    }
+}
+
+fn input(key: &str, photos: usize) -> ChecklistItemInput {
+    ChecklistItemInput {
+        key: key.to_string(),
+        photos: (0..photos).map(|i| format!("uploads/{}-{}.jpg", key, i)).collect(),
+        note: None,
+    }
+}
+
+fn submission(order_id: Uuid, customer_id: Uuid, items: Vec<ChecklistItemInput>) -> ChecklistSubmission {
+    ChecklistSubmission {
+        order_id,
+        customer_id,
+        category: Some("Bathroom".to_string()),
+        items,
+    }
+}
+
+async fn service_with_templates() -> (CompletionService<MockChecklists>, Arc<MockChecklists>) {
+    let repo = Arc::new(MockChecklists::default());
+    let service = CompletionService::new(repo.clone(), CompletionConfig::default());
+    service
+        .create_template(None, vec![spec("site_cleaned", 1)], None)
+        .await
+        .unwrap();
+    service
+        .create_template(Some("bathroom"), vec![spec("grout_sealed", 2), spec("site_cleaned", 1)], None)
+        .await
+        .unwrap();
+    (service, repo)
+}
+
+#[tokio::test]
+async fn test_templates_are_versioned_per_category_with_default_fallback() {
+    let (service, _) = service_with_templates().await;
+
+    let updated = service
+        .create_template(Some(" BATHROOM "), vec![spec("grout_sealed", 3)], None)
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 2);
+    assert_eq!(updated.category.as_deref(), Some("bathroom"));
+
+    assert_eq!(service.template_for(Some("bathroom")).await.unwrap().id, updated.id);
+    let fallback = service.template_for(Some("kitchen")).await.unwrap();
+    assert_eq!(fallback.category, None);
+    assert_eq!(fallback.version, 1);
+
+    let empty = CompletionService::new(Arc::new(MockChecklists::default()), CompletionConfig::default());
+    assert!(matches!(empty.template_for(Some("kitchen")).await, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_submit_requires_every_item_with_its_photos() {
+    let (service, _) = service_with_templates().await;
+    let worker_id = Uuid::new_v4();
+    let order_id = Uuid::new_v4();
+    let customer_id = Uuid::new_v4();
+
+    let missing = submission(order_id, customer_id, vec![input("grout_sealed", 2)]);
+    assert!(matches!(service.submit(worker_id, missing).await, Err(DomainError::Validation { .. })));
+
+    let too_few = submission(order_id, customer_id, vec![input("grout_sealed", 1), input("site_cleaned", 1)]);
+    assert!(matches!(service.submit(worker_id, too_few).await, Err(DomainError::Validation { .. })));
+
+    let unknown = submission(
+        order_id,
+        customer_id,
+        vec![input("grout_sealed", 2), input("site_cleaned", 1), input("painted", 1)],
+    );
+    assert!(matches!(service.submit(worker_id, unknown).await, Err(DomainError::Validation { .. })));
+
+    let complete = submission(order_id, customer_id, vec![input("site_cleaned", 1), input("GROUT_SEALED", 2)]);
+    let checklist = service.submit(worker_id, complete).await.unwrap();
+    let keys: Vec<&str> = checklist.items.iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(keys, vec!["grout_sealed", "site_cleaned"]);
+    assert_eq!(checklist.template_version, 1);
+    assert!(!checklist.is_signed());
+
+    let other_worker = submission(order_id, customer_id, vec![input("grout_sealed", 2), input("site_cleaned", 1)]);
+    assert!(matches!(
+        service.submit(Uuid::new_v4(), other_worker).await,
+        Err(DomainError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_sign_off_is_recorded_once_and_locks_the_checklist() {
+    let (service, _) = service_with_templates().await;
+    let worker_id = Uuid::new_v4();
+    let order_id = Uuid::new_v4();
+    let customer_id = Uuid::new_v4();
+    let items = vec![input("grout_sealed", 2), input("site_cleaned", 1)];
+    service
+        .submit(worker_id, submission(order_id, customer_id, items.clone()))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        service.signed_completion(order_id).await,
+        Err(DomainError::BusinessRule { .. })
+    ));
+    assert!(matches!(
+        service.sign_off(worker_id, order_id, "Jane Citizen", None).await,
+        Err(DomainError::NotFound { .. })
+    ));
+    assert!(matches!(
+        service.sign_off(customer_id, order_id, " ", None).await,
+        Err(DomainError::Validation { .. })
+    ));
+
+    let signed = service
+        .sign_off(customer_id, order_id, "  Jane   Citizen ", Some("iPhone 15 / iOS 18.1"))
+        .await
+        .unwrap();
+    let sign_off = signed.sign_off.as_ref().unwrap();
+    assert_eq!(sign_off.signed_name, "Jane Citizen");
+    assert_eq!(sign_off.device_info.as_deref(), Some("iPhone 15 / iOS 18.1"));
+
+    assert!(matches!(
+        service.sign_off(customer_id, order_id, "Someone Else", None).await,
+        Err(DomainError::BusinessRule { .. })
+    ));
+    assert!(matches!(
+        service.submit(worker_id, submission(order_id, customer_id, items)).await,
+        Err(DomainError::BusinessRule { .. })
+    ));
+
+    let completion = service.signed_completion(order_id).await.unwrap();
+    assert_eq!(completion, signed);
+    assert_eq!(service.find(worker_id, order_id).await.unwrap(), signed);
+    assert!(service.find(Uuid::new_v4(), order_id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_signed_completion_detects_changes_after_sign_off() {
+    let (service, repo) = service_with_templates().await;
+    let order_id = Uuid::new_v4();
+    let customer_id = Uuid::new_v4();
+    service
+        .submit(
+            Uuid::new_v4(),
+            submission(order_id, customer_id, vec![input("grout_sealed", 2), input("site_cleaned", 1)]),
+        )
+        .await
+        .unwrap();
+    service.sign_off(customer_id, order_id, "Jane Citizen", None).await.unwrap();
+
+    repo.checklists.lock().unwrap()[0].items[0].photos.pop();
+
+    assert!(matches!(
+        service.signed_completion(order_id).await,
+        Err(DomainError::Internal { .. })
+    ));
+}
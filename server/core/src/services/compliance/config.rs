@@ -0,0 +1,36 @@
+//! Configuration for the compliance service
+
+use std::collections::HashMap;
+
+use crate::domain::entities::compliance::{ComplianceAction, ComplianceRequirement, ComplianceRule};
+
+/// Configuration for the compliance service
+#[derive(Debug, Clone)]
+pub struct ComplianceConfig {
+    /// Rules per market, keyed by country calling code (e.g. "+86")
+    pub markets: HashMap<String, Vec<ComplianceRule>>,
+    /// Submissions allowed per requirement before support has to step in
+    pub max_attempts: u32,
+}
+
+impl ComplianceConfig {
+    /// Rules of a market; markets without rules require nothing
+    pub fn rules_for(&self, market: &str) -> &[ComplianceRule] {
+        self.markets.get(market).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+impl Default for ComplianceConfig {
+    fn default() -> Self {
+        // Mainland China requires real-name verification before trading online
+        let china = vec![ComplianceRule::new(
+            ComplianceRequirement::RealName,
+            vec![ComplianceAction::Order, ComplianceAction::Payout],
+        )];
+
+        Self {
+            markets: HashMap::from([("+86".to_string(), china)]),
+            max_attempts: 5,
+        }
+    }
+}
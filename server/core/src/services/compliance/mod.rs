@@ -0,0 +1,22 @@
+//! Compliance service module for market-specific regulatory checks
+//!
+//! This module handles:
+//! - Listing the checks a user's market requires, such as real-name
+//!   verification or an ICP filing in China
+//! - Submitting checks to the market's verification provider
+//! - Applying results the provider reports later
+//! - Holding back orders and payouts until the required checks are verified
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ComplianceConfig;
+pub use service::ComplianceService;
+pub use traits::{
+    ComplianceGateTrait, ComplianceProviderTrait, ComplianceSubmission, ProviderComplianceOutcome,
+    ProviderComplianceResult,
+};
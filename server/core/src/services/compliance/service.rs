@@ -0,0 +1,285 @@
+//! Compliance service for market-specific regulatory checks
+//!
+//! A user's market is the country code of their phone number. Each market
+//! lists the checks its users must pass; submissions go to the provider
+//! registered for that market and requirement, and only the outcome and the
+//! last characters of the document number are stored. Orders and payouts
+//! held back by a rule are refused until its check is verified.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::{
+    ComplianceAction, ComplianceCheck, ComplianceRequirement, ComplianceRule, ComplianceStatus,
+    ComplianceStep,
+};
+use crate::domain::entities::user::User;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{ComplianceRepository, UserRepository};
+use crate::services::tenant;
+
+use super::config::ComplianceConfig;
+use super::traits::{
+    ComplianceGateTrait, ComplianceProviderTrait, ComplianceSubmission, ProviderComplianceOutcome,
+};
+
+/// Service managing users' market compliance checks
+pub struct ComplianceService<C, U>
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    checks: Arc<C>,
+    users: Arc<U>,
+    providers: HashMap<(String, ComplianceRequirement), Arc<dyn ComplianceProviderTrait>>,
+    config: ComplianceConfig,
+}
+
+impl<C, U> ComplianceService<C, U>
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    /// Create a new compliance service without providers
+    pub fn new(checks: Arc<C>, users: Arc<U>, config: ComplianceConfig) -> Self {
+        Self {
+            checks,
+            users,
+            providers: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Check a market's requirement with a provider
+    ///
+    /// # Arguments
+    /// * `market` - Country calling code of the market (e.g. "+86")
+    /// * `provider` - Provider checking the requirement it reports
+    pub fn with_provider(mut self, market: impl Into<String>, provider: Arc<dyn ComplianceProviderTrait>) -> Self {
+        self.providers.insert((market.into(), provider.requirement()), provider);
+        self
+    }
+
+    /// List the requirements of the user's market and their progress
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such user
+    pub async fn steps(&self, user_id: Uuid) -> DomainResult<Vec<ComplianceStep>> {
+        let user = self.find_user(user_id).await?;
+        self.steps_for(&user).await
+    }
+
+    /// Submit a user's details for a requirement of their market
+    ///
+    /// Submitting again for a verified requirement returns it unchanged.
+    ///
+    /// # Returns
+    /// * `Ok(ComplianceStep)` - The requirement's progress after the submission
+    /// * `Err(DomainError::Validation)` - Missing details, or the market does
+    ///   not require this of the user
+    /// * `Err(DomainError::BusinessRule)` - A submission is still being
+    ///   checked, or no submissions are left
+    /// * `Err(DomainError::Internal)` - No provider is configured, or it failed
+    pub async fn submit(
+        &self,
+        user_id: Uuid,
+        requirement: ComplianceRequirement,
+        submission: ComplianceSubmission,
+    ) -> DomainResult<ComplianceStep> {
+        if submission.legal_name.trim().is_empty() || submission.document_number.trim().is_empty() {
+            return Err(DomainError::Validation {
+                message: "Legal name and document number are required".to_string(),
+            });
+        }
+
+        let user = self.find_user(user_id).await?;
+        let market = user.country_code.clone();
+        let rule = self
+            .rules_for(&user)
+            .find(|rule| rule.requirement == requirement)
+            .cloned()
+            .ok_or_else(|| DomainError::Validation {
+                message: format!("{} is not required in market {}", requirement.as_str(), market),
+            })?;
+
+        let existing = self
+            .checks
+            .find_by_user(user_id)
+            .await?
+            .into_iter()
+            .find(|check| check.requirement == requirement);
+        match &existing {
+            Some(check) if check.is_verified() => return Ok(self.step(&rule, &market, Some(check))),
+            Some(check) if check.status == ComplianceStatus::Pending => {
+                return Err(DomainError::BusinessRule {
+                    message: format!("The {} submission is still being checked", requirement.as_str()),
+                });
+            }
+            Some(check) if check.attempts >= self.config.max_attempts => {
+                return Err(DomainError::BusinessRule {
+                    message: format!("No {} submissions left", requirement.as_str()),
+                });
+            }
+            _ => {}
+        }
+
+        let provider = self
+            .providers
+            .get(&(market.clone(), requirement))
+            .ok_or_else(|| DomainError::Internal {
+                message: format!("No {} provider configured for market {}", requirement.as_str(), market),
+            })?;
+        let result = provider.submit(user_id, &submission).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to submit {} check to {}: {}", requirement.as_str(), provider.provider_name(), e),
+        })?;
+
+        let mut check = existing.unwrap_or_else(|| {
+            ComplianceCheck::new(user_id, requirement, market.clone(), provider.provider_name())
+        });
+        check.provider = provider.provider_name().to_string();
+        check.submit(result.reference, &submission.document_number);
+        apply_outcome(&mut check, result.outcome);
+        let check = self.checks.save(check).await?;
+
+        info!(
+            user_id = %user_id,
+            requirement = requirement.as_str(),
+            status = check.status.as_str(),
+            "Compliance check submitted"
+        );
+
+        Ok(self.step(&rule, &market, Some(&check)))
+    }
+
+    /// Apply the result of a submission the provider reported later
+    ///
+    /// Provider results are received for every tenant; the result is applied
+    /// in the tenant of the check. Results for submissions that are no
+    /// longer pending are ignored.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No check for this submission
+    pub async fn apply_provider_result(
+        &self,
+        provider: &str,
+        reference: &str,
+        outcome: ProviderComplianceOutcome,
+    ) -> DomainResult<ComplianceCheck> {
+        let tenant = self
+            .checks
+            .find_tenant_by_reference(provider, reference)
+            .await?
+            .unwrap_or_default();
+
+        tenant::scope(tenant, self.apply_result_in_tenant(provider, reference, outcome)).await
+    }
+
+    /// Apply a provider result in the current tenant
+    async fn apply_result_in_tenant(
+        &self,
+        provider: &str,
+        reference: &str,
+        outcome: ProviderComplianceOutcome,
+    ) -> DomainResult<ComplianceCheck> {
+        let mut check = self
+            .checks
+            .find_by_reference(provider, reference)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Compliance check".to_string(),
+            })?;
+
+        if check.status != ComplianceStatus::Pending {
+            warn!(check_id = %check.id, status = check.status.as_str(), "Ignoring result for settled compliance check");
+            return Ok(check);
+        }
+
+        apply_outcome(&mut check, outcome);
+        let check = self.checks.save(check).await?;
+        info!(
+            user_id = %check.user_id,
+            requirement = check.requirement.as_str(),
+            status = check.status.as_str(),
+            "Compliance check completed"
+        );
+
+        Ok(check)
+    }
+
+    /// List the requirements of a user's market and their progress
+    async fn steps_for(&self, user: &User) -> DomainResult<Vec<ComplianceStep>> {
+        let rules: Vec<&ComplianceRule> = self.rules_for(user).collect();
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let checks = self.checks.find_by_user(user.id).await?;
+        Ok(rules
+            .into_iter()
+            .map(|rule| {
+                let check = checks.iter().find(|check| check.requirement == rule.requirement);
+                self.step(rule, &user.country_code, check)
+            })
+            .collect())
+    }
+
+    /// Rules of the user's market that apply to them
+    fn rules_for<'a>(&'a self, user: &User) -> impl Iterator<Item = &'a ComplianceRule> {
+        let user_type = user.user_type;
+        self.config
+            .rules_for(&user.country_code)
+            .iter()
+            .filter(move |rule| rule.applies_to(user_type))
+    }
+
+    /// Progress on a rule given the user's check for it
+    fn step(&self, rule: &ComplianceRule, market: &str, check: Option<&ComplianceCheck>) -> ComplianceStep {
+        let attempts = check.map_or(0, |check| check.attempts);
+        ComplianceStep {
+            requirement: rule.requirement,
+            market: market.to_string(),
+            status: check.map_or(ComplianceStatus::Required, |check| check.status),
+            gates: rule.gates.clone(),
+            rejection_reason: check.and_then(|check| check.rejection_reason.clone()),
+            attempts_remaining: self.config.max_attempts.saturating_sub(attempts),
+        }
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> DomainResult<User> {
+        self.users.find_by_id(user_id).await?.ok_or_else(|| DomainError::NotFound {
+            resource: "User".to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl<C, U> ComplianceGateTrait for ComplianceService<C, U>
+where
+    C: ComplianceRepository + 'static,
+    U: UserRepository + 'static,
+{
+    async fn outstanding_steps(&self, user: &User) -> DomainResult<Vec<ComplianceStep>> {
+        let steps = self.steps_for(user).await?;
+        Ok(steps
+            .into_iter()
+            .filter(|step| step.status != ComplianceStatus::Verified)
+            .collect())
+    }
+
+    async fn blocking_step(&self, user_id: Uuid, action: ComplianceAction) -> DomainResult<Option<ComplianceStep>> {
+        let steps = self.steps(user_id).await?;
+        Ok(steps.into_iter().find(|step| step.blocks(action)))
+    }
+}
+
+/// Record a provider's outcome on a check
+fn apply_outcome(check: &mut ComplianceCheck, outcome: ProviderComplianceOutcome) {
+    match outcome {
+        ProviderComplianceOutcome::Verified => check.verify(),
+        ProviderComplianceOutcome::Pending => {}
+        ProviderComplianceOutcome::Rejected(reason) => check.reject(reason),
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for the compliance service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,330 @@
+//! Unit tests for the compliance service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::{
+    ComplianceAction, ComplianceCheck, ComplianceRequirement, ComplianceRule, ComplianceStatus,
+};
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::{ComplianceRepository, UserRepository};
+use crate::services::compliance::{
+    ComplianceConfig, ComplianceGateTrait, ComplianceProviderTrait, ComplianceService,
+    ComplianceSubmission, ProviderComplianceOutcome, ProviderComplianceResult,
+};
+use crate::services::tenant::TenantId;
+
+#[derive(Default)]
+struct MockChecks {
+    checks: Mutex<Vec<ComplianceCheck>>,
+}
+
+#[async_trait]
+impl ComplianceRepository for MockChecks {
+    async fn save(&self, check: ComplianceCheck) -> Result<ComplianceCheck, DomainError> {
+        let mut checks = self.checks.lock().unwrap();
+        checks.retain(|c| !(c.user_id == check.user_id && c.requirement == check.requirement));
+        checks.push(check.clone());
+        Ok(check)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ComplianceCheck>, DomainError> {
+        Ok(self.checks.lock().unwrap().iter().filter(|c| c.user_id == user_id).cloned().collect())
+    }
+
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<ComplianceCheck>, DomainError> {
+        Ok(self
+            .checks
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|c| c.provider == provider && c.provider_reference.as_deref() == Some(provider_reference))
+            .cloned())
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        Ok(self
+            .find_by_reference(provider, provider_reference)
+            .await?
+            .map(|_| TenantId::default()))
+    }
+}
+
+#[derive(Default)]
+struct MockUserRepository {
+    users: Mutex<Vec<User>>,
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepository {
+    async fn find_by_phone(&self, _phone_hash: &str, _country_code: &str) -> Result<Option<User>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.users.lock().unwrap().iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        Ok(user)
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn exists_by_phone(&self, _phone_hash: &str, _country_code: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn count_by_type(&self, _user_type: Option<UserType>) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+}
+
+/// Provider whose outcome depends on the submitted document number
+///
+/// Numbers ending in "0000" are rejected, numbers ending in "1111" wait for
+/// a later result and all others are verified.
+#[derive(Default)]
+struct MockProvider {
+    submissions: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl ComplianceProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "mock_real_name"
+    }
+
+    fn requirement(&self) -> ComplianceRequirement {
+        ComplianceRequirement::RealName
+    }
+
+    async fn submit(
+        &self,
+        user_id: Uuid,
+        submission: &ComplianceSubmission,
+    ) -> Result<ProviderComplianceResult, String> {
+        let mut submissions = self.submissions.lock().unwrap();
+        submissions.push(user_id);
+
+        let outcome = if submission.document_number.ends_with("0000") {
+            ProviderComplianceOutcome::Rejected("Name does not match the document".to_string())
+        } else if submission.document_number.ends_with("1111") {
+            ProviderComplianceOutcome::Pending
+        } else {
+            ProviderComplianceOutcome::Verified
+        };
+        Ok(ProviderComplianceResult { reference: format!("rn_{}", submissions.len()), outcome })
+    }
+}
+
+type TestService = ComplianceService<MockChecks, MockUserRepository>;
+
+struct Fixture {
+    service: TestService,
+    users: Arc<MockUserRepository>,
+    provider: Arc<MockProvider>,
+}
+
+fn fixture() -> Fixture {
+    let mut config = ComplianceConfig { max_attempts: 2, ..ComplianceConfig::default() };
+    config.markets.get_mut("+86").unwrap().push(
+        ComplianceRule::new(ComplianceRequirement::IcpFiling, vec![ComplianceAction::Payout])
+            .for_user_types(vec![UserType::Worker]),
+    );
+
+    let users = Arc::new(MockUserRepository::default());
+    let provider = Arc::new(MockProvider::default());
+    let service = ComplianceService::new(Arc::new(MockChecks::default()), users.clone(), config)
+        .with_provider("+86", provider.clone());
+
+    Fixture { service, users, provider }
+}
+
+async fn create_user(f: &Fixture, country_code: &str, user_type: UserType) -> Uuid {
+    let mut user = User::new(format!("hash_{}", Uuid::new_v4()), country_code.to_string());
+    user.set_user_type(user_type);
+    f.users.create(user).await.unwrap().id
+}
+
+fn submission(document_number: &str) -> ComplianceSubmission {
+    ComplianceSubmission {
+        legal_name: "Wang Fang".to_string(),
+        document_number: document_number.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_steps_follow_the_users_market_and_type() {
+    let f = fixture();
+    let customer = create_user(&f, "+86", UserType::Customer).await;
+    let worker = create_user(&f, "+86", UserType::Worker).await;
+    let australian = create_user(&f, "+61", UserType::Worker).await;
+
+    let steps = f.service.steps(customer).await.unwrap();
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].requirement, ComplianceRequirement::RealName);
+    assert_eq!(steps[0].status, ComplianceStatus::Required);
+    assert_eq!(steps[0].attempts_remaining, 2);
+
+    let requirements: Vec<_> = f.service.steps(worker).await.unwrap().into_iter().map(|s| s.requirement).collect();
+    assert_eq!(requirements, vec![ComplianceRequirement::RealName, ComplianceRequirement::IcpFiling]);
+
+    assert!(f.service.steps(australian).await.unwrap().is_empty());
+    assert!(f.service.blocking_step(australian, ComplianceAction::Payout).await.unwrap().is_none());
+
+    let result = f.service.steps(Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_verified_submission_lifts_the_gate() {
+    let f = fixture();
+    let customer = create_user(&f, "+86", UserType::Customer).await;
+
+    let blocking = f.service.blocking_step(customer, ComplianceAction::Order).await.unwrap().unwrap();
+    assert_eq!(blocking.requirement, ComplianceRequirement::RealName);
+    let result = f.service.ensure_allowed(customer, ComplianceAction::Order).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let step = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("11010519491231002X"))
+        .await
+        .unwrap();
+    assert_eq!(step.status, ComplianceStatus::Verified);
+    assert!(f.service.blocking_step(customer, ComplianceAction::Order).await.unwrap().is_none());
+    f.service.ensure_allowed(customer, ComplianceAction::Payout).await.unwrap();
+
+    let user = f.users.find_by_id(customer).await.unwrap().unwrap();
+    let outstanding = f.service.outstanding_steps(&user).await.unwrap();
+    assert!(outstanding.is_empty());
+
+    // Submitting again returns the verified step without another provider call
+    let again = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("110105194912310000"))
+        .await
+        .unwrap();
+    assert_eq!(again.status, ComplianceStatus::Verified);
+    assert_eq!(f.provider.submissions.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_pending_submission_completes_with_provider_result() {
+    let f = fixture();
+    let customer = create_user(&f, "+86", UserType::Customer).await;
+
+    let step = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("110105194912311111"))
+        .await
+        .unwrap();
+    assert_eq!(step.status, ComplianceStatus::Pending);
+    assert!(f.service.blocking_step(customer, ComplianceAction::Order).await.unwrap().is_some());
+
+    let result = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("11010519491231002X"))
+        .await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let check = f
+        .service
+        .apply_provider_result("mock_real_name", "rn_1", ProviderComplianceOutcome::Verified)
+        .await
+        .unwrap();
+    assert!(check.is_verified());
+    assert_eq!(check.document_last4.as_deref(), Some("1111"));
+    assert!(f.service.blocking_step(customer, ComplianceAction::Order).await.unwrap().is_none());
+
+    // A late rejection does not undo the verification
+    let ignored = f
+        .service
+        .apply_provider_result("mock_real_name", "rn_1", ProviderComplianceOutcome::Rejected("late".to_string()))
+        .await
+        .unwrap();
+    assert!(ignored.is_verified());
+
+    let unknown = f
+        .service
+        .apply_provider_result("mock_real_name", "rn_unknown", ProviderComplianceOutcome::Verified)
+        .await;
+    assert!(matches!(unknown, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_rejected_submissions_are_limited() {
+    let f = fixture();
+    let customer = create_user(&f, "+86", UserType::Customer).await;
+
+    let step = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("110105194912310000"))
+        .await
+        .unwrap();
+    assert_eq!(step.status, ComplianceStatus::Rejected);
+    assert_eq!(step.rejection_reason.as_deref(), Some("Name does not match the document"));
+    assert_eq!(step.attempts_remaining, 1);
+
+    let step = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("110105194912310000"))
+        .await
+        .unwrap();
+    assert_eq!(step.attempts_remaining, 0);
+
+    let result = f
+        .service
+        .submit(customer, ComplianceRequirement::RealName, submission("11010519491231002X"))
+        .await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert_eq!(f.provider.submissions.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_invalid_submissions_are_refused() {
+    let f = fixture();
+    let customer = create_user(&f, "+86", UserType::Customer).await;
+    let worker = create_user(&f, "+86", UserType::Worker).await;
+    let australian = create_user(&f, "+61", UserType::Customer).await;
+
+    let result = f.service.submit(customer, ComplianceRequirement::RealName, submission("  ")).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    // The ICP filing only applies to workers in China
+    let result = f
+        .service
+        .submit(customer, ComplianceRequirement::IcpFiling, submission("京ICP备12345678号"))
+        .await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+    let result = f
+        .service
+        .submit(australian, ComplianceRequirement::RealName, submission("11010519491231002X"))
+        .await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    // No provider checks ICP filings yet
+    let result = f
+        .service
+        .submit(worker, ComplianceRequirement::IcpFiling, submission("京ICP备12345678号"))
+        .await;
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+}
@@ -0,0 +1,98 @@
+//! Traits for market compliance providers and the checks other services consult
+
+use async_trait::async_trait;
+use std::fmt;
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::{ComplianceAction, ComplianceRequirement, ComplianceStep};
+use crate::domain::entities::user::User;
+use crate::errors::{DomainError, DomainResult};
+
+/// What a user submits for a compliance requirement
+///
+/// For real-name verification this is the name and number on the national
+/// identity document; for an ICP filing the business name and filing number.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ComplianceSubmission {
+    /// Legal name of the person or business
+    pub legal_name: String,
+    /// Number of the identity document or filing
+    pub document_number: String,
+}
+
+impl fmt::Debug for ComplianceSubmission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComplianceSubmission")
+            .field("legal_name", &"[REDACTED]")
+            .field("document_number", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Outcome of a provider's check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderComplianceOutcome {
+    /// The submission matches the provider's records
+    Verified,
+    /// The provider is still checking; the result is applied later
+    Pending,
+    /// The submission does not match, with the provider's reason
+    Rejected(String),
+}
+
+/// A submission as reported by the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderComplianceResult {
+    /// Provider's identifier for the submission
+    pub reference: String,
+    /// Outcome of the check
+    pub outcome: ProviderComplianceOutcome,
+}
+
+/// Trait for a provider checking one compliance requirement
+#[async_trait]
+pub trait ComplianceProviderTrait: Send + Sync {
+    /// Name of the provider, stored with each check
+    fn provider_name(&self) -> &str;
+
+    /// Requirement the provider checks
+    fn requirement(&self) -> ComplianceRequirement;
+
+    /// Submit a user's details for checking
+    ///
+    /// # Arguments
+    /// * `user_id` - User the submission belongs to
+    /// * `submission` - Details to check; they are not stored by the platform
+    async fn submit(
+        &self,
+        user_id: Uuid,
+        submission: &ComplianceSubmission,
+    ) -> Result<ProviderComplianceResult, String>;
+}
+
+/// Trait for checking whether compliance requirements hold a user back
+#[async_trait]
+pub trait ComplianceGateTrait: Send + Sync {
+    /// Requirements of the user's market that are not verified yet
+    async fn outstanding_steps(&self, user: &User) -> DomainResult<Vec<ComplianceStep>>;
+
+    /// The first unverified requirement holding back an action, if any
+    async fn blocking_step(&self, user_id: Uuid, action: ComplianceAction) -> DomainResult<Option<ComplianceStep>>;
+
+    /// Refuse an action a requirement still holds back
+    ///
+    /// # Returns
+    /// * `Err(DomainError::BusinessRule)` - A required check is not verified
+    async fn ensure_allowed(&self, user_id: Uuid, action: ComplianceAction) -> DomainResult<()> {
+        match self.blocking_step(user_id, action).await? {
+            Some(step) => Err(DomainError::BusinessRule {
+                message: format!(
+                    "The {} check is required before this {}",
+                    step.requirement.as_str(),
+                    action.as_str()
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Configuration for the credit wallet service
+
+/// Configuration for the credit wallet service
+#[derive(Debug, Clone)]
+pub struct CreditWalletConfig {
+    /// Days until promotional credit expires when no expiry is given; 0 never expires
+    pub promotion_expiry_days: i64,
+    /// Interval between runs of the expiry job, in seconds
+    pub expiry_interval_seconds: u64,
+    /// Maximum number of grants expired per run
+    pub expiry_batch_size: usize,
+    /// Whether to enable the background expiry job
+    pub expiry_enabled: bool,
+}
+
+impl Default for CreditWalletConfig {
+    fn default() -> Self {
+        Self {
+            promotion_expiry_days: 90,
+            expiry_interval_seconds: 3_600, // Hourly
+            expiry_batch_size: 500,
+            expiry_enabled: true,
+        }
+    }
+}
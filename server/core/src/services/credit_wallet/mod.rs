@@ -0,0 +1,18 @@
+//! Credit wallet service module for customer store credit
+//!
+//! This module handles:
+//! - Granting refund, promotional and goodwill credit with optional expiry
+//! - Applying credit to a job before the payment method is charged
+//! - Expiring unspent credit in the background
+//! - Reporting wallet balances and history
+//!
+//! Every change is also posted to the double-entry ledger.
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::CreditWalletConfig;
+pub use service::{CreditApplication, CreditWalletService};
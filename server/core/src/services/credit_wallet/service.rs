@@ -0,0 +1,334 @@
+//! Credit wallet service for customer store credit
+//!
+//! The journal holds the money; grants track what each customer can still
+//! spend and when it expires. Every wallet change is posted to the ledger
+//! under the same reference, so retried calls are recorded once.
+//!
+//! Wallets belong to a tenant. The expiry job expires the credit of each
+//! tenant given to [`CreditWalletService::with_tenants`] in turn.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::credit_wallet::{
+    CreditGrant, CreditSource, CreditTransaction, CreditTransactionKind,
+};
+use crate::domain::entities::payment::normalize_currency;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{CreditWalletRepository, JournalRepository};
+use crate::services::ledger::LedgerService;
+use crate::services::tenant::{self, TenantId};
+
+use super::config::CreditWalletConfig;
+
+/// Result of applying wallet credit to a charge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditApplication {
+    /// Credit spent on the charge in minor units
+    pub credit_applied: i64,
+    /// What is left to charge to the payment method in minor units
+    pub amount_due: i64,
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+}
+
+/// Service managing customer credit wallets
+pub struct CreditWalletService<W, J>
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    wallet: Arc<W>,
+    ledger: Arc<LedgerService<J>>,
+    config: CreditWalletConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<W, J> CreditWalletService<W, J>
+where
+    W: CreditWalletRepository + 'static,
+    J: JournalRepository + 'static,
+{
+    /// Create a new credit wallet service
+    pub fn new(wallet: Arc<W>, ledger: Arc<LedgerService<J>>, config: CreditWalletConfig) -> Self {
+        Self {
+            wallet,
+            ledger,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Expire the credit of these tenants in the background task, instead
+    /// of that of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Grant credit to a customer
+    ///
+    /// Promotional credit without an expiry gets the configured default.
+    /// Granting again with the same reference returns the existing grant.
+    ///
+    /// # Returns
+    /// * `Ok(CreditGrant)` - The grant
+    /// * `Err(DomainError::Validation)` - Invalid amount or currency
+    /// * `Err(DomainError::BusinessRule)` - The expiry is in the past
+    pub async fn issue(
+        &self,
+        customer_id: Uuid,
+        source: CreditSource,
+        reference: &str,
+        amount: i64,
+        currency: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> DomainResult<CreditGrant> {
+        if let Some(grant) = self.wallet.find_grant_by_reference(reference).await? {
+            return Ok(grant);
+        }
+
+        let now = Utc::now();
+        let expires_at = expires_at.or_else(|| self.default_expiry(source, now));
+        if expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(DomainError::BusinessRule {
+                message: "Credit cannot expire in the past".to_string(),
+            });
+        }
+
+        let grant = CreditGrant::new(customer_id, source, reference, amount, currency, expires_at)
+            .map_err(|message| DomainError::Validation { message })?;
+
+        self.ledger
+            .issue_credit(customer_id, reference, grant.amount, &grant.currency)
+            .await?;
+        let grant = self.wallet.save_grant(grant).await?;
+        self.wallet
+            .save_transaction(CreditTransaction::new(
+                customer_id,
+                CreditTransactionKind::Issued,
+                grant.amount,
+                &grant.currency,
+                reference,
+            ))
+            .await?;
+
+        info!(
+            customer_id = %customer_id,
+            source = source.as_str(),
+            amount = grant.amount,
+            currency = %grant.currency,
+            expires_at = ?grant.expires_at,
+            "Credit issued"
+        );
+
+        Ok(grant)
+    }
+
+    /// Spend a customer's credit on a job, crediting the worker
+    ///
+    /// Grants closest to expiry are spent first. Redeeming again with the
+    /// same reference returns the earlier redemption.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::BusinessRule)` - The amount is not positive or
+    ///   exceeds the spendable credit
+    pub async fn redeem(
+        &self,
+        customer_id: Uuid,
+        worker_id: Uuid,
+        reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<CreditTransaction> {
+        let currency = normalize(currency)?;
+        if let Some(transaction) = self.wallet.find_transaction(CreditTransactionKind::Redeemed, reference).await? {
+            return Ok(transaction);
+        }
+
+        if amount <= 0 {
+            return Err(DomainError::BusinessRule {
+                message: "Redeemed amount must be positive".to_string(),
+            });
+        }
+
+        let mut grants = self.wallet.find_spendable_grants(customer_id, &currency, Utc::now()).await?;
+        let available: i64 = grants.iter().map(|g| g.remaining).sum();
+        if amount > available {
+            return Err(DomainError::BusinessRule {
+                message: format!("Insufficient credit: {} available, {} requested", available, amount),
+            });
+        }
+
+        self.ledger
+            .redeem_credit(customer_id, worker_id, reference, amount, &currency)
+            .await?;
+
+        let mut outstanding = amount;
+        for grant in grants.iter_mut() {
+            if outstanding == 0 {
+                break;
+            }
+            outstanding -= grant.consume(outstanding);
+            self.wallet.update_grant(grant).await?;
+        }
+
+        let transaction = self
+            .wallet
+            .save_transaction(CreditTransaction::new(
+                customer_id,
+                CreditTransactionKind::Redeemed,
+                amount,
+                &currency,
+                reference,
+            ))
+            .await?;
+
+        info!(customer_id = %customer_id, amount, currency = %currency, reference, "Credit redeemed");
+
+        Ok(transaction)
+    }
+
+    /// Apply as much credit as possible to a charge before the payment
+    /// method is charged for the rest
+    ///
+    /// Applying again for the same order returns the credit applied the
+    /// first time.
+    pub async fn apply_to_charge(
+        &self,
+        customer_id: Uuid,
+        worker_id: Uuid,
+        order_reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<CreditApplication> {
+        let currency = normalize(currency)?;
+        let existing = self
+            .wallet
+            .find_transaction(CreditTransactionKind::Redeemed, order_reference)
+            .await?;
+
+        let credit_applied = match existing {
+            Some(transaction) => transaction.amount,
+            None => {
+                let applied = self.balance(customer_id, &currency).await?.min(amount);
+                if applied > 0 {
+                    self.redeem(customer_id, worker_id, order_reference, applied, &currency).await?;
+                }
+                applied.max(0)
+            }
+        };
+
+        Ok(CreditApplication {
+            credit_applied,
+            amount_due: amount - credit_applied,
+            currency,
+        })
+    }
+
+    /// Credit a customer can spend in a currency
+    pub async fn balance(&self, customer_id: Uuid, currency: &str) -> DomainResult<i64> {
+        let grants = self
+            .wallet
+            .find_spendable_grants(customer_id, &normalize(currency)?, Utc::now())
+            .await?;
+        Ok(grants.iter().map(|g| g.remaining).sum())
+    }
+
+    /// A customer's most recent wallet changes, newest first
+    pub async fn history(&self, customer_id: Uuid, limit: usize) -> DomainResult<Vec<CreditTransaction>> {
+        self.wallet.find_transactions(customer_id, limit).await
+    }
+
+    /// Expire the unspent credit of grants past their expiry
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of grants expired
+    pub async fn expire_due(&self) -> DomainResult<usize> {
+        let grants = self
+            .wallet
+            .find_expired_grants(Utc::now(), self.config.expiry_batch_size)
+            .await?;
+        let mut expired = 0;
+
+        for mut grant in grants {
+            let amount = grant.remaining;
+            let reference = grant.id.to_string();
+
+            if let Err(e) = self
+                .ledger
+                .expire_credit(grant.customer_id, &reference, amount, &grant.currency)
+                .await
+            {
+                warn!(grant_id = %grant.id, "Failed to expire credit: {}", e);
+                continue;
+            }
+
+            grant.consume(amount);
+            self.wallet.update_grant(&grant).await?;
+            self.wallet
+                .save_transaction(CreditTransaction::new(
+                    grant.customer_id,
+                    CreditTransactionKind::Expired,
+                    amount,
+                    &grant.currency,
+                    reference,
+                ))
+                .await?;
+            expired += 1;
+        }
+
+        if expired > 0 {
+            info!(expired, "Expired unspent credit");
+        }
+
+        Ok(expired)
+    }
+
+    /// Start the credit expiry job as a background task
+    ///
+    /// This spawns a tokio task that expires due credit at the configured interval
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.expiry_enabled {
+            warn!("Credit expiry is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.expiry_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Credit expiry started - checking every {} seconds",
+                self.config.expiry_interval_seconds
+            );
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for tenant in &self.tenants {
+                    if let Err(e) = tenant::scope(tenant.clone(), self.expire_due()).await {
+                        error!("Credit expiry for tenant {} failed to run: {}", tenant, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Default expiry of credit from a source granted at `now`
+    fn default_expiry(&self, source: CreditSource, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match source {
+            CreditSource::Promotion if self.config.promotion_expiry_days > 0 => {
+                Some(now + Duration::days(self.config.promotion_expiry_days))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Normalize a currency code, rejecting invalid ones as a validation error
+fn normalize(currency: &str) -> DomainResult<String> {
+    normalize_currency(currency).map_err(|message| DomainError::Validation { message })
+}
@@ -0,0 +1,248 @@
+//! Unit tests for the credit wallet service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::credit_wallet::{
+    CreditGrant, CreditSource, CreditTransaction, CreditTransactionKind,
+};
+use crate::errors::DomainError;
+use crate::repositories::CreditWalletRepository;
+use crate::services::credit_wallet::{CreditWalletConfig, CreditWalletService};
+use crate::services::ledger::tests::service_tests::MockJournal;
+use crate::services::ledger::{LedgerConfig, LedgerService};
+
+#[derive(Default)]
+struct MockWallet {
+    grants: Mutex<Vec<CreditGrant>>,
+    transactions: Mutex<Vec<CreditTransaction>>,
+}
+
+#[async_trait]
+impl CreditWalletRepository for MockWallet {
+    async fn save_grant(&self, grant: CreditGrant) -> Result<CreditGrant, DomainError> {
+        self.grants.lock().unwrap().push(grant.clone());
+        Ok(grant)
+    }
+
+    async fn update_grant(&self, grant: &CreditGrant) -> Result<(), DomainError> {
+        let mut grants = self.grants.lock().unwrap();
+        if let Some(existing) = grants.iter_mut().find(|g| g.id == grant.id) {
+            *existing = grant.clone();
+        }
+        Ok(())
+    }
+
+    async fn find_grant_by_reference(&self, reference: &str) -> Result<Option<CreditGrant>, DomainError> {
+        Ok(self.grants.lock().unwrap().iter().find(|g| g.reference == reference).cloned())
+    }
+
+    async fn find_spendable_grants(
+        &self,
+        customer_id: Uuid,
+        currency: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CreditGrant>, DomainError> {
+        let mut grants: Vec<_> = self
+            .grants
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|g| g.customer_id == customer_id && g.currency == currency && g.is_spendable(now))
+            .cloned()
+            .collect();
+        grants.sort_by_key(|g| (g.expires_at.is_none(), g.expires_at, g.created_at));
+        Ok(grants)
+    }
+
+    async fn find_expired_grants(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<CreditGrant>, DomainError> {
+        Ok(self
+            .grants
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|g| g.remaining > 0 && g.is_expired(now))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn save_transaction(&self, transaction: CreditTransaction) -> Result<CreditTransaction, DomainError> {
+        self.transactions.lock().unwrap().push(transaction.clone());
+        Ok(transaction)
+    }
+
+    async fn find_transaction(
+        &self,
+        kind: CreditTransactionKind,
+        reference: &str,
+    ) -> Result<Option<CreditTransaction>, DomainError> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|t| t.kind == kind && t.reference == reference)
+            .cloned())
+    }
+
+    async fn find_transactions(
+        &self,
+        customer_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<CreditTransaction>, DomainError> {
+        Ok(self
+            .transactions
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|t| t.customer_id == customer_id)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+struct Fixture {
+    service: CreditWalletService<MockWallet, MockJournal>,
+    wallet: Arc<MockWallet>,
+    ledger: Arc<LedgerService<MockJournal>>,
+}
+
+fn fixture() -> Fixture {
+    let wallet = Arc::new(MockWallet::default());
+    let ledger = Arc::new(LedgerService::new(Arc::new(MockJournal::default()), LedgerConfig::default()));
+    let service = CreditWalletService::new(wallet.clone(), ledger.clone(), CreditWalletConfig::default());
+    Fixture { service, wallet, ledger }
+}
+
+#[tokio::test]
+async fn test_issued_credit_is_posted_to_the_ledger() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+
+    let grant = f
+        .service
+        .issue(customer_id, CreditSource::Refund, "refund_1", 2_000, "aud", None)
+        .await
+        .unwrap();
+    let again = f
+        .service
+        .issue(customer_id, CreditSource::Refund, "refund_1", 2_000, "AUD", None)
+        .await
+        .unwrap();
+
+    assert_eq!(grant.id, again.id);
+    assert_eq!(grant.expires_at, None);
+    assert_eq!(f.service.balance(customer_id, "AUD").await.unwrap(), 2_000);
+    assert_eq!(f.ledger.customer_credit_balance(customer_id, "AUD").await.unwrap(), 2_000);
+}
+
+#[tokio::test]
+async fn test_promotional_credit_gets_the_default_expiry() {
+    let f = fixture();
+
+    let grant = f
+        .service
+        .issue(Uuid::new_v4(), CreditSource::Promotion, "promo_1", 500, "AUD", None)
+        .await
+        .unwrap();
+
+    assert!(grant.expires_at.unwrap() > Utc::now() + Duration::days(89));
+
+    let result = f
+        .service
+        .issue(Uuid::new_v4(), CreditSource::Promotion, "promo_2", 500, "AUD", Some(Utc::now()))
+        .await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_redemption_spends_the_soonest_expiring_credit_first() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    let soon = Utc::now() + Duration::days(7);
+    f.service
+        .issue(customer_id, CreditSource::Goodwill, "goodwill_1", 1_000, "AUD", None)
+        .await
+        .unwrap();
+    f.service
+        .issue(customer_id, CreditSource::Promotion, "promo_1", 600, "AUD", Some(soon))
+        .await
+        .unwrap();
+
+    f.service
+        .redeem(customer_id, Uuid::new_v4(), "order_1", 800, "AUD")
+        .await
+        .unwrap();
+
+    let grants = f.wallet.grants.lock().unwrap().clone();
+    assert_eq!(grants.iter().find(|g| g.reference == "promo_1").unwrap().remaining, 0);
+    assert_eq!(grants.iter().find(|g| g.reference == "goodwill_1").unwrap().remaining, 800);
+
+    let result = f.service.redeem(customer_id, Uuid::new_v4(), "order_2", 801, "AUD").await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_credit_is_applied_before_charging_the_payment_method() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    let worker_id = Uuid::new_v4();
+    f.service
+        .issue(customer_id, CreditSource::Refund, "refund_1", 3_000, "AUD", None)
+        .await
+        .unwrap();
+
+    let application = f
+        .service
+        .apply_to_charge(customer_id, worker_id, "order_1", 10_000, "AUD")
+        .await
+        .unwrap();
+    let again = f
+        .service
+        .apply_to_charge(customer_id, worker_id, "order_1", 10_000, "AUD")
+        .await
+        .unwrap();
+
+    assert_eq!((application.credit_applied, application.amount_due), (3_000, 7_000));
+    assert_eq!(again, application);
+    assert_eq!(f.service.balance(customer_id, "AUD").await.unwrap(), 0);
+    assert_eq!(f.ledger.worker_balance(worker_id, "AUD").await.unwrap(), 3_000);
+
+    let without_credit = f
+        .service
+        .apply_to_charge(customer_id, worker_id, "order_2", 5_000, "AUD")
+        .await
+        .unwrap();
+    assert_eq!((without_credit.credit_applied, without_credit.amount_due), (0, 5_000));
+}
+
+#[tokio::test]
+async fn test_expired_credit_is_written_off() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    f.service
+        .issue(customer_id, CreditSource::Promotion, "promo_1", 500, "AUD", None)
+        .await
+        .unwrap();
+    f.wallet.grants.lock().unwrap()[0].expires_at = Some(Utc::now() - Duration::minutes(1));
+
+    assert_eq!(f.service.balance(customer_id, "AUD").await.unwrap(), 0);
+    assert_eq!(f.service.expire_due().await.unwrap(), 1);
+    assert_eq!(f.service.expire_due().await.unwrap(), 0);
+
+    assert_eq!(f.ledger.customer_credit_balance(customer_id, "AUD").await.unwrap(), 0);
+    assert!(f.ledger.verify_books().await.unwrap().is_balanced());
+
+    let history = f.service.history(customer_id, 10).await.unwrap();
+    let kinds: Vec<_> = history.iter().map(|t| t.kind).collect();
+    assert_eq!(kinds, vec![CreditTransactionKind::Expired, CreditTransactionKind::Issued]);
+}
@@ -0,0 +1,38 @@
+//! Configuration for the export service
+
+/// Configuration for the export service
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    /// How often the background task processes queued jobs (in seconds)
+    pub interval_seconds: u64,
+    /// Maximum number of jobs processed per cycle
+    pub max_jobs_per_cycle: usize,
+    /// Rows read from a source per query, at most 100 (the largest page
+    /// repositories return)
+    pub page_size: u32,
+    /// Attempts before a job is given up
+    pub max_attempts: u32,
+    /// Running jobs not updated for this long are queued again (in seconds)
+    pub stale_after_seconds: i64,
+    /// Lifetime of download links (in seconds)
+    pub download_ttl_seconds: i64,
+    /// Path of the export endpoints; download links point to `{path}/{id}/download`
+    pub download_path: String,
+    /// Whether to enable background processing
+    pub enabled: bool,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 10,
+            max_jobs_per_cycle: 2,
+            page_size: 100,
+            max_attempts: 3,
+            stale_after_seconds: 600,
+            download_ttl_seconds: 15 * 60,
+            download_path: "/api/v1/admin/exports".to_string(),
+            enabled: true,
+        }
+    }
+}
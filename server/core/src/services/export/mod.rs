@@ -0,0 +1,21 @@
+//! Export service module for long-running administrator exports
+//!
+//! This module handles:
+//! - Queueing exports of audit logs and attribution cohorts as jobs
+//! - Writing queued jobs page by page to CSV files in object storage,
+//!   retrying failed attempts and recovering jobs whose worker stopped
+//! - Reporting the progress of a job and minting signed download links
+//!   once it completes
+
+mod config;
+mod service;
+mod sources;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ExportConfig;
+pub use service::{ExportProcessingResult, ExportService};
+pub use sources::{AttributionCohortExportSource, AuditLogExportSource};
+pub use traits::{ExportSourceTrait, ObjectStorageTrait};
@@ -0,0 +1,363 @@
+//! Export service
+//!
+//! Administrators request exports that can take too long to produce within
+//! a request. Each request is saved as a queued job; a background task
+//! claims queued jobs, reads their rows page by page from the matching
+//! export source and stores the CSV file in object storage, recording
+//! progress after every page so the job can be polled.
+//!
+//! A failed attempt queues the job again until `max_attempts` is reached.
+//! Jobs left running by a worker that stopped, e.g. during a deployment,
+//! are queued again once they have not been updated for
+//! `stale_after_seconds`.
+//!
+//! Finished files are downloaded through signed links, which expire after
+//! `download_ttl_seconds` and can be minted again by polling the job.
+//!
+//! Jobs belong to the tenant they were requested in and export its rows.
+//! The background task processes the jobs of each tenant given to
+//! [`ExportService::with_tenants`] in turn.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::export_job::{ExportJob, ExportKind, ExportStatus};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::ExportJobRepository;
+use crate::services::signed_url::SignedUrlService;
+use crate::services::tenant::{self, TenantId};
+
+use super::config::ExportConfig;
+use super::traits::{ExportSourceTrait, ObjectStorageTrait};
+
+/// Content type of export files
+const CSV_CONTENT_TYPE: &str = "text/csv";
+
+/// Service for queueing, processing and downloading exports
+pub struct ExportService<J>
+where
+    J: ExportJobRepository + 'static,
+{
+    jobs: Arc<J>,
+    storage: Arc<dyn ObjectStorageTrait>,
+    signed_urls: Arc<SignedUrlService>,
+    sources: HashMap<ExportKind, Arc<dyn ExportSourceTrait>>,
+    config: ExportConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<J> ExportService<J>
+where
+    J: ExportJobRepository + 'static,
+{
+    /// Create a new export service without any export sources
+    pub fn new(
+        jobs: Arc<J>,
+        storage: Arc<dyn ObjectStorageTrait>,
+        signed_urls: Arc<SignedUrlService>,
+        config: ExportConfig,
+    ) -> Self {
+        Self {
+            jobs,
+            storage,
+            signed_urls,
+            sources: HashMap::new(),
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Enable exports of the kind the source produces
+    pub fn with_source(mut self, source: Arc<dyn ExportSourceTrait>) -> Self {
+        self.sources.insert(source.kind(), source);
+        self
+    }
+
+    /// Process the exports of these tenants in the background task,
+    /// instead of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Queue an export
+    ///
+    /// # Arguments
+    /// * `requested_by` - Administrator requesting the export
+    /// * `kind` - Data to export
+    /// * `from` - Start of the exported time range, unbounded when `None`
+    /// * `to` - End of the exported time range, now when `None`
+    ///
+    /// # Returns
+    /// * `Ok(ExportJob)` - The queued job
+    /// * `Err(DomainError::Validation)` - The kind cannot be exported or the
+    ///   range ends before it starts
+    pub async fn create(
+        &self,
+        requested_by: Uuid,
+        kind: ExportKind,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> DomainResult<ExportJob> {
+        if !self.sources.contains_key(&kind) {
+            return Err(DomainError::Validation {
+                message: format!("Exports of {} are not available", kind.as_str()),
+            });
+        }
+
+        let job = ExportJob::new(kind, requested_by, from, to);
+        if job.from.is_some_and(|from| from > job.to) {
+            return Err(DomainError::Validation {
+                message: "Export period must not end before it starts".to_string(),
+            });
+        }
+
+        let job = self.jobs.create(job).await?;
+        info!(
+            job_id = %job.id,
+            kind = kind.as_str(),
+            requested_by = %requested_by,
+            "Export queued"
+        );
+
+        Ok(job)
+    }
+
+    /// Get an export job requested by an administrator
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No job exists with this ID for the
+    ///   administrator
+    pub async fn get(&self, job_id: Uuid, requested_by: Uuid) -> DomainResult<ExportJob> {
+        match self.jobs.find_by_id(job_id).await? {
+            Some(job) if job.requested_by == requested_by => Ok(job),
+            _ => Err(DomainError::NotFound {
+                resource: "Export".to_string(),
+            }),
+        }
+    }
+
+    /// Mint a download link for a completed job
+    ///
+    /// # Returns
+    /// * `Ok(Some((url, expires_at)))` - A signed link and when it stops working
+    /// * `Ok(None)` - The job has not completed
+    pub fn download_url(&self, job: &ExportJob) -> DomainResult<Option<(String, DateTime<Utc>)>> {
+        if job.status != ExportStatus::Completed {
+            return Ok(None);
+        }
+
+        let expires_at = Utc::now() + Duration::seconds(self.config.download_ttl_seconds);
+        let path = format!("{}/{}/download", self.config.download_path, job.id);
+        let url = self.signed_urls.sign_until(&path, expires_at)?;
+        Ok(Some((url, expires_at)))
+    }
+
+    /// Read the file of a completed job
+    ///
+    /// Access is checked by the signed link, not by the requesting
+    /// administrator.
+    ///
+    /// # Returns
+    /// * `Ok((bytes, filename))` - The CSV file and a name to save it as
+    /// * `Err(DomainError::NotFound)` - No completed job exists with this ID
+    pub async fn download(&self, job_id: Uuid) -> DomainResult<(Vec<u8>, String)> {
+        let job = self.jobs.find_by_id(job_id).await?;
+        let Some((job, key)) = job.and_then(|job| job.object_key.clone().map(|key| (job, key))) else {
+            return Err(DomainError::NotFound {
+                resource: "Export".to_string(),
+            });
+        };
+
+        let bytes = self.storage.get(&key).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to read export {}: {}", job.id, e),
+        })?;
+        let filename = format!(
+            "{}-{}.csv",
+            job.kind.as_str().replace('_', "-"),
+            job.created_at.format("%Y%m%d-%H%M%S")
+        );
+
+        Ok((bytes, filename))
+    }
+
+    /// Process queued jobs
+    ///
+    /// Stale running jobs are queued again first. At most
+    /// `max_jobs_per_cycle` jobs are processed per call.
+    ///
+    /// # Returns
+    /// * `Ok(ExportProcessingResult)` - Summary of the processing cycle
+    /// * `Err(DomainError)` - If jobs could not be loaded
+    pub async fn process_queued(&self) -> DomainResult<ExportProcessingResult> {
+        let mut result = ExportProcessingResult::default();
+
+        let stale_before = Utc::now() - Duration::seconds(self.config.stale_after_seconds);
+        result.requeued = self.jobs.requeue_stale(stale_before).await?;
+        if result.requeued > 0 {
+            warn!(count = result.requeued, "Requeued exports whose worker stopped");
+        }
+
+        let jobs = self.jobs.find_queued(self.config.max_jobs_per_cycle).await?;
+        for mut job in jobs {
+            job.start();
+            if !self.jobs.claim(&job).await? {
+                continue;
+            }
+
+            match self.run(&mut job).await {
+                Ok(key) => {
+                    job.complete(key);
+                    result.completed += 1;
+                    info!(job_id = %job.id, rows = job.rows_exported, "Export completed");
+                }
+                Err(e) => {
+                    warn!(
+                        job_id = %job.id,
+                        attempt = job.attempts,
+                        error = %e,
+                        "Export attempt failed"
+                    );
+                    if job.attempts >= self.config.max_attempts {
+                        job.fail(e.to_string());
+                        result.failed += 1;
+                    } else {
+                        job.retry(e.to_string());
+                        result.retried += 1;
+                    }
+                }
+            }
+
+            if let Err(e) = self.jobs.update(&job).await {
+                error!(job_id = %job.id, error = %e, "Failed to update export job");
+                result.errors.push(format!("{}: {}", job.id, e));
+            }
+        }
+
+        if result.total_processed() > 0 {
+            info!(
+                completed = result.completed,
+                retried = result.retried,
+                failed = result.failed,
+                "Export processing completed"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Write a claimed job's file to object storage
+    ///
+    /// # Returns
+    /// * `Ok(key)` - Key of the stored file
+    async fn run(&self, job: &mut ExportJob) -> DomainResult<String> {
+        let source = self.sources.get(&job.kind).cloned().ok_or_else(|| DomainError::Internal {
+            message: format!("No export source for {}", job.kind.as_str()),
+        })?;
+
+        job.total_rows = Some(source.count(job).await?);
+        self.jobs.update(job).await?;
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(source.header()).map_err(csv_error)?;
+
+        let page_size = self.config.page_size.max(1);
+        loop {
+            let rows = source.page(job, job.rows_exported, page_size).await?;
+            for row in &rows {
+                writer.write_record(row).map_err(csv_error)?;
+            }
+
+            job.record_progress(job.rows_exported + rows.len() as u64);
+            if (rows.len() as u32) < page_size {
+                break;
+            }
+            self.jobs.update(job).await?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| DomainError::Internal {
+            message: format!("Failed to write export file: {}", e),
+        })?;
+        let key = format!("exports/{}/{}.csv", job.kind.as_str(), job.id);
+        self.storage
+            .put(&key, bytes, CSV_CONTENT_TYPE)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to store export file: {}", e),
+            })?;
+
+        Ok(key)
+    }
+
+    /// Start the export worker as a background task
+    ///
+    /// This spawns a tokio task that processes queued exports at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Export processing is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Export worker started - will process exports every {} seconds",
+                self.config.interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                for tenant in &self.tenants {
+                    match tenant::scope(tenant.clone(), self.process_queued()).await {
+                        Ok(result) => {
+                            if !result.errors.is_empty() {
+                                warn!(
+                                    "Export processing for tenant {} completed with errors: {:?}",
+                                    tenant, result.errors
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("Export processing cycle for tenant {} failed: {}", tenant, e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Result of an export processing cycle
+#[derive(Debug, Default)]
+pub struct ExportProcessingResult {
+    /// Number of jobs whose file was stored
+    pub completed: usize,
+    /// Number of failed jobs queued for another attempt
+    pub retried: usize,
+    /// Number of jobs given up after the last attempt
+    pub failed: usize,
+    /// Number of stale running jobs queued again
+    pub requeued: u64,
+    /// Any errors encountered while saving job state
+    pub errors: Vec<String>,
+}
+
+impl ExportProcessingResult {
+    /// Get total number of jobs processed
+    pub fn total_processed(&self) -> usize {
+        self.completed + self.retried + self.failed
+    }
+}
+
+fn csv_error(e: csv::Error) -> DomainError {
+    DomainError::Internal {
+        message: format!("Failed to write export file: {}", e),
+    }
+}
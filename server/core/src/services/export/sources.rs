@@ -0,0 +1,151 @@
+//! Export sources for the data administrators can export
+
+use async_trait::async_trait;
+use chrono::DateTime;
+use std::sync::Arc;
+
+use re_shared::types::{DateRange, Pagination};
+
+use crate::domain::entities::audit::AuditLogFilter;
+use crate::domain::entities::export_job::{ExportJob, ExportKind};
+use crate::errors::DomainError;
+use crate::repositories::{AttributionRepository, AuditLogRepository};
+
+use super::traits::ExportSourceTrait;
+
+/// Exports audit log entries, newest first
+///
+/// Phone numbers are only exported masked.
+pub struct AuditLogExportSource<R: AuditLogRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: AuditLogRepository> AuditLogExportSource<R> {
+    /// Create a new audit log export source
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+}
+
+fn audit_filter(job: &ExportJob) -> AuditLogFilter {
+    AuditLogFilter {
+        date_range: DateRange::new(job.from, Some(job.to)),
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl<R: AuditLogRepository + 'static> ExportSourceTrait for AuditLogExportSource<R> {
+    fn kind(&self) -> ExportKind {
+        ExportKind::AuditLogs
+    }
+
+    fn header(&self) -> &'static [&'static str] {
+        &[
+            "id",
+            "created_at",
+            "event_type",
+            "success",
+            "user_id",
+            "phone_masked",
+            "ip_address",
+            "user_agent",
+            "failure_reason",
+            "trace_id",
+        ]
+    }
+
+    async fn count(&self, job: &ExportJob) -> Result<u64, DomainError> {
+        let (_, total) = self.repository.query(&audit_filter(job), &Pagination::new(1, 1)).await?;
+        Ok(total)
+    }
+
+    async fn page(&self, job: &ExportJob, offset: u64, limit: u32) -> Result<Vec<Vec<String>>, DomainError> {
+        let pagination = Pagination::from_offset(offset as u32, limit);
+        let (logs, _) = self.repository.query(&audit_filter(job), &pagination).await?;
+
+        Ok(logs
+            .into_iter()
+            .map(|log| {
+                vec![
+                    log.id.to_string(),
+                    log.created_at.to_rfc3339(),
+                    log.event_type.as_str().to_string(),
+                    log.success.to_string(),
+                    log.user_id.map(|id| id.to_string()).unwrap_or_default(),
+                    log.phone_masked.unwrap_or_default(),
+                    log.ip_address,
+                    log.user_agent.unwrap_or_default(),
+                    log.failure_reason.unwrap_or_default(),
+                    log.trace_id.unwrap_or_default(),
+                ]
+            })
+            .collect())
+    }
+}
+
+/// Exports weekly registration cohorts per campaign
+///
+/// A cohort report has at most a few rows per campaign and week, so it is
+/// read once per page and sliced.
+pub struct AttributionCohortExportSource<R: AttributionRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: AttributionRepository> AttributionCohortExportSource<R> {
+    /// Create a new attribution cohort export source
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    async fn rows(&self, job: &ExportJob) -> Result<Vec<Vec<String>>, DomainError> {
+        let from = job.from.unwrap_or(DateTime::UNIX_EPOCH);
+        let cohorts = self.repository.cohorts(from, job.to).await?;
+
+        Ok(cohorts
+            .into_iter()
+            .map(|cohort| {
+                vec![
+                    cohort.cohort_week.to_string(),
+                    cohort.utm_source.unwrap_or_default(),
+                    cohort.utm_medium.unwrap_or_default(),
+                    cohort.utm_campaign.unwrap_or_default(),
+                    cohort.registrations.to_string(),
+                    cohort.activated.to_string(),
+                ]
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<R: AttributionRepository + 'static> ExportSourceTrait for AttributionCohortExportSource<R> {
+    fn kind(&self) -> ExportKind {
+        ExportKind::AttributionCohorts
+    }
+
+    fn header(&self) -> &'static [&'static str] {
+        &[
+            "cohort_week",
+            "utm_source",
+            "utm_medium",
+            "utm_campaign",
+            "registrations",
+            "activated",
+        ]
+    }
+
+    async fn count(&self, job: &ExportJob) -> Result<u64, DomainError> {
+        Ok(self.rows(job).await?.len() as u64)
+    }
+
+    async fn page(&self, job: &ExportJob, offset: u64, limit: u32) -> Result<Vec<Vec<String>>, DomainError> {
+        Ok(self
+            .rows(job)
+            .await?
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for the export service
+
+#[cfg(test)]
+mod service_tests;
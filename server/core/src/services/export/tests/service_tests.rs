@@ -0,0 +1,254 @@
+//! Unit tests for the export service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::export_job::{ExportJob, ExportKind, ExportStatus};
+use crate::errors::DomainError;
+use crate::repositories::ExportJobRepository;
+use crate::services::export::{ExportConfig, ExportService, ExportSourceTrait, ObjectStorageTrait};
+use crate::services::signed_url::{SignedUrlConfig, SignedUrlService};
+
+#[derive(Default)]
+struct MockExportJobRepository {
+    jobs: Mutex<HashMap<Uuid, ExportJob>>,
+    /// Progress saved while jobs were running
+    progress: Mutex<Vec<u64>>,
+}
+
+impl MockExportJobRepository {
+    fn job(&self, id: Uuid) -> ExportJob {
+        self.jobs.lock().unwrap()[&id].clone()
+    }
+}
+
+#[async_trait]
+impl ExportJobRepository for MockExportJobRepository {
+    async fn create(&self, job: ExportJob) -> Result<ExportJob, DomainError> {
+        self.jobs.lock().unwrap().insert(job.id, job.clone());
+        Ok(job)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ExportJob>, DomainError> {
+        Ok(self.jobs.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_queued(&self, limit: usize) -> Result<Vec<ExportJob>, DomainError> {
+        let mut jobs: Vec<_> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| job.status == ExportStatus::Queued)
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|job| job.created_at);
+        jobs.truncate(limit);
+        Ok(jobs)
+    }
+
+    async fn claim(&self, job: &ExportJob) -> Result<bool, DomainError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        match jobs.get(&job.id) {
+            Some(saved) if saved.status == ExportStatus::Queued => {
+                jobs.insert(job.id, job.clone());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn update(&self, job: &ExportJob) -> Result<(), DomainError> {
+        if job.status == ExportStatus::Running {
+            self.progress.lock().unwrap().push(job.rows_exported);
+        }
+        self.jobs.lock().unwrap().insert(job.id, job.clone());
+        Ok(())
+    }
+
+    async fn requeue_stale(&self, updated_before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let mut count = 0;
+        for job in self.jobs.lock().unwrap().values_mut() {
+            if job.status == ExportStatus::Running && job.updated_at < updated_before {
+                job.status = ExportStatus::Queued;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[derive(Default)]
+struct MockObjectStorage {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+    unavailable: AtomicBool,
+}
+
+#[async_trait]
+impl ObjectStorageTrait for MockObjectStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), String> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err("storage unavailable".to_string());
+        }
+        self.objects.lock().unwrap().insert(key.to_string(), bytes);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| "not found".to_string())
+    }
+}
+
+/// Source of numbered rows
+struct MockSource {
+    rows: u64,
+}
+
+#[async_trait]
+impl ExportSourceTrait for MockSource {
+    fn kind(&self) -> ExportKind {
+        ExportKind::AuditLogs
+    }
+
+    fn header(&self) -> &'static [&'static str] {
+        &["n", "label"]
+    }
+
+    async fn count(&self, _job: &ExportJob) -> Result<u64, DomainError> {
+        Ok(self.rows)
+    }
+
+    async fn page(&self, _job: &ExportJob, offset: u64, limit: u32) -> Result<Vec<Vec<String>>, DomainError> {
+        Ok((offset..self.rows.min(offset + limit as u64))
+            .map(|n| vec![n.to_string(), format!("row, {}", n)])
+            .collect())
+    }
+}
+
+struct Fixture {
+    jobs: Arc<MockExportJobRepository>,
+    storage: Arc<MockObjectStorage>,
+    signed_urls: Arc<SignedUrlService>,
+    service: ExportService<MockExportJobRepository>,
+}
+
+fn fixture(rows: u64) -> Fixture {
+    let jobs = Arc::new(MockExportJobRepository::default());
+    let storage = Arc::new(MockObjectStorage::default());
+    let signed_urls = Arc::new(
+        SignedUrlService::new(SignedUrlConfig::new("export-test-signing-key-0123456789abcdef")).unwrap(),
+    );
+    let config = ExportConfig {
+        page_size: 10,
+        ..Default::default()
+    };
+    let service = ExportService::new(jobs.clone(), storage.clone(), signed_urls.clone(), config)
+        .with_source(Arc::new(MockSource { rows }));
+
+    Fixture {
+        jobs,
+        storage,
+        signed_urls,
+        service,
+    }
+}
+
+#[tokio::test]
+async fn test_export_is_written_in_pages_and_downloaded_through_signed_link() {
+    let f = fixture(25);
+    let admin = Uuid::new_v4();
+
+    let job = f.service.create(admin, ExportKind::AuditLogs, None, None).await.unwrap();
+    assert_eq!(job.status, ExportStatus::Queued);
+    assert!(f.service.download_url(&job).unwrap().is_none());
+
+    let result = f.service.process_queued().await.unwrap();
+    assert_eq!(result.completed, 1);
+
+    let job = f.service.get(job.id, admin).await.unwrap();
+    assert_eq!(job.status, ExportStatus::Completed);
+    assert_eq!(job.rows_exported, 25);
+    assert_eq!(job.total_rows, Some(25));
+    assert_eq!(job.progress_percent(), Some(100));
+    assert_eq!(*f.jobs.progress.lock().unwrap(), vec![0, 10, 20]);
+
+    let (url, expires_at) = f.service.download_url(&job).unwrap().unwrap();
+    assert!(url.starts_with(&format!("/api/v1/admin/exports/{}/download?", job.id)));
+    let verified = f.signed_urls.verify(&url).unwrap();
+    assert_eq!(verified.expires_at.timestamp(), expires_at.timestamp());
+
+    let (bytes, filename) = f.service.download(job.id).await.unwrap();
+    let csv = String::from_utf8(bytes).unwrap();
+    assert!(filename.starts_with("audit-logs-") && filename.ends_with(".csv"));
+    assert_eq!(csv.lines().count(), 26);
+    assert!(csv.starts_with("n,label\n0,\"row, 0\"\n"));
+}
+
+#[tokio::test]
+async fn test_failed_exports_are_retried_then_given_up() {
+    let f = fixture(3);
+    f.storage.unavailable.store(true, Ordering::SeqCst);
+    let job = f.service.create(Uuid::new_v4(), ExportKind::AuditLogs, None, None).await.unwrap();
+
+    for attempt in 1..=2 {
+        let result = f.service.process_queued().await.unwrap();
+        assert_eq!(result.retried, 1);
+        let saved = f.jobs.job(job.id);
+        assert_eq!(saved.status, ExportStatus::Queued);
+        assert_eq!(saved.attempts, attempt);
+    }
+
+    let result = f.service.process_queued().await.unwrap();
+    assert_eq!(result.failed, 1);
+    let saved = f.jobs.job(job.id);
+    assert_eq!(saved.status, ExportStatus::Failed);
+    assert!(saved.error.unwrap().contains("storage unavailable"));
+    assert!(f.service.download(job.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_stale_running_exports_are_requeued() {
+    let f = fixture(3);
+    let mut job = ExportJob::new(ExportKind::AuditLogs, Uuid::new_v4(), None, None);
+    job.start();
+    job.updated_at = Utc::now() - Duration::hours(1);
+    f.jobs.create(job.clone()).await.unwrap();
+
+    let result = f.service.process_queued().await.unwrap();
+    assert_eq!(result.requeued, 1);
+    assert_eq!(result.completed, 1);
+
+    let saved = f.jobs.job(job.id);
+    assert_eq!(saved.status, ExportStatus::Completed);
+    assert_eq!(saved.attempts, 2);
+}
+
+#[tokio::test]
+async fn test_exports_are_validated_and_private_to_requester() {
+    let f = fixture(3);
+    let admin = Uuid::new_v4();
+    let now = Utc::now();
+
+    let unavailable = f.service.create(admin, ExportKind::AttributionCohorts, None, None).await;
+    assert!(matches!(unavailable, Err(DomainError::Validation { .. })));
+
+    let reversed = f
+        .service
+        .create(admin, ExportKind::AuditLogs, Some(now), Some(now - Duration::days(1)))
+        .await;
+    assert!(matches!(reversed, Err(DomainError::Validation { .. })));
+
+    let job = f.service.create(admin, ExportKind::AuditLogs, None, None).await.unwrap();
+    assert!(f.service.get(job.id, admin).await.is_ok());
+    let other = f.service.get(job.id, Uuid::new_v4()).await;
+    assert!(matches!(other, Err(DomainError::NotFound { .. })));
+}
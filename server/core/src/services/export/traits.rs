@@ -0,0 +1,50 @@
+//! Traits for export sources and file storage
+
+use async_trait::async_trait;
+
+use crate::domain::entities::export_job::{ExportJob, ExportKind};
+use crate::errors::DomainError;
+
+/// Trait for the data an export kind writes
+#[async_trait]
+pub trait ExportSourceTrait: Send + Sync {
+    /// Kind of export this source produces
+    fn kind(&self) -> ExportKind;
+
+    /// Column names written as the first line of the file
+    fn header(&self) -> &'static [&'static str];
+
+    /// Number of rows the job will export
+    async fn count(&self, job: &ExportJob) -> Result<u64, DomainError>;
+
+    /// Read a page of rows for the job
+    ///
+    /// Rows must be returned in a stable order, so pages read one after
+    /// another neither skip nor repeat rows.
+    ///
+    /// # Arguments
+    /// * `offset` - Number of rows already read; always a multiple of `limit`
+    /// * `limit` - Maximum number of rows to return
+    ///
+    /// # Returns
+    /// * `Ok(rows)` - The page, shorter than `limit` only on the last page
+    async fn page(&self, job: &ExportJob, offset: u64, limit: u32) -> Result<Vec<Vec<String>>, DomainError>;
+}
+
+/// Trait for object storage holding finished export files
+#[async_trait]
+pub trait ObjectStorageTrait: Send + Sync {
+    /// Store an object, replacing any object with the same key
+    ///
+    /// # Returns
+    /// * `Ok(())` - The object was stored
+    /// * `Err(String)` - Why the object could not be stored
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), String>;
+
+    /// Read an object
+    ///
+    /// # Returns
+    /// * `Ok(bytes)` - The object's content
+    /// * `Err(String)` - Why the object could not be read
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String>;
+}
@@ -0,0 +1,13 @@
+//! Fee schedule service module for versioned platform fees
+//!
+//! This module handles:
+//! - Creating fee schedule versions per market and job category with an effective date
+//! - Resolving the schedule in effect, preferring category schedules over market-wide ones
+//! - Fixing the fee on an order when its quote is accepted, so later changes are grandfathered
+
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use service::{FeeScheduleService, NewFeeSchedule};
@@ -0,0 +1,212 @@
+//! Fee schedule service for versioned platform fees
+//!
+//! Schedules are never edited or back-dated: a fee change is a new version
+//! that takes effect at a future time. The fee of an order is resolved once,
+//! when the customer accepts the quote, and stored with the schedule
+//! version so the payment is charged exactly what was agreed.
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::normalize_market;
+use crate::domain::entities::fee_schedule::{normalize_category, FeeSchedule, OrderFee};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{FeeScheduleRepository, OrderFeeRepository};
+
+/// Input for a new fee schedule version
+#[derive(Debug, Clone)]
+pub struct NewFeeSchedule {
+    /// Market code (ISO 3166-1 alpha-2)
+    pub market: String,
+    /// Job category, `None` for the whole market
+    pub category: Option<String>,
+    /// Commission in basis points of the quoted amount
+    pub commission_basis_points: i64,
+    /// Smallest fee charged per order in minor units
+    pub minimum_fee: i64,
+    /// ISO 4217 currency code
+    pub currency: String,
+    /// When the schedule takes effect; `None` takes effect immediately
+    pub effective_from: Option<DateTime<Utc>>,
+}
+
+/// Service managing fee schedules and the fees fixed on orders
+pub struct FeeScheduleService<S, O>
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    schedules: Arc<S>,
+    order_fees: Arc<O>,
+}
+
+impl<S, O> FeeScheduleService<S, O>
+where
+    S: FeeScheduleRepository + 'static,
+    O: OrderFeeRepository + 'static,
+{
+    /// Create a new fee schedule service
+    pub fn new(schedules: Arc<S>, order_fees: Arc<O>) -> Self {
+        Self { schedules, order_fees }
+    }
+
+    /// Create the next fee schedule version for a market and category
+    ///
+    /// # Returns
+    /// * `Ok(FeeSchedule)` - The created schedule
+    /// * `Err(DomainError::Validation)` - Invalid commission, minimum fee or currency
+    /// * `Err(DomainError::BusinessRule)` - The schedule would take effect in the past
+    pub async fn create_schedule(
+        &self,
+        input: NewFeeSchedule,
+        created_by: Option<Uuid>,
+    ) -> DomainResult<FeeSchedule> {
+        let now = Utc::now();
+        let effective_from = input.effective_from.unwrap_or(now);
+        if effective_from < now {
+            return Err(DomainError::BusinessRule {
+                message: "Fee schedules cannot take effect in the past".to_string(),
+            });
+        }
+
+        let market = normalize_market(&input.market);
+        let category = normalized_category(input.category.as_deref());
+        let version = self
+            .schedules
+            .latest_version(&market, category.as_deref())
+            .await?
+            .unwrap_or(0)
+            + 1;
+
+        let schedule = FeeSchedule::new(
+            &market,
+            category.as_deref(),
+            version,
+            input.commission_basis_points,
+            input.minimum_fee,
+            &input.currency,
+            effective_from,
+            created_by,
+        )
+        .map_err(|message| DomainError::Validation { message })?;
+
+        let schedule = self.schedules.save(schedule).await?;
+        info!(
+            market = %schedule.market,
+            category = ?schedule.category,
+            version = schedule.version,
+            effective_from = %schedule.effective_from,
+            "Fee schedule created"
+        );
+
+        Ok(schedule)
+    }
+
+    /// List every schedule version of a market, newest first
+    pub async fn list_schedules(&self, market: &str) -> DomainResult<Vec<FeeSchedule>> {
+        self.schedules.find_by_market(&normalize_market(market)).await
+    }
+
+    /// Resolve the schedule in effect for a job at a point in time
+    ///
+    /// A schedule for the job's category takes precedence over the
+    /// market-wide schedule.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No schedule is in effect for the market
+    pub async fn resolve(
+        &self,
+        market: &str,
+        category: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> DomainResult<FeeSchedule> {
+        let market = normalize_market(market);
+
+        if let Some(category) = normalized_category(category) {
+            if let Some(schedule) = self.schedules.find_effective(&market, Some(&category), at).await? {
+                return Ok(schedule);
+            }
+        }
+
+        self.schedules
+            .find_effective(&market, None, at)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: format!("Fee schedule for market {}", market),
+            })
+    }
+
+    /// Fix the fee of an order when the customer accepts its quote
+    ///
+    /// Accepting again returns the fee fixed the first time, so the order
+    /// keeps its schedule even if a newer one has taken effect since.
+    ///
+    /// # Returns
+    /// * `Ok(OrderFee)` - The fee fixed on the order
+    /// * `Err(DomainError::NotFound)` - No schedule is in effect for the market
+    /// * `Err(DomainError::BusinessRule)` - The quote is not positive or its
+    ///   currency differs from the schedule's
+    pub async fn accept_quote(
+        &self,
+        order_id: Uuid,
+        market: &str,
+        category: Option<&str>,
+        quote_amount: i64,
+        currency: &str,
+        accepted_at: DateTime<Utc>,
+    ) -> DomainResult<OrderFee> {
+        if let Some(fee) = self.order_fees.find_by_order(order_id).await? {
+            return Ok(fee);
+        }
+
+        if quote_amount <= 0 {
+            return Err(DomainError::BusinessRule {
+                message: "Quote amount must be positive".to_string(),
+            });
+        }
+
+        let schedule = self.resolve(market, category, accepted_at).await?;
+        if !schedule.currency.eq_ignore_ascii_case(currency) {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Quote currency {} does not match fee schedule currency {}",
+                    currency, schedule.currency
+                ),
+            });
+        }
+
+        let fee = self
+            .order_fees
+            .save(schedule.apply(order_id, quote_amount, accepted_at))
+            .await?;
+        info!(
+            order_id = %order_id,
+            schedule_id = %fee.schedule_id,
+            schedule_version = fee.schedule_version,
+            fee_amount = fee.fee_amount,
+            "Order fee fixed at quote acceptance"
+        );
+
+        Ok(fee)
+    }
+
+    /// Get the fee fixed on an order
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - The order's quote has not been accepted
+    pub async fn order_fee(&self, order_id: Uuid) -> DomainResult<OrderFee> {
+        self.order_fees
+            .find_by_order(order_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Order fee".to_string(),
+            })
+    }
+}
+
+/// Normalize an optional category, treating blank as market-wide
+fn normalized_category(category: Option<&str>) -> Option<String> {
+    category.map(normalize_category).filter(|c| !c.is_empty())
+}
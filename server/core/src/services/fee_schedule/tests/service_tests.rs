@@ -0,0 +1,205 @@
+//! Unit tests for the fee schedule service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::fee_schedule::{FeeSchedule, OrderFee};
+use crate::errors::DomainError;
+use crate::repositories::{FeeScheduleRepository, OrderFeeRepository};
+use crate::services::fee_schedule::{FeeScheduleService, NewFeeSchedule};
+
+#[derive(Default)]
+struct MockFeeSchedules {
+    schedules: Mutex<Vec<FeeSchedule>>,
+}
+
+impl MockFeeSchedules {
+    fn matching(&self, market: &str, category: Option<&str>) -> Vec<FeeSchedule> {
+        self.schedules
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.market == market && s.category.as_deref() == category)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl FeeScheduleRepository for MockFeeSchedules {
+    async fn save(&self, schedule: FeeSchedule) -> Result<FeeSchedule, DomainError> {
+        self.schedules.lock().unwrap().push(schedule.clone());
+        Ok(schedule)
+    }
+
+    async fn find_by_market(&self, market: &str) -> Result<Vec<FeeSchedule>, DomainError> {
+        let mut schedules: Vec<_> = self
+            .schedules
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.market == market)
+            .cloned()
+            .collect();
+        schedules.sort_by_key(|s| std::cmp::Reverse(s.effective_from));
+        Ok(schedules)
+    }
+
+    async fn latest_version(
+        &self,
+        market: &str,
+        category: Option<&str>,
+    ) -> Result<Option<i32>, DomainError> {
+        Ok(self.matching(market, category).iter().map(|s| s.version).max())
+    }
+
+    async fn find_effective(
+        &self,
+        market: &str,
+        category: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<FeeSchedule>, DomainError> {
+        Ok(self
+            .matching(market, category)
+            .into_iter()
+            .filter(|s| s.effective_from <= at)
+            .max_by_key(|s| s.effective_from))
+    }
+}
+
+#[derive(Default)]
+struct MockOrderFees {
+    fees: Mutex<Vec<OrderFee>>,
+}
+
+#[async_trait]
+impl OrderFeeRepository for MockOrderFees {
+    async fn save(&self, fee: OrderFee) -> Result<OrderFee, DomainError> {
+        self.fees.lock().unwrap().push(fee.clone());
+        Ok(fee)
+    }
+
+    async fn find_by_order(&self, order_id: Uuid) -> Result<Option<OrderFee>, DomainError> {
+        Ok(self.fees.lock().unwrap().iter().find(|f| f.order_id == order_id).cloned())
+    }
+}
+
+fn service() -> FeeScheduleService<MockFeeSchedules, MockOrderFees> {
+    FeeScheduleService::new(Arc::new(MockFeeSchedules::default()), Arc::new(MockOrderFees::default()))
+}
+
+fn new_schedule(
+    category: Option<&str>,
+    commission_basis_points: i64,
+    effective_from: Option<DateTime<Utc>>,
+) -> NewFeeSchedule {
+    NewFeeSchedule {
+        market: "au".to_string(),
+        category: category.map(str::to_string),
+        commission_basis_points,
+        minimum_fee: 0,
+        currency: "AUD".to_string(),
+        effective_from,
+    }
+}
+
+#[tokio::test]
+async fn test_versions_increase_per_market_and_category() {
+    let service = service();
+
+    let first = service.create_schedule(new_schedule(None, 1_000, None), None).await.unwrap();
+    let second = service.create_schedule(new_schedule(None, 1_200, None), None).await.unwrap();
+    let category = service
+        .create_schedule(new_schedule(Some("Plumbing"), 800, None), None)
+        .await
+        .unwrap();
+
+    assert_eq!((first.version, second.version, category.version), (1, 2, 1));
+    assert_eq!(service.list_schedules("AU").await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_schedules_cannot_be_backdated() {
+    let service = service();
+    let yesterday = Utc::now() - Duration::days(1);
+
+    let result = service.create_schedule(new_schedule(None, 1_000, Some(yesterday)), None).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let result = service.create_schedule(new_schedule(None, 20_000, None), None).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_category_schedule_takes_precedence_over_market_schedule() {
+    let service = service();
+    service.create_schedule(new_schedule(None, 1_000, None), None).await.unwrap();
+    service
+        .create_schedule(new_schedule(Some("plumbing"), 800, None), None)
+        .await
+        .unwrap();
+
+    let plumbing = service.resolve("AU", Some("Plumbing"), Utc::now()).await.unwrap();
+    let painting = service.resolve("AU", Some("painting"), Utc::now()).await.unwrap();
+
+    assert_eq!(plumbing.commission_basis_points, 800);
+    assert_eq!(painting.commission_basis_points, 1_000);
+    assert!(matches!(
+        service.resolve("NZ", None, Utc::now()).await,
+        Err(DomainError::NotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_fee_is_fixed_at_quote_acceptance() {
+    let service = service();
+    let now = Utc::now();
+    service
+        .create_schedule(new_schedule(None, 1_000, Some(now + Duration::hours(1))), None)
+        .await
+        .unwrap();
+    service
+        .create_schedule(new_schedule(None, 1_500, Some(now + Duration::hours(2))), None)
+        .await
+        .unwrap();
+
+    let early = Uuid::new_v4();
+    let late = Uuid::new_v4();
+    let early_fee = service
+        .accept_quote(early, "AU", None, 10_000, "aud", now + Duration::minutes(90))
+        .await
+        .unwrap();
+    let late_fee = service
+        .accept_quote(late, "AU", None, 10_000, "AUD", now + Duration::hours(3))
+        .await
+        .unwrap();
+
+    assert_eq!((early_fee.schedule_version, early_fee.fee_amount), (1, 1_000));
+    assert_eq!((late_fee.schedule_version, late_fee.fee_amount), (2, 1_500));
+
+    // Accepting again after the new schedule took effect keeps the agreed fee
+    let again = service
+        .accept_quote(early, "AU", None, 10_000, "AUD", now + Duration::hours(3))
+        .await
+        .unwrap();
+    assert_eq!(again, early_fee);
+    assert_eq!(service.order_fee(early).await.unwrap(), early_fee);
+}
+
+#[tokio::test]
+async fn test_quote_currency_must_match_the_schedule() {
+    let service = service();
+    service.create_schedule(new_schedule(None, 1_000, None), None).await.unwrap();
+
+    let result = service
+        .accept_quote(Uuid::new_v4(), "AU", None, 10_000, "NZD", Utc::now())
+        .await;
+
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(matches!(
+        service.order_fee(Uuid::new_v4()).await,
+        Err(DomainError::NotFound { .. })
+    ));
+}
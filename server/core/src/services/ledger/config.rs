@@ -0,0 +1,29 @@
+//! Configuration for the ledger service
+
+/// Configuration for the ledger service
+#[derive(Debug, Clone)]
+pub struct LedgerConfig {
+    /// Platform commission on job payments, in basis points of the amount
+    pub platform_fee_basis_points: i64,
+    /// Interval between checks that the books balance, in seconds
+    pub verification_interval_seconds: u64,
+    /// Whether to enable the background verification job
+    pub verification_enabled: bool,
+}
+
+impl LedgerConfig {
+    /// Platform commission on an amount, rounded down
+    pub fn platform_fee(&self, amount: i64) -> i64 {
+        amount * self.platform_fee_basis_points.clamp(0, 10_000) / 10_000
+    }
+}
+
+impl Default for LedgerConfig {
+    fn default() -> Self {
+        Self {
+            platform_fee_basis_points: 1_000, // 10% commission
+            verification_interval_seconds: 3_600, // Hourly
+            verification_enabled: true,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Ledger service module keeping the platform's books in double entry
+//!
+//! This module handles:
+//! - Posting payments, tips, fees, credits, payouts, refunds and chargebacks as balanced journal entries
+//! - Rejecting entries that would break the bookkeeping invariants
+//! - Deriving worker earnings and customer credit from the journal
+//! - Periodically verifying that the books balance
+
+mod config;
+mod service;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::LedgerConfig;
+pub use service::LedgerService;
@@ -0,0 +1,427 @@
+//! Ledger service for the platform's double-entry books
+//!
+//! Every posting is built as a journal entry that must balance before it
+//! is saved, and is keyed by its business event so a retried posting
+//! returns the existing entry instead of recording the money twice.
+//! Balances are always derived from the journal, never stored.
+//!
+//! Each tenant keeps its own books. The verification job checks those of
+//! each tenant given to [`LedgerService::with_tenants`] in turn.
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::journal::{
+    AccountKey, AccountType, EntrySide, JournalEntry, JournalLine, JournalTransactionType,
+    LedgerVerification,
+};
+use crate::domain::entities::payment::{normalize_currency, Payment, Payout};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::JournalRepository;
+use crate::services::tenant::{self, current_tenant, TenantId};
+
+use super::config::LedgerConfig;
+
+/// Service posting money movements to the double-entry journal
+pub struct LedgerService<J: JournalRepository + 'static> {
+    journal: Arc<J>,
+    config: LedgerConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<J: JournalRepository + 'static> LedgerService<J> {
+    /// Create a new ledger service
+    pub fn new(journal: Arc<J>, config: LedgerConfig) -> Self {
+        Self {
+            journal,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Verify the books of these tenants in the background task, instead
+    /// of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Post a captured job payment, splitting it between worker and platform
+    /// at the configured default platform fee
+    pub async fn record_payment(&self, payment: &Payment) -> DomainResult<JournalEntry> {
+        self.record_payment_with_fee(payment, self.config.platform_fee(payment.amount)).await
+    }
+
+    /// Post a captured job payment with the platform fee fixed on its order
+    ///
+    /// # Returns
+    /// * `Err(DomainError::BusinessRule)` - The fee is negative or exceeds the payment
+    pub async fn record_payment_with_fee(&self, payment: &Payment, fee: i64) -> DomainResult<JournalEntry> {
+        if !(0..=payment.amount).contains(&fee) {
+            return Err(DomainError::BusinessRule {
+                message: format!("Platform fee {} is outside the payment amount {}", fee, payment.amount),
+            });
+        }
+        let currency = &payment.currency;
+
+        self.post(
+            JournalTransactionType::Payment,
+            &payment.id.to_string(),
+            vec![
+                JournalLine::debit(AccountKey::platform(AccountType::ProviderCash, currency), payment.amount),
+                JournalLine::credit(AccountKey::worker_payable(payment.worker_id, currency), payment.amount - fee),
+                JournalLine::credit(AccountKey::platform(AccountType::PlatformRevenue, currency), fee),
+            ],
+            payment.created_at,
+            format!("Payment {}", payment.provider_reference),
+        )
+        .await
+    }
+
+    /// Post a tip paid alongside a payment; tips go to the worker in full
+    pub async fn record_tip(
+        &self,
+        payment: &Payment,
+        tip_reference: &str,
+        amount: i64,
+        occurred_at: DateTime<Utc>,
+    ) -> DomainResult<JournalEntry> {
+        let currency = &payment.currency;
+
+        self.post(
+            JournalTransactionType::Tip,
+            tip_reference,
+            vec![
+                JournalLine::debit(AccountKey::platform(AccountType::ProviderCash, currency), amount),
+                JournalLine::credit(AccountKey::worker_payable(payment.worker_id, currency), amount),
+            ],
+            occurred_at,
+            format!("Tip on payment {}", payment.provider_reference),
+        )
+        .await
+    }
+
+    /// Post a processing fee the provider deducted from the balance
+    pub async fn record_processing_fee(
+        &self,
+        fee_reference: &str,
+        amount: i64,
+        currency: &str,
+        occurred_at: DateTime<Utc>,
+    ) -> DomainResult<JournalEntry> {
+        self.post(
+            JournalTransactionType::Fee,
+            fee_reference,
+            vec![
+                JournalLine::debit(AccountKey::platform(AccountType::ProcessingFees, currency), amount),
+                JournalLine::credit(AccountKey::platform(AccountType::ProviderCash, currency), amount),
+            ],
+            occurred_at,
+            format!("Processing fee {}", fee_reference),
+        )
+        .await
+    }
+
+    /// Grant store credit to a customer
+    pub async fn issue_credit(
+        &self,
+        customer_id: Uuid,
+        credit_reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<JournalEntry> {
+        self.post(
+            JournalTransactionType::CreditIssued,
+            credit_reference,
+            vec![
+                JournalLine::debit(AccountKey::platform(AccountType::CustomerIncentives, currency), amount),
+                JournalLine::credit(AccountKey::customer_credit(customer_id, currency), amount),
+            ],
+            Utc::now(),
+            format!("Store credit for customer {}", customer_id),
+        )
+        .await
+    }
+
+    /// Spend a customer's store credit on a job, crediting the worker
+    ///
+    /// # Returns
+    /// * `Err(DomainError::BusinessRule)` - The customer does not have enough credit
+    pub async fn redeem_credit(
+        &self,
+        customer_id: Uuid,
+        worker_id: Uuid,
+        redemption_reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<JournalEntry> {
+        let credit = AccountKey::customer_credit(customer_id, &normalize(currency)?);
+        if let Some(entry) = self.existing(JournalTransactionType::CreditRedeemed, redemption_reference).await? {
+            return Ok(entry);
+        }
+        self.ensure_covered(&credit, amount, "store credit").await?;
+
+        self.post(
+            JournalTransactionType::CreditRedeemed,
+            redemption_reference,
+            vec![
+                JournalLine::debit(credit, amount),
+                JournalLine::credit(AccountKey::worker_payable(worker_id, currency), amount),
+            ],
+            Utc::now(),
+            format!("Store credit redeemed by customer {}", customer_id),
+        )
+        .await
+    }
+
+    /// Write off a customer's unspent store credit that expired
+    pub async fn expire_credit(
+        &self,
+        customer_id: Uuid,
+        expiry_reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<JournalEntry> {
+        self.post(
+            JournalTransactionType::CreditExpired,
+            expiry_reference,
+            vec![
+                JournalLine::debit(AccountKey::customer_credit(customer_id, currency), amount),
+                JournalLine::credit(AccountKey::platform(AccountType::CustomerIncentives, currency), amount),
+            ],
+            Utc::now(),
+            format!("Expired store credit of customer {}", customer_id),
+        )
+        .await
+    }
+
+    /// Post a transfer of earnings to a worker
+    ///
+    /// # Returns
+    /// * `Err(DomainError::BusinessRule)` - The worker is owed less than the payout
+    pub async fn record_payout(&self, payout: &Payout) -> DomainResult<JournalEntry> {
+        let payable = AccountKey::worker_payable(payout.worker_id, &payout.currency);
+        if let Some(entry) = self.existing(JournalTransactionType::Payout, &payout.id.to_string()).await? {
+            return Ok(entry);
+        }
+        self.ensure_covered(&payable, payout.amount, "earnings").await?;
+
+        self.post(
+            JournalTransactionType::Payout,
+            &payout.id.to_string(),
+            vec![
+                JournalLine::debit(payable, payout.amount),
+                JournalLine::credit(AccountKey::platform(AccountType::ProviderCash, &payout.currency), payout.amount),
+            ],
+            Utc::now(),
+            format!("Payout {} to worker {}", payout.id, payout.worker_id),
+        )
+        .await
+    }
+
+    /// Post a refund, clawing it back from the worker and the platform in
+    /// the same proportion the payment was split
+    pub async fn record_refund(
+        &self,
+        payment: &Payment,
+        refund_reference: &str,
+        amount: i64,
+        occurred_at: DateTime<Utc>,
+    ) -> DomainResult<JournalEntry> {
+        self.post(
+            JournalTransactionType::Refund,
+            refund_reference,
+            self.clawback_lines(payment, amount),
+            occurred_at,
+            format!("Refund of payment {}", payment.provider_reference),
+        )
+        .await
+    }
+
+    /// Post a chargeback, clawed back like a refund until the dispute is decided
+    pub async fn record_chargeback(
+        &self,
+        payment: &Payment,
+        dispute_reference: &str,
+        amount: i64,
+        occurred_at: DateTime<Utc>,
+    ) -> DomainResult<JournalEntry> {
+        self.post(
+            JournalTransactionType::Chargeback,
+            dispute_reference,
+            self.clawback_lines(payment, amount),
+            occurred_at,
+            format!("Chargeback of payment {}", payment.provider_reference),
+        )
+        .await
+    }
+
+    /// Reverse the chargeback of a dispute the platform won
+    ///
+    /// # Returns
+    /// * `Ok(None)` - No chargeback was posted for the dispute
+    pub async fn reverse_chargeback(
+        &self,
+        dispute_reference: &str,
+        occurred_at: DateTime<Utc>,
+    ) -> DomainResult<Option<JournalEntry>> {
+        let Some(chargeback) = self.existing(JournalTransactionType::Chargeback, dispute_reference).await? else {
+            return Ok(None);
+        };
+
+        let lines = chargeback
+            .lines
+            .into_iter()
+            .map(|line| match line.side {
+                EntrySide::Debit => JournalLine::credit(line.account, line.amount),
+                EntrySide::Credit => JournalLine::debit(line.account, line.amount),
+            })
+            .collect();
+
+        self.post(
+            JournalTransactionType::ChargebackReversal,
+            dispute_reference,
+            lines,
+            occurred_at,
+            format!("Won chargeback {}", dispute_reference),
+        )
+        .await
+        .map(Some)
+    }
+
+    /// What the platform owes a worker in a currency
+    pub async fn worker_balance(&self, worker_id: Uuid, currency: &str) -> DomainResult<i64> {
+        let account = AccountKey::worker_payable(worker_id, &normalize(currency)?);
+        Ok(self.journal.account_balance(&account).await?.balance())
+    }
+
+    /// Store credit a customer can spend in a currency
+    pub async fn customer_credit_balance(&self, customer_id: Uuid, currency: &str) -> DomainResult<i64> {
+        let account = AccountKey::customer_credit(customer_id, &normalize(currency)?);
+        Ok(self.journal.account_balance(&account).await?.balance())
+    }
+
+    /// Check that debits equal credits in every currency and every entry
+    ///
+    /// An unbalanced result is logged as an error; owner accounts with a
+    /// negative balance, e.g. a refund after the worker was paid out, are
+    /// reported as warnings.
+    pub async fn verify_books(&self) -> DomainResult<LedgerVerification> {
+        let balances = self.journal.trial_balance().await?;
+        let unbalanced = self.journal.find_unbalanced_entries().await?;
+        let verification = LedgerVerification::from_trial_balance(&balances, unbalanced);
+
+        if verification.is_balanced() {
+            info!(currencies = verification.totals.len(), "Ledger verification passed");
+        } else {
+            error!(
+                tenant = %current_tenant(),
+                totals = ?verification.totals,
+                unbalanced_entries = ?verification.unbalanced_entries,
+                "Ledger verification failed: the books do not balance"
+            );
+        }
+
+        for account in &verification.negative_accounts {
+            warn!(
+                tenant = %current_tenant(),
+                account_type = account.account.account_type.as_str(),
+                owner_id = ?account.account.owner_id,
+                currency = %account.account.currency,
+                balance = account.balance(),
+                "Ledger account has a negative balance"
+            );
+        }
+
+        Ok(verification)
+    }
+
+    /// Start the ledger verification as a background task
+    ///
+    /// This spawns a tokio task that verifies the books at the configured interval
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.verification_enabled {
+            warn!("Ledger verification is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.verification_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Ledger verification started - checking every {} seconds",
+                self.config.verification_interval_seconds
+            );
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for tenant in &self.tenants {
+                    if let Err(e) = tenant::scope(tenant.clone(), self.verify_books()).await {
+                        error!("Ledger verification for tenant {} failed to run: {}", tenant, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Lines moving `amount` out of the provider balance, split between the
+    /// worker and the platform like the payment
+    fn clawback_lines(&self, payment: &Payment, amount: i64) -> Vec<JournalLine> {
+        let fee = self.config.platform_fee(amount);
+        let currency = &payment.currency;
+
+        vec![
+            JournalLine::debit(AccountKey::worker_payable(payment.worker_id, currency), amount - fee),
+            JournalLine::debit(AccountKey::platform(AccountType::PlatformRevenue, currency), fee),
+            JournalLine::credit(AccountKey::platform(AccountType::ProviderCash, currency), amount),
+        ]
+    }
+
+    /// Reject a debit larger than an owner account's balance
+    async fn ensure_covered(&self, account: &AccountKey, amount: i64, what: &str) -> DomainResult<()> {
+        let available = self.journal.account_balance(account).await?.balance();
+        if amount > available {
+            return Err(DomainError::BusinessRule {
+                message: format!("Insufficient {}: {} available, {} requested", what, available, amount),
+            });
+        }
+        Ok(())
+    }
+
+    /// Find an already posted entry for a business event
+    async fn existing(
+        &self,
+        transaction_type: JournalTransactionType,
+        reference: &str,
+    ) -> DomainResult<Option<JournalEntry>> {
+        self.journal.find_by_reference(transaction_type, reference).await
+    }
+
+    /// Build, check and save a journal entry unless the event was already posted
+    async fn post(
+        &self,
+        transaction_type: JournalTransactionType,
+        reference: &str,
+        lines: Vec<JournalLine>,
+        occurred_at: DateTime<Utc>,
+        description: String,
+    ) -> DomainResult<JournalEntry> {
+        if let Some(entry) = self.existing(transaction_type, reference).await? {
+            return Ok(entry);
+        }
+
+        let entry = JournalEntry::new(transaction_type, reference, lines, occurred_at)
+            .map_err(|message| DomainError::BusinessRule { message })?
+            .with_description(description);
+
+        self.journal.save(entry).await
+    }
+}
+
+/// Normalize a currency code, rejecting invalid ones as a validation error
+fn normalize(currency: &str) -> DomainResult<String> {
+    normalize_currency(currency).map_err(|message| DomainError::Validation { message })
+}
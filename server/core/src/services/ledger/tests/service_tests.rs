@@ -0,0 +1,227 @@
+//! Unit tests for the ledger service
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::journal::{
+    AccountBalance, AccountKey, AccountType, EntrySide, JournalEntry, JournalTransactionType,
+};
+use crate::domain::entities::payment::{Payment, Payout};
+use crate::errors::DomainError;
+use crate::repositories::JournalRepository;
+use crate::services::ledger::{LedgerConfig, LedgerService};
+
+/// In-memory journal shared with the tests of services that post to the ledger
+#[derive(Default)]
+pub struct MockJournal {
+    pub entries: Mutex<Vec<JournalEntry>>,
+}
+
+impl MockJournal {
+    fn balance_of(entries: &[JournalEntry], account: &AccountKey) -> AccountBalance {
+        let mut balance = AccountBalance::empty(account.clone());
+        for line in entries.iter().flat_map(|e| &e.lines).filter(|l| &l.account == account) {
+            match line.side {
+                EntrySide::Debit => balance.debits += line.amount,
+                EntrySide::Credit => balance.credits += line.amount,
+            }
+        }
+        balance
+    }
+}
+
+#[async_trait]
+impl JournalRepository for MockJournal {
+    async fn save(&self, entry: JournalEntry) -> Result<JournalEntry, DomainError> {
+        self.entries.lock().unwrap().push(entry.clone());
+        Ok(entry)
+    }
+
+    async fn find_by_reference(
+        &self,
+        transaction_type: JournalTransactionType,
+        reference: &str,
+    ) -> Result<Option<JournalEntry>, DomainError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.transaction_type == transaction_type && e.reference == reference)
+            .cloned())
+    }
+
+    async fn account_balance(&self, account: &AccountKey) -> Result<AccountBalance, DomainError> {
+        Ok(Self::balance_of(&self.entries.lock().unwrap(), account))
+    }
+
+    async fn trial_balance(&self) -> Result<Vec<AccountBalance>, DomainError> {
+        let entries = self.entries.lock().unwrap();
+        let mut accounts: Vec<AccountKey> = entries
+            .iter()
+            .flat_map(|e| e.lines.iter().map(|l| l.account.clone()))
+            .collect();
+        accounts.sort();
+        accounts.dedup();
+
+        Ok(accounts.iter().map(|a| Self::balance_of(&entries, a)).collect())
+    }
+
+    async fn find_unbalanced_entries(&self) -> Result<Vec<Uuid>, DomainError> {
+        Ok(self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.totals_by_currency().values().any(|(d, c)| d != c))
+            .map(|e| e.id)
+            .collect())
+    }
+}
+
+fn service() -> (LedgerService<MockJournal>, Arc<MockJournal>) {
+    let journal = Arc::new(MockJournal::default());
+    (LedgerService::new(journal.clone(), LedgerConfig::default()), journal)
+}
+
+fn payment(amount: i64) -> Payment {
+    Payment::new("stripe", "ch_1", Uuid::new_v4(), Uuid::new_v4(), amount, "AUD").unwrap()
+}
+
+#[tokio::test]
+async fn test_payment_is_split_between_worker_and_platform() {
+    let (service, journal) = service();
+    let payment = payment(10_000);
+
+    let entry = service.record_payment(&payment).await.unwrap();
+
+    assert_eq!(entry.lines.len(), 3);
+    assert_eq!(service.worker_balance(payment.worker_id, "aud").await.unwrap(), 9_000);
+    let revenue = journal
+        .account_balance(&AccountKey::platform(AccountType::PlatformRevenue, "AUD"))
+        .await
+        .unwrap();
+    assert_eq!(revenue.balance(), 1_000);
+}
+
+#[tokio::test]
+async fn test_posting_the_same_event_twice_records_it_once() {
+    let (service, journal) = service();
+    let payment = payment(10_000);
+
+    let first = service.record_payment(&payment).await.unwrap();
+    let second = service.record_payment(&payment).await.unwrap();
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(journal.entries.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_tips_go_to_the_worker_in_full() {
+    let (service, _) = service();
+    let payment = payment(10_000);
+
+    service.record_payment(&payment).await.unwrap();
+    service.record_tip(&payment, "tip_1", 500, Utc::now()).await.unwrap();
+
+    assert_eq!(service.worker_balance(payment.worker_id, "AUD").await.unwrap(), 9_500);
+}
+
+#[tokio::test]
+async fn test_payout_cannot_exceed_worker_earnings() {
+    let (service, _) = service();
+    let payment = payment(10_000);
+    service.record_payment(&payment).await.unwrap();
+
+    let too_much = Payout::new(&payment, 9_001);
+    let result = service.record_payout(&too_much).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let payout = Payout::new(&payment, 9_000);
+    service.record_payout(&payout).await.unwrap();
+    assert_eq!(service.worker_balance(payment.worker_id, "AUD").await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_credit_redemption_is_limited_to_issued_credit() {
+    let (service, _) = service();
+    let customer_id = Uuid::new_v4();
+    let worker_id = Uuid::new_v4();
+
+    service.issue_credit(customer_id, "goodwill_1", 2_000, "AUD").await.unwrap();
+    let result = service.redeem_credit(customer_id, worker_id, "order_1", 2_500, "AUD").await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    service.redeem_credit(customer_id, worker_id, "order_1", 1_500, "AUD").await.unwrap();
+    assert_eq!(service.customer_credit_balance(customer_id, "AUD").await.unwrap(), 500);
+    assert_eq!(service.worker_balance(worker_id, "AUD").await.unwrap(), 1_500);
+}
+
+#[tokio::test]
+async fn test_won_chargeback_is_reversed() {
+    let (service, _) = service();
+    let payment = payment(10_000);
+    service.record_payment(&payment).await.unwrap();
+
+    service.record_chargeback(&payment, "dp_1", 10_000, Utc::now()).await.unwrap();
+    assert_eq!(service.worker_balance(payment.worker_id, "AUD").await.unwrap(), 0);
+
+    let reversal = service.reverse_chargeback("dp_1", Utc::now()).await.unwrap();
+    assert!(reversal.is_some());
+    assert_eq!(service.worker_balance(payment.worker_id, "AUD").await.unwrap(), 9_000);
+    assert!(service.reverse_chargeback("dp_unknown", Utc::now()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_books_balance_after_every_kind_of_posting() {
+    let (service, _) = service();
+    let payment = payment(10_000);
+    let customer_id = Uuid::new_v4();
+
+    service.record_payment(&payment).await.unwrap();
+    service.record_tip(&payment, "tip_1", 500, Utc::now()).await.unwrap();
+    service.record_processing_fee("fee_1", 320, "AUD", Utc::now()).await.unwrap();
+    service.issue_credit(customer_id, "goodwill_1", 1_000, "CNY").await.unwrap();
+    service.redeem_credit(customer_id, payment.worker_id, "order_1", 1_000, "CNY").await.unwrap();
+    service.record_payout(&Payout::new(&payment, 9_500)).await.unwrap();
+    service.record_refund(&payment, "re_1", 2_000, Utc::now()).await.unwrap();
+
+    let verification = service.verify_books().await.unwrap();
+
+    assert!(verification.is_balanced());
+    assert_eq!(verification.totals.len(), 2);
+    // The refund after the payout leaves the worker owing the platform
+    assert_eq!(verification.negative_accounts.len(), 1);
+    assert_eq!(verification.negative_accounts[0].balance(), -1_800);
+}
+
+#[tokio::test]
+async fn test_verification_detects_unbalanced_entries() {
+    let (service, journal) = service();
+    let payment = payment(10_000);
+    let mut entry = service.record_payment(&payment).await.unwrap();
+
+    // Simulate an entry corrupted outside the service
+    entry.id = Uuid::new_v4();
+    entry.lines[0].amount += 1;
+    journal.entries.lock().unwrap().push(entry.clone());
+
+    let verification = service.verify_books().await.unwrap();
+
+    assert!(!verification.is_balanced());
+    assert_eq!(verification.unbalanced_entries, vec![entry.id]);
+}
+
+#[tokio::test]
+async fn test_payment_uses_the_fee_fixed_on_the_order() {
+    let (service, _) = service();
+    let payment = payment(10_000);
+
+    let result = service.record_payment_with_fee(&payment, 10_001).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    service.record_payment_with_fee(&payment, 1_500).await.unwrap();
+    assert_eq!(service.worker_balance(payment.worker_id, "AUD").await.unwrap(), 8_500);
+}
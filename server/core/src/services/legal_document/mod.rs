@@ -0,0 +1,14 @@
+//! Legal document service module for terms of service and privacy policies
+//!
+//! This module handles:
+//! - Publishing document versions per market in several languages with an effective date
+//! - Resolving the version in effect, preferring market-specific versions over global ones
+//! - Falling back to the default language when a translation is missing
+//! - Recording which exact version each user accepted and which they still must accept
+
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use service::{LegalDocumentService, LegalTranslation, NewLegalDocumentVersion};
@@ -0,0 +1,311 @@
+//! Legal document service for versioned terms of service and privacy policies
+//!
+//! Documents are never edited: a change is a new version, published in all
+//! of its languages at once, that takes effect at a given time. Users
+//! accept an exact version, and only the version currently in effect can
+//! be accepted, so what was accepted is always what the app showed.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::normalize_market;
+use crate::domain::entities::legal_document::{
+    normalize_language, LegalAcceptance, LegalDocument, LegalDocumentKind, DEFAULT_LEGAL_LANGUAGE,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::LegalDocumentRepository;
+
+/// Longest title accepted for a translation
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// One language of a new document version
+#[derive(Debug, Clone)]
+pub struct LegalTranslation {
+    /// Language code, e.g. "en" or "zh-CN"
+    pub language: String,
+    /// Title shown above the document
+    pub title: String,
+    /// Document text in Markdown
+    pub body: String,
+}
+
+/// Input for a new legal document version
+#[derive(Debug, Clone)]
+pub struct NewLegalDocumentVersion {
+    /// Kind of document
+    pub kind: LegalDocumentKind,
+    /// Market code (ISO 3166-1 alpha-2), `None` for every market
+    pub market: Option<String>,
+    /// When the version takes effect; `None` takes effect immediately
+    pub effective_from: Option<DateTime<Utc>>,
+    /// Translations, which must include the default language
+    pub translations: Vec<LegalTranslation>,
+}
+
+/// Service serving legal documents and recording their acceptance
+pub struct LegalDocumentService<L>
+where
+    L: LegalDocumentRepository + 'static,
+{
+    documents: Arc<L>,
+}
+
+impl<L> LegalDocumentService<L>
+where
+    L: LegalDocumentRepository + 'static,
+{
+    /// Create a new legal document service
+    pub fn new(documents: Arc<L>) -> Self {
+        Self { documents }
+    }
+
+    /// Publish the next version of a document for a market
+    ///
+    /// # Returns
+    /// * `Ok(Vec<LegalDocument>)` - The translations of the published version
+    /// * `Err(DomainError::Validation)` - Invalid market, language, title or body,
+    ///   a duplicated language, or no translation in the default language
+    /// * `Err(DomainError::BusinessRule)` - The version would take effect in the past
+    pub async fn publish(
+        &self,
+        input: NewLegalDocumentVersion,
+        created_by: Option<Uuid>,
+    ) -> DomainResult<Vec<LegalDocument>> {
+        let now = Utc::now();
+        let effective_from = input.effective_from.unwrap_or(now);
+        if effective_from < now {
+            return Err(DomainError::BusinessRule {
+                message: "Legal documents cannot take effect in the past".to_string(),
+            });
+        }
+
+        let market = normalized_market(input.market.as_deref());
+        if market
+            .as_deref()
+            .is_some_and(|m| m.len() != 2 || !m.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            return Err(DomainError::Validation {
+                message: "Market must be a two-letter country code".to_string(),
+            });
+        }
+
+        let mut languages = HashSet::new();
+        for translation in &input.translations {
+            let language = normalize_language(&translation.language).ok_or_else(|| DomainError::Validation {
+                message: format!("Invalid language: {}", translation.language),
+            })?;
+            if !languages.insert(language.clone()) {
+                return Err(DomainError::Validation {
+                    message: format!("Language {} is listed more than once", language),
+                });
+            }
+            let title = translation.title.trim();
+            if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+                return Err(DomainError::Validation {
+                    message: format!("Title must be between 1 and {} characters", MAX_TITLE_LENGTH),
+                });
+            }
+            if translation.body.trim().is_empty() {
+                return Err(DomainError::Validation {
+                    message: format!("The {} translation has no text", language),
+                });
+            }
+        }
+        if !languages.contains(DEFAULT_LEGAL_LANGUAGE) {
+            return Err(DomainError::Validation {
+                message: format!("A translation in \"{}\" is required", DEFAULT_LEGAL_LANGUAGE),
+            });
+        }
+
+        let version = self
+            .documents
+            .latest_version(input.kind, market.as_deref())
+            .await?
+            .unwrap_or(0)
+            + 1;
+
+        let translations: Vec<LegalDocument> = input
+            .translations
+            .iter()
+            .map(|translation| LegalDocument {
+                id: Uuid::new_v4(),
+                kind: input.kind,
+                market: market.clone(),
+                version,
+                language: normalize_language(&translation.language).unwrap_or_default(),
+                title: translation.title.trim().to_string(),
+                body: translation.body.clone(),
+                effective_from,
+                created_by,
+                created_at: now,
+            })
+            .collect();
+        self.documents.save_version(&translations).await?;
+
+        info!(
+            kind = input.kind.as_str(),
+            market = ?market,
+            version,
+            languages = translations.len(),
+            effective_from = %effective_from,
+            "Legal document version published"
+        );
+        Ok(translations)
+    }
+
+    /// Get the version of a document in effect for a market
+    ///
+    /// A version for the market takes precedence over the version for every
+    /// market. The translation falls back to the default language.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No version is in effect
+    pub async fn current(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        language: &str,
+        at: DateTime<Utc>,
+    ) -> DomainResult<LegalDocument> {
+        let translations = self.effective(kind, market, at).await?;
+        pick_translation(translations, language).ok_or_else(|| not_found(kind))
+    }
+
+    /// Get an exact version of a document
+    ///
+    /// Unlike [`current`](Self::current), the market is not resolved: it
+    /// must be the market of the version, as returned with the document.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such version, or it has not taken effect yet
+    pub async fn version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        version: i32,
+        language: &str,
+    ) -> DomainResult<LegalDocument> {
+        let market = normalized_market(market);
+        let translations = self
+            .documents
+            .find_version(kind, market.as_deref(), version)
+            .await?;
+        pick_translation(translations, language)
+            .filter(|document| document.effective_from <= Utc::now())
+            .ok_or_else(|| not_found(kind))
+    }
+
+    /// Record that a user accepted the version of a document in effect for their market
+    ///
+    /// # Arguments
+    /// * `version` - Version the app showed; must be the version in effect
+    /// * `language` - Language the document was shown in
+    ///
+    /// # Returns
+    /// * `Ok(LegalAcceptance)` - The recorded acceptance
+    /// * `Err(DomainError::NotFound)` - No version is in effect
+    /// * `Err(DomainError::BusinessRule)` - The version is not the one in effect
+    pub async fn accept(
+        &self,
+        user_id: Uuid,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        version: i32,
+        language: &str,
+    ) -> DomainResult<LegalAcceptance> {
+        let document = self.current(kind, market, language, Utc::now()).await?;
+        if document.version != version {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Version {} of the {} document is not current; version {} must be accepted",
+                    version,
+                    kind.as_str(),
+                    document.version
+                ),
+            });
+        }
+
+        let acceptance = LegalAcceptance::new(user_id, &document);
+        self.documents.save_acceptance(&acceptance).await?;
+
+        info!(
+            user_id = %user_id,
+            kind = kind.as_str(),
+            market = ?document.market,
+            version,
+            "Legal document accepted"
+        );
+        Ok(acceptance)
+    }
+
+    /// List the documents in effect that a user has not accepted
+    ///
+    /// Kinds without any version in effect are skipped.
+    pub async fn pending(
+        &self,
+        user_id: Uuid,
+        market: Option<&str>,
+        language: &str,
+        at: DateTime<Utc>,
+    ) -> DomainResult<Vec<LegalDocument>> {
+        let acceptances = self.documents.find_acceptances(user_id).await?;
+
+        let mut pending = Vec::new();
+        for kind in LegalDocumentKind::ALL {
+            let translations = self.effective(kind, market, at).await?;
+            if let Some(document) = pick_translation(translations, language) {
+                if !acceptances.iter().any(|acceptance| acceptance.covers(&document)) {
+                    pending.push(document);
+                }
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Translations of the version in effect, for the market or else for every market
+    async fn effective(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> DomainResult<Vec<LegalDocument>> {
+        if let Some(market) = normalized_market(market) {
+            let translations = self.documents.find_effective(kind, Some(&market), at).await?;
+            if !translations.is_empty() {
+                return Ok(translations);
+            }
+        }
+
+        self.documents.find_effective(kind, None, at).await
+    }
+}
+
+/// Normalize an optional market, treating blank as none
+fn normalized_market(market: Option<&str>) -> Option<String> {
+    market.map(normalize_market).filter(|m| !m.is_empty())
+}
+
+/// Pick the translation in a language, else in the default language
+fn pick_translation(translations: Vec<LegalDocument>, language: &str) -> Option<LegalDocument> {
+    let language = normalize_language(language).unwrap_or_else(|| DEFAULT_LEGAL_LANGUAGE.to_string());
+
+    let mut fallback = None;
+    for document in translations {
+        if document.language == language {
+            return Some(document);
+        }
+        if document.language == DEFAULT_LEGAL_LANGUAGE || fallback.is_none() {
+            fallback = Some(document);
+        }
+    }
+    fallback
+}
+
+fn not_found(kind: LegalDocumentKind) -> DomainError {
+    DomainError::NotFound {
+        resource: format!("Legal document {}", kind.as_str()),
+    }
+}
@@ -0,0 +1,248 @@
+//! Unit tests for the legal document service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::legal_document::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+use crate::errors::DomainError;
+use crate::repositories::LegalDocumentRepository;
+use crate::services::legal_document::{LegalDocumentService, LegalTranslation, NewLegalDocumentVersion};
+
+#[derive(Default)]
+struct MockLegalDocuments {
+    documents: Mutex<Vec<LegalDocument>>,
+    acceptances: Mutex<Vec<LegalAcceptance>>,
+}
+
+impl MockLegalDocuments {
+    fn matching(&self, kind: LegalDocumentKind, market: Option<&str>) -> Vec<LegalDocument> {
+        self.documents
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| d.kind == kind && d.market.as_deref() == market)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl LegalDocumentRepository for MockLegalDocuments {
+    async fn save_version(&self, translations: &[LegalDocument]) -> Result<(), DomainError> {
+        self.documents.lock().unwrap().extend_from_slice(translations);
+        Ok(())
+    }
+
+    async fn latest_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+    ) -> Result<Option<i32>, DomainError> {
+        Ok(self.matching(kind, market).iter().map(|d| d.version).max())
+    }
+
+    async fn find_effective(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<LegalDocument>, DomainError> {
+        let documents = self.matching(kind, market);
+        let version = documents
+            .iter()
+            .filter(|d| d.effective_from <= at)
+            .max_by_key(|d| d.effective_from)
+            .map(|d| d.version);
+        Ok(documents.into_iter().filter(|d| Some(d.version) == version).collect())
+    }
+
+    async fn find_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        version: i32,
+    ) -> Result<Vec<LegalDocument>, DomainError> {
+        Ok(self.matching(kind, market).into_iter().filter(|d| d.version == version).collect())
+    }
+
+    async fn save_acceptance(&self, acceptance: &LegalAcceptance) -> Result<(), DomainError> {
+        let mut acceptances = self.acceptances.lock().unwrap();
+        let duplicate = acceptances.iter().any(|a| {
+            a.user_id == acceptance.user_id
+                && a.kind == acceptance.kind
+                && a.market == acceptance.market
+                && a.version == acceptance.version
+        });
+        if !duplicate {
+            acceptances.push(acceptance.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_acceptances(&self, user_id: Uuid) -> Result<Vec<LegalAcceptance>, DomainError> {
+        Ok(self
+            .acceptances
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+}
+
+fn translation(language: &str, title: &str) -> LegalTranslation {
+    LegalTranslation {
+        language: language.to_string(),
+        title: title.to_string(),
+        body: format!("# {}", title),
+    }
+}
+
+fn new_version(kind: LegalDocumentKind, market: Option<&str>, translations: Vec<LegalTranslation>) -> NewLegalDocumentVersion {
+    NewLegalDocumentVersion {
+        kind,
+        market: market.map(str::to_string),
+        effective_from: None,
+        translations,
+    }
+}
+
+fn service() -> (LegalDocumentService<MockLegalDocuments>, Arc<MockLegalDocuments>) {
+    let documents = Arc::new(MockLegalDocuments::default());
+    (LegalDocumentService::new(documents.clone()), documents)
+}
+
+#[tokio::test]
+async fn test_publish_requires_default_language_and_increments_version() {
+    let (service, _) = service();
+
+    let missing_default = service
+        .publish(new_version(LegalDocumentKind::Terms, None, vec![translation("zh", "服务条款")]), None)
+        .await;
+    assert!(matches!(missing_default, Err(DomainError::Validation { .. })));
+
+    let first = service
+        .publish(
+            new_version(
+                LegalDocumentKind::Terms,
+                Some("au"),
+                vec![translation("en", "Terms"), translation("zh-CN", "服务条款")],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 2);
+    assert_eq!(first[0].version, 1);
+    assert_eq!(first[0].market.as_deref(), Some("AU"));
+    assert_eq!(first[1].language, "zh");
+
+    let second = service
+        .publish(new_version(LegalDocumentKind::Terms, Some("AU"), vec![translation("en", "Terms v2")]), None)
+        .await
+        .unwrap();
+    assert_eq!(second[0].version, 2);
+}
+
+#[tokio::test]
+async fn test_current_prefers_market_version_and_falls_back_to_default_language() {
+    let (service, _) = service();
+    service
+        .publish(new_version(LegalDocumentKind::Privacy, None, vec![translation("en", "Global privacy")]), None)
+        .await
+        .unwrap();
+    service
+        .publish(
+            new_version(
+                LegalDocumentKind::Privacy,
+                Some("CN"),
+                vec![translation("en", "China privacy"), translation("zh", "隐私政策")],
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let now = Utc::now() + Duration::seconds(1);
+
+    let cn = service.current(LegalDocumentKind::Privacy, Some("cn"), "zh-CN", now).await.unwrap();
+    assert_eq!(cn.title, "隐私政策");
+
+    let au = service.current(LegalDocumentKind::Privacy, Some("AU"), "zh", now).await.unwrap();
+    assert_eq!(au.title, "Global privacy");
+    assert_eq!(au.market, None);
+
+    let terms = service.current(LegalDocumentKind::Terms, Some("AU"), "en", now).await;
+    assert!(matches!(terms, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_future_version_is_not_served_until_effective() {
+    let (service, _) = service();
+    service
+        .publish(new_version(LegalDocumentKind::Terms, None, vec![translation("en", "Terms v1")]), None)
+        .await
+        .unwrap();
+    let effective_from = Utc::now() + Duration::days(30);
+    service
+        .publish(
+            NewLegalDocumentVersion {
+                effective_from: Some(effective_from),
+                ..new_version(LegalDocumentKind::Terms, None, vec![translation("en", "Terms v2")])
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let now = service.current(LegalDocumentKind::Terms, None, "en", Utc::now()).await.unwrap();
+    assert_eq!(now.version, 1);
+    let later = service.current(LegalDocumentKind::Terms, None, "en", effective_from).await.unwrap();
+    assert_eq!(later.version, 2);
+
+    let pinned = service.version(LegalDocumentKind::Terms, None, 2, "en").await;
+    assert!(matches!(pinned, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_accept_only_current_version_and_pending_tracks_acceptance() {
+    let (service, _) = service();
+    service
+        .publish(new_version(LegalDocumentKind::Terms, None, vec![translation("en", "Terms")]), None)
+        .await
+        .unwrap();
+    service
+        .publish(new_version(LegalDocumentKind::Privacy, None, vec![translation("en", "Privacy")]), None)
+        .await
+        .unwrap();
+    let user_id = Uuid::new_v4();
+    let now = Utc::now() + Duration::seconds(1);
+
+    let pending = service.pending(user_id, Some("AU"), "en", now).await.unwrap();
+    assert_eq!(pending.len(), 2);
+
+    let stale = service.accept(user_id, LegalDocumentKind::Terms, Some("AU"), 0, "en").await;
+    assert!(matches!(stale, Err(DomainError::BusinessRule { .. })));
+
+    let acceptance = service
+        .accept(user_id, LegalDocumentKind::Terms, Some("AU"), 1, "en")
+        .await
+        .unwrap();
+    assert_eq!(acceptance.market, None);
+
+    let pending = service.pending(user_id, Some("AU"), "en", now).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].kind, LegalDocumentKind::Privacy);
+
+    service
+        .publish(new_version(LegalDocumentKind::Terms, Some("AU"), vec![translation("en", "AU terms")]), None)
+        .await
+        .unwrap();
+    let pending = service
+        .pending(user_id, Some("AU"), "en", Utc::now() + Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 2);
+}
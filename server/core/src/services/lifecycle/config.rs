@@ -0,0 +1,31 @@
+//! Configuration for the lifecycle hook service
+
+/// Configuration for the lifecycle hook service
+#[derive(Debug, Clone)]
+pub struct LifecycleHookConfig {
+    /// Maximum number of hook runs waiting in the queue
+    pub queue_capacity: usize,
+    /// Maximum number of hooks running at once
+    pub max_concurrent: usize,
+    /// Attempts before a hook run is given up
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every failed attempt (in milliseconds)
+    pub base_retry_delay_ms: u64,
+    /// Upper bound for the retry delay (in milliseconds)
+    pub max_retry_delay_ms: u64,
+    /// Whether to run hooks at all
+    pub enabled: bool,
+}
+
+impl Default for LifecycleHookConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 1000,
+            max_concurrent: 4,
+            max_attempts: 5, // Gives up after roughly 15 minutes
+            base_retry_delay_ms: 60_000,
+            max_retry_delay_ms: 600_000,
+            enabled: true,
+        }
+    }
+}
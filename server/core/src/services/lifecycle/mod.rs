@@ -0,0 +1,19 @@
+//! Lifecycle hook module for first-party integrations
+//!
+//! This module handles:
+//! - Registering hooks in code, such as CRM sync, email sequences and analytics
+//! - Queueing user registration, order completion, payout and canary
+//!   exposure events for them
+//! - Running the hooks off the request path, retrying failures with
+//!   exponential backoff
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::LifecycleHookConfig;
+pub use service::{LifecycleHookMetrics, LifecycleHookService};
+pub use traits::{LifecycleEvent, LifecycleHookTrait};
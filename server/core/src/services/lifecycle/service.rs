@@ -0,0 +1,225 @@
+//! Lifecycle hook service
+//!
+//! Publishing an event queues one run for every hook handling it and
+//! returns immediately, so hooks never slow down or fail the request that
+//! caused the event. A background task runs the queued hooks; a failed run
+//! is queued again after an exponentially growing delay until it succeeds
+//! or runs out of attempts. The queue is held in memory: runs still queued
+//! when the process stops are lost.
+//!
+//! Hooks run in the tenant scope the event was published in, so they read
+//! and write the rows of the tenant whose request caused it.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{error, info, warn};
+
+use crate::services::tenant::{self, current_tenant, TenantId};
+
+use super::config::LifecycleHookConfig;
+use super::traits::{LifecycleEvent, LifecycleHookTrait};
+
+/// Counts of hook runs since the service started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LifecycleHookMetrics {
+    /// Runs queued for published events
+    pub queued: u64,
+    /// Runs that completed
+    pub succeeded: u64,
+    /// Failed runs queued again
+    pub retried: u64,
+    /// Runs given up after their last attempt
+    pub failed: u64,
+    /// Runs dropped because the queue was full
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    queued: AtomicU64,
+    succeeded: AtomicU64,
+    retried: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// A hook to run for an event
+struct HookRun {
+    hook: Arc<dyn LifecycleHookTrait>,
+    event: Arc<LifecycleEvent>,
+    tenant: TenantId,
+    attempt: u32,
+}
+
+/// Service running first-party hooks after lifecycle events
+pub struct LifecycleHookService {
+    hooks: Vec<Arc<dyn LifecycleHookTrait>>,
+    config: LifecycleHookConfig,
+    sender: mpsc::Sender<HookRun>,
+    receiver: Mutex<Option<mpsc::Receiver<HookRun>>>,
+    counters: Arc<Counters>,
+}
+
+impl LifecycleHookService {
+    /// Create a lifecycle hook service without hooks
+    pub fn new(config: LifecycleHookConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        Self {
+            hooks: Vec::new(),
+            config,
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    /// Register a hook
+    pub fn with_hook(mut self, hook: Arc<dyn LifecycleHookTrait>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    /// Counts of hook runs so far
+    pub fn metrics(&self) -> LifecycleHookMetrics {
+        LifecycleHookMetrics {
+            queued: self.counters.queued.load(Ordering::Relaxed),
+            succeeded: self.counters.succeeded.load(Ordering::Relaxed),
+            retried: self.counters.retried.load(Ordering::Relaxed),
+            failed: self.counters.failed.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Queue a run of every hook handling an event
+    ///
+    /// Never waits: when the queue is full the runs are dropped and logged.
+    ///
+    /// # Returns
+    /// * `usize` - Number of hook runs queued
+    pub fn publish(&self, event: LifecycleEvent) -> usize {
+        if !self.config.enabled {
+            return 0;
+        }
+
+        let event = Arc::new(event);
+        let tenant = current_tenant();
+        let mut queued = 0;
+        for hook in self.hooks.iter().filter(|hook| hook.handles(&event)) {
+            let run = HookRun {
+                hook: hook.clone(),
+                event: event.clone(),
+                tenant: tenant.clone(),
+                attempt: 1,
+            };
+            match self.sender.try_send(run) {
+                Ok(()) => {
+                    self.counters.queued.fetch_add(1, Ordering::Relaxed);
+                    queued += 1;
+                }
+                Err(_) => {
+                    self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                    error!(
+                        hook = hook.name(),
+                        event_type = event.event_type(),
+                        "Lifecycle hook queue is full, dropping hook run"
+                    );
+                }
+            }
+        }
+
+        queued
+    }
+
+    /// Start running queued hooks as a background task
+    ///
+    /// Only the first call starts a task; later calls do nothing.
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Lifecycle hooks are disabled");
+            return;
+        }
+        let Some(mut receiver) = self.receiver.lock().ok().and_then(|mut receiver| receiver.take()) else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            info!("Lifecycle hook runner started with {} hooks", self.hooks.len());
+
+            let permits = Arc::new(Semaphore::new(self.config.max_concurrent.max(1)));
+            while let Some(run) = receiver.recv().await {
+                let Ok(permit) = permits.clone().acquire_owned().await else {
+                    break;
+                };
+                let service = self.clone();
+                tokio::spawn(async move {
+                    service.run(run).await;
+                    drop(permit);
+                });
+            }
+        });
+    }
+
+    /// Run a hook once, queueing it again after a delay if it fails
+    async fn run(self: Arc<Self>, run: HookRun) {
+        let hook_name = run.hook.name().to_string();
+        let event_type = run.event.event_type();
+
+        let error = match tenant::scope(run.tenant.clone(), run.hook.handle(&run.event)).await {
+            Ok(()) => {
+                self.counters.succeeded.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            Err(e) => e,
+        };
+
+        if run.attempt >= self.config.max_attempts {
+            self.counters.failed.fetch_add(1, Ordering::Relaxed);
+            error!(
+                hook = %hook_name,
+                event_type,
+                attempt = run.attempt,
+                error = %error,
+                "Lifecycle hook failed, giving up"
+            );
+            return;
+        }
+
+        let delay = self.retry_delay(run.attempt);
+        warn!(
+            hook = %hook_name,
+            event_type,
+            attempt = run.attempt,
+            retry_in_ms = delay.as_millis() as u64,
+            error = %error,
+            "Lifecycle hook failed, retrying"
+        );
+        self.counters.retried.fetch_add(1, Ordering::Relaxed);
+
+        // Wait outside the runner so the delay holds no concurrency permit
+        let sender = self.sender.clone();
+        let counters = self.counters.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let retry = HookRun {
+                attempt: run.attempt + 1,
+                ..run
+            };
+            if sender.send(retry).await.is_err() {
+                counters.failed.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Delay before the retry following a failed attempt
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        let delay_ms = self
+            .config
+            .base_retry_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.config.max_retry_delay_ms);
+        Duration::from_millis(delay_ms)
+    }
+}
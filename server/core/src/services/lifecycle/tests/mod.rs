@@ -0,0 +1,4 @@
+//! Tests for lifecycle hook service
+
+#[cfg(test)]
+mod service_tests;
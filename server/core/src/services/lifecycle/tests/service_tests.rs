@@ -0,0 +1,211 @@
+//! Unit tests for lifecycle hook service
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::services::lifecycle::{
+    LifecycleEvent, LifecycleHookConfig, LifecycleHookMetrics, LifecycleHookService, LifecycleHookTrait,
+};
+use crate::services::tenant::{self, current_tenant, TenantId};
+
+/// Hook recording the events it ran for, failing a number of times first
+struct RecordingHook {
+    name: &'static str,
+    only: Option<&'static str>,
+    failures: AtomicU32,
+    events: Mutex<Vec<LifecycleEvent>>,
+    tenants: Mutex<Vec<TenantId>>,
+}
+
+impl RecordingHook {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            only: None,
+            failures: AtomicU32::new(0),
+            events: Mutex::new(Vec::new()),
+            tenants: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn only(mut self, event_type: &'static str) -> Self {
+        self.only = Some(event_type);
+        self
+    }
+
+    fn failing(self, times: u32) -> Self {
+        self.failures.store(times, Ordering::SeqCst);
+        self
+    }
+
+    fn events(&self) -> Vec<LifecycleEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    fn tenants(&self) -> Vec<TenantId> {
+        self.tenants.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl LifecycleHookTrait for RecordingHook {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn handles(&self, event: &LifecycleEvent) -> bool {
+        self.only.is_none_or(|event_type| event.event_type() == event_type)
+    }
+
+    async fn handle(&self, event: &LifecycleEvent) -> Result<(), String> {
+        let failing = self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |failures| failures.checked_sub(1))
+            .is_ok();
+        if failing {
+            return Err("CRM unavailable".to_string());
+        }
+
+        self.events.lock().unwrap().push(event.clone());
+        self.tenants.lock().unwrap().push(current_tenant());
+        Ok(())
+    }
+}
+
+fn fast_config() -> LifecycleHookConfig {
+    LifecycleHookConfig {
+        base_retry_delay_ms: 1,
+        max_retry_delay_ms: 5,
+        ..LifecycleHookConfig::default()
+    }
+}
+
+fn user_registered() -> LifecycleEvent {
+    LifecycleEvent::UserRegistered {
+        user_id: Uuid::new_v4(),
+        country_code: "+61".to_string(),
+        registered_at: chrono::Utc::now(),
+        attribution: None,
+    }
+}
+
+fn payout_paid() -> LifecycleEvent {
+    LifecycleEvent::PayoutPaid {
+        payout_id: Uuid::new_v4(),
+        worker_id: Uuid::new_v4(),
+        amount: 12_500,
+        currency: "AUD".to_string(),
+    }
+}
+
+/// Wait until the metrics satisfy a condition
+async fn wait_for(service: &LifecycleHookService, condition: impl Fn(&LifecycleHookMetrics) -> bool) {
+    for _ in 0..500 {
+        if condition(&service.metrics()) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+    panic!("Hooks never reached the expected state: {:?}", service.metrics());
+}
+
+#[tokio::test]
+async fn test_event_runs_hooks_handling_it() {
+    let crm = Arc::new(RecordingHook::new("crm"));
+    let analytics = Arc::new(RecordingHook::new("analytics").only("payout_paid"));
+    let service = Arc::new(
+        LifecycleHookService::new(fast_config())
+            .with_hook(crm.clone())
+            .with_hook(analytics.clone()),
+    );
+    service.clone().start_background_task();
+
+    let event = user_registered();
+    assert_eq!(service.publish(event.clone()), 1);
+    assert_eq!(service.publish(payout_paid()), 2);
+    wait_for(&service, |metrics| metrics.succeeded == 3).await;
+
+    assert_eq!(crm.events().len(), 2);
+    assert!(crm.events().contains(&event));
+    assert_eq!(analytics.events().len(), 1);
+    assert_eq!(analytics.events()[0].event_type(), "payout_paid");
+}
+
+#[tokio::test]
+async fn test_hooks_run_in_the_tenant_the_event_was_published_in() {
+    let crm = Arc::new(RecordingHook::new("crm").failing(1));
+    let service = Arc::new(LifecycleHookService::new(fast_config()).with_hook(crm.clone()));
+    service.clone().start_background_task();
+
+    let tenant_a = TenantId::parse("tenant-a").unwrap();
+    tenant::scope(tenant_a.clone(), async { service.publish(user_registered()) }).await;
+    service.publish(payout_paid());
+    wait_for(&service, |metrics| metrics.succeeded == 2).await;
+
+    let tenants = crm.tenants();
+    assert_eq!(tenants.len(), 2);
+    assert!(tenants.contains(&tenant_a));
+    assert!(tenants.contains(&TenantId::default()));
+}
+
+#[tokio::test]
+async fn test_failed_hook_is_retried() {
+    let crm = Arc::new(RecordingHook::new("crm").failing(2));
+    let service = Arc::new(LifecycleHookService::new(fast_config()).with_hook(crm.clone()));
+    service.clone().start_background_task();
+
+    service.publish(user_registered());
+    wait_for(&service, |metrics| metrics.succeeded == 1).await;
+
+    assert_eq!(service.metrics().retried, 2);
+    assert_eq!(crm.events().len(), 1);
+}
+
+#[tokio::test]
+async fn test_hook_given_up_after_max_attempts() {
+    let crm = Arc::new(RecordingHook::new("crm").failing(10));
+    let service = Arc::new(
+        LifecycleHookService::new(LifecycleHookConfig {
+            max_attempts: 3,
+            ..fast_config()
+        })
+        .with_hook(crm.clone()),
+    );
+    service.clone().start_background_task();
+
+    service.publish(user_registered());
+    wait_for(&service, |metrics| metrics.failed == 1).await;
+
+    let metrics = service.metrics();
+    assert_eq!(metrics.retried, 2);
+    assert_eq!(metrics.succeeded, 0);
+    assert!(crm.events().is_empty());
+}
+
+#[tokio::test]
+async fn test_publish_never_waits_for_a_full_queue() {
+    let service = LifecycleHookService::new(LifecycleHookConfig {
+        queue_capacity: 1,
+        ..fast_config()
+    })
+    .with_hook(Arc::new(RecordingHook::new("crm")))
+    .with_hook(Arc::new(RecordingHook::new("email")));
+
+    assert_eq!(service.publish(user_registered()), 1);
+    assert_eq!(service.metrics().dropped, 1);
+}
+
+#[tokio::test]
+async fn test_disabled_service_queues_nothing() {
+    let service = LifecycleHookService::new(LifecycleHookConfig {
+        enabled: false,
+        ..fast_config()
+    })
+    .with_hook(Arc::new(RecordingHook::new("crm")));
+
+    assert_eq!(service.publish(user_registered()), 0);
+}
@@ -0,0 +1,91 @@
+//! Traits for first-party lifecycle hooks
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::entities::attribution::RegistrationAttribution;
+
+/// A lifecycle event hooks are run for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    /// A user signed in for the first time
+    UserRegistered {
+        /// The new user
+        user_id: Uuid,
+        /// Dialing code of the user's phone, e.g. `+61`
+        country_code: String,
+        /// When the user registered
+        registered_at: DateTime<Utc>,
+        /// Campaign the user arrived through, present only with marketing consent
+        attribution: Option<RegistrationAttribution>,
+    },
+    /// A customer signed off an order's completion checklist
+    OrderCompleted {
+        /// The completed order
+        order_id: Uuid,
+        /// Customer who signed off
+        customer_id: Uuid,
+        /// Worker who did the job
+        worker_id: Uuid,
+        /// When the customer signed off
+        completed_at: DateTime<Utc>,
+    },
+    /// A payout was transferred to a worker
+    PayoutPaid {
+        /// The payout
+        payout_id: Uuid,
+        /// Worker paid
+        worker_id: Uuid,
+        /// Amount in minor units
+        amount: i64,
+        /// ISO 4217 currency code
+        currency: String,
+    },
+    /// A user's request was routed by a canary rollout
+    CanaryExposure {
+        /// Name of the canary, e.g. `pricing-engine-v2`
+        experiment: String,
+        /// Cohort the user is in, `canary` or `control`
+        cohort: String,
+        /// The user
+        user_id: Uuid,
+        /// When the request was routed
+        exposed_at: DateTime<Utc>,
+    },
+}
+
+impl LifecycleEvent {
+    /// Name of the event type, as used in logs
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            LifecycleEvent::UserRegistered { .. } => "user_registered",
+            LifecycleEvent::OrderCompleted { .. } => "order_completed",
+            LifecycleEvent::PayoutPaid { .. } => "payout_paid",
+            LifecycleEvent::CanaryExposure { .. } => "canary_exposure",
+        }
+    }
+}
+
+/// Trait for first-party services run after lifecycle events
+#[async_trait]
+pub trait LifecycleHookTrait: Send + Sync {
+    /// Name of the hook, as used in logs and metrics
+    fn name(&self) -> &str;
+
+    /// Whether the hook runs for an event; every event by default
+    fn handles(&self, _event: &LifecycleEvent) -> bool {
+        true
+    }
+
+    /// Run the hook for an event
+    ///
+    /// A hook is run again after it fails, so it has to be idempotent.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The hook completed
+    /// * `Err(String)` - Why the hook failed; the run is retried
+    async fn handle(&self, event: &LifecycleEvent) -> Result<(), String>;
+}
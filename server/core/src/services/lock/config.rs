@@ -0,0 +1,25 @@
+//! Configuration for distributed locks
+
+use std::time::Duration;
+
+/// Configuration for distributed locks
+#[derive(Debug, Clone)]
+pub struct DistributedLockConfig {
+    /// How long a lock is held if it is never released, e.g. because the
+    /// instance holding it crashed; longer than the work it guards
+    pub ttl: Duration,
+    /// How long to wait for a lock held by another instance
+    pub wait_timeout: Duration,
+    /// Delay between attempts to take a lock held by another instance
+    pub retry_interval: Duration,
+}
+
+impl Default for DistributedLockConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(30),
+            wait_timeout: Duration::from_secs(10),
+            retry_interval: Duration::from_millis(50),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Distributed locks serializing work across API instances
+//!
+//! Some operations must not run concurrently for the same subject even
+//! when requests land on different instances: sending a verification code
+//! to a phone number, or rotating the refresh tokens of a session. This
+//! module provides:
+//! - A trait for lock stores, implemented over Redis in the infrastructure layer
+//! - `DistributedLock`, which waits for a lock, runs the work and releases it
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::DistributedLockConfig;
+pub use service::DistributedLock;
+pub use traits::DistributedLockTrait;
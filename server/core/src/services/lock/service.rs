@@ -0,0 +1,86 @@
+//! Running work while holding a distributed lock
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::time::{sleep, Instant};
+use tracing::warn;
+
+use crate::errors::{DomainError, DomainResult};
+
+use super::config::DistributedLockConfig;
+use super::traits::DistributedLockTrait;
+
+/// Runs work while holding a lock shared by every API instance
+///
+/// Locks only prevent the same work from running twice at once; the state
+/// the work changes is still checked by the work itself. So when the lock
+/// store fails the work runs without the lock instead of failing the
+/// request.
+#[derive(Clone)]
+pub struct DistributedLock {
+    /// Store holding the locks
+    store: Arc<dyn DistributedLockTrait>,
+    /// Lock configuration
+    config: DistributedLockConfig,
+}
+
+impl DistributedLock {
+    /// Create a lock runner
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Store holding the locks
+    /// * `config` - Lock lifetime and waiting configuration
+    pub fn new(store: Arc<dyn DistributedLockTrait>, config: DistributedLockConfig) -> Self {
+        Self { store, config }
+    }
+
+    /// Run `work` while holding the lock `key`
+    ///
+    /// Waits up to `wait_timeout` for a lock held by another instance.
+    ///
+    /// # Returns
+    ///
+    /// * The result of `work`
+    /// * `Err(DomainError::Conflict)` - The lock was not released in time;
+    ///   `work` did not run
+    pub async fn run<T, F>(&self, key: &str, work: F) -> DomainResult<T>
+    where
+        F: Future<Output = DomainResult<T>>,
+    {
+        let started = Instant::now();
+        let token = loop {
+            match self.store.try_acquire(key, self.config.ttl).await {
+                Ok(Some(token)) => break Some(token),
+                Ok(None) if started.elapsed() < self.config.wait_timeout => {
+                    sleep(self.config.retry_interval).await;
+                }
+                Ok(None) => {
+                    return Err(DomainError::Conflict {
+                        message: format!(
+                            "Lock {} still held by another instance after {} ms",
+                            key,
+                            self.config.wait_timeout.as_millis()
+                        ),
+                    });
+                }
+                Err(e) => {
+                    warn!(lock = key, error = %e, "Failed to acquire lock, continuing without it");
+                    break None;
+                }
+            }
+        };
+
+        let result = work.await;
+
+        if let Some(token) = token {
+            match self.store.release(key, &token).await {
+                Ok(true) => {}
+                Ok(false) => warn!(lock = key, "Lock expired before the work holding it finished"),
+                Err(e) => warn!(lock = key, error = %e, "Failed to release lock"),
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for distributed locks
+
+#[cfg(test)]
+pub(crate) mod service_tests;
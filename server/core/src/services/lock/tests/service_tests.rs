@@ -0,0 +1,141 @@
+//! Unit tests for running work under a distributed lock
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::errors::DomainError;
+use crate::services::lock::{DistributedLock, DistributedLockConfig, DistributedLockTrait};
+
+/// In-memory lock store, ignoring lock lifetimes
+#[derive(Default)]
+pub struct MockLockStore {
+    held: Mutex<HashMap<String, String>>,
+    failing: bool,
+}
+
+impl MockLockStore {
+    pub fn failing() -> Self {
+        Self { failing: true, ..Self::default() }
+    }
+
+    pub fn is_held(&self, key: &str) -> bool {
+        self.held.lock().unwrap().contains_key(key)
+    }
+}
+
+#[async_trait]
+impl DistributedLockTrait for MockLockStore {
+    async fn try_acquire(&self, key: &str, _ttl: Duration) -> Result<Option<String>, String> {
+        if self.failing {
+            return Err("Lock store unavailable".to_string());
+        }
+        let mut held = self.held.lock().unwrap();
+        if held.contains_key(key) {
+            return Ok(None);
+        }
+        let token = Uuid::new_v4().to_string();
+        held.insert(key.to_string(), token.clone());
+        Ok(Some(token))
+    }
+
+    async fn release(&self, key: &str, token: &str) -> Result<bool, String> {
+        let mut held = self.held.lock().unwrap();
+        if held.get(key).map(String::as_str) == Some(token) {
+            held.remove(key);
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+fn fast_config() -> DistributedLockConfig {
+    DistributedLockConfig {
+        ttl: Duration::from_secs(5),
+        wait_timeout: Duration::from_millis(200),
+        retry_interval: Duration::from_millis(5),
+    }
+}
+
+#[tokio::test]
+async fn test_work_for_the_same_key_is_serialized() {
+    let store = Arc::new(MockLockStore::default());
+    let lock = DistributedLock::new(store.clone(), fast_config());
+    let running = Arc::new(AtomicUsize::new(0));
+    let overlaps = Arc::new(AtomicUsize::new(0));
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let lock = lock.clone();
+            let running = running.clone();
+            let overlaps = overlaps.clone();
+            tokio::spawn(async move {
+                lock.run("phone:+61412345678", async {
+                    if running.fetch_add(1, Ordering::SeqCst) > 0 {
+                        overlaps.fetch_add(1, Ordering::SeqCst);
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .await
+            })
+        })
+        .collect();
+
+    for task in tasks {
+        task.await.unwrap().unwrap();
+    }
+    assert_eq!(overlaps.load(Ordering::SeqCst), 0);
+    assert!(!store.is_held("phone:+61412345678"));
+}
+
+#[tokio::test]
+async fn test_lock_held_too_long_is_a_conflict() {
+    let store = Arc::new(MockLockStore::default());
+    let token = store.try_acquire("family", Duration::from_secs(5)).await.unwrap();
+    assert!(token.is_some());
+
+    let lock = DistributedLock::new(store.clone(), fast_config());
+    let ran = AtomicUsize::new(0);
+    let result = lock
+        .run("family", async {
+            ran.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .await;
+
+    assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    assert_eq!(ran.load(Ordering::SeqCst), 0);
+    assert!(store.is_held("family"));
+}
+
+#[tokio::test]
+async fn test_lock_is_released_when_work_fails() {
+    let store = Arc::new(MockLockStore::default());
+    let lock = DistributedLock::new(store.clone(), fast_config());
+
+    let result: Result<(), _> = lock
+        .run("family", async {
+            Err(DomainError::Internal {
+                message: "SMS provider down".to_string(),
+            })
+        })
+        .await;
+
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert!(!store.is_held("family"));
+}
+
+#[tokio::test]
+async fn test_work_runs_when_the_lock_store_fails() {
+    let lock = DistributedLock::new(Arc::new(MockLockStore::failing()), fast_config());
+
+    let result = lock.run("family", async { Ok(7) }).await;
+
+    assert_eq!(result.unwrap(), 7);
+}
@@ -0,0 +1,25 @@
+//! Trait for lock store integration
+
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Trait for stores of locks shared by every API instance
+#[async_trait]
+pub trait DistributedLockTrait: Send + Sync {
+    /// Take a lock if no one holds it, without waiting
+    ///
+    /// # Arguments
+    /// * `key` - Name of the lock
+    /// * `ttl` - Time after which the lock is released even if `release` is never called
+    ///
+    /// # Returns
+    /// * `Ok(Some(token))` - The lock was taken; the token releases it
+    /// * `Ok(None)` - The lock is held by someone else
+    async fn try_acquire(&self, key: &str, ttl: Duration) -> Result<Option<String>, String>;
+
+    /// Release a lock if it is still held with `token`
+    ///
+    /// # Returns
+    /// * `Ok(false)` - The lock expired, and may have been taken by someone else since
+    async fn release(&self, key: &str, token: &str) -> Result<bool, String>;
+}
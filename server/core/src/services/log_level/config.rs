@@ -0,0 +1,35 @@
+//! Configuration for the runtime log level service
+
+/// Configuration for the runtime log level service
+#[derive(Debug, Clone)]
+pub struct LogLevelConfig {
+    /// Filter restored when an override expires or is cleared (e.g. "info")
+    pub default_filter: String,
+    /// Lifetime of an override when the caller does not choose one, in seconds
+    pub default_ttl_seconds: i64,
+    /// Longest lifetime an override can be given, in seconds
+    pub max_ttl_seconds: i64,
+    /// Maximum number of per-target directives in one override
+    pub max_targets: usize,
+    /// How often expired overrides are reverted (in seconds)
+    pub check_interval_seconds: u64,
+}
+
+impl LogLevelConfig {
+    /// Create a configuration restoring the given filter with default lifetimes
+    pub fn new(default_filter: impl Into<String>) -> Self {
+        Self {
+            default_filter: default_filter.into(),
+            default_ttl_seconds: 15 * 60,
+            max_ttl_seconds: 4 * 60 * 60,
+            max_targets: 20,
+            check_interval_seconds: 10,
+        }
+    }
+}
+
+impl Default for LogLevelConfig {
+    fn default() -> Self {
+        Self::new("info")
+    }
+}
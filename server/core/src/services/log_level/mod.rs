@@ -0,0 +1,15 @@
+//! Runtime log level module
+//!
+//! This module handles:
+//! - Raising the global log level or the level of selected targets (e.g.
+//!   `re_core::services::auth=debug`) without a redeploy
+//! - Reverting to the configured filter once the override expires
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::LogLevelConfig;
+pub use service::{LogFilterTrait, LogLevelOverride, LogLevelService, LogLevelStatus};
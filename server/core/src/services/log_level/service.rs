@@ -0,0 +1,286 @@
+//! Runtime log level service
+//!
+//! Administrators diagnosing a production issue can raise the global log
+//! level or the level of a few targets for a limited time. The override is
+//! applied to the process-wide tracing filter through [`LogFilterTrait`] and
+//! reverted to the configured filter when it expires or is cleared.
+//!
+//! Each instance keeps its own filter. The admin routes announce overrides
+//! on the cluster event bus so the other instances apply them as well.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::errors::{DomainError, DomainResult};
+
+use super::config::LogLevelConfig;
+
+/// Levels accepted for the global level and target directives
+const LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "off"];
+
+/// A tracing target: a crate or module path such as `re_core::services::auth`
+static TARGET_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*(::[A-Za-z_][A-Za-z0-9_]*)*$").unwrap());
+
+/// Process-wide log filter the service reconfigures
+pub trait LogFilterTrait: Send + Sync {
+    /// Replace the active filter
+    ///
+    /// # Arguments
+    /// * `filter` - Comma-separated directives, e.g. `info,re_core::services::auth=debug`
+    fn apply(&self, filter: &str) -> Result<(), String>;
+}
+
+/// A temporary change of the log filter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevelOverride {
+    /// Global level, or `None` to keep the configured filter as the base
+    pub level: Option<String>,
+
+    /// Level per target, e.g. `re_core::services::auth` => `debug`
+    pub targets: BTreeMap<String, String>,
+
+    /// Filter applied while the override is active
+    pub filter: String,
+
+    /// Administrator who set the override
+    pub set_by: Option<Uuid>,
+
+    /// Timestamp when the override was applied
+    pub created_at: DateTime<Utc>,
+
+    /// Timestamp after which the configured filter is restored
+    pub expires_at: DateTime<Utc>,
+}
+
+impl LogLevelOverride {
+    /// Checks whether the override has expired at the given time
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Filter currently in effect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevelStatus {
+    /// Filter currently applied
+    pub filter: String,
+
+    /// Filter restored when the override ends
+    pub default_filter: String,
+
+    /// The active override, if any
+    pub active_override: Option<LogLevelOverride>,
+}
+
+/// Service that changes the log filter at runtime and reverts it after a TTL
+pub struct LogLevelService<F>
+where
+    F: LogFilterTrait + 'static,
+{
+    filter: Arc<F>,
+    active: Mutex<Option<LogLevelOverride>>,
+    config: LogLevelConfig,
+}
+
+impl<F> LogLevelService<F>
+where
+    F: LogFilterTrait + 'static,
+{
+    /// Create a new log level service
+    ///
+    /// The filter is expected to start out as `config.default_filter`.
+    pub fn new(filter: Arc<F>, config: LogLevelConfig) -> Self {
+        Self {
+            filter,
+            active: Mutex::new(None),
+            config,
+        }
+    }
+
+    /// Get the filter in effect, reverting an expired override first
+    pub fn status(&self) -> DomainResult<LogLevelStatus> {
+        self.revert_expired(Utc::now())?;
+
+        let active_override = self.active.lock().unwrap().clone();
+        let filter = active_override
+            .as_ref()
+            .map(|active| active.filter.clone())
+            .unwrap_or_else(|| self.config.default_filter.clone());
+
+        Ok(LogLevelStatus {
+            filter,
+            default_filter: self.config.default_filter.clone(),
+            active_override,
+        })
+    }
+
+    /// Apply a temporary override, replacing any active one
+    ///
+    /// # Arguments
+    /// * `level` - Optional global level; without it the configured filter stays the base
+    /// * `targets` - Level per target, e.g. `re_core::services::auth` => `debug`
+    /// * `ttl` - Optional lifetime of the override (default from configuration)
+    /// * `set_by` - Administrator setting the override
+    ///
+    /// # Returns
+    /// * `Ok(LogLevelOverride)` - The applied override with normalized levels
+    /// * `Err(DomainError::Validation)` - Unknown level, invalid target, empty
+    ///   override, too many targets or lifetime out of range
+    /// * `Err(DomainError::Internal)` - The filter could not be applied
+    pub fn set_override(
+        &self,
+        level: Option<&str>,
+        targets: &BTreeMap<String, String>,
+        ttl: Option<Duration>,
+        set_by: Option<Uuid>,
+    ) -> DomainResult<LogLevelOverride> {
+        if level.is_none() && targets.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Either a level or at least one target must be given".to_string(),
+            });
+        }
+
+        if targets.len() > self.config.max_targets {
+            return Err(DomainError::Validation {
+                message: format!("At most {} targets can be overridden", self.config.max_targets),
+            });
+        }
+
+        let ttl = ttl.unwrap_or_else(|| Duration::seconds(self.config.default_ttl_seconds));
+        if ttl <= Duration::zero() || ttl > Duration::seconds(self.config.max_ttl_seconds) {
+            return Err(DomainError::Validation {
+                message: format!(
+                    "Lifetime must be between 1 and {} seconds",
+                    self.config.max_ttl_seconds
+                ),
+            });
+        }
+
+        let level = level.map(parse_level).transpose()?;
+        let targets = targets
+            .iter()
+            .map(|(target, target_level)| {
+                let target = target.trim();
+                if !TARGET_REGEX.is_match(target) {
+                    return Err(DomainError::Validation {
+                        message: format!("Invalid log target: {}", target),
+                    });
+                }
+                Ok((target.to_string(), parse_level(target_level)?))
+            })
+            .collect::<DomainResult<BTreeMap<String, String>>>()?;
+
+        let mut directives = vec![level.clone().unwrap_or_else(|| self.config.default_filter.clone())];
+        directives.extend(targets.iter().map(|(target, level)| format!("{}={}", target, level)));
+        let filter = directives.join(",");
+
+        self.filter
+            .apply(&filter)
+            .map_err(|message| DomainError::Internal { message })?;
+
+        let now = Utc::now();
+        let active = LogLevelOverride {
+            level,
+            targets,
+            filter,
+            set_by,
+            created_at: now,
+            expires_at: now + ttl,
+        };
+        *self.active.lock().unwrap() = Some(active.clone());
+
+        warn!(
+            filter = %active.filter,
+            set_by = ?set_by,
+            expires_at = %active.expires_at,
+            "Log level override applied"
+        );
+
+        Ok(active)
+    }
+
+    /// Clear the active override and restore the configured filter
+    ///
+    /// # Returns
+    /// * `Ok(true)` if an override was active
+    pub fn clear_override(&self) -> DomainResult<bool> {
+        let mut active = self.active.lock().unwrap();
+        if active.is_none() {
+            return Ok(false);
+        }
+
+        self.restore_default()?;
+        *active = None;
+
+        info!("Log level override cleared");
+        Ok(true)
+    }
+
+    /// Restore the configured filter if the active override has expired
+    ///
+    /// # Returns
+    /// * `Ok(true)` if an expired override was reverted
+    pub fn revert_expired(&self, now: DateTime<Utc>) -> DomainResult<bool> {
+        let mut active = self.active.lock().unwrap();
+        if !active.as_ref().is_some_and(|active| active.is_expired(now)) {
+            return Ok(false);
+        }
+
+        self.restore_default()?;
+        *active = None;
+
+        info!("Log level override expired, configured filter restored");
+        Ok(true)
+    }
+
+    /// Apply the configured filter
+    fn restore_default(&self) -> DomainResult<()> {
+        self.filter
+            .apply(&self.config.default_filter)
+            .map_err(|message| DomainError::Internal { message })
+    }
+
+    /// Start reverting expired overrides as a background task
+    ///
+    /// This spawns a tokio task that checks the active override at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        let interval = std::time::Duration::from_secs(self.config.check_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Log level service started - will check overrides every {} seconds",
+                self.config.check_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                if let Err(e) = self.revert_expired(Utc::now()) {
+                    error!("Failed to revert log level override: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Normalize a level name, rejecting unknown levels
+fn parse_level(level: &str) -> DomainResult<String> {
+    let level = level.trim().to_ascii_lowercase();
+    if LEVELS.contains(&level.as_str()) {
+        Ok(level)
+    } else {
+        Err(DomainError::Validation {
+            message: format!("Unknown log level: {}", level),
+        })
+    }
+}
@@ -0,0 +1,120 @@
+//! Unit tests for the runtime log level service
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::errors::DomainError;
+use crate::services::log_level::{LogFilterTrait, LogLevelConfig, LogLevelService};
+
+/// Filter that records every applied filter
+#[derive(Default)]
+struct RecordingFilter {
+    applied: Mutex<Vec<String>>,
+}
+
+impl RecordingFilter {
+    fn last(&self) -> Option<String> {
+        self.applied.lock().unwrap().last().cloned()
+    }
+}
+
+impl LogFilterTrait for RecordingFilter {
+    fn apply(&self, filter: &str) -> Result<(), String> {
+        self.applied.lock().unwrap().push(filter.to_string());
+        Ok(())
+    }
+}
+
+fn service() -> (Arc<RecordingFilter>, LogLevelService<RecordingFilter>) {
+    let filter = Arc::new(RecordingFilter::default());
+    let service = LogLevelService::new(filter.clone(), LogLevelConfig::new("info,sqlx=warn"));
+    (filter, service)
+}
+
+fn targets(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(target, level)| (target.to_string(), level.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_override_builds_filter_from_level_and_targets() {
+    let (filter, service) = service();
+    let admin = Uuid::new_v4();
+
+    let active = service
+        .set_override(
+            Some("WARN"),
+            &targets(&[("re_core::services::auth", "Debug")]),
+            None,
+            Some(admin),
+        )
+        .unwrap();
+    assert_eq!(active.filter, "warn,re_core::services::auth=debug");
+    assert_eq!(active.level.as_deref(), Some("warn"));
+    assert_eq!(active.set_by, Some(admin));
+    assert!(active.expires_at > Utc::now() + Duration::minutes(14));
+    assert_eq!(filter.last().as_deref(), Some("warn,re_core::services::auth=debug"));
+
+    let status = service.status().unwrap();
+    assert_eq!(status.filter, "warn,re_core::services::auth=debug");
+    assert_eq!(status.default_filter, "info,sqlx=warn");
+    assert_eq!(status.active_override, Some(active));
+}
+
+#[test]
+fn test_targets_alone_extend_the_configured_filter() {
+    let (filter, service) = service();
+
+    service
+        .set_override(None, &targets(&[("re_infra::sms", "trace")]), None, None)
+        .unwrap();
+    assert_eq!(filter.last().as_deref(), Some("info,sqlx=warn,re_infra::sms=trace"));
+}
+
+#[test]
+fn test_invalid_overrides_are_rejected() {
+    let (filter, service) = service();
+    let invalid = |result| matches!(result, Err(DomainError::Validation { .. }));
+
+    assert!(invalid(service.set_override(None, &BTreeMap::new(), None, None)));
+    assert!(invalid(service.set_override(Some("verbose"), &BTreeMap::new(), None, None)));
+    assert!(invalid(service.set_override(None, &targets(&[("re_core=debug", "info")]), None, None)));
+    assert!(invalid(service.set_override(None, &targets(&[("re_core", "loud")]), None, None)));
+    assert!(invalid(service.set_override(Some("debug"), &BTreeMap::new(), Some(Duration::zero()), None)));
+    assert!(invalid(service.set_override(Some("debug"), &BTreeMap::new(), Some(Duration::days(1)), None)));
+
+    assert!(filter.last().is_none());
+    assert!(service.status().unwrap().active_override.is_none());
+}
+
+#[test]
+fn test_expired_override_reverts_to_configured_filter() {
+    let (filter, service) = service();
+    let active = service
+        .set_override(Some("debug"), &BTreeMap::new(), Some(Duration::minutes(5)), None)
+        .unwrap();
+
+    assert!(!service.revert_expired(Utc::now()).unwrap());
+    assert_eq!(filter.last().as_deref(), Some("debug"));
+
+    assert!(service.revert_expired(active.expires_at).unwrap());
+    assert_eq!(filter.last().as_deref(), Some("info,sqlx=warn"));
+    assert!(service.status().unwrap().active_override.is_none());
+    assert!(!service.revert_expired(active.expires_at).unwrap());
+}
+
+#[test]
+fn test_clear_override_restores_configured_filter() {
+    let (filter, service) = service();
+    assert!(!service.clear_override().unwrap());
+
+    service.set_override(Some("trace"), &BTreeMap::new(), None, None).unwrap();
+    assert!(service.clear_override().unwrap());
+    assert_eq!(filter.last().as_deref(), Some("info,sqlx=warn"));
+    assert_eq!(service.status().unwrap().filter, "info,sqlx=warn");
+}
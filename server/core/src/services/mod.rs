@@ -1,25 +1,113 @@
 //! Business services containing domain logic and use cases.
 
+pub mod admin_sso;
+pub mod attribution;
 pub mod audit;
+pub mod audit_retention;
 pub mod auth;
+pub mod calendar;
+pub mod campaign;
+pub mod cluster;
+pub mod completion;
+pub mod compliance;
+pub mod credit_wallet;
 pub mod encryption;
+pub mod export;
+pub mod fee_schedule;
+pub mod ledger;
+pub mod legal_document;
+pub mod lifecycle;
+pub mod lock;
+pub mod log_level;
+pub mod notification;
+pub mod oauth;
+pub mod organization_billing;
+pub mod payment_intent;
+pub mod payment_method;
+pub mod payment_risk;
+pub mod payment_webhook;
+pub mod payout_method;
+pub mod phone_hash;
+pub mod reconciliation;
+pub mod security_webhook;
+pub mod service_area;
+pub mod signed_url;
+pub mod singleflight;
+pub mod sms_cost;
+pub mod sms_delivery;
+pub mod sms_queue;
+pub mod tenant;
 pub mod token;
+pub mod trace;
+pub mod user_import;
 pub mod verification;
+pub mod webhook_dead_letter;
 
 // Re-export commonly used types
-pub use audit::{AuditService, AuditServiceConfig};
+pub use admin_sso::{AdminSsoService, AdminSsoServiceConfig, OidcProviderTrait, SsoLoginStoreTrait};
+pub use attribution::{AttributionConfig, AttributionService};
+pub use audit::{AuditEventPublisherTrait, AuditService, AuditServiceConfig};
+pub use audit_retention::{
+    AuditPartitionManagerTrait, AuditRetentionAction, AuditRetentionConfig, AuditRetentionService,
+};
 pub use auth::{AuthService, AuthServiceConfig, RateLimiterTrait};
+pub use calendar::{BusinessCalendar, BusinessCalendarConfig, MarketCalendar, TimeSlot};
+pub use campaign::{CampaignService, CampaignServiceConfig, NewCampaign, NotificationSenderTrait};
+pub use cluster::{ClusterEvent, ClusterEventHandlerTrait, ClusterEventPublisherTrait};
+pub use completion::{ChecklistItemInput, ChecklistSubmission, CompletionConfig, CompletionService};
+pub use compliance::{ComplianceConfig, ComplianceGateTrait, ComplianceProviderTrait, ComplianceService};
+pub use credit_wallet::{CreditApplication, CreditWalletConfig, CreditWalletService};
 pub use encryption::{
     AesGcmOtpEncryption, EncryptedOtp, OtpEncryption, OtpEncryptionConfig,
     KeyManager, KeyRotationConfig, EncryptedCacheServiceTrait, StorageBackend,
     EncryptedVerificationAdapter,
 };
+pub use export::{ExportConfig, ExportService, ExportSourceTrait, ObjectStorageTrait};
+pub use fee_schedule::{FeeScheduleService, NewFeeSchedule};
+pub use ledger::{LedgerConfig, LedgerService};
+pub use legal_document::{LegalDocumentService, LegalTranslation, NewLegalDocumentVersion};
+pub use lifecycle::{LifecycleEvent, LifecycleHookConfig, LifecycleHookService, LifecycleHookTrait};
+pub use lock::{DistributedLock, DistributedLockConfig, DistributedLockTrait};
+pub use log_level::{LogFilterTrait, LogLevelConfig, LogLevelService};
+pub use notification::{DispatchResult, MarketQuietHours, MessageScheduler, MessageSchedulerConfig};
+pub use oauth::{OAuthConfig, OAuthError, OAuthService};
+pub use organization_billing::{OrganizationBillingConfig, OrganizationBillingService};
+pub use payment_intent::{
+    NewPayment, PaymentIntentConfig, PaymentIntentProviderTrait, PaymentIntentResult,
+    PaymentIntentService,
+};
+pub use payment_method::{PaymentMethodConfig, PaymentMethodProviderTrait, PaymentMethodService};
+pub use payment_risk::{PaymentAttempt, PaymentRiskConfig, PaymentRiskService};
+pub use payment_webhook::{
+    PaymentWebhookConfig, PaymentWebhookProviderTrait, PaymentWebhookService, WebhookOutcome,
+};
+pub use payout_method::{
+    PayoutEligibility, PayoutMethodConfig, PayoutMethodProviderTrait, PayoutMethodService,
+};
+pub use phone_hash::{PhoneHashRehashConfig, PhoneHashRehashReport, PhoneHashRehashService};
+pub use reconciliation::{
+    PaymentReportProviderTrait, ReconciliationAlertTrait, ReconciliationConfig,
+    ReconciliationService,
+};
+pub use security_webhook::{SecurityWebhookConfig, SecurityWebhookService, WebhookSenderTrait};
+pub use service_area::{ServiceAreaConfig, ServiceAreaInput, ServiceAreaService};
+pub use signed_url::{SignedUrlConfig, SignedUrlService, VerifiedUrl};
+pub use singleflight::SingleFlight;
+pub use sms_cost::{SmsBudgetDecision, SmsCostService, SmsSpendCounterTrait, SmsSpendReport};
+pub use sms_delivery::{
+    ReceiptSummary, SmsDeliveryConfig, SmsDeliveryHealth, SmsDeliveryService, SmsReceiptProviderTrait,
+};
+pub use sms_queue::{SmsQueueConfig, SmsQueueResult, SmsQueueService, SmsQueueStoreTrait};
 pub use token::{TokenService, TokenServiceConfig};
+pub use user_import::{UserImportConfig, UserImportReport, UserImportService};
 pub use verification::{
     VerificationService, VerificationServiceConfig, 
     SendCodeResult, VerifyCodeResult,
     SmsServiceTrait, CacheServiceTrait,
 };
+pub use webhook_dead_letter::{
+    WebhookDeadLetterConfig, WebhookDeadLetterService, WebhookReplayTrait,
+};
 
 // Placeholder for future service modules
 // pub mod order_service;
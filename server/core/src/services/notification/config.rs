@@ -0,0 +1,76 @@
+//! Configuration for the message scheduler
+
+use chrono::NaiveTime;
+use std::collections::HashMap;
+
+/// Default quiet hours of a market, in the market's local time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketQuietHours {
+    /// Local time at which quiet hours start
+    pub start: NaiveTime,
+    /// Local time at which quiet hours end
+    pub end: NaiveTime,
+    /// Offset of the market's local time from UTC, in minutes
+    pub utc_offset_minutes: i32,
+}
+
+impl MarketQuietHours {
+    /// Create a quiet hours window for a market
+    pub fn new(start: NaiveTime, end: NaiveTime, utc_offset_minutes: i32) -> Self {
+        Self {
+            start,
+            end,
+            utc_offset_minutes,
+        }
+    }
+}
+
+/// Configuration for the message scheduler
+#[derive(Debug, Clone)]
+pub struct MessageSchedulerConfig {
+    /// How often the background task dispatches due messages (in seconds)
+    pub dispatch_interval_seconds: u64,
+    /// Maximum number of messages dispatched per cycle
+    pub batch_size: usize,
+    /// Delay before retrying a failed delivery (in seconds)
+    pub retry_delay_seconds: i64,
+    /// How long a dispatcher's claim on a due message lasts before another
+    /// instance may claim it again (in seconds)
+    pub claim_lease_seconds: i64,
+    /// Quiet hours applied to recipients who have not set their own, keyed by
+    /// dialing code (e.g. `+61`)
+    pub market_quiet_hours: HashMap<String, MarketQuietHours>,
+    /// Whether to enable background dispatch
+    pub enabled: bool,
+}
+
+impl MessageSchedulerConfig {
+    /// Quiet hours of the market a phone number belongs to, preferring the
+    /// longest matching dialing code
+    pub fn market_quiet_hours_for(&self, phone: &str) -> Option<&MarketQuietHours> {
+        self.market_quiet_hours
+            .iter()
+            .filter(|(country_code, _)| phone.starts_with(country_code.as_str()))
+            .max_by_key(|(country_code, _)| country_code.len())
+            .map(|(_, quiet_hours)| quiet_hours)
+    }
+}
+
+impl Default for MessageSchedulerConfig {
+    fn default() -> Self {
+        let evening = NaiveTime::from_hms_opt(21, 0, 0).unwrap_or_default();
+        let morning = NaiveTime::from_hms_opt(8, 0, 0).unwrap_or_default();
+
+        Self {
+            dispatch_interval_seconds: 60, // Check for due messages every minute
+            batch_size: 100,
+            retry_delay_seconds: 300, // Retry failed sends after 5 minutes
+            claim_lease_seconds: 600,
+            market_quiet_hours: HashMap::from([
+                ("+61".to_string(), MarketQuietHours::new(evening, morning, 600)), // Australia (AEST)
+                ("+86".to_string(), MarketQuietHours::new(evening, morning, 480)), // China (CST)
+            ]),
+            enabled: true,
+        }
+    }
+}
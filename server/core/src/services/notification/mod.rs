@@ -0,0 +1,15 @@
+//! Notification service module for scheduled messages and reminder automations
+//!
+//! This module handles:
+//! - Scheduling templated messages for future delivery
+//! - Deferring non-critical delivery outside each user's or market's quiet hours
+//! - Background dispatch of due messages with retries
+
+mod config;
+mod scheduler;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{MarketQuietHours, MessageSchedulerConfig};
+pub use scheduler::{DispatchResult, MessageScheduler};
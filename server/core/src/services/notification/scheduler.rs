@@ -0,0 +1,404 @@
+//! Message scheduler for reminders and other time-based notifications
+//!
+//! Messages are rendered from templates and persisted with a delivery time
+//! that has already been moved out of the recipient's quiet hours. A
+//! background task dispatches due messages and re-checks preferences at
+//! send time, since users may change them after a message was scheduled.
+//!
+//! Quiet hours come from the user's preferences, or from the recipient's
+//! market when the user has not set any. Critical categories (OTPs and
+//! security alerts) are never deferred or suppressed.
+//!
+//! Messages belong to a tenant. The background task dispatches those of
+//! each tenant given to [`MessageScheduler::with_tenants`] in turn.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::notification::{
+    MessageTemplate, NotificationCategory, NotificationPreferences, ScheduledMessage,
+    ScheduledMessageStatus,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{NotificationPreferencesRepository, ScheduledMessageRepository};
+use crate::services::tenant::{self, TenantId};
+use crate::services::verification::SmsServiceTrait;
+
+use super::config::MessageSchedulerConfig;
+
+/// Service for scheduling and dispatching templated messages
+pub struct MessageScheduler<M, P, S>
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    messages: Arc<M>,
+    preferences: Arc<P>,
+    sms_service: Arc<S>,
+    config: MessageSchedulerConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<M, P, S> MessageScheduler<M, P, S>
+where
+    M: ScheduledMessageRepository + 'static,
+    P: NotificationPreferencesRepository + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    /// Create a new message scheduler
+    pub fn new(
+        messages: Arc<M>,
+        preferences: Arc<P>,
+        sms_service: Arc<S>,
+        config: MessageSchedulerConfig,
+    ) -> Self {
+        Self {
+            messages,
+            preferences,
+            sms_service,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Create a new message scheduler with default configuration
+    pub fn with_defaults(messages: Arc<M>, preferences: Arc<P>, sms_service: Arc<S>) -> Self {
+        Self::new(messages, preferences, sms_service, MessageSchedulerConfig::default())
+    }
+
+    /// Dispatch the messages of these tenants in the background task,
+    /// instead of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Schedule a templated message for a user
+    ///
+    /// The message takes the template's default category. See
+    /// [`Self::schedule_in_category`].
+    pub async fn schedule(
+        &self,
+        user_id: Uuid,
+        phone: &str,
+        template: MessageTemplate,
+        params: HashMap<String, String>,
+        at: DateTime<Utc>,
+    ) -> DomainResult<ScheduledMessage> {
+        self.schedule_in_category(user_id, phone, template, template.category(), params, at)
+            .await
+    }
+
+    /// Schedule a templated message for a user in a notification category
+    ///
+    /// If `at` falls inside the recipient's quiet hours the delivery time of
+    /// a non-critical message is pushed to the end of the quiet period in
+    /// the recipient's timezone. Critical messages keep the requested time.
+    ///
+    /// # Arguments
+    /// * `user_id` - Recipient user
+    /// * `phone` - Recipient phone number in E.164 format
+    /// * `template` - Message template to render at send time
+    /// * `category` - Category deciding which preferences and quiet hours apply
+    /// * `params` - Template parameters
+    /// * `at` - Requested delivery time
+    ///
+    /// # Returns
+    /// * `Ok(ScheduledMessage)` - The persisted message with its effective delivery time
+    /// * `Err(DomainError::Validation)` - Invalid phone number or missing template parameter
+    /// * `Err(DomainError::BusinessRule)` - The user has opted out of the category
+    pub async fn schedule_in_category(
+        &self,
+        user_id: Uuid,
+        phone: &str,
+        template: MessageTemplate,
+        category: NotificationCategory,
+        params: HashMap<String, String>,
+        at: DateTime<Utc>,
+    ) -> DomainResult<ScheduledMessage> {
+        if !self.sms_service.is_valid_phone_number(phone) {
+            return Err(DomainError::Validation {
+                message: "Invalid phone number format".to_string(),
+            });
+        }
+
+        template
+            .render(&params)
+            .map_err(|missing| DomainError::Validation {
+                message: format!("Missing template parameter: {}", missing),
+            })?;
+
+        let preferences = self.load_preferences(user_id, phone).await?;
+        if !preferences.accepts(category) {
+            return Err(DomainError::BusinessRule {
+                message: format!("User has disabled {} notifications", category.as_str()),
+            });
+        }
+
+        let mut message = ScheduledMessage::new(user_id, phone.to_string(), template, params, at)
+            .with_category(category);
+        if !category.is_critical() {
+            message.deliver_at = preferences.next_allowed_time(at);
+        }
+
+        if message.deliver_at != at {
+            debug!(
+                message_id = %message.id,
+                requested_for = %at,
+                deliver_at = %message.deliver_at,
+                "Scheduled message deferred past quiet hours"
+            );
+        }
+
+        let message = self.messages.save(message).await?;
+        info!(
+            message_id = %message.id,
+            user_id = %user_id,
+            template = message.template.as_str(),
+            category = message.category.as_str(),
+            "Message scheduled"
+        );
+
+        Ok(message)
+    }
+
+    /// Cancel a pending scheduled message
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The message was pending and is now cancelled
+    /// * `Ok(false)` - The message was already sent, failed or cancelled
+    /// * `Err(DomainError::NotFound)` - No message exists with this ID
+    pub async fn cancel(&self, message_id: Uuid) -> DomainResult<bool> {
+        let message = self
+            .messages
+            .find_by_id(message_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Scheduled message".to_string(),
+            })?;
+
+        self.cancel_message(message).await
+    }
+
+    /// Cancel a pending message scheduled for a user
+    ///
+    /// Messages addressed to other users are reported as not found, so
+    /// callers cannot probe for them.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The message was pending and is now cancelled
+    /// * `Ok(false)` - The message was already sent, failed or cancelled
+    /// * `Err(DomainError::NotFound)` - The user has no message with this ID
+    pub async fn cancel_for_user(&self, user_id: Uuid, message_id: Uuid) -> DomainResult<bool> {
+        let message = self
+            .messages
+            .find_by_id(message_id)
+            .await?
+            .filter(|message| message.user_id == user_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Scheduled message".to_string(),
+            })?;
+
+        self.cancel_message(message).await
+    }
+
+    async fn cancel_message(&self, mut message: ScheduledMessage) -> DomainResult<bool> {
+        let message_id = message.id;
+        if !message.cancel() {
+            return Ok(false);
+        }
+
+        self.messages.update(message).await?;
+        info!(message_id = %message_id, "Scheduled message cancelled");
+        Ok(true)
+    }
+
+    /// List pending messages scheduled for a user
+    pub async fn list_pending(&self, user_id: Uuid) -> DomainResult<Vec<ScheduledMessage>> {
+        self.messages.find_pending_by_user(user_id).await
+    }
+
+    /// Dispatch all messages that are due
+    ///
+    /// Due messages are claimed before anything is sent, so schedulers
+    /// running on several instances deliver each message once. Each due
+    /// message is re-checked against the user's current preferences:
+    /// messages in a category the user opted out of are cancelled, and
+    /// non-critical messages that would now land inside quiet hours are
+    /// deferred to the next allowed window.
+    ///
+    /// # Returns
+    /// * `Ok(DispatchResult)` - Summary of the dispatch cycle
+    /// * `Err(DomainError)` - If due messages could not be loaded
+    pub async fn dispatch_due(&self) -> DomainResult<DispatchResult> {
+        let now = Utc::now();
+        let claim_until = now + Duration::seconds(self.config.claim_lease_seconds);
+        let due = self
+            .messages
+            .claim_due(now, self.config.batch_size, claim_until)
+            .await?;
+        let mut result = DispatchResult::default();
+
+        for message in due {
+            let message_id = message.id;
+            if let Err(e) = self.dispatch_one(message, now, &mut result).await {
+                error!(message_id = %message_id, error = %e, "Failed to dispatch scheduled message");
+                result.errors.push(format!("{}: {}", message_id, e));
+            }
+        }
+
+        if result.total_processed() > 0 {
+            info!(
+                sent = result.sent,
+                deferred = result.deferred,
+                failed = result.failed,
+                cancelled = result.cancelled,
+                "Scheduled message dispatch completed"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Dispatch a single claimed message and persist its new state
+    ///
+    /// Saving the message in its new state releases the claim.
+    async fn dispatch_one(
+        &self,
+        mut message: ScheduledMessage,
+        now: DateTime<Utc>,
+        result: &mut DispatchResult,
+    ) -> DomainResult<()> {
+        message.release();
+        let preferences = self.load_preferences(message.user_id, &message.phone).await?;
+
+        if !preferences.accepts(message.category) {
+            message.cancel();
+            self.messages.update(message).await?;
+            result.cancelled += 1;
+            return Ok(());
+        }
+
+        if !message.category.is_critical() && preferences.is_quiet_at(now) {
+            message.deliver_at = preferences.next_allowed_time(now);
+            self.messages.update(message).await?;
+            result.deferred += 1;
+            return Ok(());
+        }
+
+        let body = message.template.render(&message.params).map_err(|missing| {
+            DomainError::Validation {
+                message: format!("Missing template parameter: {}", missing),
+            }
+        })?;
+
+        match self.sms_service.send_message(&message.phone, &body).await {
+            Ok(provider_message_id) => {
+                message.mark_sent(provider_message_id);
+                result.sent += 1;
+            }
+            Err(e) => {
+                warn!(message_id = %message.id, error = %e, "Scheduled message delivery failed");
+                message.record_failure(e);
+                if message.status == ScheduledMessageStatus::Failed {
+                    result.failed += 1;
+                } else {
+                    message.deliver_at = now + Duration::seconds(self.config.retry_delay_seconds);
+                    result.deferred += 1;
+                }
+            }
+        }
+
+        self.messages.update(message).await?;
+        Ok(())
+    }
+
+    /// Load a user's preferences, falling back to defaults
+    ///
+    /// Users without quiet hours of their own get the quiet hours of the
+    /// recipient's market. The market's UTC offset is only used when the
+    /// user has no stored preferences, and so no known timezone.
+    async fn load_preferences(&self, user_id: Uuid, phone: &str) -> DomainResult<NotificationPreferences> {
+        let stored = self.preferences.find_by_user_id(user_id).await?;
+        let has_timezone = stored.is_some();
+        let mut preferences = stored.unwrap_or_else(|| NotificationPreferences::new(user_id));
+
+        if !preferences.has_quiet_hours() {
+            if let Some(market) = self.config.market_quiet_hours_for(phone) {
+                preferences = preferences.with_quiet_hours(market.start, market.end);
+                if !has_timezone {
+                    preferences = preferences.with_utc_offset_minutes(market.utc_offset_minutes);
+                }
+            }
+        }
+
+        Ok(preferences)
+    }
+
+    /// Start the scheduler as a background task
+    ///
+    /// This spawns a tokio task that dispatches due messages at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Message scheduler is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.dispatch_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Message scheduler started - will dispatch every {} seconds",
+                self.config.dispatch_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                for tenant in &self.tenants {
+                    match tenant::scope(tenant.clone(), self.dispatch_due()).await {
+                        Ok(result) => {
+                            if !result.errors.is_empty() {
+                                warn!(
+                                    "Dispatch for tenant {} completed with errors: {:?}",
+                                    tenant, result.errors
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("Message dispatch cycle for tenant {} failed: {}", tenant, e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Result of a dispatch cycle
+#[derive(Debug, Default)]
+pub struct DispatchResult {
+    /// Number of messages delivered
+    pub sent: usize,
+    /// Number of messages deferred because of quiet hours or a retriable failure
+    pub deferred: usize,
+    /// Number of messages that exhausted their delivery attempts
+    pub failed: usize,
+    /// Number of messages cancelled because the user opted out of their category
+    pub cancelled: usize,
+    /// Any errors encountered during dispatch
+    pub errors: Vec<String>,
+}
+
+impl DispatchResult {
+    /// Get total number of messages processed
+    pub fn total_processed(&self) -> usize {
+        self.sent + self.deferred + self.failed + self.cancelled
+    }
+}
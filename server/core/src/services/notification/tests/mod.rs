@@ -0,0 +1,4 @@
+//! Tests for the notification service module.
+
+#[cfg(test)]
+mod scheduler_tests;
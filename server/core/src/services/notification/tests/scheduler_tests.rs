@@ -0,0 +1,517 @@
+//! Unit tests for the message scheduler
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::notification::{
+    MessageTemplate, NotificationCategory, NotificationPreferences, ScheduledMessage,
+    ScheduledMessageStatus,
+};
+use crate::errors::DomainError;
+use crate::repositories::{NotificationPreferencesRepository, ScheduledMessageRepository};
+use crate::services::notification::{MarketQuietHours, MessageScheduler, MessageSchedulerConfig};
+use crate::services::verification::SmsServiceTrait;
+
+#[derive(Default)]
+struct MockMessageRepository {
+    messages: Mutex<HashMap<Uuid, ScheduledMessage>>,
+}
+
+impl MockMessageRepository {
+    fn get(&self, id: Uuid) -> ScheduledMessage {
+        self.messages.lock().unwrap().get(&id).cloned().unwrap()
+    }
+
+    fn make_due(&self, id: Uuid) {
+        let mut messages = self.messages.lock().unwrap();
+        let message = messages.get_mut(&id).unwrap();
+        message.deliver_at = Utc::now() - Duration::minutes(1);
+    }
+}
+
+#[async_trait]
+impl ScheduledMessageRepository for MockMessageRepository {
+    async fn save(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+        self.messages.lock().unwrap().insert(message.id, message.clone());
+        Ok(message)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ScheduledMessage>, DomainError> {
+        Ok(self.messages.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        _claim_until: DateTime<Utc>,
+    ) -> Result<Vec<ScheduledMessage>, DomainError> {
+        let mut messages = self.messages.lock().unwrap();
+        let mut due: Vec<_> = messages.values_mut().filter(|m| m.is_due(now)).collect();
+        due.sort_by_key(|m| m.deliver_at);
+        Ok(due
+            .into_iter()
+            .take(limit)
+            .map(|m| {
+                m.claim();
+                m.clone()
+            })
+            .collect())
+    }
+
+    async fn find_pending_by_user(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, DomainError> {
+        Ok(self
+            .messages
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|m| m.user_id == user_id && m.status == ScheduledMessageStatus::Pending)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+        let mut messages = self.messages.lock().unwrap();
+        if !messages.contains_key(&message.id) {
+            return Err(DomainError::NotFound {
+                resource: "Scheduled message".to_string(),
+            });
+        }
+        messages.insert(message.id, message.clone());
+        Ok(message)
+    }
+}
+
+#[derive(Default)]
+struct MockPreferencesRepository {
+    preferences: Mutex<HashMap<Uuid, NotificationPreferences>>,
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for MockPreferencesRepository {
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>, DomainError> {
+        Ok(self.preferences.lock().unwrap().get(&user_id).cloned())
+    }
+
+    async fn upsert(
+        &self,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, DomainError> {
+        self.preferences
+            .lock()
+            .unwrap()
+            .insert(preferences.user_id, preferences.clone());
+        Ok(preferences)
+    }
+}
+
+struct MockSmsService {
+    sent: Arc<Mutex<Vec<(String, String)>>>,
+    should_fail: bool,
+}
+
+impl MockSmsService {
+    fn new(should_fail: bool) -> Self {
+        Self {
+            sent: Arc::new(Mutex::new(Vec::new())),
+            should_fail,
+        }
+    }
+}
+
+#[async_trait]
+impl SmsServiceTrait for MockSmsService {
+    async fn send_verification_code(&self, phone: &str, code: &str) -> Result<String, String> {
+        self.send_message(phone, code).await
+    }
+
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        // Let concurrent dispatchers interleave at the send
+        tokio::task::yield_now().await;
+        if self.should_fail {
+            return Err("SMS service error".to_string());
+        }
+        self.sent
+            .lock()
+            .unwrap()
+            .push((phone.to_string(), message.to_string()));
+        Ok(format!("mock-msg-{}", Uuid::new_v4()))
+    }
+
+    fn is_valid_phone_number(&self, phone: &str) -> bool {
+        phone.starts_with('+') && phone.len() >= 10
+    }
+}
+
+type TestScheduler = MessageScheduler<MockMessageRepository, MockPreferencesRepository, MockSmsService>;
+
+fn create_scheduler(
+    sms_should_fail: bool,
+) -> (
+    TestScheduler,
+    Arc<MockMessageRepository>,
+    Arc<MockPreferencesRepository>,
+    Arc<MockSmsService>,
+) {
+    // No market quiet hours, so results do not depend on the time of day
+    let config = MessageSchedulerConfig {
+        market_quiet_hours: HashMap::new(),
+        ..MessageSchedulerConfig::default()
+    };
+    create_scheduler_with_config(sms_should_fail, config)
+}
+
+fn create_scheduler_with_config(
+    sms_should_fail: bool,
+    config: MessageSchedulerConfig,
+) -> (
+    TestScheduler,
+    Arc<MockMessageRepository>,
+    Arc<MockPreferencesRepository>,
+    Arc<MockSmsService>,
+) {
+    let messages = Arc::new(MockMessageRepository::default());
+    let preferences = Arc::new(MockPreferencesRepository::default());
+    let sms = Arc::new(MockSmsService::new(sms_should_fail));
+    let scheduler = MessageScheduler::new(messages.clone(), preferences.clone(), sms.clone(), config);
+    (scheduler, messages, preferences, sms)
+}
+
+/// Scheduler whose `+61` market is quiet around the current time
+fn create_scheduler_with_quiet_market() -> (
+    TestScheduler,
+    Arc<MockMessageRepository>,
+    Arc<MockPreferencesRepository>,
+    Arc<MockSmsService>,
+) {
+    let now = Utc::now().time();
+    let config = MessageSchedulerConfig {
+        market_quiet_hours: HashMap::from([(
+            "+61".to_string(),
+            MarketQuietHours::new(now - Duration::minutes(30), now + Duration::minutes(30), 0),
+        )]),
+        ..MessageSchedulerConfig::default()
+    };
+    create_scheduler_with_config(false, config)
+}
+
+fn review_params() -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("worker_name".to_string(), "Alex".to_string());
+    params
+}
+
+/// Quiet hours window centred on the current time
+fn quiet_now_preferences(user_id: Uuid) -> NotificationPreferences {
+    let now = Utc::now().time();
+    NotificationPreferences::new(user_id)
+        .with_quiet_hours(now - Duration::minutes(30), now + Duration::minutes(30))
+}
+
+#[tokio::test]
+async fn test_schedule_without_preferences_keeps_requested_time() {
+    let (scheduler, _, _, _) = create_scheduler(false);
+    let at = Utc::now() + Duration::hours(2);
+
+    let message = scheduler
+        .schedule(Uuid::new_v4(), "+61412345678", MessageTemplate::ReviewRequest, review_params(), at)
+        .await
+        .unwrap();
+
+    assert_eq!(message.deliver_at, at);
+    assert_eq!(message.status, ScheduledMessageStatus::Pending);
+}
+
+#[tokio::test]
+async fn test_schedule_defers_past_quiet_hours() {
+    let (scheduler, _, preferences, _) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let prefs = quiet_now_preferences(user_id);
+    preferences.upsert(prefs.clone()).await.unwrap();
+
+    let at = Utc::now();
+    let message = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), at)
+        .await
+        .unwrap();
+
+    assert!(message.deliver_at > at);
+    assert!(!prefs.is_quiet_at(message.deliver_at));
+    assert_eq!(message.requested_for, at);
+}
+
+#[tokio::test]
+async fn test_schedule_rejects_missing_template_params() {
+    let (scheduler, _, _, _) = create_scheduler(false);
+
+    let result = scheduler
+        .schedule(
+            Uuid::new_v4(),
+            "+61412345678",
+            MessageTemplate::WorkerArrivalReminder,
+            review_params(),
+            Utc::now(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_schedule_rejects_when_reminders_disabled() {
+    let (scheduler, _, preferences, _) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let mut prefs = NotificationPreferences::new(user_id);
+    prefs.reminders_enabled = false;
+    preferences.upsert(prefs).await.unwrap();
+
+    let result = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), Utc::now())
+        .await;
+
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_dispatch_sends_due_messages() {
+    let (scheduler, messages, _, sms) = create_scheduler(false);
+    let message = scheduler
+        .schedule(
+            Uuid::new_v4(),
+            "+61412345678",
+            MessageTemplate::ReviewRequest,
+            review_params(),
+            Utc::now() - Duration::minutes(1),
+        )
+        .await
+        .unwrap();
+
+    let result = scheduler.dispatch_due().await.unwrap();
+
+    assert_eq!(result.sent, 1);
+    assert_eq!(messages.get(message.id).status, ScheduledMessageStatus::Sent);
+    let sent = sms.sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].1.contains("Alex"));
+}
+
+#[tokio::test]
+async fn test_concurrent_dispatchers_send_each_message_once() {
+    let (first, messages, preferences, sms) = create_scheduler(false);
+    let second = MessageScheduler::new(
+        messages.clone(),
+        preferences.clone(),
+        sms.clone(),
+        MessageSchedulerConfig {
+            market_quiet_hours: HashMap::new(),
+            ..MessageSchedulerConfig::default()
+        },
+    );
+    for _ in 0..5 {
+        first
+            .schedule(
+                Uuid::new_v4(),
+                "+61412345678",
+                MessageTemplate::ReviewRequest,
+                review_params(),
+                Utc::now() - Duration::minutes(1),
+            )
+            .await
+            .unwrap();
+    }
+
+    let (a, b) = tokio::join!(first.dispatch_due(), second.dispatch_due());
+
+    assert_eq!(a.unwrap().sent + b.unwrap().sent, 5);
+    assert_eq!(sms.sent.lock().unwrap().len(), 5);
+}
+
+#[tokio::test]
+async fn test_dispatch_defers_when_quiet_hours_changed() {
+    let (scheduler, messages, preferences, sms) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let message = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), Utc::now())
+        .await
+        .unwrap();
+
+    // User enables quiet hours after the message was scheduled
+    preferences.upsert(quiet_now_preferences(user_id)).await.unwrap();
+    messages.make_due(message.id);
+
+    let result = scheduler.dispatch_due().await.unwrap();
+
+    assert_eq!(result.deferred, 1);
+    assert!(sms.sent.lock().unwrap().is_empty());
+    let stored = messages.get(message.id);
+    assert_eq!(stored.status, ScheduledMessageStatus::Pending);
+    assert!(stored.deliver_at > Utc::now());
+}
+
+#[tokio::test]
+async fn test_dispatch_retries_then_fails() {
+    let (scheduler, messages, _, _) = create_scheduler(true);
+    let message = scheduler
+        .schedule(Uuid::new_v4(), "+61412345678", MessageTemplate::ReviewRequest, review_params(), Utc::now())
+        .await
+        .unwrap();
+
+    let result = scheduler.dispatch_due().await.unwrap();
+    assert_eq!(result.deferred, 1);
+    assert_eq!(messages.get(message.id).attempts, 1);
+
+    messages.make_due(message.id);
+    scheduler.dispatch_due().await.unwrap();
+    messages.make_due(message.id);
+    let result = scheduler.dispatch_due().await.unwrap();
+
+    assert_eq!(result.failed, 1);
+    assert_eq!(messages.get(message.id).status, ScheduledMessageStatus::Failed);
+}
+
+#[tokio::test]
+async fn test_cancel_pending_message() {
+    let (scheduler, _, _, _) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let message = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), Utc::now() + Duration::hours(1))
+        .await
+        .unwrap();
+
+    assert!(scheduler.cancel(message.id).await.unwrap());
+    assert!(!scheduler.cancel(message.id).await.unwrap());
+    assert!(scheduler.list_pending(user_id).await.unwrap().is_empty());
+
+    let missing = scheduler.cancel(Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_cancel_for_user_ignores_other_users_messages() {
+    let (scheduler, _, _, _) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let message = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), Utc::now() + Duration::hours(1))
+        .await
+        .unwrap();
+
+    let other = scheduler.cancel_for_user(Uuid::new_v4(), message.id).await;
+    assert!(matches!(other, Err(DomainError::NotFound { .. })));
+    assert_eq!(scheduler.list_pending(user_id).await.unwrap().len(), 1);
+
+    assert!(scheduler.cancel_for_user(user_id, message.id).await.unwrap());
+    assert!(scheduler.list_pending(user_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_schedule_defers_past_market_quiet_hours() {
+    let (scheduler, _, _, _) = create_scheduler_with_quiet_market();
+    let at = Utc::now();
+
+    let message = scheduler
+        .schedule_in_category(
+            Uuid::new_v4(),
+            "+61412345678",
+            MessageTemplate::Custom,
+            NotificationCategory::Marketing,
+            HashMap::from([("body".to_string(), "Spring sale".to_string())]),
+            at,
+        )
+        .await
+        .unwrap();
+
+    assert!(message.deliver_at > at);
+    assert_eq!(message.category, NotificationCategory::Marketing);
+
+    // Recipients outside the market are not affected
+    let message = scheduler
+        .schedule(Uuid::new_v4(), "+8613812345678", MessageTemplate::ReviewRequest, review_params(), at)
+        .await
+        .unwrap();
+    assert_eq!(message.deliver_at, at);
+}
+
+#[tokio::test]
+async fn test_user_quiet_hours_override_market_quiet_hours() {
+    let (scheduler, _, preferences, _) = create_scheduler_with_quiet_market();
+    let user_id = Uuid::new_v4();
+    let now = Utc::now().time();
+    preferences
+        .upsert(
+            NotificationPreferences::new(user_id)
+                .with_quiet_hours(now + Duration::hours(3), now + Duration::hours(4)),
+        )
+        .await
+        .unwrap();
+
+    let at = Utc::now();
+    let message = scheduler
+        .schedule(user_id, "+61412345678", MessageTemplate::ReviewRequest, review_params(), at)
+        .await
+        .unwrap();
+
+    assert_eq!(message.deliver_at, at);
+}
+
+#[tokio::test]
+async fn test_critical_messages_bypass_quiet_hours() {
+    let (scheduler, messages, preferences, sms) = create_scheduler_with_quiet_market();
+    let user_id = Uuid::new_v4();
+    let mut prefs = quiet_now_preferences(user_id);
+    prefs.reminders_enabled = false;
+    prefs.marketing_enabled = false;
+    preferences.upsert(prefs).await.unwrap();
+
+    let at = Utc::now() - Duration::minutes(1);
+    let message = scheduler
+        .schedule_in_category(
+            user_id,
+            "+61412345678",
+            MessageTemplate::Custom,
+            NotificationCategory::SecurityAlert,
+            HashMap::from([("body".to_string(), "New sign-in".to_string())]),
+            at,
+        )
+        .await
+        .unwrap();
+    assert_eq!(message.deliver_at, at);
+
+    let result = scheduler.dispatch_due().await.unwrap();
+
+    assert_eq!(result.sent, 1);
+    assert_eq!(messages.get(message.id).status, ScheduledMessageStatus::Sent);
+    assert_eq!(sms.sent.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_dispatch_cancels_when_marketing_disabled() {
+    let (scheduler, messages, preferences, sms) = create_scheduler(false);
+    let user_id = Uuid::new_v4();
+    let message = scheduler
+        .schedule_in_category(
+            user_id,
+            "+61412345678",
+            MessageTemplate::Custom,
+            NotificationCategory::Digest,
+            HashMap::from([("body".to_string(), "Your weekly summary".to_string())]),
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+
+    let mut prefs = NotificationPreferences::new(user_id);
+    prefs.marketing_enabled = false;
+    preferences.upsert(prefs).await.unwrap();
+
+    let result = scheduler.dispatch_due().await.unwrap();
+
+    assert_eq!(result.cancelled, 1);
+    assert!(sms.sent.lock().unwrap().is_empty());
+    assert_eq!(messages.get(message.id).status, ScheduledMessageStatus::Cancelled);
+}
@@ -0,0 +1,34 @@
+//! Configuration for the OAuth service
+
+use crate::domain::entities::oauth::{
+    SCOPE_MATERIALS_READ, SCOPE_MATERIALS_WRITE, SCOPE_ORDERS_READ, SCOPE_PROFILE_READ,
+};
+
+/// Configuration for the OAuth service
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    /// Lifetime of partner access tokens, in seconds
+    pub access_token_ttl_seconds: i64,
+    /// Lifetime of authorization codes, in seconds
+    pub authorization_code_ttl_seconds: i64,
+    /// Scopes clients can be registered for
+    pub supported_scopes: Vec<String>,
+}
+
+impl Default for OAuthConfig {
+    fn default() -> Self {
+        Self {
+            access_token_ttl_seconds: 3600,
+            authorization_code_ttl_seconds: 600,
+            supported_scopes: [
+                SCOPE_PROFILE_READ,
+                SCOPE_ORDERS_READ,
+                SCOPE_MATERIALS_READ,
+                SCOPE_MATERIALS_WRITE,
+            ]
+            .iter()
+            .map(|scope| scope.to_string())
+            .collect(),
+        }
+    }
+}
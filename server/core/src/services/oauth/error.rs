@@ -0,0 +1,50 @@
+//! Errors of the OAuth token endpoint
+
+use thiserror::Error;
+
+use crate::errors::DomainError;
+
+/// Error returned by the token endpoint grants
+///
+/// The variants map to the error codes of RFC 6749, section 5.2, which
+/// partner libraries rely on.
+#[derive(Debug, Error)]
+pub enum OAuthError {
+    /// A required parameter is missing or malformed
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// Unknown client, wrong secret or inactive client
+    #[error("Client authentication failed")]
+    InvalidClient,
+
+    /// The authorization code is invalid, expired, used or issued to another client
+    #[error("Invalid grant: {0}")]
+    InvalidGrant(String),
+
+    /// The client is not allowed to use this grant
+    #[error("Client is not authorized for this grant type")]
+    UnauthorizedClient,
+
+    /// A requested scope is unknown or not allowed for the client
+    #[error("Requested scope is not allowed")]
+    InvalidScope,
+
+    /// Storage or signing failed
+    #[error(transparent)]
+    Server(#[from] DomainError),
+}
+
+impl OAuthError {
+    /// RFC 6749 error code
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::InvalidRequest(_) => "invalid_request",
+            Self::InvalidClient => "invalid_client",
+            Self::InvalidGrant(_) => "invalid_grant",
+            Self::UnauthorizedClient => "unauthorized_client",
+            Self::InvalidScope => "invalid_scope",
+            Self::Server(_) => "server_error",
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! OAuth2 authorization server module for partner integrations
+//!
+//! This module handles:
+//! - Registering partner applications as OAuth clients with a hashed secret
+//! - The client credentials grant for a partner's own account
+//! - User consent and single-use authorization codes, with optional PKCE
+//! - Exchanging codes for scope-limited tokens signed by the token service
+
+mod config;
+mod error;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::OAuthConfig;
+pub use error::OAuthError;
+pub use service::{
+    AuthorizationGrant, AuthorizationRequest, ConsentPrompt, NewOAuthClient, OAuthService,
+    PartnerAccessToken, RegisteredClient,
+};
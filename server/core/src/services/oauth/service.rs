@@ -0,0 +1,478 @@
+//! OAuth service issuing partner access tokens
+//!
+//! Client secrets and authorization codes are only stored hashed. Tokens
+//! are signed by the token service with the partner audience, so they are
+//! never accepted by the main API. A user's consent is remembered per
+//! client; revoking it stops codes already issued from being exchanged.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use constant_time_eq::constant_time_eq;
+use rand::RngCore;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{
+    format_scope, hash_oauth_secret, parse_scope, OAuthAuthorizationCode, OAuthClient, OAuthConsent,
+    OAuthGrantType,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{OAuthRepository, TokenRepository};
+use crate::services::token::TokenService;
+
+use super::config::OAuthConfig;
+use super::error::OAuthError;
+
+/// PKCE challenge method supported for authorization codes
+const PKCE_METHOD_S256: &str = "S256";
+
+/// A partner application to register
+#[derive(Debug, Clone)]
+pub struct NewOAuthClient {
+    /// Partner name shown on the consent screen
+    pub name: String,
+    /// Redirect URIs for the authorization code grant
+    pub redirect_uris: Vec<String>,
+    /// Scopes the client may request
+    pub scopes: Vec<String>,
+    /// Grants the client may use
+    pub grant_types: Vec<OAuthGrantType>,
+}
+
+/// A newly registered client with its secret
+///
+/// The secret is only available here; it cannot be recovered later.
+#[derive(Debug, Clone)]
+pub struct RegisteredClient {
+    /// The registered client
+    pub client: OAuthClient,
+    /// Plain client secret to hand to the partner
+    pub client_secret: String,
+}
+
+/// Parameters of an authorization request
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizationRequest {
+    /// Public identifier of the client
+    pub client_id: String,
+    /// Redirect URI the code is sent to
+    pub redirect_uri: String,
+    /// Space-delimited scopes requested
+    pub scope: String,
+    /// Opaque value the client uses to match the response to its request
+    pub state: Option<String>,
+    /// PKCE code challenge
+    pub code_challenge: Option<String>,
+    /// PKCE code challenge method; only `S256` is supported
+    pub code_challenge_method: Option<String>,
+}
+
+/// What the user is asked to approve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsentPrompt {
+    /// Public identifier of the client
+    pub client_id: String,
+    /// Partner name
+    pub client_name: String,
+    /// Requested scopes
+    pub scopes: Vec<String>,
+    /// Whether the user already approved every requested scope
+    pub previously_granted: bool,
+}
+
+/// An authorization code issued after the user approved a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizationGrant {
+    /// Single-use authorization code
+    pub code: String,
+    /// Registered redirect URI the code is sent to
+    pub redirect_uri: String,
+    /// State from the request, returned unchanged
+    pub state: Option<String>,
+}
+
+impl AuthorizationGrant {
+    /// Redirect URI with the code and state appended as query parameters
+    pub fn redirect_url(&self) -> String {
+        let separator = if self.redirect_uri.contains('?') { '&' } else { '?' };
+        let mut url = format!("{}{}code={}", self.redirect_uri, separator, encode_query_value(&self.code));
+        if let Some(state) = &self.state {
+            url.push_str("&state=");
+            url.push_str(&encode_query_value(state));
+        }
+        url
+    }
+}
+
+/// A scope-limited access token for a partner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartnerAccessToken {
+    /// Signed JWT
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    /// Lifetime of the token, in seconds
+    pub expires_in: i64,
+    /// Space-delimited scopes the token grants
+    pub scope: String,
+}
+
+/// Service implementing the OAuth2 authorization server for partners
+pub struct OAuthService<O, R>
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    repository: Arc<O>,
+    token_service: Arc<TokenService<R>>,
+    config: OAuthConfig,
+}
+
+impl<O, R> OAuthService<O, R>
+where
+    O: OAuthRepository + 'static,
+    R: TokenRepository + 'static,
+{
+    /// Create a new OAuth service
+    pub fn new(repository: Arc<O>, token_service: Arc<TokenService<R>>, config: OAuthConfig) -> Self {
+        Self { repository, token_service, config }
+    }
+
+    /// Register a partner application
+    ///
+    /// # Returns
+    /// * `Ok(RegisteredClient)` - The client and its secret, shown only once
+    /// * `Err(DomainError::Validation)` - Unsupported scope, invalid redirect URI or missing fields
+    pub async fn register_client(&self, new: NewOAuthClient) -> DomainResult<RegisteredClient> {
+        if let Some(scope) = new.scopes.iter().find(|s| !self.config.supported_scopes.contains(s)) {
+            return Err(DomainError::Validation {
+                message: format!("Unsupported scope: {}", scope),
+            });
+        }
+        if let Some(uri) = new.redirect_uris.iter().find(|uri| !is_valid_redirect_uri(uri)) {
+            return Err(DomainError::Validation {
+                message: format!("Invalid redirect URI: {}", uri),
+            });
+        }
+
+        let client_secret = random_token();
+        let client = OAuthClient::new(
+            format!("rec_{}", Uuid::new_v4().simple()),
+            hash_oauth_secret(&client_secret),
+            new.name.trim(),
+            new.redirect_uris,
+            new.scopes,
+            new.grant_types,
+        )
+        .map_err(|message| DomainError::Validation { message })?;
+
+        let client = self.repository.save_client(client).await?;
+        info!(client_id = %client.client_id, name = %client.name, "OAuth client registered");
+
+        Ok(RegisteredClient { client, client_secret })
+    }
+
+    /// List registered clients, newest first
+    pub async fn list_clients(&self) -> DomainResult<Vec<OAuthClient>> {
+        self.repository.list_clients().await
+    }
+
+    /// Enable or disable a client
+    ///
+    /// A disabled client cannot obtain new tokens; tokens already issued
+    /// stay valid until they expire.
+    pub async fn set_client_active(&self, client_id: &str, is_active: bool) -> DomainResult<OAuthClient> {
+        let mut client = self.find_client(client_id).await?;
+        client.is_active = is_active;
+        client.updated_at = Utc::now();
+        self.repository.update_client(&client).await?;
+
+        info!(client_id = %client.client_id, is_active, "OAuth client updated");
+        Ok(client)
+    }
+
+    /// Check an authorization request and describe it for the consent screen
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Unknown client, unregistered redirect URI,
+    ///   or scopes or PKCE method not allowed
+    pub async fn prepare_authorization(
+        &self,
+        user_id: Uuid,
+        request: &AuthorizationRequest,
+    ) -> DomainResult<ConsentPrompt> {
+        let (client, scopes) = self.validate_authorization(request).await?;
+        let previously_granted = self
+            .repository
+            .find_consent(user_id, &client.client_id)
+            .await?
+            .is_some_and(|consent| consent.covers(&scopes));
+
+        Ok(ConsentPrompt {
+            client_id: client.client_id,
+            client_name: client.name,
+            scopes,
+            previously_granted,
+        })
+    }
+
+    /// Record the user's approval and issue an authorization code
+    ///
+    /// Scopes approved earlier for the client are kept.
+    pub async fn approve_authorization(
+        &self,
+        user_id: Uuid,
+        request: &AuthorizationRequest,
+    ) -> DomainResult<AuthorizationGrant> {
+        let (client, scopes) = self.validate_authorization(request).await?;
+
+        let consent = match self.repository.find_consent(user_id, &client.client_id).await? {
+            Some(mut consent) => {
+                consent.extend(&scopes);
+                consent
+            }
+            None => OAuthConsent::new(user_id, &client.client_id, scopes.clone()),
+        };
+        self.repository.save_consent(&consent).await?;
+
+        let code = random_token();
+        self.repository
+            .save_authorization_code(&OAuthAuthorizationCode::new(
+                hash_oauth_secret(&code),
+                &client.client_id,
+                user_id,
+                &request.redirect_uri,
+                scopes,
+                request.code_challenge.clone(),
+                self.config.authorization_code_ttl_seconds,
+            ))
+            .await?;
+
+        info!(client_id = %client.client_id, user_id = %user_id, "OAuth authorization approved");
+
+        Ok(AuthorizationGrant {
+            code,
+            redirect_uri: request.redirect_uri.clone(),
+            state: request.state.clone(),
+        })
+    }
+
+    /// Consents a user has given
+    pub async fn user_consents(&self, user_id: Uuid) -> DomainResult<Vec<OAuthConsent>> {
+        self.repository.find_consents_by_user(user_id).await
+    }
+
+    /// Revoke a user's consent for a client
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - The user had not consented to the client
+    pub async fn revoke_consent(&self, user_id: Uuid, client_id: &str) -> DomainResult<()> {
+        if !self.repository.delete_consent(user_id, client_id).await? {
+            return Err(DomainError::NotFound {
+                resource: "OAuth consent".to_string(),
+            });
+        }
+
+        info!(client_id = %client_id, user_id = %user_id, "OAuth consent revoked");
+        Ok(())
+    }
+
+    /// Client credentials grant: a token for the client's own account
+    ///
+    /// Without a requested scope the token grants every scope allowed for
+    /// the client. The token's subject is the client identifier.
+    pub async fn client_credentials(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        scope: Option<&str>,
+    ) -> Result<PartnerAccessToken, OAuthError> {
+        let client = self.authenticate_client(client_id, client_secret).await?;
+        if !client.allows_grant(OAuthGrantType::ClientCredentials) {
+            return Err(OAuthError::UnauthorizedClient);
+        }
+
+        let scopes = match scope.map(parse_scope) {
+            Some(scopes) if !scopes.is_empty() => scopes,
+            _ => client.allowed_scopes.clone(),
+        };
+        if !client.allows_scopes(&scopes) {
+            return Err(OAuthError::InvalidScope);
+        }
+
+        self.issue_token(client.client_id.clone(), &client, &scopes)
+    }
+
+    /// Authorization code grant: a token to act on behalf of the approving user
+    ///
+    /// # Arguments
+    /// * `redirect_uri` - Must equal the redirect URI of the authorization request
+    /// * `code_verifier` - PKCE verifier, required when the request had a challenge
+    pub async fn exchange_code(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: Option<&str>,
+    ) -> Result<PartnerAccessToken, OAuthError> {
+        let client = self.authenticate_client(client_id, client_secret).await?;
+        if !client.allows_grant(OAuthGrantType::AuthorizationCode) {
+            return Err(OAuthError::UnauthorizedClient);
+        }
+
+        let code = self
+            .repository
+            .consume_authorization_code(&hash_oauth_secret(code), Utc::now())
+            .await?
+            .ok_or_else(|| OAuthError::InvalidGrant("Unknown or used authorization code".to_string()))?;
+
+        if code.client_id != client.client_id {
+            return Err(OAuthError::InvalidGrant("Code was issued to another client".to_string()));
+        }
+        if Utc::now() >= code.expires_at {
+            return Err(OAuthError::InvalidGrant("Authorization code expired".to_string()));
+        }
+        if code.redirect_uri != redirect_uri {
+            return Err(OAuthError::InvalidGrant("Redirect URI does not match".to_string()));
+        }
+        if !code.verifies(code_verifier) {
+            return Err(OAuthError::InvalidGrant("Invalid code verifier".to_string()));
+        }
+
+        // The user may have revoked the client between approval and exchange
+        let consented = self
+            .repository
+            .find_consent(code.user_id, &client.client_id)
+            .await?
+            .is_some_and(|consent| consent.covers(&code.scopes));
+        if !consented {
+            return Err(OAuthError::InvalidGrant("Consent was revoked".to_string()));
+        }
+
+        self.issue_token(code.user_id.to_string(), &client, &code.scopes)
+    }
+
+    /// Validate an authorization request against the client's registration
+    async fn validate_authorization(
+        &self,
+        request: &AuthorizationRequest,
+    ) -> DomainResult<(OAuthClient, Vec<String>)> {
+        let client = self
+            .repository
+            .find_client(&request.client_id)
+            .await?
+            .filter(|client| client.allows_grant(OAuthGrantType::AuthorizationCode))
+            .ok_or_else(|| DomainError::Validation {
+                message: "Unknown client".to_string(),
+            })?;
+
+        if !client.allows_redirect_uri(&request.redirect_uri) {
+            return Err(DomainError::Validation {
+                message: "Redirect URI is not registered for the client".to_string(),
+            });
+        }
+
+        let scopes = parse_scope(&request.scope);
+        if scopes.is_empty() || !client.allows_scopes(&scopes) {
+            return Err(DomainError::Validation {
+                message: "Requested scope is not allowed".to_string(),
+            });
+        }
+
+        match (&request.code_challenge, request.code_challenge_method.as_deref()) {
+            (None, None) | (Some(_), Some(PKCE_METHOD_S256)) => {}
+            _ => {
+                return Err(DomainError::Validation {
+                    message: "Code challenge must use the S256 method".to_string(),
+                })
+            }
+        }
+
+        Ok((client, scopes))
+    }
+
+    /// Find an active client and check its secret
+    async fn authenticate_client(&self, client_id: &str, client_secret: &str) -> Result<OAuthClient, OAuthError> {
+        let client = self
+            .repository
+            .find_client(client_id)
+            .await?
+            .filter(|client| client.is_active)
+            .ok_or(OAuthError::InvalidClient)?;
+
+        let hash = hash_oauth_secret(client_secret);
+        if !constant_time_eq(hash.as_bytes(), client.client_secret_hash.as_bytes()) {
+            return Err(OAuthError::InvalidClient);
+        }
+
+        Ok(client)
+    }
+
+    /// Sign a token for the client
+    fn issue_token(
+        &self,
+        subject: String,
+        client: &OAuthClient,
+        scopes: &[String],
+    ) -> Result<PartnerAccessToken, OAuthError> {
+        let expires_in = self.config.access_token_ttl_seconds;
+        let access_token = self.token_service.generate_partner_token(
+            subject,
+            client.client_id.clone(),
+            scopes,
+            expires_in,
+        )?;
+
+        info!(client_id = %client.client_id, scope = %format_scope(scopes), "Partner access token issued");
+
+        Ok(PartnerAccessToken {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+            scope: format_scope(scopes),
+        })
+    }
+
+    /// Find a client by its public identifier
+    async fn find_client(&self, client_id: &str) -> DomainResult<OAuthClient> {
+        self.repository
+            .find_client(client_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "OAuth client".to_string(),
+            })
+    }
+}
+
+/// Random URL-safe token with 256 bits of entropy
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Whether a redirect URI may be registered
+///
+/// HTTPS is required, except for loopback addresses used during partner
+/// development. Fragments are not allowed.
+fn is_valid_redirect_uri(uri: &str) -> bool {
+    let loopback = ["http://localhost", "http://127.0.0.1"].iter().any(|prefix| {
+        uri.strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(':') || rest.starts_with('/'))
+    });
+    (uri.starts_with("https://") && uri.len() > "https://".len() || loopback) && !uri.contains('#')
+}
+
+/// Percent-encode a query parameter value
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,395 @@
+//! Unit tests for the OAuth service
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::Algorithm;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::oauth::{
+    OAuthAuthorizationCode, OAuthClient, OAuthConsent, OAuthGrantType, SCOPE_MATERIALS_WRITE,
+    SCOPE_ORDERS_READ, SCOPE_PROFILE_READ,
+};
+use crate::domain::entities::token::RefreshToken;
+use crate::errors::DomainError;
+use crate::repositories::{OAuthRepository, TokenRepository};
+use crate::services::oauth::{
+    AuthorizationRequest, NewOAuthClient, OAuthConfig, OAuthError, OAuthService, RegisteredClient,
+};
+use crate::services::token::{TokenService, TokenServiceConfig};
+
+const REDIRECT_URI: &str = "https://supplier.example.com/callback";
+
+#[derive(Default)]
+struct MockOAuthRepository {
+    clients: Mutex<Vec<OAuthClient>>,
+    consents: Mutex<Vec<OAuthConsent>>,
+    codes: Mutex<Vec<OAuthAuthorizationCode>>,
+}
+
+#[async_trait]
+impl OAuthRepository for MockOAuthRepository {
+    async fn save_client(&self, client: OAuthClient) -> Result<OAuthClient, DomainError> {
+        self.clients.lock().unwrap().push(client.clone());
+        Ok(client)
+    }
+
+    async fn update_client(&self, client: &OAuthClient) -> Result<(), DomainError> {
+        let mut clients = self.clients.lock().unwrap();
+        if let Some(existing) = clients.iter_mut().find(|c| c.client_id == client.client_id) {
+            *existing = client.clone();
+        }
+        Ok(())
+    }
+
+    async fn find_client(&self, client_id: &str) -> Result<Option<OAuthClient>, DomainError> {
+        Ok(self.clients.lock().unwrap().iter().find(|c| c.client_id == client_id).cloned())
+    }
+
+    async fn list_clients(&self) -> Result<Vec<OAuthClient>, DomainError> {
+        Ok(self.clients.lock().unwrap().iter().rev().cloned().collect())
+    }
+
+    async fn save_consent(&self, consent: &OAuthConsent) -> Result<(), DomainError> {
+        let mut consents = self.consents.lock().unwrap();
+        consents.retain(|c| !(c.user_id == consent.user_id && c.client_id == consent.client_id));
+        consents.push(consent.clone());
+        Ok(())
+    }
+
+    async fn find_consent(&self, user_id: Uuid, client_id: &str) -> Result<Option<OAuthConsent>, DomainError> {
+        Ok(self
+            .consents
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|c| c.user_id == user_id && c.client_id == client_id)
+            .cloned())
+    }
+
+    async fn find_consents_by_user(&self, user_id: Uuid) -> Result<Vec<OAuthConsent>, DomainError> {
+        Ok(self.consents.lock().unwrap().iter().filter(|c| c.user_id == user_id).cloned().collect())
+    }
+
+    async fn delete_consent(&self, user_id: Uuid, client_id: &str) -> Result<bool, DomainError> {
+        let mut consents = self.consents.lock().unwrap();
+        let before = consents.len();
+        consents.retain(|c| !(c.user_id == user_id && c.client_id == client_id));
+        Ok(consents.len() < before)
+    }
+
+    async fn save_authorization_code(&self, code: &OAuthAuthorizationCode) -> Result<(), DomainError> {
+        self.codes.lock().unwrap().push(code.clone());
+        Ok(())
+    }
+
+    async fn consume_authorization_code(
+        &self,
+        code_hash: &str,
+        used_at: DateTime<Utc>,
+    ) -> Result<Option<OAuthAuthorizationCode>, DomainError> {
+        let mut codes = self.codes.lock().unwrap();
+        Ok(codes
+            .iter_mut()
+            .find(|c| c.code_hash == code_hash && c.used_at.is_none())
+            .map(|code| {
+                code.used_at = Some(used_at);
+                code.clone()
+            }))
+    }
+}
+
+/// Token repository without refresh tokens or blacklisted tokens
+struct NoopTokenRepository;
+
+#[async_trait]
+impl TokenRepository for NoopTokenRepository {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        Ok(token)
+    }
+
+    async fn find_refresh_token(&self, _token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, _id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_user_id(&self, _user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn revoke_token(&self, _token_hash: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn revoke_all_user_tokens(&self, _user_id: Uuid) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn count_user_tokens(&self, _user_id: Uuid) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn find_by_token_family(&self, _token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn revoke_token_family(&self, _token_family: &str) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+
+    async fn is_token_blacklisted(&self, _token_jti: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn blacklist_token(&self, _token_jti: &str, _expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        Ok(0)
+    }
+}
+
+struct Fixture {
+    service: OAuthService<MockOAuthRepository, NoopTokenRepository>,
+    repository: Arc<MockOAuthRepository>,
+    tokens: Arc<TokenService<NoopTokenRepository>>,
+}
+
+fn fixture() -> Fixture {
+    let repository = Arc::new(MockOAuthRepository::default());
+    // Use HS256 for tests to avoid needing key files
+    let mut token_config = TokenServiceConfig::default();
+    token_config.algorithm = Algorithm::HS256;
+    let tokens = Arc::new(TokenService::new(NoopTokenRepository, token_config).unwrap());
+    let service = OAuthService::new(repository.clone(), tokens.clone(), OAuthConfig::default());
+
+    Fixture { service, repository, tokens }
+}
+
+async fn register(f: &Fixture, grant_types: Vec<OAuthGrantType>) -> RegisteredClient {
+    f.service
+        .register_client(NewOAuthClient {
+            name: "Tile Supplies Pty Ltd".to_string(),
+            redirect_uris: vec![REDIRECT_URI.to_string()],
+            scopes: vec![SCOPE_PROFILE_READ.to_string(), SCOPE_ORDERS_READ.to_string()],
+            grant_types,
+        })
+        .await
+        .unwrap()
+}
+
+fn authorization_request(client_id: &str) -> AuthorizationRequest {
+    AuthorizationRequest {
+        client_id: client_id.to_string(),
+        redirect_uri: REDIRECT_URI.to_string(),
+        scope: SCOPE_PROFILE_READ.to_string(),
+        state: Some("xyz 1".to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_client_secret_is_stored_hashed() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::ClientCredentials]).await;
+
+    let stored = f.repository.find_client(&registered.client.client_id).await.unwrap().unwrap();
+    assert!(stored.client_id.starts_with("rec_"));
+    assert_ne!(stored.client_secret_hash, registered.client_secret);
+
+    let result = f
+        .service
+        .register_client(NewOAuthClient {
+            name: "Paint Co".to_string(),
+            redirect_uris: vec!["http://paint.example.com/callback".to_string()],
+            scopes: vec![SCOPE_PROFILE_READ.to_string()],
+            grant_types: vec![OAuthGrantType::AuthorizationCode],
+        })
+        .await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn test_client_credentials_token_is_scope_limited() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::ClientCredentials]).await;
+    let client_id = registered.client.client_id.clone();
+
+    let token = f
+        .service
+        .client_credentials(&client_id, &registered.client_secret, Some(SCOPE_ORDERS_READ))
+        .await
+        .unwrap();
+    assert_eq!(token.token_type, "Bearer");
+    assert_eq!(token.scope, SCOPE_ORDERS_READ);
+
+    let claims = f.tokens.verify_partner_token(&token.access_token).await.unwrap();
+    assert_eq!(claims.sub, client_id);
+    assert_eq!(claims.client_id.as_deref(), Some(client_id.as_str()));
+    assert!(claims.has_scope(SCOPE_ORDERS_READ));
+    assert!(!claims.has_scope(SCOPE_PROFILE_READ));
+
+    // Partner tokens are not accepted by the main API
+    assert!(f.tokens.verify_access_token(&token.access_token).await.is_err());
+
+    let all = f.service.client_credentials(&client_id, &registered.client_secret, None).await.unwrap();
+    assert_eq!(all.scope, format!("{} {}", SCOPE_PROFILE_READ, SCOPE_ORDERS_READ));
+}
+
+#[tokio::test]
+async fn test_client_credentials_rejects_bad_secret_scope_or_grant() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::ClientCredentials]).await;
+    let client_id = registered.client.client_id.clone();
+
+    let result = f.service.client_credentials(&client_id, "wrong", None).await;
+    assert!(matches!(result, Err(OAuthError::InvalidClient)));
+
+    let result = f
+        .service
+        .client_credentials(&client_id, &registered.client_secret, Some(SCOPE_MATERIALS_WRITE))
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidScope)));
+
+    let code_only = register(&f, vec![OAuthGrantType::AuthorizationCode]).await;
+    let result = f
+        .service
+        .client_credentials(&code_only.client.client_id, &code_only.client_secret, None)
+        .await;
+    assert!(matches!(result, Err(OAuthError::UnauthorizedClient)));
+
+    f.service.set_client_active(&client_id, false).await.unwrap();
+    let result = f.service.client_credentials(&client_id, &registered.client_secret, None).await;
+    assert!(matches!(result, Err(OAuthError::InvalidClient)));
+}
+
+#[tokio::test]
+async fn test_authorization_code_flow_acts_for_user() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::AuthorizationCode]).await;
+    let client_id = registered.client.client_id.clone();
+    let user_id = Uuid::new_v4();
+    let request = authorization_request(&client_id);
+
+    let prompt = f.service.prepare_authorization(user_id, &request).await.unwrap();
+    assert_eq!(prompt.client_name, "Tile Supplies Pty Ltd");
+    assert!(!prompt.previously_granted);
+
+    let grant = f.service.approve_authorization(user_id, &request).await.unwrap();
+    assert!(grant.redirect_url().starts_with(&format!("{}?code=", REDIRECT_URI)));
+    assert!(grant.redirect_url().ends_with("&state=xyz%201"));
+    assert!(f.service.prepare_authorization(user_id, &request).await.unwrap().previously_granted);
+
+    let token = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, None)
+        .await
+        .unwrap();
+    let claims = f.tokens.verify_partner_token(&token.access_token).await.unwrap();
+    assert_eq!(claims.user_id().unwrap(), user_id);
+    assert!(claims.has_scope(SCOPE_PROFILE_READ));
+
+    // Codes are single use
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, None)
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+}
+
+#[tokio::test]
+async fn test_authorization_request_is_checked_against_registration() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::AuthorizationCode]).await;
+    let client_id = registered.client.client_id.clone();
+    let user_id = Uuid::new_v4();
+
+    for request in [
+        AuthorizationRequest { redirect_uri: "https://evil.example.com/callback".to_string(), ..authorization_request(&client_id) },
+        AuthorizationRequest { scope: SCOPE_MATERIALS_WRITE.to_string(), ..authorization_request(&client_id) },
+        AuthorizationRequest { scope: String::new(), ..authorization_request(&client_id) },
+        AuthorizationRequest {
+            code_challenge: Some("challenge".to_string()),
+            code_challenge_method: Some("plain".to_string()),
+            ..authorization_request(&client_id)
+        },
+        authorization_request("rec_unknown"),
+    ] {
+        let result = f.service.prepare_authorization(user_id, &request).await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+    }
+}
+
+#[tokio::test]
+async fn test_code_exchange_checks_pkce_and_redirect_uri() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::AuthorizationCode]).await;
+    let client_id = registered.client.client_id.clone();
+    let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    let request = AuthorizationRequest {
+        code_challenge: Some(URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))),
+        code_challenge_method: Some("S256".to_string()),
+        ..authorization_request(&client_id)
+    };
+
+    let grant = f.service.approve_authorization(Uuid::new_v4(), &request).await.unwrap();
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, Some("wrong"))
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+
+    let grant = f.service.approve_authorization(Uuid::new_v4(), &request).await.unwrap();
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, "https://other.example.com", Some(verifier))
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+
+    let grant = f.service.approve_authorization(Uuid::new_v4(), &request).await.unwrap();
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, Some(verifier))
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_revoked_or_expired_code_cannot_be_exchanged() {
+    let f = fixture();
+    let registered = register(&f, vec![OAuthGrantType::AuthorizationCode]).await;
+    let client_id = registered.client.client_id.clone();
+    let user_id = Uuid::new_v4();
+
+    let grant = f.service.approve_authorization(user_id, &authorization_request(&client_id)).await.unwrap();
+    f.service.revoke_consent(user_id, &client_id).await.unwrap();
+    assert!(f.service.user_consents(user_id).await.unwrap().is_empty());
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, None)
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+
+    let grant = f.service.approve_authorization(user_id, &authorization_request(&client_id)).await.unwrap();
+    for code in f.repository.codes.lock().unwrap().iter_mut() {
+        code.expires_at = Utc::now() - Duration::seconds(1);
+    }
+    let result = f
+        .service
+        .exchange_code(&client_id, &registered.client_secret, &grant.code, REDIRECT_URI, None)
+        .await;
+    assert!(matches!(result, Err(OAuthError::InvalidGrant(_))));
+
+    let result = f.service.revoke_consent(Uuid::new_v4(), &client_id).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
@@ -0,0 +1,25 @@
+//! Configuration for the organization billing service
+
+/// Configuration for the organization billing service
+#[derive(Debug, Clone)]
+pub struct OrganizationBillingConfig {
+    /// Most members an organization can have
+    pub max_members: usize,
+    /// Most payment methods an organization can save
+    pub max_payment_methods: usize,
+    /// Interval between runs of the invoicing job, in seconds
+    pub invoice_interval_seconds: u64,
+    /// Whether to enable the background invoicing job
+    pub invoicing_enabled: bool,
+}
+
+impl Default for OrganizationBillingConfig {
+    fn default() -> Self {
+        Self {
+            max_members: 500,
+            max_payment_methods: 5,
+            invoice_interval_seconds: 3_600, // Hourly
+            invoicing_enabled: true,
+        }
+    }
+}
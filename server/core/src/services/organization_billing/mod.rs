@@ -0,0 +1,16 @@
+//! Organization billing service module for company accounts
+//!
+//! This module handles:
+//! - Creating organizations and managing their members and roles
+//! - Enforcing members' monthly spend limits when they accept quotes
+//! - Saving the payment methods organizations are billed to
+//! - Issuing consolidated monthly invoices of members' completed orders
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::OrganizationBillingConfig;
+pub use service::{MemberSpend, OrganizationBillingService, ORGANIZATION_BILLING_HOOK_NAME};
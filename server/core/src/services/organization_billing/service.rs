@@ -0,0 +1,621 @@
+//! Organization billing service
+//!
+//! Quotes accepted by members of an organization are charged to it instead
+//! of the member. The order flow calls [`OrganizationBillingService::accept_quote`]
+//! before fixing the order's fee, which refuses quotes taking the member
+//! over their monthly spend limit. Completed orders reach this service as
+//! `OrderCompleted` lifecycle events, and a background job collects the
+//! charges completed in a month on one consolidated invoice per organization
+//! once the month has ended.
+//!
+//! Organizations belong to a tenant. The invoicing job invoices those of
+//! each tenant given to [`OrganizationBillingService::with_tenants`] in turn.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Months, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::organization::{
+    month_bounds, month_start, ConsolidatedInvoice, Organization, OrganizationCharge, OrganizationMember,
+    OrganizationPaymentMethod, OrganizationRole,
+};
+use crate::domain::entities::payment::normalize_currency;
+use crate::domain::entities::payment_method::looks_like_card_number;
+use crate::errors::{AuthError, DomainError, DomainResult};
+use crate::repositories::OrganizationRepository;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookTrait};
+use crate::services::payment_method::PaymentMethodProviderTrait;
+use crate::services::tenant::{self, TenantId};
+
+use super::config::OrganizationBillingConfig;
+
+/// Name of the lifecycle hook recording completed orders
+pub const ORGANIZATION_BILLING_HOOK_NAME: &str = "organization_billing";
+
+/// A member with what they spent in the current month
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberSpend {
+    /// The member
+    pub member: OrganizationMember,
+    /// Quotes accepted this calendar month in minor units
+    pub spent_this_month: i64,
+}
+
+/// Service managing organizations and their consolidated billing
+pub struct OrganizationBillingService<O, V>
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    organizations: Arc<O>,
+    provider: Arc<V>,
+    config: OrganizationBillingConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<O, V> OrganizationBillingService<O, V>
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    /// Create a new organization billing service
+    pub fn new(organizations: Arc<O>, provider: Arc<V>, config: OrganizationBillingConfig) -> Self {
+        Self {
+            organizations,
+            provider,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Invoice the organizations of these tenants in the background task,
+    /// instead of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Create an organization with the creating user as its first admin
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Invalid name, billing email or currency
+    /// * `Err(DomainError::BusinessRule)` - The user already belongs to an organization
+    pub async fn create_organization(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        billing_email: &str,
+        currency: &str,
+    ) -> DomainResult<Organization> {
+        if self.organizations.find_membership(user_id).await?.is_some() {
+            return Err(DomainError::BusinessRule {
+                message: "User already belongs to an organization".to_string(),
+            });
+        }
+
+        let organization = Organization::new(name, billing_email, currency)
+            .map_err(|message| DomainError::Validation { message })?;
+        let admin = OrganizationMember {
+            organization_id: organization.id,
+            user_id,
+            role: OrganizationRole::Admin,
+            monthly_spend_limit: None,
+            added_at: organization.created_at,
+        };
+        self.organizations.create(&organization, &admin).await?;
+
+        info!(organization_id = %organization.id, user_id = %user_id, "Organization created");
+        Ok(organization)
+    }
+
+    /// The organization a user belongs to, if any
+    pub async fn membership(&self, user_id: Uuid) -> DomainResult<Option<OrganizationMember>> {
+        self.organizations.find_membership(user_id).await
+    }
+
+    /// List the members of an organization with their spend this month
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - The user is not a member of the organization
+    /// * `Err(DomainError::Auth)` - The user is not an admin of the organization
+    pub async fn members(&self, admin_id: Uuid, organization_id: Uuid) -> DomainResult<Vec<MemberSpend>> {
+        self.require_admin(admin_id, organization_id).await?;
+
+        let (from, to) = month_bounds(Utc::now());
+        let spend = self.organizations.member_spend(organization_id, from, to).await?;
+        let members = self.organizations.find_members(organization_id).await?;
+
+        Ok(members
+            .into_iter()
+            .map(|member| MemberSpend {
+                spent_this_month: spend.get(&member.user_id).copied().unwrap_or(0),
+                member,
+            })
+            .collect())
+    }
+
+    /// Add a member, or change the role and spend limit of an existing one
+    ///
+    /// # Arguments
+    /// * `monthly_spend_limit` - Most the member may spend per month in minor units, `None` for no limit
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - The spend limit is negative
+    /// * `Err(DomainError::BusinessRule)` - The user belongs to another organization,
+    ///   the organization is full, or the last admin would be demoted
+    pub async fn save_member(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+        user_id: Uuid,
+        role: OrganizationRole,
+        monthly_spend_limit: Option<i64>,
+    ) -> DomainResult<OrganizationMember> {
+        self.require_admin(admin_id, organization_id).await?;
+
+        if monthly_spend_limit.is_some_and(|limit| limit < 0) {
+            return Err(DomainError::Validation {
+                message: "Monthly spend limit must not be negative".to_string(),
+            });
+        }
+
+        let members = self.organizations.find_members(organization_id).await?;
+        let existing = members.iter().find(|member| member.user_id == user_id);
+        match existing {
+            Some(member) => {
+                if member.is_admin() && role != OrganizationRole::Admin && Self::admin_count(&members) == 1 {
+                    return Err(DomainError::BusinessRule {
+                        message: "An organization must keep at least one admin".to_string(),
+                    });
+                }
+            }
+            None => {
+                if self.organizations.find_membership(user_id).await?.is_some() {
+                    return Err(DomainError::BusinessRule {
+                        message: "User already belongs to an organization".to_string(),
+                    });
+                }
+                if members.len() >= self.config.max_members {
+                    return Err(DomainError::BusinessRule {
+                        message: format!("An organization can have at most {} members", self.config.max_members),
+                    });
+                }
+            }
+        }
+
+        let member = OrganizationMember {
+            organization_id,
+            user_id,
+            role,
+            monthly_spend_limit,
+            added_at: existing.map_or_else(Utc::now, |member| member.added_at),
+        };
+        self.organizations.save_member(&member).await?;
+
+        info!(
+            organization_id = %organization_id,
+            user_id = %user_id,
+            role = role.as_str(),
+            monthly_spend_limit,
+            "Organization member saved"
+        );
+        Ok(member)
+    }
+
+    /// Remove a member from an organization
+    ///
+    /// Charges the member already incurred stay with the organization.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - The user is not a member
+    /// * `Err(DomainError::BusinessRule)` - The member is the last admin
+    pub async fn remove_member(&self, admin_id: Uuid, organization_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        self.require_admin(admin_id, organization_id).await?;
+
+        let members = self.organizations.find_members(organization_id).await?;
+        let member = members
+            .iter()
+            .find(|member| member.user_id == user_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Organization member".to_string(),
+            })?;
+        if member.is_admin() && Self::admin_count(&members) == 1 {
+            return Err(DomainError::BusinessRule {
+                message: "An organization must keep at least one admin".to_string(),
+            });
+        }
+
+        self.organizations.remove_member(organization_id, user_id).await?;
+        info!(organization_id = %organization_id, user_id = %user_id, "Organization member removed");
+        Ok(())
+    }
+
+    /// List an organization's payment methods, newest first
+    pub async fn payment_methods(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+    ) -> DomainResult<Vec<OrganizationPaymentMethod>> {
+        self.require_admin(admin_id, organization_id).await?;
+        self.organizations.find_payment_methods(organization_id).await
+    }
+
+    /// Save a card the device tokenized with the provider
+    ///
+    /// # Arguments
+    /// * `token` - Provider's identifier for the payment method
+    /// * `make_default` - Whether invoices are charged to it; the first method always is
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Missing token, a raw card number, or an expired card
+    /// * `Err(DomainError::BusinessRule)` - The organization saved the maximum number of methods
+    /// * `Err(DomainError::Internal)` - The provider could not attach the method
+    pub async fn add_payment_method(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+        token: &str,
+        make_default: bool,
+    ) -> DomainResult<OrganizationPaymentMethod> {
+        self.require_admin(admin_id, organization_id).await?;
+
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Payment method token is required".to_string(),
+            });
+        }
+        if looks_like_card_number(token) {
+            return Err(DomainError::Validation {
+                message: "Card numbers must be tokenized with the payment provider".to_string(),
+            });
+        }
+
+        let existing = self.organizations.find_payment_methods(organization_id).await?;
+        if existing.len() >= self.config.max_payment_methods {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "At most {} payment methods can be saved",
+                    self.config.max_payment_methods
+                ),
+            });
+        }
+
+        let provider = self.provider.provider_name();
+        let card = self.provider.attach(organization_id, token).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to attach {} payment method: {}", provider, e),
+        })?;
+        let mut method = OrganizationPaymentMethod {
+            id: Uuid::new_v4(),
+            organization_id,
+            provider: provider.to_string(),
+            provider_reference: card.reference,
+            brand: card.brand.to_ascii_lowercase(),
+            last4: card.last4,
+            exp_month: card.exp_month,
+            exp_year: card.exp_year,
+            is_default: false,
+            added_by: admin_id,
+            created_at: Utc::now(),
+        };
+        if method.is_expired(method.created_at) {
+            return Err(DomainError::Validation {
+                message: "The card has expired".to_string(),
+            });
+        }
+
+        self.organizations.save_payment_method(&method).await?;
+        if make_default || existing.is_empty() {
+            self.organizations.set_default_payment_method(organization_id, method.id).await?;
+            method.is_default = true;
+        }
+
+        info!(
+            organization_id = %organization_id,
+            payment_method_id = %method.id,
+            "Organization payment method saved"
+        );
+        Ok(method)
+    }
+
+    /// Remove a payment method
+    ///
+    /// When the default method is removed, the newest remaining method
+    /// becomes the default.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such method in the organization
+    /// * `Err(DomainError::Internal)` - The provider could not detach the method
+    pub async fn remove_payment_method(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+        method_id: Uuid,
+    ) -> DomainResult<()> {
+        self.require_admin(admin_id, organization_id).await?;
+
+        let methods = self.organizations.find_payment_methods(organization_id).await?;
+        let method = methods
+            .iter()
+            .find(|method| method.id == method_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payment method".to_string(),
+            })?;
+
+        self.provider
+            .detach(&method.provider_reference)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to detach {} payment method: {}", method.provider, e),
+            })?;
+        self.organizations.delete_payment_method(method.id).await?;
+
+        if method.is_default {
+            if let Some(next) = methods.iter().find(|other| other.id != method.id) {
+                self.organizations.set_default_payment_method(organization_id, next.id).await?;
+            }
+        }
+
+        info!(organization_id = %organization_id, payment_method_id = %method_id, "Organization payment method removed");
+        Ok(())
+    }
+
+    /// Charge an accepted quote to the accepting user's organization
+    ///
+    /// Call before fixing the order's fee. Accepting again returns the
+    /// charge recorded the first time.
+    ///
+    /// # Returns
+    /// * `Ok(Some(OrganizationCharge))` - The quote is charged to the organization
+    /// * `Ok(None)` - The user belongs to no organization and pays personally
+    /// * `Err(DomainError::BusinessRule)` - The quote is not positive, is in another
+    ///   currency than the organization's, or exceeds the member's monthly spend limit
+    pub async fn accept_quote(
+        &self,
+        user_id: Uuid,
+        order_id: Uuid,
+        quote_amount: i64,
+        currency: &str,
+        accepted_at: DateTime<Utc>,
+    ) -> DomainResult<Option<OrganizationCharge>> {
+        if let Some(charge) = self.organizations.find_charge_by_order(order_id).await? {
+            return Ok(Some(charge));
+        }
+        let Some(member) = self.organizations.find_membership(user_id).await? else {
+            return Ok(None);
+        };
+        let organization = self.find_organization(member.organization_id).await?;
+
+        if quote_amount <= 0 {
+            return Err(DomainError::BusinessRule {
+                message: "Quote amount must be positive".to_string(),
+            });
+        }
+        let currency = normalize_currency(currency).map_err(|message| DomainError::Validation { message })?;
+        if currency != organization.currency {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Quote currency {} does not match organization currency {}",
+                    currency, organization.currency
+                ),
+            });
+        }
+
+        let charge = OrganizationCharge {
+            id: Uuid::new_v4(),
+            organization_id: organization.id,
+            user_id,
+            order_id,
+            amount: quote_amount,
+            currency,
+            accepted_at,
+            completed_at: None,
+            invoice_id: None,
+        };
+        let (from, to) = month_bounds(accepted_at);
+        let saved = self
+            .organizations
+            .save_charge_within_limit(&charge, member.monthly_spend_limit, from, to)
+            .await?;
+        if !saved {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Accepting this quote would exceed your monthly spend limit of {} {}",
+                    member.monthly_spend_limit.unwrap_or_default(),
+                    organization.currency
+                ),
+            });
+        }
+
+        info!(
+            organization_id = %organization.id,
+            user_id = %user_id,
+            order_id = %order_id,
+            amount = quote_amount,
+            "Quote charged to organization"
+        );
+        Ok(Some(charge))
+    }
+
+    /// Issue the invoices of the month before `now`
+    ///
+    /// Each organization with completed, uninvoiced charges gets one invoice
+    /// per month, collecting every charge completed before the month ended.
+    /// Charges whose completion was recorded after their month's invoice was
+    /// issued go on the next month's invoice.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of invoices issued
+    pub async fn issue_invoices(&self, now: DateTime<Utc>) -> DomainResult<usize> {
+        let (current_month, _) = month_bounds(now);
+        let period_start = month_start(now.date_naive()) - Months::new(1);
+        let mut issued = 0;
+
+        for organization_id in self.organizations.find_organizations_to_invoice(current_month).await? {
+            if self
+                .organizations
+                .find_invoice_for_period(organization_id, period_start)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+
+            let organization = self.find_organization(organization_id).await?;
+            let charges = self
+                .organizations
+                .find_uninvoiced_charges(organization_id, current_month)
+                .await?;
+            if charges.is_empty() {
+                continue;
+            }
+
+            let payment_method_id = self
+                .organizations
+                .find_payment_methods(organization_id)
+                .await?
+                .into_iter()
+                .find(|method| method.is_default && !method.is_expired(now))
+                .map(|method| method.id);
+            if payment_method_id.is_none() {
+                warn!(organization_id = %organization_id, "Issuing invoice without a valid payment method");
+            }
+
+            let invoice = ConsolidatedInvoice::new(&organization, period_start, &charges, payment_method_id);
+            self.organizations.save_invoice(&invoice).await?;
+            info!(
+                organization_id = %organization_id,
+                invoice_id = %invoice.id,
+                period_start = %period_start,
+                lines = invoice.lines.len(),
+                total_amount = invoice.total_amount,
+                "Consolidated invoice issued"
+            );
+            issued += 1;
+        }
+
+        Ok(issued)
+    }
+
+    /// List an organization's invoices, most recent period first
+    pub async fn invoices(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+        limit: usize,
+    ) -> DomainResult<Vec<ConsolidatedInvoice>> {
+        self.require_admin(admin_id, organization_id).await?;
+        self.organizations.find_invoices(organization_id, limit).await
+    }
+
+    /// Get one of an organization's invoices
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such invoice in the organization
+    pub async fn invoice(
+        &self,
+        admin_id: Uuid,
+        organization_id: Uuid,
+        invoice_id: Uuid,
+    ) -> DomainResult<ConsolidatedInvoice> {
+        self.require_admin(admin_id, organization_id).await?;
+        self.organizations
+            .find_invoice(invoice_id)
+            .await?
+            .filter(|invoice| invoice.organization_id == organization_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Invoice".to_string(),
+            })
+    }
+
+    /// Start the invoicing job as a background task
+    ///
+    /// This spawns a tokio task that issues due invoices at the configured interval
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.invoicing_enabled {
+            warn!("Organization invoicing is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.invoice_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Organization invoicing started - checking every {} seconds",
+                self.config.invoice_interval_seconds
+            );
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let now = Utc::now();
+                for tenant in &self.tenants {
+                    if let Err(e) = tenant::scope(tenant.clone(), self.issue_invoices(now)).await {
+                        error!("Organization invoicing for tenant {} failed to run: {}", tenant, e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Ensure a user is an admin of an organization
+    ///
+    /// Users outside the organization get `NotFound`, so they cannot tell
+    /// which organizations exist.
+    async fn require_admin(&self, user_id: Uuid, organization_id: Uuid) -> DomainResult<OrganizationMember> {
+        let member = self
+            .organizations
+            .find_membership(user_id)
+            .await?
+            .filter(|member| member.organization_id == organization_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Organization".to_string(),
+            })?;
+        if !member.is_admin() {
+            return Err(DomainError::Auth(AuthError::InsufficientPermissions));
+        }
+        Ok(member)
+    }
+
+    async fn find_organization(&self, organization_id: Uuid) -> DomainResult<Organization> {
+        self.organizations
+            .find_by_id(organization_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Organization".to_string(),
+            })
+    }
+
+    fn admin_count(members: &[OrganizationMember]) -> usize {
+        members.iter().filter(|member| member.is_admin()).count()
+    }
+}
+
+#[async_trait]
+impl<O, V> LifecycleHookTrait for OrganizationBillingService<O, V>
+where
+    O: OrganizationRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    fn name(&self) -> &str {
+        ORGANIZATION_BILLING_HOOK_NAME
+    }
+
+    fn handles(&self, event: &LifecycleEvent) -> bool {
+        matches!(event, LifecycleEvent::OrderCompleted { .. })
+    }
+
+    async fn handle(&self, event: &LifecycleEvent) -> Result<(), String> {
+        let LifecycleEvent::OrderCompleted { order_id, completed_at, .. } = event else {
+            return Ok(());
+        };
+
+        self.organizations
+            .complete_charge(*order_id, *completed_at)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
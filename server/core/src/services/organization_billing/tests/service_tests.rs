@@ -0,0 +1,439 @@
+//! Unit tests for the organization billing service
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::organization::{
+    ConsolidatedInvoice, Organization, OrganizationCharge, OrganizationMember, OrganizationPaymentMethod,
+    OrganizationRole,
+};
+use crate::errors::{AuthError, DomainError};
+use crate::repositories::OrganizationRepository;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookTrait};
+use crate::services::organization_billing::{OrganizationBillingConfig, OrganizationBillingService};
+use crate::services::payment_method::{PaymentMethodProviderTrait, ProviderPaymentMethod};
+
+#[derive(Default)]
+struct MockOrganizations {
+    organizations: Mutex<Vec<Organization>>,
+    members: Mutex<Vec<OrganizationMember>>,
+    methods: Mutex<Vec<OrganizationPaymentMethod>>,
+    charges: Mutex<Vec<OrganizationCharge>>,
+    invoices: Mutex<Vec<ConsolidatedInvoice>>,
+}
+
+#[async_trait]
+impl OrganizationRepository for MockOrganizations {
+    async fn create(&self, organization: &Organization, admin: &OrganizationMember) -> Result<(), DomainError> {
+        self.organizations.lock().unwrap().push(organization.clone());
+        self.members.lock().unwrap().push(admin.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, DomainError> {
+        Ok(self.organizations.lock().unwrap().iter().find(|o| o.id == id).cloned())
+    }
+
+    async fn find_membership(&self, user_id: Uuid) -> Result<Option<OrganizationMember>, DomainError> {
+        Ok(self.members.lock().unwrap().iter().find(|m| m.user_id == user_id).cloned())
+    }
+
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, DomainError> {
+        Ok(self
+            .members
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.organization_id == organization_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn save_member(&self, member: &OrganizationMember) -> Result<(), DomainError> {
+        let mut members = self.members.lock().unwrap();
+        members.retain(|m| m.user_id != member.user_id);
+        members.push(member.clone());
+        Ok(())
+    }
+
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let mut members = self.members.lock().unwrap();
+        let before = members.len();
+        members.retain(|m| !(m.organization_id == organization_id && m.user_id == user_id));
+        Ok(members.len() < before)
+    }
+
+    async fn save_payment_method(&self, method: &OrganizationPaymentMethod) -> Result<(), DomainError> {
+        self.methods.lock().unwrap().push(method.clone());
+        Ok(())
+    }
+
+    async fn find_payment_methods(&self, organization_id: Uuid) -> Result<Vec<OrganizationPaymentMethod>, DomainError> {
+        Ok(self
+            .methods
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|m| m.organization_id == organization_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_default_payment_method(&self, organization_id: Uuid, id: Uuid) -> Result<(), DomainError> {
+        for method in self.methods.lock().unwrap().iter_mut().filter(|m| m.organization_id == organization_id) {
+            method.is_default = method.id == id;
+        }
+        Ok(())
+    }
+
+    async fn delete_payment_method(&self, id: Uuid) -> Result<bool, DomainError> {
+        let mut methods = self.methods.lock().unwrap();
+        let before = methods.len();
+        methods.retain(|m| m.id != id);
+        Ok(methods.len() < before)
+    }
+
+    async fn find_charge_by_order(&self, order_id: Uuid) -> Result<Option<OrganizationCharge>, DomainError> {
+        Ok(self.charges.lock().unwrap().iter().find(|c| c.order_id == order_id).cloned())
+    }
+
+    async fn save_charge_within_limit(
+        &self,
+        charge: &OrganizationCharge,
+        limit: Option<i64>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let mut charges = self.charges.lock().unwrap();
+        let spent: i64 = charges
+            .iter()
+            .filter(|c| c.user_id == charge.user_id && c.accepted_at >= from && c.accepted_at < to)
+            .map(|c| c.amount)
+            .sum();
+        if limit.is_some_and(|limit| spent + charge.amount > limit) {
+            return Ok(false);
+        }
+        charges.push(charge.clone());
+        Ok(true)
+    }
+
+    async fn complete_charge(&self, order_id: Uuid, completed_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let mut charges = self.charges.lock().unwrap();
+        match charges.iter_mut().find(|c| c.order_id == order_id && c.completed_at.is_none()) {
+            Some(charge) => {
+                charge.completed_at = Some(completed_at);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn member_spend(
+        &self,
+        organization_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<Uuid, i64>, DomainError> {
+        let mut spend = HashMap::new();
+        for charge in self.charges.lock().unwrap().iter().filter(|c| {
+            c.organization_id == organization_id && c.accepted_at >= from && c.accepted_at < to
+        }) {
+            *spend.entry(charge.user_id).or_insert(0) += charge.amount;
+        }
+        Ok(spend)
+    }
+
+    async fn find_uninvoiced_charges(
+        &self,
+        organization_id: Uuid,
+        completed_before: DateTime<Utc>,
+    ) -> Result<Vec<OrganizationCharge>, DomainError> {
+        Ok(self
+            .charges
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| {
+                c.organization_id == organization_id
+                    && c.invoice_id.is_none()
+                    && c.completed_at.is_some_and(|at| at < completed_before)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_organizations_to_invoice(&self, completed_before: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError> {
+        let mut ids: Vec<Uuid> = self
+            .charges
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c.invoice_id.is_none() && c.completed_at.is_some_and(|at| at < completed_before))
+            .map(|c| c.organization_id)
+            .collect();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    async fn save_invoice(&self, invoice: &ConsolidatedInvoice) -> Result<(), DomainError> {
+        for charge in self.charges.lock().unwrap().iter_mut() {
+            if invoice.lines.iter().any(|line| line.order_id == charge.order_id) {
+                charge.invoice_id = Some(invoice.id);
+            }
+        }
+        self.invoices.lock().unwrap().push(invoice.clone());
+        Ok(())
+    }
+
+    async fn find_invoice(&self, id: Uuid) -> Result<Option<ConsolidatedInvoice>, DomainError> {
+        Ok(self.invoices.lock().unwrap().iter().find(|i| i.id == id).cloned())
+    }
+
+    async fn find_invoice_for_period(
+        &self,
+        organization_id: Uuid,
+        period_start: NaiveDate,
+    ) -> Result<Option<ConsolidatedInvoice>, DomainError> {
+        Ok(self
+            .invoices
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|i| i.organization_id == organization_id && i.period_start == period_start)
+            .cloned())
+    }
+
+    async fn find_invoices(&self, organization_id: Uuid, limit: usize) -> Result<Vec<ConsolidatedInvoice>, DomainError> {
+        Ok(self
+            .invoices
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|i| i.organization_id == organization_id)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Provider that attaches any `pm_` token as a Visa card
+#[derive(Default)]
+struct MockProvider {
+    detached: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl PaymentMethodProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    async fn attach(&self, _customer_id: Uuid, token: &str) -> Result<ProviderPaymentMethod, String> {
+        if !token.starts_with("pm_") {
+            return Err("No such payment method".to_string());
+        }
+        Ok(ProviderPaymentMethod {
+            reference: token.to_string(),
+            brand: "Visa".to_string(),
+            last4: "4242".to_string(),
+            exp_month: 12,
+            exp_year: 2099,
+        })
+    }
+
+    async fn detach(&self, reference: &str) -> Result<(), String> {
+        self.detached.lock().unwrap().push(reference.to_string());
+        Ok(())
+    }
+}
+
+struct Fixture {
+    service: OrganizationBillingService<MockOrganizations, MockProvider>,
+    provider: Arc<MockProvider>,
+    organization: Organization,
+    admin_id: Uuid,
+}
+
+async fn fixture() -> Fixture {
+    let provider = Arc::new(MockProvider::default());
+    let service = OrganizationBillingService::new(
+        Arc::new(MockOrganizations::default()),
+        provider.clone(),
+        OrganizationBillingConfig {
+            max_members: 3,
+            ..Default::default()
+        },
+    );
+    let admin_id = Uuid::new_v4();
+    let organization = service
+        .create_organization(admin_id, "Acme Builders", "Billing@Acme.example", "aud")
+        .await
+        .unwrap();
+
+    Fixture { service, provider, organization, admin_id }
+}
+
+fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+}
+
+#[tokio::test]
+async fn test_create_organization_makes_creator_admin() {
+    let f = fixture().await;
+
+    assert_eq!(f.organization.currency, "AUD");
+    assert_eq!(f.organization.billing_email, "billing@acme.example");
+    let membership = f.service.membership(f.admin_id).await.unwrap().unwrap();
+    assert_eq!(membership.role, OrganizationRole::Admin);
+
+    let result = f.service.create_organization(f.admin_id, "Other", "a@b.example", "AUD").await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_only_admins_manage_the_organization() {
+    let f = fixture().await;
+    let member_id = Uuid::new_v4();
+    f.service
+        .save_member(f.admin_id, f.organization.id, member_id, OrganizationRole::Member, Some(50_000))
+        .await
+        .unwrap();
+
+    let result = f.service.members(member_id, f.organization.id).await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::InsufficientPermissions))));
+
+    let outsider = f.service.members(Uuid::new_v4(), f.organization.id).await;
+    assert!(matches!(outsider, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_last_admin_cannot_be_demoted_or_removed() {
+    let f = fixture().await;
+
+    let demote = f
+        .service
+        .save_member(f.admin_id, f.organization.id, f.admin_id, OrganizationRole::Member, None)
+        .await;
+    assert!(matches!(demote, Err(DomainError::BusinessRule { .. })));
+
+    let remove = f.service.remove_member(f.admin_id, f.organization.id, f.admin_id).await;
+    assert!(matches!(remove, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_spend_limit_is_enforced_per_calendar_month() {
+    let f = fixture().await;
+    let member_id = Uuid::new_v4();
+    f.service
+        .save_member(f.admin_id, f.organization.id, member_id, OrganizationRole::Member, Some(50_000))
+        .await
+        .unwrap();
+
+    let charge = f
+        .service
+        .accept_quote(member_id, Uuid::new_v4(), 30_000, "AUD", at(2026, 9, 10))
+        .await
+        .unwrap();
+    assert!(charge.is_some());
+
+    let over = f
+        .service
+        .accept_quote(member_id, Uuid::new_v4(), 25_000, "AUD", at(2026, 9, 20))
+        .await;
+    assert!(matches!(over, Err(DomainError::BusinessRule { .. })));
+
+    let next_month = f
+        .service
+        .accept_quote(member_id, Uuid::new_v4(), 25_000, "AUD", at(2026, 10, 1))
+        .await
+        .unwrap();
+    assert!(next_month.is_some());
+}
+
+#[tokio::test]
+async fn test_accept_quote_is_idempotent_and_skips_non_members() {
+    let f = fixture().await;
+    let order_id = Uuid::new_v4();
+
+    let first = f.service.accept_quote(f.admin_id, order_id, 10_000, "AUD", at(2026, 9, 1)).await.unwrap();
+    let again = f.service.accept_quote(f.admin_id, order_id, 10_000, "AUD", at(2026, 9, 2)).await.unwrap();
+    assert_eq!(first, again);
+
+    let wrong_currency = f.service.accept_quote(f.admin_id, Uuid::new_v4(), 10_000, "NZD", at(2026, 9, 1)).await;
+    assert!(matches!(wrong_currency, Err(DomainError::BusinessRule { .. })));
+
+    let personal = f.service.accept_quote(Uuid::new_v4(), Uuid::new_v4(), 10_000, "AUD", at(2026, 9, 1)).await;
+    assert_eq!(personal.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_completed_orders_are_invoiced_once_per_month() {
+    let f = fixture().await;
+    let member_id = Uuid::new_v4();
+    f.service
+        .save_member(f.admin_id, f.organization.id, member_id, OrganizationRole::Member, None)
+        .await
+        .unwrap();
+    let method = f
+        .service
+        .add_payment_method(f.admin_id, f.organization.id, "pm_company", false)
+        .await
+        .unwrap();
+    assert!(method.is_default);
+
+    let completed = Uuid::new_v4();
+    let in_progress = Uuid::new_v4();
+    f.service.accept_quote(f.admin_id, completed, 20_000, "AUD", at(2026, 9, 3)).await.unwrap();
+    f.service.accept_quote(member_id, in_progress, 15_000, "AUD", at(2026, 9, 4)).await.unwrap();
+    f.service
+        .handle(&LifecycleEvent::OrderCompleted {
+            order_id: completed,
+            customer_id: f.admin_id,
+            worker_id: Uuid::new_v4(),
+            completed_at: at(2026, 9, 28),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(f.service.issue_invoices(at(2026, 10, 1)).await.unwrap(), 1);
+    assert_eq!(f.service.issue_invoices(at(2026, 10, 2)).await.unwrap(), 0);
+
+    let invoices = f.service.invoices(f.admin_id, f.organization.id, 10).await.unwrap();
+    assert_eq!(invoices.len(), 1);
+    let invoice = &invoices[0];
+    assert_eq!(invoice.period_start, NaiveDate::from_ymd_opt(2026, 9, 1).unwrap());
+    assert_eq!(invoice.total_amount, 20_000);
+    assert_eq!(invoice.lines.len(), 1);
+    assert_eq!(invoice.payment_method_id, Some(method.id));
+}
+
+#[tokio::test]
+async fn test_removing_default_payment_method_promotes_next() {
+    let f = fixture().await;
+    let first = f
+        .service
+        .add_payment_method(f.admin_id, f.organization.id, "pm_first", false)
+        .await
+        .unwrap();
+    let second = f
+        .service
+        .add_payment_method(f.admin_id, f.organization.id, "pm_second", false)
+        .await
+        .unwrap();
+    assert!(!second.is_default);
+
+    f.service
+        .remove_payment_method(f.admin_id, f.organization.id, first.id)
+        .await
+        .unwrap();
+
+    let methods = f.service.payment_methods(f.admin_id, f.organization.id).await.unwrap();
+    assert_eq!(methods.len(), 1);
+    assert!(methods[0].is_default);
+    assert_eq!(*f.provider.detached.lock().unwrap(), vec!["pm_first".to_string()]);
+}
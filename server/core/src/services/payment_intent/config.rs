@@ -0,0 +1,11 @@
+//! Configuration for the payment intent service
+
+/// Configuration for the payment intent service
+///
+/// By default the provider and the card issuer decide when to challenge the
+/// cardholder, unless the caller requires 3-D Secure.
+#[derive(Debug, Clone, Default)]
+pub struct PaymentIntentConfig {
+    /// Request 3-D Secure on every payment, not only when risk checks ask for it
+    pub always_require_three_ds: bool,
+}
@@ -0,0 +1,18 @@
+//! Payment intent service module for creating customer payments with a provider
+//!
+//! This module handles:
+//! - Creating and confirming payment intents, requesting 3-D Secure when required
+//! - Returning the client action the mobile app needs to authenticate a payment
+//! - Recording payments that wait for authentication and their status timeline
+//! - Posting captured payments to the platform's double-entry books
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::PaymentIntentConfig;
+pub use service::{NewPayment, PaymentIntentResult, PaymentIntentService};
+pub use traits::{PaymentIntentProviderTrait, PaymentIntentRequest, ProviderIntentStatus, ProviderPaymentIntent};
@@ -0,0 +1,231 @@
+//! Payment intent service for creating customer payments
+//!
+//! A payment is recorded as soon as the provider has confirmed its intent.
+//! When the card issuer asks for 3-D Secure, the payment waits in
+//! `requires_action` and the mobile app receives the client action it needs
+//! to show the challenge; the provider's webhook later captures or fails it.
+//! Every status change is added to the payment's timeline.
+
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::ComplianceAction;
+use crate::domain::entities::payment::{
+    normalize_currency, Payment, PaymentClientAction, PaymentStatus, PaymentTimelineEntry,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{JournalRepository, PaymentRepository};
+use crate::services::compliance::ComplianceGateTrait;
+use crate::services::ledger::LedgerService;
+
+use super::config::PaymentIntentConfig;
+use super::traits::{PaymentIntentProviderTrait, PaymentIntentRequest, ProviderIntentStatus};
+
+/// A customer payment to create
+#[derive(Debug, Clone)]
+pub struct NewPayment {
+    /// Paying customer
+    pub customer_id: Uuid,
+    /// Worker who is paid out for the job
+    pub worker_id: Uuid,
+    /// Amount in minor units
+    pub amount: i64,
+    /// ISO 4217 currency code
+    pub currency: String,
+    /// Provider's identifier for the customer's payment method
+    pub payment_method: String,
+    /// Client-chosen key so a retried request creates one payment
+    pub idempotency_key: String,
+    /// Whether 3-D Secure is required, e.g. because the
+    /// [risk assessment](crate::domain::entities::payment_risk::PaymentRiskAssessment::requires_three_ds)
+    /// asks for it
+    pub require_three_ds: bool,
+}
+
+/// Result of creating a payment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentIntentResult {
+    /// The recorded payment
+    pub payment: Payment,
+    /// What the app must do to authenticate the payment, if anything
+    pub client_action: Option<PaymentClientAction>,
+}
+
+/// Service creating customer payments through a payment provider
+pub struct PaymentIntentService<P, J, V>
+where
+    P: PaymentRepository + 'static,
+    J: JournalRepository + 'static,
+    V: PaymentIntentProviderTrait + 'static,
+{
+    payments: Arc<P>,
+    books: Arc<LedgerService<J>>,
+    provider: Arc<V>,
+    config: PaymentIntentConfig,
+    compliance: Option<Arc<dyn ComplianceGateTrait>>,
+}
+
+impl<P, J, V> PaymentIntentService<P, J, V>
+where
+    P: PaymentRepository + 'static,
+    J: JournalRepository + 'static,
+    V: PaymentIntentProviderTrait + 'static,
+{
+    /// Create a new payment intent service
+    pub fn new(
+        payments: Arc<P>,
+        books: Arc<LedgerService<J>>,
+        provider: Arc<V>,
+        config: PaymentIntentConfig,
+    ) -> Self {
+        Self { payments, books, provider, config, compliance: None }
+    }
+
+    /// Refuse payments until the checks the customer's market requires are verified
+    pub fn with_compliance(mut self, compliance: Arc<dyn ComplianceGateTrait>) -> Self {
+        self.compliance = Some(compliance);
+        self
+    }
+
+    /// Create and confirm a payment with the provider
+    ///
+    /// Repeating a request with the same idempotency key returns the payment
+    /// created the first time.
+    ///
+    /// # Returns
+    /// * `Ok(PaymentIntentResult)` - The payment, with a client action when it
+    ///   requires authentication; declined payments are returned as failed
+    /// * `Err(DomainError::Validation)` - Invalid amount, currency or payment method
+    /// * `Err(DomainError::BusinessRule)` - A compliance check of the customer's
+    ///   market is not verified
+    /// * `Err(DomainError::Internal)` - The provider could not create the intent
+    pub async fn create_payment(&self, new: NewPayment) -> DomainResult<PaymentIntentResult> {
+        if new.amount <= 0 {
+            return Err(DomainError::Validation {
+                message: "Payment amount must be positive".to_string(),
+            });
+        }
+        if new.payment_method.trim().is_empty() || new.idempotency_key.trim().is_empty() {
+            return Err(DomainError::Validation {
+                message: "Payment method and idempotency key are required".to_string(),
+            });
+        }
+        let currency = normalize_currency(&new.currency)
+            .map_err(|message| DomainError::Validation { message })?;
+        if let Some(ref compliance) = self.compliance {
+            compliance.ensure_allowed(new.customer_id, ComplianceAction::Order).await?;
+        }
+
+        let provider = self.provider.provider_name();
+        let request = PaymentIntentRequest {
+            customer_id: new.customer_id,
+            worker_id: new.worker_id,
+            amount: new.amount,
+            currency,
+            payment_method: new.payment_method,
+            idempotency_key: new.idempotency_key,
+            require_three_ds: new.require_three_ds || self.config.always_require_three_ds,
+        };
+
+        let intent = self.provider.create_intent(&request).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to create {} payment intent: {}", provider, e),
+        })?;
+
+        let client_action = match &intent.status {
+            ProviderIntentStatus::RequiresAction(action) => Some(action.clone()),
+            _ => None,
+        };
+
+        if let Some(payment) = self.payments.find_by_intent_reference(provider, &intent.reference).await? {
+            let client_action = client_action.filter(|_| payment.status == PaymentStatus::RequiresAction);
+            return Ok(PaymentIntentResult { payment, client_action });
+        }
+
+        let (payment, note) = match intent.status {
+            ProviderIntentStatus::Succeeded { charge_reference } => {
+                let payment = Payment::new(
+                    provider,
+                    charge_reference.unwrap_or_else(|| intent.reference.clone()),
+                    request.customer_id,
+                    request.worker_id,
+                    request.amount,
+                    &request.currency,
+                )
+                .map_err(|message| DomainError::Validation { message })?
+                .with_intent(&intent.reference)
+                .with_payment_method(&request.payment_method);
+                (payment, None)
+            }
+            ProviderIntentStatus::RequiresAction(_) => (self.awaiting_payment(&request, &intent.reference)?, None),
+            ProviderIntentStatus::Failed { reason } => {
+                let mut payment = self.awaiting_payment(&request, &intent.reference)?;
+                payment
+                    .mark_failed()
+                    .map_err(|message| DomainError::Internal { message })?;
+                (payment, Some(reason))
+            }
+        };
+
+        let payment = self.payments.save(payment).await?;
+        self.payments
+            .add_timeline_entry(PaymentTimelineEntry::new(&payment, note, Utc::now()))
+            .await?;
+        if payment.is_captured() {
+            self.books.record_payment(&payment).await?;
+        }
+
+        info!(
+            payment_id = %payment.id,
+            customer_id = %payment.customer_id,
+            amount = payment.amount,
+            currency = %payment.currency,
+            status = payment.status.as_str(),
+            three_ds_requested = request.require_three_ds,
+            "Payment created"
+        );
+
+        Ok(PaymentIntentResult { payment, client_action })
+    }
+
+    /// Get a payment and its timeline, oldest first
+    ///
+    /// # Arguments
+    /// * `payment_id` - Payment ID
+    /// * `user_id` - Customer or worker of the payment
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such payment, or the user is not a party to it
+    pub async fn timeline(
+        &self,
+        payment_id: Uuid,
+        user_id: Uuid,
+    ) -> DomainResult<(Payment, Vec<PaymentTimelineEntry>)> {
+        let payment = self
+            .payments
+            .find_by_id(payment_id)
+            .await?
+            .filter(|p| p.customer_id == user_id || p.worker_id == user_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payment".to_string(),
+            })?;
+        let timeline = self.payments.find_timeline(payment.id).await?;
+
+        Ok((payment, timeline))
+    }
+
+    /// Payment waiting for authentication of an intent
+    fn awaiting_payment(&self, request: &PaymentIntentRequest, intent_reference: &str) -> DomainResult<Payment> {
+        Payment::awaiting_authentication(
+            self.provider.provider_name(),
+            intent_reference,
+            request.customer_id,
+            request.worker_id,
+            request.amount,
+            &request.currency,
+        )
+        .map(|payment| payment.with_payment_method(&request.payment_method))
+        .map_err(|message| DomainError::Validation { message })
+    }
+}
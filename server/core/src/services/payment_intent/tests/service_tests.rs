@@ -0,0 +1,192 @@
+//! Unit tests for the payment intent service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::payment::{PaymentClientAction, PaymentStatus};
+use crate::errors::DomainError;
+use crate::repositories::PaymentRepository;
+use crate::services::ledger::tests::service_tests::MockJournal;
+use crate::services::ledger::{LedgerConfig, LedgerService};
+use crate::services::payment_intent::{
+    NewPayment, PaymentIntentConfig, PaymentIntentProviderTrait, PaymentIntentRequest,
+    PaymentIntentService, ProviderIntentStatus, ProviderPaymentIntent,
+};
+use crate::services::payment_webhook::tests::service_tests::MockPayments;
+
+/// Answers every request with a fixed intent status and records the requests
+struct MockProvider {
+    status: ProviderIntentStatus,
+    requests: Mutex<Vec<PaymentIntentRequest>>,
+}
+
+impl MockProvider {
+    fn new(status: ProviderIntentStatus) -> Self {
+        Self { status, requests: Mutex::new(Vec::new()) }
+    }
+}
+
+#[async_trait]
+impl PaymentIntentProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    async fn create_intent(&self, request: &PaymentIntentRequest) -> Result<ProviderPaymentIntent, String> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(ProviderPaymentIntent {
+            reference: format!("pi_{}", request.idempotency_key),
+            status: self.status.clone(),
+        })
+    }
+}
+
+struct Fixture {
+    service: PaymentIntentService<MockPayments, MockJournal, MockProvider>,
+    payments: Arc<MockPayments>,
+    books: Arc<LedgerService<MockJournal>>,
+    provider: Arc<MockProvider>,
+}
+
+fn fixture(status: ProviderIntentStatus, config: PaymentIntentConfig) -> Fixture {
+    let payments = Arc::new(MockPayments::default());
+    let books = Arc::new(LedgerService::new(Arc::new(MockJournal::default()), LedgerConfig::default()));
+    let provider = Arc::new(MockProvider::new(status));
+    let service = PaymentIntentService::new(payments.clone(), books.clone(), provider.clone(), config);
+
+    Fixture { service, payments, books, provider }
+}
+
+fn new_payment() -> NewPayment {
+    NewPayment {
+        customer_id: Uuid::new_v4(),
+        worker_id: Uuid::new_v4(),
+        amount: 10_000,
+        currency: "aud".to_string(),
+        payment_method: "pm_card".to_string(),
+        idempotency_key: "order_1".to_string(),
+        require_three_ds: false,
+    }
+}
+
+fn sdk_action() -> ProviderIntentStatus {
+    ProviderIntentStatus::RequiresAction(PaymentClientAction::UseSdk {
+        client_secret: "pi_order_1_secret".to_string(),
+    })
+}
+
+#[tokio::test]
+async fn test_captured_payment_is_posted_to_books() {
+    let f = fixture(
+        ProviderIntentStatus::Succeeded { charge_reference: Some("ch_1".to_string()) },
+        PaymentIntentConfig::default(),
+    );
+    let new = new_payment();
+
+    let result = f.service.create_payment(new.clone()).await.unwrap();
+
+    assert_eq!(result.client_action, None);
+    assert_eq!(result.payment.status, PaymentStatus::Succeeded);
+    assert_eq!(result.payment.provider_reference, "ch_1");
+    assert_eq!(result.payment.intent_reference.as_deref(), Some("pi_order_1"));
+    assert_eq!(result.payment.payment_method.as_deref(), Some("pm_card"));
+    assert_eq!(result.payment.currency, "AUD");
+    assert_eq!(f.books.worker_balance(new.worker_id, "AUD").await.unwrap(), 9_000);
+}
+
+#[tokio::test]
+async fn test_payment_requiring_authentication_returns_client_action() {
+    let f = fixture(sdk_action(), PaymentIntentConfig::default());
+    let new = new_payment();
+
+    let result = f.service.create_payment(new.clone()).await.unwrap();
+
+    assert_eq!(result.payment.status, PaymentStatus::RequiresAction);
+    assert!(matches!(result.client_action, Some(PaymentClientAction::UseSdk { .. })));
+    assert_eq!(f.books.worker_balance(new.worker_id, "AUD").await.unwrap(), 0);
+
+    let timeline = f.payments.find_timeline(result.payment.id).await.unwrap();
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].status, PaymentStatus::RequiresAction);
+}
+
+#[tokio::test]
+async fn test_retried_request_returns_existing_payment() {
+    let f = fixture(sdk_action(), PaymentIntentConfig::default());
+    let new = new_payment();
+
+    let first = f.service.create_payment(new.clone()).await.unwrap();
+    let second = f.service.create_payment(new).await.unwrap();
+
+    assert_eq!(first.payment.id, second.payment.id);
+    assert_eq!(first.client_action, second.client_action);
+    assert_eq!(f.payments.payments.lock().unwrap().len(), 1);
+    assert_eq!(f.payments.find_timeline(first.payment.id).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_declined_payment_is_recorded_as_failed() {
+    let f = fixture(
+        ProviderIntentStatus::Failed { reason: "Your card was declined.".to_string() },
+        PaymentIntentConfig::default(),
+    );
+
+    let result = f.service.create_payment(new_payment()).await.unwrap();
+
+    assert_eq!(result.payment.status, PaymentStatus::Failed);
+    assert_eq!(result.client_action, None);
+    let timeline = f.payments.find_timeline(result.payment.id).await.unwrap();
+    assert_eq!(timeline[0].note.as_deref(), Some("Your card was declined."));
+}
+
+#[tokio::test]
+async fn test_three_ds_requested_when_required_or_configured() {
+    let f = fixture(sdk_action(), PaymentIntentConfig::default());
+    f.service.create_payment(new_payment()).await.unwrap();
+    f.service
+        .create_payment(NewPayment {
+            idempotency_key: "order_2".to_string(),
+            require_three_ds: true,
+            ..new_payment()
+        })
+        .await
+        .unwrap();
+
+    let requested: Vec<bool> = f.provider.requests.lock().unwrap().iter().map(|r| r.require_three_ds).collect();
+    assert_eq!(requested, vec![false, true]);
+
+    let f = fixture(sdk_action(), PaymentIntentConfig { always_require_three_ds: true });
+    f.service.create_payment(new_payment()).await.unwrap();
+    assert!(f.provider.requests.lock().unwrap()[0].require_three_ds);
+}
+
+#[tokio::test]
+async fn test_invalid_payment_is_rejected_before_calling_provider() {
+    let f = fixture(sdk_action(), PaymentIntentConfig::default());
+
+    for new in [
+        NewPayment { amount: 0, ..new_payment() },
+        NewPayment { currency: "AU".to_string(), ..new_payment() },
+        NewPayment { payment_method: " ".to_string(), ..new_payment() },
+    ] {
+        let result = f.service.create_payment(new).await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+    }
+    assert!(f.provider.requests.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_timeline_is_only_visible_to_parties() {
+    let f = fixture(sdk_action(), PaymentIntentConfig::default());
+    let new = new_payment();
+    let payment = f.service.create_payment(new.clone()).await.unwrap().payment;
+
+    let (found, timeline) = f.service.timeline(payment.id, new.customer_id).await.unwrap();
+    assert_eq!(found.id, payment.id);
+    assert_eq!(timeline.len(), 1);
+    assert!(f.service.timeline(payment.id, new.worker_id).await.is_ok());
+
+    let result = f.service.timeline(payment.id, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
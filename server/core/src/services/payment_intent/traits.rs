@@ -0,0 +1,65 @@
+//! Traits for payment provider intents
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::entities::payment::PaymentClientAction;
+
+/// A payment intent to create and confirm with the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentIntentRequest {
+    /// Paying customer
+    pub customer_id: Uuid,
+    /// Worker who is paid out for the job
+    pub worker_id: Uuid,
+    /// Amount in minor units
+    pub amount: i64,
+    /// ISO 4217 currency code in upper case
+    pub currency: String,
+    /// Provider's identifier for the customer's payment method
+    pub payment_method: String,
+    /// Key under which the provider deduplicates retried requests
+    pub idempotency_key: String,
+    /// Whether the cardholder must be challenged with 3-D Secure
+    pub require_three_ds: bool,
+}
+
+/// State of a payment intent after confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderIntentStatus {
+    /// The money was captured
+    Succeeded {
+        /// Provider's identifier for the capture, if reported
+        charge_reference: Option<String>,
+    },
+    /// The customer must authenticate before the money can be captured
+    RequiresAction(PaymentClientAction),
+    /// The payment was declined
+    Failed {
+        /// Why the payment was declined
+        reason: String,
+    },
+}
+
+/// A payment intent as reported by the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderPaymentIntent {
+    /// Provider's identifier for the intent
+    pub reference: String,
+    /// State after confirmation
+    pub status: ProviderIntentStatus,
+}
+
+/// Trait for creating payment intents with a payment provider
+#[async_trait]
+pub trait PaymentIntentProviderTrait: Send + Sync {
+    /// Provider name as recorded on payments (e.g. "stripe")
+    fn provider_name(&self) -> &str;
+
+    /// Create a payment intent and confirm it with the customer's payment method
+    ///
+    /// # Returns
+    /// * `Ok(ProviderPaymentIntent)` - The intent, including declined ones
+    /// * `Err(String)` - Why the provider could not be reached or refused the request
+    async fn create_intent(&self, request: &PaymentIntentRequest) -> Result<ProviderPaymentIntent, String>;
+}
@@ -0,0 +1,16 @@
+//! Configuration for the saved payment method service
+
+/// Configuration for the saved payment method service
+#[derive(Debug, Clone)]
+pub struct PaymentMethodConfig {
+    /// Most payment methods a customer can save
+    pub max_methods_per_customer: usize,
+}
+
+impl Default for PaymentMethodConfig {
+    fn default() -> Self {
+        Self {
+            max_methods_per_customer: 10,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Saved payment method service module
+//!
+//! This module handles:
+//! - Saving cards the device tokenized with the payment provider
+//! - Keeping one default method per customer
+//! - Refusing to delete a method while an escrowed payment depends on it
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::PaymentMethodConfig;
+pub use service::PaymentMethodService;
+pub use traits::{PaymentMethodProviderTrait, ProviderPaymentMethod};
@@ -0,0 +1,205 @@
+//! Saved payment method service
+//!
+//! The first method a customer saves becomes the default. A method cannot
+//! be deleted while a payment charged to it is still held in escrow, since
+//! refunds of that payment go back to the same card; once the escrow is
+//! released the method is detached at the provider and deleted.
+
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::payment_method::{looks_like_card_number, SavedPaymentMethod};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{PaymentMethodRepository, PaymentRepository, PayoutRepository};
+
+use super::config::PaymentMethodConfig;
+use super::traits::PaymentMethodProviderTrait;
+
+/// Service managing customers' saved payment methods
+pub struct PaymentMethodService<M, P, O, V>
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    methods: Arc<M>,
+    payments: Arc<P>,
+    payouts: Arc<O>,
+    provider: Arc<V>,
+    config: PaymentMethodConfig,
+}
+
+impl<M, P, O, V> PaymentMethodService<M, P, O, V>
+where
+    M: PaymentMethodRepository + 'static,
+    P: PaymentRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PaymentMethodProviderTrait + 'static,
+{
+    /// Create a new saved payment method service
+    pub fn new(
+        methods: Arc<M>,
+        payments: Arc<P>,
+        payouts: Arc<O>,
+        provider: Arc<V>,
+        config: PaymentMethodConfig,
+    ) -> Self {
+        Self { methods, payments, payouts, provider, config }
+    }
+
+    /// List a customer's payment methods, newest first
+    pub async fn list(&self, customer_id: Uuid) -> DomainResult<Vec<SavedPaymentMethod>> {
+        self.methods.find_by_customer(customer_id).await
+    }
+
+    /// Save a payment method the device tokenized with the provider
+    ///
+    /// Saving the same provider token again returns the method saved the
+    /// first time.
+    ///
+    /// # Arguments
+    /// * `customer_id` - Customer saving the method
+    /// * `token` - Provider's identifier for the payment method
+    /// * `make_default` - Whether to make it the default; the first method always is
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Missing token, a raw card number, or an expired card
+    /// * `Err(DomainError::BusinessRule)` - The customer saved the maximum number of methods
+    /// * `Err(DomainError::Internal)` - The provider could not attach the method
+    pub async fn add(
+        &self,
+        customer_id: Uuid,
+        token: &str,
+        make_default: bool,
+    ) -> DomainResult<SavedPaymentMethod> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Payment method token is required".to_string(),
+            });
+        }
+        if looks_like_card_number(token) {
+            return Err(DomainError::Validation {
+                message: "Card numbers must be tokenized with the payment provider".to_string(),
+            });
+        }
+
+        let provider = self.provider.provider_name();
+        if let Some(existing) = self.methods.find_by_reference(customer_id, provider, token).await? {
+            return Ok(existing);
+        }
+
+        let existing = self.methods.find_by_customer(customer_id).await?;
+        if existing.len() >= self.config.max_methods_per_customer {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "At most {} payment methods can be saved",
+                    self.config.max_methods_per_customer
+                ),
+            });
+        }
+
+        let card = self.provider.attach(customer_id, token).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to attach {} payment method: {}", provider, e),
+        })?;
+        let method = SavedPaymentMethod::new(
+            customer_id,
+            provider,
+            card.reference,
+            card.brand,
+            card.last4,
+            card.exp_month,
+            card.exp_year,
+        )
+        .map_err(|message| DomainError::Internal { message })?;
+        if method.is_expired(Utc::now()) {
+            return Err(DomainError::Validation {
+                message: "The card has expired".to_string(),
+            });
+        }
+
+        let mut method = self.methods.save(method).await?;
+        if make_default || existing.is_empty() {
+            self.methods.set_default(customer_id, method.id).await?;
+            method.is_default = true;
+        }
+
+        info!(
+            customer_id = %customer_id,
+            payment_method_id = %method.id,
+            brand = %method.brand,
+            "Payment method saved"
+        );
+
+        Ok(method)
+    }
+
+    /// Make a payment method the customer's default
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such method, or it belongs to another customer
+    pub async fn set_default(&self, customer_id: Uuid, method_id: Uuid) -> DomainResult<SavedPaymentMethod> {
+        let mut method = self.find_owned(customer_id, method_id).await?;
+
+        self.methods.set_default(customer_id, method.id).await?;
+        method.is_default = true;
+        Ok(method)
+    }
+
+    /// Delete a payment method
+    ///
+    /// When the default method is deleted, the newest remaining method
+    /// becomes the default.
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such method, or it belongs to another customer
+    /// * `Err(DomainError::BusinessRule)` - A payment charged to the method is held in escrow
+    /// * `Err(DomainError::Internal)` - The provider could not detach the method
+    pub async fn delete(&self, customer_id: Uuid, method_id: Uuid) -> DomainResult<()> {
+        let method = self.find_owned(customer_id, method_id).await?;
+
+        let payments = self
+            .payments
+            .find_by_payment_method(customer_id, &method.provider_reference)
+            .await?;
+        for payment in payments.iter().filter(|p| p.provider == method.provider) {
+            let payouts = self.payouts.find_by_payment(payment.id).await?;
+            if payment.holds_escrow(&payouts) {
+                return Err(DomainError::BusinessRule {
+                    message: "Payment method is funding a payment held in escrow".to_string(),
+                });
+            }
+        }
+
+        self.provider
+            .detach(&method.provider_reference)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to detach {} payment method: {}", method.provider, e),
+            })?;
+        self.methods.delete(method.id).await?;
+
+        if method.is_default {
+            if let Some(next) = self.methods.find_by_customer(customer_id).await?.first() {
+                self.methods.set_default(customer_id, next.id).await?;
+            }
+        }
+
+        info!(customer_id = %customer_id, payment_method_id = %method.id, "Payment method deleted");
+        Ok(())
+    }
+
+    /// Find a payment method belonging to a customer
+    async fn find_owned(&self, customer_id: Uuid, method_id: Uuid) -> DomainResult<SavedPaymentMethod> {
+        self.methods
+            .find_by_id(method_id)
+            .await?
+            .filter(|m| m.customer_id == customer_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payment method".to_string(),
+            })
+    }
+}
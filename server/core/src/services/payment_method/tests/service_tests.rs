@@ -0,0 +1,242 @@
+//! Unit tests for the saved payment method service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::payment::{Payment, PaymentStatus, Payout, PayoutStatus};
+use crate::domain::entities::payment_method::SavedPaymentMethod;
+use crate::errors::DomainError;
+use crate::repositories::{PaymentMethodRepository, PaymentRepository, PayoutRepository};
+use crate::services::payment_method::{
+    PaymentMethodConfig, PaymentMethodProviderTrait, PaymentMethodService, ProviderPaymentMethod,
+};
+use crate::services::payment_webhook::tests::service_tests::{MockPayments, MockPayouts};
+
+#[derive(Default)]
+struct MockPaymentMethods {
+    methods: Mutex<Vec<SavedPaymentMethod>>,
+}
+
+#[async_trait]
+impl PaymentMethodRepository for MockPaymentMethods {
+    async fn save(&self, method: SavedPaymentMethod) -> Result<SavedPaymentMethod, DomainError> {
+        self.methods.lock().unwrap().push(method.clone());
+        Ok(method)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SavedPaymentMethod>, DomainError> {
+        Ok(self.methods.lock().unwrap().iter().find(|m| m.id == id).cloned())
+    }
+
+    async fn find_by_reference(
+        &self,
+        customer_id: Uuid,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<SavedPaymentMethod>, DomainError> {
+        Ok(self
+            .methods
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|m| {
+                m.customer_id == customer_id && m.provider == provider && m.provider_reference == provider_reference
+            })
+            .cloned())
+    }
+
+    async fn find_by_customer(&self, customer_id: Uuid) -> Result<Vec<SavedPaymentMethod>, DomainError> {
+        Ok(self
+            .methods
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|m| m.customer_id == customer_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_default(&self, customer_id: Uuid, id: Uuid) -> Result<(), DomainError> {
+        for method in self.methods.lock().unwrap().iter_mut().filter(|m| m.customer_id == customer_id) {
+            method.is_default = method.id == id;
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let mut methods = self.methods.lock().unwrap();
+        let before = methods.len();
+        methods.retain(|m| m.id != id);
+        Ok(methods.len() < before)
+    }
+}
+
+/// Provider that attaches any `pm_` token as a Visa card
+#[derive(Default)]
+struct MockProvider {
+    detached: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl PaymentMethodProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    async fn attach(&self, _customer_id: Uuid, token: &str) -> Result<ProviderPaymentMethod, String> {
+        if !token.starts_with("pm_") {
+            return Err("No such payment method".to_string());
+        }
+        Ok(ProviderPaymentMethod {
+            reference: token.to_string(),
+            brand: "visa".to_string(),
+            last4: "4242".to_string(),
+            exp_month: 12,
+            exp_year: if token == "pm_expired" { 2020 } else { 2099 },
+        })
+    }
+
+    async fn detach(&self, reference: &str) -> Result<(), String> {
+        self.detached.lock().unwrap().push(reference.to_string());
+        Ok(())
+    }
+}
+
+struct Fixture {
+    service: PaymentMethodService<MockPaymentMethods, MockPayments, MockPayouts, MockProvider>,
+    methods: Arc<MockPaymentMethods>,
+    payments: Arc<MockPayments>,
+    payouts: Arc<MockPayouts>,
+    provider: Arc<MockProvider>,
+}
+
+fn fixture() -> Fixture {
+    let methods = Arc::new(MockPaymentMethods::default());
+    let payments = Arc::new(MockPayments::default());
+    let payouts = Arc::new(MockPayouts::default());
+    let provider = Arc::new(MockProvider::default());
+    let service = PaymentMethodService::new(
+        methods.clone(),
+        payments.clone(),
+        payouts.clone(),
+        provider.clone(),
+        PaymentMethodConfig { max_methods_per_customer: 3 },
+    );
+
+    Fixture { service, methods, payments, payouts, provider }
+}
+
+/// Record a captured payment charged to a payment method
+async fn charge(f: &Fixture, customer_id: Uuid, payment_method: &str) -> Payment {
+    let payment = Payment::new("stripe", format!("ch_{}", Uuid::new_v4()), customer_id, Uuid::new_v4(), 5_000, "AUD")
+        .unwrap()
+        .with_payment_method(payment_method);
+    f.payments.save(payment).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_first_method_becomes_default() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+
+    let first = f.service.add(customer_id, "pm_1", false).await.unwrap();
+    let second = f.service.add(customer_id, "pm_2", false).await.unwrap();
+    assert!(first.is_default);
+    assert!(!second.is_default);
+
+    let third = f.service.add(customer_id, "pm_3", true).await.unwrap();
+    assert!(third.is_default);
+    let defaults: Vec<Uuid> = f.service.list(customer_id).await.unwrap().iter().filter(|m| m.is_default).map(|m| m.id).collect();
+    assert_eq!(defaults, vec![third.id]);
+
+    // Saving the same token again is idempotent
+    let again = f.service.add(customer_id, "pm_2", false).await.unwrap();
+    assert_eq!(again.id, second.id);
+
+    let result = f.service.add(customer_id, "pm_4", false).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_card_numbers_and_expired_cards_are_rejected() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+
+    for token in ["", "4242 4242 4242 4242", "pm_expired"] {
+        let result = f.service.add(customer_id, token, false).await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })), "token {:?}", token);
+    }
+
+    let result = f.service.add(customer_id, "tok_unknown", false).await;
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert!(f.methods.methods.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_is_blocked_while_escrow_is_active() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    let method = f.service.add(customer_id, "pm_1", false).await.unwrap();
+    let payment = charge(&f, customer_id, "pm_1").await;
+
+    let result = f.service.delete(customer_id, method.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let mut payout = Payout::new(&payment, 4_500);
+    f.payouts.save(payout.clone()).await.unwrap();
+    let result = f.service.delete(customer_id, method.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(f.provider.detached.lock().unwrap().is_empty());
+
+    payout.status = PayoutStatus::Paid;
+    f.payouts.update(&payout).await.unwrap();
+    f.service.delete(customer_id, method.id).await.unwrap();
+    assert_eq!(*f.provider.detached.lock().unwrap(), vec!["pm_1".to_string()]);
+    assert!(f.service.list(customer_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_refunded_or_failed_payments_do_not_block_delete() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    let method = f.service.add(customer_id, "pm_1", false).await.unwrap();
+
+    let mut refunded = charge(&f, customer_id, "pm_1").await;
+    refunded.apply_refund(5_000).unwrap();
+    f.payments.update(&refunded).await.unwrap();
+    let mut failed = charge(&f, customer_id, "pm_1").await;
+    failed.status = PaymentStatus::Failed;
+    f.payments.update(&failed).await.unwrap();
+    // Another customer's payment with the same reference is not considered
+    charge(&f, Uuid::new_v4(), "pm_1").await;
+
+    assert!(f.service.delete(customer_id, method.id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_deleting_default_promotes_newest_method() {
+    let f = fixture();
+    let customer_id = Uuid::new_v4();
+    let first = f.service.add(customer_id, "pm_1", false).await.unwrap();
+    f.service.add(customer_id, "pm_2", false).await.unwrap();
+    let newest = f.service.add(customer_id, "pm_3", false).await.unwrap();
+
+    f.service.delete(customer_id, first.id).await.unwrap();
+
+    let stored = f.methods.find_by_id(newest.id).await.unwrap().unwrap();
+    assert!(stored.is_default);
+}
+
+#[tokio::test]
+async fn test_methods_of_other_customers_are_not_found() {
+    let f = fixture();
+    let method = f.service.add(Uuid::new_v4(), "pm_1", false).await.unwrap();
+    let other = Uuid::new_v4();
+
+    let result = f.service.set_default(other, method.id).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    let result = f.service.delete(other, method.id).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
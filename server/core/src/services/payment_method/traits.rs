@@ -0,0 +1,40 @@
+//! Traits for saving payment methods with the payment provider
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// A card payment method as reported by the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderPaymentMethod {
+    /// Provider's identifier for the payment method
+    pub reference: String,
+    /// Card brand (e.g. "visa")
+    pub brand: String,
+    /// Last four digits of the card number
+    pub last4: String,
+    /// Expiry month, 1 to 12
+    pub exp_month: u32,
+    /// Expiry year, four digits
+    pub exp_year: i32,
+}
+
+/// Trait for a payment provider that stores customers' cards
+#[async_trait]
+pub trait PaymentMethodProviderTrait: Send + Sync {
+    /// Name of the provider, stored with each payment method
+    fn provider_name(&self) -> &str;
+
+    /// Attach a payment method the device tokenized to the customer
+    ///
+    /// # Arguments
+    /// * `customer_id` - Customer saving the method
+    /// * `token` - Provider's identifier returned by its SDK
+    ///
+    /// # Returns
+    /// * `Ok(ProviderPaymentMethod)` - The card's display details
+    /// * `Err(String)` - Unknown token, not a card, or the provider failed
+    async fn attach(&self, customer_id: Uuid, token: &str) -> Result<ProviderPaymentMethod, String>;
+
+    /// Detach a payment method so it can no longer be charged
+    async fn detach(&self, reference: &str) -> Result<(), String>;
+}
@@ -0,0 +1,51 @@
+//! Configuration for the payment risk service
+
+use std::collections::HashMap;
+
+use crate::domain::entities::payment_risk::RiskAction;
+
+/// Configuration for the payment risk service
+#[derive(Debug, Clone)]
+pub struct PaymentRiskConfig {
+    /// Whether risk rules are evaluated; when disabled every attempt is allowed
+    pub enabled: bool,
+    /// Accounts younger than this many days count as new
+    pub new_account_days: i64,
+    /// Amount in minor units, per currency, above which a new account's payment is high
+    pub high_amount_thresholds: HashMap<String, i64>,
+    /// Action when a new account pays a high amount
+    pub new_account_high_amount_action: RiskAction,
+    /// Maximum distinct cards per account within the card window
+    pub max_cards_per_account: usize,
+    /// Window for counting distinct cards, in hours
+    pub card_window_hours: i64,
+    /// Action when an account uses too many cards
+    pub too_many_cards_action: RiskAction,
+    /// Action when the card's billing country differs from the job's market
+    pub billing_country_mismatch_action: RiskAction,
+}
+
+impl PaymentRiskConfig {
+    /// High amount threshold for a currency, if one is configured
+    pub fn high_amount_threshold(&self, currency: &str) -> Option<i64> {
+        self.high_amount_thresholds.get(&currency.to_ascii_uppercase()).copied()
+    }
+}
+
+impl Default for PaymentRiskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            new_account_days: 7,
+            high_amount_thresholds: HashMap::from([
+                ("AUD".to_string(), 200_000), // A$2,000
+                ("CNY".to_string(), 1_000_000), // ¥10,000
+            ]),
+            new_account_high_amount_action: RiskAction::Review,
+            max_cards_per_account: 3,
+            card_window_hours: 24,
+            too_many_cards_action: RiskAction::Block,
+            billing_country_mismatch_action: RiskAction::RequireThreeDs,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Payment risk service module for fraud checks before payment creation
+//!
+//! This module handles:
+//! - Evaluating new-account, card velocity and billing country rules on payment attempts
+//! - Deciding whether a payment proceeds, needs 3-D Secure, waits for review or is blocked
+//! - Queueing attempts for manual review and recording administrators' decisions
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::PaymentRiskConfig;
+pub use service::{PaymentAttempt, PaymentRiskService};
@@ -0,0 +1,213 @@
+//! Payment risk service evaluating fraud rules before a payment is created
+//!
+//! Every attempt is stored with the rules it triggered, so card velocity
+//! can be counted across attempts and reviewers can see why a payment was
+//! held.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::calendar::normalize_market;
+use crate::domain::entities::payment::normalize_currency;
+use crate::domain::entities::payment_risk::{
+    PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::PaymentRiskRepository;
+
+use super::config::PaymentRiskConfig;
+
+/// A payment about to be created, as seen by the risk rules
+#[derive(Debug, Clone)]
+pub struct PaymentAttempt {
+    /// Paying customer
+    pub user_id: Uuid,
+    /// When the customer's account was created
+    pub account_created_at: DateTime<Utc>,
+    /// Amount in minor units
+    pub amount: i64,
+    /// ISO 4217 currency code
+    pub currency: String,
+    /// Market of the job (ISO 3166-1 alpha-2)
+    pub market: String,
+    /// Billing country of the card, if the provider reported one
+    pub billing_country: Option<String>,
+    /// Provider fingerprint identifying the card, if known
+    pub card_fingerprint: Option<String>,
+}
+
+/// Service assessing the fraud risk of payment attempts
+pub struct PaymentRiskService<R: PaymentRiskRepository + 'static> {
+    repository: Arc<R>,
+    config: PaymentRiskConfig,
+}
+
+impl<R: PaymentRiskRepository + 'static> PaymentRiskService<R> {
+    /// Create a new payment risk service
+    pub fn new(repository: Arc<R>, config: PaymentRiskConfig) -> Self {
+        Self { repository, config }
+    }
+
+    /// Assess a payment attempt before its payment intent is created
+    ///
+    /// The caller creates the payment only if the assessment
+    /// [permits it](PaymentRiskAssessment::permits_payment), with 3-D Secure
+    /// when [required](PaymentRiskAssessment::requires_three_ds).
+    ///
+    /// # Returns
+    /// * `Ok(PaymentRiskAssessment)` - The stored assessment
+    /// * `Err(DomainError::Validation)` - Invalid currency code
+    pub async fn assess(&self, attempt: PaymentAttempt) -> DomainResult<PaymentRiskAssessment> {
+        let currency = normalize_currency(&attempt.currency)
+            .map_err(|message| DomainError::Validation { message })?;
+        let market = normalize_market(&attempt.market);
+        let billing_country = attempt
+            .billing_country
+            .as_deref()
+            .map(normalize_market)
+            .filter(|c| !c.is_empty());
+        let now = Utc::now();
+
+        let mut triggered = Vec::new();
+        if self.config.enabled {
+            if self.is_new_account_high_amount(&attempt, &currency, now) {
+                triggered.push(PaymentRiskRule::NewAccountHighAmount);
+            }
+            if self.uses_too_many_cards(&attempt, now).await? {
+                triggered.push(PaymentRiskRule::TooManyCards);
+            }
+            if billing_country.as_ref().is_some_and(|country| *country != market) {
+                triggered.push(PaymentRiskRule::BillingCountryMismatch);
+            }
+        }
+
+        let action = triggered
+            .iter()
+            .map(|rule| self.action_for(*rule))
+            .max()
+            .unwrap_or(RiskAction::Allow);
+
+        let assessment = PaymentRiskAssessment {
+            id: Uuid::new_v4(),
+            user_id: attempt.user_id,
+            amount: attempt.amount,
+            currency,
+            market,
+            billing_country,
+            card_fingerprint: attempt.card_fingerprint,
+            triggered_rules: triggered,
+            action,
+            review_status: if action == RiskAction::Review {
+                ReviewStatus::Pending
+            } else {
+                ReviewStatus::NotRequired
+            },
+            reviewed_by: None,
+            reviewed_at: None,
+            review_note: None,
+            created_at: now,
+        };
+        let assessment = self.repository.save(assessment).await?;
+
+        if assessment.action != RiskAction::Allow {
+            let rules: Vec<&str> = assessment.triggered_rules.iter().map(|r| r.as_str()).collect();
+            warn!(
+                assessment_id = %assessment.id,
+                user_id = %assessment.user_id,
+                amount = assessment.amount,
+                currency = %assessment.currency,
+                action = assessment.action.as_str(),
+                rules = ?rules,
+                "Payment attempt triggered risk rules"
+            );
+        }
+
+        Ok(assessment)
+    }
+
+    /// List assessments waiting for review, oldest first
+    pub async fn pending_reviews(&self, limit: usize) -> DomainResult<Vec<PaymentRiskAssessment>> {
+        self.repository.find_pending_reviews(limit).await
+    }
+
+    /// Get an assessment by ID
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No assessment exists with this ID
+    pub async fn get(&self, id: Uuid) -> DomainResult<PaymentRiskAssessment> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payment risk assessment".to_string(),
+            })
+    }
+
+    /// Record an administrator's decision on a held payment
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No assessment exists with this ID
+    /// * `Err(DomainError::BusinessRule)` - The assessment is not pending review
+    pub async fn decide_review(
+        &self,
+        id: Uuid,
+        approved: bool,
+        reviewer: Uuid,
+        note: Option<String>,
+    ) -> DomainResult<PaymentRiskAssessment> {
+        let mut assessment = self.get(id).await?;
+        assessment
+            .decide(approved, reviewer, note)
+            .map_err(|message| DomainError::BusinessRule { message })?;
+        self.repository.update_review(&assessment).await?;
+
+        info!(
+            assessment_id = %assessment.id,
+            reviewer = %reviewer,
+            status = assessment.review_status.as_str(),
+            "Payment risk review decided"
+        );
+
+        Ok(assessment)
+    }
+
+    /// Whether a recently created account is paying more than the threshold
+    fn is_new_account_high_amount(&self, attempt: &PaymentAttempt, currency: &str, now: DateTime<Utc>) -> bool {
+        let is_new = now - attempt.account_created_at < Duration::days(self.config.new_account_days);
+        is_new
+            && self
+                .config
+                .high_amount_threshold(currency)
+                .is_some_and(|threshold| attempt.amount > threshold)
+    }
+
+    /// Whether the attempt's card brings the account over the card limit
+    async fn uses_too_many_cards(&self, attempt: &PaymentAttempt, now: DateTime<Utc>) -> DomainResult<bool> {
+        let Some(fingerprint) = attempt.card_fingerprint.as_deref() else {
+            return Ok(false);
+        };
+
+        let since = now - Duration::hours(self.config.card_window_hours);
+        let mut cards: HashSet<String> = self
+            .repository
+            .find_card_fingerprints(attempt.user_id, since)
+            .await?
+            .into_iter()
+            .collect();
+        cards.insert(fingerprint.to_string());
+
+        Ok(cards.len() > self.config.max_cards_per_account)
+    }
+
+    /// Configured action of a rule
+    fn action_for(&self, rule: PaymentRiskRule) -> RiskAction {
+        match rule {
+            PaymentRiskRule::NewAccountHighAmount => self.config.new_account_high_amount_action,
+            PaymentRiskRule::TooManyCards => self.config.too_many_cards_action,
+            PaymentRiskRule::BillingCountryMismatch => self.config.billing_country_mismatch_action,
+        }
+    }
+}
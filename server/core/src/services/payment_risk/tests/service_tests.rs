@@ -0,0 +1,158 @@
+//! Unit tests for the payment risk service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::payment_risk::{
+    PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction,
+};
+use crate::errors::DomainError;
+use crate::repositories::PaymentRiskRepository;
+use crate::services::payment_risk::{PaymentAttempt, PaymentRiskConfig, PaymentRiskService};
+
+#[derive(Default)]
+struct MockPaymentRiskRepository {
+    assessments: Mutex<Vec<PaymentRiskAssessment>>,
+}
+
+#[async_trait]
+impl PaymentRiskRepository for MockPaymentRiskRepository {
+    async fn save(&self, assessment: PaymentRiskAssessment) -> Result<PaymentRiskAssessment, DomainError> {
+        self.assessments.lock().unwrap().push(assessment.clone());
+        Ok(assessment)
+    }
+
+    async fn update_review(&self, assessment: &PaymentRiskAssessment) -> Result<(), DomainError> {
+        let mut assessments = self.assessments.lock().unwrap();
+        if let Some(existing) = assessments.iter_mut().find(|a| a.id == assessment.id) {
+            *existing = assessment.clone();
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentRiskAssessment>, DomainError> {
+        Ok(self.assessments.lock().unwrap().iter().find(|a| a.id == id).cloned())
+    }
+
+    async fn find_pending_reviews(&self, limit: usize) -> Result<Vec<PaymentRiskAssessment>, DomainError> {
+        Ok(self
+            .assessments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.review_status == ReviewStatus::Pending)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_card_fingerprints(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<String>, DomainError> {
+        let mut cards: Vec<String> = self
+            .assessments
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|a| a.user_id == user_id && a.created_at >= since)
+            .filter_map(|a| a.card_fingerprint.clone())
+            .collect();
+        cards.sort();
+        cards.dedup();
+        Ok(cards)
+    }
+}
+
+fn service() -> PaymentRiskService<MockPaymentRiskRepository> {
+    PaymentRiskService::new(Arc::new(MockPaymentRiskRepository::default()), PaymentRiskConfig::default())
+}
+
+fn attempt(user_id: Uuid, account_age_days: i64, amount: i64, card: &str) -> PaymentAttempt {
+    PaymentAttempt {
+        user_id,
+        account_created_at: Utc::now() - Duration::days(account_age_days),
+        amount,
+        currency: "aud".to_string(),
+        market: "AU".to_string(),
+        billing_country: Some("au".to_string()),
+        card_fingerprint: Some(card.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_ordinary_payment_is_allowed() {
+    let assessment = service().assess(attempt(Uuid::new_v4(), 30, 500_000, "card_1")).await.unwrap();
+
+    assert!(assessment.triggered_rules.is_empty());
+    assert_eq!(assessment.action, RiskAction::Allow);
+    assert!(assessment.permits_payment());
+}
+
+#[tokio::test]
+async fn test_new_account_paying_a_high_amount_is_held_for_review() {
+    let service = service();
+    let reviewer = Uuid::new_v4();
+
+    let assessment = service.assess(attempt(Uuid::new_v4(), 1, 250_000, "card_1")).await.unwrap();
+
+    assert_eq!(assessment.triggered_rules, vec![PaymentRiskRule::NewAccountHighAmount]);
+    assert_eq!(assessment.review_status, ReviewStatus::Pending);
+    assert!(!assessment.permits_payment());
+    assert_eq!(service.pending_reviews(10).await.unwrap().len(), 1);
+
+    let decided = service
+        .decide_review(assessment.id, true, reviewer, Some("Verified by phone".to_string()))
+        .await
+        .unwrap();
+    assert!(decided.permits_payment());
+    assert_eq!(decided.reviewed_by, Some(reviewer));
+    assert!(service.pending_reviews(10).await.unwrap().is_empty());
+
+    let again = service.decide_review(assessment.id, false, reviewer, None).await;
+    assert!(matches!(again, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_cycling_through_cards_is_blocked() {
+    let service = service();
+    let user_id = Uuid::new_v4();
+
+    for card in ["card_1", "card_2", "card_3", "card_1"] {
+        let assessment = service.assess(attempt(user_id, 30, 1_000, card)).await.unwrap();
+        assert_eq!(assessment.action, RiskAction::Allow);
+    }
+
+    let assessment = service.assess(attempt(user_id, 30, 1_000, "card_4")).await.unwrap();
+    assert_eq!(assessment.triggered_rules, vec![PaymentRiskRule::TooManyCards]);
+    assert_eq!(assessment.action, RiskAction::Block);
+    assert!(!assessment.permits_payment());
+}
+
+#[tokio::test]
+async fn test_foreign_billing_country_requires_three_ds() {
+    let service = service();
+    let mut foreign = attempt(Uuid::new_v4(), 30, 1_000, "card_1");
+    foreign.billing_country = Some("US".to_string());
+
+    let assessment = service.assess(foreign).await.unwrap();
+
+    assert_eq!(assessment.triggered_rules, vec![PaymentRiskRule::BillingCountryMismatch]);
+    assert!(assessment.requires_three_ds());
+    assert!(assessment.permits_payment());
+}
+
+#[tokio::test]
+async fn test_most_severe_action_wins() {
+    let service = service();
+    let mut risky = attempt(Uuid::new_v4(), 1, 250_000, "card_1");
+    risky.billing_country = Some("US".to_string());
+
+    let assessment = service.assess(risky).await.unwrap();
+
+    assert_eq!(assessment.triggered_rules.len(), 2);
+    assert_eq!(assessment.action, RiskAction::Review);
+}
@@ -0,0 +1,19 @@
+//! Configuration for the payment webhook service
+
+/// Configuration for the payment webhook service
+#[derive(Debug, Clone)]
+pub struct PaymentWebhookConfig {
+    /// Whether refunds freeze pending payouts; chargebacks always do
+    pub freeze_payouts_on_refund: bool,
+    /// Whether to notify the customer and the worker
+    pub notify_parties: bool,
+}
+
+impl Default for PaymentWebhookConfig {
+    fn default() -> Self {
+        Self {
+            freeze_payouts_on_refund: true, // Worker earnings may need adjusting
+            notify_parties: true,
+        }
+    }
+}
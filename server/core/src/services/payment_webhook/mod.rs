@@ -0,0 +1,21 @@
+//! Payment webhook service module for payment confirmations, refunds and chargebacks reported by providers
+//!
+//! This module handles:
+//! - Verifying provider webhook signatures before anything else
+//! - Recording every verified webhook with its full payload for audit
+//! - Capturing or failing payments that waited for 3-D Secure authentication
+//! - Updating payment and ledger state for refunds and chargebacks
+//! - Freezing the worker payouts funded by an affected payment
+//! - Opening and resolving disputes, notifying both the customer and the worker
+//! - Dead-lettering events that fail to be processed and replaying them
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::PaymentWebhookConfig;
+pub use service::{PaymentWebhookService, WebhookOutcome};
+pub use traits::PaymentWebhookProviderTrait;
@@ -0,0 +1,716 @@
+//! Payment webhook service for payment confirmations, refunds and chargebacks
+//!
+//! Webhooks are only acted on after the provider adapter has verified their
+//! signature. Each verified webhook is stored with its full payload before
+//! it is processed and marked processed afterwards, so a redelivery of a
+//! handled event is dropped while a delivery that failed half-way is
+//! retried. Payments waiting for 3-D Secure are captured or failed when the
+//! provider confirms the outcome of authentication. Chargebacks open a dispute and freeze the worker's pending
+//! payouts; payouts stay frozen after a dispute is decided until an
+//! administrator releases them. Refunds and chargebacks are recorded both in
+//! the provider ledger used for reconciliation and in the platform's
+//! double-entry books.
+//!
+//! Webhooks are received for every tenant at once: each event is handled in
+//! the tenant of the payment or dispute it refers to, or in the default
+//! tenant when the platform does not know it.
+//!
+//! Events that fail to be processed after their signature was verified are
+//! recorded as dead letters when a dead-letter repository is configured.
+//! A dead letter is resolved once the provider redelivers the event
+//! successfully, or when an administrator replays it.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::dispute::Dispute;
+use crate::domain::entities::payment::{
+    LedgerEntry, LedgerEntryKind, Payment, PaymentStatus, PaymentTimelineEntry,
+};
+use crate::domain::entities::payment_webhook::{
+    ParsedWebhookEvent, PaymentWebhookAction, PaymentWebhookEvent,
+};
+use crate::domain::entities::webhook_dead_letter::{WebhookDeadLetter, WebhookDirection};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{
+    DisputeRepository, JournalRepository, PaymentLedgerRepository, PaymentRepository,
+    PaymentWebhookEventRepository, PayoutRepository, WebhookDeadLetterRepository,
+};
+use crate::services::campaign::NotificationSenderTrait;
+use crate::services::ledger::LedgerService;
+use crate::services::tenant::{self, TenantId};
+use crate::services::webhook_dead_letter::WebhookReplayTrait;
+
+use super::config::PaymentWebhookConfig;
+use super::traits::PaymentWebhookProviderTrait;
+
+/// How a webhook was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookOutcome {
+    /// The event was already processed
+    Duplicate,
+    /// The event type is not acted on
+    Ignored,
+    /// The event refers to a payment the platform does not know
+    PaymentNotFound,
+    /// The event refers to a dispute the platform does not know
+    DisputeNotFound,
+    /// A payment waiting for authentication was captured
+    PaymentConfirmed,
+    /// A payment waiting for authentication failed
+    PaymentFailed,
+    /// A refund was applied to the payment and the ledger
+    RefundRecorded,
+    /// A chargeback opened a dispute
+    DisputeOpened,
+    /// A dispute was decided
+    DisputeResolved,
+}
+
+impl WebhookOutcome {
+    /// Convert to string representation for storage and responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::Ignored => "ignored",
+            Self::PaymentNotFound => "payment_not_found",
+            Self::DisputeNotFound => "dispute_not_found",
+            Self::PaymentConfirmed => "payment_confirmed",
+            Self::PaymentFailed => "payment_failed",
+            Self::RefundRecorded => "refund_recorded",
+            Self::DisputeOpened => "dispute_opened",
+            Self::DisputeResolved => "dispute_resolved",
+        }
+    }
+}
+
+/// Service handling payment provider webhooks
+pub struct PaymentWebhookService<P, Y, L, J, D, W, V, N>
+where
+    P: PaymentRepository + 'static,
+    Y: PayoutRepository + 'static,
+    L: PaymentLedgerRepository + 'static,
+    J: JournalRepository + 'static,
+    D: DisputeRepository + 'static,
+    W: PaymentWebhookEventRepository + 'static,
+    V: PaymentWebhookProviderTrait + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    payments: Arc<P>,
+    payouts: Arc<Y>,
+    ledger: Arc<L>,
+    books: Arc<LedgerService<J>>,
+    disputes: Arc<D>,
+    webhook_events: Arc<W>,
+    provider: Arc<V>,
+    notifier: Arc<N>,
+    dead_letters: Option<Arc<dyn WebhookDeadLetterRepository>>,
+    config: PaymentWebhookConfig,
+}
+
+impl<P, Y, L, J, D, W, V, N> PaymentWebhookService<P, Y, L, J, D, W, V, N>
+where
+    P: PaymentRepository + 'static,
+    Y: PayoutRepository + 'static,
+    L: PaymentLedgerRepository + 'static,
+    J: JournalRepository + 'static,
+    D: DisputeRepository + 'static,
+    W: PaymentWebhookEventRepository + 'static,
+    V: PaymentWebhookProviderTrait + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    /// Create a new payment webhook service
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        payments: Arc<P>,
+        payouts: Arc<Y>,
+        ledger: Arc<L>,
+        books: Arc<LedgerService<J>>,
+        disputes: Arc<D>,
+        webhook_events: Arc<W>,
+        provider: Arc<V>,
+        notifier: Arc<N>,
+        config: PaymentWebhookConfig,
+    ) -> Self {
+        Self {
+            payments,
+            payouts,
+            ledger,
+            books,
+            disputes,
+            webhook_events,
+            provider,
+            notifier,
+            dead_letters: None,
+            config,
+        }
+    }
+
+    /// Record events that fail to be processed as dead letters
+    pub fn with_dead_letters(mut self, dead_letters: Arc<dyn WebhookDeadLetterRepository>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Name of the HTTP header carrying the provider's signature
+    pub fn signature_header(&self) -> &str {
+        self.provider.signature_header()
+    }
+
+    /// Verify and handle a webhook
+    ///
+    /// # Arguments
+    /// * `payload` - Raw request body, exactly as received
+    /// * `signature` - Value of the provider's signature header, if present
+    ///
+    /// # Returns
+    /// * `Ok(WebhookOutcome)` - How the webhook was handled
+    /// * `Err(DomainError::Unauthorized)` - Missing or invalid signature
+    /// * `Err(DomainError::Validation)` - The payload could not be decoded
+    /// * `Err(DomainError::BusinessRule)` - The event conflicts with the payment, e.g. an over-refund
+    pub async fn handle_webhook(
+        &self,
+        payload: &[u8],
+        signature: Option<&str>,
+    ) -> DomainResult<WebhookOutcome> {
+        let signature = signature.ok_or(DomainError::Unauthorized)?;
+        if let Err(e) = self.provider.verify_signature(payload, signature) {
+            warn!(provider = self.provider.provider_name(), error = %e, "Rejected payment webhook signature");
+            return Err(DomainError::Unauthorized);
+        }
+
+        let event = self.provider.parse_event(payload).map_err(|e| DomainError::Validation {
+            message: format!("Invalid webhook payload: {}", e),
+        })?;
+
+        match self.process_event(&event).await {
+            Ok(outcome) => Ok(outcome),
+            Err(error) => {
+                self.dead_letter(&event, &error).await;
+                Err(error)
+            }
+        }
+    }
+
+    /// Process an event again from its stored payload
+    ///
+    /// Used to replay dead letters. The signature is not checked again, as
+    /// it was verified when the event was received and has expired since.
+    /// An event processed in the meantime is reported as a duplicate.
+    ///
+    /// # Returns
+    /// * `Ok(WebhookOutcome)` - How the event was handled
+    /// * `Err(DomainError::Validation)` - The payload could not be decoded
+    /// * `Err(DomainError::BusinessRule)` - The event conflicts with the payment
+    pub async fn replay_event(&self, payload: &JsonValue) -> DomainResult<WebhookOutcome> {
+        let event = self
+            .provider
+            .parse_event(payload.to_string().as_bytes())
+            .map_err(|e| DomainError::Validation {
+                message: format!("Invalid webhook payload: {}", e),
+            })?;
+
+        self.process_event(&event).await
+    }
+
+    async fn process_event(&self, event: &ParsedWebhookEvent) -> DomainResult<WebhookOutcome> {
+        let provider = self.provider.provider_name();
+        let (record, redelivered) = match self.webhook_events.find_by_event_id(provider, &event.event_id).await? {
+            Some(record) if record.is_processed() => {
+                debug!(event_id = %event.event_id, "Dropped redelivered payment webhook");
+                return Ok(WebhookOutcome::Duplicate);
+            }
+            Some(record) => (record, true),
+            None => {
+                let record = self
+                    .webhook_events
+                    .save(PaymentWebhookEvent::received(provider, event))
+                    .await?;
+                (record, false)
+            }
+        };
+
+        let tenant = self.find_tenant(&event.action).await?;
+        let outcome = tenant::scope(tenant, self.apply(event)).await?;
+
+        self.webhook_events
+            .mark_processed(record.id, outcome.as_str(), Utc::now())
+            .await?;
+
+        info!(
+            event_id = %event.event_id,
+            event_type = %event.event_type,
+            outcome = outcome.as_str(),
+            "Payment webhook processed"
+        );
+
+        // An earlier delivery of the event failed and may have been dead-lettered
+        if redelivered {
+            if let Some(ref dead_letters) = self.dead_letters {
+                if let Err(e) = dead_letters
+                    .resolve_event(WebhookDirection::Inbound, provider, &event.event_id, outcome.as_str())
+                    .await
+                {
+                    warn!(event_id = %event.event_id, error = %e, "Failed to resolve payment webhook dead letter");
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Tenant of the payment or dispute an event refers to
+    async fn find_tenant(&self, action: &PaymentWebhookAction) -> DomainResult<TenantId> {
+        let provider = self.provider.provider_name();
+        let tenant = match action {
+            PaymentWebhookAction::Refund { payment_reference, .. }
+            | PaymentWebhookAction::DisputeOpened { payment_reference, .. } => {
+                self.payments.find_tenant_by_reference(provider, payment_reference).await?
+            }
+            PaymentWebhookAction::PaymentSucceeded { intent_reference, .. }
+            | PaymentWebhookAction::PaymentFailed { intent_reference, .. } => {
+                self.payments.find_tenant_by_reference(provider, intent_reference).await?
+            }
+            PaymentWebhookAction::DisputeClosed { dispute_reference, .. } => {
+                self.disputes
+                    .find_tenant_by_provider_dispute_id(provider, dispute_reference)
+                    .await?
+            }
+            PaymentWebhookAction::Ignored => None,
+        };
+
+        Ok(tenant.unwrap_or_default())
+    }
+
+    /// Apply an event to the payment or dispute it refers to
+    async fn apply(&self, event: &ParsedWebhookEvent) -> DomainResult<WebhookOutcome> {
+        let outcome = match &event.action {
+            PaymentWebhookAction::Refund {
+                payment_reference,
+                refund_reference,
+                amount,
+                currency,
+            } => {
+                self.handle_refund(event, payment_reference, refund_reference, *amount, currency)
+                    .await?
+            }
+            PaymentWebhookAction::DisputeOpened {
+                payment_reference,
+                dispute_reference,
+                amount,
+                currency,
+                reason,
+            } => {
+                self.handle_dispute_opened(
+                    event,
+                    payment_reference,
+                    dispute_reference,
+                    *amount,
+                    currency,
+                    reason,
+                )
+                .await?
+            }
+            PaymentWebhookAction::DisputeClosed { dispute_reference, won } => {
+                self.handle_dispute_closed(event, dispute_reference, *won).await?
+            }
+            PaymentWebhookAction::PaymentSucceeded { intent_reference, charge_reference } => {
+                self.handle_payment_succeeded(event, intent_reference, charge_reference.clone())
+                    .await?
+            }
+            PaymentWebhookAction::PaymentFailed { intent_reference, reason } => {
+                self.handle_payment_failed(event, intent_reference, reason).await?
+            }
+            PaymentWebhookAction::Ignored => WebhookOutcome::Ignored,
+        };
+
+        Ok(outcome)
+    }
+
+    /// Record an event that failed to be processed
+    ///
+    /// A failure to record the dead letter is only logged: the provider
+    /// redelivers the event anyway.
+    async fn dead_letter(&self, event: &ParsedWebhookEvent, error: &DomainError) {
+        let Some(ref dead_letters) = self.dead_letters else {
+            return;
+        };
+
+        let dead_letter = WebhookDeadLetter::inbound(
+            self.provider.provider_name(),
+            &event.event_id,
+            &event.event_type,
+            event.payload.clone(),
+            error.to_string(),
+        );
+        if let Err(e) = dead_letters.record(dead_letter).await {
+            warn!(event_id = %event.event_id, error = %e, "Failed to dead-letter payment webhook");
+        }
+    }
+
+    async fn handle_payment_succeeded(
+        &self,
+        event: &ParsedWebhookEvent,
+        intent_reference: &str,
+        charge_reference: Option<String>,
+    ) -> DomainResult<WebhookOutcome> {
+        let Some(mut payment) = self.find_intent_payment(intent_reference).await? else {
+            return Ok(WebhookOutcome::PaymentNotFound);
+        };
+        if payment.is_captured() {
+            return Ok(WebhookOutcome::PaymentConfirmed);
+        }
+
+        payment
+            .confirm(charge_reference)
+            .map_err(|message| DomainError::BusinessRule { message })?;
+        self.payments.update(&payment).await?;
+        self.payments
+            .add_timeline_entry(PaymentTimelineEntry::new(&payment, None, event.occurred_at))
+            .await?;
+        self.books.record_payment(&payment).await?;
+
+        let message = format!(
+            "Your RenovEasy payment of {} {} was confirmed.",
+            format_amount(payment.amount),
+            payment.currency
+        );
+        self.notify_parties(&payment, "Payment confirmed", &message).await;
+
+        info!(payment_id = %payment.id, "Payment confirmed after authentication");
+        Ok(WebhookOutcome::PaymentConfirmed)
+    }
+
+    async fn handle_payment_failed(
+        &self,
+        event: &ParsedWebhookEvent,
+        intent_reference: &str,
+        reason: &str,
+    ) -> DomainResult<WebhookOutcome> {
+        let Some(mut payment) = self.find_intent_payment(intent_reference).await? else {
+            return Ok(WebhookOutcome::PaymentNotFound);
+        };
+        // A late failure of an earlier attempt does not undo a capture
+        if payment.status != PaymentStatus::RequiresAction {
+            return Ok(WebhookOutcome::PaymentFailed);
+        }
+
+        payment
+            .mark_failed()
+            .map_err(|message| DomainError::BusinessRule { message })?;
+        self.payments.update(&payment).await?;
+        self.payments
+            .add_timeline_entry(PaymentTimelineEntry::new(
+                &payment,
+                Some(reason.to_string()),
+                event.occurred_at,
+            ))
+            .await?;
+
+        info!(payment_id = %payment.id, reason = %reason, "Payment authentication failed");
+        Ok(WebhookOutcome::PaymentFailed)
+    }
+
+    async fn handle_refund(
+        &self,
+        event: &ParsedWebhookEvent,
+        payment_reference: &str,
+        refund_reference: &str,
+        amount: i64,
+        currency: &str,
+    ) -> DomainResult<WebhookOutcome> {
+        let Some(mut payment) = self.find_payment(payment_reference).await? else {
+            return Ok(WebhookOutcome::PaymentNotFound);
+        };
+        Self::check_currency(&payment, currency)?;
+
+        payment
+            .apply_refund(amount)
+            .map_err(|message| DomainError::BusinessRule { message })?;
+        self.payments.update(&payment).await?;
+
+        self.record_ledger_entry(
+            refund_reference,
+            LedgerEntryKind::Refund,
+            -amount,
+            &payment,
+            event,
+            format!("Refund of payment {}", payment.provider_reference),
+        )
+        .await?;
+        self.books
+            .record_refund(&payment, refund_reference, amount, event.occurred_at)
+            .await?;
+
+        if self.config.freeze_payouts_on_refund {
+            self.freeze_payouts(&payment, &format!("Refund {}", refund_reference))
+                .await?;
+        }
+
+        let message = format!(
+            "A refund of {} {} was issued for your RenovEasy payment.",
+            format_amount(amount),
+            payment.currency
+        );
+        self.notify_parties(&payment, "Payment refunded", &message).await;
+
+        Ok(WebhookOutcome::RefundRecorded)
+    }
+
+    async fn handle_dispute_opened(
+        &self,
+        event: &ParsedWebhookEvent,
+        payment_reference: &str,
+        dispute_reference: &str,
+        amount: i64,
+        currency: &str,
+        reason: &str,
+    ) -> DomainResult<WebhookOutcome> {
+        let provider = self.provider.provider_name();
+        if self
+            .disputes
+            .find_by_provider_dispute_id(provider, dispute_reference)
+            .await?
+            .is_some()
+        {
+            return Ok(WebhookOutcome::DisputeOpened);
+        }
+
+        let Some(mut payment) = self.find_payment(payment_reference).await? else {
+            return Ok(WebhookOutcome::PaymentNotFound);
+        };
+        Self::check_currency(&payment, currency)?;
+
+        payment.mark_disputed();
+        self.payments.update(&payment).await?;
+
+        // The provider withdraws the disputed amount from the balance
+        self.record_ledger_entry(
+            dispute_reference,
+            LedgerEntryKind::Adjustment,
+            -amount,
+            &payment,
+            event,
+            format!("Chargeback of payment {}", payment.provider_reference),
+        )
+        .await?;
+        self.books
+            .record_chargeback(&payment, dispute_reference, amount, event.occurred_at)
+            .await?;
+
+        let dispute = self
+            .disputes
+            .save(Dispute::open(&payment, dispute_reference, reason, amount, event.occurred_at))
+            .await?;
+
+        self.freeze_payouts(&payment, &format!("Chargeback {}", dispute_reference))
+            .await?;
+
+        let message = format!(
+            "Your bank disputed a RenovEasy payment of {} {}. Payouts for this job are on hold while we investigate.",
+            format_amount(amount),
+            payment.currency
+        );
+        self.notify_parties(&payment, "Payment disputed", &message).await;
+
+        info!(dispute_id = %dispute.id, payment_id = %payment.id, "Dispute opened");
+        Ok(WebhookOutcome::DisputeOpened)
+    }
+
+    async fn handle_dispute_closed(
+        &self,
+        event: &ParsedWebhookEvent,
+        dispute_reference: &str,
+        won: bool,
+    ) -> DomainResult<WebhookOutcome> {
+        let provider = self.provider.provider_name();
+        let Some(mut dispute) = self
+            .disputes
+            .find_by_provider_dispute_id(provider, dispute_reference)
+            .await?
+        else {
+            warn!(dispute_reference = %dispute_reference, "Payment webhook for unknown dispute");
+            return Ok(WebhookOutcome::DisputeNotFound);
+        };
+
+        if !dispute.resolve(won, event.occurred_at) {
+            return Ok(WebhookOutcome::DisputeResolved);
+        }
+        self.disputes.update(&dispute).await?;
+
+        let Some(mut payment) = self.payments.find_by_id(dispute.payment_id).await? else {
+            return Err(DomainError::Internal {
+                message: format!("Payment {} of dispute {} not found", dispute.payment_id, dispute.id),
+            });
+        };
+
+        if won {
+            // The provider returns the disputed amount to the balance
+            self.record_ledger_entry(
+                dispute_reference,
+                LedgerEntryKind::Adjustment,
+                dispute.amount,
+                &payment,
+                event,
+                format!("Won chargeback of payment {}", payment.provider_reference),
+            )
+            .await?;
+            self.books
+                .reverse_chargeback(dispute_reference, event.occurred_at)
+                .await?;
+
+            let still_disputed = self
+                .disputes
+                .find_by_payment(payment.id)
+                .await?
+                .iter()
+                .any(|d| d.id != dispute.id && d.is_open());
+            if !still_disputed {
+                payment.clear_dispute();
+                self.payments.update(&payment).await?;
+            }
+        }
+
+        let message = if won {
+            "The dispute on your RenovEasy payment was resolved in favour of the payment."
+        } else {
+            "The dispute on your RenovEasy payment was resolved in favour of the cardholder."
+        };
+        self.notify_parties(&payment, "Payment dispute resolved", message).await;
+
+        info!(dispute_id = %dispute.id, won = won, "Dispute resolved");
+        Ok(WebhookOutcome::DisputeResolved)
+    }
+
+    async fn find_payment(&self, payment_reference: &str) -> DomainResult<Option<Payment>> {
+        let payment = self
+            .payments
+            .find_by_provider_reference(self.provider.provider_name(), payment_reference)
+            .await?;
+
+        if payment.is_none() {
+            warn!(payment_reference = %payment_reference, "Payment webhook for unknown payment");
+        }
+
+        Ok(payment)
+    }
+
+    async fn find_intent_payment(&self, intent_reference: &str) -> DomainResult<Option<Payment>> {
+        let payment = self
+            .payments
+            .find_by_intent_reference(self.provider.provider_name(), intent_reference)
+            .await?;
+
+        if payment.is_none() {
+            warn!(intent_reference = %intent_reference, "Payment webhook for unknown payment intent");
+        }
+
+        Ok(payment)
+    }
+
+    fn check_currency(payment: &Payment, currency: &str) -> DomainResult<()> {
+        if !payment.currency.eq_ignore_ascii_case(currency) {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Webhook currency {} does not match payment currency {}",
+                    currency, payment.currency
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    async fn record_ledger_entry(
+        &self,
+        reference: &str,
+        kind: LedgerEntryKind,
+        amount: i64,
+        payment: &Payment,
+        event: &ParsedWebhookEvent,
+        description: String,
+    ) -> DomainResult<()> {
+        let mut entry = LedgerEntry::new(
+            self.provider.provider_name(),
+            reference,
+            kind,
+            amount,
+            &payment.currency,
+            event.occurred_at,
+        )
+        .map_err(|message| DomainError::Validation { message })?;
+        entry.description = Some(description);
+
+        self.ledger.save(entry).await?;
+        Ok(())
+    }
+
+    /// Freeze the pending payouts funded by a payment
+    async fn freeze_payouts(&self, payment: &Payment, reason: &str) -> DomainResult<usize> {
+        let mut frozen = 0;
+        for mut payout in self.payouts.find_by_payment(payment.id).await? {
+            if payout.freeze(reason) {
+                self.payouts.update(&payout).await?;
+                frozen += 1;
+            }
+        }
+
+        if frozen > 0 {
+            info!(payment_id = %payment.id, frozen = frozen, reason = %reason, "Payouts frozen");
+        }
+        Ok(frozen)
+    }
+
+    /// Notify the customer and the worker; failures are logged, not returned
+    async fn notify_parties(&self, payment: &Payment, title: &str, body: &str) {
+        if !self.config.notify_parties {
+            return;
+        }
+
+        for user_id in [payment.customer_id, payment.worker_id] {
+            if let Err(e) = self
+                .notifier
+                .send_notification(user_id, Uuid::new_v4(), title, body)
+                .await
+            {
+                warn!(user_id = %user_id, payment_id = %payment.id, error = %e, "Failed to send payment notification");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<P, Y, L, J, D, W, V, N> WebhookReplayTrait for PaymentWebhookService<P, Y, L, J, D, W, V, N>
+where
+    P: PaymentRepository + 'static,
+    Y: PayoutRepository + 'static,
+    L: PaymentLedgerRepository + 'static,
+    J: JournalRepository + 'static,
+    D: DisputeRepository + 'static,
+    W: PaymentWebhookEventRepository + 'static,
+    V: PaymentWebhookProviderTrait + 'static,
+    N: NotificationSenderTrait + 'static,
+{
+    fn direction(&self) -> WebhookDirection {
+        WebhookDirection::Inbound
+    }
+
+    fn source(&self) -> &str {
+        self.provider.provider_name()
+    }
+
+    async fn replay(&self, dead_letter: &WebhookDeadLetter) -> Result<String, String> {
+        self.replay_event(&dead_letter.payload)
+            .await
+            .map(|outcome| outcome.as_str().to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Format an amount in minor units with two decimal places
+fn format_amount(amount: i64) -> String {
+    format!("{}.{:02}", amount / 100, (amount % 100).abs())
+}
@@ -0,0 +1,770 @@
+//! Unit tests for the payment webhook service
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::dispute::{Dispute, DisputeStatus};
+use crate::domain::entities::payment::{
+    LedgerEntry, LedgerEntryKind, Payment, PaymentStatus, PaymentTimelineEntry, Payout, PayoutStatus,
+};
+use crate::domain::entities::payment_webhook::{
+    ParsedWebhookEvent, PaymentWebhookAction, PaymentWebhookEvent,
+};
+use crate::domain::entities::webhook_dead_letter::{DeadLetterStatus, WebhookDirection};
+use crate::errors::DomainError;
+use crate::repositories::{
+    DisputeRepository, PaymentLedgerRepository, PaymentRepository, PaymentWebhookEventRepository,
+    PayoutRepository,
+};
+use crate::services::campaign::NotificationSenderTrait;
+use crate::services::ledger::tests::service_tests::MockJournal;
+use crate::services::ledger::{LedgerConfig, LedgerService};
+use crate::services::tenant::{self, current_tenant, TenantId};
+use crate::services::payment_webhook::{
+    PaymentWebhookConfig, PaymentWebhookProviderTrait, PaymentWebhookService, WebhookOutcome,
+};
+use crate::services::webhook_dead_letter::tests::service_tests::MockDeadLetters;
+use crate::services::webhook_dead_letter::WebhookReplayTrait;
+
+const SIGNATURE: &str = "valid-signature";
+
+#[derive(Default)]
+pub(crate) struct MockPayments {
+    pub(crate) payments: Mutex<HashMap<Uuid, Payment>>,
+    pub(crate) timeline: Mutex<Vec<PaymentTimelineEntry>>,
+    /// Tenant each payment was saved in
+    pub(crate) tenants: Mutex<HashMap<Uuid, TenantId>>,
+}
+
+impl MockPayments {
+    fn in_current_tenant(&self, payment: &Payment) -> bool {
+        self.tenants.lock().unwrap().get(&payment.id).cloned().unwrap_or_default() == current_tenant()
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for MockPayments {
+    async fn save(&self, payment: Payment) -> Result<Payment, DomainError> {
+        self.tenants.lock().unwrap().insert(payment.id, current_tenant());
+        self.payments.lock().unwrap().insert(payment.id, payment.clone());
+        Ok(payment)
+    }
+
+    async fn find_by_provider_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<Payment>, DomainError> {
+        Ok(self
+            .payments
+            .lock()
+            .unwrap()
+            .values()
+            .find(|p| {
+                p.provider == provider
+                    && p.provider_reference == provider_reference
+                    && self.in_current_tenant(p)
+            })
+            .cloned())
+    }
+
+    async fn find_by_intent_reference(
+        &self,
+        provider: &str,
+        intent_reference: &str,
+    ) -> Result<Option<Payment>, DomainError> {
+        Ok(self
+            .payments
+            .lock()
+            .unwrap()
+            .values()
+            .find(|p| {
+                p.provider == provider
+                    && p.intent_reference.as_deref() == Some(intent_reference)
+                    && self.in_current_tenant(p)
+            })
+            .cloned())
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        let payment_id = self
+            .payments
+            .lock()
+            .unwrap()
+            .values()
+            .find(|p| {
+                p.provider == provider
+                    && (p.provider_reference == reference
+                        || p.intent_reference.as_deref() == Some(reference))
+            })
+            .map(|p| p.id);
+        Ok(payment_id.map(|id| self.tenants.lock().unwrap().get(&id).cloned().unwrap_or_default()))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, DomainError> {
+        Ok(self.payments.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_payment_method(
+        &self,
+        customer_id: Uuid,
+        payment_method: &str,
+    ) -> Result<Vec<Payment>, DomainError> {
+        Ok(self
+            .payments
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|p| p.customer_id == customer_id && p.payment_method.as_deref() == Some(payment_method))
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, payment: &Payment) -> Result<(), DomainError> {
+        self.payments.lock().unwrap().insert(payment.id, payment.clone());
+        Ok(())
+    }
+
+    async fn add_timeline_entry(
+        &self,
+        entry: PaymentTimelineEntry,
+    ) -> Result<PaymentTimelineEntry, DomainError> {
+        self.timeline.lock().unwrap().push(entry.clone());
+        Ok(entry)
+    }
+
+    async fn find_timeline(&self, payment_id: Uuid) -> Result<Vec<PaymentTimelineEntry>, DomainError> {
+        Ok(self
+            .timeline
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MockPayouts {
+    pub(crate) payouts: Mutex<HashMap<Uuid, Payout>>,
+}
+
+#[async_trait]
+impl PayoutRepository for MockPayouts {
+    async fn save(&self, payout: Payout) -> Result<Payout, DomainError> {
+        self.payouts.lock().unwrap().insert(payout.id, payout.clone());
+        Ok(payout)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payout>, DomainError> {
+        Ok(self.payouts.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Payout>, DomainError> {
+        Ok(self
+            .payouts
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|p| p.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, payout: &Payout) -> Result<(), DomainError> {
+        self.payouts.lock().unwrap().insert(payout.id, payout.clone());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct MockLedger {
+    entries: Mutex<Vec<LedgerEntry>>,
+}
+
+#[async_trait]
+impl PaymentLedgerRepository for MockLedger {
+    async fn save(&self, entry: LedgerEntry) -> Result<LedgerEntry, DomainError> {
+        self.entries.lock().unwrap().push(entry.clone());
+        Ok(entry)
+    }
+
+    async fn find_by_period(
+        &self,
+        _provider: &str,
+        _start: DateTime<Utc>,
+        _end: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, DomainError> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+}
+
+#[derive(Default)]
+struct MockDisputes {
+    disputes: Mutex<HashMap<Uuid, Dispute>>,
+}
+
+#[async_trait]
+impl DisputeRepository for MockDisputes {
+    async fn save(&self, dispute: Dispute) -> Result<Dispute, DomainError> {
+        self.disputes.lock().unwrap().insert(dispute.id, dispute.clone());
+        Ok(dispute)
+    }
+
+    async fn find_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<Dispute>, DomainError> {
+        Ok(self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .find(|d| d.provider == provider && d.provider_dispute_id == provider_dispute_id)
+            .cloned())
+    }
+
+    async fn find_tenant_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        Ok(self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .any(|d| d.provider == provider && d.provider_dispute_id == provider_dispute_id)
+            .then(TenantId::default))
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Dispute>, DomainError> {
+        Ok(self
+            .disputes
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|d| d.payment_id == payment_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, dispute: &Dispute) -> Result<(), DomainError> {
+        self.disputes.lock().unwrap().insert(dispute.id, dispute.clone());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct MockWebhookEvents {
+    events: Mutex<Vec<PaymentWebhookEvent>>,
+}
+
+#[async_trait]
+impl PaymentWebhookEventRepository for MockWebhookEvents {
+    async fn save(&self, event: PaymentWebhookEvent) -> Result<PaymentWebhookEvent, DomainError> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(event)
+    }
+
+    async fn find_by_event_id(
+        &self,
+        provider: &str,
+        event_id: &str,
+    ) -> Result<Option<PaymentWebhookEvent>, DomainError> {
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|e| e.provider == provider && e.event_id == event_id)
+            .cloned())
+    }
+
+    async fn mark_processed(
+        &self,
+        id: Uuid,
+        outcome: &str,
+        processed_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        if let Some(event) = self.events.lock().unwrap().iter_mut().find(|e| e.id == id) {
+            event.outcome = Some(outcome.to_string());
+            event.processed_at = Some(processed_at);
+        }
+        Ok(())
+    }
+}
+
+/// Accepts a fixed signature and decodes the payload as an action
+struct MockProvider;
+
+impl PaymentWebhookProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    fn signature_header(&self) -> &str {
+        "Stripe-Signature"
+    }
+
+    fn verify_signature(&self, _payload: &[u8], signature: &str) -> Result<(), String> {
+        if signature == SIGNATURE {
+            Ok(())
+        } else {
+            Err("No matching signature".to_string())
+        }
+    }
+
+    fn parse_event(&self, payload: &[u8]) -> Result<ParsedWebhookEvent, String> {
+        let payload: serde_json::Value = serde_json::from_slice(payload).map_err(|e| e.to_string())?;
+        let action = serde_json::from_value(payload["action"].clone()).map_err(|e| e.to_string())?;
+
+        Ok(ParsedWebhookEvent {
+            event_id: payload["id"].as_str().unwrap_or_default().to_string(),
+            event_type: "test".to_string(),
+            occurred_at: Utc.with_ymd_and_hms(2025, 8, 1, 12, 0, 0).unwrap(),
+            action,
+            payload,
+        })
+    }
+}
+
+#[derive(Default)]
+struct MockNotifier {
+    sent: Mutex<Vec<(Uuid, String)>>,
+}
+
+#[async_trait]
+impl NotificationSenderTrait for MockNotifier {
+    async fn send_notification(
+        &self,
+        user_id: Uuid,
+        _delivery_id: Uuid,
+        title: &str,
+        _body: &str,
+    ) -> Result<String, String> {
+        self.sent.lock().unwrap().push((user_id, title.to_string()));
+        Ok("mock-notification".to_string())
+    }
+}
+
+type TestService = PaymentWebhookService<
+    MockPayments,
+    MockPayouts,
+    MockLedger,
+    MockJournal,
+    MockDisputes,
+    MockWebhookEvents,
+    MockProvider,
+    MockNotifier,
+>;
+
+struct Fixture {
+    service: TestService,
+    payments: Arc<MockPayments>,
+    payouts: Arc<MockPayouts>,
+    ledger: Arc<MockLedger>,
+    books: Arc<LedgerService<MockJournal>>,
+    disputes: Arc<MockDisputes>,
+    events: Arc<MockWebhookEvents>,
+    notifier: Arc<MockNotifier>,
+    payment: Payment,
+    payout: Payout,
+}
+
+async fn fixture() -> Fixture {
+    let payments = Arc::new(MockPayments::default());
+    let payouts = Arc::new(MockPayouts::default());
+    let ledger = Arc::new(MockLedger::default());
+    let books = Arc::new(LedgerService::new(Arc::new(MockJournal::default()), LedgerConfig::default()));
+    let disputes = Arc::new(MockDisputes::default());
+    let events = Arc::new(MockWebhookEvents::default());
+    let notifier = Arc::new(MockNotifier::default());
+
+    let payment = payments
+        .save(Payment::new("stripe", "ch_1", Uuid::new_v4(), Uuid::new_v4(), 10_000, "AUD").unwrap())
+        .await
+        .unwrap();
+    let payout = payouts.save(Payout::new(&payment, 8_500)).await.unwrap();
+    books.record_payment(&payment).await.unwrap();
+
+    let service = PaymentWebhookService::new(
+        payments.clone(),
+        payouts.clone(),
+        ledger.clone(),
+        books.clone(),
+        disputes.clone(),
+        events.clone(),
+        Arc::new(MockProvider),
+        notifier.clone(),
+        PaymentWebhookConfig::default(),
+    );
+
+    Fixture { service, payments, payouts, ledger, books, disputes, events, notifier, payment, payout }
+}
+
+fn webhook(event_id: &str, action: PaymentWebhookAction) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({ "id": event_id, "action": action })).unwrap()
+}
+
+fn dispute_opened() -> PaymentWebhookAction {
+    PaymentWebhookAction::DisputeOpened {
+        payment_reference: "ch_1".to_string(),
+        dispute_reference: "dp_1".to_string(),
+        amount: 10_000,
+        currency: "aud".to_string(),
+        reason: "fraudulent".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_missing_or_invalid_signature() {
+    let f = fixture().await;
+    let payload = webhook("evt_1", dispute_opened());
+
+    for signature in [None, Some("forged")] {
+        let result = f.service.handle_webhook(&payload, signature).await;
+        assert!(matches!(result, Err(DomainError::Unauthorized)));
+    }
+    assert!(f.events.events.lock().unwrap().is_empty());
+    assert!(f.disputes.disputes.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_refund_updates_payment_ledger_and_payouts() {
+    let f = fixture().await;
+    let payload = webhook(
+        "evt_1",
+        PaymentWebhookAction::Refund {
+            payment_reference: "ch_1".to_string(),
+            refund_reference: "re_1".to_string(),
+            amount: 2_500,
+            currency: "AUD".to_string(),
+        },
+    );
+
+    let outcome = f.service.handle_webhook(&payload, Some(SIGNATURE)).await.unwrap();
+    assert_eq!(outcome, WebhookOutcome::RefundRecorded);
+
+    let payment = f.payments.find_by_id(f.payment.id).await.unwrap().unwrap();
+    assert_eq!(payment.status, PaymentStatus::PartiallyRefunded);
+    assert_eq!(payment.refunded_amount, 2_500);
+
+    let entries = f.ledger.entries.lock().unwrap().clone();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].provider_reference, "re_1");
+    assert_eq!(entries[0].kind, LedgerEntryKind::Refund);
+    assert_eq!(entries[0].amount, -2_500);
+
+    // The refund is clawed back from the worker's earnings in the books
+    assert_eq!(f.books.worker_balance(f.payment.worker_id, "AUD").await.unwrap(), 6_750);
+    assert!(f.books.verify_books().await.unwrap().is_balanced());
+
+    let payout = f.payouts.payouts.lock().unwrap()[&f.payout.id].clone();
+    assert_eq!(payout.status, PayoutStatus::Frozen);
+
+    let notified: Vec<Uuid> = f.notifier.sent.lock().unwrap().iter().map(|(id, _)| *id).collect();
+    assert_eq!(notified, vec![f.payment.customer_id, f.payment.worker_id]);
+
+    // The full payload is kept with the outcome for audit
+    let events = f.events.events.lock().unwrap().clone();
+    assert_eq!(events[0].payload["action"]["refund_reference"], "re_1");
+    assert_eq!(events[0].outcome.as_deref(), Some("refund_recorded"));
+}
+
+#[tokio::test]
+async fn test_chargeback_opens_dispute_and_freezes_payouts() {
+    let f = fixture().await;
+
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_1", dispute_opened()), Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(outcome, WebhookOutcome::DisputeOpened);
+
+    let payment = f.payments.find_by_id(f.payment.id).await.unwrap().unwrap();
+    assert_eq!(payment.status, PaymentStatus::Disputed);
+
+    let dispute = f.disputes.find_by_provider_dispute_id("stripe", "dp_1").await.unwrap().unwrap();
+    assert_eq!(dispute.payment_id, f.payment.id);
+    assert_eq!(dispute.status, DisputeStatus::Open);
+    assert_eq!(dispute.reason, "fraudulent");
+
+    let entries = f.ledger.entries.lock().unwrap().clone();
+    assert_eq!(entries[0].kind, LedgerEntryKind::Adjustment);
+    assert_eq!(entries[0].amount, -10_000);
+    assert_eq!(f.books.worker_balance(f.payment.worker_id, "AUD").await.unwrap(), 0);
+
+    let payout = f.payouts.payouts.lock().unwrap()[&f.payout.id].clone();
+    assert_eq!(payout.status, PayoutStatus::Frozen);
+    assert_eq!(payout.frozen_reason.as_deref(), Some("Chargeback dp_1"));
+    assert_eq!(f.notifier.sent.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_redelivered_event_is_processed_once() {
+    let f = fixture().await;
+    let payload = webhook("evt_1", dispute_opened());
+
+    f.service.handle_webhook(&payload, Some(SIGNATURE)).await.unwrap();
+    let outcome = f.service.handle_webhook(&payload, Some(SIGNATURE)).await.unwrap();
+
+    assert_eq!(outcome, WebhookOutcome::Duplicate);
+    assert_eq!(f.disputes.disputes.lock().unwrap().len(), 1);
+    assert_eq!(f.ledger.entries.lock().unwrap().len(), 1);
+    assert_eq!(f.events.events.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_won_dispute_restores_payment_and_keeps_payouts_frozen() {
+    let f = fixture().await;
+    f.service
+        .handle_webhook(&webhook("evt_1", dispute_opened()), Some(SIGNATURE))
+        .await
+        .unwrap();
+
+    let closed = PaymentWebhookAction::DisputeClosed {
+        dispute_reference: "dp_1".to_string(),
+        won: true,
+    };
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_2", closed), Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(outcome, WebhookOutcome::DisputeResolved);
+
+    let dispute = f.disputes.find_by_provider_dispute_id("stripe", "dp_1").await.unwrap().unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Won);
+
+    let payment = f.payments.find_by_id(f.payment.id).await.unwrap().unwrap();
+    assert_eq!(payment.status, PaymentStatus::Succeeded);
+
+    let ledger_total: i64 = f.ledger.entries.lock().unwrap().iter().map(|e| e.amount).sum();
+    assert_eq!(ledger_total, 0);
+    assert_eq!(f.books.worker_balance(f.payment.worker_id, "AUD").await.unwrap(), 9_000);
+
+    let payout = f.payouts.payouts.lock().unwrap()[&f.payout.id].clone();
+    assert_eq!(payout.status, PayoutStatus::Frozen);
+}
+
+#[tokio::test]
+async fn test_unknown_payment_is_recorded_without_changes() {
+    let f = fixture().await;
+    let refund = PaymentWebhookAction::Refund {
+        payment_reference: "ch_unknown".to_string(),
+        refund_reference: "re_1".to_string(),
+        amount: 100,
+        currency: "AUD".to_string(),
+    };
+
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_1", refund), Some(SIGNATURE))
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, WebhookOutcome::PaymentNotFound);
+    assert!(f.ledger.entries.lock().unwrap().is_empty());
+    assert!(f.notifier.sent.lock().unwrap().is_empty());
+    assert_eq!(
+        f.events.events.lock().unwrap()[0].outcome.as_deref(),
+        Some("payment_not_found")
+    );
+}
+
+#[tokio::test]
+async fn test_event_is_handled_in_the_tenant_of_its_payment() {
+    let f = fixture().await;
+    let tenant_a = TenantId::parse("tenant-a").unwrap();
+    let payment = tenant::scope(
+        tenant_a.clone(),
+        f.payments.save(Payment::new("stripe", "ch_a", Uuid::new_v4(), Uuid::new_v4(), 5_000, "AUD").unwrap()),
+    )
+    .await
+    .unwrap();
+    let refund = PaymentWebhookAction::Refund {
+        payment_reference: "ch_a".to_string(),
+        refund_reference: "re_a".to_string(),
+        amount: 1_000,
+        currency: "AUD".to_string(),
+    };
+
+    // The webhook arrives outside any tenant but finds the tenant's payment
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_1", refund), Some(SIGNATURE))
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, WebhookOutcome::RefundRecorded);
+    let payment = f.payments.find_by_id(payment.id).await.unwrap().unwrap();
+    assert_eq!(payment.refunded_amount, 1_000);
+}
+
+#[tokio::test]
+async fn test_over_refund_is_rejected_and_left_unprocessed() {
+    let f = fixture().await;
+    let refund = PaymentWebhookAction::Refund {
+        payment_reference: "ch_1".to_string(),
+        refund_reference: "re_1".to_string(),
+        amount: 10_001,
+        currency: "AUD".to_string(),
+    };
+
+    let result = f
+        .service
+        .handle_webhook(&webhook("evt_1", refund), Some(SIGNATURE))
+        .await;
+
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(!f.events.events.lock().unwrap()[0].is_processed());
+    assert!(f.ledger.entries.lock().unwrap().is_empty());
+}
+
+async fn awaiting_payment(f: &Fixture) -> Payment {
+    f.payments
+        .save(
+            Payment::awaiting_authentication("stripe", "pi_1", Uuid::new_v4(), Uuid::new_v4(), 10_000, "AUD")
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_payment_succeeded_captures_authenticated_payment() {
+    let f = fixture().await;
+    let payment = awaiting_payment(&f).await;
+    let succeeded = PaymentWebhookAction::PaymentSucceeded {
+        intent_reference: "pi_1".to_string(),
+        charge_reference: Some("ch_2".to_string()),
+    };
+
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_1", succeeded.clone()), Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(outcome, WebhookOutcome::PaymentConfirmed);
+
+    let stored = f.payments.find_by_id(payment.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, PaymentStatus::Succeeded);
+    assert_eq!(stored.provider_reference, "ch_2");
+    assert_eq!(f.books.worker_balance(payment.worker_id, "AUD").await.unwrap(), 9_000);
+
+    let timeline = f.payments.find_timeline(payment.id).await.unwrap();
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0].status, PaymentStatus::Succeeded);
+
+    // A second confirmation of the same intent changes nothing
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_2", succeeded), Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(outcome, WebhookOutcome::PaymentConfirmed);
+    assert_eq!(f.payments.find_timeline(payment.id).await.unwrap().len(), 1);
+    assert_eq!(f.books.worker_balance(payment.worker_id, "AUD").await.unwrap(), 9_000);
+}
+
+#[tokio::test]
+async fn test_payment_failed_marks_payment_failed_without_posting() {
+    let f = fixture().await;
+    let payment = awaiting_payment(&f).await;
+    let failed = PaymentWebhookAction::PaymentFailed {
+        intent_reference: "pi_1".to_string(),
+        reason: "The card was declined after authentication".to_string(),
+    };
+
+    let outcome = f
+        .service
+        .handle_webhook(&webhook("evt_1", failed), Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(outcome, WebhookOutcome::PaymentFailed);
+
+    let stored = f.payments.find_by_id(payment.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, PaymentStatus::Failed);
+    assert_eq!(f.books.worker_balance(payment.worker_id, "AUD").await.unwrap(), 0);
+
+    let timeline = f.payments.find_timeline(payment.id).await.unwrap();
+    assert_eq!(timeline[0].status, PaymentStatus::Failed);
+    assert_eq!(timeline[0].note.as_deref(), Some("The card was declined after authentication"));
+}
+
+fn over_refund() -> PaymentWebhookAction {
+    PaymentWebhookAction::Refund {
+        payment_reference: "ch_1".to_string(),
+        refund_reference: "re_1".to_string(),
+        amount: 12_000,
+        currency: "AUD".to_string(),
+    }
+}
+
+/// Raise the payment amount so an over-refund becomes valid, as if the
+/// cause of the failure had been fixed
+fn raise_payment_amount(payments: &MockPayments, payment: &Payment) {
+    payments.payments.lock().unwrap().get_mut(&payment.id).unwrap().amount = 20_000;
+}
+
+#[tokio::test]
+async fn test_failed_event_is_dead_lettered_and_resolved_by_redelivery() {
+    let f = fixture().await;
+    let dead_letters = Arc::new(MockDeadLetters::default());
+    let service = f.service.with_dead_letters(dead_letters.clone());
+    let payload = webhook("evt_1", over_refund());
+
+    // Unsigned requests are never dead-lettered
+    assert!(service.handle_webhook(&payload, None).await.is_err());
+    assert!(dead_letters.all().is_empty());
+
+    assert!(service.handle_webhook(&payload, Some(SIGNATURE)).await.is_err());
+    assert!(service.handle_webhook(&payload, Some(SIGNATURE)).await.is_err());
+    let dead_letter = dead_letters.all()[0].clone();
+    assert_eq!(dead_letters.all().len(), 1);
+    assert_eq!(dead_letter.direction, WebhookDirection::Inbound);
+    assert_eq!(dead_letter.event_id, "evt_1");
+    assert_eq!(dead_letter.failures, 2);
+    assert_eq!(dead_letter.payload["action"]["refund_reference"], "re_1");
+
+    raise_payment_amount(&f.payments, &f.payment);
+    let outcome = service.handle_webhook(&payload, Some(SIGNATURE)).await.unwrap();
+    assert_eq!(outcome, WebhookOutcome::RefundRecorded);
+
+    let dead_letter = dead_letters.all()[0].clone();
+    assert_eq!(dead_letter.status, DeadLetterStatus::Resolved);
+    assert_eq!(dead_letter.resolution.as_deref(), Some("refund_recorded"));
+}
+
+#[tokio::test]
+async fn test_replay_processes_stored_payload_once() {
+    let f = fixture().await;
+    let dead_letters = Arc::new(MockDeadLetters::default());
+    let service = f.service.with_dead_letters(dead_letters.clone());
+
+    assert!(service
+        .handle_webhook(&webhook("evt_1", over_refund()), Some(SIGNATURE))
+        .await
+        .is_err());
+    let dead_letter = dead_letters.all()[0].clone();
+
+    raise_payment_amount(&f.payments, &f.payment);
+    assert_eq!(service.source(), "stripe");
+    assert_eq!(service.replay(&dead_letter).await.unwrap(), "refund_recorded");
+    assert_eq!(service.replay(&dead_letter).await.unwrap(), "duplicate");
+
+    assert_eq!(f.ledger.entries.lock().unwrap().len(), 1);
+    let payment = f.payments.find_by_id(f.payment.id).await.unwrap().unwrap();
+    assert_eq!(payment.refunded_amount, 12_000);
+}
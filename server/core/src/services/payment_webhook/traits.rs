@@ -0,0 +1,30 @@
+//! Traits for payment provider webhooks
+
+use crate::domain::entities::payment_webhook::ParsedWebhookEvent;
+
+/// Trait for verifying and decoding a payment provider's webhooks
+pub trait PaymentWebhookProviderTrait: Send + Sync {
+    /// Provider name as recorded on payments and ledger entries (e.g. "stripe")
+    fn provider_name(&self) -> &str;
+
+    /// Name of the HTTP header carrying the webhook signature
+    fn signature_header(&self) -> &str;
+
+    /// Verify that a payload was signed by the provider
+    ///
+    /// # Arguments
+    /// * `payload` - Raw request body, exactly as received
+    /// * `signature` - Value of the signature header
+    ///
+    /// # Returns
+    /// * `Ok(())` - The signature is valid
+    /// * `Err(String)` - Why the signature was rejected
+    fn verify_signature(&self, payload: &[u8], signature: &str) -> Result<(), String>;
+
+    /// Decode a verified payload
+    ///
+    /// # Returns
+    /// * `Ok(ParsedWebhookEvent)` - The event, with `PaymentWebhookAction::Ignored` for unhandled types
+    /// * `Err(String)` - The payload is malformed
+    fn parse_event(&self, payload: &[u8]) -> Result<ParsedWebhookEvent, String>;
+}
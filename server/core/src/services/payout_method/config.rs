@@ -0,0 +1,19 @@
+//! Configuration for the payout method service
+
+/// Configuration for the payout method service
+#[derive(Debug, Clone)]
+pub struct PayoutMethodConfig {
+    /// Most payout methods a worker can register
+    pub max_methods_per_worker: usize,
+    /// Wrong micro-deposit confirmations allowed before verification fails
+    pub max_micro_deposit_attempts: u32,
+}
+
+impl Default for PayoutMethodConfig {
+    fn default() -> Self {
+        Self {
+            max_methods_per_worker: 5,
+            max_micro_deposit_attempts: 3,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Payout method service module
+//!
+//! This module handles:
+//! - Registering workers' bank accounts with the payment provider
+//! - Verifying accounts by identity match or micro-deposits
+//! - Refusing payout requests until the worker has a verified account
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::PayoutMethodConfig;
+pub use service::{PayoutEligibility, PayoutMethodService};
+pub use traits::{PayoutMethodProviderTrait, ProviderBankAccount, ProviderVerification};
@@ -0,0 +1,386 @@
+//! Payout method service
+//!
+//! A worker's bank account must be verified before anything is paid out to
+//! it. When the provider can match the account holder to the worker's
+//! verified identity the account is verified immediately or by a later
+//! webhook; otherwise the provider sends two micro-deposits and the worker
+//! confirms the amounts, with a limited number of attempts.
+
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::ComplianceAction;
+use crate::domain::entities::payment::{Payout, PayoutStatus};
+use crate::domain::entities::payout_method::{PayoutMethod, PayoutMethodStatus};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{PayoutMethodRepository, PayoutRepository};
+use crate::services::compliance::ComplianceGateTrait;
+use crate::services::lifecycle::{LifecycleEvent, LifecycleHookService};
+use crate::services::tenant;
+
+use super::config::PayoutMethodConfig;
+use super::traits::{PayoutMethodProviderTrait, ProviderVerification};
+
+/// Whether a worker can request payouts, and what to do if not
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayoutEligibility {
+    /// Payouts go to this verified account
+    Ready(PayoutMethod),
+    /// The worker has not added a bank account
+    NoPayoutMethod,
+    /// The provider is still verifying the newest account
+    PendingVerification(PayoutMethod),
+    /// The worker has to confirm the micro-deposits sent to the newest account
+    AwaitingMicroDeposits(PayoutMethod),
+    /// The newest account failed verification; another has to be added
+    VerificationFailed(PayoutMethod),
+}
+
+/// Service managing workers' payout methods and payout requests
+pub struct PayoutMethodService<M, O, V>
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    methods: Arc<M>,
+    payouts: Arc<O>,
+    provider: Arc<V>,
+    config: PayoutMethodConfig,
+    lifecycle_hooks: Option<Arc<LifecycleHookService>>,
+    compliance: Option<Arc<dyn ComplianceGateTrait>>,
+}
+
+impl<M, O, V> PayoutMethodService<M, O, V>
+where
+    M: PayoutMethodRepository + 'static,
+    O: PayoutRepository + 'static,
+    V: PayoutMethodProviderTrait + 'static,
+{
+    /// Create a new payout method service
+    pub fn new(methods: Arc<M>, payouts: Arc<O>, provider: Arc<V>, config: PayoutMethodConfig) -> Self {
+        Self {
+            methods,
+            payouts,
+            provider,
+            config,
+            lifecycle_hooks: None,
+            compliance: None,
+        }
+    }
+
+    /// Run lifecycle hooks when a payout is transferred
+    pub fn with_lifecycle_hooks(mut self, hooks: Arc<LifecycleHookService>) -> Self {
+        self.lifecycle_hooks = Some(hooks);
+        self
+    }
+
+    /// Refuse payouts until the checks the worker's market requires are verified
+    pub fn with_compliance(mut self, compliance: Arc<dyn ComplianceGateTrait>) -> Self {
+        self.compliance = Some(compliance);
+        self
+    }
+
+    /// List a worker's payout methods, newest first
+    pub async fn list(&self, worker_id: Uuid) -> DomainResult<Vec<PayoutMethod>> {
+        self.methods.find_by_worker(worker_id).await
+    }
+
+    /// Register a bank account the app tokenized with the provider
+    ///
+    /// # Arguments
+    /// * `worker_id` - Worker registering the account
+    /// * `token` - Provider's identifier for the bank account
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Missing token
+    /// * `Err(DomainError::BusinessRule)` - The worker registered the maximum number of accounts
+    /// * `Err(DomainError::Internal)` - The provider could not register the account
+    pub async fn add(&self, worker_id: Uuid, token: &str) -> DomainResult<PayoutMethod> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Bank account token is required".to_string(),
+            });
+        }
+
+        let existing = self.methods.find_by_worker(worker_id).await?;
+        if existing.len() >= self.config.max_methods_per_worker {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "At most {} payout methods can be registered",
+                    self.config.max_methods_per_worker
+                ),
+            });
+        }
+
+        let provider = self.provider.provider_name();
+        let account = self
+            .provider
+            .add_bank_account(worker_id, token)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to add {} bank account: {}", provider, e),
+            })?;
+        let mut method = PayoutMethod::new(
+            worker_id,
+            provider,
+            account.reference,
+            account.bank_name,
+            account.last4,
+            &account.currency,
+        )
+        .map_err(|message| DomainError::Internal { message })?;
+        apply_verification(&mut method, account.verification);
+
+        let method = self.methods.save(method).await?;
+        info!(
+            worker_id = %worker_id,
+            payout_method_id = %method.id,
+            status = method.status.as_str(),
+            "Payout method added"
+        );
+
+        Ok(method)
+    }
+
+    /// Confirm the micro-deposit amounts the worker saw on their statement
+    ///
+    /// # Arguments
+    /// * `amounts` - The two deposit amounts in minor units
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - Not two amounts between 1 and 99
+    /// * `Err(DomainError::NotFound)` - No such method, or it belongs to another worker
+    /// * `Err(DomainError::BusinessRule)` - The method is not awaiting micro-deposits,
+    ///   or the amounts do not match
+    /// * `Err(DomainError::Internal)` - The provider failed
+    pub async fn verify_micro_deposits(
+        &self,
+        worker_id: Uuid,
+        method_id: Uuid,
+        amounts: &[i64],
+    ) -> DomainResult<PayoutMethod> {
+        if amounts.len() != 2 || amounts.iter().any(|amount| !(1..=99).contains(amount)) {
+            return Err(DomainError::Validation {
+                message: "Enter the two deposit amounts, each between 1 and 99 cents".to_string(),
+            });
+        }
+
+        let mut method = self.find_owned(worker_id, method_id).await?;
+        if method.status != PayoutMethodStatus::AwaitingMicroDeposits {
+            return Err(DomainError::BusinessRule {
+                message: format!("Payout method is not awaiting micro-deposits: {}", method.status.as_str()),
+            });
+        }
+
+        let matched = self
+            .provider
+            .verify_micro_deposits(&method.provider_reference, amounts)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to verify {} micro-deposits: {}", method.provider, e),
+            })?;
+
+        if matched {
+            method.mark_verified();
+            self.methods.update(&method).await?;
+            info!(worker_id = %worker_id, payout_method_id = %method.id, "Payout method verified");
+            return Ok(method);
+        }
+
+        let exhausted = method.record_failed_attempt(self.config.max_micro_deposit_attempts);
+        self.methods.update(&method).await?;
+        warn!(
+            worker_id = %worker_id,
+            payout_method_id = %method.id,
+            failed_attempts = method.failed_attempts,
+            "Wrong micro-deposit amounts"
+        );
+
+        let message = if exhausted {
+            "Deposit amounts do not match and no attempts remain; add the bank account again".to_string()
+        } else {
+            format!(
+                "Deposit amounts do not match; {} attempts remain",
+                self.config.max_micro_deposit_attempts - method.failed_attempts
+            )
+        };
+        Err(DomainError::BusinessRule { message })
+    }
+
+    /// Record the provider's verification result for a bank account
+    ///
+    /// Called from the provider's webhook, which is received for every
+    /// tenant; the result is applied in the tenant of the account. Results
+    /// for accounts already verified or failed are ignored.
+    ///
+    /// # Returns
+    /// * `Ok(Some(PayoutMethod))` - The account's updated state
+    /// * `Ok(None)` - The account is not registered
+    pub async fn apply_provider_verification(
+        &self,
+        provider_reference: &str,
+        verification: ProviderVerification,
+    ) -> DomainResult<Option<PayoutMethod>> {
+        let tenant = self
+            .methods
+            .find_tenant_by_reference(self.provider.provider_name(), provider_reference)
+            .await?
+            .unwrap_or_default();
+
+        tenant::scope(tenant, self.apply_verification_in_tenant(provider_reference, verification)).await
+    }
+
+    /// Apply a verification result in the current tenant
+    async fn apply_verification_in_tenant(
+        &self,
+        provider_reference: &str,
+        verification: ProviderVerification,
+    ) -> DomainResult<Option<PayoutMethod>> {
+        let provider = self.provider.provider_name();
+        let Some(mut method) = self.methods.find_by_reference(provider, provider_reference).await? else {
+            return Ok(None);
+        };
+
+        if matches!(
+            method.status,
+            PayoutMethodStatus::PendingVerification | PayoutMethodStatus::AwaitingMicroDeposits
+        ) {
+            apply_verification(&mut method, verification);
+            self.methods.update(&method).await?;
+            info!(
+                payout_method_id = %method.id,
+                status = method.status.as_str(),
+                "Payout method verification updated"
+            );
+        }
+
+        Ok(Some(method))
+    }
+
+    /// Whether a worker can request payouts
+    ///
+    /// Payouts go to the newest verified account. Without one, the state of
+    /// the newest account tells the worker what to do next.
+    pub async fn payout_eligibility(&self, worker_id: Uuid) -> DomainResult<PayoutEligibility> {
+        let methods = self.methods.find_by_worker(worker_id).await?;
+
+        if let Some(verified) = methods.iter().find(|m| m.is_verified()) {
+            return Ok(PayoutEligibility::Ready(verified.clone()));
+        }
+
+        Ok(match methods.into_iter().next() {
+            None => PayoutEligibility::NoPayoutMethod,
+            Some(method) => match method.status {
+                PayoutMethodStatus::AwaitingMicroDeposits => PayoutEligibility::AwaitingMicroDeposits(method),
+                PayoutMethodStatus::VerificationFailed => PayoutEligibility::VerificationFailed(method),
+                PayoutMethodStatus::PendingVerification | PayoutMethodStatus::Verified => {
+                    PayoutEligibility::PendingVerification(method)
+                }
+            },
+        })
+    }
+
+    /// Transfer a pending payout to the worker's verified bank account
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No such payout, or it belongs to another worker
+    /// * `Err(DomainError::BusinessRule)` - The worker has no verified account, the
+    ///   account's currency differs, the payout is frozen or already paid, or a
+    ///   compliance check of the worker's market is not verified
+    /// * `Err(DomainError::Internal)` - The provider could not transfer the payout
+    pub async fn request_payout(&self, worker_id: Uuid, payout_id: Uuid) -> DomainResult<Payout> {
+        let mut payout = self
+            .payouts
+            .find_by_id(payout_id)
+            .await?
+            .filter(|p| p.worker_id == worker_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payout".to_string(),
+            })?;
+        if payout.status != PayoutStatus::Pending {
+            return Err(DomainError::BusinessRule {
+                message: format!("Payout is not pending: {}", payout.status.as_str()),
+            });
+        }
+
+        if let Some(ref compliance) = self.compliance {
+            compliance.ensure_allowed(worker_id, ComplianceAction::Payout).await?;
+        }
+
+        let method = match self.payout_eligibility(worker_id).await? {
+            PayoutEligibility::Ready(method) => method,
+            _ => {
+                return Err(DomainError::BusinessRule {
+                    message: "A verified payout method is required".to_string(),
+                })
+            }
+        };
+        if method.currency != payout.currency {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Payout in {} cannot be sent to a {} bank account",
+                    payout.currency, method.currency
+                ),
+            });
+        }
+
+        let transfer_reference = self
+            .provider
+            .transfer(
+                &method.provider_reference,
+                payout.amount,
+                &payout.currency,
+                &payout.id.to_string(),
+            )
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to transfer {} payout: {}", method.provider, e),
+            })?;
+        payout
+            .mark_paid(transfer_reference)
+            .map_err(|message| DomainError::Internal { message })?;
+        self.payouts.update(&payout).await?;
+
+        info!(
+            worker_id = %worker_id,
+            payout_id = %payout.id,
+            payout_method_id = %method.id,
+            amount = payout.amount,
+            "Payout transferred"
+        );
+        if let Some(ref hooks) = self.lifecycle_hooks {
+            hooks.publish(LifecycleEvent::PayoutPaid {
+                payout_id: payout.id,
+                worker_id,
+                amount: payout.amount,
+                currency: payout.currency.clone(),
+            });
+        }
+
+        Ok(payout)
+    }
+
+    /// Find a payout method belonging to a worker
+    async fn find_owned(&self, worker_id: Uuid, method_id: Uuid) -> DomainResult<PayoutMethod> {
+        self.methods
+            .find_by_id(method_id)
+            .await?
+            .filter(|m| m.worker_id == worker_id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Payout method".to_string(),
+            })
+    }
+}
+
+/// Move a payout method to the state the provider reported
+fn apply_verification(method: &mut PayoutMethod, verification: ProviderVerification) {
+    match verification {
+        ProviderVerification::Verified => method.mark_verified(),
+        ProviderVerification::MicroDepositsSent => method.await_micro_deposits(),
+        ProviderVerification::Failed => method.mark_failed(),
+        ProviderVerification::Pending => {}
+    }
+}
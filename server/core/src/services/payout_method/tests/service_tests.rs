@@ -0,0 +1,310 @@
+//! Unit tests for the payout method service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::compliance::{
+    ComplianceAction, ComplianceRequirement, ComplianceStatus, ComplianceStep,
+};
+use crate::domain::entities::payment::{Payment, Payout, PayoutStatus};
+use crate::domain::entities::payout_method::{PayoutMethod, PayoutMethodStatus};
+use crate::domain::entities::user::User;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{PayoutMethodRepository, PayoutRepository};
+use crate::services::compliance::ComplianceGateTrait;
+use crate::services::payment_webhook::tests::service_tests::MockPayouts;
+use crate::services::payout_method::{
+    PayoutEligibility, PayoutMethodConfig, PayoutMethodProviderTrait, PayoutMethodService,
+    ProviderBankAccount, ProviderVerification,
+};
+use crate::services::tenant::TenantId;
+
+#[derive(Default)]
+struct MockPayoutMethods {
+    methods: Mutex<Vec<PayoutMethod>>,
+}
+
+#[async_trait]
+impl PayoutMethodRepository for MockPayoutMethods {
+    async fn save(&self, method: PayoutMethod) -> Result<PayoutMethod, DomainError> {
+        self.methods.lock().unwrap().push(method.clone());
+        Ok(method)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PayoutMethod>, DomainError> {
+        Ok(self.methods.lock().unwrap().iter().find(|m| m.id == id).cloned())
+    }
+
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<PayoutMethod>, DomainError> {
+        Ok(self
+            .methods
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|m| m.provider == provider && m.provider_reference == provider_reference)
+            .cloned())
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        Ok(self
+            .find_by_reference(provider, provider_reference)
+            .await?
+            .map(|_| TenantId::default()))
+    }
+
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<PayoutMethod>, DomainError> {
+        Ok(self
+            .methods
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|m| m.worker_id == worker_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, method: &PayoutMethod) -> Result<(), DomainError> {
+        for existing in self.methods.lock().unwrap().iter_mut().filter(|m| m.id == method.id) {
+            *existing = method.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Provider whose verification outcome is chosen by the token:
+/// `btok_id` matches the identity, `btok_pending` waits for a webhook and
+/// anything else gets micro-deposits of 32 and 45 cents
+#[derive(Default)]
+struct MockProvider {
+    transfers: Mutex<Vec<(String, i64)>>,
+}
+
+#[async_trait]
+impl PayoutMethodProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    async fn add_bank_account(&self, _worker_id: Uuid, token: &str) -> Result<ProviderBankAccount, String> {
+        let verification = match token {
+            "btok_id" => ProviderVerification::Verified,
+            "btok_pending" => ProviderVerification::Pending,
+            _ => ProviderVerification::MicroDepositsSent,
+        };
+        Ok(ProviderBankAccount {
+            reference: format!("ba_{}", token),
+            bank_name: Some("ANZ".to_string()),
+            last4: "6789".to_string(),
+            currency: "aud".to_string(),
+            verification,
+        })
+    }
+
+    async fn verify_micro_deposits(&self, _reference: &str, amounts: &[i64]) -> Result<bool, String> {
+        Ok(amounts == [32, 45])
+    }
+
+    async fn transfer(
+        &self,
+        reference: &str,
+        amount: i64,
+        _currency: &str,
+        idempotency_key: &str,
+    ) -> Result<String, String> {
+        self.transfers.lock().unwrap().push((reference.to_string(), amount));
+        Ok(format!("tr_{}", idempotency_key))
+    }
+}
+
+/// Compliance gate with an unverified real-name check holding back payouts
+struct UnverifiedRealName;
+
+#[async_trait]
+impl ComplianceGateTrait for UnverifiedRealName {
+    async fn outstanding_steps(&self, _user: &User) -> DomainResult<Vec<ComplianceStep>> {
+        Ok(vec![ComplianceStep {
+            requirement: ComplianceRequirement::RealName,
+            market: "+86".to_string(),
+            status: ComplianceStatus::Required,
+            gates: vec![ComplianceAction::Payout],
+            rejection_reason: None,
+            attempts_remaining: 5,
+        }])
+    }
+
+    async fn blocking_step(&self, _user_id: Uuid, action: ComplianceAction) -> DomainResult<Option<ComplianceStep>> {
+        let steps = self.outstanding_steps(&User::new(String::new(), "+86".to_string())).await?;
+        Ok(steps.into_iter().find(|step| step.blocks(action)))
+    }
+}
+
+struct Fixture {
+    service: PayoutMethodService<MockPayoutMethods, MockPayouts, MockProvider>,
+    payouts: Arc<MockPayouts>,
+    provider: Arc<MockProvider>,
+}
+
+fn fixture() -> Fixture {
+    let methods = Arc::new(MockPayoutMethods::default());
+    let payouts = Arc::new(MockPayouts::default());
+    let provider = Arc::new(MockProvider::default());
+    let service = PayoutMethodService::new(
+        methods,
+        payouts.clone(),
+        provider.clone(),
+        PayoutMethodConfig { max_methods_per_worker: 3, max_micro_deposit_attempts: 2 },
+    );
+
+    Fixture { service, payouts, provider }
+}
+
+/// Record a pending payout of 85 AUD for a worker
+async fn pending_payout(f: &Fixture, worker_id: Uuid) -> Payout {
+    let payment = Payment::new("stripe", "ch_1", Uuid::new_v4(), worker_id, 10_000, "AUD").unwrap();
+    f.payouts.save(Payout::new(&payment, 8_500)).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_identity_match_verifies_immediately() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+
+    let method = f.service.add(worker_id, "btok_id").await.unwrap();
+    assert_eq!(method.status, PayoutMethodStatus::Verified);
+    assert!(method.verified_at.is_some());
+    assert_eq!(method.currency, "AUD");
+
+    let eligibility = f.service.payout_eligibility(worker_id).await.unwrap();
+    assert_eq!(eligibility, PayoutEligibility::Ready(method));
+}
+
+#[tokio::test]
+async fn test_micro_deposits_verify_with_limited_attempts() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+    let method = f.service.add(worker_id, "btok_deposits").await.unwrap();
+    assert_eq!(method.status, PayoutMethodStatus::AwaitingMicroDeposits);
+
+    let result = f.service.verify_micro_deposits(worker_id, method.id, &[32]).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+    let result = f.service.verify_micro_deposits(Uuid::new_v4(), method.id, &[32, 45]).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+
+    let result = f.service.verify_micro_deposits(worker_id, method.id, &[45, 32]).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    let verified = f.service.verify_micro_deposits(worker_id, method.id, &[32, 45]).await.unwrap();
+    assert!(verified.is_verified());
+
+    let other = f.service.add(worker_id, "btok_other").await.unwrap();
+    for _ in 0..2 {
+        let result = f.service.verify_micro_deposits(worker_id, other.id, &[1, 2]).await;
+        assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    }
+    let failed = f.service.list(worker_id).await.unwrap().into_iter().find(|m| m.id == other.id).unwrap();
+    assert_eq!(failed.status, PayoutMethodStatus::VerificationFailed);
+    let result = f.service.verify_micro_deposits(worker_id, other.id, &[32, 45]).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_provider_webhook_completes_pending_verification() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+    let method = f.service.add(worker_id, "btok_pending").await.unwrap();
+    assert_eq!(
+        f.service.payout_eligibility(worker_id).await.unwrap(),
+        PayoutEligibility::PendingVerification(method.clone())
+    );
+
+    let updated = f
+        .service
+        .apply_provider_verification(&method.provider_reference, ProviderVerification::Failed)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.status, PayoutMethodStatus::VerificationFailed);
+
+    // A late result does not revive a failed account
+    let ignored = f
+        .service
+        .apply_provider_verification(&method.provider_reference, ProviderVerification::Verified)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ignored.status, PayoutMethodStatus::VerificationFailed);
+
+    let unknown = f
+        .service
+        .apply_provider_verification("ba_unknown", ProviderVerification::Verified)
+        .await
+        .unwrap();
+    assert!(unknown.is_none());
+}
+
+#[tokio::test]
+async fn test_payout_requests_wait_for_verification() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+    let payout = pending_payout(&f, worker_id).await;
+
+    assert_eq!(f.service.payout_eligibility(worker_id).await.unwrap(), PayoutEligibility::NoPayoutMethod);
+    let result = f.service.request_payout(worker_id, payout.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let method = f.service.add(worker_id, "btok_deposits").await.unwrap();
+    assert!(matches!(
+        f.service.payout_eligibility(worker_id).await.unwrap(),
+        PayoutEligibility::AwaitingMicroDeposits(_)
+    ));
+    let result = f.service.request_payout(worker_id, payout.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(f.provider.transfers.lock().unwrap().is_empty());
+
+    f.service.verify_micro_deposits(worker_id, method.id, &[32, 45]).await.unwrap();
+    let paid = f.service.request_payout(worker_id, payout.id).await.unwrap();
+    assert_eq!(paid.status, PayoutStatus::Paid);
+    assert_eq!(paid.transfer_reference, Some(format!("tr_{}", payout.id)));
+    assert_eq!(
+        *f.provider.transfers.lock().unwrap(),
+        vec![(method.provider_reference.clone(), 8_500)]
+    );
+
+    // Paying out twice is refused
+    let result = f.service.request_payout(worker_id, payout.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+}
+
+#[tokio::test]
+async fn test_payouts_of_other_workers_are_not_found() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+    let payout = pending_payout(&f, worker_id).await;
+
+    let intruder = Uuid::new_v4();
+    f.service.add(intruder, "btok_id").await.unwrap();
+    let result = f.service.request_payout(intruder, payout.id).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_payout_requests_wait_for_compliance() {
+    let f = fixture();
+    let worker_id = Uuid::new_v4();
+    let payout = pending_payout(&f, worker_id).await;
+    f.service.add(worker_id, "btok_id").await.unwrap();
+
+    let service = f.service.with_compliance(Arc::new(UnverifiedRealName));
+    let result = service.request_payout(worker_id, payout.id).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(f.provider.transfers.lock().unwrap().is_empty());
+}
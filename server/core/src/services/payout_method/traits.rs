@@ -0,0 +1,75 @@
+//! Traits for verifying and paying out to workers' bank accounts
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Outcome of the provider's verification when a bank account is added
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderVerification {
+    /// The account holder matched the worker's verified identity
+    Verified,
+    /// The provider is still checking; the result arrives by webhook
+    Pending,
+    /// The provider sent two micro-deposits for the worker to confirm
+    MicroDepositsSent,
+    /// The account holder did not match the worker's identity
+    Failed,
+}
+
+/// A bank account as reported by the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderBankAccount {
+    /// Provider's identifier for the bank account
+    pub reference: String,
+    /// Name of the bank, when known
+    pub bank_name: Option<String>,
+    /// Last four digits of the account number
+    pub last4: String,
+    /// ISO 4217 currency code of the account
+    pub currency: String,
+    /// Verification outcome
+    pub verification: ProviderVerification,
+}
+
+/// Trait for a payment provider that pays workers out to their bank accounts
+#[async_trait]
+pub trait PayoutMethodProviderTrait: Send + Sync {
+    /// Name of the provider, stored with each payout method
+    fn provider_name(&self) -> &str;
+
+    /// Register a bank account the app tokenized for the worker
+    ///
+    /// The provider first tries to match the account holder to the worker's
+    /// verified identity and falls back to micro-deposits.
+    ///
+    /// # Arguments
+    /// * `worker_id` - Worker registering the account
+    /// * `token` - Provider's identifier returned by its SDK
+    async fn add_bank_account(&self, worker_id: Uuid, token: &str) -> Result<ProviderBankAccount, String>;
+
+    /// Confirm the micro-deposit amounts the worker saw on their statement
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The amounts match and the account is verified
+    /// * `Ok(false)` - The amounts do not match
+    /// * `Err(String)` - The provider failed
+    async fn verify_micro_deposits(&self, reference: &str, amounts: &[i64]) -> Result<bool, String>;
+
+    /// Transfer a payout to a verified bank account
+    ///
+    /// # Arguments
+    /// * `reference` - Provider's identifier for the bank account
+    /// * `amount` - Amount in minor units
+    /// * `currency` - ISO 4217 currency code
+    /// * `idempotency_key` - Key that makes retries of the same payout safe
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Provider's identifier for the transfer
+    async fn transfer(
+        &self,
+        reference: &str,
+        amount: i64,
+        currency: &str,
+        idempotency_key: &str,
+    ) -> Result<String, String>;
+}
@@ -0,0 +1,27 @@
+//! Configuration for the phone hash rehash service
+
+use crate::services::auth::PhoneHasher;
+
+/// Configuration for the phone hash rehash service
+#[derive(Debug, Clone)]
+pub struct PhoneHashRehashConfig {
+    /// Hasher holding the pepper keyring
+    pub phone_hasher: PhoneHasher,
+    /// Interval between runs of the rehash job, in seconds
+    pub interval_seconds: u64,
+    /// Maximum number of users rehashed per run
+    pub batch_size: usize,
+    /// Whether to enable the background rehash job
+    pub enabled: bool,
+}
+
+impl Default for PhoneHashRehashConfig {
+    fn default() -> Self {
+        Self {
+            phone_hasher: PhoneHasher::default(),
+            interval_seconds: 300, // Every 5 minutes
+            batch_size: 1_000,
+            enabled: true,
+        }
+    }
+}
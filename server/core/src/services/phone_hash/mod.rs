@@ -0,0 +1,16 @@
+//! Phone hash service module for pepper rotation
+//!
+//! This module handles:
+//! - Bringing stored phone hashes to the current pepper version
+//! - Rehashing outdated rows in the background after a rotation
+//!
+//! Hashes are rehashed from the stored digest, so no phone number is needed.
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::PhoneHashRehashConfig;
+pub use service::{PhoneHashRehashReport, PhoneHashRehashService};
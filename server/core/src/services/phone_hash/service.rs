@@ -0,0 +1,121 @@
+//! Phone hash rehash service
+//!
+//! After a pepper is appended to the keyring, users who do not sign in keep
+//! hashes at older versions, which lookups must go on trying. This service
+//! brings those rows to the current version in batches, so the rotation
+//! window closes without waiting for every user to return.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::errors::DomainResult;
+use crate::repositories::PhoneHashRepository;
+
+use super::config::PhoneHashRehashConfig;
+
+/// Outcome of a rehash run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhoneHashRehashReport {
+    /// Users with an outdated hash that were read
+    pub scanned: usize,
+    /// Users whose hash was brought to the current version
+    pub rehashed: usize,
+    /// Users whose hash changed before it could be replaced
+    pub skipped: usize,
+}
+
+/// Service rehashing stored phone hashes to the current pepper version
+pub struct PhoneHashRehashService<R: PhoneHashRepository + 'static> {
+    repository: Arc<R>,
+    config: PhoneHashRehashConfig,
+}
+
+impl<R: PhoneHashRepository + 'static> PhoneHashRehashService<R> {
+    /// Create a new phone hash rehash service
+    pub fn new(repository: Arc<R>, config: PhoneHashRehashConfig) -> Self {
+        Self { repository, config }
+    }
+
+    /// Number of users whose hash is still at an older version
+    pub async fn outdated_count(&self) -> DomainResult<u64> {
+        let current_version = self.config.phone_hasher.current_version();
+        if current_version < 2 {
+            return Ok(0);
+        }
+        self.repository.count_outdated(current_version).await
+    }
+
+    /// Rehash one batch of users with outdated hashes
+    ///
+    /// Rows changed in the meantime, for instance by a sign-in, are skipped.
+    pub async fn rehash_batch(&self) -> DomainResult<PhoneHashRehashReport> {
+        let hasher = &self.config.phone_hasher;
+        let current_version = hasher.current_version();
+        let mut report = PhoneHashRehashReport::default();
+        if current_version < 2 {
+            return Ok(report);
+        }
+
+        let users = self
+            .repository
+            .find_outdated(current_version, self.config.batch_size)
+            .await?;
+        report.scanned = users.len();
+
+        for user in users {
+            let Some(new_hash) = hasher.rehash(&user.phone_hash, user.phone_hash_version) else {
+                report.skipped += 1;
+                continue;
+            };
+
+            let replaced = self
+                .repository
+                .replace_phone_hash(user.id, &user.phone_hash, &new_hash, current_version)
+                .await?;
+            if replaced {
+                report.rehashed += 1;
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        if report.rehashed > 0 {
+            info!(
+                rehashed = report.rehashed,
+                skipped = report.skipped,
+                version = current_version,
+                "Rehashed phone hashes"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Start the rehash job as a background task
+    ///
+    /// This spawns a tokio task that rehashes a batch at the configured interval
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Phone hash rehashing is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Phone hash rehashing started - checking every {} seconds",
+                self.config.interval_seconds
+            );
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.rehash_batch().await {
+                    error!("Phone hash rehashing failed to run: {}", e);
+                }
+            }
+        });
+    }
+}
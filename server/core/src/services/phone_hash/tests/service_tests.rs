@@ -0,0 +1,172 @@
+//! Unit tests for the phone hash rehash service
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use re_shared::config::auth::PhoneHashConfig;
+
+use crate::domain::entities::user::User;
+use crate::errors::DomainError;
+use crate::repositories::PhoneHashRepository;
+use crate::services::auth::PhoneHasher;
+use crate::services::phone_hash::{PhoneHashRehashConfig, PhoneHashRehashService};
+
+const FIRST_PEPPER: &str = "first-pepper-0123456789abcdef01234567";
+const SECOND_PEPPER: &str = "second-pepper-0123456789abcdef0123456";
+const THIRD_PEPPER: &str = "third-pepper-0123456789abcdef01234567";
+
+#[derive(Default)]
+struct MockPhoneHashes {
+    users: Mutex<Vec<User>>,
+    /// Users whose row changes between being read and being replaced
+    concurrently_updated: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl PhoneHashRepository for MockPhoneHashes {
+    async fn find_outdated(&self, current_version: u16, limit: usize) -> Result<Vec<User>, DomainError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|u| u.phone_hash_version > 0 && u.phone_hash_version < current_version)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_outdated(&self, current_version: u16) -> Result<u64, DomainError> {
+        Ok(self.find_outdated(current_version, usize::MAX).await?.len() as u64)
+    }
+
+    async fn replace_phone_hash(
+        &self,
+        user_id: Uuid,
+        old_hash: &str,
+        new_hash: &str,
+        new_version: u16,
+    ) -> Result<bool, DomainError> {
+        if self.concurrently_updated.lock().unwrap().contains(&user_id) {
+            return Ok(false);
+        }
+
+        let mut users = self.users.lock().unwrap();
+        match users.iter_mut().find(|u| u.id == user_id && u.phone_hash == old_hash) {
+            Some(user) => {
+                user.phone_hash = new_hash.to_string();
+                user.phone_hash_version = new_version;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+fn hasher(peppers: &[&str]) -> PhoneHasher {
+    PhoneHasher::new(&PhoneHashConfig {
+        peppers: peppers.iter().map(|&p| p.into()).collect(),
+        accept_unpeppered: false,
+    })
+}
+
+fn user_hashed_with(hasher: &PhoneHasher, phone: &str) -> User {
+    let mut user = User::new(hasher.hash(phone), "+61".to_string());
+    user.phone_hash_version = hasher.current_version();
+    user
+}
+
+fn create_service(
+    repository: Arc<MockPhoneHashes>,
+    phone_hasher: PhoneHasher,
+    batch_size: usize,
+) -> PhoneHashRehashService<MockPhoneHashes> {
+    PhoneHashRehashService::new(
+        repository,
+        PhoneHashRehashConfig {
+            phone_hasher,
+            batch_size,
+            ..PhoneHashRehashConfig::default()
+        },
+    )
+}
+
+#[test]
+fn test_rehash_matches_hashing_the_phone_at_the_current_version() {
+    let first = hasher(&[FIRST_PEPPER]);
+    let second = hasher(&[FIRST_PEPPER, SECOND_PEPPER]);
+    let third = hasher(&[FIRST_PEPPER, SECOND_PEPPER, THIRD_PEPPER]);
+
+    assert_eq!(third.rehash(&first.hash("412345678"), 1), Some(third.hash("412345678")));
+    assert_eq!(third.rehash(&second.hash("412345678"), 2), Some(third.hash("412345678")));
+    assert_eq!(third.rehash(&third.hash("412345678"), 3), None);
+    assert_eq!(third.rehash(&PhoneHasher::default().hash("412345678"), 0), None);
+}
+
+#[test]
+fn test_lookup_hashes_try_every_version_newest_first() {
+    let third = hasher(&[FIRST_PEPPER, SECOND_PEPPER, THIRD_PEPPER]);
+
+    assert_eq!(
+        third.lookup_hashes("412345678"),
+        vec![
+            third.hash("412345678"),
+            hasher(&[FIRST_PEPPER, SECOND_PEPPER]).hash("412345678"),
+            hasher(&[FIRST_PEPPER]).hash("412345678"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rehash_batch_brings_outdated_users_to_current_version() {
+    let first = hasher(&[FIRST_PEPPER]);
+    let current = hasher(&[FIRST_PEPPER, SECOND_PEPPER]);
+    let outdated = user_hashed_with(&first, "412345678");
+    let up_to_date = user_hashed_with(&current, "412000111");
+    let unpeppered = user_hashed_with(&PhoneHasher::default(), "412000222");
+    let repository = Arc::new(MockPhoneHashes::default());
+    *repository.users.lock().unwrap() = vec![outdated.clone(), up_to_date.clone(), unpeppered.clone()];
+    let service = create_service(repository.clone(), current.clone(), 100);
+
+    assert_eq!(service.outdated_count().await.unwrap(), 1);
+    let report = service.rehash_batch().await.unwrap();
+
+    assert_eq!((report.scanned, report.rehashed, report.skipped), (1, 1, 0));
+    let users = repository.users.lock().unwrap().clone();
+    assert_eq!(users[0].phone_hash, current.hash("412345678"));
+    assert_eq!(users[0].phone_hash_version, 2);
+    assert_eq!(users[1].phone_hash, up_to_date.phone_hash);
+    // Bare SHA-256 hashes are rehashed when the user signs in
+    assert_eq!(users[2].phone_hash, unpeppered.phone_hash);
+    assert_eq!(service.outdated_count().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_rehash_batch_honours_batch_size_and_skips_changed_rows() {
+    let first = hasher(&[FIRST_PEPPER]);
+    let current = hasher(&[FIRST_PEPPER, SECOND_PEPPER]);
+    let users: Vec<User> = ["412000001", "412000002", "412000003"]
+        .iter()
+        .map(|phone| user_hashed_with(&first, phone))
+        .collect();
+    let repository = Arc::new(MockPhoneHashes::default());
+    *repository.users.lock().unwrap() = users.clone();
+    repository.concurrently_updated.lock().unwrap().push(users[0].id);
+    let service = create_service(repository.clone(), current, 2);
+
+    let report = service.rehash_batch().await.unwrap();
+
+    assert_eq!((report.scanned, report.rehashed, report.skipped), (2, 1, 1));
+    assert_eq!(service.outdated_count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_rehash_batch_does_nothing_without_a_rotation() {
+    let repository = Arc::new(MockPhoneHashes::default());
+    *repository.users.lock().unwrap() = vec![user_hashed_with(&PhoneHasher::default(), "412345678")];
+    let service = create_service(repository, hasher(&[FIRST_PEPPER]), 100);
+
+    assert_eq!(service.rehash_batch().await.unwrap(), Default::default());
+    assert_eq!(service.outdated_count().await.unwrap(), 0);
+}
@@ -0,0 +1,38 @@
+//! Configuration for the reconciliation service
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+/// Configuration for the reconciliation service
+#[derive(Debug, Clone)]
+pub struct ReconciliationConfig {
+    /// Hour of the day (UTC) at which the previous day is reconciled
+    pub run_hour_utc: u32,
+    /// Largest tolerated total discrepancy per currency before alerting, in minor units
+    pub alert_threshold: i64,
+    /// Whether to enable the nightly background job
+    pub enabled: bool,
+}
+
+impl ReconciliationConfig {
+    /// Next scheduled run strictly after `now`
+    pub fn next_run_after(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let run_time = NaiveTime::from_hms_opt(self.run_hour_utc.min(23), 0, 0).unwrap_or(NaiveTime::MIN);
+        let today = now.date_naive().and_time(run_time).and_utc();
+
+        if today > now {
+            today
+        } else {
+            today + Duration::days(1)
+        }
+    }
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            run_hour_utc: 3, // Leaves time for late provider settlements
+            alert_threshold: 1_000, // 10.00 in AUD or CNY
+            enabled: true,
+        }
+    }
+}
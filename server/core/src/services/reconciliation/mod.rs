@@ -0,0 +1,19 @@
+//! Financial reconciliation service module comparing the payment ledger with provider reports
+//!
+//! This module handles:
+//! - Fetching provider balance transactions for a period
+//! - Matching them against ledger entries by provider reference
+//! - Persisting discrepancy reports for review and export
+//! - Alerting administrators when discrepancies exceed a threshold
+//! - Running the reconciliation nightly for the previous day
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ReconciliationConfig;
+pub use service::ReconciliationService;
+pub use traits::{PaymentReportProviderTrait, ReconciliationAlertTrait};
@@ -0,0 +1,184 @@
+//! Reconciliation service for the payment ledger
+//!
+//! Each run fetches the provider's balance transactions for a period,
+//! matches them against the ledger and saves the outcome as a report.
+//! Reports whose discrepancies exceed the configured threshold in any
+//! currency are sent to administrators. A failed alert is logged and
+//! recorded on the report rather than failing the run, so the report is
+//! never lost.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::reconciliation::ReconciliationReport;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{PaymentLedgerRepository, ReconciliationReportRepository};
+
+use super::config::ReconciliationConfig;
+use super::traits::{PaymentReportProviderTrait, ReconciliationAlertTrait};
+
+/// Service for reconciling the payment ledger against provider reports
+pub struct ReconciliationService<L, R, P, A>
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    ledger: Arc<L>,
+    reports: Arc<R>,
+    provider: Arc<P>,
+    alerts: Arc<A>,
+    config: ReconciliationConfig,
+}
+
+impl<L, R, P, A> ReconciliationService<L, R, P, A>
+where
+    L: PaymentLedgerRepository + 'static,
+    R: ReconciliationReportRepository + 'static,
+    P: PaymentReportProviderTrait + 'static,
+    A: ReconciliationAlertTrait + 'static,
+{
+    /// Create a new reconciliation service
+    pub fn new(
+        ledger: Arc<L>,
+        reports: Arc<R>,
+        provider: Arc<P>,
+        alerts: Arc<A>,
+        config: ReconciliationConfig,
+    ) -> Self {
+        Self {
+            ledger,
+            reports,
+            provider,
+            alerts,
+            config,
+        }
+    }
+
+    /// Create a new reconciliation service with default configuration
+    pub fn with_defaults(ledger: Arc<L>, reports: Arc<R>, provider: Arc<P>, alerts: Arc<A>) -> Self {
+        Self::new(ledger, reports, provider, alerts, ReconciliationConfig::default())
+    }
+
+    /// Reconcile one UTC calendar day
+    pub async fn reconcile_day(&self, date: NaiveDate) -> DomainResult<ReconciliationReport> {
+        let start = date.and_time(chrono::NaiveTime::MIN).and_utc();
+        self.reconcile_period(start, start + Duration::days(1)).await
+    }
+
+    /// Reconcile a period and save the resulting report
+    ///
+    /// # Returns
+    /// * `Ok(ReconciliationReport)` - The saved report
+    /// * `Err(DomainError::Validation)` - The period is empty or ends in the future
+    /// * `Err(DomainError::Internal)` - The provider report could not be fetched
+    pub async fn reconcile_period(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> DomainResult<ReconciliationReport> {
+        if start >= end {
+            return Err(DomainError::Validation {
+                message: "Reconciliation period must end after it starts".to_string(),
+            });
+        }
+
+        if end > Utc::now() {
+            return Err(DomainError::Validation {
+                message: "Reconciliation period must not end in the future".to_string(),
+            });
+        }
+
+        let provider = self.provider.provider_name().to_string();
+        let ledger = self.ledger.find_by_period(&provider, start, end).await?;
+        let transactions = self
+            .provider
+            .fetch_transactions(start, end)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to fetch {} report: {}", provider, e),
+            })?;
+
+        let mut report = ReconciliationReport::reconcile(provider, start, end, &ledger, &transactions);
+
+        if report.exceeds_threshold(self.config.alert_threshold) {
+            match self.alerts.send_alert(&report).await {
+                Ok(()) => report.alerted = true,
+                Err(e) => error!(report_id = %report.id, error = %e, "Failed to send reconciliation alert"),
+            }
+        }
+
+        let report = self.reports.save(report).await?;
+
+        if report.is_balanced() {
+            info!(
+                report_id = %report.id,
+                provider = %report.provider,
+                matched = report.matched_count,
+                "Reconciliation balanced"
+            );
+        } else {
+            warn!(
+                report_id = %report.id,
+                provider = %report.provider,
+                matched = report.matched_count,
+                discrepancies = report.discrepancies.len(),
+                totals = ?report.discrepancy_totals,
+                "Reconciliation found discrepancies"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Get a report with its discrepancies
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No report exists with this ID
+    pub async fn get_report(&self, id: Uuid) -> DomainResult<ReconciliationReport> {
+        self.reports
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Reconciliation report".to_string(),
+            })
+    }
+
+    /// List the most recent reports
+    pub async fn list_reports(&self, limit: usize) -> DomainResult<Vec<ReconciliationReport>> {
+        self.reports.list_recent(limit).await
+    }
+
+    /// Start the nightly reconciliation as a background task
+    ///
+    /// This spawns a tokio task that reconciles the previous UTC day once a
+    /// day at the configured hour
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Nightly reconciliation is disabled");
+            return;
+        }
+
+        tokio::spawn(async move {
+            info!(
+                "Nightly reconciliation started - will run daily at {:02}:00 UTC",
+                self.config.run_hour_utc
+            );
+
+            loop {
+                let now = Utc::now();
+                let next_run = self.config.next_run_after(now);
+                let wait = (next_run - now).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+
+                let date = next_run.date_naive() - Duration::days(1);
+                if let Err(e) = self.reconcile_day(date).await {
+                    error!("Nightly reconciliation for {} failed: {}", date, e);
+                }
+            }
+        });
+    }
+}
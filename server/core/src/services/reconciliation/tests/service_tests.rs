@@ -0,0 +1,262 @@
+//! Unit tests for the reconciliation service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::payment::{LedgerEntry, LedgerEntryKind};
+use crate::domain::entities::reconciliation::{ProviderTransaction, ReconciliationReport};
+use crate::errors::DomainError;
+use crate::repositories::{PaymentLedgerRepository, ReconciliationReportRepository};
+use crate::services::reconciliation::{
+    PaymentReportProviderTrait, ReconciliationAlertTrait, ReconciliationConfig,
+    ReconciliationService,
+};
+
+struct MockLedger {
+    entries: Vec<LedgerEntry>,
+}
+
+#[async_trait]
+impl PaymentLedgerRepository for MockLedger {
+    async fn save(&self, entry: LedgerEntry) -> Result<LedgerEntry, DomainError> {
+        Ok(entry)
+    }
+
+    async fn find_by_period(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, DomainError> {
+        Ok(self
+            .entries
+            .iter()
+            .filter(|e| e.provider == provider && e.occurred_at >= start && e.occurred_at < end)
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Default)]
+struct MockReports {
+    reports: Mutex<HashMap<Uuid, ReconciliationReport>>,
+}
+
+#[async_trait]
+impl ReconciliationReportRepository for MockReports {
+    async fn save(&self, report: ReconciliationReport) -> Result<ReconciliationReport, DomainError> {
+        self.reports.lock().unwrap().insert(report.id, report.clone());
+        Ok(report)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ReconciliationReport>, DomainError> {
+        Ok(self.reports.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list_recent(&self, limit: usize) -> Result<Vec<ReconciliationReport>, DomainError> {
+        let mut reports: Vec<_> = self.reports.lock().unwrap().values().cloned().collect();
+        reports.sort_by_key(|r| std::cmp::Reverse(r.period_start));
+        reports.truncate(limit);
+        Ok(reports)
+    }
+}
+
+struct MockProvider {
+    transactions: Result<Vec<ProviderTransaction>, String>,
+}
+
+#[async_trait]
+impl PaymentReportProviderTrait for MockProvider {
+    fn provider_name(&self) -> &str {
+        "stripe"
+    }
+
+    async fn fetch_transactions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ProviderTransaction>, String> {
+        self.transactions.clone().map(|transactions| {
+            transactions
+                .into_iter()
+                .filter(|t| t.occurred_at >= start && t.occurred_at < end)
+                .collect()
+        })
+    }
+}
+
+#[derive(Default)]
+struct MockAlerts {
+    fail: bool,
+    sent: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl ReconciliationAlertTrait for MockAlerts {
+    async fn send_alert(&self, report: &ReconciliationReport) -> Result<(), String> {
+        if self.fail {
+            return Err("webhook unavailable".to_string());
+        }
+        self.sent.lock().unwrap().push(report.id);
+        Ok(())
+    }
+}
+
+fn day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 8, 1).unwrap()
+}
+
+fn at_noon() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 8, 1, 12, 0, 0).unwrap()
+}
+
+fn ledger_entry(reference: &str, amount: i64) -> LedgerEntry {
+    LedgerEntry::new("stripe", reference, LedgerEntryKind::Charge, amount, "AUD", at_noon()).unwrap()
+}
+
+fn provider_transaction(reference: &str, amount: i64) -> ProviderTransaction {
+    ProviderTransaction {
+        reference: reference.to_string(),
+        kind: LedgerEntryKind::Charge,
+        amount,
+        currency: "AUD".to_string(),
+        occurred_at: at_noon(),
+    }
+}
+
+type TestService = ReconciliationService<MockLedger, MockReports, MockProvider, MockAlerts>;
+
+fn service(
+    entries: Vec<LedgerEntry>,
+    transactions: Result<Vec<ProviderTransaction>, String>,
+    alerts: MockAlerts,
+) -> (TestService, Arc<MockReports>, Arc<MockAlerts>) {
+    let reports = Arc::new(MockReports::default());
+    let alerts = Arc::new(alerts);
+    let service = ReconciliationService::new(
+        Arc::new(MockLedger { entries }),
+        reports.clone(),
+        Arc::new(MockProvider { transactions }),
+        alerts.clone(),
+        ReconciliationConfig {
+            alert_threshold: 1_000,
+            ..ReconciliationConfig::default()
+        },
+    );
+    (service, reports, alerts)
+}
+
+#[tokio::test]
+async fn test_balanced_day_is_saved_without_alert() {
+    let (service, reports, alerts) = service(
+        vec![ledger_entry("txn_1", 5000)],
+        Ok(vec![provider_transaction("txn_1", 5000)]),
+        MockAlerts::default(),
+    );
+
+    let report = service.reconcile_day(day()).await.unwrap();
+
+    assert!(report.is_balanced());
+    assert!(!report.alerted);
+    assert_eq!(report.period_start, Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap());
+    assert_eq!(report.period_end, Utc.with_ymd_and_hms(2025, 8, 2, 0, 0, 0).unwrap());
+    assert!(alerts.sent.lock().unwrap().is_empty());
+    assert!(reports.find_by_id(report.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_discrepancy_below_threshold_does_not_alert() {
+    let (service, _, alerts) = service(
+        vec![ledger_entry("txn_1", 5000)],
+        Ok(vec![provider_transaction("txn_1", 4500)]),
+        MockAlerts::default(),
+    );
+
+    let report = service.reconcile_day(day()).await.unwrap();
+
+    assert_eq!(report.discrepancies.len(), 1);
+    assert!(!report.alerted);
+    assert!(alerts.sent.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_discrepancy_above_threshold_alerts() {
+    let (service, _, alerts) = service(
+        vec![ledger_entry("txn_1", 5000)],
+        Ok(vec![provider_transaction("txn_1", 5000), provider_transaction("txn_2", 2500)]),
+        MockAlerts::default(),
+    );
+
+    let report = service.reconcile_day(day()).await.unwrap();
+
+    assert!(report.alerted);
+    assert_eq!(*alerts.sent.lock().unwrap(), vec![report.id]);
+}
+
+#[tokio::test]
+async fn test_failed_alert_still_saves_report() {
+    let (service, reports, _) = service(
+        vec![],
+        Ok(vec![provider_transaction("txn_1", 2500)]),
+        MockAlerts {
+            fail: true,
+            ..MockAlerts::default()
+        },
+    );
+
+    let report = service.reconcile_day(day()).await.unwrap();
+
+    assert!(!report.alerted);
+    let saved = service.get_report(report.id).await.unwrap();
+    assert_eq!(saved.discrepancies.len(), 1);
+    assert_eq!(reports.list_recent(10).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_provider_failure_is_an_error() {
+    let (service, reports, _) = service(vec![], Err("timeout".to_string()), MockAlerts::default());
+
+    let result = service.reconcile_day(day()).await;
+
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert!(reports.list_recent(10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_periods_are_rejected() {
+    let (service, _, _) = service(vec![], Ok(vec![]), MockAlerts::default());
+    let start = at_noon();
+
+    assert!(matches!(
+        service.reconcile_period(start, start).await,
+        Err(DomainError::Validation { .. })
+    ));
+    assert!(matches!(
+        service.reconcile_period(Utc::now(), Utc::now() + Duration::hours(1)).await,
+        Err(DomainError::Validation { .. })
+    ));
+    assert!(matches!(
+        service.get_report(Uuid::new_v4()).await,
+        Err(DomainError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_next_run_is_at_configured_hour() {
+    let config = ReconciliationConfig {
+        run_hour_utc: 3,
+        ..ReconciliationConfig::default()
+    };
+
+    let before = Utc.with_ymd_and_hms(2025, 8, 1, 1, 30, 0).unwrap();
+    assert_eq!(config.next_run_after(before), Utc.with_ymd_and_hms(2025, 8, 1, 3, 0, 0).unwrap());
+
+    let exactly = Utc.with_ymd_and_hms(2025, 8, 1, 3, 0, 0).unwrap();
+    assert_eq!(config.next_run_after(exactly), Utc.with_ymd_and_hms(2025, 8, 2, 3, 0, 0).unwrap());
+
+    let after = Utc.with_ymd_and_hms(2025, 8, 1, 22, 0, 0).unwrap();
+    assert_eq!(config.next_run_after(after), Utc.with_ymd_and_hms(2025, 8, 2, 3, 0, 0).unwrap());
+}
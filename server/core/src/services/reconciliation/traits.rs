@@ -0,0 +1,39 @@
+//! Traits for payment provider reports and reconciliation alerts
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::reconciliation::{ProviderTransaction, ReconciliationReport};
+
+/// Trait for fetching a payment provider's record of balance movements
+#[async_trait]
+pub trait PaymentReportProviderTrait: Send + Sync {
+    /// Provider name as recorded on ledger entries (e.g. "stripe")
+    fn provider_name(&self) -> &str;
+
+    /// Fetch every balance movement within a period
+    ///
+    /// # Arguments
+    /// * `start` - Start of the period (inclusive)
+    /// * `end` - End of the period (exclusive)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ProviderTransaction>)` - All movements, across pages
+    /// * `Err(String)` - Why the report could not be fetched
+    async fn fetch_transactions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ProviderTransaction>, String>;
+}
+
+/// Trait for notifying administrators about reconciliation problems
+#[async_trait]
+pub trait ReconciliationAlertTrait: Send + Sync {
+    /// Send an alert about a report whose discrepancies exceed the threshold
+    ///
+    /// # Returns
+    /// * `Ok(())` - Alert delivered
+    /// * `Err(String)` - Why the alert could not be delivered
+    async fn send_alert(&self, report: &ReconciliationReport) -> Result<(), String>;
+}
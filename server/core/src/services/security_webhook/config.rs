@@ -0,0 +1,34 @@
+//! Configuration for the security webhook service
+
+/// Configuration for the security webhook service
+#[derive(Debug, Clone)]
+pub struct SecurityWebhookConfig {
+    /// How often the background task delivers queued events (in seconds)
+    pub delivery_interval_seconds: u64,
+    /// Maximum number of deliveries attempted per cycle
+    pub max_deliveries_per_cycle: usize,
+    /// Attempts before a delivery is given up
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every failed attempt (in seconds)
+    pub base_retry_delay_seconds: i64,
+    /// Upper bound for the retry delay (in seconds)
+    pub max_retry_delay_seconds: i64,
+    /// Maximum number of registered webhooks
+    pub max_webhooks: usize,
+    /// Whether to enable background delivery
+    pub enabled: bool,
+}
+
+impl Default for SecurityWebhookConfig {
+    fn default() -> Self {
+        Self {
+            delivery_interval_seconds: 15,
+            max_deliveries_per_cycle: 100,
+            max_attempts: 8, // Gives up after roughly two hours
+            base_retry_delay_seconds: 30,
+            max_retry_delay_seconds: 3600,
+            max_webhooks: 20,
+            enabled: true,
+        }
+    }
+}
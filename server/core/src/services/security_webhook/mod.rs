@@ -0,0 +1,22 @@
+//! Security webhook service module for forwarding audit events to operators
+//!
+//! This module handles:
+//! - Registering HTTPS endpoints with a generated signing secret
+//! - Queueing rate-limit violations, account locks, refresh-token reuse and
+//!   administrator actions recorded by the audit service
+//! - Delivering HMAC-signed payloads with exponential backoff retries
+//! - Dead-lettering deliveries that are given up and redelivering them
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::SecurityWebhookConfig;
+pub use service::{
+    sign_payload, SecurityWebhookService, WebhookDeliveryResult, EVENT_ID_HEADER,
+    SIGNATURE_HEADER,
+};
+pub use traits::{WebhookRequest, WebhookSenderTrait};
@@ -0,0 +1,484 @@
+//! Security webhook service
+//!
+//! Every security event recorded by the audit service is matched against
+//! the registered webhooks and queued as one delivery per subscribed
+//! endpoint. A background task posts due deliveries and reschedules failed
+//! ones with exponential backoff until `max_attempts` is reached.
+//!
+//! Payloads are signed with the webhook's secret. The signature header has
+//! the form `t=<unix timestamp>,v1=<hex HMAC-SHA256>`, where the HMAC covers
+//! `<timestamp>.<body>`; receivers should reject stale timestamps to
+//! prevent replays.
+//!
+//! Deliveries given up after the last attempt are recorded as dead letters
+//! when a dead-letter repository is configured, so administrators can
+//! redeliver them once the endpoint is fixed. Redeliveries carry the
+//! original event ID.
+//!
+//! Webhooks belong to a tenant and receive the events of its accounts. The
+//! background task delivers the events of each tenant given to
+//! [`SecurityWebhookService::with_tenants`] in turn.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde_json::{json, Value as JsonValue};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::audit::AuditLog;
+use crate::domain::entities::security_webhook::{
+    SecurityEventKind, SecurityWebhook, WebhookDelivery, WebhookDeliveryStatus,
+};
+use crate::domain::entities::webhook_dead_letter::{
+    WebhookDeadLetter, WebhookDirection, SECURITY_WEBHOOK_SOURCE,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{SecurityWebhookRepository, WebhookDeadLetterRepository};
+use crate::services::audit::AuditEventPublisherTrait;
+use crate::services::tenant::{self, TenantId};
+use crate::services::webhook_dead_letter::WebhookReplayTrait;
+
+use super::config::SecurityWebhookConfig;
+use super::traits::{WebhookRequest, WebhookSenderTrait};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "X-RenovEasy-Signature";
+
+/// Header carrying the audit event ID, for deduplication by receivers
+pub const EVENT_ID_HEADER: &str = "X-RenovEasy-Event-Id";
+
+/// Maximum length of a webhook URL
+const MAX_URL_LENGTH: usize = 2048;
+
+/// Service for registering security webhooks and delivering events to them
+pub struct SecurityWebhookService<R, S>
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    repository: Arc<R>,
+    sender: Arc<S>,
+    dead_letters: Option<Arc<dyn WebhookDeadLetterRepository>>,
+    config: SecurityWebhookConfig,
+    tenants: Vec<TenantId>,
+}
+
+impl<R, S> SecurityWebhookService<R, S>
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    /// Create a new security webhook service
+    pub fn new(repository: Arc<R>, sender: Arc<S>, config: SecurityWebhookConfig) -> Self {
+        Self {
+            repository,
+            sender,
+            dead_letters: None,
+            config,
+            tenants: vec![TenantId::default()],
+        }
+    }
+
+    /// Record deliveries that are given up as dead letters
+    pub fn with_dead_letters(mut self, dead_letters: Arc<dyn WebhookDeadLetterRepository>) -> Self {
+        self.dead_letters = Some(dead_letters);
+        self
+    }
+
+    /// Deliver the events of these tenants in the background task, instead
+    /// of those of the default tenant only
+    pub fn with_tenants(mut self, tenants: Vec<TenantId>) -> Self {
+        self.tenants = tenants;
+        self
+    }
+
+    /// Create a new security webhook service with default configuration
+    pub fn with_defaults(repository: Arc<R>, sender: Arc<S>) -> Self {
+        Self::new(repository, sender, SecurityWebhookConfig::default())
+    }
+
+    /// Register an endpoint for security events
+    ///
+    /// An empty `events` list subscribes the endpoint to every kind.
+    ///
+    /// # Returns
+    /// * `Ok(SecurityWebhook)` - The saved webhook including its signing
+    ///   secret, which is shown to the administrator only once
+    /// * `Err(DomainError::Validation)` - The URL is not HTTPS
+    /// * `Err(DomainError::BusinessRule)` - The webhook limit is reached
+    pub async fn register_webhook(
+        &self,
+        url: String,
+        events: Vec<SecurityEventKind>,
+        created_by: Uuid,
+    ) -> DomainResult<SecurityWebhook> {
+        if !is_valid_webhook_url(&url) {
+            return Err(DomainError::Validation {
+                message: "Webhook URL must be an HTTPS URL".to_string(),
+            });
+        }
+
+        if self.repository.list().await?.len() >= self.config.max_webhooks {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "At most {} security webhooks can be registered",
+                    self.config.max_webhooks
+                ),
+            });
+        }
+
+        let mut events = if events.is_empty() {
+            SecurityEventKind::ALL.to_vec()
+        } else {
+            events
+        };
+        events.sort_by_key(|kind| kind.as_str());
+        events.dedup();
+
+        let webhook = SecurityWebhook::new(url, random_secret(), events, created_by);
+        let webhook = self.repository.save(webhook).await?;
+        info!(
+            webhook_id = %webhook.id,
+            created_by = %created_by,
+            "Security webhook registered"
+        );
+
+        Ok(webhook)
+    }
+
+    /// List registered webhooks
+    pub async fn list_webhooks(&self) -> DomainResult<Vec<SecurityWebhook>> {
+        self.repository.list().await
+    }
+
+    /// Delete a webhook and drop its queued deliveries
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No webhook exists with this ID
+    pub async fn delete_webhook(&self, id: Uuid) -> DomainResult<()> {
+        if !self.repository.delete(id).await? {
+            return Err(DomainError::NotFound {
+                resource: "Security webhook".to_string(),
+            });
+        }
+
+        info!(webhook_id = %id, "Security webhook deleted");
+        Ok(())
+    }
+
+    /// Queue an audit event for every webhook subscribed to its kind
+    ///
+    /// Events that are not security events are ignored.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of deliveries queued
+    pub async fn enqueue(&self, audit_log: &AuditLog) -> DomainResult<usize> {
+        let Some(kind) = SecurityEventKind::from_event_type(audit_log.event_type) else {
+            return Ok(0);
+        };
+
+        let webhooks = self.repository.find_subscribed(kind).await?;
+        if webhooks.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = event_payload(kind, audit_log);
+        let deliveries: Vec<_> = webhooks
+            .iter()
+            .map(|webhook| WebhookDelivery::new(webhook.id, kind, payload.clone()))
+            .collect();
+        let queued = deliveries.len();
+
+        self.repository.save_deliveries(deliveries).await?;
+        Ok(queued)
+    }
+
+    /// Attempt deliveries that are due
+    ///
+    /// At most `max_deliveries_per_cycle` deliveries are attempted per call.
+    ///
+    /// # Returns
+    /// * `Ok(WebhookDeliveryResult)` - Summary of the delivery cycle
+    /// * `Err(DomainError)` - If deliveries could not be loaded
+    pub async fn deliver_due(&self) -> DomainResult<WebhookDeliveryResult> {
+        let now = Utc::now();
+        let mut result = WebhookDeliveryResult::default();
+        let mut webhooks: HashMap<Uuid, Option<SecurityWebhook>> = HashMap::new();
+
+        let deliveries = self
+            .repository
+            .find_due_deliveries(now, self.config.max_deliveries_per_cycle)
+            .await?;
+
+        for mut delivery in deliveries {
+            let webhook = match webhooks.get(&delivery.webhook_id) {
+                Some(webhook) => webhook.clone(),
+                None => {
+                    let webhook = self.repository.find_by_id(delivery.webhook_id).await?;
+                    webhooks.insert(delivery.webhook_id, webhook.clone());
+                    webhook
+                }
+            };
+
+            let sent = match webhook {
+                Some(webhook) if webhook.is_active => {
+                    let request = signed_request(&webhook, &delivery.payload, delivery.id, now.timestamp());
+                    self.sender.send(&request).await
+                }
+                _ => Err("Webhook is no longer active".to_string()),
+            };
+
+            match sent {
+                Ok(()) => {
+                    delivery.mark_delivered();
+                    result.delivered += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        delivery_id = %delivery.id,
+                        webhook_id = %delivery.webhook_id,
+                        attempt = delivery.attempts + 1,
+                        error = %e,
+                        "Security webhook delivery failed"
+                    );
+                    delivery.record_failure(
+                        e,
+                        now,
+                        self.config.max_attempts,
+                        Duration::seconds(self.config.base_retry_delay_seconds),
+                        Duration::seconds(self.config.max_retry_delay_seconds),
+                    );
+                    if delivery.status == WebhookDeliveryStatus::Failed {
+                        self.dead_letter(&delivery).await;
+                        result.failed += 1;
+                    } else {
+                        result.retried += 1;
+                    }
+                }
+            }
+
+            let delivery_id = delivery.id;
+            if let Err(e) = self.repository.update_delivery(delivery).await {
+                error!(delivery_id = %delivery_id, error = %e, "Failed to update security webhook delivery");
+                result.errors.push(format!("{}: {}", delivery_id, e));
+            }
+        }
+
+        if result.total_processed() > 0 {
+            info!(
+                delivered = result.delivered,
+                retried = result.retried,
+                failed = result.failed,
+                "Security webhook delivery completed"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Record a delivery that was given up
+    async fn dead_letter(&self, delivery: &WebhookDelivery) {
+        let Some(ref dead_letters) = self.dead_letters else {
+            return;
+        };
+
+        if let Err(e) = dead_letters.record(WebhookDeadLetter::outbound(delivery)).await {
+            error!(delivery_id = %delivery.id, error = %e, "Failed to dead-letter security webhook delivery");
+        }
+    }
+
+    /// Start the delivery worker as a background task
+    ///
+    /// This spawns a tokio task that delivers queued events at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Security webhook delivery is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(self.config.delivery_interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Security webhook worker started - will deliver every {} seconds",
+                self.config.delivery_interval_seconds
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+
+            loop {
+                interval_timer.tick().await;
+
+                for tenant in &self.tenants {
+                    match tenant::scope(tenant.clone(), self.deliver_due()).await {
+                        Ok(result) => {
+                            if !result.errors.is_empty() {
+                                warn!(
+                                    "Security webhook delivery for tenant {} completed with errors: {:?}",
+                                    tenant, result.errors
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            error!("Security webhook delivery cycle for tenant {} failed: {}", tenant, e);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<R, S> AuditEventPublisherTrait for SecurityWebhookService<R, S>
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    async fn publish(&self, audit_log: &AuditLog) -> Result<(), String> {
+        self.enqueue(audit_log).await.map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl<R, S> WebhookReplayTrait for SecurityWebhookService<R, S>
+where
+    R: SecurityWebhookRepository + 'static,
+    S: WebhookSenderTrait + 'static,
+{
+    fn direction(&self) -> WebhookDirection {
+        WebhookDirection::Outbound
+    }
+
+    fn source(&self) -> &str {
+        SECURITY_WEBHOOK_SOURCE
+    }
+
+    async fn replay(&self, dead_letter: &WebhookDeadLetter) -> Result<String, String> {
+        let webhook = match dead_letter.target_id {
+            Some(webhook_id) => {
+                let tenant = self
+                    .repository
+                    .find_tenant_by_id(webhook_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .unwrap_or_default();
+                tenant::scope(tenant, self.repository.find_by_id(webhook_id))
+                    .await
+                    .map_err(|e| e.to_string())?
+            }
+            None => None,
+        };
+        let Some(webhook) = webhook.filter(|webhook| webhook.is_active) else {
+            return Err("Webhook is no longer active".to_string());
+        };
+
+        let delivery_id = Uuid::parse_str(&dead_letter.event_id).unwrap_or(dead_letter.id);
+        let request = signed_request(&webhook, &dead_letter.payload, delivery_id, Utc::now().timestamp());
+        self.sender.send(&request).await?;
+
+        info!(webhook_id = %webhook.id, delivery_id = %delivery_id, "Security webhook delivery replayed");
+        Ok("delivered".to_string())
+    }
+}
+
+/// Result of a security webhook delivery cycle
+#[derive(Debug, Default)]
+pub struct WebhookDeliveryResult {
+    /// Number of deliveries accepted by their endpoint
+    pub delivered: usize,
+    /// Number of failed deliveries scheduled for another attempt
+    pub retried: usize,
+    /// Number of deliveries given up after the last attempt
+    pub failed: usize,
+    /// Any errors encountered while saving delivery state
+    pub errors: Vec<String>,
+}
+
+impl WebhookDeliveryResult {
+    /// Get total number of deliveries attempted
+    pub fn total_processed(&self) -> usize {
+        self.delivered + self.retried + self.failed
+    }
+}
+
+/// Compute the signature header value for a payload
+///
+/// # Arguments
+/// * `secret` - The webhook's signing secret
+/// * `timestamp` - Unix timestamp of the attempt
+/// * `body` - The exact request body
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("t={},v1={}", timestamp, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Build the signed request for one delivery attempt
+///
+/// `delivery_id` is sent as the event ID when the payload has none.
+fn signed_request(
+    webhook: &SecurityWebhook,
+    payload: &JsonValue,
+    delivery_id: Uuid,
+    timestamp: i64,
+) -> WebhookRequest {
+    let body = payload.to_string();
+    let event_id = payload
+        .get("id")
+        .and_then(JsonValue::as_str)
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .unwrap_or(delivery_id);
+
+    WebhookRequest {
+        url: webhook.url.clone(),
+        signature: sign_payload(&webhook.secret, timestamp, &body),
+        body,
+        event_id,
+    }
+}
+
+/// JSON body describing an audit event
+///
+/// Phone numbers are only included masked.
+fn event_payload(kind: SecurityEventKind, audit_log: &AuditLog) -> JsonValue {
+    json!({
+        "id": audit_log.id,
+        "kind": kind.as_str(),
+        "event_type": audit_log.event_type.as_str(),
+        "occurred_at": audit_log.created_at.to_rfc3339(),
+        "user_id": audit_log.user_id,
+        "phone_masked": audit_log.phone_masked,
+        "ip_address": audit_log.ip_address,
+        "reason": audit_log.failure_reason,
+        "data": audit_log.event_data,
+    })
+}
+
+/// Random signing secret with 256 bits of entropy
+fn random_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+/// Whether a webhook URL may be registered
+///
+/// Only HTTPS URLs without credentials or fragments are accepted.
+fn is_valid_webhook_url(url: &str) -> bool {
+    let Some(rest) = url.strip_prefix("https://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?']).next().unwrap_or_default();
+    url.len() <= MAX_URL_LENGTH && !host.is_empty() && !host.contains('@') && !url.contains('#')
+}
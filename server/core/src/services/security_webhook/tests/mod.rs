@@ -0,0 +1,4 @@
+//! Tests for the security webhook service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,314 @@
+//! Unit tests for the security webhook service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::audit::{AuditEventType, AuditLog};
+use crate::domain::entities::security_webhook::{
+    SecurityEventKind, SecurityWebhook, WebhookDelivery, WebhookDeliveryStatus,
+};
+use crate::domain::entities::webhook_dead_letter::WebhookDirection;
+use crate::errors::DomainError;
+use crate::repositories::SecurityWebhookRepository;
+use crate::services::security_webhook::{
+    sign_payload, SecurityWebhookConfig, SecurityWebhookService, WebhookRequest,
+    WebhookSenderTrait,
+};
+use crate::services::tenant::TenantId;
+use crate::services::webhook_dead_letter::tests::service_tests::MockDeadLetters;
+use crate::services::webhook_dead_letter::WebhookReplayTrait;
+
+#[derive(Default)]
+struct MockSecurityWebhookRepository {
+    webhooks: Mutex<HashMap<Uuid, SecurityWebhook>>,
+    deliveries: Mutex<Vec<WebhookDelivery>>,
+}
+
+impl MockSecurityWebhookRepository {
+    fn deliveries(&self) -> Vec<WebhookDelivery> {
+        self.deliveries.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl SecurityWebhookRepository for MockSecurityWebhookRepository {
+    async fn save(&self, webhook: SecurityWebhook) -> Result<SecurityWebhook, DomainError> {
+        self.webhooks.lock().unwrap().insert(webhook.id, webhook.clone());
+        Ok(webhook)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SecurityWebhook>, DomainError> {
+        Ok(self.webhooks.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn find_tenant_by_id(&self, id: Uuid) -> Result<Option<TenantId>, DomainError> {
+        Ok(self.webhooks.lock().unwrap().contains_key(&id).then(TenantId::default))
+    }
+
+    async fn list(&self) -> Result<Vec<SecurityWebhook>, DomainError> {
+        Ok(self.webhooks.lock().unwrap().values().cloned().collect())
+    }
+
+    async fn find_subscribed(&self, kind: SecurityEventKind) -> Result<Vec<SecurityWebhook>, DomainError> {
+        Ok(self
+            .webhooks
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|w| w.subscribes_to(kind))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        self.deliveries.lock().unwrap().retain(|d| d.webhook_id != id);
+        Ok(self.webhooks.lock().unwrap().remove(&id).is_some())
+    }
+
+    async fn save_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), DomainError> {
+        self.deliveries.lock().unwrap().extend(deliveries);
+        Ok(())
+    }
+
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        Ok(self
+            .deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|d| d.status == WebhookDeliveryStatus::Pending && d.next_attempt_at <= now)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_delivery(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, DomainError> {
+        let mut deliveries = self.deliveries.lock().unwrap();
+        let existing = deliveries
+            .iter_mut()
+            .find(|d| d.id == delivery.id)
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Security webhook delivery".to_string(),
+            })?;
+        *existing = delivery.clone();
+        Ok(delivery)
+    }
+}
+
+#[derive(Default)]
+struct MockWebhookSender {
+    fail: bool,
+    sent: Mutex<Vec<WebhookRequest>>,
+}
+
+#[async_trait]
+impl WebhookSenderTrait for MockWebhookSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String> {
+        self.sent.lock().unwrap().push(request.clone());
+        if self.fail {
+            Err("Webhook returned 500 Internal Server Error".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn service(
+    sender: MockWebhookSender,
+) -> (
+    SecurityWebhookService<MockSecurityWebhookRepository, MockWebhookSender>,
+    Arc<MockSecurityWebhookRepository>,
+    Arc<MockWebhookSender>,
+) {
+    let repository = Arc::new(MockSecurityWebhookRepository::default());
+    let sender = Arc::new(sender);
+    let config = SecurityWebhookConfig {
+        max_attempts: 3,
+        base_retry_delay_seconds: 30,
+        max_retry_delay_seconds: 3600,
+        ..Default::default()
+    };
+    (
+        SecurityWebhookService::new(repository.clone(), sender.clone(), config),
+        repository,
+        sender,
+    )
+}
+
+fn make_due(repository: &MockSecurityWebhookRepository) {
+    for delivery in repository.deliveries.lock().unwrap().iter_mut() {
+        delivery.next_attempt_at = Utc::now() - Duration::seconds(1);
+    }
+}
+
+#[tokio::test]
+async fn test_register_rejects_non_https_url() {
+    let (service, _, _) = service(MockWebhookSender::default());
+
+    for url in ["http://example.com/hook", "https://", "https://user@example.com/hook"] {
+        let result = service
+            .register_webhook(url.to_string(), vec![], Uuid::new_v4())
+            .await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })), "{}", url);
+    }
+}
+
+#[tokio::test]
+async fn test_register_without_events_subscribes_to_all() {
+    let (service, _, _) = service(MockWebhookSender::default());
+
+    let webhook = service
+        .register_webhook("https://siem.example.com/hook".to_string(), vec![], Uuid::new_v4())
+        .await
+        .unwrap();
+
+    assert_eq!(webhook.events.len(), SecurityEventKind::ALL.len());
+    assert!(webhook.secret.starts_with("whsec_"));
+}
+
+#[tokio::test]
+async fn test_enqueue_matches_subscribed_webhooks_only() {
+    let (service, repository, _) = service(MockWebhookSender::default());
+    let admin = Uuid::new_v4();
+
+    let locks = service
+        .register_webhook("https://a.example.com".to_string(), vec![SecurityEventKind::AccountLocked], admin)
+        .await
+        .unwrap();
+    service
+        .register_webhook("https://b.example.com".to_string(), vec![SecurityEventKind::AdminAction], admin)
+        .await
+        .unwrap();
+
+    let log = AuditLog::new(AuditEventType::AccountLocked, "system".to_string());
+    assert_eq!(service.enqueue(&log).await.unwrap(), 1);
+
+    let login = AuditLog::new(AuditEventType::LoginSuccess, "203.0.113.1".to_string());
+    assert_eq!(service.enqueue(&login).await.unwrap(), 0);
+
+    let deliveries = repository.deliveries();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].webhook_id, locks.id);
+    assert_eq!(deliveries[0].payload["kind"], "account_locked");
+}
+
+#[tokio::test]
+async fn test_deliver_signs_payload() {
+    let (service, repository, sender) = service(MockWebhookSender::default());
+
+    let webhook = service
+        .register_webhook("https://siem.example.com/hook".to_string(), vec![], Uuid::new_v4())
+        .await
+        .unwrap();
+    let log = AuditLog::new(AuditEventType::RateLimitIpExceeded, "203.0.113.1".to_string());
+    service.enqueue(&log).await.unwrap();
+
+    let result = service.deliver_due().await.unwrap();
+    assert_eq!(result.delivered, 1);
+
+    let sent = sender.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].event_id, log.id);
+
+    let timestamp: i64 = sent[0].signature[2..sent[0].signature.find(',').unwrap()]
+        .parse()
+        .unwrap();
+    assert_eq!(sent[0].signature, sign_payload(&webhook.secret, timestamp, &sent[0].body));
+
+    let delivery = &repository.deliveries()[0];
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+    assert!(delivery.delivered_at.is_some());
+}
+
+#[tokio::test]
+async fn test_failed_delivery_backs_off_then_gives_up() {
+    let (service, repository, sender) = service(MockWebhookSender {
+        fail: true,
+        ..Default::default()
+    });
+
+    service
+        .register_webhook("https://siem.example.com/hook".to_string(), vec![], Uuid::new_v4())
+        .await
+        .unwrap();
+    let log = AuditLog::new(AuditEventType::RefreshTokenReuseDetected, "203.0.113.1".to_string());
+    service.enqueue(&log).await.unwrap();
+
+    let before = Utc::now();
+    let result = service.deliver_due().await.unwrap();
+    assert_eq!(result.retried, 1);
+    let delivery = repository.deliveries()[0].clone();
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert!(delivery.next_attempt_at >= before + Duration::seconds(30));
+
+    // Not due yet
+    assert_eq!(service.deliver_due().await.unwrap().total_processed(), 0);
+
+    make_due(&repository);
+    service.deliver_due().await.unwrap();
+    let delivery = repository.deliveries()[0].clone();
+    assert!(delivery.next_attempt_at >= before + Duration::seconds(60));
+
+    make_due(&repository);
+    let result = service.deliver_due().await.unwrap();
+    assert_eq!(result.failed, 1);
+
+    let delivery = repository.deliveries()[0].clone();
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+    assert_eq!(delivery.attempts, 3);
+    assert!(delivery.last_error.is_some());
+    assert_eq!(sender.sent.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_delete_unknown_webhook() {
+    let (service, _, _) = service(MockWebhookSender::default());
+
+    let result = service.delete_webhook(Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_given_up_delivery_is_dead_lettered_and_replayed() {
+    let (service, repository, _) = service(MockWebhookSender {
+        fail: true,
+        ..Default::default()
+    });
+    let dead_letters = Arc::new(MockDeadLetters::default());
+    let service = service.with_dead_letters(dead_letters.clone());
+
+    let webhook = service
+        .register_webhook("https://siem.example.com/hook".to_string(), vec![], Uuid::new_v4())
+        .await
+        .unwrap();
+    let log = AuditLog::new(AuditEventType::AccountLocked, "203.0.113.1".to_string());
+    service.enqueue(&log).await.unwrap();
+
+    for _ in 0..3 {
+        make_due(&repository);
+        service.deliver_due().await.unwrap();
+    }
+    let dead_letter = dead_letters.all()[0].clone();
+    assert_eq!(dead_letter.direction, WebhookDirection::Outbound);
+    assert_eq!(dead_letter.target_id, Some(webhook.id));
+    assert_eq!(dead_letter.event_id, repository.deliveries()[0].id.to_string());
+
+    // The endpoint is fixed
+    let sender = Arc::new(MockWebhookSender::default());
+    let fixed = SecurityWebhookService::new(repository.clone(), sender.clone(), SecurityWebhookConfig::default());
+    assert_eq!(fixed.replay(&dead_letter).await.unwrap(), "delivered");
+
+    let sent = sender.sent.lock().unwrap()[0].clone();
+    assert_eq!(sent.event_id, log.id);
+    assert_eq!(sent.body, dead_letter.payload.to_string());
+
+    fixed.delete_webhook(webhook.id).await.unwrap();
+    assert!(fixed.replay(&dead_letter).await.is_err());
+}
@@ -0,0 +1,34 @@
+//! Traits for security webhook delivery channels
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// A signed event ready to be posted to an endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookRequest {
+    /// Endpoint URL
+    pub url: String,
+    /// JSON body, exactly as signed
+    pub body: String,
+    /// Value of the signature header
+    pub signature: String,
+    /// ID of the audit event, identical across retries and endpoints
+    pub event_id: Uuid,
+}
+
+/// Trait for HTTP clients that post security events to operator endpoints
+#[async_trait]
+pub trait WebhookSenderTrait: Send + Sync {
+    /// Post an event
+    ///
+    /// Implementations send `signature` in the [`SIGNATURE_HEADER`] header
+    /// and `event_id` in the [`EVENT_ID_HEADER`] header.
+    ///
+    /// [`SIGNATURE_HEADER`]: super::SIGNATURE_HEADER
+    /// [`EVENT_ID_HEADER`]: super::EVENT_ID_HEADER
+    ///
+    /// # Returns
+    /// * `Ok(())` - The endpoint answered with a 2xx status
+    /// * `Err(String)` - Why the endpoint did not accept the event
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String>;
+}
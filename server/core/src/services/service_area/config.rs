@@ -0,0 +1,22 @@
+//! Configuration for worker service areas
+
+/// Configuration for the service-area service
+#[derive(Debug, Clone)]
+pub struct ServiceAreaConfig {
+    /// Douglas-Peucker tolerance in degrees used to simplify uploaded polygons
+    pub simplification_tolerance: f64,
+    /// Maximum number of service areas per worker
+    pub max_areas_per_worker: usize,
+    /// Maximum number of workers returned by a location match
+    pub max_match_results: usize,
+}
+
+impl Default for ServiceAreaConfig {
+    fn default() -> Self {
+        Self {
+            simplification_tolerance: 0.0001, // Roughly 11 metres
+            max_areas_per_worker: 10,
+            max_match_results: 200,
+        }
+    }
+}
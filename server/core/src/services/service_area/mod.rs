@@ -0,0 +1,15 @@
+//! Worker service-area module
+//!
+//! This module handles:
+//! - Validating and simplifying uploaded GeoJSON polygons
+//! - Storing the polygons that describe where each worker accepts jobs
+//! - Matching a job location to the workers whose areas contain it
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ServiceAreaConfig;
+pub use service::{ServiceAreaInput, ServiceAreaService};
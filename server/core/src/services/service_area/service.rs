@@ -0,0 +1,143 @@
+//! Service for managing worker service areas and matching job locations
+//!
+//! Uploaded polygons are validated and simplified before storage. Location
+//! matching is delegated to the repository so the database can use its
+//! spatial index.
+
+use re_shared::types::common::Coordinate;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::service_area::ServiceArea;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{ServiceAreaRepository, UserRepository};
+
+use super::config::ServiceAreaConfig;
+
+/// A service area as uploaded by a worker
+#[derive(Debug, Clone)]
+pub struct ServiceAreaInput {
+    /// Optional display name
+    pub name: Option<String>,
+    /// GeoJSON `Polygon` geometry or `Feature`
+    pub geojson: JsonValue,
+}
+
+/// Service for worker service-area polygons
+pub struct ServiceAreaService<R, U>
+where
+    R: ServiceAreaRepository + 'static,
+    U: UserRepository + 'static,
+{
+    areas: Arc<R>,
+    users: Arc<U>,
+    config: ServiceAreaConfig,
+}
+
+impl<R, U> ServiceAreaService<R, U>
+where
+    R: ServiceAreaRepository + 'static,
+    U: UserRepository + 'static,
+{
+    /// Create a new service-area service
+    pub fn new(areas: Arc<R>, users: Arc<U>, config: ServiceAreaConfig) -> Self {
+        Self { areas, users, config }
+    }
+
+    /// Create a new service-area service with default configuration
+    pub fn with_defaults(areas: Arc<R>, users: Arc<U>) -> Self {
+        Self::new(areas, users, ServiceAreaConfig::default())
+    }
+
+    /// Replace a worker's service areas
+    ///
+    /// # Arguments
+    /// * `worker_id` - The worker's unique identifier
+    /// * `inputs` - Uploaded polygons; an empty list clears all areas
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ServiceArea>)` - The stored, simplified areas
+    /// * `Err(DomainError::NotFound)` - The user does not exist
+    /// * `Err(DomainError::BusinessRule)` - The user is not a worker
+    /// * `Err(DomainError::Validation)` - Too many areas or an invalid polygon
+    pub async fn set_service_areas(
+        &self,
+        worker_id: Uuid,
+        inputs: Vec<ServiceAreaInput>,
+    ) -> DomainResult<Vec<ServiceArea>> {
+        let user = self
+            .users
+            .find_by_id(worker_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "User".to_string(),
+            })?;
+
+        if !user.is_worker() {
+            return Err(DomainError::BusinessRule {
+                message: "Only workers can define service areas".to_string(),
+            });
+        }
+
+        if inputs.len() > self.config.max_areas_per_worker {
+            return Err(DomainError::Validation {
+                message: format!(
+                    "At most {} service areas are allowed",
+                    self.config.max_areas_per_worker
+                ),
+            });
+        }
+
+        let areas = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                ServiceArea::from_geojson(
+                    worker_id,
+                    input.name,
+                    &input.geojson,
+                    self.config.simplification_tolerance,
+                )
+                .map_err(|reason| DomainError::Validation {
+                    message: format!("Service area {}: {}", index + 1, reason),
+                })
+            })
+            .collect::<DomainResult<Vec<_>>>()?;
+
+        let areas = self.areas.replace_for_worker(worker_id, areas).await?;
+
+        info!(
+            worker_id = %worker_id,
+            areas = areas.len(),
+            "Worker service areas updated"
+        );
+
+        Ok(areas)
+    }
+
+    /// Get a worker's service areas
+    pub async fn get_service_areas(&self, worker_id: Uuid) -> DomainResult<Vec<ServiceArea>> {
+        self.areas.find_by_worker(worker_id).await
+    }
+
+    /// Find workers whose service areas contain a location
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Uuid>)` - Matching worker IDs, up to `max_match_results`
+    /// * `Err(DomainError::Validation)` - The coordinate is out of range
+    pub async fn find_workers_covering(&self, location: Coordinate) -> DomainResult<Vec<Uuid>> {
+        if !(-90.0..=90.0).contains(&location.latitude)
+            || !(-180.0..=180.0).contains(&location.longitude)
+        {
+            return Err(DomainError::Validation {
+                message: "Location is out of range".to_string(),
+            });
+        }
+
+        self.areas
+            .find_workers_containing(location, self.config.max_match_results)
+            .await
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for the service-area service
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,175 @@
+//! Unit tests for the service-area service
+
+use async_trait::async_trait;
+use re_shared::types::common::Coordinate;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::entities::service_area::ServiceArea;
+use crate::domain::entities::user::{User, UserType};
+use crate::errors::DomainError;
+use crate::repositories::{ServiceAreaRepository, UserRepository};
+use crate::services::service_area::{ServiceAreaInput, ServiceAreaService};
+
+#[derive(Default)]
+struct MockServiceAreaRepository {
+    areas: Mutex<HashMap<Uuid, Vec<ServiceArea>>>,
+}
+
+#[async_trait]
+impl ServiceAreaRepository for MockServiceAreaRepository {
+    async fn replace_for_worker(
+        &self,
+        worker_id: Uuid,
+        areas: Vec<ServiceArea>,
+    ) -> Result<Vec<ServiceArea>, DomainError> {
+        self.areas.lock().unwrap().insert(worker_id, areas.clone());
+        Ok(areas)
+    }
+
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<ServiceArea>, DomainError> {
+        Ok(self.areas.lock().unwrap().get(&worker_id).cloned().unwrap_or_default())
+    }
+
+    async fn find_workers_containing(
+        &self,
+        point: Coordinate,
+        limit: usize,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        Ok(self
+            .areas
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, areas)| areas.iter().any(|area| area.contains(&point)))
+            .map(|(worker_id, _)| *worker_id)
+            .take(limit)
+            .collect())
+    }
+}
+
+#[derive(Default)]
+struct MockUserRepository {
+    users: Mutex<Vec<User>>,
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepository {
+    async fn find_by_phone(&self, _phone_hash: &str, _country_code: &str) -> Result<Option<User>, DomainError> {
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.users.lock().unwrap().iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        Ok(user)
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn exists_by_phone(&self, _phone_hash: &str, _country_code: &str) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn count_by_type(&self, _user_type: Option<UserType>) -> Result<u64, DomainError> {
+        Ok(0)
+    }
+}
+
+type TestService = ServiceAreaService<MockServiceAreaRepository, MockUserRepository>;
+
+async fn create_service() -> (TestService, Uuid, Uuid) {
+    let users = Arc::new(MockUserRepository::default());
+
+    let mut worker = User::new("worker_hash".to_string(), "+61".to_string());
+    worker.set_user_type(UserType::Worker);
+    let worker = users.create(worker).await.unwrap();
+
+    let mut customer = User::new("customer_hash".to_string(), "+61".to_string());
+    customer.set_user_type(UserType::Customer);
+    let customer = users.create(customer).await.unwrap();
+
+    let service = ServiceAreaService::with_defaults(Arc::new(MockServiceAreaRepository::default()), users);
+    (service, worker.id, customer.id)
+}
+
+/// A square on the inner side of a bay, around Balmain in Sydney
+fn balmain() -> ServiceAreaInput {
+    ServiceAreaInput {
+        name: Some("Balmain".to_string()),
+        geojson: json!({
+            "type": "Polygon",
+            "coordinates": [[[151.17, -33.86], [151.19, -33.86], [151.19, -33.85], [151.17, -33.85], [151.17, -33.86]]]
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_set_and_match_service_areas() {
+    let (service, worker_id, _) = create_service().await;
+
+    let areas = service.set_service_areas(worker_id, vec![balmain()]).await.unwrap();
+    assert_eq!(areas.len(), 1);
+    assert_eq!(service.get_service_areas(worker_id).await.unwrap().len(), 1);
+
+    let inside = service
+        .find_workers_covering(Coordinate::new(-33.855, 151.18))
+        .await
+        .unwrap();
+    assert_eq!(inside, vec![worker_id]);
+
+    // Across the water, well within a radius-based match
+    let across_bay = service
+        .find_workers_covering(Coordinate::new(-33.845, 151.18))
+        .await
+        .unwrap();
+    assert!(across_bay.is_empty());
+}
+
+#[tokio::test]
+async fn test_rejects_non_workers() {
+    let (service, _, customer_id) = create_service().await;
+
+    let result = service.set_service_areas(customer_id, vec![balmain()]).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+
+    let missing = service.set_service_areas(Uuid::new_v4(), vec![balmain()]).await;
+    assert!(matches!(missing, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_polygon_with_index() {
+    let (service, worker_id, _) = create_service().await;
+    let invalid = ServiceAreaInput {
+        name: None,
+        geojson: json!({ "type": "Polygon", "coordinates": [] }),
+    };
+
+    let result = service.set_service_areas(worker_id, vec![balmain(), invalid]).await;
+
+    match result {
+        Err(DomainError::Validation { message }) => assert!(message.starts_with("Service area 2")),
+        other => panic!("expected validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_out_of_range_location() {
+    let (service, _, _) = create_service().await;
+
+    let result = service.find_workers_covering(Coordinate::new(95.0, 0.0)).await;
+
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
@@ -0,0 +1,31 @@
+//! Configuration for the signed URL service
+
+use re_shared::config::SecretString;
+
+/// Configuration for the signed URL service
+#[derive(Debug, Clone)]
+pub struct SignedUrlConfig {
+    /// Secret the HMAC signatures are computed with, at least 32 bytes
+    pub signing_key: SecretString,
+    /// Previous secret, still accepted for links issued before a rotation
+    pub previous_signing_key: Option<SecretString>,
+    /// Origin prepended to minted links (e.g. "https://api.renoveasy.com")
+    pub base_url: Option<String>,
+    /// Lifetime of a link when the caller does not choose one, in seconds
+    pub default_ttl_seconds: i64,
+    /// Longest lifetime a link can be given, in seconds
+    pub max_ttl_seconds: i64,
+}
+
+impl SignedUrlConfig {
+    /// Create a configuration with the given signing key and default lifetimes
+    pub fn new(signing_key: impl Into<SecretString>) -> Self {
+        Self {
+            signing_key: signing_key.into(),
+            previous_signing_key: None,
+            base_url: None,
+            default_ttl_seconds: 15 * 60,
+            max_ttl_seconds: 7 * 24 * 60 * 60,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Signed URL service module
+//!
+//! This module handles:
+//! - Minting links that expire, such as photo downloads, invoice PDFs and
+//!   verification deep links
+//! - Verifying that a presented link was issued by us, is unchanged and has
+//!   not expired
+
+mod config;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::SignedUrlConfig;
+pub use service::{SignedUrlService, VerifiedUrl, EXPIRES_PARAM, SIGNATURE_PARAM};
@@ -0,0 +1,84 @@
+//! Unit tests for the signed URL service
+
+use chrono::{Duration, Utc};
+
+use crate::errors::{DomainError, TokenError};
+use crate::services::signed_url::{SignedUrlConfig, SignedUrlService};
+
+const KEY: &str = "0123456789abcdef0123456789abcdef";
+const OLD_KEY: &str = "fedcba9876543210fedcba9876543210";
+
+fn service() -> SignedUrlService {
+    SignedUrlService::new(SignedUrlConfig::new(KEY)).unwrap()
+}
+
+#[test]
+fn test_signed_link_round_trips() {
+    let service = service();
+
+    let link = service.sign("/api/v1/files/photos/abc.jpg?size=large", None).unwrap();
+    assert!(link.starts_with("/api/v1/files/photos/abc.jpg?size=large&exp="));
+
+    let verified = service.verify(&link).unwrap();
+    assert_eq!(verified.path, "/api/v1/files/photos/abc.jpg");
+    assert_eq!(verified.query.as_deref(), Some("size=large"));
+    assert!(verified.expires_at > Utc::now() + Duration::minutes(14));
+
+    let bare = service.sign("/api/v1/invoices/42.pdf", Some(Duration::hours(1))).unwrap();
+    assert_eq!(service.verify(&bare).unwrap().query, None);
+}
+
+#[test]
+fn test_tampered_or_expired_links_are_rejected() {
+    let service = service();
+    let link = service.sign("/api/v1/files/photos/abc.jpg", None).unwrap();
+
+    let other_file = link.replace("abc.jpg", "xyz.jpg");
+    assert!(matches!(
+        service.verify(&other_file),
+        Err(DomainError::Token(TokenError::InvalidSignature))
+    ));
+
+    let (unsigned, _) = link.rsplit_once("&sig=").unwrap();
+    let extended = unsigned.replace("exp=", "exp=9") + "&sig=00";
+    assert!(service.verify(&extended).is_err());
+    assert!(service.verify("/api/v1/files/photos/abc.jpg").is_err());
+
+    let other_key = SignedUrlService::new(SignedUrlConfig::new(OLD_KEY)).unwrap();
+    assert!(other_key.verify(&link).is_err());
+
+    let expired = service
+        .sign_until("/api/v1/files/photos/abc.jpg", Utc::now() - Duration::seconds(1))
+        .unwrap();
+    assert!(matches!(
+        service.verify(&expired),
+        Err(DomainError::Token(TokenError::TokenExpired))
+    ));
+}
+
+#[test]
+fn test_previous_key_is_accepted_after_rotation() {
+    let old = SignedUrlService::new(SignedUrlConfig::new(OLD_KEY)).unwrap();
+    let link = old.sign("/verify/email?token=abc", None).unwrap();
+
+    let mut config = SignedUrlConfig::new(KEY);
+    config.previous_signing_key = Some(OLD_KEY.into());
+    config.base_url = Some("https://api.renoveasy.com/".to_string());
+    let rotated = SignedUrlService::new(config).unwrap();
+
+    assert_eq!(rotated.verify(&link).unwrap().query.as_deref(), Some("token=abc"));
+    let fresh = rotated.sign("/verify/email?token=abc", None).unwrap();
+    assert!(fresh.starts_with("https://api.renoveasy.com/verify/email?"));
+    assert!(old.verify(fresh.trim_start_matches("https://api.renoveasy.com")).is_err());
+}
+
+#[test]
+fn test_invalid_requests_are_refused() {
+    assert!(SignedUrlService::new(SignedUrlConfig::new("short")).is_err());
+
+    let service = service();
+    assert!(service.sign("files/abc.jpg", None).is_err());
+    assert!(service.sign("/files/abc.jpg?exp=1", None).is_err());
+    assert!(service.sign("/files/abc.jpg", Some(Duration::days(8))).is_err());
+    assert!(service.sign("/files/abc.jpg", Some(Duration::zero())).is_err());
+}
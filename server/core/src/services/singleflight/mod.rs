@@ -0,0 +1,13 @@
+//! Coalescing of concurrent fetches of the same key
+//!
+//! When a hot cache entry expires, every request that misses it would
+//! otherwise reach the backend at once: the database for a user profile,
+//! the identity provider for its signing keys. `SingleFlight` lets the
+//! first of them fetch while the others wait for its result.
+
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use service::SingleFlight;
@@ -0,0 +1,120 @@
+//! Sharing the result of a fetch with concurrent callers
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::sync::watch;
+
+/// Result of a fetch, published once the fetch finishes
+type Outcome<V, E> = Option<Result<V, E>>;
+
+/// Runs at most one fetch per key at a time
+///
+/// A caller finding a fetch of its key in flight waits for that fetch and
+/// receives a copy of its result, errors included, instead of fetching
+/// itself. If the fetching caller is cancelled before finishing, one of
+/// the waiting callers fetches instead.
+pub struct SingleFlight<K, V, E> {
+    /// Fetches in flight, by key
+    flights: Mutex<HashMap<K, watch::Receiver<Outcome<V, E>>>>,
+    /// Callers served the result of another caller's fetch
+    coalesced: AtomicU64,
+}
+
+/// Removes a flight once its fetch finished or was cancelled
+struct Landing<'a, K: Eq + Hash, V, E> {
+    flights: &'a Mutex<HashMap<K, watch::Receiver<Outcome<V, E>>>>,
+    key: &'a K,
+}
+
+impl<K: Eq + Hash, V, E> Drop for Landing<'_, K, V, E> {
+    fn drop(&mut self) {
+        if let Ok(mut flights) = self.flights.lock() {
+            flights.remove(self.key);
+        }
+    }
+}
+
+impl<K, V, E> SingleFlight<K, V, E>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    E: Clone,
+{
+    /// Create a coalescer with no fetch in flight
+    pub fn new() -> Self {
+        Self {
+            flights: Mutex::new(HashMap::new()),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    /// Run `fetch` for `key`, unless a fetch of `key` is already in flight,
+    /// in which case wait for its result
+    ///
+    /// `fetch` is not polled when the result comes from another caller.
+    pub async fn run<F>(&self, key: K, fetch: F) -> Result<V, E>
+    where
+        F: Future<Output = Result<V, E>>,
+    {
+        loop {
+            let (sender, mut receiver) = {
+                let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+                match flights.get(&key) {
+                    Some(receiver) => (None, receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        flights.insert(key.clone(), receiver.clone());
+                        (Some(sender), receiver)
+                    }
+                }
+            };
+
+            let Some(sender) = sender else {
+                // A sender dropped without an outcome means the fetching
+                // caller was cancelled; take over the fetch
+                if let Ok(outcome) = receiver.wait_for(Option::is_some).await {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    if let Some(result) = outcome.clone() {
+                        return result;
+                    }
+                }
+                continue;
+            };
+
+            let _landing = Landing {
+                flights: &self.flights,
+                key: &key,
+            };
+            let result = fetch.await;
+            sender.send_replace(Some(result.clone()));
+            return result;
+        }
+    }
+
+    /// Whether a fetch of `key` is in flight
+    pub fn is_in_flight(&self, key: &K) -> bool {
+        self.flights
+            .lock()
+            .map(|flights| flights.contains_key(key))
+            .unwrap_or(false)
+    }
+
+    /// Number of callers served the result of another caller's fetch so far
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+}
+
+impl<K, V, E> Default for SingleFlight<K, V, E>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    E: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for fetch coalescing
+
+#[cfg(test)]
+mod service_tests;
@@ -0,0 +1,90 @@
+//! Unit tests for fetch coalescing
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::errors::DomainError;
+use crate::services::singleflight::SingleFlight;
+
+/// A fetch that takes a while, counting how often it ran
+async fn slow_fetch(fetches: &AtomicUsize, value: u32) -> Result<u32, DomainError> {
+    fetches.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    Ok(value)
+}
+
+#[tokio::test]
+async fn test_concurrent_fetches_of_a_key_are_coalesced() {
+    let flights = SingleFlight::<&str, u32, DomainError>::new();
+    let fetches = AtomicUsize::new(0);
+
+    let (first, second, third) = tokio::join!(
+        flights.run("jwks", slow_fetch(&fetches, 1)),
+        flights.run("jwks", slow_fetch(&fetches, 2)),
+        flights.run("jwks", slow_fetch(&fetches, 3)),
+    );
+
+    assert_eq!((first.unwrap(), second.unwrap(), third.unwrap()), (1, 1, 1));
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    assert_eq!(flights.coalesced(), 2);
+    assert!(!flights.is_in_flight(&"jwks"));
+}
+
+#[tokio::test]
+async fn test_fetches_of_different_keys_run_separately() {
+    let flights = SingleFlight::<&str, u32, DomainError>::new();
+    let fetches = AtomicUsize::new(0);
+
+    let (first, second) = tokio::join!(
+        flights.run("user:1", slow_fetch(&fetches, 1)),
+        flights.run("user:2", slow_fetch(&fetches, 2)),
+    );
+
+    assert_eq!((first.unwrap(), second.unwrap()), (1, 2));
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    assert_eq!(flights.coalesced(), 0);
+}
+
+#[tokio::test]
+async fn test_errors_are_shared_and_not_remembered() {
+    let flights = SingleFlight::<&str, u32, DomainError>::new();
+    let failing = async {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        Err(DomainError::Internal {
+            message: "Connection reset".to_string(),
+        })
+    };
+
+    let fetches = AtomicUsize::new(0);
+    let (first, second) = tokio::join!(flights.run("user:1", failing), flights.run("user:1", slow_fetch(&fetches, 2)));
+    assert!(matches!(first, Err(DomainError::Internal { .. })));
+    assert!(matches!(second, Err(DomainError::Internal { .. })));
+    assert_eq!(fetches.load(Ordering::SeqCst), 0);
+
+    assert_eq!(flights.run("user:1", slow_fetch(&fetches, 2)).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_waiting_caller_fetches_when_the_fetching_caller_is_cancelled() {
+    let flights = Arc::new(SingleFlight::<&str, u32, DomainError>::new());
+    let fetches = Arc::new(AtomicUsize::new(0));
+
+    let cancelled = {
+        let (flights, fetches) = (flights.clone(), fetches.clone());
+        tokio::spawn(async move { flights.run("user:1", slow_fetch(&fetches, 1)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert!(flights.is_in_flight(&"user:1"));
+
+    let waiting = {
+        let (flights, fetches) = (flights.clone(), fetches.clone());
+        tokio::spawn(async move { flights.run("user:1", slow_fetch(&fetches, 2)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    cancelled.abort();
+
+    assert_eq!(waiting.await.unwrap().unwrap(), 2);
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    assert!(!flights.is_in_flight(&"user:1"));
+}
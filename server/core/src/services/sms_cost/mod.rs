@@ -0,0 +1,17 @@
+//! SMS cost service module for estimated SMS spend and the daily budget
+//!
+//! This module handles:
+//! - Estimating the cost of each message by provider and destination prefix
+//! - Aggregating the daily spend, in a shared counter for the budget check
+//!   and in the database for reporting
+//! - Refusing messages, or keeping only the cheapest provider of each
+//!   destination, once the daily budget is spent
+
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use service::{SmsBudgetDecision, SmsCostService, SmsSpendReport};
+pub use traits::SmsSpendCounterTrait;
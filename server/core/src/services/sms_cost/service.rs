@@ -0,0 +1,176 @@
+use chrono::{NaiveDate, Utc};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use re_shared::config::{SmsBudgetConfig, SmsOverBudgetAction};
+
+use crate::domain::entities::sms_spend::SmsDailySpend;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::SmsSpendRepository;
+
+use super::traits::SmsSpendCounterTrait;
+
+/// Longest period covered by a spend report, in days
+const MAX_REPORT_DAYS: i64 = 366;
+
+/// Whether a message may be sent within the daily budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmsBudgetDecision {
+    /// The budget is not spent, or the provider is the cheapest for the
+    /// destination and over-budget messages are downgraded
+    Allowed,
+    /// The budget is spent
+    Refused,
+}
+
+/// Estimated SMS spend over a period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmsSpendReport {
+    /// Currency of the amounts
+    pub currency: String,
+    /// Spend allowed per UTC day, None for no cap
+    pub daily_budget_micros: Option<u64>,
+    /// What happens to messages once the budget is spent
+    pub over_budget: SmsOverBudgetAction,
+    /// Spend of the current UTC day so far
+    pub today_micros: u64,
+    /// Spend per day, provider and prefix, oldest day first
+    pub days: Vec<SmsDailySpend>,
+}
+
+/// Service estimating SMS costs and enforcing the daily budget
+pub struct SmsCostService {
+    spend: Arc<dyn SmsSpendRepository>,
+    counter: Arc<dyn SmsSpendCounterTrait>,
+    config: SmsBudgetConfig,
+}
+
+impl SmsCostService {
+    /// Create a new SMS cost service
+    ///
+    /// # Arguments
+    /// * `spend` - Durable daily spend, by provider and prefix
+    /// * `counter` - Shared daily total read by the budget check
+    /// * `config` - Rates and daily budget
+    pub fn new(
+        spend: Arc<dyn SmsSpendRepository>,
+        counter: Arc<dyn SmsSpendCounterTrait>,
+        config: SmsBudgetConfig,
+    ) -> Self {
+        Self { spend, counter, config }
+    }
+
+    /// Rates and daily budget
+    pub fn config(&self) -> &SmsBudgetConfig {
+        &self.config
+    }
+
+    /// Estimated cost of a message, in millionths of the budget currency
+    pub fn estimate(&self, provider: &str, phone_number: &str) -> u64 {
+        self.config
+            .rate(provider, phone_number)
+            .map(|rate| rate.cost_micros)
+            .unwrap_or(0)
+    }
+
+    /// Check whether a message may be sent through a provider
+    ///
+    /// The budget fails open: when the counter cannot be read, messages are
+    /// allowed, since verification codes must keep flowing.
+    pub async fn check_budget(&self, provider: &str, phone_number: &str) -> SmsBudgetDecision {
+        let Some(budget) = self.config.daily_budget_micros else {
+            return SmsBudgetDecision::Allowed;
+        };
+
+        let spent = match self.counter.total(Utc::now().date_naive()).await {
+            Ok(spent) => spent,
+            Err(e) => {
+                warn!("Failed to read SMS spend, allowing message: {}", e);
+                return SmsBudgetDecision::Allowed;
+            }
+        };
+
+        let cost = self.estimate(provider, phone_number);
+        if spent.saturating_add(cost) <= budget {
+            return SmsBudgetDecision::Allowed;
+        }
+
+        match self.config.over_budget {
+            SmsOverBudgetAction::Refuse => SmsBudgetDecision::Refused,
+            SmsOverBudgetAction::Downgrade => {
+                let cheapest = self.config.cheapest_cost(phone_number).unwrap_or(0);
+                if cost <= cheapest {
+                    SmsBudgetDecision::Allowed
+                } else {
+                    SmsBudgetDecision::Refused
+                }
+            }
+        }
+    }
+
+    /// Add a message sent through a provider to today's spend
+    ///
+    /// Failures are logged and otherwise ignored: the message was sent.
+    pub async fn record(&self, provider: &str, phone_number: &str) {
+        let day = Utc::now().date_naive();
+        let rate = self.config.rate(provider, phone_number);
+        let prefix = rate.map(|rate| rate.prefix.as_str()).unwrap_or("");
+        let cost = rate.map(|rate| rate.cost_micros).unwrap_or(0);
+
+        if rate.is_none() {
+            debug!(provider, "No SMS rate matches the destination");
+        }
+
+        if cost > 0 {
+            if let Err(e) = self.counter.add(day, cost).await {
+                warn!("Failed to add to SMS spend counter: {}", e);
+            }
+        }
+        if let Err(e) = self.spend.add(day, provider, prefix, cost).await {
+            warn!("Failed to record SMS spend: {}", e);
+        }
+    }
+
+    /// Report the spend of the days between `from` and `to` inclusive
+    ///
+    /// # Returns
+    /// * `Err(DomainError::Validation)` - `from` is after `to`, or the period
+    ///   is longer than a year
+    pub async fn spend_report(&self, from: NaiveDate, to: NaiveDate) -> DomainResult<SmsSpendReport> {
+        if from > to {
+            return Err(DomainError::Validation {
+                message: "The start of the period must not be after its end".to_string(),
+            });
+        }
+        if (to - from).num_days() >= MAX_REPORT_DAYS {
+            return Err(DomainError::Validation {
+                message: format!("The period must not exceed {} days", MAX_REPORT_DAYS),
+            });
+        }
+
+        let days = self.spend.find_between(from, to).await?;
+
+        // The counter holds the budgeted total; the database is the fallback
+        let today = Utc::now().date_naive();
+        let today_micros = match self.counter.total(today).await {
+            Ok(total) => total,
+            Err(e) => {
+                warn!("Failed to read SMS spend counter: {}", e);
+                self.spend
+                    .find_between(today, today)
+                    .await?
+                    .iter()
+                    .map(|spend| spend.cost_micros)
+                    .sum()
+            }
+        };
+
+        Ok(SmsSpendReport {
+            currency: self.config.currency.clone(),
+            daily_budget_micros: self.config.daily_budget_micros,
+            over_budget: self.config.over_budget,
+            today_micros,
+            days,
+        })
+    }
+}
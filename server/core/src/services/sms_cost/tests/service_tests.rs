@@ -0,0 +1,195 @@
+//! Unit tests for the SMS cost service
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use re_shared::config::{SmsBudgetConfig, SmsOverBudgetAction, SmsRate};
+
+use crate::domain::entities::sms_spend::SmsDailySpend;
+use crate::errors::DomainError;
+use crate::repositories::SmsSpendRepository;
+use crate::services::sms_cost::{SmsBudgetDecision, SmsCostService, SmsSpendCounterTrait};
+
+#[derive(Default)]
+struct MockSpendRepository {
+    spend: Mutex<Vec<SmsDailySpend>>,
+}
+
+#[async_trait]
+impl SmsSpendRepository for MockSpendRepository {
+    async fn add(
+        &self,
+        day: NaiveDate,
+        provider: &str,
+        prefix: &str,
+        cost_micros: u64,
+    ) -> Result<(), DomainError> {
+        let mut spend = self.spend.lock().unwrap();
+        match spend
+            .iter_mut()
+            .find(|spend| spend.day == day && spend.provider == provider && spend.prefix == prefix)
+        {
+            Some(existing) => {
+                existing.messages += 1;
+                existing.cost_micros += cost_micros;
+            }
+            None => spend.push(SmsDailySpend {
+                day,
+                provider: provider.to_string(),
+                prefix: prefix.to_string(),
+                messages: 1,
+                cost_micros,
+            }),
+        }
+        Ok(())
+    }
+
+    async fn find_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<SmsDailySpend>, DomainError> {
+        Ok(self
+            .spend
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|spend| spend.day >= from && spend.day <= to)
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Default)]
+struct MockSpendCounter {
+    totals: Mutex<HashMap<NaiveDate, u64>>,
+    unavailable: bool,
+}
+
+#[async_trait]
+impl SmsSpendCounterTrait for MockSpendCounter {
+    async fn add(&self, day: NaiveDate, cost_micros: u64) -> Result<u64, String> {
+        if self.unavailable {
+            return Err("Redis unavailable".to_string());
+        }
+        let mut totals = self.totals.lock().unwrap();
+        let total = totals.entry(day).or_default();
+        *total += cost_micros;
+        Ok(*total)
+    }
+
+    async fn total(&self, day: NaiveDate) -> Result<u64, String> {
+        if self.unavailable {
+            return Err("Redis unavailable".to_string());
+        }
+        Ok(self.totals.lock().unwrap().get(&day).copied().unwrap_or(0))
+    }
+}
+
+fn rate(provider: &str, prefix: &str, cost_micros: u64) -> SmsRate {
+    SmsRate {
+        provider: provider.to_string(),
+        prefix: prefix.to_string(),
+        cost_micros,
+    }
+}
+
+fn config(over_budget: SmsOverBudgetAction) -> SmsBudgetConfig {
+    SmsBudgetConfig {
+        daily_budget_micros: Some(100_000),
+        over_budget,
+        rates: vec![
+            rate("twilio", "+", 80_000),
+            rate("twilio", "+61", 50_000),
+            rate("aws-sns", "+61", 40_000),
+        ],
+        ..Default::default()
+    }
+}
+
+fn service(config: SmsBudgetConfig, counter: MockSpendCounter) -> (SmsCostService, Arc<MockSpendRepository>) {
+    let spend = Arc::new(MockSpendRepository::default());
+    (SmsCostService::new(spend.clone(), Arc::new(counter), config), spend)
+}
+
+#[tokio::test]
+async fn test_estimate_uses_longest_matching_prefix() {
+    let (service, _) = service(config(SmsOverBudgetAction::Refuse), MockSpendCounter::default());
+
+    assert_eq!(service.estimate("twilio", "+61412345678"), 50_000);
+    assert_eq!(service.estimate("twilio", "+8613800138000"), 80_000);
+    assert_eq!(service.estimate("aws-sns", "+8613800138000"), 0);
+    assert_eq!(service.estimate("vonage", "+61412345678"), 0);
+}
+
+#[tokio::test]
+async fn test_record_aggregates_daily_spend() {
+    let (service, spend) = service(config(SmsOverBudgetAction::Refuse), MockSpendCounter::default());
+
+    service.record("twilio", "+61412345678").await;
+    service.record("twilio", "+61498765432").await;
+    service.record("aws-sns", "+61412345678").await;
+
+    let today = Utc::now().date_naive();
+    let report = service.spend_report(today, today).await.unwrap();
+    assert_eq!(report.today_micros, 140_000);
+    assert_eq!(report.currency, "USD");
+
+    let twilio = report.days.iter().find(|spend| spend.provider == "twilio").unwrap();
+    assert_eq!((twilio.prefix.as_str(), twilio.messages, twilio.cost_micros), ("+61", 2, 100_000));
+    assert_eq!(spend.spend.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_refuses_messages_over_budget() {
+    let (service, _) = service(config(SmsOverBudgetAction::Refuse), MockSpendCounter::default());
+
+    assert_eq!(service.check_budget("twilio", "+61412345678").await, SmsBudgetDecision::Allowed);
+    service.record("twilio", "+61412345678").await;
+    assert_eq!(service.check_budget("twilio", "+61412345678").await, SmsBudgetDecision::Allowed);
+    service.record("twilio", "+61412345678").await;
+
+    assert_eq!(service.check_budget("twilio", "+61412345678").await, SmsBudgetDecision::Refused);
+    assert_eq!(service.check_budget("aws-sns", "+61412345678").await, SmsBudgetDecision::Refused);
+}
+
+#[tokio::test]
+async fn test_downgrades_to_cheapest_provider_over_budget() {
+    let (service, _) = service(config(SmsOverBudgetAction::Downgrade), MockSpendCounter::default());
+    service.record("twilio", "+61412345678").await;
+    service.record("twilio", "+61412345678").await;
+
+    assert_eq!(service.check_budget("twilio", "+61412345678").await, SmsBudgetDecision::Refused);
+    assert_eq!(service.check_budget("aws-sns", "+61412345678").await, SmsBudgetDecision::Allowed);
+}
+
+#[tokio::test]
+async fn test_budget_fails_open_without_counter() {
+    let counter = MockSpendCounter {
+        unavailable: true,
+        ..Default::default()
+    };
+    let (service, _) = service(config(SmsOverBudgetAction::Refuse), counter);
+
+    for _ in 0..3 {
+        service.record("twilio", "+61412345678").await;
+    }
+    assert_eq!(service.check_budget("twilio", "+61412345678").await, SmsBudgetDecision::Allowed);
+
+    // The report falls back to the database
+    let today = Utc::now().date_naive();
+    assert_eq!(service.spend_report(today, today).await.unwrap().today_micros, 150_000);
+}
+
+#[tokio::test]
+async fn test_spend_report_rejects_invalid_period() {
+    let (service, _) = service(SmsBudgetConfig::default(), MockSpendCounter::default());
+    let today = Utc::now().date_naive();
+
+    assert!(matches!(
+        service.spend_report(today, today - Duration::days(1)).await,
+        Err(DomainError::Validation { .. })
+    ));
+    assert!(matches!(
+        service.spend_report(today - Duration::days(400), today).await,
+        Err(DomainError::Validation { .. })
+    ));
+}
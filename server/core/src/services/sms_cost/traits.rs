@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// Shared counter of the estimated spend of each UTC day
+///
+/// Read before every message, so it must be cheap; the database keeps the
+/// durable breakdown by provider and prefix.
+#[async_trait]
+pub trait SmsSpendCounterTrait: Send + Sync {
+    /// Add to the spend of a day and return the new total, in millionths
+    /// of the budget currency
+    async fn add(&self, day: NaiveDate, cost_micros: u64) -> Result<u64, String>;
+
+    /// Total spend of a day, in millionths of the budget currency
+    async fn total(&self, day: NaiveDate) -> Result<u64, String>;
+}
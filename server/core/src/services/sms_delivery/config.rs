@@ -0,0 +1,25 @@
+//! Configuration for SMS delivery tracking
+
+/// Configuration for SMS delivery tracking
+#[derive(Debug, Clone)]
+pub struct SmsDeliveryConfig {
+    /// Number of recent final receipts kept per provider
+    pub window_size: usize,
+    /// Age after which a receipt no longer counts (in seconds)
+    pub window_seconds: i64,
+    /// Fewest receipts in the window before a failure rate is reported
+    pub min_receipts: usize,
+    /// Failure rate above which a provider is considered degraded
+    pub max_failure_rate: f64,
+}
+
+impl Default for SmsDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            window_size: 200,
+            window_seconds: 900, // 15 minutes
+            min_receipts: 20,
+            max_failure_rate: 0.3,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Recent delivery failure rate of each SMS provider
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::domain::entities::sms_delivery::SmsDeliveryStatus;
+
+use super::config::SmsDeliveryConfig;
+
+/// Receipt time and whether the message was delivered, oldest first
+type Outcomes = VecDeque<(DateTime<Utc>, bool)>;
+
+/// Recent delivery outcomes of each SMS provider
+///
+/// Fed with the final delivery receipts of each provider, keyed by the
+/// provider name recorded in the delivery log. Outcomes are kept in
+/// process memory: each instance judges providers from the receipts it
+/// received, which is a sample of all of them.
+pub struct SmsDeliveryHealth {
+    config: SmsDeliveryConfig,
+    /// Outcomes of each provider
+    outcomes: Mutex<HashMap<String, Outcomes>>,
+}
+
+impl SmsDeliveryHealth {
+    /// Create an empty tracker
+    pub fn new(config: SmsDeliveryConfig) -> Self {
+        Self {
+            config,
+            outcomes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record the final status of a message sent through a provider
+    ///
+    /// Messages still in flight (`Sent`) are ignored.
+    pub fn record(&self, provider: &str, status: SmsDeliveryStatus) {
+        self.record_at(provider, status, Utc::now());
+    }
+
+    /// Record a final status received at a given time
+    pub fn record_at(&self, provider: &str, status: SmsDeliveryStatus, at: DateTime<Utc>) {
+        if !status.is_final() {
+            return;
+        }
+
+        let mut outcomes = self.outcomes.lock().unwrap_or_else(|e| e.into_inner());
+        let window = outcomes.entry(provider.to_string()).or_default();
+        window.push_back((at, status == SmsDeliveryStatus::Delivered));
+        while window.len() > self.config.window_size {
+            window.pop_front();
+        }
+    }
+
+    /// Share of recent messages the provider failed to deliver
+    ///
+    /// # Returns
+    /// * `None` - Too few recent receipts to judge the provider
+    pub fn failure_rate(&self, provider: &str) -> Option<f64> {
+        let since = Utc::now() - Duration::seconds(self.config.window_seconds);
+        let outcomes = self.outcomes.lock().unwrap_or_else(|e| e.into_inner());
+        let recent: Vec<bool> = outcomes
+            .get(provider)?
+            .iter()
+            .filter(|(at, _)| *at >= since)
+            .map(|(_, delivered)| *delivered)
+            .collect();
+
+        if recent.len() < self.config.min_receipts.max(1) {
+            return None;
+        }
+
+        let failed = recent.iter().filter(|delivered| !**delivered).count();
+        Some(failed as f64 / recent.len() as f64)
+    }
+
+    /// Whether the provider fails to deliver too many recent messages
+    pub fn is_degraded(&self, provider: &str) -> bool {
+        self.failure_rate(provider)
+            .is_some_and(|rate| rate > self.config.max_failure_rate)
+    }
+}
+
+impl Default for SmsDeliveryHealth {
+    fn default() -> Self {
+        Self::new(SmsDeliveryConfig::default())
+    }
+}
@@ -0,0 +1,20 @@
+//! SMS delivery service module for delivery receipts reported by SMS providers
+//!
+//! This module handles:
+//! - Verifying that delivery callbacks were sent by the provider
+//! - Applying delivery receipts to the SMS delivery log
+//! - Tracking the recent delivery failure rate of each provider, so that
+//!   failover can route around a provider whose messages stop arriving
+
+mod config;
+mod health;
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::SmsDeliveryConfig;
+pub use health::SmsDeliveryHealth;
+pub use service::{ReceiptSummary, SmsDeliveryService};
+pub use traits::SmsReceiptProviderTrait;
@@ -0,0 +1,142 @@
+//! SMS delivery service for provider delivery receipts
+//!
+//! Providers report the fate of each message in a callback. Callbacks are
+//! only acted on after the provider adapter has verified them. Each
+//! receipt updates the delivery log entry of its message, matched on the
+//! provider and the provider message ID; receipts of messages the log does
+//! not know, e.g. sent before the log existed, are skipped. Final statuses
+//! feed the failure rate of the provider in [`SmsDeliveryHealth`], which
+//! failover consults to route around a degraded provider.
+
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::SmsDeliveryLogRepository;
+
+use super::health::SmsDeliveryHealth;
+use super::traits::SmsReceiptProviderTrait;
+
+/// How the receipts of a callback were handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiptSummary {
+    /// Receipts that changed the status of a message
+    pub applied: usize,
+    /// Receipts repeating or superseded by a known status
+    pub unchanged: usize,
+    /// Receipts of messages missing from the delivery log
+    pub unknown: usize,
+}
+
+/// Service applying SMS delivery receipts
+pub struct SmsDeliveryService<R>
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    logs: Arc<R>,
+    providers: Vec<Arc<dyn SmsReceiptProviderTrait>>,
+    health: Arc<SmsDeliveryHealth>,
+}
+
+impl<R> SmsDeliveryService<R>
+where
+    R: SmsDeliveryLogRepository + 'static,
+{
+    /// Create a new SMS delivery service
+    ///
+    /// # Arguments
+    /// * `logs` - Delivery log the receipts are applied to
+    /// * `health` - Tracker fed with the final status of each message
+    pub fn new(logs: Arc<R>, health: Arc<SmsDeliveryHealth>) -> Self {
+        Self {
+            logs,
+            providers: Vec::new(),
+            health,
+        }
+    }
+
+    /// Accept the callbacks of a provider
+    pub fn with_provider(mut self, provider: Arc<dyn SmsReceiptProviderTrait>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// Tracker of the recent failure rate of each provider
+    pub fn health(&self) -> Arc<SmsDeliveryHealth> {
+        self.health.clone()
+    }
+
+    /// Adapter of a provider, by the name recorded in the delivery log
+    pub fn provider(&self, name: &str) -> Option<&dyn SmsReceiptProviderTrait> {
+        self.providers
+            .iter()
+            .find(|provider| provider.provider_name() == name)
+            .map(|provider| provider.as_ref())
+    }
+
+    /// Handle a delivery callback of a provider
+    ///
+    /// # Arguments
+    /// * `provider` - Provider name as recorded in the delivery log
+    /// * `url` - Full URL the provider called, including the query string
+    /// * `payload` - Raw request body, exactly as received
+    /// * `signature` - Value of the provider's signature header, if any
+    ///
+    /// # Returns
+    /// * `Ok(ReceiptSummary)` - How the receipts were handled
+    /// * `Err(DomainError::NotFound)` - The provider is not configured
+    /// * `Err(DomainError::Unauthorized)` - The callback could not be verified
+    /// * `Err(DomainError::Validation)` - The payload could not be decoded
+    pub async fn handle_callback(
+        &self,
+        provider: &str,
+        url: &str,
+        payload: &[u8],
+        signature: Option<&str>,
+    ) -> DomainResult<ReceiptSummary> {
+        let adapter = self.provider(provider).ok_or_else(|| DomainError::NotFound {
+            resource: format!("SMS provider {}", provider),
+        })?;
+
+        if let Err(e) = adapter.verify_callback(url, payload, signature) {
+            warn!(provider, error = %e, "Rejected SMS delivery callback");
+            return Err(DomainError::Unauthorized);
+        }
+
+        let receipts = adapter.parse_receipts(payload).map_err(|e| DomainError::Validation {
+            message: format!("Invalid delivery callback: {}", e),
+        })?;
+
+        let mut summary = ReceiptSummary::default();
+        for receipt in receipts {
+            let Some(mut log) = self
+                .logs
+                .find_by_provider_message_id(provider, &receipt.provider_message_id)
+                .await?
+            else {
+                debug!(provider, message_id = %receipt.provider_message_id, "Receipt for unknown SMS");
+                summary.unknown += 1;
+                continue;
+            };
+
+            if !log.apply_receipt(receipt.status, receipt.error) {
+                summary.unchanged += 1;
+                continue;
+            }
+
+            let log = self.logs.update(log).await?;
+            self.health.record(provider, log.status);
+            summary.applied += 1;
+
+            info!(
+                provider,
+                message_id = %receipt.provider_message_id,
+                trace_id = log.trace_id.as_deref().unwrap_or("-"),
+                status = log.status.as_str(),
+                "SMS delivery status updated"
+            );
+        }
+
+        Ok(summary)
+    }
+}
@@ -0,0 +1,182 @@
+//! Unit tests for the SMS delivery service
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::entities::sms_delivery::{SmsDeliveryLog, SmsDeliveryReceipt, SmsDeliveryStatus};
+use crate::errors::DomainError;
+use crate::repositories::SmsDeliveryLogRepository;
+use crate::services::sms_delivery::{
+    ReceiptSummary, SmsDeliveryConfig, SmsDeliveryHealth, SmsDeliveryService, SmsReceiptProviderTrait,
+};
+
+const SIGNATURE: &str = "valid-signature";
+
+#[derive(Default)]
+struct MockDeliveryLogs {
+    logs: Mutex<Vec<SmsDeliveryLog>>,
+}
+
+#[async_trait]
+impl SmsDeliveryLogRepository for MockDeliveryLogs {
+    async fn save(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        self.logs.lock().unwrap().push(log.clone());
+        Ok(log)
+    }
+
+    async fn find_by_provider_message_id(
+        &self,
+        provider: &str,
+        provider_message_id: &str,
+    ) -> Result<Option<SmsDeliveryLog>, DomainError> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|log| log.provider == provider && log.provider_message_id.as_deref() == Some(provider_message_id))
+            .cloned())
+    }
+
+    async fn find_by_trace_id(&self, trace_id: &str) -> Result<Vec<SmsDeliveryLog>, DomainError> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|log| log.trace_id.as_deref() == Some(trace_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        let mut logs = self.logs.lock().unwrap();
+        let existing = logs
+            .iter_mut()
+            .find(|existing| existing.id == log.id)
+            .ok_or_else(|| DomainError::NotFound { resource: "SMS delivery log".to_string() })?;
+        *existing = log.clone();
+        Ok(log)
+    }
+}
+
+/// Provider whose callbacks are lines of `message_id:status`
+struct MockReceiptProvider;
+
+impl SmsReceiptProviderTrait for MockReceiptProvider {
+    fn provider_name(&self) -> &str {
+        "Mock"
+    }
+
+    fn signature_header(&self) -> Option<&str> {
+        Some("X-Mock-Signature")
+    }
+
+    fn verify_callback(&self, _url: &str, _payload: &[u8], signature: Option<&str>) -> Result<(), String> {
+        match signature {
+            Some(SIGNATURE) => Ok(()),
+            _ => Err("bad signature".to_string()),
+        }
+    }
+
+    fn parse_receipts(&self, payload: &[u8]) -> Result<Vec<SmsDeliveryReceipt>, String> {
+        std::str::from_utf8(payload)
+            .map_err(|e| e.to_string())?
+            .lines()
+            .map(|line| {
+                let (id, status) = line.split_once(':').ok_or("missing status")?;
+                Ok(SmsDeliveryReceipt {
+                    provider_message_id: id.to_string(),
+                    status: SmsDeliveryStatus::from_str(status).ok_or("unknown status")?,
+                    error: None,
+                })
+            })
+            .collect()
+    }
+}
+
+async fn create_service() -> (SmsDeliveryService<MockDeliveryLogs>, Arc<MockDeliveryLogs>) {
+    let logs = Arc::new(MockDeliveryLogs::default());
+    for id in ["SM1", "SM2"] {
+        logs.save(SmsDeliveryLog::sent(Some("trace-1".to_string()), "Mock", id, "+*******5678"))
+            .await
+            .unwrap();
+    }
+
+    let health = Arc::new(SmsDeliveryHealth::new(SmsDeliveryConfig {
+        min_receipts: 2,
+        ..SmsDeliveryConfig::default()
+    }));
+    let service = SmsDeliveryService::new(logs.clone(), health).with_provider(Arc::new(MockReceiptProvider));
+    (service, logs)
+}
+
+#[tokio::test]
+async fn test_receipts_update_the_delivery_log() {
+    let (service, logs) = create_service().await;
+
+    let summary = service
+        .handle_callback("Mock", "https://example.com", b"SM1:delivered\nSM2:undelivered\nSM9:delivered", Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(summary, ReceiptSummary { applied: 2, unchanged: 0, unknown: 1 });
+
+    let statuses: Vec<_> = logs.find_by_trace_id("trace-1").await.unwrap().iter().map(|log| log.status).collect();
+    assert_eq!(statuses, vec![SmsDeliveryStatus::Delivered, SmsDeliveryStatus::Undelivered]);
+    assert_eq!(service.health().failure_rate("Mock"), Some(0.5));
+
+    // Redelivered and out-of-order receipts change nothing
+    let summary = service
+        .handle_callback("Mock", "https://example.com", b"SM1:delivered\nSM2:sent", Some(SIGNATURE))
+        .await
+        .unwrap();
+    assert_eq!(summary, ReceiptSummary { applied: 0, unchanged: 2, unknown: 0 });
+    assert_eq!(service.health().failure_rate("Mock"), Some(0.5));
+}
+
+#[tokio::test]
+async fn test_rejects_unverified_and_malformed_callbacks() {
+    let (service, _logs) = create_service().await;
+
+    let result = service.handle_callback("Mock", "https://example.com", b"SM1:delivered", None).await;
+    assert!(matches!(result, Err(DomainError::Unauthorized)));
+
+    let result = service.handle_callback("Mock", "https://example.com", b"SM1", Some(SIGNATURE)).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    let result = service.handle_callback("Other", "https://example.com", b"SM1:delivered", Some(SIGNATURE)).await;
+    assert!(matches!(result, Err(DomainError::NotFound { .. })));
+}
+
+#[test]
+fn test_health_reports_degraded_providers() {
+    let health = SmsDeliveryHealth::new(SmsDeliveryConfig {
+        window_size: 4,
+        window_seconds: 60,
+        min_receipts: 3,
+        max_failure_rate: 0.5,
+    });
+
+    health.record("Twilio", SmsDeliveryStatus::Failed);
+    health.record("Twilio", SmsDeliveryStatus::Sent);
+    health.record("Twilio", SmsDeliveryStatus::Undelivered);
+    assert_eq!(health.failure_rate("Twilio"), None);
+    assert!(!health.is_degraded("Twilio"));
+
+    health.record("Twilio", SmsDeliveryStatus::Delivered);
+    assert!(health.is_degraded("Twilio"));
+
+    // The window keeps the 4 latest receipts
+    health.record("Twilio", SmsDeliveryStatus::Delivered);
+    health.record("Twilio", SmsDeliveryStatus::Delivered);
+    assert_eq!(health.failure_rate("Twilio"), Some(0.25));
+    assert!(!health.is_degraded("Twilio"));
+
+    // Receipts older than the window no longer count
+    let old = Utc::now() - Duration::seconds(120);
+    health.record_at("AWS SNS", SmsDeliveryStatus::Failed, old);
+    health.record_at("AWS SNS", SmsDeliveryStatus::Failed, old);
+    health.record_at("AWS SNS", SmsDeliveryStatus::Failed, old);
+    assert_eq!(health.failure_rate("AWS SNS"), None);
+}
@@ -0,0 +1,30 @@
+use crate::domain::entities::sms_delivery::SmsDeliveryReceipt;
+
+/// Trait for decoding the delivery callbacks of an SMS provider
+pub trait SmsReceiptProviderTrait: Send + Sync {
+    /// Provider name as recorded in the delivery log (e.g. "Twilio")
+    fn provider_name(&self) -> &str;
+
+    /// Name of the HTTP header carrying the callback signature, if any
+    fn signature_header(&self) -> Option<&str>;
+
+    /// Verify that a callback was sent by the provider
+    ///
+    /// # Arguments
+    /// * `url` - Full URL the provider called, including the query string
+    /// * `payload` - Raw request body, exactly as received
+    /// * `signature` - Value of the signature header, if any
+    ///
+    /// # Returns
+    /// * `Ok(())` - The callback is authentic
+    /// * `Err(String)` - Why the callback was rejected
+    fn verify_callback(&self, url: &str, payload: &[u8], signature: Option<&str>) -> Result<(), String>;
+
+    /// Decode the receipts of a verified callback
+    ///
+    /// # Returns
+    /// * `Ok(Vec<SmsDeliveryReceipt>)` - The receipts, empty for callbacks
+    ///   that carry none (e.g. subscription confirmations)
+    /// * `Err(String)` - The payload is malformed
+    fn parse_receipts(&self, payload: &[u8]) -> Result<Vec<SmsDeliveryReceipt>, String>;
+}
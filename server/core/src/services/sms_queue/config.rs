@@ -0,0 +1,31 @@
+//! Configuration for the SMS queue
+
+/// Configuration for the SMS queue
+#[derive(Debug, Clone)]
+pub struct SmsQueueConfig {
+    /// How often the background task sends due messages (in milliseconds)
+    pub poll_interval_ms: u64,
+    /// Maximum number of messages sent per cycle
+    pub batch_size: usize,
+    /// Attempts before a message is given up and dead-lettered
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every failed attempt (in seconds)
+    pub base_retry_delay_seconds: i64,
+    /// Upper bound for the retry delay (in seconds)
+    pub max_retry_delay_seconds: i64,
+    /// Whether to enable background sending
+    pub enabled: bool,
+}
+
+impl Default for SmsQueueConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 250, // Verification codes should leave within a second
+            batch_size: 50,
+            max_attempts: 5,
+            base_retry_delay_seconds: 2,
+            max_retry_delay_seconds: 60,
+            enabled: true,
+        }
+    }
+}
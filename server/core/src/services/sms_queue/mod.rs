@@ -0,0 +1,18 @@
+//! SMS queue service module for sending messages outside the request path
+//!
+//! This module handles:
+//! - Queueing verification codes and text messages in a shared store
+//! - Sending due messages from a background worker
+//! - Retrying failed sends with exponential backoff
+//! - Dead-lettering messages that are given up or expire before being sent
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::SmsQueueConfig;
+pub use service::{SmsQueueResult, SmsQueueService};
+pub use traits::SmsQueueStoreTrait;
@@ -0,0 +1,271 @@
+//! SMS queue service
+//!
+//! Messages are pushed to a shared store and sent by a background task, so
+//! requests such as `/auth/send-code` do not wait for the SMS provider.
+//! Failed sends are retried with exponential backoff until `max_attempts`
+//! is reached, after which the job is moved to a dead-letter list with any
+//! verification code redacted.
+//!
+//! Verification codes that expire before they could be sent are
+//! dead-lettered without another attempt. A job claimed by an instance
+//! that stops before finishing it is lost; users can request a new code.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::future::join_all;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::sms_job::{SmsJob, SmsJobPayload};
+use crate::errors::{DomainError, DomainResult};
+use crate::services::verification::{SmsQueueTrait, SmsServiceTrait};
+
+use super::config::SmsQueueConfig;
+use super::traits::SmsQueueStoreTrait;
+
+/// Service for queueing SMS and sending them in the background
+pub struct SmsQueueService<Q, S>
+where
+    Q: SmsQueueStoreTrait + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    store: Arc<Q>,
+    sms_service: Arc<S>,
+    config: SmsQueueConfig,
+}
+
+impl<Q, S> SmsQueueService<Q, S>
+where
+    Q: SmsQueueStoreTrait + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    /// Create a new SMS queue service
+    pub fn new(store: Arc<Q>, sms_service: Arc<S>, config: SmsQueueConfig) -> Self {
+        Self {
+            store,
+            sms_service,
+            config,
+        }
+    }
+
+    /// Create a new SMS queue service with default configuration
+    pub fn with_defaults(store: Arc<Q>, sms_service: Arc<S>) -> Self {
+        Self::new(store, sms_service, SmsQueueConfig::default())
+    }
+
+    /// Queue a job for sending
+    ///
+    /// # Returns
+    /// * `Ok(Uuid)` - ID of the queued job
+    /// * `Err(DomainError::Validation)` - Invalid phone number
+    /// * `Err(DomainError::Internal)` - The store is unavailable
+    pub async fn enqueue(&self, job: SmsJob) -> DomainResult<Uuid> {
+        if !self.sms_service.is_valid_phone_number(&job.phone) {
+            return Err(DomainError::Validation {
+                message: "Invalid phone number format".to_string(),
+            });
+        }
+
+        self.store.push(&job).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to queue SMS: {}", e),
+        })?;
+
+        Ok(job.id)
+    }
+
+    /// Send the jobs that are due
+    ///
+    /// At most `batch_size` jobs are claimed per call and sent concurrently.
+    ///
+    /// # Returns
+    /// * `Ok(SmsQueueResult)` - Summary of the cycle
+    /// * `Err(DomainError)` - If due jobs could not be claimed
+    pub async fn process_due(&self) -> DomainResult<SmsQueueResult> {
+        let now = Utc::now();
+        let jobs = self
+            .store
+            .claim_due(now, self.config.batch_size)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to claim queued SMS: {}", e),
+            })?;
+
+        let outcomes = join_all(jobs.into_iter().map(|job| self.process(job, now))).await;
+
+        let mut result = SmsQueueResult::default();
+        for outcome in outcomes {
+            match outcome {
+                Ok(JobOutcome::Sent) => result.sent += 1,
+                Ok(JobOutcome::Retried) => result.retried += 1,
+                Ok(JobOutcome::DeadLettered) => result.dead_lettered += 1,
+                Ok(JobOutcome::Expired) => result.expired += 1,
+                Err(e) => result.errors.push(e),
+            }
+        }
+
+        if result.total_processed() > 0 {
+            info!(
+                sent = result.sent,
+                retried = result.retried,
+                dead_lettered = result.dead_lettered,
+                expired = result.expired,
+                "SMS queue cycle completed"
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Send a claimed job and store its outcome
+    async fn process(&self, mut job: SmsJob, now: DateTime<Utc>) -> Result<JobOutcome, String> {
+        if job.is_expired(now) {
+            warn!(job_id = %job.id, attempts = job.attempts, "Queued SMS expired before it was sent");
+            job.last_error = Some("Expired before it was sent".to_string());
+            self.dead_letter(&job).await?;
+            return Ok(JobOutcome::Expired);
+        }
+
+        let sent = match job.payload {
+            SmsJobPayload::VerificationCode { ref code } => {
+                self.sms_service.send_verification_code(&job.phone, code).await
+            }
+            SmsJobPayload::Message { ref text } => self.sms_service.send_message(&job.phone, text).await,
+        };
+
+        let error = match sent {
+            Ok(message_id) => {
+                info!(job_id = %job.id, message_id = %message_id, attempt = job.attempts + 1, "Queued SMS sent");
+                return Ok(JobOutcome::Sent);
+            }
+            Err(e) => e,
+        };
+
+        warn!(
+            job_id = %job.id,
+            attempt = job.attempts + 1,
+            error = %error,
+            "Queued SMS send failed"
+        );
+
+        let retry = job.record_failure(
+            error,
+            now,
+            self.config.max_attempts,
+            Duration::seconds(self.config.base_retry_delay_seconds),
+            Duration::seconds(self.config.max_retry_delay_seconds),
+        );
+        if !retry {
+            self.dead_letter(&job).await?;
+            return Ok(JobOutcome::DeadLettered);
+        }
+
+        self.store.push(&job).await.map_err(|e| {
+            error!(job_id = %job.id, error = %e, "Failed to requeue SMS");
+            format!("{}: {}", job.id, e)
+        })?;
+        Ok(JobOutcome::Retried)
+    }
+
+    /// Move a job that was given up to the dead-letter list
+    async fn dead_letter(&self, job: &SmsJob) -> Result<(), String> {
+        self.store.dead_letter(&job.redacted()).await.map_err(|e| {
+            error!(job_id = %job.id, error = %e, "Failed to dead-letter SMS");
+            format!("{}: {}", job.id, e)
+        })
+    }
+
+    /// The most recently dead-lettered jobs, newest first
+    pub async fn dead_letters(&self, limit: usize) -> DomainResult<Vec<SmsJob>> {
+        self.store.dead_letters(limit).await.map_err(|e| DomainError::Internal {
+            message: format!("Failed to load SMS dead letters: {}", e),
+        })
+    }
+
+    /// Start the queue worker as a background task
+    ///
+    /// This spawns a tokio task that sends due messages at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("SMS queue worker is disabled");
+            return;
+        }
+
+        let interval = std::time::Duration::from_millis(self.config.poll_interval_ms);
+
+        tokio::spawn(async move {
+            info!(
+                "SMS queue worker started - will poll every {} ms",
+                self.config.poll_interval_ms
+            );
+
+            let mut interval_timer = tokio::time::interval(interval);
+            interval_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval_timer.tick().await;
+
+                match self.process_due().await {
+                    Ok(result) => {
+                        if !result.errors.is_empty() {
+                            warn!("SMS queue cycle completed with errors: {:?}", result.errors);
+                        }
+                    }
+                    Err(e) => {
+                        error!("SMS queue cycle failed: {}", e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<Q, S> SmsQueueTrait for SmsQueueService<Q, S>
+where
+    Q: SmsQueueStoreTrait + 'static,
+    S: SmsServiceTrait + 'static,
+{
+    async fn enqueue_verification_code(
+        &self,
+        phone: &str,
+        code: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String, String> {
+        let job = SmsJob::verification_code(phone.to_string(), code.to_string(), expires_at);
+        self.enqueue(job)
+            .await
+            .map(|id| id.to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Outcome of sending a single claimed job
+enum JobOutcome {
+    Sent,
+    Retried,
+    DeadLettered,
+    Expired,
+}
+
+/// Result of an SMS queue cycle
+#[derive(Debug, Default)]
+pub struct SmsQueueResult {
+    /// Number of messages accepted by the provider
+    pub sent: usize,
+    /// Number of failed messages scheduled for another attempt
+    pub retried: usize,
+    /// Number of messages given up after the last attempt
+    pub dead_lettered: usize,
+    /// Number of messages dropped because they expired before being sent
+    pub expired: usize,
+    /// Any errors encountered while saving job state
+    pub errors: Vec<String>,
+}
+
+impl SmsQueueResult {
+    /// Get total number of jobs processed
+    pub fn total_processed(&self) -> usize {
+        self.sent + self.retried + self.dead_lettered + self.expired
+    }
+}
@@ -0,0 +1,253 @@
+//! Unit tests for the SMS queue service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::sync::Arc;
+
+use crate::domain::entities::sms_job::{SmsJob, SmsJobPayload, REDACTED_CODE};
+use crate::errors::DomainError;
+use crate::services::sms_queue::{SmsQueueConfig, SmsQueueService, SmsQueueStoreTrait};
+use crate::services::verification::{SmsQueueTrait, SmsServiceTrait};
+
+/// In-memory queue store
+#[derive(Default)]
+pub(crate) struct MockSmsQueueStore {
+    pub(crate) jobs: Mutex<Vec<SmsJob>>,
+    pub(crate) dead: Mutex<Vec<SmsJob>>,
+    pub(crate) unavailable: AtomicBool,
+}
+
+impl MockSmsQueueStore {
+    pub(crate) fn jobs(&self) -> Vec<SmsJob> {
+        self.jobs.lock().unwrap().clone()
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.unavailable.load(Ordering::SeqCst) {
+            return Err("Queue store unavailable".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SmsQueueStoreTrait for MockSmsQueueStore {
+    async fn push(&self, job: &SmsJob) -> Result<(), String> {
+        self.check()?;
+        self.jobs.lock().unwrap().push(job.clone());
+        Ok(())
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<SmsJob>, String> {
+        self.check()?;
+        let mut jobs = self.jobs.lock().unwrap();
+        let (mut due, pending): (Vec<_>, Vec<_>) = jobs.drain(..).partition(|job| job.next_attempt_at <= now);
+        *jobs = pending;
+        jobs.extend(due.split_off(due.len().min(limit)));
+        Ok(due)
+    }
+
+    async fn dead_letter(&self, job: &SmsJob) -> Result<(), String> {
+        self.check()?;
+        self.dead.lock().unwrap().insert(0, job.clone());
+        Ok(())
+    }
+
+    async fn dead_letters(&self, limit: usize) -> Result<Vec<SmsJob>, String> {
+        self.check()?;
+        Ok(self.dead.lock().unwrap().iter().take(limit).cloned().collect())
+    }
+}
+
+/// SMS provider failing its first `failures` sends
+#[derive(Default)]
+struct FlakySmsService {
+    failures: Mutex<u32>,
+    sent: Mutex<Vec<(String, String)>>,
+}
+
+impl FlakySmsService {
+    fn failing(failures: u32) -> Self {
+        Self {
+            failures: Mutex::new(failures),
+            ..Default::default()
+        }
+    }
+
+    fn sent(&self) -> Vec<(String, String)> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    fn send(&self, phone: &str, body: &str) -> Result<String, String> {
+        let mut failures = self.failures.lock().unwrap();
+        if *failures > 0 {
+            *failures -= 1;
+            return Err("Provider timed out".to_string());
+        }
+        self.sent.lock().unwrap().push((phone.to_string(), body.to_string()));
+        Ok("provider-msg".to_string())
+    }
+}
+
+#[async_trait]
+impl SmsServiceTrait for FlakySmsService {
+    async fn send_verification_code(&self, phone: &str, code: &str) -> Result<String, String> {
+        self.send(phone, code)
+    }
+
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        self.send(phone, message)
+    }
+
+    fn is_valid_phone_number(&self, phone: &str) -> bool {
+        phone.starts_with('+') && phone.len() >= 10
+    }
+}
+
+fn service(
+    sms_service: FlakySmsService,
+) -> (SmsQueueService<MockSmsQueueStore, FlakySmsService>, Arc<MockSmsQueueStore>, Arc<FlakySmsService>) {
+    let store = Arc::new(MockSmsQueueStore::default());
+    let sms_service = Arc::new(sms_service);
+    let service = SmsQueueService::new(store.clone(), sms_service.clone(), SmsQueueConfig::default());
+    (service, store, sms_service)
+}
+
+fn code_job(phone: &str) -> SmsJob {
+    SmsJob::verification_code(phone.to_string(), "123456".to_string(), Utc::now() + Duration::minutes(5))
+}
+
+/// Make every queued job due now
+fn make_due(store: &MockSmsQueueStore) {
+    for job in store.jobs.lock().unwrap().iter_mut() {
+        job.next_attempt_at = Utc::now() - Duration::seconds(1);
+    }
+}
+
+#[tokio::test]
+async fn test_sends_queued_verification_code() {
+    let (service, store, sms_service) = service(FlakySmsService::default());
+
+    let job_id = service.enqueue(code_job("+61412345678")).await.unwrap();
+    assert_eq!(store.jobs()[0].id, job_id);
+    assert!(sms_service.sent().is_empty());
+
+    let result = service.process_due().await.unwrap();
+    assert_eq!(result.sent, 1);
+    assert!(store.jobs().is_empty());
+    assert_eq!(sms_service.sent(), vec![("+61412345678".to_string(), "123456".to_string())]);
+}
+
+#[tokio::test]
+async fn test_sends_queued_text_message() {
+    let (service, _, sms_service) = service(FlakySmsService::default());
+
+    service
+        .enqueue(SmsJob::message("+61412345678".to_string(), "Your job starts tomorrow".to_string()))
+        .await
+        .unwrap();
+    service.process_due().await.unwrap();
+
+    assert_eq!(sms_service.sent()[0].1, "Your job starts tomorrow");
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_phone() {
+    let (service, store, _) = service(FlakySmsService::default());
+
+    let result = service.enqueue(code_job("0412")).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+    assert!(store.jobs().is_empty());
+}
+
+#[tokio::test]
+async fn test_retries_with_exponential_backoff() {
+    let (service, store, sms_service) = service(FlakySmsService::failing(2));
+    service.enqueue(code_job("+61412345678")).await.unwrap();
+
+    let before = Utc::now();
+    let result = service.process_due().await.unwrap();
+    assert_eq!(result.retried, 1);
+    let job = &store.jobs()[0];
+    assert_eq!(job.attempts, 1);
+    assert_eq!(job.last_error.as_deref(), Some("Provider timed out"));
+    assert!(job.next_attempt_at >= before + Duration::seconds(2));
+
+    // Not due yet
+    assert_eq!(service.process_due().await.unwrap().total_processed(), 0);
+
+    make_due(&store);
+    let before = Utc::now();
+    service.process_due().await.unwrap();
+    assert!(store.jobs()[0].next_attempt_at >= before + Duration::seconds(4));
+
+    make_due(&store);
+    assert_eq!(service.process_due().await.unwrap().sent, 1);
+    assert_eq!(sms_service.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn test_dead_letters_after_max_attempts_with_code_redacted() {
+    let (service, store, _) = service(FlakySmsService::failing(10));
+    service.enqueue(code_job("+61412345678")).await.unwrap();
+
+    for _ in 0..SmsQueueConfig::default().max_attempts - 1 {
+        assert_eq!(service.process_due().await.unwrap().retried, 1);
+        make_due(&store);
+    }
+    assert_eq!(service.process_due().await.unwrap().dead_lettered, 1);
+    assert!(store.jobs().is_empty());
+
+    let dead = service.dead_letters(10).await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].attempts, 5);
+    assert_eq!(
+        dead[0].payload,
+        SmsJobPayload::VerificationCode { code: REDACTED_CODE.to_string() }
+    );
+}
+
+#[tokio::test]
+async fn test_dead_letters_expired_codes_without_sending() {
+    let (service, store, sms_service) = service(FlakySmsService::default());
+    let job = SmsJob::verification_code("+61412345678".to_string(), "123456".to_string(), Utc::now());
+    service.enqueue(job).await.unwrap();
+
+    let result = service.process_due().await.unwrap();
+    assert_eq!(result.expired, 1);
+    assert!(sms_service.sent().is_empty());
+    assert!(store.jobs().is_empty());
+    assert_eq!(service.dead_letters(10).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_claims_at_most_batch_size() {
+    let store = Arc::new(MockSmsQueueStore::default());
+    let config = SmsQueueConfig { batch_size: 2, ..Default::default() };
+    let service = SmsQueueService::new(store.clone(), Arc::new(FlakySmsService::default()), config);
+    for _ in 0..3 {
+        service.enqueue(code_job("+61412345678")).await.unwrap();
+    }
+
+    assert_eq!(service.process_due().await.unwrap().sent, 2);
+    assert_eq!(store.jobs().len(), 1);
+}
+
+#[tokio::test]
+async fn test_enqueue_verification_code_returns_job_id() {
+    let (service, store, _) = service(FlakySmsService::default());
+
+    let message_id = service
+        .enqueue_verification_code("+61412345678", "654321", Utc::now() + Duration::minutes(5))
+        .await
+        .unwrap();
+    assert_eq!(store.jobs()[0].id.to_string(), message_id);
+
+    store.unavailable.store(true, Ordering::SeqCst);
+    assert!(service
+        .enqueue_verification_code("+61412345678", "654321", Utc::now() + Duration::minutes(5))
+        .await
+        .is_err());
+}
@@ -0,0 +1,25 @@
+//! Traits for SMS queue storage
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::sms_job::SmsJob;
+
+/// Trait for stores holding queued SMS jobs shared by every instance
+#[async_trait]
+pub trait SmsQueueStoreTrait: Send + Sync {
+    /// Add a job, or put a failed one back, due at its `next_attempt_at`
+    async fn push(&self, job: &SmsJob) -> Result<(), String>;
+
+    /// Remove and return up to `limit` jobs due at `now`
+    ///
+    /// Each job is handed to a single caller, even when several instances
+    /// claim jobs concurrently.
+    async fn claim_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<SmsJob>, String>;
+
+    /// Append a job that was given up to the dead-letter list
+    async fn dead_letter(&self, job: &SmsJob) -> Result<(), String>;
+
+    /// The most recently dead-lettered jobs, newest first
+    async fn dead_letters(&self, limit: usize) -> Result<Vec<SmsJob>, String>;
+}
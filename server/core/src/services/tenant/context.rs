@@ -0,0 +1,25 @@
+//! Task-local tenant context
+//!
+//! The API wraps each request in [`scope`]; anything awaited inside it,
+//! down to the repositories, reads the tenant with [`current_tenant`].
+//! Outside a scope, e.g. in background jobs and command-line tools, the
+//! default tenant applies. Tasks started with `tokio::spawn` do not inherit
+//! the scope and must be given the tenant explicitly.
+
+use std::future::Future;
+
+use super::tenant_id::TenantId;
+
+tokio::task_local! {
+    static TENANT: TenantId;
+}
+
+/// Run a future on behalf of the given tenant
+pub fn scope<F: Future>(tenant: TenantId, future: F) -> impl Future<Output = F::Output> {
+    TENANT.scope(tenant, future)
+}
+
+/// Tenant of the request being served, or the default tenant
+pub fn current_tenant() -> TenantId {
+    TENANT.try_with(TenantId::clone).unwrap_or_default()
+}
@@ -0,0 +1,50 @@
+//! Tenant module for white-labeled marketplaces
+//!
+//! This module handles:
+//! - Identifying the marketplace (tenant) an API request is served for
+//! - Carrying the tenant through the services and repositories a request
+//!   calls, without threading it through every signature
+//! - Exposing the tenant to the data layer, which scopes the rows it reads
+//!   and writes to it
+//!
+//! # Scope
+//!
+//! Every table holding a marketplace's data carries a `tenant_id`, and
+//! every query on it is scoped to the current tenant: accounts, tokens and
+//! the audit log, but also payments, payouts, disputes, the journal, fee
+//! schedules, credit wallets, campaigns, scheduled messages, organizations,
+//! legal documents, compliance checks, security webhooks, OAuth consents,
+//! exports and the rest. Access tokens name the tenant that issued them and
+//! are refused by other tenants, so a signed-in user always acts as an
+//! account of the marketplace being served. Orders have no table of their
+//! own; the rows keyed by an order (fees, checklists, payments) are scoped.
+//!
+//! Tables of the deployment itself have no tenant: staff identities and
+//! OAuth clients, which serve every marketplace; verification codes, OTP
+//! fallbacks and the token blacklist, keyed by phone number or token ID;
+//! and the records of the deployment's provider accounts, i.e. SMS spend
+//! and delivery logs, payment webhook events, dead letters, the payment
+//! provider ledger and reconciliation reports.
+//!
+//! Work that does not start with a request finds its tenant explicitly:
+//! - Provider callbacks (payment webhooks, payout method and compliance
+//!   results) look up the tenant of the record they refer to, and dead
+//!   letters are replayed in the tenant of their webhook
+//! - Lifecycle hooks and audit writes run in the tenant the event was
+//!   recorded in
+//! - Background jobs (campaigns, scheduled messages, credit expiry,
+//!   invoicing, exports, webhook deliveries, ledger verification) run once
+//!   for each tenant they are given, the default tenant unless configured
+//! - The command-line user import imports into the default tenant
+//!
+//! Maintenance jobs such as re-hashing phone numbers, token cleanup, audit
+//! retention and reconciliation span all tenants.
+
+mod context;
+mod tenant_id;
+
+#[cfg(test)]
+mod tests;
+
+pub use context::{current_tenant, scope};
+pub use tenant_id::{TenantId, DEFAULT_TENANT, MAX_TENANT_ID_LENGTH};
@@ -0,0 +1,64 @@
+//! Identifier of a tenant
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// Tenant of single-marketplace deployments and of data that predates tenancy
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Maximum length of a tenant identifier
+pub const MAX_TENANT_ID_LENGTH: usize = 64;
+
+/// Identifier of a white-labeled marketplace
+///
+/// Identifiers are stored with every tenant-scoped row and used in cache
+/// keys, so only lowercase ASCII letters, digits and `-` are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Parse a tenant identifier
+    ///
+    /// # Returns
+    /// * `Some(TenantId)` - The identifier is well-formed
+    /// * `None` - The identifier is empty, too long or has other characters
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_TENANT_ID_LENGTH
+            && !value.starts_with('-')
+            && value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        valid.then(|| Self(value.to_string()))
+    }
+
+    /// The identifier as stored
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is the default tenant
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_TENANT
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TenantId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).ok_or_else(|| serde::de::Error::custom(format!("invalid tenant ID: {}", value)))
+    }
+}
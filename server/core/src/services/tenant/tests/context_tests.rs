@@ -0,0 +1,26 @@
+//! Unit tests for the task-local tenant context
+
+use crate::services::tenant::{current_tenant, scope, TenantId};
+
+#[tokio::test]
+async fn test_tenant_is_visible_inside_scope_only() {
+    assert!(current_tenant().is_default());
+
+    let brand = TenantId::parse("brand-a").unwrap();
+    let inner = scope(brand.clone(), async { current_tenant() }).await;
+
+    assert_eq!(inner, brand);
+    assert!(current_tenant().is_default());
+}
+
+#[tokio::test]
+async fn test_nested_scope_overrides_tenant() {
+    let (outer, inner) = scope(TenantId::parse("outer").unwrap(), async {
+        let inner = scope(TenantId::parse("inner").unwrap(), async { current_tenant() }).await;
+        (current_tenant(), inner)
+    })
+    .await;
+
+    assert_eq!(outer.as_str(), "outer");
+    assert_eq!(inner.as_str(), "inner");
+}
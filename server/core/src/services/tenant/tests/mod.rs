@@ -0,0 +1,4 @@
+//! Tests for the tenant module
+
+mod context_tests;
+mod tenant_id_tests;
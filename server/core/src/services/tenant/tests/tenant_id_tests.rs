@@ -0,0 +1,34 @@
+//! Unit tests for tenant identifiers
+
+use crate::services::tenant::{TenantId, DEFAULT_TENANT};
+
+#[test]
+fn test_tenant_id_validation() {
+    assert_eq!(TenantId::parse("brand-a").unwrap().as_str(), "brand-a");
+    assert_eq!(TenantId::parse("reno2").unwrap().to_string(), "reno2");
+
+    assert!(TenantId::parse("").is_none());
+    assert!(TenantId::parse("Brand").is_none());
+    assert!(TenantId::parse("-brand").is_none());
+    assert!(TenantId::parse("brand a").is_none());
+    assert!(TenantId::parse("brand:a").is_none());
+    assert!(TenantId::parse(&"a".repeat(65)).is_none());
+}
+
+#[test]
+fn test_default_tenant() {
+    let tenant = TenantId::default();
+
+    assert_eq!(tenant.as_str(), DEFAULT_TENANT);
+    assert!(tenant.is_default());
+    assert!(!TenantId::parse("brand-a").unwrap().is_default());
+}
+
+#[test]
+fn test_tenant_id_deserialization_is_validated() {
+    let tenant: TenantId = serde_json::from_str("\"brand-a\"").unwrap();
+    assert_eq!(tenant.as_str(), "brand-a");
+    assert_eq!(serde_json::to_string(&tenant).unwrap(), "\"brand-a\"");
+
+    assert!(serde_json::from_str::<TenantId>("\"Brand A\"").is_err());
+}
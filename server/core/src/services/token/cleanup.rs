@@ -20,6 +20,9 @@ pub struct TokenCleanupConfig {
     pub batch_size: usize,
     /// Whether to enable automatic cleanup
     pub enabled: bool,
+    /// Whether expired tokens are moved to the archive instead of discarded,
+    /// preserving their history for security investigations
+    pub archive_expired_tokens: bool,
 }
 
 impl Default for TokenCleanupConfig {
@@ -29,6 +32,7 @@ impl Default for TokenCleanupConfig {
             grace_period_days: 7,   // Keep expired tokens for 7 days
             batch_size: 1000,       // Process up to 1000 tokens per batch
             enabled: true,
+            archive_expired_tokens: false,
         }
     }
 }
@@ -48,7 +52,8 @@ impl<R: TokenRepository> TokenCleanupService<R> {
     /// Run a single cleanup cycle
     ///
     /// This method performs the following cleanup tasks:
-    /// 1. Delete expired refresh tokens (with grace period)
+    /// 1. Delete expired refresh tokens (with grace period), archiving them
+    ///    first if configured
     /// 2. Clean up expired blacklist entries
     /// 3. Revoke orphaned tokens from incomplete rotations
     ///
@@ -68,7 +73,12 @@ impl<R: TokenRepository> TokenCleanupService<R> {
         match self.cleanup_expired_tokens().await {
             Ok(count) => {
                 result.expired_tokens_deleted = count;
-                info!("Deleted {} expired refresh tokens", count);
+                if self.config.archive_expired_tokens {
+                    result.expired_tokens_archived = count;
+                    info!("Archived {} expired refresh tokens", count);
+                } else {
+                    info!("Deleted {} expired refresh tokens", count);
+                }
             }
             Err(e) => {
                 error!("Failed to cleanup expired tokens: {}", e);
@@ -115,8 +125,15 @@ impl<R: TokenRepository> TokenCleanupService<R> {
     }
 
     /// Clean up expired refresh tokens with grace period
+    ///
+    /// When archival is configured and fails, the tokens are kept rather
+    /// than deleted without a record.
     async fn cleanup_expired_tokens(&self) -> Result<usize, DomainError> {
-        self.repository.delete_expired_tokens().await
+        if self.config.archive_expired_tokens {
+            self.repository.archive_expired_tokens().await
+        } else {
+            self.repository.delete_expired_tokens().await
+        }
     }
 
     /// Clean up expired blacklist entries
@@ -177,6 +194,8 @@ impl<R: TokenRepository> TokenCleanupService<R> {
 pub struct CleanupResult {
     /// Number of expired refresh tokens deleted
     pub expired_tokens_deleted: usize,
+    /// Number of the deleted tokens that were archived first
+    pub expired_tokens_archived: usize,
     /// Number of expired blacklist entries deleted
     pub blacklist_entries_deleted: usize,
     /// Number of orphaned tokens revoked
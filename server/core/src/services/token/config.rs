@@ -2,6 +2,7 @@
 
 use jsonwebtoken::Algorithm;
 use re_shared::config::auth::AuthConfig;
+use re_shared::config::SecretString;
 use super::key_manager::{Rs256KeyManager, Rs256KeyConfig};
 use crate::errors::DomainError;
 
@@ -9,7 +10,7 @@ use crate::errors::DomainError;
 #[derive(Debug, Clone)]
 pub struct TokenServiceConfig {
     /// JWT signing secret (for HS256, deprecated)
-    pub jwt_secret: String,
+    pub jwt_secret: SecretString,
     /// JWT signing algorithm
     pub algorithm: Algorithm,
     /// Access token expiry in minutes
@@ -18,6 +19,17 @@ pub struct TokenServiceConfig {
     pub refresh_token_expiry_days: i64,
     /// RS256 key configuration (optional, for RS256 algorithm)
     pub rs256_config: Option<Rs256KeyConfig>,
+    /// Minutes without a refresh after which a session expires (None disables)
+    ///
+    /// Only enforced when a session activity store is attached. Must be
+    /// longer than the access token expiry, since active clients refresh
+    /// about once per access token lifetime.
+    pub session_idle_timeout_minutes: Option<i64>,
+    /// Seconds a blacklist check of an access token is reused (0 disables)
+    ///
+    /// Revocations broadcast by other instances update the cache at once;
+    /// this bounds how stale it gets when a broadcast is missed.
+    pub revocation_cache_ttl_seconds: i64,
 }
 
 impl Default for TokenServiceConfig {
@@ -25,11 +37,13 @@ impl Default for TokenServiceConfig {
         let auth_config = AuthConfig::default();
         // Default to RS256 algorithm with key files
         Self {
-            jwt_secret: auth_config.jwt_secret().to_string(),
+            jwt_secret: auth_config.jwt.secret.clone(),
             algorithm: Algorithm::RS256,
             access_token_expiry_minutes: auth_config.access_token_expiry_seconds() / 60,
             refresh_token_expiry_days: auth_config.refresh_token_expiry_seconds() / (60 * 60 * 24),
             rs256_config: Some(Rs256KeyConfig::default()),
+            session_idle_timeout_minutes: None,
+            revocation_cache_ttl_seconds: 30,
         }
     }
 }
@@ -52,12 +66,25 @@ impl From<AuthConfig> for TokenServiceConfig {
             None
         };
         
+        let session_idle_timeout_minutes = std::env::var("SESSION_IDLE_TIMEOUT_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|minutes: &i64| *minutes > 0);
+        
+        let revocation_cache_ttl_seconds = std::env::var("TOKEN_REVOCATION_CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|seconds: &i64| *seconds >= 0)
+            .unwrap_or(30);
+        
         Self {
-            jwt_secret: config.jwt_secret().to_string(),
+            jwt_secret: config.jwt.secret.clone(),
             algorithm,
             access_token_expiry_minutes: config.access_token_expiry_seconds() / 60,
             refresh_token_expiry_days: config.refresh_token_expiry_seconds() / (60 * 60 * 24),
             rs256_config,
+            session_idle_timeout_minutes,
+            revocation_cache_ttl_seconds,
         }
     }
 }
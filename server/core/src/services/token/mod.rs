@@ -6,11 +6,17 @@
 //! - Token revocation and cleanup
 //! - RS256 key management for asymmetric signing
 //! - Background cleanup of expired tokens
+//! - Idle timeouts for sessions without recent activity
+//! - Session records listing each signed-in device
+//! - Revocations cached in-process and broadcast to other instances
 
 mod cleanup;
 mod config;
 mod key_manager;
+mod revocation;
 mod service;
+mod session_activity;
+mod session_store;
 
 #[cfg(test)]
 mod tests;
@@ -18,4 +24,7 @@ mod tests;
 pub use cleanup::{TokenCleanupService, TokenCleanupConfig, CleanupResult};
 pub use config::TokenServiceConfig;
 pub use key_manager::{Rs256KeyManager, Rs256KeyConfig};
-pub use service::TokenService;
\ No newline at end of file
+pub use revocation::{RevocationCache, TokenRevocation, TokenRevocationBroadcasterTrait};
+pub use service::TokenService;
+pub use session_activity::SessionActivityStoreTrait;
+pub use session_store::{SessionInfo, SessionMetadata, SessionStoreTrait};
\ No newline at end of file
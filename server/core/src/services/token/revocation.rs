@@ -0,0 +1,145 @@
+//! Token revocation cache shared across instances
+//!
+//! Verifying an access token checks the blacklist in the database. Each
+//! instance caches the outcome per token ID for a short time, and remembers
+//! revoked sessions (token families) for the lifetime of an access token so
+//! their access tokens are rejected too. Revocations are broadcast to the
+//! other instances, which apply them to their caches immediately instead of
+//! serving stale entries until they expire.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Number of cached blacklist checks above which expired ones are dropped
+const MAX_CACHED_CHECKS: usize = 10_000;
+
+/// A revocation announced to every instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TokenRevocation {
+    /// An access token was blacklisted
+    AccessToken {
+        /// JWT ID of the token
+        jti: String,
+        /// When the token expires, after which it needs no blacklisting
+        expires_at: DateTime<Utc>,
+    },
+    /// A session was revoked along with all tokens of its family
+    TokenFamily {
+        /// Token family ID of the session
+        token_family: String,
+    },
+}
+
+/// Trait for announcing revocations to the other instances
+#[async_trait]
+pub trait TokenRevocationBroadcasterTrait: Send + Sync {
+    /// Broadcast a revocation
+    ///
+    /// Delivery is best effort: instances that miss it fall back to the
+    /// database once their cached entries expire.
+    async fn broadcast(&self, revocation: &TokenRevocation) -> Result<(), String>;
+}
+
+/// Outcome of a blacklist check
+struct CachedCheck {
+    revoked: bool,
+    valid_until: DateTime<Utc>,
+}
+
+/// In-process cache of token revocations
+pub struct RevocationCache {
+    /// How long a blacklist check is reused
+    check_ttl: Duration,
+    /// How long a revoked family is remembered
+    family_ttl: Duration,
+    /// Blacklist checks by JWT ID
+    checks: RwLock<HashMap<String, CachedCheck>>,
+    /// Revoked token families and when they can be forgotten
+    revoked_families: RwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+impl RevocationCache {
+    /// Create an empty revocation cache
+    ///
+    /// # Arguments
+    ///
+    /// * `check_ttl` - How long a blacklist check is reused, zero to disable
+    /// * `family_ttl` - How long a revoked family is remembered, at least
+    ///   the access token lifetime
+    pub fn new(check_ttl: Duration, family_ttl: Duration) -> Self {
+        Self {
+            check_ttl,
+            family_ttl,
+            checks: RwLock::new(HashMap::new()),
+            revoked_families: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Cached blacklist status of an access token, if still fresh
+    pub fn is_token_revoked(&self, jti: &str) -> Option<bool> {
+        let checks = self.checks.read().ok()?;
+        checks
+            .get(jti)
+            .filter(|check| check.valid_until > Utc::now())
+            .map(|check| check.revoked)
+    }
+
+    /// Remember the outcome of a blacklist check
+    pub fn record_check(&self, jti: &str, revoked: bool) {
+        if self.check_ttl <= Duration::zero() {
+            return;
+        }
+
+        self.insert_check(jti, revoked, Utc::now() + self.check_ttl);
+    }
+
+    /// Whether a token family was revoked recently
+    pub fn is_family_revoked(&self, token_family: &str) -> bool {
+        self.revoked_families
+            .read()
+            .map(|families| {
+                families
+                    .get(token_family)
+                    .is_some_and(|until| *until > Utc::now())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Apply a revocation made on this or another instance
+    pub fn apply(&self, revocation: &TokenRevocation) {
+        match revocation {
+            TokenRevocation::AccessToken { jti, expires_at } => {
+                self.insert_check(jti, true, *expires_at);
+            }
+            TokenRevocation::TokenFamily { token_family } => {
+                if let Ok(mut families) = self.revoked_families.write() {
+                    let now = Utc::now();
+                    families.retain(|_, until| *until > now);
+                    families.insert(token_family.clone(), now + self.family_ttl);
+                }
+            }
+        }
+    }
+
+    fn insert_check(&self, jti: &str, revoked: bool, valid_until: DateTime<Utc>) {
+        let Ok(mut checks) = self.checks.write() else {
+            return;
+        };
+
+        let now = Utc::now();
+        // A check that started before the token was blacklisted must not undo it
+        if !revoked && checks.get(jti).is_some_and(|check| check.revoked && check.valid_until > now) {
+            return;
+        }
+
+        if checks.len() >= MAX_CACHED_CHECKS {
+            checks.retain(|_, check| check.valid_until > now);
+        }
+        checks.insert(jti.to_string(), CachedCheck { revoked, valid_until });
+    }
+}
@@ -4,15 +4,25 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation,
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 use rand::Rng;
-use chrono::TimeZone;
+use chrono::{TimeZone, Utc};
+use std::sync::Arc;
+use tracing::{info, warn};
 
-use crate::domain::entities::token::{Claims, RefreshToken, TokenPair};
+use crate::domain::entities::audit::AuditLog;
+use crate::domain::entities::token::{
+    Claims, RefreshToken, TokenPair, ACCESS_TOKEN_EXPIRY_MINUTES, JWT_PARTNER_AUDIENCE,
+};
 use crate::domain::entities::user::UserType;
 use crate::errors::{DomainError, TokenError};
 use crate::repositories::TokenRepository;
+use crate::services::lock::DistributedLock;
+use crate::services::tenant::current_tenant;
 
 use super::config::TokenServiceConfig;
 use super::key_manager::Rs256KeyManager;
+use super::revocation::{RevocationCache, TokenRevocation, TokenRevocationBroadcasterTrait};
+use super::session_activity::SessionActivityStoreTrait;
+use super::session_store::{SessionInfo, SessionMetadata, SessionStoreTrait};
 
 /// Service for managing JWT tokens and refresh tokens
 pub struct TokenService<R: TokenRepository> {
@@ -23,6 +33,16 @@ pub struct TokenService<R: TokenRepository> {
     validation: Validation,
     /// Optional RS256 key manager for asymmetric signing
     rs256_key_manager: Option<Rs256KeyManager>,
+    /// Optional store of session activity for idle timeouts
+    session_activity: Option<Arc<dyn SessionActivityStoreTrait>>,
+    /// Optional store of session records for device management
+    sessions: Option<Arc<dyn SessionStoreTrait>>,
+    /// In-process cache of blacklist checks and revoked sessions
+    revocations: Arc<RevocationCache>,
+    /// Optional broadcaster announcing revocations to other instances
+    revocation_broadcaster: Option<Arc<dyn TokenRevocationBroadcasterTrait>>,
+    /// Optional lock serializing rotations of a session across instances
+    rotation_lock: Option<DistributedLock>,
 }
 
 impl<R: TokenRepository> TokenService<R> {
@@ -52,8 +72,8 @@ impl<R: TokenRepository> TokenService<R> {
                 (encoding_key, decoding_key, Some(manager))
             } else {
                 // Use HS256 with symmetric key (backward compatibility)
-                let encoding_key = EncodingKey::from_secret(config.jwt_secret.as_bytes());
-                let decoding_key = DecodingKey::from_secret(config.jwt_secret.as_bytes());
+                let encoding_key = EncodingKey::from_secret(config.jwt_secret.expose_secret().as_bytes());
+                let decoding_key = DecodingKey::from_secret(config.jwt_secret.expose_secret().as_bytes());
                 (encoding_key, decoding_key, None)
             };
         
@@ -63,6 +83,8 @@ impl<R: TokenRepository> TokenService<R> {
         validation.validate_exp = true;
         validation.validate_nbf = true;
         
+        let revocations = Self::new_revocation_cache(&config);
+
         Ok(Self {
             repository,
             config,
@@ -70,6 +92,11 @@ impl<R: TokenRepository> TokenService<R> {
             decoding_key,
             validation,
             rs256_key_manager,
+            session_activity: None,
+            sessions: None,
+            revocations,
+            revocation_broadcaster: None,
+            rotation_lock: None,
         })
     }
     
@@ -101,6 +128,8 @@ impl<R: TokenRepository> TokenService<R> {
         validation.validate_exp = true;
         validation.validate_nbf = true;
         
+        let revocations = Self::new_revocation_cache(&config);
+        
         Self {
             repository,
             config,
@@ -108,9 +137,70 @@ impl<R: TokenRepository> TokenService<R> {
             decoding_key,
             validation,
             rs256_key_manager: Some(key_manager),
+            session_activity: None,
+            sessions: None,
+            revocations,
+            revocation_broadcaster: None,
+            rotation_lock: None,
         }
     }
 
+    /// Enables idle timeouts using the given session activity store
+    ///
+    /// Refreshes are rejected once a session has been idle for longer than
+    /// `session_idle_timeout_minutes`; without that setting activity is
+    /// still recorded but never enforced.
+    pub fn with_session_activity_store(mut self, store: Arc<dyn SessionActivityStoreTrait>) -> Self {
+        self.session_activity = Some(store);
+        self
+    }
+
+    /// Records sessions with their device metadata in the given store
+    ///
+    /// The store also records session activity, replacing any store set
+    /// with [`Self::with_session_activity_store`].
+    pub fn with_session_store(mut self, store: Arc<dyn SessionStoreTrait>) -> Self {
+        self.session_activity = Some(store.clone());
+        self.sessions = Some(store);
+        self
+    }
+
+    /// Announces blacklisted tokens and revoked sessions to other instances
+    ///
+    /// Revocations received from other instances are applied to the cache
+    /// returned by [`Self::revocation_cache`].
+    pub fn with_revocation_broadcaster(mut self, broadcaster: Arc<dyn TokenRevocationBroadcasterTrait>) -> Self {
+        self.revocation_broadcaster = Some(broadcaster);
+        self
+    }
+
+    /// Serializes refresh token rotations of the same session across API instances
+    ///
+    /// Without the lock two instances refreshing with the same token at
+    /// once both see it unrevoked and each issue a refresh token, forking
+    /// the session. With it the second refresh sees the token rotated by
+    /// the first and is treated as a reuse of a revoked token.
+    pub fn with_rotation_lock(mut self, lock: DistributedLock) -> Self {
+        self.rotation_lock = Some(lock);
+        self
+    }
+
+    /// The in-process revocation cache used when verifying access tokens
+    pub fn revocation_cache(&self) -> Arc<RevocationCache> {
+        self.revocations.clone()
+    }
+
+    /// Creates the revocation cache, remembering revoked sessions for as
+    /// long as their access tokens may still be presented
+    fn new_revocation_cache(config: &TokenServiceConfig) -> Arc<RevocationCache> {
+        let access_token_lifetime = ACCESS_TOKEN_EXPIRY_MINUTES.max(config.access_token_expiry_minutes);
+
+        Arc::new(RevocationCache::new(
+            chrono::Duration::seconds(config.revocation_cache_ttl_seconds),
+            chrono::Duration::minutes(access_token_lifetime),
+        ))
+    }
+
     /// Generates a new token pair (access + refresh tokens) for a user
     ///
     /// # Arguments
@@ -133,6 +223,36 @@ impl<R: TokenRepository> TokenService<R> {
         phone_hash: Option<String>,
         device_fingerprint: Option<String>,
     ) -> Result<TokenPair, DomainError> {
+        let (token_pair, refresh_token) =
+            self.issue_tokens(user_id, user_type, is_verified, phone_hash, device_fingerprint)?;
+
+        // Store the refresh token
+        self.repository
+            .save_refresh_token(refresh_token)
+            .await
+            .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))?;
+
+        self.start_session(&token_pair).await;
+        Ok(token_pair)
+    }
+
+    /// Generates a new token pair without storing its refresh token
+    ///
+    /// Used when the refresh token is saved as part of a larger transaction;
+    /// call [`Self::start_session`] once it is committed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((TokenPair, RefreshToken))` - The token pair and the refresh token to store
+    /// * `Err(TokenError)` - Token generation failed
+    pub fn issue_tokens(
+        &self,
+        user_id: Uuid,
+        user_type: Option<UserType>,
+        is_verified: bool,
+        phone_hash: Option<String>,
+        device_fingerprint: Option<String>,
+    ) -> Result<(TokenPair, RefreshToken), DomainError> {
         // Generate token family ID for new token chains
         let token_family = Some(Uuid::new_v4().to_string());
         
@@ -143,25 +263,66 @@ impl<R: TokenRepository> TokenService<R> {
             is_verified,
             phone_hash,
             device_fingerprint.clone(),
+            token_family.clone(),
         )?;
         
         // Generate refresh token with family tracking
-        let refresh_token = self.generate_refresh_token(
+        let (refresh_token_string, refresh_token) = self.new_refresh_token(
             user_id,
             token_family.clone(),
             device_fingerprint.clone(),
             None,
-        ).await?;
+        );
         
-        Ok(TokenPair::new_with_metadata(
+        let token_pair = TokenPair::new_with_metadata(
             access_token,
-            refresh_token,
+            refresh_token_string,
             token_family,
             device_fingerprint,
-        ))
+        );
+        Ok((token_pair, refresh_token))
+    }
+
+    /// Starts tracking the activity of a newly issued token pair's session
+    pub async fn start_session(&self, token_pair: &TokenPair) {
+        if let Some(ref family) = token_pair.token_family {
+            self.record_session_activity(family).await;
+        }
+    }
+
+    /// Records where a newly issued token pair's session was started from
+    ///
+    /// Does nothing without a session store; a failing store is logged and
+    /// does not fail the sign-in.
+    pub async fn record_session(&self, user_id: Uuid, token_pair: &TokenPair, metadata: SessionMetadata) {
+        let (Some(store), Some(family)) = (&self.sessions, &token_pair.token_family) else {
+            return;
+        };
+
+        let now = Utc::now();
+        let session = SessionInfo {
+            token_family: family.clone(),
+            user_id,
+            device_fingerprint_hash: token_pair
+                .device_fingerprint
+                .as_deref()
+                .map(Claims::hash_device_fingerprint),
+            device: metadata.user_agent.as_deref().map(AuditLog::extract_device_info),
+            ip_address: metadata.ip_address,
+            country: metadata.country,
+            created_at: now,
+            last_activity_at: now,
+        };
+        if let Err(e) = store.save_session(&session, self.session_ttl_seconds()).await {
+            warn!(error = %e, "Failed to record session");
+        }
     }
 
     /// Generates an access token
+    ///
+    /// The token carries its session's family so that revoking the session
+    /// also rejects the access token, and the tenant it is issued by so
+    /// that other marketplaces of the deployment reject it.
     fn generate_access_token(
         &self,
         user_id: Uuid,
@@ -169,18 +330,23 @@ impl<R: TokenRepository> TokenService<R> {
         is_verified: bool,
         phone_hash: Option<String>,
         device_fingerprint: Option<String>,
+        token_family: Option<String>,
     ) -> Result<String, DomainError> {
         let user_type_str = user_type.map(|ut| match ut {
             UserType::Customer => "customer".to_string(),
             UserType::Worker => "worker".to_string(),
         });
-        let claims = Claims::new_access_token(
+        // Only the hash of the fingerprint is embedded to bind the token to the device
+        let mut claims = Claims::new_access_token(
             user_id,
             user_type_str,
             is_verified,
             phone_hash,
-            device_fingerprint,
+            device_fingerprint.as_deref().map(Claims::hash_device_fingerprint),
         );
+        claims.token_family = token_family;
+        let tenant = current_tenant();
+        claims.tenant = (!tenant.is_default()).then(|| tenant.to_string());
         self.encode_jwt(&claims)
     }
 
@@ -192,6 +358,26 @@ impl<R: TokenRepository> TokenService<R> {
         device_fingerprint: Option<String>,
         previous_token_id: Option<Uuid>,
     ) -> Result<String, DomainError> {
+        let (token_string, refresh_token) =
+            self.new_refresh_token(user_id, token_family, device_fingerprint, previous_token_id);
+        
+        // Store the refresh token
+        self.repository
+            .save_refresh_token(refresh_token)
+            .await
+            .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))?;
+        
+        Ok(token_string)
+    }
+
+    /// Generates a refresh token string and the entity storing its hash
+    fn new_refresh_token(
+        &self,
+        user_id: Uuid,
+        token_family: Option<String>,
+        device_fingerprint: Option<String>,
+        previous_token_id: Option<Uuid>,
+    ) -> (String, RefreshToken) {
         // Generate a random token string
         let mut rng = rand::thread_rng();
         let token_string: String = (0..32)
@@ -216,13 +402,7 @@ impl<R: TokenRepository> TokenService<R> {
             previous_token_id,
         );
         
-        // Store the refresh token
-        self.repository
-            .save_refresh_token(refresh_token)
-            .await
-            .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))?;
-        
-        Ok(token_string)
+        (token_string, refresh_token)
     }
 
     /// Encodes claims into a JWT
@@ -254,9 +434,8 @@ impl<R: TokenRepository> TokenService<R> {
                 }
             })?;
         
-        // Check if token is blacklisted
-        if self.repository.is_token_blacklisted(&token_data.claims.jti).await
-            .unwrap_or(false) {
+        // Check if token is blacklisted or its session revoked
+        if self.is_revoked(&token_data.claims).await {
             return Err(DomainError::Token(TokenError::TokenRevoked));
         }
         
@@ -289,6 +468,77 @@ impl<R: TokenRepository> TokenService<R> {
         Ok(token_data.claims)
     }
 
+    /// Generates a scope-limited access token for a partner application
+    ///
+    /// Partner tokens carry the partner audience, so the main API rejects
+    /// them; they are only accepted through `verify_partner_token`.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - The user the client acts for, or the client itself
+    /// * `client_id` - The OAuth client the token is issued to
+    /// * `scopes` - The scopes the token grants
+    /// * `expires_in_seconds` - Lifetime of the token
+    pub fn generate_partner_token(
+        &self,
+        subject: String,
+        client_id: String,
+        scopes: &[String],
+        expires_in_seconds: i64,
+    ) -> Result<String, DomainError> {
+        let claims = Claims::new_partner_token(subject, client_id, scopes, expires_in_seconds);
+        self.encode_jwt(&claims)
+    }
+
+    /// Generates an access token for an administrator signed in through single sign-on
+    ///
+    /// Admin tokens are ordinary access tokens with the `admin` user type and
+    /// the granted roles; they have no refresh token and expire with the
+    /// admin session.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_id` - The administrator's identity ID
+    /// * `roles` - The administrative roles granted
+    /// * `expires_in_seconds` - Lifetime of the admin session
+    pub fn generate_admin_token(
+        &self,
+        admin_id: Uuid,
+        roles: Vec<String>,
+        expires_in_seconds: i64,
+    ) -> Result<String, DomainError> {
+        let claims = Claims::new_admin_token(admin_id, roles, expires_in_seconds);
+        self.encode_jwt(&claims)
+    }
+
+    /// Verifies a partner access token and returns the claims
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Claims)` - The decoded claims, with `client_id` and `scope` set
+    /// * `Err(TokenError)` - Token is invalid, expired, revoked or not a partner token
+    pub async fn verify_partner_token(&self, token: &str) -> Result<Claims, DomainError> {
+        let mut validation = self.validation.clone();
+        validation.set_audience(&[JWT_PARTNER_AUDIENCE]);
+
+        let claims = decode::<Claims>(token, &self.decoding_key, &validation)
+            .map_err(|e| match e.kind() {
+                jsonwebtoken::errors::ErrorKind::ExpiredSignature => DomainError::Token(TokenError::TokenExpired),
+                jsonwebtoken::errors::ErrorKind::ImmatureSignature => DomainError::Token(TokenError::TokenNotYetValid),
+                _ => DomainError::Token(TokenError::InvalidTokenFormat),
+            })?
+            .claims;
+
+        if claims.client_id.is_none() {
+            return Err(DomainError::Token(TokenError::InvalidTokenFormat));
+        }
+        if self.is_revoked(&claims).await {
+            return Err(DomainError::Token(TokenError::TokenRevoked));
+        }
+
+        Ok(claims)
+    }
+
     /// Verifies a refresh token and returns the user ID
     ///
     /// # Arguments
@@ -342,6 +592,38 @@ impl<R: TokenRepository> TokenService<R> {
         is_verified: bool,
         phone_hash: Option<String>,
         device_fingerprint: Option<String>,
+    ) -> Result<TokenPair, DomainError> {
+        let Some(ref lock) = self.rotation_lock else {
+            return self
+                .rotate_tokens(refresh_token, user_type, is_verified, phone_hash, device_fingerprint)
+                .await;
+        };
+
+        // The session is only known once the token is loaded; it is loaded
+        // again under the lock, after any concurrent rotation finished
+        let token_family = self
+            .repository
+            .find_refresh_token(&self.hash_token(refresh_token))
+            .await
+            .ok()
+            .flatten()
+            .and_then(|token| token.token_family);
+
+        let rotation = self.rotate_tokens(refresh_token, user_type, is_verified, phone_hash, device_fingerprint);
+        match token_family {
+            Some(family) => lock.run(&format!("token:rotation:{}", family), rotation).await,
+            None => rotation.await,
+        }
+    }
+
+    /// Verifies a refresh token and rotates it
+    async fn rotate_tokens(
+        &self,
+        refresh_token: &str,
+        user_type: Option<UserType>,
+        is_verified: bool,
+        phone_hash: Option<String>,
+        device_fingerprint: Option<String>,
     ) -> Result<TokenPair, DomainError> {
         let token_hash = self.hash_token(refresh_token);
         
@@ -362,7 +644,7 @@ impl<R: TokenRepository> TokenService<R> {
             // If token is revoked, it might be a reuse attack
             // Revoke entire token family for security
             if let Some(ref family) = old_token.token_family {
-                let _ = self.repository.revoke_token_family(family).await;
+                let _ = self.revoke_token_family(family).await;
             }
             return Err(DomainError::Token(TokenError::TokenRevoked));
         }
@@ -374,12 +656,20 @@ impl<R: TokenRepository> TokenService<R> {
                 // Device mismatch - potential security issue
                 // Revoke the token family
                 if let Some(ref family) = old_token.token_family {
-                    let _ = self.repository.revoke_token_family(family).await;
+                    let _ = self.revoke_token_family(family).await;
                 }
                 return Err(DomainError::Token(TokenError::InvalidTokenFormat));
             }
         }
         
+        // Reject sessions that have been idle for too long
+        if let Some(ref family) = old_token.token_family {
+            self.check_session_idle(family).await?;
+        }
+        
+        // Keep the rotated tokens bound to the device of the session
+        let device_fingerprint = device_fingerprint.or_else(|| old_token.device_fingerprint.clone());
+        
         // Generate new access token
         let access_token = self.generate_access_token(
             old_token.user_id,
@@ -387,6 +677,7 @@ impl<R: TokenRepository> TokenService<R> {
             is_verified,
             phone_hash,
             device_fingerprint.clone(),
+            old_token.token_family.clone(),
         )?;
         
         // Rotate refresh token (generate new one, revoke old one)
@@ -400,6 +691,10 @@ impl<R: TokenRepository> TokenService<R> {
         // Revoke the old refresh token
         let _ = self.repository.revoke_token(&token_hash).await;
         
+        if let Some(ref family) = old_token.token_family {
+            self.record_session_activity(family).await;
+        }
+        
         Ok(TokenPair::new_with_metadata(
             access_token,
             new_refresh_token,
@@ -408,6 +703,56 @@ impl<R: TokenRepository> TokenService<R> {
         ))
     }
     
+    /// Rejects a session whose last activity is older than the idle timeout
+    ///
+    /// The whole token family is revoked so the session cannot be resumed.
+    /// Sessions without recorded activity and store failures are let
+    /// through, since the absolute refresh token expiry still applies.
+    async fn check_session_idle(&self, token_family: &str) -> Result<(), DomainError> {
+        let (Some(store), Some(timeout_minutes)) =
+            (&self.session_activity, self.config.session_idle_timeout_minutes)
+        else {
+            return Ok(());
+        };
+
+        let last_activity = match store.last_activity(token_family).await {
+            Ok(Some(last_activity)) => last_activity,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                warn!(error = %e, "Failed to load session activity");
+                return Ok(());
+            }
+        };
+
+        if Utc::now() - last_activity <= chrono::Duration::minutes(timeout_minutes) {
+            return Ok(());
+        }
+
+        info!(token_family = %token_family, last_activity = %last_activity, "Session expired due to inactivity");
+        let _ = self.revoke_token_family(token_family).await;
+        if let Err(e) = store.clear(token_family).await {
+            warn!(error = %e, "Failed to clear session activity");
+        }
+
+        Err(DomainError::Token(TokenError::SessionIdleTimeout))
+    }
+
+    /// Records activity on a session, kept for the refresh token lifetime
+    async fn record_session_activity(&self, token_family: &str) {
+        let Some(ref store) = self.session_activity else {
+            return;
+        };
+
+        if let Err(e) = store.record_activity(token_family, Utc::now(), self.session_ttl_seconds()).await {
+            warn!(error = %e, "Failed to record session activity");
+        }
+    }
+
+    /// How long session records are kept: the refresh token lifetime
+    fn session_ttl_seconds(&self) -> u64 {
+        (self.config.refresh_token_expiry_days.max(1) * 24 * 60 * 60) as u64
+    }
+    
     /// Refreshes an access token only (backward compatibility)
     ///
     /// # Arguments
@@ -430,7 +775,7 @@ impl<R: TokenRepository> TokenService<R> {
         let user_id = self.verify_refresh_token(refresh_token).await?;
         
         // Generate new access token
-        self.generate_access_token(user_id, user_type, is_verified, None, None)
+        self.generate_access_token(user_id, user_type, is_verified, None, None, None)
     }
 
     /// Revokes all tokens for a user
@@ -451,6 +796,23 @@ impl<R: TokenRepository> TokenService<R> {
             .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))
     }
 
+    /// Revokes all tokens of several users, e.g. to sign out every user of a type
+    ///
+    /// # Arguments
+    ///
+    /// * `user_ids` - The users' UUIDs
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of tokens revoked
+    /// * `Err(TokenError)` - Revocation failed
+    pub async fn revoke_tokens_for_users(&self, user_ids: &[Uuid]) -> Result<usize, DomainError> {
+        self.repository
+            .revoke_tokens_batch(user_ids)
+            .await
+            .map_err(|_| DomainError::Token(TokenError::TokenGenerationFailed))
+    }
+
     /// Revokes a specific refresh token
     ///
     /// # Arguments
@@ -518,7 +880,14 @@ impl<R: TokenRepository> TokenService<R> {
             .await
             .map_err(|_| DomainError::Internal {
                 message: "Failed to blacklist token".to_string(),
-            })
+            })?;
+
+        self.publish_revocation(TokenRevocation::AccessToken {
+            jti: token_data.claims.jti,
+            expires_at,
+        })
+        .await;
+        Ok(())
     }
     
     /// Revokes all tokens for a specific device
@@ -559,7 +928,159 @@ impl<R: TokenRepository> TokenService<R> {
         Ok(revoked_count)
     }
     
-    /// Cleans up expired tokens and blacklist entries
+    /// Checks whether a user has signed in from a device before
+    ///
+    /// Revoked and expired tokens still count, as long as they are stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - No stored token of the user records a device, so
+    ///   there is nothing to compare against
+    /// * `Ok(Some(bool))` - Whether the fingerprint is among the recorded devices
+    pub async fn is_known_device(
+        &self,
+        user_id: Uuid,
+        device_fingerprint: &str,
+    ) -> Result<Option<bool>, DomainError> {
+        let tokens = self.repository
+            .find_by_user_id(user_id)
+            .await
+            .map_err(|_| DomainError::Internal {
+                message: "Failed to find user tokens".to_string(),
+            })?;
+
+        let mut devices = tokens.iter().filter_map(|token| token.device_fingerprint.as_deref()).peekable();
+        if devices.peek().is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(devices.any(|fp| fp == device_fingerprint)))
+    }
+
+    /// Checks whether an access token is blacklisted or belongs to a revoked session
+    ///
+    /// Blacklist lookups are cached in-process; a failing lookup lets the
+    /// token through and is not cached.
+    async fn is_revoked(&self, claims: &Claims) -> bool {
+        if let Some(ref family) = claims.token_family {
+            if self.revocations.is_family_revoked(family) {
+                return true;
+            }
+        }
+
+        if let Some(revoked) = self.revocations.is_token_revoked(&claims.jti) {
+            return revoked;
+        }
+
+        match self.repository.is_token_blacklisted(&claims.jti).await {
+            Ok(revoked) => {
+                self.revocations.record_check(&claims.jti, revoked);
+                revoked
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Revokes all refresh tokens of a family and announces the revocation
+    async fn revoke_token_family(&self, token_family: &str) -> Result<usize, DomainError> {
+        let revoked = self.repository.revoke_token_family(token_family).await?;
+
+        self.publish_revocation(TokenRevocation::TokenFamily {
+            token_family: token_family.to_string(),
+        })
+        .await;
+        Ok(revoked)
+    }
+
+    /// Applies a revocation locally and broadcasts it to other instances
+    async fn publish_revocation(&self, revocation: TokenRevocation) {
+        self.revocations.apply(&revocation);
+
+        if let Some(ref broadcaster) = self.revocation_broadcaster {
+            if let Err(e) = broadcaster.broadcast(&revocation).await {
+                warn!(error = %e, "Failed to broadcast token revocation");
+            }
+        }
+    }
+
+    /// Lists the signed-in sessions of a user, most recently active first
+    ///
+    /// Only sessions with a refresh token that is neither revoked nor
+    /// expired are listed, so sessions ended by a logout or a detected
+    /// token reuse disappear even if their record is still stored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<SessionInfo>)` - The active sessions; empty without a session store
+    /// * `Err(DomainError::Internal)` - The sessions could not be loaded
+    pub async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<SessionInfo>, DomainError> {
+        let Some(ref store) = self.sessions else {
+            return Ok(Vec::new());
+        };
+
+        let sessions = store
+            .list_sessions(user_id)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to load sessions: {}", e),
+            })?;
+        let tokens = self.repository
+            .find_by_user_id(user_id)
+            .await
+            .map_err(|_| DomainError::Internal {
+                message: "Failed to find user tokens".to_string(),
+            })?;
+
+        let mut active: Vec<SessionInfo> = sessions
+            .into_iter()
+            .filter(|session| {
+                tokens.iter().any(|token| {
+                    token.is_valid() && token.token_family.as_deref() == Some(session.token_family.as_str())
+                })
+            })
+            .collect();
+        active.sort_by_key(|s| std::cmp::Reverse(s.last_activity_at));
+        Ok(active)
+    }
+
+    /// Revokes a session of a user, identified by its token family
+    ///
+    /// All refresh tokens of the family are revoked and its activity is
+    /// cleared. Access tokens issued to the session are rejected from then
+    /// on by every instance the revocation reaches.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The session was revoked
+    /// * `Ok(false)` - The user has no session with this ID
+    pub async fn revoke_session(&self, user_id: Uuid, token_family: &str) -> Result<bool, DomainError> {
+        let tokens = self.repository
+            .find_by_token_family(token_family)
+            .await
+            .map_err(|_| DomainError::Internal {
+                message: "Failed to find session tokens".to_string(),
+            })?;
+
+        if tokens.is_empty() || tokens.iter().any(|token| token.user_id != user_id) {
+            return Ok(false);
+        }
+
+        self.revoke_token_family(token_family)
+            .await
+            .map_err(|_| DomainError::Internal {
+                message: "Failed to revoke session tokens".to_string(),
+            })?;
+
+        if let Some(ref store) = self.session_activity {
+            if let Err(e) = store.clear(token_family).await {
+                warn!(error = %e, "Failed to clear session activity");
+            }
+        }
+
+        info!(token_family = %token_family, "Session revoked");
+        Ok(true)
+    }
+
     ///
     /// # Returns
     ///
@@ -0,0 +1,34 @@
+//! Session activity tracking for idle timeouts
+//!
+//! A session is a refresh-token family: every rotation keeps the family ID,
+//! so the family's last activity is the last time any of its tokens was
+//! issued or refreshed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Trait for storing the last activity of each session
+#[async_trait]
+pub trait SessionActivityStoreTrait: Send + Sync {
+    /// Record activity on a session
+    ///
+    /// # Arguments
+    /// * `token_family` - Refresh-token family ID
+    /// * `at` - Time of the activity
+    /// * `ttl_seconds` - How long to keep the record; at least the absolute session lifetime
+    async fn record_activity(
+        &self,
+        token_family: &str,
+        at: DateTime<Utc>,
+        ttl_seconds: u64,
+    ) -> Result<(), String>;
+
+    /// Get the last recorded activity of a session
+    ///
+    /// # Returns
+    /// * `Ok(None)` - No activity recorded, e.g. for sessions started before tracking was enabled
+    async fn last_activity(&self, token_family: &str) -> Result<Option<DateTime<Utc>>, String>;
+
+    /// Forget a session's activity
+    async fn clear(&self, token_family: &str) -> Result<(), String>;
+}
@@ -0,0 +1,65 @@
+//! Session records for device management
+//!
+//! A session is a refresh-token family. Besides its last activity, used
+//! for idle timeouts, the store keeps the device, network and location a
+//! session was started from, so users can review their signed-in devices
+//! and sign them out one at a time.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::session_activity::SessionActivityStoreTrait;
+
+/// Where a session was started from, as known when signing in
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetadata {
+    /// User agent of the signing-in client
+    pub user_agent: Option<String>,
+    /// IP address of the signing-in client
+    pub ip_address: Option<String>,
+    /// Country the IP address resolved to, if known
+    pub country: Option<String>,
+}
+
+/// A signed-in session of a user
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    /// Refresh-token family ID, also used as the session ID
+    pub token_family: String,
+    /// The signed-in user
+    pub user_id: Uuid,
+    /// Hash of the device fingerprint the session is bound to
+    pub device_fingerprint_hash: Option<String>,
+    /// Device type and OS derived from the user agent
+    pub device: Option<String>,
+    /// IP address the session was started from
+    pub ip_address: Option<String>,
+    /// Country the IP address resolved to, if known
+    pub country: Option<String>,
+    /// When the session was started
+    pub created_at: DateTime<Utc>,
+    /// When a token of the session was last issued or refreshed
+    pub last_activity_at: DateTime<Utc>,
+}
+
+/// Trait for storing sessions with their device metadata
+///
+/// Activity recorded through [`SessionActivityStoreTrait`] updates the
+/// session's `last_activity_at`, and clearing a session's activity removes
+/// the session.
+#[async_trait]
+pub trait SessionStoreTrait: SessionActivityStoreTrait {
+    /// Save a session, replacing any previous record of its family
+    ///
+    /// # Arguments
+    /// * `session` - The session to save
+    /// * `ttl_seconds` - How long to keep the record; at least the absolute session lifetime
+    async fn save_session(&self, session: &SessionInfo, ttl_seconds: u64) -> Result<(), String>;
+
+    /// Get a session by its refresh-token family ID
+    async fn find_session(&self, token_family: &str) -> Result<Option<SessionInfo>, String>;
+
+    /// List the recorded sessions of a user, in no particular order
+    async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<SessionInfo>, String>;
+}
@@ -151,11 +151,13 @@ async fn test_rs256_token_generation() {
     // Create token service with RS256
     let repository = MockTokenRepository::new();
     let config = TokenServiceConfig {
-        jwt_secret: "not-used-for-rs256".to_string(),
+        jwt_secret: "not-used-for-rs256".into(),
         algorithm: Algorithm::RS256,
         access_token_expiry_minutes: 15,
         refresh_token_expiry_days: 7,
         rs256_config: None, // Not needed when using with_rs256_keys
+        session_idle_timeout_minutes: None,
+        revocation_cache_ttl_seconds: 30,
     };
 
     let service = TokenService::with_rs256_keys(repository, config, key_manager);
@@ -184,11 +186,13 @@ async fn test_rs256_token_verification() {
     // Create token service with RS256
     let repository = MockTokenRepository::new();
     let config = TokenServiceConfig {
-        jwt_secret: "not-used-for-rs256".to_string(),
+        jwt_secret: "not-used-for-rs256".into(),
         algorithm: Algorithm::RS256,
         access_token_expiry_minutes: 15,
         refresh_token_expiry_days: 7,
         rs256_config: None,
+        session_idle_timeout_minutes: None,
+        revocation_cache_ttl_seconds: 30,
     };
 
     let service = TokenService::with_rs256_keys(repository, config, key_manager);
@@ -223,11 +227,13 @@ async fn test_rs256_invalid_token_rejection() {
     // Create token service with RS256
     let repository = MockTokenRepository::new();
     let config = TokenServiceConfig {
-        jwt_secret: "not-used-for-rs256".to_string(),
+        jwt_secret: "not-used-for-rs256".into(),
         algorithm: Algorithm::RS256,
         access_token_expiry_minutes: 15,
         refresh_token_expiry_days: 7,
         rs256_config: None,
+        session_idle_timeout_minutes: None,
+        revocation_cache_ttl_seconds: 30,
     };
 
     let service = TokenService::with_rs256_keys(repository, config, key_manager);
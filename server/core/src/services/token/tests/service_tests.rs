@@ -2,6 +2,7 @@
 
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::{Duration, Utc};
 use async_trait::async_trait;
@@ -11,7 +12,13 @@ use crate::domain::entities::token::{Claims, RefreshToken};
 use crate::domain::entities::user::UserType;
 use crate::errors::{DomainError, TokenError};
 use crate::repositories::TokenRepository;
-use crate::services::token::{TokenService, TokenServiceConfig};
+use crate::services::lock::tests::service_tests::MockLockStore;
+use crate::services::lock::{DistributedLock, DistributedLockConfig, DistributedLockTrait};
+use crate::services::tenant::{self, TenantId};
+use crate::services::token::{
+    SessionActivityStoreTrait, SessionInfo, SessionMetadata, SessionStoreTrait, TokenRevocation,
+    TokenRevocationBroadcasterTrait, TokenService, TokenServiceConfig,
+};
 
 /// Mock implementation of TokenRepository for testing
 struct MockTokenRepository {
@@ -191,6 +198,23 @@ async fn test_verify_access_token() {
     assert!(!claims.is_verified);
 }
 
+#[tokio::test]
+async fn test_access_token_names_the_issuing_tenant() {
+    let service = create_test_service();
+    let user_id = Uuid::new_v4();
+
+    let default_pair = service.generate_tokens(user_id, None, false, None, None).await.unwrap();
+    let claims = service.verify_access_token(&default_pair.access_token).await.unwrap();
+    assert_eq!(claims.tenant, None);
+
+    let tenant = TenantId::parse("buildco").unwrap();
+    let tenant_pair = tenant::scope(tenant, service.generate_tokens(user_id, None, false, None, None))
+        .await
+        .unwrap();
+    let claims = service.verify_access_token(&tenant_pair.access_token).await.unwrap();
+    assert_eq!(claims.tenant, Some("buildco".to_string()));
+}
+
 #[tokio::test]
 async fn test_verify_invalid_access_token() {
     let service = create_test_service();
@@ -268,6 +292,27 @@ async fn test_revoke_tokens() {
     assert_eq!(count, 0);
 }
 
+#[tokio::test]
+async fn test_revoke_tokens_for_users() {
+    let service = create_test_service();
+    let users: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+    for user_id in &users {
+        for _ in 0..2 {
+            service
+                .generate_tokens(*user_id, None, false, None, None)
+                .await
+                .unwrap();
+        }
+    }
+
+    let revoked = service.revoke_tokens_for_users(&users[..2]).await.unwrap();
+
+    assert_eq!(revoked, 4);
+    assert_eq!(service.repository.count_user_tokens(users[0]).await.unwrap(), 0);
+    assert_eq!(service.repository.count_user_tokens(users[1]).await.unwrap(), 0);
+    assert_eq!(service.repository.count_user_tokens(users[2]).await.unwrap(), 2);
+}
+
 #[tokio::test]
 async fn test_revoke_specific_refresh_token() {
     let service = create_test_service();
@@ -475,3 +520,396 @@ async fn test_blacklist_cleanup() {
     let is_blacklisted = repository.is_token_blacklisted(valid_jti).await.unwrap();
     assert!(is_blacklisted);
 }
+
+/// In-memory session activity store
+#[derive(Default)]
+struct MockSessionActivityStore {
+    activity: Mutex<HashMap<String, chrono::DateTime<chrono::Utc>>>,
+}
+
+#[async_trait]
+impl SessionActivityStoreTrait for MockSessionActivityStore {
+    async fn record_activity(
+        &self,
+        token_family: &str,
+        at: chrono::DateTime<chrono::Utc>,
+        _ttl_seconds: u64,
+    ) -> Result<(), String> {
+        self.activity.lock().unwrap().insert(token_family.to_string(), at);
+        Ok(())
+    }
+
+    async fn last_activity(&self, token_family: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+        Ok(self.activity.lock().unwrap().get(token_family).copied())
+    }
+
+    async fn clear(&self, token_family: &str) -> Result<(), String> {
+        self.activity.lock().unwrap().remove(token_family);
+        Ok(())
+    }
+}
+
+fn create_idle_timeout_service(store: Arc<MockSessionActivityStore>) -> TokenService<MockTokenRepository> {
+    let mut config = TokenServiceConfig::default();
+    config.algorithm = Algorithm::HS256;
+    config.rs256_config = None;
+    config.session_idle_timeout_minutes = Some(60);
+    TokenService::new(MockTokenRepository::new(), config)
+        .expect("Failed to create token service")
+        .with_session_activity_store(store)
+}
+
+#[tokio::test]
+async fn test_refresh_within_idle_window_extends_session() {
+    let store = Arc::new(MockSessionActivityStore::default());
+    let service = create_idle_timeout_service(store.clone());
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    let family = token_pair.token_family.clone().unwrap();
+    let issued_activity = store.last_activity(&family).await.unwrap().unwrap();
+
+    store.activity.lock().unwrap().insert(family.clone(), Utc::now() - Duration::minutes(59));
+    let refreshed = service
+        .refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(refreshed.token_family, Some(family.clone()));
+    assert!(store.last_activity(&family).await.unwrap().unwrap() >= issued_activity);
+}
+
+#[tokio::test]
+async fn test_refresh_after_idle_window_is_rejected() {
+    let store = Arc::new(MockSessionActivityStore::default());
+    let service = create_idle_timeout_service(store.clone());
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Worker), true, None, None)
+        .await
+        .unwrap();
+    let family = token_pair.token_family.clone().unwrap();
+
+    store.activity.lock().unwrap().insert(family.clone(), Utc::now() - Duration::minutes(61));
+    let result = service
+        .refresh_tokens(&token_pair.refresh_token, Some(UserType::Worker), true, None, None)
+        .await;
+
+    assert!(matches!(result, Err(DomainError::Token(TokenError::SessionIdleTimeout))));
+    // The whole session is revoked, not just this token
+    let family_tokens = service.repository.find_by_token_family(&family).await.unwrap();
+    assert!(family_tokens.iter().all(|t| t.is_revoked));
+    assert!(store.last_activity(&family).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_session_without_recorded_activity_can_refresh() {
+    let store = Arc::new(MockSessionActivityStore::default());
+    let service = create_idle_timeout_service(store.clone());
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    store.activity.lock().unwrap().clear();
+
+    let result = service
+        .refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None)
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_access_token_carries_hashed_device_fingerprint() {
+    let service = create_test_service();
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, Some("device_abc".to_string()))
+        .await
+        .unwrap();
+    let claims = service.verify_access_token(&token_pair.access_token).await.unwrap();
+
+    assert_ne!(claims.device_fingerprint.as_deref(), Some("device_abc"));
+    assert!(claims.is_bound_to_device(Some("device_abc")));
+    assert!(!claims.is_bound_to_device(Some("device_xyz")));
+}
+
+#[tokio::test]
+async fn test_refresh_keeps_access_token_bound_to_device() {
+    let service = create_test_service();
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, Some("device_abc".to_string()))
+        .await
+        .unwrap();
+    let refreshed = service
+        .refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    let claims = service.verify_access_token(&refreshed.access_token).await.unwrap();
+
+    assert!(claims.is_bound_to_device(Some("device_abc")));
+    assert!(!claims.is_bound_to_device(Some("device_xyz")));
+}
+
+/// Broadcaster recording every revocation it is asked to announce
+#[derive(Default)]
+struct MockRevocationBroadcaster {
+    broadcasts: Mutex<Vec<TokenRevocation>>,
+}
+
+#[async_trait]
+impl TokenRevocationBroadcasterTrait for MockRevocationBroadcaster {
+    async fn broadcast(&self, revocation: &TokenRevocation) -> Result<(), String> {
+        self.broadcasts.lock().unwrap().push(revocation.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_blacklisted_token_rejected_and_broadcast() {
+    let broadcaster = Arc::new(MockRevocationBroadcaster::default());
+    let service = create_test_service().with_revocation_broadcaster(broadcaster.clone());
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    // Caches the token as not blacklisted
+    let claims = service.verify_access_token(&token_pair.access_token).await.unwrap();
+
+    service.blacklist_access_token(&token_pair.access_token).await.unwrap();
+
+    let result = service.verify_access_token(&token_pair.access_token).await;
+    assert!(matches!(result, Err(DomainError::Token(TokenError::TokenRevoked))));
+    assert!(matches!(
+        broadcaster.broadcasts.lock().unwrap().as_slice(),
+        [TokenRevocation::AccessToken { jti, .. }] if *jti == claims.jti
+    ));
+}
+
+#[tokio::test]
+async fn test_revoked_session_rejects_its_access_tokens() {
+    let broadcaster = Arc::new(MockRevocationBroadcaster::default());
+    let service = create_test_service().with_revocation_broadcaster(broadcaster.clone());
+    let user_id = Uuid::new_v4();
+
+    let session = service
+        .generate_tokens(user_id, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    let other_session = service
+        .generate_tokens(user_id, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    let family = session.token_family.clone().unwrap();
+
+    assert!(service.revoke_session(user_id, &family).await.unwrap());
+
+    let result = service.verify_access_token(&session.access_token).await;
+    assert!(matches!(result, Err(DomainError::Token(TokenError::TokenRevoked))));
+    assert!(service.verify_access_token(&other_session.access_token).await.is_ok());
+    assert_eq!(
+        broadcaster.broadcasts.lock().unwrap().as_slice(),
+        [TokenRevocation::TokenFamily { token_family: family }]
+    );
+}
+
+#[tokio::test]
+async fn test_revocation_from_other_instance_overrides_cached_check() {
+    let service = create_test_service();
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Worker), true, None, None)
+        .await
+        .unwrap();
+    let claims = service.verify_access_token(&token_pair.access_token).await.unwrap();
+
+    // Another instance blacklisted the token and announced it
+    service.revocation_cache().apply(&TokenRevocation::AccessToken {
+        jti: claims.jti.clone(),
+        expires_at: Utc::now() + Duration::minutes(15),
+    });
+
+    let result = service.verify_access_token(&token_pair.access_token).await;
+    assert!(matches!(result, Err(DomainError::Token(TokenError::TokenRevoked))));
+}
+
+fn create_locked_service(store: Arc<MockLockStore>) -> TokenService<MockTokenRepository> {
+    let config = DistributedLockConfig {
+        wait_timeout: std::time::Duration::from_millis(50),
+        retry_interval: std::time::Duration::from_millis(5),
+        ..DistributedLockConfig::default()
+    };
+    create_test_service().with_rotation_lock(DistributedLock::new(store, config))
+}
+
+#[tokio::test]
+async fn test_rotation_holds_the_session_lock() {
+    let store = Arc::new(MockLockStore::default());
+    let service = create_locked_service(store.clone());
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    let lock_key = format!("token:rotation:{}", token_pair.token_family.clone().unwrap());
+
+    let refreshed = service
+        .refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+    assert_eq!(refreshed.token_family, token_pair.token_family);
+    assert!(!store.is_held(&lock_key));
+
+    // A rotation of the session running on another instance
+    let token = store.try_acquire(&lock_key, std::time::Duration::from_secs(5)).await.unwrap();
+    assert!(token.is_some());
+
+    let result = service
+        .refresh_tokens(&refreshed.refresh_token, Some(UserType::Customer), true, None, None)
+        .await;
+    assert!(matches!(result, Err(DomainError::Conflict { .. })));
+
+    // The waiting refresh did not rotate the token
+    store.release(&lock_key, &token.unwrap()).await.unwrap();
+    assert!(service
+        .refresh_tokens(&refreshed.refresh_token, Some(UserType::Customer), true, None, None)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_second_rotation_of_a_token_is_rejected_under_lock() {
+    let service = create_locked_service(Arc::new(MockLockStore::default()));
+
+    let token_pair = service
+        .generate_tokens(Uuid::new_v4(), Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+
+    let (first, second) = tokio::join!(
+        service.refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None),
+        service.refresh_tokens(&token_pair.refresh_token, Some(UserType::Customer), true, None, None),
+    );
+
+    assert!(first.is_ok() != second.is_ok());
+}
+
+/// In-memory session store
+#[derive(Default)]
+struct MockSessionStore {
+    sessions: Mutex<HashMap<String, SessionInfo>>,
+}
+
+#[async_trait]
+impl SessionActivityStoreTrait for MockSessionStore {
+    async fn record_activity(
+        &self,
+        token_family: &str,
+        at: chrono::DateTime<chrono::Utc>,
+        _ttl_seconds: u64,
+    ) -> Result<(), String> {
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(token_family) {
+            session.last_activity_at = at;
+        }
+        Ok(())
+    }
+
+    async fn last_activity(&self, token_family: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+        Ok(self.sessions.lock().unwrap().get(token_family).map(|session| session.last_activity_at))
+    }
+
+    async fn clear(&self, token_family: &str) -> Result<(), String> {
+        self.sessions.lock().unwrap().remove(token_family);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SessionStoreTrait for MockSessionStore {
+    async fn save_session(&self, session: &SessionInfo, _ttl_seconds: u64) -> Result<(), String> {
+        self.sessions.lock().unwrap().insert(session.token_family.clone(), session.clone());
+        Ok(())
+    }
+
+    async fn find_session(&self, token_family: &str) -> Result<Option<SessionInfo>, String> {
+        Ok(self.sessions.lock().unwrap().get(token_family).cloned())
+    }
+
+    async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<SessionInfo>, String> {
+        let sessions = self.sessions.lock().unwrap();
+        Ok(sessions.values().filter(|session| session.user_id == user_id).cloned().collect())
+    }
+}
+
+/// Signs a user in from an iPhone, recording the session's metadata
+async fn sign_in(service: &TokenService<MockTokenRepository>, user_id: Uuid) -> crate::domain::entities::token::TokenPair {
+    let token_pair = service
+        .generate_tokens(user_id, Some(UserType::Customer), true, None, Some("device_abc".to_string()))
+        .await
+        .unwrap();
+    let metadata = SessionMetadata {
+        user_agent: Some("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X)".to_string()),
+        ip_address: Some("203.0.113.7".to_string()),
+        country: Some("AU".to_string()),
+    };
+    service.record_session(user_id, &token_pair, metadata).await;
+    token_pair
+}
+
+#[tokio::test]
+async fn test_sessions_list_devices_most_recent_first() {
+    let store = Arc::new(MockSessionStore::default());
+    let service = create_test_service().with_session_store(store.clone());
+    let user_id = Uuid::new_v4();
+
+    let first = sign_in(&service, user_id).await;
+    let second = sign_in(&service, user_id).await;
+    sign_in(&service, Uuid::new_v4()).await;
+    service
+        .refresh_tokens(&first.refresh_token, Some(UserType::Customer), true, None, None)
+        .await
+        .unwrap();
+
+    let sessions = service.list_sessions(user_id).await.unwrap();
+    let families: Vec<_> = sessions.iter().map(|session| Some(session.token_family.clone())).collect();
+    assert_eq!(families, vec![first.token_family, second.token_family]);
+    assert_eq!(sessions[0].device.as_deref(), Some("Mobile/iOS"));
+    assert_eq!(sessions[0].ip_address.as_deref(), Some("203.0.113.7"));
+    assert_eq!(sessions[0].country.as_deref(), Some("AU"));
+    assert!(sessions[0].last_activity_at > sessions[0].created_at);
+    assert_ne!(sessions[0].device_fingerprint_hash.as_deref(), Some("device_abc"));
+}
+
+#[tokio::test]
+async fn test_ended_sessions_are_not_listed() {
+    let store = Arc::new(MockSessionStore::default());
+    let service = create_test_service().with_session_store(store.clone());
+    let user_id = Uuid::new_v4();
+
+    let revoked = sign_in(&service, user_id).await;
+    let family = revoked.token_family.clone().unwrap();
+    sign_in(&service, user_id).await;
+
+    assert!(service.revoke_session(user_id, &family).await.unwrap());
+    assert!(store.find_session(&family).await.unwrap().is_none());
+    assert_eq!(service.list_sessions(user_id).await.unwrap().len(), 1);
+
+    // Signed out everywhere; records left behind are not listed
+    service.revoke_tokens(user_id).await.unwrap();
+    assert!(service.list_sessions(user_id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sessions_without_store_are_not_listed() {
+    let service = create_test_service();
+    let user_id = Uuid::new_v4();
+
+    sign_in(&service, user_id).await;
+
+    assert!(service.list_sessions(user_id).await.unwrap().is_empty());
+}
@@ -18,6 +18,7 @@ struct MockTokenRepository {
     revoke_token_family_response: usize,
     is_token_blacklisted_response: bool,
     delete_expired_tokens_response: usize,
+    archive_expired_tokens_response: Option<usize>,
     cleanup_blacklist_response: usize,
 }
 
@@ -30,6 +31,7 @@ impl MockTokenRepository {
             revoke_token_family_response: 0,
             is_token_blacklisted_response: false,
             delete_expired_tokens_response: 0,
+            archive_expired_tokens_response: None,
             cleanup_blacklist_response: 0,
         }
     }
@@ -59,6 +61,11 @@ impl MockTokenRepository {
         self.cleanup_blacklist_response = blacklist;
         self
     }
+
+    fn with_archive_response(mut self, archived: Option<usize>) -> Self {
+        self.archive_expired_tokens_response = archived;
+        self
+    }
 }
 
 #[async_trait]
@@ -107,6 +114,12 @@ impl TokenRepository for MockTokenRepository {
         Ok(self.delete_expired_tokens_response)
     }
 
+    async fn archive_expired_tokens(&self) -> Result<usize, DomainError> {
+        self.archive_expired_tokens_response.ok_or_else(|| DomainError::Internal {
+            message: "Archive table unavailable".to_string(),
+        })
+    }
+
     async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
         Ok(self.cleanup_blacklist_response)
     }
@@ -256,6 +269,10 @@ async fn test_token_blacklist_check() {
         phone_hash: None,
         device_fingerprint: None,
         token_family: None,
+        scope: None,
+        client_id: None,
+        roles: Vec::new(),
+        tenant: None,
     };
 
     let token = service.encode_jwt(&claims).unwrap();
@@ -286,6 +303,44 @@ async fn test_cleanup_expired_tokens() {
     assert_eq!(result.total_cleaned(), 23);
 }
 
+#[tokio::test]
+async fn test_cleanup_archives_expired_tokens() {
+    let mock_repo = MockTokenRepository::new()
+        .with_cleanup_responses(15, 8)
+        .with_archive_response(Some(12));
+
+    let cleanup_config = TokenCleanupConfig {
+        archive_expired_tokens: true,
+        ..TokenCleanupConfig::default()
+    };
+    let cleanup_service = TokenCleanupService::new(Arc::new(mock_repo), cleanup_config);
+
+    let result = cleanup_service.run_cleanup().await.unwrap();
+
+    assert_eq!(result.expired_tokens_deleted, 12);
+    assert_eq!(result.expired_tokens_archived, 12);
+    assert!(result.is_success());
+}
+
+#[tokio::test]
+async fn test_cleanup_keeps_tokens_when_archival_fails() {
+    let mock_repo = MockTokenRepository::new().with_cleanup_responses(15, 8);
+
+    let cleanup_config = TokenCleanupConfig {
+        archive_expired_tokens: true,
+        ..TokenCleanupConfig::default()
+    };
+    let cleanup_service = TokenCleanupService::new(Arc::new(mock_repo), cleanup_config);
+
+    let result = cleanup_service.run_cleanup().await.unwrap();
+
+    // Expired tokens are not deleted without a record
+    assert_eq!(result.expired_tokens_deleted, 0);
+    assert_eq!(result.expired_tokens_archived, 0);
+    assert_eq!(result.blacklist_entries_deleted, 8);
+    assert_eq!(result.errors.len(), 1);
+}
+
 #[tokio::test]
 async fn test_token_rotation_creates_chain() {
     let user_id = Uuid::new_v4();
@@ -0,0 +1,43 @@
+//! Task-local trace context
+//!
+//! The API wraps each request in [`scope`]; anything awaited inside it can
+//! read the trace ID with [`current_trace_id`]. Tasks started with
+//! `tokio::spawn` do not inherit the scope and must be given the trace ID
+//! explicitly.
+
+use std::future::Future;
+use uuid::Uuid;
+
+/// Maximum length of a trace ID accepted from a client
+pub const MAX_TRACE_ID_LENGTH: usize = 64;
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Run a future with the given trace ID as its trace context
+pub fn scope<F: Future>(trace_id: String, future: F) -> impl Future<Output = F::Output> {
+    TRACE_ID.scope(trace_id, future)
+}
+
+/// Trace ID of the request being served, if any
+pub fn current_trace_id() -> Option<String> {
+    TRACE_ID.try_with(|trace_id| trace_id.clone()).ok()
+}
+
+/// Generate a new trace ID
+pub fn new_trace_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Whether a client-supplied trace ID is safe to propagate
+///
+/// Trace IDs end up in provider requests and database rows, so only short
+/// identifiers made of ASCII letters, digits, `-`, `_` and `.` are accepted.
+pub fn is_valid_trace_id(trace_id: &str) -> bool {
+    !trace_id.is_empty()
+        && trace_id.len() <= MAX_TRACE_ID_LENGTH
+        && trace_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
@@ -0,0 +1,14 @@
+//! Request trace module
+//!
+//! This module handles:
+//! - Carrying the trace ID of the API request being served through the
+//!   services it calls, without threading it through every signature
+//! - Exposing the trace ID to audit rows and SMS provider calls so a request
+//!   can be followed through to the provider's delivery receipt
+
+mod context;
+
+#[cfg(test)]
+mod tests;
+
+pub use context::{current_trace_id, is_valid_trace_id, new_trace_id, scope, MAX_TRACE_ID_LENGTH};
@@ -0,0 +1,36 @@
+//! Unit tests for the task-local trace context
+
+use crate::services::trace::{current_trace_id, is_valid_trace_id, new_trace_id, scope};
+
+#[tokio::test]
+async fn test_trace_id_is_visible_inside_scope_only() {
+    assert_eq!(current_trace_id(), None);
+
+    let inner = scope("req-123".to_string(), async { current_trace_id() }).await;
+
+    assert_eq!(inner.as_deref(), Some("req-123"));
+    assert_eq!(current_trace_id(), None);
+}
+
+#[tokio::test]
+async fn test_nested_scope_overrides_trace_id() {
+    let (outer, inner) = scope("outer".to_string(), async {
+        let inner = scope("inner".to_string(), async { current_trace_id() }).await;
+        (current_trace_id(), inner)
+    })
+    .await;
+
+    assert_eq!(outer.as_deref(), Some("outer"));
+    assert_eq!(inner.as_deref(), Some("inner"));
+}
+
+#[test]
+fn test_trace_id_validation() {
+    assert!(is_valid_trace_id(&new_trace_id()));
+    assert!(is_valid_trace_id("1-67891233-abcdef.01_x"));
+
+    assert!(!is_valid_trace_id(""));
+    assert!(!is_valid_trace_id(&"a".repeat(65)));
+    assert!(!is_valid_trace_id("abc def"));
+    assert!(!is_valid_trace_id("abc\r\nX-Injected: 1"));
+}
@@ -0,0 +1,3 @@
+//! Tests for the request trace module
+
+mod context_tests;
@@ -0,0 +1,25 @@
+//! Configuration for the user import service
+
+use crate::services::auth::PhoneHasher;
+
+/// Configuration for the user import service
+#[derive(Debug, Clone)]
+pub struct UserImportConfig {
+    /// Users created per transaction, and records handled between checkpoints
+    pub batch_size: usize,
+    /// Markets users may be imported into, by dialing code (e.g. `+61`)
+    pub markets: Vec<String>,
+    /// Hasher for stored phone numbers; must match the auth service's, or
+    /// imported users could not sign in
+    pub phone_hasher: PhoneHasher,
+}
+
+impl Default for UserImportConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 500,
+            markets: vec!["+86".to_string(), "+61".to_string()],
+            phone_hasher: PhoneHasher::default(),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Reading user exports from the legacy system
+
+use serde::Deserialize;
+
+use crate::errors::{DomainError, DomainResult};
+
+/// File format of a legacy export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row naming the columns
+    Csv,
+    /// An array of objects
+    Json,
+}
+
+impl ExportFormat {
+    /// Detect the format from a file name's extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A user as exported by the legacy system
+///
+/// Only `phone` is required; in CSV exports empty cells count as missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct LegacyUserRecord {
+    /// Phone number, in E.164 or the local format of `country`
+    pub phone: String,
+    /// Country of the user, as ISO 3166-1 alpha-2 code or dialing code;
+    /// needed for phone numbers in local format
+    #[serde(default)]
+    pub country: Option<String>,
+    /// `customer` or `worker`, if the user had chosen
+    #[serde(default)]
+    pub user_type: Option<String>,
+    /// When the user registered, in RFC 3339 or `YYYY-MM-DD HH:MM:SS` (UTC)
+    #[serde(default)]
+    pub registered_at: Option<String>,
+    /// Whether the user was blocked in the legacy system
+    #[serde(default)]
+    pub blocked: Option<bool>,
+}
+
+/// Read every record of an export, in file order
+///
+/// # Returns
+/// * `Ok(Vec<LegacyUserRecord>)` - The records
+/// * `Err(DomainError::Validation)` - The export is malformed; the message
+///   names the offending record
+pub fn parse_export(format: ExportFormat, content: &[u8]) -> DomainResult<Vec<LegacyUserRecord>> {
+    match format {
+        ExportFormat::Csv => csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(content)
+            .deserialize()
+            .enumerate()
+            .map(|(index, record)| {
+                record.map_err(|e| DomainError::Validation {
+                    message: format!("Invalid CSV export at record {}: {}", index + 1, e),
+                })
+            })
+            .collect(),
+        ExportFormat::Json => serde_json::from_slice(content).map_err(|e| DomainError::Validation {
+            message: format!("Invalid JSON export: {}", e),
+        }),
+    }
+}
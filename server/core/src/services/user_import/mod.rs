@@ -0,0 +1,23 @@
+//! User import module for migrating users from the legacy system
+//!
+//! This module handles:
+//! - Reading legacy exports in CSV or JSON
+//! - Normalizing phone numbers to E.164 and hashing them like the auth service does
+//! - Assigning each user the market of their phone number's dialing code
+//! - Detecting duplicates within the export and against registered users
+//! - Dry runs producing a validation report without writing anything
+//! - Creating users in batches, saving a checkpoint after each batch so a
+//!   failed import resumes where it stopped
+
+mod config;
+mod export;
+mod service;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::UserImportConfig;
+pub use export::{parse_export, ExportFormat, LegacyUserRecord};
+pub use service::{
+    ImportedUser, UserImportIssue, UserImportIssueKind, UserImportReport, UserImportService,
+};
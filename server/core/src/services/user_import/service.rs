@@ -0,0 +1,428 @@
+//! User import service creating users from legacy exports
+//!
+//! Every record is validated before anything is written, so duplicates are
+//! judged against the whole export in file order and a resumed import skips
+//! exactly the records an uninterrupted one would have. Users are then
+//! created a batch at a time; the checkpoint saved after each batch lets a
+//! failed import resume after the last committed one. Records of a batch
+//! that failed are checked against registered users again on resume.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::domain::entities::user::{User, UserType};
+use crate::domain::entities::user_import::UserImportCheckpoint;
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::{UserImportRepository, UserRepository};
+use crate::services::auth::{
+    extract_country_code, mask_phone, normalize_to_e164, validate_phone_with_country, CountryCode,
+};
+
+use super::config::UserImportConfig;
+use super::export::LegacyUserRecord;
+
+/// Why a record was not imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserImportIssueKind {
+    /// The phone number is missing or not valid for its country
+    InvalidPhone,
+    /// The phone number belongs to a market users are not imported into
+    UnsupportedMarket,
+    /// The user type is neither `customer` nor `worker`
+    InvalidUserType,
+    /// The registration time cannot be read or is in the future
+    InvalidDate,
+    /// An earlier record of the export has the same phone number
+    DuplicateInExport,
+    /// A user with the phone number is already registered
+    AlreadyRegistered,
+}
+
+impl UserImportIssueKind {
+    /// Convert to string representation for responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidPhone => "invalid_phone",
+            Self::UnsupportedMarket => "unsupported_market",
+            Self::InvalidUserType => "invalid_user_type",
+            Self::InvalidDate => "invalid_date",
+            Self::DuplicateInExport => "duplicate_in_export",
+            Self::AlreadyRegistered => "already_registered",
+        }
+    }
+}
+
+/// A record that was not imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserImportIssue {
+    /// Position of the record in the export, starting at 1
+    pub record: u64,
+    /// Why the record was not imported
+    pub kind: UserImportIssueKind,
+    /// Details for the operator; phone numbers are masked
+    pub message: String,
+}
+
+/// A user created from a record, or that a dry run would create
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportedUser {
+    /// Position of the record in the export, starting at 1
+    pub record: u64,
+    /// ID of the user; a dry run's IDs are not stored
+    pub user_id: Uuid,
+    /// Market of the user, by dialing code
+    pub country_code: String,
+}
+
+/// Outcome of an import or a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UserImportReport {
+    /// Whether nothing was written
+    pub dry_run: bool,
+    /// Records in the export
+    pub total_records: u64,
+    /// Records already handled by an earlier run of the same import
+    pub resumed_from: u64,
+    /// Users created, or that a dry run would create
+    pub imported: u64,
+    /// Records skipped because the user is already registered
+    pub already_registered: u64,
+    /// Records skipped because an earlier record has the same phone number
+    pub duplicates: u64,
+    /// Records skipped because they are invalid
+    pub invalid: u64,
+    /// Users created, or to create, per market
+    pub markets: BTreeMap<String, u64>,
+    /// Users created, or to create, in export order
+    pub users: Vec<ImportedUser>,
+    /// Records not imported, in export order
+    pub issues: Vec<UserImportIssue>,
+}
+
+impl UserImportReport {
+    fn add(&mut self, user: ImportedUser) {
+        self.imported += 1;
+        *self.markets.entry(user.country_code.clone()).or_insert(0) += 1;
+        self.users.push(user);
+    }
+
+    fn skip(&mut self, issue: UserImportIssue) {
+        match issue.kind {
+            UserImportIssueKind::AlreadyRegistered => self.already_registered += 1,
+            UserImportIssueKind::DuplicateInExport => self.duplicates += 1,
+            _ => self.invalid += 1,
+        }
+        self.issues.push(issue);
+    }
+}
+
+impl ImportedUser {
+    fn new(index: usize, user: &User) -> Self {
+        Self {
+            record: index as u64 + 1,
+            user_id: user.id,
+            country_code: user.country_code.clone(),
+        }
+    }
+}
+
+/// A validated record, ready to be created unless already registered
+struct PreparedUser {
+    /// Phone number without the dialing code, for lookups under every hash
+    local_phone: String,
+    user: User,
+}
+
+/// Service importing users from legacy exports
+pub struct UserImportService<U, C>
+where
+    U: UserRepository + 'static,
+    C: UserImportRepository + 'static,
+{
+    users: Arc<U>,
+    checkpoints: Arc<C>,
+    config: UserImportConfig,
+}
+
+impl<U, C> UserImportService<U, C>
+where
+    U: UserRepository + 'static,
+    C: UserImportRepository + 'static,
+{
+    /// Create a new user import service
+    pub fn new(users: Arc<U>, checkpoints: Arc<C>, config: UserImportConfig) -> Self {
+        Self {
+            users,
+            checkpoints,
+            config,
+        }
+    }
+
+    /// Identify the import of an export by the SHA-256 of its content
+    pub fn import_id(export: &[u8]) -> String {
+        hex::encode(Sha256::digest(export))
+    }
+
+    /// Validate an export without writing anything
+    ///
+    /// # Returns
+    /// * `Ok(UserImportReport)` - What an import of the export would do
+    pub async fn dry_run(&self, records: &[LegacyUserRecord]) -> DomainResult<UserImportReport> {
+        let mut report = UserImportReport {
+            dry_run: true,
+            total_records: records.len() as u64,
+            ..UserImportReport::default()
+        };
+
+        for (index, prepared) in self.prepare(records).into_iter().enumerate() {
+            if let Some(user) = self.check_registered(index, prepared, &mut report).await? {
+                report.add(ImportedUser::new(index, &user));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Import an export, resuming after the last checkpoint of the import
+    ///
+    /// # Arguments
+    /// * `import_id` - Identifier of the import, see [`Self::import_id`]
+    /// * `records` - Every record of the export, in file order
+    ///
+    /// # Returns
+    /// * `Ok(UserImportReport)` - What this run did
+    /// * `Err(DomainError::BusinessRule)` - The import already completed, or
+    ///   its checkpoint is past the end of the export
+    pub async fn import(
+        &self,
+        import_id: &str,
+        records: &[LegacyUserRecord],
+    ) -> DomainResult<UserImportReport> {
+        let mut checkpoint = self
+            .checkpoints
+            .find_checkpoint(import_id)
+            .await?
+            .unwrap_or_else(|| UserImportCheckpoint::new(import_id.to_string()));
+
+        let total = records.len() as u64;
+        if checkpoint.completed {
+            return Err(DomainError::BusinessRule {
+                message: format!("Import {} already completed", import_id),
+            });
+        }
+        if checkpoint.records_processed > total {
+            return Err(DomainError::BusinessRule {
+                message: format!(
+                    "Import {} has handled {} records but the export has only {}",
+                    import_id, checkpoint.records_processed, total
+                ),
+            });
+        }
+
+        let start = checkpoint.records_processed as usize;
+        let mut report = UserImportReport {
+            dry_run: false,
+            total_records: total,
+            resumed_from: checkpoint.records_processed,
+            ..UserImportReport::default()
+        };
+        if start > 0 {
+            info!(import_id, resumed_from = start, total, "Resuming user import");
+        }
+
+        let mut prepared = self.prepare(records).into_iter().enumerate().skip(start).peekable();
+        while prepared.peek().is_some() {
+            let mut batch = Vec::new();
+            let mut imported = Vec::new();
+            let mut batch_records = 0;
+            for (index, user) in prepared.by_ref().take(self.config.batch_size.max(1)) {
+                batch_records += 1;
+                if let Some(user) = self.check_registered(index, user, &mut report).await? {
+                    imported.push(ImportedUser::new(index, &user));
+                    batch.push(user);
+                }
+            }
+
+            // Creating a batch is all or nothing, so every user of it was created
+            let created = self.users.create_many(batch).await? as u64;
+            for user in imported {
+                report.add(user);
+            }
+
+            checkpoint.records_processed += batch_records;
+            checkpoint.imported += created;
+            checkpoint.skipped += batch_records - created;
+            checkpoint.completed = checkpoint.records_processed == total;
+            checkpoint.updated_at = Utc::now();
+            self.checkpoints.save_checkpoint(&checkpoint).await?;
+
+            info!(
+                import_id,
+                processed = checkpoint.records_processed,
+                total,
+                imported = checkpoint.imported,
+                "Committed user import batch"
+            );
+        }
+
+        if !checkpoint.completed {
+            // Nothing was left to handle, e.g. an empty export
+            checkpoint.completed = true;
+            checkpoint.updated_at = Utc::now();
+            self.checkpoints.save_checkpoint(&checkpoint).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Validate every record and find duplicates within the export
+    fn prepare(&self, records: &[LegacyUserRecord]) -> Vec<Result<PreparedUser, UserImportIssue>> {
+        let mut first_seen: HashMap<String, u64> = HashMap::new();
+
+        records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| {
+                let number = index as u64 + 1;
+                let prepared = self.prepare_record(number, record)?;
+
+                let key = format!("{}{}", prepared.user.country_code, prepared.local_phone);
+                if let Some(first) = first_seen.get(&key) {
+                    return Err(UserImportIssue {
+                        record: number,
+                        kind: UserImportIssueKind::DuplicateInExport,
+                        message: format!("Same phone number as record {}", first),
+                    });
+                }
+                first_seen.insert(key, number);
+
+                Ok(prepared)
+            })
+            .collect()
+    }
+
+    /// Turn a record into the user to create
+    fn prepare_record(&self, number: u64, record: &LegacyUserRecord) -> Result<PreparedUser, UserImportIssue> {
+        let issue = |kind, message: String| UserImportIssue {
+            record: number,
+            kind,
+            message,
+        };
+
+        let phone = normalize_to_e164(&record.phone, record.country.as_deref().and_then(default_country))
+            .filter(|phone| validate_phone_with_country(phone))
+            .ok_or_else(|| {
+                issue(
+                    UserImportIssueKind::InvalidPhone,
+                    format!("Invalid phone number {}", mask_phone(&record.phone)),
+                )
+            })?;
+
+        let (country_code, local_phone) = extract_country_code(&phone);
+        if !self.config.markets.contains(&country_code) {
+            return Err(issue(
+                UserImportIssueKind::UnsupportedMarket,
+                format!("Users are not imported into market {}", country_code),
+            ));
+        }
+
+        let user_type = match record.user_type.as_deref().map(str::to_ascii_lowercase).as_deref() {
+            None | Some("") => None,
+            Some("customer") => Some(UserType::Customer),
+            Some("worker") => Some(UserType::Worker),
+            Some(other) => {
+                return Err(issue(
+                    UserImportIssueKind::InvalidUserType,
+                    format!("Unknown user type {}", other),
+                ))
+            }
+        };
+
+        let registered_at = match record.registered_at.as_deref() {
+            None | Some("") => None,
+            Some(value) => match parse_timestamp(value) {
+                Some(at) if at <= Utc::now() => Some(at),
+                _ => {
+                    return Err(issue(
+                        UserImportIssueKind::InvalidDate,
+                        format!("Invalid registration time {}", value),
+                    ))
+                }
+            },
+        };
+
+        let phone_hash = self.config.phone_hasher.hash(&local_phone);
+        let mut user = User::new(phone_hash, country_code);
+        user.phone_hash_version = self.config.phone_hasher.current_version();
+        user.user_type = user_type;
+        // The legacy system verified phone numbers at sign-up
+        user.is_verified = true;
+        user.is_blocked = record.blocked.unwrap_or(false);
+        if let Some(registered_at) = registered_at {
+            user.created_at = registered_at;
+        }
+
+        Ok(PreparedUser { local_phone, user })
+    }
+
+    /// Record the issue of a skipped record, or return the user to create
+    /// unless one is already registered under any hash of the phone number
+    async fn check_registered(
+        &self,
+        index: usize,
+        prepared: Result<PreparedUser, UserImportIssue>,
+        report: &mut UserImportReport,
+    ) -> DomainResult<Option<User>> {
+        let prepared = match prepared {
+            Ok(prepared) => prepared,
+            Err(issue) => {
+                report.skip(issue);
+                return Ok(None);
+            }
+        };
+
+        let country_code = &prepared.user.country_code;
+        for phone_hash in self.config.phone_hasher.lookup_hashes(&prepared.local_phone) {
+            if self.users.exists_by_phone(&phone_hash, country_code).await? {
+                report.skip(UserImportIssue {
+                    record: index as u64 + 1,
+                    kind: UserImportIssueKind::AlreadyRegistered,
+                    message: format!(
+                        "A user with phone number {} is already registered",
+                        mask_phone(&prepared.local_phone)
+                    ),
+                });
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(prepared.user))
+    }
+}
+
+/// Country assumed for local-format phone numbers of a record
+fn default_country(country: &str) -> Option<CountryCode> {
+    match country.trim().trim_start_matches('+').to_ascii_uppercase().as_str() {
+        "CN" | "86" => Some(CountryCode::China),
+        "AU" | "61" => Some(CountryCode::Australia),
+        _ => None,
+    }
+}
+
+/// Parse a registration time in RFC 3339, or as `YYYY-MM-DD HH:MM:SS` in UTC
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|at| at.and_utc())
+        })
+}
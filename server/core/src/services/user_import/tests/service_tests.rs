@@ -0,0 +1,288 @@
+//! Unit tests for the user import service
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::user::{User, UserType};
+use crate::domain::entities::user_import::UserImportCheckpoint;
+use crate::errors::DomainError;
+use crate::repositories::{UserImportRepository, UserRepository};
+use crate::services::auth::PhoneHasher;
+use crate::services::user_import::{
+    parse_export, ExportFormat, LegacyUserRecord, UserImportConfig, UserImportIssueKind,
+    UserImportService,
+};
+
+/// User repository whose batch inserts fail after a number of successes
+#[derive(Default)]
+struct MockUserRepository {
+    users: Mutex<Vec<User>>,
+    batches_before_failure: Mutex<Option<usize>>,
+    batches: AtomicUsize,
+}
+
+impl MockUserRepository {
+    fn failing_after(batches: usize) -> Self {
+        let repository = Self::default();
+        *repository.batches_before_failure.lock().unwrap() = Some(batches);
+        repository
+    }
+
+    fn recover(&self) {
+        *self.batches_before_failure.lock().unwrap() = None;
+    }
+
+    fn users(&self) -> Vec<User> {
+        self.users.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepository {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|u| u.phone_hash == phone_hash && u.country_code == country_code)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.users.lock().unwrap().iter().find(|u| u.id == id).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        let batch = self.batches.fetch_add(1, Ordering::SeqCst);
+        if self.batches_before_failure.lock().unwrap().is_some_and(|limit| batch >= limit) {
+            return Err(DomainError::Internal { message: "Connection lost".to_string() });
+        }
+
+        let count = users.len();
+        self.users.lock().unwrap().extend(users);
+        Ok(count)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        Ok(user)
+    }
+
+    async fn delete(&self, _id: Uuid) -> Result<bool, DomainError> {
+        Ok(false)
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        Ok(self.find_by_phone(phone_hash, country_code).await?.is_some())
+    }
+
+    async fn count_by_type(&self, _user_type: Option<UserType>) -> Result<u64, DomainError> {
+        Ok(self.users.lock().unwrap().len() as u64)
+    }
+}
+
+#[derive(Default)]
+struct MockCheckpoints {
+    checkpoints: Mutex<HashMap<String, UserImportCheckpoint>>,
+}
+
+#[async_trait]
+impl UserImportRepository for MockCheckpoints {
+    async fn find_checkpoint(&self, import_id: &str) -> Result<Option<UserImportCheckpoint>, DomainError> {
+        Ok(self.checkpoints.lock().unwrap().get(import_id).cloned())
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &UserImportCheckpoint) -> Result<(), DomainError> {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .insert(checkpoint.import_id.clone(), checkpoint.clone());
+        Ok(())
+    }
+}
+
+type TestService = UserImportService<MockUserRepository, MockCheckpoints>;
+
+fn create_service(users: Arc<MockUserRepository>, checkpoints: Arc<MockCheckpoints>) -> TestService {
+    let config = UserImportConfig {
+        batch_size: 2,
+        ..UserImportConfig::default()
+    };
+    UserImportService::new(users, checkpoints, config)
+}
+
+fn record(phone: &str, country: Option<&str>) -> LegacyUserRecord {
+    LegacyUserRecord {
+        phone: phone.to_string(),
+        country: country.map(str::to_string),
+        ..LegacyUserRecord::default()
+    }
+}
+
+const EXPORT_CSV: &str = "\
+phone,country,user_type,registered_at,blocked
+0412 345 678,AU,Worker,2019-03-04 05:06:07,
++8613812345678,,customer,2020-01-01T08:00:00+08:00,true
++61 412 345 678,,,,
+12345,AU,,,
++447700900123,,,,
++61412000111,,landlord,,
++61412000222,,,2999-01-01 00:00:00,
++61412000333,AU,,,
+";
+
+#[test]
+fn test_parse_csv_and_json_exports() {
+    let records = parse_export(ExportFormat::Csv, EXPORT_CSV.as_bytes()).unwrap();
+    assert_eq!(records.len(), 8);
+    assert_eq!(records[0].phone, "0412 345 678");
+    assert_eq!(records[0].country.as_deref(), Some("AU"));
+    assert_eq!(records[1].country, None);
+    assert_eq!(records[1].blocked, Some(true));
+    assert_eq!(records[2].user_type, None);
+
+    let json = r#"[{"phone": "+61412345678", "user_type": "worker"}, {"phone": "13812345678", "country": "+86"}]"#;
+    let records = parse_export(ExportFormat::Json, json.as_bytes()).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].country.as_deref(), Some("+86"));
+
+    assert!(matches!(
+        parse_export(ExportFormat::Csv, b"phone,blocked\n+61412345678,maybe\n"),
+        Err(DomainError::Validation { .. })
+    ));
+    assert!(matches!(
+        parse_export(ExportFormat::Json, b"{\"phone\": \"+61412345678\"}"),
+        Err(DomainError::Validation { .. })
+    ));
+
+    assert_eq!(ExportFormat::from_path("/exports/users.CSV"), Some(ExportFormat::Csv));
+    assert_eq!(ExportFormat::from_path("users.json"), Some(ExportFormat::Json));
+    assert_eq!(ExportFormat::from_path("users"), None);
+}
+
+#[tokio::test]
+async fn test_dry_run_reports_without_writing() {
+    let users = Arc::new(MockUserRepository::default());
+    let registered = User::new(PhoneHasher::default().hash("412000333"), "+61".to_string());
+    users.create(registered).await.unwrap();
+
+    let checkpoints = Arc::new(MockCheckpoints::default());
+    let service = create_service(users.clone(), checkpoints.clone());
+    let records = parse_export(ExportFormat::Csv, EXPORT_CSV.as_bytes()).unwrap();
+
+    let report = service.dry_run(&records).await.unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.total_records, 8);
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.duplicates, 1);
+    assert_eq!(report.already_registered, 1);
+    assert_eq!(report.invalid, 4);
+    assert_eq!(report.markets.get("+61"), Some(&1));
+    assert_eq!(report.markets.get("+86"), Some(&1));
+    let imported: Vec<u64> = report.users.iter().map(|user| user.record).collect();
+    assert_eq!(imported, vec![1, 2]);
+
+    let issues: Vec<(u64, UserImportIssueKind)> = report.issues.iter().map(|i| (i.record, i.kind)).collect();
+    assert_eq!(
+        issues,
+        vec![
+            (3, UserImportIssueKind::DuplicateInExport),
+            (4, UserImportIssueKind::InvalidPhone),
+            (5, UserImportIssueKind::UnsupportedMarket),
+            (6, UserImportIssueKind::InvalidUserType),
+            (7, UserImportIssueKind::InvalidDate),
+            (8, UserImportIssueKind::AlreadyRegistered),
+        ]
+    );
+    assert!(report.issues[2].message.contains("+44"));
+    assert!(!report.issues[5].message.contains("412000333"));
+
+    assert_eq!(users.users().len(), 1);
+    assert!(checkpoints.checkpoints.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_maps_legacy_fields() {
+    let users = Arc::new(MockUserRepository::default());
+    let service = create_service(users.clone(), Arc::new(MockCheckpoints::default()));
+    let records = parse_export(ExportFormat::Csv, EXPORT_CSV.as_bytes()).unwrap();
+
+    let report = service.import("export", &records).await.unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.imported, 3);
+
+    let created = users.users();
+    let worker_hash = PhoneHasher::default().hash("412345678");
+    let worker = created.iter().find(|u| u.phone_hash == worker_hash).unwrap();
+    assert_eq!(worker.user_type, Some(UserType::Worker));
+    assert_eq!(worker.created_at, Utc.with_ymd_and_hms(2019, 3, 4, 5, 6, 7).unwrap());
+    assert!(worker.is_verified);
+    assert!(!worker.is_blocked);
+
+    let customer = created.iter().find(|u| u.country_code == "+86").unwrap();
+    assert_eq!(customer.user_type, Some(UserType::Customer));
+    assert_eq!(customer.created_at, Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+    assert!(customer.is_blocked);
+}
+
+#[tokio::test]
+async fn test_failed_import_resumes_after_last_checkpoint() {
+    let records: Vec<LegacyUserRecord> = (0..7)
+        .map(|i| record(&format!("0412 000 {:03}", i), Some("AU")))
+        .chain(std::iter::once(record("+61412000001", None)))
+        .collect();
+
+    let users = Arc::new(MockUserRepository::failing_after(2));
+    let checkpoints = Arc::new(MockCheckpoints::default());
+    let service = create_service(users.clone(), checkpoints.clone());
+    let import_id = TestService::import_id(b"legacy export");
+
+    let result = service.import(&import_id, &records).await;
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert_eq!(users.users().len(), 4);
+
+    let checkpoint = checkpoints.find_checkpoint(&import_id).await.unwrap().unwrap();
+    assert_eq!(checkpoint.records_processed, 4);
+    assert_eq!(checkpoint.imported, 4);
+    assert!(!checkpoint.completed);
+
+    users.recover();
+    let report = service.import(&import_id, &records).await.unwrap();
+    assert_eq!(report.resumed_from, 4);
+    assert_eq!(report.imported, 3);
+    assert_eq!(report.duplicates, 1);
+    assert_eq!(report.issues[0].record, 8);
+    let imported: Vec<u64> = report.users.iter().map(|user| user.record).collect();
+    assert_eq!(imported, vec![5, 6, 7]);
+    assert_eq!(users.users().len(), 7);
+
+    let checkpoint = checkpoints.find_checkpoint(&import_id).await.unwrap().unwrap();
+    assert_eq!(checkpoint.records_processed, 8);
+    assert_eq!(checkpoint.imported, 7);
+    assert_eq!(checkpoint.skipped, 1);
+    assert!(checkpoint.completed);
+
+    assert!(matches!(
+        service.import(&import_id, &records).await,
+        Err(DomainError::BusinessRule { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_empty_export_completes() {
+    let checkpoints = Arc::new(MockCheckpoints::default());
+    let service = create_service(Arc::new(MockUserRepository::default()), checkpoints.clone());
+
+    let report = service.import("empty", &[]).await.unwrap();
+    assert_eq!(report.imported, 0);
+    assert!(checkpoints.find_checkpoint("empty").await.unwrap().unwrap().completed);
+}
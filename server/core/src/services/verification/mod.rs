@@ -5,6 +5,7 @@
 //! - Code verification with attempt tracking
 //! - Rate limiting and cooldown periods
 //! - Integration with SMS and cache services
+//! - Carrier lookups rejecting numbers that cannot receive SMS
 //! - Enhanced security with account locking and brute force protection
 
 mod config;
@@ -20,6 +21,6 @@ pub use config::VerificationServiceConfig;
 pub use enhanced_verification::{
     AccountLockInfo, EnhancedVerificationService, LockReason, VerificationStats,
 };
-pub use service::VerificationService;
-pub use traits::{SmsServiceTrait, CacheServiceTrait};
-pub use types::{SendCodeResult, VerifyCodeResult};
\ No newline at end of file
+pub use service::{VerificationService, FIXED_CODE_MESSAGE_ID};
+pub use traits::{SmsServiceTrait, SmsQueueTrait, CacheServiceTrait, PhoneNumberLookupTrait};
+pub use types::{PhoneLineType, PhoneNumberLookup, SendCodeResult, VerifyCodeResult};
\ No newline at end of file
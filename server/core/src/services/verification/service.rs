@@ -8,12 +8,17 @@ use constant_time_eq::constant_time_eq;
 use tracing;
 
 use crate::domain::entities::verification_code::{VerificationCode, CODE_LENGTH, MAX_ATTEMPTS};
-use crate::errors::{DomainError, DomainResult, ValidationError};
+use crate::errors::{AuthError, DomainError, DomainResult, ValidationError};
+
+use crate::services::lock::DistributedLock;
 
 use super::config::VerificationServiceConfig;
 use super::enhanced_verification::EnhancedVerificationService;
-use super::traits::{SmsServiceTrait, CacheServiceTrait};
-use super::types::{SendCodeResult, VerifyCodeResult};
+use super::traits::{SmsServiceTrait, SmsQueueTrait, CacheServiceTrait, PhoneNumberLookupTrait};
+use super::types::{PhoneLineType, SendCodeResult, VerifyCodeResult};
+
+/// Message ID reported when a fixed code is stored instead of sent
+pub const FIXED_CODE_MESSAGE_ID: &str = "trusted-tester";
 
 /// Metadata for OTP tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +51,12 @@ pub struct VerificationService<S: SmsServiceTrait, C: CacheServiceTrait> {
     config: VerificationServiceConfig,
     /// Enhanced verification service for security features
     enhanced_service: Arc<EnhancedVerificationService>,
+    /// Optional lock serializing sends to the same phone across instances
+    send_lock: Option<DistributedLock>,
+    /// Optional queue sending codes in the background
+    sms_queue: Option<Arc<dyn SmsQueueTrait>>,
+    /// Optional carrier lookup rejecting numbers that cannot receive SMS
+    number_lookup: Option<Arc<dyn PhoneNumberLookupTrait>>,
 }
 
 impl<S: SmsServiceTrait, C: CacheServiceTrait> VerificationService<S, C> {
@@ -74,17 +85,51 @@ impl<S: SmsServiceTrait, C: CacheServiceTrait> VerificationService<S, C> {
             cache_service,
             config,
             enhanced_service,
+            send_lock: None,
+            sms_queue: None,
+            number_lookup: None,
         }
     }
 
+    /// Serializes sends to the same phone number across API instances
+    ///
+    /// Without the lock two concurrent requests can both pass the cooldown
+    /// check, each sending an SMS and the second overwriting the code of
+    /// the first.
+    pub fn with_send_lock(mut self, lock: DistributedLock) -> Self {
+        self.send_lock = Some(lock);
+        self
+    }
+
+    /// Queues codes instead of sending them while handling the request
+    ///
+    /// The code is stored before it is queued, so the caller can respond
+    /// without waiting for the SMS provider. When the queue is unavailable
+    /// the code is sent directly.
+    pub fn with_sms_queue(mut self, sms_queue: Arc<dyn SmsQueueTrait>) -> Self {
+        self.sms_queue = Some(sms_queue);
+        self
+    }
+
+    /// Looks numbers up with their carrier before a code is sent
+    ///
+    /// Landlines, VoIP and nonexistent numbers are rejected with an error
+    /// the user can act on, instead of a code that is never delivered.
+    /// When the lookup fails the code is sent anyway.
+    pub fn with_number_lookup(mut self, number_lookup: Arc<dyn PhoneNumberLookupTrait>) -> Self {
+        self.number_lookup = Some(number_lookup);
+        self
+    }
+
     /// Send a verification code to a phone number
     ///
     /// This method:
-    /// 1. Validates the phone number format
+    /// 1. Validates the phone number format, and with a carrier lookup
+    ///    that the number can receive SMS
     /// 2. Checks for existing codes and cooldown periods
     /// 3. Generates a new verification code
     /// 4. Stores the code in cache
-    /// 5. Sends the code via SMS
+    /// 5. Sends the code via SMS, or queues it when a queue is configured
     ///
     /// # Arguments
     ///
@@ -93,6 +138,8 @@ impl<S: SmsServiceTrait, C: CacheServiceTrait> VerificationService<S, C> {
     /// # Returns
     ///
     /// * `Ok(SendCodeResult)` - Result containing the verification code and SMS details
+    /// * `Err(DomainError::Auth)` - The carrier lookup reports a landline,
+    ///   VoIP or nonexistent number
     /// * `Err(DomainError)` - If validation fails or sending fails
     pub async fn send_verification_code(&self, phone: &str) -> DomainResult<SendCodeResult> {
         // Validate phone number format
@@ -102,6 +149,55 @@ impl<S: SmsServiceTrait, C: CacheServiceTrait> VerificationService<S, C> {
             });
         }
 
+        self.check_reachable(phone).await?;
+
+        match &self.send_lock {
+            Some(lock) => lock.run(&format!("verification:send:{}", phone), self.send_new_code(phone)).await,
+            None => self.send_new_code(phone).await,
+        }
+    }
+
+    /// Reject numbers the carrier lookup reports as unable to receive SMS
+    async fn check_reachable(&self, phone: &str) -> DomainResult<()> {
+        let Some(ref number_lookup) = self.number_lookup else {
+            return Ok(());
+        };
+
+        let lookup = match number_lookup.lookup(phone).await {
+            Ok(lookup) => lookup,
+            Err(e) => {
+                tracing::warn!(
+                    phone = phone,
+                    error = %e,
+                    event = "number_lookup_failed",
+                    "Phone number lookup failed, sending the code anyway"
+                );
+                return Ok(());
+            }
+        };
+
+        let error = if !lookup.valid {
+            AuthError::UnreachablePhoneNumber
+        } else {
+            match lookup.line_type {
+                PhoneLineType::Landline => AuthError::LandlinePhoneNumber,
+                PhoneLineType::Voip => AuthError::VoipPhoneNumber,
+                PhoneLineType::Mobile | PhoneLineType::Unknown => return Ok(()),
+            }
+        };
+
+        tracing::warn!(
+            phone = phone,
+            line_type = ?lookup.line_type,
+            carrier = lookup.carrier.as_deref().unwrap_or("-"),
+            event = "unreachable_phone_number",
+            "Refusing to send a verification code to a number that cannot receive SMS"
+        );
+        Err(DomainError::Auth(error))
+    }
+
+    /// Check the cooldown, then generate, store and send a new code
+    async fn send_new_code(&self, phone: &str) -> DomainResult<SendCodeResult> {
         // Check if a code already exists and is still valid
         if let Ok(true) = self.cache_service.code_exists(phone).await {
             // Check TTL to see if we're still in cooldown
@@ -185,22 +281,94 @@ impl<S: SmsServiceTrait, C: CacheServiceTrait> VerificationService<S, C> {
             "Stored OTP metadata for tracking"
         );
 
-        // Send SMS
-        let message_id = self
-            .sms_service
+        // Send SMS, or queue it when a queue is configured
+        let message_id = self.dispatch_code(&verification_code).await?;
+
+        // Calculate next resend time
+        let next_resend_at = Utc::now() + chrono::Duration::seconds(self.config.resend_cooldown_seconds);
+
+        Ok(SendCodeResult {
+            verification_code,
+            message_id,
+            next_resend_at,
+        })
+    }
+
+    /// Queue a stored code for sending, falling back to sending it directly
+    async fn dispatch_code(&self, verification_code: &VerificationCode) -> DomainResult<String> {
+        let phone = verification_code.phone.as_str();
+
+        if let Some(ref sms_queue) = self.sms_queue {
+            match sms_queue
+                .enqueue_verification_code(phone, &verification_code.code, verification_code.expires_at)
+                .await
+            {
+                Ok(message_id) => return Ok(message_id),
+                Err(e) => tracing::warn!(
+                    phone = phone,
+                    error = %e,
+                    event = "sms_enqueue_failed",
+                    "Failed to queue verification code, sending it directly"
+                ),
+            }
+        }
+
+        self.sms_service
             .send_verification_code(phone, &verification_code.code)
             .await
             .map_err(|e| DomainError::Internal {
                 message: format!("Failed to send SMS: {}", e),
+            })
+    }
+
+    /// Store a fixed verification code without sending an SMS
+    ///
+    /// Used for trusted testers such as app store reviewers, who are given
+    /// the code out of band. Previous codes are invalidated and the code
+    /// expires like a generated one.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone` - The phone number the code is stored for (E.164 format)
+    /// * `code` - The fixed 6-digit code
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SendCodeResult)` - Result with a placeholder message ID
+    /// * `Err(DomainError)` - If the code is malformed or cannot be stored
+    pub async fn store_fixed_code(&self, phone: &str, code: &str) -> DomainResult<SendCodeResult> {
+        if code.len() != CODE_LENGTH || !code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(DomainError::Validation {
+                message: format!("Fixed verification code must be {} digits", CODE_LENGTH),
+            });
+        }
+
+        self.invalidate_previous_codes(phone).await?;
+
+        let mut verification_code = VerificationCode::new_with_expiration(
+            phone.to_string(),
+            self.config.code_expiration_minutes,
+        );
+        verification_code.code = code.to_string();
+
+        self.cache_service
+            .store_code(phone, &verification_code.code)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to store verification code: {}", e),
             })?;
 
-        // Calculate next resend time
-        let next_resend_at = Utc::now() + chrono::Duration::seconds(self.config.resend_cooldown_seconds);
+        tracing::info!(
+            phone = phone,
+            event = "fixed_otp_stored",
+            session_id = %verification_code.id,
+            "Stored fixed verification code for trusted tester"
+        );
 
         Ok(SendCodeResult {
             verification_code,
-            message_id,
-            next_resend_at,
+            message_id: FIXED_CODE_MESSAGE_ID.to_string(),
+            next_resend_at: Utc::now() + chrono::Duration::seconds(self.config.resend_cooldown_seconds),
         })
     }
 
@@ -5,7 +5,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use crate::domain::entities::verification_code::MAX_ATTEMPTS;
-use crate::services::verification::traits::{SmsServiceTrait, CacheServiceTrait};
+use crate::services::verification::traits::{SmsServiceTrait, CacheServiceTrait, PhoneNumberLookupTrait};
+use crate::services::verification::types::{PhoneLineType, PhoneNumberLookup};
 
 // Mock SMS service for testing
 pub struct MockSmsService {
@@ -128,4 +129,38 @@ impl CacheServiceTrait for MockCacheService {
         self.codes.lock().unwrap().remove(phone);
         Ok(())
     }
-}
\ No newline at end of file
+}
+// Mock carrier lookup for testing
+#[derive(Default)]
+pub struct MockPhoneNumberLookup {
+    pub results: HashMap<String, PhoneNumberLookup>,
+    pub should_fail: bool,
+}
+
+impl MockPhoneNumberLookup {
+    pub fn with_result(mut self, phone: &str, valid: bool, line_type: PhoneLineType) -> Self {
+        self.results.insert(
+            phone.to_string(),
+            PhoneNumberLookup {
+                valid,
+                line_type,
+                carrier: None,
+            },
+        );
+        self
+    }
+}
+
+#[async_trait]
+impl PhoneNumberLookupTrait for MockPhoneNumberLookup {
+    async fn lookup(&self, phone: &str) -> Result<PhoneNumberLookup, String> {
+        if self.should_fail {
+            return Err("Lookup service error".to_string());
+        }
+        Ok(self.results.get(phone).cloned().unwrap_or(PhoneNumberLookup {
+            valid: true,
+            line_type: PhoneLineType::Mobile,
+            carrier: None,
+        }))
+    }
+}
@@ -3,13 +3,17 @@
 use std::sync::Arc;
 
 use crate::domain::entities::verification_code::CODE_LENGTH;
-use crate::errors::{DomainError, ValidationError};
+use crate::errors::{AuthError, DomainError, ValidationError};
+use crate::services::lock::tests::service_tests::MockLockStore;
+use crate::services::lock::{DistributedLock, DistributedLockConfig, DistributedLockTrait};
+use crate::services::sms_queue::tests::service_tests::MockSmsQueueStore;
+use crate::services::sms_queue::SmsQueueService;
 use crate::services::verification::{VerificationService, VerificationServiceConfig};
-use crate::services::verification::CacheServiceTrait;
+use crate::services::verification::{CacheServiceTrait, PhoneLineType};
 use crate::services::verification::service::OtpMetadata;
 use chrono::{Utc, Duration};
 
-use super::mocks::{MockSmsService, MockCacheService};
+use super::mocks::{MockSmsService, MockCacheService, MockPhoneNumberLookup};
 
 #[tokio::test]
 async fn test_send_verification_code_success() {
@@ -330,4 +334,109 @@ fn test_otp_metadata_structure() {
     assert!(!metadata.is_used);
     assert_eq!(metadata.phone, "+1234567890");
     assert_eq!(metadata.session_id, "test-session-id");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_sends_to_the_same_phone_are_serialized() {
+    let lock_store = Arc::new(MockLockStore::default());
+    let lock_config = DistributedLockConfig {
+        wait_timeout: std::time::Duration::from_millis(50),
+        retry_interval: std::time::Duration::from_millis(5),
+        ..DistributedLockConfig::default()
+    };
+    let sms_service = Arc::new(MockSmsService::new(false));
+    let cache_service = Arc::new(MockCacheService::new(false));
+    let service = VerificationService::new(sms_service.clone(), cache_service, VerificationServiceConfig::default())
+        .with_send_lock(DistributedLock::new(lock_store.clone(), lock_config));
+
+    // A send to the same phone in progress on another instance
+    let token = lock_store
+        .try_acquire("verification:send:+1234567890", std::time::Duration::from_secs(5))
+        .await
+        .unwrap()
+        .unwrap();
+    let result = service.send_verification_code("+1234567890").await;
+    assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    assert_eq!(sms_service.get_sent_code("+1234567890"), None);
+
+    lock_store.release("verification:send:+1234567890", &token).await.unwrap();
+    assert!(service.send_verification_code("+1234567890").await.is_ok());
+    assert!(!lock_store.is_held("verification:send:+1234567890"));
+}
+
+#[tokio::test]
+async fn test_queues_code_when_queue_is_configured() {
+    let sms_service = Arc::new(MockSmsService::new(false));
+    let cache_service = Arc::new(MockCacheService::new(false));
+    let store = Arc::new(MockSmsQueueStore::default());
+    let queue = Arc::new(SmsQueueService::with_defaults(store.clone(), sms_service.clone()));
+    let service = VerificationService::new(sms_service.clone(), cache_service.clone(), VerificationServiceConfig::default())
+        .with_sms_queue(queue.clone());
+
+    let result = service.send_verification_code("+1234567890").await.unwrap();
+
+    // Stored and queued, but not sent yet
+    assert!(cache_service.code_exists("+1234567890").await.unwrap());
+    assert_eq!(sms_service.get_sent_code("+1234567890"), None);
+    assert_eq!(store.jobs()[0].id.to_string(), result.message_id);
+
+    queue.process_due().await.unwrap();
+    assert_eq!(sms_service.get_sent_code("+1234567890"), Some(result.verification_code.code));
+}
+
+#[tokio::test]
+async fn test_sends_directly_when_queue_is_unavailable() {
+    let sms_service = Arc::new(MockSmsService::new(false));
+    let cache_service = Arc::new(MockCacheService::new(false));
+    let store = Arc::new(MockSmsQueueStore::default());
+    store.unavailable.store(true, std::sync::atomic::Ordering::SeqCst);
+    let queue = Arc::new(SmsQueueService::with_defaults(store, sms_service.clone()));
+    let service = VerificationService::new(sms_service.clone(), cache_service, VerificationServiceConfig::default())
+        .with_sms_queue(queue);
+
+    let result = service.send_verification_code("+1234567890").await.unwrap();
+
+    assert!(result.message_id.starts_with("mock-msg-"));
+    assert_eq!(sms_service.get_sent_code("+1234567890"), Some(result.verification_code.code));
+}
+
+#[tokio::test]
+async fn test_number_lookup_rejects_numbers_that_cannot_receive_sms() {
+    let sms_service = Arc::new(MockSmsService::new(false));
+    let cache_service = Arc::new(MockCacheService::new(false));
+    let lookup = MockPhoneNumberLookup::default()
+        .with_result("+61298765432", true, PhoneLineType::Landline)
+        .with_result("+61412000000", true, PhoneLineType::Voip)
+        .with_result("+61400000000", false, PhoneLineType::Unknown);
+    let service = VerificationService::new(sms_service.clone(), cache_service.clone(), VerificationServiceConfig::default())
+        .with_number_lookup(Arc::new(lookup));
+
+    let result = service.send_verification_code("+61298765432").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::LandlinePhoneNumber))));
+    let result = service.send_verification_code("+61412000000").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::VoipPhoneNumber))));
+    let result = service.send_verification_code("+61400000000").await;
+    assert!(matches!(result, Err(DomainError::Auth(AuthError::UnreachablePhoneNumber))));
+
+    // Nothing was stored or sent for the rejected numbers
+    assert!(!cache_service.code_exists("+61298765432").await.unwrap());
+    assert_eq!(sms_service.get_sent_code("+61298765432"), None);
+
+    let result = service.send_verification_code("+61412345678").await.unwrap();
+    assert_eq!(sms_service.get_sent_code("+61412345678"), Some(result.verification_code.code));
+}
+
+#[tokio::test]
+async fn test_sends_code_when_number_lookup_fails() {
+    let sms_service = Arc::new(MockSmsService::new(false));
+    let cache_service = Arc::new(MockCacheService::new(false));
+    let lookup = MockPhoneNumberLookup {
+        should_fail: true,
+        ..Default::default()
+    };
+    let service = VerificationService::new(sms_service.clone(), cache_service, VerificationServiceConfig::default())
+        .with_number_lookup(Arc::new(lookup));
+
+    let result = service.send_verification_code("+61412345678").await.unwrap();
+    assert_eq!(sms_service.get_sent_code("+61412345678"), Some(result.verification_code.code));
+}
@@ -1,16 +1,62 @@
 //! Traits for SMS and cache service integration
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::types::PhoneNumberLookup;
 
 /// Trait for SMS service integration
 #[async_trait]
 pub trait SmsServiceTrait: Send + Sync {
     /// Send a verification code via SMS
     async fn send_verification_code(&self, phone: &str, code: &str) -> Result<String, String>;
+    /// Send an arbitrary text message, such as a scheduled reminder
+    ///
+    /// Providers that only support verification codes keep the default,
+    /// which reports the operation as unsupported.
+    async fn send_message(&self, _phone: &str, _message: &str) -> Result<String, String> {
+        Err("Sending arbitrary messages is not supported by this SMS provider".to_string())
+    }
     /// Check if the phone number format is valid
     fn is_valid_phone_number(&self, phone: &str) -> bool;
 }
 
+/// Trait for carrier lookups of phone numbers before codes are sent
+#[async_trait]
+pub trait PhoneNumberLookupTrait: Send + Sync {
+    /// Look up whether a number exists and which kind of line it is
+    ///
+    /// # Arguments
+    /// * `phone` - Phone number in E.164 format
+    ///
+    /// # Returns
+    /// * `Ok(PhoneNumberLookup)` - What the carrier reports about the number
+    /// * `Err(String)` - The lookup service could not be reached
+    async fn lookup(&self, phone: &str) -> Result<PhoneNumberLookup, String>;
+}
+
+/// Trait for queues sending verification codes outside the request path
+#[async_trait]
+pub trait SmsQueueTrait: Send + Sync {
+    /// Queue a verification code for sending
+    ///
+    /// # Arguments
+    /// * `phone` - Recipient phone number in E.164 format
+    /// * `code` - The verification code
+    /// * `expires_at` - When the code expires; the message is dropped if it
+    ///   has not been sent by then
+    ///
+    /// # Returns
+    /// * `Ok(String)` - ID of the queued message
+    /// * `Err(String)` - The message could not be queued
+    async fn enqueue_verification_code(
+        &self,
+        phone: &str,
+        code: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String, String>;
+}
+
 /// Trait for cache service integration
 #[async_trait]
 pub trait CacheServiceTrait: Send + Sync {
@@ -23,4 +23,28 @@ pub struct VerifyCodeResult {
     pub remaining_attempts: Option<i32>,
     /// Error message if verification failed
     pub error_message: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Kind of line a phone number belongs to, as reported by a carrier lookup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoneLineType {
+    /// Mobile number that can receive SMS
+    Mobile,
+    /// Fixed line that cannot receive SMS
+    Landline,
+    /// Fixed or non-fixed VoIP number
+    Voip,
+    /// Any other line type, or not reported by the carrier
+    Unknown,
+}
+
+/// Result of a carrier lookup of a phone number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhoneNumberLookup {
+    /// Whether the number exists in its country's numbering plan
+    pub valid: bool,
+    /// Kind of line the number belongs to
+    pub line_type: PhoneLineType,
+    /// Name of the carrier, if known
+    pub carrier: Option<String>,
+}
@@ -0,0 +1,24 @@
+//! Configuration for the webhook dead-letter service
+
+/// Configuration for the webhook dead-letter service
+#[derive(Debug, Clone)]
+pub struct WebhookDeadLetterConfig {
+    /// Maximum number of dead letters returned by a listing
+    pub max_list_limit: usize,
+    /// Maximum number of dead letters replayed by one bulk replay; a larger
+    /// range is replayed by repeating the request
+    pub max_bulk_replay: usize,
+    /// Replays not finished after this long are assumed to have stopped and
+    /// may be started again (in seconds)
+    pub stale_replay_seconds: i64,
+}
+
+impl Default for WebhookDeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            max_list_limit: 200,
+            max_bulk_replay: 100,
+            stale_replay_seconds: 300,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Webhook dead-letter service module for webhooks that failed to be handled
+//!
+//! This module handles:
+//! - Listing dead letters of received provider webhooks and of security
+//!   webhook deliveries that were given up, with their full payloads
+//! - Replaying a single dead letter or every pending dead letter in a time
+//!   range through the service that originally handled the webhook
+//! - Guarding replays so a dead letter is replayed at most once at a time
+//!   and never again once it is resolved
+
+mod config;
+mod service;
+mod traits;
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+pub use config::WebhookDeadLetterConfig;
+pub use service::{BulkReplayResult, ReplayResult, ReplayStatus, WebhookDeadLetterService};
+pub use traits::WebhookReplayTrait;
@@ -0,0 +1,281 @@
+//! Webhook dead-letter service
+//!
+//! Received provider webhooks that fail to be processed and security
+//! webhook deliveries that are given up are recorded as dead letters by
+//! the services handling them. Administrators inspect the payloads and,
+//! once the cause is fixed, replay dead letters one by one or every pending
+//! dead letter of a time range.
+//!
+//! Replays are protected against running twice. A replay first claims the
+//! dead letter, which only succeeds while it is pending, so concurrent
+//! replays of the same dead letter, e.g. overlapping bulk replays, handle it
+//! once. Resolved dead letters are never replayed again. The services
+//! replaying webhooks are idempotent per event as well: received events
+//! already processed are dropped and deliveries carry their original event
+//! ID for deduplication by the endpoint.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::webhook_dead_letter::{
+    DeadLetterFilter, DeadLetterStatus, WebhookDeadLetter, WebhookDirection,
+};
+use crate::errors::{DomainError, DomainResult};
+use crate::repositories::WebhookDeadLetterRepository;
+
+use super::config::WebhookDeadLetterConfig;
+use super::traits::WebhookReplayTrait;
+
+/// How a replay request was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayStatus {
+    /// The webhook was handled and the dead letter resolved
+    Replayed,
+    /// The webhook failed again; the dead letter stays pending
+    Failed,
+    /// The dead letter was already resolved and was not replayed
+    AlreadyResolved,
+    /// Another replay of the dead letter is in progress
+    InProgress,
+}
+
+impl ReplayStatus {
+    /// Convert to string representation for responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Replayed => "replayed",
+            Self::Failed => "failed",
+            Self::AlreadyResolved => "already_resolved",
+            Self::InProgress => "in_progress",
+        }
+    }
+}
+
+/// Result of replaying one dead letter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayResult {
+    /// Dead letter the result belongs to
+    pub dead_letter_id: Uuid,
+    /// How the request was handled
+    pub status: ReplayStatus,
+    /// How the webhook was handled, or why it failed again
+    pub detail: Option<String>,
+}
+
+/// Result of replaying the pending dead letters of a time range
+#[derive(Debug, Default)]
+pub struct BulkReplayResult {
+    /// Results of the dead letters replayed, in the order they were replayed
+    pub results: Vec<ReplayResult>,
+    /// Whether more pending dead letters matched than were replayed
+    pub has_more: bool,
+    /// Dead letters whose replay could not be recorded
+    pub errors: Vec<String>,
+}
+
+impl BulkReplayResult {
+    /// Number of results with the given status
+    pub fn count(&self, status: ReplayStatus) -> usize {
+        self.results.iter().filter(|result| result.status == status).count()
+    }
+}
+
+/// Service for inspecting and replaying dead-lettered webhooks
+pub struct WebhookDeadLetterService<R>
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    repository: Arc<R>,
+    replayers: HashMap<(WebhookDirection, String), Arc<dyn WebhookReplayTrait>>,
+    config: WebhookDeadLetterConfig,
+}
+
+impl<R> WebhookDeadLetterService<R>
+where
+    R: WebhookDeadLetterRepository + 'static,
+{
+    /// Create a new dead-letter service that cannot replay anything yet
+    pub fn new(repository: Arc<R>, config: WebhookDeadLetterConfig) -> Self {
+        Self {
+            repository,
+            replayers: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Enable replays of the webhooks the service handles
+    pub fn with_replayer(mut self, replayer: Arc<dyn WebhookReplayTrait>) -> Self {
+        self.replayers
+            .insert((replayer.direction(), replayer.source().to_string()), replayer);
+        self
+    }
+
+    /// List dead letters, most recent failure first
+    ///
+    /// The limit is capped at `max_list_limit`.
+    pub async fn list(
+        &self,
+        filter: &DeadLetterFilter,
+        limit: usize,
+    ) -> DomainResult<Vec<WebhookDeadLetter>> {
+        self.repository
+            .list(filter, limit.clamp(1, self.config.max_list_limit))
+            .await
+    }
+
+    /// Get a dead letter
+    ///
+    /// # Returns
+    /// * `Err(DomainError::NotFound)` - No dead letter exists with this ID
+    pub async fn get(&self, id: Uuid) -> DomainResult<WebhookDeadLetter> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound {
+                resource: "Webhook dead letter".to_string(),
+            })
+    }
+
+    /// Replay a dead letter
+    ///
+    /// # Arguments
+    /// * `id` - Dead letter to replay
+    /// * `replayed_by` - Administrator requesting the replay
+    ///
+    /// # Returns
+    /// * `Ok(ReplayResult)` - How the replay was handled
+    /// * `Err(DomainError::NotFound)` - No dead letter exists with this ID
+    /// * `Err(DomainError::BusinessRule)` - Webhooks from the dead letter's
+    ///   source cannot be replayed
+    pub async fn replay(&self, id: Uuid, replayed_by: Uuid) -> DomainResult<ReplayResult> {
+        let dead_letter = self.get(id).await?;
+        self.replay_dead_letter(dead_letter, replayed_by).await
+    }
+
+    /// Replay the pending dead letters that first failed within a time range
+    ///
+    /// Dead letters are replayed oldest first, at most `max_bulk_replay` of
+    /// them; `has_more` reports whether the request should be repeated.
+    /// Dead letters that fail again stay pending and are replayed again by
+    /// a repeated request.
+    ///
+    /// # Returns
+    /// * `Ok(BulkReplayResult)` - Results of the dead letters replayed
+    /// * `Err(DomainError::Validation)` - The range ends before it starts
+    pub async fn replay_range(
+        &self,
+        direction: Option<WebhookDirection>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        replayed_by: Uuid,
+    ) -> DomainResult<BulkReplayResult> {
+        if from > to {
+            return Err(DomainError::Validation {
+                message: "Replay period must not end before it starts".to_string(),
+            });
+        }
+
+        let limit = self.config.max_bulk_replay;
+        let mut dead_letters = self
+            .repository
+            .find_pending(direction, from, to, limit + 1)
+            .await?;
+
+        let mut result = BulkReplayResult {
+            has_more: dead_letters.len() > limit,
+            ..Default::default()
+        };
+        dead_letters.truncate(limit);
+
+        for dead_letter in dead_letters {
+            let id = dead_letter.id;
+            match self.replay_dead_letter(dead_letter, replayed_by).await {
+                Ok(replayed) => result.results.push(replayed),
+                Err(e) => result.errors.push(format!("{}: {}", id, e)),
+            }
+        }
+
+        info!(
+            replayed = result.count(ReplayStatus::Replayed),
+            failed = result.count(ReplayStatus::Failed),
+            errors = result.errors.len(),
+            replayed_by = %replayed_by,
+            "Webhook dead letters replayed"
+        );
+
+        Ok(result)
+    }
+
+    async fn replay_dead_letter(
+        &self,
+        mut dead_letter: WebhookDeadLetter,
+        replayed_by: Uuid,
+    ) -> DomainResult<ReplayResult> {
+        if dead_letter.status == DeadLetterStatus::Resolved {
+            return Ok(ReplayResult {
+                dead_letter_id: dead_letter.id,
+                status: ReplayStatus::AlreadyResolved,
+                detail: dead_letter.resolution,
+            });
+        }
+
+        let replayer = self
+            .replayers
+            .get(&(dead_letter.direction, dead_letter.source.clone()))
+            .cloned()
+            .ok_or_else(|| DomainError::BusinessRule {
+                message: format!(
+                    "{} webhooks from {} cannot be replayed",
+                    dead_letter.direction.as_str(),
+                    dead_letter.source
+                ),
+            })?;
+
+        dead_letter.start_replay(replayed_by);
+        let stale_before = Utc::now() - Duration::seconds(self.config.stale_replay_seconds);
+        if !self.repository.claim(&dead_letter, stale_before).await? {
+            return Ok(ReplayResult {
+                dead_letter_id: dead_letter.id,
+                status: ReplayStatus::InProgress,
+                detail: None,
+            });
+        }
+
+        let result = match replayer.replay(&dead_letter).await {
+            Ok(outcome) => {
+                info!(
+                    dead_letter_id = %dead_letter.id,
+                    event_id = %dead_letter.event_id,
+                    outcome = %outcome,
+                    "Webhook dead letter replayed"
+                );
+                dead_letter.resolve(outcome.clone());
+                ReplayResult {
+                    dead_letter_id: dead_letter.id,
+                    status: ReplayStatus::Replayed,
+                    detail: Some(outcome),
+                }
+            }
+            Err(e) => {
+                warn!(
+                    dead_letter_id = %dead_letter.id,
+                    event_id = %dead_letter.event_id,
+                    error = %e,
+                    "Webhook dead letter replay failed"
+                );
+                dead_letter.record_failure(e.clone());
+                ReplayResult {
+                    dead_letter_id: dead_letter.id,
+                    status: ReplayStatus::Failed,
+                    detail: Some(e),
+                }
+            }
+        };
+
+        self.repository.update(&dead_letter).await?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,4 @@
+//! Tests for the webhook dead-letter service
+
+#[cfg(test)]
+pub(crate) mod service_tests;
@@ -0,0 +1,289 @@
+//! Unit tests for the webhook dead-letter service
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::domain::entities::webhook_dead_letter::{
+    DeadLetterFilter, DeadLetterStatus, WebhookDeadLetter, WebhookDirection,
+};
+use crate::errors::DomainError;
+use crate::repositories::WebhookDeadLetterRepository;
+use crate::services::webhook_dead_letter::{
+    ReplayStatus, WebhookDeadLetterConfig, WebhookDeadLetterService, WebhookReplayTrait,
+};
+
+#[derive(Default)]
+pub(crate) struct MockDeadLetters {
+    pub(crate) dead_letters: Mutex<HashMap<Uuid, WebhookDeadLetter>>,
+}
+
+impl MockDeadLetters {
+    pub(crate) fn all(&self) -> Vec<WebhookDeadLetter> {
+        self.dead_letters.lock().unwrap().values().cloned().collect()
+    }
+
+    fn get(&self, id: Uuid) -> WebhookDeadLetter {
+        self.dead_letters.lock().unwrap()[&id].clone()
+    }
+}
+
+#[async_trait]
+impl WebhookDeadLetterRepository for MockDeadLetters {
+    async fn record(&self, dead_letter: WebhookDeadLetter) -> Result<WebhookDeadLetter, DomainError> {
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        let existing = dead_letters.values_mut().find(|d| {
+            d.direction == dead_letter.direction
+                && d.source == dead_letter.source
+                && d.event_id == dead_letter.event_id
+        });
+
+        match existing {
+            Some(existing) => {
+                existing.error = dead_letter.error;
+                existing.failures += 1;
+                existing.status = DeadLetterStatus::Pending;
+                Ok(existing.clone())
+            }
+            None => {
+                dead_letters.insert(dead_letter.id, dead_letter.clone());
+                Ok(dead_letter)
+            }
+        }
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDeadLetter>, DomainError> {
+        Ok(self.dead_letters.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list(
+        &self,
+        filter: &DeadLetterFilter,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError> {
+        let mut dead_letters: Vec<_> = self
+            .all()
+            .into_iter()
+            .filter(|d| filter.direction.is_none_or(|direction| d.direction == direction))
+            .filter(|d| filter.status.is_none_or(|status| d.status == status))
+            .collect();
+        dead_letters.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+        dead_letters.truncate(limit);
+        Ok(dead_letters)
+    }
+
+    async fn find_pending(
+        &self,
+        direction: Option<WebhookDirection>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError> {
+        let mut dead_letters: Vec<_> = self
+            .all()
+            .into_iter()
+            .filter(|d| d.is_pending() && d.created_at >= from && d.created_at <= to)
+            .filter(|d| direction.is_none_or(|direction| d.direction == direction))
+            .collect();
+        dead_letters.sort_by_key(|d| d.created_at);
+        dead_letters.truncate(limit);
+        Ok(dead_letters)
+    }
+
+    async fn claim(
+        &self,
+        dead_letter: &WebhookDeadLetter,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        let claimable = dead_letters.get(&dead_letter.id).is_some_and(|saved| {
+            saved.status == DeadLetterStatus::Pending
+                || (saved.status == DeadLetterStatus::Replaying && saved.updated_at < stale_before)
+        });
+        if claimable {
+            dead_letters.insert(dead_letter.id, dead_letter.clone());
+        }
+        Ok(claimable)
+    }
+
+    async fn update(&self, dead_letter: &WebhookDeadLetter) -> Result<(), DomainError> {
+        self.dead_letters.lock().unwrap().insert(dead_letter.id, dead_letter.clone());
+        Ok(())
+    }
+
+    async fn resolve_event(
+        &self,
+        direction: WebhookDirection,
+        source: &str,
+        event_id: &str,
+        resolution: &str,
+    ) -> Result<bool, DomainError> {
+        let mut dead_letters = self.dead_letters.lock().unwrap();
+        let pending = dead_letters.values_mut().find(|d| {
+            d.direction == direction && d.source == source && d.event_id == event_id && d.is_pending()
+        });
+        Ok(pending.map(|d| d.resolve(resolution)).is_some())
+    }
+}
+
+/// Replays inbound "stripe" webhooks, failing those whose payload says so
+#[derive(Default)]
+struct MockReplayer {
+    replayed: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl WebhookReplayTrait for MockReplayer {
+    fn direction(&self) -> WebhookDirection {
+        WebhookDirection::Inbound
+    }
+
+    fn source(&self) -> &str {
+        "stripe"
+    }
+
+    async fn replay(&self, dead_letter: &WebhookDeadLetter) -> Result<String, String> {
+        self.replayed.lock().unwrap().push(dead_letter.event_id.clone());
+        if dead_letter.payload["fail"] == true {
+            Err("Payment not found".to_string())
+        } else {
+            Ok("refund_recorded".to_string())
+        }
+    }
+}
+
+fn service(
+    config: WebhookDeadLetterConfig,
+) -> (
+    WebhookDeadLetterService<MockDeadLetters>,
+    Arc<MockDeadLetters>,
+    Arc<MockReplayer>,
+) {
+    let repository = Arc::new(MockDeadLetters::default());
+    let replayer = Arc::new(MockReplayer::default());
+    let service = WebhookDeadLetterService::new(repository.clone(), config)
+        .with_replayer(replayer.clone());
+    (service, repository, replayer)
+}
+
+async fn dead_letter(
+    repository: &MockDeadLetters,
+    event_id: &str,
+    minutes_ago: i64,
+    fail: bool,
+) -> WebhookDeadLetter {
+    let mut dead_letter = WebhookDeadLetter::inbound(
+        "stripe",
+        event_id,
+        "charge.refunded",
+        json!({ "id": event_id, "fail": fail }),
+        "Database unavailable",
+    );
+    dead_letter.created_at = Utc::now() - Duration::minutes(minutes_ago);
+    repository.record(dead_letter).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_replay_resolves_dead_letter_once() {
+    let (service, repository, replayer) = service(WebhookDeadLetterConfig::default());
+    let admin = Uuid::new_v4();
+    let letter = dead_letter(&repository, "evt_1", 5, false).await;
+
+    let result = service.replay(letter.id, admin).await.unwrap();
+    assert_eq!(result.status, ReplayStatus::Replayed);
+    assert_eq!(result.detail.as_deref(), Some("refund_recorded"));
+
+    let saved = repository.get(letter.id);
+    assert_eq!(saved.status, DeadLetterStatus::Resolved);
+    assert_eq!(saved.replayed_by, Some(admin));
+
+    // Replaying a resolved dead letter does not handle the webhook again
+    let again = service.replay(letter.id, admin).await.unwrap();
+    assert_eq!(again.status, ReplayStatus::AlreadyResolved);
+    assert_eq!(replayer.replayed.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_failed_replay_stays_pending() {
+    let (service, repository, _) = service(WebhookDeadLetterConfig::default());
+    let letter = dead_letter(&repository, "evt_1", 5, true).await;
+
+    let result = service.replay(letter.id, Uuid::new_v4()).await.unwrap();
+    assert_eq!(result.status, ReplayStatus::Failed);
+
+    let saved = repository.get(letter.id);
+    assert!(saved.is_pending());
+    assert_eq!(saved.failures, 2);
+    assert_eq!(saved.error, "Payment not found");
+}
+
+#[tokio::test]
+async fn test_replay_in_progress_is_not_started_twice() {
+    let (service, repository, replayer) = service(WebhookDeadLetterConfig::default());
+    let mut letter = dead_letter(&repository, "evt_1", 5, false).await;
+    letter.start_replay(Uuid::new_v4());
+    repository.update(&letter).await.unwrap();
+
+    let result = service.replay(letter.id, Uuid::new_v4()).await.unwrap();
+    assert_eq!(result.status, ReplayStatus::InProgress);
+    assert!(replayer.replayed.lock().unwrap().is_empty());
+
+    // A replay that stopped without finishing can be taken over
+    let service = WebhookDeadLetterService::new(
+        repository.clone(),
+        WebhookDeadLetterConfig {
+            stale_replay_seconds: -60,
+            ..Default::default()
+        },
+    )
+    .with_replayer(replayer.clone());
+    let result = service.replay(letter.id, Uuid::new_v4()).await.unwrap();
+    assert_eq!(result.status, ReplayStatus::Replayed);
+}
+
+#[tokio::test]
+async fn test_replay_of_unknown_source_is_rejected() {
+    let (service, repository, _) = service(WebhookDeadLetterConfig::default());
+    let letter = repository
+        .record(WebhookDeadLetter::inbound("twilio", "SM1", "status", json!({}), "Timeout"))
+        .await
+        .unwrap();
+
+    let result = service.replay(letter.id, Uuid::new_v4()).await;
+    assert!(matches!(result, Err(DomainError::BusinessRule { .. })));
+    assert!(repository.get(letter.id).is_pending());
+
+    let missing = service.replay(Uuid::new_v4(), Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(DomainError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_bulk_replay_covers_range_oldest_first() {
+    let (service, repository, replayer) = service(WebhookDeadLetterConfig {
+        max_bulk_replay: 2,
+        ..Default::default()
+    });
+    dead_letter(&repository, "evt_old", 120, false).await;
+    dead_letter(&repository, "evt_1", 30, false).await;
+    dead_letter(&repository, "evt_2", 20, true).await;
+    dead_letter(&repository, "evt_3", 10, false).await;
+
+    let from = Utc::now() - Duration::minutes(60);
+    let result = service
+        .replay_range(None, from, Utc::now(), Uuid::new_v4())
+        .await
+        .unwrap();
+
+    assert_eq!(*replayer.replayed.lock().unwrap(), vec!["evt_1", "evt_2"]);
+    assert_eq!(result.count(ReplayStatus::Replayed), 1);
+    assert_eq!(result.count(ReplayStatus::Failed), 1);
+    assert!(result.has_more);
+
+    let reversed = service
+        .replay_range(None, Utc::now(), from, Uuid::new_v4())
+        .await;
+    assert!(matches!(reversed, Err(DomainError::Validation { .. })));
+}
@@ -0,0 +1,25 @@
+//! Traits for services that handle dead-lettered webhooks again
+
+use async_trait::async_trait;
+
+use crate::domain::entities::webhook_dead_letter::{WebhookDeadLetter, WebhookDirection};
+
+/// Trait for services that can handle a dead-lettered webhook again
+#[async_trait]
+pub trait WebhookReplayTrait: Send + Sync {
+    /// Direction of the webhooks this service replays
+    fn direction(&self) -> WebhookDirection;
+
+    /// Source of the webhooks this service replays
+    fn source(&self) -> &str;
+
+    /// Handle the webhook again
+    ///
+    /// Implementations must be idempotent per event: replaying a webhook
+    /// that was already handled must not apply it twice.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Short description of how the webhook was handled
+    /// * `Err(String)` - Why the webhook failed again
+    async fn replay(&self, dead_letter: &WebhookDeadLetter) -> Result<String, String>;
+}
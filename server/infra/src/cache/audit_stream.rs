@@ -0,0 +1,356 @@
+//! Audit log pipeline over a Redis Stream
+//!
+//! [`RedisStreamAuditLogRepository`] appends new audit log entries to a
+//! Redis Stream instead of writing them to the database, taking the audit
+//! write off the latency path of the request. [`AuditStreamWorker`] reads
+//! the stream as a member of a consumer group and flushes the entries to
+//! the database in batches. Lookups go to the database and do not see
+//! entries until they are flushed, usually within a second.
+//!
+//! Each entry holds the tenant it was recorded for and the log as JSON:
+//! - `tenant` - Tenant of the request that produced the entry
+//! - `log` - The serialized [`AuditLog`]
+//!
+//! Entries are acknowledged once written. Entries left pending by a failed
+//! write or by a worker that went away are claimed again by any worker
+//! after `claim_idle_ms`, which requires Redis 6.2 or newer; the database
+//! must therefore tolerate entries being written twice. The stream is
+//! trimmed to about `max_len` entries, so entries are lost if the workers
+//! fall that far behind.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::streams::{StreamId, StreamMaxlen, StreamRangeReply, StreamReadOptions, StreamReadReply};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use re_core::domain::entities::audit::{AuditEventType, AuditLog, AuditLogFilter};
+use re_core::errors::DomainError;
+use re_core::repositories::audit::AuditLogRepository;
+use re_core::services::tenant::{current_tenant, scope, TenantId};
+use re_shared::types::Pagination;
+
+use crate::cache::redis_client::RedisClient;
+use crate::InfrastructureError;
+
+/// Delay before reading again after the stream could not be read
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Configuration for the audit log stream
+#[derive(Debug, Clone)]
+pub struct AuditStreamConfig {
+    /// Key of the stream, put under the key prefix of the Redis client
+    pub stream_key: String,
+    /// Consumer group of the workers
+    pub group: String,
+    /// Name of this worker within the group
+    pub consumer: String,
+    /// Maximum number of entries written to the database at once
+    pub batch_size: usize,
+    /// How long a read waits for new entries (in milliseconds)
+    pub block_ms: usize,
+    /// How long an entry stays pending before another worker claims it (in milliseconds)
+    pub claim_idle_ms: u64,
+    /// Approximate number of entries the stream is trimmed to
+    pub max_len: usize,
+}
+
+impl Default for AuditStreamConfig {
+    fn default() -> Self {
+        Self {
+            stream_key: "audit_events".to_string(),
+            group: "audit_writers".to_string(),
+            consumer: format!("audit-writer-{}", Uuid::new_v4()),
+            batch_size: 100,
+            block_ms: 1000,
+            claim_idle_ms: 60_000,
+            max_len: 100_000,
+        }
+    }
+}
+
+/// Audit log repository appending new entries to a Redis Stream
+///
+/// Entries are created by appending them to the stream, or written to the
+/// wrapped repository directly when Redis is unavailable. Every other call
+/// goes to the wrapped repository.
+pub struct RedisStreamAuditLogRepository {
+    redis_client: Arc<RedisClient>,
+    inner: Arc<dyn AuditLogRepository>,
+    config: AuditStreamConfig,
+}
+
+impl RedisStreamAuditLogRepository {
+    /// Create a repository appending to the stream and reading from `inner`
+    pub fn new(redis_client: Arc<RedisClient>, inner: Arc<dyn AuditLogRepository>, config: AuditStreamConfig) -> Self {
+        Self {
+            redis_client,
+            inner,
+            config,
+        }
+    }
+
+    /// Append an entry to the stream
+    async fn append(&self, audit_log: &AuditLog) -> Result<(), String> {
+        let log = serde_json::to_string(audit_log).map_err(|e| format!("Failed to encode audit log: {}", e))?;
+        let tenant = current_tenant();
+
+        let mut conn = self.redis_client.get_connection();
+        let _: String = conn
+            .xadd_maxlen(
+                self.redis_client.key(&self.config.stream_key),
+                StreamMaxlen::Approx(self.config.max_len),
+                "*",
+                &[("tenant", tenant.as_str()), ("log", log.as_str())],
+            )
+            .await
+            .map_err(|e| format!("Failed to append audit log: {}", e))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for RedisStreamAuditLogRepository {
+    async fn create(&self, audit_log: &AuditLog) -> Result<(), DomainError> {
+        match self.append(audit_log).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                warn!(error = %e, "Audit stream unavailable, writing audit log directly");
+                self.inner.create(audit_log).await
+            }
+        }
+    }
+
+    async fn find_by_user(&self, user_id: Uuid, limit: usize) -> Result<Vec<AuditLog>, DomainError> {
+        self.inner.find_by_user(user_id, limit).await
+    }
+
+    async fn find_by_phone_hash(&self, phone_hash: &str, limit: usize) -> Result<Vec<AuditLog>, DomainError> {
+        self.inner.find_by_phone_hash(phone_hash, limit).await
+    }
+
+    async fn count_failed_attempts(
+        &self,
+        action: &str,
+        phone_hash: Option<&str>,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<usize, DomainError> {
+        self.inner.count_failed_attempts(action, phone_hash, ip_address, since).await
+    }
+
+    async fn find_suspicious_activity(
+        &self,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        self.inner.find_suspicious_activity(ip_address, since).await
+    }
+
+    async fn archive_old_logs(&self) -> Result<usize, DomainError> {
+        self.inner.archive_old_logs().await
+    }
+
+    async fn delete_archived_logs(&self) -> Result<usize, DomainError> {
+        self.inner.delete_archived_logs().await
+    }
+
+    async fn archive_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        self.inner.archive_logs_before(before, limit).await
+    }
+
+    async fn delete_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        self.inner.delete_logs_before(before, limit).await
+    }
+
+    async fn find_by_event_types(
+        &self,
+        event_types: Vec<AuditEventType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        self.inner.find_by_event_types(event_types, from, to, limit).await
+    }
+
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        self.inner.query(filter, pagination).await
+    }
+}
+
+/// Worker flushing the audit log stream to a repository
+pub struct AuditStreamWorker {
+    redis_client: Arc<RedisClient>,
+    repository: Arc<dyn AuditLogRepository>,
+    config: AuditStreamConfig,
+}
+
+impl AuditStreamWorker {
+    /// Create a worker writing the entries of the stream to `repository`
+    pub fn new(
+        redis_client: Arc<RedisClient>,
+        repository: Arc<dyn AuditLogRepository>,
+        config: AuditStreamConfig,
+    ) -> Self {
+        Self {
+            redis_client,
+            repository,
+            config,
+        }
+    }
+
+    /// Create the consumer group, and the stream, if they do not exist
+    pub async fn ensure_group(&self) -> Result<(), InfrastructureError> {
+        let mut conn = self.redis_client.get_connection();
+        let result: redis::RedisResult<()> = conn
+            .xgroup_create_mkstream(self.redis_client.key(&self.config.stream_key), &self.config.group, "0")
+            .await;
+
+        match result {
+            Err(e) if e.code() != Some("BUSYGROUP") => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Flush the entries left pending too long, then the new entries
+    ///
+    /// Waits up to `block_ms` for new entries.
+    ///
+    /// # Returns
+    /// * `Result<usize, InfrastructureError>` - Number of entries written
+    pub async fn run_once(&self) -> Result<usize, InfrastructureError> {
+        let claimed = self.claim_stale_entries().await?;
+        let mut written = self.flush(claimed).await?;
+
+        let options = StreamReadOptions::default()
+            .group(&self.config.group, &self.config.consumer)
+            .count(self.config.batch_size)
+            .block(self.config.block_ms);
+        let mut conn = self.redis_client.get_connection();
+        let reply: Option<StreamReadReply> = conn
+            .xread_options(&[&self.redis_client.key(&self.config.stream_key)], &[">"], &options)
+            .await?;
+
+        let entries = reply
+            .into_iter()
+            .flat_map(|reply| reply.keys)
+            .flat_map(|key| key.ids)
+            .collect();
+        written += self.flush(entries).await?;
+
+        Ok(written)
+    }
+
+    /// Claim the entries pending for longer than `claim_idle_ms`
+    async fn claim_stale_entries(&self) -> Result<Vec<StreamId>, InfrastructureError> {
+        let mut conn = self.redis_client.get_connection();
+        let reply: Vec<redis::Value> = redis::cmd("XAUTOCLAIM")
+            .arg(self.redis_client.key(&self.config.stream_key))
+            .arg(&self.config.group)
+            .arg(&self.config.consumer)
+            .arg(self.config.claim_idle_ms)
+            .arg("0-0")
+            .arg("COUNT")
+            .arg(self.config.batch_size)
+            .query_async(&mut conn)
+            .await?;
+
+        // The reply is the next cursor, the claimed entries, and on Redis 7
+        // the IDs of entries deleted meanwhile
+        match reply.get(1) {
+            Some(entries) => Ok(redis::from_redis_value::<StreamRangeReply>(entries)?.ids),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Write entries to the repository and acknowledge them
+    ///
+    /// Entries that cannot be decoded are acknowledged and dropped. Entries
+    /// of a tenant whose batch fails stay pending and are claimed again.
+    async fn flush(&self, entries: Vec<StreamId>) -> Result<usize, InfrastructureError> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+
+        let mut acknowledged = Vec::new();
+        let mut batches: HashMap<TenantId, (Vec<AuditLog>, Vec<String>)> = HashMap::new();
+        for entry in entries {
+            match decode_entry(&entry) {
+                Some((tenant, audit_log)) => {
+                    let batch = batches.entry(tenant).or_default();
+                    batch.0.push(audit_log);
+                    batch.1.push(entry.id);
+                }
+                None => {
+                    warn!("Dropping malformed audit stream entry {}", entry.id);
+                    acknowledged.push(entry.id);
+                }
+            }
+        }
+
+        let mut written = 0;
+        for (tenant, (audit_logs, ids)) in batches {
+            match scope(tenant.clone(), self.repository.create_batch(&audit_logs)).await {
+                Ok(_) => {
+                    written += audit_logs.len();
+                    acknowledged.extend(ids);
+                }
+                Err(e) => error!("Failed to write {} audit logs of tenant {}: {}", audit_logs.len(), tenant, e),
+            }
+        }
+
+        if !acknowledged.is_empty() {
+            let mut conn = self.redis_client.get_connection();
+            let _: usize = conn
+                .xack(self.redis_client.key(&self.config.stream_key), &self.config.group, &acknowledged)
+                .await?;
+        }
+
+        Ok(written)
+    }
+
+    /// Start flushing the stream as a background task
+    pub fn start_background_task(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!(
+                "Audit stream worker {} started on {}",
+                self.config.consumer, self.config.stream_key
+            );
+
+            // The group is created again after failures in case the stream was removed
+            let mut group_ready = false;
+            loop {
+                if !group_ready {
+                    if let Err(e) = self.ensure_group().await {
+                        error!("Failed to create audit stream consumer group: {}", e);
+                        tokio::time::sleep(RETRY_DELAY).await;
+                        continue;
+                    }
+                    group_ready = true;
+                }
+
+                if let Err(e) = self.run_once().await {
+                    error!("Failed to flush audit stream: {}", e);
+                    group_ready = false;
+                    tokio::time::sleep(RETRY_DELAY).await;
+                }
+            }
+        });
+    }
+}
+
+/// Tenant and audit log of a stream entry, None if malformed
+pub(crate) fn decode_entry(entry: &StreamId) -> Option<(TenantId, AuditLog)> {
+    let tenant = TenantId::parse(&entry.get::<String>("tenant")?)?;
+    let audit_log = serde_json::from_str(&entry.get::<String>("log")?).ok()?;
+    Some((tenant, audit_log))
+}
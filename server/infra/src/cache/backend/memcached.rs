@@ -0,0 +1,269 @@
+//! Memcached cache backend
+//!
+//! Speaks the memcached text protocol over a small pool of TCP
+//! connections, opened on first use and reopened after an I/O error.
+//! Remaining TTLs are read with the meta `mg` command, which requires
+//! memcached 1.6 or later.
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tracing::{debug, error, info};
+
+use super::CacheBackend;
+use crate::InfrastructureError;
+
+/// Longest key memcached accepts
+const MAX_KEY_LENGTH: usize = 250;
+
+/// Expiry times above 30 days are read by memcached as Unix timestamps
+const MAX_RELATIVE_EXPIRY_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Default timeout of a single command
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Reply of memcached to a command
+#[derive(Debug)]
+struct Reply {
+    /// First line of the reply, without the line ending
+    line: String,
+    /// Data block of a `VALUE` reply
+    value: Option<String>,
+}
+
+/// Cache backend storing entries in memcached
+pub struct MemcachedBackend {
+    /// `host:port` of the server
+    address: String,
+    /// Connections, opened on first use
+    connections: Vec<Mutex<Option<BufStream<TcpStream>>>>,
+    /// Connection the next command is sent on
+    next: AtomicUsize,
+    /// Timeout of a single command, connecting included
+    timeout: Duration,
+}
+
+impl MemcachedBackend {
+    /// Connect to a memcached server
+    ///
+    /// # Arguments
+    /// * `address` - `host:port` of the server
+    /// * `pool_size` - Number of connections commands are spread over
+    ///
+    /// # Returns
+    /// * `Result<Self, InfrastructureError>` - Backend, or an error if the
+    ///   server cannot be reached
+    pub async fn connect(address: impl Into<String>, pool_size: usize) -> Result<Self, InfrastructureError> {
+        let backend = Self {
+            address: address.into(),
+            connections: (0..pool_size.max(1)).map(|_| Mutex::new(None)).collect(),
+            next: AtomicUsize::new(0),
+            timeout: DEFAULT_TIMEOUT,
+        };
+
+        let reply = backend.command("version", None).await?;
+        info!("Connected to memcached at {} ({})", backend.address, reply.line);
+        Ok(backend)
+    }
+
+    /// Use a different timeout for every command
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send a command and read the reply
+    ///
+    /// A connection that failed or timed out is dropped and reopened by the
+    /// next command using it, since it may have a partial reply pending.
+    async fn command(&self, line: &str, data: Option<&str>) -> Result<Reply, InfrastructureError> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.connections.len();
+        let mut connection = self.connections[index].lock().await;
+
+        let result = tokio::time::timeout(self.timeout, async {
+            if connection.is_none() {
+                let stream = TcpStream::connect(&self.address).await?;
+                stream.set_nodelay(true)?;
+                *connection = Some(BufStream::new(stream));
+            }
+            let stream = connection.as_mut().expect("connection opened above");
+            exchange(stream, line, data).await
+        })
+        .await;
+
+        let reply = match result {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(e)) => {
+                *connection = None;
+                error!("memcached command failed on {}: {}", self.address, e);
+                return Err(InfrastructureError::CacheBackend(format!("memcached I/O error: {}", e)));
+            }
+            Err(_) => {
+                *connection = None;
+                error!("memcached command timed out on {}", self.address);
+                return Err(InfrastructureError::CacheBackend(format!(
+                    "memcached did not reply within {} ms",
+                    self.timeout.as_millis()
+                )));
+            }
+        };
+
+        if reply.line == "ERROR"
+            || reply.line.starts_with("CLIENT_ERROR")
+            || reply.line.starts_with("SERVER_ERROR")
+        {
+            return Err(InfrastructureError::CacheBackend(format!("memcached rejected command: {}", reply.line)));
+        }
+        Ok(reply)
+    }
+}
+
+/// Write a command and read its reply on a connection
+async fn exchange(stream: &mut BufStream<TcpStream>, line: &str, data: Option<&str>) -> std::io::Result<Reply> {
+    stream.write_all(line.as_bytes()).await?;
+    stream.write_all(b"\r\n").await?;
+    if let Some(data) = data {
+        stream.write_all(data.as_bytes()).await?;
+        stream.write_all(b"\r\n").await?;
+    }
+    stream.flush().await?;
+
+    let first = read_line(stream).await?;
+    let Some(header) = first.strip_prefix("VALUE ") else {
+        return Ok(Reply { line: first, value: None });
+    };
+
+    // VALUE <key> <flags> <bytes>, then the data block and END
+    let length: usize = header
+        .split(' ')
+        .nth(2)
+        .and_then(|bytes| bytes.parse().ok())
+        .ok_or_else(|| invalid_reply(&first))?;
+    let mut block = vec![0; length + 2];
+    stream.read_exact(&mut block).await?;
+    block.truncate(length);
+    let end = read_line(stream).await?;
+    if end != "END" {
+        return Err(invalid_reply(&end));
+    }
+
+    let value = String::from_utf8(block).map_err(|_| invalid_reply("non UTF-8 value"))?;
+    Ok(Reply { line: first, value: Some(value) })
+}
+
+async fn read_line(stream: &mut BufStream<TcpStream>) -> std::io::Result<String> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await? == 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn invalid_reply(reply: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, format!("unexpected reply '{}'", reply))
+}
+
+/// Check a key can be sent in a text protocol command
+fn validate_key(key: &str) -> Result<(), InfrastructureError> {
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH || key.bytes().any(|b| b.is_ascii_whitespace() || b.is_ascii_control()) {
+        return Err(InfrastructureError::CacheBackend(format!(
+            "'{}' is not a valid memcached key",
+            key
+        )));
+    }
+    Ok(())
+}
+
+/// Expiry time as memcached reads it: relative up to 30 days, a Unix
+/// timestamp beyond that, 0 for no expiry
+pub(crate) fn exptime(expiry_seconds: Option<u64>) -> u64 {
+    match expiry_seconds {
+        Some(seconds) if seconds > MAX_RELATIVE_EXPIRY_SECONDS => {
+            chrono::Utc::now().timestamp().max(0) as u64 + seconds
+        }
+        Some(seconds) => seconds,
+        None => 0,
+    }
+}
+
+/// Remaining TTL from the flags of an `mg <key> t` reply
+pub(crate) fn parse_meta_ttl(line: &str) -> Result<Option<i64>, InfrastructureError> {
+    if line == "EN" {
+        return Ok(None);
+    }
+    let ttl = line
+        .strip_prefix("HD")
+        .and_then(|flags| flags.split(' ').find_map(|flag| flag.strip_prefix('t')))
+        .and_then(|ttl| ttl.parse::<i64>().ok())
+        .ok_or_else(|| InfrastructureError::CacheBackend(format!("Unexpected memcached reply '{}'", line)))?;
+    Ok((ttl >= 0).then_some(ttl))
+}
+
+#[async_trait]
+impl CacheBackend for MemcachedBackend {
+    fn name(&self) -> &'static str {
+        "memcached"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        validate_key(key)?;
+        debug!("Getting key '{}' from memcached", key);
+        Ok(self.command(&format!("get {}", key), None).await?.value)
+    }
+
+    async fn set(&self, key: &str, value: &str, expiry_seconds: u64) -> Result<(), InfrastructureError> {
+        validate_key(key)?;
+        debug!("Setting key '{}' in memcached with expiry {}s", key, expiry_seconds);
+        let line = format!("set {} 0 {} {}", key, exptime(Some(expiry_seconds)), value.len());
+        match self.command(&line, Some(value)).await?.line.as_str() {
+            "STORED" => Ok(()),
+            other => Err(InfrastructureError::CacheBackend(format!(
+                "memcached did not store '{}': {}",
+                key, other
+            ))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, InfrastructureError> {
+        validate_key(key)?;
+        debug!("Deleting key '{}' from memcached", key);
+        Ok(self.command(&format!("delete {}", key), None).await?.line == "DELETED")
+    }
+
+    async fn incr(&self, key: &str, expiry_seconds: Option<u64>) -> Result<i64, InfrastructureError> {
+        validate_key(key)?;
+        debug!("Incrementing counter '{}' in memcached", key);
+
+        // incr fails on a missing key and add fails on an existing one, so
+        // a counter created concurrently is incremented on the second pass
+        for _ in 0..2 {
+            let reply = self.command(&format!("incr {} 1", key), None).await?;
+            if reply.line != "NOT_FOUND" {
+                return reply.line.parse().map_err(|_| {
+                    InfrastructureError::CacheBackend(format!("Unexpected memcached reply '{}'", reply.line))
+                });
+            }
+
+            let line = format!("add {} 0 {} 1", key, exptime(expiry_seconds));
+            if self.command(&line, Some("1")).await?.line == "STORED" {
+                return Ok(1);
+            }
+        }
+
+        Err(InfrastructureError::CacheBackend(format!("Failed to increment counter '{}'", key)))
+    }
+
+    async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError> {
+        validate_key(key)?;
+        let line = format!("touch {} {}", key, exptime(Some(expiry_seconds)));
+        Ok(self.command(&line, None).await?.line == "TOUCHED")
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError> {
+        validate_key(key)?;
+        parse_meta_ttl(&self.command(&format!("mg {} t", key), None).await?.line)
+    }
+}
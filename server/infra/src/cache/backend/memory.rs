@@ -0,0 +1,173 @@
+//! In-process cache backend
+//!
+//! Keeps entries in a bounded moka cache, so nothing survives a restart and
+//! nothing is shared between instances. Suited to tests, local development
+//! and deployments running a single instance without Redis.
+
+use async_trait::async_trait;
+use moka::future::Cache;
+use moka::ops::compute::Op;
+use moka::Expiry;
+use std::time::{Duration, Instant};
+
+use super::CacheBackend;
+use crate::InfrastructureError;
+
+/// Default maximum number of entries
+const DEFAULT_MAX_CAPACITY: u64 = 100_000;
+
+/// Cached value and when it expires
+#[derive(Debug, Clone)]
+struct MemoryEntry {
+    value: String,
+    expires_at: Option<Instant>,
+}
+
+impl MemoryEntry {
+    fn new(value: String, expiry_seconds: Option<u64>) -> Self {
+        Self {
+            value,
+            expires_at: expiry_seconds.map(|seconds| Instant::now() + Duration::from_secs(seconds)),
+        }
+    }
+
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.expires_at.map(|expires_at| expires_at.saturating_duration_since(now))
+    }
+}
+
+/// Evicts every entry at its own `expires_at`
+struct EntryExpiry;
+
+impl Expiry<String, MemoryEntry> for EntryExpiry {
+    fn expire_after_create(&self, _key: &String, entry: &MemoryEntry, created_at: Instant) -> Option<Duration> {
+        entry.remaining(created_at)
+    }
+
+    fn expire_after_update(
+        &self,
+        _key: &String,
+        entry: &MemoryEntry,
+        updated_at: Instant,
+        _duration_until_expiry: Option<Duration>,
+    ) -> Option<Duration> {
+        entry.remaining(updated_at)
+    }
+}
+
+/// Cache backend keeping entries in process memory
+#[derive(Clone)]
+pub struct InMemoryCacheBackend {
+    cache: Cache<String, MemoryEntry>,
+}
+
+impl InMemoryCacheBackend {
+    /// Create a backend holding at most `max_capacity` entries; the least
+    /// recently used entries are evicted beyond that
+    pub fn new(max_capacity: u64) -> Self {
+        Self {
+            cache: Cache::builder()
+                .max_capacity(max_capacity)
+                .expire_after(EntryExpiry)
+                .build(),
+        }
+    }
+
+    /// Entry of a key if it has not expired
+    async fn live_entry(&self, key: &str) -> Option<MemoryEntry> {
+        self.cache.get(key).await.filter(|entry| entry.is_live(Instant::now()))
+    }
+}
+
+impl Default for InMemoryCacheBackend {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CAPACITY)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryCacheBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        Ok(self.live_entry(key).await.map(|entry| entry.value))
+    }
+
+    async fn set(&self, key: &str, value: &str, expiry_seconds: u64) -> Result<(), InfrastructureError> {
+        self.cache
+            .insert(key.to_string(), MemoryEntry::new(value.to_string(), Some(expiry_seconds)))
+            .await;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, InfrastructureError> {
+        Ok(self
+            .cache
+            .remove(key)
+            .await
+            .is_some_and(|entry| entry.is_live(Instant::now())))
+    }
+
+    async fn incr(&self, key: &str, expiry_seconds: Option<u64>) -> Result<i64, InfrastructureError> {
+        let result = self
+            .cache
+            .entry_by_ref(key)
+            .and_try_compute_with(|existing| async move {
+                let entry = match existing.map(|existing| existing.into_value()) {
+                    Some(entry) if entry.is_live(Instant::now()) => {
+                        let count: i64 = entry.value.parse().map_err(|_| {
+                            InfrastructureError::CacheBackend(format!("Value of '{}' is not an integer", key))
+                        })?;
+                        MemoryEntry {
+                            value: (count + 1).to_string(),
+                            expires_at: entry.expires_at,
+                        }
+                    }
+                    _ => MemoryEntry::new("1".to_string(), expiry_seconds),
+                };
+                Ok::<_, InfrastructureError>(Op::Put(entry))
+            })
+            .await?;
+
+        result
+            .into_entry()
+            .and_then(|entry| entry.into_value().value.parse().ok())
+            .ok_or_else(|| InfrastructureError::CacheBackend(format!("Failed to increment '{}'", key)))
+    }
+
+    async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError> {
+        let result = self
+            .cache
+            .entry_by_ref(key)
+            .and_compute_with(|existing| async move {
+                match existing.map(|existing| existing.into_value()) {
+                    Some(entry) if entry.is_live(Instant::now()) => {
+                        Op::Put(MemoryEntry::new(entry.value, Some(expiry_seconds)))
+                    }
+                    _ => Op::Nop,
+                }
+            })
+            .await;
+
+        Ok(matches!(result, moka::ops::compute::CompResult::ReplacedWith(_)))
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, InfrastructureError> {
+        Ok(self.live_entry(key).await.is_some())
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError> {
+        let now = Instant::now();
+        Ok(self
+            .live_entry(key)
+            .await
+            .and_then(|entry| entry.remaining(now))
+            .map(|remaining| remaining.as_secs() as i64))
+    }
+}
@@ -0,0 +1,106 @@
+//! Cache backends for short-lived values
+//!
+//! Verification codes, encrypted OTPs and their attempt counters only need
+//! a handful of key-value operations. `CacheBackend` abstracts them so that
+//! `VerificationCache` and `OtpRedisStorage` run against Redis in
+//! production, an in-process cache in tests and single-instance
+//! deployments, or memcached where that is what the hosting environment
+//! provides. `TieredCacheBackend` keeps recent reads of selected keys in
+//! process memory in front of any of them.
+//!
+//! Values are strings and expiry is in seconds, as with `RedisClient`.
+
+pub mod memcached;
+pub mod memory;
+pub mod tiered;
+
+use async_trait::async_trait;
+
+use crate::cache::RedisClient;
+use crate::InfrastructureError;
+
+pub use memcached::MemcachedBackend;
+pub use memory::InMemoryCacheBackend;
+pub use tiered::{TieredCacheBackend, TieredCacheConfig};
+
+/// Key-value store holding expiring cache entries
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Name of the backend in logs
+    fn name(&self) -> &'static str;
+
+    /// Get a value, or None if the key does not exist or expired
+    async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError>;
+
+    /// Set a value expiring after `expiry_seconds`, replacing any previous value
+    async fn set(&self, key: &str, value: &str, expiry_seconds: u64) -> Result<(), InfrastructureError>;
+
+    /// Delete a key
+    ///
+    /// # Returns
+    /// * `Result<bool, InfrastructureError>` - True if the key existed
+    async fn delete(&self, key: &str) -> Result<bool, InfrastructureError>;
+
+    /// Increment a counter, creating it at 1 if it does not exist
+    ///
+    /// # Arguments
+    /// * `key` - Counter key
+    /// * `expiry_seconds` - Expiry set when the counter is created
+    ///
+    /// # Returns
+    /// * `Result<i64, InfrastructureError>` - New counter value
+    async fn incr(&self, key: &str, expiry_seconds: Option<u64>) -> Result<i64, InfrastructureError>;
+
+    /// Make an existing key expire after `expiry_seconds`
+    ///
+    /// # Returns
+    /// * `Result<bool, InfrastructureError>` - False if the key does not exist
+    async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError>;
+
+    /// Check if a key exists
+    async fn exists(&self, key: &str) -> Result<bool, InfrastructureError> {
+        Ok(self.get(key).await?.is_some())
+    }
+
+    /// Get time-to-live for a key
+    ///
+    /// # Returns
+    /// * `Result<Option<i64>, InfrastructureError>` - TTL in seconds, None if
+    ///   the key doesn't exist or has no expiry
+    async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError>;
+}
+
+#[async_trait]
+impl CacheBackend for RedisClient {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        RedisClient::get(self, key).await
+    }
+
+    async fn set(&self, key: &str, value: &str, expiry_seconds: u64) -> Result<(), InfrastructureError> {
+        self.set_with_expiry(key, value, expiry_seconds).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, InfrastructureError> {
+        RedisClient::delete(self, key).await
+    }
+
+    async fn incr(&self, key: &str, expiry_seconds: Option<u64>) -> Result<i64, InfrastructureError> {
+        self.increment(key, expiry_seconds).await
+    }
+
+    async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError> {
+        RedisClient::expire(self, key, expiry_seconds).await
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, InfrastructureError> {
+        RedisClient::exists(self, key).await
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError> {
+        RedisClient::ttl(self, key).await
+    }
+}
@@ -0,0 +1,168 @@
+//! Two-tier cache backend
+//!
+//! Read-heavy values that rarely change, such as account locks checked on
+//! every authenticated call, are kept for a few seconds in a small moka
+//! cache in front of a shared backend, usually Redis, saving a round trip
+//! per read. Only keys under the configured prefixes are kept in process
+//! memory; every other key goes straight to the shared backend.
+//!
+//! Writes go to the shared backend and drop the local copy. With an
+//! invalidation publisher, the changed keys are also published as a
+//! [`ClusterEvent::CacheInvalidated`] event so that the other instances
+//! drop their copies through [`TieredCacheBackend::invalidate_local`].
+//! Copies are at most `local_ttl_seconds` old when an event is missed, and
+//! a value expiring in the shared backend may be read locally for as long.
+
+use async_trait::async_trait;
+use moka::future::Cache;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use re_core::services::cluster::{ClusterEvent, ClusterEventPublisherTrait};
+
+use super::CacheBackend;
+use crate::InfrastructureError;
+
+/// Configuration for the in-process tier
+#[derive(Debug, Clone)]
+pub struct TieredCacheConfig {
+    /// Maximum number of entries kept in process memory
+    pub max_capacity: u64,
+    /// How long a value read from the shared backend is reused (in seconds)
+    pub local_ttl_seconds: u64,
+    /// Prefixes of the keys kept in process memory
+    pub cached_prefixes: Vec<String>,
+}
+
+impl Default for TieredCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_capacity: 10_000,
+            local_ttl_seconds: 5,
+            // Account locks as stored by `AccountLockService` through `VerificationCache`
+            cached_prefixes: vec!["verification:code:account_lock:".to_string()],
+        }
+    }
+}
+
+/// Cache backend keeping recent reads in process memory in front of a
+/// shared backend
+pub struct TieredCacheBackend {
+    /// Values read from the shared backend, None for missing keys
+    local: Cache<String, Option<String>>,
+    shared: Arc<dyn CacheBackend>,
+    cached_prefixes: Vec<String>,
+    publisher: Option<Arc<dyn ClusterEventPublisherTrait>>,
+}
+
+impl TieredCacheBackend {
+    /// Create a backend caching reads of `shared` in process memory
+    pub fn new(shared: Arc<dyn CacheBackend>, config: TieredCacheConfig) -> Self {
+        Self {
+            local: Cache::builder()
+                .max_capacity(config.max_capacity)
+                .time_to_live(Duration::from_secs(config.local_ttl_seconds))
+                .build(),
+            shared,
+            cached_prefixes: config.cached_prefixes,
+            publisher: None,
+        }
+    }
+
+    /// Publish the keys written through this backend so that other
+    /// instances drop their copies
+    pub fn with_invalidation_publisher(mut self, publisher: Arc<dyn ClusterEventPublisherTrait>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Drop the copies of keys changed by another instance
+    pub async fn invalidate_local(&self, keys: &[String]) {
+        for key in keys {
+            self.local.invalidate(key).await;
+        }
+    }
+
+    /// Whether a key is kept in process memory
+    fn is_cached(&self, key: &str) -> bool {
+        self.cached_prefixes.iter().any(|prefix| key.starts_with(prefix.as_str()))
+    }
+
+    /// Drop the copies of a key written through this backend, here and on
+    /// the other instances
+    async fn invalidate(&self, key: &str) {
+        if !self.is_cached(key) {
+            return;
+        }
+
+        self.local.invalidate(key).await;
+
+        if let Some(publisher) = &self.publisher {
+            let event = ClusterEvent::CacheInvalidated {
+                keys: vec![key.to_string()],
+            };
+            if let Err(e) = publisher.publish(&event).await {
+                // Other instances read their copy until it expires
+                warn!(error = %e, "Failed to publish cache invalidation of '{}'", key);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for TieredCacheBackend {
+    fn name(&self) -> &'static str {
+        self.shared.name()
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        if !self.is_cached(key) {
+            return self.shared.get(key).await;
+        }
+
+        if let Some(value) = self.local.get(key).await {
+            return Ok(value);
+        }
+
+        let value = self.shared.get(key).await?;
+        self.local.insert(key.to_string(), value.clone()).await;
+        Ok(value)
+    }
+
+    async fn set(&self, key: &str, value: &str, expiry_seconds: u64) -> Result<(), InfrastructureError> {
+        let result = self.shared.set(key, value, expiry_seconds).await;
+        self.invalidate(key).await;
+        result
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, InfrastructureError> {
+        let result = self.shared.delete(key).await;
+        self.invalidate(key).await;
+        result
+    }
+
+    async fn incr(&self, key: &str, expiry_seconds: Option<u64>) -> Result<i64, InfrastructureError> {
+        let result = self.shared.incr(key, expiry_seconds).await;
+        self.invalidate(key).await;
+        result
+    }
+
+    async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError> {
+        let result = self.shared.expire(key, expiry_seconds).await;
+        self.invalidate(key).await;
+        result
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, InfrastructureError> {
+        if self.is_cached(key) {
+            Ok(self.get(key).await?.is_some())
+        } else {
+            self.shared.exists(key).await
+        }
+    }
+
+    async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError> {
+        self.shared.ttl(key).await
+    }
+}
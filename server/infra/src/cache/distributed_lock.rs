@@ -0,0 +1,109 @@
+//! Distributed locks over Redis
+//!
+//! With a single Redis deployment, including one behind Sentinel, a lock
+//! is a key taken with `SET NX PX` on that deployment. A failover can lose
+//! a lock that was not yet replicated; deployments that cannot afford this
+//! configure several independent masters and use Redlock, which takes the
+//! lock on a majority of them.
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use re_core::services::lock::DistributedLockTrait;
+
+use crate::cache::RedisClient;
+use crate::InfrastructureError;
+
+/// Lock store over one Redis deployment or, with Redlock, several
+/// independent Redis masters
+#[derive(Clone)]
+pub struct RedisDistributedLock {
+    /// Clients of the masters locks are taken on
+    clients: Vec<RedisClient>,
+}
+
+impl RedisDistributedLock {
+    /// Create a lock store taking locks on a single Redis deployment
+    pub fn new(client: RedisClient) -> Self {
+        Self { clients: vec![client] }
+    }
+
+    /// Create a lock store using Redlock across independent masters
+    ///
+    /// The masters must not replicate each other; an odd number, usually
+    /// 3 or 5, tolerates the most failures.
+    ///
+    /// # Returns
+    /// * `Err(InfrastructureError::Config)` - No master was given
+    pub fn redlock(clients: Vec<RedisClient>) -> Result<Self, InfrastructureError> {
+        if clients.is_empty() {
+            return Err(InfrastructureError::Config(
+                "Redlock requires at least one Redis master".to_string(),
+            ));
+        }
+        Ok(Self { clients })
+    }
+
+    /// Number of masters that must agree on a lock
+    pub fn quorum(&self) -> usize {
+        self.clients.len() / 2 + 1
+    }
+
+    /// Lifetime left to a lock taken at `started`, allowing for clock drift
+    /// between the masters
+    pub(crate) fn validity(ttl: Duration, started: Instant) -> Duration {
+        let drift = ttl / 100 + Duration::from_millis(2);
+        ttl.saturating_sub(started.elapsed() + drift)
+    }
+
+    /// Release a lock on every master, ignoring failures
+    async fn release_everywhere(&self, key: &str, token: &str) {
+        join_all(self.clients.iter().map(|client| client.release_lock(key, token))).await;
+    }
+}
+
+#[async_trait]
+impl DistributedLockTrait for RedisDistributedLock {
+    async fn try_acquire(&self, key: &str, ttl: Duration) -> Result<Option<String>, String> {
+        if let [client] = self.clients.as_slice() {
+            return client.acquire_lock(key, ttl).await.map_err(|e| e.to_string());
+        }
+
+        let token = uuid::Uuid::new_v4().to_string();
+        let started = Instant::now();
+        let results = join_all(
+            self.clients
+                .iter()
+                .map(|client| client.acquire_lock_with_token(key, &token, ttl)),
+        )
+        .await;
+
+        let acquired = results.iter().filter(|result| matches!(result, Ok(true))).count();
+        let failed = results.iter().filter(|result| result.is_err()).count();
+        if acquired >= self.quorum() && !Self::validity(ttl, started).is_zero() {
+            return Ok(Some(token));
+        }
+
+        // Give back the masters taken so that a retry can succeed
+        self.release_everywhere(key, &token).await;
+
+        if failed >= self.quorum() {
+            warn!(lock = key, failed, masters = self.clients.len(), "Redlock masters unreachable");
+            return Err(format!("{} of {} Redis masters unreachable", failed, self.clients.len()));
+        }
+        Ok(None)
+    }
+
+    async fn release(&self, key: &str, token: &str) -> Result<bool, String> {
+        let results = join_all(self.clients.iter().map(|client| client.release_lock(key, token))).await;
+
+        if results.iter().all(|result| result.is_err()) {
+            let error = results.into_iter().find_map(Result::err).map(|e| e.to_string());
+            return Err(error.unwrap_or_default());
+        }
+        let released = results.iter().filter(|result| matches!(result, Ok(true))).count();
+        Ok(released >= self.quorum())
+    }
+}
@@ -0,0 +1,88 @@
+//! Redis pub/sub event bus for notifications between API instances
+//!
+//! Cluster events are published as JSON on the `cluster_events` channel.
+//! Every instance subscribes and hands what it receives to its event
+//! handler, its own events included. Redis does not keep messages for
+//! disconnected subscribers, so events published while an instance is
+//! reconnecting are lost for that instance.
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use re_core::services::cluster::{ClusterEvent, ClusterEventHandlerTrait, ClusterEventPublisherTrait};
+use re_core::services::token::{TokenRevocation, TokenRevocationBroadcasterTrait};
+
+use crate::cache::redis_client::RedisClient;
+use crate::InfrastructureError;
+
+/// Channel the cluster events are published on
+pub const CLUSTER_EVENT_CHANNEL: &str = "cluster_events";
+
+/// Redis-based bus of cluster events
+pub struct RedisClusterEventBus {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisClusterEventBus {
+    /// Create a new Redis-based event bus
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    /// Hand the events published by any instance to a handler
+    ///
+    /// Runs until the subscription ends, which happens when the connection
+    /// to Redis drops; callers subscribe again by calling it once more.
+    /// Events that cannot be decoded, such as event types introduced by a
+    /// newer release, are skipped.
+    pub async fn listen(&self, handler: &dyn ClusterEventHandlerTrait) -> Result<(), InfrastructureError> {
+        let mut pubsub = self.redis_client.subscribe(&[CLUSTER_EVENT_CHANNEL]).await?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!("Ignoring unreadable cluster event: {}", e);
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<ClusterEvent>(&payload) {
+                Ok(event) => {
+                    debug!("Received cluster event {}", event.event_type());
+                    handler.handle(event).await;
+                }
+                Err(e) => warn!("Ignoring malformed cluster event: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ClusterEventPublisherTrait for RedisClusterEventBus {
+    async fn publish(&self, event: &ClusterEvent) -> Result<(), String> {
+        let payload = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to encode cluster event: {}", e))?;
+
+        self.redis_client
+            .publish(CLUSTER_EVENT_CHANNEL, &payload)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to publish cluster event: {}", e))
+    }
+}
+
+#[async_trait]
+impl TokenRevocationBroadcasterTrait for RedisClusterEventBus {
+    async fn broadcast(&self, revocation: &TokenRevocation) -> Result<(), String> {
+        self.publish(&ClusterEvent::TokenRevoked {
+            revocation: revocation.clone(),
+        })
+        .await
+    }
+}
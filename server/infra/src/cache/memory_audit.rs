@@ -0,0 +1,430 @@
+//! Redis memory budget monitoring and key TTL audit
+//!
+//! Every key the application writes to Redis is meant to expire: codes,
+//! counters and locks are all short-lived. A key written without a TTL,
+//! whether by a bug or by hand, stays until Redis runs out of memory. This
+//! job periodically samples each key namespace, reports its memory usage and
+//! the keys missing a TTL, and optionally gives such keys a TTL.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redis::aio::MultiplexedConnection;
+use tracing::{error, info, warn};
+
+use crate::cache::redis_client::RedisClient;
+use crate::InfrastructureError;
+
+/// Number of keys requested per SCAN call
+const SCAN_BATCH_SIZE: usize = 100;
+
+/// Key namespaces audited by default
+const DEFAULT_NAMESPACES: [&str; 9] = [
+    "otp:",
+    "verification:",
+    "rate_limit:",
+    "account_lock:",
+    "failed_attempts:",
+    "session_activity:",
+    "ip_access:",
+    "pow:",
+    "user_cache:",
+];
+
+/// A key namespace to audit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisNamespaceConfig {
+    /// Key prefix of the namespace, e.g. `otp:`, within the key prefix of
+    /// the Redis client
+    pub prefix: String,
+    /// TTL given to keys of the namespace that have none, in seconds
+    pub enforce_ttl_seconds: Option<u64>,
+}
+
+impl RedisNamespaceConfig {
+    /// Audit a namespace without enforcing TTLs
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            enforce_ttl_seconds: None,
+        }
+    }
+
+    /// Give keys of the namespace that have no TTL this one
+    pub fn with_enforced_ttl(mut self, seconds: u64) -> Self {
+        self.enforce_ttl_seconds = Some(seconds);
+        self
+    }
+}
+
+/// Configuration for the Redis memory audit
+#[derive(Debug, Clone)]
+pub struct RedisMemoryAuditConfig {
+    /// How often to run the audit (in seconds)
+    pub interval_seconds: u64,
+    /// Maximum number of keys sampled per namespace
+    pub sample_size: usize,
+    /// Memory budget in bytes; Redis `maxmemory` is used when unset
+    pub memory_budget_bytes: Option<u64>,
+    /// Namespaces to audit
+    pub namespaces: Vec<RedisNamespaceConfig>,
+    /// Whether to enable the audit
+    pub enabled: bool,
+}
+
+impl Default for RedisMemoryAuditConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 900, // Run every 15 minutes
+            sample_size: 1000,
+            memory_budget_bytes: None,
+            namespaces: DEFAULT_NAMESPACES
+                .iter()
+                .map(|prefix| RedisNamespaceConfig::new(*prefix))
+                .collect(),
+            enabled: true,
+        }
+    }
+}
+
+impl RedisMemoryAuditConfig {
+    /// Create from environment variables
+    ///
+    /// `REDIS_AUDIT_NAMESPACES` lists the key prefixes to audit, separated by
+    /// commas. `REDIS_AUDIT_ENFORCE_TTLS` lists `prefix=seconds` pairs of
+    /// namespaces whose keys are given a TTL when they have none; such
+    /// namespaces are audited even when not listed.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let enabled = std::env::var("REDIS_AUDIT_ENABLED")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.enabled);
+        let interval_seconds = std::env::var("REDIS_AUDIT_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.interval_seconds);
+        let sample_size = std::env::var("REDIS_AUDIT_SAMPLE_SIZE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(defaults.sample_size);
+        let memory_budget_bytes = std::env::var("REDIS_MEMORY_BUDGET_BYTES")
+            .ok()
+            .and_then(|value| value.parse().ok());
+
+        let mut namespaces = match std::env::var("REDIS_AUDIT_NAMESPACES") {
+            Ok(prefixes) => parse_namespaces(&prefixes),
+            Err(_) => defaults.namespaces,
+        };
+        if let Ok(ttls) = std::env::var("REDIS_AUDIT_ENFORCE_TTLS") {
+            apply_enforced_ttls(&mut namespaces, &ttls);
+        }
+
+        Self {
+            interval_seconds,
+            sample_size,
+            memory_budget_bytes,
+            namespaces,
+            enabled,
+        }
+    }
+}
+
+/// Parse comma-separated key prefixes
+pub(crate) fn parse_namespaces(prefixes: &str) -> Vec<RedisNamespaceConfig> {
+    prefixes
+        .split(',')
+        .map(str::trim)
+        .filter(|prefix| !prefix.is_empty())
+        .map(RedisNamespaceConfig::new)
+        .collect()
+}
+
+/// Apply comma-separated `prefix=seconds` pairs, adding missing namespaces
+///
+/// Malformed pairs are skipped with a warning.
+pub(crate) fn apply_enforced_ttls(namespaces: &mut Vec<RedisNamespaceConfig>, ttls: &str) {
+    for pair in ttls.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+        let parsed = pair
+            .split_once('=')
+            .and_then(|(prefix, seconds)| Some((prefix.trim(), seconds.trim().parse::<u64>().ok()?)))
+            .filter(|(prefix, seconds)| !prefix.is_empty() && *seconds > 0);
+
+        let Some((prefix, seconds)) = parsed else {
+            warn!("Ignoring malformed REDIS_AUDIT_ENFORCE_TTLS entry '{}'", pair);
+            continue;
+        };
+
+        match namespaces.iter_mut().find(|namespace| namespace.prefix == prefix) {
+            Some(namespace) => namespace.enforce_ttl_seconds = Some(seconds),
+            None => namespaces.push(RedisNamespaceConfig::new(prefix).with_enforced_ttl(seconds)),
+        }
+    }
+}
+
+/// Usage of a key namespace, measured on a sample of its keys
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamespaceUsage {
+    /// Key prefix of the namespace
+    pub prefix: String,
+    /// Number of keys sampled
+    pub keys_sampled: u64,
+    /// Memory used by the sampled keys, in bytes
+    pub memory_bytes: u64,
+    /// Sampled keys that had no TTL
+    pub keys_without_ttl: u64,
+    /// Keys given a TTL by this audit
+    pub ttls_enforced: u64,
+    /// Whether every key of the namespace was sampled
+    pub fully_scanned: bool,
+}
+
+/// Outcome of one audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedisMemoryReport {
+    /// Memory used by Redis, in bytes
+    pub used_memory_bytes: u64,
+    /// Memory budget, in bytes, if one is configured or Redis has `maxmemory` set
+    pub budget_bytes: Option<u64>,
+    /// Usage of each audited namespace
+    pub namespaces: Vec<NamespaceUsage>,
+    /// When the audit ran
+    pub sampled_at: DateTime<Utc>,
+}
+
+impl RedisMemoryReport {
+    /// Share of the memory budget in use
+    pub fn budget_used_ratio(&self) -> Option<f64> {
+        self.budget_bytes
+            .filter(|budget| *budget > 0)
+            .map(|budget| self.used_memory_bytes as f64 / budget as f64)
+    }
+
+    /// Whether Redis uses more memory than the budget
+    pub fn is_over_budget(&self) -> bool {
+        self.budget_bytes
+            .is_some_and(|budget| self.used_memory_bytes > budget)
+    }
+
+    /// Sampled keys without a TTL across all namespaces
+    pub fn keys_without_ttl(&self) -> u64 {
+        self.namespaces.iter().map(|usage| usage.keys_without_ttl).sum()
+    }
+}
+
+/// Read a numeric field from the output of Redis `INFO`
+pub(crate) fn parse_info_field(info: &str, field: &str) -> Option<u64> {
+    info.lines()
+        .filter_map(|line| line.trim().split_once(':'))
+        .find(|(name, _)| *name == field)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// Job that audits Redis memory usage and key TTLs
+pub struct RedisMemoryAuditJob {
+    redis_client: Arc<RedisClient>,
+    config: RedisMemoryAuditConfig,
+    latest_report: RwLock<Option<RedisMemoryReport>>,
+}
+
+impl RedisMemoryAuditJob {
+    /// Create a new Redis memory audit job
+    pub fn new(redis_client: Arc<RedisClient>, config: RedisMemoryAuditConfig) -> Self {
+        Self {
+            redis_client,
+            config,
+            latest_report: RwLock::new(None),
+        }
+    }
+
+    /// Report of the latest audit, if one has completed
+    pub fn metrics(&self) -> Option<RedisMemoryReport> {
+        self.latest_report.read().ok().and_then(|report| report.clone())
+    }
+
+    /// Run a single audit
+    ///
+    /// # Returns
+    /// * `Ok(RedisMemoryReport)` - Memory usage and TTL findings
+    /// * `Err(InfrastructureError)` - If Redis could not be queried
+    pub async fn run_audit(&self) -> Result<RedisMemoryReport, InfrastructureError> {
+        let mut conn = self.redis_client.get_connection();
+
+        let info: String = redis::cmd("INFO").arg("memory").query_async(&mut conn).await?;
+        let used_memory_bytes = parse_info_field(&info, "used_memory").unwrap_or(0);
+        let budget_bytes = self
+            .config
+            .memory_budget_bytes
+            .or_else(|| parse_info_field(&info, "maxmemory").filter(|max| *max > 0));
+
+        let mut namespaces = Vec::with_capacity(self.config.namespaces.len());
+        for namespace in &self.config.namespaces {
+            namespaces.push(self.audit_namespace(&mut conn, namespace).await?);
+        }
+
+        let report = RedisMemoryReport {
+            used_memory_bytes,
+            budget_bytes,
+            namespaces,
+            sampled_at: Utc::now(),
+        };
+        self.log_report(&report);
+
+        if let Ok(mut latest) = self.latest_report.write() {
+            *latest = Some(report.clone());
+        }
+
+        Ok(report)
+    }
+
+    /// Sample the keys of a namespace, enforcing its TTL if configured
+    async fn audit_namespace(
+        &self,
+        conn: &mut MultiplexedConnection,
+        namespace: &RedisNamespaceConfig,
+    ) -> Result<NamespaceUsage, InfrastructureError> {
+        let mut usage = NamespaceUsage {
+            prefix: namespace.prefix.clone(),
+            ..Default::default()
+        };
+        // Only this environment's keys when several share the instance
+        let pattern = format!("{}*", self.redis_client.key(&namespace.prefix));
+        let mut cursor: u64 = 0;
+
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH_SIZE)
+                .query_async(conn)
+                .await?;
+
+            let remaining = self.config.sample_size.saturating_sub(usage.keys_sampled as usize);
+            for key in keys.iter().take(remaining) {
+                self.audit_key(conn, namespace, key, &mut usage).await?;
+            }
+
+            cursor = next_cursor;
+            if cursor == 0 {
+                usage.fully_scanned = keys.len() <= remaining;
+                break;
+            }
+            if usage.keys_sampled as usize >= self.config.sample_size {
+                break;
+            }
+        }
+
+        Ok(usage)
+    }
+
+    /// Measure a key and give it a TTL if it lacks one
+    async fn audit_key(
+        &self,
+        conn: &mut MultiplexedConnection,
+        namespace: &RedisNamespaceConfig,
+        key: &str,
+        usage: &mut NamespaceUsage,
+    ) -> Result<(), InfrastructureError> {
+        let (ttl, memory): (i64, Option<u64>) = redis::pipe()
+            .cmd("TTL")
+            .arg(key)
+            .cmd("MEMORY")
+            .arg("USAGE")
+            .arg(key)
+            .query_async(conn)
+            .await?;
+
+        // A TTL of -2 means the key expired since it was scanned
+        if ttl == -2 {
+            return Ok(());
+        }
+
+        usage.keys_sampled += 1;
+        usage.memory_bytes += memory.unwrap_or(0);
+
+        if ttl == -1 {
+            usage.keys_without_ttl += 1;
+
+            if let Some(seconds) = namespace.enforce_ttl_seconds {
+                let applied: bool = redis::cmd("EXPIRE")
+                    .arg(key)
+                    .arg(seconds)
+                    .query_async(conn)
+                    .await?;
+                if applied {
+                    usage.ttls_enforced += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Log the findings of an audit
+    fn log_report(&self, report: &RedisMemoryReport) {
+        info!(
+            used_memory_bytes = report.used_memory_bytes,
+            budget_bytes = ?report.budget_bytes,
+            keys_without_ttl = report.keys_without_ttl(),
+            "Redis memory audit completed"
+        );
+
+        if report.is_over_budget() {
+            warn!(
+                used_memory_bytes = report.used_memory_bytes,
+                budget_bytes = ?report.budget_bytes,
+                "Redis memory usage is over budget"
+            );
+        }
+
+        for usage in &report.namespaces {
+            info!(
+                namespace = %usage.prefix,
+                keys_sampled = usage.keys_sampled,
+                memory_bytes = usage.memory_bytes,
+                fully_scanned = usage.fully_scanned,
+                "Redis namespace usage"
+            );
+
+            if usage.keys_without_ttl > usage.ttls_enforced {
+                warn!(
+                    namespace = %usage.prefix,
+                    keys_without_ttl = usage.keys_without_ttl,
+                    ttls_enforced = usage.ttls_enforced,
+                    "Redis keys without a TTL found"
+                );
+            }
+        }
+    }
+
+    /// Start the audit as a background task
+    ///
+    /// This spawns a tokio task that runs the audit at regular intervals
+    pub fn start_background_task(self: Arc<Self>) {
+        if !self.config.enabled {
+            warn!("Redis memory audit is disabled");
+            return;
+        }
+
+        let interval = Duration::from_secs(self.config.interval_seconds);
+
+        tokio::spawn(async move {
+            info!(
+                "Redis memory audit started (interval: {}s)",
+                self.config.interval_seconds
+            );
+
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_audit().await {
+                    error!("Redis memory audit failed: {}", e);
+                }
+            }
+        });
+    }
+}
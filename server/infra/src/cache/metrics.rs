@@ -0,0 +1,117 @@
+//! Cache operation metrics
+//!
+//! Counts the calls, hits, misses and errors of every cache operation and
+//! keeps a latency histogram per operation, so TTLs and connection pools
+//! can be sized from real traffic. Metrics cover the calls made by this
+//! instance since it started.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the latency histogram buckets, in microseconds; a last
+/// bucket counts the slower calls
+pub const LATENCY_BUCKETS_US: [u64; 10] = [
+    500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 1_000_000,
+];
+
+/// Metrics of one cache operation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheOperationMetrics {
+    /// Number of calls
+    pub calls: u64,
+    /// Lookups that found the key
+    pub hits: u64,
+    /// Lookups that did not find the key
+    pub misses: u64,
+    /// Calls that failed
+    pub errors: u64,
+    /// Total duration of all calls, in microseconds
+    pub total_duration_us: u64,
+    /// Duration of the slowest call, in microseconds
+    pub max_duration_us: u64,
+    /// Calls per latency bucket, one more than [`LATENCY_BUCKETS_US`]
+    pub latency_buckets: Vec<u64>,
+}
+
+impl CacheOperationMetrics {
+    /// Average duration of a call, in microseconds
+    pub fn average_duration_us(&self) -> u64 {
+        self.total_duration_us.checked_div(self.calls).unwrap_or(0)
+    }
+
+    /// Share of lookups that found the key, None if none were made
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+/// Recorder of the metrics of a cache
+#[derive(Debug, Default)]
+pub struct CacheMetrics {
+    operations: Mutex<HashMap<&'static str, CacheOperationMetrics>>,
+}
+
+impl CacheMetrics {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a finished call
+    ///
+    /// # Arguments
+    /// * `operation` - Name of the operation, e.g. `get`
+    /// * `started` - When the call started
+    /// * `result` - Result of the call
+    /// * `hit` - Whether a successful call found the key, None for calls
+    ///   that are not lookups
+    pub fn record<T, E>(
+        &self,
+        operation: &'static str,
+        started: Instant,
+        result: &Result<T, E>,
+        hit: impl FnOnce(&T) -> Option<bool>,
+    ) {
+        let hit = result.as_ref().ok().and_then(hit);
+        self.record_call(operation, started.elapsed(), result.is_err(), hit);
+    }
+
+    /// Record a finished call from its duration and outcome
+    pub fn record_call(&self, operation: &'static str, elapsed: Duration, failed: bool, hit: Option<bool>) {
+        let duration_us = elapsed.as_micros() as u64;
+        let bucket = LATENCY_BUCKETS_US
+            .iter()
+            .position(|bound| duration_us <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_US.len());
+
+        let Ok(mut operations) = self.operations.lock() else {
+            return;
+        };
+        let entry = operations.entry(operation).or_insert_with(|| CacheOperationMetrics {
+            latency_buckets: vec![0; LATENCY_BUCKETS_US.len() + 1],
+            ..Default::default()
+        });
+        entry.calls += 1;
+        entry.total_duration_us += duration_us;
+        entry.max_duration_us = entry.max_duration_us.max(duration_us);
+        entry.latency_buckets[bucket] += 1;
+        if failed {
+            entry.errors += 1;
+        }
+        match hit {
+            Some(true) => entry.hits += 1,
+            Some(false) => entry.misses += 1,
+            None => {}
+        }
+    }
+
+    /// Metrics of each operation called so far
+    pub fn snapshot(&self) -> HashMap<&'static str, CacheOperationMetrics> {
+        self.operations
+            .lock()
+            .map(|operations| operations.clone())
+            .unwrap_or_default()
+    }
+}
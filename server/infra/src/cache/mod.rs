@@ -2,13 +2,35 @@
 //! 
 //! This module provides Redis caching functionality for the RenovEasy application,
 //! including connection pooling, retry logic, and common cache operations.
+//! Verification codes and OTPs can also be kept in memory or in memcached
+//! through the [`backend::CacheBackend`] trait.
 
+pub mod audit_stream;
+pub mod backend;
+pub mod distributed_lock;
+pub mod event_bus;
+pub mod memory_audit;
+pub mod metrics;
 pub mod otp_storage;
 pub mod redis_client;
+pub mod session_store;
+pub mod token_blacklist_store;
+pub mod user_cache_store;
 pub mod verification_cache;
 
+pub use audit_stream::{AuditStreamConfig, AuditStreamWorker, RedisStreamAuditLogRepository};
+pub use backend::{CacheBackend, InMemoryCacheBackend, MemcachedBackend, TieredCacheBackend, TieredCacheConfig};
+pub use distributed_lock::RedisDistributedLock;
+pub use event_bus::RedisClusterEventBus;
+pub use memory_audit::{
+    NamespaceUsage, RedisMemoryAuditConfig, RedisMemoryAuditJob, RedisMemoryReport, RedisNamespaceConfig,
+};
+pub use metrics::{CacheMetrics, CacheOperationMetrics};
 pub use otp_storage::{OtpRedisStorage, OtpStorageConfig, OtpMetadata};
 pub use redis_client::RedisClient;
+pub use session_store::RedisSessionStore;
+pub use token_blacklist_store::RedisTokenBlacklistStore;
+pub use user_cache_store::RedisUserCacheStore;
 pub use verification_cache::VerificationCache;
 
 // Re-export commonly used types
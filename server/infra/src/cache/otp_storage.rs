@@ -1,11 +1,14 @@
 //! OTP Redis Storage with encryption and fallback support
 //!
-//! This module implements secure OTP storage in Redis with:
+//! This module implements secure OTP storage in Redis, or any other
+//! [`CacheBackend`], with:
 //! - AES-256-GCM encryption for OTP codes
 //! - Automatic TTL management (5 minutes)
 //! - Metadata tracking (attempts, creation time, expiry)
 //! - Database fallback when Redis fails
 //! - Comprehensive security logging
+//!
+//! On Redis, keys are put under the key prefix of the client.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -19,7 +22,7 @@ use re_core::services::encryption::{
     otp_encryption::{AesGcmOtpEncryption, EncryptedOtp, OtpEncryptionConfig},
 };
 
-use crate::cache::RedisClient;
+use crate::cache::{CacheBackend, RedisClient};
 use crate::database::repositories::otp_repository::OtpRepository;
 
 /// Default OTP expiration time in seconds (5 minutes)
@@ -93,8 +96,8 @@ impl Default for OtpStorageConfig {
 
 /// OTP Redis storage implementation with encryption and fallback
 pub struct OtpRedisStorage {
-    /// Redis client for cache operations
-    redis_client: RedisClient,
+    /// Backend storing encrypted OTPs and their metadata
+    backend: Arc<dyn CacheBackend>,
     /// OTP encryption service
     encryption_service: Arc<AesGcmOtpEncryption>,
     /// Database repository for fallback
@@ -112,11 +115,24 @@ impl OtpRedisStorage {
         encryption_config: OtpEncryptionConfig,
         otp_repository: Option<Arc<OtpRepository>>,
         config: OtpStorageConfig,
+    ) -> DomainResult<Self> {
+        Self::with_backend(Arc::new(redis_client), encryption_config, otp_repository, config)
+    }
+
+    /// Create an OTP storage service on any cache backend
+    ///
+    /// The database fallback and the `StorageBackend::Redis` reported for
+    /// cached OTPs apply to whichever backend is given.
+    pub fn with_backend(
+        backend: Arc<dyn CacheBackend>,
+        encryption_config: OtpEncryptionConfig,
+        otp_repository: Option<Arc<OtpRepository>>,
+        config: OtpStorageConfig,
     ) -> DomainResult<Self> {
         let encryption_service = Arc::new(AesGcmOtpEncryption::new(encryption_config)?);
 
         Ok(Self {
-            redis_client,
+            backend,
             encryption_service,
             otp_repository,
             config,
@@ -163,14 +179,14 @@ impl OtpRedisStorage {
 
         // Store both OTP and metadata with TTL
         for attempt in 0..self.config.max_redis_retries {
-            match self.redis_client
-                .set_with_expiry(&otp_key, &otp_json, self.config.expiry_seconds)
+            match self.backend
+                .set(&otp_key, &otp_json, self.config.expiry_seconds)
                 .await
             {
                 Ok(_) => {
                     // Store metadata
-                    let _ = self.redis_client
-                        .set_with_expiry(&metadata_key, &metadata_json, self.config.expiry_seconds)
+                    let _ = self.backend
+                        .set(&metadata_key, &metadata_json, self.config.expiry_seconds)
                         .await;
 
                     debug!(
@@ -231,7 +247,7 @@ impl OtpRedisStorage {
     async fn get_from_redis(&self, phone: &str) -> Result<Option<EncryptedOtp>, DomainError> {
         let otp_key = Self::format_otp_key(phone);
 
-        match self.redis_client.get(&otp_key).await
+        match self.backend.get(&otp_key).await
             .map_err(|e| DomainError::Internal {
                 message: format!("Failed to get OTP from Redis: {}", e),
             })? {
@@ -272,8 +288,8 @@ impl OtpRedisStorage {
         let metadata_key = Self::format_metadata_key(phone);
 
         // Delete from Redis
-        let _ = self.redis_client.delete(&otp_key).await;
-        let _ = self.redis_client.delete(&metadata_key).await;
+        let _ = self.backend.delete(&otp_key).await;
+        let _ = self.backend.delete(&metadata_key).await;
 
         // Delete from database if configured
         if let Some(repo) = &self.otp_repository {
@@ -385,7 +401,7 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
         let metadata_key = Self::format_metadata_key(phone);
 
         // Get current metadata
-        match self.redis_client.get(&metadata_key).await {
+        match self.backend.get(&metadata_key).await {
             Ok(Some(metadata_json)) => {
                 let mut metadata: OtpMetadata = serde_json::from_str(&metadata_json)
                     .map_err(|e| DomainError::Internal {
@@ -402,12 +418,12 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
                     })?;
 
                 // Calculate remaining TTL
-                let ttl = self.redis_client.ttl(&metadata_key).await
+                let ttl = self.backend.ttl(&metadata_key).await
                     .unwrap_or(Some(self.config.expiry_seconds as i64))
                     .unwrap_or(self.config.expiry_seconds as i64) as u64;
 
-                self.redis_client
-                    .set_with_expiry(&metadata_key, &updated_json, ttl)
+                self.backend
+                    .set(&metadata_key, &updated_json, ttl)
                     .await
                     .map_err(|e| DomainError::Internal {
                         message: format!("Failed to update metadata: {}", e)
@@ -440,7 +456,7 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
         let otp_key = Self::format_otp_key(phone);
 
         // Check Redis first
-        match self.redis_client.exists(&otp_key).await {
+        match self.backend.exists(&otp_key).await {
             Ok(true) => Ok(true),
             Ok(false) => {
                 // Not in Redis, check database if fallback is enabled
@@ -480,7 +496,7 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
     async fn get_encrypted_otp_ttl(&self, phone: &str) -> DomainResult<Option<i64>> {
         let otp_key = Self::format_otp_key(phone);
 
-        self.redis_client.ttl(&otp_key).await
+        self.backend.ttl(&otp_key).await
             .map_err(|e| DomainError::Internal {
                 message: format!("Failed to get TTL: {}", e)
             })
@@ -491,8 +507,8 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
         let metadata_key = Self::format_metadata_key(phone);
 
         // Clear from Redis
-        let _ = self.redis_client.delete(&otp_key).await;
-        let _ = self.redis_client.delete(&metadata_key).await;
+        let _ = self.backend.delete(&otp_key).await;
+        let _ = self.backend.delete(&metadata_key).await;
 
         // Clear from database if configured
         if let Some(repo) = &self.otp_repository {
@@ -514,6 +530,6 @@ impl EncryptedCacheServiceTrait for OtpRedisStorage {
 
     async fn is_redis_available(&self) -> bool {
         // Try a simple ping to check Redis availability
-        self.redis_client.get("ping:test").await.is_ok()
+        self.backend.get("ping:test").await.is_ok()
     }
 }
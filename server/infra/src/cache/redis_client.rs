@@ -4,32 +4,79 @@
 //! and basic cache operations for the RenovEasy infrastructure layer.
 //! It supports operations like set with expiry, get, and delete for caching
 //! verification codes, session data, and rate limiting counters.
+//!
+//! With Redis Sentinel configured, the client asks the sentinels for the
+//! current master instead of connecting to a fixed address. When an
+//! operation fails because the master went away or was demoted to a
+//! replica, the master is resolved again before the operation is retried,
+//! so OTPs and sessions keep being stored across a failover.
+//!
+//! With a `key_prefix` configured, every key and pub/sub channel is put
+//! under `{key_prefix}:`, so several environments can share one Redis
+//! instance. The operations of the client apply the prefix themselves;
+//! code sending commands on [`RedisClient::get_connection`] applies it with
+//! [`RedisClient::key`].
 
 use redis::{
-    aio::MultiplexedConnection,
-    AsyncCommands, Client, RedisError, RedisResult,
+    aio::{MultiplexedConnection, PubSub},
+    sentinel::{Sentinel, SentinelNodeConnectionInfo},
+    AsyncCommands, Client, IntoConnectionInfo, RedisError, RedisResult,
 };
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
 use re_shared::config::cache::CacheConfig;
+use crate::cache::metrics::CacheMetrics;
 use crate::InfrastructureError;
 
+/// Deletes a lock only if it still holds the caller's token
+const RELEASE_LOCK_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+else
+    return 0
+end
+"#;
+
+/// Connection to the Redis server commands are sent to
+struct MasterConnection {
+    /// Client for the server, for dedicated connections
+    client: Client,
+    /// Redis multiplexed connection for async operations
+    connection: MultiplexedConnection,
+    /// Number of times the master was resolved again
+    generation: u64,
+}
+
+/// Sentinels and how to reach the master they report
+struct SentinelResolver {
+    sentinel: Sentinel,
+    master_name: String,
+    /// Password and database of the master
+    node: SentinelNodeConnectionInfo,
+}
+
 /// Redis cache client with connection pooling and retry logic
 /// 
 /// Provides a thread-safe, async Redis client with automatic connection
 /// management and retry capabilities for resilient cache operations.
 #[derive(Clone)]
 pub struct RedisClient {
-    /// Redis multiplexed connection for async operations
-    connection: MultiplexedConnection,
+    /// Connection to the current master
+    master: Arc<RwLock<MasterConnection>>,
+    /// Sentinels resolving the master, if configured
+    sentinel: Option<Arc<Mutex<SentinelResolver>>>,
     /// Configuration used to create this client
     config: CacheConfig,
     /// Maximum number of retry attempts for operations
     max_retries: u32,
     /// Base delay between retries (exponential backoff)
     retry_delay_ms: u64,
+    /// Metrics of the operations, shared by the clones of this client
+    metrics: Arc<CacheMetrics>,
 }
 
 impl RedisClient {
@@ -70,21 +117,37 @@ impl RedisClient {
         max_retries: u32,
         retry_delay_ms: u64,
     ) -> Result<Self, InfrastructureError> {
-        info!(
-            "Creating Redis client with URL: {} and pool size: {}",
-            mask_url(config.redis_url()),
-            config.pool_size()
-        );
-
-        // Parse Redis URL and create client
-        let client = Client::open(config.redis_url()).map_err(|e| {
-            error!("Failed to parse Redis URL: {}", e);
-            InfrastructureError::Config(format!("Invalid Redis URL: {}", e))
-        })?;
+        config.validate_sentinels().map_err(InfrastructureError::Config)?;
+
+        let (client, sentinel) = if config.uses_sentinel() {
+            info!(
+                "Creating Redis client for master {} via sentinels {} and pool size: {}",
+                config.sentinel_master.as_deref().unwrap_or_default(),
+                config.sentinels.iter().map(|url| mask_url(url)).collect::<Vec<_>>().join(", "),
+                config.pool_size()
+            );
+
+            let mut resolver = SentinelResolver::new(&config)?;
+            let client = resolver.resolve(max_retries, retry_delay_ms).await?;
+            (client, Some(Arc::new(Mutex::new(resolver))))
+        } else {
+            info!(
+                "Creating Redis client with URL: {} and pool size: {}",
+                mask_url(config.redis_url()),
+                config.pool_size()
+            );
+
+            // Parse Redis URL and create client
+            let client = Client::open(config.redis_url()).map_err(|e| {
+                error!("Failed to parse Redis URL: {}", e);
+                InfrastructureError::Config(format!("Invalid Redis URL: {}", e))
+            })?;
+            (client, None)
+        };
 
         // Create multiplexed connection with retry logic
         let connection = Self::create_connection_with_retry(
-            client,
+            &client,
             max_retries,
             retry_delay_ms,
         ).await?;
@@ -92,16 +155,22 @@ impl RedisClient {
         info!("Redis client created successfully");
 
         Ok(Self {
-            connection,
+            master: Arc::new(RwLock::new(MasterConnection {
+                client,
+                connection,
+                generation: 0,
+            })),
+            sentinel,
             config,
             max_retries,
             retry_delay_ms,
+            metrics: Arc::new(CacheMetrics::new()),
         })
     }
 
     /// Create multiplexed connection with retry logic
     async fn create_connection_with_retry(
-        client: Client,
+        client: &Client,
         max_retries: u32,
         retry_delay_ms: u64,
     ) -> Result<MultiplexedConnection, InfrastructureError> {
@@ -166,8 +235,10 @@ impl RedisClient {
         value: &str,
         expiry_seconds: u64,
     ) -> Result<(), InfrastructureError> {
+        let key = &self.key(key);
         debug!("Setting key '{}' with expiry {}s", key, expiry_seconds);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -179,6 +250,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("set", started, &result, |_| None);
 
         match result {
             Ok(_) => {
@@ -215,8 +287,10 @@ impl RedisClient {
     /// }
     /// ```
     pub async fn get(&self, key: &str) -> Result<Option<String>, InfrastructureError> {
+        let key = &self.key(key);
         debug!("Getting key '{}'", key);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -226,6 +300,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("get", started, &result, |value| Some(value.is_some()));
 
         match result {
             Ok(value) => {
@@ -266,8 +341,10 @@ impl RedisClient {
     /// }
     /// ```
     pub async fn delete(&self, key: &str) -> Result<bool, InfrastructureError> {
+        let key = &self.key(key);
         debug!("Deleting key '{}'", key);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -277,6 +354,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("delete", started, &result, |_| None);
 
         match result {
             Ok(deleted_count) => {
@@ -311,15 +389,18 @@ impl RedisClient {
 
         loop {
             attempts += 1;
-            let conn = self.connection.clone();
+            let (conn, generation) = self.current_connection();
 
             match operation(conn).await {
                 Ok(result) => return Ok(result),
-                Err(e) if attempts < self.max_retries && is_retriable_error(&e) => {
+                Err(e) if attempts < self.max_retries && self.should_retry(&e) => {
                     warn!(
                         "Redis operation failed (attempt {}/{}): {}. Retrying in {}ms...",
                         attempts, self.max_retries, e, delay
                     );
+                    if is_failover_error(&e) {
+                        self.resolve_master(generation).await;
+                    }
                     sleep(Duration::from_millis(delay)).await;
                     // Exponential backoff with cap at 5 seconds
                     delay = (delay * 2).min(5000);
@@ -335,6 +416,51 @@ impl RedisClient {
         }
     }
 
+    /// The connection to the current master and its generation
+    fn current_connection(&self) -> (MultiplexedConnection, u64) {
+        let master = self.master.read().unwrap_or_else(|e| e.into_inner());
+        (master.connection.clone(), master.generation)
+    }
+
+    /// Whether a failed operation is worth repeating
+    fn should_retry(&self, error: &RedisError) -> bool {
+        is_retriable_error(error) || (self.sentinel.is_some() && is_failover_error(error))
+    }
+
+    /// Ask the sentinels for the master again and connect to it
+    ///
+    /// Operations failing together resolve the master once: the first one
+    /// replaces the connection of the generation they saw, the others find
+    /// it already replaced. Without sentinels this does nothing. Failures
+    /// are logged and leave the current connection in place.
+    async fn resolve_master(&self, seen_generation: u64) {
+        let Some(sentinel) = &self.sentinel else {
+            return;
+        };
+        let mut resolver = sentinel.lock().await;
+        if self.current_connection().1 != seen_generation {
+            return;
+        }
+
+        let resolved = async {
+            let client = resolver.resolve(1, self.retry_delay_ms).await?;
+            let connection = client.get_multiplexed_async_connection().await?;
+            Ok::<_, InfrastructureError>((client, connection))
+        }
+        .await;
+
+        match resolved {
+            Ok((client, connection)) => {
+                let mut master = self.master.write().unwrap_or_else(|e| e.into_inner());
+                master.client = client;
+                master.connection = connection;
+                master.generation += 1;
+                info!("Reconnected to Redis master {}", resolver.master_name);
+            }
+            Err(e) => warn!("Failed to resolve Redis master {}: {}", resolver.master_name, e),
+        }
+    }
+
     /// Check if the Redis connection is healthy
     /// 
     /// Performs a PING command to verify connectivity.
@@ -383,8 +509,10 @@ impl RedisClient {
         key: &str,
         expiry_seconds: Option<u64>,
     ) -> Result<i64, InfrastructureError> {
+        let key = &self.key(key);
         debug!("Incrementing counter '{}'", key);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -404,6 +532,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("increment", started, &result, |_| None);
 
         match result {
             Ok(count) => {
@@ -425,8 +554,10 @@ impl RedisClient {
     /// # Returns
     /// * `Result<bool, InfrastructureError>` - True if key exists
     pub async fn exists(&self, key: &str) -> Result<bool, InfrastructureError> {
+        let key = &self.key(key);
         debug!("Checking if key '{}' exists", key);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -436,6 +567,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("exists", started, &result, |exists| Some(*exists));
 
         match result {
             Ok(exists) => {
@@ -449,6 +581,165 @@ impl RedisClient {
         }
     }
 
+    /// Set the expiry of an existing key
+    ///
+    /// # Arguments
+    /// * `key` - Cache key
+    /// * `expiry_seconds` - Expiry time in seconds from now
+    ///
+    /// # Returns
+    /// * `Result<bool, InfrastructureError>` - False if the key doesn't exist
+    pub async fn expire(&self, key: &str, expiry_seconds: u64) -> Result<bool, InfrastructureError> {
+        let key = &self.key(key);
+        debug!("Setting expiry of key '{}' to {}s", key, expiry_seconds);
+
+        let started = Instant::now();
+        let result = self
+            .execute_with_retry(|mut conn| {
+                let key = key.to_string();
+
+                Box::pin(async move {
+                    conn.expire::<_, bool>(key, expiry_seconds as i64).await
+                })
+            })
+            .await;
+        self.metrics.record("expire", started, &result, |_| None);
+
+        match result {
+            Ok(updated) => {
+                debug!("Expiry of key '{}' updated: {}", key, updated);
+                Ok(updated)
+            }
+            Err(e) => {
+                error!("Failed to set expiry of key '{}': {}", key, e);
+                Err(InfrastructureError::Cache(e))
+            }
+        }
+    }
+
+    /// Take a lock if no one holds it
+    ///
+    /// The lock is a key set with `SET NX PX` to a random token, so only
+    /// the holder can release it and it disappears after `ttl` if the
+    /// holder never does.
+    ///
+    /// # Arguments
+    /// * `key` - Lock key
+    /// * `ttl` - Time after which the lock is released automatically
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, InfrastructureError>` - Token releasing the
+    ///   lock, or None if it is held by someone else
+    pub async fn acquire_lock(&self, key: &str, ttl: Duration) -> Result<Option<String>, InfrastructureError> {
+        let token = uuid::Uuid::new_v4().to_string();
+        let acquired = self.acquire_lock_with_token(key, &token, ttl).await?;
+        Ok(acquired.then_some(token))
+    }
+
+    /// Take a lock with a token chosen by the caller, as Redlock does on
+    /// every instance
+    pub(crate) async fn acquire_lock_with_token(
+        &self,
+        key: &str,
+        token: &str,
+        ttl: Duration,
+    ) -> Result<bool, InfrastructureError> {
+        let key = &self.key(key);
+        let ttl_ms = ttl.as_millis().max(1) as u64;
+        debug!("Acquiring lock '{}' for {}ms", key, ttl_ms);
+
+        let started = Instant::now();
+        let result = self
+            .execute_with_retry(|mut conn| {
+                let key = key.to_string();
+                let token = token.to_string();
+
+                Box::pin(async move {
+                    redis::cmd("SET")
+                        .arg(key)
+                        .arg(token)
+                        .arg("NX")
+                        .arg("PX")
+                        .arg(ttl_ms)
+                        .query_async::<_, Option<String>>(&mut conn)
+                        .await
+                })
+            })
+            .await;
+        self.metrics.record("acquire_lock", started, &result, |_| None);
+
+        match result {
+            Ok(Some(_)) => {
+                debug!("Acquired lock '{}'", key);
+                Ok(true)
+            }
+            Ok(None) => {
+                debug!("Lock '{}' is held by someone else", key);
+                Ok(false)
+            }
+            Err(e) => {
+                error!("Failed to acquire lock '{}': {}", key, e);
+                Err(InfrastructureError::Cache(e))
+            }
+        }
+    }
+
+    /// Release a lock taken with [`Self::acquire_lock`]
+    ///
+    /// The key is only deleted if it still holds `token`: a lock that
+    /// expired and was taken by someone else is left alone.
+    ///
+    /// # Returns
+    /// * `Result<bool, InfrastructureError>` - False if the lock was no
+    ///   longer held with `token`
+    pub async fn release_lock(&self, key: &str, token: &str) -> Result<bool, InfrastructureError> {
+        let key = &self.key(key);
+        debug!("Releasing lock '{}'", key);
+
+        let started = Instant::now();
+        let result = self
+            .execute_with_retry(|mut conn| {
+                let key = key.to_string();
+                let token = token.to_string();
+
+                Box::pin(async move {
+                    redis::Script::new(RELEASE_LOCK_SCRIPT)
+                        .key(key)
+                        .arg(token)
+                        .invoke_async::<_, i64>(&mut conn)
+                        .await
+                })
+            })
+            .await;
+        self.metrics.record("release_lock", started, &result, |_| None);
+
+        match result {
+            Ok(released) => Ok(released == 1),
+            Err(e) => {
+                error!("Failed to release lock '{}': {}", key, e);
+                Err(InfrastructureError::Cache(e))
+            }
+        }
+    }
+
+    /// Configuration the client was created with
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Metrics of the operations run through this client
+    ///
+    /// Lookups (`get`, `exists`) count hits and misses; commands sent
+    /// through [`Self::get_connection`] are not counted.
+    pub fn metrics(&self) -> Arc<CacheMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Key as stored in Redis, under the configured key prefix
+    pub fn key(&self, key: &str) -> String {
+        self.config.make_key(key)
+    }
+
     /// Get a clone of the Redis connection for advanced operations
     /// 
     /// This method is provided for operations that need direct access to Redis commands
@@ -457,7 +748,76 @@ impl RedisClient {
     /// # Returns
     /// * `MultiplexedConnection` - Cloned Redis connection
     pub fn get_connection(&self) -> MultiplexedConnection {
-        self.connection.clone()
+        self.current_connection().0
+    }
+
+    /// Open a dedicated connection for pub/sub subscriptions
+    /// 
+    /// A subscribed connection cannot run other commands, so it is never
+    /// shared with the multiplexed connection. Channels subscribed on it
+    /// directly are not put under the key prefix.
+    /// 
+    /// # Returns
+    /// * `Result<PubSub, InfrastructureError>` - Connection ready to subscribe
+    pub async fn get_pubsub(&self) -> Result<PubSub, InfrastructureError> {
+        let client = self.master.read().unwrap_or_else(|e| e.into_inner()).client.clone();
+
+        let connection = client.get_async_connection().await?;
+        Ok(connection.into_pubsub())
+    }
+
+    /// Publish a message on a pub/sub channel
+    ///
+    /// # Arguments
+    /// * `channel` - Channel name
+    /// * `message` - Message payload
+    ///
+    /// # Returns
+    /// * `Result<usize, InfrastructureError>` - Number of subscribers that received the message
+    pub async fn publish(&self, channel: &str, message: &str) -> Result<usize, InfrastructureError> {
+        let channel = &self.key(channel);
+        debug!("Publishing message on channel '{}'", channel);
+
+        let started = Instant::now();
+        let result = self
+            .execute_with_retry(|mut conn| {
+                let channel = channel.to_string();
+                let message = message.to_string();
+
+                Box::pin(async move {
+                    conn.publish::<_, _, usize>(channel, message).await
+                })
+            })
+            .await;
+        self.metrics.record("publish", started, &result, |_| None);
+
+        match result {
+            Ok(receivers) => {
+                debug!("Message on channel '{}' reached {} subscribers", channel, receivers);
+                Ok(receivers)
+            }
+            Err(e) => {
+                error!("Failed to publish on channel '{}': {}", channel, e);
+                Err(InfrastructureError::Cache(e))
+            }
+        }
+    }
+
+    /// Open a pub/sub connection subscribed to the given channels
+    ///
+    /// # Arguments
+    /// * `channels` - Channel names
+    ///
+    /// # Returns
+    /// * `Result<PubSub, InfrastructureError>` - Subscribed connection; read it with `on_message`
+    pub async fn subscribe(&self, channels: &[&str]) -> Result<PubSub, InfrastructureError> {
+        let mut pubsub = self.get_pubsub().await?;
+        for channel in channels {
+            pubsub.subscribe(self.key(channel)).await?;
+        }
+
+        info!("Subscribed to channels {:?}", channels);
+        Ok(pubsub)
     }
 
     /// Get time-to-live for a key
@@ -468,8 +828,10 @@ impl RedisClient {
     /// # Returns
     /// * `Result<Option<i64>, InfrastructureError>` - TTL in seconds, None if key doesn't exist or has no expiry
     pub async fn ttl(&self, key: &str) -> Result<Option<i64>, InfrastructureError> {
+        let key = &self.key(key);
         debug!("Getting TTL for key '{}'", key);
 
+        let started = Instant::now();
         let result = self
             .execute_with_retry(|mut conn| {
                 let key = key.to_string();
@@ -479,6 +841,7 @@ impl RedisClient {
                 })
             })
             .await;
+        self.metrics.record("ttl", started, &result, |_| None);
 
         match result {
             Ok(ttl) if ttl >= 0 => {
@@ -501,6 +864,72 @@ impl RedisClient {
     }
 }
 
+impl SentinelResolver {
+    /// Prepare to resolve the master configured in `config`
+    fn new(config: &CacheConfig) -> Result<Self, InfrastructureError> {
+        let sentinel = Sentinel::build(config.sentinels.clone()).map_err(|e| {
+            InfrastructureError::Config(format!("Invalid Redis sentinel URL: {}", e))
+        })?;
+        let master = config
+            .redis_url()
+            .to_string()
+            .into_connection_info()
+            .map_err(|e| InfrastructureError::Config(format!("Invalid Redis URL: {}", e)))?;
+
+        Ok(Self {
+            sentinel,
+            master_name: config.sentinel_master.clone().unwrap_or_default(),
+            node: SentinelNodeConnectionInfo {
+                tls_mode: None,
+                redis_connection_info: Some(master.redis),
+            },
+        })
+    }
+
+    /// Ask the sentinels for the current master, retrying with backoff
+    async fn resolve(&mut self, max_retries: u32, retry_delay_ms: u64) -> Result<Client, InfrastructureError> {
+        let mut attempts = 0;
+        let mut delay = retry_delay_ms;
+
+        loop {
+            attempts += 1;
+            match self.sentinel.async_master_for(&self.master_name, Some(&self.node)).await {
+                Ok(client) => {
+                    info!(
+                        "Sentinels report Redis master {} at {}",
+                        self.master_name,
+                        client.get_connection_info().addr
+                    );
+                    return Ok(client);
+                }
+                Err(e) if attempts < max_retries => {
+                    warn!(
+                        "Failed to resolve Redis master {} (attempt {}/{}): {}. Retrying in {}ms...",
+                        self.master_name, attempts, max_retries, e, delay
+                    );
+                    sleep(Duration::from_millis(delay)).await;
+                    delay = (delay * 2).min(5000);
+                }
+                Err(e) => {
+                    error!("Failed to resolve Redis master {}: {}", self.master_name, e);
+                    return Err(InfrastructureError::Cache(e));
+                }
+            }
+        }
+    }
+}
+
+/// Check if a Redis error means the master went away or was demoted
+///
+/// After a failover the old master drops its connections or, once it
+/// rejoins as a replica, rejects writes as read-only.
+fn is_failover_error(error: &RedisError) -> bool {
+    matches!(
+        error.kind(),
+        redis::ErrorKind::IoError | redis::ErrorKind::ReadOnly | redis::ErrorKind::MasterDown
+    ) || error.is_connection_dropped()
+}
+
 /// Check if a Redis error is retriable
 /// 
 /// Determines if an error is transient and the operation should be retried.
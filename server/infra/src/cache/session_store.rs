@@ -0,0 +1,198 @@
+//! Redis-backed session store for device management and idle timeouts
+//!
+//! Each session is a hash under `session:{family}` holding the user, the
+//! device, network and location it was started from, and its last
+//! activity as a Unix timestamp. `user_sessions:{user_id}` is the set of
+//! a user's session families. Both expire with the absolute session
+//! lifetime; families whose hash expired are dropped from the set when the
+//! user's sessions are next listed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use re_core::services::token::{SessionActivityStoreTrait, SessionInfo, SessionStoreTrait};
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the session store
+pub struct RedisSessionStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisSessionStore {
+    /// Create a new Redis-based session store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    pub(crate) fn session_key(token_family: &str) -> String {
+        format!("session:{}", token_family)
+    }
+
+    pub(crate) fn user_key(user_id: Uuid) -> String {
+        format!("user_sessions:{}", user_id)
+    }
+
+    /// Fields of a session's hash
+    pub(crate) fn to_fields(session: &SessionInfo) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("user_id", session.user_id.to_string()),
+            ("created_at", session.created_at.timestamp().to_string()),
+            ("last_activity_at", session.last_activity_at.timestamp().to_string()),
+        ];
+        let optional = [
+            ("device_fingerprint_hash", &session.device_fingerprint_hash),
+            ("device", &session.device),
+            ("ip_address", &session.ip_address),
+            ("country", &session.country),
+        ];
+        for (name, value) in optional {
+            if let Some(value) = value {
+                fields.push((name, value.clone()));
+            }
+        }
+        fields
+    }
+
+    /// Read a session from its hash
+    ///
+    /// Hashes without a user, written by activity on a session started
+    /// before sessions were recorded, are not sessions.
+    pub(crate) fn from_fields(token_family: &str, mut fields: HashMap<String, String>) -> Option<SessionInfo> {
+        let timestamp = |value: Option<String>| -> Option<DateTime<Utc>> {
+            value?.parse().ok().and_then(|t| Utc.timestamp_opt(t, 0).single())
+        };
+
+        let user_id = Uuid::parse_str(fields.get("user_id")?).ok()?;
+        let last_activity_at = timestamp(fields.remove("last_activity_at"))?;
+        Some(SessionInfo {
+            token_family: token_family.to_string(),
+            user_id,
+            device_fingerprint_hash: fields.remove("device_fingerprint_hash"),
+            device: fields.remove("device"),
+            ip_address: fields.remove("ip_address"),
+            country: fields.remove("country"),
+            created_at: timestamp(fields.remove("created_at")).unwrap_or(last_activity_at),
+            last_activity_at,
+        })
+    }
+}
+
+#[async_trait]
+impl SessionActivityStoreTrait for RedisSessionStore {
+    async fn record_activity(
+        &self,
+        token_family: &str,
+        at: DateTime<Utc>,
+        ttl_seconds: u64,
+    ) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+        let key = self.redis_client.key(&Self::session_key(token_family));
+
+        redis::pipe()
+            .atomic()
+            .hset(&key, "last_activity_at", at.timestamp())
+            .ignore()
+            .expire(&key, ttl_seconds as i64)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to record session activity: {}", e))
+    }
+
+    async fn last_activity(&self, token_family: &str) -> Result<Option<DateTime<Utc>>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let timestamp: Option<i64> = conn
+            .hget(self.redis_client.key(&Self::session_key(token_family)), "last_activity_at")
+            .await
+            .map_err(|e| format!("Failed to load session activity: {}", e))?;
+
+        Ok(timestamp.and_then(|t| Utc.timestamp_opt(t, 0).single()))
+    }
+
+    async fn clear(&self, token_family: &str) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+        let key = self.redis_client.key(&Self::session_key(token_family));
+
+        let user_id: Option<String> = conn
+            .hget(&key, "user_id")
+            .await
+            .map_err(|e| format!("Failed to load session: {}", e))?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(&key).ignore();
+        if let Some(user_id) = user_id.and_then(|id| Uuid::parse_str(&id).ok()) {
+            pipe.srem(self.redis_client.key(&Self::user_key(user_id)), token_family).ignore();
+        }
+        pipe.query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to clear session: {}", e))
+    }
+}
+
+#[async_trait]
+impl SessionStoreTrait for RedisSessionStore {
+    async fn save_session(&self, session: &SessionInfo, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+        let key = self.redis_client.key(&Self::session_key(&session.token_family));
+        let user_key = self.redis_client.key(&Self::user_key(session.user_id));
+
+        redis::pipe()
+            .atomic()
+            .del(&key)
+            .ignore()
+            .hset_multiple(&key, &Self::to_fields(session))
+            .ignore()
+            .expire(&key, ttl_seconds as i64)
+            .ignore()
+            .sadd(&user_key, &session.token_family)
+            .ignore()
+            .expire(&user_key, ttl_seconds as i64)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to save session: {}", e))
+    }
+
+    async fn find_session(&self, token_family: &str) -> Result<Option<SessionInfo>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let fields: HashMap<String, String> = conn
+            .hgetall(self.redis_client.key(&Self::session_key(token_family)))
+            .await
+            .map_err(|e| format!("Failed to load session: {}", e))?;
+
+        Ok(Self::from_fields(token_family, fields))
+    }
+
+    async fn list_sessions(&self, user_id: Uuid) -> Result<Vec<SessionInfo>, String> {
+        let mut conn = self.redis_client.get_connection();
+        let user_key = self.redis_client.key(&Self::user_key(user_id));
+
+        let families: Vec<String> = conn
+            .smembers(&user_key)
+            .await
+            .map_err(|e| format!("Failed to list sessions: {}", e))?;
+
+        let mut sessions = Vec::with_capacity(families.len());
+        let mut expired = Vec::new();
+        for family in families {
+            match self.find_session(&family).await? {
+                Some(session) if session.user_id == user_id => sessions.push(session),
+                _ => expired.push(family),
+            }
+        }
+
+        if !expired.is_empty() {
+            conn.srem::<_, _, ()>(&user_key, expired)
+                .await
+                .map_err(|e| format!("Failed to drop expired sessions: {}", e))?;
+        }
+        Ok(sessions)
+    }
+}
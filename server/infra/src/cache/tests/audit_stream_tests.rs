@@ -0,0 +1,70 @@
+//! Unit tests for the audit log stream
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use redis::streams::StreamId;
+use redis::Value;
+
+use re_core::domain::entities::audit::{AuditEventType, AuditLog};
+use re_core::repositories::audit::{AuditLogRepository, MockAuditLogRepository};
+use re_core::services::tenant::{scope, TenantId};
+use re_shared::config::cache::CacheConfig;
+
+use crate::cache::audit_stream::decode_entry;
+use crate::cache::{AuditStreamConfig, AuditStreamWorker, RedisClient, RedisStreamAuditLogRepository};
+
+fn entry(fields: &[(&str, &str)]) -> StreamId {
+    StreamId {
+        id: "1-0".to_string(),
+        map: fields
+            .iter()
+            .map(|(field, value)| (field.to_string(), Value::Data(value.as_bytes().to_vec())))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+#[test]
+fn test_decode_entry() {
+    let audit_log = AuditLog::new(AuditEventType::LoginSuccess, "127.0.0.1");
+    let log = serde_json::to_string(&audit_log).unwrap();
+
+    let (tenant, decoded) = decode_entry(&entry(&[("tenant", "acme"), ("log", &log)])).unwrap();
+    assert_eq!(tenant.as_str(), "acme");
+    assert_eq!(decoded, audit_log);
+
+    assert!(decode_entry(&entry(&[("log", &log)])).is_none());
+    assert!(decode_entry(&entry(&[("tenant", "Not A Tenant"), ("log", &log)])).is_none());
+    assert!(decode_entry(&entry(&[("tenant", "acme"), ("log", "{}")])).is_none());
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_audit_logs_are_flushed_from_the_stream() {
+    let config = CacheConfig::new(
+        std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string())
+    );
+    let client = Arc::new(RedisClient::new(config).await.unwrap());
+    let _ = client.delete("test:audit_events").await;
+
+    let stream_config = AuditStreamConfig {
+        stream_key: "test:audit_events".to_string(),
+        block_ms: 100,
+        ..AuditStreamConfig::default()
+    };
+    let database = Arc::new(MockAuditLogRepository::new());
+    let repository = RedisStreamAuditLogRepository::new(client.clone(), database.clone(), stream_config.clone());
+    let worker = AuditStreamWorker::new(client.clone(), database.clone(), stream_config);
+    worker.ensure_group().await.unwrap();
+
+    let audit_log = AuditLog::new(AuditEventType::LoginSuccess, "127.0.0.1");
+    scope(TenantId::parse("acme").unwrap(), repository.create(&audit_log)).await.unwrap();
+    assert!(database.get_all_logs().is_empty());
+
+    assert_eq!(worker.run_once().await.unwrap(), 1);
+    assert_eq!(database.get_all_logs(), vec![audit_log]);
+    assert_eq!(worker.run_once().await.unwrap(), 0);
+
+    let _ = client.delete("test:audit_events").await;
+}
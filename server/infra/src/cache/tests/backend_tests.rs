@@ -0,0 +1,212 @@
+//! Unit tests for cache backends
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use re_core::services::cluster::{ClusterEvent, ClusterEventPublisherTrait};
+
+use crate::cache::backend::memcached::{exptime, parse_meta_ttl};
+use crate::cache::{
+    CacheBackend, InMemoryCacheBackend, MemcachedBackend, TieredCacheBackend, TieredCacheConfig, VerificationCache,
+};
+
+#[tokio::test]
+async fn test_memory_backend_basic_operations() {
+    let backend = InMemoryCacheBackend::default();
+
+    backend.set("key", "value", 60).await.unwrap();
+    assert_eq!(backend.get("key").await.unwrap(), Some("value".to_string()));
+    assert!(backend.exists("key").await.unwrap());
+    let ttl = backend.ttl("key").await.unwrap().unwrap();
+    assert!(ttl > 55 && ttl <= 60);
+
+    assert!(backend.delete("key").await.unwrap());
+    assert!(!backend.delete("key").await.unwrap());
+    assert_eq!(backend.get("key").await.unwrap(), None);
+    assert_eq!(backend.ttl("key").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_memory_backend_counters() {
+    let backend = InMemoryCacheBackend::default();
+
+    assert_eq!(backend.incr("counter", Some(60)).await.unwrap(), 1);
+    assert_eq!(backend.incr("counter", Some(1)).await.unwrap(), 2);
+    assert_eq!(backend.get("counter").await.unwrap(), Some("2".to_string()));
+    // The expiry is only set when the counter is created
+    assert!(backend.ttl("counter").await.unwrap().unwrap() > 55);
+
+    assert_eq!(backend.incr("forever", None).await.unwrap(), 1);
+    assert_eq!(backend.ttl("forever").await.unwrap(), None);
+
+    backend.set("text", "abc", 60).await.unwrap();
+    assert!(backend.incr("text", None).await.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_backend_expiry() {
+    let backend = InMemoryCacheBackend::default();
+
+    assert!(!backend.expire("missing", 60).await.unwrap());
+
+    backend.set("short", "value", 60).await.unwrap();
+    assert!(backend.expire("short", 1).await.unwrap());
+    assert!(backend.ttl("short").await.unwrap().unwrap() <= 1);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    assert_eq!(backend.get("short").await.unwrap(), None);
+    assert!(!backend.exists("short").await.unwrap());
+    assert_eq!(backend.incr("short", None).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_verification_cache_on_memory_backend() {
+    let service = VerificationCache::with_backend(Arc::new(InMemoryCacheBackend::default()));
+    let phone = "1234567890";
+
+    service.store_code(phone, "123456").await.unwrap();
+    assert!(service.code_exists(phone).await.unwrap());
+    assert!(service.get_code_ttl(phone).await.unwrap().is_some());
+
+    assert!(!service.verify_code(phone, "000000").await.unwrap());
+    assert_eq!(service.get_remaining_attempts(phone).await.unwrap(), 2);
+
+    assert!(service.verify_code(phone, "123456").await.unwrap());
+    assert!(!service.code_exists(phone).await.unwrap());
+    assert_eq!(service.get_remaining_attempts(phone).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_verification_cache_metrics() {
+    let service = VerificationCache::with_backend(Arc::new(InMemoryCacheBackend::default()));
+    let phone = "1234567890";
+
+    assert!(!service.verify_code(phone, "123456").await.unwrap());
+    service.store_code(phone, "123456").await.unwrap();
+    assert!(service.verify_code(phone, "123456").await.unwrap());
+    assert!(!service.code_exists(phone).await.unwrap());
+
+    let metrics = service.metrics().snapshot();
+    let verify = &metrics["verify_code"];
+    assert_eq!((verify.calls, verify.hits, verify.misses, verify.errors), (2, 1, 1, 0));
+    assert_eq!(verify.latency_buckets.iter().sum::<u64>(), 2);
+    assert_eq!(metrics["store_code"].calls, 1);
+    assert_eq!((metrics["code_exists"].hits, metrics["code_exists"].misses), (0, 1));
+}
+
+/// Publisher recording the events it is given
+#[derive(Default)]
+struct RecordingPublisher {
+    events: Mutex<Vec<ClusterEvent>>,
+}
+
+#[async_trait]
+impl ClusterEventPublisherTrait for RecordingPublisher {
+    async fn publish(&self, event: &ClusterEvent) -> Result<(), String> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+fn tiered_config() -> TieredCacheConfig {
+    TieredCacheConfig {
+        cached_prefixes: vec!["lock:".to_string()],
+        ..TieredCacheConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_tiered_backend_serves_cached_keys_locally() {
+    let shared = Arc::new(InMemoryCacheBackend::default());
+    let backend = TieredCacheBackend::new(shared.clone(), tiered_config());
+
+    shared.set("lock:user-1", "locked", 60).await.unwrap();
+    assert_eq!(backend.get("lock:user-1").await.unwrap(), Some("locked".to_string()));
+    assert!(!backend.exists("lock:user-2").await.unwrap());
+
+    // Changes made by another instance are not seen until invalidated
+    shared.delete("lock:user-1").await.unwrap();
+    shared.set("lock:user-2", "locked", 60).await.unwrap();
+    assert!(backend.exists("lock:user-1").await.unwrap());
+    assert!(!backend.exists("lock:user-2").await.unwrap());
+
+    backend
+        .invalidate_local(&["lock:user-1".to_string(), "lock:user-2".to_string()])
+        .await;
+    assert!(!backend.exists("lock:user-1").await.unwrap());
+    assert!(backend.exists("lock:user-2").await.unwrap());
+
+    // Other keys always go to the shared backend
+    shared.set("other", "1", 60).await.unwrap();
+    assert!(backend.exists("other").await.unwrap());
+    shared.delete("other").await.unwrap();
+    assert!(!backend.exists("other").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_tiered_backend_invalidates_written_keys() {
+    let shared = Arc::new(InMemoryCacheBackend::default());
+    let publisher = Arc::new(RecordingPublisher::default());
+    let backend = TieredCacheBackend::new(shared.clone(), tiered_config())
+        .with_invalidation_publisher(publisher.clone());
+
+    assert_eq!(backend.get("lock:user-1").await.unwrap(), None);
+    backend.set("lock:user-1", "locked", 60).await.unwrap();
+    assert_eq!(backend.get("lock:user-1").await.unwrap(), Some("locked".to_string()));
+    assert_eq!(shared.get("lock:user-1").await.unwrap(), Some("locked".to_string()));
+    assert!(backend.ttl("lock:user-1").await.unwrap().unwrap() > 55);
+
+    assert!(backend.delete("lock:user-1").await.unwrap());
+    assert_eq!(backend.get("lock:user-1").await.unwrap(), None);
+
+    assert_eq!(backend.incr("lock:attempts", Some(60)).await.unwrap(), 1);
+    assert_eq!(backend.incr("counter", Some(60)).await.unwrap(), 1);
+
+    // Only writes of cached keys are published
+    let events = publisher.events.lock().unwrap();
+    assert_eq!(
+        *events,
+        vec![
+            ClusterEvent::CacheInvalidated { keys: vec!["lock:user-1".to_string()] },
+            ClusterEvent::CacheInvalidated { keys: vec!["lock:user-1".to_string()] },
+            ClusterEvent::CacheInvalidated { keys: vec!["lock:attempts".to_string()] },
+        ]
+    );
+}
+
+#[test]
+fn test_memcached_expiry_times() {
+    assert_eq!(exptime(None), 0);
+    assert_eq!(exptime(Some(300)), 300);
+
+    let absolute = exptime(Some(31 * 24 * 60 * 60));
+    assert!(absolute > chrono::Utc::now().timestamp() as u64);
+}
+
+#[test]
+fn test_memcached_meta_ttl() {
+    assert_eq!(parse_meta_ttl("EN").unwrap(), None);
+    assert_eq!(parse_meta_ttl("HD t42").unwrap(), Some(42));
+    assert_eq!(parse_meta_ttl("HD t-1").unwrap(), None);
+    assert!(parse_meta_ttl("HD").is_err());
+}
+
+#[tokio::test]
+#[ignore] // Requires actual memcached server
+async fn test_memcached_backend_operations() {
+    let address = std::env::var("MEMCACHED_ADDRESS").unwrap_or_else(|_| "localhost:11211".to_string());
+    let backend = MemcachedBackend::connect(address, 2).await.unwrap();
+
+    let _ = backend.delete("test:memcached").await;
+    backend.set("test:memcached", "value", 60).await.unwrap();
+    assert_eq!(backend.get("test:memcached").await.unwrap(), Some("value".to_string()));
+    assert!(backend.ttl("test:memcached").await.unwrap().unwrap() > 55);
+    assert!(backend.expire("test:memcached", 120).await.unwrap());
+    assert!(backend.delete("test:memcached").await.unwrap());
+    assert_eq!(backend.get("test:memcached").await.unwrap(), None);
+
+    let _ = backend.delete("test:memcached:counter").await;
+    assert_eq!(backend.incr("test:memcached:counter", Some(60)).await.unwrap(), 1);
+    assert_eq!(backend.incr("test:memcached:counter", Some(60)).await.unwrap(), 2);
+    assert!(backend.set("invalid key", "value", 60).await.is_err());
+}
@@ -0,0 +1,45 @@
+//! Unit tests for distributed locks over Redis
+
+use std::time::{Duration, Instant};
+
+use re_core::services::lock::DistributedLockTrait;
+use re_shared::config::cache::CacheConfig;
+
+use crate::cache::{RedisClient, RedisDistributedLock};
+
+#[test]
+fn test_validity_allows_for_clock_drift() {
+    let started = Instant::now();
+    let validity = RedisDistributedLock::validity(Duration::from_secs(10), started);
+
+    assert!(validity <= Duration::from_millis(9_898));
+    assert!(validity > Duration::from_secs(9));
+    assert!(RedisDistributedLock::validity(Duration::from_millis(1), started).is_zero());
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_lock_is_exclusive_and_released_by_its_token() {
+    let config = CacheConfig::new(
+        std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string())
+    );
+    let client = RedisClient::new(config).await.unwrap();
+    let _ = client.delete("lock:test").await;
+
+    let token = client.acquire_lock("lock:test", Duration::from_secs(5)).await.unwrap().unwrap();
+    assert!(client.acquire_lock("lock:test", Duration::from_secs(5)).await.unwrap().is_none());
+    assert!(!client.release_lock("lock:test", "someone-else").await.unwrap());
+    assert!(client.release_lock("lock:test", &token).await.unwrap());
+
+    let lock = RedisDistributedLock::new(client.clone());
+    assert_eq!(lock.quorum(), 1);
+    let token = lock.try_acquire("lock:test", Duration::from_secs(5)).await.unwrap().unwrap();
+    assert!(lock.try_acquire("lock:test", Duration::from_secs(5)).await.unwrap().is_none());
+    assert!(lock.release("lock:test", &token).await.unwrap());
+
+    // Redlock needs independent masters; the quorum is a strict majority
+    let redlock = RedisDistributedLock::redlock(vec![client.clone(), client.clone(), client]).unwrap();
+    assert_eq!(redlock.quorum(), 2);
+    assert!(RedisDistributedLock::redlock(Vec::new()).is_err());
+}
@@ -0,0 +1,53 @@
+//! Unit tests for the Redis cluster event bus
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use re_core::services::cluster::{ClusterEvent, ClusterEventHandlerTrait, ClusterEventPublisherTrait};
+use re_shared::config::cache::CacheConfig;
+
+use crate::cache::{RedisClient, RedisClusterEventBus};
+
+/// Handler recording the events it receives
+#[derive(Default)]
+struct RecordingHandler {
+    events: Mutex<Vec<ClusterEvent>>,
+}
+
+#[async_trait]
+impl ClusterEventHandlerTrait for RecordingHandler {
+    async fn handle(&self, event: ClusterEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_published_events_reach_subscribers() {
+    let config = CacheConfig::new(
+        std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string())
+    );
+    let client = Arc::new(RedisClient::new(config).await.unwrap());
+    let bus = Arc::new(RedisClusterEventBus::new(client.clone()));
+    let handler = Arc::new(RecordingHandler::default());
+
+    let listener = {
+        let bus = bus.clone();
+        let handler = handler.clone();
+        tokio::spawn(async move { bus.listen(handler.as_ref()).await })
+    };
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let event = ClusterEvent::ConfigReloaded {
+        section: "ip_access".to_string(),
+    };
+    bus.publish(&event).await.unwrap();
+    client.publish("cluster_events", "not json").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(*handler.events.lock().unwrap(), vec![event]);
+    listener.abort();
+}
@@ -0,0 +1,65 @@
+//! Unit tests for Redis memory audit
+
+use chrono::Utc;
+
+use crate::cache::memory_audit::{
+    apply_enforced_ttls, parse_info_field, parse_namespaces, NamespaceUsage, RedisMemoryReport,
+    RedisNamespaceConfig,
+};
+
+#[test]
+fn test_parse_info_field() {
+    let info = "# Memory\r\nused_memory:1048576\r\nused_memory_human:1.00M\r\nmaxmemory:0\r\n";
+
+    assert_eq!(parse_info_field(info, "used_memory"), Some(1_048_576));
+    assert_eq!(parse_info_field(info, "maxmemory"), Some(0));
+    assert_eq!(parse_info_field(info, "used_memory_human"), None);
+    assert_eq!(parse_info_field(info, "used_memory_peak"), None);
+}
+
+#[test]
+fn test_enforced_ttls_applied_to_namespaces() {
+    let mut namespaces = parse_namespaces("otp:, rate_limit:,,");
+    apply_enforced_ttls(&mut namespaces, "rate_limit:=3600, cache:=86400, otp:=soon, =60");
+
+    assert_eq!(
+        namespaces,
+        vec![
+            RedisNamespaceConfig::new("otp:"),
+            RedisNamespaceConfig::new("rate_limit:").with_enforced_ttl(3600),
+            RedisNamespaceConfig::new("cache:").with_enforced_ttl(86400),
+        ]
+    );
+}
+
+#[test]
+fn test_report_budget() {
+    let mut report = RedisMemoryReport {
+        used_memory_bytes: 750,
+        budget_bytes: Some(1000),
+        namespaces: vec![
+            NamespaceUsage {
+                prefix: "otp:".to_string(),
+                keys_without_ttl: 2,
+                ..Default::default()
+            },
+            NamespaceUsage {
+                prefix: "rate_limit:".to_string(),
+                keys_without_ttl: 3,
+                ..Default::default()
+            },
+        ],
+        sampled_at: Utc::now(),
+    };
+
+    assert_eq!(report.budget_used_ratio(), Some(0.75));
+    assert!(!report.is_over_budget());
+    assert_eq!(report.keys_without_ttl(), 5);
+
+    report.used_memory_bytes = 1001;
+    assert!(report.is_over_budget());
+
+    report.budget_bytes = None;
+    assert_eq!(report.budget_used_ratio(), None);
+    assert!(!report.is_over_budget());
+}
@@ -0,0 +1,40 @@
+//! Unit tests for cache metrics
+
+use std::time::Duration;
+
+use crate::cache::metrics::{CacheMetrics, LATENCY_BUCKETS_US};
+
+#[test]
+fn test_record_counts_hits_misses_and_errors() {
+    let metrics = CacheMetrics::new();
+    metrics.record_call("get", Duration::from_micros(200), false, Some(true));
+    metrics.record_call("get", Duration::from_millis(3), false, Some(false));
+    metrics.record_call("get", Duration::from_secs(2), true, None);
+    metrics.record_call("set", Duration::from_millis(1), false, None);
+
+    let snapshot = metrics.snapshot();
+    let get = &snapshot["get"];
+    assert_eq!((get.calls, get.hits, get.misses, get.errors), (3, 1, 1, 1));
+    assert_eq!(get.max_duration_us, 2_000_000);
+    assert_eq!(get.average_duration_us(), (200 + 3_000 + 2_000_000) / 3);
+    assert_eq!(get.hit_ratio(), Some(0.5));
+
+    // 200µs, 3ms and 2s land in the first, fourth and overflow buckets
+    assert_eq!(get.latency_buckets.len(), LATENCY_BUCKETS_US.len() + 1);
+    assert_eq!(get.latency_buckets[0], 1);
+    assert_eq!(get.latency_buckets[3], 1);
+    assert_eq!(get.latency_buckets[LATENCY_BUCKETS_US.len()], 1);
+
+    assert_eq!(snapshot["set"].hit_ratio(), None);
+}
+
+#[test]
+fn test_record_classifies_results() {
+    let metrics = CacheMetrics::new();
+    let started = std::time::Instant::now();
+    metrics.record("get", started, &Ok::<_, String>(Some("value")), |value| Some(value.is_some()));
+    metrics.record("get", started, &Err::<Option<&str>, _>("down".to_string()), |value| Some(value.is_some()));
+
+    let get = &metrics.snapshot()["get"];
+    assert_eq!((get.calls, get.hits, get.misses, get.errors), (2, 1, 0, 1));
+}
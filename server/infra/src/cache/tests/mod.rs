@@ -1,8 +1,26 @@
 //! Unit tests for cache module
 
+#[cfg(test)]
+pub mod audit_stream_tests;
+#[cfg(test)]
+pub mod backend_tests;
+#[cfg(test)]
+pub mod distributed_lock_tests;
+#[cfg(test)]
+pub mod event_bus_tests;
+#[cfg(test)]
+pub mod memory_audit_tests;
+#[cfg(test)]
+pub mod metrics_tests;
 #[cfg(test)]
 pub mod otp_storage_tests;
 #[cfg(test)]
 pub mod redis_client_tests;
 #[cfg(test)]
-pub mod verification_cache_tests;
\ No newline at end of file
+pub mod session_store_tests;
+#[cfg(test)]
+pub mod token_blacklist_store_tests;
+#[cfg(test)]
+pub mod user_cache_store_tests;
+#[cfg(test)]
+pub mod verification_cache_tests;
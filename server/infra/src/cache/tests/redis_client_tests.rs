@@ -1,6 +1,6 @@
 //! Unit tests for Redis client
 
-use crate::cache::redis_client::{RedisClient, mask_url, is_retriable_error};
+use crate::cache::redis_client::{RedisClient, mask_url, is_retriable_error, is_failover_error};
 use re_shared::config::cache::CacheConfig;
 use redis::{RedisError, ErrorKind};
 
@@ -33,6 +33,48 @@ fn test_is_retriable_error() {
     assert!(!is_retriable_error(&parse_error));
 }
 
+#[test]
+fn test_is_failover_error() {
+    // A demoted master rejects writes until the master is resolved again
+    let read_only = RedisError::from((ErrorKind::ReadOnly, "You can't write against a read only replica."));
+    assert!(is_failover_error(&read_only));
+    assert!(!is_retriable_error(&read_only));
+
+    let dropped = RedisError::from(std::io::Error::new(
+        std::io::ErrorKind::ConnectionReset,
+        "Connection reset by peer",
+    ));
+    assert!(is_failover_error(&dropped));
+
+    let type_error = RedisError::from((ErrorKind::TypeError, "Invalid type"));
+    assert!(!is_failover_error(&type_error));
+}
+
+#[tokio::test]
+async fn test_sentinels_require_master_name() {
+    let mut config = CacheConfig::new("redis://:secret@localhost:6379/2");
+    config.sentinels = vec!["redis://localhost:26379".to_string()];
+
+    let result = RedisClient::new(config.clone()).await;
+    assert!(matches!(result, Err(crate::InfrastructureError::Config(_))));
+
+    let config = config.with_sentinels("renoveasy", ["redis://localhost:26379"]);
+    assert!(config.validate_sentinels().is_ok());
+    assert!(config.uses_sentinel());
+}
+
+#[test]
+fn test_key_prefix() {
+    assert_eq!(CacheConfig::parse_key_prefix(" staging: "), Some("staging".to_string()));
+    assert_eq!(CacheConfig::parse_key_prefix(""), None);
+    assert_eq!(CacheConfig::parse_key_prefix(":"), None);
+
+    let config = CacheConfig::new("redis://localhost:6379");
+    assert_eq!(config.make_key("otp:encrypted:123"), "otp:encrypted:123");
+    let config = config.with_prefix("staging");
+    assert_eq!(config.make_key("otp:encrypted:123"), "staging:otp:encrypted:123");
+}
+
 #[tokio::test]
 async fn test_client_creation_with_invalid_url() {
     let config = CacheConfig::new("invalid://url");
@@ -117,4 +159,33 @@ async fn test_health_check() {
     
     let healthy = client.health_check().await.unwrap();
     assert!(healthy);
-}
\ No newline at end of file
+}
+#[tokio::test]
+#[ignore] // Requires Redis Sentinel monitoring a master named by REDIS_SENTINEL_MASTER
+async fn test_master_resolved_through_sentinels() {
+    let sentinels = std::env::var("REDIS_SENTINELS")
+        .unwrap_or_else(|_| "redis://localhost:26379".to_string());
+    let master = std::env::var("REDIS_SENTINEL_MASTER").unwrap_or_else(|_| "mymaster".to_string());
+    let config = CacheConfig::new("redis://localhost:6379").with_sentinels(master, sentinels.split(','));
+
+    let client = RedisClient::new(config).await.unwrap();
+
+    client.set_with_expiry("test:sentinel", "value", 60).await.unwrap();
+    assert_eq!(client.get("test:sentinel").await.unwrap(), Some("value".to_string()));
+    client.delete("test:sentinel").await.unwrap();
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_keys_are_put_under_the_prefix() {
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let client = RedisClient::new(CacheConfig::new(url.clone())).await.unwrap();
+    let staging = RedisClient::new(CacheConfig::new(url).with_prefix("test_staging")).await.unwrap();
+
+    staging.set_with_expiry("test:prefixed", "value", 60).await.unwrap();
+    assert_eq!(staging.get("test:prefixed").await.unwrap(), Some("value".to_string()));
+    assert_eq!(client.get("test_staging:test:prefixed").await.unwrap(), Some("value".to_string()));
+    assert_eq!(client.get("test:prefixed").await.unwrap(), None);
+
+    assert!(staging.delete("test:prefixed").await.unwrap());
+}
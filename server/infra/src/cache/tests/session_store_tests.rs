@@ -0,0 +1,53 @@
+//! Unit tests for the session store
+
+use chrono::{TimeZone, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use re_core::services::token::SessionInfo;
+
+use crate::cache::session_store::RedisSessionStore;
+
+fn session() -> SessionInfo {
+    SessionInfo {
+        token_family: "family-1".to_string(),
+        user_id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+        device_fingerprint_hash: Some("9f86d0".to_string()),
+        device: Some("Mobile/iOS".to_string()),
+        ip_address: Some("203.0.113.7".to_string()),
+        country: None,
+        created_at: Utc.timestamp_opt(1_790_000_000, 0).unwrap(),
+        last_activity_at: Utc.timestamp_opt(1_790_000_600, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_format_keys() {
+    let user_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+
+    assert_eq!(RedisSessionStore::session_key("family-1"), "session:family-1");
+    assert_eq!(
+        RedisSessionStore::user_key(user_id),
+        "user_sessions:550e8400-e29b-41d4-a716-446655440000"
+    );
+}
+
+#[test]
+fn test_session_round_trips_through_hash_fields() {
+    let session = session();
+
+    let fields: HashMap<String, String> = RedisSessionStore::to_fields(&session)
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+
+    assert!(!fields.contains_key("country"));
+    assert_eq!(RedisSessionStore::from_fields("family-1", fields), Some(session));
+}
+
+#[test]
+fn test_activity_without_session_is_not_a_session() {
+    let fields = HashMap::from([("last_activity_at".to_string(), "1790000600".to_string())]);
+
+    assert_eq!(RedisSessionStore::from_fields("family-1", fields), None);
+}
@@ -0,0 +1,42 @@
+//! Unit tests for the Redis token blacklist
+
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+
+use re_core::repositories::decorators::TokenBlacklistStoreTrait;
+use re_shared::config::cache::CacheConfig;
+
+use crate::cache::{RedisClient, RedisTokenBlacklistStore};
+
+#[test]
+fn test_entries_live_as_long_as_the_token() {
+    let now = Utc.with_ymd_and_hms(2025, 8, 14, 10, 0, 0).unwrap();
+
+    assert_eq!(RedisTokenBlacklistStore::key("abc"), "token_blacklist:abc");
+    assert_eq!(RedisTokenBlacklistStore::remaining_seconds(now + Duration::minutes(15), now), 900);
+    assert_eq!(RedisTokenBlacklistStore::remaining_seconds(now + Duration::milliseconds(1), now), 1);
+    assert_eq!(RedisTokenBlacklistStore::remaining_seconds(now, now), 0);
+    assert_eq!(RedisTokenBlacklistStore::remaining_seconds(now - Duration::minutes(1), now), 0);
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_blacklisted_tokens_expire_with_the_token() {
+    let config = CacheConfig::new(
+        std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string())
+    );
+    let client = Arc::new(RedisClient::new(config).await.unwrap());
+    let store = RedisTokenBlacklistStore::new(client.clone());
+
+    store.add("test-jti", Utc::now() + Duration::minutes(15)).await.unwrap();
+    assert!(store.contains("test-jti").await.unwrap());
+    let ttl = client.ttl("token_blacklist:test-jti").await.unwrap().unwrap();
+    assert!(ttl > 890 && ttl <= 900);
+
+    store.add("test-expired", Utc::now() - Duration::minutes(1)).await.unwrap();
+    assert!(!store.contains("test-expired").await.unwrap());
+
+    client.delete("token_blacklist:test-jti").await.unwrap();
+}
@@ -0,0 +1,37 @@
+//! Unit tests for the shared user cache
+
+use uuid::Uuid;
+
+use re_core::services::tenant::TenantId;
+
+use crate::cache::user_cache_store::RedisUserCacheStore;
+
+#[test]
+fn test_format_keys() {
+    let id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+    let tenant = TenantId::default();
+
+    assert_eq!(
+        RedisUserCacheStore::id_key(&tenant, id),
+        "user_cache:id:550e8400-e29b-41d4-a716-446655440000"
+    );
+    assert_eq!(
+        RedisUserCacheStore::phone_key(&tenant, "3f2a9c", "+61"),
+        "user_cache:phone:+61:3f2a9c"
+    );
+}
+
+#[test]
+fn test_format_keys_of_other_tenants() {
+    let id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+    let tenant = TenantId::parse("brand-a").unwrap();
+
+    assert_eq!(
+        RedisUserCacheStore::id_key(&tenant, id),
+        "user_cache:brand-a:id:550e8400-e29b-41d4-a716-446655440000"
+    );
+    assert_eq!(
+        RedisUserCacheStore::phone_key(&tenant, "3f2a9c", "+61"),
+        "user_cache:brand-a:phone:+61:3f2a9c"
+    );
+}
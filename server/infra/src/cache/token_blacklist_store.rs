@@ -0,0 +1,61 @@
+//! Redis-backed access token blacklist
+//!
+//! A blacklisted token is stored under `token_blacklist:{jti}` with a TTL
+//! equal to the token's remaining lifetime, so entries disappear once the
+//! token would be rejected as expired anyway and need no cleanup.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use re_core::repositories::decorators::TokenBlacklistStoreTrait;
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the token blacklist store
+pub struct RedisTokenBlacklistStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisTokenBlacklistStore {
+    /// Create a new Redis-based token blacklist
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    pub(crate) fn key(token_jti: &str) -> String {
+        format!("token_blacklist:{}", token_jti)
+    }
+
+    /// Seconds until a token expires, rounded up; zero once it has expired
+    pub(crate) fn remaining_seconds(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> u64 {
+        let millis = (expires_at - now).num_milliseconds();
+        if millis <= 0 {
+            return 0;
+        }
+        (millis as u64).div_ceil(1000)
+    }
+}
+
+#[async_trait]
+impl TokenBlacklistStoreTrait for RedisTokenBlacklistStore {
+    async fn add(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), String> {
+        let ttl = Self::remaining_seconds(expires_at, Utc::now());
+        if ttl == 0 {
+            // Expired tokens are rejected without consulting the blacklist
+            return Ok(());
+        }
+
+        self.redis_client
+            .set_with_expiry(&Self::key(token_jti), "1", ttl)
+            .await
+            .map_err(|e| format!("Failed to blacklist token: {}", e))
+    }
+
+    async fn contains(&self, token_jti: &str) -> Result<bool, String> {
+        self.redis_client
+            .exists(&Self::key(token_jti))
+            .await
+            .map_err(|e| format!("Failed to check token blacklist: {}", e))
+    }
+}
@@ -0,0 +1,111 @@
+//! Redis-backed shared cache of user lookups
+//!
+//! Users are stored as JSON under `user_cache:id:{id}`, and
+//! `user_cache:phone:{country_code}:{phone_hash}` holds the ID of the user
+//! with that phone number. Dropping a user only deletes its ID key: a phone
+//! key left behind resolves to nothing, or to a user whose phone number no
+//! longer matches, and is ignored until it expires.
+//!
+//! Users of tenants other than the default one are kept under
+//! `user_cache:{tenant}:id:{id}` and `user_cache:{tenant}:phone:...`, the
+//! tenant being the one the call is made for.
+
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use re_core::domain::entities::user::User;
+use re_core::repositories::decorators::UserCacheStoreTrait;
+use re_core::services::tenant::{current_tenant, TenantId};
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the shared user cache
+pub struct RedisUserCacheStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisUserCacheStore {
+    /// Create a new Redis-based shared user cache
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    /// Prefix of the keys of a tenant
+    fn prefix(tenant: &TenantId) -> String {
+        if tenant.is_default() {
+            "user_cache".to_string()
+        } else {
+            format!("user_cache:{}", tenant)
+        }
+    }
+
+    pub(crate) fn id_key(tenant: &TenantId, id: Uuid) -> String {
+        format!("{}:id:{}", Self::prefix(tenant), id)
+    }
+
+    pub(crate) fn phone_key(tenant: &TenantId, phone_hash: &str, country_code: &str) -> String {
+        format!("{}:phone:{}:{}", Self::prefix(tenant), country_code, phone_hash)
+    }
+}
+
+#[async_trait]
+impl UserCacheStoreTrait for RedisUserCacheStore {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<User>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let value: Option<String> = conn
+            .get(self.redis_client.key(&Self::id_key(&current_tenant(), id)))
+            .await
+            .map_err(|e| format!("Failed to load cached user: {}", e))?;
+
+        value
+            .map(|value| {
+                serde_json::from_str(&value).map_err(|e| format!("Failed to parse cached user: {}", e))
+            })
+            .transpose()
+    }
+
+    async fn get_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let id: Option<String> = conn
+            .get(self.redis_client.key(&Self::phone_key(&current_tenant(), phone_hash, country_code)))
+            .await
+            .map_err(|e| format!("Failed to load cached user ID: {}", e))?;
+        let Some(id) = id.and_then(|id| Uuid::parse_str(&id).ok()) else {
+            return Ok(None);
+        };
+
+        let user = self.get_by_id(id).await?;
+        Ok(user.filter(|user| user.phone_hash == phone_hash && user.country_code == country_code))
+    }
+
+    async fn put(&self, user: &User, ttl_seconds: u64) -> Result<(), String> {
+        let value =
+            serde_json::to_string(user).map_err(|e| format!("Failed to serialize user: {}", e))?;
+        let mut conn = self.redis_client.get_connection();
+        let tenant = current_tenant();
+
+        redis::pipe()
+            .atomic()
+            .set_ex(self.redis_client.key(&Self::id_key(&tenant, user.id)), value, ttl_seconds)
+            .set_ex(
+                self.redis_client.key(&Self::phone_key(&tenant, &user.phone_hash, &user.country_code)),
+                user.id.to_string(),
+                ttl_seconds,
+            )
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to cache user: {}", e))
+    }
+
+    async fn remove(&self, id: Uuid) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+
+        conn.del::<_, ()>(self.redis_client.key(&Self::id_key(&current_tenant(), id)))
+            .await
+            .map_err(|e| format!("Failed to drop cached user: {}", e))
+    }
+}
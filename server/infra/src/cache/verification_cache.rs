@@ -5,14 +5,23 @@
 //! - Attempt tracking with 3 max attempts per code
 //! - Secure code storage and validation
 //! 
-//! The service uses Redis for storage with the following key patterns:
+//! The service stores codes in a [`CacheBackend`], Redis unless another
+//! backend is given, with the following key patterns:
 //! - `verification:code:{phone}` - Stores the verification code
 //! - `verification:attempts:{phone}` - Tracks verification attempts
+//!
+//! Calls, hits, misses and latency of each operation are available from
+//! [`VerificationCache::metrics`].
 
+use async_trait::async_trait;
 use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Instant;
 use tracing::{debug, info, warn};
 
-use crate::cache::RedisClient;
+use re_core::services::verification::CacheServiceTrait;
+
+use crate::cache::{CacheBackend, CacheMetrics, RedisClient};
 use crate::InfrastructureError;
 
 /// Default expiration time for verification codes (5 minutes)
@@ -30,8 +39,10 @@ const ATTEMPTS_EXPIRY_SECONDS: u64 = 300;
 /// attempt tracking and automatic expiration.
 #[derive(Clone)]
 pub struct VerificationCache {
-    /// Redis client for cache operations
-    redis_client: RedisClient,
+    /// Backend storing codes and attempt counters
+    backend: Arc<dyn CacheBackend>,
+    /// Metrics of the operations, shared by the clones of this service
+    metrics: Arc<CacheMetrics>,
 }
 
 impl VerificationCache {
@@ -52,7 +63,28 @@ impl VerificationCache {
     /// }
     /// ```
     pub fn new(redis_client: RedisClient) -> Self {
-        Self { redis_client }
+        Self::with_backend(Arc::new(redis_client))
+    }
+
+    /// Create a verification cache service storing codes in any cache backend
+    /// 
+    /// # Arguments
+    /// * `backend` - Backend for cache operations, e.g. an `InMemoryCacheBackend`
+    ///   in tests or a `MemcachedBackend`
+    pub fn with_backend(backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            metrics: Arc::new(CacheMetrics::new()),
+        }
+    }
+
+    /// Metrics of the operations of this service
+    ///
+    /// `verify_code`, `code_exists` and `get_code_ttl` count a hit when a
+    /// code was stored for the phone number; `verify_code` counts neither
+    /// once the attempts are exhausted.
+    pub fn metrics(&self) -> Arc<CacheMetrics> {
+        self.metrics.clone()
     }
 
     /// Store a verification code for a phone number
@@ -83,6 +115,14 @@ impl VerificationCache {
         phone: &str,
         code: &str,
     ) -> Result<(), InfrastructureError> {
+        let started = Instant::now();
+        let result = self.store_hashed_code(phone, code).await;
+        self.metrics.record("store_code", started, &result, |_| None);
+        result
+    }
+
+    /// Store the hash of a code and reset the attempt counter
+    async fn store_hashed_code(&self, phone: &str, code: &str) -> Result<(), InfrastructureError> {
         let code_key = Self::format_code_key(phone);
         let attempts_key = Self::format_attempts_key(phone);
         
@@ -96,12 +136,12 @@ impl VerificationCache {
         );
         
         // Store the hashed code with expiration
-        self.redis_client
-            .set_with_expiry(&code_key, &hashed_code, CODE_EXPIRY_SECONDS)
+        self.backend
+            .set(&code_key, &hashed_code, CODE_EXPIRY_SECONDS)
             .await?;
         
         // Reset attempt counter (will be created on first verification attempt)
-        let _ = self.redis_client.delete(&attempts_key).await;
+        let _ = self.backend.delete(&attempts_key).await;
         
         info!(
             "Verification code stored successfully for phone: {}",
@@ -143,6 +183,18 @@ impl VerificationCache {
         phone: &str,
         code: &str,
     ) -> Result<bool, InfrastructureError> {
+        let started = Instant::now();
+        let result = self.check_code(phone, code).await;
+        self.metrics.record("verify_code", started, &result, |(found, _)| *found);
+        result.map(|(_, is_valid)| is_valid)
+    }
+
+    /// Check a code against the stored one
+    ///
+    /// # Returns
+    /// * `Result<(Option<bool>, bool), InfrastructureError>` - Whether a code
+    ///   was stored, None if the attempts are exhausted, and whether it matched
+    async fn check_code(&self, phone: &str, code: &str) -> Result<(Option<bool>, bool), InfrastructureError> {
         let code_key = Self::format_code_key(phone);
         let attempts_key = Self::format_attempts_key(phone);
         
@@ -152,8 +204,8 @@ impl VerificationCache {
         );
         
         // Check current attempt count
-        let attempts = self.redis_client
-            .increment(&attempts_key, Some(ATTEMPTS_EXPIRY_SECONDS))
+        let attempts = self.backend
+            .incr(&attempts_key, Some(ATTEMPTS_EXPIRY_SECONDS))
             .await?;
         
         // Check if max attempts exceeded
@@ -163,7 +215,7 @@ impl VerificationCache {
                 MAX_ATTEMPTS,
                 Self::mask_phone(phone)
             );
-            return Ok(false);
+            return Ok((None, false));
         }
         
         debug!(
@@ -173,14 +225,14 @@ impl VerificationCache {
         );
         
         // Retrieve stored hashed code
-        let stored_hash = match self.redis_client.get(&code_key).await? {
+        let stored_hash = match self.backend.get(&code_key).await? {
             Some(hash) => hash,
             None => {
                 debug!(
                     "No verification code found for phone: {} (expired or not set)",
                     Self::mask_phone(phone)
                 );
-                return Ok(false);
+                return Ok((Some(false), false));
             }
         };
         
@@ -195,8 +247,8 @@ impl VerificationCache {
             );
             
             // Clean up after successful verification
-            let _ = self.redis_client.delete(&code_key).await;
-            let _ = self.redis_client.delete(&attempts_key).await;
+            let _ = self.backend.delete(&code_key).await;
+            let _ = self.backend.delete(&attempts_key).await;
         } else {
             warn!(
                 "Invalid verification code for phone: {} (attempt {}/{})",
@@ -206,7 +258,7 @@ impl VerificationCache {
             );
         }
         
-        Ok(is_valid)
+        Ok((Some(true), is_valid))
     }
 
     /// Get remaining verification attempts for a phone number
@@ -232,8 +284,11 @@ impl VerificationCache {
     ) -> Result<i64, InfrastructureError> {
         let attempts_key = Self::format_attempts_key(phone);
         
-        // Get current attempts from Redis
-        let current_attempts = match self.redis_client.get(&attempts_key).await? {
+        // Get current attempts from the cache
+        let started = Instant::now();
+        let result = self.backend.get(&attempts_key).await;
+        self.metrics.record("get_remaining_attempts", started, &result, |_| None);
+        let current_attempts = match result? {
             Some(count_str) => count_str.parse::<i64>().unwrap_or(0),
             None => 0,
         };
@@ -269,7 +324,10 @@ impl VerificationCache {
     /// ```
     pub async fn code_exists(&self, phone: &str) -> Result<bool, InfrastructureError> {
         let code_key = Self::format_code_key(phone);
-        self.redis_client.exists(&code_key).await
+        let started = Instant::now();
+        let result = self.backend.exists(&code_key).await;
+        self.metrics.record("code_exists", started, &result, |exists| Some(*exists));
+        result
     }
 
     /// Get time-to-live for a verification code
@@ -292,7 +350,10 @@ impl VerificationCache {
     /// ```
     pub async fn get_code_ttl(&self, phone: &str) -> Result<Option<i64>, InfrastructureError> {
         let code_key = Self::format_code_key(phone);
-        self.redis_client.ttl(&code_key).await
+        let started = Instant::now();
+        let result = self.backend.ttl(&code_key).await;
+        self.metrics.record("get_code_ttl", started, &result, |ttl| Some(ttl.is_some()));
+        result
     }
 
     /// Clear verification code and attempts for a phone number
@@ -322,8 +383,10 @@ impl VerificationCache {
             Self::mask_phone(phone)
         );
         
-        let _ = self.redis_client.delete(&code_key).await;
-        let _ = self.redis_client.delete(&attempts_key).await;
+        let started = Instant::now();
+        let _ = self.backend.delete(&code_key).await;
+        let _ = self.backend.delete(&attempts_key).await;
+        self.metrics.record_call("clear_verification", started.elapsed(), false, None);
         
         info!(
             "Verification data cleared for phone: {}",
@@ -333,19 +396,19 @@ impl VerificationCache {
         Ok(())
     }
 
-    /// Format Redis key for verification code storage
+    /// Format cache key for verification code storage
     fn format_code_key(phone: &str) -> String {
         format!("verification:code:{}", phone)
     }
 
-    /// Format Redis key for attempt tracking
+    /// Format cache key for attempt tracking
     fn format_attempts_key(phone: &str) -> String {
         format!("verification:attempts:{}", phone)
     }
 
     /// Hash a verification code using SHA-256
     /// 
-    /// Provides secure storage by hashing codes before storing in Redis.
+    /// Provides secure storage by hashing codes before storing in the cache.
     fn hash_code(code: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(code.as_bytes());
@@ -363,4 +426,31 @@ impl VerificationCache {
             format!("***{}", &phone[phone.len() - 4..])
         }
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl CacheServiceTrait for VerificationCache {
+    async fn store_code(&self, phone: &str, code: &str) -> Result<(), String> {
+        VerificationCache::store_code(self, phone, code).await.map_err(|e| e.to_string())
+    }
+
+    async fn verify_code(&self, phone: &str, code: &str) -> Result<bool, String> {
+        VerificationCache::verify_code(self, phone, code).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_remaining_attempts(&self, phone: &str) -> Result<i64, String> {
+        VerificationCache::get_remaining_attempts(self, phone).await.map_err(|e| e.to_string())
+    }
+
+    async fn code_exists(&self, phone: &str) -> Result<bool, String> {
+        VerificationCache::code_exists(self, phone).await.map_err(|e| e.to_string())
+    }
+
+    async fn get_code_ttl(&self, phone: &str) -> Result<Option<i64>, String> {
+        VerificationCache::get_code_ttl(self, phone).await.map_err(|e| e.to_string())
+    }
+
+    async fn clear_verification(&self, phone: &str) -> Result<(), String> {
+        VerificationCache::clear_verification(self, phone).await.map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,176 @@
+//! Capability discovery
+//!
+//! What an instance can do depends on the crate features it was built with
+//! and on how it is configured: SMS is only sent for real when a provider
+//! is both compiled in and selected. Capabilities are computed once at
+//! startup, reported to administrators, and checked against what the
+//! environment requires so that, for example, a production instance built
+//! without an SMS provider refuses to start instead of logging codes.
+
+use serde::{Deserialize, Serialize};
+
+use re_shared::config::Environment;
+
+use crate::InfrastructureError;
+
+/// Crate features the infrastructure layer was built with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompiledFeatures {
+    /// `mysql`: MySQL repositories
+    pub mysql: bool,
+    /// `sqlite`: SQLite repositories for local development and CI
+    pub sqlite: bool,
+    /// `redis-cache`: Redis caching and rate limiting
+    pub redis_cache: bool,
+    /// `twilio-sms`: Twilio SMS provider
+    pub twilio_sms: bool,
+    /// `aws-sns`: AWS SNS SMS provider
+    pub aws_sns: bool,
+    /// `vonage-sms`: Vonage SMS provider
+    pub vonage_sms: bool,
+    /// `mock-services`: Mock implementations for testing
+    pub mock_services: bool,
+}
+
+impl CompiledFeatures {
+    /// Features of the running build
+    pub fn current() -> Self {
+        Self {
+            mysql: cfg!(feature = "mysql"),
+            sqlite: cfg!(feature = "sqlite"),
+            redis_cache: cfg!(feature = "redis-cache"),
+            twilio_sms: cfg!(feature = "twilio-sms"),
+            aws_sns: cfg!(feature = "aws-sns"),
+            vonage_sms: cfg!(feature = "vonage-sms"),
+            mock_services: cfg!(feature = "mock-services"),
+        }
+    }
+}
+
+/// Services an instance is configured to use
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfiguredServices {
+    /// Whether a database URL is configured
+    pub database: bool,
+    /// Whether a Redis URL is configured
+    pub redis: bool,
+    /// Selected SMS provider ("twilio", "aws-sns", "vonage", "failover", "mock")
+    pub sms_provider: String,
+}
+
+/// Something an instance can do, given its features and configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Persist data in a database
+    Database,
+    /// Cache and rate-limit through Redis
+    RedisCache,
+    /// Send SMS through a real provider rather than the mock
+    RealSms,
+}
+
+impl Capability {
+    /// Every capability, in reporting order
+    pub const ALL: [Capability; 3] = [Self::Database, Self::RedisCache, Self::RealSms];
+
+    /// Convert to string representation for responses and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::RedisCache => "redis_cache",
+            Self::RealSms => "real_sms",
+        }
+    }
+
+    /// Capabilities an instance must have to run in an environment
+    pub fn required_in(environment: Environment) -> Vec<Capability> {
+        match environment {
+            Environment::Production => Self::ALL.to_vec(),
+            Environment::Staging => vec![Self::Database, Self::RedisCache],
+            Environment::Development => Vec::new(),
+        }
+    }
+}
+
+/// Capabilities of the running instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Environment the instance runs in
+    pub environment: Environment,
+    /// Crate features the instance was built with
+    pub features: CompiledFeatures,
+    /// Selected SMS provider
+    pub sms_provider: String,
+    /// Capabilities the instance has
+    pub available: Vec<Capability>,
+}
+
+impl Capabilities {
+    /// Compute the capabilities of an instance
+    pub fn detect(
+        environment: Environment,
+        features: CompiledFeatures,
+        configured: &ConfiguredServices,
+    ) -> Self {
+        let real_sms = match configured.sms_provider.as_str() {
+            "twilio" => features.twilio_sms,
+            "aws-sns" => features.aws_sns,
+            "vonage" => features.vonage_sms,
+            "failover" => features.twilio_sms || features.aws_sns || features.vonage_sms,
+            _ => false,
+        };
+
+        let available = Capability::ALL
+            .into_iter()
+            .filter(|capability| match capability {
+                Capability::Database => (features.mysql || features.sqlite) && configured.database,
+                Capability::RedisCache => features.redis_cache && configured.redis,
+                Capability::RealSms => real_sms,
+            })
+            .collect();
+
+        Self {
+            environment,
+            features,
+            sms_provider: configured.sms_provider.clone(),
+            available,
+        }
+    }
+
+    /// Whether the instance has a capability
+    pub fn has(&self, capability: Capability) -> bool {
+        self.available.contains(&capability)
+    }
+
+    /// Capabilities the environment requires
+    pub fn required(&self) -> Vec<Capability> {
+        Capability::required_in(self.environment)
+    }
+
+    /// Capabilities the environment requires but the instance lacks
+    pub fn missing(&self) -> Vec<Capability> {
+        self.required()
+            .into_iter()
+            .filter(|capability| !self.has(*capability))
+            .collect()
+    }
+
+    /// Check that the instance has every capability its environment requires
+    ///
+    /// # Returns
+    /// * `Err(InfrastructureError::Config)` - Required capabilities are missing
+    pub fn ensure_required(&self) -> Result<(), InfrastructureError> {
+        let missing = self.missing();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let names: Vec<&str> = missing.iter().map(Capability::as_str).collect();
+        Err(InfrastructureError::Config(format!(
+            "Missing capabilities required in {}: {}",
+            self.environment,
+            names.join(", ")
+        )))
+    }
+}
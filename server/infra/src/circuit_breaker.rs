@@ -0,0 +1,150 @@
+//! Circuit breaker shared by the clients of external services
+//!
+//! After `failure_threshold` consecutive failures the circuit opens and
+//! requests fail at once for `open_duration`, instead of each waiting for
+//! a service that is down to time out. A single trial request is then let
+//! through; its success closes the circuit and its failure opens it again.
+//! What counts as a failure is up to the caller.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests go through
+    Closed,
+    /// Requests fail at once
+    Open,
+    /// A trial request is deciding whether to close the circuit again
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Convert to string representation for logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Circuit breaker counting consecutive failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// Name of the guarded service in logs
+    name: String,
+    failure_threshold: u32,
+    open_duration: Duration,
+    inner: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            name: "Service".to_string(),
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+            }),
+        }
+    }
+
+    /// Name the guarded service in logs, e.g. `Database` or `Twilio`
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Current state of the circuit
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Time until a request is let through again, None when one would be now
+    pub fn retry_after(&self) -> Option<Duration> {
+        let inner = self.lock();
+        if inner.state == CircuitState::Closed {
+            return None;
+        }
+
+        let elapsed = inner.opened_at.map(|at| at.elapsed()).unwrap_or(self.open_duration);
+        self.open_duration.checked_sub(elapsed).filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Ask to send a request
+    ///
+    /// Once the open period is over the first caller is let through as the
+    /// trial request; everyone else keeps being turned away until it
+    /// completes. A trial that never reports back, e.g. because its request
+    /// was cancelled, is replaced by another after a further open period.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The request may be sent
+    /// * `Err(retry_after)` - The circuit is open; try again after this long
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut inner = self.lock();
+        if inner.state == CircuitState::Closed {
+            return Ok(());
+        }
+
+        let elapsed = inner.opened_at.map(|at| at.elapsed()).unwrap_or(self.open_duration);
+        if elapsed < self.open_duration {
+            return Err(self.open_duration - elapsed);
+        }
+
+        info!("{} circuit half-open, sending trial request", self.name);
+        inner.state = CircuitState::HalfOpen;
+        inner.opened_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Record a request that reached the service
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        if inner.state != CircuitState::Closed {
+            info!("{} circuit closed", self.name);
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+    }
+
+    /// Record a failure, opening the circuit at the threshold or
+    /// when the trial request failed
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        let trip = inner.state == CircuitState::HalfOpen
+            || (inner.state == CircuitState::Closed && inner.consecutive_failures >= self.failure_threshold);
+        if trip {
+            warn!(
+                "{} circuit opened after {} consecutive failures; failing fast for {} seconds",
+                self.name,
+                inner.consecutive_failures,
+                self.open_duration.as_secs()
+            );
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
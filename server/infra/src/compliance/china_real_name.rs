@@ -0,0 +1,159 @@
+//! Real-name verification for users in mainland China
+//!
+//! Residents are verified by matching their legal name against their
+//! resident identity card number with a licensed verification provider.
+//! Most submissions are answered immediately; those the provider has to
+//! check manually are answered later through its callback, which carries
+//! the verification ID returned here.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::debug;
+use uuid::Uuid;
+
+use re_core::domain::entities::compliance::ComplianceRequirement;
+use re_core::services::compliance::{
+    ComplianceProviderTrait, ComplianceSubmission, ProviderComplianceOutcome, ProviderComplianceResult,
+};
+use re_shared::config::SecretString;
+
+use crate::InfrastructureError;
+
+/// Provider name recorded on real-name checks in China
+pub const CHINA_REAL_NAME_PROVIDER: &str = "cn_real_name";
+
+/// Real-name verification provider configuration
+#[derive(Debug, Clone)]
+pub struct ChinaRealNameConfig {
+    /// App code issued by the verification provider
+    pub app_code: SecretString,
+    /// Base URL of the verification API
+    pub api_base: String,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl ChinaRealNameConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let app_code = std::env::var("CN_REAL_NAME_APP_CODE")
+            .map_err(|_| InfrastructureError::Config("CN_REAL_NAME_APP_CODE not set".to_string()))?;
+        let api_base = std::env::var("CN_REAL_NAME_API_BASE")
+            .map_err(|_| InfrastructureError::Config("CN_REAL_NAME_API_BASE not set".to_string()))?;
+
+        Ok(Self {
+            app_code: SecretString::from(app_code),
+            api_base,
+            request_timeout_secs: std::env::var("CN_REAL_NAME_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct VerificationRequest<'a> {
+    name: &'a str,
+    id_card_number: &'a str,
+    /// Our identifier for the user, echoed back in callbacks
+    out_user_id: String,
+}
+
+/// The fields of a verification result used by the platform
+#[derive(Debug, Deserialize)]
+struct Verification {
+    verification_id: String,
+    /// "match", "mismatch", "invalid_id" or "review"
+    result: String,
+    message: Option<String>,
+}
+
+impl Verification {
+    /// Map the provider's result to a compliance outcome
+    fn outcome(&self) -> ProviderComplianceOutcome {
+        match self.result.as_str() {
+            "match" => ProviderComplianceOutcome::Verified,
+            "mismatch" | "invalid_id" => ProviderComplianceOutcome::Rejected(
+                self.message.clone().unwrap_or_else(|| self.result.clone()),
+            ),
+            _ => ProviderComplianceOutcome::Pending,
+        }
+    }
+}
+
+/// Real-name verification provider for mainland China
+pub struct ChinaRealNameProvider {
+    client: reqwest::Client,
+    config: ChinaRealNameConfig,
+}
+
+impl ChinaRealNameProvider {
+    /// Create a new real-name verification provider
+    pub fn new(config: ChinaRealNameConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a real-name verification provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::new(ChinaRealNameConfig::from_env()?)
+    }
+
+    /// Send a request and decode the response, returning the provider's error body on failure
+    async fn send<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, String> {
+        let response = request
+            .header("Authorization", format!("APPCODE {}", self.config.app_code.expose_secret()))
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Verification provider returned {}: {}", status, body));
+        }
+        response.json::<T>().await.map_err(|e| format!("Invalid response: {}", e))
+    }
+}
+
+#[async_trait]
+impl ComplianceProviderTrait for ChinaRealNameProvider {
+    fn provider_name(&self) -> &str {
+        CHINA_REAL_NAME_PROVIDER
+    }
+
+    fn requirement(&self) -> ComplianceRequirement {
+        ComplianceRequirement::RealName
+    }
+
+    async fn submit(
+        &self,
+        user_id: Uuid,
+        submission: &ComplianceSubmission,
+    ) -> Result<ProviderComplianceResult, String> {
+        let verification: Verification = self
+            .send(
+                self.client
+                    .post(format!("{}/v1/id-card/verifications", self.config.api_base))
+                    .json(&VerificationRequest {
+                        name: submission.legal_name.trim(),
+                        id_card_number: submission.document_number.trim(),
+                        out_user_id: user_id.to_string(),
+                    }),
+            )
+            .await?;
+
+        let outcome = verification.outcome();
+        debug!(user_id = %user_id, verification = %verification.verification_id, "Submitted real-name verification");
+
+        Ok(ProviderComplianceResult {
+            reference: verification.verification_id,
+            outcome,
+        })
+    }
+}
@@ -0,0 +1,13 @@
+//! Compliance Provider Module
+//!
+//! This module provides adapters to the verification providers that check
+//! the compliance requirements of a market.
+//!
+//! ## Features
+//!
+//! - **China Real-Name Verification**: Matching residents' legal names against their identity card numbers
+
+pub mod china_real_name;
+
+// Re-export commonly used types
+pub use china_real_name::{ChinaRealNameConfig, ChinaRealNameProvider, CHINA_REAL_NAME_PROVIDER};
@@ -0,0 +1,99 @@
+//! Retry and circuit breaking for database access
+//!
+//! During a MySQL failover every new connection attempt hangs until the pool's
+//! acquire timeout, so each request waits that long before failing. Database
+//! access through `DatabasePool::acquire`, `DatabasePool::begin_transaction`
+//! and `DatabasePool::execute`, which the MySQL repositories take all their
+//! connections through, is instead bounded per attempt, retried with
+//! exponential backoff on transient failures, and guarded by a circuit
+//! breaker: after `failure_threshold`
+//! consecutive transient failures the circuit opens and requests fail at
+//! once for `open_duration`. A single trial request is then let through; its
+//! success closes the circuit and its failure opens it again.
+//!
+//! Only failures that point at the database being unreachable count towards
+//! the breaker. Errors such as constraint violations or missing rows are
+//! returned as they are and leave the circuit closed.
+
+use sqlx::mysql::MySqlDatabaseError;
+use std::time::Duration;
+
+pub use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+
+/// MySQL error numbers seen while a server is unreachable, restarting or
+/// being failed over
+const TRANSIENT_MYSQL_ERRORS: [u16; 7] = [
+    1040, // Too many connections
+    1053, // Server shutdown in progress
+    1290, // Running with --read-only (demoted primary)
+    1836, // Running in read-only mode
+    1927, // Connection was killed
+    2006, // Server has gone away
+    2013, // Lost connection during query
+];
+
+/// Configuration for retrying database access and breaking the circuit
+#[derive(Debug, Clone)]
+pub struct DatabaseResilienceConfig {
+    /// Attempts after the first one for transient failures
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled after every retry
+    pub base_retry_delay: Duration,
+    /// Upper bound for the retry delay
+    pub max_retry_delay: Duration,
+    /// Maximum time one attempt waits for a connection
+    pub acquire_timeout: Duration,
+    /// Maximum time one attempt of an operation may take
+    pub query_timeout: Duration,
+    /// Consecutive transient failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is let through
+    pub open_duration: Duration,
+}
+
+impl Default for DatabaseResilienceConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_retry_delay: Duration::from_millis(100),
+            max_retry_delay: Duration::from_secs(1),
+            acquire_timeout: Duration::from_secs(3),
+            query_timeout: Duration::from_secs(10),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(15),
+        }
+    }
+}
+
+impl DatabaseResilienceConfig {
+    /// Delay before the given retry (1-based), doubling up to `max_retry_delay`
+    pub fn retry_delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_retry_delay
+            .saturating_mul(factor)
+            .min(self.max_retry_delay)
+    }
+}
+
+/// Whether an error means the database could not be reached or is failing
+/// over, so the operation may succeed when retried
+pub fn is_transient_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::Tls(_) => true,
+        sqlx::Error::Database(db_err) => db_err
+            .try_downcast_ref::<MySqlDatabaseError>()
+            .is_some_and(|e| TRANSIENT_MYSQL_ERRORS.contains(&e.number())),
+        _ => false,
+    }
+}
+
+/// Error returned without contacting the database while the circuit is open
+pub fn circuit_open_error(retry_after: Duration) -> sqlx::Error {
+    sqlx::Error::Io(std::io::Error::new(
+        std::io::ErrorKind::ConnectionRefused,
+        format!(
+            "Database circuit breaker is open; retry in {} ms",
+            retry_after.as_millis()
+        ),
+    ))
+}
@@ -0,0 +1,128 @@
+//! Retrying writes that lose a lock conflict
+//!
+//! InnoDB resolves a deadlock by rolling back one of the transactions
+//! (error 1213) and gives up on a row lock held too long by another
+//! transaction (error 1205). Either way the losing statement had no effect
+//! and running it again usually succeeds. Write errors are mapped with
+//! [`write_error`], which reports lock conflicts as `DomainError::Conflict`,
+//! and [`retry_on_deadlock`] repeats a write failing with it a bounded
+//! number of times. Delays are jittered so that writers which collided do
+//! not collide again in lockstep. A write still conflicting after the last
+//! attempt fails with `DomainError::Conflict`.
+//!
+//! Only retry whole transactions: after a deadlock MySQL has rolled back
+//! every statement of the transaction, not just the failing one.
+
+use rand::Rng;
+use sqlx::mysql::MySqlDatabaseError;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+use re_core::errors::DomainError;
+
+/// MySQL error numbers of a write losing a lock conflict
+const LOCK_CONFLICT_MYSQL_ERRORS: [u16; 2] = [
+    1205, // Lock wait timeout exceeded
+    1213, // Deadlock found when trying to get lock
+];
+
+/// Configuration for retrying writes that lose a lock conflict
+#[derive(Debug, Clone)]
+pub struct DeadlockRetryPolicy {
+    /// Attempts per write, the first one included
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every retry
+    pub base_delay: Duration,
+    /// Upper bound for the retry delay
+    pub max_delay: Duration,
+}
+
+impl Default for DeadlockRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(20),
+            max_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+impl DeadlockRetryPolicy {
+    /// Delay before the given retry (1-based): between half and all of the
+    /// doubled base delay, capped at `max_delay`
+    pub fn retry_delay(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+/// Whether an error means a write lost a lock conflict with another transaction
+pub fn is_lock_conflict(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(db_err) => db_err
+            .try_downcast_ref::<MySqlDatabaseError>()
+            .is_some_and(|e| LOCK_CONFLICT_MYSQL_ERRORS.contains(&e.number())),
+        _ => false,
+    }
+}
+
+/// Map the error of a write, reporting lock conflicts as `DomainError::Conflict`
+///
+/// # Arguments
+/// * `context` - What failed, e.g. "Failed to update user"
+pub fn write_error(context: &str, error: sqlx::Error) -> DomainError {
+    if is_lock_conflict(&error) {
+        DomainError::Conflict {
+            message: format!("{}: {}", context, error),
+        }
+    } else {
+        DomainError::Internal {
+            message: format!("{}: {}", context, error),
+        }
+    }
+}
+
+/// Run a write, retrying it with the default policy while it loses lock conflicts
+///
+/// # Arguments
+/// * `operation` - Name of the write in logs
+/// * `call` - Runs the write, mapping its errors with [`write_error`]
+pub async fn retry_on_deadlock<T, F, Fut>(operation: &str, call: F) -> Result<T, DomainError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DomainError>>,
+{
+    retry_on_deadlock_with(&DeadlockRetryPolicy::default(), operation, call).await
+}
+
+/// Run a write, retrying it according to `policy` while it loses lock conflicts
+pub async fn retry_on_deadlock_with<T, F, Fut>(
+    policy: &DeadlockRetryPolicy,
+    operation: &str,
+    mut call: F,
+) -> Result<T, DomainError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, DomainError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match call().await {
+            Err(DomainError::Conflict { message }) if attempt < policy.max_attempts => {
+                let delay = policy.retry_delay(attempt);
+                warn!(
+                    "{} lost a lock conflict (attempt {}), retrying in {} ms: {}",
+                    operation,
+                    attempt,
+                    delay.as_millis(),
+                    message
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
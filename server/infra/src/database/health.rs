@@ -0,0 +1,121 @@
+//! Database health checks with degradation states
+//!
+//! A database that answers slowly, or whose pool has no idle connection
+//! left, still serves requests but makes every one of them wait. Reporting
+//! it as degraded rather than healthy or down lets load balancers and
+//! operators tell a slow database from an unreachable one.
+
+use std::time::Duration;
+
+use re_shared::types::{HealthStatus, ServiceHealth};
+
+use super::connection::PoolStatistics;
+
+/// Thresholds of the database health check
+#[derive(Debug, Clone)]
+pub struct DatabaseHealthConfig {
+    /// Query latency above which the database is degraded
+    pub degraded_latency: Duration,
+    /// Query latency after which the database is considered down
+    pub timeout: Duration,
+}
+
+impl Default for DatabaseHealthConfig {
+    fn default() -> Self {
+        Self {
+            degraded_latency: Duration::from_millis(250),
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl DatabaseHealthConfig {
+    /// Load from `DB_HEALTH_DEGRADED_MS` and `DB_HEALTH_TIMEOUT_MS`, with the
+    /// defaults for unset variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            degraded_latency: std::env::var("DB_HEALTH_DEGRADED_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.degraded_latency),
+            timeout: std::env::var("DB_HEALTH_TIMEOUT_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+        }
+    }
+}
+
+/// Health state of the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseHealthState {
+    /// Answering quickly with idle connections to spare
+    Healthy,
+    /// Answering, but slowly or with every connection in use
+    Degraded,
+    /// Not answering within the timeout
+    Down,
+}
+
+/// Result of a database health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseHealth {
+    pub state: DatabaseHealthState,
+    /// Round-trip time of the check query, or the time until it failed
+    pub latency: Duration,
+    /// Why the database is degraded or down
+    pub message: Option<String>,
+}
+
+impl DatabaseHealth {
+    /// Assess the outcome of a check query
+    ///
+    /// # Arguments
+    /// * `result` - Outcome of the query, with the error if it failed
+    /// * `latency` - How long the query took
+    /// * `pool` - Pool statistics at the time of the check
+    /// * `config` - Degradation thresholds
+    pub fn assess(
+        result: Result<(), String>,
+        latency: Duration,
+        pool: &PoolStatistics,
+        config: &DatabaseHealthConfig,
+    ) -> Self {
+        let (state, message) = match result {
+            Err(e) => (DatabaseHealthState::Down, Some(e)),
+            Ok(()) if latency > config.degraded_latency => (
+                DatabaseHealthState::Degraded,
+                Some(format!(
+                    "Query took {} ms, above {} ms",
+                    latency.as_millis(),
+                    config.degraded_latency.as_millis()
+                )),
+            ),
+            Ok(()) if pool.connections >= pool.max_connections && pool.idle_connections == 0 => (
+                DatabaseHealthState::Degraded,
+                Some(format!("All {} connections are in use", pool.max_connections)),
+            ),
+            Ok(()) => (DatabaseHealthState::Healthy, None),
+        };
+
+        Self { state, latency, message }
+    }
+}
+
+impl From<DatabaseHealth> for ServiceHealth {
+    fn from(health: DatabaseHealth) -> Self {
+        Self {
+            status: match health.state {
+                DatabaseHealthState::Healthy => HealthStatus::Healthy,
+                DatabaseHealthState::Degraded => HealthStatus::Degraded,
+                DatabaseHealthState::Down => HealthStatus::Unhealthy,
+            },
+            message: health.message,
+            response_time_ms: Some(health.latency.as_millis() as u64),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Embedded database migrations
+//!
+//! The SQL files in `server/migrations` are compiled into the binary, so a
+//! deployment can bring its schema up to date without sqlx-cli. Applied
+//! versions are tracked in the same `_sqlx_migrations` table sqlx-cli uses,
+//! so databases migrated with either tool stay interchangeable.
+//!
+//! Migrations are plain server-side SQL, sent to MySQL one file at a time:
+//! no `mysql` client directives such as `DELIMITER`, no MariaDB-only syntax
+//! and no global settings the application user cannot change.
+
+use sqlx::migrate::Migrator;
+use sqlx::MySqlPool;
+
+use crate::InfrastructureError;
+
+/// Migrations embedded from `server/migrations`
+static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+/// Migrations the embedded migrator applies
+pub fn migrator() -> &'static Migrator {
+    &MIGRATOR
+}
+
+/// Apply every embedded migration that has not been applied yet
+///
+/// Safe to call on every start. Fails without applying anything further if
+/// an applied migration was edited after it ran or is missing from the binary.
+///
+/// # Arguments
+/// * `pool` - MySQL connection pool of the database to migrate
+///
+/// # Returns
+/// * `Result<(), InfrastructureError>` - Success or error
+pub async fn run_migrations(pool: &MySqlPool) -> Result<(), InfrastructureError> {
+    let migrator = migrator();
+    tracing::info!(
+        "Running database migrations ({} embedded)",
+        migrator.iter().count()
+    );
+
+    migrator.run(pool).await.map_err(|e| {
+        tracing::error!("Database migration failed: {}", e);
+        InfrastructureError::Migration(e)
+    })?;
+
+    tracing::info!("Database migrations completed");
+    Ok(())
+}
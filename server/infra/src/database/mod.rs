@@ -1,16 +1,61 @@
-//! Database module - MySQL implementations using SQLx
+//! Database module - MySQL (and optionally SQLite) implementations using SQLx
 //! 
 //! This module provides database access layer implementations including:
 //! - Connection pool management
+//! - Health checks reporting healthy, degraded or down
+//! - Retries and circuit breaking for transient connection failures
+//! - Retries of writes losing a deadlock or lock wait
 //! - Repository pattern implementations
+//! - Country-based sharding of users across regional databases
 //! - Transaction support
 //! - Database migrations
+//! - Detecting schema drift at startup
+//! - Database-agnostic user, token and audit log repositories over `AnyPool`
+//! - SQLite connection for local development and CI (`sqlite` feature)
 
+pub mod circuit_breaker;
 pub mod connection;
+pub mod deadlock;
+pub mod health;
+pub mod migrations;
 pub mod mysql;
 pub mod repositories;
+pub mod schema;
+pub mod sharding;
+#[cfg(feature = "generic-sql")]
+pub mod sql;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(test)]
+mod tests;
 
 // Re-export commonly used types
+pub use circuit_breaker::{CircuitBreaker, CircuitState, DatabaseResilienceConfig};
 pub use connection::{DatabasePool, PoolStatistics};
-pub use mysql::{MySqlUserRepository, MySqlTokenRepository, MySqlAuditLogRepository};
-pub use repositories::OtpRepository;
\ No newline at end of file
+pub use deadlock::{retry_on_deadlock, DeadlockRetryPolicy};
+pub use health::{DatabaseHealth, DatabaseHealthConfig, DatabaseHealthState};
+pub use migrations::run_migrations;
+pub use schema::verify_schema;
+pub use mysql::{
+    MySqlScheduledMessageRepository, MySqlClosureDateRepository, MySqlServiceAreaRepository,
+    MySqlCampaignRepository, MySqlPaymentLedgerRepository, MySqlReconciliationReportRepository,
+    MySqlPaymentRepository, MySqlPayoutRepository, MySqlDisputeRepository,
+    MySqlPaymentWebhookEventRepository, MySqlJournalRepository, MySqlFeeScheduleRepository,
+    MySqlOrderFeeRepository, MySqlCreditWalletRepository, MySqlPaymentRiskRepository,
+    MySqlOAuthRepository, MySqlAdminIdentityRepository, MySqlPaymentMethodRepository,
+    MySqlPayoutMethodRepository, MySqlCompletionChecklistRepository,
+    MySqlSecurityWebhookRepository, MySqlAttributionRepository, MySqlOrganizationRepository,
+    MySqlLegalDocumentRepository, MySqlUserImportRepository,
+    MySqlPhoneHashRepository, MySqlComplianceRepository, MySqlExportJobRepository,
+    MySqlWebhookDeadLetterRepository, MySqlAuditPartitionManager,
+};
+pub use repositories::OtpRepository;
+pub use sharding::{ShardRouter, ShardedUnitOfWork, ShardedUserRepository};
+#[cfg(feature = "generic-sql")]
+pub use sql::{
+    SqlDatabase, SqlDialect, SqlUserRepository, SqlTokenRepository, SqlAuditLogRepository,
+    SqlUnitOfWork,
+};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteDatabase;
@@ -0,0 +1,134 @@
+//! MySQL implementation of the AdminIdentityRepository trait.
+//!
+//! Roles are stored as a JSON array of role names. Administrators are
+//! upserted on every login, keyed by identity provider issuer and subject.
+//! Back-office staff operate every tenant, so identities are not scoped to
+//! a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::admin::{AdminIdentity, AdminRole};
+use re_core::errors::DomainError;
+use re_core::repositories::AdminIdentityRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for an administrator
+const IDENTITY_COLUMNS: &str = "id, issuer, subject, email, name, roles, created_at, last_login_at";
+
+/// MySQL implementation of the administrator identity repository
+pub struct MySqlAdminIdentityRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlAdminIdentityRepository {
+    /// Create a new MySQL administrator identity repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlAdminIdentityRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to AdminIdentity entity
+    fn row_to_identity(row: &sqlx::mysql::MySqlRow) -> Result<AdminIdentity, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let roles: serde_json::Value = row.try_get("roles")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get roles: {}", e) })?;
+        let roles: Vec<String> = serde_json::from_value(roles)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid roles: {}", e) })?;
+        let roles = roles
+            .iter()
+            .map(|role| AdminRole::from_str(role)
+                .ok_or_else(|| DomainError::Internal { message: format!("Unknown admin role: {}", role) }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AdminIdentity {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            issuer: row.try_get("issuer")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get issuer: {}", e) })?,
+            subject: row.try_get("subject")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get subject: {}", e) })?,
+            email: row.try_get("email")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get email: {}", e) })?,
+            name: row.try_get("name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get name: {}", e) })?,
+            roles,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            last_login_at: row.try_get::<DateTime<Utc>, _>("last_login_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get last_login_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl AdminIdentityRepository for MySqlAdminIdentityRepository {
+    async fn find_by_subject(&self, issuer: &str, subject: &str) -> Result<Option<AdminIdentity>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM admin_identities WHERE issuer = ? AND subject = ?",
+            IDENTITY_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(issuer)
+            .bind(subject)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find admin identity: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_identity).transpose()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<AdminIdentity>, DomainError> {
+        let query = format!("SELECT {} FROM admin_identities WHERE id = ?", IDENTITY_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find admin identity: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_identity).transpose()
+    }
+
+    async fn save(&self, identity: AdminIdentity) -> Result<AdminIdentity, DomainError> {
+        let roles = serde_json::to_string(&identity.role_names())
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize roles: {}", e) })?;
+
+        let query = r#"
+            INSERT INTO admin_identities (
+                id, issuer, subject, email, name, roles, created_at, last_login_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                email = VALUES(email),
+                name = VALUES(name),
+                roles = VALUES(roles),
+                last_login_at = VALUES(last_login_at)
+        "#;
+
+        sqlx::query(query)
+            .bind(identity.id.to_string())
+            .bind(&identity.issuer)
+            .bind(&identity.subject)
+            .bind(&identity.email)
+            .bind(&identity.name)
+            .bind(roles)
+            .bind(identity.created_at)
+            .bind(identity.last_login_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save admin identity: {}", e) })?;
+
+        Ok(identity)
+    }
+}
@@ -0,0 +1,187 @@
+//! MySQL implementation of the AttributionRepository trait.
+//!
+//! One row is kept per user. Cohorts are grouped by the Monday of the
+//! registration week, and joined with the users table to count users who
+//! went on to select a user type. Rows are deleted with their user, and
+//! are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::attribution::{
+    AttributionCohort, RegistrationAttribution, UserAttribution,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::AttributionRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+const ATTRIBUTION_COLUMNS: &str = r#"
+    user_id, utm_source, utm_medium, utm_campaign, utm_term, utm_content,
+    referrer, registered_at
+"#;
+
+/// MySQL implementation of the attribution repository
+pub struct MySqlAttributionRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlAttributionRepository {
+    /// Create a new MySQL attribution repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlAttributionRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to UserAttribution entity
+    fn row_to_attribution(row: &sqlx::mysql::MySqlRow) -> Result<UserAttribution, DomainError> {
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+        let value = |column: &str| -> Result<Option<String>, DomainError> {
+            row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })
+        };
+
+        Ok(UserAttribution {
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            attribution: RegistrationAttribution {
+                utm_source: value("utm_source")?,
+                utm_medium: value("utm_medium")?,
+                utm_campaign: value("utm_campaign")?,
+                utm_term: value("utm_term")?,
+                utm_content: value("utm_content")?,
+                referrer: value("referrer")?,
+                // Only attribution of consenting users is stored
+                marketing_consent: true,
+            },
+            registered_at: row.try_get::<DateTime<Utc>, _>("registered_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get registered_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to AttributionCohort
+    fn row_to_cohort(row: &sqlx::mysql::MySqlRow) -> Result<AttributionCohort, DomainError> {
+        let count = |column: &str| -> Result<u64, DomainError> {
+            row.try_get::<i64, _>(column)
+                .map(|value| value as u64)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })
+        };
+
+        Ok(AttributionCohort {
+            cohort_week: row.try_get::<NaiveDate, _>("cohort_week")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get cohort_week: {}", e) })?,
+            utm_source: row.try_get("utm_source")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get utm_source: {}", e) })?,
+            utm_medium: row.try_get("utm_medium")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get utm_medium: {}", e) })?,
+            utm_campaign: row.try_get("utm_campaign")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get utm_campaign: {}", e) })?,
+            registrations: count("registrations")?,
+            activated: count("activated")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AttributionRepository for MySqlAttributionRepository {
+    async fn save(&self, attribution: &UserAttribution) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO user_attributions (
+                user_id, tenant_id, utm_source, utm_medium, utm_campaign, utm_term, utm_content,
+                referrer, registered_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                utm_source = VALUES(utm_source),
+                utm_medium = VALUES(utm_medium),
+                utm_campaign = VALUES(utm_campaign),
+                utm_term = VALUES(utm_term),
+                utm_content = VALUES(utm_content),
+                referrer = VALUES(referrer),
+                registered_at = VALUES(registered_at)
+        "#;
+
+        let values = &attribution.attribution;
+        sqlx::query(query)
+            .bind(attribution.user_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&values.utm_source)
+            .bind(&values.utm_medium)
+            .bind(&values.utm_campaign)
+            .bind(&values.utm_term)
+            .bind(&values.utm_content)
+            .bind(&values.referrer)
+            .bind(attribution.registered_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save attribution: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<UserAttribution>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM user_attributions WHERE user_id = ? AND tenant_id = ? LIMIT 1",
+            ATTRIBUTION_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find attribution: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_attribution(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM user_attributions WHERE user_id = ? AND tenant_id = ?")
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete attribution: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn cohorts(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<AttributionCohort>, DomainError> {
+        let query = r#"
+            SELECT
+                DATE(DATE_SUB(a.registered_at, INTERVAL WEEKDAY(a.registered_at) DAY)) AS cohort_week,
+                a.utm_source,
+                a.utm_medium,
+                a.utm_campaign,
+                CAST(COUNT(*) AS SIGNED) AS registrations,
+                CAST(COALESCE(SUM(u.user_type IS NOT NULL), 0) AS SIGNED) AS activated
+            FROM user_attributions a
+            JOIN users u ON u.id = a.user_id AND u.deleted_at IS NULL
+            WHERE a.tenant_id = ? AND a.registered_at >= ? AND a.registered_at < ?
+            GROUP BY cohort_week, a.utm_source, a.utm_medium, a.utm_campaign
+            ORDER BY cohort_week ASC, registrations DESC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(current_tenant().to_string())
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to load attribution cohorts: {}", e) })?;
+
+        rows.iter().map(Self::row_to_cohort).collect()
+    }
+}
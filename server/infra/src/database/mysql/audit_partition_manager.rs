@@ -0,0 +1,164 @@
+//! MySQL partition maintenance for the auth_audit_log table.
+//!
+//! The table is partitioned by `RANGE (UNIX_TIMESTAMP(created_at))` with one
+//! partition per calendar month (UTC), named `pYYYYMM`, and a trailing
+//! `pmax` partition bounded by `MAXVALUE`. New months are split off `pmax`
+//! before any rows reach them, so reorganizing it stays cheap.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use sqlx::Row;
+
+use re_core::services::audit_retention::AuditPartitionManagerTrait;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Partition receiving rows beyond the last monthly partition
+const CATCH_ALL_PARTITION: &str = "pmax";
+
+/// A partition of the audit log table
+struct Partition {
+    name: String,
+    /// Exclusive upper bound as a Unix timestamp; `None` for `MAXVALUE`
+    bound: Option<i64>,
+}
+
+/// MySQL implementation of AuditPartitionManagerTrait
+pub struct MySqlAuditPartitionManager {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlAuditPartitionManager {
+    /// Create a new MySQL audit partition manager
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the partitions of the audit log table in order
+    async fn partitions(&self) -> Result<Vec<Partition>, String> {
+        let query = r#"
+            SELECT PARTITION_NAME, PARTITION_DESCRIPTION
+            FROM information_schema.PARTITIONS
+            WHERE TABLE_SCHEMA = DATABASE()
+            AND TABLE_NAME = 'auth_audit_log'
+            AND PARTITION_NAME IS NOT NULL
+            ORDER BY PARTITION_ORDINAL_POSITION
+        "#;
+
+        let rows = sqlx::query(query)
+            .fetch_all(&mut *connection(&self.pool).await.map_err(|e| e.to_string())?)
+            .await
+            .map_err(|e| format!("Failed to load audit partitions: {}", e))?;
+
+        rows.iter()
+            .map(|row| {
+                let name: String = row
+                    .try_get("PARTITION_NAME")
+                    .map_err(|e| format!("Failed to get partition name: {}", e))?;
+                let description: Option<String> = row
+                    .try_get("PARTITION_DESCRIPTION")
+                    .map_err(|e| format!("Failed to get partition bound: {}", e))?;
+                let bound = match description.as_deref() {
+                    None | Some("MAXVALUE") => None,
+                    Some(value) => Some(
+                        value
+                            .parse()
+                            .map_err(|e| format!("Invalid bound of partition {}: {}", name, e))?,
+                    ),
+                };
+                Ok(Partition { name, bound })
+            })
+            .collect()
+    }
+
+    /// First second of the month after the month of `at`
+    fn next_month_start(at: DateTime<Utc>) -> DateTime<Utc> {
+        let (year, month) = if at.month() == 12 {
+            (at.year() + 1, 1)
+        } else {
+            (at.year(), at.month() + 1)
+        };
+        Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+            .single()
+            .unwrap_or(at)
+    }
+
+    async fn alter(&self, statement: &str) -> Result<(), String> {
+        sqlx::query(statement)
+            .execute(&mut *connection(&self.pool).await.map_err(|e| e.to_string())?)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to alter audit partitions: {}", e))
+    }
+}
+
+#[async_trait]
+impl AuditPartitionManagerTrait for MySqlAuditPartitionManager {
+    async fn ensure_partitions(&self, through: DateTime<Utc>) -> Result<Vec<String>, String> {
+        let partitions = self.partitions().await?;
+        if partitions.is_empty() {
+            return Err("auth_audit_log is not partitioned".to_string());
+        }
+
+        let Some(last_bound) = partitions.iter().filter_map(|p| p.bound).max() else {
+            return Err("auth_audit_log has no monthly partitions".to_string());
+        };
+        let Some(mut start) = DateTime::from_timestamp(last_bound, 0) else {
+            return Err(format!("Invalid partition bound: {}", last_bound));
+        };
+
+        let mut created = Vec::new();
+        let mut definitions = Vec::new();
+        while start <= through {
+            let end = Self::next_month_start(start);
+            let name = format!("p{}", start.format("%Y%m"));
+            definitions.push(format!("PARTITION {} VALUES LESS THAN ({})", name, end.timestamp()));
+            created.push(name);
+            start = end;
+        }
+        if created.is_empty() {
+            return Ok(created);
+        }
+
+        let has_catch_all = partitions.iter().any(|p| p.bound.is_none());
+        let statement = if has_catch_all {
+            definitions.push(format!("PARTITION {} VALUES LESS THAN MAXVALUE", CATCH_ALL_PARTITION));
+            format!(
+                "ALTER TABLE auth_audit_log REORGANIZE PARTITION {} INTO ({})",
+                CATCH_ALL_PARTITION,
+                definitions.join(", ")
+            )
+        } else {
+            format!("ALTER TABLE auth_audit_log ADD PARTITION ({})", definitions.join(", "))
+        };
+        self.alter(&statement).await?;
+
+        Ok(created)
+    }
+
+    async fn drop_partitions_before(&self, before: DateTime<Utc>) -> Result<Vec<String>, String> {
+        let cutoff = before.timestamp();
+        let expired: Vec<String> = self
+            .partitions()
+            .await?
+            .into_iter()
+            .filter(|p| p.bound.is_some_and(|bound| bound <= cutoff))
+            .map(|p| p.name)
+            .collect();
+        if expired.is_empty() {
+            return Ok(expired);
+        }
+
+        self.alter(&format!(
+            "ALTER TABLE auth_audit_log DROP PARTITION {}",
+            expired.join(", ")
+        ))
+        .await?;
+
+        Ok(expired)
+    }
+}
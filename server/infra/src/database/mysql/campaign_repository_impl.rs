@@ -0,0 +1,489 @@
+//! MySQL implementation of the CampaignRepository trait.
+//!
+//! Campaigns keep their audience filters and localized content in JSON
+//! columns. Audiences are resolved with a single `INSERT ... SELECT` over
+//! the users table, and the unique `(campaign_id, user_id)` key keeps
+//! enqueueing idempotent. Campaigns and deliveries are claimed with
+//! conditional updates so several dispatchers can share the tables.
+//!
+//! Campaigns and their deliveries are scoped to the current tenant, and
+//! audiences are drawn from that tenant's users only.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::campaign::{
+    Campaign, CampaignChannel, CampaignDelivery, CampaignStats, CampaignStatus, DeliveryStatus,
+};
+use re_core::domain::entities::user::UserType;
+use re_core::errors::DomainError;
+use re_core::repositories::CampaignRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+const CAMPAIGN_COLUMNS: &str = r#"
+    id, name, channel, audience, content, default_language, scheduled_at, status,
+    audience_size, created_by, created_at, updated_at, completed_at
+"#;
+
+const DELIVERY_COLUMNS: &str = r#"
+    id, campaign_id, user_id, country_code, status, next_attempt_at,
+    error, provider_message_id, sent_at, opened_at
+"#;
+
+/// MySQL implementation of the campaign repository
+pub struct MySqlCampaignRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlCampaignRepository {
+    /// Create a new MySQL campaign repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlCampaignRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to Campaign entity
+    fn row_to_campaign(row: &sqlx::mysql::MySqlRow) -> Result<Campaign, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let created_by: String = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = CampaignStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown campaign status: {}", status_str) })?;
+
+        let channel_str: String = row.try_get("channel")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get channel: {}", e) })?;
+        let channel = CampaignChannel::from_str(&channel_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown campaign channel: {}", channel_str) })?;
+
+        let audience: serde_json::Value = row.try_get("audience")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get audience: {}", e) })?;
+        let content: serde_json::Value = row.try_get("content")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get content: {}", e) })?;
+
+        let audience_size: u64 = row.try_get("audience_size")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get audience_size: {}", e) })?;
+
+        Ok(Campaign {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            name: row.try_get("name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get name: {}", e) })?,
+            channel,
+            audience: serde_json::from_value(audience)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid audience: {}", e) })?,
+            content: serde_json::from_value(content)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid content: {}", e) })?,
+            default_language: row.try_get("default_language")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get default_language: {}", e) })?,
+            scheduled_at: row.try_get::<DateTime<Utc>, _>("scheduled_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get scheduled_at: {}", e) })?,
+            status,
+            audience_size,
+            created_by: Uuid::parse_str(&created_by)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid creator UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+            completed_at: row.try_get("completed_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get completed_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to CampaignDelivery entity
+    fn row_to_delivery(row: &sqlx::mysql::MySqlRow) -> Result<CampaignDelivery, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let campaign_id: String = row.try_get("campaign_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get campaign_id: {}", e) })?;
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = DeliveryStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown delivery status: {}", status_str) })?;
+
+        Ok(CampaignDelivery {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            campaign_id: Uuid::parse_str(&campaign_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid campaign UUID: {}", e) })?,
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid user UUID: {}", e) })?,
+            country_code: row.try_get("country_code")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get country_code: {}", e) })?,
+            status,
+            next_attempt_at: row.try_get::<DateTime<Utc>, _>("next_attempt_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get next_attempt_at: {}", e) })?,
+            error: row.try_get("error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get error: {}", e) })?,
+            provider_message_id: row.try_get("provider_message_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_message_id: {}", e) })?,
+            sent_at: row.try_get("sent_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get sent_at: {}", e) })?,
+            opened_at: row.try_get("opened_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get opened_at: {}", e) })?,
+        })
+    }
+
+    /// Serialize a value for a JSON column
+    fn to_json<T: serde::Serialize>(value: &T) -> Result<String, DomainError> {
+        serde_json::to_string(value)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize campaign: {}", e) })
+    }
+}
+
+#[async_trait]
+impl CampaignRepository for MySqlCampaignRepository {
+    async fn save(&self, campaign: Campaign) -> Result<Campaign, DomainError> {
+        let query = r#"
+            INSERT INTO campaigns (
+                id, tenant_id, name, channel, audience, content, default_language, scheduled_at, status,
+                audience_size, created_by, created_at, updated_at, completed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(campaign.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&campaign.name)
+            .bind(campaign.channel.as_str())
+            .bind(Self::to_json(&campaign.audience)?)
+            .bind(Self::to_json(&campaign.content)?)
+            .bind(&campaign.default_language)
+            .bind(campaign.scheduled_at)
+            .bind(campaign.status.as_str())
+            .bind(campaign.audience_size)
+            .bind(campaign.created_by.to_string())
+            .bind(campaign.created_at)
+            .bind(campaign.updated_at)
+            .bind(campaign.completed_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save campaign: {}", e) })?;
+
+        Ok(campaign)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Campaign>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM campaigns WHERE id = ? AND tenant_id = ? LIMIT 1",
+            CAMPAIGN_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find campaign: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_campaign(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn list_recent(&self, limit: usize) -> Result<Vec<Campaign>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM campaigns WHERE tenant_id = ? ORDER BY created_at DESC LIMIT ?",
+            CAMPAIGN_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(limit as i64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list campaigns: {}", e) })?;
+
+        rows.iter().map(Self::row_to_campaign).collect()
+    }
+
+    async fn find_by_status(&self, status: CampaignStatus) -> Result<Vec<Campaign>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM campaigns WHERE tenant_id = ? AND status = ? ORDER BY scheduled_at ASC",
+            CAMPAIGN_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(status.as_str())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find campaigns: {}", e) })?;
+
+        rows.iter().map(Self::row_to_campaign).collect()
+    }
+
+    async fn update(&self, campaign: Campaign) -> Result<Campaign, DomainError> {
+        let query = r#"
+            UPDATE campaigns
+            SET status = ?, audience_size = ?, updated_at = ?, completed_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(campaign.status.as_str())
+            .bind(campaign.audience_size)
+            .bind(campaign.updated_at)
+            .bind(campaign.completed_at)
+            .bind(campaign.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update campaign: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound {
+                resource: "Campaign".to_string(),
+            });
+        }
+
+        Ok(campaign)
+    }
+
+    async fn claim_start(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE campaigns
+            SET status = 'dispatching', updated_at = ?
+            WHERE id = ? AND tenant_id = ?
+              AND (status = 'scheduled' OR (status = 'dispatching' AND updated_at <= ?))
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(now)
+            .bind(campaign_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(stale_before)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to claim campaign: {}", e) })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn enqueue_deliveries(&self, campaign: &Campaign) -> Result<u64, DomainError> {
+        let audience = &campaign.audience;
+        let now = Utc::now();
+        let tenant_id = current_tenant().to_string();
+
+        let mut query = String::from(
+            r#"
+            INSERT IGNORE INTO campaign_deliveries (
+                id, tenant_id, campaign_id, user_id, country_code, status, next_attempt_at, created_at
+            )
+            SELECT UUID(), tenant_id, ?, id, country_code, 'pending', ?, ?
+            FROM users
+            WHERE tenant_id = ? AND is_blocked = FALSE AND deleted_at IS NULL
+            "#,
+        );
+        if audience.user_type.is_some() {
+            query.push_str(" AND user_type = ?");
+        }
+        if !audience.country_codes.is_empty() {
+            let placeholders = vec!["?"; audience.country_codes.len()].join(", ");
+            query.push_str(&format!(" AND country_code IN ({})", placeholders));
+        }
+        if audience.active_within_days.is_some() {
+            query.push_str(" AND last_login_at >= ?");
+        }
+
+        let mut insert = sqlx::query(&query)
+            .bind(campaign.id.to_string())
+            .bind(now)
+            .bind(now)
+            .bind(&tenant_id);
+        if let Some(user_type) = audience.user_type {
+            insert = insert.bind(match user_type {
+                UserType::Customer => "customer",
+                UserType::Worker => "worker",
+            });
+        }
+        for country_code in &audience.country_codes {
+            insert = insert.bind(country_code);
+        }
+        if let Some(days) = audience.active_within_days {
+            insert = insert.bind(now - Duration::days(days as i64));
+        }
+
+        insert
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to enqueue campaign deliveries: {}", e) })?;
+
+        let count = "SELECT COUNT(*) AS total FROM campaign_deliveries WHERE campaign_id = ? AND tenant_id = ?";
+        let total: i64 = sqlx::query(count)
+            .bind(campaign.id.to_string())
+            .bind(&tenant_id)
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to count campaign deliveries: {}", e) })?
+            .try_get("total")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get total: {}", e) })?;
+
+        Ok(total as u64)
+    }
+
+    async fn claim_pending_deliveries(
+        &self,
+        campaign_id: Uuid,
+        now: DateTime<Utc>,
+        limit: usize,
+        claim_until: DateTime<Utc>,
+    ) -> Result<Vec<CampaignDelivery>, DomainError> {
+        // The conditional UPDATE is atomic, so concurrent dispatchers claim
+        // disjoint rows; each claim is then read back by its own ID
+        let claim_id = Uuid::new_v4().to_string();
+        let tenant_id = current_tenant().to_string();
+        let mut conn = connection(&self.pool).await?;
+
+        let claim = r#"
+            UPDATE campaign_deliveries
+            SET status = 'processing', claimed_by = ?, claimed_until = ?
+            WHERE campaign_id = ? AND tenant_id = ?
+              AND ((status = 'pending' AND next_attempt_at <= ?)
+                OR (status = 'processing' AND claimed_until <= ?))
+            ORDER BY next_attempt_at ASC
+            LIMIT ?
+        "#;
+
+        let claimed = sqlx::query(claim)
+            .bind(&claim_id)
+            .bind(claim_until)
+            .bind(campaign_id.to_string())
+            .bind(&tenant_id)
+            .bind(now)
+            .bind(now)
+            .bind(limit as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to claim pending deliveries: {}", e) })?;
+
+        if claimed.rows_affected() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM campaign_deliveries
+            WHERE claimed_by = ? AND tenant_id = ? AND status = 'processing'
+            ORDER BY next_attempt_at ASC
+            "#,
+            DELIVERY_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(&claim_id)
+            .bind(&tenant_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to load claimed deliveries: {}", e) })?;
+
+        rows.iter().map(Self::row_to_delivery).collect()
+    }
+
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<CampaignDelivery>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM campaign_deliveries WHERE id = ? AND tenant_id = ? LIMIT 1",
+            DELIVERY_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find campaign delivery: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_delivery(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn update_delivery(&self, delivery: CampaignDelivery) -> Result<CampaignDelivery, DomainError> {
+        let query = r#"
+            UPDATE campaign_deliveries
+            SET status = ?, next_attempt_at = ?, error = ?, provider_message_id = ?,
+                sent_at = ?, opened_at = ?, claimed_by = NULL, claimed_until = NULL
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(delivery.status.as_str())
+            .bind(delivery.next_attempt_at)
+            .bind(&delivery.error)
+            .bind(&delivery.provider_message_id)
+            .bind(delivery.sent_at)
+            .bind(delivery.opened_at)
+            .bind(delivery.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update campaign delivery: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound {
+                resource: "Campaign delivery".to_string(),
+            });
+        }
+
+        Ok(delivery)
+    }
+
+    async fn stats(&self, campaign_id: Uuid) -> Result<CampaignStats, DomainError> {
+        let query = r#"
+            SELECT
+                CAST(COALESCE(SUM(status IN ('pending', 'processing')), 0) AS SIGNED) AS pending,
+                CAST(COALESCE(SUM(status = 'sent'), 0) AS SIGNED) AS sent,
+                CAST(COALESCE(SUM(status = 'failed'), 0) AS SIGNED) AS failed,
+                CAST(COALESCE(SUM(status = 'opted_out'), 0) AS SIGNED) AS opted_out,
+                CAST(COALESCE(SUM(opened_at IS NOT NULL), 0) AS SIGNED) AS opened
+            FROM campaign_deliveries
+            WHERE campaign_id = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(campaign_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to load campaign stats: {}", e) })?;
+
+        let count = |column: &str| -> Result<u64, DomainError> {
+            row.try_get::<i64, _>(column)
+                .map(|value| value as u64)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })
+        };
+
+        Ok(CampaignStats {
+            pending: count("pending")?,
+            sent: count("sent")?,
+            failed: count("failed")?,
+            opted_out: count("opted_out")?,
+            opened: count("opened")?,
+        })
+    }
+}
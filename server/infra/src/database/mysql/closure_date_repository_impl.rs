@@ -0,0 +1,132 @@
+//! MySQL implementation of the ClosureDateRepository trait.
+//!
+//! This module persists ad-hoc business closure dates added by
+//! administrators for the business calendar, scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::calendar::ClosureDate;
+use re_core::errors::DomainError;
+use re_core::repositories::ClosureDateRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the closure date repository
+pub struct MySqlClosureDateRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlClosureDateRepository {
+    /// Create a new MySQL closure date repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlClosureDateRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ClosureDate entity
+    fn row_to_closure(row: &sqlx::mysql::MySqlRow) -> Result<ClosureDate, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let created_by: Option<String> = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+
+        Ok(ClosureDate {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            market: row.try_get("market")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?,
+            date: row.try_get::<NaiveDate, _>("closure_date")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get closure_date: {}", e) })?,
+            reason: row.try_get("reason")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reason: {}", e) })?,
+            created_by: created_by
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid creator UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl ClosureDateRepository for MySqlClosureDateRepository {
+    async fn save(&self, closure: ClosureDate) -> Result<ClosureDate, DomainError> {
+        let query = r#"
+            INSERT INTO business_closures (id, tenant_id, market, closure_date, reason, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(closure.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&closure.market)
+            .bind(closure.date)
+            .bind(&closure.reason)
+            .bind(closure.created_by.map(|id| id.to_string()))
+            .bind(closure.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| {
+                if let sqlx::Error::Database(db_err) = &e {
+                    if db_err.is_unique_violation() {
+                        return DomainError::Validation {
+                            message: format!(
+                                "Market {} already has a closure on {}",
+                                closure.market, closure.date
+                            ),
+                        };
+                    }
+                }
+                DomainError::Internal { message: format!("Failed to save closure date: {}", e) }
+            })?;
+
+        Ok(closure)
+    }
+
+    async fn find_by_market(
+        &self,
+        market: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ClosureDate>, DomainError> {
+        let query = r#"
+            SELECT id, market, closure_date, reason, created_by, created_at
+            FROM business_closures
+            WHERE tenant_id = ? AND market = ? AND closure_date BETWEEN ? AND ?
+            ORDER BY closure_date ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(current_tenant().to_string())
+            .bind(market)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find closure dates: {}", e) })?;
+
+        rows.iter().map(Self::row_to_closure).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM business_closures WHERE id = ? AND tenant_id = ?")
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete closure date: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,248 @@
+//! MySQL implementation of the CompletionChecklistRepository trait.
+//!
+//! Templates and checklists are scoped to the current tenant, so each tenant
+//! versions its own templates.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::completion::{ChecklistTemplate, CompletionChecklist, CustomerSignOff};
+use re_core::errors::DomainError;
+use re_core::repositories::CompletionChecklistRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a checklist template
+const TEMPLATE_COLUMNS: &str = "id, category, version, items, created_by, created_at";
+
+/// Columns selected for an order's completion checklist
+const CHECKLIST_COLUMNS: &str = r#"
+    order_id, worker_id, customer_id, template_id, template_version, items, submitted_at,
+    signed_name, signed_at, device_info, content_hash
+"#;
+
+/// MySQL implementation of the completion checklist repository
+pub struct MySqlCompletionChecklistRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlCompletionChecklistRepository {
+    /// Create a new MySQL completion checklist repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlCompletionChecklistRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Parse a UUID column
+    fn uuid(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Uuid, DomainError> {
+        let value: String = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        Uuid::parse_str(&value)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+    }
+
+    /// Convert database row to ChecklistTemplate entity
+    fn row_to_template(row: &sqlx::mysql::MySqlRow) -> Result<ChecklistTemplate, DomainError> {
+        let items: serde_json::Value = row.try_get("items")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get items: {}", e) })?;
+        let created_by: Option<String> = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+
+        Ok(ChecklistTemplate {
+            id: Self::uuid(row, "id")?,
+            category: row.try_get("category")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get category: {}", e) })?,
+            version: row.try_get("version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })?,
+            items: serde_json::from_value(items)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid checklist items: {}", e) })?,
+            created_by: created_by
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to CompletionChecklist entity
+    fn row_to_checklist(row: &sqlx::mysql::MySqlRow) -> Result<CompletionChecklist, DomainError> {
+        let items: serde_json::Value = row.try_get("items")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get items: {}", e) })?;
+        let signed_name: Option<String> = row.try_get("signed_name")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get signed_name: {}", e) })?;
+        let signed_at: Option<DateTime<Utc>> = row.try_get("signed_at")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get signed_at: {}", e) })?;
+        let content_hash: Option<String> = row.try_get("content_hash")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get content_hash: {}", e) })?;
+        let device_info: Option<String> = row.try_get("device_info")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get device_info: {}", e) })?;
+
+        let sign_off = match (signed_name, signed_at, content_hash) {
+            (Some(signed_name), Some(signed_at), Some(content_hash)) => Some(CustomerSignOff {
+                signed_name,
+                signed_at,
+                device_info,
+                content_hash,
+            }),
+            _ => None,
+        };
+
+        Ok(CompletionChecklist {
+            order_id: Self::uuid(row, "order_id")?,
+            worker_id: Self::uuid(row, "worker_id")?,
+            customer_id: Self::uuid(row, "customer_id")?,
+            template_id: Self::uuid(row, "template_id")?,
+            template_version: row.try_get("template_version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get template_version: {}", e) })?,
+            items: serde_json::from_value(items)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid checklist items: {}", e) })?,
+            submitted_at: row.try_get::<DateTime<Utc>, _>("submitted_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get submitted_at: {}", e) })?,
+            sign_off,
+        })
+    }
+
+    /// Serialize a value for a JSON column
+    fn to_json<T: serde::Serialize>(value: &T) -> Result<String, DomainError> {
+        serde_json::to_string(value)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize checklist: {}", e) })
+    }
+}
+
+#[async_trait]
+impl CompletionChecklistRepository for MySqlCompletionChecklistRepository {
+    async fn save_template(&self, template: ChecklistTemplate) -> Result<ChecklistTemplate, DomainError> {
+        let query = r#"
+            INSERT INTO checklist_templates (id, tenant_id, category, version, items, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(template.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&template.category)
+            .bind(template.version)
+            .bind(Self::to_json(&template.items)?)
+            .bind(template.created_by.map(|id| id.to_string()))
+            .bind(template.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save checklist template: {}", e) })?;
+
+        Ok(template)
+    }
+
+    async fn latest_template(&self, category: Option<&str>) -> Result<Option<ChecklistTemplate>, DomainError> {
+        // `<=>` is MySQL's NULL-safe equality, matching the default template for `None`
+        let query = format!(
+            "SELECT {} FROM checklist_templates WHERE tenant_id = ? AND category <=> ? \
+             ORDER BY version DESC LIMIT 1",
+            TEMPLATE_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(category)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find checklist template: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_template).transpose()
+    }
+
+    async fn save_checklist(&self, checklist: &CompletionChecklist) -> Result<bool, DomainError> {
+        // Replace an earlier submission only while it is unsigned
+        let update = r#"
+            UPDATE order_completion_checklists
+            SET template_id = ?, template_version = ?, items = ?, submitted_at = ?
+            WHERE order_id = ? AND worker_id = ? AND tenant_id = ? AND signed_at IS NULL
+        "#;
+
+        let tenant_id = current_tenant().to_string();
+        let items = Self::to_json(&checklist.items)?;
+        let updated = sqlx::query(update)
+            .bind(checklist.template_id.to_string())
+            .bind(checklist.template_version)
+            .bind(&items)
+            .bind(checklist.submitted_at)
+            .bind(checklist.order_id.to_string())
+            .bind(checklist.worker_id.to_string())
+            .bind(&tenant_id)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save completion checklist: {}", e) })?;
+        if updated.rows_affected() > 0 {
+            return Ok(true);
+        }
+
+        // No unsigned submission to replace; a signed row makes the insert a no-op
+        let insert = r#"
+            INSERT IGNORE INTO order_completion_checklists (
+                order_id, tenant_id, worker_id, customer_id, template_id, template_version, items,
+                submitted_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let inserted = sqlx::query(insert)
+            .bind(checklist.order_id.to_string())
+            .bind(&tenant_id)
+            .bind(checklist.worker_id.to_string())
+            .bind(checklist.customer_id.to_string())
+            .bind(checklist.template_id.to_string())
+            .bind(checklist.template_version)
+            .bind(&items)
+            .bind(checklist.submitted_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save completion checklist: {}", e) })?;
+
+        Ok(inserted.rows_affected() > 0)
+    }
+
+    async fn find_checklist(&self, order_id: Uuid) -> Result<Option<CompletionChecklist>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM order_completion_checklists WHERE order_id = ? AND tenant_id = ?",
+            CHECKLIST_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(order_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find completion checklist: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_checklist).transpose()
+    }
+
+    async fn record_sign_off(&self, order_id: Uuid, sign_off: &CustomerSignOff) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE order_completion_checklists
+            SET signed_name = ?, signed_at = ?, device_info = ?, content_hash = ?
+            WHERE order_id = ? AND tenant_id = ? AND signed_at IS NULL
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(&sign_off.signed_name)
+            .bind(sign_off.signed_at)
+            .bind(&sign_off.device_info)
+            .bind(&sign_off.content_hash)
+            .bind(order_id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to record sign-off: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,183 @@
+//! MySQL implementation of the ComplianceRepository trait.
+//!
+//! Compliance checks are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::compliance::{ComplianceCheck, ComplianceRequirement, ComplianceStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::ComplianceRepository;
+use re_core::services::tenant::{current_tenant, TenantId};
+use crate::database::DatabasePool;
+use super::{connection, parse_tenant};
+
+/// Columns selected for a compliance check
+const CHECK_COLUMNS: &str = r#"
+    id, user_id, requirement, market, provider, provider_reference, document_last4,
+    status, rejection_reason, attempts, verified_at, created_at, updated_at
+"#;
+
+/// MySQL implementation of the compliance repository
+pub struct MySqlComplianceRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlComplianceRepository {
+    /// Create a new MySQL compliance repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlComplianceRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ComplianceCheck entity
+    fn row_to_check(row: &sqlx::mysql::MySqlRow) -> Result<ComplianceCheck, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let requirement_str: String = row.try_get("requirement")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get requirement: {}", e) })?;
+        let requirement = ComplianceRequirement::from_str(&requirement_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown compliance requirement: {}", requirement_str) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = ComplianceStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown compliance status: {}", status_str) })?;
+
+        Ok(ComplianceCheck {
+            id: uuid("id")?,
+            user_id: uuid("user_id")?,
+            requirement,
+            market: row.try_get("market")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            document_last4: row.try_get("document_last4")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get document_last4: {}", e) })?,
+            status,
+            rejection_reason: row.try_get("rejection_reason")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get rejection_reason: {}", e) })?,
+            attempts: row.try_get("attempts")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get attempts: {}", e) })?,
+            verified_at: row.try_get::<Option<DateTime<Utc>>, _>("verified_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get verified_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl ComplianceRepository for MySqlComplianceRepository {
+    async fn save(&self, check: ComplianceCheck) -> Result<ComplianceCheck, DomainError> {
+        // A user has one check per requirement; resubmissions replace its progress
+        let query = r#"
+            INSERT INTO user_compliance_checks (
+                id, tenant_id, user_id, requirement, market, provider, provider_reference,
+                document_last4, status, rejection_reason, attempts, verified_at, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                market = VALUES(market),
+                provider = VALUES(provider),
+                provider_reference = VALUES(provider_reference),
+                document_last4 = VALUES(document_last4),
+                status = VALUES(status),
+                rejection_reason = VALUES(rejection_reason),
+                attempts = VALUES(attempts),
+                verified_at = VALUES(verified_at),
+                updated_at = VALUES(updated_at)
+        "#;
+
+        sqlx::query(query)
+            .bind(check.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(check.user_id.to_string())
+            .bind(check.requirement.as_str())
+            .bind(&check.market)
+            .bind(&check.provider)
+            .bind(&check.provider_reference)
+            .bind(&check.document_last4)
+            .bind(check.status.as_str())
+            .bind(&check.rejection_reason)
+            .bind(check.attempts)
+            .bind(check.verified_at)
+            .bind(check.created_at)
+            .bind(check.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save compliance check: {}", e) })?;
+
+        Ok(check)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ComplianceCheck>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM user_compliance_checks WHERE user_id = ? AND tenant_id = ? ORDER BY created_at",
+            CHECK_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list compliance checks: {}", e) })?;
+
+        rows.iter().map(Self::row_to_check).collect()
+    }
+
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<ComplianceCheck>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM user_compliance_checks WHERE provider = ? AND provider_reference = ? AND tenant_id = ?",
+            CHECK_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(provider)
+            .bind(provider_reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find compliance check: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_check).transpose()
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        let tenant_id: Option<String> = sqlx::query_scalar(
+            "SELECT tenant_id FROM user_compliance_checks WHERE provider = ? AND provider_reference = ? LIMIT 1",
+        )
+        .bind(provider)
+        .bind(provider_reference)
+        .fetch_optional(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to find compliance check tenant: {}", e) })?;
+
+        tenant_id.map(parse_tenant).transpose()
+    }
+}
@@ -0,0 +1,280 @@
+//! MySQL implementation of the CreditWalletRepository trait.
+//!
+//! Grants and transactions are scoped to the current tenant, and their
+//! references only have to be unique within a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::credit_wallet::{
+    CreditGrant, CreditSource, CreditTransaction, CreditTransactionKind,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::CreditWalletRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a credit grant
+const GRANT_COLUMNS: &str = r#"
+    id, customer_id, source, reference, amount, remaining, currency, expires_at, created_at
+"#;
+
+/// MySQL implementation of the credit wallet repository
+pub struct MySqlCreditWalletRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlCreditWalletRepository {
+    /// Create a new MySQL credit wallet repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlCreditWalletRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Parse a UUID column
+    fn uuid_column(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Uuid, DomainError> {
+        let value: String = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        Uuid::parse_str(&value)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+    }
+
+    /// Convert database row to CreditGrant entity
+    fn row_to_grant(row: &sqlx::mysql::MySqlRow) -> Result<CreditGrant, DomainError> {
+        let source_str: String = row.try_get("source")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get source: {}", e) })?;
+        let source = CreditSource::from_str(&source_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown credit source: {}", source_str) })?;
+
+        Ok(CreditGrant {
+            id: Self::uuid_column(row, "id")?,
+            customer_id: Self::uuid_column(row, "customer_id")?,
+            source,
+            reference: row.try_get("reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reference: {}", e) })?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            remaining: row.try_get("remaining")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get remaining: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            expires_at: row.try_get::<Option<DateTime<Utc>>, _>("expires_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get expires_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to CreditTransaction entity
+    fn row_to_transaction(row: &sqlx::mysql::MySqlRow) -> Result<CreditTransaction, DomainError> {
+        let kind_str: String = row.try_get("kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get kind: {}", e) })?;
+        let kind = CreditTransactionKind::from_str(&kind_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown credit transaction kind: {}", kind_str) })?;
+
+        Ok(CreditTransaction {
+            id: Self::uuid_column(row, "id")?,
+            customer_id: Self::uuid_column(row, "customer_id")?,
+            kind,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            reference: row.try_get("reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reference: {}", e) })?,
+            occurred_at: row.try_get::<DateTime<Utc>, _>("occurred_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get occurred_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl CreditWalletRepository for MySqlCreditWalletRepository {
+    async fn save_grant(&self, grant: CreditGrant) -> Result<CreditGrant, DomainError> {
+        let query = r#"
+            INSERT INTO credit_grants (
+                id, tenant_id, customer_id, source, reference, amount, remaining, currency,
+                expires_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(grant.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(grant.customer_id.to_string())
+            .bind(grant.source.as_str())
+            .bind(&grant.reference)
+            .bind(grant.amount)
+            .bind(grant.remaining)
+            .bind(&grant.currency)
+            .bind(grant.expires_at)
+            .bind(grant.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save credit grant: {}", e) })?;
+
+        Ok(grant)
+    }
+
+    async fn update_grant(&self, grant: &CreditGrant) -> Result<(), DomainError> {
+        sqlx::query("UPDATE credit_grants SET remaining = ? WHERE id = ? AND tenant_id = ?")
+            .bind(grant.remaining)
+            .bind(grant.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update credit grant: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_grant_by_reference(&self, reference: &str) -> Result<Option<CreditGrant>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM credit_grants WHERE reference = ? AND tenant_id = ?",
+            GRANT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find credit grant: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_grant).transpose()
+    }
+
+    async fn find_spendable_grants(
+        &self,
+        customer_id: Uuid,
+        currency: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<CreditGrant>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM credit_grants
+            WHERE customer_id = ? AND currency = ? AND tenant_id = ? AND remaining > 0
+              AND (expires_at IS NULL OR expires_at > ?)
+            ORDER BY expires_at IS NULL, expires_at ASC, created_at ASC
+            "#,
+            GRANT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(customer_id.to_string())
+            .bind(currency)
+            .bind(current_tenant().to_string())
+            .bind(now)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find spendable credit: {}", e) })?;
+
+        rows.iter().map(Self::row_to_grant).collect()
+    }
+
+    async fn find_expired_grants(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<CreditGrant>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM credit_grants
+            WHERE tenant_id = ? AND remaining > 0 AND expires_at <= ?
+            ORDER BY expires_at ASC
+            LIMIT ?
+            "#,
+            GRANT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(now)
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find expired credit: {}", e) })?;
+
+        rows.iter().map(Self::row_to_grant).collect()
+    }
+
+    async fn save_transaction(&self, transaction: CreditTransaction) -> Result<CreditTransaction, DomainError> {
+        let query = r#"
+            INSERT INTO credit_transactions (
+                id, tenant_id, customer_id, kind, amount, currency, reference, occurred_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(transaction.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(transaction.customer_id.to_string())
+            .bind(transaction.kind.as_str())
+            .bind(transaction.amount)
+            .bind(&transaction.currency)
+            .bind(&transaction.reference)
+            .bind(transaction.occurred_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save credit transaction: {}", e) })?;
+
+        Ok(transaction)
+    }
+
+    async fn find_transaction(
+        &self,
+        kind: CreditTransactionKind,
+        reference: &str,
+    ) -> Result<Option<CreditTransaction>, DomainError> {
+        let query = r#"
+            SELECT id, customer_id, kind, amount, currency, reference, occurred_at
+            FROM credit_transactions
+            WHERE kind = ? AND reference = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(kind.as_str())
+            .bind(reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find credit transaction: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_transaction).transpose()
+    }
+
+    async fn find_transactions(
+        &self,
+        customer_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<CreditTransaction>, DomainError> {
+        let query = r#"
+            SELECT id, customer_id, kind, amount, currency, reference, occurred_at
+            FROM credit_transactions
+            WHERE customer_id = ? AND tenant_id = ?
+            ORDER BY occurred_at DESC
+            LIMIT ?
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(customer_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find credit transactions: {}", e) })?;
+
+        rows.iter().map(Self::row_to_transaction).collect()
+    }
+}
@@ -0,0 +1,182 @@
+//! MySQL implementation of the DisputeRepository trait.
+//!
+//! Disputes are scoped to the current tenant, except for the tenant lookup
+//! provider webhooks are routed with.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::dispute::{Dispute, DisputeStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::DisputeRepository;
+use re_core::services::tenant::{current_tenant, TenantId};
+use crate::database::DatabasePool;
+use super::{connection, parse_tenant};
+
+const DISPUTE_COLUMNS: &str = r#"
+    id, payment_id, provider, provider_dispute_id, reason, amount, currency,
+    status, opened_at, resolved_at
+"#;
+
+/// MySQL implementation of the dispute repository
+pub struct MySqlDisputeRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlDisputeRepository {
+    /// Create a new MySQL dispute repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlDisputeRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to Dispute entity
+    fn row_to_dispute(row: &sqlx::mysql::MySqlRow) -> Result<Dispute, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = DisputeStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown dispute status: {}", status_str) })?;
+
+        Ok(Dispute {
+            id: uuid("id")?,
+            payment_id: uuid("payment_id")?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_dispute_id: row.try_get("provider_dispute_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_dispute_id: {}", e) })?,
+            reason: row.try_get("reason")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reason: {}", e) })?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            status,
+            opened_at: row.try_get::<DateTime<Utc>, _>("opened_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get opened_at: {}", e) })?,
+            resolved_at: row.try_get::<Option<DateTime<Utc>>, _>("resolved_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get resolved_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl DisputeRepository for MySqlDisputeRepository {
+    async fn save(&self, dispute: Dispute) -> Result<Dispute, DomainError> {
+        let query = r#"
+            INSERT INTO payment_disputes (
+                id, tenant_id, payment_id, provider, provider_dispute_id, reason, amount, currency,
+                status, opened_at, resolved_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(dispute.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(dispute.payment_id.to_string())
+            .bind(&dispute.provider)
+            .bind(&dispute.provider_dispute_id)
+            .bind(&dispute.reason)
+            .bind(dispute.amount)
+            .bind(&dispute.currency)
+            .bind(dispute.status.as_str())
+            .bind(dispute.opened_at)
+            .bind(dispute.resolved_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save dispute: {}", e) })?;
+
+        Ok(dispute)
+    }
+
+    async fn find_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<Dispute>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM payment_disputes
+            WHERE provider = ? AND provider_dispute_id = ? AND tenant_id = ?
+            LIMIT 1
+            "#,
+            DISPUTE_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(provider)
+            .bind(provider_dispute_id)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find dispute: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_dispute).transpose()
+    }
+
+    async fn find_tenant_by_provider_dispute_id(
+        &self,
+        provider: &str,
+        provider_dispute_id: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        let query = "SELECT tenant_id FROM payment_disputes WHERE provider = ? AND provider_dispute_id = ? LIMIT 1";
+
+        let tenant_id: Option<String> = sqlx::query_scalar(query)
+            .bind(provider)
+            .bind(provider_dispute_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find dispute tenant: {}", e) })?;
+
+        tenant_id.map(parse_tenant).transpose()
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Dispute>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payment_disputes WHERE payment_id = ? AND tenant_id = ? ORDER BY opened_at ASC",
+            DISPUTE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(payment_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find disputes: {}", e) })?;
+
+        rows.iter().map(Self::row_to_dispute).collect()
+    }
+
+    async fn update(&self, dispute: &Dispute) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payment_disputes
+            SET status = ?, resolved_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(dispute.status.as_str())
+            .bind(dispute.resolved_at)
+            .bind(dispute.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update dispute: {}", e) })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+//! MySQL implementation of the ExportJobRepository trait.
+//!
+//! Export jobs are scoped to the current tenant, so each tenant's jobs are
+//! run in that tenant and only export its own rows.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::export_job::{ExportJob, ExportKind, ExportStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::ExportJobRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for an export job
+const JOB_COLUMNS: &str = r#"
+    id, kind, requested_by, range_from, range_to, status, rows_exported, total_rows,
+    object_key, error, attempts, created_at, started_at, finished_at, updated_at
+"#;
+
+/// MySQL implementation of the export job repository
+pub struct MySqlExportJobRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlExportJobRepository {
+    /// Create a new MySQL export job repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlExportJobRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ExportJob entity
+    fn row_to_job(row: &sqlx::mysql::MySqlRow) -> Result<ExportJob, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let kind_str: String = row.try_get("kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get kind: {}", e) })?;
+        let kind = ExportKind::from_str(&kind_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown export kind: {}", kind_str) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = ExportStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown export status: {}", status_str) })?;
+
+        Ok(ExportJob {
+            id: uuid("id")?,
+            kind,
+            requested_by: uuid("requested_by")?,
+            from: row.try_get::<Option<DateTime<Utc>>, _>("range_from")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get range_from: {}", e) })?,
+            to: row.try_get::<DateTime<Utc>, _>("range_to")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get range_to: {}", e) })?,
+            status,
+            rows_exported: row.try_get("rows_exported")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get rows_exported: {}", e) })?,
+            total_rows: row.try_get("total_rows")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get total_rows: {}", e) })?,
+            object_key: row.try_get("object_key")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get object_key: {}", e) })?,
+            error: row.try_get("error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get error: {}", e) })?,
+            attempts: row.try_get("attempts")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get attempts: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            started_at: row.try_get::<Option<DateTime<Utc>>, _>("started_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get started_at: {}", e) })?,
+            finished_at: row.try_get::<Option<DateTime<Utc>>, _>("finished_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get finished_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl ExportJobRepository for MySqlExportJobRepository {
+    async fn create(&self, job: ExportJob) -> Result<ExportJob, DomainError> {
+        let query = r#"
+            INSERT INTO export_jobs (
+                id, tenant_id, kind, requested_by, range_from, range_to, status, rows_exported,
+                total_rows, object_key, error, attempts, created_at, started_at, finished_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(job.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(job.kind.as_str())
+            .bind(job.requested_by.to_string())
+            .bind(job.from)
+            .bind(job.to)
+            .bind(job.status.as_str())
+            .bind(job.rows_exported)
+            .bind(job.total_rows)
+            .bind(&job.object_key)
+            .bind(&job.error)
+            .bind(job.attempts)
+            .bind(job.created_at)
+            .bind(job.started_at)
+            .bind(job.finished_at)
+            .bind(job.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to create export job: {}", e) })?;
+
+        Ok(job)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ExportJob>, DomainError> {
+        let query = format!("SELECT {} FROM export_jobs WHERE id = ? AND tenant_id = ?", JOB_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find export job: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_job).transpose()
+    }
+
+    async fn find_queued(&self, limit: usize) -> Result<Vec<ExportJob>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM export_jobs WHERE tenant_id = ? AND status = 'queued' ORDER BY created_at LIMIT ?",
+            JOB_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find queued export jobs: {}", e) })?;
+
+        rows.iter().map(Self::row_to_job).collect()
+    }
+
+    async fn claim(&self, job: &ExportJob) -> Result<bool, DomainError> {
+        // Only the worker whose update finds the job still queued runs it
+        let query = r#"
+            UPDATE export_jobs
+            SET status = ?, rows_exported = ?, total_rows = ?, attempts = ?, started_at = ?, updated_at = ?
+            WHERE id = ? AND tenant_id = ? AND status = 'queued'
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(job.status.as_str())
+            .bind(job.rows_exported)
+            .bind(job.total_rows)
+            .bind(job.attempts)
+            .bind(job.started_at)
+            .bind(job.updated_at)
+            .bind(job.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to claim export job: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update(&self, job: &ExportJob) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE export_jobs
+            SET status = ?, rows_exported = ?, total_rows = ?, object_key = ?, error = ?,
+                attempts = ?, started_at = ?, finished_at = ?, updated_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(job.status.as_str())
+            .bind(job.rows_exported)
+            .bind(job.total_rows)
+            .bind(&job.object_key)
+            .bind(&job.error)
+            .bind(job.attempts)
+            .bind(job.started_at)
+            .bind(job.finished_at)
+            .bind(job.updated_at)
+            .bind(job.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update export job: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn requeue_stale(&self, updated_before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let query = r#"
+            UPDATE export_jobs
+            SET status = 'queued', updated_at = ?
+            WHERE tenant_id = ? AND status = 'running' AND updated_at < ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(Utc::now())
+            .bind(current_tenant().to_string())
+            .bind(updated_before)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to requeue export jobs: {}", e) })?;
+
+        Ok(result.rows_affected())
+    }
+}
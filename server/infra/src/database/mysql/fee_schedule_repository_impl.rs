@@ -0,0 +1,175 @@
+//! MySQL implementation of the FeeScheduleRepository trait.
+//!
+//! Market-wide schedules are stored with an empty `category` so the unique
+//! version key also covers them. Each tenant sets its own fees, so schedules
+//! are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::fee_schedule::FeeSchedule;
+use re_core::errors::DomainError;
+use re_core::repositories::FeeScheduleRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a fee schedule
+const SCHEDULE_COLUMNS: &str = r#"
+    id, market, category, version, commission_basis_points, minimum_fee,
+    currency, effective_from, created_by, created_at
+"#;
+
+/// MySQL implementation of the fee schedule repository
+pub struct MySqlFeeScheduleRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlFeeScheduleRepository {
+    /// Create a new MySQL fee schedule repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlFeeScheduleRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to FeeSchedule entity
+    fn row_to_schedule(row: &sqlx::mysql::MySqlRow) -> Result<FeeSchedule, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let category: String = row.try_get("category")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get category: {}", e) })?;
+        let created_by: Option<String> = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+        let commission: i32 = row.try_get("commission_basis_points")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get commission_basis_points: {}", e) })?;
+
+        Ok(FeeSchedule {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            market: row.try_get("market")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?,
+            category: Some(category).filter(|c| !c.is_empty()),
+            version: row.try_get("version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })?,
+            commission_basis_points: commission as i64,
+            minimum_fee: row.try_get("minimum_fee")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get minimum_fee: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            effective_from: row.try_get::<DateTime<Utc>, _>("effective_from")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get effective_from: {}", e) })?,
+            created_by: created_by
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl FeeScheduleRepository for MySqlFeeScheduleRepository {
+    async fn save(&self, schedule: FeeSchedule) -> Result<FeeSchedule, DomainError> {
+        let query = r#"
+            INSERT INTO fee_schedules (
+                id, tenant_id, market, category, version, commission_basis_points, minimum_fee,
+                currency, effective_from, created_by, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(schedule.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&schedule.market)
+            .bind(schedule.category.as_deref().unwrap_or(""))
+            .bind(schedule.version)
+            .bind(schedule.commission_basis_points as i32)
+            .bind(schedule.minimum_fee)
+            .bind(&schedule.currency)
+            .bind(schedule.effective_from)
+            .bind(schedule.created_by.map(|id| id.to_string()))
+            .bind(schedule.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save fee schedule: {}", e) })?;
+
+        Ok(schedule)
+    }
+
+    async fn find_by_market(&self, market: &str) -> Result<Vec<FeeSchedule>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM fee_schedules WHERE market = ? AND tenant_id = ? \
+             ORDER BY effective_from DESC, version DESC",
+            SCHEDULE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(market)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find fee schedules: {}", e) })?;
+
+        rows.iter().map(Self::row_to_schedule).collect()
+    }
+
+    async fn latest_version(
+        &self,
+        market: &str,
+        category: Option<&str>,
+    ) -> Result<Option<i32>, DomainError> {
+        let query = r#"
+            SELECT MAX(version) AS version FROM fee_schedules
+            WHERE market = ? AND category = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(market)
+            .bind(category.unwrap_or(""))
+            .bind(current_tenant().to_string())
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get latest fee schedule version: {}", e) })?;
+
+        row.try_get("version")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })
+    }
+
+    async fn find_effective(
+        &self,
+        market: &str,
+        category: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<FeeSchedule>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM fee_schedules
+            WHERE market = ? AND category = ? AND tenant_id = ? AND effective_from <= ?
+            ORDER BY effective_from DESC, version DESC
+            LIMIT 1
+            "#,
+            SCHEDULE_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(market)
+            .bind(category.unwrap_or(""))
+            .bind(current_tenant().to_string())
+            .bind(at)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find effective fee schedule: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_schedule).transpose()
+    }
+}
@@ -0,0 +1,281 @@
+//! MySQL implementation of the JournalRepository trait.
+//!
+//! Entries live in `journal_entries` and their lines in `journal_lines`;
+//! both are written in one transaction so a half-written entry can never
+//! unbalance the books. Each tenant keeps its own books: entries and lines
+//! are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::journal::{
+    AccountBalance, AccountKey, AccountType, EntrySide, JournalEntry, JournalLine,
+    JournalTransactionType,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::JournalRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::{begin, connection};
+
+/// Debit and credit sums of the selected lines, as signed integers
+const LINE_TOTALS: &str = r#"
+    CAST(COALESCE(SUM(CASE WHEN side = 'debit' THEN amount ELSE 0 END), 0) AS SIGNED) AS debits,
+    CAST(COALESCE(SUM(CASE WHEN side = 'credit' THEN amount ELSE 0 END), 0) AS SIGNED) AS credits
+"#;
+
+/// MySQL implementation of the journal repository
+pub struct MySqlJournalRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlJournalRepository {
+    /// Create a new MySQL journal repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlJournalRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert the account columns of a row to an AccountKey
+    fn row_to_account(row: &sqlx::mysql::MySqlRow) -> Result<AccountKey, DomainError> {
+        let type_str: String = row.try_get("account_type")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get account_type: {}", e) })?;
+        let account_type = AccountType::from_str(&type_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown account type: {}", type_str) })?;
+
+        let owner_id: Option<String> = row.try_get("owner_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get owner_id: {}", e) })?;
+        let owner_id = owner_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?;
+
+        Ok(AccountKey {
+            account_type,
+            owner_id,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to JournalLine entity
+    fn row_to_line(row: &sqlx::mysql::MySqlRow) -> Result<JournalLine, DomainError> {
+        let side_str: String = row.try_get("side")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get side: {}", e) })?;
+        let side = EntrySide::from_str(&side_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown entry side: {}", side_str) })?;
+
+        Ok(JournalLine {
+            account: Self::row_to_account(row)?,
+            side,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to an AccountBalance
+    fn row_to_balance(row: &sqlx::mysql::MySqlRow, account: AccountKey) -> Result<AccountBalance, DomainError> {
+        Ok(AccountBalance {
+            account,
+            debits: row.try_get("debits")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get debits: {}", e) })?,
+            credits: row.try_get("credits")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get credits: {}", e) })?,
+        })
+    }
+
+    /// Load the lines of an entry in the order they were written
+    async fn find_lines(&self, entry_id: &str) -> Result<Vec<JournalLine>, DomainError> {
+        let query = r#"
+            SELECT account_type, owner_id, currency, side, amount
+            FROM journal_lines
+            WHERE entry_id = ? AND tenant_id = ?
+            ORDER BY line_no ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(entry_id)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find journal lines: {}", e) })?;
+
+        rows.iter().map(Self::row_to_line).collect()
+    }
+}
+
+#[async_trait]
+impl JournalRepository for MySqlJournalRepository {
+    async fn save(&self, entry: JournalEntry) -> Result<JournalEntry, DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        let query = r#"
+            INSERT INTO journal_entries (
+                id, tenant_id, transaction_type, reference, description, occurred_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(entry.id.to_string())
+            .bind(&tenant_id)
+            .bind(entry.transaction_type.as_str())
+            .bind(&entry.reference)
+            .bind(&entry.description)
+            .bind(entry.occurred_at)
+            .bind(entry.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save journal entry: {}", e) })?;
+
+        for (line_no, line) in entry.lines.iter().enumerate() {
+            let query = r#"
+                INSERT INTO journal_lines (
+                    entry_id, line_no, tenant_id, account_type, owner_id, currency, side, amount
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#;
+
+            sqlx::query(query)
+                .bind(entry.id.to_string())
+                .bind(line_no as u16)
+                .bind(&tenant_id)
+                .bind(line.account.account_type.as_str())
+                .bind(line.account.owner_id.map(|id| id.to_string()))
+                .bind(&line.account.currency)
+                .bind(line.side.as_str())
+                .bind(line.amount)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to save journal line: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit journal entry: {}", e) })?;
+
+        Ok(entry)
+    }
+
+    async fn find_by_reference(
+        &self,
+        transaction_type: JournalTransactionType,
+        reference: &str,
+    ) -> Result<Option<JournalEntry>, DomainError> {
+        let query = r#"
+            SELECT id, transaction_type, reference, description, occurred_at, created_at
+            FROM journal_entries
+            WHERE transaction_type = ? AND reference = ? AND tenant_id = ?
+            LIMIT 1
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(transaction_type.as_str())
+            .bind(reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find journal entry: {}", e) })?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let lines = self.find_lines(&id).await?;
+
+        Ok(Some(JournalEntry {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            transaction_type,
+            reference: row.try_get("reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reference: {}", e) })?,
+            description: row.try_get("description")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get description: {}", e) })?,
+            lines,
+            occurred_at: row.try_get::<DateTime<Utc>, _>("occurred_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get occurred_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        }))
+    }
+
+    async fn account_balance(&self, account: &AccountKey) -> Result<AccountBalance, DomainError> {
+        // `<=>` matches NULL owners of platform accounts
+        let query = format!(
+            "SELECT {} FROM journal_lines \
+             WHERE tenant_id = ? AND account_type = ? AND owner_id <=> ? AND currency = ?",
+            LINE_TOTALS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(account.account_type.as_str())
+            .bind(account.owner_id.map(|id| id.to_string()))
+            .bind(&account.currency)
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get account balance: {}", e) })?;
+
+        Self::row_to_balance(&row, account.clone())
+    }
+
+    async fn trial_balance(&self) -> Result<Vec<AccountBalance>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT account_type, owner_id, currency, {}
+            FROM journal_lines
+            WHERE tenant_id = ?
+            GROUP BY account_type, owner_id, currency
+            ORDER BY account_type, owner_id, currency
+            "#,
+            LINE_TOTALS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get trial balance: {}", e) })?;
+
+        rows.iter()
+            .map(|row| Self::row_to_balance(row, Self::row_to_account(row)?))
+            .collect()
+    }
+
+    async fn find_unbalanced_entries(&self) -> Result<Vec<Uuid>, DomainError> {
+        let query = r#"
+            SELECT DISTINCT entry_id
+            FROM (
+                SELECT entry_id, currency,
+                       SUM(CASE WHEN side = 'debit' THEN amount ELSE -amount END) AS net
+                FROM journal_lines
+                WHERE tenant_id = ?
+                GROUP BY entry_id, currency
+            ) AS totals
+            WHERE net <> 0
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find unbalanced entries: {}", e) })?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("entry_id")
+                    .map_err(|e| DomainError::Internal { message: format!("Failed to get entry_id: {}", e) })?;
+                Uuid::parse_str(&id)
+                    .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+            })
+            .collect()
+    }
+}
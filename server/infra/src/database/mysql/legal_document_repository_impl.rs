@@ -0,0 +1,260 @@
+//! MySQL implementation of the LegalDocumentRepository trait.
+//!
+//! Versions for every market are stored with an empty `market` so the
+//! unique version key also covers them. Each tenant publishes its own
+//! documents, and documents and acceptances are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::legal_document::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+use re_core::errors::DomainError;
+use re_core::repositories::LegalDocumentRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::{begin, connection};
+
+/// Columns selected for a legal document
+const DOCUMENT_COLUMNS: &str = r#"
+    id, kind, market, version, language, title, body, effective_from, created_by, created_at
+"#;
+
+/// MySQL implementation of the legal document repository
+pub struct MySqlLegalDocumentRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlLegalDocumentRepository {
+    /// Create a new MySQL legal document repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlLegalDocumentRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    fn parse_kind(row: &sqlx::mysql::MySqlRow) -> Result<LegalDocumentKind, DomainError> {
+        let kind: String = row.try_get("kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get kind: {}", e) })?;
+        LegalDocumentKind::from_str(&kind)
+            .ok_or_else(|| DomainError::Internal { message: format!("Invalid legal document kind: {}", kind) })
+    }
+
+    fn parse_market(row: &sqlx::mysql::MySqlRow) -> Result<Option<String>, DomainError> {
+        let market: String = row.try_get("market")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?;
+        Ok(Some(market).filter(|m| !m.is_empty()))
+    }
+
+    fn parse_uuid(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Uuid, DomainError> {
+        let value: String = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        Uuid::parse_str(&value)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+    }
+
+    /// Convert database row to LegalDocument entity
+    fn row_to_document(row: &sqlx::mysql::MySqlRow) -> Result<LegalDocument, DomainError> {
+        let created_by: Option<String> = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+
+        Ok(LegalDocument {
+            id: Self::parse_uuid(row, "id")?,
+            kind: Self::parse_kind(row)?,
+            market: Self::parse_market(row)?,
+            version: row.try_get("version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })?,
+            language: row.try_get("language")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get language: {}", e) })?,
+            title: row.try_get("title")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get title: {}", e) })?,
+            body: row.try_get("body")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get body: {}", e) })?,
+            effective_from: row.try_get::<DateTime<Utc>, _>("effective_from")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get effective_from: {}", e) })?,
+            created_by: created_by
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to LegalAcceptance entity
+    fn row_to_acceptance(row: &sqlx::mysql::MySqlRow) -> Result<LegalAcceptance, DomainError> {
+        Ok(LegalAcceptance {
+            user_id: Self::parse_uuid(row, "user_id")?,
+            document_id: Self::parse_uuid(row, "document_id")?,
+            kind: Self::parse_kind(row)?,
+            market: Self::parse_market(row)?,
+            version: row.try_get("version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })?,
+            accepted_at: row.try_get::<DateTime<Utc>, _>("accepted_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get accepted_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl LegalDocumentRepository for MySqlLegalDocumentRepository {
+    async fn save_version(&self, translations: &[LegalDocument]) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO legal_documents (
+                id, tenant_id, kind, market, version, language, title, body, effective_from,
+                created_by, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        for document in translations {
+            sqlx::query(query)
+                .bind(document.id.to_string())
+                .bind(&tenant_id)
+                .bind(document.kind.as_str())
+                .bind(document.market.as_deref().unwrap_or(""))
+                .bind(document.version)
+                .bind(&document.language)
+                .bind(&document.title)
+                .bind(&document.body)
+                .bind(document.effective_from)
+                .bind(document.created_by.map(|id| id.to_string()))
+                .bind(document.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to save legal document: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })
+    }
+
+    async fn latest_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+    ) -> Result<Option<i32>, DomainError> {
+        let query = r#"
+            SELECT MAX(version) AS version FROM legal_documents
+            WHERE kind = ? AND market = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(kind.as_str())
+            .bind(market.unwrap_or(""))
+            .bind(current_tenant().to_string())
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get latest legal document version: {}", e) })?;
+
+        row.try_get("version")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get version: {}", e) })
+    }
+
+    async fn find_effective(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<LegalDocument>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM legal_documents
+            WHERE kind = ? AND market = ? AND tenant_id = ? AND version = (
+                SELECT version FROM legal_documents
+                WHERE kind = ? AND market = ? AND tenant_id = ? AND effective_from <= ?
+                ORDER BY effective_from DESC, version DESC
+                LIMIT 1
+            )
+            "#,
+            DOCUMENT_COLUMNS
+        );
+
+        let tenant_id = current_tenant().to_string();
+        let rows = sqlx::query(&query)
+            .bind(kind.as_str())
+            .bind(market.unwrap_or(""))
+            .bind(&tenant_id)
+            .bind(kind.as_str())
+            .bind(market.unwrap_or(""))
+            .bind(&tenant_id)
+            .bind(at)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find effective legal document: {}", e) })?;
+
+        rows.iter().map(Self::row_to_document).collect()
+    }
+
+    async fn find_version(
+        &self,
+        kind: LegalDocumentKind,
+        market: Option<&str>,
+        version: i32,
+    ) -> Result<Vec<LegalDocument>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM legal_documents WHERE kind = ? AND market = ? AND version = ? AND tenant_id = ?",
+            DOCUMENT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(kind.as_str())
+            .bind(market.unwrap_or(""))
+            .bind(version)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find legal document version: {}", e) })?;
+
+        rows.iter().map(Self::row_to_document).collect()
+    }
+
+    async fn save_acceptance(&self, acceptance: &LegalAcceptance) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT IGNORE INTO legal_acceptances (
+                user_id, kind, market, version, tenant_id, document_id, accepted_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(acceptance.user_id.to_string())
+            .bind(acceptance.kind.as_str())
+            .bind(acceptance.market.as_deref().unwrap_or(""))
+            .bind(acceptance.version)
+            .bind(current_tenant().to_string())
+            .bind(acceptance.document_id.to_string())
+            .bind(acceptance.accepted_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save legal acceptance: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_acceptances(&self, user_id: Uuid) -> Result<Vec<LegalAcceptance>, DomainError> {
+        let query = r#"
+            SELECT user_id, kind, market, version, document_id, accepted_at
+            FROM legal_acceptances
+            WHERE user_id = ? AND tenant_id = ?
+            ORDER BY accepted_at DESC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find legal acceptances: {}", e) })?;
+
+        rows.iter().map(Self::row_to_acceptance).collect()
+    }
+}
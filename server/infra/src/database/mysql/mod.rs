@@ -2,12 +2,105 @@
 //!
 //! This module contains MySQL implementations of repository traits
 //! using SQLx for database operations.
+//!
+//! Repositories hold a [`DatabasePool`] and take every connection and
+//! transaction through it, so their queries are retried on transient
+//! failures and fail at once while the pool's circuit breaker is open.
+//!
+//! Users, refresh tokens and auth audit logs are stored by the generic
+//! repositories of [`crate::database::sql`], which run on MySQL as well.
+
+use sqlx::{pool::PoolConnection, MySql, Transaction};
+
+use re_core::errors::DomainError;
+use re_core::services::tenant::TenantId;
 
-pub mod user_repository_impl;
-pub mod token_repository_impl;
-pub mod audit_repository_impl;
+use super::DatabasePool;
+
+pub mod audit_partition_manager;
+pub mod scheduled_message_repository_impl;
+pub mod closure_date_repository_impl;
+pub mod service_area_repository_impl;
+pub mod campaign_repository_impl;
+pub mod payment_ledger_repository_impl;
+pub mod reconciliation_report_repository_impl;
+pub mod payment_repository_impl;
+pub mod payout_repository_impl;
+pub mod dispute_repository_impl;
+pub mod payment_webhook_event_repository_impl;
+pub mod journal_repository_impl;
+pub mod legal_document_repository_impl;
+pub mod fee_schedule_repository_impl;
+pub mod order_fee_repository_impl;
+pub mod credit_wallet_repository_impl;
+pub mod payment_risk_repository_impl;
+pub mod oauth_repository_impl;
+pub mod admin_identity_repository_impl;
+pub mod payment_method_repository_impl;
+pub mod payout_method_repository_impl;
+pub mod completion_repository_impl;
+pub mod security_webhook_repository_impl;
+pub mod sms_delivery_log_repository_impl;
+pub mod sms_spend_repository_impl;
+pub mod attribution_repository_impl;
+pub mod organization_repository_impl;
+pub mod user_import_repository_impl;
+pub mod phone_hash_repository_impl;
+pub mod compliance_repository_impl;
+pub mod export_job_repository_impl;
+pub mod webhook_dead_letter_repository_impl;
 
 // Re-export the MySQL implementations
-pub use user_repository_impl::MySqlUserRepository;
-pub use token_repository_impl::MySqlTokenRepository;
-pub use audit_repository_impl::MySqlAuditLogRepository;
\ No newline at end of file
+pub use audit_partition_manager::MySqlAuditPartitionManager;
+pub use scheduled_message_repository_impl::MySqlScheduledMessageRepository;
+pub use closure_date_repository_impl::MySqlClosureDateRepository;
+pub use service_area_repository_impl::MySqlServiceAreaRepository;
+pub use campaign_repository_impl::MySqlCampaignRepository;
+pub use payment_ledger_repository_impl::MySqlPaymentLedgerRepository;
+pub use reconciliation_report_repository_impl::MySqlReconciliationReportRepository;
+pub use payment_repository_impl::MySqlPaymentRepository;
+pub use payout_repository_impl::MySqlPayoutRepository;
+pub use dispute_repository_impl::MySqlDisputeRepository;
+pub use payment_webhook_event_repository_impl::MySqlPaymentWebhookEventRepository;
+pub use journal_repository_impl::MySqlJournalRepository;
+pub use legal_document_repository_impl::MySqlLegalDocumentRepository;
+pub use fee_schedule_repository_impl::MySqlFeeScheduleRepository;
+pub use order_fee_repository_impl::MySqlOrderFeeRepository;
+pub use credit_wallet_repository_impl::MySqlCreditWalletRepository;
+pub use payment_risk_repository_impl::MySqlPaymentRiskRepository;
+pub use oauth_repository_impl::MySqlOAuthRepository;
+pub use admin_identity_repository_impl::MySqlAdminIdentityRepository;
+pub use payment_method_repository_impl::MySqlPaymentMethodRepository;
+pub use payout_method_repository_impl::MySqlPayoutMethodRepository;
+pub use completion_repository_impl::MySqlCompletionChecklistRepository;
+pub use security_webhook_repository_impl::MySqlSecurityWebhookRepository;
+pub use sms_delivery_log_repository_impl::MySqlSmsDeliveryLogRepository;
+pub use sms_spend_repository_impl::MySqlSmsSpendRepository;
+pub use attribution_repository_impl::MySqlAttributionRepository;
+pub use organization_repository_impl::MySqlOrganizationRepository;
+pub use user_import_repository_impl::MySqlUserImportRepository;
+pub use phone_hash_repository_impl::MySqlPhoneHashRepository;
+pub use compliance_repository_impl::MySqlComplianceRepository;
+pub use export_job_repository_impl::MySqlExportJobRepository;
+pub use webhook_dead_letter_repository_impl::MySqlWebhookDeadLetterRepository;
+
+/// Take a connection through the pool's retries and circuit breaker
+pub(crate) async fn connection(pool: &DatabasePool) -> Result<PoolConnection<MySql>, DomainError> {
+    pool.acquire().await.map_err(|e| DomainError::Internal {
+        message: format!("Failed to acquire database connection: {}", e),
+    })
+}
+
+/// Parse a `tenant_id` column read back from a tenant lookup
+pub(crate) fn parse_tenant(id: String) -> Result<TenantId, DomainError> {
+    TenantId::parse(&id).ok_or_else(|| DomainError::Internal {
+        message: format!("Invalid tenant ID: {}", id),
+    })
+}
+
+/// Begin a transaction through the pool's retries and circuit breaker
+pub(crate) async fn begin(pool: &DatabasePool) -> Result<Transaction<'static, MySql>, DomainError> {
+    pool.begin_transaction().await.map_err(|e| DomainError::Internal {
+        message: format!("Failed to begin transaction: {}", e),
+    })
+}
@@ -0,0 +1,348 @@
+//! MySQL implementation of the OAuthRepository trait.
+//!
+//! Redirect URIs, scopes and grant types are stored as JSON arrays.
+//! Authorization codes are consumed with a conditional update, so a code
+//! can only be exchanged once even under concurrent requests.
+//!
+//! Clients are registered once for the deployment and may be used with any
+//! tenant; consents and authorization codes are scoped to the current
+//! tenant, so a code can only be exchanged in the tenant that issued it.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::oauth::{
+    OAuthAuthorizationCode, OAuthClient, OAuthConsent, OAuthGrantType,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::OAuthRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a client
+const CLIENT_COLUMNS: &str = r#"
+    id, client_id, client_secret_hash, name, redirect_uris, allowed_scopes, grant_types,
+    is_active, created_at, updated_at
+"#;
+
+/// Columns selected for an authorization code
+const CODE_COLUMNS: &str = r#"
+    code_hash, client_id, user_id, redirect_uri, scopes, code_challenge, expires_at, used_at, created_at
+"#;
+
+/// MySQL implementation of the OAuth repository
+pub struct MySqlOAuthRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlOAuthRepository {
+    /// Create a new MySQL OAuth repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlOAuthRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Read a JSON array of strings from a column
+    fn get_string_list(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Vec<String>, DomainError> {
+        let value: serde_json::Value = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        serde_json::from_value(value)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid {}: {}", column, e) })
+    }
+
+    /// Serialize a list of strings as a JSON array
+    fn to_json(values: &[impl AsRef<str>]) -> Result<String, DomainError> {
+        let values: Vec<&str> = values.iter().map(|v| v.as_ref()).collect();
+        serde_json::to_string(&values)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize list: {}", e) })
+    }
+
+    /// Convert database row to OAuthClient entity
+    fn row_to_client(row: &sqlx::mysql::MySqlRow) -> Result<OAuthClient, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let grant_types = Self::get_string_list(row, "grant_types")?
+            .iter()
+            .map(|grant| OAuthGrantType::from_str(grant)
+                .ok_or_else(|| DomainError::Internal { message: format!("Unknown grant type: {}", grant) }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(OAuthClient {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            client_id: row.try_get("client_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get client_id: {}", e) })?,
+            client_secret_hash: row.try_get("client_secret_hash")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get client_secret_hash: {}", e) })?,
+            name: row.try_get("name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get name: {}", e) })?,
+            redirect_uris: Self::get_string_list(row, "redirect_uris")?,
+            allowed_scopes: Self::get_string_list(row, "allowed_scopes")?,
+            grant_types,
+            is_active: row.try_get("is_active")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get is_active: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to OAuthConsent entity
+    fn row_to_consent(row: &sqlx::mysql::MySqlRow) -> Result<OAuthConsent, DomainError> {
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+
+        Ok(OAuthConsent {
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            client_id: row.try_get("client_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get client_id: {}", e) })?,
+            scopes: Self::get_string_list(row, "scopes")?,
+            granted_at: row.try_get::<DateTime<Utc>, _>("granted_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get granted_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to OAuthAuthorizationCode entity
+    fn row_to_code(row: &sqlx::mysql::MySqlRow) -> Result<OAuthAuthorizationCode, DomainError> {
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+
+        Ok(OAuthAuthorizationCode {
+            code_hash: row.try_get("code_hash")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get code_hash: {}", e) })?,
+            client_id: row.try_get("client_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get client_id: {}", e) })?,
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            redirect_uri: row.try_get("redirect_uri")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get redirect_uri: {}", e) })?,
+            scopes: Self::get_string_list(row, "scopes")?,
+            code_challenge: row.try_get("code_challenge")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get code_challenge: {}", e) })?,
+            expires_at: row.try_get::<DateTime<Utc>, _>("expires_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get expires_at: {}", e) })?,
+            used_at: row.try_get::<Option<DateTime<Utc>>, _>("used_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get used_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl OAuthRepository for MySqlOAuthRepository {
+    async fn save_client(&self, client: OAuthClient) -> Result<OAuthClient, DomainError> {
+        let grant_types: Vec<&str> = client.grant_types.iter().map(|g| g.as_str()).collect();
+
+        let query = r#"
+            INSERT INTO oauth_clients (
+                id, client_id, client_secret_hash, name, redirect_uris, allowed_scopes, grant_types,
+                is_active, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(client.id.to_string())
+            .bind(&client.client_id)
+            .bind(&client.client_secret_hash)
+            .bind(&client.name)
+            .bind(Self::to_json(&client.redirect_uris)?)
+            .bind(Self::to_json(&client.allowed_scopes)?)
+            .bind(Self::to_json(&grant_types)?)
+            .bind(client.is_active)
+            .bind(client.created_at)
+            .bind(client.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save OAuth client: {}", e) })?;
+
+        Ok(client)
+    }
+
+    async fn update_client(&self, client: &OAuthClient) -> Result<(), DomainError> {
+        let grant_types: Vec<&str> = client.grant_types.iter().map(|g| g.as_str()).collect();
+
+        let query = r#"
+            UPDATE oauth_clients
+            SET name = ?, redirect_uris = ?, allowed_scopes = ?, grant_types = ?, is_active = ?, updated_at = ?
+            WHERE client_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(&client.name)
+            .bind(Self::to_json(&client.redirect_uris)?)
+            .bind(Self::to_json(&client.allowed_scopes)?)
+            .bind(Self::to_json(&grant_types)?)
+            .bind(client.is_active)
+            .bind(client.updated_at)
+            .bind(&client.client_id)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update OAuth client: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_client(&self, client_id: &str) -> Result<Option<OAuthClient>, DomainError> {
+        let query = format!("SELECT {} FROM oauth_clients WHERE client_id = ?", CLIENT_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(client_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find OAuth client: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_client).transpose()
+    }
+
+    async fn list_clients(&self) -> Result<Vec<OAuthClient>, DomainError> {
+        let query = format!("SELECT {} FROM oauth_clients ORDER BY created_at DESC", CLIENT_COLUMNS);
+
+        let rows = sqlx::query(&query)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list OAuth clients: {}", e) })?;
+
+        rows.iter().map(Self::row_to_client).collect()
+    }
+
+    async fn save_consent(&self, consent: &OAuthConsent) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO oauth_consents (user_id, client_id, tenant_id, scopes, granted_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE scopes = VALUES(scopes), granted_at = VALUES(granted_at)
+        "#;
+
+        sqlx::query(query)
+            .bind(consent.user_id.to_string())
+            .bind(&consent.client_id)
+            .bind(current_tenant().to_string())
+            .bind(Self::to_json(&consent.scopes)?)
+            .bind(consent.granted_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save OAuth consent: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_consent(&self, user_id: Uuid, client_id: &str) -> Result<Option<OAuthConsent>, DomainError> {
+        let query = r#"
+            SELECT user_id, client_id, scopes, granted_at
+            FROM oauth_consents
+            WHERE user_id = ? AND client_id = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(user_id.to_string())
+            .bind(client_id)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find OAuth consent: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_consent).transpose()
+    }
+
+    async fn find_consents_by_user(&self, user_id: Uuid) -> Result<Vec<OAuthConsent>, DomainError> {
+        let query = r#"
+            SELECT user_id, client_id, scopes, granted_at
+            FROM oauth_consents
+            WHERE user_id = ? AND tenant_id = ?
+            ORDER BY granted_at DESC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find OAuth consents: {}", e) })?;
+
+        rows.iter().map(Self::row_to_consent).collect()
+    }
+
+    async fn delete_consent(&self, user_id: Uuid, client_id: &str) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM oauth_consents WHERE user_id = ? AND client_id = ? AND tenant_id = ?")
+            .bind(user_id.to_string())
+            .bind(client_id)
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete OAuth consent: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn save_authorization_code(&self, code: &OAuthAuthorizationCode) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO oauth_authorization_codes (
+                code_hash, tenant_id, client_id, user_id, redirect_uri, scopes, code_challenge,
+                expires_at, used_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(&code.code_hash)
+            .bind(current_tenant().to_string())
+            .bind(&code.client_id)
+            .bind(code.user_id.to_string())
+            .bind(&code.redirect_uri)
+            .bind(Self::to_json(&code.scopes)?)
+            .bind(&code.code_challenge)
+            .bind(code.expires_at)
+            .bind(code.used_at)
+            .bind(code.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save authorization code: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn consume_authorization_code(
+        &self,
+        code_hash: &str,
+        used_at: DateTime<Utc>,
+    ) -> Result<Option<OAuthAuthorizationCode>, DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let result = sqlx::query(
+            "UPDATE oauth_authorization_codes SET used_at = ? \
+             WHERE code_hash = ? AND tenant_id = ? AND used_at IS NULL",
+        )
+        .bind(used_at)
+        .bind(code_hash)
+        .bind(&tenant_id)
+        .execute(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to consume authorization code: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let query = format!(
+            "SELECT {} FROM oauth_authorization_codes WHERE code_hash = ? AND tenant_id = ?",
+            CODE_COLUMNS
+        );
+        let row = sqlx::query(&query)
+            .bind(code_hash)
+            .bind(&tenant_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find authorization code: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_code).transpose()
+    }
+}
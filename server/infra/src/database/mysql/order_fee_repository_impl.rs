@@ -0,0 +1,120 @@
+//! MySQL implementation of the OrderFeeRepository trait.
+//!
+//! Order fees are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::fee_schedule::OrderFee;
+use re_core::errors::DomainError;
+use re_core::repositories::OrderFeeRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the order fee repository
+pub struct MySqlOrderFeeRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlOrderFeeRepository {
+    /// Create a new MySQL order fee repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlOrderFeeRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to OrderFee entity
+    fn row_to_order_fee(row: &sqlx::mysql::MySqlRow) -> Result<OrderFee, DomainError> {
+        let order_id: String = row.try_get("order_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get order_id: {}", e) })?;
+        let schedule_id: String = row.try_get("schedule_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get schedule_id: {}", e) })?;
+        let category: String = row.try_get("category")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get category: {}", e) })?;
+        let commission: i32 = row.try_get("commission_basis_points")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get commission_basis_points: {}", e) })?;
+
+        Ok(OrderFee {
+            order_id: Uuid::parse_str(&order_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            schedule_id: Uuid::parse_str(&schedule_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            schedule_version: row.try_get("schedule_version")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get schedule_version: {}", e) })?,
+            market: row.try_get("market")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?,
+            category: Some(category).filter(|c| !c.is_empty()),
+            commission_basis_points: commission as i64,
+            minimum_fee: row.try_get("minimum_fee")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get minimum_fee: {}", e) })?,
+            quote_amount: row.try_get("quote_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get quote_amount: {}", e) })?,
+            fee_amount: row.try_get("fee_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get fee_amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            accepted_at: row.try_get::<DateTime<Utc>, _>("accepted_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get accepted_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl OrderFeeRepository for MySqlOrderFeeRepository {
+    async fn save(&self, fee: OrderFee) -> Result<OrderFee, DomainError> {
+        let query = r#"
+            INSERT INTO order_fees (
+                order_id, tenant_id, schedule_id, schedule_version, market, category,
+                commission_basis_points, minimum_fee, quote_amount, fee_amount,
+                currency, accepted_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(fee.order_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(fee.schedule_id.to_string())
+            .bind(fee.schedule_version)
+            .bind(&fee.market)
+            .bind(fee.category.as_deref().unwrap_or(""))
+            .bind(fee.commission_basis_points as i32)
+            .bind(fee.minimum_fee)
+            .bind(fee.quote_amount)
+            .bind(fee.fee_amount)
+            .bind(&fee.currency)
+            .bind(fee.accepted_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save order fee: {}", e) })?;
+
+        Ok(fee)
+    }
+
+    async fn find_by_order(&self, order_id: Uuid) -> Result<Option<OrderFee>, DomainError> {
+        let query = r#"
+            SELECT order_id, schedule_id, schedule_version, market, category,
+                   commission_basis_points, minimum_fee, quote_amount, fee_amount,
+                   currency, accepted_at
+            FROM order_fees
+            WHERE order_id = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(order_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find order fee: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_order_fee).transpose()
+    }
+}
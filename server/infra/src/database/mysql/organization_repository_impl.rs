@@ -0,0 +1,660 @@
+//! MySQL implementation of the OrganizationRepository trait.
+//!
+//! A charge is saved in a transaction that locks the member's row first,
+//! so concurrent quote acceptances by one member are checked against their
+//! spend limit one after the other. Invoice lines are not stored separately:
+//! they are the charges linked to the invoice. Organizations and all their
+//! members, payment methods, charges and invoices are scoped to the current
+//! tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::Row;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use re_core::domain::entities::organization::{
+    ConsolidatedInvoice, InvoiceLine, Organization, OrganizationCharge, OrganizationMember,
+    OrganizationPaymentMethod, OrganizationRole,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::OrganizationRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::{begin, connection};
+
+/// Columns selected for a member
+const MEMBER_COLUMNS: &str = "organization_id, user_id, role, monthly_spend_limit, added_at";
+
+/// Columns selected for a payment method
+const METHOD_COLUMNS: &str = r#"
+    id, organization_id, provider, provider_reference, brand, last4, exp_month, exp_year,
+    is_default, added_by, created_at
+"#;
+
+/// Columns selected for a charge
+const CHARGE_COLUMNS: &str = r#"
+    id, organization_id, user_id, order_id, amount, currency, accepted_at, completed_at, invoice_id
+"#;
+
+/// Columns selected for an invoice
+const INVOICE_COLUMNS: &str = r#"
+    id, organization_id, period_start, currency, total_amount, payment_method_id, issued_at
+"#;
+
+/// MySQL implementation of the organization repository
+pub struct MySqlOrganizationRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlOrganizationRepository {
+    /// Create a new MySQL organization repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlOrganizationRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    fn uuid(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Uuid, DomainError> {
+        let value: String = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        Uuid::parse_str(&value)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+    }
+
+    fn optional_uuid(row: &sqlx::mysql::MySqlRow, column: &str) -> Result<Option<Uuid>, DomainError> {
+        let value: Option<String> = row.try_get(column)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+        value
+            .map(|v| Uuid::parse_str(&v).map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) }))
+            .transpose()
+    }
+
+    /// Convert database row to Organization entity
+    fn row_to_organization(row: &sqlx::mysql::MySqlRow) -> Result<Organization, DomainError> {
+        Ok(Organization {
+            id: Self::uuid(row, "id")?,
+            name: row.try_get("name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get name: {}", e) })?,
+            billing_email: row.try_get("billing_email")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get billing_email: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to OrganizationMember entity
+    fn row_to_member(row: &sqlx::mysql::MySqlRow) -> Result<OrganizationMember, DomainError> {
+        let role: String = row.try_get("role")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get role: {}", e) })?;
+
+        Ok(OrganizationMember {
+            organization_id: Self::uuid(row, "organization_id")?,
+            user_id: Self::uuid(row, "user_id")?,
+            role: OrganizationRole::from_str(&role)
+                .ok_or_else(|| DomainError::Internal { message: format!("Invalid organization role: {}", role) })?,
+            monthly_spend_limit: row.try_get("monthly_spend_limit")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get monthly_spend_limit: {}", e) })?,
+            added_at: row.try_get::<DateTime<Utc>, _>("added_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get added_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to OrganizationPaymentMethod entity
+    fn row_to_method(row: &sqlx::mysql::MySqlRow) -> Result<OrganizationPaymentMethod, DomainError> {
+        Ok(OrganizationPaymentMethod {
+            id: Self::uuid(row, "id")?,
+            organization_id: Self::uuid(row, "organization_id")?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            brand: row.try_get("brand")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get brand: {}", e) })?,
+            last4: row.try_get("last4")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get last4: {}", e) })?,
+            exp_month: row.try_get::<u8, _>("exp_month")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get exp_month: {}", e) })?
+                .into(),
+            exp_year: row.try_get::<i16, _>("exp_year")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get exp_year: {}", e) })?
+                .into(),
+            is_default: row.try_get("is_default")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get is_default: {}", e) })?,
+            added_by: Self::uuid(row, "added_by")?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to OrganizationCharge entity
+    fn row_to_charge(row: &sqlx::mysql::MySqlRow) -> Result<OrganizationCharge, DomainError> {
+        Ok(OrganizationCharge {
+            id: Self::uuid(row, "id")?,
+            organization_id: Self::uuid(row, "organization_id")?,
+            user_id: Self::uuid(row, "user_id")?,
+            order_id: Self::uuid(row, "order_id")?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            accepted_at: row.try_get::<DateTime<Utc>, _>("accepted_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get accepted_at: {}", e) })?,
+            completed_at: row.try_get::<Option<DateTime<Utc>>, _>("completed_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get completed_at: {}", e) })?,
+            invoice_id: Self::optional_uuid(row, "invoice_id")?,
+        })
+    }
+
+    /// Convert an invoice row and its charges to a ConsolidatedInvoice entity
+    fn row_to_invoice(
+        row: &sqlx::mysql::MySqlRow,
+        charges: Vec<OrganizationCharge>,
+    ) -> Result<ConsolidatedInvoice, DomainError> {
+        let lines = charges
+            .into_iter()
+            .filter_map(|charge| {
+                charge.completed_at.map(|completed_at| InvoiceLine {
+                    order_id: charge.order_id,
+                    user_id: charge.user_id,
+                    amount: charge.amount,
+                    completed_at,
+                })
+            })
+            .collect();
+
+        Ok(ConsolidatedInvoice {
+            id: Self::uuid(row, "id")?,
+            organization_id: Self::uuid(row, "organization_id")?,
+            period_start: row.try_get::<NaiveDate, _>("period_start")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get period_start: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            lines,
+            total_amount: row.try_get("total_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get total_amount: {}", e) })?,
+            payment_method_id: Self::optional_uuid(row, "payment_method_id")?,
+            issued_at: row.try_get::<DateTime<Utc>, _>("issued_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get issued_at: {}", e) })?,
+        })
+    }
+
+    /// Load an invoice row together with its lines
+    async fn load_invoice(&self, row: &sqlx::mysql::MySqlRow) -> Result<ConsolidatedInvoice, DomainError> {
+        let query = format!(
+            "SELECT {} FROM organization_charges WHERE invoice_id = ? AND tenant_id = ? ORDER BY completed_at ASC",
+            CHARGE_COLUMNS
+        );
+        let invoice_id = Self::uuid(row, "id")?;
+
+        let rows = sqlx::query(&query)
+            .bind(invoice_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to load invoice lines: {}", e) })?;
+        let charges = rows.iter().map(Self::row_to_charge).collect::<Result<Vec<_>, _>>()?;
+
+        Self::row_to_invoice(row, charges)
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for MySqlOrganizationRepository {
+    async fn create(&self, organization: &Organization, admin: &OrganizationMember) -> Result<(), DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        sqlx::query(
+            "INSERT INTO organizations (id, tenant_id, name, billing_email, currency, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(organization.id.to_string())
+        .bind(&tenant_id)
+        .bind(&organization.name)
+        .bind(&organization.billing_email)
+        .bind(&organization.currency)
+        .bind(organization.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to save organization: {}", e) })?;
+
+        sqlx::query(
+            "INSERT INTO organization_members (organization_id, user_id, tenant_id, role, monthly_spend_limit, added_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(admin.organization_id.to_string())
+        .bind(admin.user_id.to_string())
+        .bind(&tenant_id)
+        .bind(admin.role.as_str())
+        .bind(admin.monthly_spend_limit)
+        .bind(admin.added_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to save organization admin: {}", e) })?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, DomainError> {
+        let result = sqlx::query(
+            "SELECT id, name, billing_email, currency, created_at FROM organizations WHERE id = ? AND tenant_id = ?",
+        )
+        .bind(id.to_string())
+        .bind(current_tenant().to_string())
+        .fetch_optional(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to find organization: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_organization).transpose()
+    }
+
+    async fn find_membership(&self, user_id: Uuid) -> Result<Option<OrganizationMember>, DomainError> {
+        let query = format!("SELECT {} FROM organization_members WHERE user_id = ? AND tenant_id = ?", MEMBER_COLUMNS);
+
+        let result = sqlx::query(&query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find membership: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_member).transpose()
+    }
+
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<OrganizationMember>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM organization_members WHERE organization_id = ? AND tenant_id = ? ORDER BY added_at ASC",
+            MEMBER_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(organization_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list members: {}", e) })?;
+
+        rows.iter().map(Self::row_to_member).collect()
+    }
+
+    async fn save_member(&self, member: &OrganizationMember) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO organization_members (organization_id, user_id, tenant_id, role, monthly_spend_limit, added_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                role = VALUES(role),
+                monthly_spend_limit = VALUES(monthly_spend_limit)
+        "#;
+
+        sqlx::query(query)
+            .bind(member.organization_id.to_string())
+            .bind(member.user_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(member.role.as_str())
+            .bind(member.monthly_spend_limit)
+            .bind(member.added_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save member: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn remove_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "DELETE FROM organization_members WHERE organization_id = ? AND user_id = ? AND tenant_id = ?",
+        )
+        .bind(organization_id.to_string())
+        .bind(user_id.to_string())
+        .bind(current_tenant().to_string())
+        .execute(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to remove member: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn save_payment_method(&self, method: &OrganizationPaymentMethod) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO organization_payment_methods (
+                id, tenant_id, organization_id, provider, provider_reference, brand, last4, exp_month,
+                exp_year, is_default, added_by, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(method.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(method.organization_id.to_string())
+            .bind(&method.provider)
+            .bind(&method.provider_reference)
+            .bind(&method.brand)
+            .bind(&method.last4)
+            .bind(method.exp_month as u8)
+            .bind(method.exp_year as i16)
+            .bind(method.is_default)
+            .bind(method.added_by.to_string())
+            .bind(method.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payment method: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_payment_methods(&self, organization_id: Uuid) -> Result<Vec<OrganizationPaymentMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM organization_payment_methods WHERE organization_id = ? AND tenant_id = ? \
+             ORDER BY created_at DESC",
+            METHOD_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(organization_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list payment methods: {}", e) })?;
+
+        rows.iter().map(Self::row_to_method).collect()
+    }
+
+    async fn set_default_payment_method(&self, organization_id: Uuid, id: Uuid) -> Result<(), DomainError> {
+        sqlx::query(
+            "UPDATE organization_payment_methods SET is_default = (id = ?) WHERE organization_id = ? AND tenant_id = ?",
+        )
+        .bind(id.to_string())
+        .bind(organization_id.to_string())
+        .bind(current_tenant().to_string())
+        .execute(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to set default payment method: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn delete_payment_method(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM organization_payment_methods WHERE id = ? AND tenant_id = ?")
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete payment method: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_charge_by_order(&self, order_id: Uuid) -> Result<Option<OrganizationCharge>, DomainError> {
+        let query = format!("SELECT {} FROM organization_charges WHERE order_id = ? AND tenant_id = ?", CHARGE_COLUMNS);
+
+        let result = sqlx::query(&query)
+            .bind(order_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find charge: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_charge).transpose()
+    }
+
+    async fn save_charge_within_limit(
+        &self,
+        charge: &OrganizationCharge,
+        limit: Option<i64>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        // Serialize acceptances by the same member until this one is committed
+        sqlx::query("SELECT user_id FROM organization_members WHERE user_id = ? AND tenant_id = ? FOR UPDATE")
+            .bind(charge.user_id.to_string())
+            .bind(&tenant_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to lock member: {}", e) })?;
+
+        if let Some(limit) = limit {
+            let spent: i64 = sqlx::query(
+                r#"
+                SELECT CAST(COALESCE(SUM(amount), 0) AS SIGNED) AS spent
+                FROM organization_charges
+                WHERE user_id = ? AND tenant_id = ? AND accepted_at >= ? AND accepted_at < ?
+                "#,
+            )
+            .bind(charge.user_id.to_string())
+            .bind(&tenant_id)
+            .bind(from)
+            .bind(to)
+            .fetch_one(&mut *tx)
+            .await
+            .and_then(|row| row.try_get("spent"))
+            .map_err(|e| DomainError::Internal { message: format!("Failed to sum member spend: {}", e) })?;
+
+            if spent + charge.amount > limit {
+                return Ok(false);
+            }
+        }
+
+        let query = r#"
+            INSERT INTO organization_charges (
+                id, tenant_id, organization_id, user_id, order_id, amount, currency, accepted_at,
+                completed_at, invoice_id
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(charge.id.to_string())
+            .bind(&tenant_id)
+            .bind(charge.organization_id.to_string())
+            .bind(charge.user_id.to_string())
+            .bind(charge.order_id.to_string())
+            .bind(charge.amount)
+            .bind(&charge.currency)
+            .bind(charge.accepted_at)
+            .bind(charge.completed_at)
+            .bind(charge.invoice_id.map(|id| id.to_string()))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save charge: {}", e) })?;
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })?;
+
+        Ok(true)
+    }
+
+    async fn complete_charge(&self, order_id: Uuid, completed_at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "UPDATE organization_charges SET completed_at = ? WHERE order_id = ? AND tenant_id = ? AND completed_at IS NULL",
+        )
+        .bind(completed_at)
+        .bind(order_id.to_string())
+        .bind(current_tenant().to_string())
+        .execute(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to complete charge: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn member_spend(
+        &self,
+        organization_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<Uuid, i64>, DomainError> {
+        let query = r#"
+            SELECT user_id, CAST(SUM(amount) AS SIGNED) AS spent
+            FROM organization_charges
+            WHERE organization_id = ? AND tenant_id = ? AND accepted_at >= ? AND accepted_at < ?
+            GROUP BY user_id
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(organization_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to sum member spend: {}", e) })?;
+
+        rows.iter()
+            .map(|row| {
+                let spent: i64 = row.try_get("spent")
+                    .map_err(|e| DomainError::Internal { message: format!("Failed to get spent: {}", e) })?;
+                Ok((Self::uuid(row, "user_id")?, spent))
+            })
+            .collect()
+    }
+
+    async fn find_uninvoiced_charges(
+        &self,
+        organization_id: Uuid,
+        completed_before: DateTime<Utc>,
+    ) -> Result<Vec<OrganizationCharge>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM organization_charges
+            WHERE organization_id = ? AND tenant_id = ? AND invoice_id IS NULL AND completed_at < ?
+            ORDER BY completed_at ASC
+            "#,
+            CHARGE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(organization_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(completed_before)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find uninvoiced charges: {}", e) })?;
+
+        rows.iter().map(Self::row_to_charge).collect()
+    }
+
+    async fn find_organizations_to_invoice(&self, completed_before: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT organization_id FROM organization_charges \
+             WHERE tenant_id = ? AND invoice_id IS NULL AND completed_at < ?",
+        )
+        .bind(current_tenant().to_string())
+        .bind(completed_before)
+        .fetch_all(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to find organizations to invoice: {}", e) })?;
+
+        rows.iter().map(|row| Self::uuid(row, "organization_id")).collect()
+    }
+
+    async fn save_invoice(&self, invoice: &ConsolidatedInvoice) -> Result<(), DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        let query = r#"
+            INSERT INTO organization_invoices (
+                id, tenant_id, organization_id, period_start, currency, total_amount, payment_method_id, issued_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(invoice.id.to_string())
+            .bind(&tenant_id)
+            .bind(invoice.organization_id.to_string())
+            .bind(invoice.period_start)
+            .bind(&invoice.currency)
+            .bind(invoice.total_amount)
+            .bind(invoice.payment_method_id.map(|id| id.to_string()))
+            .bind(invoice.issued_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save invoice: {}", e) })?;
+
+        for line in &invoice.lines {
+            sqlx::query(
+                "UPDATE organization_charges SET invoice_id = ? WHERE order_id = ? AND tenant_id = ? AND invoice_id IS NULL",
+            )
+            .bind(invoice.id.to_string())
+            .bind(line.order_id.to_string())
+            .bind(&tenant_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to link charge to invoice: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })
+    }
+
+    async fn find_invoice(&self, id: Uuid) -> Result<Option<ConsolidatedInvoice>, DomainError> {
+        let query = format!("SELECT {} FROM organization_invoices WHERE id = ? AND tenant_id = ?", INVOICE_COLUMNS);
+
+        let result = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find invoice: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(self.load_invoice(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_invoice_for_period(
+        &self,
+        organization_id: Uuid,
+        period_start: NaiveDate,
+    ) -> Result<Option<ConsolidatedInvoice>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM organization_invoices WHERE organization_id = ? AND period_start = ? AND tenant_id = ?",
+            INVOICE_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(organization_id.to_string())
+            .bind(period_start)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find invoice: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(self.load_invoice(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_invoices(&self, organization_id: Uuid, limit: usize) -> Result<Vec<ConsolidatedInvoice>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM organization_invoices WHERE organization_id = ? AND tenant_id = ? \
+             ORDER BY period_start DESC LIMIT ?",
+            INVOICE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(organization_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(limit as u32)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list invoices: {}", e) })?;
+
+        let mut invoices = Vec::with_capacity(rows.len());
+        for row in &rows {
+            invoices.push(self.load_invoice(row).await?);
+        }
+        Ok(invoices)
+    }
+}
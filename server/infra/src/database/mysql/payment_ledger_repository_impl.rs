@@ -0,0 +1,118 @@
+//! MySQL implementation of the PaymentLedgerRepository trait.
+//!
+//! Ledger entries mirror the deployment's provider accounts, which settle
+//! the payments of every tenant, so they are not scoped to a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment::{LedgerEntry, LedgerEntryKind};
+use re_core::errors::DomainError;
+use re_core::repositories::PaymentLedgerRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the payment ledger repository
+pub struct MySqlPaymentLedgerRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPaymentLedgerRepository {
+    /// Create a new MySQL payment ledger repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPaymentLedgerRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to LedgerEntry entity
+    fn row_to_entry(row: &sqlx::mysql::MySqlRow) -> Result<LedgerEntry, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+
+        let kind_str: String = row.try_get("kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get kind: {}", e) })?;
+        let kind = LedgerEntryKind::from_str(&kind_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown ledger entry kind: {}", kind_str) })?;
+
+        Ok(LedgerEntry {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            kind,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            description: row.try_get("description")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get description: {}", e) })?,
+            occurred_at: row.try_get::<DateTime<Utc>, _>("occurred_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get occurred_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentLedgerRepository for MySqlPaymentLedgerRepository {
+    async fn save(&self, entry: LedgerEntry) -> Result<LedgerEntry, DomainError> {
+        let query = r#"
+            INSERT INTO payment_ledger_entries (
+                id, provider, provider_reference, kind, amount, currency,
+                description, occurred_at, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(entry.id.to_string())
+            .bind(&entry.provider)
+            .bind(&entry.provider_reference)
+            .bind(entry.kind.as_str())
+            .bind(entry.amount)
+            .bind(&entry.currency)
+            .bind(&entry.description)
+            .bind(entry.occurred_at)
+            .bind(entry.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save ledger entry: {}", e) })?;
+
+        Ok(entry)
+    }
+
+    async fn find_by_period(
+        &self,
+        provider: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<LedgerEntry>, DomainError> {
+        let query = r#"
+            SELECT id, provider, provider_reference, kind, amount, currency,
+                   description, occurred_at, created_at
+            FROM payment_ledger_entries
+            WHERE provider = ? AND occurred_at >= ? AND occurred_at < ?
+            ORDER BY occurred_at ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(provider)
+            .bind(start)
+            .bind(end)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find ledger entries: {}", e) })?;
+
+        rows.iter().map(Self::row_to_entry).collect()
+    }
+}
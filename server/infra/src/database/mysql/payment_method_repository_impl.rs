@@ -0,0 +1,185 @@
+//! MySQL implementation of the PaymentMethodRepository trait.
+//!
+//! The default flag is moved with a single update over the customer's
+//! methods, so a customer never ends up with two defaults. Saved methods are
+//! scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment_method::SavedPaymentMethod;
+use re_core::errors::DomainError;
+use re_core::repositories::PaymentMethodRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a payment method
+const METHOD_COLUMNS: &str = r#"
+    id, customer_id, provider, provider_reference, brand, last4, exp_month, exp_year,
+    is_default, created_at
+"#;
+
+/// MySQL implementation of the saved payment method repository
+pub struct MySqlPaymentMethodRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPaymentMethodRepository {
+    /// Create a new MySQL saved payment method repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPaymentMethodRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to SavedPaymentMethod entity
+    fn row_to_method(row: &sqlx::mysql::MySqlRow) -> Result<SavedPaymentMethod, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        Ok(SavedPaymentMethod {
+            id: uuid("id")?,
+            customer_id: uuid("customer_id")?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            brand: row.try_get("brand")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get brand: {}", e) })?,
+            last4: row.try_get("last4")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get last4: {}", e) })?,
+            exp_month: row.try_get::<u8, _>("exp_month")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get exp_month: {}", e) })?
+                .into(),
+            exp_year: row.try_get::<i16, _>("exp_year")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get exp_year: {}", e) })?
+                .into(),
+            is_default: row.try_get("is_default")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get is_default: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentMethodRepository for MySqlPaymentMethodRepository {
+    async fn save(&self, method: SavedPaymentMethod) -> Result<SavedPaymentMethod, DomainError> {
+        let query = r#"
+            INSERT INTO saved_payment_methods (
+                id, tenant_id, customer_id, provider, provider_reference, brand, last4, exp_month,
+                exp_year, is_default, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(method.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(method.customer_id.to_string())
+            .bind(&method.provider)
+            .bind(&method.provider_reference)
+            .bind(&method.brand)
+            .bind(&method.last4)
+            .bind(method.exp_month)
+            .bind(method.exp_year)
+            .bind(method.is_default)
+            .bind(method.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payment method: {}", e) })?;
+
+        Ok(method)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SavedPaymentMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM saved_payment_methods WHERE id = ? AND tenant_id = ?",
+            METHOD_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment method: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_method).transpose()
+    }
+
+    async fn find_by_reference(
+        &self,
+        customer_id: Uuid,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<SavedPaymentMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM saved_payment_methods \
+             WHERE customer_id = ? AND provider = ? AND provider_reference = ? AND tenant_id = ?",
+            METHOD_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(customer_id.to_string())
+            .bind(provider)
+            .bind(provider_reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment method: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_method).transpose()
+    }
+
+    async fn find_by_customer(&self, customer_id: Uuid) -> Result<Vec<SavedPaymentMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM saved_payment_methods WHERE customer_id = ? AND tenant_id = ? \
+             ORDER BY created_at DESC",
+            METHOD_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(customer_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list payment methods: {}", e) })?;
+
+        rows.iter().map(Self::row_to_method).collect()
+    }
+
+    async fn set_default(&self, customer_id: Uuid, id: Uuid) -> Result<(), DomainError> {
+        sqlx::query("UPDATE saved_payment_methods SET is_default = (id = ?) WHERE customer_id = ? AND tenant_id = ?")
+            .bind(id.to_string())
+            .bind(customer_id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to set default payment method: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM saved_payment_methods WHERE id = ? AND tenant_id = ?")
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete payment method: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,305 @@
+//! MySQL implementation of the PaymentRepository trait.
+//!
+//! Payments and their timelines are scoped to the current tenant, except
+//! for the tenant lookup provider webhooks are routed with.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment::{Payment, PaymentStatus, PaymentTimelineEntry};
+use re_core::errors::DomainError;
+use re_core::repositories::PaymentRepository;
+use re_core::services::tenant::{current_tenant, TenantId};
+use crate::database::DatabasePool;
+use super::{connection, parse_tenant};
+
+const PAYMENT_COLUMNS: &str = r#"
+    id, provider, provider_reference, intent_reference, payment_method, customer_id, worker_id,
+    amount, refunded_amount, currency, status, created_at, updated_at
+"#;
+
+/// MySQL implementation of the payment repository
+pub struct MySqlPaymentRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPaymentRepository {
+    /// Create a new MySQL payment repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPaymentRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to Payment entity
+    fn row_to_payment(row: &sqlx::mysql::MySqlRow) -> Result<Payment, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = PaymentStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown payment status: {}", status_str) })?;
+
+        Ok(Payment {
+            id: uuid("id")?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            intent_reference: row.try_get("intent_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get intent_reference: {}", e) })?,
+            payment_method: row.try_get("payment_method")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get payment_method: {}", e) })?,
+            customer_id: uuid("customer_id")?,
+            worker_id: uuid("worker_id")?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            refunded_amount: row.try_get("refunded_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get refunded_amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            status,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to PaymentTimelineEntry entity
+    fn row_to_timeline_entry(row: &sqlx::mysql::MySqlRow) -> Result<PaymentTimelineEntry, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = PaymentStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown payment status: {}", status_str) })?;
+
+        Ok(PaymentTimelineEntry {
+            id: uuid("id")?,
+            payment_id: uuid("payment_id")?,
+            status,
+            note: row.try_get("note")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get note: {}", e) })?,
+            occurred_at: row.try_get::<DateTime<Utc>, _>("occurred_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get occurred_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for MySqlPaymentRepository {
+    async fn save(&self, payment: Payment) -> Result<Payment, DomainError> {
+        let query = r#"
+            INSERT INTO payments (
+                id, tenant_id, provider, provider_reference, intent_reference, payment_method, customer_id,
+                worker_id, amount, refunded_amount, currency, status, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(payment.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&payment.provider)
+            .bind(&payment.provider_reference)
+            .bind(&payment.intent_reference)
+            .bind(&payment.payment_method)
+            .bind(payment.customer_id.to_string())
+            .bind(payment.worker_id.to_string())
+            .bind(payment.amount)
+            .bind(payment.refunded_amount)
+            .bind(&payment.currency)
+            .bind(payment.status.as_str())
+            .bind(payment.created_at)
+            .bind(payment.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payment: {}", e) })?;
+
+        Ok(payment)
+    }
+
+    async fn find_by_provider_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<Payment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payments WHERE provider = ? AND provider_reference = ? AND tenant_id = ? LIMIT 1",
+            PAYMENT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(provider)
+            .bind(provider_reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_payment).transpose()
+    }
+
+    async fn find_by_intent_reference(
+        &self,
+        provider: &str,
+        intent_reference: &str,
+    ) -> Result<Option<Payment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payments WHERE provider = ? AND intent_reference = ? AND tenant_id = ? LIMIT 1",
+            PAYMENT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(provider)
+            .bind(intent_reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_payment).transpose()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payments WHERE id = ? AND tenant_id = ? LIMIT 1",
+            PAYMENT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_payment).transpose()
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        let query = r#"
+            SELECT tenant_id FROM payments
+            WHERE provider = ? AND (provider_reference = ? OR intent_reference = ?)
+            LIMIT 1
+        "#;
+
+        let tenant_id: Option<String> = sqlx::query_scalar(query)
+            .bind(provider)
+            .bind(reference)
+            .bind(reference)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment tenant: {}", e) })?;
+
+        tenant_id.map(parse_tenant).transpose()
+    }
+
+    async fn find_by_payment_method(
+        &self,
+        customer_id: Uuid,
+        payment_method: &str,
+    ) -> Result<Vec<Payment>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM payments
+            WHERE customer_id = ? AND payment_method = ? AND tenant_id = ?
+            ORDER BY created_at DESC
+            "#,
+            PAYMENT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(customer_id.to_string())
+            .bind(payment_method)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payments: {}", e) })?;
+
+        rows.iter().map(Self::row_to_payment).collect()
+    }
+
+    async fn update(&self, payment: &Payment) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payments
+            SET provider_reference = ?, refunded_amount = ?, status = ?, updated_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(&payment.provider_reference)
+            .bind(payment.refunded_amount)
+            .bind(payment.status.as_str())
+            .bind(payment.updated_at)
+            .bind(payment.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update payment: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn add_timeline_entry(
+        &self,
+        entry: PaymentTimelineEntry,
+    ) -> Result<PaymentTimelineEntry, DomainError> {
+        let query = r#"
+            INSERT INTO payment_timeline (id, tenant_id, payment_id, status, note, occurred_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(entry.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(entry.payment_id.to_string())
+            .bind(entry.status.as_str())
+            .bind(&entry.note)
+            .bind(entry.occurred_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payment timeline entry: {}", e) })?;
+
+        Ok(entry)
+    }
+
+    async fn find_timeline(&self, payment_id: Uuid) -> Result<Vec<PaymentTimelineEntry>, DomainError> {
+        let query = r#"
+            SELECT id, payment_id, status, note, occurred_at
+            FROM payment_timeline
+            WHERE payment_id = ? AND tenant_id = ?
+            ORDER BY occurred_at ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(payment_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payment timeline: {}", e) })?;
+
+        rows.iter().map(Self::row_to_timeline_entry).collect()
+    }
+}
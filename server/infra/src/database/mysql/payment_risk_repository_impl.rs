@@ -0,0 +1,220 @@
+//! MySQL implementation of the PaymentRiskRepository trait.
+//!
+//! Triggered rules are stored as a JSON array of rule names. Assessments are
+//! scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment_risk::{
+    PaymentRiskAssessment, PaymentRiskRule, ReviewStatus, RiskAction,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::PaymentRiskRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for an assessment
+const ASSESSMENT_COLUMNS: &str = r#"
+    id, user_id, amount, currency, market, billing_country, card_fingerprint,
+    triggered_rules, action, review_status, reviewed_by, reviewed_at, review_note, created_at
+"#;
+
+/// MySQL implementation of the payment risk repository
+pub struct MySqlPaymentRiskRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPaymentRiskRepository {
+    /// Create a new MySQL payment risk repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPaymentRiskRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to PaymentRiskAssessment entity
+    fn row_to_assessment(row: &sqlx::mysql::MySqlRow) -> Result<PaymentRiskAssessment, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+        let reviewed_by: Option<String> = row.try_get("reviewed_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get reviewed_by: {}", e) })?;
+
+        let rules: serde_json::Value = row.try_get("triggered_rules")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get triggered_rules: {}", e) })?;
+        let rules: Vec<String> = serde_json::from_value(rules)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid triggered_rules: {}", e) })?;
+        let triggered_rules = rules
+            .iter()
+            .map(|rule| PaymentRiskRule::from_str(rule)
+                .ok_or_else(|| DomainError::Internal { message: format!("Unknown risk rule: {}", rule) }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let action_str: String = row.try_get("action")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get action: {}", e) })?;
+        let action = RiskAction::from_str(&action_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown risk action: {}", action_str) })?;
+
+        let status_str: String = row.try_get("review_status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get review_status: {}", e) })?;
+        let review_status = ReviewStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown review status: {}", status_str) })?;
+
+        Ok(PaymentRiskAssessment {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            market: row.try_get("market")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get market: {}", e) })?,
+            billing_country: row.try_get("billing_country")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get billing_country: {}", e) })?,
+            card_fingerprint: row.try_get("card_fingerprint")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get card_fingerprint: {}", e) })?,
+            triggered_rules,
+            action,
+            review_status,
+            reviewed_by: reviewed_by
+                .map(|id| Uuid::parse_str(&id))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            reviewed_at: row.try_get::<Option<DateTime<Utc>>, _>("reviewed_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reviewed_at: {}", e) })?,
+            review_note: row.try_get("review_note")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get review_note: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentRiskRepository for MySqlPaymentRiskRepository {
+    async fn save(&self, assessment: PaymentRiskAssessment) -> Result<PaymentRiskAssessment, DomainError> {
+        let rules: Vec<&str> = assessment.triggered_rules.iter().map(|r| r.as_str()).collect();
+        let rules = serde_json::to_string(&rules)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize risk rules: {}", e) })?;
+
+        let query = r#"
+            INSERT INTO payment_risk_assessments (
+                id, tenant_id, user_id, amount, currency, market, billing_country, card_fingerprint,
+                triggered_rules, action, review_status, reviewed_by, reviewed_at, review_note, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(assessment.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(assessment.user_id.to_string())
+            .bind(assessment.amount)
+            .bind(&assessment.currency)
+            .bind(&assessment.market)
+            .bind(&assessment.billing_country)
+            .bind(&assessment.card_fingerprint)
+            .bind(rules)
+            .bind(assessment.action.as_str())
+            .bind(assessment.review_status.as_str())
+            .bind(assessment.reviewed_by.map(|id| id.to_string()))
+            .bind(assessment.reviewed_at)
+            .bind(&assessment.review_note)
+            .bind(assessment.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save risk assessment: {}", e) })?;
+
+        Ok(assessment)
+    }
+
+    async fn update_review(&self, assessment: &PaymentRiskAssessment) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payment_risk_assessments
+            SET review_status = ?, reviewed_by = ?, reviewed_at = ?, review_note = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(assessment.review_status.as_str())
+            .bind(assessment.reviewed_by.map(|id| id.to_string()))
+            .bind(assessment.reviewed_at)
+            .bind(&assessment.review_note)
+            .bind(assessment.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update risk review: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PaymentRiskAssessment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payment_risk_assessments WHERE id = ? AND tenant_id = ?",
+            ASSESSMENT_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find risk assessment: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_assessment).transpose()
+    }
+
+    async fn find_pending_reviews(&self, limit: usize) -> Result<Vec<PaymentRiskAssessment>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payment_risk_assessments WHERE tenant_id = ? AND review_status = 'pending' \
+             ORDER BY created_at ASC LIMIT ?",
+            ASSESSMENT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find pending risk reviews: {}", e) })?;
+
+        rows.iter().map(Self::row_to_assessment).collect()
+    }
+
+    async fn find_card_fingerprints(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<String>, DomainError> {
+        let query = r#"
+            SELECT DISTINCT card_fingerprint
+            FROM payment_risk_assessments
+            WHERE user_id = ? AND tenant_id = ? AND created_at >= ? AND card_fingerprint IS NOT NULL
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(since)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find card fingerprints: {}", e) })?;
+
+        rows.iter()
+            .map(|row| row.try_get("card_fingerprint")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get card_fingerprint: {}", e) }))
+            .collect()
+    }
+}
@@ -0,0 +1,134 @@
+//! MySQL implementation of the PaymentWebhookEventRepository trait.
+//!
+//! Payloads are stored as JSON exactly as decoded from the provider's
+//! request so they can be audited later. Providers deliver the events of
+//! every tenant to one endpoint, so events are not scoped to a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment_webhook::PaymentWebhookEvent;
+use re_core::errors::DomainError;
+use re_core::repositories::PaymentWebhookEventRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the payment webhook event repository
+pub struct MySqlPaymentWebhookEventRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPaymentWebhookEventRepository {
+    /// Create a new MySQL payment webhook event repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPaymentWebhookEventRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to PaymentWebhookEvent entity
+    fn row_to_event(row: &sqlx::mysql::MySqlRow) -> Result<PaymentWebhookEvent, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+
+        Ok(PaymentWebhookEvent {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            event_id: row.try_get("event_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get event_id: {}", e) })?,
+            event_type: row.try_get("event_type")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get event_type: {}", e) })?,
+            payload: row.try_get::<serde_json::Value, _>("payload")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get payload: {}", e) })?,
+            outcome: row.try_get("outcome")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get outcome: {}", e) })?,
+            received_at: row.try_get::<DateTime<Utc>, _>("received_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get received_at: {}", e) })?,
+            processed_at: row.try_get::<Option<DateTime<Utc>>, _>("processed_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get processed_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentWebhookEventRepository for MySqlPaymentWebhookEventRepository {
+    async fn save(&self, event: PaymentWebhookEvent) -> Result<PaymentWebhookEvent, DomainError> {
+        let payload = serde_json::to_string(&event.payload)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize payload: {}", e) })?;
+
+        let query = r#"
+            INSERT INTO payment_webhook_events (
+                id, provider, event_id, event_type, payload, outcome, received_at, processed_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(event.id.to_string())
+            .bind(&event.provider)
+            .bind(&event.event_id)
+            .bind(&event.event_type)
+            .bind(payload)
+            .bind(&event.outcome)
+            .bind(event.received_at)
+            .bind(event.processed_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save webhook event: {}", e) })?;
+
+        Ok(event)
+    }
+
+    async fn find_by_event_id(
+        &self,
+        provider: &str,
+        event_id: &str,
+    ) -> Result<Option<PaymentWebhookEvent>, DomainError> {
+        let query = r#"
+            SELECT id, provider, event_id, event_type, payload, outcome, received_at, processed_at
+            FROM payment_webhook_events
+            WHERE provider = ? AND event_id = ?
+            LIMIT 1
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(provider)
+            .bind(event_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find webhook event: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_event).transpose()
+    }
+
+    async fn mark_processed(
+        &self,
+        id: Uuid,
+        outcome: &str,
+        processed_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payment_webhook_events
+            SET outcome = ?, processed_at = ?
+            WHERE id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(outcome)
+            .bind(processed_at)
+            .bind(id.to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to mark webhook event processed: {}", e) })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! MySQL implementation of the PayoutMethodRepository trait.
+//!
+//! Payout methods are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payout_method::{PayoutMethod, PayoutMethodStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::PayoutMethodRepository;
+use re_core::services::tenant::{current_tenant, TenantId};
+use crate::database::DatabasePool;
+use super::{connection, parse_tenant};
+
+/// Columns selected for a payout method
+const METHOD_COLUMNS: &str = r#"
+    id, worker_id, provider, provider_reference, bank_name, account_last4, currency,
+    status, failed_attempts, verified_at, created_at, updated_at
+"#;
+
+/// MySQL implementation of the payout method repository
+pub struct MySqlPayoutMethodRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPayoutMethodRepository {
+    /// Create a new MySQL payout method repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPayoutMethodRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to PayoutMethod entity
+    fn row_to_method(row: &sqlx::mysql::MySqlRow) -> Result<PayoutMethod, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = PayoutMethodStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown payout method status: {}", status_str) })?;
+
+        Ok(PayoutMethod {
+            id: uuid("id")?,
+            worker_id: uuid("worker_id")?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_reference: row.try_get("provider_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_reference: {}", e) })?,
+            bank_name: row.try_get("bank_name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get bank_name: {}", e) })?,
+            account_last4: row.try_get("account_last4")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get account_last4: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            status,
+            failed_attempts: row.try_get("failed_attempts")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get failed_attempts: {}", e) })?,
+            verified_at: row.try_get::<Option<DateTime<Utc>>, _>("verified_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get verified_at: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutMethodRepository for MySqlPayoutMethodRepository {
+    async fn save(&self, method: PayoutMethod) -> Result<PayoutMethod, DomainError> {
+        let query = r#"
+            INSERT INTO payout_methods (
+                id, tenant_id, worker_id, provider, provider_reference, bank_name, account_last4,
+                currency, status, failed_attempts, verified_at, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(method.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(method.worker_id.to_string())
+            .bind(&method.provider)
+            .bind(&method.provider_reference)
+            .bind(&method.bank_name)
+            .bind(&method.account_last4)
+            .bind(&method.currency)
+            .bind(method.status.as_str())
+            .bind(method.failed_attempts)
+            .bind(method.verified_at)
+            .bind(method.created_at)
+            .bind(method.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payout method: {}", e) })?;
+
+        Ok(method)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PayoutMethod>, DomainError> {
+        let query = format!("SELECT {} FROM payout_methods WHERE id = ? AND tenant_id = ?", METHOD_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payout method: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_method).transpose()
+    }
+
+    async fn find_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<PayoutMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payout_methods WHERE provider = ? AND provider_reference = ? AND tenant_id = ?",
+            METHOD_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(provider)
+            .bind(provider_reference)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payout method: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_method).transpose()
+    }
+
+    async fn find_tenant_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> Result<Option<TenantId>, DomainError> {
+        let tenant_id: Option<String> = sqlx::query_scalar(
+            "SELECT tenant_id FROM payout_methods WHERE provider = ? AND provider_reference = ?",
+        )
+        .bind(provider)
+        .bind(provider_reference)
+        .fetch_optional(&mut *connection(&self.pool).await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to find payout method tenant: {}", e) })?;
+
+        tenant_id.map(parse_tenant).transpose()
+    }
+
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<PayoutMethod>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM payout_methods WHERE worker_id = ? AND tenant_id = ? ORDER BY created_at DESC",
+            METHOD_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(worker_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list payout methods: {}", e) })?;
+
+        rows.iter().map(Self::row_to_method).collect()
+    }
+
+    async fn update(&self, method: &PayoutMethod) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payout_methods
+            SET status = ?, failed_attempts = ?, verified_at = ?, updated_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(method.status.as_str())
+            .bind(method.failed_attempts)
+            .bind(method.verified_at)
+            .bind(method.updated_at)
+            .bind(method.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update payout method: {}", e) })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+//! MySQL implementation of the PayoutRepository trait.
+//!
+//! Payouts are scoped to the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::payment::{Payout, PayoutStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::PayoutRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the payout repository
+pub struct MySqlPayoutRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPayoutRepository {
+    /// Create a new MySQL payout repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPayoutRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to Payout entity
+    fn row_to_payout(row: &sqlx::mysql::MySqlRow) -> Result<Payout, DomainError> {
+        let uuid = |column: &str| -> Result<Uuid, DomainError> {
+            let value: String = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            Uuid::parse_str(&value)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = PayoutStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown payout status: {}", status_str) })?;
+
+        Ok(Payout {
+            id: uuid("id")?,
+            payment_id: uuid("payment_id")?,
+            worker_id: uuid("worker_id")?,
+            amount: row.try_get("amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get amount: {}", e) })?,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            status,
+            frozen_reason: row.try_get("frozen_reason")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get frozen_reason: {}", e) })?,
+            transfer_reference: row.try_get("transfer_reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get transfer_reference: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl PayoutRepository for MySqlPayoutRepository {
+    async fn save(&self, payout: Payout) -> Result<Payout, DomainError> {
+        let query = r#"
+            INSERT INTO payouts (
+                id, tenant_id, payment_id, worker_id, amount, currency, status,
+                frozen_reason, transfer_reference, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(payout.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(payout.payment_id.to_string())
+            .bind(payout.worker_id.to_string())
+            .bind(payout.amount)
+            .bind(&payout.currency)
+            .bind(payout.status.as_str())
+            .bind(&payout.frozen_reason)
+            .bind(&payout.transfer_reference)
+            .bind(payout.created_at)
+            .bind(payout.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save payout: {}", e) })?;
+
+        Ok(payout)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Payout>, DomainError> {
+        let query = r#"
+            SELECT id, payment_id, worker_id, amount, currency, status,
+                   frozen_reason, transfer_reference, created_at, updated_at
+            FROM payouts
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payout: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_payout).transpose()
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> Result<Vec<Payout>, DomainError> {
+        let query = r#"
+            SELECT id, payment_id, worker_id, amount, currency, status,
+                   frozen_reason, transfer_reference, created_at, updated_at
+            FROM payouts
+            WHERE payment_id = ? AND tenant_id = ?
+            ORDER BY created_at ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(payment_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find payouts: {}", e) })?;
+
+        rows.iter().map(Self::row_to_payout).collect()
+    }
+
+    async fn update(&self, payout: &Payout) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE payouts
+            SET status = ?, frozen_reason = ?, transfer_reference = ?, updated_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(payout.status.as_str())
+            .bind(&payout.frozen_reason)
+            .bind(&payout.transfer_reference)
+            .bind(payout.updated_at)
+            .bind(payout.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update payout: {}", e) })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! MySQL implementation of the PhoneHashRepository trait.
+//!
+//! Rehashing reads users in id order and replaces each hash with a
+//! conditional update, so rows changed by a concurrent sign-in are left alone.
+
+use async_trait::async_trait;
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::user::User;
+use re_core::errors::DomainError;
+use re_core::repositories::PhoneHashRepository;
+
+use crate::database::sql::SqlUserRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of PhoneHashRepository
+pub struct MySqlPhoneHashRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlPhoneHashRepository {
+    /// Create a new MySQL phone hash repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlPhoneHashRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PhoneHashRepository for MySqlPhoneHashRepository {
+    async fn find_outdated(&self, current_version: u16, limit: usize) -> Result<Vec<User>, DomainError> {
+        // Users are decoded by the generic user repository, over the pool it runs on
+        let database = self.pool.sql_database();
+        let query = format!(
+            "SELECT {} FROM users WHERE phone_hash_version BETWEEN 1 AND ? ORDER BY id LIMIT ?",
+            SqlUserRepository::columns(database.dialect())
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(i32::from(current_version.saturating_sub(1)))
+            .bind(limit as i64)
+            .fetch_all(&mut *database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find outdated phone hashes: {}", e) })?;
+
+        rows.iter().map(SqlUserRepository::row_to_user).collect()
+    }
+
+    async fn count_outdated(&self, current_version: u16) -> Result<u64, DomainError> {
+        let query = r#"
+            SELECT COUNT(*) as count
+            FROM users
+            WHERE phone_hash_version BETWEEN 1 AND ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(current_version.saturating_sub(1))
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to count outdated phone hashes: {}", e) })?;
+
+        let count: i64 = row.try_get("count")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get count: {}", e) })?;
+
+        Ok(count as u64)
+    }
+
+    async fn replace_phone_hash(
+        &self,
+        user_id: Uuid,
+        old_hash: &str,
+        new_hash: &str,
+        new_version: u16,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE users
+            SET phone_hash = ?, phone_hash_version = ?, updated_at = NOW()
+            WHERE id = ? AND phone_hash = ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(new_hash)
+            .bind(new_version)
+            .bind(user_id.to_string())
+            .bind(old_hash)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to replace phone hash: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
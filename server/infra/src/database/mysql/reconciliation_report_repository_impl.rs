@@ -0,0 +1,214 @@
+//! MySQL implementation of the ReconciliationReportRepository trait.
+//!
+//! Report summaries live in `reconciliation_reports` and their
+//! discrepancies in `reconciliation_discrepancies`; both are written in one
+//! transaction. Reports compare the deployment's provider settlements with
+//! its ledger, so they are not scoped to a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use re_core::domain::entities::reconciliation::{
+    Discrepancy, DiscrepancyKind, ReconciliationReport,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::ReconciliationReportRepository;
+use crate::database::DatabasePool;
+use super::{begin, connection};
+
+const REPORT_COLUMNS: &str = r#"
+    id, provider, period_start, period_end, ledger_count, provider_count,
+    matched_count, discrepancy_totals, alerted, created_at
+"#;
+
+/// MySQL implementation of the reconciliation report repository
+pub struct MySqlReconciliationReportRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlReconciliationReportRepository {
+    /// Create a new MySQL reconciliation report repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlReconciliationReportRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to a ReconciliationReport without its discrepancies
+    fn row_to_report(row: &sqlx::mysql::MySqlRow) -> Result<ReconciliationReport, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+
+        let totals: serde_json::Value = row.try_get("discrepancy_totals")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get discrepancy_totals: {}", e) })?;
+        let discrepancy_totals: BTreeMap<String, i64> = serde_json::from_value(totals)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid discrepancy_totals: {}", e) })?;
+
+        let ledger_count: u32 = row.try_get("ledger_count")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get ledger_count: {}", e) })?;
+        let provider_count: u32 = row.try_get("provider_count")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_count: {}", e) })?;
+        let matched_count: u32 = row.try_get("matched_count")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get matched_count: {}", e) })?;
+
+        Ok(ReconciliationReport {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            period_start: row.try_get::<DateTime<Utc>, _>("period_start")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get period_start: {}", e) })?,
+            period_end: row.try_get::<DateTime<Utc>, _>("period_end")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get period_end: {}", e) })?,
+            ledger_count: ledger_count as usize,
+            provider_count: provider_count as usize,
+            matched_count: matched_count as usize,
+            discrepancies: Vec::new(),
+            discrepancy_totals,
+            alerted: row.try_get("alerted")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get alerted: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to Discrepancy entity
+    fn row_to_discrepancy(row: &sqlx::mysql::MySqlRow) -> Result<Discrepancy, DomainError> {
+        let kind_str: String = row.try_get("kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get kind: {}", e) })?;
+        let kind = DiscrepancyKind::from_str(&kind_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown discrepancy kind: {}", kind_str) })?;
+
+        Ok(Discrepancy {
+            reference: row.try_get("reference")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reference: {}", e) })?,
+            kind,
+            currency: row.try_get("currency")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get currency: {}", e) })?,
+            ledger_amount: row.try_get("ledger_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get ledger_amount: {}", e) })?,
+            provider_amount: row.try_get("provider_amount")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_amount: {}", e) })?,
+        })
+    }
+
+    /// Load the discrepancies of a report, ordered by reference
+    async fn find_discrepancies(&self, report_id: Uuid) -> Result<Vec<Discrepancy>, DomainError> {
+        let query = r#"
+            SELECT reference, kind, currency, ledger_amount, provider_amount
+            FROM reconciliation_discrepancies
+            WHERE report_id = ?
+            ORDER BY reference ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(report_id.to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find discrepancies: {}", e) })?;
+
+        rows.iter().map(Self::row_to_discrepancy).collect()
+    }
+}
+
+#[async_trait]
+impl ReconciliationReportRepository for MySqlReconciliationReportRepository {
+    async fn save(&self, report: ReconciliationReport) -> Result<ReconciliationReport, DomainError> {
+        let totals = serde_json::to_string(&report.discrepancy_totals)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize totals: {}", e) })?;
+
+        let mut tx = begin(&self.pool).await?;
+
+        let query = r#"
+            INSERT INTO reconciliation_reports (
+                id, provider, period_start, period_end, ledger_count, provider_count,
+                matched_count, discrepancy_totals, alerted, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(report.id.to_string())
+            .bind(&report.provider)
+            .bind(report.period_start)
+            .bind(report.period_end)
+            .bind(report.ledger_count as u32)
+            .bind(report.provider_count as u32)
+            .bind(report.matched_count as u32)
+            .bind(totals)
+            .bind(report.alerted)
+            .bind(report.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save reconciliation report: {}", e) })?;
+
+        for discrepancy in &report.discrepancies {
+            let query = r#"
+                INSERT INTO reconciliation_discrepancies (
+                    report_id, reference, kind, currency, ledger_amount, provider_amount
+                ) VALUES (?, ?, ?, ?, ?, ?)
+            "#;
+
+            sqlx::query(query)
+                .bind(report.id.to_string())
+                .bind(&discrepancy.reference)
+                .bind(discrepancy.kind.as_str())
+                .bind(&discrepancy.currency)
+                .bind(discrepancy.ledger_amount)
+                .bind(discrepancy.provider_amount)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to save discrepancy: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit reconciliation report: {}", e) })?;
+
+        Ok(report)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ReconciliationReport>, DomainError> {
+        let query = format!("SELECT {} FROM reconciliation_reports WHERE id = ? LIMIT 1", REPORT_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find reconciliation report: {}", e) })?;
+
+        let mut report = match row {
+            Some(row) => Self::row_to_report(&row)?,
+            None => return Ok(None),
+        };
+
+        report.discrepancies = self.find_discrepancies(id).await?;
+        Ok(Some(report))
+    }
+
+    async fn list_recent(&self, limit: usize) -> Result<Vec<ReconciliationReport>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM reconciliation_reports ORDER BY period_start DESC, created_at DESC LIMIT ?",
+            REPORT_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(limit as i64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list reconciliation reports: {}", e) })?;
+
+        let mut reports = rows.iter().map(Self::row_to_report).collect::<Result<Vec<_>, _>>()?;
+        for report in &mut reports {
+            report.discrepancies = self.find_discrepancies(report.id).await?;
+        }
+
+        Ok(reports)
+    }
+}
@@ -0,0 +1,362 @@
+//! MySQL implementation of the ScheduledMessageRepository and
+//! NotificationPreferencesRepository traits.
+//!
+//! This module persists scheduled reminder messages and per-user
+//! notification preferences (timezone offset and quiet hours), scoped to
+//! the current tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, Utc};
+use sqlx::Row;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use re_core::domain::entities::notification::{
+    MessageTemplate, NotificationCategory, NotificationPreferences, ScheduledMessage,
+    ScheduledMessageStatus,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::{NotificationPreferencesRepository, ScheduledMessageRepository};
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+const SCHEDULED_MESSAGE_COLUMNS: &str = r#"
+    id, user_id, phone, template, category, params, requested_for, deliver_at,
+    status, attempts, last_error, provider_message_id, created_at, sent_at
+"#;
+
+/// MySQL implementation of the scheduled message and notification preference repositories
+pub struct MySqlScheduledMessageRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlScheduledMessageRepository {
+    /// Create a new MySQL scheduled message repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlScheduledMessageRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ScheduledMessage entity
+    fn row_to_message(row: &sqlx::mysql::MySqlRow) -> Result<ScheduledMessage, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+
+        let template_str: String = row.try_get("template")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get template: {}", e) })?;
+        let template = MessageTemplate::from_str(&template_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown message template: {}", template_str) })?;
+
+        let category_str: String = row.try_get("category")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get category: {}", e) })?;
+        let category = NotificationCategory::from_str(&category_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown notification category: {}", category_str) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = ScheduledMessageStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown message status: {}", status_str) })?;
+
+        let params: serde_json::Value = row.try_get("params")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get params: {}", e) })?;
+        let params: HashMap<String, String> = serde_json::from_value(params)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid params: {}", e) })?;
+
+        let attempts: u32 = row.try_get("attempts")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get attempts: {}", e) })?;
+
+        Ok(ScheduledMessage {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid user UUID: {}", e) })?,
+            phone: row.try_get("phone")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get phone: {}", e) })?,
+            template,
+            category,
+            params,
+            requested_for: row.try_get::<DateTime<Utc>, _>("requested_for")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get requested_for: {}", e) })?,
+            deliver_at: row.try_get::<DateTime<Utc>, _>("deliver_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get deliver_at: {}", e) })?,
+            status,
+            attempts,
+            last_error: row.try_get("last_error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get last_error: {}", e) })?,
+            provider_message_id: row.try_get("provider_message_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_message_id: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            sent_at: row.try_get("sent_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get sent_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to NotificationPreferences entity
+    fn row_to_preferences(row: &sqlx::mysql::MySqlRow) -> Result<NotificationPreferences, DomainError> {
+        let user_id: String = row.try_get("user_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get user_id: {}", e) })?;
+
+        Ok(NotificationPreferences {
+            user_id: Uuid::parse_str(&user_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid user UUID: {}", e) })?,
+            utc_offset_minutes: row.try_get("utc_offset_minutes")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get utc_offset_minutes: {}", e) })?,
+            quiet_hours_start: row.try_get::<Option<NaiveTime>, _>("quiet_hours_start")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get quiet_hours_start: {}", e) })?,
+            quiet_hours_end: row.try_get::<Option<NaiveTime>, _>("quiet_hours_end")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get quiet_hours_end: {}", e) })?,
+            reminders_enabled: row.try_get("reminders_enabled")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get reminders_enabled: {}", e) })?,
+            marketing_enabled: row.try_get("marketing_enabled")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get marketing_enabled: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+
+    /// Serialize template parameters for the JSON column
+    fn params_to_json(params: &HashMap<String, String>) -> Result<String, DomainError> {
+        serde_json::to_string(params)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize params: {}", e) })
+    }
+}
+
+#[async_trait]
+impl ScheduledMessageRepository for MySqlScheduledMessageRepository {
+    async fn save(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+        let query = r#"
+            INSERT INTO scheduled_messages (
+                id, tenant_id, user_id, phone, template, category, params, requested_for, deliver_at,
+                status, attempts, last_error, provider_message_id, created_at, sent_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(message.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(message.user_id.to_string())
+            .bind(&message.phone)
+            .bind(message.template.as_str())
+            .bind(message.category.as_str())
+            .bind(Self::params_to_json(&message.params)?)
+            .bind(message.requested_for)
+            .bind(message.deliver_at)
+            .bind(message.status.as_str())
+            .bind(message.attempts)
+            .bind(&message.last_error)
+            .bind(&message.provider_message_id)
+            .bind(message.created_at)
+            .bind(message.sent_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save scheduled message: {}", e) })?;
+
+        Ok(message)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ScheduledMessage>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM scheduled_messages WHERE id = ? AND tenant_id = ? LIMIT 1",
+            SCHEDULED_MESSAGE_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find scheduled message: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_message(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+        claim_until: DateTime<Utc>,
+    ) -> Result<Vec<ScheduledMessage>, DomainError> {
+        // The conditional UPDATE is atomic, so concurrent dispatchers claim
+        // disjoint rows; each claim is then read back by its own ID
+        let claim_id = Uuid::new_v4().to_string();
+        let tenant_id = current_tenant().to_string();
+        let mut conn = connection(&self.pool).await?;
+
+        let claim = r#"
+            UPDATE scheduled_messages
+            SET status = 'processing', claimed_by = ?, claimed_until = ?
+            WHERE tenant_id = ?
+              AND ((status = 'pending' AND deliver_at <= ?)
+                OR (status = 'processing' AND claimed_until <= ?))
+            ORDER BY deliver_at ASC
+            LIMIT ?
+        "#;
+
+        let claimed = sqlx::query(claim)
+            .bind(&claim_id)
+            .bind(claim_until)
+            .bind(&tenant_id)
+            .bind(now)
+            .bind(now)
+            .bind(limit as i64)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to claim due messages: {}", e) })?;
+
+        if claimed.rows_affected() == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM scheduled_messages
+            WHERE claimed_by = ? AND tenant_id = ? AND status = 'processing'
+            ORDER BY deliver_at ASC
+            "#,
+            SCHEDULED_MESSAGE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(&claim_id)
+            .bind(&tenant_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to load claimed messages: {}", e) })?;
+
+        rows.iter().map(Self::row_to_message).collect()
+    }
+
+    async fn find_pending_by_user(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM scheduled_messages
+            WHERE user_id = ? AND tenant_id = ? AND status = 'pending'
+            ORDER BY deliver_at ASC
+            "#,
+            SCHEDULED_MESSAGE_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find pending messages: {}", e) })?;
+
+        rows.iter().map(Self::row_to_message).collect()
+    }
+
+    async fn update(&self, message: ScheduledMessage) -> Result<ScheduledMessage, DomainError> {
+        let query = r#"
+            UPDATE scheduled_messages SET
+                deliver_at = ?,
+                status = ?,
+                attempts = ?,
+                last_error = ?,
+                provider_message_id = ?,
+                sent_at = ?,
+                claimed_by = NULL,
+                claimed_until = NULL
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(message.deliver_at)
+            .bind(message.status.as_str())
+            .bind(message.attempts)
+            .bind(&message.last_error)
+            .bind(&message.provider_message_id)
+            .bind(message.sent_at)
+            .bind(message.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update scheduled message: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound { resource: "Scheduled message".to_string() });
+        }
+
+        Ok(message)
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for MySqlScheduledMessageRepository {
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>, DomainError> {
+        let query = r#"
+            SELECT user_id, utc_offset_minutes, quiet_hours_start, quiet_hours_end,
+                   reminders_enabled, marketing_enabled, updated_at
+            FROM notification_preferences
+            WHERE user_id = ? AND tenant_id = ?
+            LIMIT 1
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find notification preferences: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_preferences(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn upsert(
+        &self,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, DomainError> {
+        let query = r#"
+            INSERT INTO notification_preferences (
+                user_id, tenant_id, utc_offset_minutes, quiet_hours_start, quiet_hours_end,
+                reminders_enabled, marketing_enabled, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                utc_offset_minutes = VALUES(utc_offset_minutes),
+                quiet_hours_start = VALUES(quiet_hours_start),
+                quiet_hours_end = VALUES(quiet_hours_end),
+                reminders_enabled = VALUES(reminders_enabled),
+                marketing_enabled = VALUES(marketing_enabled),
+                updated_at = VALUES(updated_at)
+        "#;
+
+        let mut preferences = preferences;
+        preferences.updated_at = Utc::now();
+
+        sqlx::query(query)
+            .bind(preferences.user_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(preferences.utc_offset_minutes)
+            .bind(preferences.quiet_hours_start)
+            .bind(preferences.quiet_hours_end)
+            .bind(preferences.reminders_enabled)
+            .bind(preferences.marketing_enabled)
+            .bind(preferences.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save notification preferences: {}", e) })?;
+
+        Ok(preferences)
+    }
+}
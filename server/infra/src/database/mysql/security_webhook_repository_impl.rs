@@ -0,0 +1,317 @@
+//! MySQL implementation of the SecurityWebhookRepository trait.
+//!
+//! Subscribed event kinds and delivery payloads are stored in JSON columns.
+//! Deliveries reference their webhook with `ON DELETE CASCADE`, so deleting
+//! a webhook drops its queue. Webhooks and deliveries are scoped to the
+//! current tenant, so a tenant's endpoints only receive its own events.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::security_webhook::{
+    SecurityEventKind, SecurityWebhook, WebhookDelivery, WebhookDeliveryStatus,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::SecurityWebhookRepository;
+use re_core::services::tenant::{current_tenant, TenantId};
+use crate::database::DatabasePool;
+use super::{begin, connection, parse_tenant};
+
+const WEBHOOK_COLUMNS: &str = r#"
+    id, url, secret, events, is_active, created_by, created_at
+"#;
+
+const DELIVERY_COLUMNS: &str = r#"
+    id, webhook_id, event_kind, payload, status, attempts, next_attempt_at,
+    last_error, created_at, delivered_at
+"#;
+
+/// Longest error stored for a failed attempt
+const MAX_ERROR_LENGTH: usize = 500;
+
+/// MySQL implementation of the security webhook repository
+pub struct MySqlSecurityWebhookRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlSecurityWebhookRepository {
+    /// Create a new MySQL security webhook repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlSecurityWebhookRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to SecurityWebhook entity
+    fn row_to_webhook(row: &sqlx::mysql::MySqlRow) -> Result<SecurityWebhook, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let created_by: String = row.try_get("created_by")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get created_by: {}", e) })?;
+
+        let events: serde_json::Value = row.try_get("events")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get events: {}", e) })?;
+
+        Ok(SecurityWebhook {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            url: row.try_get("url")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get url: {}", e) })?,
+            secret: row.try_get("secret")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get secret: {}", e) })?,
+            events: serde_json::from_value(events)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid events: {}", e) })?,
+            is_active: row.try_get("is_active")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get is_active: {}", e) })?,
+            created_by: Uuid::parse_str(&created_by)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid creator UUID: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+
+    /// Convert database row to WebhookDelivery entity
+    fn row_to_delivery(row: &sqlx::mysql::MySqlRow) -> Result<WebhookDelivery, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let webhook_id: String = row.try_get("webhook_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get webhook_id: {}", e) })?;
+
+        let kind_str: String = row.try_get("event_kind")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get event_kind: {}", e) })?;
+        let event_kind = SecurityEventKind::from_str(&kind_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown security event kind: {}", kind_str) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = WebhookDeliveryStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown delivery status: {}", status_str) })?;
+
+        Ok(WebhookDelivery {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            webhook_id: Uuid::parse_str(&webhook_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid webhook UUID: {}", e) })?,
+            event_kind,
+            payload: row.try_get("payload")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get payload: {}", e) })?,
+            status,
+            attempts: row.try_get("attempts")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get attempts: {}", e) })?,
+            next_attempt_at: row.try_get::<DateTime<Utc>, _>("next_attempt_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get next_attempt_at: {}", e) })?,
+            last_error: row.try_get("last_error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get last_error: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            delivered_at: row.try_get("delivered_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get delivered_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl SecurityWebhookRepository for MySqlSecurityWebhookRepository {
+    async fn save(&self, webhook: SecurityWebhook) -> Result<SecurityWebhook, DomainError> {
+        let query = r#"
+            INSERT INTO security_webhooks (
+                id, tenant_id, url, secret, events, is_active, created_by, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let events = serde_json::to_string(&webhook.events)
+            .map_err(|e| DomainError::Internal { message: format!("Failed to serialize events: {}", e) })?;
+
+        sqlx::query(query)
+            .bind(webhook.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&webhook.url)
+            .bind(&webhook.secret)
+            .bind(events)
+            .bind(webhook.is_active)
+            .bind(webhook.created_by.to_string())
+            .bind(webhook.created_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save security webhook: {}", e) })?;
+
+        Ok(webhook)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<SecurityWebhook>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM security_webhooks WHERE id = ? AND tenant_id = ? LIMIT 1",
+            WEBHOOK_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find security webhook: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_webhook(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_tenant_by_id(&self, id: Uuid) -> Result<Option<TenantId>, DomainError> {
+        let tenant_id: Option<String> = sqlx::query_scalar("SELECT tenant_id FROM security_webhooks WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find security webhook tenant: {}", e) })?;
+
+        tenant_id.map(parse_tenant).transpose()
+    }
+
+    async fn list(&self) -> Result<Vec<SecurityWebhook>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM security_webhooks WHERE tenant_id = ? ORDER BY created_at DESC",
+            WEBHOOK_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list security webhooks: {}", e) })?;
+
+        rows.iter().map(Self::row_to_webhook).collect()
+    }
+
+    async fn find_subscribed(&self, kind: SecurityEventKind) -> Result<Vec<SecurityWebhook>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM security_webhooks
+            WHERE tenant_id = ? AND is_active = TRUE AND JSON_CONTAINS(events, JSON_QUOTE(?))
+            "#,
+            WEBHOOK_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(kind.as_str())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find security webhooks: {}", e) })?;
+
+        rows.iter().map(Self::row_to_webhook).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM security_webhooks WHERE id = ? AND tenant_id = ?")
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete security webhook: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn save_deliveries(&self, deliveries: Vec<WebhookDelivery>) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO security_webhook_deliveries (
+                id, tenant_id, webhook_id, event_kind, payload, status, attempts, next_attempt_at,
+                last_error, created_at, delivered_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        for delivery in deliveries {
+            sqlx::query(query)
+                .bind(delivery.id.to_string())
+                .bind(&tenant_id)
+                .bind(delivery.webhook_id.to_string())
+                .bind(delivery.event_kind.as_str())
+                .bind(&delivery.payload)
+                .bind(delivery.status.as_str())
+                .bind(delivery.attempts)
+                .bind(delivery.next_attempt_at)
+                .bind(&delivery.last_error)
+                .bind(delivery.created_at)
+                .bind(delivery.delivered_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to save security webhook delivery: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM security_webhook_deliveries
+            WHERE tenant_id = ? AND status = 'pending' AND next_attempt_at <= ?
+            ORDER BY next_attempt_at ASC
+            LIMIT ?
+            "#,
+            DELIVERY_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(current_tenant().to_string())
+            .bind(now)
+            .bind(limit as i64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find due webhook deliveries: {}", e) })?;
+
+        rows.iter().map(Self::row_to_delivery).collect()
+    }
+
+    async fn update_delivery(&self, delivery: WebhookDelivery) -> Result<WebhookDelivery, DomainError> {
+        let query = r#"
+            UPDATE security_webhook_deliveries
+            SET status = ?, attempts = ?, next_attempt_at = ?, last_error = ?, delivered_at = ?
+            WHERE id = ? AND tenant_id = ?
+        "#;
+
+        let last_error = delivery
+            .last_error
+            .as_ref()
+            .map(|error| error.chars().take(MAX_ERROR_LENGTH).collect::<String>());
+
+        let result = sqlx::query(query)
+            .bind(delivery.status.as_str())
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(last_error)
+            .bind(delivery.delivered_at)
+            .bind(delivery.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update security webhook delivery: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound {
+                resource: "Security webhook delivery".to_string(),
+            });
+        }
+
+        Ok(delivery)
+    }
+}
@@ -0,0 +1,164 @@
+//! MySQL implementation of the ServiceAreaRepository trait.
+//!
+//! Service areas are stored as `POLYGON` geometries in SRID 4326 with a
+//! spatial index, so location matching runs in MySQL with `ST_Contains`.
+//! WKT is exchanged with explicit long-lat axis order, since MySQL defaults
+//! to lat-long for geographic reference systems. Areas are scoped to the
+//! current tenant, so workers are only matched within their marketplace.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use re_shared::types::common::Coordinate;
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::service_area::ServiceArea;
+use re_core::errors::DomainError;
+use re_core::repositories::ServiceAreaRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::{begin, connection};
+
+/// MySQL implementation of the service area repository
+pub struct MySqlServiceAreaRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlServiceAreaRepository {
+    /// Create a new MySQL service area repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlServiceAreaRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to ServiceArea entity
+    fn row_to_area(row: &sqlx::mysql::MySqlRow) -> Result<ServiceArea, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+        let worker_id: String = row.try_get("worker_id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get worker_id: {}", e) })?;
+        let geojson: String = row.try_get("area_geojson")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get area: {}", e) })?;
+        let geojson: serde_json::Value = serde_json::from_str(&geojson)
+            .map_err(|e| DomainError::Internal { message: format!("Invalid area GeoJSON: {}", e) })?;
+
+        let ring = geojson["coordinates"][0]
+            .as_array()
+            .ok_or_else(|| DomainError::Internal { message: "Stored area has no ring".to_string() })?
+            .iter()
+            .map(|position| match (position[0].as_f64(), position[1].as_f64()) {
+                (Some(longitude), Some(latitude)) => Ok(Coordinate::new(latitude, longitude)),
+                _ => Err(DomainError::Internal { message: "Invalid stored position".to_string() }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ServiceArea {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            worker_id: Uuid::parse_str(&worker_id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid worker UUID: {}", e) })?,
+            name: row.try_get("name")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get name: {}", e) })?,
+            ring,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl ServiceAreaRepository for MySqlServiceAreaRepository {
+    async fn replace_for_worker(
+        &self,
+        worker_id: Uuid,
+        areas: Vec<ServiceArea>,
+    ) -> Result<Vec<ServiceArea>, DomainError> {
+        let tenant_id = current_tenant().to_string();
+        let mut tx = begin(&self.pool).await?;
+
+        sqlx::query("DELETE FROM worker_service_areas WHERE worker_id = ? AND tenant_id = ?")
+            .bind(worker_id.to_string())
+            .bind(&tenant_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to delete service areas: {}", e) })?;
+
+        for area in &areas {
+            let query = r#"
+                INSERT INTO worker_service_areas (id, tenant_id, worker_id, name, area, created_at)
+                VALUES (?, ?, ?, ?, ST_GeomFromText(?, 4326, 'axis-order=long-lat'), ?)
+            "#;
+
+            sqlx::query(query)
+                .bind(area.id.to_string())
+                .bind(&tenant_id)
+                .bind(worker_id.to_string())
+                .bind(&area.name)
+                .bind(area.to_wkt())
+                .bind(area.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to save service area: {}", e) })?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit service areas: {}", e) })?;
+
+        Ok(areas)
+    }
+
+    async fn find_by_worker(&self, worker_id: Uuid) -> Result<Vec<ServiceArea>, DomainError> {
+        let query = r#"
+            SELECT id, worker_id, name, ST_AsGeoJSON(area) AS area_geojson, created_at
+            FROM worker_service_areas
+            WHERE worker_id = ? AND tenant_id = ?
+            ORDER BY created_at ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(worker_id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find service areas: {}", e) })?;
+
+        rows.iter().map(Self::row_to_area).collect()
+    }
+
+    async fn find_workers_containing(
+        &self,
+        point: Coordinate,
+        limit: usize,
+    ) -> Result<Vec<Uuid>, DomainError> {
+        let query = r#"
+            SELECT DISTINCT worker_id
+            FROM worker_service_areas
+            WHERE ST_Contains(area, ST_GeomFromText(?, 4326, 'axis-order=long-lat'))
+              AND tenant_id = ?
+            LIMIT ?
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(format!("POINT({} {})", point.longitude, point.latitude))
+            .bind(current_tenant().to_string())
+            .bind(limit as i64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to match service areas: {}", e) })?;
+
+        rows.iter()
+            .map(|row| {
+                let worker_id: String = row.try_get("worker_id")
+                    .map_err(|e| DomainError::Internal { message: format!("Failed to get worker_id: {}", e) })?;
+                Uuid::parse_str(&worker_id)
+                    .map_err(|e| DomainError::Internal { message: format!("Invalid worker UUID: {}", e) })
+            })
+            .collect()
+    }
+}
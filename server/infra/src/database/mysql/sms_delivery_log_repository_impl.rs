@@ -0,0 +1,260 @@
+//! MySQL implementation of the SmsDeliveryLogRepository trait.
+//!
+//! Messages are sent through the deployment's provider accounts, so delivery
+//! logs are not scoped to a tenant.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::mysql::{MySql, MySqlArguments};
+use sqlx::query::Query;
+use sqlx::Row;
+use uuid::Uuid;
+
+use re_core::domain::entities::sms_delivery::{SmsDeliveryLog, SmsDeliveryStatus, SmsLogFilter};
+use re_core::errors::DomainError;
+use re_core::repositories::{SmsDeliveryLogRepository, SmsLogRepository};
+use re_shared::types::Pagination;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a delivery log entry
+const LOG_COLUMNS: &str = r#"
+    id, trace_id, provider, provider_message_id, phone_masked, status, error,
+    template, cost_micros, created_at, updated_at
+"#;
+
+/// MySQL implementation of the SMS delivery log repository
+pub struct MySqlSmsDeliveryLogRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlSmsDeliveryLogRepository {
+    /// Create a new MySQL SMS delivery log repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlSmsDeliveryLogRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to SmsDeliveryLog entity
+    fn row_to_log(row: &sqlx::mysql::MySqlRow) -> Result<SmsDeliveryLog, DomainError> {
+        let id: String = row.try_get("id")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get id: {}", e) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = SmsDeliveryStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown SMS delivery status: {}", status_str) })?;
+
+        Ok(SmsDeliveryLog {
+            id: Uuid::parse_str(&id)
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })?,
+            trace_id: row.try_get("trace_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get trace_id: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            provider_message_id: row.try_get("provider_message_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider_message_id: {}", e) })?,
+            phone_masked: row.try_get("phone_masked")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get phone_masked: {}", e) })?,
+            status,
+            error: row.try_get("error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get error: {}", e) })?,
+            template: row.try_get("template")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get template: {}", e) })?,
+            cost_micros: row.try_get("cost_micros")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get cost_micros: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+
+    /// WHERE clause of the set filter criteria, in the order they are bound
+    fn filter_clause(filter: &SmsLogFilter) -> String {
+        let mut conditions = Vec::new();
+        if filter.phone_masked.is_some() {
+            conditions.push("phone_masked = ?");
+        }
+        if filter.provider.is_some() {
+            conditions.push("provider = ?");
+        }
+        if filter.status.is_some() {
+            conditions.push("status = ?");
+        }
+        if filter.template.is_some() {
+            conditions.push("template = ?");
+        }
+        if filter.date_range.from.is_some() {
+            conditions.push("created_at >= ?");
+        }
+        if filter.date_range.to.is_some() {
+            conditions.push("created_at <= ?");
+        }
+
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        }
+    }
+
+    /// Bind the values of the set filter criteria
+    fn bind_filter<'q>(
+        mut query: Query<'q, MySql, MySqlArguments>,
+        filter: &'q SmsLogFilter,
+    ) -> Query<'q, MySql, MySqlArguments> {
+        if let Some(ref phone_masked) = filter.phone_masked {
+            query = query.bind(phone_masked);
+        }
+        if let Some(ref provider) = filter.provider {
+            query = query.bind(provider);
+        }
+        if let Some(status) = filter.status {
+            query = query.bind(status.as_str());
+        }
+        if let Some(ref template) = filter.template {
+            query = query.bind(template);
+        }
+        if let Some(from) = filter.date_range.from {
+            query = query.bind(from);
+        }
+        if let Some(to) = filter.date_range.to {
+            query = query.bind(to);
+        }
+        query
+    }
+}
+
+#[async_trait]
+impl SmsDeliveryLogRepository for MySqlSmsDeliveryLogRepository {
+    async fn save(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        let query = r#"
+            INSERT INTO sms_delivery_logs (
+                id, trace_id, provider, provider_message_id, phone_masked, status, error,
+                template, cost_micros, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(log.id.to_string())
+            .bind(&log.trace_id)
+            .bind(&log.provider)
+            .bind(&log.provider_message_id)
+            .bind(&log.phone_masked)
+            .bind(log.status.as_str())
+            .bind(&log.error)
+            .bind(&log.template)
+            .bind(log.cost_micros)
+            .bind(log.created_at)
+            .bind(log.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save SMS delivery log: {}", e) })?;
+
+        Ok(log)
+    }
+
+    async fn find_by_provider_message_id(
+        &self,
+        provider: &str,
+        provider_message_id: &str,
+    ) -> Result<Option<SmsDeliveryLog>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM sms_delivery_logs WHERE provider = ? AND provider_message_id = ? LIMIT 1",
+            LOG_COLUMNS
+        );
+
+        let result = sqlx::query(&query)
+            .bind(provider)
+            .bind(provider_message_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find SMS delivery log: {}", e) })?;
+
+        match result {
+            Some(row) => Ok(Some(Self::row_to_log(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_trace_id(&self, trace_id: &str) -> Result<Vec<SmsDeliveryLog>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM sms_delivery_logs WHERE trace_id = ? ORDER BY created_at ASC",
+            LOG_COLUMNS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(trace_id)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find SMS delivery logs: {}", e) })?;
+
+        rows.iter().map(Self::row_to_log).collect()
+    }
+
+    async fn update(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        let query = r#"
+            UPDATE sms_delivery_logs SET
+                status = ?,
+                error = ?,
+                updated_at = ?
+            WHERE id = ?
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(log.status.as_str())
+            .bind(&log.error)
+            .bind(log.updated_at)
+            .bind(log.id.to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update SMS delivery log: {}", e) })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound { resource: "SMS delivery log".to_string() });
+        }
+
+        Ok(log)
+    }
+}
+
+#[async_trait]
+impl SmsLogRepository for MySqlSmsDeliveryLogRepository {
+    async fn query(
+        &self,
+        filter: &SmsLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<SmsDeliveryLog>, u64), DomainError> {
+        let where_clause = Self::filter_clause(filter);
+
+        let count_query = format!("SELECT COUNT(*) AS total FROM sms_delivery_logs {}", where_clause);
+        let total: i64 = Self::bind_filter(sqlx::query(&count_query), filter)
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to count SMS logs: {}", e) })?
+            .try_get("total")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get total: {}", e) })?;
+
+        let query = format!(
+            "SELECT {} FROM sms_delivery_logs {} ORDER BY created_at DESC LIMIT ? OFFSET ?",
+            LOG_COLUMNS, where_clause
+        );
+
+        let rows = Self::bind_filter(sqlx::query(&query), filter)
+            .bind(pagination.limit_i64())
+            .bind(pagination.offset_i64())
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to query SMS logs: {}", e) })?;
+
+        let logs = rows.iter().map(Self::row_to_log).collect::<Result<Vec<_>, _>>()?;
+        Ok((logs, total.max(0) as u64))
+    }
+}
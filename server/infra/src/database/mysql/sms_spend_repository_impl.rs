@@ -0,0 +1,99 @@
+//! MySQL implementation of the SmsSpendRepository trait.
+//!
+//! Each message adds to the row of its day, provider and rate prefix, so
+//! the table stays small enough to report spend over long periods. Spend is
+//! billed to the deployment's provider accounts, so it is not scoped to a
+//! tenant.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::Row;
+
+use re_core::domain::entities::sms_spend::SmsDailySpend;
+use re_core::errors::DomainError;
+use re_core::repositories::SmsSpendRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the daily SMS spend repository
+pub struct MySqlSmsSpendRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlSmsSpendRepository {
+    /// Create a new MySQL daily SMS spend repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlSmsSpendRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to SmsDailySpend entity
+    fn row_to_spend(row: &sqlx::mysql::MySqlRow) -> Result<SmsDailySpend, DomainError> {
+        Ok(SmsDailySpend {
+            day: row.try_get::<NaiveDate, _>("spend_date")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get spend_date: {}", e) })?,
+            provider: row.try_get("provider")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get provider: {}", e) })?,
+            prefix: row.try_get("prefix")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get prefix: {}", e) })?,
+            messages: row.try_get("messages")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get messages: {}", e) })?,
+            cost_micros: row.try_get("cost_micros")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get cost_micros: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl SmsSpendRepository for MySqlSmsSpendRepository {
+    async fn add(
+        &self,
+        day: NaiveDate,
+        provider: &str,
+        prefix: &str,
+        cost_micros: u64,
+    ) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO sms_daily_spend (spend_date, provider, prefix, messages, cost_micros)
+            VALUES (?, ?, ?, 1, ?)
+            ON DUPLICATE KEY UPDATE
+                messages = messages + 1,
+                cost_micros = cost_micros + VALUES(cost_micros)
+        "#;
+
+        sqlx::query(query)
+            .bind(day)
+            .bind(provider)
+            .bind(prefix)
+            .bind(cost_micros)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to record SMS spend: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn find_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<SmsDailySpend>, DomainError> {
+        let query = r#"
+            SELECT spend_date, provider, prefix, messages, cost_micros
+            FROM sms_daily_spend
+            WHERE spend_date BETWEEN ? AND ?
+            ORDER BY spend_date ASC, provider ASC, prefix ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find SMS spend: {}", e) })?;
+
+        rows.iter().map(Self::row_to_spend).collect()
+    }
+}
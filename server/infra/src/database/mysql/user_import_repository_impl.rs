@@ -0,0 +1,100 @@
+//! MySQL implementation of the UserImportRepository trait.
+//!
+//! Users are imported into the current tenant, so checkpoints are scoped to
+//! it too: the same export can be imported into another tenant from scratch.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+
+use re_core::domain::entities::user_import::UserImportCheckpoint;
+use re_core::errors::DomainError;
+use re_core::repositories::UserImportRepository;
+use re_core::services::tenant::current_tenant;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// MySQL implementation of the user import repository
+pub struct MySqlUserImportRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlUserImportRepository {
+    /// Create a new MySQL user import repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlUserImportRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to UserImportCheckpoint entity
+    fn row_to_checkpoint(row: &sqlx::mysql::MySqlRow) -> Result<UserImportCheckpoint, DomainError> {
+        Ok(UserImportCheckpoint {
+            import_id: row.try_get("import_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get import_id: {}", e) })?,
+            records_processed: row.try_get("records_processed")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get records_processed: {}", e) })?,
+            imported: row.try_get("imported")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get imported: {}", e) })?,
+            skipped: row.try_get("skipped")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get skipped: {}", e) })?,
+            completed: row.try_get("completed")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get completed: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+}
+
+#[async_trait]
+impl UserImportRepository for MySqlUserImportRepository {
+    async fn find_checkpoint(&self, import_id: &str) -> Result<Option<UserImportCheckpoint>, DomainError> {
+        let query = r#"
+            SELECT import_id, records_processed, imported, skipped, completed, updated_at
+            FROM user_import_checkpoints
+            WHERE tenant_id = ? AND import_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(current_tenant().to_string())
+            .bind(import_id)
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find import checkpoint: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_checkpoint).transpose()
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &UserImportCheckpoint) -> Result<(), DomainError> {
+        let query = r#"
+            INSERT INTO user_import_checkpoints (
+                tenant_id, import_id, records_processed, imported, skipped, completed, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                records_processed = VALUES(records_processed),
+                imported = VALUES(imported),
+                skipped = VALUES(skipped),
+                completed = VALUES(completed),
+                updated_at = VALUES(updated_at)
+        "#;
+
+        sqlx::query(query)
+            .bind(current_tenant().to_string())
+            .bind(&checkpoint.import_id)
+            .bind(checkpoint.records_processed)
+            .bind(checkpoint.imported)
+            .bind(checkpoint.skipped)
+            .bind(checkpoint.completed)
+            .bind(checkpoint.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to save import checkpoint: {}", e) })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,344 @@
+//! MySQL implementation of the WebhookDeadLetterRepository trait.
+//!
+//! Payloads are stored in a JSON column. Each webhook has at most one dead
+//! letter, keyed by direction, source and event ID. Dead letters are
+//! reviewed and replayed by back-office staff for every tenant, so they are
+//! not scoped to a tenant; a replay runs in the tenant of its webhook.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::mysql::MySqlArguments;
+use sqlx::query::Query;
+use sqlx::{MySql, Row};
+use uuid::Uuid;
+
+use re_core::domain::entities::webhook_dead_letter::{
+    DeadLetterFilter, DeadLetterStatus, WebhookDeadLetter, WebhookDirection,
+};
+use re_core::errors::DomainError;
+use re_core::repositories::WebhookDeadLetterRepository;
+use crate::database::DatabasePool;
+use super::connection;
+
+/// Columns selected for a dead letter
+const DEAD_LETTER_COLUMNS: &str = r#"
+    id, direction, source, event_id, event_type, target_id, payload, error, failures,
+    status, replays, replayed_by, replayed_at, resolution, created_at, updated_at
+"#;
+
+/// MySQL implementation of the webhook dead-letter repository
+pub struct MySqlWebhookDeadLetterRepository {
+    /// Database connection pool
+    pool: DatabasePool,
+}
+
+impl MySqlWebhookDeadLetterRepository {
+    /// Create a new MySQL webhook dead-letter repository
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    ///
+    /// # Returns
+    /// A new instance of MySqlWebhookDeadLetterRepository
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Convert database row to WebhookDeadLetter entity
+    fn row_to_dead_letter(row: &sqlx::mysql::MySqlRow) -> Result<WebhookDeadLetter, DomainError> {
+        let optional_uuid = |column: &str| -> Result<Option<Uuid>, DomainError> {
+            let value: Option<String> = row.try_get(column)
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get {}: {}", column, e) })?;
+            value
+                .map(|value| Uuid::parse_str(&value))
+                .transpose()
+                .map_err(|e| DomainError::Internal { message: format!("Invalid UUID: {}", e) })
+        };
+
+        let id = optional_uuid("id")?
+            .ok_or_else(|| DomainError::Internal { message: "Dead letter without ID".to_string() })?;
+
+        let direction_str: String = row.try_get("direction")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get direction: {}", e) })?;
+        let direction = WebhookDirection::from_str(&direction_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown webhook direction: {}", direction_str) })?;
+
+        let status_str: String = row.try_get("status")
+            .map_err(|e| DomainError::Internal { message: format!("Failed to get status: {}", e) })?;
+        let status = DeadLetterStatus::from_str(&status_str)
+            .ok_or_else(|| DomainError::Internal { message: format!("Unknown dead letter status: {}", status_str) })?;
+
+        Ok(WebhookDeadLetter {
+            id,
+            direction,
+            source: row.try_get("source")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get source: {}", e) })?,
+            event_id: row.try_get("event_id")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get event_id: {}", e) })?,
+            event_type: row.try_get("event_type")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get event_type: {}", e) })?,
+            target_id: optional_uuid("target_id")?,
+            payload: row.try_get("payload")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get payload: {}", e) })?,
+            error: row.try_get("error")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get error: {}", e) })?,
+            failures: row.try_get("failures")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get failures: {}", e) })?,
+            status,
+            replays: row.try_get("replays")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get replays: {}", e) })?,
+            replayed_by: optional_uuid("replayed_by")?,
+            replayed_at: row.try_get::<Option<DateTime<Utc>>, _>("replayed_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get replayed_at: {}", e) })?,
+            resolution: row.try_get("resolution")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get resolution: {}", e) })?,
+            created_at: row.try_get::<DateTime<Utc>, _>("created_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get created_at: {}", e) })?,
+            updated_at: row.try_get::<DateTime<Utc>, _>("updated_at")
+                .map_err(|e| DomainError::Internal { message: format!("Failed to get updated_at: {}", e) })?,
+        })
+    }
+
+    /// Bind the values of a dead-letter filter in the order `list` adds its conditions
+    fn bind_filter<'q>(
+        mut query: Query<'q, MySql, MySqlArguments>,
+        filter: &'q DeadLetterFilter,
+    ) -> Query<'q, MySql, MySqlArguments> {
+        if let Some(direction) = filter.direction {
+            query = query.bind(direction.as_str());
+        }
+        if let Some(ref source) = filter.source {
+            query = query.bind(source);
+        }
+        if let Some(status) = filter.status {
+            query = query.bind(status.as_str());
+        }
+        if let Some(from) = filter.from {
+            query = query.bind(from);
+        }
+        if let Some(to) = filter.to {
+            query = query.bind(to);
+        }
+        query
+    }
+}
+
+#[async_trait]
+impl WebhookDeadLetterRepository for MySqlWebhookDeadLetterRepository {
+    async fn record(&self, dead_letter: WebhookDeadLetter) -> Result<WebhookDeadLetter, DomainError> {
+        // A replay in progress keeps its state; it records its own outcome
+        let query = r#"
+            INSERT INTO webhook_dead_letters (
+                id, direction, source, event_id, event_type, target_id, payload, error, failures,
+                status, replays, replayed_by, replayed_at, resolution, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE
+                error = VALUES(error),
+                failures = failures + 1,
+                status = IF(status = 'replaying', status, 'pending'),
+                updated_at = VALUES(updated_at)
+        "#;
+
+        sqlx::query(query)
+            .bind(dead_letter.id.to_string())
+            .bind(dead_letter.direction.as_str())
+            .bind(&dead_letter.source)
+            .bind(&dead_letter.event_id)
+            .bind(&dead_letter.event_type)
+            .bind(dead_letter.target_id.map(|id| id.to_string()))
+            .bind(&dead_letter.payload)
+            .bind(&dead_letter.error)
+            .bind(dead_letter.failures)
+            .bind(dead_letter.status.as_str())
+            .bind(dead_letter.replays)
+            .bind(dead_letter.replayed_by.map(|id| id.to_string()))
+            .bind(dead_letter.replayed_at)
+            .bind(&dead_letter.resolution)
+            .bind(dead_letter.created_at)
+            .bind(dead_letter.updated_at)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to record webhook dead letter: {}", e) })?;
+
+        let query = format!(
+            "SELECT {} FROM webhook_dead_letters WHERE direction = ? AND source = ? AND event_id = ?",
+            DEAD_LETTER_COLUMNS
+        );
+
+        let row = sqlx::query(&query)
+            .bind(dead_letter.direction.as_str())
+            .bind(&dead_letter.source)
+            .bind(&dead_letter.event_id)
+            .fetch_one(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find webhook dead letter: {}", e) })?;
+
+        Self::row_to_dead_letter(&row)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookDeadLetter>, DomainError> {
+        let query = format!("SELECT {} FROM webhook_dead_letters WHERE id = ?", DEAD_LETTER_COLUMNS);
+
+        let row = sqlx::query(&query)
+            .bind(id.to_string())
+            .fetch_optional(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find webhook dead letter: {}", e) })?;
+
+        row.as_ref().map(Self::row_to_dead_letter).transpose()
+    }
+
+    async fn list(
+        &self,
+        filter: &DeadLetterFilter,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError> {
+        let mut conditions = Vec::new();
+        if filter.direction.is_some() {
+            conditions.push("direction = ?");
+        }
+        if filter.source.is_some() {
+            conditions.push("source = ?");
+        }
+        if filter.status.is_some() {
+            conditions.push("status = ?");
+        }
+        if filter.from.is_some() {
+            conditions.push("created_at >= ?");
+        }
+        if filter.to.is_some() {
+            conditions.push("created_at <= ?");
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let query = format!(
+            "SELECT {} FROM webhook_dead_letters {} ORDER BY created_at DESC LIMIT ?",
+            DEAD_LETTER_COLUMNS, where_clause
+        );
+
+        let rows = Self::bind_filter(sqlx::query(&query), filter)
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to list webhook dead letters: {}", e) })?;
+
+        rows.iter().map(Self::row_to_dead_letter).collect()
+    }
+
+    async fn find_pending(
+        &self,
+        direction: Option<WebhookDirection>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDeadLetter>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM webhook_dead_letters
+            WHERE status = 'pending' AND created_at >= ? AND created_at <= ?
+              AND (? IS NULL OR direction = ?)
+            ORDER BY created_at
+            LIMIT ?
+            "#,
+            DEAD_LETTER_COLUMNS
+        );
+
+        let direction = direction.map(|direction| direction.as_str());
+        let rows = sqlx::query(&query)
+            .bind(from)
+            .bind(to)
+            .bind(direction)
+            .bind(direction)
+            .bind(limit as u64)
+            .fetch_all(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find pending webhook dead letters: {}", e) })?;
+
+        rows.iter().map(Self::row_to_dead_letter).collect()
+    }
+
+    async fn claim(
+        &self,
+        dead_letter: &WebhookDeadLetter,
+        stale_before: DateTime<Utc>,
+    ) -> Result<bool, DomainError> {
+        // Only the caller whose update finds the dead letter pending, or its
+        // replay stale, runs the replay
+        let query = r#"
+            UPDATE webhook_dead_letters
+            SET status = ?, replays = ?, replayed_by = ?, replayed_at = ?, updated_at = ?
+            WHERE id = ?
+              AND (status = 'pending' OR (status = 'replaying' AND updated_at < ?))
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(dead_letter.status.as_str())
+            .bind(dead_letter.replays)
+            .bind(dead_letter.replayed_by.map(|id| id.to_string()))
+            .bind(dead_letter.replayed_at)
+            .bind(dead_letter.updated_at)
+            .bind(dead_letter.id.to_string())
+            .bind(stale_before)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to claim webhook dead letter: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update(&self, dead_letter: &WebhookDeadLetter) -> Result<(), DomainError> {
+        let query = r#"
+            UPDATE webhook_dead_letters
+            SET error = ?, failures = ?, status = ?, replays = ?, replayed_by = ?, replayed_at = ?,
+                resolution = ?, updated_at = ?
+            WHERE id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(&dead_letter.error)
+            .bind(dead_letter.failures)
+            .bind(dead_letter.status.as_str())
+            .bind(dead_letter.replays)
+            .bind(dead_letter.replayed_by.map(|id| id.to_string()))
+            .bind(dead_letter.replayed_at)
+            .bind(&dead_letter.resolution)
+            .bind(dead_letter.updated_at)
+            .bind(dead_letter.id.to_string())
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to update webhook dead letter: {}", e) })?;
+
+        Ok(())
+    }
+
+    async fn resolve_event(
+        &self,
+        direction: WebhookDirection,
+        source: &str,
+        event_id: &str,
+        resolution: &str,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            UPDATE webhook_dead_letters
+            SET status = 'resolved', resolution = ?, updated_at = ?
+            WHERE direction = ? AND source = ? AND event_id = ? AND status = 'pending'
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(resolution)
+            .bind(Utc::now())
+            .bind(direction.as_str())
+            .bind(source)
+            .bind(event_id)
+            .execute(&mut *connection(&self.pool).await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to resolve webhook dead letter: {}", e) })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
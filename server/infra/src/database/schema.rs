@@ -0,0 +1,217 @@
+//! Detecting schema drift at startup
+//!
+//! An instance running against a database that is missing a migration, or
+//! whose tables were altered by hand, fails on the first query touching
+//! the difference, possibly hours after it started serving. `verify_schema`
+//! checks once at startup that every embedded migration was applied and
+//! that the columns and indexes the repositories rely on exist, and fails
+//! with an `InfrastructureError::Config` listing every difference.
+//!
+//! A database migrated past this build is only logged: during a rolling
+//! deployment instances of the previous release keep serving after the
+//! next release's migrations ran.
+
+use sqlx::{MySqlPool, Row};
+use std::collections::HashSet;
+
+use super::migrations::migrator;
+use crate::InfrastructureError;
+
+/// A table the repositories rely on
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredTable {
+    pub name: &'static str,
+    pub columns: &'static [&'static str],
+    pub indexes: &'static [&'static str],
+}
+
+/// Tables, columns and indexes the MySQL repositories of users, refresh
+/// tokens and audit logs query
+pub const REQUIRED_TABLES: &[RequiredTable] = &[
+    RequiredTable {
+        name: "users",
+        columns: &[
+            "id", "tenant_id", "phone_hash", "country_code", "user_type", "created_at", "updated_at",
+            "last_login_at", "last_login_country", "phone_hash_version", "is_verified", "is_blocked",
+            "deleted_at",
+        ],
+        indexes: &["PRIMARY", "uk_users_tenant_phone_hash"],
+    },
+    RequiredTable {
+        name: "refresh_tokens",
+        columns: &[
+            "id", "tenant_id", "user_id", "token_hash", "created_at", "expires_at", "last_used_at",
+            "is_revoked", "revoked_at", "revoke_reason", "device_fingerprint", "token_family",
+            "previous_token_id",
+        ],
+        indexes: &["PRIMARY", "uk_token_hash", "idx_refresh_tokens_tenant_user", "idx_refresh_tokens_expires_at"],
+    },
+    RequiredTable {
+        name: "refresh_tokens_archive",
+        columns: &["id", "tenant_id", "user_id", "token_hash", "token_family", "archived_at"],
+        indexes: &["PRIMARY", "idx_refresh_tokens_archive_token_hash"],
+    },
+    RequiredTable {
+        name: "auth_audit_log",
+        columns: &[
+            "id", "tenant_id", "event_type", "user_id", "phone_hash", "ip_address", "success",
+            "event_data", "created_at", "archived", "archived_at", "trace_id",
+        ],
+        indexes: &["PRIMARY", "idx_auth_audit_log_tenant_created", "idx_auth_audit_log_archival"],
+    },
+];
+
+/// What the live database looks like
+#[derive(Debug, Clone, Default)]
+pub struct LiveSchema {
+    /// Versions of the migrations applied successfully
+    pub applied: HashSet<i64>,
+    /// Versions of migrations that failed part-way
+    pub failed: HashSet<i64>,
+    /// `(table, column)` pairs
+    pub columns: HashSet<(String, String)>,
+    /// `(table, index)` pairs
+    pub indexes: HashSet<(String, String)>,
+}
+
+impl LiveSchema {
+    /// Read the applied migrations, columns and indexes of the connected database
+    pub async fn read(pool: &MySqlPool) -> Result<Self, InfrastructureError> {
+        let mut schema = Self::default();
+
+        let migrations = sqlx::query("SELECT version, success FROM _sqlx_migrations")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| {
+                InfrastructureError::Config(format!(
+                    "Cannot read applied migrations; apply them with --migrate-only: {}",
+                    e
+                ))
+            })?;
+        for row in migrations {
+            let version: i64 = row.try_get("version")?;
+            if row.try_get::<bool, _>("success")? {
+                schema.applied.insert(version);
+            } else {
+                schema.failed.insert(version);
+            }
+        }
+
+        let columns = sqlx::query(
+            "SELECT TABLE_NAME AS table_name, COLUMN_NAME AS column_name \
+             FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE()",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in columns {
+            schema.columns.insert((row.try_get("table_name")?, row.try_get("column_name")?));
+        }
+
+        let indexes = sqlx::query(
+            "SELECT DISTINCT TABLE_NAME AS table_name, INDEX_NAME AS index_name \
+             FROM information_schema.STATISTICS WHERE TABLE_SCHEMA = DATABASE()",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in indexes {
+            schema.indexes.insert((row.try_get("table_name")?, row.try_get("index_name")?));
+        }
+
+        Ok(schema)
+    }
+
+    /// Describe every way this schema differs from what this build expects
+    ///
+    /// # Arguments
+    /// * `migrations` - Versions of the migrations embedded in this build
+    /// * `tables` - Tables the repositories rely on
+    ///
+    /// # Returns
+    /// One line per difference; empty if there is none
+    pub fn drift(&self, migrations: &[i64], tables: &[RequiredTable]) -> Vec<String> {
+        let mut drift = Vec::new();
+
+        let mut failed: Vec<_> = self.failed.iter().copied().collect();
+        failed.sort_unstable();
+        for version in failed {
+            drift.push(format!("migration {} failed part-way and must be repaired by hand", version));
+        }
+
+        let pending: Vec<String> = migrations
+            .iter()
+            .filter(|version| !self.applied.contains(version) && !self.failed.contains(version))
+            .map(|version| version.to_string())
+            .collect();
+        if !pending.is_empty() {
+            drift.push(format!("migrations {} are not applied", pending.join(", ")));
+        }
+
+        for table in tables {
+            for column in table.columns {
+                if !self.columns.contains(&(table.name.to_string(), column.to_string())) {
+                    drift.push(format!("column {}.{} is missing", table.name, column));
+                }
+            }
+            for index in table.indexes {
+                if !self.indexes.contains(&(table.name.to_string(), index.to_string())) {
+                    drift.push(format!("index {} on {} is missing", index, table.name));
+                }
+            }
+        }
+
+        drift
+    }
+
+    /// Versions of applied migrations this build does not know, if any
+    pub fn unknown_migrations(&self, migrations: &[i64]) -> Vec<i64> {
+        let mut unknown: Vec<i64> = self
+            .applied
+            .iter()
+            .filter(|version| !migrations.contains(version))
+            .copied()
+            .collect();
+        unknown.sort_unstable();
+        unknown
+    }
+}
+
+/// Versions of the migrations embedded in this build
+pub fn embedded_migrations() -> Vec<i64> {
+    migrator().iter().map(|migration| migration.version).collect()
+}
+
+/// Verify that the connected database has the schema this build expects
+///
+/// # Returns
+/// * `Ok(())` - Every embedded migration is applied and every required
+///   column and index exists
+/// * `Err(InfrastructureError::Config)` - The schema drifted; the message
+///   lists every difference
+/// * `Err(InfrastructureError::Database)` - The schema cannot be read
+pub async fn verify_schema(pool: &MySqlPool) -> Result<(), InfrastructureError> {
+    let live = LiveSchema::read(pool).await?;
+    let migrations = embedded_migrations();
+
+    let unknown = live.unknown_migrations(&migrations);
+    if !unknown.is_empty() {
+        tracing::warn!(
+            "Database has migrations newer than this build: {:?}; expected during a rolling deployment",
+            unknown
+        );
+    }
+
+    let drift = live.drift(&migrations, REQUIRED_TABLES);
+    if !drift.is_empty() {
+        return Err(InfrastructureError::Config(format!(
+            "Database schema does not match this build: {}",
+            drift.join("; ")
+        )));
+    }
+
+    tracing::info!(
+        "Database schema verified ({} migrations, {} required tables)",
+        migrations.len(),
+        REQUIRED_TABLES.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,291 @@
+//! Country-based sharding of the users table
+//!
+//! Users of some countries live in regional databases, such as China's users
+//! in a database hosted in China. `ShardRouter` maps a country calling code
+//! to the shard holding its users, falling back to the primary database, and
+//! `ShardedUserRepository` routes each `UserRepository` call through it:
+//!
+//! - Lookups and writes by phone number or user go to the shard of the
+//!   country code
+//! - Lookups, deletes and restores by ID try every shard, primary first
+//! - Counts add up the counts of every shard
+//! - Streams read the shards one after another, primary first
+//!
+//! `ShardedUnitOfWork` opens the transactions saving a login, which create
+//! or update the user, on the shard of the user's country.
+//!
+//! Only users are sharded, and users are never moved between shards. Every
+//! shard needs the full schema; rows referencing users by foreign key, such
+//! as refresh tokens, must be written to the shard of their user.
+
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use re_core::domain::entities::user::{User, UserType};
+use re_core::errors::DomainError;
+use re_core::repositories::{UnitOfWork, UnitOfWorkTransaction, UserRepository};
+use re_shared::config::database::DatabaseConfig;
+
+use super::connection::DatabasePool;
+use super::sql::{SqlUnitOfWork, SqlUserRepository};
+use crate::InfrastructureError;
+
+/// Name of the primary database in logs
+pub const PRIMARY_SHARD: &str = "primary";
+
+/// Maps country calling codes to the shard holding their users
+///
+/// Generic over what a shard is, so the same routing serves connection
+/// pools and the repositories built on them.
+#[derive(Debug, Clone)]
+pub struct ShardRouter<T> {
+    /// Shard names, the primary database first
+    names: Vec<String>,
+    /// Shards, in the order of `names`
+    shards: Vec<T>,
+    /// Index of the shard of each country code assigned to a shard
+    countries: HashMap<String, usize>,
+}
+
+impl<T> ShardRouter<T> {
+    /// Create a router sending every country to the primary database
+    pub fn new(primary: T) -> Self {
+        Self {
+            names: vec![PRIMARY_SHARD.to_string()],
+            shards: vec![primary],
+            countries: HashMap::new(),
+        }
+    }
+
+    /// Add a shard holding the users of some countries
+    ///
+    /// A country already assigned to another shard moves to this one.
+    pub fn with_shard<I, S>(mut self, name: impl Into<String>, country_codes: I, shard: T) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let index = self.shards.len();
+        self.names.push(name.into());
+        self.shards.push(shard);
+        for code in country_codes {
+            self.countries.insert(code.into(), index);
+        }
+        self
+    }
+
+    /// Index of the shard holding the users of a country
+    fn index_of(&self, country_code: &str) -> usize {
+        self.countries.get(country_code).copied().unwrap_or(0)
+    }
+
+    /// Shard holding the users of a country
+    pub fn route(&self, country_code: &str) -> &T {
+        &self.shards[self.index_of(country_code)]
+    }
+
+    /// Name of the shard holding the users of a country
+    pub fn shard_name(&self, country_code: &str) -> &str {
+        &self.names[self.index_of(country_code)]
+    }
+
+    /// The primary database
+    pub fn primary(&self) -> &T {
+        &self.shards[0]
+    }
+
+    /// Every shard, the primary database first
+    pub fn shards(&self) -> &[T] {
+        &self.shards
+    }
+
+    /// Router with the same countries over shards derived from these
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> ShardRouter<U> {
+        ShardRouter {
+            names: self.names.clone(),
+            shards: self.shards.iter().map(f).collect(),
+            countries: self.countries.clone(),
+        }
+    }
+}
+
+impl ShardRouter<DatabasePool> {
+    /// Connect to the primary database and every shard it configures
+    ///
+    /// # Returns
+    /// * `Err(InfrastructureError::Config)` - A country is assigned to more
+    ///   than one shard
+    /// * `Err(InfrastructureError::Database)` - A database cannot be reached
+    pub async fn connect(config: DatabaseConfig) -> Result<Self, InfrastructureError> {
+        config.validate_shards().map_err(InfrastructureError::Config)?;
+
+        let mut router = Self::new(DatabasePool::new(config.clone()).await?);
+        for shard in &config.shards {
+            tracing::info!(
+                "Connecting to database shard {} for country codes {}",
+                shard.name,
+                shard.country_codes.join(", ")
+            );
+            let pool = DatabasePool::new(config.for_shard(shard)).await?;
+            router = router.with_shard(shard.name.clone(), shard.country_codes.iter().cloned(), pool);
+        }
+
+        Ok(router)
+    }
+
+    /// Close the pools of every shard
+    pub async fn close(&self) {
+        for pool in &self.shards {
+            pool.close().await;
+        }
+    }
+}
+
+/// User repository routing each call to the shard of the user's country
+pub struct ShardedUserRepository<R> {
+    router: ShardRouter<R>,
+}
+
+impl<R> ShardedUserRepository<R> {
+    /// Create a repository over one user repository per shard
+    pub fn new(router: ShardRouter<R>) -> Self {
+        Self { router }
+    }
+
+    /// The repositories of the shards
+    pub fn router(&self) -> &ShardRouter<R> {
+        &self.router
+    }
+}
+
+impl ShardedUserRepository<SqlUserRepository> {
+    /// Create a repository over the connection pools of the shards
+    pub fn from_pools(pools: &ShardRouter<DatabasePool>) -> Self {
+        Self::new(pools.map(|pool| SqlUserRepository::new(pool.sql_database())))
+    }
+}
+
+#[async_trait]
+impl<R: UserRepository> UserRepository for ShardedUserRepository<R> {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        self.router.route(country_code).find_by_phone(phone_hash, country_code).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        for shard in self.router.shards() {
+            if let Some(user) = shard.find_by_id(id).await? {
+                return Ok(Some(user));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.router.route(&user.country_code).create(user).await
+    }
+
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        let mut by_shard: Vec<Vec<User>> = self.router.shards().iter().map(|_| Vec::new()).collect();
+        for user in users {
+            by_shard[self.router.index_of(&user.country_code)].push(user);
+        }
+
+        let mut created = 0;
+        for (shard, users) in self.router.shards().iter().zip(by_shard) {
+            if !users.is_empty() {
+                created += shard.create_many(users).await?;
+            }
+        }
+        Ok(created)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        self.router.route(&user.country_code).update(user).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        for shard in self.router.shards() {
+            if shard.delete(id).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        for shard in self.router.shards() {
+            if let Some(user) = shard.find_including_deleted(id).await? {
+                return Ok(Some(user));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        for shard in self.router.shards() {
+            if shard.restore(id).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        self.router.route(country_code).exists_by_phone(phone_hash, country_code).await
+    }
+
+    async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError> {
+        let mut count = 0;
+        for shard in self.router.shards() {
+            count += shard.count_by_type(user_type).await?;
+        }
+        Ok(count)
+    }
+
+    fn find_all_stream(&self, user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        stream::iter(self.router.shards())
+            .flat_map(move |shard| shard.find_all_stream(user_type))
+            .boxed()
+    }
+}
+
+/// Unit of work opening each transaction on the shard of the user's country
+///
+/// Must be used with a `ShardedUserRepository` over the same shards, or new
+/// users are written to one database and looked up in another.
+pub struct ShardedUnitOfWork<U> {
+    router: ShardRouter<U>,
+}
+
+impl<U> ShardedUnitOfWork<U> {
+    /// Create a unit of work over one unit of work per shard
+    pub fn new(router: ShardRouter<U>) -> Self {
+        Self { router }
+    }
+
+    /// The units of work of the shards
+    pub fn router(&self) -> &ShardRouter<U> {
+        &self.router
+    }
+}
+
+impl ShardedUnitOfWork<SqlUnitOfWork> {
+    /// Create a unit of work over the connection pools of the shards
+    pub fn from_pools(pools: &ShardRouter<DatabasePool>) -> Self {
+        Self::new(pools.map(|pool| SqlUnitOfWork::new(pool.sql_database())))
+    }
+}
+
+#[async_trait]
+impl<U: UnitOfWork> UnitOfWork for ShardedUnitOfWork<U> {
+    /// Begin a transaction on the primary database
+    async fn begin(&self) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        self.router.primary().begin().await
+    }
+
+    async fn begin_for_country(&self, country_code: &str) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        self.router.route(country_code).begin_for_country(country_code).await
+    }
+}
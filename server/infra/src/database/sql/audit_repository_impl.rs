@@ -0,0 +1,573 @@
+//! Database-agnostic implementation of the AuditLogRepository trait.
+//!
+//! Events are recorded in, and looked up within, the tenant of the current
+//! request. Archiving and retention span all tenants, and their cut-offs are
+//! computed in Rust because date arithmetic differs between databases.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::any::{AnyArguments, AnyRow};
+use sqlx::query::Query;
+use sqlx::Any;
+use uuid::Uuid;
+
+use re_core::domain::entities::audit::{AuditEventType, AuditLog, AuditLogFilter};
+use re_core::errors::DomainError;
+use re_core::repositories::audit::AuditLogRepository;
+use re_core::services::tenant::current_tenant;
+use re_shared::types::Pagination;
+
+use super::connection::SqlDatabase;
+use super::dialect::SqlDialect;
+use super::row;
+
+/// Columns written for an audit log, in insert order
+const INSERT_COLUMNS: &str = r#"
+    id, tenant_id, event_type, user_id, phone_masked, phone_hash,
+    ip_address, user_agent, device_info, action, success,
+    error_message, failure_reason, token_id, rate_limit_type,
+    event_data, created_at, archived, archived_at, trace_id
+"#;
+
+/// Days audit logs stay active before they are archived
+const ARCHIVE_AFTER_DAYS: i64 = 90;
+
+/// Days archived audit logs are kept before they are deleted
+const DELETE_ARCHIVED_AFTER_DAYS: i64 = 7;
+
+/// Generic SQL implementation of AuditLogRepository
+pub struct SqlAuditLogRepository {
+    /// Database connection pool
+    database: SqlDatabase,
+}
+
+impl SqlAuditLogRepository {
+    /// Create a new generic SQL audit log repository
+    ///
+    /// # Arguments
+    /// * `database` - Pool and dialect of the database
+    ///
+    /// # Returns
+    /// A new instance of SqlAuditLogRepository
+    pub fn new(database: SqlDatabase) -> Self {
+        Self { database }
+    }
+
+    fn dialect(&self) -> SqlDialect {
+        self.database.dialect()
+    }
+
+    /// Columns selected for an audit log
+    fn columns(&self) -> String {
+        let d = self.dialect();
+        format!(
+            "id, event_type, user_id, phone_masked, phone_hash, ip_address, {}, device_info, \
+             action, {}, {}, failure_reason, token_id, rate_limit_type, {}, {}, {}, {}, trace_id",
+            d.text("user_agent"),
+            d.boolean("success"),
+            d.text("error_message"),
+            d.json("event_data"),
+            d.timestamp("created_at"),
+            d.boolean("archived"),
+            d.timestamp("archived_at"),
+        )
+    }
+
+    /// Convert database row to AuditLog entity
+    fn row_to_audit_log(row: &AnyRow) -> Result<AuditLog, DomainError> {
+        let event_type_str: String = row::get(row, "event_type")?;
+        let event_type = AuditEventType::from_str(&event_type_str)
+            .ok_or_else(|| DomainError::Internal {
+                message: format!("Unknown event type: {}", event_type_str),
+            })?;
+
+        let event_data: Option<String> = row::get_optional(row, "event_data")?;
+        let event_data = event_data
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| DomainError::Internal {
+                message: format!("Invalid event_data: {}", e),
+            })?;
+
+        Ok(AuditLog {
+            id: row::get_uuid(row, "id")?,
+            event_type,
+            user_id: row::get_optional_uuid(row, "user_id")?,
+            phone_masked: row::get_optional(row, "phone_masked")?,
+            phone_hash: row::get_optional(row, "phone_hash")?,
+            ip_address: row::get(row, "ip_address")?,
+            user_agent: row::get_optional(row, "user_agent")?,
+            device_info: row::get_optional(row, "device_info")?,
+            event_data,
+            failure_reason: row::get_optional(row, "failure_reason")?,
+            token_id: row::get_optional_uuid(row, "token_id")?,
+            rate_limit_type: row::get_optional(row, "rate_limit_type")?,
+            trace_id: row::get_optional(row, "trace_id")?,
+            success: row::get_bool(row, "success")?,
+            action: row::get(row, "action")?,
+            error_message: row::get_optional(row, "error_message")?,
+            created_at: row::get_timestamp(row, "created_at")?,
+            archived: row::get_bool(row, "archived")?,
+            archived_at: row::get_optional_timestamp(row, "archived_at")?,
+        })
+    }
+
+    /// Bind the tenant and the values of an audit log filter in the order
+    /// `query` adds its conditions
+    fn bind_filter<'q>(
+        &self,
+        mut query: Query<'q, Any, AnyArguments<'q>>,
+        filter: &'q AuditLogFilter,
+    ) -> Query<'q, Any, AnyArguments<'q>> {
+        query = query.bind(current_tenant().to_string());
+        if let Some(user_id) = filter.user_id {
+            query = query.bind(user_id.to_string());
+        }
+        if let Some(ref phone_hash) = filter.phone_hash {
+            query = query.bind(phone_hash);
+        }
+        for event_type in &filter.event_types {
+            query = query.bind(event_type.as_str());
+        }
+        if let Some(ref ip_address) = filter.ip_address {
+            query = query.bind(ip_address);
+        }
+        if let Some(from) = filter.date_range.from {
+            query = query.bind(self.dialect().encode_timestamp(from));
+        }
+        if let Some(to) = filter.date_range.to {
+            query = query.bind(self.dialect().encode_timestamp(to));
+        }
+        query
+    }
+
+    async fn fetch_logs<'q>(
+        &self,
+        query: Query<'q, Any, AnyArguments<'q>>,
+        context: &str,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        let rows = query
+            .fetch_all(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to {}: {}", context, e),
+            })?;
+
+        rows.iter().map(Self::row_to_audit_log).collect()
+    }
+
+    async fn execute<'q>(
+        &self,
+        query: Query<'q, Any, AnyArguments<'q>>,
+        context: &str,
+    ) -> Result<usize, DomainError> {
+        let result = query
+            .execute(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to {}: {}", context, e),
+            })?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// Condition selecting the oldest `limit` logs matching `condition`
+    ///
+    /// The subquery is wrapped in a derived table because MySQL does not
+    /// allow `LIMIT` in an `IN` subquery on the table being modified.
+    fn oldest_batch(condition: &str) -> String {
+        format!(
+            "id IN (SELECT id FROM (SELECT id FROM auth_audit_log WHERE {} ORDER BY created_at LIMIT ?) AS batch)",
+            condition
+        )
+    }
+
+    /// Insert an audit log entry, on the pool or within a transaction
+    pub(crate) async fn insert_audit_log<'e, E>(
+        executor: E,
+        d: SqlDialect,
+        audit_log: &AuditLog,
+    ) -> Result<(), DomainError>
+    where
+        E: sqlx::Executor<'e, Database = Any>,
+    {
+        Self::insert_audit_log_with(executor, d, audit_log, false).await.map(|_| ())
+    }
+
+    /// Insert an audit log entry, skipping it if its ID is already stored
+    /// when `skip_existing` is set
+    ///
+    /// # Returns
+    /// * Number of rows inserted, 0 when the entry was skipped
+    async fn insert_audit_log_with<'e, E>(
+        executor: E,
+        d: SqlDialect,
+        audit_log: &AuditLog,
+        skip_existing: bool,
+    ) -> Result<u64, DomainError>
+    where
+        E: sqlx::Executor<'e, Database = Any>,
+    {
+        let ts = d.timestamp_param();
+        let mut query = format!(
+            "INSERT INTO auth_audit_log ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, {}, {ts}, ?, {ts}, ?)",
+            INSERT_COLUMNS,
+            d.json_param()
+        );
+        if skip_existing {
+            query = d.insert_ignore(&query, "id");
+        }
+
+        let event_data_json = audit_log
+            .event_data
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to serialize event_data: {}", e),
+            })?;
+
+        let result = sqlx::query(&d.sql(&query))
+            .bind(audit_log.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(audit_log.event_type.as_str())
+            .bind(audit_log.user_id.map(|id| id.to_string()))
+            .bind(&audit_log.phone_masked)
+            .bind(&audit_log.phone_hash)
+            .bind(&audit_log.ip_address)
+            .bind(&audit_log.user_agent)
+            .bind(&audit_log.device_info)
+            .bind(&audit_log.action)
+            .bind(audit_log.success)
+            .bind(&audit_log.error_message)
+            .bind(&audit_log.failure_reason)
+            .bind(audit_log.token_id.map(|id| id.to_string()))
+            .bind(&audit_log.rate_limit_type)
+            .bind(event_data_json)
+            .bind(d.encode_timestamp(audit_log.created_at))
+            .bind(audit_log.archived)
+            .bind(audit_log.archived_at.map(|at| d.encode_timestamp(at)))
+            .bind(&audit_log.trace_id)
+            .execute(executor)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to create audit log: {}", e),
+            })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for SqlAuditLogRepository {
+    async fn create(&self, audit_log: &AuditLog) -> Result<(), DomainError> {
+        Self::insert_audit_log(&mut *self.database.acquire().await?, self.dialect(), audit_log).await
+    }
+
+    /// Entries are written in one transaction; entries whose ID is already
+    /// stored are skipped, so a batch that may have been written before can
+    /// be written again
+    async fn create_batch(&self, audit_logs: &[AuditLog]) -> Result<usize, DomainError> {
+        let mut tx = self.database.begin().await?;
+
+        let mut created = 0;
+        for audit_log in audit_logs {
+            created += Self::insert_audit_log_with(&mut *tx, self.dialect(), audit_log, true).await?;
+        }
+
+        tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit audit logs: {}", e) })?;
+
+        Ok(created as usize)
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM auth_audit_log WHERE user_id = ? AND tenant_id = ? ORDER BY created_at DESC LIMIT ?",
+            self.columns()
+        );
+        let query = self.dialect().sql(&query);
+
+        self.fetch_logs(
+            sqlx::query(&query)
+                .bind(user_id.to_string())
+                .bind(current_tenant().to_string())
+                .bind(limit as i64),
+            "find audit logs by user",
+        )
+        .await
+    }
+
+    async fn find_by_phone_hash(
+        &self,
+        phone_hash: &str,
+        limit: usize,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM auth_audit_log WHERE phone_hash = ? AND tenant_id = ? ORDER BY created_at DESC LIMIT ?",
+            self.columns()
+        );
+        let query = self.dialect().sql(&query);
+
+        self.fetch_logs(
+            sqlx::query(&query)
+                .bind(phone_hash)
+                .bind(current_tenant().to_string())
+                .bind(limit as i64),
+            "find audit logs by phone hash",
+        )
+        .await
+    }
+
+    async fn count_failed_attempts(
+        &self,
+        action: &str,
+        phone_hash: Option<&str>,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let mut query = format!(
+            "SELECT COUNT(*) AS count FROM auth_audit_log \
+             WHERE action = ? AND tenant_id = ? AND NOT success AND created_at >= {}",
+            d.timestamp_param()
+        );
+        if phone_hash.is_some() {
+            query.push_str(" AND phone_hash = ?");
+        }
+        if ip_address.is_some() {
+            query.push_str(" AND ip_address = ?");
+        }
+
+        let query = d.sql(&query);
+        let mut query_builder = sqlx::query(&query)
+            .bind(action)
+            .bind(current_tenant().to_string())
+            .bind(d.encode_timestamp(since));
+        if let Some(phone) = phone_hash {
+            query_builder = query_builder.bind(phone);
+        }
+        if let Some(ip) = ip_address {
+            query_builder = query_builder.bind(ip);
+        }
+
+        let row = query_builder
+            .fetch_one(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to count failed attempts: {}", e),
+            })?;
+
+        let count: i64 = row::get(&row, "count")?;
+        Ok(count as usize)
+    }
+
+    async fn find_suspicious_activity(
+        &self,
+        ip_address: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM auth_audit_log
+            WHERE created_at >= {}
+            AND tenant_id = ?
+            {}
+            AND (
+                NOT success
+                OR event_type IN ('RATE_LIMIT_EXCEEDED', 'SUSPICIOUS_ACTIVITY', 'INVALID_TOKEN_USAGE')
+            )
+            ORDER BY created_at DESC
+            "#,
+            self.columns(),
+            d.timestamp_param(),
+            if ip_address.is_some() { "AND ip_address = ?" } else { "" }
+        );
+
+        let query = d.sql(&query);
+        let mut query_builder = sqlx::query(&query)
+            .bind(d.encode_timestamp(since))
+            .bind(current_tenant().to_string());
+        if let Some(ip) = ip_address {
+            query_builder = query_builder.bind(ip);
+        }
+
+        self.fetch_logs(query_builder, "find suspicious activity").await
+    }
+
+    async fn archive_old_logs(&self) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let ts = d.timestamp_param();
+        let query = format!(
+            "UPDATE auth_audit_log SET archived = ?, archived_at = {ts} WHERE created_at < {ts} AND NOT archived"
+        );
+        let query = d.sql(&query);
+
+        let now = Utc::now();
+        self.execute(
+            sqlx::query(&query)
+                .bind(true)
+                .bind(d.encode_timestamp(now))
+                .bind(d.encode_timestamp(now - Duration::days(ARCHIVE_AFTER_DAYS))),
+            "archive old logs",
+        )
+        .await
+    }
+
+    async fn delete_archived_logs(&self) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            "DELETE FROM auth_audit_log WHERE archived AND archived_at < {}",
+            d.timestamp_param()
+        );
+        let query = d.sql(&query);
+
+        self.execute(
+            sqlx::query(&query)
+                .bind(d.encode_timestamp(Utc::now() - Duration::days(DELETE_ARCHIVED_AFTER_DAYS))),
+            "delete archived logs",
+        )
+        .await
+    }
+
+    async fn archive_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let ts = d.timestamp_param();
+        let query = format!(
+            "UPDATE auth_audit_log SET archived = ?, archived_at = {ts} WHERE {}",
+            Self::oldest_batch(&format!("created_at < {ts} AND NOT archived"))
+        );
+        let query = d.sql(&query);
+
+        self.execute(
+            sqlx::query(&query)
+                .bind(true)
+                .bind(d.encode_timestamp(Utc::now()))
+                .bind(d.encode_timestamp(before))
+                .bind(limit as i64),
+            "archive logs",
+        )
+        .await
+    }
+
+    async fn delete_logs_before(&self, before: DateTime<Utc>, limit: usize) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            "DELETE FROM auth_audit_log WHERE {}",
+            Self::oldest_batch(&format!("created_at < {}", d.timestamp_param()))
+        );
+        let query = d.sql(&query);
+
+        self.execute(
+            sqlx::query(&query)
+                .bind(d.encode_timestamp(before))
+                .bind(limit as i64),
+            "delete logs",
+        )
+        .await
+    }
+
+    async fn find_by_event_types(
+        &self,
+        event_types: Vec<AuditEventType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> Result<Vec<AuditLog>, DomainError> {
+        let d = self.dialect();
+        let placeholders = vec!["?"; event_types.len()].join(", ");
+
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM auth_audit_log
+            WHERE event_type IN ({})
+            AND tenant_id = ?
+            AND created_at >= {ts}
+            AND created_at <= {ts}
+            ORDER BY created_at DESC
+            {}
+            "#,
+            self.columns(),
+            placeholders,
+            if let Some(l) = limit { format!("LIMIT {}", l) } else { String::new() },
+            ts = d.timestamp_param(),
+        );
+
+        let query = d.sql(&query);
+        let mut query_builder = sqlx::query(&query);
+        for event_type in &event_types {
+            query_builder = query_builder.bind(event_type.as_str());
+        }
+        query_builder = query_builder
+            .bind(current_tenant().to_string())
+            .bind(d.encode_timestamp(from))
+            .bind(d.encode_timestamp(to));
+
+        self.fetch_logs(query_builder, "find audit logs by event types").await
+    }
+
+    async fn query(
+        &self,
+        filter: &AuditLogFilter,
+        pagination: &Pagination,
+    ) -> Result<(Vec<AuditLog>, u64), DomainError> {
+        let d = self.dialect();
+        let mut conditions = vec!["tenant_id = ?".to_string()];
+        if filter.user_id.is_some() {
+            conditions.push("user_id = ?".to_string());
+        }
+        if filter.phone_hash.is_some() {
+            conditions.push("phone_hash = ?".to_string());
+        }
+        if !filter.event_types.is_empty() {
+            let placeholders = vec!["?"; filter.event_types.len()].join(", ");
+            conditions.push(format!("event_type IN ({})", placeholders));
+        }
+        if filter.ip_address.is_some() {
+            conditions.push("ip_address = ?".to_string());
+        }
+        if filter.date_range.from.is_some() {
+            conditions.push(format!("created_at >= {}", d.timestamp_param()));
+        }
+        if filter.date_range.to.is_some() {
+            conditions.push(format!("created_at <= {}", d.timestamp_param()));
+        }
+
+        let where_clause = format!("WHERE {}", conditions.join(" AND "));
+
+        let count_query = d.sql(&format!("SELECT COUNT(*) AS total FROM auth_audit_log {}", where_clause)).into_owned();
+        let count_row = self
+            .bind_filter(sqlx::query(&count_query), filter)
+            .fetch_one(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal {
+                message: format!("Failed to count audit logs: {}", e),
+            })?;
+        let total: i64 = row::get(&count_row, "total")?;
+
+        let query = d.sql(&format!(
+            "SELECT {} FROM auth_audit_log {} ORDER BY created_at DESC LIMIT ? OFFSET ?",
+            self.columns(),
+            where_clause
+        ))
+        .into_owned();
+
+        let logs = self
+            .fetch_logs(
+                self.bind_filter(sqlx::query(&query), filter)
+                    .bind(pagination.limit_i64())
+                    .bind(pagination.offset_i64()),
+                "query audit logs",
+            )
+            .await?;
+
+        Ok((logs, total as u64))
+    }
+}
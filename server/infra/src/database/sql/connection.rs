@@ -0,0 +1,108 @@
+//! Connection pool shared by the generic repositories
+//!
+//! On MySQL the pool belongs to a [`DatabasePool`], and connections and
+//! transactions are taken through its retries and circuit breaker like
+//! those of the MySQL repositories.
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::pool::PoolConnection;
+use sqlx::{Any, AnyPool, Transaction};
+
+use re_core::errors::DomainError;
+
+use super::dialect::SqlDialect;
+use crate::database::DatabasePool;
+use crate::InfrastructureError;
+
+/// `AnyPool` together with the dialect of the database behind it
+#[derive(Clone)]
+pub struct SqlDatabase {
+    /// SQLx pool over whichever driver the URL selected
+    pool: AnyPool,
+    /// Dialect queries are adapted to
+    dialect: SqlDialect,
+    /// Pool whose retries and circuit breaker guard this one, if any
+    resilience: Option<DatabasePool>,
+}
+
+impl SqlDatabase {
+    /// Connect to the database a URL points at
+    ///
+    /// # Arguments
+    /// * `url` - Connection URL; its scheme selects the driver and dialect
+    /// * `max_connections` - Maximum number of pooled connections
+    ///
+    /// # Returns
+    /// * `Result<Self, InfrastructureError>` - Connected database, or error
+    pub async fn connect(url: &str, max_connections: u32) -> Result<Self, InfrastructureError> {
+        let dialect = SqlDialect::from_url(url).ok_or_else(|| {
+            InfrastructureError::Config(format!(
+                "Unsupported database URL scheme: {}",
+                url.split(':').next().unwrap_or_default()
+            ))
+        })?;
+
+        install_default_drivers();
+        let pool = AnyPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(url)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to connect to {} database: {}", dialect.as_str(), e);
+                InfrastructureError::Database(e)
+            })?;
+
+        Ok(Self::from_pool(pool, dialect))
+    }
+
+    /// Wrap a pool that is already connected
+    pub fn from_pool(pool: AnyPool, dialect: SqlDialect) -> Self {
+        Self { pool, dialect, resilience: None }
+    }
+
+    /// Take connections through the retries and circuit breaker of `pool`
+    pub fn with_resilience(mut self, pool: DatabasePool) -> Self {
+        self.resilience = Some(pool);
+        self
+    }
+
+    /// Get a reference to the underlying SQLx pool
+    pub fn get_pool(&self) -> &AnyPool {
+        &self.pool
+    }
+
+    /// Dialect of the database
+    pub fn dialect(&self) -> SqlDialect {
+        self.dialect
+    }
+
+    /// Acquire a connection, through the retries and circuit breaker if any
+    pub(crate) async fn acquire(&self) -> Result<PoolConnection<Any>, DomainError> {
+        let connection = match &self.resilience {
+            Some(resilience) => resilience.acquire_with(|| self.pool.acquire()).await,
+            None => self.pool.acquire().await.map_err(InfrastructureError::Database),
+        };
+
+        connection.map_err(|e| DomainError::Internal {
+            message: format!("Failed to acquire database connection: {}", e),
+        })
+    }
+
+    /// Begin a transaction, through the retries and circuit breaker if any
+    pub(crate) async fn begin(&self) -> Result<Transaction<'static, Any>, DomainError> {
+        let tx = match &self.resilience {
+            Some(resilience) => resilience.acquire_with(|| self.pool.begin()).await,
+            None => self.pool.begin().await.map_err(InfrastructureError::Database),
+        };
+
+        tx.map_err(|e| DomainError::Internal {
+            message: format!("Failed to begin transaction: {}", e),
+        })
+    }
+
+    /// Close all connections in the pool
+    pub async fn close(&self) {
+        tracing::info!("Closing {} database", self.dialect.as_str());
+        self.pool.close().await;
+    }
+}
@@ -0,0 +1,195 @@
+//! SQL dialect differences handled by the generic repositories
+//!
+//! The `Any` driver in SQLx only carries integers, floats, text, blobs and
+//! booleans between the database and Rust. Timestamps are therefore selected
+//! as text and bound as text, booleans and unsigned integers are selected as
+//! signed integers, and JSON and MySQL `TEXT` columns are selected as text. Queries are written with `?` placeholders and rewritten
+//! to `$1, $2, ...` for PostgreSQL.
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+
+/// Format MySQL and PostgreSQL accept for timestamp literals
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+/// Database flavour a generic repository talks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl SqlDialect {
+    /// Detect the dialect from a connection URL
+    pub fn from_url(url: &str) -> Option<Self> {
+        let scheme = url.split(':').next()?.to_ascii_lowercase();
+        match scheme.as_str() {
+            "mysql" | "mariadb" => Some(Self::MySql),
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// Convert to string representation for logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MySql => "mysql",
+            Self::Postgres => "postgres",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    /// Rewrite `?` placeholders for the dialect
+    ///
+    /// Question marks inside string literals are left alone.
+    pub fn sql<'a>(&self, query: &'a str) -> Cow<'a, str> {
+        if *self != Self::Postgres || !query.contains('?') {
+            return Cow::Borrowed(query);
+        }
+
+        let mut rewritten = String::with_capacity(query.len() + 16);
+        let mut index = 0;
+        let mut in_literal = false;
+        for c in query.chars() {
+            match c {
+                '\'' => {
+                    in_literal = !in_literal;
+                    rewritten.push(c);
+                }
+                '?' if !in_literal => {
+                    index += 1;
+                    rewritten.push('$');
+                    rewritten.push_str(&index.to_string());
+                }
+                _ => rewritten.push(c),
+            }
+        }
+        Cow::Owned(rewritten)
+    }
+
+    /// Select expression returning a timestamp column as text
+    pub fn timestamp(&self, column: &str) -> String {
+        match self {
+            Self::MySql => format!("DATE_FORMAT({0}, '%Y-%m-%d %H:%i:%s.%f') AS {0}", column),
+            Self::Postgres => format!(
+                "to_char({0} AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS.US') AS {0}",
+                column
+            ),
+            Self::Sqlite => column.to_string(),
+        }
+    }
+
+    /// Select expression returning a boolean column as an integer
+    pub fn boolean(&self, column: &str) -> String {
+        match self {
+            Self::MySql => format!("CAST({0} AS SIGNED) AS {0}", column),
+            Self::Postgres => format!("CAST({0} AS INTEGER) AS {0}", column),
+            Self::Sqlite => column.to_string(),
+        }
+    }
+
+    /// Select expression returning an integer column as a signed integer
+    ///
+    /// The `Any` driver rejects MySQL's unsigned and `TINYINT` columns.
+    pub fn integer(&self, column: &str) -> String {
+        match self {
+            Self::MySql => format!("CAST({0} AS SIGNED) AS {0}", column),
+            Self::Postgres | Self::Sqlite => column.to_string(),
+        }
+    }
+
+    /// Select expression returning a `TEXT` column as a string
+    ///
+    /// MySQL sends `TEXT` columns as blobs, which the `Any` driver does not
+    /// decode into strings.
+    pub fn text(&self, column: &str) -> String {
+        match self {
+            Self::MySql => format!("CAST({0} AS CHAR) AS {0}", column),
+            Self::Postgres | Self::Sqlite => column.to_string(),
+        }
+    }
+
+    /// Select expression returning a JSON column as text
+    pub fn json(&self, column: &str) -> String {
+        match self {
+            Self::MySql => format!("CAST({0} AS CHAR) AS {0}", column),
+            Self::Postgres => format!("CAST({0} AS TEXT) AS {0}", column),
+            Self::Sqlite => column.to_string(),
+        }
+    }
+
+    /// Placeholder for a timestamp bound with [`SqlDialect::encode_timestamp`]
+    pub fn timestamp_param(&self) -> &'static str {
+        match self {
+            Self::Postgres => "CAST(? AS TIMESTAMPTZ)",
+            Self::MySql | Self::Sqlite => "?",
+        }
+    }
+
+    /// Placeholder for JSON bound as text
+    pub fn json_param(&self) -> &'static str {
+        match self {
+            Self::Postgres => "CAST(? AS JSONB)",
+            Self::MySql | Self::Sqlite => "?",
+        }
+    }
+
+    /// Turn `INSERT INTO ...` into an insert that skips rows conflicting on
+    /// `conflict_column`, so the affected row count tells whether it was new
+    pub fn insert_ignore(&self, insert: &str, conflict_column: &str) -> String {
+        match self {
+            Self::MySql => insert.trim_start().replacen("INSERT INTO", "INSERT IGNORE INTO", 1),
+            Self::Postgres | Self::Sqlite => {
+                format!("{} ON CONFLICT ({}) DO NOTHING", insert.trim_end(), conflict_column)
+            }
+        }
+    }
+
+    /// Clause making an insert overwrite `update_columns` of the row
+    /// conflicting on `conflict_column`
+    pub fn upsert(&self, conflict_column: &str, update_columns: &[&str]) -> String {
+        match self {
+            Self::MySql => format!(
+                "ON DUPLICATE KEY UPDATE {}",
+                update_columns
+                    .iter()
+                    .map(|c| format!("{0} = VALUES({0})", c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Postgres | Self::Sqlite => format!(
+                "ON CONFLICT ({}) DO UPDATE SET {}",
+                conflict_column,
+                update_columns
+                    .iter()
+                    .map(|c| format!("{0} = excluded.{0}", c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Encode a timestamp for binding
+    ///
+    /// SQLite stores timestamps as text and compares them as strings, so the
+    /// format matches the one SQLx's SQLite driver writes.
+    pub fn encode_timestamp(&self, at: DateTime<Utc>) -> String {
+        match self {
+            Self::Sqlite => at.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+            Self::MySql | Self::Postgres => at.format(TIMESTAMP_FORMAT).to_string(),
+        }
+    }
+
+    /// Decode a timestamp selected with [`SqlDialect::timestamp`]
+    pub fn decode_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+        if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+            return Ok(at.with_timezone(&Utc));
+        }
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+            .map(|at| at.and_utc())
+            .map_err(|e| format!("Invalid timestamp {}: {}", value, e))
+    }
+}
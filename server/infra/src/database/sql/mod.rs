@@ -0,0 +1,36 @@
+//! Database-agnostic repository implementations over `sqlx::AnyPool`
+//!
+//! One implementation of a repository serves every database SQLx's `Any`
+//! driver can reach, instead of a copy per backend. Enabled with the
+//! `generic-sql` feature, which the `sqlite` feature turns on.
+//!
+//! - `SqlDatabase`: `AnyPool` paired with the `SqlDialect` of its database
+//! - `SqlDialect`: placeholder style, type casts and upserts per database
+//! - `SqlUserRepository`, `SqlTokenRepository`, `SqlAuditLogRepository`
+//! - `SqlUnitOfWork`: transactions spanning the three repositories above
+//!
+//! These are the only implementations of the user, token and audit log
+//! repositories. On MySQL they run on the `AnyPool` of a
+//! [`DatabasePool`](crate::database::DatabasePool), obtained with
+//! `DatabasePool::sql_database`, and share its retries and circuit breaker.
+//! The other MySQL repositories in [`crate::database::mysql`] stay on the
+//! typed pool: the `Any` driver cannot decode MySQL's `DATETIME`, `DECIMAL`
+//! or `JSON` columns without per-query casts.
+//!
+//! Every query is scoped to the tenant of the request being served.
+
+pub mod connection;
+pub mod dialect;
+mod row;
+pub mod user_repository_impl;
+pub mod token_repository_impl;
+pub mod audit_repository_impl;
+pub mod unit_of_work_impl;
+
+// Re-export the generic implementations
+pub use connection::SqlDatabase;
+pub use dialect::SqlDialect;
+pub use user_repository_impl::SqlUserRepository;
+pub use token_repository_impl::SqlTokenRepository;
+pub use audit_repository_impl::SqlAuditLogRepository;
+pub use unit_of_work_impl::SqlUnitOfWork;
@@ -0,0 +1,84 @@
+//! Column accessors for rows fetched through the `Any` driver
+//!
+//! Columns are expected to be selected with the expressions of
+//! [`SqlDialect`](super::dialect::SqlDialect), so timestamps arrive as text
+//! and booleans as integers whatever the database.
+//!
+//! Nullable columns go through [`get_optional`]: the `Any` driver of SQLx
+//! 0.7 never reports a value as null, so decoding `Option<T>` from a NULL
+//! fails instead of returning `None`.
+
+use chrono::{DateTime, Utc};
+use sqlx::any::AnyRow;
+use sqlx::{Any, Decode, Row, Type, TypeInfo, ValueRef};
+use uuid::Uuid;
+
+use re_core::errors::DomainError;
+
+use super::dialect::SqlDialect;
+
+/// Get a column value
+pub(crate) fn get<'r, T>(row: &'r AnyRow, column: &str) -> Result<T, DomainError>
+where
+    T: Decode<'r, Any> + Type<Any>,
+{
+    row.try_get(column).map_err(|e| DomainError::Internal {
+        message: format!("Failed to get {}: {}", column, e),
+    })
+}
+
+/// Get a nullable column value
+pub(crate) fn get_optional<'r, T>(row: &'r AnyRow, column: &str) -> Result<Option<T>, DomainError>
+where
+    T: Decode<'r, Any> + Type<Any>,
+{
+    let value = row.try_get_raw(column).map_err(|e| DomainError::Internal {
+        message: format!("Failed to get {}: {}", column, e),
+    })?;
+    if value.type_info().name() == "NULL" {
+        return Ok(None);
+    }
+    get(row, column).map(Some)
+}
+
+/// Get a boolean column selected with `SqlDialect::boolean`
+pub(crate) fn get_bool(row: &AnyRow, column: &str) -> Result<bool, DomainError> {
+    get::<i64>(row, column).map(|value| value != 0)
+}
+
+/// Get a UUID column stored as text
+pub(crate) fn get_uuid(row: &AnyRow, column: &str) -> Result<Uuid, DomainError> {
+    let value: String = get(row, column)?;
+    Uuid::parse_str(&value).map_err(|e| DomainError::Internal {
+        message: format!("Invalid {} UUID: {}", column, e),
+    })
+}
+
+/// Get a nullable UUID column stored as text
+pub(crate) fn get_optional_uuid(row: &AnyRow, column: &str) -> Result<Option<Uuid>, DomainError> {
+    let value: Option<String> = get_optional(row, column)?;
+    value
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .map_err(|e| DomainError::Internal {
+            message: format!("Invalid {} UUID: {}", column, e),
+        })
+}
+
+/// Get a timestamp column selected with `SqlDialect::timestamp`
+pub(crate) fn get_timestamp(row: &AnyRow, column: &str) -> Result<DateTime<Utc>, DomainError> {
+    let value: String = get(row, column)?;
+    SqlDialect::decode_timestamp(&value).map_err(|message| DomainError::Internal { message })
+}
+
+/// Get a nullable timestamp column selected with `SqlDialect::timestamp`
+pub(crate) fn get_optional_timestamp(
+    row: &AnyRow,
+    column: &str,
+) -> Result<Option<DateTime<Utc>>, DomainError> {
+    let value: Option<String> = get_optional(row, column)?;
+    value
+        .map(|value| SqlDialect::decode_timestamp(&value))
+        .transpose()
+        .map_err(|message| DomainError::Internal { message })
+}
@@ -0,0 +1,484 @@
+//! Database-agnostic implementation of the TokenRepository trait.
+//!
+//! Tokens are issued by, and only refresh sessions of, the tenant of the
+//! current request. Cleanup, archiving and the JTI blacklist span all tenants.
+//!
+//! Writes losing a lock conflict with a concurrent transaction are retried.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::any::AnyRow;
+use sqlx::Any;
+use uuid::Uuid;
+
+use re_core::domain::entities::token::RefreshToken;
+use re_core::errors::DomainError;
+use re_core::repositories::TokenRepository;
+use re_core::services::tenant::current_tenant;
+
+use crate::database::deadlock::{retry_on_deadlock, write_error};
+
+use super::connection::SqlDatabase;
+use super::dialect::SqlDialect;
+use super::row;
+
+/// Days revoked tokens are kept before they are deleted
+const REVOKED_TOKEN_RETENTION_DAYS: i64 = 30;
+
+/// Rows written per statement by batch operations, keeping statements well
+/// below the placeholder limit and short enough not to hold locks for long
+const BATCH_SIZE: usize = 500;
+
+/// Columns copied from `refresh_tokens` to `refresh_tokens_archive`
+const ARCHIVED_COLUMNS: &str = "id, tenant_id, user_id, token_hash, created_at, expires_at, last_used_at, \
+    is_revoked, revoked_at, revoke_reason, device_id, device_name, device_type, device_fingerprint, \
+    ip_address, user_agent, token_family, previous_token_id, rotated_from_token_id, rotated_to_token_id, \
+    use_count, last_rotation_at, rotation_count";
+
+/// Generic SQL implementation of TokenRepository
+pub struct SqlTokenRepository {
+    /// Database connection pool
+    database: SqlDatabase,
+}
+
+impl SqlTokenRepository {
+    /// Create a new generic SQL token repository
+    ///
+    /// # Arguments
+    /// * `database` - Pool and dialect of the database
+    ///
+    /// # Returns
+    /// A new instance of SqlTokenRepository
+    pub fn new(database: SqlDatabase) -> Self {
+        Self { database }
+    }
+
+    fn dialect(&self) -> SqlDialect {
+        self.database.dialect()
+    }
+
+    /// Columns selected for a refresh token
+    fn columns(&self) -> String {
+        let d = self.dialect();
+        format!(
+            "id, user_id, token_hash, {}, {}, {}, token_family, device_fingerprint, previous_token_id",
+            d.timestamp("created_at"),
+            d.timestamp("expires_at"),
+            d.boolean("is_revoked"),
+        )
+    }
+
+    /// Convert database row to RefreshToken entity
+    fn row_to_token(row: &AnyRow) -> Result<RefreshToken, DomainError> {
+        Ok(RefreshToken {
+            id: row::get_uuid(row, "id")?,
+            user_id: row::get_uuid(row, "user_id")?,
+            token_hash: row::get(row, "token_hash")?,
+            created_at: row::get_timestamp(row, "created_at")?,
+            expires_at: row::get_timestamp(row, "expires_at")?,
+            is_revoked: row::get_bool(row, "is_revoked")?,
+            token_family: row::get_optional(row, "token_family")?,
+            device_fingerprint: row::get_optional(row, "device_fingerprint")?,
+            previous_token_id: row::get_optional_uuid(row, "previous_token_id")?,
+        })
+    }
+
+    /// Map the error of a token insert, reporting a duplicate token hash as
+    /// a validation error
+    fn insert_error(context: &str, error: sqlx::Error) -> DomainError {
+        if let sqlx::Error::Database(db_err) = &error {
+            if db_err.is_unique_violation() {
+                return DomainError::Validation { message: "Token already exists".to_string() };
+            }
+        }
+        write_error(context, error)
+    }
+
+    /// Insert a refresh token, on the pool or within a transaction
+    pub(crate) async fn insert_refresh_token<'e, E>(
+        executor: E,
+        d: SqlDialect,
+        token: &RefreshToken,
+    ) -> Result<(), DomainError>
+    where
+        E: sqlx::Executor<'e, Database = Any>,
+    {
+        let ts = d.timestamp_param();
+        let query = format!(
+            r#"
+            INSERT INTO refresh_tokens (
+                id, tenant_id, user_id, token_hash, created_at, expires_at, is_revoked,
+                token_family, device_fingerprint, previous_token_id
+            ) VALUES (?, ?, ?, ?, {ts}, {ts}, ?, ?, ?, ?)
+            "#
+        );
+
+        sqlx::query(&d.sql(&query))
+            .bind(token.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(token.user_id.to_string())
+            .bind(&token.token_hash)
+            .bind(d.encode_timestamp(token.created_at))
+            .bind(d.encode_timestamp(token.expires_at))
+            .bind(token.is_revoked)
+            .bind(&token.token_family)
+            .bind(&token.device_fingerprint)
+            .bind(token.previous_token_id.map(|id| id.to_string()))
+            .execute(executor)
+            .await
+            .map_err(|e| Self::insert_error("Failed to save refresh token", e))?;
+
+        Ok(())
+    }
+
+    /// Tokens removed by cleanup: expired, or revoked before the retention
+    /// period; binds the current time and the end of the retention period
+    fn expired_condition(&self) -> String {
+        let ts = self.dialect().timestamp_param();
+        format!("expires_at < {ts} OR (is_revoked AND created_at < {ts})")
+    }
+
+    /// Revoke the active tokens of the current tenant matching `condition`,
+    /// which binds one value
+    async fn revoke_where(&self, condition: &str, bind: &str, operation: &str) -> Result<u64, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            "UPDATE refresh_tokens SET is_revoked = ? WHERE {} AND tenant_id = ? AND NOT is_revoked",
+            condition
+        );
+        let context = format!("Failed to {}", operation.to_lowercase());
+
+        let result = retry_on_deadlock(operation, || async {
+            sqlx::query(&d.sql(&query))
+                .bind(true)
+                .bind(bind)
+                .bind(current_tenant().to_string())
+                .execute(&mut *self.database.acquire().await?)
+                .await
+                .map_err(|e| write_error(&context, e))
+        })
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl TokenRepository for SqlTokenRepository {
+    async fn save_refresh_token(&self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        // A token hash already in use fails on the unique key
+        retry_on_deadlock("Save refresh token", || async {
+            Self::insert_refresh_token(&mut *self.database.acquire().await?, self.dialect(), &token).await
+        })
+        .await?;
+        Ok(token)
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM refresh_tokens WHERE token_hash = ? AND tenant_id = ? LIMIT 1",
+            self.columns()
+        );
+
+        let result = sqlx::query(&self.dialect().sql(&query))
+            .bind(token_hash)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find refresh token: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_token).transpose()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RefreshToken>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM refresh_tokens WHERE id = ? AND tenant_id = ? LIMIT 1",
+            self.columns()
+        );
+
+        let result = sqlx::query(&self.dialect().sql(&query))
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find token by id: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_token).transpose()
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<RefreshToken>, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM refresh_tokens
+            WHERE user_id = ?
+                AND tenant_id = ?
+                AND NOT is_revoked
+                AND expires_at > {}
+            ORDER BY created_at DESC
+            "#,
+            self.columns(),
+            d.timestamp_param()
+        );
+
+        let rows = sqlx::query(&d.sql(&query))
+            .bind(user_id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(d.encode_timestamp(Utc::now()))
+            .fetch_all(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find user tokens: {}", e) })?;
+
+        rows.iter().map(Self::row_to_token).collect()
+    }
+
+    async fn find_by_token_family(&self, token_family: &str) -> Result<Vec<RefreshToken>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM refresh_tokens WHERE token_family = ? AND tenant_id = ? ORDER BY created_at DESC",
+            self.columns()
+        );
+
+        let rows = sqlx::query(&self.dialect().sql(&query))
+            .bind(token_family)
+            .bind(current_tenant().to_string())
+            .fetch_all(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to find tokens by family: {}", e) })?;
+
+        rows.iter().map(Self::row_to_token).collect()
+    }
+
+    async fn revoke_token_family(&self, token_family: &str) -> Result<usize, DomainError> {
+        self.revoke_where("token_family = ?", token_family, "Revoke token family")
+            .await
+            .map(|revoked| revoked as usize)
+    }
+
+    async fn is_token_blacklisted(&self, token_jti: &str) -> Result<bool, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            "SELECT CASE WHEN EXISTS(SELECT 1 FROM token_blacklist WHERE jti = ? AND expires_at > {}) \
+             THEN 1 ELSE 0 END AS blacklisted",
+            d.timestamp_param()
+        );
+
+        let row = sqlx::query(&d.sql(&query))
+            .bind(token_jti)
+            .bind(d.encode_timestamp(Utc::now()))
+            .fetch_one(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to check blacklist: {}", e) })?;
+
+        row::get_bool(&row, "blacklisted")
+    }
+
+    async fn blacklist_token(&self, token_jti: &str, expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+        self.blacklist_tokens_batch(&[(token_jti.to_string(), expires_at)]).await
+    }
+
+    async fn revoke_token(&self, token_hash: &str) -> Result<bool, DomainError> {
+        self.revoke_where("token_hash = ?", token_hash, "Revoke token")
+            .await
+            .map(|revoked| revoked > 0)
+    }
+
+    async fn revoke_all_user_tokens(&self, user_id: Uuid) -> Result<usize, DomainError> {
+        self.revoke_where("user_id = ?", &user_id.to_string(), "Revoke user tokens")
+            .await
+            .map(|revoked| revoked as usize)
+    }
+
+    async fn save_refresh_tokens_batch(&self, tokens: Vec<RefreshToken>) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let ts = d.timestamp_param();
+        let tenant = current_tenant().to_string();
+        let (tokens, tenant) = (&tokens, &tenant);
+
+        retry_on_deadlock("Save refresh tokens", || async move {
+            let mut tx = self.database.begin().await?;
+
+            for chunk in tokens.chunks(BATCH_SIZE) {
+                let values = vec![format!("(?, ?, ?, ?, {ts}, {ts}, ?, ?, ?, ?)"); chunk.len()].join(", ");
+                let query = format!(
+                    r#"
+                    INSERT INTO refresh_tokens (
+                        id, tenant_id, user_id, token_hash, created_at, expires_at, is_revoked,
+                        token_family, device_fingerprint, previous_token_id
+                    ) VALUES {}
+                    "#,
+                    values
+                );
+
+                let query = d.sql(&query);
+                let mut statement = sqlx::query(&query);
+                for token in chunk {
+                    statement = statement
+                        .bind(token.id.to_string())
+                        .bind(tenant.as_str())
+                        .bind(token.user_id.to_string())
+                        .bind(&token.token_hash)
+                        .bind(d.encode_timestamp(token.created_at))
+                        .bind(d.encode_timestamp(token.expires_at))
+                        .bind(token.is_revoked)
+                        .bind(&token.token_family)
+                        .bind(&token.device_fingerprint)
+                        .bind(token.previous_token_id.map(|id| id.to_string()));
+                }
+
+                statement
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| Self::insert_error("Failed to save refresh tokens", e))?;
+            }
+
+            tx.commit().await
+                .map_err(|e| write_error("Failed to commit transaction", e))?;
+
+            Ok(tokens.len())
+        })
+        .await
+    }
+
+    async fn revoke_tokens_batch(&self, user_ids: &[Uuid]) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let tenant = current_tenant().to_string();
+        let mut revoked = 0;
+
+        // Each chunk commits on its own; a failure leaves earlier users signed out
+        for chunk in user_ids.chunks(BATCH_SIZE) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let query = format!(
+                "UPDATE refresh_tokens SET is_revoked = ? \
+                 WHERE user_id IN ({}) AND tenant_id = ? AND NOT is_revoked",
+                placeholders
+            );
+
+            let (query, tenant) = (&d.sql(&query), &tenant);
+            let result = retry_on_deadlock("Revoke tokens of users", || async move {
+                let mut statement = sqlx::query(query).bind(true);
+                for user_id in chunk {
+                    statement = statement.bind(user_id.to_string());
+                }
+                statement
+                    .bind(tenant.as_str())
+                    .execute(&mut *self.database.acquire().await?)
+                    .await
+                    .map_err(|e| write_error("Failed to revoke user tokens", e))
+            })
+            .await?;
+            revoked += result.rows_affected() as usize;
+        }
+
+        Ok(revoked)
+    }
+
+    async fn blacklist_tokens_batch(&self, tokens: &[(String, DateTime<Utc>)]) -> Result<(), DomainError> {
+        let d = self.dialect();
+        let ts = d.timestamp_param();
+        let now = d.encode_timestamp(Utc::now());
+
+        for chunk in tokens.chunks(BATCH_SIZE) {
+            let values = vec![format!("(?, {ts}, {ts})"); chunk.len()].join(", ");
+            let query = format!(
+                "INSERT INTO token_blacklist (jti, expires_at, created_at) VALUES {} {}",
+                values,
+                d.upsert("jti", &["expires_at"])
+            );
+
+            let query = d.sql(&query);
+            let mut statement = sqlx::query(&query);
+            for (token_jti, expires_at) in chunk {
+                statement = statement
+                    .bind(token_jti)
+                    .bind(d.encode_timestamp(*expires_at))
+                    .bind(&now);
+            }
+
+            statement
+                .execute(&mut *self.database.acquire().await?)
+                .await
+                .map_err(|e| DomainError::Internal { message: format!("Failed to blacklist tokens: {}", e) })?;
+        }
+
+        Ok(())
+    }
+
+    async fn delete_expired_tokens(&self) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let query = format!("DELETE FROM refresh_tokens WHERE {}", self.expired_condition());
+
+        let now = Utc::now();
+        let revoked_before = d.encode_timestamp(now - Duration::days(REVOKED_TOKEN_RETENTION_DAYS));
+        let now = d.encode_timestamp(now);
+        let result = retry_on_deadlock("Delete expired tokens", || async {
+            sqlx::query(&d.sql(&query))
+                .bind(&now)
+                .bind(&revoked_before)
+                .execute(&mut *self.database.acquire().await?)
+                .await
+                .map_err(|e| write_error("Failed to delete expired tokens", e))
+        })
+        .await?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn archive_expired_tokens(&self) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let archive = format!(
+            r#"
+            INSERT INTO refresh_tokens_archive ({columns}, archived_at)
+            SELECT {columns}, {ts}
+            FROM refresh_tokens
+            WHERE {condition}
+            "#,
+            columns = ARCHIVED_COLUMNS,
+            ts = d.timestamp_param(),
+            condition = self.expired_condition(),
+        );
+        let delete = format!("DELETE FROM refresh_tokens WHERE {}", self.expired_condition());
+        let (archive, delete) = (&d.sql(&archive), &d.sql(&delete));
+
+        retry_on_deadlock("Archive expired tokens", || async move {
+            let mut tx = self.database.begin().await?;
+
+            let now = Utc::now();
+            let revoked_before = d.encode_timestamp(now - Duration::days(REVOKED_TOKEN_RETENTION_DAYS));
+            let now = d.encode_timestamp(now);
+            sqlx::query(archive)
+                .bind(&now)
+                .bind(&now)
+                .bind(&revoked_before)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| write_error("Failed to archive expired tokens", e))?;
+
+            let result = sqlx::query(delete)
+                .bind(&now)
+                .bind(&revoked_before)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| write_error("Failed to delete expired tokens", e))?;
+
+            tx.commit().await
+                .map_err(|e| write_error("Failed to commit transaction", e))?;
+
+            Ok(result.rows_affected() as usize)
+        })
+        .await
+    }
+
+    async fn cleanup_blacklist(&self) -> Result<usize, DomainError> {
+        let d = self.dialect();
+        let query = format!("DELETE FROM token_blacklist WHERE expires_at < {}", d.timestamp_param());
+
+        let result = sqlx::query(&d.sql(&query))
+            .bind(d.encode_timestamp(Utc::now()))
+            .execute(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to cleanup blacklist: {}", e) })?;
+
+        Ok(result.rows_affected() as usize)
+    }
+}
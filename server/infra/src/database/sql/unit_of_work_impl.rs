@@ -0,0 +1,83 @@
+//! Database-agnostic implementation of the UnitOfWork trait.
+//!
+//! Transactions write through the same statements as the user, token and
+//! audit log repositories, on a single connection held until commit.
+
+use async_trait::async_trait;
+use sqlx::{Any, Transaction};
+
+use re_core::domain::entities::audit::AuditLog;
+use re_core::domain::entities::token::RefreshToken;
+use re_core::domain::entities::user::User;
+use re_core::errors::DomainError;
+use re_core::repositories::{UnitOfWork, UnitOfWorkTransaction};
+
+use super::connection::SqlDatabase;
+use super::dialect::SqlDialect;
+use super::{SqlAuditLogRepository, SqlTokenRepository, SqlUserRepository};
+
+/// Generic SQL implementation of UnitOfWork
+pub struct SqlUnitOfWork {
+    /// Database connection pool
+    database: SqlDatabase,
+}
+
+impl SqlUnitOfWork {
+    /// Create a new generic SQL unit of work
+    ///
+    /// # Arguments
+    /// * `database` - Pool and dialect of the database
+    ///
+    /// # Returns
+    /// A new instance of SqlUnitOfWork
+    pub fn new(database: SqlDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for SqlUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        let tx = self.database.begin().await?;
+
+        Ok(Box::new(SqlUnitOfWorkTransaction { tx, dialect: self.database.dialect() }))
+    }
+}
+
+/// An open transaction, rolled back if dropped before commit
+pub struct SqlUnitOfWorkTransaction {
+    tx: Transaction<'static, Any>,
+    dialect: SqlDialect,
+}
+
+#[async_trait]
+impl UnitOfWorkTransaction for SqlUnitOfWorkTransaction {
+    async fn create_user(&mut self, user: User) -> Result<User, DomainError> {
+        // A concurrent registration of the phone fails on the unique key
+        SqlUserRepository::insert_user(&mut *self.tx, self.dialect, &user).await?;
+        Ok(user)
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User, DomainError> {
+        SqlUserRepository::update_user(&mut *self.tx, self.dialect, user).await
+    }
+
+    async fn save_refresh_token(&mut self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        SqlTokenRepository::insert_refresh_token(&mut *self.tx, self.dialect, &token).await?;
+        Ok(token)
+    }
+
+    async fn create_audit_log(&mut self, audit_log: &AuditLog) -> Result<(), DomainError> {
+        SqlAuditLogRepository::insert_audit_log(&mut *self.tx, self.dialect, audit_log).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        self.tx.commit().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to commit transaction: {}", e) })
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.tx.rollback().await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to roll back transaction: {}", e) })
+    }
+}
@@ -0,0 +1,442 @@
+//! Database-agnostic implementation of the UserRepository trait.
+//!
+//! Deleting a user only sets `deleted_at`, so the account can be restored
+//! during the deletion grace period; every lookup except
+//! `find_including_deleted` skips deleted users.
+//!
+//! Every query is scoped to the tenant of the request being served, so
+//! users of other marketplaces are neither found nor modified.
+//!
+//! Writes losing a lock conflict with a concurrent transaction are retried.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures_util::stream::{BoxStream, StreamExt};
+use futures_util::TryFutureExt;
+use sqlx::any::AnyRow;
+use sqlx::Any;
+use uuid::Uuid;
+
+use re_core::domain::entities::user::{User, UserType};
+use re_core::errors::DomainError;
+use re_core::repositories::UserRepository;
+use re_core::services::tenant::current_tenant;
+
+use crate::database::deadlock::{retry_on_deadlock, write_error};
+
+use super::connection::SqlDatabase;
+use super::dialect::SqlDialect;
+use super::row;
+
+/// Generic SQL implementation of UserRepository
+pub struct SqlUserRepository {
+    /// Database connection pool
+    database: SqlDatabase,
+    /// Queries of `find_all_stream` without and with a user type, built
+    /// once since a stream borrows its query until dropped
+    stream_queries: [String; 2],
+}
+
+impl SqlUserRepository {
+    /// Create a new generic SQL user repository
+    ///
+    /// # Arguments
+    /// * `database` - Pool and dialect of the database
+    ///
+    /// # Returns
+    /// A new instance of SqlUserRepository
+    pub fn new(database: SqlDatabase) -> Self {
+        let d = database.dialect();
+        let stream_query = |condition: &str| {
+            d.sql(&format!(
+                "SELECT {} FROM users WHERE {} ORDER BY created_at, id",
+                Self::columns(d),
+                condition
+            ))
+            .into_owned()
+        };
+        let stream_queries = [
+            stream_query("tenant_id = ? AND deleted_at IS NULL"),
+            stream_query("user_type = ? AND tenant_id = ? AND deleted_at IS NULL"),
+        ];
+
+        Self { database, stream_queries }
+    }
+
+    fn dialect(&self) -> SqlDialect {
+        self.database.dialect()
+    }
+
+    fn user_type_str(user_type: UserType) -> &'static str {
+        match user_type {
+            UserType::Customer => "customer",
+            UserType::Worker => "worker",
+        }
+    }
+
+    /// Columns selected for a user
+    pub(crate) fn columns(d: SqlDialect) -> String {
+        format!(
+            "id, phone_hash, country_code, user_type, {}, {}, {}, last_login_country, \
+             {}, {}, {}, {}",
+            d.timestamp("created_at"),
+            d.timestamp("updated_at"),
+            d.timestamp("last_login_at"),
+            d.integer("phone_hash_version"),
+            d.boolean("is_verified"),
+            d.boolean("is_blocked"),
+            d.timestamp("deleted_at"),
+        )
+    }
+
+    /// Convert database row to User entity
+    ///
+    /// Expects the columns of [`SqlUserRepository::columns`].
+    pub(crate) fn row_to_user(row: &AnyRow) -> Result<User, DomainError> {
+        let user_type_str: Option<String> = row::get_optional(row, "user_type")?;
+        let user_type = user_type_str.map(|s| match s.as_str() {
+            "worker" => UserType::Worker,
+            _ => UserType::Customer,
+        });
+
+        let phone_hash_version: i64 = row::get(row, "phone_hash_version")?;
+
+        Ok(User {
+            id: row::get_uuid(row, "id")?,
+            phone_hash: row::get(row, "phone_hash")?,
+            country_code: row::get(row, "country_code")?,
+            user_type,
+            created_at: row::get_timestamp(row, "created_at")?,
+            updated_at: row::get_timestamp(row, "updated_at")?,
+            last_login_at: row::get_optional_timestamp(row, "last_login_at")?,
+            last_login_country: row::get_optional(row, "last_login_country")?,
+            phone_hash_version: u16::try_from(phone_hash_version).map_err(|e| DomainError::Internal {
+                message: format!("Invalid phone_hash_version: {}", e),
+            })?,
+            is_verified: row::get_bool(row, "is_verified")?,
+            is_blocked: row::get_bool(row, "is_blocked")?,
+            deleted_at: row::get_optional_timestamp(row, "deleted_at")?,
+        })
+    }
+
+    /// Insert a user, on the pool or within a transaction
+    pub(crate) async fn insert_user<'e, E>(
+        executor: E,
+        d: SqlDialect,
+        user: &User,
+    ) -> Result<(), DomainError>
+    where
+        E: sqlx::Executor<'e, Database = Any>,
+    {
+        let ts = d.timestamp_param();
+        let query = format!(
+            r#"
+            INSERT INTO users (
+                id, tenant_id, phone_hash, country_code, user_type,
+                created_at, updated_at, last_login_at, last_login_country, phone_hash_version,
+                is_verified, is_blocked
+            ) VALUES (?, ?, ?, ?, ?, {ts}, {ts}, {ts}, ?, ?, ?, ?)
+            "#
+        );
+
+        sqlx::query(&d.sql(&query))
+            .bind(user.id.to_string())
+            .bind(current_tenant().to_string())
+            .bind(&user.phone_hash)
+            .bind(&user.country_code)
+            .bind(user.user_type.map(Self::user_type_str))
+            .bind(d.encode_timestamp(user.created_at))
+            .bind(d.encode_timestamp(user.updated_at))
+            .bind(user.last_login_at.map(|at| d.encode_timestamp(at)))
+            .bind(&user.last_login_country)
+            .bind(i32::from(user.phone_hash_version))
+            .bind(user.is_verified)
+            .bind(user.is_blocked)
+            .execute(executor)
+            .await
+            .map_err(|e| write_error("Failed to create user", e))?;
+
+        Ok(())
+    }
+
+    /// Update a user, on the pool or within a transaction
+    pub(crate) async fn update_user<'e, E>(
+        executor: E,
+        d: SqlDialect,
+        user: User,
+    ) -> Result<User, DomainError>
+    where
+        E: sqlx::Executor<'e, Database = Any>,
+    {
+        let ts = d.timestamp_param();
+        let query = format!(
+            r#"
+            UPDATE users SET
+                phone_hash = ?,
+                country_code = ?,
+                user_type = ?,
+                updated_at = {ts},
+                last_login_at = {ts},
+                last_login_country = ?,
+                phone_hash_version = ?,
+                is_verified = ?,
+                is_blocked = ?
+            WHERE id = ? AND tenant_id = ? AND deleted_at IS NULL
+            "#
+        );
+
+        let now = Utc::now();
+        let result = sqlx::query(&d.sql(&query))
+            .bind(&user.phone_hash)
+            .bind(&user.country_code)
+            .bind(user.user_type.map(Self::user_type_str))
+            .bind(d.encode_timestamp(now))
+            .bind(user.last_login_at.map(|at| d.encode_timestamp(at)))
+            .bind(&user.last_login_country)
+            .bind(i32::from(user.phone_hash_version))
+            .bind(user.is_verified)
+            .bind(user.is_blocked)
+            .bind(user.id.to_string())
+            .bind(current_tenant().to_string())
+            .execute(executor)
+            .await
+            .map_err(|e| write_error("Failed to update user", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound { resource: "User".to_string() });
+        }
+
+        let mut updated_user = user;
+        updated_user.updated_at = now;
+        Ok(updated_user)
+    }
+
+    async fn fetch_user(&self, condition: &str, id: Uuid) -> Result<Option<User>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM users WHERE {} AND tenant_id = ? LIMIT 1",
+            Self::columns(self.dialect()),
+            condition
+        );
+
+        let result = sqlx::query(&self.dialect().sql(&query))
+            .bind(id.to_string())
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Database query failed: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_user).transpose()
+    }
+}
+
+#[async_trait]
+impl UserRepository for SqlUserRepository {
+    async fn find_by_phone(
+        &self,
+        phone_hash: &str,
+        country_code: &str,
+    ) -> Result<Option<User>, DomainError> {
+        let query = format!(
+            "SELECT {} FROM users \
+             WHERE phone_hash = ? AND country_code = ? AND tenant_id = ? AND deleted_at IS NULL LIMIT 1",
+            Self::columns(self.dialect())
+        );
+
+        let result = sqlx::query(&self.dialect().sql(&query))
+            .bind(phone_hash)
+            .bind(country_code)
+            .bind(current_tenant().to_string())
+            .fetch_optional(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Database query failed: {}", e) })?;
+
+        result.as_ref().map(Self::row_to_user).transpose()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.fetch_user("id = ? AND deleted_at IS NULL", id).await
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        let d = self.dialect();
+
+        // Deleted users keep their phone number until purged
+        let existing = sqlx::query(&d.sql(&format!(
+            "SELECT {} FROM users WHERE phone_hash = ? AND country_code = ? AND tenant_id = ? LIMIT 1",
+            d.timestamp("deleted_at")
+        )))
+        .bind(&user.phone_hash)
+        .bind(&user.country_code)
+        .bind(current_tenant().to_string())
+        .fetch_optional(&mut *self.database.acquire().await?)
+        .await
+        .map_err(|e| DomainError::Internal { message: format!("Failed to check user existence: {}", e) })?;
+
+        if let Some(row) = existing {
+            let message = match row::get_optional_timestamp(&row, "deleted_at")? {
+                Some(_) => "Phone number belongs to a deleted account that can still be restored",
+                None => "Phone number already registered",
+            };
+            return Err(DomainError::Validation { message: message.to_string() });
+        }
+
+        retry_on_deadlock("Create user", || async {
+            Self::insert_user(&mut *self.database.acquire().await?, d, &user).await
+        })
+        .await?;
+        Ok(user)
+    }
+
+    async fn create_many(&self, users: Vec<User>) -> Result<usize, DomainError> {
+        let users = &users;
+        retry_on_deadlock("Create users", || async move {
+            let mut tx = self.database.begin().await?;
+
+            for user in users {
+                Self::insert_user(&mut *tx, self.dialect(), user).await?;
+            }
+
+            tx.commit().await
+                .map_err(|e| write_error("Failed to commit transaction", e))?;
+
+            Ok(users.len())
+        })
+        .await
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        retry_on_deadlock("Update user", || async {
+            Self::update_user(&mut *self.database.acquire().await?, self.dialect(), user.clone()).await
+        })
+        .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        // Rows are kept so the account can be restored during the grace period
+        let d = self.dialect();
+        let ts = d.timestamp_param();
+        let query = format!(
+            "UPDATE users SET deleted_at = {ts}, updated_at = {ts} \
+             WHERE id = ? AND tenant_id = ? AND deleted_at IS NULL"
+        );
+
+        let now = d.encode_timestamp(Utc::now());
+        let result = retry_on_deadlock("Delete user", || async {
+            sqlx::query(&d.sql(&query))
+                .bind(&now)
+                .bind(&now)
+                .bind(id.to_string())
+                .bind(current_tenant().to_string())
+                .execute(&mut *self.database.acquire().await?)
+                .await
+                .map_err(|e| write_error("Failed to delete user", e))
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_including_deleted(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        self.fetch_user("id = ?", id).await
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        let d = self.dialect();
+        let query = format!(
+            "UPDATE users SET deleted_at = NULL, updated_at = {} \
+             WHERE id = ? AND tenant_id = ? AND deleted_at IS NOT NULL",
+            d.timestamp_param()
+        );
+
+        let result = retry_on_deadlock("Restore user", || async {
+            sqlx::query(&d.sql(&query))
+                .bind(d.encode_timestamp(Utc::now()))
+                .bind(id.to_string())
+                .bind(current_tenant().to_string())
+                .execute(&mut *self.database.acquire().await?)
+                .await
+                .map_err(|e| write_error("Failed to restore user", e))
+        })
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn exists_by_phone(
+        &self,
+        phone_hash: &str,
+        country_code: &str,
+    ) -> Result<bool, DomainError> {
+        let query = r#"
+            SELECT CASE WHEN EXISTS(
+                SELECT 1 FROM users
+                WHERE phone_hash = ? AND country_code = ? AND tenant_id = ? AND deleted_at IS NULL
+            ) THEN 1 ELSE 0 END AS user_exists
+        "#;
+
+        let result = sqlx::query(&self.dialect().sql(query))
+            .bind(phone_hash)
+            .bind(country_code)
+            .bind(current_tenant().to_string())
+            .fetch_one(&mut *self.database.acquire().await?)
+            .await
+            .map_err(|e| DomainError::Internal { message: format!("Failed to check user existence: {}", e) })?;
+
+        row::get_bool(&result, "user_exists")
+    }
+
+    async fn count_by_type(&self, user_type: Option<UserType>) -> Result<u64, DomainError> {
+        let mut connection = self.database.acquire().await?;
+        let tenant = current_tenant().to_string();
+        let result = match user_type {
+            Some(ut) => {
+                let query = "SELECT COUNT(*) AS count FROM users \
+                             WHERE user_type = ? AND tenant_id = ? AND deleted_at IS NULL";
+                sqlx::query(&self.dialect().sql(query))
+                    .bind(Self::user_type_str(ut))
+                    .bind(tenant)
+                    .fetch_one(&mut *connection)
+                    .await
+            }
+            None => {
+                let query = "SELECT COUNT(*) AS count FROM users WHERE tenant_id = ? AND deleted_at IS NULL";
+                sqlx::query(&self.dialect().sql(query))
+                    .bind(tenant)
+                    .fetch_one(&mut *connection)
+                    .await
+            }
+        };
+
+        let row = result
+            .map_err(|e| DomainError::Internal { message: format!("Failed to count users: {}", e) })?;
+
+        let count: i64 = row::get(&row, "count")?;
+        Ok(count as u64)
+    }
+
+    fn find_all_stream(&self, user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        let query = &self.stream_queries[usize::from(user_type.is_some())];
+        let tenant = current_tenant().to_string();
+
+        async move {
+            // Fails at once while the circuit is open, instead of on the first row
+            drop(self.database.acquire().await?);
+
+            let mut query = sqlx::query(query);
+            if let Some(ut) = user_type {
+                query = query.bind(Self::user_type_str(ut));
+            }
+
+            // Rows are decoded as the database sends them, holding a pool connection until dropped
+            Ok(query
+                .bind(tenant)
+                .fetch(self.database.get_pool())
+                .map(|row| {
+                    row.map_err(|e| DomainError::Internal { message: format!("Failed to stream users: {}", e) })
+                        .and_then(|row| Self::row_to_user(&row))
+                }))
+        }
+        .try_flatten_stream()
+            .boxed()
+    }
+}
@@ -0,0 +1,128 @@
+//! SQLite connection pool with schema bootstrap
+//!
+//! Creates the database file when it does not exist and applies the
+//! embedded schema before the pool is handed out, so a fresh checkout can
+//! run the API without a MySQL server or manual migrations. The pool is an
+//! `AnyPool` so the generic SQL repositories can use it.
+
+use sqlx::any::{install_default_drivers, AnyPoolOptions};
+use sqlx::{AnyPool, Executor};
+use std::time::Duration;
+
+use crate::database::sql::{SqlDatabase, SqlDialect};
+use crate::InfrastructureError;
+
+/// Schema applied when the pool is created
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// Settings applied to every new connection
+const CONNECTION_PRAGMAS: &str = r#"
+    PRAGMA foreign_keys = ON;
+    PRAGMA journal_mode = WAL;
+    PRAGMA busy_timeout = 5000;
+"#;
+
+/// SQLite connection pool wrapper
+#[derive(Clone)]
+pub struct SqliteDatabase {
+    /// Pool shared with the generic SQL repositories
+    database: SqlDatabase,
+}
+
+impl SqliteDatabase {
+    /// Open a SQLite database and bootstrap its schema
+    ///
+    /// # Arguments
+    /// * `url` - SQLite URL such as `sqlite://renoveasy_dev.db` or `sqlite::memory:`
+    /// * `max_connections` - Maximum number of pooled connections
+    ///
+    /// # Returns
+    /// * `Result<Self, InfrastructureError>` - Database with the schema applied, or error
+    pub async fn new(url: &str, max_connections: u32) -> Result<Self, InfrastructureError> {
+        tracing::info!("Opening SQLite database with max_connections: {}", max_connections);
+
+        if SqlDialect::from_url(url) != Some(SqlDialect::Sqlite) {
+            return Err(InfrastructureError::Config(format!("Invalid SQLite URL: {}", url)));
+        }
+
+        // Every connection to `sqlite::memory:` opens its own empty database,
+        // so an in-memory pool is limited to a single long-lived connection
+        let in_memory = url.contains(":memory:") || url.contains("mode=memory");
+        let mut pool_options = AnyPoolOptions::new().after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute(CONNECTION_PRAGMAS).await?;
+                Ok(())
+            })
+        });
+        pool_options = if in_memory {
+            pool_options
+                .max_connections(1)
+                .min_connections(1)
+                .idle_timeout(None)
+                .max_lifetime(None)
+        } else {
+            pool_options.max_connections(max_connections)
+        };
+
+        // Create the database file when it does not exist
+        let url = if in_memory || url.contains("mode=") {
+            url.to_string()
+        } else if url.contains('?') {
+            format!("{}&mode=rwc", url)
+        } else {
+            format!("{}?mode=rwc", url)
+        };
+
+        install_default_drivers();
+        let pool = pool_options
+            .acquire_timeout(Duration::from_secs(5))
+            .connect(&url)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to open SQLite database: {}", e);
+                InfrastructureError::Database(e)
+            })?;
+
+        let database = Self {
+            database: SqlDatabase::from_pool(pool, SqlDialect::Sqlite),
+        };
+        database.bootstrap_schema().await?;
+
+        tracing::info!("SQLite database ready");
+
+        Ok(database)
+    }
+
+    /// Create the tables and indexes that do not exist yet
+    ///
+    /// Runs on every start; existing tables and data are left untouched.
+    pub async fn bootstrap_schema(&self) -> Result<(), InfrastructureError> {
+        tracing::info!("Bootstrapping SQLite schema");
+
+        sqlx::raw_sql(SCHEMA)
+            .execute(self.get_pool())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to bootstrap SQLite schema: {}", e);
+                InfrastructureError::Database(e)
+            })?;
+
+        Ok(())
+    }
+
+    /// Pool and dialect to construct the generic SQL repositories with
+    pub fn database(&self) -> &SqlDatabase {
+        &self.database
+    }
+
+    /// Get a reference to the underlying SQLx pool
+    pub fn get_pool(&self) -> &AnyPool {
+        self.database.get_pool()
+    }
+
+    /// Close all connections in the pool
+    pub async fn close(&self) {
+        tracing::info!("Closing SQLite database");
+        self.database.get_pool().close().await;
+    }
+}
@@ -0,0 +1,16 @@
+//! SQLite-specific database implementations
+//!
+//! Lets developers and CI run the API against a local database file
+//! instead of a MySQL server. Enabled with the `sqlite` feature.
+//!
+//! Only the connection lives here; the user, token and audit log
+//! repositories behind the authentication flow are the generic ones in
+//! [`crate::database::sql`], shared with MySQL:
+//! - `SqliteDatabase`: connection pool that bootstraps the schema on startup
+//! - `SqlUserRepository`, `SqlTokenRepository`, `SqlAuditLogRepository`
+//!   constructed with `SqliteDatabase::database()`
+
+pub mod connection;
+
+// Re-export the SQLite connection
+pub use connection::SqliteDatabase;
@@ -0,0 +1,131 @@
+-- ============================================================================
+-- RenovEasy SQLite schema for local development and CI
+-- ============================================================================
+-- Mirrors the MySQL tables (migrations 001-004, 011, 025, 036 and 037) used
+-- by the repositories with a SQLite implementation. Executed on every
+-- connection and safe to re-run. UUIDs and timestamps are stored as TEXT,
+-- booleans as INTEGER.
+--
+-- Tables are only created when missing, so database files created before
+-- a column was added here must be deleted and recreated.
+
+PRAGMA foreign_keys = ON;
+
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT NOT NULL PRIMARY KEY,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    phone_hash TEXT NOT NULL,
+    phone_hash_version INTEGER NOT NULL DEFAULT 0,
+    country_code TEXT NOT NULL,
+    user_type TEXT NULL CHECK (user_type IN ('customer', 'worker')),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    last_login_at TEXT NULL,
+    last_login_country TEXT NULL,
+    is_verified INTEGER NOT NULL DEFAULT 0,
+    is_blocked INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT NULL,
+    UNIQUE (tenant_id, phone_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_users_user_type ON users(user_type);
+CREATE INDEX IF NOT EXISTS idx_users_phone_hash_version ON users(phone_hash_version);
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id TEXT NOT NULL PRIMARY KEY,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    last_used_at TEXT NULL,
+    is_revoked INTEGER NOT NULL DEFAULT 0,
+    revoked_at TEXT NULL,
+    revoke_reason TEXT NULL,
+    device_id TEXT NULL,
+    device_name TEXT NULL,
+    device_type TEXT NULL,
+    device_fingerprint TEXT NULL,
+    ip_address TEXT NULL,
+    user_agent TEXT NULL,
+    token_family TEXT NULL,
+    previous_token_id TEXT NULL,
+    rotated_from_token_id TEXT NULL,
+    rotated_to_token_id TEXT NULL,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_rotation_at TEXT NULL,
+    rotation_count INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_user_id ON refresh_tokens(user_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_tenant_user ON refresh_tokens(tenant_id, user_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_expires_at ON refresh_tokens(expires_at);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_token_family ON refresh_tokens(token_family);
+
+CREATE TABLE IF NOT EXISTS refresh_tokens_archive (
+    id TEXT NOT NULL PRIMARY KEY,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    user_id TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    last_used_at TEXT NULL,
+    is_revoked INTEGER NOT NULL,
+    revoked_at TEXT NULL,
+    revoke_reason TEXT NULL,
+    device_id TEXT NULL,
+    device_name TEXT NULL,
+    device_type TEXT NULL,
+    device_fingerprint TEXT NULL,
+    ip_address TEXT NULL,
+    user_agent TEXT NULL,
+    token_family TEXT NULL,
+    previous_token_id TEXT NULL,
+    rotated_from_token_id TEXT NULL,
+    rotated_to_token_id TEXT NULL,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_rotation_at TEXT NULL,
+    rotation_count INTEGER NOT NULL DEFAULT 0,
+    archived_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_archive_user_id ON refresh_tokens_archive(user_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_archive_token_family ON refresh_tokens_archive(token_family);
+
+CREATE TABLE IF NOT EXISTS token_blacklist (
+    jti TEXT NOT NULL PRIMARY KEY,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_blacklist_expires_at ON token_blacklist(expires_at);
+
+CREATE TABLE IF NOT EXISTS auth_audit_log (
+    id TEXT NOT NULL PRIMARY KEY,
+    tenant_id TEXT NOT NULL DEFAULT 'default',
+    event_type TEXT NOT NULL,
+    user_id TEXT NULL,
+    phone_masked TEXT NULL,
+    phone_hash TEXT NULL,
+    ip_address TEXT NOT NULL,
+    user_agent TEXT NULL,
+    device_info TEXT NULL,
+    action TEXT NOT NULL,
+    success INTEGER NOT NULL,
+    error_message TEXT NULL,
+    failure_reason TEXT NULL,
+    token_id TEXT NULL,
+    rate_limit_type TEXT NULL,
+    event_data TEXT NULL,
+    created_at TEXT NOT NULL,
+    archived INTEGER NOT NULL DEFAULT 0,
+    archived_at TEXT NULL,
+    trace_id TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_user_id ON auth_audit_log(user_id);
+CREATE INDEX IF NOT EXISTS idx_audit_phone_hash ON auth_audit_log(phone_hash);
+CREATE INDEX IF NOT EXISTS idx_audit_ip_address ON auth_audit_log(ip_address);
+CREATE INDEX IF NOT EXISTS idx_audit_created_at ON auth_audit_log(created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_event_type ON auth_audit_log(event_type);
+CREATE INDEX IF NOT EXISTS idx_audit_tenant_created ON auth_audit_log(tenant_id, created_at);
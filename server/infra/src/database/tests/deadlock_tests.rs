@@ -0,0 +1,96 @@
+//! Unit tests for retrying writes that lose a lock conflict
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use re_core::errors::DomainError;
+
+use crate::database::deadlock::{
+    is_lock_conflict, retry_on_deadlock_with, write_error, DeadlockRetryPolicy,
+};
+
+fn fast_policy(max_attempts: u32) -> DeadlockRetryPolicy {
+    DeadlockRetryPolicy {
+        max_attempts,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(2),
+    }
+}
+
+fn conflict() -> DomainError {
+    DomainError::Conflict {
+        message: "Deadlock found when trying to get lock".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_conflicting_write_is_retried() {
+    let calls = AtomicU32::new(0);
+
+    let result = retry_on_deadlock_with(&fast_policy(3), "Update user", || async {
+        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err(conflict())
+        } else {
+            Ok(7)
+        }
+    })
+    .await;
+
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_conflict_returned_once_attempts_run_out() {
+    let calls = AtomicU32::new(0);
+
+    let result: Result<(), _> = retry_on_deadlock_with(&fast_policy(2), "Update user", || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(conflict())
+    })
+    .await;
+
+    assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_other_errors_are_not_retried() {
+    let calls = AtomicU32::new(0);
+
+    let result: Result<(), _> = retry_on_deadlock_with(&fast_policy(3), "Update user", || async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(DomainError::Internal {
+            message: "Syntax error".to_string(),
+        })
+    })
+    .await;
+
+    assert!(matches!(result, Err(DomainError::Internal { .. })));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_retry_delay_is_jittered_and_capped() {
+    let policy = DeadlockRetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(20),
+        max_delay: Duration::from_millis(50),
+    };
+
+    for _ in 0..20 {
+        let first = policy.retry_delay(1);
+        assert!(first >= Duration::from_millis(10) && first <= Duration::from_millis(20));
+        let capped = policy.retry_delay(4);
+        assert!(capped >= Duration::from_millis(25) && capped <= Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn test_non_lock_errors_map_to_internal() {
+    assert!(!is_lock_conflict(&sqlx::Error::RowNotFound));
+    assert!(!is_lock_conflict(&sqlx::Error::PoolTimedOut));
+
+    let error = write_error("Failed to update user", sqlx::Error::RowNotFound);
+    assert!(matches!(error, DomainError::Internal { message } if message.starts_with("Failed to update user: ")));
+}
@@ -0,0 +1,62 @@
+//! Unit tests for database health assessment
+
+use std::time::Duration;
+
+use re_shared::types::{HealthStatus, ServiceHealth};
+
+use crate::database::circuit_breaker::CircuitState;
+use crate::database::connection::PoolStatistics;
+use crate::database::health::{DatabaseHealth, DatabaseHealthConfig, DatabaseHealthState};
+
+fn pool(connections: u32, idle_connections: usize) -> PoolStatistics {
+    PoolStatistics {
+        connections,
+        idle_connections,
+        active_connections: connections - idle_connections as u32,
+        max_connections: 10,
+        acquire_wait: None,
+        circuit: CircuitState::Closed,
+    }
+}
+
+#[test]
+fn test_fast_query_with_idle_connections_is_healthy() {
+    let health = DatabaseHealth::assess(
+        Ok(()),
+        Duration::from_millis(5),
+        &pool(4, 2),
+        &DatabaseHealthConfig::default(),
+    );
+
+    assert_eq!(health.state, DatabaseHealthState::Healthy);
+    assert!(health.message.is_none());
+}
+
+#[test]
+fn test_slow_query_or_saturated_pool_is_degraded() {
+    let config = DatabaseHealthConfig::default();
+
+    let slow = DatabaseHealth::assess(Ok(()), Duration::from_millis(400), &pool(4, 2), &config);
+    assert_eq!(slow.state, DatabaseHealthState::Degraded);
+    assert!(slow.message.unwrap().contains("400 ms"));
+
+    let saturated = DatabaseHealth::assess(Ok(()), Duration::from_millis(5), &pool(10, 0), &config);
+    assert_eq!(saturated.state, DatabaseHealthState::Degraded);
+    assert!(saturated.message.unwrap().contains("All 10 connections"));
+}
+
+#[test]
+fn test_failed_query_is_down_and_maps_to_unhealthy() {
+    let health = DatabaseHealth::assess(
+        Err("No answer within 2000 ms".to_string()),
+        Duration::from_secs(2),
+        &pool(0, 0),
+        &DatabaseHealthConfig::default(),
+    );
+    assert_eq!(health.state, DatabaseHealthState::Down);
+
+    let service: ServiceHealth = health.into();
+    assert_eq!(service.status, HealthStatus::Unhealthy);
+    assert_eq!(service.message.as_deref(), Some("No answer within 2000 ms"));
+    assert_eq!(service.response_time_ms, Some(2000));
+}
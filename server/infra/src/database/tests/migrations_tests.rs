@@ -0,0 +1,52 @@
+//! Unit tests for the embedded migrations
+//!
+//! sqlx sends each migration to MySQL as one multi-statement query, so the
+//! SQL the migrator runs must be plain server-side SQL: no `mysql` client
+//! directives and no MariaDB-only syntax.
+
+use crate::database::migrations::migrator;
+
+#[test]
+fn test_migrations_do_not_use_client_delimiters() {
+    for migration in migrator().iter() {
+        assert!(
+            !migration
+                .sql
+                .lines()
+                .any(|line| line.trim_start().to_uppercase().starts_with("DELIMITER")),
+            "migration {} uses the mysql client's DELIMITER directive",
+            migration.version
+        );
+        assert!(
+            !migration.sql.contains("$$"),
+            "migration {} ends a statement with a custom delimiter",
+            migration.version
+        );
+    }
+}
+
+#[test]
+fn test_migrations_avoid_mariadb_only_syntax() {
+    for migration in migrator().iter() {
+        let sql = migration.sql.to_uppercase();
+        for clause in ["ADD COLUMN IF NOT EXISTS", "CREATE INDEX IF NOT EXISTS"] {
+            assert!(
+                !sql.contains(clause),
+                "migration {} uses `{}`, which MySQL does not support",
+                migration.version,
+                clause
+            );
+        }
+    }
+}
+
+#[test]
+fn test_migrations_do_not_set_global_variables() {
+    for migration in migrator().iter() {
+        assert!(
+            !migration.sql.to_uppercase().contains("SET GLOBAL"),
+            "migration {} sets a global variable the application user cannot change",
+            migration.version
+        );
+    }
+}
@@ -1,4 +1,24 @@
 //! Unit tests for database module
 
 #[cfg(test)]
-pub mod connection_tests;
\ No newline at end of file
+pub mod connection_tests;
+#[cfg(test)]
+pub mod health_tests;
+#[cfg(test)]
+pub mod circuit_breaker_tests;
+#[cfg(test)]
+pub mod deadlock_tests;
+#[cfg(all(test, feature = "generic-sql"))]
+pub mod sql_dialect_tests;
+#[cfg(all(test, feature = "sqlite"))]
+pub mod sql_repository_tests;
+#[cfg(test)]
+pub mod sharding_tests;
+#[cfg(test)]
+pub mod schema_tests;
+#[cfg(test)]
+pub mod migrations_tests;
+#[cfg(test)]
+pub mod tenant_isolation_tests;
+#[cfg(all(test, feature = "sqlite"))]
+pub mod sqlite_auth_flow_tests;
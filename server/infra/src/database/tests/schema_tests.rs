@@ -0,0 +1,77 @@
+//! Unit tests for detecting schema drift
+
+use crate::database::schema::{embedded_migrations, LiveSchema, RequiredTable, REQUIRED_TABLES};
+
+const USERS: RequiredTable = RequiredTable {
+    name: "users",
+    columns: &["id", "tenant_id"],
+    indexes: &["PRIMARY"],
+};
+
+/// A database with every migration applied and the `users` table complete
+fn migrated(migrations: &[i64]) -> LiveSchema {
+    LiveSchema {
+        applied: migrations.iter().copied().collect(),
+        columns: [("users", "id"), ("users", "tenant_id")]
+            .into_iter()
+            .map(|(table, column)| (table.to_string(), column.to_string()))
+            .collect(),
+        indexes: [("users".to_string(), "PRIMARY".to_string())].into_iter().collect(),
+        ..LiveSchema::default()
+    }
+}
+
+#[test]
+fn test_matching_schema_has_no_drift() {
+    assert!(migrated(&[1, 2, 3]).drift(&[1, 2, 3], &[USERS]).is_empty());
+}
+
+#[test]
+fn test_pending_and_failed_migrations_are_reported() {
+    let mut live = migrated(&[1]);
+    live.failed.insert(2);
+
+    let drift = live.drift(&[1, 2, 3, 4], &[USERS]);
+    assert_eq!(
+        drift,
+        vec![
+            "migration 2 failed part-way and must be repaired by hand".to_string(),
+            "migrations 3, 4 are not applied".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_missing_columns_and_indexes_are_reported() {
+    let mut live = migrated(&[1]);
+    live.columns.remove(&("users".to_string(), "tenant_id".to_string()));
+    live.indexes.clear();
+
+    let drift = live.drift(&[1], &[USERS]);
+    assert_eq!(
+        drift,
+        vec![
+            "column users.tenant_id is missing".to_string(),
+            "index PRIMARY on users is missing".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_newer_migrations_are_not_drift() {
+    let live = migrated(&[1, 2, 3]);
+
+    assert!(live.drift(&[1, 2], &[USERS]).is_empty());
+    assert_eq!(live.unknown_migrations(&[1, 2]), vec![3]);
+}
+
+#[test]
+fn test_required_tables_come_from_embedded_migrations() {
+    let migrations = embedded_migrations();
+    assert!(migrations.contains(&37));
+
+    // An empty database lacks every migration, column and index
+    let drift = LiveSchema::default().drift(&migrations, REQUIRED_TABLES);
+    let required: usize = REQUIRED_TABLES.iter().map(|table| table.columns.len() + table.indexes.len()).sum();
+    assert_eq!(drift.len(), required + 1);
+}
@@ -0,0 +1,235 @@
+//! Unit tests for country-based sharding of users
+
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use re_core::domain::entities::audit::AuditLog;
+use re_core::domain::entities::token::RefreshToken;
+use re_core::domain::entities::user::{User, UserType};
+use re_core::errors::DomainError;
+use re_core::repositories::{UnitOfWork, UnitOfWorkTransaction, UserRepository};
+
+use crate::database::sharding::{ShardRouter, ShardedUnitOfWork, ShardedUserRepository, PRIMARY_SHARD};
+
+/// In-memory users of one shard
+#[derive(Default)]
+struct ShardUsers {
+    users: Mutex<Vec<User>>,
+}
+
+impl ShardUsers {
+    fn len(&self) -> usize {
+        self.users.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl UserRepository for ShardUsers {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.phone_hash == phone_hash && user.country_code == country_code)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.users.lock().unwrap().iter().find(|user| user.id == id).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        let mut users = self.users.lock().unwrap();
+        let existing = users
+            .iter_mut()
+            .find(|existing| existing.id == user.id)
+            .ok_or_else(|| DomainError::NotFound { resource: "User".to_string() })?;
+        *existing = user.clone();
+        Ok(user)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let mut users = self.users.lock().unwrap();
+        let before = users.len();
+        users.retain(|user| user.id != id);
+        Ok(users.len() < before)
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        Ok(self.find_by_phone(phone_hash, country_code).await?.is_some())
+    }
+
+    async fn count_by_type(&self, _user_type: Option<UserType>) -> Result<u64, DomainError> {
+        Ok(self.len() as u64)
+    }
+
+    fn find_all_stream(&self, user_type: Option<UserType>) -> BoxStream<'_, Result<User, DomainError>> {
+        let users: Vec<Result<User, DomainError>> = self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|user| user_type.is_none() || user.user_type == user_type)
+            .cloned()
+            .map(Ok)
+            .collect();
+        stream::iter(users).boxed()
+    }
+}
+
+fn china_sharded() -> ShardedUserRepository<ShardUsers> {
+    ShardedUserRepository::new(
+        ShardRouter::new(ShardUsers::default()).with_shard("cn", ["+86"], ShardUsers::default()),
+    )
+}
+
+#[test]
+fn test_router_falls_back_to_primary() {
+    let router = ShardRouter::new("primary-pool").with_shard("cn", ["+86", "+852"], "cn-pool");
+
+    assert_eq!(*router.route("+86"), "cn-pool");
+    assert_eq!(*router.route("+852"), "cn-pool");
+    assert_eq!(*router.route("+61"), "primary-pool");
+    assert_eq!(router.shard_name("+86"), "cn");
+    assert_eq!(router.shard_name("+61"), PRIMARY_SHARD);
+
+    let lengths = router.map(|pool| pool.len());
+    assert_eq!(*lengths.route("+86"), "cn-pool".len());
+    assert_eq!(lengths.shards().len(), 2);
+}
+
+#[tokio::test]
+async fn test_users_are_stored_on_their_country_shard() {
+    let repository = china_sharded();
+    let china = User::new("hash-cn".to_string(), "+86".to_string());
+    let australia = User::new("hash-au".to_string(), "+61".to_string());
+
+    repository.create(china.clone()).await.unwrap();
+    repository.create(australia.clone()).await.unwrap();
+
+    assert_eq!(repository.router().primary().len(), 1);
+    assert_eq!(repository.router().route("+86").len(), 1);
+    assert!(repository.find_by_phone("hash-cn", "+86").await.unwrap().is_some());
+    assert!(repository.router().primary().find_by_phone("hash-cn", "+86").await.unwrap().is_none());
+    assert!(repository.exists_by_phone("hash-au", "+61").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_lookups_by_id_reach_every_shard() {
+    let repository = china_sharded();
+    let china = User::new("hash-cn".to_string(), "+86".to_string());
+    repository.create(china.clone()).await.unwrap();
+
+    assert_eq!(repository.find_by_id(china.id).await.unwrap().map(|user| user.id), Some(china.id));
+    assert!(repository.find_by_id(Uuid::new_v4()).await.unwrap().is_none());
+
+    assert!(repository.delete(china.id).await.unwrap());
+    assert!(!repository.delete(china.id).await.unwrap());
+    assert_eq!(repository.router().route("+86").len(), 0);
+}
+
+#[tokio::test]
+async fn test_batches_and_counts_span_shards() {
+    let repository = china_sharded();
+    let users = vec![
+        User::new("hash-1".to_string(), "+86".to_string()),
+        User::new("hash-2".to_string(), "+61".to_string()),
+        User::new("hash-3".to_string(), "+86".to_string()),
+    ];
+
+    assert_eq!(repository.create_many(users).await.unwrap(), 3);
+
+    assert_eq!(repository.router().route("+86").len(), 2);
+    assert_eq!(repository.router().primary().len(), 1);
+    assert_eq!(repository.count_by_type(None).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_streams_read_every_shard_primary_first() {
+    let repository = china_sharded();
+    let china = User::new("hash-cn".to_string(), "+86".to_string());
+    let mut australia = User::new("hash-au".to_string(), "+61".to_string());
+    australia.user_type = Some(UserType::Worker);
+    repository.create(china.clone()).await.unwrap();
+    repository.create(australia.clone()).await.unwrap();
+
+    let streamed: Vec<Uuid> = repository.find_all_stream(None).map_ok(|user| user.id).try_collect().await.unwrap();
+    assert_eq!(streamed, vec![australia.id, china.id]);
+
+    let workers: Vec<User> = repository.find_all_stream(Some(UserType::Worker)).try_collect().await.unwrap();
+    assert_eq!(workers.len(), 1);
+    assert_eq!(workers[0].id, australia.id);
+}
+
+/// Unit of work of one shard, counting the transactions begun on it
+#[derive(Default)]
+struct ShardTransactions {
+    begun: Mutex<usize>,
+}
+
+impl ShardTransactions {
+    fn begun(&self) -> usize {
+        *self.begun.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for ShardTransactions {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWorkTransaction>, DomainError> {
+        *self.begun.lock().unwrap() += 1;
+        Ok(Box::new(NoopTransaction))
+    }
+}
+
+struct NoopTransaction;
+
+#[async_trait]
+impl UnitOfWorkTransaction for NoopTransaction {
+    async fn create_user(&mut self, user: User) -> Result<User, DomainError> {
+        Ok(user)
+    }
+
+    async fn update_user(&mut self, user: User) -> Result<User, DomainError> {
+        Ok(user)
+    }
+
+    async fn save_refresh_token(&mut self, token: RefreshToken) -> Result<RefreshToken, DomainError> {
+        Ok(token)
+    }
+
+    async fn create_audit_log(&mut self, _audit_log: &AuditLog) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_transactions_are_begun_on_the_country_shard() {
+    let unit_of_work = ShardedUnitOfWork::new(
+        ShardRouter::new(ShardTransactions::default()).with_shard("cn", ["+86"], ShardTransactions::default()),
+    );
+
+    unit_of_work.begin_for_country("+86").await.unwrap();
+    assert_eq!(unit_of_work.router().route("+86").begun(), 1);
+    assert_eq!(unit_of_work.router().primary().begun(), 0);
+
+    unit_of_work.begin_for_country("+61").await.unwrap();
+    unit_of_work.begin().await.unwrap();
+    assert_eq!(unit_of_work.router().primary().begun(), 2);
+    assert_eq!(unit_of_work.router().route("+86").begun(), 1);
+}
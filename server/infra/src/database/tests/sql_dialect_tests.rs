@@ -0,0 +1,63 @@
+//! Unit tests for SQL dialect handling
+
+use chrono::{TimeZone, Utc};
+
+use crate::database::sql::SqlDialect;
+
+#[test]
+fn test_dialect_is_detected_from_url() {
+    assert_eq!(SqlDialect::from_url("mysql://root@localhost/app"), Some(SqlDialect::MySql));
+    assert_eq!(SqlDialect::from_url("postgres://localhost/app"), Some(SqlDialect::Postgres));
+    assert_eq!(SqlDialect::from_url("postgresql://localhost/app"), Some(SqlDialect::Postgres));
+    assert_eq!(SqlDialect::from_url("sqlite::memory:"), Some(SqlDialect::Sqlite));
+    assert_eq!(SqlDialect::from_url("redis://localhost"), None);
+}
+
+#[test]
+fn test_placeholders_are_numbered_for_postgres_only() {
+    let query = "SELECT id FROM users WHERE phone_hash = ? AND note <> '?' AND country_code = ?";
+
+    assert_eq!(SqlDialect::MySql.sql(query), query);
+    assert_eq!(SqlDialect::Sqlite.sql(query), query);
+    assert_eq!(
+        SqlDialect::Postgres.sql(query),
+        "SELECT id FROM users WHERE phone_hash = $1 AND note <> '?' AND country_code = $2"
+    );
+}
+
+#[test]
+fn test_conflict_handling_per_dialect() {
+    let insert = "INSERT INTO refresh_tokens (id, token_hash) VALUES (?, ?)";
+
+    assert_eq!(
+        SqlDialect::MySql.insert_ignore(insert, "token_hash"),
+        "INSERT IGNORE INTO refresh_tokens (id, token_hash) VALUES (?, ?)"
+    );
+    assert!(SqlDialect::Sqlite
+        .insert_ignore(insert, "token_hash")
+        .ends_with("ON CONFLICT (token_hash) DO NOTHING"));
+
+    assert_eq!(
+        SqlDialect::MySql.upsert("jti", &["expires_at"]),
+        "ON DUPLICATE KEY UPDATE expires_at = VALUES(expires_at)"
+    );
+    assert_eq!(
+        SqlDialect::Postgres.upsert("jti", &["expires_at"]),
+        "ON CONFLICT (jti) DO UPDATE SET expires_at = excluded.expires_at"
+    );
+}
+
+#[test]
+fn test_timestamps_round_trip() {
+    let at = Utc.with_ymd_and_hms(2026, 10, 17, 8, 30, 15).unwrap() + chrono::Duration::microseconds(250);
+
+    for dialect in [SqlDialect::MySql, SqlDialect::Postgres, SqlDialect::Sqlite] {
+        let encoded = dialect.encode_timestamp(at);
+        assert_eq!(SqlDialect::decode_timestamp(&encoded), Ok(at), "{}", dialect.as_str());
+    }
+
+    // SQLite stores timestamps in the format SQLx's SQLite driver writes
+    assert_eq!(SqlDialect::Sqlite.encode_timestamp(at), "2026-10-17T08:30:15.000250+00:00");
+    assert_eq!(SqlDialect::MySql.encode_timestamp(at), "2026-10-17 08:30:15.000250");
+    assert!(SqlDialect::decode_timestamp("yesterday").is_err());
+}
@@ -0,0 +1,236 @@
+//! Tests for the generic SQL repositories against an in-memory SQLite database
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use re_core::domain::entities::audit::{AuditEventType, AuditLog, AuditLogFilter};
+use re_core::domain::entities::token::RefreshToken;
+use re_core::domain::entities::user::{User, UserType};
+use re_core::errors::DomainError;
+use re_core::repositories::audit::AuditLogRepository;
+use re_core::repositories::{TokenRepository, UnitOfWork, UserRepository};
+use re_core::services::tenant::{self, TenantId};
+use re_shared::types::Pagination;
+
+use crate::database::sql::{
+    SqlAuditLogRepository, SqlTokenRepository, SqlUnitOfWork, SqlUserRepository,
+};
+use crate::database::sqlite::SqliteDatabase;
+
+async fn database() -> SqliteDatabase {
+    SqliteDatabase::new("sqlite::memory:", 1).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_user_round_trip_and_soft_delete() {
+    let database = database().await;
+    let repository = SqlUserRepository::new(database.database().clone());
+
+    let mut user = User::new("hash-1".to_string(), "+61".to_string());
+    user.set_user_type(UserType::Worker);
+    user.phone_hash_version = 2;
+    user.last_login_at = Some(Utc::now());
+    let user = repository.create(user).await.unwrap();
+
+    let found = repository.find_by_phone("hash-1", "+61").await.unwrap().unwrap();
+    assert_eq!(found.id, user.id);
+    assert_eq!(found.user_type, Some(UserType::Worker));
+    assert_eq!(found.phone_hash_version, 2);
+    assert_eq!(found.created_at, user.created_at);
+    assert_eq!(found.last_login_at, user.last_login_at);
+    assert!(!found.is_verified);
+
+    let mut verified = found;
+    verified.verify();
+    repository.update(verified).await.unwrap();
+    assert!(repository.find_by_id(user.id).await.unwrap().unwrap().is_verified);
+    assert!(repository.exists_by_phone("hash-1", "+61").await.unwrap());
+    assert_eq!(repository.count_by_type(Some(UserType::Worker)).await.unwrap(), 1);
+
+    assert!(repository.delete(user.id).await.unwrap());
+    assert!(repository.find_by_id(user.id).await.unwrap().is_none());
+    assert!(repository.find_including_deleted(user.id).await.unwrap().unwrap().deleted_at.is_some());
+    assert!(matches!(
+        repository.create(User::new("hash-1".to_string(), "+61".to_string())).await,
+        Err(DomainError::Validation { .. })
+    ));
+
+    assert!(repository.restore(user.id).await.unwrap());
+    assert!(repository.exists_by_phone("hash-1", "+61").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_token_revocation_and_blacklist() {
+    let database = database().await;
+    let users = SqlUserRepository::new(database.database().clone());
+    let repository = SqlTokenRepository::new(database.database().clone());
+
+    let user = users.create(User::new("hash-2".to_string(), "+86".to_string())).await.unwrap();
+    let token = RefreshToken::new_with_metadata(
+        user.id,
+        "token-hash".to_string(),
+        Some("family".to_string()),
+        None,
+        None,
+    );
+    repository.save_refresh_token(token.clone()).await.unwrap();
+    assert!(matches!(
+        repository.save_refresh_token(token.clone()).await,
+        Err(DomainError::Validation { .. })
+    ));
+
+    let found = repository.find_refresh_token("token-hash").await.unwrap().unwrap();
+    assert_eq!(found.id, token.id);
+    assert_eq!(found.expires_at, token.expires_at);
+    assert_eq!(repository.find_by_user_id(user.id).await.unwrap().len(), 1);
+
+    assert_eq!(repository.revoke_token_family("family").await.unwrap(), 1);
+    assert!(repository.find_by_id(token.id).await.unwrap().unwrap().is_revoked);
+    assert!(!repository.revoke_token("token-hash").await.unwrap());
+    assert!(repository.find_by_user_id(user.id).await.unwrap().is_empty());
+
+    repository.blacklist_token("jti-1", Utc::now() - Duration::minutes(1)).await.unwrap();
+    assert!(!repository.is_token_blacklisted("jti-1").await.unwrap());
+    repository.blacklist_token("jti-1", Utc::now() + Duration::minutes(15)).await.unwrap();
+    assert!(repository.is_token_blacklisted("jti-1").await.unwrap());
+    assert_eq!(repository.cleanup_blacklist().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_expired_tokens_archived_before_deletion() {
+    let database = database().await;
+    let users = SqlUserRepository::new(database.database().clone());
+    let repository = SqlTokenRepository::new(database.database().clone());
+
+    let user = users.create(User::new("hash-3".to_string(), "+61".to_string())).await.unwrap();
+    let active = RefreshToken::new(user.id, "active-hash".to_string());
+    let mut expired = RefreshToken::new_with_metadata(
+        user.id,
+        "expired-hash".to_string(),
+        Some("family".to_string()),
+        None,
+        None,
+    );
+    expired.expires_at = Utc::now() - Duration::days(1);
+    repository.save_refresh_token(active.clone()).await.unwrap();
+    repository.save_refresh_token(expired.clone()).await.unwrap();
+
+    assert_eq!(repository.archive_expired_tokens().await.unwrap(), 1);
+    assert!(repository.find_by_id(expired.id).await.unwrap().is_none());
+    assert!(repository.find_by_id(active.id).await.unwrap().is_some());
+
+    let (id, token_family): (String, String) =
+        sqlx::query_as("SELECT id, token_family FROM refresh_tokens_archive")
+            .fetch_one(database.get_pool())
+            .await
+            .unwrap();
+    assert_eq!(id, expired.id.to_string());
+    assert_eq!(token_family, "family");
+
+    assert_eq!(repository.archive_expired_tokens().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_audit_logs_query_and_retention() {
+    let database = database().await;
+    let repository = SqlAuditLogRepository::new(database.database().clone());
+    let user_id = Uuid::new_v4();
+
+    for days in [1, 2, 40, 50] {
+        let mut log = AuditLog::new(AuditEventType::LoginFailure, "10.0.0.1").with_user(user_id);
+        log.created_at = Utc::now() - Duration::days(days);
+        log.event_data = Some(serde_json::json!({ "attempt": days }));
+        repository.create(&log).await.unwrap();
+    }
+
+    let logs = repository.find_by_user(user_id, 10).await.unwrap();
+    assert_eq!(logs.len(), 4);
+    assert!(logs[0].created_at > logs[1].created_at);
+    assert_eq!(logs[0].event_data, Some(serde_json::json!({ "attempt": 1 })));
+    assert!(!logs[0].success);
+
+    let failed = repository
+        .count_failed_attempts(AuditEventType::LoginFailure.as_str(), None, Some("10.0.0.1"), Utc::now() - Duration::days(7))
+        .await
+        .unwrap();
+    assert_eq!(failed, 2);
+
+    let filter = AuditLogFilter {
+        user_id: Some(user_id),
+        ..Default::default()
+    };
+    let (page, total) = repository.query(&filter, &Pagination::new(2, 3)).await.unwrap();
+    assert_eq!(total, 4);
+    assert_eq!(page.len(), 1);
+
+    let cutoff = Utc::now() - Duration::days(30);
+    assert_eq!(repository.archive_logs_before(cutoff, 1).await.unwrap(), 1);
+    assert_eq!(repository.archive_logs_before(cutoff, 10).await.unwrap(), 1);
+    assert!(repository.find_by_user(user_id, 10).await.unwrap()[3].archived);
+    assert_eq!(repository.delete_logs_before(cutoff, 10).await.unwrap(), 2);
+    assert_eq!(repository.find_by_user(user_id, 10).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_rows_are_invisible_to_other_tenants() {
+    let database = database().await;
+    let users = SqlUserRepository::new(database.database().clone());
+    let tokens = SqlTokenRepository::new(database.database().clone());
+    let audit = SqlAuditLogRepository::new(database.database().clone());
+    let tenant_a = TenantId::parse("tenant-a").unwrap();
+    let tenant_b = TenantId::parse("tenant-b").unwrap();
+
+    let user = User::new("hash-5".to_string(), "+61".to_string());
+    let user = tenant::scope(tenant_a.clone(), users.create(user)).await.unwrap();
+    let token = RefreshToken::new(user.id, "token-hash-5".to_string());
+    tenant::scope(tenant_a.clone(), tokens.save_refresh_token(token)).await.unwrap();
+    let log = AuditLog::new(AuditEventType::LoginSuccess, "203.0.113.7").with_user(user.id);
+    tenant::scope(tenant_a.clone(), audit.create(&log)).await.unwrap();
+
+    assert!(tenant::scope(tenant_a.clone(), users.find_by_id(user.id)).await.unwrap().is_some());
+    assert!(tenant::scope(tenant_b.clone(), users.find_by_id(user.id)).await.unwrap().is_none());
+    assert!(users.find_by_phone("hash-5", "+61").await.unwrap().is_none());
+    assert!(tenant::scope(tenant_b.clone(), tokens.find_refresh_token("token-hash-5"))
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(tenant::scope(tenant_b.clone(), tokens.revoke_all_user_tokens(user.id)).await.unwrap(), 0);
+    assert!(tenant::scope(tenant_b.clone(), audit.find_by_user(user.id, 10)).await.unwrap().is_empty());
+    assert_eq!(tenant::scope(tenant_a.clone(), audit.find_by_user(user.id, 10)).await.unwrap().len(), 1);
+
+    // The same phone registers separately in another tenant
+    let other = User::new("hash-5".to_string(), "+61".to_string());
+    let other = tenant::scope(tenant_b.clone(), users.create(other)).await.unwrap();
+    assert_ne!(other.id, user.id);
+    assert_eq!(tenant::scope(tenant_b, users.count_by_type(None)).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_unit_of_work_commits_and_batches_skip_existing_logs() {
+    let database = database().await;
+    let users = SqlUserRepository::new(database.database().clone());
+    let tokens = SqlTokenRepository::new(database.database().clone());
+    let audit = SqlAuditLogRepository::new(database.database().clone());
+    let unit_of_work = SqlUnitOfWork::new(database.database().clone());
+
+    let user = User::new("hash-6".to_string(), "+61".to_string());
+    let mut tx = unit_of_work.begin().await.unwrap();
+    tx.create_user(user.clone()).await.unwrap();
+    tx.save_refresh_token(RefreshToken::new(user.id, "token-hash-6".to_string())).await.unwrap();
+    tx.rollback().await.unwrap();
+    assert!(users.find_by_id(user.id).await.unwrap().is_none());
+
+    let mut tx = unit_of_work.begin().await.unwrap();
+    tx.create_user(user.clone()).await.unwrap();
+    tx.save_refresh_token(RefreshToken::new(user.id, "token-hash-6".to_string())).await.unwrap();
+    tx.commit().await.unwrap();
+    assert!(users.find_by_id(user.id).await.unwrap().is_some());
+    assert!(tokens.find_refresh_token("token-hash-6").await.unwrap().is_some());
+
+    let logs: Vec<AuditLog> = (0..3)
+        .map(|_| AuditLog::new(AuditEventType::LoginSuccess, "203.0.113.7").with_user(user.id))
+        .collect();
+    assert_eq!(audit.create_batch(&logs[..2]).await.unwrap(), 2);
+    assert_eq!(audit.create_batch(&logs).await.unwrap(), 1);
+    assert_eq!(audit.find_by_user(user.id, 10).await.unwrap().len(), 3);
+}
@@ -0,0 +1,177 @@
+//! Authentication flow against the generic SQL repositories on an in-memory SQLite database
+
+use async_trait::async_trait;
+use jsonwebtoken::Algorithm;
+use std::sync::Arc;
+
+use re_core::domain::entities::audit::AuditEventType;
+use re_core::repositories::audit::AuditLogRepository;
+use re_core::repositories::{TokenRepository, UserRepository};
+use re_core::services::audit::{AuditService, AuditServiceConfig};
+use re_core::services::auth::{AuthService, AuthServiceConfig, RateLimiterTrait};
+use re_core::services::token::{TokenService, TokenServiceConfig};
+use re_core::services::verification::{SmsServiceTrait, VerificationService, VerificationServiceConfig};
+
+use crate::cache::{InMemoryCacheBackend, VerificationCache};
+use crate::database::sql::{SqlAuditLogRepository, SqlTokenRepository, SqlUserRepository};
+use crate::database::sqlite::SqliteDatabase;
+
+struct NoopSmsService;
+
+#[async_trait]
+impl SmsServiceTrait for NoopSmsService {
+    async fn send_verification_code(&self, _phone: &str, _code: &str) -> Result<String, String> {
+        Ok("sqlite-test".to_string())
+    }
+
+    fn is_valid_phone_number(&self, phone: &str) -> bool {
+        phone.starts_with('+')
+    }
+}
+
+struct UnlimitedRateLimiter;
+
+#[async_trait]
+impl RateLimiterTrait for UnlimitedRateLimiter {
+    async fn check_sms_rate_limit(&self, _phone: &str) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    async fn increment_sms_counter(&self, _phone: &str) -> Result<i64, String> {
+        Ok(1)
+    }
+
+    async fn get_rate_limit_reset_time(&self, _phone: &str) -> Result<Option<i64>, String> {
+        Ok(None)
+    }
+
+    async fn check_ip_verification_limit(&self, _ip: &str) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    async fn increment_ip_verification_counter(&self, _ip: &str) -> Result<i64, String> {
+        Ok(1)
+    }
+
+    async fn get_ip_rate_limit_reset_time(&self, _ip: &str) -> Result<Option<i64>, String> {
+        Ok(None)
+    }
+
+    async fn log_rate_limit_violation(
+        &self,
+        _identifier: &str,
+        _identifier_type: &str,
+        _action: &str,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Use HS256 for tests to avoid needing key files
+fn hs256_config() -> TokenServiceConfig {
+    TokenServiceConfig {
+        algorithm: Algorithm::HS256,
+        rs256_config: None,
+        ..TokenServiceConfig::default()
+    }
+}
+
+type SqliteAuthService = AuthService<
+    SqlUserRepository,
+    NoopSmsService,
+    VerificationCache,
+    UnlimitedRateLimiter,
+    SqlTokenRepository,
+    SqlAuditLogRepository,
+>;
+
+fn auth_service(database: &SqliteDatabase) -> SqliteAuthService {
+    let sql = database.database().clone();
+
+    let verification_service = Arc::new(VerificationService::new(
+        Arc::new(NoopSmsService),
+        Arc::new(VerificationCache::with_backend(Arc::new(InMemoryCacheBackend::new(1_000)))),
+        VerificationServiceConfig::default(),
+    ));
+
+    let token_service = Arc::new(
+        TokenService::new(SqlTokenRepository::new(sql.clone()), hs256_config()).unwrap(),
+    );
+
+    let audit_service = Arc::new(AuditService::new(
+        Arc::new(SqlAuditLogRepository::new(sql.clone())),
+        AuditServiceConfig { async_writes: false, ..AuditServiceConfig::default() },
+    ));
+
+    AuthService::with_audit(
+        Arc::new(SqlUserRepository::new(sql)),
+        verification_service,
+        Arc::new(UnlimitedRateLimiter),
+        token_service,
+        audit_service,
+        AuthServiceConfig::default(),
+    )
+}
+
+#[tokio::test]
+async fn test_login_refresh_and_logout_without_mysql() {
+    let database = SqliteDatabase::new("sqlite::memory:", 1).await.unwrap();
+    let auth = auth_service(&database);
+    let users = SqlUserRepository::new(database.database().clone());
+    let tokens = SqlTokenRepository::new(database.database().clone());
+    let audit = SqlAuditLogRepository::new(database.database().clone());
+    let phone = "+61412345678";
+    let ip = Some("203.0.113.7".to_string());
+
+    // Registration: the first verified code creates the user
+    let sent = auth.send_verification_code(phone, ip.clone(), None).await.unwrap();
+    let login = auth
+        .verify_code(phone, &sent.verification_code.code, ip.clone(), None, None)
+        .await
+        .unwrap();
+    assert!(login.requires_type_selection);
+    assert_eq!(users.count_by_type(None).await.unwrap(), 1);
+
+    let token_service =
+        TokenService::new(SqlTokenRepository::new(database.database().clone()), hs256_config()).unwrap();
+    let user_id = token_service
+        .verify_access_token(&login.access_token)
+        .await
+        .unwrap()
+        .user_id()
+        .unwrap();
+    assert!(users.find_by_id(user_id).await.unwrap().unwrap().is_verified);
+    assert_eq!(tokens.find_by_user_id(user_id).await.unwrap().len(), 1);
+
+    // A second login finds the existing user
+    let sent = auth.send_verification_code(phone, ip.clone(), None).await.unwrap();
+    auth.verify_code(phone, &sent.verification_code.code, ip.clone(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(users.count_by_type(None).await.unwrap(), 1);
+
+    // Refreshing rotates the stored refresh token
+    let refreshed = auth
+        .refresh_token(&login.refresh_token, ip.clone(), None, None)
+        .await
+        .unwrap();
+    assert_ne!(refreshed.refresh_token, login.refresh_token);
+    assert!(auth.refresh_token(&login.refresh_token, ip.clone(), None, None).await.is_err());
+
+    // Logout revokes the sessions and blacklists the access token
+    auth.logout(user_id, Some(refreshed.access_token.clone()), ip, None, None)
+        .await
+        .unwrap();
+    assert!(tokens.find_by_user_id(user_id).await.unwrap().is_empty());
+    assert!(auth.refresh_token(&refreshed.refresh_token, None, None, None).await.is_err());
+    assert!(token_service.verify_access_token(&refreshed.access_token).await.is_err());
+
+    let events: Vec<_> = audit
+        .find_by_user(user_id, 50)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|log| log.event_type)
+        .collect();
+    assert!(events.contains(&AuditEventType::LoginSuccess));
+}
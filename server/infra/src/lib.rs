@@ -8,16 +8,28 @@
 //! 
 //! The infrastructure layer contains:
 //! - **Database**: MySQL implementations using SQLx
-//! - **Cache**: Redis client for caching and rate limiting
-//! - **SMS**: SMS service integrations (Twilio, AWS SNS)
+//! - **Cache**: Redis client for caching and rate limiting, with in-memory and
+//!   memcached backends for verification codes
+//! - **SMS**: SMS service integrations (Twilio, AWS SNS, Vonage)
+//! - **Payments**: Payment intents, webhooks and reports for reconciliation (Stripe)
+//! - **Compliance**: Market verification providers (real-name verification in China)
+//! - **Storage**: Object storage for generated files such as exports
+//! - **Seeder**: Demo and test data loaded from fixture files
 //! - **External APIs**: HTTP client implementations
 //!
 //! ## Features
 //!
-//! - `mysql`: Enable MySQL database support (default)
+//! - `mysql`: Enable MySQL database support (default, implies `generic-sql`)
+//! - `generic-sql`: Enable database-agnostic repositories over `sqlx::AnyPool`
+//! - `sqlite`: Enable a SQLite database for local development and CI (implies `generic-sql`)
 //! - `redis-cache`: Enable Redis caching support (default) 
 //! - `twilio-sms`: Enable Twilio SMS service (default)
+//! - `aws-sns`: Enable AWS SNS SMS service (default)
+//! - `vonage-sms`: Enable Vonage SMS service (default)
 //! - `mock-services`: Enable mock implementations for testing
+//!
+//! The features an instance was built with, together with its
+//! configuration, determine its [`capabilities::Capabilities`].
 
 // Re-export core types for convenience  
 pub use re_core::errors::*;
@@ -32,9 +44,27 @@ pub mod sms;
 /// Cache module - Redis client and operations  
 pub mod cache;
 
+/// Payments module - Payment provider adapters
+pub mod payments;
+
+/// Compliance module - Market verification provider adapters
+pub mod compliance;
+
+/// Storage module - Object storage adapters
+pub mod storage;
+
+/// Seeder module - Demo and test data from fixture files
+pub mod seeder;
+
 /// Services module - Infrastructure service implementations
 pub mod services;
 
+/// Capabilities module - Features and configured services of the instance
+pub mod capabilities;
+
+/// Circuit breaker module - Failing fast while an external service is down
+pub mod circuit_breaker;
+
 /// Configuration module for infrastructure services
 pub mod config {
     //! Configuration management for infrastructure services
@@ -45,7 +75,7 @@ pub mod config {
     //! - SMS service credentials  
     //! - Environment-specific settings
     
-    use re_shared::config::{database::DatabaseConfig, cache::CacheConfig};
+    use re_shared::config::{database::DatabaseConfig, cache::CacheConfig, SecretString};
     use serde::{Deserialize, Serialize};
     
     // Re-export shared configs for backward compatibility
@@ -65,14 +95,26 @@ pub mod config {
     /// SMS service configuration
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct SmsConfig {
-        /// SMS service provider ("twilio", "aws-sns", "mock")
+        /// SMS service provider ("twilio", "aws-sns", "vonage", "failover", "mock")
         pub provider: String,
         /// API credentials
-        pub api_key: String,
+        pub api_key: SecretString,
         /// API secret/token
-        pub api_secret: String,
+        pub api_secret: SecretString,
         /// From phone number
         pub from_number: String,
+        /// Per-market sender IDs and branding
+        #[serde(default)]
+        pub markets: Vec<re_shared::config::SmsMarketConfig>,
+        /// Provider per recipient dialing code
+        #[serde(default)]
+        pub routes: Vec<re_shared::config::SmsRoute>,
+        /// Per-provider concurrency and rate caps
+        #[serde(default)]
+        pub provider_limits: Vec<re_shared::config::SmsProviderLimits>,
+        /// Record messages instead of calling the provider APIs
+        #[serde(default)]
+        pub dry_run: bool,
     }
     
     impl Default for InfrastructureConfig {
@@ -82,9 +124,13 @@ pub mod config {
                 cache: CacheConfig::default(),
                 sms: SmsConfig {
                     provider: "mock".to_string(),
-                    api_key: String::new(),
-                    api_secret: String::new(),
+                    api_key: SecretString::default(),
+                    api_secret: SecretString::default(),
                     from_number: "+1234567890".to_string(),
+                    markets: Vec::new(),
+                    routes: Vec::new(),
+                    provider_limits: Vec::new(),
+                    dry_run: false,
                 },
             }
         }
@@ -94,6 +140,11 @@ pub mod config {
 /// Infrastructure service container
 #[derive(Clone)]
 pub struct InfrastructureServices {
+    /// Database connection pool, migrated to the latest schema
+    #[cfg(feature = "mysql")]
+    database: Option<database::DatabasePool>,
+    /// Capabilities detected at startup
+    capabilities: Option<capabilities::Capabilities>,
     // Services will be added as modules are implemented
     _marker: std::marker::PhantomData<()>,
 }
@@ -102,9 +153,36 @@ impl InfrastructureServices {
     /// Create new infrastructure services container
     pub fn new() -> Self {
         Self {
+            #[cfg(feature = "mysql")]
+            database: None,
+            capabilities: None,
             _marker: std::marker::PhantomData,
         }
     }
+
+    /// Use the given database connection pool
+    #[cfg(feature = "mysql")]
+    pub fn with_database(mut self, database: database::DatabasePool) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Database connection pool, if one was initialized
+    #[cfg(feature = "mysql")]
+    pub fn database(&self) -> Option<&database::DatabasePool> {
+        self.database.as_ref()
+    }
+
+    /// Use the given capabilities
+    pub fn with_capabilities(mut self, capabilities: capabilities::Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// Capabilities detected at startup, if any
+    pub fn capabilities(&self) -> Option<&capabilities::Capabilities> {
+        self.capabilities.as_ref()
+    }
 }
 
 impl Default for InfrastructureServices {
@@ -116,7 +194,10 @@ impl Default for InfrastructureServices {
 /// Initialize infrastructure services with async runtime
 /// 
 /// This function sets up:
+/// - Capability checks, failing when the environment requires a capability
+///   the build or configuration lacks
 /// - Database connection pools
+/// - Database schema, by applying pending migrations
 /// - Redis connections
 /// - SMS service clients
 /// - Tokio async runtime configuration
@@ -124,15 +205,35 @@ pub async fn initialize() -> Result<InfrastructureServices, InfrastructureError>
     tracing::info!("Initializing infrastructure services...");
     
     // Load configuration
-    let _config = load_config()?;
+    let config = load_config()?;
+
+    let capabilities = capabilities::Capabilities::detect(
+        re_shared::config::Environment::from_env(),
+        capabilities::CompiledFeatures::current(),
+        &capabilities::ConfiguredServices {
+            database: !config.database.url.is_empty(),
+            redis: !config.cache.url.is_empty(),
+            sms_provider: config.sms.provider.clone(),
+        },
+    );
+    capabilities.ensure_required()?;
+    let services = InfrastructureServices::new().with_capabilities(capabilities);
+
+    #[cfg(feature = "mysql")]
+    let services = {
+        let pool = database::DatabasePool::new(config.database).await?;
+        database::run_migrations(pool.get_pool()).await?;
+        services.with_database(pool)
+    };
+    #[cfg(not(feature = "mysql"))]
+    let _ = config;
     
-    // TODO: Initialize database pool
     // TODO: Initialize Redis client
     // TODO: Initialize SMS service
     
     tracing::info!("Infrastructure services initialized successfully");
     
-    Ok(InfrastructureServices::new())
+    Ok(services)
 }
 
 /// Load infrastructure configuration from environment
@@ -146,10 +247,24 @@ fn load_config() -> Result<config::InfrastructureConfig, InfrastructureError> {
     // Load SMS config (still local to infra)
     let sms = config::SmsConfig {
         provider: std::env::var("SMS_PROVIDER").unwrap_or_else(|_| "mock".to_string()),
-        api_key: std::env::var("SMS_API_KEY").unwrap_or_default(),
-        api_secret: std::env::var("SMS_API_SECRET").unwrap_or_default(),
+        api_key: re_shared::config::SecretString::from(std::env::var("SMS_API_KEY").unwrap_or_default()),
+        api_secret: re_shared::config::SecretString::from(std::env::var("SMS_API_SECRET").unwrap_or_default()),
         from_number: std::env::var("SMS_FROM_NUMBER").unwrap_or_else(|_| "+1234567890".to_string()),
+        markets: re_shared::config::SmsMarketConfig::list_from_env(),
+        routes: re_shared::config::SmsRoute::list_from_env(),
+        provider_limits: re_shared::config::SmsProviderLimits::list_from_env(),
+        dry_run: std::env::var("SMS_DRY_RUN")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(false),
     };
+    for market in &sms.markets {
+        market.validate().map_err(InfrastructureError::Config)?;
+    }
+    re_shared::config::SmsRoute::validate_all(&sms.routes).map_err(InfrastructureError::Config)?;
+    for limits in &sms.provider_limits {
+        limits.validate().map_err(InfrastructureError::Config)?;
+    }
     
     Ok(config::InfrastructureConfig {
         database,
@@ -165,10 +280,18 @@ pub enum InfrastructureError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
     
+    /// Database migration error
+    #[error("Migration error: {0}")]
+    Migration(#[from] sqlx::migrate::MigrateError),
+    
     /// Redis cache error
     #[error("Cache error: {0}")]
     Cache(#[from] redis::RedisError),
     
+    /// Error of a cache backend other than Redis
+    #[error("Cache backend error: {0}")]
+    CacheBackend(String),
+    
     /// HTTP request error for external services
     #[error("HTTP request error: {0}")]
     Http(#[from] reqwest::Error),
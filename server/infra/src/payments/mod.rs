@@ -0,0 +1,28 @@
+//! Payment Provider Module
+//!
+//! This module provides adapters to payment providers used by the
+//! financial reconciliation job and the payment webhook endpoint.
+//!
+//! ## Features
+//!
+//! - **Stripe Reports**: Balance transactions fetched from the Stripe API
+//! - **Reconciliation Alerts**: Webhook notifications for discrepancies above the threshold
+//! - **Stripe Webhooks**: Signature verification and decoding of refund, dispute and payment confirmation events
+//! - **Stripe Payment Intents**: Payment creation with 3-D Secure when required
+//! - **Stripe Payment Methods**: Saving and removing customers' tokenized cards
+//! - **Stripe Payouts**: Verifying workers' bank accounts and transferring payouts through Connect
+
+pub mod reconciliation_alert;
+pub mod stripe_payment_intents;
+pub mod stripe_payment_methods;
+pub mod stripe_payouts;
+pub mod stripe_reports;
+pub mod stripe_webhooks;
+
+// Re-export commonly used types
+pub use reconciliation_alert::{ReconciliationAlertConfig, WebhookReconciliationAlert};
+pub use stripe_payment_intents::{StripePaymentIntentConfig, StripePaymentIntentProvider};
+pub use stripe_payment_methods::{StripePaymentMethodConfig, StripePaymentMethodProvider};
+pub use stripe_payouts::{StripePayoutConfig, StripePayoutProvider};
+pub use stripe_reports::{StripeBalanceReportProvider, StripeReportConfig};
+pub use stripe_webhooks::{StripeWebhookConfig, StripeWebhookProvider};
@@ -0,0 +1,102 @@
+//! Webhook alerts for reconciliation discrepancies
+//!
+//! Posts a JSON summary of the report to a configured URL. The `text` field
+//! makes the payload usable with Slack-compatible incoming webhooks.
+
+use async_trait::async_trait;
+use serde_json::json;
+use std::time::Duration;
+
+use re_core::domain::entities::reconciliation::ReconciliationReport;
+use re_core::services::reconciliation::ReconciliationAlertTrait;
+
+use crate::InfrastructureError;
+
+/// Reconciliation alert configuration
+#[derive(Debug, Clone)]
+pub struct ReconciliationAlertConfig {
+    /// URL that receives alert payloads
+    pub webhook_url: String,
+    /// Timeout for webhook requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl ReconciliationAlertConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let webhook_url = std::env::var("RECONCILIATION_ALERT_WEBHOOK_URL").map_err(|_| {
+            InfrastructureError::Config("RECONCILIATION_ALERT_WEBHOOK_URL not set".to_string())
+        })?;
+
+        Ok(Self {
+            webhook_url,
+            request_timeout_secs: std::env::var("RECONCILIATION_ALERT_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+        })
+    }
+}
+
+/// Sends reconciliation alerts to a webhook
+pub struct WebhookReconciliationAlert {
+    client: reqwest::Client,
+    config: ReconciliationAlertConfig,
+}
+
+impl WebhookReconciliationAlert {
+    /// Create a new webhook alert sender
+    pub fn new(config: ReconciliationAlertConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+}
+
+#[async_trait]
+impl ReconciliationAlertTrait for WebhookReconciliationAlert {
+    async fn send_alert(&self, report: &ReconciliationReport) -> Result<(), String> {
+        let totals = report
+            .discrepancy_totals
+            .iter()
+            .map(|(currency, total)| format!("{} {}", currency, total))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let text = format!(
+            "Reconciliation of {} for {} to {} found {} discrepancies (totals in minor units: {}). Report {}",
+            report.provider,
+            report.period_start.to_rfc3339(),
+            report.period_end.to_rfc3339(),
+            report.discrepancies.len(),
+            totals,
+            report.id,
+        );
+
+        let payload = json!({
+            "text": text,
+            "report_id": report.id,
+            "provider": report.provider,
+            "period_start": report.period_start,
+            "period_end": report.period_end,
+            "discrepancy_count": report.discrepancies.len(),
+            "discrepancy_totals": report.discrepancy_totals,
+        });
+
+        let response = self
+            .client
+            .post(&self.config.webhook_url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Webhook returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
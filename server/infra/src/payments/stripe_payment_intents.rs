@@ -0,0 +1,209 @@
+//! Stripe payment intents
+//!
+//! Creates and confirms a payment intent in one request. Stripe decides with
+//! the card issuer whether 3-D Secure is needed; the platform can force a
+//! challenge with `request_three_d_secure=any`. Intents that need
+//! authentication are returned with the client secret the mobile app passes
+//! to the Stripe SDK, and are captured or failed later through the
+//! `payment_intent.*` webhooks.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+use re_core::domain::entities::payment::PaymentClientAction;
+use re_core::services::payment_intent::{
+    PaymentIntentProviderTrait, PaymentIntentRequest, ProviderIntentStatus, ProviderPaymentIntent,
+};
+use re_shared::config::SecretString;
+
+use super::stripe_reports::STRIPE_PROVIDER;
+use super::stripe_webhooks::StripePaymentError;
+use crate::InfrastructureError;
+
+/// Stripe payment intent configuration
+#[derive(Debug, Clone)]
+pub struct StripePaymentIntentConfig {
+    /// Stripe secret API key
+    pub secret_key: SecretString,
+    /// Base URL of the Stripe API
+    pub api_base: String,
+    /// Deep link the bank's challenge page returns to, for redirect-based authentication
+    pub return_url: Option<String>,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl StripePaymentIntentConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let secret_key = std::env::var("STRIPE_SECRET_KEY")
+            .map_err(|_| InfrastructureError::Config("STRIPE_SECRET_KEY not set".to_string()))?;
+
+        Ok(Self {
+            secret_key: SecretString::from(secret_key),
+            api_base: std::env::var("STRIPE_API_BASE")
+                .unwrap_or_else(|_| "https://api.stripe.com".to_string()),
+            return_url: std::env::var("STRIPE_RETURN_URL").ok(),
+            request_timeout_secs: std::env::var("STRIPE_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        })
+    }
+}
+
+/// The fields of a Stripe payment intent used by the platform
+#[derive(Debug, Deserialize)]
+struct PaymentIntent {
+    id: String,
+    status: String,
+    client_secret: Option<String>,
+    latest_charge: Option<String>,
+    next_action: Option<NextAction>,
+    last_payment_error: Option<StripePaymentError>,
+}
+
+/// What the customer must do before the intent can proceed
+#[derive(Debug, Deserialize)]
+struct NextAction {
+    #[serde(rename = "type")]
+    kind: String,
+    redirect_to_url: Option<RedirectToUrl>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedirectToUrl {
+    url: String,
+}
+
+/// Error body Stripe returns for declined and invalid requests
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ApiError,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    message: Option<String>,
+    code: Option<String>,
+    payment_intent: Option<PaymentIntent>,
+}
+
+impl PaymentIntent {
+    fn into_provider_intent(self) -> Result<ProviderPaymentIntent, String> {
+        let status = match self.status.as_str() {
+            "succeeded" => ProviderIntentStatus::Succeeded {
+                charge_reference: self.latest_charge,
+            },
+            "requires_action" => {
+                let client_secret = self
+                    .client_secret
+                    .ok_or_else(|| format!("Payment intent {} has no client secret", self.id))?;
+                let redirect = self
+                    .next_action
+                    .filter(|action| action.kind == "redirect_to_url")
+                    .and_then(|action| action.redirect_to_url);
+
+                ProviderIntentStatus::RequiresAction(match redirect {
+                    Some(redirect) => PaymentClientAction::RedirectToUrl {
+                        client_secret,
+                        url: redirect.url,
+                    },
+                    None => PaymentClientAction::UseSdk { client_secret },
+                })
+            }
+            "requires_payment_method" | "canceled" => ProviderIntentStatus::Failed {
+                reason: self
+                    .last_payment_error
+                    .map(|error| error.reason())
+                    .unwrap_or_else(|| "Payment failed".to_string()),
+            },
+            other => return Err(format!("Unexpected status {} of payment intent {}", other, self.id)),
+        };
+
+        Ok(ProviderPaymentIntent {
+            reference: self.id,
+            status,
+        })
+    }
+}
+
+/// Stripe payment intent provider
+pub struct StripePaymentIntentProvider {
+    client: reqwest::Client,
+    config: StripePaymentIntentConfig,
+}
+
+impl StripePaymentIntentProvider {
+    /// Create a new Stripe payment intent provider
+    pub fn new(config: StripePaymentIntentConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a Stripe payment intent provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::new(StripePaymentIntentConfig::from_env()?)
+    }
+}
+
+#[async_trait]
+impl PaymentIntentProviderTrait for StripePaymentIntentProvider {
+    fn provider_name(&self) -> &str {
+        STRIPE_PROVIDER
+    }
+
+    async fn create_intent(&self, request: &PaymentIntentRequest) -> Result<ProviderPaymentIntent, String> {
+        let three_ds = if request.require_three_ds { "any" } else { "automatic" };
+        let mut params = vec![
+            ("amount", request.amount.to_string()),
+            ("currency", request.currency.to_ascii_lowercase()),
+            ("payment_method", request.payment_method.clone()),
+            ("payment_method_types[]", "card".to_string()),
+            ("payment_method_options[card][request_three_d_secure]", three_ds.to_string()),
+            ("confirm", "true".to_string()),
+            ("metadata[customer_id]", request.customer_id.to_string()),
+            ("metadata[worker_id]", request.worker_id.to_string()),
+        ];
+        if let Some(return_url) = &self.config.return_url {
+            params.push(("return_url", return_url.clone()));
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/v1/payment_intents", self.config.api_base))
+            .bearer_auth(self.config.secret_key.expose_secret())
+            .header("Idempotency-Key", &request.idempotency_key)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if status.is_success() {
+            let intent = response
+                .json::<PaymentIntent>()
+                .await
+                .map_err(|e| format!("Invalid response: {}", e))?;
+            debug!(intent_id = %intent.id, status = %intent.status, "Created Stripe payment intent");
+            return intent.into_provider_intent();
+        }
+
+        // Declined cards come back as 402 with the failed intent attached
+        let body = response.text().await.unwrap_or_default();
+        match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(ErrorResponse { error: ApiError { payment_intent: Some(mut intent), message, code } }) => {
+                if intent.last_payment_error.is_none() {
+                    intent.last_payment_error = Some(StripePaymentError { code, message });
+                }
+                intent.into_provider_intent()
+            }
+            _ => Err(format!("Stripe returned {}: {}", status, body)),
+        }
+    }
+}
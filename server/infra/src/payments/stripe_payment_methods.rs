@@ -0,0 +1,176 @@
+//! Stripe saved payment methods
+//!
+//! The app tokenizes cards with the Stripe SDK and sends the payment method
+//! ID. Saving attaches it to the customer's Stripe customer, created on the
+//! first save and found again through its `user_id` metadata; Stripe only
+//! allows attached payment methods to be charged more than once. Deleting
+//! detaches the method, after which it cannot be charged.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+use uuid::Uuid;
+
+use re_core::services::payment_method::{PaymentMethodProviderTrait, ProviderPaymentMethod};
+use re_shared::config::SecretString;
+
+use super::stripe_reports::STRIPE_PROVIDER;
+use crate::InfrastructureError;
+
+/// Stripe payment method configuration
+#[derive(Debug, Clone)]
+pub struct StripePaymentMethodConfig {
+    /// Stripe secret API key
+    pub secret_key: SecretString,
+    /// Base URL of the Stripe API
+    pub api_base: String,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl StripePaymentMethodConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let secret_key = std::env::var("STRIPE_SECRET_KEY")
+            .map_err(|_| InfrastructureError::Config("STRIPE_SECRET_KEY not set".to_string()))?;
+
+        Ok(Self {
+            secret_key: SecretString::from(secret_key),
+            api_base: std::env::var("STRIPE_API_BASE")
+                .unwrap_or_else(|_| "https://api.stripe.com".to_string()),
+            request_timeout_secs: std::env::var("STRIPE_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        })
+    }
+}
+
+/// The fields of a Stripe customer used by the platform
+#[derive(Debug, Deserialize)]
+struct Customer {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomerSearch {
+    data: Vec<Customer>,
+}
+
+/// The fields of a Stripe payment method used by the platform
+#[derive(Debug, Deserialize)]
+struct PaymentMethod {
+    id: String,
+    card: Option<Card>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Card {
+    brand: String,
+    last4: String,
+    exp_month: u32,
+    exp_year: i32,
+}
+
+/// Stripe saved payment method provider
+pub struct StripePaymentMethodProvider {
+    client: reqwest::Client,
+    config: StripePaymentMethodConfig,
+}
+
+impl StripePaymentMethodProvider {
+    /// Create a new Stripe payment method provider
+    pub fn new(config: StripePaymentMethodConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a Stripe payment method provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::new(StripePaymentMethodConfig::from_env()?)
+    }
+
+    /// Send a request and decode the response, returning Stripe's error body on failure
+    async fn send<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, String> {
+        let response = request
+            .bearer_auth(self.config.secret_key.expose_secret())
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Stripe returned {}: {}", status, body));
+        }
+        response.json::<T>().await.map_err(|e| format!("Invalid response: {}", e))
+    }
+
+    /// Find the customer's Stripe customer, creating it on first use
+    async fn customer(&self, customer_id: Uuid) -> Result<String, String> {
+        let query = format!("metadata['user_id']:'{}'", customer_id);
+        let search: CustomerSearch = self
+            .send(
+                self.client
+                    .get(format!("{}/v1/customers/search", self.config.api_base))
+                    .query(&[("query", query.as_str()), ("limit", "1")]),
+            )
+            .await?;
+        if let Some(customer) = search.data.into_iter().next() {
+            return Ok(customer.id);
+        }
+
+        // Search results lag behind creation, so concurrent first saves
+        // share one customer through the idempotency key
+        let customer: Customer = self
+            .send(
+                self.client
+                    .post(format!("{}/v1/customers", self.config.api_base))
+                    .header("Idempotency-Key", format!("customer_{}", customer_id))
+                    .form(&[("metadata[user_id]", customer_id.to_string())]),
+            )
+            .await?;
+        debug!(customer_id = %customer_id, stripe_customer = %customer.id, "Created Stripe customer");
+        Ok(customer.id)
+    }
+}
+
+#[async_trait]
+impl PaymentMethodProviderTrait for StripePaymentMethodProvider {
+    fn provider_name(&self) -> &str {
+        STRIPE_PROVIDER
+    }
+
+    async fn attach(&self, customer_id: Uuid, token: &str) -> Result<ProviderPaymentMethod, String> {
+        let customer = self.customer(customer_id).await?;
+        let method: PaymentMethod = self
+            .send(
+                self.client
+                    .post(format!("{}/v1/payment_methods/{}/attach", self.config.api_base, token))
+                    .form(&[("customer", customer.as_str())]),
+            )
+            .await?;
+
+        let card = method
+            .card
+            .ok_or_else(|| format!("Payment method {} is not a card", method.id))?;
+        Ok(ProviderPaymentMethod {
+            reference: method.id,
+            brand: card.brand,
+            last4: card.last4,
+            exp_month: card.exp_month,
+            exp_year: card.exp_year,
+        })
+    }
+
+    async fn detach(&self, reference: &str) -> Result<(), String> {
+        let _: PaymentMethod = self
+            .send(self.client.post(format!("{}/v1/payment_methods/{}/detach", self.config.api_base, reference)))
+            .await?;
+        Ok(())
+    }
+}
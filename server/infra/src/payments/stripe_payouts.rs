@@ -0,0 +1,214 @@
+//! Stripe payout methods and transfers
+//!
+//! Workers are paid through Stripe Connect. Registering a bank account
+//! creates a Custom connected account for the worker with the tokenized
+//! account as its external account; the connected account's ID is the
+//! payout method's reference, and payouts are transfers to it. Stripe
+//! verifies the account holder against the identity the worker provided
+//! instead of sending micro-deposits, and reports later results through
+//! `account.updated` webhooks.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+use uuid::Uuid;
+
+use re_core::services::payout_method::{
+    PayoutMethodProviderTrait, ProviderBankAccount, ProviderVerification,
+};
+use re_shared::config::SecretString;
+
+use super::stripe_reports::STRIPE_PROVIDER;
+use crate::InfrastructureError;
+
+/// Stripe payout configuration
+#[derive(Debug, Clone)]
+pub struct StripePayoutConfig {
+    /// Stripe secret API key
+    pub secret_key: SecretString,
+    /// Base URL of the Stripe API
+    pub api_base: String,
+    /// Country of the workers' connected accounts
+    pub country: String,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl StripePayoutConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let secret_key = std::env::var("STRIPE_SECRET_KEY")
+            .map_err(|_| InfrastructureError::Config("STRIPE_SECRET_KEY not set".to_string()))?;
+
+        Ok(Self {
+            secret_key: SecretString::from(secret_key),
+            api_base: std::env::var("STRIPE_API_BASE")
+                .unwrap_or_else(|_| "https://api.stripe.com".to_string()),
+            country: std::env::var("STRIPE_CONNECT_COUNTRY").unwrap_or_else(|_| "AU".to_string()),
+            request_timeout_secs: std::env::var("STRIPE_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        })
+    }
+}
+
+/// The fields of a Stripe connected account used by the platform
+#[derive(Debug, Deserialize)]
+struct Account {
+    id: String,
+    external_accounts: ExternalAccounts,
+    individual: Option<Person>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalAccounts {
+    data: Vec<BankAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BankAccount {
+    bank_name: Option<String>,
+    last4: String,
+    currency: String,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    verification: PersonVerification,
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonVerification {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transfer {
+    id: String,
+}
+
+impl Account {
+    /// Map the bank account and identity checks to a verification outcome
+    fn verification(&self, bank_account: &BankAccount) -> ProviderVerification {
+        match bank_account.status.as_str() {
+            "verification_failed" | "errored" => return ProviderVerification::Failed,
+            "verified" => return ProviderVerification::Verified,
+            _ => {}
+        }
+        match self.individual.as_ref().map(|p| p.verification.status.as_str()) {
+            Some("verified") => ProviderVerification::Verified,
+            Some("unverified") => ProviderVerification::Failed,
+            _ => ProviderVerification::Pending,
+        }
+    }
+}
+
+/// Stripe Connect payout provider
+pub struct StripePayoutProvider {
+    client: reqwest::Client,
+    config: StripePayoutConfig,
+}
+
+impl StripePayoutProvider {
+    /// Create a new Stripe payout provider
+    pub fn new(config: StripePayoutConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a Stripe payout provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::new(StripePayoutConfig::from_env()?)
+    }
+
+    /// Send a request and decode the response, returning Stripe's error body on failure
+    async fn send<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, String> {
+        let response = request
+            .bearer_auth(self.config.secret_key.expose_secret())
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Stripe returned {}: {}", status, body));
+        }
+        response.json::<T>().await.map_err(|e| format!("Invalid response: {}", e))
+    }
+}
+
+#[async_trait]
+impl PayoutMethodProviderTrait for StripePayoutProvider {
+    fn provider_name(&self) -> &str {
+        STRIPE_PROVIDER
+    }
+
+    async fn add_bank_account(&self, worker_id: Uuid, token: &str) -> Result<ProviderBankAccount, String> {
+        let account: Account = self
+            .send(
+                self.client
+                    .post(format!("{}/v1/accounts", self.config.api_base))
+                    .header("Idempotency-Key", format!("payout_method_{}", token))
+                    .form(&[
+                        ("type", "custom".to_string()),
+                        ("country", self.config.country.clone()),
+                        ("business_type", "individual".to_string()),
+                        ("capabilities[transfers][requested]", "true".to_string()),
+                        ("external_account", token.to_string()),
+                        ("metadata[user_id]", worker_id.to_string()),
+                    ]),
+            )
+            .await?;
+
+        let bank_account = account
+            .external_accounts
+            .data
+            .first()
+            .ok_or_else(|| format!("Account {} has no bank account", account.id))?;
+        let verification = account.verification(bank_account);
+        debug!(worker_id = %worker_id, account = %account.id, "Created Stripe connected account");
+
+        Ok(ProviderBankAccount {
+            bank_name: bank_account.bank_name.clone(),
+            last4: bank_account.last4.clone(),
+            currency: bank_account.currency.clone(),
+            verification,
+            reference: account.id,
+        })
+    }
+
+    async fn verify_micro_deposits(&self, _reference: &str, _amounts: &[i64]) -> Result<bool, String> {
+        Err("Stripe verifies connected accounts without micro-deposits".to_string())
+    }
+
+    async fn transfer(
+        &self,
+        reference: &str,
+        amount: i64,
+        currency: &str,
+        idempotency_key: &str,
+    ) -> Result<String, String> {
+        let transfer: Transfer = self
+            .send(
+                self.client
+                    .post(format!("{}/v1/transfers", self.config.api_base))
+                    .header("Idempotency-Key", format!("payout_{}", idempotency_key))
+                    .form(&[
+                        ("amount", amount.to_string()),
+                        ("currency", currency.to_ascii_lowercase()),
+                        ("destination", reference.to_string()),
+                        ("metadata[payout_id]", idempotency_key.to_string()),
+                    ]),
+            )
+            .await?;
+        debug!(transfer = %transfer.id, destination = %reference, "Created Stripe transfer");
+        Ok(transfer.id)
+    }
+}
@@ -0,0 +1,195 @@
+//! Stripe balance transaction reports
+//!
+//! Fetches balance transactions from the Stripe API for reconciliation.
+//! Each transaction is reported under its source object ID (charge, refund,
+//! payout) when it has one, and with its net amount, so a ledger that
+//! records a charge and its fee under the charge ID reconciles against a
+//! single Stripe transaction.
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::debug;
+
+use re_core::domain::entities::payment::{normalize_currency, LedgerEntryKind};
+use re_core::domain::entities::reconciliation::ProviderTransaction;
+use re_core::services::reconciliation::PaymentReportProviderTrait;
+use re_shared::config::SecretString;
+
+use crate::InfrastructureError;
+
+/// Provider name recorded on Stripe ledger entries
+pub const STRIPE_PROVIDER: &str = "stripe";
+
+/// Stripe report configuration
+#[derive(Debug, Clone)]
+pub struct StripeReportConfig {
+    /// Stripe secret API key (restricted keys need read access to balance)
+    pub secret_key: SecretString,
+    /// Base URL of the Stripe API
+    pub api_base: String,
+    /// Number of transactions requested per page (Stripe allows at most 100)
+    pub page_size: u32,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl StripeReportConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let secret_key = std::env::var("STRIPE_SECRET_KEY")
+            .map_err(|_| InfrastructureError::Config("STRIPE_SECRET_KEY not set".to_string()))?;
+
+        Ok(Self {
+            secret_key: SecretString::from(secret_key),
+            api_base: std::env::var("STRIPE_API_BASE")
+                .unwrap_or_else(|_| "https://api.stripe.com".to_string()),
+            page_size: std::env::var("STRIPE_REPORT_PAGE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            request_timeout_secs: std::env::var("STRIPE_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        })
+    }
+}
+
+/// One page of the balance transaction list
+#[derive(Debug, Deserialize)]
+struct BalanceTransactionList {
+    data: Vec<BalanceTransaction>,
+    has_more: bool,
+}
+
+/// The fields of a Stripe balance transaction used for reconciliation
+#[derive(Debug, Deserialize)]
+struct BalanceTransaction {
+    id: String,
+    net: i64,
+    currency: String,
+    created: i64,
+    #[serde(rename = "type")]
+    kind: String,
+    source: Option<String>,
+}
+
+impl BalanceTransaction {
+    fn into_provider_transaction(self) -> Result<ProviderTransaction, String> {
+        let occurred_at = Utc
+            .timestamp_opt(self.created, 0)
+            .single()
+            .ok_or_else(|| format!("Invalid timestamp on {}", self.id))?;
+
+        Ok(ProviderTransaction {
+            kind: map_transaction_type(&self.kind),
+            amount: self.net,
+            currency: normalize_currency(&self.currency)?,
+            occurred_at,
+            reference: self.source.unwrap_or(self.id),
+        })
+    }
+}
+
+/// Map a Stripe balance transaction type to a ledger entry kind
+fn map_transaction_type(kind: &str) -> LedgerEntryKind {
+    match kind {
+        "charge" | "payment" => LedgerEntryKind::Charge,
+        "refund" | "payment_refund" => LedgerEntryKind::Refund,
+        "stripe_fee" | "application_fee" | "tax_fee" => LedgerEntryKind::Fee,
+        "payout" => LedgerEntryKind::Payout,
+        _ => LedgerEntryKind::Adjustment,
+    }
+}
+
+/// Stripe balance transaction report provider
+pub struct StripeBalanceReportProvider {
+    client: reqwest::Client,
+    config: StripeReportConfig,
+}
+
+impl StripeBalanceReportProvider {
+    /// Create a new Stripe report provider
+    pub fn new(config: StripeReportConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Create a Stripe report provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::new(StripeReportConfig::from_env()?)
+    }
+
+    async fn fetch_page(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        starting_after: Option<&str>,
+    ) -> Result<BalanceTransactionList, String> {
+        let mut params = vec![
+            ("created[gte]", start.timestamp().to_string()),
+            ("created[lt]", end.timestamp().to_string()),
+            ("limit", self.config.page_size.clamp(1, 100).to_string()),
+        ];
+        if let Some(cursor) = starting_after {
+            params.push(("starting_after", cursor.to_string()));
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/v1/balance_transactions", self.config.api_base))
+            .bearer_auth(self.config.secret_key.expose_secret())
+            .query(&params)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Stripe returned {}: {}", status, body));
+        }
+
+        response
+            .json::<BalanceTransactionList>()
+            .await
+            .map_err(|e| format!("Invalid response: {}", e))
+    }
+}
+
+#[async_trait]
+impl PaymentReportProviderTrait for StripeBalanceReportProvider {
+    fn provider_name(&self) -> &str {
+        STRIPE_PROVIDER
+    }
+
+    async fn fetch_transactions(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ProviderTransaction>, String> {
+        let mut transactions = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let page = self.fetch_page(start, end, cursor.as_deref()).await?;
+            cursor = page.data.last().map(|t| t.id.clone());
+
+            for transaction in page.data {
+                transactions.push(transaction.into_provider_transaction()?);
+            }
+
+            if !page.has_more || cursor.is_none() {
+                break;
+            }
+        }
+
+        debug!(count = transactions.len(), "Fetched Stripe balance transactions");
+        Ok(transactions)
+    }
+}
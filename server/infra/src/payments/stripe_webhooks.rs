@@ -0,0 +1,276 @@
+//! Stripe webhook verification and decoding
+//!
+//! Refund, dispute and payment intent confirmation events are decoded;
+//! other event types are ignored.
+//!
+//! Signatures follow Stripe's scheme: the `Stripe-Signature` header carries
+//! a timestamp and one or more `v1` HMAC-SHA256 signatures of
+//! `"{timestamp}.{payload}"` keyed with the endpoint's signing secret.
+//! Requests whose timestamp is outside the tolerance are rejected to limit
+//! replays.
+
+use chrono::{TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use re_core::domain::entities::payment::normalize_currency;
+use re_core::domain::entities::payment_webhook::{ParsedWebhookEvent, PaymentWebhookAction};
+use re_core::services::payment_webhook::PaymentWebhookProviderTrait;
+use re_shared::config::SecretString;
+
+use super::stripe_reports::STRIPE_PROVIDER;
+use crate::InfrastructureError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Stripe webhook configuration
+#[derive(Debug, Clone)]
+pub struct StripeWebhookConfig {
+    /// Signing secret of the webhook endpoint (`whsec_...`)
+    pub signing_secret: SecretString,
+    /// Largest accepted age of a signature in seconds
+    pub tolerance_secs: i64,
+}
+
+impl StripeWebhookConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let signing_secret = std::env::var("STRIPE_WEBHOOK_SECRET")
+            .map_err(|_| InfrastructureError::Config("STRIPE_WEBHOOK_SECRET not set".to_string()))?;
+
+        Ok(Self {
+            signing_secret: SecretString::from(signing_secret),
+            tolerance_secs: std::env::var("STRIPE_WEBHOOK_TOLERANCE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+        })
+    }
+}
+
+/// The envelope of every Stripe event
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    created: i64,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: serde_json::Value,
+}
+
+/// The fields of a Stripe refund object used by the platform
+#[derive(Debug, Deserialize)]
+struct StripeRefund {
+    id: String,
+    amount: i64,
+    currency: String,
+    charge: String,
+    status: Option<String>,
+}
+
+/// The fields of a Stripe dispute object used by the platform
+#[derive(Debug, Deserialize)]
+struct StripeDispute {
+    id: String,
+    amount: i64,
+    currency: String,
+    charge: String,
+    reason: String,
+    status: String,
+}
+
+/// The fields of a Stripe payment intent object used by the platform
+#[derive(Debug, Deserialize)]
+struct StripePaymentIntent {
+    id: String,
+    latest_charge: Option<String>,
+    last_payment_error: Option<StripePaymentError>,
+}
+
+/// Why Stripe declined a payment
+#[derive(Debug, Deserialize)]
+pub(crate) struct StripePaymentError {
+    pub(crate) code: Option<String>,
+    pub(crate) message: Option<String>,
+}
+
+impl StripePaymentError {
+    /// Human-readable reason, falling back to the error code
+    pub(crate) fn reason(&self) -> String {
+        self.message
+            .clone()
+            .or_else(|| self.code.clone())
+            .unwrap_or_else(|| "Payment failed".to_string())
+    }
+}
+
+/// Stripe webhook provider
+pub struct StripeWebhookProvider {
+    config: StripeWebhookConfig,
+}
+
+impl StripeWebhookProvider {
+    /// Create a new Stripe webhook provider
+    pub fn new(config: StripeWebhookConfig) -> Self {
+        Self { config }
+    }
+
+    /// Create a Stripe webhook provider from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Ok(Self::new(StripeWebhookConfig::from_env()?))
+    }
+
+    /// Verify a signature header against the current time
+    fn verify_at(&self, payload: &[u8], header: &str, now: i64) -> Result<(), String> {
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+
+        for part in header.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+                Some(("v1", value)) => signatures.push(value),
+                _ => {}
+            }
+        }
+
+        let timestamp = timestamp.ok_or("Missing timestamp")?;
+        if signatures.is_empty() {
+            return Err("Missing v1 signature".to_string());
+        }
+        if (now - timestamp).abs() > self.config.tolerance_secs {
+            return Err("Timestamp outside the tolerance".to_string());
+        }
+
+        for signature in signatures {
+            let Ok(signature) = hex::decode(signature) else {
+                continue;
+            };
+
+            let mut mac = HmacSha256::new_from_slice(self.config.signing_secret.expose_secret().as_bytes())
+                .map_err(|e| format!("Invalid signing secret: {}", e))?;
+            mac.update(timestamp.to_string().as_bytes());
+            mac.update(b".");
+            mac.update(payload);
+
+            if mac.verify_slice(&signature).is_ok() {
+                return Ok(());
+            }
+        }
+
+        Err("No matching signature".to_string())
+    }
+}
+
+impl PaymentWebhookProviderTrait for StripeWebhookProvider {
+    fn provider_name(&self) -> &str {
+        STRIPE_PROVIDER
+    }
+
+    fn signature_header(&self) -> &str {
+        "Stripe-Signature"
+    }
+
+    fn verify_signature(&self, payload: &[u8], signature: &str) -> Result<(), String> {
+        self.verify_at(payload, signature, Utc::now().timestamp())
+    }
+
+    fn parse_event(&self, payload: &[u8]) -> Result<ParsedWebhookEvent, String> {
+        let raw: serde_json::Value =
+            serde_json::from_slice(payload).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let event: StripeEvent =
+            serde_json::from_value(raw.clone()).map_err(|e| format!("Invalid event: {}", e))?;
+
+        let occurred_at = Utc
+            .timestamp_opt(event.created, 0)
+            .single()
+            .ok_or_else(|| format!("Invalid timestamp on {}", event.id))?;
+
+        let object = event.data.object;
+        let action = match event.event_type.as_str() {
+            "refund.created" => {
+                let refund: StripeRefund =
+                    serde_json::from_value(object).map_err(|e| format!("Invalid refund: {}", e))?;
+
+                if matches!(refund.status.as_deref(), Some("failed" | "canceled")) {
+                    PaymentWebhookAction::Ignored
+                } else {
+                    PaymentWebhookAction::Refund {
+                        payment_reference: refund.charge,
+                        refund_reference: refund.id,
+                        amount: refund.amount,
+                        currency: normalize_currency(&refund.currency)?,
+                    }
+                }
+            }
+            "charge.dispute.created" => {
+                let dispute: StripeDispute =
+                    serde_json::from_value(object).map_err(|e| format!("Invalid dispute: {}", e))?;
+
+                // Inquiries ("warning_*") do not withdraw funds
+                if dispute.status.starts_with("warning_") {
+                    PaymentWebhookAction::Ignored
+                } else {
+                    PaymentWebhookAction::DisputeOpened {
+                        payment_reference: dispute.charge,
+                        dispute_reference: dispute.id,
+                        amount: dispute.amount,
+                        currency: normalize_currency(&dispute.currency)?,
+                        reason: dispute.reason,
+                    }
+                }
+            }
+            "charge.dispute.closed" => {
+                let dispute: StripeDispute =
+                    serde_json::from_value(object).map_err(|e| format!("Invalid dispute: {}", e))?;
+
+                match dispute.status.as_str() {
+                    "won" => PaymentWebhookAction::DisputeClosed {
+                        dispute_reference: dispute.id,
+                        won: true,
+                    },
+                    "lost" => PaymentWebhookAction::DisputeClosed {
+                        dispute_reference: dispute.id,
+                        won: false,
+                    },
+                    _ => PaymentWebhookAction::Ignored,
+                }
+            }
+            "payment_intent.succeeded" => {
+                let intent: StripePaymentIntent =
+                    serde_json::from_value(object).map_err(|e| format!("Invalid payment intent: {}", e))?;
+
+                PaymentWebhookAction::PaymentSucceeded {
+                    intent_reference: intent.id,
+                    charge_reference: intent.latest_charge,
+                }
+            }
+            "payment_intent.payment_failed" => {
+                let intent: StripePaymentIntent =
+                    serde_json::from_value(object).map_err(|e| format!("Invalid payment intent: {}", e))?;
+
+                PaymentWebhookAction::PaymentFailed {
+                    intent_reference: intent.id,
+                    reason: intent
+                        .last_payment_error
+                        .map(|error| error.reason())
+                        .unwrap_or_else(|| "Payment failed".to_string()),
+                }
+            }
+            _ => PaymentWebhookAction::Ignored,
+        };
+
+        Ok(ParsedWebhookEvent {
+            event_id: event.id,
+            event_type: event.event_type,
+            occurred_at,
+            action,
+            payload: raw,
+        })
+    }
+}
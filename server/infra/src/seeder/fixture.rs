@@ -0,0 +1,110 @@
+//! Reading seed fixtures from TOML or JSON files
+
+use serde::Deserialize;
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::user::UserType;
+use re_core::errors::{DomainError, DomainResult};
+
+/// File format of a fixture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureFormat {
+    /// Arrays of tables named `users`, `admins` and `verification_codes`
+    Toml,
+    /// An object with the same arrays
+    Json,
+}
+
+impl FixtureFormat {
+    /// Detect the format from a file name's extension
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Data to seed; every section is optional
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeedFixture {
+    /// Phone-based user accounts
+    #[serde(default)]
+    pub users: Vec<UserFixture>,
+    /// Administrators of the admin portal
+    #[serde(default)]
+    pub admins: Vec<AdminFixture>,
+    /// Verification codes that sign a phone number in without an SMS
+    #[serde(default)]
+    pub verification_codes: Vec<VerificationCodeFixture>,
+}
+
+/// A user account
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserFixture {
+    /// Phone number in E.164
+    pub phone: String,
+    /// `customer` or `worker`; unset users choose at their first login
+    #[serde(default)]
+    pub user_type: Option<UserType>,
+    /// Whether the phone number is verified
+    #[serde(default = "default_verified")]
+    pub verified: bool,
+    /// Whether the account is blocked
+    #[serde(default)]
+    pub blocked: bool,
+}
+
+/// An administrator, keyed by the identity provider's issuer and subject
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdminFixture {
+    /// Issuer URL of the identity provider
+    pub issuer: String,
+    /// Subject identifier at the identity provider
+    pub subject: String,
+    /// Email address
+    #[serde(default)]
+    pub email: Option<String>,
+    /// Display name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Roles granted: `admin`, `support` or `finance`
+    #[serde(default)]
+    pub roles: Vec<AdminRole>,
+}
+
+/// A verification code for a phone number
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerificationCodeFixture {
+    /// Phone number in E.164
+    pub phone: String,
+    /// Six-digit code
+    pub code: String,
+}
+
+/// Seeded users have signed in before unless the fixture says otherwise
+fn default_verified() -> bool {
+    true
+}
+
+/// Read a fixture
+///
+/// # Returns
+/// * `Ok(SeedFixture)` - The fixture
+/// * `Err(DomainError::Validation)` - The fixture is malformed
+pub fn parse_fixture(format: FixtureFormat, content: &str) -> DomainResult<SeedFixture> {
+    match format {
+        FixtureFormat::Toml => toml::from_str(content).map_err(|e| DomainError::Validation {
+            message: format!("Invalid TOML fixture: {}", e),
+        }),
+        FixtureFormat::Json => serde_json::from_str(content).map_err(|e| DomainError::Validation {
+            message: format!("Invalid JSON fixture: {}", e),
+        }),
+    }
+}
@@ -0,0 +1,19 @@
+//! Seed Data Module
+//!
+//! This module loads demo and test data from fixture files, for demo
+//! environments and integration tests.
+//!
+//! ## Features
+//!
+//! - **Fixtures**: Users, administrators and verification codes read from TOML or JSON
+//! - **Seeder**: Idempotent writes through the repositories, so a fixture can be applied again
+
+pub mod fixture;
+pub mod runner;
+
+// Re-export commonly used types
+pub use fixture::{parse_fixture, AdminFixture, FixtureFormat, SeedFixture, UserFixture, VerificationCodeFixture};
+pub use runner::{SeedReport, Seeder};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,193 @@
+//! Writing a seed fixture through the repositories
+
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use re_core::domain::entities::admin::AdminIdentity;
+use re_core::domain::entities::user::User;
+use re_core::domain::entities::verification_code::CODE_LENGTH;
+use re_core::errors::{DomainError, DomainResult};
+use re_core::repositories::{AdminIdentityRepository, UserRepository};
+use re_core::services::auth::{extract_country_code, normalize_to_e164, validate_phone_with_country, PhoneHasher};
+use re_core::services::verification::CacheServiceTrait;
+
+use super::fixture::{SeedFixture, UserFixture};
+
+/// Outcome of seeding a fixture
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SeedReport {
+    /// Users that did not exist yet
+    pub users_created: usize,
+    /// Existing users brought in line with the fixture
+    pub users_updated: usize,
+    /// Administrators created or updated
+    pub admins_saved: usize,
+    /// Verification codes stored
+    pub verification_codes_stored: usize,
+    /// Sections that could not be seeded
+    pub errors: Vec<String>,
+}
+
+/// Seeds users, administrators and verification codes from a fixture
+///
+/// Seeding is idempotent: users are matched by phone number and
+/// administrators by issuer and subject, so a fixture can be applied again
+/// to reset the demo data it describes. Administrators and verification
+/// codes are only seeded when their store is attached.
+pub struct Seeder {
+    /// Repository the users are written to
+    users: Arc<dyn UserRepository>,
+    /// Hashes phone numbers like the auth service does
+    phone_hasher: PhoneHasher,
+    /// Repository the administrators are written to
+    admins: Option<Arc<dyn AdminIdentityRepository>>,
+    /// Store the verification codes are written to
+    verification_codes: Option<Arc<dyn CacheServiceTrait>>,
+}
+
+impl Seeder {
+    /// Create a seeder writing users only
+    pub fn new(users: Arc<dyn UserRepository>, phone_hasher: PhoneHasher) -> Self {
+        Self {
+            users,
+            phone_hasher,
+            admins: None,
+            verification_codes: None,
+        }
+    }
+
+    /// Seed administrators into the given repository
+    pub fn with_admin_repository(mut self, admins: Arc<dyn AdminIdentityRepository>) -> Self {
+        self.admins = Some(admins);
+        self
+    }
+
+    /// Seed verification codes into the given store
+    ///
+    /// Codes expire like codes sent by SMS, so they are seeded right before
+    /// the demo or test that uses them.
+    pub fn with_verification_store(mut self, verification_codes: Arc<dyn CacheServiceTrait>) -> Self {
+        self.verification_codes = Some(verification_codes);
+        self
+    }
+
+    /// Seed every section of a fixture
+    ///
+    /// The fixture is validated before anything is written.
+    ///
+    /// # Returns
+    /// * `Ok(SeedReport)` - What was seeded; sections without a store are
+    ///   reported in `errors`
+    /// * `Err(DomainError::Validation)` - The fixture holds an invalid entry
+    /// * `Err(DomainError)` - A write failed; entries before it were seeded
+    pub async fn seed(&self, fixture: &SeedFixture) -> DomainResult<SeedReport> {
+        let users = fixture
+            .users
+            .iter()
+            .map(|user| Ok((normalize_phone(&user.phone)?, user)))
+            .collect::<DomainResult<Vec<_>>>()?;
+        let codes = fixture
+            .verification_codes
+            .iter()
+            .map(|entry| {
+                if entry.code.len() != CODE_LENGTH || !entry.code.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(DomainError::Validation {
+                        message: format!("Verification codes must be {} digits", CODE_LENGTH),
+                    });
+                }
+                Ok((normalize_phone(&entry.phone)?, entry.code.as_str()))
+            })
+            .collect::<DomainResult<Vec<_>>>()?;
+
+        let mut report = SeedReport::default();
+
+        for (phone, fixture) in users {
+            if self.seed_user(&phone, fixture).await? {
+                report.users_created += 1;
+            } else {
+                report.users_updated += 1;
+            }
+        }
+
+        match (&self.admins, fixture.admins.is_empty()) {
+            (_, true) => {}
+            (Some(admins), false) => {
+                for entry in &fixture.admins {
+                    let mut admin = admins
+                        .find_by_subject(&entry.issuer, &entry.subject)
+                        .await?
+                        .unwrap_or_else(|| AdminIdentity::new(entry.issuer.clone(), entry.subject.clone()));
+                    admin.record_login(entry.email.clone(), entry.name.clone(), entry.roles.clone());
+                    admins.save(admin).await?;
+                    report.admins_saved += 1;
+                }
+            }
+            (None, false) => report
+                .errors
+                .push(format!("Skipped {} administrators: no admin repository", fixture.admins.len())),
+        }
+
+        match (&self.verification_codes, codes.is_empty()) {
+            (_, true) => {}
+            (Some(store), false) => {
+                for (phone, code) in codes {
+                    store
+                        .store_code(&phone, code)
+                        .await
+                        .map_err(|message| DomainError::Internal { message })?;
+                    report.verification_codes_stored += 1;
+                }
+            }
+            (None, false) => report
+                .errors
+                .push(format!("Skipped {} verification codes: no verification store", codes.len())),
+        }
+
+        for error in &report.errors {
+            warn!("{}", error);
+        }
+        info!(
+            "Seeded {} new users, {} existing users, {} administrators and {} verification codes",
+            report.users_created, report.users_updated, report.admins_saved, report.verification_codes_stored
+        );
+
+        Ok(report)
+    }
+
+    /// Create or update a user
+    ///
+    /// # Returns
+    /// Whether the user was created
+    async fn seed_user(&self, phone: &str, fixture: &UserFixture) -> DomainResult<bool> {
+        let (country_code, local_phone) = extract_country_code(phone);
+        let phone_hash = self.phone_hasher.hash(&local_phone);
+
+        let existing = self.users.find_by_phone(&phone_hash, &country_code).await?;
+        let created = existing.is_none();
+        let mut user = existing.unwrap_or_else(|| {
+            let mut user = User::new(phone_hash, country_code);
+            user.phone_hash_version = self.phone_hasher.current_version();
+            user
+        });
+        user.user_type = fixture.user_type;
+        user.is_verified = fixture.verified;
+        user.is_blocked = fixture.blocked;
+
+        if created {
+            self.users.create(user).await?;
+        } else {
+            self.users.update(user).await?;
+        }
+        Ok(created)
+    }
+}
+
+/// Normalize a fixture phone number to E.164
+fn normalize_phone(phone: &str) -> DomainResult<String> {
+    normalize_to_e164(phone, None)
+        .filter(|phone| validate_phone_with_country(phone))
+        .ok_or_else(|| DomainError::Validation {
+            message: format!("Invalid phone number in fixture: {}", phone),
+        })
+}
@@ -0,0 +1,84 @@
+//! Unit tests for reading seed fixtures
+
+use re_core::domain::entities::admin::AdminRole;
+use re_core::domain::entities::user::UserType;
+use re_core::errors::DomainError;
+
+use crate::seeder::{parse_fixture, FixtureFormat};
+
+#[test]
+fn test_format_from_path() {
+    assert_eq!(FixtureFormat::from_path("fixtures/demo.toml"), Some(FixtureFormat::Toml));
+    assert_eq!(FixtureFormat::from_path("demo.JSON"), Some(FixtureFormat::Json));
+    assert_eq!(FixtureFormat::from_path("demo.yaml"), None);
+    assert_eq!(FixtureFormat::from_path("demo"), None);
+}
+
+#[test]
+fn test_parse_toml_fixture() {
+    let fixture = parse_fixture(
+        FixtureFormat::Toml,
+        r#"
+        [[users]]
+        phone = "+61412345678"
+        user_type = "worker"
+
+        [[users]]
+        phone = "+8613812345678"
+        blocked = true
+
+        [[admins]]
+        issuer = "https://login.example.com"
+        subject = "demo-admin"
+        roles = ["admin", "finance"]
+
+        [[verification_codes]]
+        phone = "+61412345678"
+        code = "123456"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(fixture.users.len(), 2);
+    assert_eq!(fixture.users[0].user_type, Some(UserType::Worker));
+    assert!(fixture.users[0].verified);
+    assert!(!fixture.users[0].blocked);
+    assert_eq!(fixture.users[1].user_type, None);
+    assert!(fixture.users[1].blocked);
+    assert_eq!(fixture.admins[0].roles, vec![AdminRole::Admin, AdminRole::Finance]);
+    assert_eq!(fixture.admins[0].email, None);
+    assert_eq!(fixture.verification_codes[0].code, "123456");
+}
+
+#[test]
+fn test_parse_json_fixture_with_missing_sections() {
+    let fixture = parse_fixture(
+        FixtureFormat::Json,
+        r#"{"users": [{"phone": "+61412345678", "user_type": "customer", "verified": false}]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(fixture.users.len(), 1);
+    assert_eq!(fixture.users[0].user_type, Some(UserType::Customer));
+    assert!(!fixture.users[0].verified);
+    assert!(fixture.admins.is_empty());
+    assert!(fixture.verification_codes.is_empty());
+}
+
+#[test]
+fn test_parse_rejects_unknown_fields() {
+    let result = parse_fixture(FixtureFormat::Toml, "[[users]]\nphone = \"+61412345678\"\nrole = \"worker\"\n");
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    let result = parse_fixture(FixtureFormat::Json, r#"{"orders": []}"#);
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
+
+#[test]
+fn test_parse_rejects_unknown_roles() {
+    let result = parse_fixture(
+        FixtureFormat::Toml,
+        "[[admins]]\nissuer = \"https://login.example.com\"\nsubject = \"a\"\nroles = [\"owner\"]\n",
+    );
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+}
@@ -0,0 +1,6 @@
+//! Unit tests for the seeder module
+
+#[cfg(test)]
+mod fixture_tests;
+#[cfg(test)]
+mod runner_tests;
@@ -0,0 +1,260 @@
+//! Unit tests for seeding fixtures
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use re_core::domain::entities::admin::{AdminIdentity, AdminRole};
+use re_core::domain::entities::user::{User, UserType};
+use re_core::errors::DomainError;
+use re_core::repositories::{AdminIdentityRepository, UserRepository};
+use re_core::services::auth::PhoneHasher;
+use re_core::services::verification::CacheServiceTrait;
+
+use crate::seeder::{parse_fixture, FixtureFormat, SeedFixture, Seeder};
+
+#[derive(Default)]
+struct MockUserRepository {
+    users: Mutex<Vec<User>>,
+}
+
+#[async_trait]
+impl UserRepository for MockUserRepository {
+    async fn find_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<Option<User>, DomainError> {
+        Ok(self
+            .users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| user.phone_hash == phone_hash && user.country_code == country_code)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, DomainError> {
+        Ok(self.users.lock().unwrap().iter().find(|user| user.id == id).cloned())
+    }
+
+    async fn create(&self, user: User) -> Result<User, DomainError> {
+        self.users.lock().unwrap().push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(&self, user: User) -> Result<User, DomainError> {
+        let mut users = self.users.lock().unwrap();
+        let existing = users
+            .iter_mut()
+            .find(|existing| existing.id == user.id)
+            .ok_or_else(|| DomainError::NotFound { resource: "User".to_string() })?;
+        *existing = user.clone();
+        Ok(user)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
+        let mut users = self.users.lock().unwrap();
+        let before = users.len();
+        users.retain(|user| user.id != id);
+        Ok(users.len() < before)
+    }
+
+    async fn exists_by_phone(&self, phone_hash: &str, country_code: &str) -> Result<bool, DomainError> {
+        Ok(self.find_by_phone(phone_hash, country_code).await?.is_some())
+    }
+
+    async fn count_by_type(&self, _user_type: Option<UserType>) -> Result<u64, DomainError> {
+        Ok(self.users.lock().unwrap().len() as u64)
+    }
+}
+
+#[derive(Default)]
+struct MockAdminIdentityRepository {
+    admins: Mutex<Vec<AdminIdentity>>,
+}
+
+#[async_trait]
+impl AdminIdentityRepository for MockAdminIdentityRepository {
+    async fn find_by_subject(&self, issuer: &str, subject: &str) -> Result<Option<AdminIdentity>, DomainError> {
+        Ok(self
+            .admins
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|admin| admin.issuer == issuer && admin.subject == subject)
+            .cloned())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<AdminIdentity>, DomainError> {
+        Ok(self.admins.lock().unwrap().iter().find(|admin| admin.id == id).cloned())
+    }
+
+    async fn save(&self, identity: AdminIdentity) -> Result<AdminIdentity, DomainError> {
+        let mut admins = self.admins.lock().unwrap();
+        admins.retain(|admin| admin.id != identity.id);
+        admins.push(identity.clone());
+        Ok(identity)
+    }
+}
+
+#[derive(Default)]
+struct MockVerificationStore {
+    codes: Mutex<HashMap<String, String>>,
+}
+
+#[async_trait]
+impl CacheServiceTrait for MockVerificationStore {
+    async fn store_code(&self, phone: &str, code: &str) -> Result<(), String> {
+        self.codes.lock().unwrap().insert(phone.to_string(), code.to_string());
+        Ok(())
+    }
+
+    async fn verify_code(&self, phone: &str, code: &str) -> Result<bool, String> {
+        Ok(self.codes.lock().unwrap().get(phone).map(String::as_str) == Some(code))
+    }
+
+    async fn get_remaining_attempts(&self, _phone: &str) -> Result<i64, String> {
+        Ok(3)
+    }
+
+    async fn code_exists(&self, phone: &str) -> Result<bool, String> {
+        Ok(self.codes.lock().unwrap().contains_key(phone))
+    }
+
+    async fn get_code_ttl(&self, _phone: &str) -> Result<Option<i64>, String> {
+        Ok(Some(300))
+    }
+
+    async fn clear_verification(&self, phone: &str) -> Result<(), String> {
+        self.codes.lock().unwrap().remove(phone);
+        Ok(())
+    }
+}
+
+fn demo_fixture() -> SeedFixture {
+    parse_fixture(
+        FixtureFormat::Toml,
+        r#"
+        [[users]]
+        phone = "+61412345678"
+        user_type = "worker"
+
+        [[users]]
+        phone = "+8613812345678"
+        user_type = "customer"
+        blocked = true
+
+        [[admins]]
+        issuer = "https://login.example.com"
+        subject = "demo-admin"
+        email = "admin@example.com"
+        roles = ["admin"]
+
+        [[verification_codes]]
+        phone = "+61412345678"
+        code = "123456"
+        "#,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_seed_creates_every_section() {
+    let users = Arc::new(MockUserRepository::default());
+    let admins = Arc::new(MockAdminIdentityRepository::default());
+    let codes = Arc::new(MockVerificationStore::default());
+    let seeder = Seeder::new(users.clone(), PhoneHasher::default())
+        .with_admin_repository(admins.clone())
+        .with_verification_store(codes.clone());
+
+    let report = seeder.seed(&demo_fixture()).await.unwrap();
+
+    assert_eq!(report.users_created, 2);
+    assert_eq!(report.users_updated, 0);
+    assert_eq!(report.admins_saved, 1);
+    assert_eq!(report.verification_codes_stored, 1);
+    assert!(report.errors.is_empty());
+
+    // Users are stored like the auth service stores them
+    let worker = users
+        .find_by_phone(&PhoneHasher::default().hash("412345678"), "+61")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(worker.user_type, Some(UserType::Worker));
+    assert!(worker.is_verified);
+    assert!(!worker.is_blocked);
+    let customer = users
+        .find_by_phone(&PhoneHasher::default().hash("13812345678"), "+86")
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(customer.is_blocked);
+
+    let admin = admins
+        .find_by_subject("https://login.example.com", "demo-admin")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(admin.roles, vec![AdminRole::Admin]);
+    assert_eq!(admin.email.as_deref(), Some("admin@example.com"));
+
+    assert!(codes.verify_code("+61412345678", "123456").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_seed_is_idempotent() {
+    let users = Arc::new(MockUserRepository::default());
+    let admins = Arc::new(MockAdminIdentityRepository::default());
+    let seeder = Seeder::new(users.clone(), PhoneHasher::default()).with_admin_repository(admins.clone());
+    let mut fixture = demo_fixture();
+    fixture.verification_codes.clear();
+
+    seeder.seed(&fixture).await.unwrap();
+    let admin_id = admins.admins.lock().unwrap()[0].id;
+
+    fixture.users[0].blocked = true;
+    fixture.admins[0].roles = vec![AdminRole::Support];
+    let report = seeder.seed(&fixture).await.unwrap();
+
+    assert_eq!(report.users_created, 0);
+    assert_eq!(report.users_updated, 2);
+    assert_eq!(users.users.lock().unwrap().len(), 2);
+    assert!(users.users.lock().unwrap().iter().all(|user| user.is_blocked));
+
+    let admins = admins.admins.lock().unwrap();
+    assert_eq!(admins.len(), 1);
+    assert_eq!(admins[0].id, admin_id);
+    assert_eq!(admins[0].roles, vec![AdminRole::Support]);
+}
+
+#[tokio::test]
+async fn test_seed_reports_sections_without_store() {
+    let users = Arc::new(MockUserRepository::default());
+    let seeder = Seeder::new(users.clone(), PhoneHasher::default());
+
+    let report = seeder.seed(&demo_fixture()).await.unwrap();
+
+    assert_eq!(report.users_created, 2);
+    assert_eq!(report.admins_saved, 0);
+    assert_eq!(report.verification_codes_stored, 0);
+    assert_eq!(report.errors.len(), 2);
+}
+
+#[tokio::test]
+async fn test_seed_validates_before_writing() {
+    let users = Arc::new(MockUserRepository::default());
+    let codes = Arc::new(MockVerificationStore::default());
+    let seeder = Seeder::new(users.clone(), PhoneHasher::default()).with_verification_store(codes.clone());
+
+    let mut fixture = demo_fixture();
+    fixture.verification_codes[0].code = "12ab".to_string();
+    let result = seeder.seed(&fixture).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    let mut fixture = demo_fixture();
+    fixture.users[1].phone = "12345".to_string();
+    let result = seeder.seed(&fixture).await;
+    assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+    assert!(users.users.lock().unwrap().is_empty());
+    assert!(codes.codes.lock().unwrap().is_empty());
+}
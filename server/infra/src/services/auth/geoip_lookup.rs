@@ -0,0 +1,237 @@
+//! Geo-IP country lookup backed by MaxMind GeoLite2/GeoIP2 Country CSV files
+//!
+//! The CSV databases are loaded into memory as sorted address ranges and
+//! searched with a binary search, so lookups never leave the process. The
+//! files are the `Blocks-IPv4`, `Blocks-IPv6` and `Locations-en` files of
+//! the Country edition.
+
+use async_trait::async_trait;
+use ipnetwork::IpNetwork;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
+use tracing::info;
+
+use re_core::services::auth::GeoIpLookupTrait;
+
+use crate::InfrastructureError;
+
+/// Geo-IP database configuration
+#[derive(Debug, Clone)]
+pub struct GeoIpConfig {
+    /// Path to the `GeoLite2-Country-Blocks-IPv4.csv` file
+    pub blocks_ipv4_path: String,
+    /// Path to the `GeoLite2-Country-Blocks-IPv6.csv` file, if IPv6 should be resolved
+    pub blocks_ipv6_path: Option<String>,
+    /// Path to the `GeoLite2-Country-Locations-en.csv` file
+    pub locations_path: String,
+}
+
+impl GeoIpConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let blocks_ipv4_path = std::env::var("GEOIP_BLOCKS_IPV4_PATH")
+            .map_err(|_| InfrastructureError::Config("GEOIP_BLOCKS_IPV4_PATH not set".to_string()))?;
+        let locations_path = std::env::var("GEOIP_LOCATIONS_PATH")
+            .map_err(|_| InfrastructureError::Config("GEOIP_LOCATIONS_PATH not set".to_string()))?;
+
+        Ok(Self {
+            blocks_ipv4_path,
+            blocks_ipv6_path: std::env::var("GEOIP_BLOCKS_IPV6_PATH").ok(),
+            locations_path,
+        })
+    }
+}
+
+/// A contiguous address range mapped to a country
+#[derive(Debug, Clone)]
+struct CountryRange<T> {
+    start: T,
+    end: T,
+    country: String,
+}
+
+/// In-memory geo-IP country database
+pub struct GeoLite2CountryLookup {
+    ipv4: Vec<CountryRange<u32>>,
+    ipv6: Vec<CountryRange<u128>>,
+}
+
+impl GeoLite2CountryLookup {
+    /// Load the database from the configured CSV files
+    pub fn from_config(config: &GeoIpConfig) -> Result<Self, InfrastructureError> {
+        let locations = read_file(&config.locations_path)?;
+        let blocks_ipv4 = read_file(&config.blocks_ipv4_path)?;
+        let blocks_ipv6 = match &config.blocks_ipv6_path {
+            Some(path) => read_file(path)?,
+            None => String::new(),
+        };
+
+        let lookup = Self::from_csv(&blocks_ipv4, &blocks_ipv6, &locations)?;
+        info!(
+            ipv4_ranges = lookup.ipv4.len(),
+            ipv6_ranges = lookup.ipv6.len(),
+            "Loaded geo-IP country database"
+        );
+
+        Ok(lookup)
+    }
+
+    /// Load the database from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Self::from_config(&GeoIpConfig::from_env()?)
+    }
+
+    /// Build the database from CSV contents
+    ///
+    /// Blocks without a country fall back to their registered country;
+    /// blocks with neither (e.g. satellite providers) are skipped.
+    pub fn from_csv(
+        blocks_ipv4: &str,
+        blocks_ipv6: &str,
+        locations: &str,
+    ) -> Result<Self, InfrastructureError> {
+        let countries = parse_locations(locations)?;
+
+        let mut ipv4 = Vec::new();
+        let mut ipv6 = Vec::new();
+
+        for (network, country) in parse_blocks(blocks_ipv4, &countries)?
+            .into_iter()
+            .chain(parse_blocks(blocks_ipv6, &countries)?)
+        {
+            match network {
+                IpNetwork::V4(net) => ipv4.push(CountryRange {
+                    start: u32::from(net.network()),
+                    end: u32::from(net.broadcast()),
+                    country,
+                }),
+                IpNetwork::V6(net) => {
+                    let start = u128::from(net.network());
+                    let end = start | u128::MAX.checked_shr(u32::from(net.prefix())).unwrap_or(0);
+                    ipv6.push(CountryRange { start, end, country });
+                }
+            }
+        }
+
+        ipv4.sort_by_key(|r| r.start);
+        ipv6.sort_by_key(|r| r.start);
+
+        Ok(Self { ipv4, ipv6 })
+    }
+
+    /// Find the country of an address
+    pub fn country_of(&self, ip: IpAddr) -> Option<&str> {
+        match ip {
+            IpAddr::V4(ip) => find_range(&self.ipv4, u32::from(ip)),
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ip) => find_range(&self.ipv4, u32::from(ip)),
+                None => find_range(&self.ipv6, u128::from(ip)),
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl GeoIpLookupTrait for GeoLite2CountryLookup {
+    async fn lookup_country(&self, ip: IpAddr) -> Result<Option<String>, String> {
+        Ok(self.country_of(ip).map(str::to_string))
+    }
+}
+
+/// Binary search for the range containing `value`
+fn find_range<T: Ord + Copy>(ranges: &[CountryRange<T>], value: T) -> Option<&str> {
+    let index = ranges.partition_point(|r| r.start <= value);
+    let range = ranges.get(index.checked_sub(1)?)?;
+    (value <= range.end).then_some(range.country.as_str())
+}
+
+fn read_file(path: &str) -> Result<String, InfrastructureError> {
+    std::fs::read_to_string(Path::new(path))
+        .map_err(|e| InfrastructureError::Config(format!("Failed to read {}: {}", path, e)))
+}
+
+/// Parse the locations file into a map of geoname ID to country code
+fn parse_locations(csv: &str) -> Result<HashMap<String, String>, InfrastructureError> {
+    let mut lines = csv.lines();
+    let header = split_csv_line(lines.next().unwrap_or_default());
+    let geoname_col = column(&header, "geoname_id")?;
+    let country_col = column(&header, "country_iso_code")?;
+
+    let mut countries = HashMap::new();
+    for line in lines.filter(|l| !l.trim().is_empty()) {
+        let fields = split_csv_line(line);
+        if let (Some(id), Some(country)) = (fields.get(geoname_col), fields.get(country_col)) {
+            if !country.is_empty() {
+                countries.insert(id.clone(), country.to_ascii_uppercase());
+            }
+        }
+    }
+
+    Ok(countries)
+}
+
+/// Parse a blocks file into networks and their country codes
+fn parse_blocks(
+    csv: &str,
+    countries: &HashMap<String, String>,
+) -> Result<Vec<(IpNetwork, String)>, InfrastructureError> {
+    let mut lines = csv.lines();
+    let Some(header) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let header = split_csv_line(header);
+    let network_col = column(&header, "network")?;
+    let geoname_col = column(&header, "geoname_id")?;
+    let registered_col = column(&header, "registered_country_geoname_id")?;
+
+    let mut blocks = Vec::new();
+    for line in lines.filter(|l| !l.trim().is_empty()) {
+        let fields = split_csv_line(line);
+        let field = |col: usize| fields.get(col).map(String::as_str).unwrap_or_default();
+
+        let network: IpNetwork = field(network_col).parse().map_err(|e| {
+            InfrastructureError::Config(format!("Invalid network '{}': {}", field(network_col), e))
+        })?;
+
+        let country = countries
+            .get(field(geoname_col))
+            .or_else(|| countries.get(field(registered_col)));
+
+        if let Some(country) = country {
+            blocks.push((network, country.clone()));
+        }
+    }
+
+    Ok(blocks)
+}
+
+fn column(header: &[String], name: &str) -> Result<usize, InfrastructureError> {
+    header
+        .iter()
+        .position(|h| h == name)
+        .ok_or_else(|| InfrastructureError::Config(format!("Geo-IP CSV is missing column {}", name)))
+}
+
+/// Split a CSV line, honouring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
@@ -0,0 +1,94 @@
+//! Redis-backed storage for IP allowlists and denylists
+//!
+//! Each list is a Redis set of normalized CIDR ranges
+//! (`ip_access:{list}`) with the entry details kept in a companion hash
+//! (`ip_access:{list}:entries`). Both are updated in one transaction.
+
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+
+use re_core::services::auth::{IpAccessEntry, IpAccessList, IpAccessStoreTrait};
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the IP access store
+pub struct RedisIpAccessStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisIpAccessStore {
+    /// Create a new Redis-based IP access store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    fn set_key(list: IpAccessList) -> String {
+        format!("ip_access:{}", list.as_str())
+    }
+
+    fn entries_key(list: IpAccessList) -> String {
+        format!("ip_access:{}:entries", list.as_str())
+    }
+}
+
+#[async_trait]
+impl IpAccessStoreTrait for RedisIpAccessStore {
+    async fn add_entry(&self, list: IpAccessList, entry: &IpAccessEntry) -> Result<(), String> {
+        let value = serde_json::to_string(entry)
+            .map_err(|e| format!("Failed to serialize IP access entry: {}", e))?;
+        let mut conn = self.redis_client.get_connection();
+
+        redis::pipe()
+            .atomic()
+            .sadd(self.redis_client.key(&Self::set_key(list)), &entry.cidr)
+            .hset(self.redis_client.key(&Self::entries_key(list)), &entry.cidr, value)
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to add IP access entry: {}", e))
+    }
+
+    async fn remove_entry(&self, list: IpAccessList, cidr: &str) -> Result<bool, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let (removed, _): (i64, i64) = redis::pipe()
+            .atomic()
+            .srem(self.redis_client.key(&Self::set_key(list)), cidr)
+            .hdel(self.redis_client.key(&Self::entries_key(list)), cidr)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to remove IP access entry: {}", e))?;
+
+        Ok(removed > 0)
+    }
+
+    async fn list_entries(&self, list: IpAccessList) -> Result<Vec<IpAccessEntry>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let cidrs: Vec<String> = conn
+            .smembers(self.redis_client.key(&Self::set_key(list)))
+            .await
+            .map_err(|e| format!("Failed to list IP access entries: {}", e))?;
+        let details: HashMap<String, String> = conn
+            .hgetall(self.redis_client.key(&Self::entries_key(list)))
+            .await
+            .map_err(|e| format!("Failed to load IP access entries: {}", e))?;
+
+        let entries = cidrs
+            .into_iter()
+            .filter_map(|cidr| {
+                let entry = details
+                    .get(&cidr)
+                    .and_then(|value| serde_json::from_str::<IpAccessEntry>(value).ok());
+                if entry.is_none() {
+                    warn!(cidr = %cidr, list = list.as_str(), "IP access entry has no details");
+                }
+                entry
+            })
+            .collect();
+
+        Ok(entries)
+    }
+}
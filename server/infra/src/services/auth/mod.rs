@@ -1,10 +1,26 @@
 //! Authentication-related infrastructure services
 
+pub mod geoip_lookup;
+pub mod ip_access_store;
+pub mod oidc_provider;
+pub mod proof_of_work_store;
 pub mod rate_limiter;
+pub mod security_webhook_sender;
+pub mod session_activity_store;
+pub mod sso_login_store;
+pub mod token_revocation_channel;
 
+pub use geoip_lookup::{GeoIpConfig, GeoLite2CountryLookup};
+pub use ip_access_store::RedisIpAccessStore;
+pub use oidc_provider::HttpOidcProvider;
+pub use proof_of_work_store::RedisProofOfWorkStore;
 pub use rate_limiter::{
     RedisRateLimiter, 
     RateLimitStatus, 
     RateLimitInfo,
     LimitInfo,
-};
\ No newline at end of file
+};
+pub use security_webhook_sender::HttpWebhookSender;
+pub use session_activity_store::RedisSessionActivityStore;
+pub use sso_login_store::RedisSsoLoginStore;
+pub use token_revocation_channel::RedisTokenRevocationChannel;
@@ -0,0 +1,230 @@
+//! OpenID Connect provider for admin single sign-on
+//!
+//! Works with any standards-compliant provider such as Okta or Azure AD.
+//! Endpoints are read from the issuer's discovery document. ID tokens are
+//! verified against the provider's published signing keys, which are cached
+//! and refetched when a token is signed with a key not seen before, so
+//! provider key rotation needs no restart. Concurrent logins needing the
+//! same document share a single request to the provider.
+
+use async_trait::async_trait;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use re_core::services::admin_sso::{OidcIdentity, OidcProviderTrait};
+use re_core::services::singleflight::SingleFlight;
+use re_shared::config::auth::AdminSsoConfig;
+
+use crate::InfrastructureError;
+
+/// Timeout for requests to the identity provider, in seconds
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// The fields of the discovery document used by the platform
+#[derive(Debug, Clone, Deserialize)]
+struct Discovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+/// The fields of the token response used by the platform
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+/// OpenID Connect identity provider
+pub struct HttpOidcProvider {
+    client: reqwest::Client,
+    config: AdminSsoConfig,
+    discovery: RwLock<Option<Discovery>>,
+    keys: RwLock<Option<JwkSet>>,
+    /// Discovery requests in flight
+    discovery_fetches: SingleFlight<(), Discovery, String>,
+    /// Key set requests in flight, by key set URI
+    key_fetches: SingleFlight<String, JwkSet, String>,
+}
+
+impl HttpOidcProvider {
+    /// Create a new OpenID Connect provider
+    pub fn new(config: AdminSsoConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            config,
+            discovery: RwLock::new(None),
+            keys: RwLock::new(None),
+            discovery_fetches: SingleFlight::new(),
+            key_fetches: SingleFlight::new(),
+        })
+    }
+
+    /// Fetch the discovery document once and keep it
+    async fn discovery(&self) -> Result<Discovery, String> {
+        if let Some(discovery) = self.discovery.read().await.as_ref() {
+            return Ok(discovery.clone());
+        }
+
+        self.discovery_fetches.run((), self.fetch_discovery()).await
+    }
+
+    /// Request the discovery document and keep it
+    async fn fetch_discovery(&self) -> Result<Discovery, String> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.config.issuer_url.trim_end_matches('/')
+        );
+        let discovery: Discovery = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Discovery request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid discovery document: {}", e))?;
+
+        *self.discovery.write().await = Some(discovery.clone());
+        Ok(discovery)
+    }
+
+    /// Find the signing key of an ID token, refetching the key set for unknown keys
+    async fn signing_key(&self, jwks_uri: &str, kid: &str) -> Result<DecodingKey, String> {
+        if let Some(jwk) = self.keys.read().await.as_ref().and_then(|keys| keys.find(kid)) {
+            return DecodingKey::from_jwk(jwk).map_err(|e| format!("Unusable signing key: {}", e));
+        }
+
+        let keys = self
+            .key_fetches
+            .run(jwks_uri.to_string(), self.fetch_keys(jwks_uri))
+            .await?;
+        keys.find(kid)
+            .ok_or_else(|| format!("Unknown signing key {}", kid))
+            .and_then(|jwk| DecodingKey::from_jwk(jwk).map_err(|e| format!("Unusable signing key: {}", e)))
+    }
+
+    /// Request the provider's key set and keep it
+    async fn fetch_keys(&self, jwks_uri: &str) -> Result<JwkSet, String> {
+        let keys: JwkSet = self
+            .client
+            .get(jwks_uri)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("Key set request failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid key set: {}", e))?;
+        debug!(keys = keys.keys.len(), "Fetched identity provider signing keys");
+
+        *self.keys.write().await = Some(keys.clone());
+        Ok(keys)
+    }
+}
+
+/// Read the groups claim, which providers send as an array or a single string
+fn groups_from_claims(claims: &Map<String, Value>, groups_claim: &str) -> Vec<String> {
+    match claims.get(groups_claim) {
+        Some(Value::Array(groups)) => groups
+            .iter()
+            .filter_map(|group| group.as_str().map(str::to_string))
+            .collect(),
+        Some(Value::String(group)) => vec![group.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// Read an optional string claim
+fn string_claim(claims: &Map<String, Value>, name: &str) -> Option<String> {
+    claims.get(name).and_then(Value::as_str).map(str::to_string)
+}
+
+#[async_trait]
+impl OidcProviderTrait for HttpOidcProvider {
+    async fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> Result<String, String> {
+        let discovery = self.discovery().await?;
+        let scope = self.config.scopes.join(" ");
+
+        let url = reqwest::Url::parse_with_params(
+            &discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", self.config.client_id.as_str()),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("scope", scope.as_str()),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| format!("Invalid authorization endpoint: {}", e))?;
+
+        Ok(url.into())
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> Result<OidcIdentity, String> {
+        let discovery = self.discovery().await?;
+
+        let response = self
+            .client
+            .post(&discovery.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.expose_secret()),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("Token request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Token request rejected with {}: {}", status, body));
+        }
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid token response: {}", e))?;
+
+        let header = decode_header(&token.id_token).map_err(|e| format!("Invalid ID token: {}", e))?;
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err("ID tokens signed with a shared secret are not accepted".to_string());
+        }
+        let kid = header.kid.ok_or("ID token has no key ID")?;
+        let key = self.signing_key(&discovery.jwks_uri, &kid).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&discovery.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = decode::<Map<String, Value>>(&token.id_token, &key, &validation)
+            .map_err(|e| format!("ID token rejected: {}", e))?
+            .claims;
+
+        Ok(OidcIdentity {
+            issuer: discovery.issuer,
+            subject: string_claim(&claims, "sub").ok_or("ID token has no subject")?,
+            email: string_claim(&claims, "email"),
+            name: string_claim(&claims, "name"),
+            groups: groups_from_claims(&claims, &self.config.groups_claim),
+            nonce: string_claim(&claims, "nonce"),
+        })
+    }
+}
@@ -0,0 +1,48 @@
+//! Redis-backed store of issued proof-of-work challenges
+//!
+//! The difficulty of each challenge is kept under `pow:{nonce}` until it
+//! expires or a solution is submitted. It is read with GETDEL, so a
+//! challenge can only be used for one send-code request.
+
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use std::sync::Arc;
+
+use re_core::services::auth::ProofOfWorkStoreTrait;
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the proof-of-work challenge store
+pub struct RedisProofOfWorkStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisProofOfWorkStore {
+    /// Create a new Redis-based challenge store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    fn key(nonce: &str) -> String {
+        format!("pow:{}", nonce)
+    }
+}
+
+#[async_trait]
+impl ProofOfWorkStoreTrait for RedisProofOfWorkStore {
+    async fn save(&self, nonce: &str, difficulty: u8, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+
+        conn.set_ex::<_, _, ()>(self.redis_client.key(&Self::key(nonce)), difficulty, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to store proof-of-work challenge: {}", e))
+    }
+
+    async fn take(&self, nonce: &str) -> Result<Option<u8>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        conn.get_del(self.redis_client.key(&Self::key(nonce)))
+            .await
+            .map_err(|e| format!("Failed to load proof-of-work challenge: {}", e))
+    }
+}
@@ -1,15 +1,78 @@
 //! Redis-based rate limiter implementation for authentication services
+//!
+//! SMS sends and verification attempts are counted with the algorithm
+//! selected in `RateLimitConfig`:
+//! - Fixed window: a counter per calendar window, cheapest but a burst at
+//!   a window boundary gets through twice the limit
+//! - Sliding window log: a sorted set with the timestamp of every request
+//!   made during the last window
+//! - Token bucket: a hash with the tokens left and when they were counted,
+//!   refilled continuously at the limit per window
+//!
+//! Checking a limit never counts a request; requests are counted once
+//! they were served. Sliding window logs and token buckets are updated by
+//! Lua scripts so concurrent requests on different instances can't both
+//! take the last slot between a read and a write.
+//!
+//! Keys are put under the key prefix of the Redis client.
 
 use async_trait::async_trait;
 use chrono::Utc;
 use redis::AsyncCommands;
 use std::sync::Arc;
 use tracing::warn;
+use uuid::Uuid;
 
 use re_core::{DomainError, DomainResult};
 use re_core::RateLimiterTrait;
+use re_shared::config::rate_limit::RateLimitAlgorithm;
 use re_shared::RateLimitConfig;
 
+/// Window of the SMS and verification limits, in seconds
+const LIMIT_WINDOW_SECONDS: u64 = 3600;
+
+/// Drops requests that left the window, then logs one and counts the rest
+///
+/// KEYS[1] log key; ARGV now (ms), window (ms), unique member
+const SLIDING_WINDOW_RECORD_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+redis.call("ZREMRANGEBYSCORE", KEYS[1], "-inf", now - window)
+redis.call("ZADD", KEYS[1], now, ARGV[3])
+redis.call("PEXPIRE", KEYS[1], window)
+return redis.call("ZCARD", KEYS[1])
+"#;
+
+/// Refills a bucket and optionally takes a token from it
+///
+/// KEYS[1] bucket key; ARGV now (ms), capacity, window (ms), 1 to take a token.
+/// Returns the whole tokens left, the milliseconds until one is available
+/// and the milliseconds until the bucket is full again.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+local window = tonumber(ARGV[3])
+local take = tonumber(ARGV[4])
+local rate = capacity / window
+
+local bucket = redis.call("HMGET", KEYS[1], "tokens", "updated_at")
+local tokens = tonumber(bucket[1]) or capacity
+local updated_at = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - updated_at) * rate)
+
+if take == 1 then
+    tokens = math.max(0, tokens - 1)
+    redis.call("HSET", KEYS[1], "tokens", tostring(tokens), "updated_at", now)
+    redis.call("PEXPIRE", KEYS[1], window)
+end
+
+local until_token = 0
+if tokens < 1 then
+    until_token = math.ceil((1 - tokens) / rate)
+end
+return {math.floor(tokens), until_token, math.ceil((capacity - tokens) / rate)}
+"#;
+
 use crate::cache::redis_client::RedisClient;
 
 /// Redis-based implementation of the rate limiter trait
@@ -27,9 +90,39 @@ impl RedisRateLimiter {
         }
     }
 
+    /// Key of the lock of a phone number
+    fn phone_lock_key(&self, phone_hash: &str) -> String {
+        self.redis_client.key(&format!("account_lock:phone:{}", phone_hash))
+    }
+
+    /// Key of the lock of an IP
+    fn ip_lock_key(&self, ip: &str) -> String {
+        self.redis_client.key(&format!("account_lock:ip:{}", ip))
+    }
+
+    /// Key of the SMS limit of a phone number
+    fn sms_limit_key(&self, phone_hash: &str) -> String {
+        self.redis_client.key(&format!("rate_limit:sms:{}", phone_hash))
+    }
+
+    /// Key of the verification limit of an IP
+    fn ip_verification_limit_key(&self, ip: &str) -> String {
+        self.redis_client.key(&format!("rate_limit:ip_verification:{}", ip))
+    }
+
+    /// Key of the failed attempts of a phone number
+    fn phone_failed_attempts_key(&self, phone_hash: &str) -> String {
+        self.redis_client.key(&format!("failed_attempts:phone:{}", phone_hash))
+    }
+
+    /// Key of the failed attempts of an IP
+    fn ip_failed_attempts_key(&self, ip: &str) -> String {
+        self.redis_client.key(&format!("failed_attempts:ip:{}", ip))
+    }
+
     /// Check if a phone number is locked due to failed attempts
     pub async fn is_phone_locked(&self, phone: &str) -> DomainResult<bool> {
-        let key = format!("account_lock:phone:{}", hash_phone(phone));
+        let key = self.phone_lock_key(&hash_phone(phone));
         let mut conn = self.redis_client.get_connection();
 
         let exists: bool = conn.exists(&key).await
@@ -42,7 +135,7 @@ impl RedisRateLimiter {
 
     /// Check if an IP is locked
     pub async fn is_ip_locked(&self, ip: &str) -> DomainResult<bool> {
-        let key = format!("account_lock:ip:{}", ip);
+        let key = self.ip_lock_key(ip);
         let mut conn = self.redis_client.get_connection();
 
         let exists: bool = conn.exists(&key).await
@@ -55,7 +148,7 @@ impl RedisRateLimiter {
 
     /// Lock a phone number for the configured duration
     async fn lock_phone(&self, phone: &str) -> DomainResult<()> {
-        let key = format!("account_lock:phone:{}", hash_phone(phone));
+        let key = self.phone_lock_key(&hash_phone(phone));
         let mut conn = self.redis_client.get_connection();
 
         let lockout_duration = self.config.auth.account_lock_duration;
@@ -72,7 +165,7 @@ impl RedisRateLimiter {
 
     /// Lock an IP address for the configured duration
     async fn lock_ip(&self, ip: &str) -> DomainResult<()> {
-        let key = format!("account_lock:ip:{}", ip);
+        let key = self.ip_lock_key(ip);
         let mut conn = self.redis_client.get_connection();
 
         let lockout_duration = self.config.auth.account_lock_duration;
@@ -87,135 +180,222 @@ impl RedisRateLimiter {
         Ok(())
     }
 
-    /// Check rate limit for a specific key using sliding window algorithm
+    /// Check whether a key has a request left, without counting one
     async fn check_rate_limit(
         &self,
         key: &str,
         limit: u32,
         window_seconds: u64,
     ) -> DomainResult<RateLimitStatus> {
-        let mut conn = self.redis_client.get_connection();
-
-        let now = Utc::now().timestamp_millis();
-        let window_start = now - (window_seconds as i64 * 1000);
-
-        // Remove old entries outside the window
-        // Remove expired entries using raw Redis command
-        let _: redis::RedisResult<i64> = redis::cmd("ZREMRANGEBYSCORE")
-            .arg(key)
-            .arg("-inf")
-            .arg(window_start)
-            .query_async(&mut conn)
-            .await;
-
-        // Count current entries in the window
-        let count: u32 = conn.zcount(key, window_start, "+inf").await
-            .map_err(|e| DomainError::Internal {
-                message: format!("Failed to count rate limit: {}", e),
-            })?;
-
-        if count >= limit {
-            // Get the oldest entry to calculate retry time
-            let oldest: Vec<(String, i64)> = conn.zrangebyscore_limit_withscores(
-                key,
-                window_start,
-                "+inf",
-                0,
-                1
-            ).await
-            .map_err(|e| DomainError::Internal {
-                message: format!("Failed to get rate limit window: {}", e),
-            })?;
-
-            let retry_after = if let Some((_, timestamp)) = oldest.first() {
-                ((timestamp + (window_seconds as i64 * 1000) - now) / 1000).max(1) as u64
-            } else {
-                window_seconds
-            };
+        let usage = self.usage(key, limit, window_seconds).await?;
 
+        if usage.used >= limit {
             Ok(RateLimitStatus::Exceeded {
-                retry_after_seconds: retry_after,
+                retry_after_seconds: usage.retry_after_seconds.unwrap_or(window_seconds).max(1),
                 limit,
                 window_seconds,
             })
         } else {
-            // Add current request to the window
-            conn.zadd(key, &now.to_string(), now).await
-                .map_err(|e| DomainError::Internal {
-                    message: format!("Failed to update rate limit: {}", e),
-                })?;
-
-            // Set expiry on the key
-            conn.expire(key, window_seconds as i64).await
-                .map_err(|e| DomainError::Internal {
-                    message: format!("Failed to set expiry: {}", e),
-                })?;
-
             Ok(RateLimitStatus::Ok {
-                remaining: limit - count - 1,
+                remaining: limit - usage.used,
                 limit,
                 window_seconds,
             })
         }
     }
 
+    /// Count a served request against a key
+    ///
+    /// # Returns
+    /// * Requests counted in the window, the new one included
+    async fn record_request(&self, key: &str, limit: u32, window_seconds: u64) -> DomainResult<u32> {
+        let mut conn = self.redis_client.get_connection();
+        let now = Utc::now().timestamp_millis();
+        let window_ms = window_seconds as i64 * 1000;
+        let internal = |e: redis::RedisError| DomainError::Internal {
+            message: format!("Failed to update rate limit: {}", e),
+        };
+
+        match self.config.algorithm {
+            RateLimitAlgorithm::FixedWindow => {
+                let (window_key, _) = fixed_window(key, now, window_ms);
+                let count: u32 = conn.incr(&window_key, 1).await.map_err(internal)?;
+                if count == 1 {
+                    conn.expire::<_, ()>(&window_key, window_seconds as i64)
+                        .await
+                        .map_err(internal)?;
+                }
+                Ok(count)
+            }
+            RateLimitAlgorithm::SlidingWindowLog => {
+                // Requests logged in the same millisecond must not collapse into one entry
+                let member = format!("{}-{}", now, Uuid::new_v4().simple());
+                redis::Script::new(SLIDING_WINDOW_RECORD_SCRIPT)
+                    .key(key)
+                    .arg(now)
+                    .arg(window_ms)
+                    .arg(member)
+                    .invoke_async::<_, u32>(&mut conn)
+                    .await
+                    .map_err(internal)
+            }
+            RateLimitAlgorithm::TokenBucket => {
+                let bucket = self.token_bucket(key, limit, window_seconds, true).await?;
+                Ok(limit.saturating_sub(bucket.tokens))
+            }
+        }
+    }
+
+    /// Requests counted against a key and when the next one is allowed
+    async fn usage(&self, key: &str, limit: u32, window_seconds: u64) -> DomainResult<LimitUsage> {
+        let mut conn = self.redis_client.get_connection();
+        let now = Utc::now().timestamp_millis();
+        let window_ms = window_seconds as i64 * 1000;
+        let internal = |e: redis::RedisError| DomainError::Internal {
+            message: format!("Failed to count rate limit: {}", e),
+        };
+
+        match self.config.algorithm {
+            RateLimitAlgorithm::FixedWindow => {
+                let (window_key, remaining_ms) = fixed_window(key, now, window_ms);
+                let count: Option<u32> = conn.get(&window_key).await.map_err(internal)?;
+                let used = count.unwrap_or(0);
+
+                Ok(LimitUsage {
+                    used,
+                    retry_after_seconds: (used >= limit).then(|| millis_to_seconds(remaining_ms)),
+                    reset_after_seconds: (used > 0).then(|| millis_to_seconds(remaining_ms)),
+                })
+            }
+            RateLimitAlgorithm::SlidingWindowLog => {
+                let window_start = now - window_ms;
+                let used: u32 = conn.zcount(key, window_start, "+inf").await.map_err(internal)?;
+                if used == 0 {
+                    return Ok(LimitUsage::default());
+                }
+
+                // The window frees up a slot when the oldest request leaves it
+                let oldest: Vec<(String, i64)> = conn
+                    .zrangebyscore_limit_withscores(key, window_start, "+inf", 0, 1)
+                    .await
+                    .map_err(internal)?;
+                let reset_after = oldest
+                    .first()
+                    .map(|(_, timestamp)| millis_to_seconds(timestamp + window_ms - now));
+
+                Ok(LimitUsage {
+                    used,
+                    retry_after_seconds: if used >= limit { reset_after } else { None },
+                    reset_after_seconds: reset_after,
+                })
+            }
+            RateLimitAlgorithm::TokenBucket => {
+                let bucket = self.token_bucket(key, limit, window_seconds, false).await?;
+
+                Ok(LimitUsage {
+                    used: limit.saturating_sub(bucket.tokens),
+                    retry_after_seconds: (bucket.until_token_ms > 0)
+                        .then(|| millis_to_seconds(bucket.until_token_ms)),
+                    reset_after_seconds: (bucket.until_full_ms > 0)
+                        .then(|| millis_to_seconds(bucket.until_full_ms)),
+                })
+            }
+        }
+    }
+
+    /// Refill the token bucket of a key, taking a token if `take` is set
+    async fn token_bucket(
+        &self,
+        key: &str,
+        limit: u32,
+        window_seconds: u64,
+        take: bool,
+    ) -> DomainResult<TokenBucket> {
+        let mut conn = self.redis_client.get_connection();
+
+        let (tokens, until_token_ms, until_full_ms): (u32, i64, i64) =
+            redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                .key(token_bucket_key(key))
+                .arg(Utc::now().timestamp_millis())
+                .arg(limit)
+                .arg(window_seconds * 1000)
+                .arg(u8::from(take))
+                .invoke_async(&mut conn)
+                .await
+                .map_err(|e| DomainError::Internal {
+                    message: format!("Failed to update token bucket: {}", e),
+                })?;
+
+        Ok(TokenBucket {
+            tokens,
+            until_token_ms,
+            until_full_ms,
+        })
+    }
+
+    /// Keys a limit may be stored under, whatever the algorithm
+    fn limit_keys(&self, key: &str) -> Vec<String> {
+        let now = Utc::now().timestamp_millis();
+        let (window_key, _) = fixed_window(key, now, LIMIT_WINDOW_SECONDS as i64 * 1000);
+        vec![key.to_string(), window_key, token_bucket_key(key)]
+    }
+
     /// Check phone SMS rate limit
     pub async fn check_phone_sms_limit(&self, phone: &str) -> DomainResult<RateLimitStatus> {
         // First check if phone is locked
         if self.is_phone_locked(phone).await? {
-            let ttl = self.get_lock_ttl(&format!("account_lock:phone:{}", hash_phone(phone))).await?;
+            let ttl = self.get_lock_ttl(&self.phone_lock_key(&hash_phone(phone))).await?;
             return Ok(RateLimitStatus::Locked {
                 retry_after_seconds: ttl.unwrap_or(3600),
                 reason: "Phone locked due to excessive failed attempts".to_string(),
             });
         }
 
-        let key = format!("rate_limit:sms:{}", hash_phone(phone));
+        let key = self.sms_limit_key(&hash_phone(phone));
         let limit = self.config.sms.per_phone_per_hour;
-        let window = 3600u64; // 1 hour window for SMS
-        self.check_rate_limit(&key, limit, window).await
+        self.check_rate_limit(&key, limit, LIMIT_WINDOW_SECONDS).await
     }
 
     /// Check IP verification limit (internal)
     pub async fn check_ip_verification_limit_internal(&self, ip: &str) -> DomainResult<RateLimitStatus> {
         // First check if IP is locked
         if self.is_ip_locked(ip).await? {
-            let ttl = self.get_lock_ttl(&format!("account_lock:ip:{}", ip)).await?;
+            let ttl = self.get_lock_ttl(&self.ip_lock_key(ip)).await?;
             return Ok(RateLimitStatus::Locked {
                 retry_after_seconds: ttl.unwrap_or(3600),
                 reason: "IP locked due to excessive requests".to_string(),
             });
         }
 
-        let key = format!("rate_limit:ip_verification:{}", ip);
+        let key = self.ip_verification_limit_key(ip);
         let limit = self.config.auth.login_per_ip_per_hour;
-        let window = 3600; // 1 hour in seconds
-        self.check_rate_limit(&key, limit, window).await
+        self.check_rate_limit(&key, limit, LIMIT_WINDOW_SECONDS).await
     }
 
     /// Get the status of all rate limits for a phone number
     pub async fn get_phone_status(&self, phone: &str) -> DomainResult<RateLimitInfo> {
         let is_locked = self.is_phone_locked(phone).await?;
         let lock_ttl = if is_locked {
-            self.get_lock_ttl(&format!("account_lock:phone:{}", hash_phone(phone))).await?
+            self.get_lock_ttl(&self.phone_lock_key(&hash_phone(phone))).await?
         } else {
             None
         };
 
         // Get SMS limit status
-        let sms_key = format!("rate_limit:sms:{}", hash_phone(phone));
-        let sms_count = self.get_current_count(&sms_key).await?;
+        let sms_key = self.sms_limit_key(&hash_phone(phone));
+        let sms_limit = self.config.sms.per_phone_per_hour;
+        let sms_count = self.usage(&sms_key, sms_limit, LIMIT_WINDOW_SECONDS).await?.used;
 
         // Get failed attempts
-        let failed_key = format!("failed_attempts:phone:{}", hash_phone(phone));
+        let failed_key = self.phone_failed_attempts_key(&hash_phone(phone));
         let failed_attempts = self.get_current_count(&failed_key).await?;
 
         let limits = vec![
             LimitInfo {
                 limit_type: "sms".to_string(),
                 current: sms_count,
-                limit: self.config.sms.per_phone_per_hour,
-                window_seconds: 3600, // 1 hour window
+                limit: sms_limit,
+                window_seconds: LIMIT_WINDOW_SECONDS,
             },
         ];
 
@@ -234,25 +414,29 @@ impl RedisRateLimiter {
     pub async fn get_ip_status(&self, ip: &str) -> DomainResult<RateLimitInfo> {
         let is_locked = self.is_ip_locked(ip).await?;
         let lock_ttl = if is_locked {
-            self.get_lock_ttl(&format!("account_lock:ip:{}", ip)).await?
+            self.get_lock_ttl(&self.ip_lock_key(ip)).await?
         } else {
             None
         };
 
         // Get verification limit status
-        let verification_key = format!("rate_limit:ip_verification:{}", ip);
-        let verification_count = self.get_current_count(&verification_key).await?;
+        let verification_key = self.ip_verification_limit_key(ip);
+        let verification_limit = self.config.auth.login_per_ip_per_hour;
+        let verification_count = self
+            .usage(&verification_key, verification_limit, LIMIT_WINDOW_SECONDS)
+            .await?
+            .used;
 
         // Get failed attempts
-        let failed_key = format!("failed_attempts:ip:{}", ip);
+        let failed_key = self.ip_failed_attempts_key(ip);
         let failed_attempts = self.get_current_count(&failed_key).await?;
 
         let limits = vec![
             LimitInfo {
                 limit_type: "verification".to_string(),
                 current: verification_count,
-                limit: self.config.auth.login_per_ip_per_hour,
-                window_seconds: 3600,
+                limit: verification_limit,
+                window_seconds: LIMIT_WINDOW_SECONDS,
             },
         ];
 
@@ -272,11 +456,9 @@ impl RedisRateLimiter {
         let mut conn = self.redis_client.get_connection();
 
         let phone_hash = hash_phone(phone);
-        let keys = vec![
-            format!("rate_limit:sms:{}", phone_hash),
-            format!("failed_attempts:phone:{}", phone_hash),
-            format!("account_lock:phone:{}", phone_hash),
-        ];
+        let mut keys = self.limit_keys(&self.sms_limit_key(&phone_hash));
+        keys.push(self.phone_failed_attempts_key(&phone_hash));
+        keys.push(self.phone_lock_key(&phone_hash));
 
         for key in keys {
             let _: Result<(), _> = conn.del(&key).await;
@@ -289,11 +471,9 @@ impl RedisRateLimiter {
     pub async fn reset_ip_limits(&self, ip: &str) -> DomainResult<()> {
         let mut conn = self.redis_client.get_connection();
 
-        let keys = vec![
-            format!("rate_limit:ip_verification:{}", ip),
-            format!("failed_attempts:ip:{}", ip),
-            format!("account_lock:ip:{}", ip),
-        ];
+        let mut keys = self.limit_keys(&self.ip_verification_limit_key(ip));
+        keys.push(self.ip_failed_attempts_key(ip));
+        keys.push(self.ip_lock_key(ip));
 
         for key in keys {
             let _: Result<(), _> = conn.del(&key).await;
@@ -333,7 +513,7 @@ impl RedisRateLimiter {
 
     /// Increment failed attempts for a phone and check if should lock
     pub async fn increment_failed_attempts(&self, phone: &str) -> DomainResult<bool> {
-        let key = format!("failed_attempts:phone:{}", hash_phone(phone));
+        let key = self.phone_failed_attempts_key(&hash_phone(phone));
         let mut conn = self.redis_client.get_connection();
 
         // Use sliding window for failed attempts too
@@ -387,52 +567,23 @@ impl RateLimiterTrait for RedisRateLimiter {
     }
 
     async fn increment_sms_counter(&self, phone: &str) -> Result<i64, String> {
-        let key = format!("rate_limit:sms:{}", hash_phone(phone));
-        let mut conn = self.redis_client.get_connection();
-
-        let now = Utc::now().timestamp_millis();
-
-        // Add to sorted set
-        conn.zadd(&key, &now.to_string(), now).await
-            .map_err(|e| format!("Failed to increment counter: {}", e))?;
-
-        // Set expiry
-        let window = 3600i64; // 1 hour window
-        conn.expire(&key, window).await
-            .map_err(|e| format!("Failed to set expiry: {}", e))?;
-
-        // Count entries in window
-        let window_start = now - (window as i64 * 1000);
-        let count: i64 = conn.zcount(&key, window_start, "+inf").await
-            .map_err(|e| format!("Failed to count: {}", e))?;
+        let key = self.sms_limit_key(&hash_phone(phone));
+        let limit = self.config.sms.per_phone_per_hour;
 
-        Ok(count)
+        self.record_request(&key, limit, LIMIT_WINDOW_SECONDS)
+            .await
+            .map(i64::from)
+            .map_err(|e| e.to_string())
     }
 
     async fn get_rate_limit_reset_time(&self, phone: &str) -> Result<Option<i64>, String> {
-        let key = format!("rate_limit:sms:{}", hash_phone(phone));
-        let mut conn = self.redis_client.get_connection();
+        let key = self.sms_limit_key(&hash_phone(phone));
+        let limit = self.config.sms.per_phone_per_hour;
 
-        let now = Utc::now().timestamp_millis();
-        let window = 3600i64 * 1000; // 1 hour window in milliseconds
-        let window_start = now - window;
-
-        // Get the oldest entry in the current window
-        let oldest: Vec<(String, i64)> = conn.zrangebyscore_limit_withscores(
-            &key,
-            window_start,
-            "+inf",
-            0,
-            1
-        ).await
-        .map_err(|e| format!("Failed to get oldest entry: {}", e))?;
-
-        if let Some((_, timestamp)) = oldest.first() {
-            let reset_time = (timestamp + window - now) / 1000;
-            Ok(Some(reset_time.max(0)))
-        } else {
-            Ok(None)
-        }
+        let usage = self.usage(&key, limit, LIMIT_WINDOW_SECONDS)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(usage.reset_after_seconds.map(|seconds| seconds as i64))
     }
 
     async fn check_ip_verification_limit(&self, ip: &str) -> Result<bool, String> {
@@ -443,52 +594,23 @@ impl RateLimiterTrait for RedisRateLimiter {
     }
 
     async fn increment_ip_verification_counter(&self, ip: &str) -> Result<i64, String> {
-        let key = format!("rate_limit:ip_verification:{}", ip);
-        let mut conn = self.redis_client.get_connection();
-
-        let now = Utc::now().timestamp_millis();
-
-        // Add to sorted set
-        conn.zadd(&key, &now.to_string(), now).await
-            .map_err(|e| format!("Failed to increment IP counter: {}", e))?;
-
-        // Set expiry (1 hour TTL)
-        let window = 3600i64; // 1 hour window
-        conn.expire(&key, window).await
-            .map_err(|e| format!("Failed to set expiry: {}", e))?;
-
-        // Count entries in window
-        let window_start = now - (window as i64 * 1000);
-        let count: i64 = conn.zcount(&key, window_start, "+inf").await
-            .map_err(|e| format!("Failed to count IP attempts: {}", e))?;
+        let key = self.ip_verification_limit_key(ip);
+        let limit = self.config.auth.login_per_ip_per_hour;
 
-        Ok(count)
+        self.record_request(&key, limit, LIMIT_WINDOW_SECONDS)
+            .await
+            .map(i64::from)
+            .map_err(|e| e.to_string())
     }
 
     async fn get_ip_rate_limit_reset_time(&self, ip: &str) -> Result<Option<i64>, String> {
-        let key = format!("rate_limit:ip_verification:{}", ip);
-        let mut conn = self.redis_client.get_connection();
+        let key = self.ip_verification_limit_key(ip);
+        let limit = self.config.auth.login_per_ip_per_hour;
 
-        let now = Utc::now().timestamp_millis();
-        let window = 3600i64 * 1000; // 1 hour window in milliseconds
-        let window_start = now - window;
-
-        // Get the oldest entry in the current window
-        let oldest: Vec<(String, i64)> = conn.zrangebyscore_limit_withscores(
-            &key,
-            window_start,
-            "+inf",
-            0,
-            1
-        ).await
-        .map_err(|e| format!("Failed to get oldest IP entry: {}", e))?;
-
-        if let Some((_, timestamp)) = oldest.first() {
-            let reset_time = (timestamp + window - now) / 1000;
-            Ok(Some(reset_time.max(0)))
-        } else {
-            Ok(None)
-        }
+        let usage = self.usage(&key, limit, LIMIT_WINDOW_SECONDS)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(usage.reset_after_seconds.map(|seconds| seconds as i64))
     }
 
     async fn log_rate_limit_violation(
@@ -565,6 +687,44 @@ pub struct LimitInfo {
     pub window_seconds: u64,
 }
 
+/// Requests counted against a limit
+#[derive(Debug, Clone, Copy, Default)]
+struct LimitUsage {
+    /// Requests counted in the current window
+    used: u32,
+    /// Seconds until the next request is allowed, if the limit is reached
+    retry_after_seconds: Option<u64>,
+    /// Seconds until no request is counted any more, if any is
+    reset_after_seconds: Option<u64>,
+}
+
+/// State of a token bucket after refilling it
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// Whole tokens left
+    tokens: u32,
+    /// Milliseconds until a token is available
+    until_token_ms: i64,
+    /// Milliseconds until the bucket is full again
+    until_full_ms: i64,
+}
+
+/// Key of the fixed window containing `now_ms` and the milliseconds it has left
+pub(crate) fn fixed_window(key: &str, now_ms: i64, window_ms: i64) -> (String, i64) {
+    let index = now_ms.div_euclid(window_ms);
+    (format!("{}:fw:{}", key, index), (index + 1) * window_ms - now_ms)
+}
+
+/// Key of the token bucket of a limit
+fn token_bucket_key(key: &str) -> String {
+    format!("{}:tb", key)
+}
+
+/// Round milliseconds up to whole seconds
+fn millis_to_seconds(millis: i64) -> u64 {
+    (millis.max(0) as u64).div_ceil(1000)
+}
+
 /// Hash a phone number for audit logging (privacy protection)
 fn hash_phone(phone: &str) -> String {
     use sha2::{Sha256, Digest};
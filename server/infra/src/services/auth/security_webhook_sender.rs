@@ -0,0 +1,56 @@
+//! HTTP delivery of security events to operator webhooks
+//!
+//! Posts the signed JSON body produced by the security webhook service.
+//! Redirects are not followed, so a signed payload is only ever sent to
+//! the registered URL.
+
+use async_trait::async_trait;
+use std::time::Duration;
+
+use re_core::services::security_webhook::{
+    WebhookRequest, WebhookSenderTrait, EVENT_ID_HEADER, SIGNATURE_HEADER,
+};
+
+use crate::InfrastructureError;
+
+/// Timeout for webhook requests, in seconds
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Sends security events to webhooks over HTTPS
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookSender {
+    /// Create a new webhook sender
+    pub fn new() -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl WebhookSenderTrait for HttpWebhookSender {
+    async fn send(&self, request: &WebhookRequest) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, &request.signature)
+            .header(EVENT_ID_HEADER, request.event_id.to_string())
+            .body(request.body.clone())
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Webhook returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Redis-backed session activity store for idle timeouts
+//!
+//! The last activity of each refresh-token family is kept as a Unix
+//! timestamp under `session_activity:{family}`, expiring with the
+//! absolute session lifetime so abandoned sessions do not accumulate.
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use redis::AsyncCommands;
+use std::sync::Arc;
+
+use re_core::services::token::SessionActivityStoreTrait;
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the session activity store
+pub struct RedisSessionActivityStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisSessionActivityStore {
+    /// Create a new Redis-based session activity store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    fn key(token_family: &str) -> String {
+        format!("session_activity:{}", token_family)
+    }
+}
+
+#[async_trait]
+impl SessionActivityStoreTrait for RedisSessionActivityStore {
+    async fn record_activity(
+        &self,
+        token_family: &str,
+        at: DateTime<Utc>,
+        ttl_seconds: u64,
+    ) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+
+        conn.set_ex::<_, _, ()>(self.redis_client.key(&Self::key(token_family)), at.timestamp(), ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to record session activity: {}", e))
+    }
+
+    async fn last_activity(&self, token_family: &str) -> Result<Option<DateTime<Utc>>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let timestamp: Option<i64> = conn
+            .get(self.redis_client.key(&Self::key(token_family)))
+            .await
+            .map_err(|e| format!("Failed to load session activity: {}", e))?;
+
+        Ok(timestamp.and_then(|t| Utc.timestamp_opt(t, 0).single()))
+    }
+
+    async fn clear(&self, token_family: &str) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+
+        conn.del::<_, ()>(self.redis_client.key(&Self::key(token_family)))
+            .await
+            .map_err(|e| format!("Failed to clear session activity: {}", e))
+    }
+}
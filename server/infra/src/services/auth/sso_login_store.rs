@@ -0,0 +1,55 @@
+//! Redis-backed store of admin single sign-on logins in progress
+//!
+//! The nonce and PKCE verifier of each started login are kept as JSON under
+//! `admin_sso:{state}` until the identity provider redirects back. They are
+//! read with GETDEL, so a state can only complete one login.
+
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use std::sync::Arc;
+
+use re_core::services::admin_sso::{PendingSsoLogin, SsoLoginStoreTrait};
+
+use crate::cache::redis_client::RedisClient;
+
+/// Redis-based implementation of the pending login store
+pub struct RedisSsoLoginStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisSsoLoginStore {
+    /// Create a new Redis-based pending login store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    fn key(state: &str) -> String {
+        format!("admin_sso:{}", state)
+    }
+}
+
+#[async_trait]
+impl SsoLoginStoreTrait for RedisSsoLoginStore {
+    async fn save(&self, state: &str, login: &PendingSsoLogin, ttl_seconds: u64) -> Result<(), String> {
+        let value = serde_json::to_string(login)
+            .map_err(|e| format!("Failed to serialize pending login: {}", e))?;
+        let mut conn = self.redis_client.get_connection();
+
+        conn.set_ex::<_, _, ()>(self.redis_client.key(&Self::key(state)), value, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to store pending login: {}", e))
+    }
+
+    async fn take(&self, state: &str) -> Result<Option<PendingSsoLogin>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let value: Option<String> = conn
+            .get_del(self.redis_client.key(&Self::key(state)))
+            .await
+            .map_err(|e| format!("Failed to load pending login: {}", e))?;
+
+        value
+            .map(|v| serde_json::from_str(&v).map_err(|e| format!("Invalid pending login: {}", e)))
+            .transpose()
+    }
+}
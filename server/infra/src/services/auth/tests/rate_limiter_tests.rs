@@ -142,3 +142,68 @@ fn test_hash_phone_consistency() {
     assert_ne!(hash1, different_hash);
 }
 
+
+#[test]
+fn test_fixed_windows_follow_the_clock() {
+    use crate::services::auth::rate_limiter::fixed_window;
+
+    let hour = 3_600_000;
+    let (first, left) = fixed_window("rate_limit:sms:abc", 3 * hour + 1_000, hour);
+    assert_eq!(first, "rate_limit:sms:abc:fw:3");
+    assert_eq!(left, hour - 1_000);
+
+    // A request a second later, past the boundary, starts a fresh count
+    let (second, left) = fixed_window("rate_limit:sms:abc", 4 * hour, hour);
+    assert_eq!(second, "rate_limit:sms:abc:fw:4");
+    assert_eq!(left, hour);
+}
+
+#[tokio::test]
+#[ignore] // Requires actual Redis server
+async fn test_algorithms_stop_sends_at_the_limit() {
+    use std::sync::Arc;
+
+    use re_core::RateLimiterTrait;
+    use re_shared::config::cache::CacheConfig;
+    use re_shared::config::rate_limit::RateLimitAlgorithm;
+    use re_shared::RateLimitConfig;
+
+    use crate::cache::RedisClient;
+    use crate::services::auth::RedisRateLimiter;
+
+    let config = CacheConfig::new(
+        std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string())
+    );
+    let client = Arc::new(RedisClient::new(config).await.unwrap());
+
+    for algorithm in [
+        RateLimitAlgorithm::FixedWindow,
+        RateLimitAlgorithm::SlidingWindowLog,
+        RateLimitAlgorithm::TokenBucket,
+    ] {
+        let limiter = RedisRateLimiter::new(
+            client.clone(),
+            RateLimitConfig { algorithm, ..RateLimitConfig::default() },
+        );
+        let phone = "+15550000001";
+        limiter.reset_phone_limits(phone).await.unwrap();
+
+        // Checking never counts a send
+        for _ in 0..5 {
+            assert!(!limiter.check_sms_rate_limit(phone).await.unwrap());
+        }
+
+        for sent in 1..=3 {
+            assert!(!limiter.check_sms_rate_limit(phone).await.unwrap(), "{:?}", algorithm);
+            assert_eq!(limiter.increment_sms_counter(phone).await.unwrap(), sent, "{:?}", algorithm);
+        }
+
+        assert!(limiter.check_sms_rate_limit(phone).await.unwrap(), "{:?}", algorithm);
+        let reset = limiter.get_rate_limit_reset_time(phone).await.unwrap().unwrap();
+        assert!(reset > 0 && reset <= 3600, "{:?}", algorithm);
+
+        limiter.reset_phone_limits(phone).await.unwrap();
+        assert!(!limiter.check_sms_rate_limit(phone).await.unwrap(), "{:?}", algorithm);
+    }
+}
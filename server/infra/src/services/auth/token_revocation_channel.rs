@@ -0,0 +1,91 @@
+//! Redis pub/sub channel for token revocations
+//!
+//! Revocations are published as JSON on the `token_revocations` channel.
+//! Every instance subscribes and applies what it receives to its token
+//! service's revocation cache, its own revocations included, which is
+//! harmless. Redis does not keep messages for disconnected subscribers, so
+//! an instance that misses one relies on its cached checks expiring.
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+
+use re_core::services::token::{RevocationCache, TokenRevocation, TokenRevocationBroadcasterTrait};
+
+use crate::cache::redis_client::RedisClient;
+use crate::InfrastructureError;
+
+/// Channel the revocations are published on
+pub const TOKEN_REVOCATION_CHANNEL: &str = "token_revocations";
+
+/// Delay before subscribing again after the connection drops
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Redis-based broadcaster of token revocations
+pub struct RedisTokenRevocationChannel {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisTokenRevocationChannel {
+    /// Create a new Redis-based revocation channel
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    /// Apply revocations published by any instance to a revocation cache
+    ///
+    /// Spawns a task that stays subscribed for the lifetime of the process,
+    /// subscribing again whenever the connection drops.
+    pub fn start_listener(&self, cache: Arc<RevocationCache>) {
+        let redis_client = self.redis_client.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match Self::listen(&redis_client, &cache).await {
+                    Ok(()) => warn!("Token revocation subscription closed, subscribing again"),
+                    Err(e) => error!("Token revocation subscription failed: {}", e),
+                }
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+    }
+
+    /// Apply revocations until the subscription ends
+    async fn listen(redis_client: &RedisClient, cache: &RevocationCache) -> Result<(), InfrastructureError> {
+        let mut pubsub = redis_client.subscribe(&[TOKEN_REVOCATION_CHANNEL]).await?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let payload: String = match message.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!("Ignoring unreadable token revocation: {}", e);
+                    continue;
+                }
+            };
+
+            match serde_json::from_str::<TokenRevocation>(&payload) {
+                Ok(revocation) => cache.apply(&revocation),
+                Err(e) => warn!("Ignoring malformed token revocation: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenRevocationBroadcasterTrait for RedisTokenRevocationChannel {
+    async fn broadcast(&self, revocation: &TokenRevocation) -> Result<(), String> {
+        let payload = serde_json::to_string(revocation)
+            .map_err(|e| format!("Failed to encode token revocation: {}", e))?;
+
+        self.redis_client
+            .publish(TOKEN_REVOCATION_CHANNEL, &payload)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Failed to publish token revocation: {}", e))
+    }
+}
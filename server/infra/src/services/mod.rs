@@ -1,3 +1,4 @@
 //! Infrastructure services module
 
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod sms;
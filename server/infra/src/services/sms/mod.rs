@@ -0,0 +1,7 @@
+//! SMS-related infrastructure services
+
+pub mod queue;
+pub mod spend_counter;
+
+pub use queue::RedisSmsQueueStore;
+pub use spend_counter::RedisSmsSpendCounter;
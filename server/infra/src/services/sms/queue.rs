@@ -0,0 +1,135 @@
+//! Redis-backed store of the SMS queue
+//!
+//! Jobs are kept as JSON in the hash `sms:queue:jobs`, keyed by job ID, and
+//! scheduled in the sorted set `sms:queue:due` scored by the millisecond
+//! timestamp of their next attempt. Jobs are claimed by a Lua script that
+//! removes them from both keys, so each job is handed to one instance.
+//!
+//! Dead letters are pushed to the list `sms:queue:dead`, which keeps the
+//! most recent [`DEAD_LETTER_LIMIT`] entries. Keys are put under the key
+//! prefix of the Redis client.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use std::sync::Arc;
+use tracing::warn;
+
+use re_core::domain::entities::sms_job::SmsJob;
+use re_core::services::sms_queue::SmsQueueStoreTrait;
+
+use crate::cache::redis_client::RedisClient;
+
+/// Number of dead letters kept
+pub const DEAD_LETTER_LIMIT: isize = 1000;
+
+const DUE_KEY: &str = "sms:queue:due";
+const JOBS_KEY: &str = "sms:queue:jobs";
+const DEAD_KEY: &str = "sms:queue:dead";
+
+/// Removes and returns the jobs that are due
+///
+/// KEYS[1] due set, KEYS[2] job hash; ARGV now (ms), limit
+const CLAIM_DUE_SCRIPT: &str = r#"
+local ids = redis.call("ZRANGEBYSCORE", KEYS[1], "-inf", ARGV[1], "LIMIT", 0, tonumber(ARGV[2]))
+local jobs = {}
+for _, id in ipairs(ids) do
+    redis.call("ZREM", KEYS[1], id)
+    local job = redis.call("HGET", KEYS[2], id)
+    if job then
+        redis.call("HDEL", KEYS[2], id)
+        table.insert(jobs, job)
+    end
+end
+return jobs
+"#;
+
+/// Redis-based implementation of the SMS queue store
+pub struct RedisSmsQueueStore {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisSmsQueueStore {
+    /// Create a new Redis-based SMS queue store
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    /// Parse stored jobs, skipping entries that are not valid JSON
+    fn parse_jobs(entries: Vec<String>) -> Vec<SmsJob> {
+        entries
+            .into_iter()
+            .filter_map(|entry| match serde_json::from_str(&entry) {
+                Ok(job) => Some(job),
+                Err(e) => {
+                    warn!(error = %e, "Skipping unreadable SMS queue entry");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SmsQueueStoreTrait for RedisSmsQueueStore {
+    async fn push(&self, job: &SmsJob) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+        let json = serde_json::to_string(job).map_err(|e| format!("Failed to serialize SMS job: {}", e))?;
+        let id = job.id.to_string();
+
+        redis::pipe()
+            .atomic()
+            .hset(self.redis_client.key(JOBS_KEY), &id, json)
+            .ignore()
+            .zadd(self.redis_client.key(DUE_KEY), &id, job.next_attempt_at.timestamp_millis())
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to queue SMS: {}", e))
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<SmsJob>, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let entries: Vec<String> = redis::Script::new(CLAIM_DUE_SCRIPT)
+            .key(self.redis_client.key(DUE_KEY))
+            .key(self.redis_client.key(JOBS_KEY))
+            .arg(now.timestamp_millis())
+            .arg(limit)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to claim queued SMS: {}", e))?;
+
+        Ok(Self::parse_jobs(entries))
+    }
+
+    async fn dead_letter(&self, job: &SmsJob) -> Result<(), String> {
+        let mut conn = self.redis_client.get_connection();
+        let key = self.redis_client.key(DEAD_KEY);
+        let json = serde_json::to_string(job).map_err(|e| format!("Failed to serialize SMS job: {}", e))?;
+
+        redis::pipe()
+            .atomic()
+            .lpush(&key, json)
+            .ignore()
+            .ltrim(&key, 0, DEAD_LETTER_LIMIT - 1)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to dead-letter SMS: {}", e))
+    }
+
+    async fn dead_letters(&self, limit: usize) -> Result<Vec<SmsJob>, String> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.redis_client.get_connection();
+        let entries: Vec<String> = conn
+            .lrange(self.redis_client.key(DEAD_KEY), 0, limit as isize - 1)
+            .await
+            .map_err(|e| format!("Failed to read SMS dead letters: {}", e))?;
+
+        Ok(Self::parse_jobs(entries))
+    }
+}
@@ -0,0 +1,64 @@
+//! Redis-backed counter of the daily SMS spend
+//!
+//! The estimated spend of each UTC day is kept under `sms:spend:{day}`, in
+//! millionths of the budget currency, so every instance checks messages
+//! against the same total. Counters expire two days after they are last
+//! written; the database keeps the history.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use redis::AsyncCommands;
+use std::sync::Arc;
+
+use re_core::services::sms_cost::SmsSpendCounterTrait;
+
+use crate::cache::redis_client::RedisClient;
+
+/// How long a daily counter is kept after its last write
+const COUNTER_TTL_SECONDS: i64 = 2 * 24 * 60 * 60;
+
+/// Redis-based implementation of the daily SMS spend counter
+pub struct RedisSmsSpendCounter {
+    redis_client: Arc<RedisClient>,
+}
+
+impl RedisSmsSpendCounter {
+    /// Create a new Redis-based spend counter
+    pub fn new(redis_client: Arc<RedisClient>) -> Self {
+        Self { redis_client }
+    }
+
+    fn key(day: NaiveDate) -> String {
+        format!("sms:spend:{}", day)
+    }
+}
+
+#[async_trait]
+impl SmsSpendCounterTrait for RedisSmsSpendCounter {
+    async fn add(&self, day: NaiveDate, cost_micros: u64) -> Result<u64, String> {
+        let mut conn = self.redis_client.get_connection();
+        let key = self.redis_client.key(&Self::key(day));
+
+        let (total,): (u64,) = redis::pipe()
+            .atomic()
+            .incr(&key, cost_micros)
+            .expire(&key, COUNTER_TTL_SECONDS)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to add to SMS spend: {}", e))?;
+
+        Ok(total)
+    }
+
+    async fn total(&self, day: NaiveDate) -> Result<u64, String> {
+        let mut conn = self.redis_client.get_connection();
+
+        let total: Option<u64> = conn
+            .get(self.redis_client.key(&Self::key(day)))
+            .await
+            .map_err(|e| format!("Failed to read SMS spend: {}", e))?;
+
+        Ok(total.unwrap_or(0))
+    }
+}
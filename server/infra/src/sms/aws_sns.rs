@@ -13,6 +13,7 @@
 //! - Comprehensive error handling
 //! - Security: Phone number masking in logs
 //! - Automatic failover from Twilio
+//! - Request trace ID sent as the `TraceId` message attribute
 
 use async_trait::async_trait;
 use aws_config::BehaviorVersion;
@@ -23,10 +24,15 @@ use aws_sdk_sns::{
 };
 use phonenumber::{Mode, PhoneNumber};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+use re_core::services::trace::current_trace_id;
+use re_shared::config::SecretString;
+
 use crate::{
+    sms::dry_run::SmsDryRunLog,
     sms::sms_service::{mask_phone_number, SmsService},
     InfrastructureError,
 };
@@ -37,7 +43,7 @@ pub struct AwsSnsConfig {
     /// AWS Access Key ID
     pub access_key_id: String,
     /// AWS Secret Access Key
-    pub secret_access_key: String,
+    pub secret_access_key: SecretString,
     /// AWS Region (e.g., "us-east-1")
     pub region: String,
     /// SMS sender ID (optional, may not be supported in all regions)
@@ -81,7 +87,7 @@ impl AwsSnsConfig {
 
         Ok(Self {
             access_key_id,
-            secret_access_key,
+            secret_access_key: SecretString::from(secret_access_key),
             region,
             sender_id,
             sms_type,
@@ -105,6 +111,7 @@ impl AwsSnsConfig {
 pub struct AwsSnsSmsService {
     client: SnsClient,
     config: AwsSnsConfig,
+    dry_run: Option<Arc<SmsDryRunLog>>,
 }
 
 impl AwsSnsSmsService {
@@ -113,7 +120,7 @@ impl AwsSnsSmsService {
         // Create AWS credentials provider
         let credentials_provider = aws_credential_types::Credentials::new(
             &config.access_key_id,
-            &config.secret_access_key,
+            config.secret_access_key.expose_secret(),
             None,
             None,
             "aws_sns_sms_service",
@@ -139,7 +146,19 @@ impl AwsSnsSmsService {
             info!("Using sender ID: {}", sender_id);
         }
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            dry_run: None,
+        })
+    }
+
+    /// Record messages in a dry-run log instead of sending them
+    ///
+    /// Messages are still validated and prepared as if they were sent.
+    pub fn with_dry_run(mut self, log: Arc<SmsDryRunLog>) -> Self {
+        self.dry_run = Some(log);
+        self
     }
 
     /// Create from environment variables
@@ -193,7 +212,7 @@ impl AwsSnsSmsService {
     }
 
     /// Create SMS attributes for AWS SNS
-    fn create_sms_attributes(&self) -> HashMap<String, MessageAttributeValue> {
+    fn create_sms_attributes(&self, sender_id: Option<&str>) -> HashMap<String, MessageAttributeValue> {
         let mut attributes = HashMap::new();
 
         // Set SMS type (Transactional or Promotional)
@@ -207,7 +226,7 @@ impl AwsSnsSmsService {
         );
 
         // Set sender ID if configured (not supported in all regions)
-        if let Some(ref sender_id) = self.config.sender_id {
+        if let Some(sender_id) = sender_id {
             attributes.insert(
                 "AWS.SNS.SMS.SenderID".to_string(),
                 MessageAttributeValue::builder()
@@ -218,12 +237,25 @@ impl AwsSnsSmsService {
             );
         }
 
+        // Tag the message with the trace ID of the request that sent it
+        if let Some(trace_id) = current_trace_id() {
+            attributes.insert(
+                "TraceId".to_string(),
+                MessageAttributeValue::builder()
+                    .data_type("String".to_string())
+                    .string_value(trace_id)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
         attributes
     }
 
     /// Send SMS with retry logic
     async fn send_with_retry(
         &self,
+        sender_id: Option<&str>,
         to: &str,
         message: &str,
     ) -> Result<String, InfrastructureError> {
@@ -241,7 +273,7 @@ impl AwsSnsSmsService {
             );
 
             // Create the SMS attributes
-            let attributes = self.create_sms_attributes();
+            let attributes = self.create_sms_attributes(sender_id);
 
             // Try to send the message
             let result = self.client
@@ -259,9 +291,10 @@ impl AwsSnsSmsService {
                         .to_string();
 
                     info!(
-                        "SMS sent successfully to {} via AWS SNS with message ID: {}",
+                        "SMS sent successfully to {} via AWS SNS with message ID: {} (trace: {})",
                         mask_phone_number(to),
-                        message_id
+                        message_id,
+                        current_trace_id().as_deref().unwrap_or("-")
                     );
 
                     return Ok(message_id);
@@ -307,11 +340,14 @@ impl AwsSnsSmsService {
             }
         }
     }
-}
 
-#[async_trait]
-impl SmsService for AwsSnsSmsService {
-    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+    /// Validate and send a message, optionally overriding the sender ID
+    async fn send_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: Option<&str>,
+    ) -> Result<String, InfrastructureError> {
         // Validate and normalize the phone number
         let normalized_phone = self.validate_phone_number(phone_number)?;
 
@@ -329,8 +365,28 @@ impl SmsService for AwsSnsSmsService {
             ));
         }
 
+        if let Some(ref dry_run) = self.dry_run {
+            return Ok(dry_run.record(self.provider_name(), sender_id.unwrap_or_default(), &normalized_phone, message));
+        }
+
         // Send the message with retry logic
-        self.send_with_retry(&normalized_phone, message).await
+        self.send_with_retry(sender_id, &normalized_phone, message).await
+    }
+}
+
+#[async_trait]
+impl SmsService for AwsSnsSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.send_from(phone_number, message, self.config.sender_id.as_deref()).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.send_from(phone_number, message, Some(sender_id)).await
     }
 
     fn provider_name(&self) -> &str {
@@ -338,6 +394,10 @@ impl SmsService for AwsSnsSmsService {
     }
 
     async fn is_available(&self) -> bool {
+        if self.dry_run.is_some() {
+            return true;
+        }
+
         // Perform a simple health check by attempting to list SMS attributes
         // This is a lightweight operation that verifies AWS credentials and connectivity
         match self.client
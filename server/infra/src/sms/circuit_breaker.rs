@@ -0,0 +1,145 @@
+//! Circuit Breaking SMS Service
+//!
+//! A provider that is down makes every send wait for its request timeout and
+//! retries before failing, which a verification request pays for each time.
+//! This module guards one provider with a [`CircuitBreaker`]: after a run of
+//! consecutive failures the provider is skipped at once, so failover moves
+//! on to the next provider without waiting. Once the open period is over a
+//! single send probes the provider and closes the circuit if it succeeds.
+//!
+//! ## Features
+//!
+//! - Consecutive failures open the circuit, any success resets the count
+//! - Sends refused without calling the provider while the circuit is open
+//! - Provider reported unavailable while the circuit is open
+//! - Half-open probe with one send after the open period
+//! - Sends to invalid numbers neither trip nor close the circuit
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{
+    circuit_breaker::{CircuitBreaker, CircuitState},
+    sms::sms_service::{is_valid_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// Circuit breaker settings of the SMS providers
+#[derive(Debug, Clone)]
+pub struct SmsCircuitBreakerConfig {
+    /// Consecutive failed sends that open the circuit
+    pub failure_threshold: u32,
+    /// How long the provider is skipped before a probe send is let through
+    pub open_duration: Duration,
+}
+
+impl Default for SmsCircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl SmsCircuitBreakerConfig {
+    /// Load from `SMS_CIRCUIT_FAILURE_THRESHOLD` and
+    /// `SMS_CIRCUIT_OPEN_SECONDS`, with the defaults for unset variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            failure_threshold: std::env::var("SMS_CIRCUIT_FAILURE_THRESHOLD")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(defaults.failure_threshold),
+            open_duration: std::env::var("SMS_CIRCUIT_OPEN_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.open_duration),
+        }
+    }
+}
+
+/// SMS service that stops calling a provider while it keeps failing
+pub struct CircuitBreakingSmsService {
+    /// Provider the messages are sent through
+    inner: Box<dyn SmsService>,
+    /// Breaker counting the provider's consecutive failures
+    breaker: CircuitBreaker,
+}
+
+impl CircuitBreakingSmsService {
+    /// Create a new circuit breaking SMS service
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The SMS provider to guard
+    /// * `config` - Failure threshold and open period of the circuit
+    pub fn new(inner: Box<dyn SmsService>, config: &SmsCircuitBreakerConfig) -> Self {
+        let breaker = CircuitBreaker::new(config.failure_threshold, config.open_duration)
+            .with_name(format!("SMS provider {}", inner.provider_name()));
+
+        Self { inner, breaker }
+    }
+
+    /// Current state of the provider's circuit
+    pub fn state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Send through the provider unless its circuit is open
+    async fn guarded<F>(&self, phone_number: &str, send: F) -> Result<String, InfrastructureError>
+    where
+        F: std::future::Future<Output = Result<String, InfrastructureError>>,
+    {
+        // Invalid numbers are refused by every provider and say nothing
+        // about its health
+        if !is_valid_phone_number(phone_number) {
+            return send.await;
+        }
+
+        if let Err(retry_after) = self.breaker.try_acquire() {
+            return Err(InfrastructureError::Sms(format!(
+                "{} circuit is open; retry in {} ms",
+                self.inner.provider_name(),
+                retry_after.as_millis()
+            )));
+        }
+
+        let result = send.await;
+        match result {
+            Ok(_) => self.breaker.record_success(),
+            Err(_) => self.breaker.record_failure(),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl SmsService for CircuitBreakingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.guarded(phone_number, self.inner.send_sms(phone_number, message)).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.guarded(phone_number, self.inner.send_sms_from(phone_number, message, sender_id))
+            .await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        // Available again once the open period is over, for the probe
+        self.breaker.retry_after().is_none() && self.inner.is_available().await
+    }
+}
@@ -0,0 +1,96 @@
+//! Cost Tracking SMS Service
+//!
+//! Checks each message against the daily SMS budget before handing it to
+//! the provider, and adds the estimated cost of the messages the provider
+//! accepted to the daily spend.
+//!
+//! ## Features
+//!
+//! - Costs estimated by provider and destination prefix
+//! - Over-budget messages refused, so failover tries the next provider;
+//!   in downgrade mode only the cheapest provider of a destination sends
+//! - A failing spend write never fails the send
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use re_core::services::sms_cost::{SmsBudgetDecision, SmsCostService};
+
+use crate::{
+    sms::sms_service::{mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// SMS service that keeps one provider within the daily budget
+pub struct CostTrackingSmsService {
+    /// Provider the messages are sent through
+    inner: Box<dyn SmsService>,
+    /// Provider name the rates are configured under (e.g. `twilio`)
+    provider: String,
+    /// Rates, budget and daily spend
+    costs: Arc<SmsCostService>,
+}
+
+impl CostTrackingSmsService {
+    /// Create a new cost tracking SMS service
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The SMS provider to send through
+    /// * `provider` - Name the provider's rates are configured under
+    /// * `costs` - Service estimating costs and enforcing the budget
+    pub fn new(inner: Box<dyn SmsService>, provider: impl Into<String>, costs: Arc<SmsCostService>) -> Self {
+        Self {
+            inner,
+            provider: provider.into(),
+            costs,
+        }
+    }
+
+    /// Refuse the message if it would exceed the budget
+    async fn check_budget(&self, phone_number: &str) -> Result<(), InfrastructureError> {
+        match self.costs.check_budget(&self.provider, phone_number).await {
+            SmsBudgetDecision::Allowed => Ok(()),
+            SmsBudgetDecision::Refused => {
+                warn!(
+                    "Daily SMS budget spent, refusing SMS to {} via {}",
+                    mask_phone_number(phone_number),
+                    self.provider
+                );
+                Err(InfrastructureError::Sms("Daily SMS budget exceeded".to_string()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SmsService for CostTrackingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.check_budget(phone_number).await?;
+        let message_id = self.inner.send_sms(phone_number, message).await?;
+        self.costs.record(&self.provider, phone_number).await;
+        Ok(message_id)
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.check_budget(phone_number).await?;
+        let message_id = self.inner.send_sms_from(phone_number, message, sender_id).await?;
+        self.costs.record(&self.provider, phone_number).await;
+        Ok(message_id)
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+}
@@ -0,0 +1,91 @@
+//! Country Routing SMS Service
+//!
+//! Some providers reach a country more reliably or more cheaply than
+//! others, and some countries require a local provider. This module sends
+//! each message through the provider routed for the recipient's dialing
+//! code, configured as rules such as `+86 -> aliyun`, and everything else
+//! through the default route.
+//!
+//! ## Features
+//!
+//! - Longest dialing-code match, so `+1` and `+1268` can coexist
+//! - Routes naming the same provider share one instance, and with it the
+//!   provider's throttling caps
+//! - Any route may name `failover` to route between every provider
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::{
+    sms::sms_service::{mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// SMS service that selects the provider by recipient country
+pub struct CountryRoutingSmsService {
+    /// Provider of destinations without a route
+    default: Arc<dyn SmsService>,
+    /// Dialing code and provider of each route, longest dialing code first
+    routes: Vec<(String, Arc<dyn SmsService>)>,
+}
+
+impl CountryRoutingSmsService {
+    /// Create a new country routing SMS service
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - Provider of destinations without a route
+    /// * `routes` - Dialing code and provider of each route
+    pub fn new(default: Arc<dyn SmsService>, mut routes: Vec<(String, Arc<dyn SmsService>)>) -> Self {
+        routes.sort_by_key(|(country_code, _)| std::cmp::Reverse(country_code.len()));
+        Self { default, routes }
+    }
+
+    /// Provider a recipient's messages are sent through
+    pub fn service_for(&self, phone_number: &str) -> &dyn SmsService {
+        match self
+            .routes
+            .iter()
+            .find(|(country_code, _)| phone_number.starts_with(country_code.as_str()))
+        {
+            Some((country_code, service)) => {
+                debug!(
+                    "Routing SMS to {} through {} for {}",
+                    mask_phone_number(phone_number),
+                    service.provider_name(),
+                    country_code
+                );
+                service.as_ref()
+            }
+            None => self.default.as_ref(),
+        }
+    }
+}
+
+#[async_trait]
+impl SmsService for CountryRoutingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.service_for(phone_number).send_sms(phone_number, message).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.service_for(phone_number)
+            .send_sms_from(phone_number, message, sender_id)
+            .await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.default.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.default.is_available().await
+    }
+}
@@ -0,0 +1,118 @@
+//! Delivery Logging SMS Service
+//!
+//! Records every message handed to the provider in the SMS delivery log,
+//! together with the trace ID of the API request being served. Provider
+//! delivery receipts carry the provider message ID, so the log links a
+//! receipt back to the request that caused the message.
+//!
+//! ## Features
+//!
+//! - One log entry per send, for accepted and rejected messages alike
+//! - Recipient masked to the last 4 digits before it is stored
+//! - Template name and estimated cost recorded with each entry
+//! - A failing log write never fails the send
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use re_core::domain::entities::sms_delivery::SmsDeliveryLog;
+use re_core::repositories::SmsDeliveryLogRepository;
+use re_core::services::sms_cost::SmsCostService;
+use re_core::services::trace::current_trace_id;
+
+use crate::{
+    sms::sms_service::{current_template, mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// SMS service that records each send in the delivery log
+pub struct DeliveryLoggingSmsService {
+    /// Provider the messages are sent through
+    inner: Box<dyn SmsService>,
+    /// Where delivery log entries are stored
+    logs: Arc<dyn SmsDeliveryLogRepository>,
+    /// Configured provider name and the rates its sends are priced with
+    costs: Option<(String, Arc<SmsCostService>)>,
+}
+
+impl DeliveryLoggingSmsService {
+    /// Create a new delivery logging SMS service
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The SMS provider to send through
+    /// * `logs` - Repository the delivery log entries are saved to
+    pub fn new(inner: Box<dyn SmsService>, logs: Arc<dyn SmsDeliveryLogRepository>) -> Self {
+        Self {
+            inner,
+            logs,
+            costs: None,
+        }
+    }
+
+    /// Record the estimated cost of each accepted message
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - Name the provider's rates are configured under
+    /// * `costs` - Service estimating costs
+    pub fn with_costs(mut self, provider: impl Into<String>, costs: Arc<SmsCostService>) -> Self {
+        self.costs = Some((provider.into(), costs));
+        self
+    }
+
+    /// Save the log entry of a send attempt
+    async fn record(&self, phone_number: &str, result: &Result<String, InfrastructureError>) {
+        let trace_id = current_trace_id();
+        let provider = self.inner.provider_name();
+        let phone_masked = mask_phone_number(phone_number);
+
+        let mut log = match result {
+            Ok(message_id) => {
+                let log = SmsDeliveryLog::sent(trace_id, provider, message_id, phone_masked);
+                match &self.costs {
+                    Some((provider, costs)) => log.with_cost_micros(costs.estimate(provider, phone_number)),
+                    None => log,
+                }
+            }
+            Err(e) => SmsDeliveryLog::failed(trace_id, provider, phone_masked, e.to_string()),
+        };
+        if let Some(template) = current_template() {
+            log = log.with_template(template);
+        }
+
+        if let Err(e) = self.logs.save(log).await {
+            warn!("Failed to record SMS delivery log: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl SmsService for DeliveryLoggingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        let result = self.inner.send_sms(phone_number, message).await;
+        self.record(phone_number, &result).await;
+        result
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        let result = self.inner.send_sms_from(phone_number, message, sender_id).await;
+        self.record(phone_number, &result).await;
+        result
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+}
@@ -0,0 +1,109 @@
+//! Dry-run SMS recording
+//!
+//! With `SMS_DRY_RUN` set, the real providers validate and prepare every
+//! message as usual but record it here instead of calling their API. This
+//! keeps the provider code paths, throttling, delivery logging and cost
+//! tracking exercised in staging without sending anything or paying for it.
+//!
+//! The most recent messages are kept in memory, so test suites can read
+//! verification codes from a debug endpoint in non-production environments.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::sms::sms_service::mask_phone_number;
+
+/// Number of messages kept by the shared log
+pub const DEFAULT_DRY_RUN_CAPACITY: usize = 200;
+
+/// Prefix of the message IDs returned for recorded messages
+pub const DRY_RUN_MESSAGE_ID_PREFIX: &str = "dry-run-";
+
+/// A message recorded instead of being sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunMessage {
+    /// Message ID returned in place of the provider's
+    pub message_id: String,
+    /// Provider that would have sent the message
+    pub provider: String,
+    /// Recipient phone number, normalized by the provider
+    pub phone_number: String,
+    /// Sender ID or number the message would have come from
+    pub sender_id: String,
+    /// Message body, as it would have been sent
+    pub body: String,
+    /// When the message was recorded
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// In-memory log of the most recent dry-run messages
+pub struct SmsDryRunLog {
+    messages: Mutex<VecDeque<DryRunMessage>>,
+    capacity: usize,
+}
+
+impl SmsDryRunLog {
+    /// Create a log keeping up to `capacity` messages
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            messages: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// The log shared by every provider of this instance
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<SmsDryRunLog>> = OnceLock::new();
+        SHARED
+            .get_or_init(|| Arc::new(Self::new(DEFAULT_DRY_RUN_CAPACITY)))
+            .clone()
+    }
+
+    /// Record a message instead of sending it
+    ///
+    /// # Returns
+    ///
+    /// The message ID to report in place of the provider's
+    pub fn record(&self, provider: &str, sender_id: &str, phone_number: &str, body: &str) -> String {
+        let message_id = format!("{}{}", DRY_RUN_MESSAGE_ID_PREFIX, Uuid::new_v4().simple());
+
+        info!(
+            "Dry run: SMS to {} via {} recorded as {} (message length: {} chars)",
+            mask_phone_number(phone_number),
+            provider,
+            message_id,
+            body.len()
+        );
+
+        let mut messages = self.messages.lock().unwrap_or_else(|e| e.into_inner());
+        if messages.len() == self.capacity {
+            messages.pop_front();
+        }
+        messages.push_back(DryRunMessage {
+            message_id: message_id.clone(),
+            provider: provider.to_string(),
+            phone_number: phone_number.to_string(),
+            sender_id: sender_id.to_string(),
+            body: body.to_string(),
+            recorded_at: Utc::now(),
+        });
+
+        message_id
+    }
+
+    /// The most recent messages, newest first, optionally only those to one number
+    pub fn recent(&self, phone_number: Option<&str>, limit: usize) -> Vec<DryRunMessage> {
+        let messages = self.messages.lock().unwrap_or_else(|e| e.into_inner());
+        messages
+            .iter()
+            .rev()
+            .filter(|message| phone_number.is_none_or(|phone| message.phone_number == phone))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
@@ -1,59 +1,99 @@
 //! Failover SMS Service Implementation
 //!
-//! This module provides an SMS service that automatically fails over from a primary
-//! provider to a backup provider when the primary service is unavailable.
+//! This module provides an SMS service that routes each message to the
+//! provider performing best for its destination country, and fails over to
+//! the next best provider when a send fails.
 //!
 //! ## Features
 //!
-//! - Automatic failover from primary to backup SMS provider
-//! - Configurable failover timeout (default: 30 seconds)
-//! - Health check monitoring for automatic recovery
-//! - Seamless switching between providers
+//! - Rolling success rate and latency per provider and destination country
+//! - Best-scoring provider picked per message, the others tried in turn
+//! - Configured provider order breaks ties, e.g. for countries without sends
+//! - Failures forgotten after a while, so recovered providers win back traffic
+//! - Providers whose delivery receipts report too many failures tried last
 //! - Comprehensive logging of failover events
 
 use async_trait::async_trait;
-use std::sync::Arc;
+use phonenumber::PhoneNumber;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::{
-    sms::sms_service::SmsService,
+    sms::sms_service::{mask_phone_number, SmsService},
     InfrastructureError,
 };
+use re_core::services::sms_delivery::SmsDeliveryHealth;
 use re_core::services::verification::SmsServiceTrait;
 
-/// State tracking for failover service
+/// Destination key of numbers whose country cannot be determined
+const UNKNOWN_COUNTRY: &str = "unknown";
+
+/// Scoring settings of the failover router
 #[derive(Debug, Clone)]
-struct FailoverState {
-    /// Whether we're currently using the backup service
-    using_backup: bool,
-    /// When the primary service last failed
-    last_primary_failure: Option<Instant>,
-    /// Number of consecutive failures on primary
-    primary_failure_count: u32,
+pub struct SmsRoutingConfig {
+    /// Number of recent sends scored per provider and country
+    pub window_size: usize,
+    /// Age after which a send no longer counts
+    pub max_sample_age: Duration,
+    /// Average send latency at which a provider loses half of `latency_weight`
+    pub latency_target: Duration,
+    /// Largest share of the score lost to latency, between 0 and 1
+    pub latency_weight: f64,
 }
 
-impl Default for FailoverState {
+impl Default for SmsRoutingConfig {
     fn default() -> Self {
         Self {
-            using_backup: false,
-            last_primary_failure: None,
-            primary_failure_count: 0,
+            window_size: 50,
+            max_sample_age: Duration::from_secs(600),
+            latency_target: Duration::from_secs(2),
+            latency_weight: 0.2,
         }
     }
 }
 
-/// SMS service with automatic failover capability
+/// Outcome of one send
+#[derive(Debug, Clone, Copy)]
+struct SendSample {
+    at: Instant,
+    success: bool,
+    latency: Duration,
+}
+
+/// Score of a provider for a destination country
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmsProviderScore {
+    /// Name of the provider
+    pub provider: String,
+    /// Dialing code of the destination, such as `+61`
+    pub country: String,
+    /// Number of recent sends scored
+    pub samples: usize,
+    /// Share of recent sends that succeeded, None without sends
+    pub success_rate: Option<f64>,
+    /// Average duration of recent sends, None without sends
+    pub average_latency: Option<Duration>,
+    /// Routing score; the highest is tried first
+    pub score: f64,
+}
+
+/// SMS service routing each message to the best-scoring provider
+///
+/// Providers start from a prior of one successful send at the latency
+/// target, so a provider with a good record is preferred over one without
+/// sends, and a single failure moves a provider without a record behind
+/// the others. Scores are kept in process memory, per instance.
 pub struct FailoverSmsService {
-    /// Primary SMS service (e.g., Twilio)
-    primary: Box<dyn SmsService>,
-    /// Backup SMS service (e.g., AWS SNS)
-    backup: Box<dyn SmsService>,
-    /// Failover state
-    state: Arc<RwLock<FailoverState>>,
-    /// How long to wait before retrying primary after failure
-    failover_timeout: Duration,
+    /// Providers in configured order of preference
+    providers: Vec<Box<dyn SmsService>>,
+    /// Recent sends per provider index and destination country
+    samples: Mutex<HashMap<(usize, String), VecDeque<SendSample>>>,
+    /// Scoring settings
+    config: SmsRoutingConfig,
+    /// Delivery failure rates reported by provider receipts
+    delivery_health: Option<Arc<SmsDeliveryHealth>>,
 }
 
 impl FailoverSmsService {
@@ -61,121 +101,230 @@ impl FailoverSmsService {
     ///
     /// # Arguments
     ///
-    /// * `primary` - The primary SMS service to use
-    /// * `backup` - The backup SMS service to fail over to
-    /// * `failover_timeout` - How long to wait before retrying the primary service
-    pub fn new(
-        primary: Box<dyn SmsService>,
-        backup: Box<dyn SmsService>,
-        failover_timeout: Duration,
-    ) -> Self {
+    /// * `providers` - The SMS services to route between, in order of
+    ///   preference when their scores are equal
+    pub fn new(providers: Vec<Box<dyn SmsService>>) -> Self {
         info!(
-            "Initializing failover SMS service with {} (primary) and {} (backup)",
-            primary.provider_name(),
-            backup.provider_name()
+            "Initializing failover SMS service over {}",
+            providers
+                .iter()
+                .map(|provider| provider.provider_name())
+                .collect::<Vec<_>>()
+                .join(", ")
         );
-        
+
         Self {
-            primary,
-            backup,
-            state: Arc::new(RwLock::new(FailoverState::default())),
-            failover_timeout,
+            providers,
+            samples: Mutex::new(HashMap::new()),
+            config: SmsRoutingConfig::default(),
+            delivery_health: None,
         }
     }
-    
-    /// Check if we should try the primary service again
-    async fn should_retry_primary(&self) -> bool {
-        let state = self.state.read().await;
-        
-        if !state.using_backup {
-            return true; // Already using primary
-        }
-        
-        if let Some(last_failure) = state.last_primary_failure {
-            // Check if enough time has passed since last failure
-            last_failure.elapsed() > self.failover_timeout
+
+    /// Score providers with custom settings
+    pub fn with_routing_config(mut self, config: SmsRoutingConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Try providers last while their delivery receipts report a failure
+    /// rate above the configured maximum
+    ///
+    /// Messages accepted by a provider can still fail to reach the phone,
+    /// which the send result does not show.
+    pub fn with_delivery_health(mut self, delivery_health: Arc<SmsDeliveryHealth>) -> Self {
+        self.delivery_health = Some(delivery_health);
+        self
+    }
+
+    /// Dialing code of the destination, such as `+61`
+    fn country_of(phone_number: &str) -> String {
+        phone_number
+            .parse::<PhoneNumber>()
+            .map(|parsed| format!("+{}", parsed.code().value()))
+            .unwrap_or_else(|_| UNKNOWN_COUNTRY.to_string())
+    }
+
+    /// Score recent sends, oldest first
+    fn score(&self, samples: &[SendSample]) -> f64 {
+        let successes = samples.iter().filter(|sample| sample.success).count();
+        let total_latency: Duration = samples.iter().map(|sample| sample.latency).sum();
+
+        // Prior of one successful send at the latency target
+        let count = samples.len() as f64 + 1.0;
+        let success_rate = (successes as f64 + 1.0) / count;
+        let latency = (total_latency + self.config.latency_target).as_secs_f64() / count;
+        let target = self.config.latency_target.as_secs_f64();
+        let latency_penalty = if latency + target > 0.0 {
+            self.config.latency_weight * latency / (latency + target)
         } else {
-            true // No recorded failure, try primary
+            0.0
+        };
+
+        success_rate - latency_penalty
+    }
+
+    /// Recent sends of a provider to a country that still count
+    fn recent_samples(&self, index: usize, country: &str) -> Vec<SendSample> {
+        let Ok(samples) = self.samples.lock() else {
+            return Vec::new();
+        };
+
+        samples
+            .get(&(index, country.to_string()))
+            .map(|samples| {
+                samples
+                    .iter()
+                    .filter(|sample| sample.at.elapsed() <= self.config.max_sample_age)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Record the outcome of a send
+    pub(crate) fn record(&self, index: usize, country: &str, success: bool, latency: Duration) {
+        let Ok(mut samples) = self.samples.lock() else {
+            return;
+        };
+
+        let window = samples.entry((index, country.to_string())).or_default();
+        window.push_back(SendSample {
+            at: Instant::now(),
+            success,
+            latency,
+        });
+        while window.len() > self.config.window_size.max(1)
+            || window
+                .front()
+                .is_some_and(|sample| sample.at.elapsed() > self.config.max_sample_age)
+        {
+            window.pop_front();
         }
     }
-    
-    /// Record a primary service failure and switch to backup
-    async fn record_primary_failure(&self) {
-        let mut state = self.state.write().await;
-        
-        state.primary_failure_count += 1;
-        state.last_primary_failure = Some(Instant::now());
-        
-        if !state.using_backup {
-            warn!(
-                "Primary SMS service ({}) failed, switching to backup ({})",
-                self.primary.provider_name(),
-                self.backup.provider_name()
-            );
-            state.using_backup = true;
+
+    /// Whether a provider's receipts report too many failed deliveries
+    fn is_degraded(&self, provider: &dyn SmsService) -> bool {
+        self.delivery_health
+            .as_ref()
+            .is_some_and(|health| health.is_degraded(provider.provider_name()))
+    }
+
+    /// Provider indexes in the order to try them for a country
+    fn ranked_providers(&self, country: &str) -> Vec<usize> {
+        let mut ranked: Vec<(usize, bool, f64)> = self
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(index, provider)| {
+                let score = self.score(&self.recent_samples(index, country));
+                (index, self.is_degraded(provider.as_ref()), score)
+            })
+            .collect();
+
+        // Stable sort keeps the configured order between equal scores
+        ranked.sort_by(|a, b| a.1.cmp(&b.1).then(b.2.total_cmp(&a.2)));
+        ranked.into_iter().map(|(index, _, _)| index).collect()
+    }
+
+    /// Scores of every provider for every country sent to recently
+    pub fn provider_scores(&self) -> Vec<SmsProviderScore> {
+        let keys: Vec<(usize, String)> = match self.samples.lock() {
+            Ok(samples) => samples.keys().cloned().collect(),
+            Err(_) => return Vec::new(),
+        };
+
+        let mut scores: Vec<SmsProviderScore> = keys
+            .into_iter()
+            .map(|(index, country)| {
+                let samples = self.recent_samples(index, &country);
+                let successes = samples.iter().filter(|sample| sample.success).count();
+                let total_latency: Duration = samples.iter().map(|sample| sample.latency).sum();
+
+                SmsProviderScore {
+                    provider: self.providers[index].provider_name().to_string(),
+                    samples: samples.len(),
+                    success_rate: (!samples.is_empty()).then(|| successes as f64 / samples.len() as f64),
+                    average_latency: (!samples.is_empty()).then(|| total_latency / samples.len() as u32),
+                    score: self.score(&samples),
+                    country,
+                }
+            })
+            .collect();
+
+        scores.sort_by(|a, b| a.country.cmp(&b.country).then(b.score.total_cmp(&a.score)));
+        scores
+    }
+
+    /// Send through one service, from the given sender if any
+    async fn send_via(
+        service: &dyn SmsService,
+        phone_number: &str,
+        message: &str,
+        sender_id: Option<&str>,
+    ) -> Result<String, InfrastructureError> {
+        match sender_id {
+            Some(sender_id) => service.send_sms_from(phone_number, message, sender_id).await,
+            None => service.send_sms(phone_number, message).await,
         }
     }
-    
-    /// Record a successful primary service operation
-    async fn record_primary_success(&self) {
-        let mut state = self.state.write().await;
-        
-        if state.using_backup {
-            info!(
-                "Primary SMS service ({}) recovered, switching back from backup",
-                self.primary.provider_name()
-            );
+
+    /// Send with the best-scoring provider, failing over to the others
+    async fn send_with_failover(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: Option<&str>,
+    ) -> Result<String, InfrastructureError> {
+        let country = Self::country_of(phone_number);
+        let ranked = self.ranked_providers(&country);
+
+        for (attempt, index) in ranked.iter().copied().enumerate() {
+            let provider = self.providers[index].as_ref();
+            if attempt > 0 {
+                warn!(
+                    "Failing over SMS to {} to {}",
+                    mask_phone_number(phone_number),
+                    provider.provider_name()
+                );
+            } else {
+                debug!("Routing SMS for {} to {}", country, provider.provider_name());
+            }
+
+            let started = Instant::now();
+            let result = Self::send_via(provider, phone_number, message, sender_id).await;
+            self.record(index, &country, result.is_ok(), started.elapsed());
+
+            match result {
+                Ok(message_id) => return Ok(message_id),
+                Err(e) => error!("SMS service ({}) failed: {}", provider.provider_name(), e),
+            }
         }
-        
-        state.using_backup = false;
-        state.primary_failure_count = 0;
-        state.last_primary_failure = None;
+
+        Err(InfrastructureError::Sms(format!(
+            "All SMS services failed: {}",
+            ranked
+                .iter()
+                .map(|index| self.providers[*index].provider_name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )))
     }
 }
 
 #[async_trait]
 impl SmsService for FailoverSmsService {
     async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
-        // Check if we should try the primary service
-        if self.should_retry_primary().await {
-            // Try primary service first
-            match self.primary.send_sms(phone_number, message).await {
-                Ok(result) => {
-                    self.record_primary_success().await;
-                    return Ok(result);
-                }
-                Err(e) => {
-                    error!(
-                        "Primary SMS service ({}) failed: {}",
-                        self.primary.provider_name(),
-                        e
-                    );
-                    self.record_primary_failure().await;
-                }
-            }
-        }
-        
-        // Use backup service
-        info!(
-            "Using backup SMS service ({}) to send message",
-            self.backup.provider_name()
-        );
-        
-        match self.backup.send_sms(phone_number, message).await {
-            Ok(result) => Ok(result),
-            Err(e) => {
-                error!(
-                    "Backup SMS service ({}) also failed: {}",
-                    self.backup.provider_name(),
-                    e
-                );
-                Err(InfrastructureError::Sms(format!(
-                    "Both primary and backup SMS services failed. Primary: {}, Backup: {}",
-                    self.primary.provider_name(),
-                    self.backup.provider_name()
-                )))
-            }
-        }
+        self.send_with_failover(phone_number, message, None).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.send_with_failover(phone_number, message, Some(sender_id)).await
     }
     
     fn provider_name(&self) -> &str {
@@ -183,19 +332,13 @@ impl SmsService for FailoverSmsService {
     }
     
     async fn is_available(&self) -> bool {
-        // Check if either service is available
-        let primary_available = self.primary.is_available().await;
-        let backup_available = self.backup.is_available().await;
-        
-        if !primary_available && backup_available {
-            // Record that primary is down
-            self.record_primary_failure().await;
-        } else if primary_available {
-            // Record that primary is up
-            self.record_primary_success().await;
+        // Available while any service is
+        for provider in &self.providers {
+            if provider.is_available().await {
+                return true;
+            }
         }
-        
-        primary_available || backup_available
+        false
     }
 }
 
@@ -206,13 +349,9 @@ pub struct FailoverSmsServiceAdapter {
 
 impl FailoverSmsServiceAdapter {
     /// Create a new failover SMS service adapter
-    pub fn new(
-        primary: Box<dyn SmsService>,
-        backup: Box<dyn SmsService>,
-        failover_timeout: Duration,
-    ) -> Self {
+    pub fn new(providers: Vec<Box<dyn SmsService>>) -> Self {
         Self {
-            inner: Arc::new(FailoverSmsService::new(primary, backup, failover_timeout)),
+            inner: Arc::new(FailoverSmsService::new(providers)),
         }
     }
 }
@@ -226,7 +365,11 @@ impl SmsServiceTrait for FailoverSmsServiceAdapter {
         }
     }
     
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        self.inner.send_sms(phone, message).await.map_err(|e| e.to_string())
+    }
+    
     fn is_valid_phone_number(&self, phone: &str) -> bool {
         crate::sms::sms_service::is_valid_phone_number(phone)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,201 @@
+//! Phone Number Lookup
+//!
+//! OTPs sent to a landline, a VoIP number or a number that does not exist
+//! are accepted by the SMS providers and then never delivered, leaving the
+//! user waiting for a code. This module asks the Twilio Lookup API about a
+//! number before a code is sent, so the verification service can reject it
+//! with an error the user can act on.
+//!
+//! ## Features
+//!
+//! - Validity and line type from Twilio Lookup v2 line type intelligence
+//! - Fixed and non-fixed VoIP numbers reported alike
+//! - Results cached per number, since each lookup is billed
+//! - Security: Phone number masking in logs
+
+use async_trait::async_trait;
+use moka::future::Cache;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use re_core::services::verification::{PhoneLineType, PhoneNumberLookup, PhoneNumberLookupTrait};
+use re_shared::config::SecretString;
+
+use crate::{sms::sms_service::mask_phone_number, InfrastructureError};
+
+/// Default endpoint of the Twilio Lookup v2 API
+pub const DEFAULT_TWILIO_LOOKUP_URL: &str = "https://lookups.twilio.com/v2/PhoneNumbers";
+
+/// Most numbers whose lookup results are kept
+const MAX_CACHED_LOOKUPS: u64 = 100_000;
+
+/// Twilio Lookup configuration
+#[derive(Debug, Clone)]
+pub struct TwilioLookupConfig {
+    /// Twilio Account SID
+    pub account_sid: String,
+    /// Twilio Auth Token
+    pub auth_token: SecretString,
+    /// Endpoint of the Lookup API
+    pub api_url: String,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+    /// How long the result for a number is reused
+    pub cache_ttl: Duration,
+}
+
+impl TwilioLookupConfig {
+    /// Create configuration from environment variables
+    ///
+    /// Uses the Twilio SMS credentials, `TWILIO_LOOKUP_URL`,
+    /// `TWILIO_LOOKUP_TIMEOUT_SECS` (default 5) and
+    /// `TWILIO_LOOKUP_CACHE_HOURS` (default 24).
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let account_sid = std::env::var("TWILIO_ACCOUNT_SID")
+            .map_err(|_| InfrastructureError::Config("TWILIO_ACCOUNT_SID not set".to_string()))?;
+        let auth_token = std::env::var("TWILIO_AUTH_TOKEN")
+            .map_err(|_| InfrastructureError::Config("TWILIO_AUTH_TOKEN not set".to_string()))?;
+
+        Ok(Self {
+            account_sid,
+            auth_token: SecretString::from(auth_token),
+            api_url: std::env::var("TWILIO_LOOKUP_URL").unwrap_or_else(|_| DEFAULT_TWILIO_LOOKUP_URL.to_string()),
+            request_timeout_secs: std::env::var("TWILIO_LOOKUP_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            cache_ttl: std::env::var("TWILIO_LOOKUP_CACHE_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(|hours: u64| Duration::from_secs(hours * 3600))
+                .unwrap_or(Duration::from_secs(24 * 3600)),
+        })
+    }
+}
+
+/// Response of the Twilio Lookup v2 API
+#[derive(Debug, Deserialize)]
+pub(crate) struct TwilioLookupResponse {
+    pub valid: bool,
+    pub line_type_intelligence: Option<TwilioLineTypeIntelligence>,
+}
+
+/// Line type intelligence of a Twilio lookup
+#[derive(Debug, Deserialize)]
+pub(crate) struct TwilioLineTypeIntelligence {
+    /// e.g. "mobile", "landline", "fixedVoip", "nonFixedVoip", "tollFree"
+    #[serde(rename = "type")]
+    pub line_type: Option<String>,
+    pub carrier_name: Option<String>,
+}
+
+/// Convert the body of a Twilio Lookup response
+pub(crate) fn parse_lookup(body: &str) -> Result<PhoneNumberLookup, InfrastructureError> {
+    let response: TwilioLookupResponse = serde_json::from_str(body)
+        .map_err(|e| InfrastructureError::Sms(format!("Invalid Twilio Lookup response: {}", e)))?;
+
+    let (line_type, carrier) = match response.line_type_intelligence {
+        Some(intelligence) => (intelligence.line_type, intelligence.carrier_name),
+        None => (None, None),
+    };
+
+    Ok(PhoneNumberLookup {
+        valid: response.valid,
+        line_type: match line_type.as_deref() {
+            Some("mobile") => PhoneLineType::Mobile,
+            Some("landline") => PhoneLineType::Landline,
+            Some("fixedVoip") | Some("nonFixedVoip") => PhoneLineType::Voip,
+            _ => PhoneLineType::Unknown,
+        },
+        carrier,
+    })
+}
+
+/// Carrier lookup through the Twilio Lookup API
+pub struct TwilioNumberLookupService {
+    client: reqwest::Client,
+    config: TwilioLookupConfig,
+    cache: Cache<String, PhoneNumberLookup>,
+}
+
+impl TwilioNumberLookupService {
+    /// Create a new Twilio number lookup service
+    pub fn new(config: TwilioLookupConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .map_err(|e| InfrastructureError::Config(format!("Failed to create Twilio Lookup HTTP client: {}", e)))?;
+
+        let cache = Cache::builder()
+            .max_capacity(MAX_CACHED_LOOKUPS)
+            .time_to_live(config.cache_ttl)
+            .build();
+
+        Ok(Self { client, config, cache })
+    }
+
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let config = TwilioLookupConfig::from_env()?;
+        Self::new(config)
+    }
+
+    /// Look a number up, reusing a cached result when there is one
+    pub async fn lookup_number(&self, phone_number: &str) -> Result<PhoneNumberLookup, InfrastructureError> {
+        if let Some(lookup) = self.cache.get(phone_number).await {
+            return Ok(lookup);
+        }
+
+        let lookup = self.request(phone_number).await?;
+        debug!(
+            "Looked up {}: valid {}, line type {:?}",
+            mask_phone_number(phone_number),
+            lookup.valid,
+            lookup.line_type
+        );
+        self.cache.insert(phone_number.to_string(), lookup.clone()).await;
+        Ok(lookup)
+    }
+
+    /// Send one request to the Lookup API
+    async fn request(&self, phone_number: &str) -> Result<PhoneNumberLookup, InfrastructureError> {
+        let url = format!("{}/{}", self.config.api_url, phone_number.replace('+', "%2B"));
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("Fields", "line_type_intelligence")])
+            .basic_auth(&self.config.account_sid, Some(self.config.auth_token.expose_secret()))
+            .send()
+            .await
+            .map_err(|e| InfrastructureError::Sms(format!("Twilio Lookup request failed: {}", e)))?;
+
+        let status = response.status();
+        // Numbers that cannot be parsed at all are not found
+        if status.as_u16() == 404 {
+            return Ok(PhoneNumberLookup {
+                valid: false,
+                line_type: PhoneLineType::Unknown,
+                carrier: None,
+            });
+        }
+        if !status.is_success() {
+            warn!("Twilio Lookup for {} returned HTTP {}", mask_phone_number(phone_number), status);
+            return Err(InfrastructureError::Sms(format!("Twilio Lookup returned HTTP {}", status)));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| InfrastructureError::Sms(format!("Failed to read Twilio Lookup response: {}", e)))?;
+        parse_lookup(&body)
+    }
+}
+
+#[async_trait]
+impl PhoneNumberLookupTrait for TwilioNumberLookupService {
+    async fn lookup(&self, phone: &str) -> Result<PhoneNumberLookup, String> {
+        self.lookup_number(phone).await.map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,118 @@
+//! Market Routing SMS Service
+//!
+//! Different countries require different sender IDs or short codes, and some
+//! require a compliance footer on every message. This module wraps an SMS
+//! provider and applies the settings of the recipient's market, selected by
+//! dialing code.
+//!
+//! ## Features
+//!
+//! - Longest dialing-code match, so `+1` and `+1268` can coexist
+//! - Per-market sender ID passed to the provider for each message
+//! - Per-market compliance footer appended to each message
+//! - Per-market template namespace for providers with registered templates
+//! - Recipients outside every enabled market use the provider defaults
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use re_shared::config::SmsMarketConfig;
+
+use crate::{
+    sms::sms_service::{mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// SMS service that selects sender and branding by recipient market
+pub struct MarketRoutingSmsService {
+    /// Provider the messages are sent through
+    inner: Box<dyn SmsService>,
+    /// Enabled markets, longest dialing code first
+    markets: Vec<SmsMarketConfig>,
+}
+
+impl MarketRoutingSmsService {
+    /// Create a new market routing SMS service
+    ///
+    /// Disabled markets are dropped; their recipients get the provider
+    /// defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The SMS provider to send through
+    /// * `markets` - Per-market sender and branding settings
+    pub fn new(inner: Box<dyn SmsService>, markets: Vec<SmsMarketConfig>) -> Self {
+        let mut markets: Vec<SmsMarketConfig> =
+            markets.into_iter().filter(|market| market.enabled).collect();
+        markets.sort_by_key(|market| std::cmp::Reverse(market.country_code.len()));
+
+        Self { inner, markets }
+    }
+
+    /// Market settings for a recipient, if the recipient is in an enabled market
+    pub fn market_for(&self, phone_number: &str) -> Option<&SmsMarketConfig> {
+        self.markets.iter().find(|market| market.matches(phone_number))
+    }
+
+    /// Provider template name for a recipient, qualified with the market's
+    /// template namespace when one is configured
+    pub fn template_name(&self, phone_number: &str, template: &str) -> String {
+        match self
+            .market_for(phone_number)
+            .and_then(|market| market.templates_namespace.as_deref())
+        {
+            Some(namespace) => format!("{}.{}", namespace, template),
+            None => template.to_string(),
+        }
+    }
+
+    /// Append the compliance footer of a market to a message
+    fn brand_message(market: &SmsMarketConfig, message: &str) -> String {
+        match market.compliance_footer.as_deref() {
+            Some(footer) => format!("{}\n{}", message, footer),
+            None => message.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SmsService for MarketRoutingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        let Some(market) = self.market_for(phone_number) else {
+            return self.inner.send_sms(phone_number, message).await;
+        };
+
+        debug!(
+            "Routing SMS to {} through market {}",
+            mask_phone_number(phone_number),
+            market.country_code
+        );
+
+        let message = Self::brand_message(market, message);
+        match market.sender_id.as_deref() {
+            Some(sender_id) => self.inner.send_sms_from(phone_number, &message, sender_id).await,
+            None => self.inner.send_sms(phone_number, &message).await,
+        }
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        let message = match self.market_for(phone_number) {
+            Some(market) => Self::brand_message(market, message),
+            None => message.to_string(),
+        };
+        self.inner.send_sms_from(phone_number, &message, sender_id).await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+}
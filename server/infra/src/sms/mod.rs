@@ -10,10 +10,31 @@
 //! - **Mock Implementation**: Console output for development
 //! - **Twilio Support**: Production SMS via Twilio API
 //! - **AWS SNS Support**: Alternative SMS provider with automatic failover
+//! - **Vonage Support**: Third provider for failover when the others are down
+//! - **Smart Routing**: Each message sent through the provider with the best
+//!   recent success rate and latency for its destination country
+//! - **Country Routing**: Per-country provider rules such as `+86 -> aliyun`
+//! - **Market Routing**: Per-country sender IDs, templates and compliance footers
+//! - **Delivery Log**: Provider message IDs recorded with the request trace ID
+//! - **Delivery Receipts**: Twilio and AWS SNS callbacks decoded and verified
+//! - **Cost Tracking**: Estimated spend per provider and destination, capped daily
+//! - **Dry Run**: Real providers record messages instead of calling their API
+//! - **Throttling**: Per-provider concurrency and rate caps, with verification
+//!   codes queued ahead of notifications
+//! - **Circuit Breaking**: Providers that keep failing skipped without waiting
+//!   for their timeouts
+//! - **Number Lookup**: Landlines, VoIP and nonexistent numbers detected with
+//!   Twilio Lookup before a code is sent
 //! - **Phone Number Validation**: E.164 format validation
 //! - **Security**: Phone number masking in logs
 
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use re_core::repositories::SmsDeliveryLogRepository;
+use re_core::services::sms_cost::SmsCostService;
+use re_core::services::sms_delivery::SmsDeliveryHealth;
+use re_shared::config::{SmsProviderLimits, SmsRoute};
 
 pub mod sms_service;
 pub mod mock_sms;
@@ -30,14 +51,49 @@ pub mod aws_sns;
 #[cfg(feature = "aws-sns")]
 pub mod aws_sns_trait_adapter;
 
+// Vonage SMS service (feature-gated)
+#[cfg(feature = "vonage-sms")]
+pub mod vonage;
+#[cfg(feature = "vonage-sms")]
+pub mod vonage_trait_adapter;
+
 // Failover SMS service
 pub mod failover_sms;
 
+// Per-country provider routing
+pub mod country_router;
+
+// Per-market sender routing
+pub mod market_router;
+
+// Delivery log keyed by request trace ID
+pub mod delivery_log;
+
+// Per-provider send queue with concurrency and rate caps
+pub mod throttle;
+
+// Delivery receipts posted by providers
+pub mod receipts;
+
+// Daily budget and estimated spend per provider
+pub mod cost;
+
+// Messages recorded instead of sent in dry-run mode
+pub mod dry_run;
+
+// Providers skipped while they keep failing
+pub mod circuit_breaker;
+
+// Carrier lookups of recipient numbers
+pub mod lookup;
+
 // Re-export commonly used types
 pub use sms_service::{
     SmsService,
     mask_phone_number,
     is_valid_phone_number,
+    with_template,
+    VERIFICATION_CODE_TEMPLATE,
 };
 pub use mock_sms::MockSmsService;
 
@@ -51,12 +107,48 @@ pub use aws_sns::{AwsSnsSmsService, AwsSnsConfig};
 #[cfg(feature = "aws-sns")]
 pub use aws_sns_trait_adapter::AwsSnsSmsServiceAdapter;
 
-pub use failover_sms::{FailoverSmsService, FailoverSmsServiceAdapter};
+#[cfg(feature = "vonage-sms")]
+pub use vonage::{VonageSmsService, VonageConfig};
+#[cfg(feature = "vonage-sms")]
+pub use vonage_trait_adapter::VonageSmsServiceAdapter;
+
+pub use failover_sms::{FailoverSmsService, FailoverSmsServiceAdapter, SmsProviderScore, SmsRoutingConfig};
+pub use country_router::CountryRoutingSmsService;
+pub use market_router::MarketRoutingSmsService;
+pub use delivery_log::DeliveryLoggingSmsService;
+pub use throttle::{SmsPriority, SmsQueueMetrics, ThrottledSmsService};
+pub use receipts::{SnsReceiptProvider, TwilioReceiptProvider};
+pub use cost::CostTrackingSmsService;
+pub use dry_run::{DryRunMessage, SmsDryRunLog};
+pub use circuit_breaker::{CircuitBreakingSmsService, SmsCircuitBreakerConfig};
+pub use lookup::{TwilioLookupConfig, TwilioNumberLookupService};
+
+/// Delivery tracking of the SMS providers
+///
+/// Each provider leg logs the messages it sends, so that provider receipts
+/// can be matched to them, and failover routes around providers whose
+/// receipts report too many failed deliveries. With costs, each leg also
+/// adds its messages to the daily spend and refuses them over budget.
+#[derive(Clone)]
+pub struct SmsDeliveryTracking {
+    /// Where sent messages are logged
+    pub logs: Arc<dyn SmsDeliveryLogRepository>,
+    /// Failure rates fed by the delivery receipts
+    pub health: Arc<SmsDeliveryHealth>,
+    /// Rates, daily budget and spend, if costs are tracked
+    pub costs: Option<Arc<SmsCostService>>,
+}
 
 /// Create an SMS service based on configuration
 ///
 /// Returns the appropriate SMS service implementation based on the
-/// provider specified in the configuration.
+/// provider specified in the configuration. When routes are configured,
+/// messages are sent through a [`CountryRoutingSmsService`] picking the
+/// provider by recipient dialing code. When markets are configured,
+/// the provider is wrapped in a [`MarketRoutingSmsService`] that applies
+/// each market's sender ID and branding. Providers with configured limits
+/// are wrapped in a [`ThrottledSmsService`] first, and every provider in a
+/// [`CircuitBreakingSmsService`] below that.
 ///
 /// # Arguments
 ///
@@ -66,13 +158,138 @@ pub use failover_sms::{FailoverSmsService, FailoverSmsServiceAdapter};
 ///
 /// A boxed SMS service implementation
 pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn SmsService> {
+    create_sms_service_with_delivery_tracking(config, None).await
+}
+
+/// Create an SMS service that logs its sends for delivery tracking
+///
+/// Same as [`create_sms_service`], with every provider leg logging the
+/// messages it sends under its own name and failover routing fed by the
+/// delivery receipts. With costs, every leg also keeps within the daily
+/// SMS budget.
+pub async fn create_sms_service_with_delivery_tracking(
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
+    let provider = if config.routes.is_empty() {
+        create_configured_provider(config, tracking).await
+    } else {
+        create_country_router(config, tracking).await
+    };
+
+    if config.markets.iter().any(|market| market.enabled) {
+        Box::new(MarketRoutingSmsService::new(provider, config.markets.clone()))
+    } else {
+        provider
+    }
+}
+
+/// Create the SMS provider named in the configuration, guarded, throttled
+/// and tracked
+async fn create_configured_provider(
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
+    let provider = create_provider_service(config, tracking).await;
+    match config.provider.as_str() {
+        // Failover legs are guarded, logged and throttled one by one
+        "failover" => provider,
+        name => track_delivery(
+            throttle_provider(break_circuit(provider), name, &config.provider_limits),
+            name,
+            tracking,
+        ),
+    }
+}
+
+/// Create a router sending each message through the provider of its country
+///
+/// The configured provider keeps the generic SMS credentials; other
+/// providers are loaded from their own environment variables, as for
+/// failover. A route whose provider is unknown or not configured is left
+/// out, so its destinations take the default route. The default route is
+/// the configured provider unless a rule overrides it.
+async fn create_country_router(
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
+    let fallback = SmsRoute::fallback(config.provider.clone());
+    let mut services: HashMap<&str, Option<Arc<dyn SmsService>>> = HashMap::new();
+    let mut routes = Vec::new();
+    let mut default = None;
+
+    for route in config.routes.iter().chain(std::iter::once(&fallback)) {
+        if route.country_code.is_none() && default.is_some() {
+            continue;
+        }
+
+        let name = route.provider.as_str();
+        let service = match services.get(name) {
+            Some(service) => service.clone(),
+            None => {
+                let service = create_route_service(name, config, tracking).await.map(Arc::from);
+                services.insert(name, service.clone());
+                service
+            }
+        };
+
+        let Some(service) = service else {
+            tracing::error!(
+                "SMS provider '{}' routed for {} is not available",
+                name,
+                route.country_code.as_deref().unwrap_or("default")
+            );
+            continue;
+        };
+
+        match route.country_code {
+            Some(ref country_code) => routes.push((country_code.clone(), service)),
+            None => default = Some(service),
+        }
+    }
+
+    let default = default.unwrap_or_else(|| {
+        tracing::warn!("No default SMS route available, using mock implementation");
+        Arc::new(MockSmsService::new())
+    });
+    tracing::info!(
+        "Created SMS service routing {} countries, default through {}",
+        routes.len(),
+        default.provider_name()
+    );
+
+    Box::new(CountryRoutingSmsService::new(default, routes))
+}
+
+/// Create the provider of a route, or None when it is unknown or not configured
+async fn create_route_service(
+    name: &str,
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Option<Box<dyn SmsService>> {
+    if name == config.provider {
+        return Some(create_configured_provider(config, tracking).await);
+    }
+
+    match name {
+        "mock" => Some(Box::new(MockSmsService::new())),
+        "failover" => Some(create_failover_sms_service(config, tracking).await),
+        _ => create_provider_leg(name, config, tracking).await,
+    }
+}
+
+/// Create the SMS provider named in the configuration
+async fn create_provider_service(
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
     match config.provider.as_str() {
         "mock" => Box::new(MockSmsService::new()),
         #[cfg(feature = "twilio-sms")]
         "twilio" => {
             // Create Twilio configuration from the generic SMS config
             let twilio_config = TwilioConfig {
-                account_sid: config.api_key.clone(),
+                account_sid: config.api_key.expose_secret().to_string(),
                 auth_token: config.api_secret.clone(),
                 from_number: config.from_number.clone(),
                 max_retries: 3,
@@ -81,7 +298,7 @@ pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn Sm
             };
             
             match TwilioSmsService::new(twilio_config) {
-                Ok(service) => Box::new(service),
+                Ok(service) => Box::new(dry_run(service, config, TwilioSmsService::with_dry_run)),
                 Err(e) => {
                     tracing::error!("Failed to initialize Twilio SMS service: {}", e);
                     tracing::warn!("Falling back to mock SMS service");
@@ -93,7 +310,7 @@ pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn Sm
         "aws-sns" => {
             // Create AWS SNS configuration from the generic SMS config
             let aws_config = AwsSnsConfig {
-                access_key_id: config.api_key.clone(),
+                access_key_id: config.api_key.expose_secret().to_string(),
                 secret_access_key: config.api_secret.clone(),
                 region: std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
                 sender_id: config.from_number.clone().into(),
@@ -104,7 +321,7 @@ pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn Sm
             };
             
             match AwsSnsSmsService::new(aws_config).await {
-                Ok(service) => Box::new(service),
+                Ok(service) => Box::new(dry_run(service, config, AwsSnsSmsService::with_dry_run)),
                 Err(e) => {
                     tracing::error!("Failed to initialize AWS SNS SMS service: {}", e);
                     tracing::warn!("Falling back to mock SMS service");
@@ -112,9 +329,31 @@ pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn Sm
                 }
             }
         }
+        #[cfg(feature = "vonage-sms")]
+        "vonage" => {
+            // Create Vonage configuration from the generic SMS config
+            let vonage_config = VonageConfig {
+                api_key: config.api_key.expose_secret().to_string(),
+                api_secret: config.api_secret.clone(),
+                from: config.from_number.clone(),
+                api_url: vonage::DEFAULT_VONAGE_API_URL.to_string(),
+                max_retries: 3,
+                retry_delay_ms: 1000,
+                request_timeout_secs: 30,
+            };
+
+            match VonageSmsService::new(vonage_config) {
+                Ok(service) => Box::new(dry_run(service, config, VonageSmsService::with_dry_run)),
+                Err(e) => {
+                    tracing::error!("Failed to initialize Vonage SMS service: {}", e);
+                    tracing::warn!("Falling back to mock SMS service");
+                    Box::new(MockSmsService::new())
+                }
+            }
+        }
         "failover" => {
-            // Create failover service with Twilio as primary and AWS SNS as backup
-            create_failover_sms_service().await
+            // Create failover service over Twilio, AWS SNS and Vonage, in that order
+            create_failover_sms_service(config, tracking).await
         }
         _ => {
             tracing::warn!(
@@ -126,17 +365,100 @@ pub async fn create_sms_service(config: &crate::config::SmsConfig) -> Box<dyn Sm
     }
 }
 
-/// Create a failover SMS service with Twilio as primary and AWS SNS as backup
+/// Cap a provider's throughput when limits are configured for it
+fn throttle_provider(
+    provider: Box<dyn SmsService>,
+    name: &str,
+    limits: &[SmsProviderLimits],
+) -> Box<dyn SmsService> {
+    match limits.iter().find(|limits| limits.provider == name) {
+        Some(limits) => Box::new(ThrottledSmsService::new(provider, limits)),
+        None => provider,
+    }
+}
+
+/// Skip a provider that keeps failing instead of waiting for its timeouts
+///
+/// The breaker sits below the throttle, so sends rejected by a full queue
+/// do not count as provider failures.
+fn break_circuit(provider: Box<dyn SmsService>) -> Box<dyn SmsService> {
+    Box::new(CircuitBreakingSmsService::new(provider, &SmsCircuitBreakerConfig::from_env()))
+}
+
+/// Log the sends of a provider, and count their cost, when delivery is tracked
+///
+/// Messages refused over budget are logged as failed; accepted messages are
+/// logged with their estimated cost.
+fn track_delivery(
+    provider: Box<dyn SmsService>,
+    name: &str,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
+    let Some(tracking) = tracking else {
+        return provider;
+    };
+
+    match &tracking.costs {
+        Some(costs) => Box::new(
+            DeliveryLoggingSmsService::new(
+                Box::new(CostTrackingSmsService::new(provider, name, costs.clone())),
+                tracking.logs.clone(),
+            )
+            .with_costs(name, costs.clone()),
+        ),
+        None => Box::new(DeliveryLoggingSmsService::new(provider, tracking.logs.clone())),
+    }
+}
+
+/// Create a failover SMS service over every configured provider
+///
+/// Each message goes to the provider with the best recent success rate and
+/// latency for its destination country, in the order Twilio, AWS SNS,
+/// Vonage between equal scores. Each provider is throttled with its own
+/// limits, if any, so that a provider rate-limited by its caps fails over
+/// to the next one, and has its own circuit breaker, so that a provider
+/// that keeps failing is skipped at once. Providers that are not compiled in or not configured
+/// are left out. With delivery tracking, each provider logs its own sends
+/// and is tried last while its receipts report too many failures.
+pub async fn create_failover_sms_service(
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Box<dyn SmsService> {
+    let mut services: Vec<Box<dyn SmsService>> = Vec::new();
+    for name in ["twilio", "aws-sns", "vonage"] {
+        if let Some(service) = create_provider_leg(name, config, tracking).await {
+            services.push(service);
+        }
+    }
+
+    let providers = services.iter().map(|service| service.provider_name().to_string()).collect::<Vec<_>>();
+    match failover_router(services, tracking.map(|tracking| tracking.health.clone())) {
+        Some(service) => {
+            tracing::info!("Created SMS service routing between {}", providers.join(", "));
+            service
+        }
+        None => {
+            tracing::error!("No SMS services available, using mock implementation");
+            Box::new(MockSmsService::new())
+        }
+    }
+}
+
+/// Create a provider from its own environment variables, guarded, throttled
+/// and tracked
 ///
-/// This function creates a resilient SMS service that automatically switches
-/// from Twilio to AWS SNS if the primary service fails.
-pub async fn create_failover_sms_service() -> Box<dyn SmsService> {
-    #[cfg(all(feature = "twilio-sms", feature = "aws-sns"))]
-    {
-        // Try to create Twilio service
-        let twilio_service = match TwilioConfig::from_env() {
-            Ok(config) => match TwilioSmsService::new(config) {
-                Ok(service) => Some(Box::new(service) as Box<dyn SmsService>),
+/// Returns None for providers that are unknown, not compiled in or not
+/// configured.
+async fn create_provider_leg(
+    name: &str,
+    config: &crate::config::SmsConfig,
+    tracking: Option<&SmsDeliveryTracking>,
+) -> Option<Box<dyn SmsService>> {
+    let service: Option<Box<dyn SmsService>> = match name {
+        #[cfg(feature = "twilio-sms")]
+        "twilio" => match TwilioConfig::from_env() {
+            Ok(twilio_config) => match TwilioSmsService::new(twilio_config) {
+                Ok(service) => Some(Box::new(dry_run(service, config, TwilioSmsService::with_dry_run))),
                 Err(e) => {
                     tracing::warn!("Failed to initialize Twilio SMS service: {}", e);
                     None
@@ -146,12 +468,11 @@ pub async fn create_failover_sms_service() -> Box<dyn SmsService> {
                 tracing::warn!("Failed to load Twilio configuration: {}", e);
                 None
             }
-        };
-        
-        // Try to create AWS SNS service
-        let aws_service = match AwsSnsConfig::from_env() {
-            Ok(config) => match AwsSnsSmsService::new(config).await {
-                Ok(service) => Some(Box::new(service) as Box<dyn SmsService>),
+        },
+        #[cfg(feature = "aws-sns")]
+        "aws-sns" => match AwsSnsConfig::from_env() {
+            Ok(aws_config) => match AwsSnsSmsService::new(aws_config).await {
+                Ok(service) => Some(Box::new(dry_run(service, config, AwsSnsSmsService::with_dry_run))),
                 Err(e) => {
                     tracing::warn!("Failed to initialize AWS SNS SMS service: {}", e);
                     None
@@ -161,28 +482,66 @@ pub async fn create_failover_sms_service() -> Box<dyn SmsService> {
                 tracing::warn!("Failed to load AWS SNS configuration: {}", e);
                 None
             }
-        };
-        
-        // Create failover service if we have at least one service
-        match (twilio_service, aws_service) {
-            (Some(primary), Some(backup)) => {
-                tracing::info!("Created failover SMS service with Twilio (primary) and AWS SNS (backup)");
-                Box::new(FailoverSmsService::new(primary, backup, Duration::from_secs(30)))
-            }
-            (Some(service), None) | (None, Some(service)) => {
-                tracing::warn!("Only one SMS service available, failover disabled");
-                service
-            }
-            (None, None) => {
-                tracing::error!("No SMS services available, using mock implementation");
-                Box::new(MockSmsService::new())
+        },
+        #[cfg(feature = "vonage-sms")]
+        "vonage" => match VonageConfig::from_env() {
+            Ok(vonage_config) => match VonageSmsService::new(vonage_config) {
+                Ok(service) => Some(Box::new(dry_run(service, config, VonageSmsService::with_dry_run))),
+                Err(e) => {
+                    tracing::warn!("Failed to initialize Vonage SMS service: {}", e);
+                    None
+                }
+            },
+            Err(e) => {
+                tracing::warn!("Failed to load Vonage configuration: {}", e);
+                None
             }
-        }
+        },
+        _ => None,
+    };
+
+    service.map(|service| {
+        track_delivery(
+            throttle_provider(break_circuit(service), name, &config.provider_limits),
+            name,
+            tracking,
+        )
+    })
+}
+
+/// Put a real provider in dry-run mode when the configuration asks for it
+///
+/// Every provider records to the shared [`SmsDryRunLog`].
+#[cfg(any(feature = "twilio-sms", feature = "aws-sns", feature = "vonage-sms"))]
+fn dry_run<S>(service: S, config: &crate::config::SmsConfig, with_dry_run: fn(S, Arc<SmsDryRunLog>) -> S) -> S {
+    if config.dry_run {
+        with_dry_run(service, SmsDryRunLog::shared())
+    } else {
+        service
     }
-    
-    #[cfg(not(all(feature = "twilio-sms", feature = "aws-sns")))]
-    {
-        tracing::warn!("Failover SMS service requires both twilio-sms and aws-sns features");
-        Box::new(MockSmsService::new())
+}
+
+/// Route messages between services, failing over from one to the next
+///
+/// Returns None without services, and the service itself if there is only
+/// one, since failover is then disabled. With delivery health, services
+/// whose receipts report too many failures are tried last.
+pub fn failover_router(
+    services: Vec<Box<dyn SmsService>>,
+    delivery_health: Option<Arc<SmsDeliveryHealth>>,
+) -> Option<Box<dyn SmsService>> {
+    match services.len() {
+        0 => None,
+        1 => {
+            tracing::warn!("Only one SMS service available, failover disabled");
+            services.into_iter().next()
+        }
+        _ => {
+            let service = FailoverSmsService::new(services);
+            Some(Box::new(match delivery_health {
+                Some(health) => service.with_delivery_health(health),
+                None => service,
+            }))
+        }
     }
-}
\ No newline at end of file
+}
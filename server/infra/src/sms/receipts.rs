@@ -0,0 +1,229 @@
+//! SMS delivery receipt adapters
+//!
+//! Decode the delivery callbacks of SMS providers for the SMS delivery
+//! service, after checking that the provider sent them.
+//!
+//! - **Twilio** posts a form to the message's status callback URL, signed
+//!   in the `X-Twilio-Signature` header with an HMAC-SHA1 of the URL and
+//!   the sorted form parameters keyed with the account's auth token.
+//! - **AWS SNS** delivery status logs are forwarded by an SNS topic with an
+//!   HTTPS subscription. The subscription URL carries a shared `token`
+//!   query parameter, as SNS does not sign with a shared secret. The
+//!   subscription is confirmed by visiting the `SubscribeURL` logged when
+//!   the confirmation request arrives.
+
+use base64::Engine;
+use constant_time_eq::constant_time_eq;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha1::Sha1;
+use tracing::warn;
+
+use re_core::domain::entities::sms_delivery::{SmsDeliveryReceipt, SmsDeliveryStatus};
+use re_core::services::sms_delivery::SmsReceiptProviderTrait;
+use re_shared::config::SecretString;
+
+use crate::InfrastructureError;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Name of Twilio in the delivery log, as reported by `TwilioSmsService`
+pub const TWILIO_PROVIDER: &str = "Twilio";
+
+/// Name of AWS SNS in the delivery log, as reported by `AwsSnsSmsService`
+pub const SNS_PROVIDER: &str = "AWS SNS";
+
+/// Twilio delivery receipts
+pub struct TwilioReceiptProvider {
+    /// Auth token the callbacks are signed with
+    auth_token: SecretString,
+}
+
+impl TwilioReceiptProvider {
+    /// Create an adapter verifying callbacks with the account's auth token
+    pub fn new(auth_token: SecretString) -> Self {
+        Self { auth_token }
+    }
+
+    /// Create from the `TWILIO_AUTH_TOKEN` environment variable
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let auth_token = std::env::var("TWILIO_AUTH_TOKEN")
+            .map_err(|_| InfrastructureError::Config("TWILIO_AUTH_TOKEN not set".to_string()))?;
+        Ok(Self::new(SecretString::from(auth_token)))
+    }
+
+    /// Signature of a callback, as Twilio computes it
+    pub fn sign(&self, url: &str, payload: &[u8]) -> String {
+        let mut params: Vec<(String, String)> = url::form_urlencoded::parse(payload).into_owned().collect();
+        params.sort();
+
+        let mut mac = HmacSha1::new_from_slice(self.auth_token.expose_secret().as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(url.as_bytes());
+        for (key, value) in &params {
+            mac.update(key.as_bytes());
+            mac.update(value.as_bytes());
+        }
+
+        base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+    }
+}
+
+impl SmsReceiptProviderTrait for TwilioReceiptProvider {
+    fn provider_name(&self) -> &str {
+        TWILIO_PROVIDER
+    }
+
+    fn signature_header(&self) -> Option<&str> {
+        Some("X-Twilio-Signature")
+    }
+
+    fn verify_callback(&self, url: &str, payload: &[u8], signature: Option<&str>) -> Result<(), String> {
+        let signature = signature.ok_or("Missing signature")?;
+        if constant_time_eq(self.sign(url, payload).as_bytes(), signature.as_bytes()) {
+            Ok(())
+        } else {
+            Err("Signature mismatch".to_string())
+        }
+    }
+
+    fn parse_receipts(&self, payload: &[u8]) -> Result<Vec<SmsDeliveryReceipt>, String> {
+        let mut message_id = None;
+        let mut message_status = None;
+        let mut error_code = None;
+        for (key, value) in url::form_urlencoded::parse(payload) {
+            match key.as_ref() {
+                "MessageSid" => message_id = Some(value.into_owned()),
+                "MessageStatus" => message_status = Some(value.into_owned()),
+                "ErrorCode" => error_code = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+
+        let message_id = message_id.ok_or("Missing MessageSid")?;
+        let message_status = message_status.ok_or("Missing MessageStatus")?;
+
+        // queued, sending, sent and accepted are not final
+        let status = match message_status.as_str() {
+            "delivered" => SmsDeliveryStatus::Delivered,
+            "undelivered" => SmsDeliveryStatus::Undelivered,
+            "failed" => SmsDeliveryStatus::Failed,
+            _ => SmsDeliveryStatus::Sent,
+        };
+
+        Ok(vec![SmsDeliveryReceipt {
+            provider_message_id: message_id,
+            status,
+            error: error_code.map(|code| format!("Twilio error {}", code)),
+        }])
+    }
+}
+
+/// Envelope of the messages SNS posts to HTTPS subscriptions
+#[derive(Debug, Deserialize)]
+struct SnsEnvelope {
+    #[serde(rename = "Type")]
+    message_type: String,
+    #[serde(rename = "Message")]
+    message: String,
+    #[serde(rename = "SubscribeURL")]
+    subscribe_url: Option<String>,
+}
+
+/// SMS delivery status log written by SNS
+#[derive(Debug, Deserialize)]
+struct SnsDeliveryStatus {
+    notification: SnsNotification,
+    delivery: Option<SnsDelivery>,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnsNotification {
+    #[serde(rename = "messageId")]
+    message_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SnsDelivery {
+    #[serde(rename = "providerResponse")]
+    provider_response: Option<String>,
+}
+
+/// AWS SNS delivery receipts
+pub struct SnsReceiptProvider {
+    /// Token carried in the query string of the subscription URL
+    token: SecretString,
+}
+
+impl SnsReceiptProvider {
+    /// Create an adapter accepting callbacks carrying `token`
+    pub fn new(token: SecretString) -> Self {
+        Self { token }
+    }
+
+    /// Create from the `AWS_SNS_RECEIPT_TOKEN` environment variable
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let token = std::env::var("AWS_SNS_RECEIPT_TOKEN")
+            .map_err(|_| InfrastructureError::Config("AWS_SNS_RECEIPT_TOKEN not set".to_string()))?;
+        Ok(Self::new(SecretString::from(token)))
+    }
+}
+
+impl SmsReceiptProviderTrait for SnsReceiptProvider {
+    fn provider_name(&self) -> &str {
+        SNS_PROVIDER
+    }
+
+    fn signature_header(&self) -> Option<&str> {
+        None
+    }
+
+    fn verify_callback(&self, url: &str, _payload: &[u8], _signature: Option<&str>) -> Result<(), String> {
+        let url = url::Url::parse(url).map_err(|e| format!("Invalid callback URL: {}", e))?;
+        let token = url
+            .query_pairs()
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+            .ok_or("Missing token")?;
+
+        let expected = self.token.expose_secret();
+        if !expected.is_empty() && constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+            Ok(())
+        } else {
+            Err("Token mismatch".to_string())
+        }
+    }
+
+    fn parse_receipts(&self, payload: &[u8]) -> Result<Vec<SmsDeliveryReceipt>, String> {
+        let envelope: SnsEnvelope = serde_json::from_slice(payload).map_err(|e| e.to_string())?;
+
+        match envelope.message_type.as_str() {
+            "Notification" => {}
+            "SubscriptionConfirmation" => {
+                warn!(
+                    "SNS delivery status subscription awaits confirmation at {}",
+                    envelope.subscribe_url.as_deref().unwrap_or("-")
+                );
+                return Ok(Vec::new());
+            }
+            _ => return Ok(Vec::new()),
+        }
+
+        let log: SnsDeliveryStatus = serde_json::from_str(&envelope.message).map_err(|e| e.to_string())?;
+        let (status, error) = match log.status.as_str() {
+            "SUCCESS" => (SmsDeliveryStatus::Delivered, None),
+            "FAILURE" => (
+                SmsDeliveryStatus::Undelivered,
+                log.delivery.and_then(|delivery| delivery.provider_response),
+            ),
+            other => return Err(format!("Unknown delivery status {}", other)),
+        };
+
+        Ok(vec![SmsDeliveryReceipt {
+            provider_message_id: log.notification.message_id,
+            status,
+            error,
+        }])
+    }
+}
@@ -3,9 +3,32 @@
 //! Defines the trait for SMS service implementations that handle
 //! sending verification codes and other SMS messages.
 
+use std::future::Future;
+
 use async_trait::async_trait;
+use crate::sms::throttle::SmsPriority;
 use crate::InfrastructureError;
 
+/// Template name of verification code messages in the SMS log
+pub const VERIFICATION_CODE_TEMPLATE: &str = "verification_code";
+
+tokio::task_local! {
+    static TEMPLATE: &'static str;
+}
+
+/// Run a future with the template name of the messages it sends
+///
+/// The SMS log records the template of each message, which the providers
+/// only see as rendered text.
+pub fn with_template<F: Future>(template: &'static str, future: F) -> impl Future<Output = F::Output> {
+    TEMPLATE.scope(template, future)
+}
+
+/// Template name of the message being sent, None outside a template scope
+pub fn current_template() -> Option<&'static str> {
+    TEMPLATE.try_with(|template| *template).ok()
+}
+
 /// SMS service trait for sending text messages
 ///
 /// Implementations include:
@@ -34,10 +57,32 @@ pub trait SmsService: Send + Sync {
     /// ```
     async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError>;
 
+    /// Send an SMS message from a specific sender ID or number
+    ///
+    /// Used by the market router to apply per-country senders. Providers
+    /// that cannot choose a sender per message keep the default, which
+    /// sends from their configured sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `phone_number` - The recipient's phone number (E.164 format)
+    /// * `message` - The message content to send
+    /// * `sender_id` - Alphanumeric sender ID, short code or number to send from
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        _sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.send_sms(phone_number, message).await
+    }
+
     /// Send a verification code via SMS
     ///
     /// This is a convenience method that formats the verification code message
-    /// according to the application's standard format.
+    /// according to the application's standard format. The message is sent
+    /// with [`SmsPriority::Otp`], so throttled providers dispatch it ahead of
+    /// queued notifications, and logged as [`VERIFICATION_CODE_TEMPLATE`].
     ///
     /// # Arguments
     ///
@@ -50,7 +95,9 @@ pub trait SmsService: Send + Sync {
     /// * `Err(InfrastructureError)` - If sending fails
     async fn send_verification_code(&self, phone_number: &str, code: &str) -> Result<String, InfrastructureError> {
         let message = format!("Your RenovEasy verification code is: {}. This code will expire in 5 minutes.", code);
-        self.send_sms(phone_number, &message).await
+        SmsPriority::Otp
+            .scope(with_template(VERIFICATION_CODE_TEMPLATE, self.send_sms(phone_number, &message)))
+            .await
     }
 
     /// Get the service provider name
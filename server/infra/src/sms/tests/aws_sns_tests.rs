@@ -27,7 +27,7 @@ fn test_aws_sns_config_from_env() {
     
     let config = config.unwrap();
     assert_eq!(config.access_key_id, "test_access_key");
-    assert_eq!(config.secret_access_key, "test_secret_key");
+    assert_eq!(config.secret_access_key.expose_secret(), "test_secret_key");
     assert_eq!(config.region, "us-east-1"); // Default region
     assert_eq!(config.sms_type, "Transactional"); // Default SMS type
     assert_eq!(config.max_retries, 3); // Default retries
@@ -61,7 +61,7 @@ fn test_aws_sns_config_with_custom_values() {
     
     let config = config.unwrap();
     assert_eq!(config.access_key_id, "custom_access_key");
-    assert_eq!(config.secret_access_key, "custom_secret_key");
+    assert_eq!(config.secret_access_key.expose_secret(), "custom_secret_key");
     assert_eq!(config.region, "eu-west-1");
     assert_eq!(config.sender_id, Some("RenovEasy".to_string()));
     assert_eq!(config.sms_type, "Promotional");
@@ -121,7 +121,7 @@ async fn test_aws_sns_service_creation() {
     
     let config = AwsSnsConfig {
         access_key_id: "test_key".to_string(),
-        secret_access_key: "test_secret".to_string(),
+        secret_access_key: "test_secret".into(),
         region: "us-east-1".to_string(),
         sender_id: Some("Test".to_string()),
         sms_type: "Transactional".to_string(),
@@ -155,7 +155,7 @@ async fn test_phone_number_validation() {
     // Create a service with test configuration
     let config = AwsSnsConfig {
         access_key_id: "test_key".to_string(),
-        secret_access_key: "test_secret".to_string(),
+        secret_access_key: "test_secret".into(),
         region: "us-east-1".to_string(),
         sender_id: None,
         sms_type: "Transactional".to_string(),
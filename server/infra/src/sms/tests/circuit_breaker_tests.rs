@@ -0,0 +1,121 @@
+//! Unit tests for the circuit breaking SMS service
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::circuit_breaker::CircuitState;
+use crate::sms::{failover_router, CircuitBreakingSmsService, MockSmsService, SmsCircuitBreakerConfig, SmsService};
+use crate::InfrastructureError;
+
+/// Provider counting its sends, failing while `down` is set
+#[derive(Clone, Default)]
+struct StubSmsService {
+    calls: Arc<AtomicUsize>,
+    down: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl SmsService for StubSmsService {
+    async fn send_sms(&self, _phone_number: &str, _message: &str) -> Result<String, InfrastructureError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.down.load(Ordering::SeqCst) {
+            Err(InfrastructureError::Sms("Request timed out".to_string()))
+        } else {
+            Ok("SM123".to_string())
+        }
+    }
+
+    fn provider_name(&self) -> &str {
+        "Twilio"
+    }
+}
+
+fn config(failure_threshold: u32, open_duration: Duration) -> SmsCircuitBreakerConfig {
+    SmsCircuitBreakerConfig {
+        failure_threshold,
+        open_duration,
+    }
+}
+
+#[tokio::test]
+async fn test_open_circuit_skips_provider() {
+    let stub = StubSmsService::default();
+    stub.down.store(true, Ordering::SeqCst);
+    let service = CircuitBreakingSmsService::new(Box::new(stub.clone()), &config(2, Duration::from_secs(60)));
+
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    assert_eq!(service.state(), CircuitState::Closed);
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    assert_eq!(service.state(), CircuitState::Open);
+    assert!(!service.is_available().await);
+
+    let error = service.send_sms("+61412345678", "Hello").await.unwrap_err();
+    assert!(error.to_string().contains("circuit is open"));
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_success_resets_failure_count() {
+    let stub = StubSmsService::default();
+    let service = CircuitBreakingSmsService::new(Box::new(stub.clone()), &config(2, Duration::from_secs(60)));
+
+    stub.down.store(true, Ordering::SeqCst);
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    stub.down.store(false, Ordering::SeqCst);
+    assert!(service.send_sms("+61412345678", "Hello").await.is_ok());
+    stub.down.store(true, Ordering::SeqCst);
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+
+    assert_eq!(service.state(), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn test_half_open_probe_closes_circuit() {
+    let stub = StubSmsService::default();
+    stub.down.store(true, Ordering::SeqCst);
+    let service = CircuitBreakingSmsService::new(Box::new(stub.clone()), &config(1, Duration::from_millis(20)));
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    assert!(!service.is_available().await);
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(service.is_available().await);
+
+    // A failed probe opens the circuit again
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    assert_eq!(service.state(), CircuitState::Open);
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 2);
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    stub.down.store(false, Ordering::SeqCst);
+    assert_eq!(service.send_sms("+61412345678", "Hello").await.unwrap(), "SM123");
+    assert_eq!(service.state(), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn test_invalid_numbers_do_not_trip_circuit() {
+    let stub = StubSmsService::default();
+    stub.down.store(true, Ordering::SeqCst);
+    let service = CircuitBreakingSmsService::new(Box::new(stub.clone()), &config(1, Duration::from_secs(60)));
+
+    assert!(service.send_sms("not-a-number", "Hello").await.is_err());
+    assert_eq!(service.state(), CircuitState::Closed);
+}
+
+#[tokio::test]
+async fn test_failover_skips_open_provider() {
+    let stub = StubSmsService::default();
+    stub.down.store(true, Ordering::SeqCst);
+    let services: Vec<Box<dyn SmsService>> = vec![
+        Box::new(CircuitBreakingSmsService::new(Box::new(stub.clone()), &config(1, Duration::from_secs(60)))),
+        Box::new(MockSmsService::with_options(false, false)),
+    ];
+    let service = failover_router(services, None).unwrap();
+
+    for _ in 0..3 {
+        assert!(service.send_sms("+61412345678", "Hello").await.unwrap().starts_with("mock_"));
+    }
+    assert_eq!(stub.calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,117 @@
+//! Unit tests for cost tracking SMS service
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use re_core::domain::entities::sms_spend::SmsDailySpend;
+use re_core::errors::DomainError;
+use re_core::repositories::SmsSpendRepository;
+use re_core::services::sms_cost::{SmsCostService, SmsSpendCounterTrait};
+use re_shared::config::{SmsBudgetConfig, SmsOverBudgetAction, SmsRate};
+
+use crate::sms::{failover_router, CostTrackingSmsService, MockSmsService, SmsService};
+
+/// Spend kept in memory, one entry per message
+#[derive(Default)]
+struct InMemorySpend {
+    messages: Mutex<Vec<(String, u64)>>,
+}
+
+#[async_trait]
+impl SmsSpendRepository for InMemorySpend {
+    async fn add(
+        &self,
+        _day: NaiveDate,
+        provider: &str,
+        _prefix: &str,
+        cost_micros: u64,
+    ) -> Result<(), DomainError> {
+        self.messages.lock().unwrap().push((provider.to_string(), cost_micros));
+        Ok(())
+    }
+
+    async fn find_between(&self, _from: NaiveDate, _to: NaiveDate) -> Result<Vec<SmsDailySpend>, DomainError> {
+        Ok(Vec::new())
+    }
+}
+
+#[async_trait]
+impl SmsSpendCounterTrait for InMemorySpend {
+    async fn add(&self, _day: NaiveDate, _cost_micros: u64) -> Result<u64, String> {
+        self.total(Utc::now().date_naive()).await
+    }
+
+    async fn total(&self, _day: NaiveDate) -> Result<u64, String> {
+        Ok(self.messages.lock().unwrap().iter().map(|(_, cost)| cost).sum())
+    }
+}
+
+fn costs(spend: Arc<InMemorySpend>, over_budget: SmsOverBudgetAction) -> Arc<SmsCostService> {
+    let config = SmsBudgetConfig {
+        daily_budget_micros: Some(100_000),
+        over_budget,
+        rates: vec![
+            SmsRate { provider: "twilio".to_string(), prefix: "+61".to_string(), cost_micros: 60_000 },
+            SmsRate { provider: "aws-sns".to_string(), prefix: "+61".to_string(), cost_micros: 40_000 },
+        ],
+        ..Default::default()
+    };
+    Arc::new(SmsCostService::new(spend.clone(), spend, config))
+}
+
+#[tokio::test]
+async fn test_records_spend_and_refuses_over_budget() {
+    let spend = Arc::new(InMemorySpend::default());
+    let service = CostTrackingSmsService::new(
+        Box::new(MockSmsService::with_options(false, false)),
+        "twilio",
+        costs(spend.clone(), SmsOverBudgetAction::Refuse),
+    );
+
+    assert!(service.send_sms("+61412345678", "Hello").await.is_ok());
+    assert_eq!(*spend.messages.lock().unwrap(), vec![("twilio".to_string(), 60_000)]);
+
+    let error = service.send_sms("+61412345678", "Hello").await.unwrap_err();
+    assert!(error.to_string().contains("budget"));
+    assert_eq!(spend.messages.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_rejected_messages_cost_nothing() {
+    let spend = Arc::new(InMemorySpend::default());
+    let service = CostTrackingSmsService::new(
+        Box::new(MockSmsService::with_options(false, true)),
+        "twilio",
+        costs(spend.clone(), SmsOverBudgetAction::Refuse),
+    );
+
+    assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+    assert!(spend.messages.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_downgrade_fails_over_to_cheapest_provider() {
+    let spend = Arc::new(InMemorySpend::default());
+    let costs = costs(spend.clone(), SmsOverBudgetAction::Downgrade);
+    let services: Vec<Box<dyn SmsService>> = vec![
+        Box::new(CostTrackingSmsService::new(
+            Box::new(MockSmsService::with_options(false, false)),
+            "twilio",
+            costs.clone(),
+        )),
+        Box::new(CostTrackingSmsService::new(
+            Box::new(MockSmsService::with_options(false, false)),
+            "aws-sns",
+            costs,
+        )),
+    ];
+    let service = failover_router(services, None).unwrap();
+
+    for _ in 0..3 {
+        assert!(service.send_sms("+61412345678", "Hello").await.is_ok());
+    }
+
+    let providers: Vec<String> = spend.messages.lock().unwrap().iter().map(|(provider, _)| provider.clone()).collect();
+    assert_eq!(providers, vec!["twilio", "aws-sns", "aws-sns"]);
+}
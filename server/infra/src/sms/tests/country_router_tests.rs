@@ -0,0 +1,106 @@
+//! Unit tests for country routing SMS service
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use re_shared::config::SmsRoute;
+
+use crate::sms::{CountryRoutingSmsService, SmsService};
+use crate::InfrastructureError;
+
+/// Provider answering with its own name
+struct NamedSmsService(&'static str);
+
+#[async_trait]
+impl SmsService for NamedSmsService {
+    async fn send_sms(&self, _phone_number: &str, _message: &str) -> Result<String, InfrastructureError> {
+        Ok(format!("{}-id", self.0))
+    }
+
+    async fn send_sms_from(
+        &self,
+        _phone_number: &str,
+        _message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        Ok(format!("{}-id-from-{}", self.0, sender_id))
+    }
+
+    fn provider_name(&self) -> &str {
+        self.0
+    }
+}
+
+fn router() -> CountryRoutingSmsService {
+    CountryRoutingSmsService::new(
+        Arc::new(NamedSmsService("Failover")),
+        vec![
+            ("+1".to_string(), Arc::new(NamedSmsService("Twilio"))),
+            ("+86".to_string(), Arc::new(NamedSmsService("Aliyun"))),
+            ("+1268".to_string(), Arc::new(NamedSmsService("Vonage"))),
+        ],
+    )
+}
+
+#[tokio::test]
+async fn test_routes_by_dialing_code() {
+    let service = router();
+
+    assert_eq!(service.send_sms("+8613812345678", "Hello").await.unwrap(), "Aliyun-id");
+    assert_eq!(service.send_sms("+12025550123", "Hello").await.unwrap(), "Twilio-id");
+    assert_eq!(service.provider_name(), "Failover");
+}
+
+#[tokio::test]
+async fn test_longest_dialing_code_wins() {
+    let service = router();
+
+    assert_eq!(service.send_sms("+12684601234", "Hello").await.unwrap(), "Vonage-id");
+}
+
+#[tokio::test]
+async fn test_unrouted_countries_use_default() {
+    let service = router();
+
+    assert_eq!(service.send_sms("+61412345678", "Hello").await.unwrap(), "Failover-id");
+}
+
+#[tokio::test]
+async fn test_sender_passed_to_routed_provider() {
+    let service = router();
+
+    assert_eq!(
+        service.send_sms_from("+8613812345678", "Hello", "RenovEasy").await.unwrap(),
+        "Aliyun-id-from-RenovEasy"
+    );
+}
+
+#[test]
+fn test_parse_route_rules() {
+    assert_eq!(SmsRoute::parse("+86 -> aliyun"), Some(SmsRoute::new("+86", "aliyun")));
+    assert_eq!(SmsRoute::parse(" default->failover "), Some(SmsRoute::fallback("failover")));
+    assert_eq!(SmsRoute::parse("+61 twilio"), None);
+    assert_eq!(SmsRoute::parse("+61 -> "), None);
+}
+
+#[test]
+fn test_resolve_route() {
+    let routes = vec![
+        SmsRoute::new("+1", "twilio"),
+        SmsRoute::new("+1268", "vonage"),
+        SmsRoute::fallback("failover"),
+    ];
+
+    assert_eq!(SmsRoute::resolve(&routes, "+12684601234").unwrap().provider, "vonage");
+    assert_eq!(SmsRoute::resolve(&routes, "+12025550123").unwrap().provider, "twilio");
+    assert_eq!(SmsRoute::resolve(&routes, "+61412345678").unwrap().provider, "failover");
+    assert_eq!(SmsRoute::resolve(&routes[..1], "+61412345678"), None);
+}
+
+#[test]
+fn test_validate_routes() {
+    assert!(SmsRoute::validate_all(&[SmsRoute::new("+86", "aliyun"), SmsRoute::fallback("twilio")]).is_ok());
+    assert!(SmsRoute::validate_all(&[SmsRoute::new("86", "aliyun")]).is_err());
+    assert!(SmsRoute::validate_all(&[SmsRoute::new("+86", "aliyun"), SmsRoute::new("+86", "twilio")]).is_err());
+    assert!(SmsRoute::validate_all(&[SmsRoute::fallback("twilio"), SmsRoute::fallback("vonage")]).is_err());
+}
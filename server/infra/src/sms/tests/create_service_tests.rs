@@ -7,9 +7,13 @@ use crate::config::SmsConfig;
 async fn test_create_mock_service() {
     let config = SmsConfig {
         provider: "mock".to_string(),
-        api_key: String::new(),
-        api_secret: String::new(),
+        api_key: Default::default(),
+        api_secret: Default::default(),
         from_number: "+1234567890".to_string(),
+        markets: Vec::new(),
+        routes: Vec::new(),
+        provider_limits: Vec::new(),
+        dry_run: false,
     };
 
     let service = create_sms_service(&config).await;
@@ -20,12 +24,103 @@ async fn test_create_mock_service() {
 async fn test_create_unknown_provider_fallback() {
     let config = SmsConfig {
         provider: "unknown".to_string(),
-        api_key: String::new(),
-        api_secret: String::new(),
+        api_key: Default::default(),
+        api_secret: Default::default(),
         from_number: "+1234567890".to_string(),
+        markets: Vec::new(),
+        routes: Vec::new(),
+        provider_limits: Vec::new(),
+        dry_run: false,
     };
 
     let service = create_sms_service(&config).await;
     // Should fallback to mock
     assert_eq!(service.provider_name(), "Mock");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_unavailable_route_uses_default() {
+    let config = SmsConfig {
+        provider: "mock".to_string(),
+        api_key: Default::default(),
+        api_secret: Default::default(),
+        from_number: "+1234567890".to_string(),
+        markets: Vec::new(),
+        routes: vec![re_shared::config::SmsRoute::new("+86", "aliyun")],
+        provider_limits: Vec::new(),
+        dry_run: false,
+    };
+
+    let service = create_sms_service(&config).await;
+    assert_eq!(service.provider_name(), "Mock");
+    assert!(service.send_sms("+8613812345678", "Hello").await.is_ok());
+}
+
+#[tokio::test]
+async fn test_failover_router_tries_each_provider_in_turn() {
+    use crate::sms::{failover_router, MockSmsService, SmsService};
+
+    assert!(failover_router(Vec::new(), None).is_none());
+
+    let services: Vec<Box<dyn SmsService>> = vec![
+        Box::new(MockSmsService::with_options(false, true)),
+        Box::new(MockSmsService::with_options(false, true)),
+        Box::new(MockSmsService::with_options(false, false)),
+    ];
+    let service = failover_router(services, None).unwrap();
+    assert!(service.send_sms("+61412345678", "Hello").await.unwrap().starts_with("mock_"));
+
+    let failing: Vec<Box<dyn SmsService>> = vec![
+        Box::new(MockSmsService::with_options(false, true)),
+        Box::new(MockSmsService::with_options(false, true)),
+    ];
+    assert!(failover_router(failing, None).unwrap().send_sms("+61412345678", "Hello").await.is_err());
+}
+
+#[tokio::test]
+async fn test_failover_router_skips_primary_with_failing_deliveries() {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use re_core::domain::entities::sms_delivery::SmsDeliveryStatus;
+    use re_core::services::sms_delivery::{SmsDeliveryConfig, SmsDeliveryHealth};
+
+    use crate::sms::{failover_router, MockSmsService, SmsService};
+    use crate::InfrastructureError;
+
+    /// Provider that accepts every message
+    struct StubSmsService;
+
+    #[async_trait]
+    impl SmsService for StubSmsService {
+        async fn send_sms(&self, _phone_number: &str, _message: &str) -> Result<String, InfrastructureError> {
+            Ok("SM123".to_string())
+        }
+
+        fn provider_name(&self) -> &str {
+            "Twilio"
+        }
+    }
+
+    let health = Arc::new(SmsDeliveryHealth::new(SmsDeliveryConfig {
+        min_receipts: 2,
+        ..Default::default()
+    }));
+    let services = || -> Vec<Box<dyn SmsService>> {
+        vec![Box::new(StubSmsService), Box::new(MockSmsService::with_options(false, false))]
+    };
+    let service = failover_router(services(), Some(health.clone())).unwrap();
+    assert_eq!(service.send_sms("+61412345678", "Hello").await.unwrap(), "SM123");
+
+    health.record("Twilio", SmsDeliveryStatus::Undelivered);
+    health.record("Twilio", SmsDeliveryStatus::Failed);
+    assert!(service.send_sms("+61412345678", "Hello").await.unwrap().starts_with("mock_"));
+
+    // A degraded primary is still tried when the backup fails
+    let services: Vec<Box<dyn SmsService>> = vec![
+        Box::new(StubSmsService),
+        Box::new(MockSmsService::with_options(false, true)),
+    ];
+    let service = failover_router(services, Some(health)).unwrap();
+    assert_eq!(service.send_sms("+61412345678", "Hello").await.unwrap(), "SM123");
+}
@@ -0,0 +1,119 @@
+//! Unit tests for delivery logging SMS service
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use re_core::domain::entities::sms_delivery::{SmsDeliveryLog, SmsDeliveryStatus};
+use re_core::errors::DomainError;
+use re_core::repositories::SmsDeliveryLogRepository;
+use re_core::services::trace;
+
+use crate::sms::{DeliveryLoggingSmsService, SmsService};
+use crate::InfrastructureError;
+
+/// Provider that accepts every message except those to `+61400000000`
+struct StubSmsService;
+
+#[async_trait]
+impl SmsService for StubSmsService {
+    async fn send_sms(&self, phone_number: &str, _message: &str) -> Result<String, InfrastructureError> {
+        if phone_number == "+61400000000" {
+            return Err(InfrastructureError::Sms("Unreachable number".to_string()));
+        }
+        Ok("SM123".to_string())
+    }
+
+    fn provider_name(&self) -> &str {
+        "Stub"
+    }
+}
+
+#[derive(Default)]
+struct InMemoryDeliveryLogs {
+    logs: Mutex<Vec<SmsDeliveryLog>>,
+}
+
+#[async_trait]
+impl SmsDeliveryLogRepository for InMemoryDeliveryLogs {
+    async fn save(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        self.logs.lock().unwrap().push(log.clone());
+        Ok(log)
+    }
+
+    async fn find_by_provider_message_id(
+        &self,
+        provider: &str,
+        provider_message_id: &str,
+    ) -> Result<Option<SmsDeliveryLog>, DomainError> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|log| {
+                log.provider == provider
+                    && log.provider_message_id.as_deref() == Some(provider_message_id)
+            })
+            .cloned())
+    }
+
+    async fn find_by_trace_id(&self, trace_id: &str) -> Result<Vec<SmsDeliveryLog>, DomainError> {
+        Ok(self
+            .logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|log| log.trace_id.as_deref() == Some(trace_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn update(&self, log: SmsDeliveryLog) -> Result<SmsDeliveryLog, DomainError> {
+        Ok(log)
+    }
+}
+
+#[tokio::test]
+async fn test_send_recorded_with_trace_id() {
+    let logs = Arc::new(InMemoryDeliveryLogs::default());
+    let service = DeliveryLoggingSmsService::new(Box::new(StubSmsService), logs.clone());
+
+    let message_id = trace::scope("req-42".to_string(), service.send_sms("+61412345678", "Hello"))
+        .await
+        .unwrap();
+
+    let log = logs.find_by_provider_message_id("Stub", &message_id).await.unwrap().unwrap();
+    assert_eq!(log.trace_id.as_deref(), Some("req-42"));
+    assert_eq!(log.status, SmsDeliveryStatus::Sent);
+    assert!(!log.phone_masked.contains("412345"));
+    assert_eq!(logs.find_by_trace_id("req-42").await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_rejected_send_recorded_as_failed() {
+    let logs = Arc::new(InMemoryDeliveryLogs::default());
+    let service = DeliveryLoggingSmsService::new(Box::new(StubSmsService), logs.clone());
+
+    let result = service.send_sms("+61400000000", "Hello").await;
+
+    assert!(result.is_err());
+    let recorded = logs.logs.lock().unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].status, SmsDeliveryStatus::Failed);
+    assert!(recorded[0].trace_id.is_none());
+    assert!(recorded[0].provider_message_id.is_none());
+}
+
+#[tokio::test]
+async fn test_verification_code_recorded_with_template() {
+    let logs = Arc::new(InMemoryDeliveryLogs::default());
+    let service = DeliveryLoggingSmsService::new(Box::new(StubSmsService), logs.clone());
+
+    service.send_verification_code("+61412345678", "123456").await.unwrap();
+    service.send_sms("+61412345678", "Hello").await.unwrap();
+
+    let recorded = logs.logs.lock().unwrap();
+    assert_eq!(recorded[0].template.as_deref(), Some("verification_code"));
+    assert!(recorded[1].template.is_none());
+    assert!(recorded[0].cost_micros.is_none());
+}
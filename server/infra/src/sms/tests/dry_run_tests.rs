@@ -0,0 +1,104 @@
+//! Unit tests for dry-run SMS recording
+
+use std::sync::Arc;
+
+use crate::sms::dry_run::DRY_RUN_MESSAGE_ID_PREFIX;
+use crate::sms::SmsDryRunLog;
+
+#[test]
+fn test_records_messages_newest_first() {
+    let log = SmsDryRunLog::new(10);
+
+    let first = log.record("Twilio", "+15551234567", "+61412345678", "First");
+    let second = log.record("Vonage", "RenovEasy", "+61412345678", "Second");
+
+    assert!(first.starts_with(DRY_RUN_MESSAGE_ID_PREFIX));
+    let recent = log.recent(None, 10);
+    assert_eq!(recent.len(), 2);
+    assert_eq!((recent[0].message_id.as_str(), recent[0].body.as_str()), (second.as_str(), "Second"));
+    assert_eq!(recent[1].provider, "Twilio");
+    assert_eq!(recent[0].sender_id, "RenovEasy");
+}
+
+#[test]
+fn test_filters_by_phone_and_limit() {
+    let log = SmsDryRunLog::new(10);
+    log.record("Twilio", "", "+61412345678", "One");
+    log.record("Twilio", "", "+8613812345678", "Two");
+    log.record("Twilio", "", "+61412345678", "Three");
+
+    let recent = log.recent(Some("+61412345678"), 10);
+    assert_eq!(recent.iter().map(|m| m.body.as_str()).collect::<Vec<_>>(), vec!["Three", "One"]);
+    assert_eq!(log.recent(None, 1)[0].body, "Three");
+}
+
+#[test]
+fn test_keeps_most_recent_messages() {
+    let log = SmsDryRunLog::new(2);
+    for body in ["One", "Two", "Three"] {
+        log.record("Twilio", "", "+61412345678", body);
+    }
+
+    let recent = log.recent(None, 10);
+    assert_eq!(recent.iter().map(|m| m.body.as_str()).collect::<Vec<_>>(), vec!["Three", "Two"]);
+}
+
+#[test]
+fn test_shared_log_is_shared() {
+    assert!(Arc::ptr_eq(&SmsDryRunLog::shared(), &SmsDryRunLog::shared()));
+}
+
+#[cfg(feature = "vonage-sms")]
+#[tokio::test]
+async fn test_vonage_dry_run_skips_api() {
+    use crate::sms::{SmsService, VonageConfig, VonageSmsService};
+
+    let log = Arc::new(SmsDryRunLog::new(10));
+    let service = VonageSmsService::new(VonageConfig {
+        api_key: "test_api_key".to_string(),
+        api_secret: "test_api_secret".into(),
+        from: "+15551234567".to_string(),
+        // Nothing listens here; a real request would fail
+        api_url: "http://127.0.0.1:9/sms/json".to_string(),
+        max_retries: 1,
+        retry_delay_ms: 10,
+        request_timeout_secs: 1,
+    })
+    .unwrap()
+    .with_dry_run(log.clone());
+
+    let message_id = service.send_verification_code("+61412345678", "123456").await.unwrap();
+
+    let recent = log.recent(None, 10);
+    assert_eq!(recent[0].message_id, message_id);
+    assert_eq!(recent[0].provider, "Vonage");
+    assert_eq!(recent[0].sender_id, "+15551234567");
+    assert!(recent[0].body.contains("123456"));
+
+    // Messages are still validated
+    assert!(service.send_sms("not-a-number", "Hello").await.is_err());
+    assert_eq!(log.recent(None, 10).len(), 1);
+}
+
+#[cfg(feature = "twilio-sms")]
+#[tokio::test]
+async fn test_twilio_dry_run_skips_api() {
+    use crate::sms::{SmsService, TwilioConfig, TwilioSmsService};
+
+    let log = Arc::new(SmsDryRunLog::new(10));
+    let service = TwilioSmsService::new(TwilioConfig {
+        account_sid: "ACtest".to_string(),
+        auth_token: "test_token".into(),
+        from_number: "+15551234567".to_string(),
+        max_retries: 1,
+        retry_delay_ms: 10,
+        request_timeout_secs: 1,
+    })
+    .unwrap()
+    .with_dry_run(log.clone());
+
+    let message_id = service.send_sms_from("+61412345678", "Hello", "RenovEasy").await.unwrap();
+
+    assert!(message_id.starts_with(DRY_RUN_MESSAGE_ID_PREFIX));
+    assert_eq!(log.recent(Some("+61412345678"), 10)[0].sender_id, "RenovEasy");
+}
@@ -0,0 +1,140 @@
+//! Unit tests for the failover SMS router
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::sms::{FailoverSmsService, SmsRoutingConfig, SmsService};
+use crate::InfrastructureError;
+
+const AU_PHONE: &str = "+61412345678";
+const US_PHONE: &str = "+12025550123";
+
+/// Provider counting its sends, failing them all if asked to
+struct StubSmsService {
+    name: &'static str,
+    fail: bool,
+    sends: Arc<AtomicUsize>,
+}
+
+impl StubSmsService {
+    fn new(name: &'static str, fail: bool) -> (Box<dyn SmsService>, Arc<AtomicUsize>) {
+        let sends = Arc::new(AtomicUsize::new(0));
+        (Box::new(Self { name, fail, sends: sends.clone() }), sends)
+    }
+}
+
+#[async_trait]
+impl SmsService for StubSmsService {
+    async fn send_sms(&self, _phone_number: &str, _message: &str) -> Result<String, InfrastructureError> {
+        self.sends.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(InfrastructureError::Sms("Provider down".to_string()));
+        }
+        Ok(format!("{}-id", self.name))
+    }
+
+    fn provider_name(&self) -> &str {
+        self.name
+    }
+}
+
+#[tokio::test]
+async fn test_prefers_configured_order_without_sends() {
+    let (twilio, _) = StubSmsService::new("Twilio", false);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "Twilio-id");
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "Twilio-id");
+}
+
+#[tokio::test]
+async fn test_routes_away_from_failing_provider_per_country() {
+    let (twilio, twilio_sends) = StubSmsService::new("Twilio", true);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    // The first send fails over, later ones go to the backup directly
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "AWS SNS-id");
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "AWS SNS-id");
+    assert_eq!(twilio_sends.load(Ordering::SeqCst), 1);
+
+    // Other countries keep their own scores
+    assert_eq!(router.send_sms(US_PHONE, "Hello").await.unwrap(), "AWS SNS-id");
+    assert_eq!(twilio_sends.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_prefers_faster_provider() {
+    let (twilio, _) = StubSmsService::new("Twilio", false);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    for _ in 0..10 {
+        router.record(0, "+61", true, Duration::from_secs(4));
+        router.record(1, "+61", true, Duration::from_millis(200));
+    }
+
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "AWS SNS-id");
+    assert_eq!(router.send_sms(US_PHONE, "Hello").await.unwrap(), "Twilio-id");
+}
+
+#[tokio::test]
+async fn test_success_rate_outweighs_latency() {
+    let (twilio, _) = StubSmsService::new("Twilio", false);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    for attempt in 0..10 {
+        router.record(0, "+61", true, Duration::from_secs(3));
+        router.record(1, "+61", attempt % 3 != 0, Duration::from_millis(100));
+    }
+
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "Twilio-id");
+}
+
+#[tokio::test]
+async fn test_failures_expire() {
+    let (twilio, _) = StubSmsService::new("Twilio", false);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]).with_routing_config(SmsRoutingConfig {
+        max_sample_age: Duration::from_millis(50),
+        ..Default::default()
+    });
+
+    router.record(0, "+61", false, Duration::from_millis(10));
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "AWS SNS-id");
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(router.send_sms(AU_PHONE, "Hello").await.unwrap(), "Twilio-id");
+}
+
+#[tokio::test]
+async fn test_reports_provider_scores() {
+    let (twilio, _) = StubSmsService::new("Twilio", true);
+    let (sns, _) = StubSmsService::new("AWS SNS", false);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    router.send_sms(AU_PHONE, "Hello").await.unwrap();
+
+    let scores = router.provider_scores();
+    assert_eq!(scores.len(), 2);
+    assert_eq!((scores[0].provider.as_str(), scores[0].country.as_str()), ("AWS SNS", "+61"));
+    assert_eq!(scores[0].success_rate, Some(1.0));
+    assert_eq!(scores[1].provider, "Twilio");
+    assert_eq!(scores[1].success_rate, Some(0.0));
+    assert!(scores[0].score > scores[1].score);
+}
+
+#[tokio::test]
+async fn test_fails_when_every_provider_fails() {
+    let (twilio, _) = StubSmsService::new("Twilio", true);
+    let (sns, _) = StubSmsService::new("AWS SNS", true);
+    let router = FailoverSmsService::new(vec![twilio, sns]);
+
+    let error = router.send_sms(AU_PHONE, "Hello").await.unwrap_err();
+    assert!(error.to_string().contains("Twilio, AWS SNS"));
+}
@@ -0,0 +1,120 @@
+//! Unit tests for the Twilio number lookup service
+
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use re_core::services::verification::PhoneLineType;
+
+use crate::sms::lookup::parse_lookup;
+use crate::sms::{TwilioLookupConfig, TwilioNumberLookupService};
+
+fn config(api_url: &str) -> TwilioLookupConfig {
+    TwilioLookupConfig {
+        account_sid: "ACtest".to_string(),
+        auth_token: "test_auth_token".into(),
+        api_url: api_url.to_string(),
+        request_timeout_secs: 5,
+        cache_ttl: Duration::from_secs(60),
+    }
+}
+
+/// Serve one canned HTTP response per request, returning the requests
+async fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/v2/PhoneNumbers", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buffer = vec![0; 8192];
+            let read = socket.read(&mut buffer).await.unwrap();
+            requests.push(String::from_utf8_lossy(&buffer[..read]).to_string());
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+
+    (url, handle)
+}
+
+#[test]
+fn test_parse_lookup_line_types() {
+    let mobile = parse_lookup(
+        r#"{"valid": true, "line_type_intelligence": {"type": "mobile", "carrier_name": "Telstra"}}"#,
+    )
+    .unwrap();
+    assert!(mobile.valid);
+    assert_eq!(mobile.line_type, PhoneLineType::Mobile);
+    assert_eq!(mobile.carrier.as_deref(), Some("Telstra"));
+
+    let landline = parse_lookup(r#"{"valid": true, "line_type_intelligence": {"type": "landline"}}"#).unwrap();
+    assert_eq!(landline.line_type, PhoneLineType::Landline);
+
+    for voip in ["fixedVoip", "nonFixedVoip"] {
+        let body = format!(r#"{{"valid": true, "line_type_intelligence": {{"type": "{}"}}}}"#, voip);
+        assert_eq!(parse_lookup(&body).unwrap().line_type, PhoneLineType::Voip);
+    }
+
+    let invalid = parse_lookup(r#"{"valid": false, "line_type_intelligence": null}"#).unwrap();
+    assert!(!invalid.valid);
+    assert_eq!(invalid.line_type, PhoneLineType::Unknown);
+
+    assert!(parse_lookup("not json").is_err());
+}
+
+#[tokio::test]
+async fn test_lookup_requests_line_type_and_caches_result() {
+    let (url, handle) = serve(vec![(
+        "200 OK",
+        r#"{"valid": true, "phone_number": "+61298765432", "line_type_intelligence": {"type": "landline", "carrier_name": "Telstra"}}"#,
+    )])
+    .await;
+    let service = TwilioNumberLookupService::new(config(&url)).unwrap();
+
+    let lookup = service.lookup_number("+61298765432").await.unwrap();
+    assert_eq!(lookup.line_type, PhoneLineType::Landline);
+
+    // Served from the cache without a second request
+    let cached = service.lookup_number("+61298765432").await.unwrap();
+    assert_eq!(cached, lookup);
+
+    let requests = handle.await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET /v2/PhoneNumbers/%2B61298765432?Fields=line_type_intelligence"));
+    assert!(requests[0].to_ascii_lowercase().contains("authorization: basic"));
+}
+
+#[tokio::test]
+async fn test_lookup_reports_unknown_numbers_as_invalid() {
+    let (url, _handle) = serve(vec![("404 Not Found", r#"{"code": 20404}"#)]).await;
+    let service = TwilioNumberLookupService::new(config(&url)).unwrap();
+
+    let lookup = service.lookup_number("+61400000000").await.unwrap();
+    assert!(!lookup.valid);
+}
+
+#[tokio::test]
+async fn test_lookup_errors_are_not_cached() {
+    let (url, handle) = serve(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", r#"{"valid": true, "line_type_intelligence": {"type": "mobile"}}"#),
+    ])
+    .await;
+    let service = TwilioNumberLookupService::new(config(&url)).unwrap();
+
+    assert!(service.lookup_number("+61412345678").await.is_err());
+    let lookup = service.lookup_number("+61412345678").await.unwrap();
+    assert_eq!(lookup.line_type, PhoneLineType::Mobile);
+
+    assert_eq!(handle.await.unwrap().len(), 2);
+}
@@ -0,0 +1,119 @@
+//! Unit tests for market routing SMS service
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use re_shared::config::SmsMarketConfig;
+
+use crate::sms::{MarketRoutingSmsService, SmsService};
+use crate::InfrastructureError;
+
+/// Sent message: recipient, body and sender override
+type SentMessage = (String, String, Option<String>);
+
+/// Provider that records what it was asked to send
+#[derive(Clone, Default)]
+struct RecordingSmsService {
+    sent: Arc<Mutex<Vec<SentMessage>>>,
+}
+
+#[async_trait]
+impl SmsService for RecordingSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.sent.lock().unwrap().push((phone_number.to_string(), message.to_string(), None));
+        Ok("recorded".to_string())
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.sent.lock().unwrap().push((
+            phone_number.to_string(),
+            message.to_string(),
+            Some(sender_id.to_string()),
+        ));
+        Ok("recorded".to_string())
+    }
+
+    fn provider_name(&self) -> &str {
+        "Recording"
+    }
+}
+
+fn australia() -> SmsMarketConfig {
+    SmsMarketConfig {
+        templates_namespace: Some("au".to_string()),
+        compliance_footer: Some("Reply STOP to opt out".to_string()),
+        ..SmsMarketConfig::new("+61", "RenovEasy")
+    }
+}
+
+#[tokio::test]
+async fn test_market_sender_and_footer_applied() {
+    let provider = RecordingSmsService::default();
+    let service = MarketRoutingSmsService::new(Box::new(provider.clone()), vec![australia()]);
+
+    service.send_sms("+61412345678", "Hello").await.unwrap();
+
+    let sent = provider.sent.lock().unwrap();
+    assert_eq!(sent[0].1, "Hello\nReply STOP to opt out");
+    assert_eq!(sent[0].2.as_deref(), Some("RenovEasy"));
+}
+
+#[tokio::test]
+async fn test_unknown_market_uses_provider_defaults() {
+    let provider = RecordingSmsService::default();
+    let service = MarketRoutingSmsService::new(Box::new(provider.clone()), vec![australia()]);
+
+    service.send_sms("+8613812345678", "Hello").await.unwrap();
+
+    let sent = provider.sent.lock().unwrap();
+    assert_eq!(sent[0].1, "Hello");
+    assert_eq!(sent[0].2, None);
+}
+
+#[tokio::test]
+async fn test_disabled_market_is_not_routed() {
+    let provider = RecordingSmsService::default();
+    let disabled = SmsMarketConfig { enabled: false, ..australia() };
+    let service = MarketRoutingSmsService::new(Box::new(provider.clone()), vec![disabled]);
+
+    assert!(service.market_for("+61412345678").is_none());
+}
+
+#[test]
+fn test_longest_dialing_code_wins() {
+    let markets = vec![
+        SmsMarketConfig::new("+1", "12345"),
+        SmsMarketConfig::new("+1268", "ANUBRAND"),
+    ];
+    let service = MarketRoutingSmsService::new(Box::new(RecordingSmsService::default()), markets);
+
+    let market = service.market_for("+12684601234").unwrap();
+    assert_eq!(market.country_code, "+1268");
+    assert_eq!(service.market_for("+14155550123").unwrap().country_code, "+1");
+}
+
+#[test]
+fn test_template_name_uses_market_namespace() {
+    let service = MarketRoutingSmsService::new(Box::new(RecordingSmsService::default()), vec![australia()]);
+
+    assert_eq!(service.template_name("+61412345678", "verification_code"), "au.verification_code");
+    assert_eq!(service.template_name("+8613812345678", "verification_code"), "verification_code");
+}
+
+#[test]
+fn test_enabled_market_requires_sender() {
+    let mut market = australia();
+    market.sender_id = None;
+    assert!(market.validate().is_err());
+
+    market.enabled = false;
+    assert!(market.validate().is_ok());
+
+    let invalid_code = SmsMarketConfig::new("61", "RenovEasy");
+    assert!(invalid_code.validate().is_err());
+}
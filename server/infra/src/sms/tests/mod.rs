@@ -6,7 +6,29 @@ pub mod sms_service_tests;
 pub mod mock_sms_tests;
 #[cfg(test)]
 pub mod create_service_tests;
+#[cfg(test)]
+pub mod failover_tests;
+#[cfg(test)]
+pub mod country_router_tests;
+#[cfg(test)]
+pub mod market_router_tests;
+#[cfg(test)]
+pub mod delivery_log_tests;
+#[cfg(test)]
+pub mod throttle_tests;
 #[cfg(all(test, feature = "twilio-sms"))]
 pub mod twilio_tests;
 #[cfg(all(test, feature = "aws-sns"))]
-pub mod aws_sns_tests;
\ No newline at end of file
+pub mod aws_sns_tests;
+#[cfg(all(test, feature = "vonage-sms"))]
+pub mod vonage_tests;
+#[cfg(test)]
+pub mod receipts_tests;
+#[cfg(test)]
+pub mod cost_tests;
+#[cfg(test)]
+pub mod dry_run_tests;
+#[cfg(test)]
+pub mod circuit_breaker_tests;
+#[cfg(test)]
+pub mod lookup_tests;
@@ -0,0 +1,97 @@
+//! Unit tests for SMS delivery receipt adapters
+
+use re_core::domain::entities::sms_delivery::SmsDeliveryStatus;
+use re_core::services::sms_delivery::SmsReceiptProviderTrait;
+use re_shared::config::SecretString;
+
+use crate::sms::{SnsReceiptProvider, TwilioReceiptProvider};
+
+const TWILIO_URL: &str = "https://api.example.com/webhooks/sms/twilio";
+
+#[test]
+fn test_twilio_signature_matches_documented_example() {
+    // Example from the Twilio webhook security documentation
+    let provider = TwilioReceiptProvider::new(SecretString::from("12345"));
+    let payload = b"CallSid=CA1234567890ABCDE&Caller=%2B12349013030&Digits=1234&From=%2B12349013030&To=%2B18005551212";
+
+    assert_eq!(
+        provider.sign("https://mycompany.com/myapp.php?foo=1&bar=2", payload),
+        "0/KCTR6DLpKmkAf8muzZqo1nDgQ="
+    );
+}
+
+#[test]
+fn test_twilio_verify_callback() {
+    let provider = TwilioReceiptProvider::new(SecretString::from("token"));
+    let payload = b"MessageSid=SM123&MessageStatus=delivered";
+    let signature = provider.sign(TWILIO_URL, payload);
+
+    assert!(provider.verify_callback(TWILIO_URL, payload, Some(&signature)).is_ok());
+    assert!(provider.verify_callback(TWILIO_URL, payload, None).is_err());
+    assert!(provider
+        .verify_callback(TWILIO_URL, b"MessageSid=SM123&MessageStatus=failed", Some(&signature))
+        .is_err());
+}
+
+#[test]
+fn test_twilio_parse_receipts() {
+    let provider = TwilioReceiptProvider::new(SecretString::from("token"));
+
+    let receipts = provider
+        .parse_receipts(b"MessageSid=SM123&MessageStatus=undelivered&ErrorCode=30003")
+        .unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].provider_message_id, "SM123");
+    assert_eq!(receipts[0].status, SmsDeliveryStatus::Undelivered);
+    assert_eq!(receipts[0].error.as_deref(), Some("Twilio error 30003"));
+
+    let receipts = provider.parse_receipts(b"MessageSid=SM123&MessageStatus=sending").unwrap();
+    assert_eq!(receipts[0].status, SmsDeliveryStatus::Sent);
+
+    assert!(provider.parse_receipts(b"MessageStatus=delivered").is_err());
+}
+
+#[test]
+fn test_sns_verify_callback() {
+    let provider = SnsReceiptProvider::new(SecretString::from("secret"));
+
+    assert!(provider
+        .verify_callback("https://api.example.com/webhooks/sms/sns?token=secret", b"{}", None)
+        .is_ok());
+    assert!(provider
+        .verify_callback("https://api.example.com/webhooks/sms/sns?token=other", b"{}", None)
+        .is_err());
+    assert!(provider
+        .verify_callback("https://api.example.com/webhooks/sms/sns", b"{}", None)
+        .is_err());
+}
+
+#[test]
+fn test_sns_parse_receipts() {
+    let provider = SnsReceiptProvider::new(SecretString::from("secret"));
+    let message = serde_json::json!({
+        "notification": { "messageId": "b1c3a2d4", "timestamp": "2026-01-01 00:00:00.000" },
+        "delivery": { "providerResponse": "Phone carrier has blocked this message" },
+        "status": "FAILURE"
+    });
+    let payload = serde_json::json!({
+        "Type": "Notification",
+        "MessageId": "e5f6",
+        "Message": message.to_string()
+    });
+
+    let receipts = provider.parse_receipts(payload.to_string().as_bytes()).unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].provider_message_id, "b1c3a2d4");
+    assert_eq!(receipts[0].status, SmsDeliveryStatus::Undelivered);
+    assert_eq!(receipts[0].error.as_deref(), Some("Phone carrier has blocked this message"));
+
+    let confirmation = serde_json::json!({
+        "Type": "SubscriptionConfirmation",
+        "Message": "You have chosen to subscribe to the topic",
+        "SubscribeURL": "https://sns.us-east-1.amazonaws.com/?Action=ConfirmSubscription"
+    });
+    assert!(provider.parse_receipts(confirmation.to_string().as_bytes()).unwrap().is_empty());
+
+    assert!(provider.parse_receipts(b"not json").is_err());
+}
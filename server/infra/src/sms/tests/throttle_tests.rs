@@ -0,0 +1,146 @@
+//! Unit tests for throttled SMS service
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use re_shared::config::SmsProviderLimits;
+use tokio::sync::Semaphore;
+
+use crate::sms::{SmsQueueMetrics, SmsService, ThrottledSmsService};
+use crate::InfrastructureError;
+
+/// Provider that records each message and holds it until the gate opens
+struct GatedSmsService {
+    gate: Arc<Semaphore>,
+    sent: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl SmsService for GatedSmsService {
+    async fn send_sms(&self, _phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.sent.lock().unwrap().push(message.to_string());
+        self.gate.acquire().await.unwrap().forget();
+        Ok(format!("SM{}", self.sent.lock().unwrap().len()))
+    }
+
+    fn provider_name(&self) -> &str {
+        "Gated"
+    }
+}
+
+fn create_service(limits: SmsProviderLimits) -> (Arc<ThrottledSmsService>, Arc<Semaphore>, Arc<Mutex<Vec<String>>>) {
+    let gate = Arc::new(Semaphore::new(0));
+    let sent = Arc::new(Mutex::new(Vec::new()));
+    let provider = GatedSmsService {
+        gate: gate.clone(),
+        sent: sent.clone(),
+    };
+
+    (Arc::new(ThrottledSmsService::new(Box::new(provider), &limits)), gate, sent)
+}
+
+fn single_send_limits() -> SmsProviderLimits {
+    SmsProviderLimits {
+        max_concurrent: 1,
+        rate_per_second: 1000.0,
+        burst: 100,
+        ..SmsProviderLimits::new("gated")
+    }
+}
+
+/// Wait until the queue metrics satisfy a condition
+async fn wait_for(service: &ThrottledSmsService, condition: impl Fn(&SmsQueueMetrics) -> bool) {
+    for _ in 0..500 {
+        if condition(&service.metrics()) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+    panic!("Queue never reached the expected state: {:?}", service.metrics());
+}
+
+#[tokio::test]
+async fn test_verification_codes_dispatched_before_notifications() {
+    let (service, gate, sent) = create_service(single_send_limits());
+
+    let mut sends = Vec::new();
+    for message in ["first", "second", "third"] {
+        let sender = service.clone();
+        sends.push(tokio::spawn(async move { sender.send_sms("+61412345678", message).await }));
+        wait_for(&service, |metrics| metrics.dispatched == 1 && metrics.queue_depth() == sends.len() - 1).await;
+    }
+
+    let otp_service = service.clone();
+    sends.push(tokio::spawn(async move {
+        otp_service.send_verification_code("+61412345678", "123456").await
+    }));
+    wait_for(&service, |metrics| metrics.otp_queue_depth == 1).await;
+
+    gate.add_permits(10);
+    for send in sends {
+        assert!(send.await.unwrap().is_ok());
+    }
+
+    let sent = sent.lock().unwrap();
+    assert_eq!(sent[0], "first");
+    assert!(sent[1].contains("123456"));
+    assert_eq!(&sent[2..], ["second", "third"]);
+}
+
+#[tokio::test]
+async fn test_send_rejected_when_queue_is_full() {
+    let (service, gate, _sent) = create_service(SmsProviderLimits {
+        max_queue_depth: 1,
+        ..single_send_limits()
+    });
+
+    let in_flight = tokio::spawn({
+        let service = service.clone();
+        async move { service.send_sms("+61412345678", "in flight").await }
+    });
+    wait_for(&service, |metrics| metrics.in_flight == 1).await;
+
+    let queued = tokio::spawn({
+        let service = service.clone();
+        async move { service.send_sms("+61412345678", "queued").await }
+    });
+    wait_for(&service, |metrics| metrics.notification_queue_depth == 1).await;
+
+    let result = service.send_sms("+61412345678", "rejected").await;
+    assert!(matches!(result, Err(InfrastructureError::Sms(ref e)) if e.contains("queue is full")));
+    assert_eq!(service.metrics().rejected, 1);
+
+    gate.add_permits(10);
+    assert!(in_flight.await.unwrap().is_ok());
+    assert!(queued.await.unwrap().is_ok());
+    assert_eq!(service.metrics().dispatched, 2);
+}
+
+#[tokio::test]
+async fn test_send_rate_capped_by_token_bucket() {
+    let (service, gate, _sent) = create_service(SmsProviderLimits {
+        max_concurrent: 10,
+        rate_per_second: 20.0,
+        burst: 1,
+        ..SmsProviderLimits::new("gated")
+    });
+    gate.add_permits(10);
+
+    let started = Instant::now();
+    let sends: Vec<_> = (0..3)
+        .map(|i| {
+            let service = service.clone();
+            tokio::spawn(async move { service.send_sms("+61412345678", &format!("message {}", i)).await })
+        })
+        .collect();
+    for send in sends {
+        assert!(send.await.unwrap().is_ok());
+    }
+
+    // One send from the burst, then one every 50ms
+    assert!(started.elapsed() >= Duration::from_millis(90));
+    let metrics = service.metrics();
+    assert_eq!(metrics.dispatched, 3);
+    assert!(metrics.max_wait_ms >= 90);
+}
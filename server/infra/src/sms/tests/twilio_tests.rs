@@ -10,7 +10,7 @@ mod tests {
     fn setup_test_config() -> TwilioConfig {
         TwilioConfig {
             account_sid: "ACtest_account_sid".to_string(),
-            auth_token: "test_auth_token".to_string(),
+            auth_token: "test_auth_token".into(),
             from_number: "+15551234567".to_string(),
             max_retries: 3,
             retry_delay_ms: 100,
@@ -40,7 +40,7 @@ mod tests {
     fn test_twilio_config_creation() {
         let config = setup_test_config();
         assert_eq!(config.account_sid, "ACtest_account_sid");
-        assert_eq!(config.auth_token, "test_auth_token");
+        assert_eq!(config.auth_token.expose_secret(), "test_auth_token");
         assert_eq!(config.from_number, "+15551234567");
         assert_eq!(config.max_retries, 3);
         assert_eq!(config.retry_delay_ms, 100);
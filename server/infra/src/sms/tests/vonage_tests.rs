@@ -0,0 +1,141 @@
+//! Unit tests for Vonage SMS service
+
+#[cfg(test)]
+#[cfg(feature = "vonage-sms")]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use crate::sms::sms_service::SmsService;
+    use crate::sms::vonage::{parse_response, VonageOutcome, DEFAULT_VONAGE_API_URL};
+    use crate::sms::{VonageConfig, VonageSmsService};
+
+    fn setup_test_config(api_url: &str) -> VonageConfig {
+        VonageConfig {
+            api_key: "test_api_key".to_string(),
+            api_secret: "test_api_secret".into(),
+            from: "+15551234567".to_string(),
+            api_url: api_url.to_string(),
+            max_retries: 2,
+            retry_delay_ms: 10,
+            request_timeout_secs: 5,
+        }
+    }
+
+    /// Serve one canned HTTP response per request, returning the request bodies
+    async fn serve(responses: Vec<&'static str>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/sms/json", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 8192];
+                let read = socket.read(&mut buffer).await.unwrap();
+                requests.push(String::from_utf8_lossy(&buffer[..read]).to_string());
+
+                let status = if body.is_empty() { "503 Service Unavailable" } else { "200 OK" };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_vonage_config_from_env() {
+        std::env::set_var("VONAGE_API_KEY", "test_api_key");
+        std::env::set_var("VONAGE_API_SECRET", "test_api_secret");
+        std::env::set_var("VONAGE_FROM", "RenovEasy");
+        std::env::set_var("VONAGE_MAX_RETRIES", "5");
+
+        let config = VonageConfig::from_env().expect("Should create config from env");
+        assert_eq!(config.api_key, "test_api_key");
+        assert_eq!(config.api_secret.expose_secret(), "test_api_secret");
+        assert_eq!(config.from, "RenovEasy");
+        assert_eq!(config.api_url, DEFAULT_VONAGE_API_URL);
+        assert_eq!(config.max_retries, 5);
+        assert_eq!(config.retry_delay_ms, 1000);
+
+        std::env::remove_var("VONAGE_API_SECRET");
+        assert!(VonageConfig::from_env().is_err());
+
+        std::env::remove_var("VONAGE_API_KEY");
+        std::env::remove_var("VONAGE_FROM");
+        std::env::remove_var("VONAGE_MAX_RETRIES");
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(r#"{"message-count":"1","messages":[{"status":"0","message-id":"0A00000012345678"}]}"#),
+            VonageOutcome::Sent("0A00000012345678".to_string())
+        );
+        assert!(matches!(
+            parse_response(r#"{"messages":[{"status":"0","message-id":"A"},{"status":"1","error-text":"Throughput Rate Exceeded"}]}"#),
+            VonageOutcome::Retryable(_)
+        ));
+        assert!(matches!(
+            parse_response(r#"{"messages":[{"status":"4","error-text":"Bad Credentials"}]}"#),
+            VonageOutcome::Rejected(e) if e.contains("Bad Credentials")
+        ));
+        assert!(matches!(parse_response("not json"), VonageOutcome::Retryable(_)));
+    }
+
+    #[tokio::test]
+    async fn test_send_sms_posts_form_and_returns_message_id() {
+        let (url, server) = serve(vec![
+            r#"{"message-count":"1","messages":[{"status":"0","message-id":"0A00000012345678"}]}"#,
+        ])
+        .await;
+        let service = VonageSmsService::new(setup_test_config(&url)).unwrap();
+
+        let message_id = service.send_sms("+61412345678", "Your code is 123456").await.unwrap();
+        assert_eq!(message_id, "0A00000012345678");
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /sms/json"));
+        assert!(requests[0].contains("api_key=test_api_key"));
+        assert!(requests[0].contains("from=15551234567"));
+        assert!(requests[0].contains("to=61412345678"));
+    }
+
+    #[tokio::test]
+    async fn test_send_sms_retries_throttled_requests() {
+        let (url, server) = serve(vec![
+            "",
+            r#"{"messages":[{"status":"0","message-id":"0A00000012345679"}]}"#,
+        ])
+        .await;
+        let service = VonageSmsService::new(setup_test_config(&url)).unwrap();
+
+        assert_eq!(service.send_sms("+61412345678", "Hello").await.unwrap(), "0A00000012345679");
+        assert_eq!(server.await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_send_sms_does_not_retry_rejected_messages() {
+        let (url, server) = serve(vec![r#"{"messages":[{"status":"6","error-text":"Unroutable message"}]}"#]).await;
+        let service = VonageSmsService::new(setup_test_config(&url)).unwrap();
+
+        assert!(service.send_sms("+61412345678", "Hello").await.is_err());
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_numbers_without_country_code() {
+        let service = VonageSmsService::new(setup_test_config(DEFAULT_VONAGE_API_URL)).unwrap();
+
+        assert!(service.send_sms("0412345678", "Hello").await.is_err());
+        assert_eq!(service.provider_name(), "Vonage");
+        assert!(service.is_available().await);
+    }
+}
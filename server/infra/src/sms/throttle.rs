@@ -0,0 +1,395 @@
+//! Throttled SMS Service
+//!
+//! Providers throttle accounts that exceed their rate limits, and under a
+//! spike of notifications verification codes then arrive late or not at all.
+//! This module puts a dispatch worker in front of one provider that caps its
+//! concurrent sends and its send rate with a token bucket. Sends over the
+//! caps wait in a bounded queue, where verification codes always go ahead of
+//! other messages.
+//!
+//! ## Features
+//!
+//! - Concurrency cap and token bucket per provider
+//! - Verification codes dispatched before notifications
+//! - Bounded queues, a send is rejected when its queue is full
+//! - Queue depth and wait time metrics
+//! - Trace context carried over to the provider call
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use re_core::services::trace;
+use re_shared::config::SmsProviderLimits;
+
+use crate::{
+    sms::sms_service::{mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// Priority of an SMS in the send queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmsPriority {
+    /// Verification codes, which a user is waiting for
+    Otp,
+    /// Notifications, reminders and other messages
+    Notification,
+}
+
+tokio::task_local! {
+    static PRIORITY: SmsPriority;
+}
+
+impl SmsPriority {
+    /// Run a future with this priority for the messages it sends
+    pub fn scope<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        PRIORITY.scope(self, future)
+    }
+
+    /// Priority of the message being sent, `Notification` outside a scope
+    pub fn current() -> Self {
+        PRIORITY.try_with(|priority| *priority).unwrap_or(Self::Notification)
+    }
+}
+
+/// Snapshot of a provider's send queue
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmsQueueMetrics {
+    /// Verification codes waiting to be dispatched
+    pub otp_queue_depth: usize,
+    /// Other messages waiting to be dispatched
+    pub notification_queue_depth: usize,
+    /// Sends handed to the provider and not yet answered
+    pub in_flight: usize,
+    /// Sends handed to the provider since start
+    pub dispatched: u64,
+    /// Sends rejected because their queue was full
+    pub rejected: u64,
+    /// Total time dispatched sends spent in the queue (in milliseconds)
+    pub total_wait_ms: u64,
+    /// Longest time a dispatched send spent in the queue (in milliseconds)
+    pub max_wait_ms: u64,
+}
+
+impl SmsQueueMetrics {
+    /// Number of sends waiting in either queue
+    pub fn queue_depth(&self) -> usize {
+        self.otp_queue_depth + self.notification_queue_depth
+    }
+
+    /// Average time a dispatched send spent in the queue (in milliseconds)
+    pub fn average_wait_ms(&self) -> u64 {
+        self.total_wait_ms.checked_div(self.dispatched).unwrap_or(0)
+    }
+}
+
+/// Live counters behind [`SmsQueueMetrics`]
+#[derive(Default)]
+struct QueueCounters {
+    otp_queue_depth: AtomicUsize,
+    notification_queue_depth: AtomicUsize,
+    in_flight: AtomicUsize,
+    dispatched: AtomicU64,
+    rejected: AtomicU64,
+    total_wait_ms: AtomicU64,
+    max_wait_ms: AtomicU64,
+}
+
+impl QueueCounters {
+    fn queue_depth(&self, priority: SmsPriority) -> &AtomicUsize {
+        match priority {
+            SmsPriority::Otp => &self.otp_queue_depth,
+            SmsPriority::Notification => &self.notification_queue_depth,
+        }
+    }
+
+    fn record_dispatch(&self, priority: SmsPriority, wait: Duration) {
+        let wait_ms = wait.as_millis() as u64;
+        self.queue_depth(priority).fetch_sub(1, Ordering::Relaxed);
+        self.dispatched.fetch_add(1, Ordering::Relaxed);
+        self.total_wait_ms.fetch_add(wait_ms, Ordering::Relaxed);
+        self.max_wait_ms.fetch_max(wait_ms, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> SmsQueueMetrics {
+        SmsQueueMetrics {
+            otp_queue_depth: self.otp_queue_depth.load(Ordering::Relaxed),
+            notification_queue_depth: self.notification_queue_depth.load(Ordering::Relaxed),
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            dispatched: self.dispatched.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            total_wait_ms: self.total_wait_ms.load(Ordering::Relaxed),
+            max_wait_ms: self.max_wait_ms.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A send waiting for the dispatch worker
+struct QueuedSms {
+    phone_number: String,
+    message: String,
+    sender_id: Option<String>,
+    trace_id: Option<String>,
+    enqueued_at: Instant,
+    reply: oneshot::Sender<Result<String, InfrastructureError>>,
+}
+
+/// Token bucket limiting how many sends start per second
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    rate_per_second: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    fn new(limits: &SmsProviderLimits) -> Self {
+        let capacity = f64::from(limits.burst.max(1));
+
+        Self {
+            capacity,
+            tokens: capacity,
+            rate_per_second: limits.rate_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Time until a token is available
+    fn wait_time(&mut self) -> Duration {
+        self.refill();
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.rate_per_second)
+        }
+    }
+
+    /// Take a token, which must be available
+    fn take(&mut self) {
+        self.refill();
+        self.tokens -= 1.0;
+    }
+}
+
+/// Background task handing queued sends to the provider
+struct DispatchWorker {
+    inner: Arc<dyn SmsService>,
+    bucket: TokenBucket,
+    permits: Arc<Semaphore>,
+    otp_queue: mpsc::Receiver<QueuedSms>,
+    notification_queue: mpsc::Receiver<QueuedSms>,
+    counters: Arc<QueueCounters>,
+}
+
+impl DispatchWorker {
+    /// Dispatch sends until both queues are closed
+    ///
+    /// Capacity is reserved before a send is picked, so a verification code
+    /// queued while the worker waits for a token still goes out first.
+    async fn run(mut self) {
+        loop {
+            let Ok(permit) = self.permits.clone().acquire_owned().await else {
+                break;
+            };
+
+            let wait = self.bucket.wait_time();
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+
+            let (priority, sms) = tokio::select! {
+                biased;
+                Some(sms) = self.otp_queue.recv() => (SmsPriority::Otp, sms),
+                Some(sms) = self.notification_queue.recv() => (SmsPriority::Notification, sms),
+                else => break,
+            };
+
+            self.bucket.take();
+            let wait = sms.enqueued_at.elapsed();
+            self.counters.record_dispatch(priority, wait);
+            debug!(
+                "Dispatching {:?} SMS to {} through {} after {}ms in queue",
+                priority,
+                mask_phone_number(&sms.phone_number),
+                self.inner.provider_name(),
+                wait.as_millis()
+            );
+
+            let inner = self.inner.clone();
+            let counters = self.counters.clone();
+            counters.in_flight.fetch_add(1, Ordering::Relaxed);
+            tokio::spawn(async move {
+                let QueuedSms {
+                    phone_number,
+                    message,
+                    sender_id,
+                    trace_id,
+                    reply,
+                    ..
+                } = sms;
+
+                let send = async {
+                    match sender_id {
+                        Some(ref sender_id) => inner.send_sms_from(&phone_number, &message, sender_id).await,
+                        None => inner.send_sms(&phone_number, &message).await,
+                    }
+                };
+                let result = match trace_id {
+                    Some(trace_id) => trace::scope(trace_id, send).await,
+                    None => send.await,
+                };
+
+                counters.in_flight.fetch_sub(1, Ordering::Relaxed);
+                drop(permit);
+                let _ = reply.send(result);
+            });
+        }
+    }
+}
+
+/// SMS service that caps a provider's throughput and queues the excess
+pub struct ThrottledSmsService {
+    /// Provider the messages are sent through
+    inner: Arc<dyn SmsService>,
+    /// Queue of verification codes
+    otp_queue: mpsc::Sender<QueuedSms>,
+    /// Queue of other messages
+    notification_queue: mpsc::Sender<QueuedSms>,
+    /// Queue metrics shared with the dispatch worker
+    counters: Arc<QueueCounters>,
+}
+
+impl ThrottledSmsService {
+    /// Create a new throttled SMS service and start its dispatch worker
+    ///
+    /// Must be called within a Tokio runtime. The worker stops when the
+    /// service is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The SMS provider to send through
+    /// * `limits` - Concurrency, rate and queue limits of the provider
+    pub fn new(inner: Box<dyn SmsService>, limits: &SmsProviderLimits) -> Self {
+        let inner: Arc<dyn SmsService> = Arc::from(inner);
+        let queue_depth = limits.max_queue_depth.max(1);
+        let (otp_queue, otp_receiver) = mpsc::channel(queue_depth);
+        let (notification_queue, notification_receiver) = mpsc::channel(queue_depth);
+        let counters = Arc::new(QueueCounters::default());
+
+        let worker = DispatchWorker {
+            inner: inner.clone(),
+            bucket: TokenBucket::new(limits),
+            permits: Arc::new(Semaphore::new(limits.max_concurrent.max(1))),
+            otp_queue: otp_receiver,
+            notification_queue: notification_receiver,
+            counters: counters.clone(),
+        };
+        tokio::spawn(worker.run());
+
+        Self {
+            inner,
+            otp_queue,
+            notification_queue,
+            counters,
+        }
+    }
+
+    /// Current queue depth and wait time metrics
+    pub fn metrics(&self) -> SmsQueueMetrics {
+        self.counters.snapshot()
+    }
+
+    /// Queue a send at the current priority and wait for the provider's answer
+    async fn enqueue(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: Option<&str>,
+    ) -> Result<String, InfrastructureError> {
+        let priority = SmsPriority::current();
+        let (reply, response) = oneshot::channel();
+        let sms = QueuedSms {
+            phone_number: phone_number.to_string(),
+            message: message.to_string(),
+            sender_id: sender_id.map(String::from),
+            trace_id: trace::current_trace_id(),
+            enqueued_at: Instant::now(),
+            reply,
+        };
+
+        let queue = match priority {
+            SmsPriority::Otp => &self.otp_queue,
+            SmsPriority::Notification => &self.notification_queue,
+        };
+
+        let depth = self.counters.queue_depth(priority);
+        depth.fetch_add(1, Ordering::Relaxed);
+        if let Err(e) = queue.try_send(sms) {
+            depth.fetch_sub(1, Ordering::Relaxed);
+            self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+
+            let reason = match e {
+                mpsc::error::TrySendError::Full(_) => "send queue is full",
+                mpsc::error::TrySendError::Closed(_) => "dispatch worker has stopped",
+            };
+            warn!(
+                "Rejected {:?} SMS to {}: {} {}",
+                priority,
+                mask_phone_number(phone_number),
+                self.inner.provider_name(),
+                reason
+            );
+
+            return Err(InfrastructureError::Sms(format!(
+                "{} {}",
+                self.inner.provider_name(),
+                reason
+            )));
+        }
+
+        response.await.unwrap_or_else(|_| {
+            Err(InfrastructureError::Sms(format!(
+                "{} dispatch worker dropped the message",
+                self.inner.provider_name()
+            )))
+        })
+    }
+}
+
+#[async_trait]
+impl SmsService for ThrottledSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.enqueue(phone_number, message, None).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        self.enqueue(phone_number, message, Some(sender_id)).await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+}
@@ -14,11 +14,16 @@
 
 use async_trait::async_trait;
 use phonenumber::{Mode, PhoneNumber};
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, warn};
 use twilio::{Client, OutboundMessage};
 
+use re_core::services::trace::current_trace_id;
+use re_shared::config::SecretString;
+
 use crate::{
+    sms::dry_run::SmsDryRunLog,
     sms::sms_service::{mask_phone_number, SmsService},
     InfrastructureError,
 };
@@ -29,7 +34,7 @@ pub struct TwilioConfig {
     /// Twilio Account SID
     pub account_sid: String,
     /// Twilio Auth Token
-    pub auth_token: String,
+    pub auth_token: SecretString,
     /// From phone number (must be a Twilio phone number)
     pub from_number: String,
     /// Maximum retry attempts for failed requests
@@ -59,7 +64,7 @@ impl TwilioConfig {
         
         Ok(Self {
             account_sid,
-            auth_token,
+            auth_token: SecretString::from(auth_token),
             from_number,
             max_retries: std::env::var("TWILIO_MAX_RETRIES")
                 .ok()
@@ -81,19 +86,32 @@ impl TwilioConfig {
 pub struct TwilioSmsService {
     client: Client,
     config: TwilioConfig,
+    dry_run: Option<Arc<SmsDryRunLog>>,
 }
 
 impl TwilioSmsService {
     /// Create a new Twilio SMS service
     pub fn new(config: TwilioConfig) -> Result<Self, InfrastructureError> {
-        let client = Client::new(&config.account_sid, &config.auth_token);
+        let client = Client::new(&config.account_sid, config.auth_token.expose_secret());
         
         info!(
             "Twilio SMS service initialized with from number: {}",
             mask_phone_number(&config.from_number)
         );
         
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            dry_run: None,
+        })
+    }
+
+    /// Record messages in a dry-run log instead of sending them
+    ///
+    /// Messages are still validated and prepared as if they were sent.
+    pub fn with_dry_run(mut self, log: Arc<SmsDryRunLog>) -> Self {
+        self.dry_run = Some(log);
+        self
     }
     
     /// Create from environment variables
@@ -145,6 +163,7 @@ impl TwilioSmsService {
     /// Send SMS with retry logic
     async fn send_with_retry(
         &self,
+        from: &str,
         to: &str,
         message: &str,
     ) -> Result<String, InfrastructureError> {
@@ -162,15 +181,18 @@ impl TwilioSmsService {
             );
             
             // Create the message
-            let msg = OutboundMessage::new(&self.config.from_number, to, message);
+            let msg = OutboundMessage::new(from, to, message);
             
             // Try to send the message
             match self.client.send_message(msg).await {
                 Ok(response) => {
+                    // Twilio's Messages API has no client reference field, so
+                    // the SID is linked to the trace ID in the delivery log
                     info!(
-                        "SMS sent successfully to {} with SID: {}",
+                        "SMS sent successfully to {} with SID: {} (trace: {})",
                         mask_phone_number(to),
-                        response.sid
+                        response.sid,
+                        current_trace_id().as_deref().unwrap_or("-")
                     );
                     return Ok(response.sid);
                 }
@@ -215,6 +237,15 @@ impl TwilioSmsService {
 #[async_trait]
 impl SmsService for TwilioSmsService {
     async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.send_sms_from(phone_number, message, &self.config.from_number).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
         // Validate and normalize the phone number
         let normalized_phone = self.validate_phone_number(phone_number)?;
         
@@ -232,8 +263,12 @@ impl SmsService for TwilioSmsService {
             ));
         }
         
+        if let Some(ref dry_run) = self.dry_run {
+            return Ok(dry_run.record(self.provider_name(), sender_id, &normalized_phone, message));
+        }
+
         // Send the message with retry logic
-        self.send_with_retry(&normalized_phone, message).await
+        self.send_with_retry(sender_id, &normalized_phone, message).await
     }
     
     fn provider_name(&self) -> &str {
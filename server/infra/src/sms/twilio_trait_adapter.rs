@@ -39,6 +39,10 @@ impl SmsServiceTrait for TwilioSmsServiceAdapter {
         }
     }
     
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        self.inner.send_sms(phone, message).await.map_err(|e| e.to_string())
+    }
+    
     fn is_valid_phone_number(&self, phone: &str) -> bool {
         // Use the same validation logic
         crate::sms::sms_service::is_valid_phone_number(phone)
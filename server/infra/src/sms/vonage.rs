@@ -0,0 +1,344 @@
+//! Vonage SMS Service Implementation
+//!
+//! This module provides SMS sending capabilities using the Vonage (formerly
+//! Nexmo) SMS API. It implements the SmsService trait and serves as a third
+//! provider when both Twilio and AWS SNS are failing or rate-limited.
+//!
+//! ## Features
+//!
+//! - International SMS support with E.164 format validation
+//! - Automatic retry logic with exponential backoff
+//! - Throttling and server errors retried, rejected messages not
+//! - Request trace ID sent as the `client-ref` of the message
+//! - Security: Phone number masking in logs
+
+use async_trait::async_trait;
+use phonenumber::{Mode, PhoneNumber};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+use re_core::services::trace::current_trace_id;
+use re_shared::config::SecretString;
+
+use crate::{
+    sms::dry_run::SmsDryRunLog,
+    sms::sms_service::{mask_phone_number, SmsService},
+    InfrastructureError,
+};
+
+/// Default endpoint of the Vonage SMS API
+pub const DEFAULT_VONAGE_API_URL: &str = "https://rest.nexmo.com/sms/json";
+
+/// Longest `client-ref` Vonage accepts
+const MAX_CLIENT_REF_LEN: usize = 100;
+
+/// Vonage SMS service configuration
+#[derive(Debug, Clone)]
+pub struct VonageConfig {
+    /// Vonage API key
+    pub api_key: String,
+    /// Vonage API secret
+    pub api_secret: SecretString,
+    /// Sender: a Vonage virtual number in E.164 format or an alphanumeric sender ID
+    pub from: String,
+    /// Endpoint of the SMS API
+    pub api_url: String,
+    /// Maximum retry attempts for failed requests
+    pub max_retries: u32,
+    /// Initial retry delay in milliseconds
+    pub retry_delay_ms: u64,
+    /// Timeout for API requests in seconds
+    pub request_timeout_secs: u64,
+}
+
+impl VonageConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let api_key = std::env::var("VONAGE_API_KEY")
+            .map_err(|_| InfrastructureError::Config("VONAGE_API_KEY not set".to_string()))?;
+        let api_secret = std::env::var("VONAGE_API_SECRET")
+            .map_err(|_| InfrastructureError::Config("VONAGE_API_SECRET not set".to_string()))?;
+        let from = std::env::var("VONAGE_FROM")
+            .map_err(|_| InfrastructureError::Config("VONAGE_FROM not set".to_string()))?;
+
+        Ok(Self {
+            api_key,
+            api_secret: SecretString::from(api_secret),
+            from,
+            api_url: std::env::var("VONAGE_API_URL").unwrap_or_else(|_| DEFAULT_VONAGE_API_URL.to_string()),
+            max_retries: std::env::var("VONAGE_MAX_RETRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            retry_delay_ms: std::env::var("VONAGE_RETRY_DELAY_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            request_timeout_secs: std::env::var("VONAGE_REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        })
+    }
+}
+
+/// Response of the Vonage SMS API
+#[derive(Debug, Deserialize)]
+pub(crate) struct VonageResponse {
+    pub messages: Vec<VonageMessageStatus>,
+}
+
+/// Status of one message part in a Vonage response
+#[derive(Debug, Deserialize)]
+pub(crate) struct VonageMessageStatus {
+    /// "0" on success, an error code otherwise
+    pub status: String,
+    #[serde(rename = "message-id")]
+    pub message_id: Option<String>,
+    #[serde(rename = "error-text")]
+    pub error_text: Option<String>,
+}
+
+/// Outcome of a send request
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum VonageOutcome {
+    /// Accepted, with the ID of the first message part
+    Sent(String),
+    /// Throttled or failed on the Vonage side; worth retrying
+    Retryable(String),
+    /// Rejected, e.g. for invalid credentials or number
+    Rejected(String),
+}
+
+/// Classify the body of a Vonage response
+///
+/// Long messages are split in parts, each with its own status; the message
+/// is only sent if every part is.
+pub(crate) fn parse_response(body: &str) -> VonageOutcome {
+    let response: VonageResponse = match serde_json::from_str(body) {
+        Ok(response) => response,
+        Err(e) => return VonageOutcome::Retryable(format!("Invalid Vonage response: {}", e)),
+    };
+
+    if let Some(failed) = response.messages.iter().find(|message| message.status != "0") {
+        let error = format!(
+            "Vonage status {}: {}",
+            failed.status,
+            failed.error_text.as_deref().unwrap_or("unknown error")
+        );
+        // 1: throttled, 5: internal error
+        return match failed.status.as_str() {
+            "1" | "5" => VonageOutcome::Retryable(error),
+            _ => VonageOutcome::Rejected(error),
+        };
+    }
+
+    match response.messages.first().and_then(|message| message.message_id.clone()) {
+        Some(message_id) => VonageOutcome::Sent(message_id),
+        None => VonageOutcome::Retryable("Vonage response has no message ID".to_string()),
+    }
+}
+
+/// Vonage SMS service implementation
+pub struct VonageSmsService {
+    client: reqwest::Client,
+    config: VonageConfig,
+    dry_run: Option<Arc<SmsDryRunLog>>,
+}
+
+impl VonageSmsService {
+    /// Create a new Vonage SMS service
+    pub fn new(config: VonageConfig) -> Result<Self, InfrastructureError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .map_err(|e| InfrastructureError::Config(format!("Failed to create Vonage HTTP client: {}", e)))?;
+
+        info!(
+            "Vonage SMS service initialized with sender: {}",
+            mask_phone_number(&config.from)
+        );
+
+        Ok(Self {
+            client,
+            config,
+            dry_run: None,
+        })
+    }
+
+    /// Record messages in a dry-run log instead of sending them
+    ///
+    /// Messages are still validated and prepared as if they were sent.
+    pub fn with_dry_run(mut self, log: Arc<SmsDryRunLog>) -> Self {
+        self.dry_run = Some(log);
+        self
+    }
+
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let config = VonageConfig::from_env()?;
+        Self::new(config)
+    }
+
+    /// Validate and normalize phone number to E.164 format
+    fn validate_phone_number(&self, phone: &str) -> Result<String, InfrastructureError> {
+        if !phone.starts_with('+') {
+            return Err(InfrastructureError::Sms(
+                "Phone number must be in E.164 format (e.g., +1234567890)".to_string()
+            ));
+        }
+
+        match phone.parse::<PhoneNumber>() {
+            Ok(parsed) => {
+                let formatted = parsed.format().mode(Mode::E164).to_string();
+                debug!("Validated phone number: {}", mask_phone_number(&formatted));
+                Ok(formatted)
+            }
+            Err(e) => {
+                error!("Invalid phone number format: {}", e);
+                Err(InfrastructureError::Sms(format!(
+                    "Invalid phone number format: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Send one request to the SMS API
+    async fn send_once(&self, from: &str, to: &str, message: &str) -> VonageOutcome {
+        let trace_id = current_trace_id();
+
+        // Vonage expects numbers without the leading '+'
+        let mut form = vec![
+            ("api_key", self.config.api_key.as_str()),
+            ("api_secret", self.config.api_secret.expose_secret()),
+            ("from", from.trim_start_matches('+')),
+            ("to", to.trim_start_matches('+')),
+            ("text", message),
+        ];
+        if let Some(trace_id) = trace_id.as_deref() {
+            form.push(("client-ref", &trace_id[..trace_id.len().min(MAX_CLIENT_REF_LEN)]));
+        }
+        if !message.is_ascii() {
+            form.push(("type", "unicode"));
+        }
+
+        let response = match self.client.post(&self.config.api_url).form(&form).send().await {
+            Ok(response) => response,
+            Err(e) => return VonageOutcome::Retryable(format!("Vonage request failed: {}", e)),
+        };
+
+        let status = response.status();
+        if status.as_u16() == 429 || status.is_server_error() {
+            return VonageOutcome::Retryable(format!("Vonage returned HTTP {}", status));
+        }
+        if !status.is_success() {
+            return VonageOutcome::Rejected(format!("Vonage returned HTTP {}", status));
+        }
+
+        match response.text().await {
+            Ok(body) => parse_response(&body),
+            Err(e) => VonageOutcome::Retryable(format!("Failed to read Vonage response: {}", e)),
+        }
+    }
+
+    /// Send SMS with retry logic
+    async fn send_with_retry(
+        &self,
+        from: &str,
+        to: &str,
+        message: &str,
+    ) -> Result<String, InfrastructureError> {
+        let mut attempts = 0;
+        let mut delay = Duration::from_millis(self.config.retry_delay_ms);
+
+        loop {
+            attempts += 1;
+
+            debug!(
+                "Sending SMS attempt {}/{} to {}",
+                attempts,
+                self.config.max_retries,
+                mask_phone_number(to)
+            );
+
+            match self.send_once(from, to, message).await {
+                VonageOutcome::Sent(message_id) => {
+                    info!(
+                        "SMS sent successfully to {} with message ID: {} (trace: {})",
+                        mask_phone_number(to),
+                        message_id,
+                        current_trace_id().as_deref().unwrap_or("-")
+                    );
+                    return Ok(message_id);
+                }
+                VonageOutcome::Rejected(e) => {
+                    error!("Vonage rejected SMS to {}: {}", mask_phone_number(to), e);
+                    return Err(InfrastructureError::Sms(format!("Invalid request: {}", e)));
+                }
+                VonageOutcome::Retryable(e) => {
+                    error!(
+                        "Failed to send SMS (attempt {}/{}): {}",
+                        attempts, self.config.max_retries, e
+                    );
+
+                    if attempts >= self.config.max_retries {
+                        return Err(InfrastructureError::Sms(format!(
+                            "Failed to send SMS after {} attempts: {}",
+                            self.config.max_retries, e
+                        )));
+                    }
+
+                    warn!("Retrying Vonage SMS after {:?}", delay);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2; // Exponential backoff
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SmsService for VonageSmsService {
+    async fn send_sms(&self, phone_number: &str, message: &str) -> Result<String, InfrastructureError> {
+        self.send_sms_from(phone_number, message, &self.config.from).await
+    }
+
+    async fn send_sms_from(
+        &self,
+        phone_number: &str,
+        message: &str,
+        sender_id: &str,
+    ) -> Result<String, InfrastructureError> {
+        let normalized_phone = self.validate_phone_number(phone_number)?;
+
+        info!(
+            "Sending SMS to {} via Vonage (message length: {} chars)",
+            mask_phone_number(&normalized_phone),
+            message.len()
+        );
+
+        // Vonage concatenates up to 6 parts, about 918 GSM characters
+        if message.chars().count() > 918 {
+            return Err(InfrastructureError::Sms(
+                "Message exceeds maximum length of 918 characters".to_string()
+            ));
+        }
+
+        if let Some(ref dry_run) = self.dry_run {
+            return Ok(dry_run.record(self.provider_name(), sender_id, &normalized_phone, message));
+        }
+
+        self.send_with_retry(sender_id, &normalized_phone, message).await
+    }
+
+    fn provider_name(&self) -> &str {
+        "Vonage"
+    }
+
+    async fn is_available(&self) -> bool {
+        !self.config.api_key.is_empty() && !self.config.api_secret.expose_secret().is_empty()
+    }
+}
@@ -0,0 +1,50 @@
+//! Vonage SMS Service Trait Adapter
+//!
+//! This module provides an adapter that implements the core SmsServiceTrait
+//! for the Vonage SMS service, bridging the infrastructure implementation
+//! with the core domain trait.
+
+use async_trait::async_trait;
+use re_core::services::verification::SmsServiceTrait;
+
+use crate::sms::vonage::{VonageSmsService, VonageConfig};
+use crate::sms::sms_service::SmsService;
+
+/// Adapter that implements the core SmsServiceTrait for Vonage
+pub struct VonageSmsServiceAdapter {
+    inner: VonageSmsService,
+}
+
+impl VonageSmsServiceAdapter {
+    /// Create a new Vonage SMS service adapter
+    pub fn new(config: VonageConfig) -> Result<Self, crate::InfrastructureError> {
+        let inner = VonageSmsService::new(config)?;
+        Ok(Self { inner })
+    }
+    
+    /// Create from environment variables
+    pub fn from_env() -> Result<Self, crate::InfrastructureError> {
+        let config = VonageConfig::from_env()?;
+        Self::new(config)
+    }
+}
+
+#[async_trait]
+impl SmsServiceTrait for VonageSmsServiceAdapter {
+    async fn send_verification_code(&self, phone: &str, code: &str) -> Result<String, String> {
+        // Use the infrastructure SmsService trait method
+        match self.inner.send_verification_code(phone, code).await {
+            Ok(message_id) => Ok(message_id),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+    
+    async fn send_message(&self, phone: &str, message: &str) -> Result<String, String> {
+        self.inner.send_sms(phone, message).await.map_err(|e| e.to_string())
+    }
+    
+    fn is_valid_phone_number(&self, phone: &str) -> bool {
+        // Use the same validation logic
+        crate::sms::sms_service::is_valid_phone_number(phone)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+//! Object storage on the local file system
+//!
+//! Each object is stored as a file named after its key below the storage
+//! directory. When several API instances run export workers, the directory
+//! must be a volume they all mount, so any instance can serve a download.
+
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+
+use re_core::services::export::ObjectStorageTrait;
+
+use crate::InfrastructureError;
+
+/// File system storage configuration
+#[derive(Debug, Clone)]
+pub struct FileSystemStorageConfig {
+    /// Directory objects are stored in
+    pub root: PathBuf,
+}
+
+impl FileSystemStorageConfig {
+    /// Create configuration from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        let root = std::env::var("OBJECT_STORAGE_DIR")
+            .map_err(|_| InfrastructureError::Config("OBJECT_STORAGE_DIR not set".to_string()))?;
+
+        Ok(Self { root: PathBuf::from(root) })
+    }
+}
+
+/// Object storage keeping objects as files
+pub struct FileSystemObjectStorage {
+    config: FileSystemStorageConfig,
+}
+
+impl FileSystemObjectStorage {
+    /// Create a new file system object storage
+    pub fn new(config: FileSystemStorageConfig) -> Self {
+        Self { config }
+    }
+
+    /// Create a file system object storage from environment variables
+    pub fn from_env() -> Result<Self, InfrastructureError> {
+        Ok(Self::new(FileSystemStorageConfig::from_env()?))
+    }
+
+    /// Path of the file holding an object
+    ///
+    /// Keys are relative paths; keys that would leave the storage
+    /// directory are rejected.
+    fn path(&self, key: &str) -> Result<PathBuf, String> {
+        let relative = Path::new(key);
+        let is_contained = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if key.is_empty() || !is_contained {
+            return Err(format!("Invalid object key: {}", key));
+        }
+
+        Ok(self.config.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl ObjectStorageTrait for FileSystemObjectStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), String> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        // Write to a temporary file first so readers never see a partial object
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, bytes)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|e| format!("Failed to move {}: {}", path.display(), e))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String> {
+        let path = self.path(key)?;
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,13 @@
+//! Object Storage Module
+//!
+//! This module provides adapters to the storage holding files the platform
+//! produces, such as finished exports.
+//!
+//! ## Features
+//!
+//! - **File System Storage**: Objects stored as files below a directory, shared between API instances through a mounted volume
+
+pub mod filesystem;
+
+// Re-export commonly used types
+pub use filesystem::{FileSystemObjectStorage, FileSystemStorageConfig};
@@ -90,8 +90,6 @@ ALTER TABLE refresh_tokens
     MODIFY COLUMN updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT 'Last modification timestamp';
 
 -- Create stored procedure for token cleanup (optional, for maintenance)
-DELIMITER $$
-
 CREATE PROCEDURE IF NOT EXISTS cleanup_expired_refresh_tokens()
 BEGIN
     -- Delete expired tokens older than 7 days (grace period after expiry)
@@ -102,9 +100,7 @@ BEGIN
     DELETE FROM refresh_tokens 
     WHERE is_revoked = TRUE 
     AND revoked_at < DATE_SUB(NOW(), INTERVAL 30 DAY);
-END$$
-
-DELIMITER ;
+END;
 
 -- ============================================================================
 -- DOWN MIGRATION
@@ -111,8 +111,6 @@ ALTER TABLE auth_audit_log
     MODIFY COLUMN archived_at TIMESTAMP NULL COMMENT 'Timestamp when record was archived';
 
 -- Create stored procedure for archiving old audit logs (90-day retention per Requirement 7.6)
-DELIMITER $$
-
 CREATE PROCEDURE IF NOT EXISTS archive_old_audit_logs()
 BEGIN
     DECLARE archived_count INT DEFAULT 0;
@@ -155,35 +153,7 @@ BEGIN
     
     -- Return the count of archived records
     SELECT archived_count AS records_archived;
-END$$
-
-DELIMITER ;
-
--- Create function to mask phone numbers consistently
-DELIMITER $$
-
-CREATE FUNCTION IF NOT EXISTS mask_phone_number(phone VARCHAR(50))
-RETURNS VARCHAR(20)
-DETERMINISTIC
-BEGIN
-    DECLARE digits VARCHAR(50);
-    DECLARE masked VARCHAR(20);
-    
-    -- Extract only digits
-    SET digits = REGEXP_REPLACE(phone, '[^0-9]', '');
-    
-    -- If less than or equal to 4 digits, mask all
-    IF LENGTH(digits) <= 4 THEN
-        SET masked = REPEAT('*', LENGTH(digits));
-    ELSE
-        -- Show only last 4 digits
-        SET masked = CONCAT('****', RIGHT(digits, 4));
-    END IF;
-    
-    RETURN masked;
-END$$
-
-DELIMITER ;
+END;
 
 -- Create event scheduler to run archival daily (optional, requires EVENT privilege)
 -- CREATE EVENT IF NOT EXISTS archive_audit_logs_daily
@@ -195,6 +165,5 @@ DELIMITER ;
 -- DOWN MIGRATION
 -- ============================================================================
 -- To rollback this migration, uncomment and run:
--- DROP FUNCTION IF EXISTS mask_phone_number;
 -- DROP PROCEDURE IF EXISTS archive_old_audit_logs;
 -- DROP TABLE IF EXISTS auth_audit_log;
\ No newline at end of file
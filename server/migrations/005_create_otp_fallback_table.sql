@@ -38,7 +38,6 @@ CREATE TABLE IF NOT EXISTS otp_fallback (
 COMMENT='Fallback storage for encrypted OTP codes when Redis is unavailable';
 
 -- Create event to automatically clean up expired OTPs
-DELIMITER $$
 CREATE EVENT IF NOT EXISTS cleanup_expired_otps
 ON SCHEDULE EVERY 1 HOUR
 COMMENT 'Clean up expired OTP records from fallback table'
@@ -47,11 +46,10 @@ BEGIN
     DELETE FROM otp_fallback 
     WHERE expires_at < NOW() 
     LIMIT 1000;
-END$$
-DELIMITER ;
+END;
 
--- Enable the event scheduler if not already enabled
-SET GLOBAL event_scheduler = ON;
+-- The event scheduler is on by default in MySQL 8.0; turning it on takes a
+-- global privilege the application user does not have, so it is left to the server.
 
 -- Add table statistics
 ALTER TABLE otp_fallback
@@ -0,0 +1,79 @@
+-- Migration: 006_create_scheduled_messages_table
+-- Description: Create scheduled_messages and notification_preferences tables for reminder automations
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create scheduled_messages table for templated reminders delivered by the message scheduler
+CREATE TABLE IF NOT EXISTS scheduled_messages (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Recipient
+    user_id CHAR(36) NOT NULL,
+    phone VARCHAR(20) NOT NULL,
+
+    -- Message content
+    template VARCHAR(50) NOT NULL,
+    params JSON NOT NULL,
+
+    -- Scheduling (deliver_at is requested_for moved out of quiet hours)
+    requested_for TIMESTAMP NOT NULL,
+    deliver_at TIMESTAMP NOT NULL,
+
+    -- Delivery state
+    status ENUM('pending', 'sent', 'failed', 'cancelled') NOT NULL DEFAULT 'pending',
+    attempts INT UNSIGNED NOT NULL DEFAULT 0,
+    last_error VARCHAR(500) NULL,
+    provider_message_id VARCHAR(255) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_scheduled_messages_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Due-message lookups scan pending rows by delivery time
+CREATE INDEX idx_scheduled_messages_status_deliver_at ON scheduled_messages(status, deliver_at);
+CREATE INDEX idx_scheduled_messages_user_id ON scheduled_messages(user_id);
+
+ALTER TABLE scheduled_messages COMMENT = 'Templated reminder messages awaiting or past delivery';
+
+-- Create notification_preferences table for per-user timezone and quiet hours
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id CHAR(36) NOT NULL,
+
+    -- Local timezone as an offset from UTC in minutes (e.g. 600 for UTC+10)
+    utc_offset_minutes INT NOT NULL DEFAULT 0,
+
+    -- Quiet hours in the user's local time; NULL disables quiet hours
+    quiet_hours_start TIME NULL,
+    quiet_hours_end TIME NULL,
+
+    reminders_enabled BOOLEAN NOT NULL DEFAULT TRUE,
+
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_notification_preferences_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE notification_preferences COMMENT = 'Per-user notification delivery preferences';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS notification_preferences;
+-- DROP TABLE IF EXISTS scheduled_messages;
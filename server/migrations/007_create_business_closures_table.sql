@@ -0,0 +1,40 @@
+-- Migration: 007_create_business_closures_table
+-- Description: Create business_closures table for ad-hoc market closure dates
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create business_closures table; public holidays stay in configuration
+CREATE TABLE IF NOT EXISTS business_closures (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Market code (ISO 3166-1 alpha-2) and local closure date
+    market CHAR(2) NOT NULL,
+    closure_date DATE NOT NULL,
+
+    reason VARCHAR(255) NOT NULL,
+
+    -- Administrator who added the closure
+    created_by CHAR(36) NULL,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_business_closures_market_date (market, closure_date),
+    CONSTRAINT fk_business_closures_created_by
+        FOREIGN KEY (created_by) REFERENCES users(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE business_closures COMMENT = 'Ad-hoc non-business dates per market';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS business_closures;
@@ -0,0 +1,40 @@
+-- Migration: 008_create_worker_service_areas_table
+-- Description: Create worker_service_areas table for polygon-based worker coverage
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create worker_service_areas table; polygons use SRID 4326 (WGS 84)
+CREATE TABLE IF NOT EXISTS worker_service_areas (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Worker the area belongs to
+    worker_id CHAR(36) NOT NULL,
+    name VARCHAR(100) NULL,
+
+    -- Validated, simplified polygon without holes
+    area POLYGON NOT NULL SRID 4326,
+
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    SPATIAL INDEX idx_worker_service_areas_area (area),
+    CONSTRAINT fk_worker_service_areas_worker_id
+        FOREIGN KEY (worker_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_worker_service_areas_worker_id ON worker_service_areas(worker_id);
+
+ALTER TABLE worker_service_areas COMMENT = 'Polygons describing where each worker accepts jobs';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS worker_service_areas;
@@ -0,0 +1,94 @@
+-- Migration: 009_create_campaigns_tables
+-- Description: Create campaigns and campaign_deliveries tables for admin notification campaigns
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create campaigns table for bulk notifications defined by administrators
+CREATE TABLE IF NOT EXISTS campaigns (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    name VARCHAR(255) NOT NULL,
+
+    -- Audience filters and localized content, keyed by language code
+    audience JSON NOT NULL,
+    content JSON NOT NULL,
+    default_language VARCHAR(10) NOT NULL,
+
+    -- Scheduling and lifecycle
+    scheduled_at TIMESTAMP NOT NULL,
+    status ENUM('scheduled', 'sending', 'completed', 'cancelled') NOT NULL DEFAULT 'scheduled',
+    audience_size BIGINT UNSIGNED NOT NULL DEFAULT 0,
+
+    -- Administrator who created the campaign
+    created_by CHAR(36) NOT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- The dispatcher scans campaigns by status in schedule order
+CREATE INDEX idx_campaigns_status_scheduled_at ON campaigns(status, scheduled_at);
+CREATE INDEX idx_campaigns_created_at ON campaigns(created_at);
+
+ALTER TABLE campaigns COMMENT = 'Bulk notification campaigns created by administrators';
+
+-- Create campaign_deliveries table with one row per campaign recipient
+CREATE TABLE IF NOT EXISTS campaign_deliveries (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    campaign_id CHAR(36) NOT NULL,
+    user_id CHAR(36) NOT NULL,
+
+    -- Recipient market, used to pick the content language
+    country_code VARCHAR(10) NOT NULL,
+
+    -- Delivery state (next_attempt_at moves past quiet hours)
+    status ENUM('pending', 'sent', 'failed', 'opted_out') NOT NULL DEFAULT 'pending',
+    next_attempt_at TIMESTAMP NOT NULL,
+    error VARCHAR(500) NULL,
+    provider_message_id VARCHAR(255) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP NULL,
+    opened_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_campaign_deliveries_campaign_user (campaign_id, user_id),
+    CONSTRAINT fk_campaign_deliveries_campaign_id
+        FOREIGN KEY (campaign_id) REFERENCES campaigns(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_campaign_deliveries_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Pending-delivery lookups scan by campaign, status and attempt time
+CREATE INDEX idx_campaign_deliveries_pending ON campaign_deliveries(campaign_id, status, next_attempt_at);
+
+ALTER TABLE campaign_deliveries COMMENT = 'Per-recipient delivery state of notification campaigns';
+
+-- Allow users to opt out of marketing campaigns
+ALTER TABLE notification_preferences
+    ADD COLUMN marketing_enabled BOOLEAN NOT NULL DEFAULT TRUE AFTER reminders_enabled;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE notification_preferences DROP COLUMN marketing_enabled;
+-- DROP TABLE IF EXISTS campaign_deliveries;
+-- DROP TABLE IF EXISTS campaigns;
@@ -0,0 +1,98 @@
+-- Migration: 010_create_payment_reconciliation_tables
+-- Description: Create payment ledger and reconciliation report tables
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create payment_ledger_entries table recording money movements with providers
+CREATE TABLE IF NOT EXISTS payment_ledger_entries (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Provider and its identifier for the movement (e.g. a Stripe balance transaction)
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NOT NULL,
+
+    -- Signed amount in the currency's minor unit
+    kind ENUM('charge', 'refund', 'fee', 'payout', 'adjustment') NOT NULL,
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+    description VARCHAR(500) NULL,
+
+    -- Timestamps
+    occurred_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Reconciliation reads a provider's entries by period
+CREATE INDEX idx_payment_ledger_provider_occurred_at ON payment_ledger_entries(provider, occurred_at);
+-- Several entries may share a reference (e.g. a charge and its fee)
+CREATE INDEX idx_payment_ledger_provider_reference ON payment_ledger_entries(provider, provider_reference);
+
+ALTER TABLE payment_ledger_entries COMMENT = 'Internal ledger of money movements with payment providers';
+
+-- Create reconciliation_reports table with one row per reconciliation run
+CREATE TABLE IF NOT EXISTS reconciliation_reports (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    provider VARCHAR(32) NOT NULL,
+
+    -- Reconciled period, end exclusive
+    period_start TIMESTAMP NOT NULL,
+    period_end TIMESTAMP NOT NULL,
+
+    -- Record counts on each side and references that matched exactly
+    ledger_count INT UNSIGNED NOT NULL DEFAULT 0,
+    provider_count INT UNSIGNED NOT NULL DEFAULT 0,
+    matched_count INT UNSIGNED NOT NULL DEFAULT 0,
+
+    -- Total discrepancy per currency in minor units, e.g. {"AUD": 2500}
+    discrepancy_totals JSON NOT NULL,
+    alerted BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_reconciliation_reports_period_start ON reconciliation_reports(period_start);
+
+ALTER TABLE reconciliation_reports COMMENT = 'Outcome of each payment reconciliation run';
+
+-- Create reconciliation_discrepancies table with one row per unreconciled reference
+CREATE TABLE IF NOT EXISTS reconciliation_discrepancies (
+    report_id CHAR(36) NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+
+    kind ENUM('missing_in_ledger', 'missing_at_provider', 'amount_mismatch', 'currency_mismatch') NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Net amounts per side; NULL when the side has no record
+    ledger_amount BIGINT NULL,
+    provider_amount BIGINT NULL,
+
+    -- Constraints
+    PRIMARY KEY (report_id, reference),
+    CONSTRAINT fk_reconciliation_discrepancies_report_id
+        FOREIGN KEY (report_id) REFERENCES reconciliation_reports(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE reconciliation_discrepancies COMMENT = 'References that did not reconcile in a report';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS reconciliation_discrepancies;
+-- DROP TABLE IF EXISTS reconciliation_reports;
+-- DROP TABLE IF EXISTS payment_ledger_entries;
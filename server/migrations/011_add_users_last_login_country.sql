@@ -0,0 +1,19 @@
+-- Migration: 011_add_users_last_login_country
+-- Description: Record the country of each user's last login for geo-IP anomaly detection
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- ISO 3166-1 alpha-2 country resolved from the login IP address
+ALTER TABLE users
+    ADD COLUMN last_login_country CHAR(2) NULL
+        COMMENT 'Country of the IP address used for the most recent login'
+        AFTER last_login_at;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE users DROP COLUMN last_login_country;
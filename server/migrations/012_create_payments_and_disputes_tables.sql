@@ -0,0 +1,139 @@
+-- Migration: 012_create_payments_and_disputes_tables
+-- Description: Create payment, payout, dispute and provider webhook tables for refund and chargeback handling
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create payments table with one row per captured customer payment
+CREATE TABLE IF NOT EXISTS payments (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Provider and its identifier for the payment (e.g. a Stripe charge)
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NOT NULL,
+
+    -- Parties
+    customer_id CHAR(36) NOT NULL,
+    worker_id CHAR(36) NOT NULL,
+
+    -- Amounts in the currency's minor unit
+    amount BIGINT NOT NULL,
+    refunded_amount BIGINT NOT NULL DEFAULT 0,
+    currency CHAR(3) NOT NULL,
+    status ENUM('succeeded', 'partially_refunded', 'refunded', 'disputed') NOT NULL DEFAULT 'succeeded',
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_payments_provider_reference (provider, provider_reference),
+    CONSTRAINT fk_payments_customer_id
+        FOREIGN KEY (customer_id) REFERENCES users(id),
+    CONSTRAINT fk_payments_worker_id
+        FOREIGN KEY (worker_id) REFERENCES users(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE payments COMMENT = 'Customer payments captured through payment providers';
+
+-- Create payouts table with the worker earnings funded by each payment
+CREATE TABLE IF NOT EXISTS payouts (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    payment_id CHAR(36) NOT NULL,
+    worker_id CHAR(36) NOT NULL,
+
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+    status ENUM('pending', 'frozen', 'paid') NOT NULL DEFAULT 'pending',
+    frozen_reason VARCHAR(255) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_payouts_payment_id
+        FOREIGN KEY (payment_id) REFERENCES payments(id)
+        ON DELETE CASCADE,
+    CONSTRAINT fk_payouts_worker_id
+        FOREIGN KEY (worker_id) REFERENCES users(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payouts_payment_id ON payouts(payment_id);
+CREATE INDEX idx_payouts_worker_status ON payouts(worker_id, status);
+
+ALTER TABLE payouts COMMENT = 'Worker payouts, frozen while refunds or chargebacks are reviewed';
+
+-- Create payment_disputes table with one row per chargeback
+CREATE TABLE IF NOT EXISTS payment_disputes (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    payment_id CHAR(36) NOT NULL,
+    provider VARCHAR(32) NOT NULL,
+    provider_dispute_id VARCHAR(255) NOT NULL,
+
+    reason VARCHAR(100) NOT NULL,
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+    status ENUM('open', 'won', 'lost') NOT NULL DEFAULT 'open',
+
+    -- Timestamps
+    opened_at TIMESTAMP NOT NULL,
+    resolved_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_payment_disputes_provider_dispute (provider, provider_dispute_id),
+    CONSTRAINT fk_payment_disputes_payment_id
+        FOREIGN KEY (payment_id) REFERENCES payments(id)
+        ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payment_disputes_payment_id ON payment_disputes(payment_id);
+
+ALTER TABLE payment_disputes COMMENT = 'Chargebacks opened against payments';
+
+-- Create payment_webhook_events table keeping every verified provider webhook
+CREATE TABLE IF NOT EXISTS payment_webhook_events (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    provider VARCHAR(32) NOT NULL,
+    event_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+
+    -- Full payload as sent by the provider, kept for audit
+    payload JSON NOT NULL,
+
+    -- How the event was handled; NULL until processing succeeded
+    outcome VARCHAR(50) NULL,
+
+    -- Timestamps
+    received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    processed_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_payment_webhook_events_event (provider, event_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payment_webhook_events_received_at ON payment_webhook_events(received_at);
+
+ALTER TABLE payment_webhook_events COMMENT = 'Verified payment provider webhooks with their full payloads';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS payment_webhook_events;
+-- DROP TABLE IF EXISTS payment_disputes;
+-- DROP TABLE IF EXISTS payouts;
+-- DROP TABLE IF EXISTS payments;
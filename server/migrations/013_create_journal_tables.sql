@@ -0,0 +1,68 @@
+-- Migration: 013_create_journal_tables
+-- Description: Create double-entry journal tables for the platform's books
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create journal_entries table with one row per recorded business event
+CREATE TABLE IF NOT EXISTS journal_entries (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Business event and its identifier (e.g. a payment ID or refund reference)
+    transaction_type ENUM(
+        'payment', 'tip', 'fee', 'credit_issued', 'credit_redeemed',
+        'payout', 'refund', 'chargeback', 'chargeback_reversal'
+    ) NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    description VARCHAR(500) NULL,
+
+    -- Timestamps
+    occurred_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_journal_entries_event (transaction_type, reference)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_journal_entries_occurred_at ON journal_entries(occurred_at);
+
+ALTER TABLE journal_entries COMMENT = 'Double-entry journal entries, each balanced per currency';
+
+-- Create journal_lines table with the debits and credits of each entry
+CREATE TABLE IF NOT EXISTS journal_lines (
+    entry_id CHAR(36) NOT NULL,
+    line_no SMALLINT UNSIGNED NOT NULL,
+
+    -- Account: type, owning worker or customer (NULL for platform accounts) and currency
+    account_type ENUM(
+        'provider_cash', 'worker_payable', 'customer_credit',
+        'platform_revenue', 'processing_fees', 'customer_incentives'
+    ) NOT NULL,
+    owner_id CHAR(36) NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Positive amount in the currency's minor unit
+    side ENUM('debit', 'credit') NOT NULL,
+    amount BIGINT NOT NULL,
+
+    -- Constraints
+    PRIMARY KEY (entry_id, line_no),
+    CONSTRAINT fk_journal_lines_entry_id
+        FOREIGN KEY (entry_id) REFERENCES journal_entries(id) ON DELETE CASCADE,
+    CONSTRAINT chk_journal_lines_amount CHECK (amount > 0)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_journal_lines_account ON journal_lines(account_type, owner_id, currency);
+
+ALTER TABLE journal_lines COMMENT = 'Debit and credit lines of journal entries';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS journal_lines;
+-- DROP TABLE IF EXISTS journal_entries;
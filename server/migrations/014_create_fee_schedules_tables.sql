@@ -0,0 +1,78 @@
+-- Migration: 014_create_fee_schedules_tables
+-- Description: Create versioned platform fee schedules and the fees fixed on orders
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create fee_schedules table; rows are never updated, a change is a new version
+CREATE TABLE IF NOT EXISTS fee_schedules (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Market code (ISO 3166-1 alpha-2) and job category, empty for the whole market
+    market CHAR(2) NOT NULL,
+    category VARCHAR(100) NOT NULL DEFAULT '',
+    version INT NOT NULL,
+
+    -- Commission in basis points and minimum fee in the currency's minor unit
+    commission_basis_points INT NOT NULL,
+    minimum_fee BIGINT NOT NULL DEFAULT 0,
+    currency CHAR(3) NOT NULL,
+
+    -- Administrator who created the schedule
+    created_by CHAR(36) NULL,
+
+    -- Timestamps
+    effective_from TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_fee_schedules_version (market, category, version),
+    CONSTRAINT chk_fee_schedules_commission CHECK (commission_basis_points BETWEEN 0 AND 10000),
+    CONSTRAINT chk_fee_schedules_minimum_fee CHECK (minimum_fee >= 0)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_fee_schedules_effective ON fee_schedules(market, category, effective_from);
+
+ALTER TABLE fee_schedules COMMENT = 'Versioned platform fee schedules per market and job category';
+
+-- Create order_fees table with the schedule applied when the quote was accepted
+CREATE TABLE IF NOT EXISTS order_fees (
+    order_id CHAR(36) NOT NULL,
+
+    -- Schedule in effect at acceptance, copied for audits
+    schedule_id CHAR(36) NOT NULL,
+    schedule_version INT NOT NULL,
+    market CHAR(2) NOT NULL,
+    category VARCHAR(100) NOT NULL DEFAULT '',
+    commission_basis_points INT NOT NULL,
+    minimum_fee BIGINT NOT NULL,
+
+    -- Amounts in the currency's minor unit
+    quote_amount BIGINT NOT NULL,
+    fee_amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Timestamps
+    accepted_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (order_id),
+    CONSTRAINT fk_order_fees_schedule_id
+        FOREIGN KEY (schedule_id) REFERENCES fee_schedules(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_order_fees_schedule_id ON order_fees(schedule_id);
+
+ALTER TABLE order_fees COMMENT = 'Platform fee fixed on each order at quote acceptance';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS order_fees;
+-- DROP TABLE IF EXISTS fee_schedules;
@@ -0,0 +1,90 @@
+-- Migration: 015_create_credit_wallet_tables
+-- Description: Create customer credit wallet tables and journal entries for expired credit
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Allow journal entries writing off expired store credit
+ALTER TABLE journal_entries
+    MODIFY COLUMN transaction_type ENUM(
+        'payment', 'tip', 'fee', 'credit_issued', 'credit_redeemed', 'credit_expired',
+        'payout', 'refund', 'chargeback', 'chargeback_reversal'
+    ) NOT NULL;
+
+-- Create credit_grants table with one row per amount of credit granted
+CREATE TABLE IF NOT EXISTS credit_grants (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Customer the credit belongs to
+    customer_id CHAR(36) NOT NULL,
+
+    -- Why the credit was granted and the identifier of the granting event
+    source ENUM('refund', 'promotion', 'goodwill') NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+
+    -- Granted and unspent amounts in the currency's minor unit
+    amount BIGINT NOT NULL,
+    remaining BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Timestamps
+    expires_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_credit_grants_reference (reference),
+    CONSTRAINT fk_credit_grants_customer_id
+        FOREIGN KEY (customer_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT chk_credit_grants_remaining CHECK (remaining BETWEEN 0 AND amount)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_credit_grants_spendable ON credit_grants(customer_id, currency, remaining);
+CREATE INDEX idx_credit_grants_expires_at ON credit_grants(expires_at);
+
+ALTER TABLE credit_grants COMMENT = 'Store credit granted to customers, spent soonest-expiring first';
+
+-- Create credit_transactions table with the history shown in each wallet
+CREATE TABLE IF NOT EXISTS credit_transactions (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    customer_id CHAR(36) NOT NULL,
+    kind ENUM('issued', 'redeemed', 'expired') NOT NULL,
+
+    -- Positive amount in the currency's minor unit
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Grant or order reference of the change
+    reference VARCHAR(255) NOT NULL,
+
+    -- Timestamps
+    occurred_at TIMESTAMP NOT NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_credit_transactions_event (kind, reference),
+    CONSTRAINT fk_credit_transactions_customer_id
+        FOREIGN KEY (customer_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_credit_transactions_customer ON credit_transactions(customer_id, occurred_at);
+
+ALTER TABLE credit_transactions COMMENT = 'Issued, redeemed and expired credit per customer wallet';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS credit_transactions;
+-- DROP TABLE IF EXISTS credit_grants;
+-- ALTER TABLE journal_entries
+--     MODIFY COLUMN transaction_type ENUM(
+--         'payment', 'tip', 'fee', 'credit_issued', 'credit_redeemed',
+--         'payout', 'refund', 'chargeback', 'chargeback_reversal'
+--     ) NOT NULL;
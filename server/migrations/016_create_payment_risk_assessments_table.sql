@@ -0,0 +1,50 @@
+-- Migration: 016_create_payment_risk_assessments_table
+-- Description: Create payment risk assessments used for card velocity and the manual review queue
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create payment_risk_assessments table with one row per payment attempt
+CREATE TABLE IF NOT EXISTS payment_risk_assessments (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Paying customer and the attempted payment
+    user_id CHAR(36) NOT NULL,
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+    market CHAR(2) NOT NULL,
+    billing_country CHAR(2) NULL,
+    card_fingerprint VARCHAR(255) NULL,
+
+    -- Outcome: triggered rule names and the most severe action
+    triggered_rules JSON NOT NULL,
+    action ENUM('allow', 'require_3ds', 'review', 'block') NOT NULL,
+
+    -- Manual review
+    review_status ENUM('not_required', 'pending', 'approved', 'rejected') NOT NULL,
+    reviewed_by CHAR(36) NULL,
+    reviewed_at TIMESTAMP NULL,
+    review_note VARCHAR(1000) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_payment_risk_assessments_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payment_risk_assessments_user_cards ON payment_risk_assessments(user_id, created_at, card_fingerprint);
+CREATE INDEX idx_payment_risk_assessments_review ON payment_risk_assessments(review_status, created_at);
+
+ALTER TABLE payment_risk_assessments COMMENT = 'Fraud rule outcomes of payment attempts and their manual reviews';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS payment_risk_assessments;
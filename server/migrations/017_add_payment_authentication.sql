@@ -0,0 +1,46 @@
+-- Migration: 017_add_payment_authentication
+-- Description: Track payment intents waiting for 3-D Secure and the status timeline of each payment
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Payments created through an intent keep its identifier; until the intent is
+-- captured the provider reference is the intent's identifier as well
+ALTER TABLE payments
+    ADD COLUMN intent_reference VARCHAR(255) NULL AFTER provider_reference,
+    MODIFY COLUMN status ENUM('requires_action', 'failed', 'succeeded', 'partially_refunded', 'refunded', 'disputed') NOT NULL DEFAULT 'succeeded',
+    ADD UNIQUE KEY uk_payments_intent_reference (provider, intent_reference);
+
+-- Create payment_timeline table with one row per payment status change
+CREATE TABLE IF NOT EXISTS payment_timeline (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    payment_id CHAR(36) NOT NULL,
+    status ENUM('requires_action', 'failed', 'succeeded', 'partially_refunded', 'refunded', 'disputed') NOT NULL,
+    note VARCHAR(1000) NULL,
+
+    -- When the change happened
+    occurred_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_payment_timeline_payment_id
+        FOREIGN KEY (payment_id) REFERENCES payments(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payment_timeline_payment ON payment_timeline(payment_id, occurred_at);
+
+ALTER TABLE payment_timeline COMMENT = 'Status changes of customer payments shown on the order timeline';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS payment_timeline;
+-- ALTER TABLE payments
+--     DROP INDEX uk_payments_intent_reference,
+--     DROP COLUMN intent_reference,
+--     MODIFY COLUMN status ENUM('succeeded', 'partially_refunded', 'refunded', 'disputed') NOT NULL DEFAULT 'succeeded';
@@ -0,0 +1,87 @@
+-- Migration: 018_create_oauth_tables
+-- Description: Create OAuth clients, user consents and authorization codes for partner integrations
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create oauth_clients table with one row per registered partner application
+CREATE TABLE IF NOT EXISTS oauth_clients (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Public identifier and SHA-256 hash of the secret
+    client_id VARCHAR(64) NOT NULL,
+    client_secret_hash CHAR(64) NOT NULL,
+
+    -- Registration: name, exact-match redirect URIs, scopes and grants as JSON arrays
+    name VARCHAR(255) NOT NULL,
+    redirect_uris JSON NOT NULL,
+    allowed_scopes JSON NOT NULL,
+    grant_types JSON NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_oauth_clients_client_id (client_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE oauth_clients COMMENT = 'Partner applications registered as OAuth2 clients';
+
+-- Create oauth_consents table with the scopes each user approved per client
+CREATE TABLE IF NOT EXISTS oauth_consents (
+    user_id CHAR(36) NOT NULL,
+    client_id VARCHAR(64) NOT NULL,
+    scopes JSON NOT NULL,
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (user_id, client_id),
+    CONSTRAINT fk_oauth_consents_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT fk_oauth_consents_client_id
+        FOREIGN KEY (client_id) REFERENCES oauth_clients(client_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE oauth_consents COMMENT = 'Scopes users approved for partner applications';
+
+-- Create oauth_authorization_codes table; codes are stored as SHA-256 hashes
+CREATE TABLE IF NOT EXISTS oauth_authorization_codes (
+    code_hash CHAR(64) NOT NULL,
+    client_id VARCHAR(64) NOT NULL,
+    user_id CHAR(36) NOT NULL,
+    redirect_uri VARCHAR(2048) NOT NULL,
+    scopes JSON NOT NULL,
+
+    -- PKCE S256 challenge, when the client sent one
+    code_challenge VARCHAR(128) NULL,
+
+    -- Lifetime and single use
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (code_hash),
+    CONSTRAINT fk_oauth_authorization_codes_client_id
+        FOREIGN KEY (client_id) REFERENCES oauth_clients(client_id) ON DELETE CASCADE,
+    CONSTRAINT fk_oauth_authorization_codes_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_oauth_authorization_codes_expires_at ON oauth_authorization_codes(expires_at);
+
+ALTER TABLE oauth_authorization_codes COMMENT = 'Single-use authorization codes of the OAuth2 code grant';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS oauth_authorization_codes;
+-- DROP TABLE IF EXISTS oauth_consents;
+-- DROP TABLE IF EXISTS oauth_clients;
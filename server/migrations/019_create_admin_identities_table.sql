@@ -0,0 +1,40 @@
+-- Migration: 019_create_admin_identities_table
+-- Description: Create admin_identities table for administrators signed in through OIDC single sign-on
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create admin_identities table with one row per identity provider subject
+CREATE TABLE IF NOT EXISTS admin_identities (
+    -- Primary key using UUID, used as the admin token subject
+    id CHAR(36) NOT NULL,
+
+    -- Identity provider issuer URL and subject identifier
+    issuer VARCHAR(512) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+
+    -- Profile reported by the provider at the last login
+    email VARCHAR(320) NULL,
+    name VARCHAR(255) NULL,
+
+    -- Roles mapped from the provider's groups at the last login, as a JSON array
+    roles JSON NOT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_login_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_admin_identities_issuer_subject (issuer(191), subject(191))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE admin_identities COMMENT = 'Administrators authenticated through the company identity provider';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS admin_identities;
@@ -0,0 +1,56 @@
+-- Migration: 020_create_saved_payment_methods_table
+-- Description: Create saved_payment_methods table and record the payment method each payment was charged to
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Payments keep the provider's identifier of the payment method charged, so a
+-- saved method cannot be deleted while one of its payments is held in escrow
+ALTER TABLE payments
+    ADD COLUMN payment_method VARCHAR(255) NULL AFTER intent_reference,
+    ADD INDEX idx_payments_customer_payment_method (customer_id, payment_method);
+
+-- Create saved_payment_methods table; cards are tokenized by the provider and
+-- only display details are stored, never card numbers
+CREATE TABLE IF NOT EXISTS saved_payment_methods (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    customer_id CHAR(36) NOT NULL,
+
+    -- Provider and its identifier for the payment method
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NOT NULL,
+
+    -- Display details
+    brand VARCHAR(32) NOT NULL,
+    last4 CHAR(4) NOT NULL,
+    exp_month TINYINT UNSIGNED NOT NULL,
+    exp_year SMALLINT NOT NULL,
+
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_saved_payment_methods_reference (customer_id, provider, provider_reference),
+    CONSTRAINT fk_saved_payment_methods_customer_id
+        FOREIGN KEY (customer_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_saved_payment_methods_customer ON saved_payment_methods(customer_id, created_at);
+
+ALTER TABLE saved_payment_methods COMMENT = 'Cards customers saved for future payments, tokenized by the payment provider';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS saved_payment_methods;
+-- ALTER TABLE payments
+--     DROP INDEX idx_payments_customer_payment_method,
+--     DROP COLUMN payment_method;
@@ -0,0 +1,56 @@
+-- Migration: 021_create_payout_methods_table
+-- Description: Create payout_methods table and record the provider transfer of each paid payout
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Payouts keep the provider's identifier of the transfer that paid them
+ALTER TABLE payouts
+    ADD COLUMN transfer_reference VARCHAR(255) NULL AFTER frozen_reason;
+
+-- Create payout_methods table; bank accounts are tokenized by the provider
+-- and must be verified before anything is paid out to them
+CREATE TABLE IF NOT EXISTS payout_methods (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    worker_id CHAR(36) NOT NULL,
+
+    -- Provider and its identifier for the bank account
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NOT NULL,
+
+    -- Display details
+    bank_name VARCHAR(128) NULL,
+    account_last4 CHAR(4) NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Verification by identity match or micro-deposits
+    status ENUM('pending_verification', 'awaiting_micro_deposits', 'verified', 'verification_failed')
+        NOT NULL DEFAULT 'pending_verification',
+    failed_attempts INT UNSIGNED NOT NULL DEFAULT 0,
+    verified_at TIMESTAMP NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_payout_methods_reference (provider, provider_reference),
+    CONSTRAINT fk_payout_methods_worker_id
+        FOREIGN KEY (worker_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_payout_methods_worker ON payout_methods(worker_id, created_at);
+
+ALTER TABLE payout_methods COMMENT = 'Bank accounts workers are paid out to, verified before the first payout';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS payout_methods;
+-- ALTER TABLE payouts DROP COLUMN transfer_reference;
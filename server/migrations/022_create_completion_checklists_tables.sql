@@ -0,0 +1,74 @@
+-- Migration: 022_create_completion_checklists_tables
+-- Description: Create checklist template and order completion tables with customer sign-off
+-- Date: 2026-10-16
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create checklist_templates table; templates are never edited, a change is
+-- a new version for the category
+CREATE TABLE IF NOT EXISTS checklist_templates (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Job category, NULL for the default template
+    category VARCHAR(64) NULL,
+    version INT NOT NULL,
+
+    -- Items in display order: [{key, label, min_photos}]
+    items JSON NOT NULL,
+
+    created_by CHAR(36) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_checklist_templates_version (category, version)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE checklist_templates COMMENT = 'Versioned completion checklists per job category';
+
+-- Create order_completion_checklists table; the row can be replaced until
+-- the customer signs off and is never changed afterwards
+CREATE TABLE IF NOT EXISTS order_completion_checklists (
+    order_id CHAR(36) NOT NULL,
+
+    worker_id CHAR(36) NOT NULL,
+    customer_id CHAR(36) NOT NULL,
+
+    -- Template version the checklist was filled in from
+    template_id CHAR(36) NOT NULL,
+    template_version INT NOT NULL,
+
+    -- Completed items: [{key, label, photos, note}]
+    items JSON NOT NULL,
+    submitted_at TIMESTAMP NOT NULL,
+
+    -- Customer sign-off: typed name, time, device and hash of the approved content
+    signed_name VARCHAR(100) NULL,
+    signed_at TIMESTAMP NULL,
+    device_info VARCHAR(255) NULL,
+    content_hash CHAR(64) NULL,
+
+    -- Constraints
+    PRIMARY KEY (order_id),
+    CONSTRAINT fk_completion_checklists_template_id
+        FOREIGN KEY (template_id) REFERENCES checklist_templates(id),
+    CONSTRAINT fk_completion_checklists_worker_id
+        FOREIGN KEY (worker_id) REFERENCES users(id),
+    CONSTRAINT fk_completion_checklists_customer_id
+        FOREIGN KEY (customer_id) REFERENCES users(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_completion_checklists_worker ON order_completion_checklists(worker_id, submitted_at);
+CREATE INDEX idx_completion_checklists_customer ON order_completion_checklists(customer_id, submitted_at);
+
+ALTER TABLE order_completion_checklists COMMENT = 'Worker completion checklists of orders with the immutable customer sign-off';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS order_completion_checklists;
+-- DROP TABLE IF EXISTS checklist_templates;
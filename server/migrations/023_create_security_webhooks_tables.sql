@@ -0,0 +1,72 @@
+-- Migration: 023_create_security_webhooks_tables
+-- Description: Create security_webhooks and security_webhook_deliveries tables for forwarding audit events
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create security_webhooks table for operator endpoints receiving security events
+CREATE TABLE IF NOT EXISTS security_webhooks (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    url VARCHAR(2048) NOT NULL,
+
+    -- HMAC-SHA256 signing secret; kept in plain text because it is needed to sign
+    secret VARCHAR(128) NOT NULL,
+
+    -- Subscribed event kinds, e.g. ["account_locked", "admin_action"]
+    events JSON NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+
+    -- Administrator who registered the webhook
+    created_by CHAR(36) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE security_webhooks COMMENT = 'Operator endpoints receiving signed security events';
+
+-- Create security_webhook_deliveries table with one row per event and endpoint
+CREATE TABLE IF NOT EXISTS security_webhook_deliveries (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    webhook_id CHAR(36) NOT NULL,
+    event_kind VARCHAR(50) NOT NULL,
+
+    -- JSON body posted to the endpoint
+    payload JSON NOT NULL,
+
+    -- Delivery state (next_attempt_at moves back exponentially after failures)
+    status ENUM('pending', 'delivered', 'failed') NOT NULL DEFAULT 'pending',
+    attempts INT UNSIGNED NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP NOT NULL,
+    last_error VARCHAR(500) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    delivered_at TIMESTAMP NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_security_webhook_deliveries_webhook_id
+        FOREIGN KEY (webhook_id) REFERENCES security_webhooks(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- The delivery worker scans by status and attempt time
+CREATE INDEX idx_security_webhook_deliveries_due ON security_webhook_deliveries(status, next_attempt_at);
+
+ALTER TABLE security_webhook_deliveries COMMENT = 'Queued and attempted deliveries of security events to webhooks';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS security_webhook_deliveries;
+-- DROP TABLE IF EXISTS security_webhooks;
@@ -0,0 +1,18 @@
+-- Migration: 024_add_scheduled_messages_category
+-- Description: Record the notification category of scheduled messages for quiet hours enforcement
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Critical categories (otp, security_alert) bypass quiet hours; marketing and
+-- digest messages follow the marketing opt-in, reminders the reminder opt-in
+ALTER TABLE scheduled_messages
+    ADD COLUMN category VARCHAR(32) NOT NULL DEFAULT 'reminder' AFTER template;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE scheduled_messages DROP COLUMN category;
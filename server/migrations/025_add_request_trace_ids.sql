@@ -0,0 +1,54 @@
+-- Migration: 025_add_request_trace_ids
+-- Description: Record request trace IDs on audit rows and log SMS handed to providers
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Trace ID of the API request that produced each audit event
+ALTER TABLE auth_audit_log
+    ADD COLUMN trace_id VARCHAR(64) NULL COMMENT 'Trace ID of the originating API request';
+
+CREATE INDEX idx_auth_audit_log_trace_id ON auth_audit_log(trace_id);
+
+-- Create sms_delivery_logs table linking provider message IDs to request
+-- trace IDs, so a delivery receipt can be followed back to its request
+CREATE TABLE IF NOT EXISTS sms_delivery_logs (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Trace ID of the API request that sent the message
+    trace_id VARCHAR(64) NULL,
+
+    -- Provider and its identifier for the message
+    provider VARCHAR(32) NOT NULL,
+    provider_message_id VARCHAR(255) NULL,
+
+    -- Recipient, masked to the last 4 digits
+    phone_masked VARCHAR(20) NOT NULL,
+
+    -- Delivery state, updated from provider receipts
+    status ENUM('sent', 'delivered', 'undelivered', 'failed') NOT NULL,
+    error VARCHAR(500) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_sms_delivery_logs_trace_id ON sms_delivery_logs(trace_id);
+CREATE INDEX idx_sms_delivery_logs_provider_message ON sms_delivery_logs(provider, provider_message_id);
+
+ALTER TABLE sms_delivery_logs COMMENT = 'SMS handed to providers, keyed by request trace ID and provider message ID';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS sms_delivery_logs;
+-- DROP INDEX idx_auth_audit_log_trace_id ON auth_audit_log;
+-- ALTER TABLE auth_audit_log DROP COLUMN trace_id;
@@ -0,0 +1,45 @@
+-- Migration: 026_create_user_attributions_table
+-- Description: Create user_attributions table for marketing attribution of consenting users
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create user_attributions table with the campaign each consenting user registered through
+CREATE TABLE IF NOT EXISTS user_attributions (
+    -- One row per user
+    user_id CHAR(36) NOT NULL,
+
+    -- Normalized UTM parameters; source, medium and campaign are lowercased
+    utm_source VARCHAR(100) NULL,
+    utm_medium VARCHAR(100) NULL,
+    utm_campaign VARCHAR(100) NULL,
+    utm_term VARCHAR(100) NULL,
+    utm_content VARCHAR(100) NULL,
+
+    -- Host of the referring page only; full URLs may carry personal data
+    referrer VARCHAR(100) NULL,
+
+    -- Timestamps
+    registered_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_user_attributions_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Cohort reports scan registrations by time
+CREATE INDEX idx_user_attributions_registered_at ON user_attributions(registered_at);
+
+ALTER TABLE user_attributions COMMENT = 'Campaign attribution of users who consented to marketing at registration';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS user_attributions;
@@ -0,0 +1,159 @@
+-- Migration: 027_create_organization_billing_tables
+-- Description: Create organizations, their members, payment methods, charges and consolidated invoices
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create organizations table for company accounts
+CREATE TABLE IF NOT EXISTS organizations (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    name VARCHAR(100) NOT NULL,
+    billing_email VARCHAR(255) NOT NULL,
+
+    -- ISO 4217 currency every charge and invoice is in
+    currency CHAR(3) NOT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Create organization_members table; a user belongs to at most one organization
+CREATE TABLE IF NOT EXISTS organization_members (
+    user_id CHAR(36) NOT NULL,
+    organization_id CHAR(36) NOT NULL,
+
+    role ENUM('admin', 'member') NOT NULL,
+
+    -- Most the member may accept per calendar month in minor units, NULL for no limit
+    monthly_spend_limit BIGINT NULL,
+
+    -- Timestamps
+    added_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (user_id),
+    CONSTRAINT fk_organization_members_organization_id
+        FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    CONSTRAINT fk_organization_members_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT chk_organization_members_limit CHECK (monthly_spend_limit IS NULL OR monthly_spend_limit >= 0)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_organization_members_organization ON organization_members(organization_id, added_at);
+
+-- Create organization_payment_methods table; cards are tokenized by the
+-- provider and only display details are stored, never card numbers
+CREATE TABLE IF NOT EXISTS organization_payment_methods (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    organization_id CHAR(36) NOT NULL,
+
+    -- Provider and its identifier for the payment method
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NOT NULL,
+
+    -- Display details
+    brand VARCHAR(32) NOT NULL,
+    last4 CHAR(4) NOT NULL,
+    exp_month TINYINT UNSIGNED NOT NULL,
+    exp_year SMALLINT NOT NULL,
+
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Organization admin who added the method
+    added_by CHAR(36) NOT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_organization_payment_methods_reference (organization_id, provider, provider_reference),
+    CONSTRAINT fk_organization_payment_methods_organization_id
+        FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Create organization_invoices table; the lines of an invoice are the
+-- charges linked to it
+CREATE TABLE IF NOT EXISTS organization_invoices (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    organization_id CHAR(36) NOT NULL,
+
+    -- First day of the invoiced month
+    period_start DATE NOT NULL,
+
+    currency CHAR(3) NOT NULL,
+    total_amount BIGINT NOT NULL,
+
+    -- Payment method charged, NULL if the organization had none
+    payment_method_id CHAR(36) NULL,
+
+    -- Timestamps
+    issued_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_organization_invoices_period (organization_id, period_start),
+    CONSTRAINT fk_organization_invoices_organization_id
+        FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Create organization_charges table with the quotes members accepted
+CREATE TABLE IF NOT EXISTS organization_charges (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    organization_id CHAR(36) NOT NULL,
+    user_id CHAR(36) NOT NULL,
+
+    -- Each order is charged at most once
+    order_id CHAR(36) NOT NULL,
+
+    amount BIGINT NOT NULL,
+    currency CHAR(3) NOT NULL,
+
+    -- Acceptance counts towards the member's limit for that month
+    accepted_at TIMESTAMP NOT NULL,
+    completed_at TIMESTAMP NULL,
+
+    -- Invoice the charge was collected on
+    invoice_id CHAR(36) NULL,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_organization_charges_order (order_id),
+    CONSTRAINT fk_organization_charges_organization_id
+        FOREIGN KEY (organization_id) REFERENCES organizations(id) ON DELETE CASCADE,
+    CONSTRAINT fk_organization_charges_invoice_id
+        FOREIGN KEY (invoice_id) REFERENCES organization_invoices(id),
+    CONSTRAINT chk_organization_charges_amount CHECK (amount > 0)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Spend limits sum a member's charges of a month
+CREATE INDEX idx_organization_charges_user_accepted ON organization_charges(user_id, accepted_at);
+-- Invoicing looks for completed charges not yet invoiced
+CREATE INDEX idx_organization_charges_uninvoiced ON organization_charges(organization_id, invoice_id, completed_at);
+
+ALTER TABLE organizations COMMENT = 'Company accounts billed for their members'' jobs';
+ALTER TABLE organization_charges COMMENT = 'Quotes accepted by organization members, charged to the organization';
+ALTER TABLE organization_invoices COMMENT = 'Monthly consolidated invoices of organizations';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS organization_charges;
+-- DROP TABLE IF EXISTS organization_invoices;
+-- DROP TABLE IF EXISTS organization_payment_methods;
+-- DROP TABLE IF EXISTS organization_members;
+-- DROP TABLE IF EXISTS organizations;
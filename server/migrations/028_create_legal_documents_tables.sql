@@ -0,0 +1,71 @@
+-- Migration: 028_create_legal_documents_tables
+-- Description: Create versioned legal documents and users' acceptances of them
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create legal_documents table with one row per translation of a version;
+-- rows are never updated, a change is a new version
+CREATE TABLE IF NOT EXISTS legal_documents (
+    -- Primary key using UUID; identifies the exact text served
+    id CHAR(36) NOT NULL,
+
+    -- Document kind, and market code (ISO 3166-1 alpha-2), empty for every market
+    kind ENUM('terms', 'privacy') NOT NULL,
+    market VARCHAR(2) NOT NULL DEFAULT '',
+    version INT NOT NULL,
+
+    -- ISO 639-1 language code and Markdown text
+    language VARCHAR(3) NOT NULL,
+    title VARCHAR(200) NOT NULL,
+    body MEDIUMTEXT NOT NULL,
+
+    -- Administrator who published the version
+    created_by CHAR(36) NULL,
+
+    -- Timestamps
+    effective_from TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_legal_documents_translation (kind, market, version, language)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_legal_documents_effective ON legal_documents(kind, market, effective_from);
+
+ALTER TABLE legal_documents COMMENT = 'Versioned terms of service and privacy policies per market and language';
+
+-- Create legal_acceptances table with the exact version each user accepted
+CREATE TABLE IF NOT EXISTS legal_acceptances (
+    user_id CHAR(36) NOT NULL,
+
+    -- Accepted version, and the translation the user was shown
+    kind ENUM('terms', 'privacy') NOT NULL,
+    market VARCHAR(2) NOT NULL DEFAULT '',
+    version INT NOT NULL,
+    document_id CHAR(36) NOT NULL,
+
+    -- Timestamps
+    accepted_at TIMESTAMP NOT NULL,
+
+    -- Constraints
+    PRIMARY KEY (user_id, kind, market, version),
+    CONSTRAINT fk_legal_acceptances_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    CONSTRAINT fk_legal_acceptances_document_id
+        FOREIGN KEY (document_id) REFERENCES legal_documents(id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE legal_acceptances COMMENT = 'Legal document versions accepted by users';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS legal_acceptances;
+-- DROP TABLE IF EXISTS legal_documents;
@@ -0,0 +1,34 @@
+-- Migration: 029_create_user_import_checkpoints_table
+-- Description: Track the progress of legacy user imports so they can resume after a failure
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create user_import_checkpoints table with one row per export imported
+CREATE TABLE IF NOT EXISTS user_import_checkpoints (
+    -- SHA-256 of the export file as lower-case hex
+    import_id CHAR(64) NOT NULL,
+
+    -- Records handled so far in export order, and what became of them
+    records_processed BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    imported BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    skipped BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    completed BOOLEAN NOT NULL DEFAULT FALSE,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL,
+
+    -- Constraints
+    PRIMARY KEY (import_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE user_import_checkpoints COMMENT = 'Progress of user imports from the legacy system, saved after every batch';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS user_import_checkpoints;
@@ -0,0 +1,24 @@
+-- Migration: 030_add_users_phone_hash_version
+-- Description: Record the pepper version of each user's phone hash for key rotation
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Position of the pepper in the keyring the hash was computed with; 0 marks
+-- a bare SHA-256 hash. Rows hashed with PHONE_HASH_PEPPER before versioning
+-- are version 1: deployments that had a pepper set should run
+--   UPDATE users SET phone_hash_version = 1;
+-- once after this migration, so the rehash job can bring them forward.
+ALTER TABLE users
+    ADD COLUMN phone_hash_version SMALLINT UNSIGNED NOT NULL DEFAULT 0
+        COMMENT 'Pepper version of phone_hash; 0 for bare SHA-256'
+        AFTER phone_hash,
+    ADD INDEX idx_users_phone_hash_version (phone_hash_version);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE users DROP INDEX idx_users_phone_hash_version, DROP COLUMN phone_hash_version;
@@ -0,0 +1,51 @@
+-- Migration: 031_create_user_compliance_checks_table
+-- Description: Track users' progress on the compliance checks their market requires
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create user_compliance_checks table with one row per user and requirement;
+-- document numbers are never stored beyond their last four characters
+CREATE TABLE IF NOT EXISTS user_compliance_checks (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    user_id CHAR(36) NOT NULL,
+
+    -- Requirement and the market that required it, as a country calling code
+    requirement ENUM('real_name', 'icp_filing') NOT NULL,
+    market VARCHAR(10) NOT NULL,
+
+    -- Provider and its identifier for the latest submission
+    provider VARCHAR(32) NOT NULL,
+    provider_reference VARCHAR(255) NULL,
+    document_last4 VARCHAR(4) NULL,
+
+    -- Progress on the requirement
+    status ENUM('required', 'pending', 'verified', 'rejected') NOT NULL DEFAULT 'required',
+    rejection_reason VARCHAR(255) NULL,
+    attempts INT UNSIGNED NOT NULL DEFAULT 0,
+    verified_at TIMESTAMP NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    UNIQUE KEY uk_user_compliance_checks_requirement (user_id, requirement),
+    CONSTRAINT fk_user_compliance_checks_user_id
+        FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE INDEX idx_user_compliance_checks_reference ON user_compliance_checks(provider, provider_reference);
+
+ALTER TABLE user_compliance_checks COMMENT = 'Market compliance checks such as real-name verification, gating orders and payouts';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS user_compliance_checks;
@@ -0,0 +1,22 @@
+-- Migration: 032_add_users_deleted_at
+-- Description: Soft-delete users so account deletion can be reversed during the grace period
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Deleted users keep their row, and their phone number, until purged;
+-- repository lookups skip rows with deleted_at set
+ALTER TABLE users
+    ADD COLUMN deleted_at TIMESTAMP NULL
+        COMMENT 'When the account was deleted; NULL for active accounts'
+        AFTER is_blocked,
+    ADD INDEX idx_users_deleted_at (deleted_at);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DELETE FROM users WHERE deleted_at IS NOT NULL;
+-- ALTER TABLE users DROP INDEX idx_users_deleted_at, DROP COLUMN deleted_at;
@@ -0,0 +1,54 @@
+-- Migration: 033_create_export_jobs_table
+-- Description: Queue long-running administrator exports and track their progress
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create export_jobs table; the exported files themselves are kept in object storage
+CREATE TABLE IF NOT EXISTS export_jobs (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Data being exported and the administrator who requested it
+    kind ENUM('audit_logs', 'attribution_cohorts') NOT NULL,
+    requested_by CHAR(36) NOT NULL,
+
+    -- Exported time range, fixed when the job is created
+    range_from TIMESTAMP NULL,
+    range_to TIMESTAMP NOT NULL,
+
+    -- Progress of the job
+    status ENUM('queued', 'running', 'completed', 'failed') NOT NULL DEFAULT 'queued',
+    rows_exported BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    total_rows BIGINT UNSIGNED NULL,
+    attempts INT UNSIGNED NOT NULL DEFAULT 0,
+
+    -- Result of the job
+    object_key VARCHAR(255) NULL,
+    error TEXT NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMP NULL,
+    finished_at TIMESTAMP NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    CONSTRAINT fk_export_jobs_requested_by
+        FOREIGN KEY (requested_by) REFERENCES users(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Workers poll queued jobs oldest first and look for stale running jobs
+CREATE INDEX idx_export_jobs_status ON export_jobs(status, created_at);
+CREATE INDEX idx_export_jobs_status_updated ON export_jobs(status, updated_at);
+
+ALTER TABLE export_jobs COMMENT = 'Background exports requested by administrators, downloaded through signed links';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS export_jobs;
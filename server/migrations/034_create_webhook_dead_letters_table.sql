@@ -0,0 +1,58 @@
+-- Migration: 034_create_webhook_dead_letters_table
+-- Description: Keep webhooks that failed to be handled so administrators can inspect and replay them
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create webhook_dead_letters table for received and delivered webhooks alike
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    -- Primary key using UUID
+    id CHAR(36) NOT NULL,
+
+    -- Origin of the webhook: provider name for received webhooks (e.g. 'stripe'),
+    -- kind of delivery for sent webhooks (e.g. 'security_webhook')
+    direction ENUM('inbound', 'outbound') NOT NULL,
+    source VARCHAR(50) NOT NULL,
+
+    -- Provider event ID for received webhooks, delivery ID for sent webhooks
+    event_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+
+    -- Endpoint a sent webhook was addressed to
+    target_id CHAR(36) NULL,
+
+    -- The full payload and the most recent failure
+    payload JSON NOT NULL,
+    error TEXT NOT NULL,
+    failures INT UNSIGNED NOT NULL DEFAULT 1,
+
+    -- Replay state
+    status ENUM('pending', 'replaying', 'resolved') NOT NULL DEFAULT 'pending',
+    replays INT UNSIGNED NOT NULL DEFAULT 0,
+    replayed_by CHAR(36) NULL,
+    replayed_at TIMESTAMP NULL,
+    resolution VARCHAR(255) NULL,
+
+    -- Timestamps
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id),
+    -- A webhook failing again keeps its dead letter
+    UNIQUE KEY uk_webhook_dead_letters_event (direction, source, event_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Administrators list and bulk-replay dead letters by state and time of failure
+CREATE INDEX idx_webhook_dead_letters_status_created ON webhook_dead_letters(status, created_at);
+CREATE INDEX idx_webhook_dead_letters_created ON webhook_dead_letters(created_at);
+
+ALTER TABLE webhook_dead_letters COMMENT = 'Received and delivered webhooks that failed, kept for inspection and replay';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS webhook_dead_letters;
@@ -0,0 +1,46 @@
+-- Migration: 035_partition_auth_audit_log_by_month
+-- Description: Partition auth_audit_log by month so expired months can be dropped instead of deleted row by row
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- MySQL does not partition tables that have foreign keys, and every unique key
+-- must include the partitioning column. Audit rows already outlive the users
+-- they mention (user_id is kept for pre-auth events too), so the foreign key is
+-- dropped rather than kept in sync.
+ALTER TABLE auth_audit_log DROP FOREIGN KEY fk_auth_audit_log_user_id;
+
+ALTER TABLE auth_audit_log
+    DROP PRIMARY KEY,
+    ADD PRIMARY KEY (id, created_at);
+
+-- One partition per calendar month (UTC), bounded by the Unix timestamp of the
+-- first second of the next month. Rows written before partitioning land in
+-- p_before_202610. pmax catches rows beyond the last month; the audit retention
+-- service splits new months off it ahead of time and drops months that have
+-- passed the retention period.
+ALTER TABLE auth_audit_log
+PARTITION BY RANGE (UNIX_TIMESTAMP(created_at)) (
+    PARTITION p_before_202610 VALUES LESS THAN (1790812800), -- 2026-10-01
+    PARTITION p202610 VALUES LESS THAN (1793491200),         -- 2026-11-01
+    PARTITION p202611 VALUES LESS THAN (1796083200),         -- 2026-12-01
+    PARTITION p202612 VALUES LESS THAN (1798761600),         -- 2027-01-01
+    PARTITION p202701 VALUES LESS THAN (1801440000),         -- 2027-02-01
+    PARTITION pmax VALUES LESS THAN MAXVALUE
+);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE auth_audit_log REMOVE PARTITIONING;
+-- ALTER TABLE auth_audit_log
+--     DROP PRIMARY KEY,
+--     ADD PRIMARY KEY (id);
+-- ALTER TABLE auth_audit_log
+--     ADD CONSTRAINT fk_auth_audit_log_user_id
+--         FOREIGN KEY (user_id) REFERENCES users(id)
+--         ON DELETE SET NULL
+--         ON UPDATE CASCADE;
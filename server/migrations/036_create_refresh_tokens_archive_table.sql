@@ -0,0 +1,63 @@
+-- Migration: 036_create_refresh_tokens_archive_table
+-- Description: Keep expired refresh tokens removed by cleanup for security investigations
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create refresh_tokens_archive table with the columns of refresh_tokens.
+-- There is no foreign key to users so the history outlives deleted accounts.
+CREATE TABLE IF NOT EXISTS refresh_tokens_archive (
+    -- Primary key, the ID the token had in refresh_tokens
+    id CHAR(36) NOT NULL,
+
+    -- Owner and hash of the token
+    user_id CHAR(36) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL,
+
+    -- Token lifecycle
+    created_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    last_used_at TIMESTAMP NULL,
+    is_revoked BOOLEAN NOT NULL,
+    revoked_at TIMESTAMP NULL,
+    revoke_reason VARCHAR(255) NULL,
+
+    -- Device/client information
+    device_id VARCHAR(255) NULL,
+    device_name VARCHAR(255) NULL,
+    device_type ENUM('ios', 'android', 'web', 'harmony', 'unknown') NULL,
+    device_fingerprint VARCHAR(255) NULL,
+    ip_address VARCHAR(45) NULL,
+    user_agent TEXT NULL,
+
+    -- Rotation chain
+    token_family VARCHAR(36) NULL,
+    previous_token_id CHAR(36) NULL,
+    rotated_from_token_id CHAR(36) NULL,
+    rotated_to_token_id CHAR(36) NULL,
+    use_count INT UNSIGNED NOT NULL DEFAULT 0,
+    last_rotation_at TIMESTAMP NULL,
+    rotation_count INT UNSIGNED NOT NULL DEFAULT 0,
+
+    -- When cleanup moved the token here
+    archived_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Constraints
+    PRIMARY KEY (id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Investigations look up a user's sessions, a leaked token or a rotation chain
+CREATE INDEX idx_refresh_tokens_archive_user_created ON refresh_tokens_archive(user_id, created_at);
+CREATE INDEX idx_refresh_tokens_archive_token_hash ON refresh_tokens_archive(token_hash);
+CREATE INDEX idx_refresh_tokens_archive_token_family ON refresh_tokens_archive(token_family);
+CREATE INDEX idx_refresh_tokens_archive_archived_at ON refresh_tokens_archive(archived_at);
+
+ALTER TABLE refresh_tokens_archive COMMENT = 'Expired and revoked refresh tokens removed by cleanup, kept for security investigations';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS refresh_tokens_archive;
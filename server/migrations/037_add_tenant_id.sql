@@ -0,0 +1,49 @@
+-- Migration: 037_add_tenant_id
+-- Description: Scope users, refresh tokens and auth audit logs to the white-labeled marketplace (tenant) they belong to
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Existing rows belong to the default tenant. The same phone number may hold
+-- an account in every marketplace, so phone hashes are unique per tenant.
+ALTER TABLE users
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the account belongs to'
+        AFTER id,
+    DROP INDEX uk_phone_hash,
+    ADD UNIQUE KEY uk_users_tenant_phone_hash (tenant_id, phone_hash);
+
+-- Refresh tokens only refresh sessions of the marketplace they were issued by
+ALTER TABLE refresh_tokens
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the token was issued by'
+        AFTER id,
+    ADD INDEX idx_refresh_tokens_tenant_user (tenant_id, user_id);
+
+-- Failed attempts are counted, and suspicious activity reported, per marketplace
+ALTER TABLE auth_audit_log
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the event happened in'
+        AFTER id,
+    ADD INDEX idx_auth_audit_log_tenant_created (tenant_id, created_at);
+
+-- Archived tokens keep the marketplace they were issued by
+ALTER TABLE refresh_tokens_archive
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the token was issued by'
+        AFTER id;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run (fails if a phone number has
+-- accounts in several tenants):
+-- ALTER TABLE refresh_tokens_archive DROP COLUMN tenant_id;
+-- ALTER TABLE auth_audit_log DROP INDEX idx_auth_audit_log_tenant_created, DROP COLUMN tenant_id;
+-- ALTER TABLE refresh_tokens DROP INDEX idx_refresh_tokens_tenant_user, DROP COLUMN tenant_id;
+-- ALTER TABLE users
+--     DROP INDEX uk_users_tenant_phone_hash,
+--     ADD UNIQUE KEY uk_phone_hash (phone_hash),
+--     DROP COLUMN tenant_id;
@@ -0,0 +1,34 @@
+-- Migration: 038_create_sms_daily_spend_table
+-- Description: Aggregate the estimated cost of the SMS sent each day, by provider and destination prefix
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Create sms_daily_spend table, one row per UTC day, provider and rate prefix
+CREATE TABLE IF NOT EXISTS sms_daily_spend (
+    -- UTC day the messages were sent
+    spend_date DATE NOT NULL,
+
+    -- Provider name used in the SMS configuration (e.g. 'twilio')
+    provider VARCHAR(50) NOT NULL,
+
+    -- Prefix of the rate the messages were priced with, empty when no rate matched
+    prefix VARCHAR(16) NOT NULL,
+
+    -- Number of messages and their estimated cost in millionths of the budget currency
+    messages BIGINT UNSIGNED NOT NULL DEFAULT 0,
+    cost_micros BIGINT UNSIGNED NOT NULL DEFAULT 0,
+
+    -- Constraints
+    PRIMARY KEY (spend_date, provider, prefix)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+ALTER TABLE sms_daily_spend COMMENT = 'Estimated SMS spend per day, provider and destination prefix';
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP TABLE IF EXISTS sms_daily_spend;
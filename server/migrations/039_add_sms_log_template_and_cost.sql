@@ -0,0 +1,25 @@
+-- Migration: 039_add_sms_log_template_and_cost
+-- Description: Record the template and estimated cost of each SMS, and index the log for support searches
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Template the message was rendered from, and its estimated cost in
+-- millionths of the budget currency when costs are tracked
+ALTER TABLE sms_delivery_logs
+    ADD COLUMN template VARCHAR(64) NULL AFTER error,
+    ADD COLUMN cost_micros BIGINT UNSIGNED NULL AFTER template;
+
+-- Support looks messages up by recipient within a period
+CREATE INDEX idx_sms_delivery_logs_phone_created ON sms_delivery_logs(phone_masked, created_at);
+CREATE INDEX idx_sms_delivery_logs_created_at ON sms_delivery_logs(created_at);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP INDEX idx_sms_delivery_logs_created_at ON sms_delivery_logs;
+-- DROP INDEX idx_sms_delivery_logs_phone_created ON sms_delivery_logs;
+-- ALTER TABLE sms_delivery_logs DROP COLUMN cost_micros, DROP COLUMN template;
@@ -0,0 +1,17 @@
+-- Migration: 040_add_campaign_channel
+-- Description: Let campaigns be delivered as SMS broadcasts as well as push notifications
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Existing campaigns were all push notifications
+ALTER TABLE campaigns
+    ADD COLUMN channel ENUM('push', 'sms') NOT NULL DEFAULT 'push' AFTER name;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- ALTER TABLE campaigns DROP COLUMN channel;
@@ -0,0 +1,306 @@
+-- Migration: 041_add_refresh_token_rotation
+-- Description: Add rotation tracking to refresh_tokens, the token blacklist and the token maintenance procedures
+-- Date: 2026-10-17
+-- Requirements: Auth-Passwordless Specification - Requirement 5.5 (JWT Token Generation)
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+-- 002 shipped these changes ahead of 003 creating refresh_tokens, in syntax
+-- only the mysql client and MariaDB accept. Databases set up that way already
+-- have the columns and indexes, so each one is only added when missing.
+
+-- Add token_family column for token rotation tracking
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.COLUMNS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND COLUMN_NAME = 'token_family'),
+    'DO 0',
+    'ALTER TABLE refresh_tokens ADD COLUMN token_family VARCHAR(36) NULL COMMENT ''Token family ID for rotation tracking - all tokens in a rotation chain share this ID'' AFTER rotated_to_token_id'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Add enhanced device fingerprint for better security
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.COLUMNS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND COLUMN_NAME = 'device_fingerprint'),
+    'DO 0',
+    'ALTER TABLE refresh_tokens ADD COLUMN device_fingerprint VARCHAR(255) NULL COMMENT ''Device fingerprint hash for enhanced security tracking'' AFTER device_type'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Add previous_token_id for complete rotation chain tracking
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.COLUMNS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND COLUMN_NAME = 'previous_token_id'),
+    'DO 0',
+    'ALTER TABLE refresh_tokens ADD COLUMN previous_token_id CHAR(36) NULL COMMENT ''Direct reference to the previous token in rotation chain'' AFTER rotated_to_token_id'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Add last_rotation_at for tracking rotation frequency
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.COLUMNS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND COLUMN_NAME = 'last_rotation_at'),
+    'DO 0',
+    'ALTER TABLE refresh_tokens ADD COLUMN last_rotation_at TIMESTAMP NULL COMMENT ''Timestamp of the last token rotation'' AFTER use_count'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Add rotation_count for security monitoring
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.COLUMNS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND COLUMN_NAME = 'rotation_count'),
+    'DO 0',
+    'ALTER TABLE refresh_tokens ADD COLUMN rotation_count INT UNSIGNED NOT NULL DEFAULT 0 COMMENT ''Number of times this token has been rotated'' AFTER last_rotation_at'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Create indexes for new columns
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.STATISTICS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND INDEX_NAME = 'idx_refresh_tokens_token_family'),
+    'DO 0',
+    'CREATE INDEX idx_refresh_tokens_token_family ON refresh_tokens(token_family)'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.STATISTICS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND INDEX_NAME = 'idx_refresh_tokens_device_fingerprint'),
+    'DO 0',
+    'CREATE INDEX idx_refresh_tokens_device_fingerprint ON refresh_tokens(device_fingerprint)'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+SET @ddl = IF(
+    EXISTS(SELECT 1 FROM information_schema.STATISTICS
+           WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'refresh_tokens' AND INDEX_NAME = 'idx_refresh_tokens_previous_token_id'),
+    'DO 0',
+    'CREATE INDEX idx_refresh_tokens_previous_token_id ON refresh_tokens(previous_token_id)'
+);
+PREPARE stmt FROM @ddl;
+EXECUTE stmt;
+DEALLOCATE PREPARE stmt;
+
+-- Create token_blacklist table for immediate token revocation
+CREATE TABLE IF NOT EXISTS token_blacklist (
+    -- JWT ID (jti claim) as primary key
+    jti VARCHAR(36) NOT NULL,
+    
+    -- Token metadata
+    token_type ENUM('access', 'refresh') NOT NULL DEFAULT 'access',
+    user_id CHAR(36) NULL,
+    
+    -- Blacklist lifecycle
+    blacklisted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    reason VARCHAR(255) NULL,
+    
+    -- Additional context
+    ip_address VARCHAR(45) NULL,
+    user_agent TEXT NULL,
+    
+    -- Audit fields
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    
+    -- Constraints
+    PRIMARY KEY (jti),
+    INDEX idx_blacklist_expires_at (expires_at),
+    INDEX idx_blacklist_user_id (user_id),
+    INDEX idx_blacklist_created_at (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT = 'Blacklist for revoked JWT tokens - enables immediate token invalidation';
+
+-- Add column comments for token_blacklist
+ALTER TABLE token_blacklist
+    MODIFY COLUMN jti VARCHAR(36) NOT NULL COMMENT 'JWT ID from the jti claim - unique identifier for the token',
+    MODIFY COLUMN token_type ENUM('access', 'refresh') NOT NULL DEFAULT 'access' COMMENT 'Type of token being blacklisted',
+    MODIFY COLUMN user_id CHAR(36) NULL COMMENT 'User ID associated with the blacklisted token',
+    MODIFY COLUMN blacklisted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT 'When the token was added to blacklist',
+    MODIFY COLUMN expires_at TIMESTAMP NOT NULL COMMENT 'When the blacklist entry can be removed (matches token expiry)',
+    MODIFY COLUMN reason VARCHAR(255) NULL COMMENT 'Reason for blacklisting (e.g., "logout", "suspicious_activity", "password_change")',
+    MODIFY COLUMN ip_address VARCHAR(45) NULL COMMENT 'IP address from which blacklist was triggered',
+    MODIFY COLUMN user_agent TEXT NULL COMMENT 'User agent of the client that triggered blacklisting',
+    MODIFY COLUMN created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT 'Record creation timestamp';
+
+-- Create stored procedure for enhanced token rotation
+CREATE PROCEDURE IF NOT EXISTS rotate_refresh_token(
+    IN p_old_token_hash VARCHAR(64),
+    IN p_new_token_hash VARCHAR(64),
+    IN p_new_token_id CHAR(36),
+    IN p_device_fingerprint VARCHAR(255)
+)
+BEGIN
+    DECLARE v_user_id CHAR(36);
+    DECLARE v_token_family VARCHAR(36);
+    DECLARE v_old_token_id CHAR(36);
+    DECLARE v_is_valid BOOLEAN;
+    
+    -- Start transaction for atomic operation
+    START TRANSACTION;
+    
+    -- Find the old token and check validity
+    SELECT id, user_id, token_family, 
+           (is_revoked = FALSE AND expires_at > NOW()) AS is_valid
+    INTO v_old_token_id, v_user_id, v_token_family, v_is_valid
+    FROM refresh_tokens
+    WHERE token_hash = p_old_token_hash
+    FOR UPDATE;
+    
+    -- Check if token is valid
+    IF v_is_valid = FALSE THEN
+        -- If token is already revoked or expired, this might be a reuse attack
+        -- Revoke entire token family for security
+        IF v_token_family IS NOT NULL THEN
+            UPDATE refresh_tokens
+            SET is_revoked = TRUE,
+                revoked_at = NOW(),
+                revoke_reason = 'Token reuse detected - possible security breach'
+            WHERE token_family = v_token_family
+            AND is_revoked = FALSE;
+        END IF;
+        
+        ROLLBACK;
+        SIGNAL SQLSTATE '45000' 
+        SET MESSAGE_TEXT = 'Invalid token - possible reuse attack detected';
+    END IF;
+    
+    -- Generate new token family if not exists
+    IF v_token_family IS NULL THEN
+        SET v_token_family = UUID();
+        
+        -- Update old token with family ID
+        UPDATE refresh_tokens
+        SET token_family = v_token_family
+        WHERE id = v_old_token_id;
+    END IF;
+    
+    -- Mark old token as rotated
+    UPDATE refresh_tokens
+    SET rotated_to_token_id = p_new_token_id,
+        is_revoked = TRUE,
+        revoked_at = NOW(),
+        revoke_reason = 'Token rotation',
+        last_rotation_at = NOW(),
+        rotation_count = rotation_count + 1
+    WHERE id = v_old_token_id;
+    
+    -- Insert new token
+    INSERT INTO refresh_tokens (
+        id, user_id, token_hash, token_family,
+        device_fingerprint, previous_token_id,
+        rotated_from_token_id, expires_at,
+        created_at
+    ) VALUES (
+        p_new_token_id,
+        v_user_id,
+        p_new_token_hash,
+        v_token_family,
+        p_device_fingerprint,
+        v_old_token_id,
+        v_old_token_id,
+        DATE_ADD(NOW(), INTERVAL 30 DAY),
+        NOW()
+    );
+    
+    COMMIT;
+END;
+
+-- Create stored procedure for token family revocation
+CREATE PROCEDURE IF NOT EXISTS revoke_token_family(
+    IN p_token_family VARCHAR(36),
+    IN p_reason VARCHAR(255)
+)
+BEGIN
+    UPDATE refresh_tokens
+    SET is_revoked = TRUE,
+        revoked_at = NOW(),
+        revoke_reason = p_reason
+    WHERE token_family = p_token_family
+    AND is_revoked = FALSE;
+    
+    SELECT ROW_COUNT() AS revoked_count;
+END;
+
+-- Create stored procedure for blacklist cleanup
+CREATE PROCEDURE IF NOT EXISTS cleanup_token_blacklist()
+BEGIN
+    -- Delete expired blacklist entries
+    DELETE FROM token_blacklist 
+    WHERE expires_at < NOW();
+    
+    SELECT ROW_COUNT() AS deleted_count;
+END;
+
+-- Replace the token cleanup procedure from 003 with one that also removes
+-- fully revoked token families
+DROP PROCEDURE IF EXISTS cleanup_expired_refresh_tokens;
+
+CREATE PROCEDURE cleanup_expired_refresh_tokens()
+BEGIN
+    -- Delete expired tokens older than 7 days (grace period after expiry)
+    DELETE FROM refresh_tokens 
+    WHERE expires_at < DATE_SUB(NOW(), INTERVAL 7 DAY);
+    
+    -- Delete revoked tokens older than 30 days
+    DELETE FROM refresh_tokens 
+    WHERE is_revoked = TRUE 
+    AND revoked_at < DATE_SUB(NOW(), INTERVAL 30 DAY);
+    
+    -- Cleanup orphaned token families (all tokens revoked)
+    DELETE FROM refresh_tokens
+    WHERE token_family IN (
+        SELECT DISTINCT token_family 
+        FROM (
+            SELECT token_family
+            FROM refresh_tokens
+            WHERE token_family IS NOT NULL
+            GROUP BY token_family
+            HAVING COUNT(*) = SUM(CASE WHEN is_revoked = TRUE THEN 1 ELSE 0 END)
+            AND MAX(revoked_at) < DATE_SUB(NOW(), INTERVAL 7 DAY)
+        ) AS orphaned_families
+    );
+END;
+
+-- Create event scheduler for automatic cleanup (runs daily at 3 AM)
+CREATE EVENT IF NOT EXISTS auto_cleanup_tokens
+ON SCHEDULE EVERY 1 DAY
+STARTS (DATE(NOW()) + INTERVAL 1 DAY + INTERVAL 3 HOUR)
+DO
+BEGIN
+    CALL cleanup_expired_refresh_tokens();
+    CALL cleanup_token_blacklist();
+END;
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP EVENT IF EXISTS auto_cleanup_tokens;
+-- DROP PROCEDURE IF EXISTS cleanup_token_blacklist;
+-- DROP PROCEDURE IF EXISTS revoke_token_family;
+-- DROP PROCEDURE IF EXISTS rotate_refresh_token;
+-- DROP TABLE IF EXISTS token_blacklist;
+-- DROP INDEX idx_refresh_tokens_previous_token_id ON refresh_tokens;
+-- DROP INDEX idx_refresh_tokens_device_fingerprint ON refresh_tokens;
+-- DROP INDEX idx_refresh_tokens_token_family ON refresh_tokens;
+-- ALTER TABLE refresh_tokens
+--     DROP COLUMN rotation_count,
+--     DROP COLUMN last_rotation_at,
+--     DROP COLUMN previous_token_id,
+--     DROP COLUMN device_fingerprint,
+--     DROP COLUMN token_family;
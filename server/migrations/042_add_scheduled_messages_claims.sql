@@ -0,0 +1,26 @@
+-- Migration: 042_add_scheduled_messages_claims
+-- Description: Let message schedulers on several instances claim due messages so each is sent once
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- A dispatcher moves due messages to 'processing' under its claim before
+-- sending; claims that outlive claimed_until are taken over by another instance
+ALTER TABLE scheduled_messages
+    MODIFY COLUMN status ENUM('pending', 'processing', 'sent', 'failed', 'cancelled') NOT NULL DEFAULT 'pending',
+    ADD COLUMN claimed_by CHAR(36) NULL AFTER status,
+    ADD COLUMN claimed_until TIMESTAMP NULL AFTER claimed_by;
+
+CREATE INDEX idx_scheduled_messages_claimed_by ON scheduled_messages(claimed_by);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP INDEX idx_scheduled_messages_claimed_by ON scheduled_messages;
+-- ALTER TABLE scheduled_messages
+--     DROP COLUMN claimed_until,
+--     DROP COLUMN claimed_by,
+--     MODIFY COLUMN status ENUM('pending', 'sent', 'failed', 'cancelled') NOT NULL DEFAULT 'pending';
@@ -0,0 +1,33 @@
+-- Migration: 043_add_campaign_dispatch_claims
+-- Description: Let campaign dispatchers on several instances claim campaigns and deliveries so each delivery is sent once
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- A dispatcher moves a due campaign to 'dispatching' before enqueueing its
+-- audience; a campaign left there past the claim lease is taken over
+ALTER TABLE campaigns
+    MODIFY COLUMN status ENUM('scheduled', 'dispatching', 'sending', 'completed', 'cancelled') NOT NULL DEFAULT 'scheduled';
+
+-- Deliveries move to 'processing' under a dispatcher's claim before they
+-- are sent; claims that outlive claimed_until are taken over
+ALTER TABLE campaign_deliveries
+    MODIFY COLUMN status ENUM('pending', 'processing', 'sent', 'failed', 'opted_out') NOT NULL DEFAULT 'pending',
+    ADD COLUMN claimed_by CHAR(36) NULL AFTER status,
+    ADD COLUMN claimed_until TIMESTAMP NULL AFTER claimed_by;
+
+CREATE INDEX idx_campaign_deliveries_claimed_by ON campaign_deliveries(claimed_by);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run:
+-- DROP INDEX idx_campaign_deliveries_claimed_by ON campaign_deliveries;
+-- ALTER TABLE campaign_deliveries
+--     DROP COLUMN claimed_until,
+--     DROP COLUMN claimed_by,
+--     MODIFY COLUMN status ENUM('pending', 'sent', 'failed', 'opted_out') NOT NULL DEFAULT 'pending';
+-- ALTER TABLE campaigns
+--     MODIFY COLUMN status ENUM('scheduled', 'sending', 'completed', 'cancelled') NOT NULL DEFAULT 'scheduled';
@@ -0,0 +1,299 @@
+-- Migration: 044_add_tenant_id_to_marketplace_tables
+-- Description: Scope every marketplace-owned table to the white-labeled marketplace (tenant) it belongs to
+-- Date: 2026-10-17
+
+-- ============================================================================
+-- UP MIGRATION
+-- ============================================================================
+
+-- Existing rows belong to the default tenant, like the users they refer to
+-- (see 037). Keys that hold identifiers chosen by a marketplace (markets,
+-- categories, versions, references) become unique per tenant; keys on
+-- UUIDs and provider identifiers stay unique across the deployment.
+--
+-- Left without a tenant on purpose: admin_identities and oauth_clients
+-- (shared by every marketplace), verification_codes, otp_fallback and
+-- token_blacklist (keyed by phone number or token ID), and the tables of
+-- the deployment's provider accounts: sms_daily_spend, sms_delivery_logs,
+-- payment_webhook_events, webhook_dead_letters, payment_ledger_entries,
+-- reconciliation_reports and reconciliation_discrepancies.
+
+-- Messaging and scheduling
+ALTER TABLE scheduled_messages
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the message is sent for'
+        AFTER id,
+    ADD INDEX idx_scheduled_messages_tenant_status (tenant_id, status, deliver_at);
+
+ALTER TABLE notification_preferences
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the user'
+        AFTER user_id;
+
+ALTER TABLE business_closures
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the closure applies to'
+        AFTER id,
+    DROP INDEX uk_business_closures_market_date,
+    ADD UNIQUE KEY uk_business_closures_tenant_market_date (tenant_id, market, closure_date);
+
+ALTER TABLE worker_service_areas
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the worker'
+        AFTER id,
+    ADD INDEX idx_worker_service_areas_tenant_worker (tenant_id, worker_id);
+
+ALTER TABLE campaigns
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the campaign is sent for'
+        AFTER id,
+    ADD INDEX idx_campaigns_tenant_status (tenant_id, status, scheduled_at);
+
+ALTER TABLE campaign_deliveries
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the campaign'
+        AFTER id,
+    ADD INDEX idx_campaign_deliveries_tenant_campaign (tenant_id, campaign_id, status);
+
+-- Payments and payouts
+ALTER TABLE payments
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the payment was taken in'
+        AFTER id,
+    ADD INDEX idx_payments_tenant_customer (tenant_id, customer_id);
+
+ALTER TABLE payment_timeline
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the payment'
+        AFTER id;
+
+ALTER TABLE payouts
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the payment paid out'
+        AFTER id,
+    ADD INDEX idx_payouts_tenant_worker_status (tenant_id, worker_id, status);
+
+ALTER TABLE payment_disputes
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the disputed payment'
+        AFTER id;
+
+ALTER TABLE payment_risk_assessments
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the payment was assessed in'
+        AFTER id,
+    ADD INDEX idx_payment_risk_assessments_tenant_review (tenant_id, review_status, created_at);
+
+ALTER TABLE saved_payment_methods
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the customer'
+        AFTER id;
+
+ALTER TABLE payout_methods
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the worker'
+        AFTER id;
+
+-- Each marketplace keeps its own books, fees and credit
+ALTER TABLE journal_entries
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace whose books the entry is posted to'
+        AFTER id,
+    DROP INDEX uk_journal_entries_event,
+    ADD UNIQUE KEY uk_journal_entries_tenant_event (tenant_id, transaction_type, reference);
+
+ALTER TABLE journal_lines
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace whose books the line is posted to'
+        AFTER line_no,
+    ADD INDEX idx_journal_lines_tenant_account (tenant_id, account_type, owner_id, currency);
+
+ALTER TABLE fee_schedules
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the fees apply to'
+        AFTER id,
+    DROP INDEX uk_fee_schedules_version,
+    ADD UNIQUE KEY uk_fee_schedules_tenant_version (tenant_id, market, category, version);
+
+ALTER TABLE order_fees
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the order'
+        AFTER order_id;
+
+ALTER TABLE credit_grants
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the credit can be spent in'
+        AFTER id,
+    DROP INDEX uk_credit_grants_reference,
+    ADD UNIQUE KEY uk_credit_grants_tenant_reference (tenant_id, reference),
+    ADD INDEX idx_credit_grants_tenant_expires_at (tenant_id, expires_at);
+
+ALTER TABLE credit_transactions
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the credit'
+        AFTER id,
+    DROP INDEX uk_credit_transactions_event,
+    ADD UNIQUE KEY uk_credit_transactions_tenant_event (tenant_id, kind, reference);
+
+-- OAuth consents and codes are granted within a marketplace
+ALTER TABLE oauth_consents
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the consent was granted in'
+        AFTER client_id;
+
+ALTER TABLE oauth_authorization_codes
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the code was issued by'
+        AFTER code_hash;
+
+-- Orders, completion and security webhooks
+ALTER TABLE checklist_templates
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the template is used in'
+        AFTER id,
+    DROP INDEX uk_checklist_templates_version,
+    ADD UNIQUE KEY uk_checklist_templates_tenant_version (tenant_id, category, version);
+
+ALTER TABLE order_completion_checklists
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the order'
+        AFTER order_id;
+
+ALTER TABLE security_webhooks
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace whose events are sent to the endpoint'
+        AFTER id;
+
+ALTER TABLE security_webhook_deliveries
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the webhook'
+        AFTER id,
+    ADD INDEX idx_security_webhook_deliveries_tenant_due (tenant_id, status, next_attempt_at);
+
+-- Users' attributions, organizations, legal acceptances, compliance and exports
+ALTER TABLE user_attributions
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the user'
+        AFTER user_id,
+    ADD INDEX idx_user_attributions_tenant_registered_at (tenant_id, registered_at);
+
+ALTER TABLE organizations
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the organization buys in'
+        AFTER id;
+
+ALTER TABLE organization_members
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the organization'
+        AFTER organization_id;
+
+ALTER TABLE organization_payment_methods
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the organization'
+        AFTER id;
+
+ALTER TABLE organization_invoices
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the organization'
+        AFTER id;
+
+ALTER TABLE organization_charges
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the organization'
+        AFTER id,
+    ADD INDEX idx_organization_charges_tenant_uninvoiced (tenant_id, invoice_id, completed_at);
+
+ALTER TABLE legal_documents
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace that publishes the document'
+        AFTER id,
+    DROP INDEX uk_legal_documents_translation,
+    ADD UNIQUE KEY uk_legal_documents_tenant_translation (tenant_id, kind, market, version, language);
+
+ALTER TABLE legal_acceptances
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the user'
+        AFTER version;
+
+ALTER TABLE user_compliance_checks
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace of the user'
+        AFTER id;
+
+ALTER TABLE export_jobs
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace whose rows are exported'
+        AFTER id,
+    ADD INDEX idx_export_jobs_tenant_status (tenant_id, status, created_at);
+
+-- An export is imported into a marketplace, and may be imported into another
+ALTER TABLE user_import_checkpoints
+    ADD COLUMN tenant_id VARCHAR(64) NOT NULL DEFAULT 'default'
+        COMMENT 'Marketplace the users are imported into'
+        FIRST,
+    DROP PRIMARY KEY,
+    ADD PRIMARY KEY (tenant_id, import_id);
+
+-- ============================================================================
+-- DOWN MIGRATION
+-- ============================================================================
+-- To rollback this migration, uncomment and run (fails if a per-tenant key
+-- holds the same value in several tenants):
+-- ALTER TABLE user_import_checkpoints DROP PRIMARY KEY, ADD PRIMARY KEY (import_id), DROP COLUMN tenant_id;
+-- ALTER TABLE export_jobs DROP INDEX idx_export_jobs_tenant_status, DROP COLUMN tenant_id;
+-- ALTER TABLE user_compliance_checks DROP COLUMN tenant_id;
+-- ALTER TABLE legal_acceptances DROP COLUMN tenant_id;
+-- ALTER TABLE legal_documents
+--     DROP INDEX uk_legal_documents_tenant_translation,
+--     ADD UNIQUE KEY uk_legal_documents_translation (kind, market, version, language),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE organization_charges DROP INDEX idx_organization_charges_tenant_uninvoiced, DROP COLUMN tenant_id;
+-- ALTER TABLE organization_invoices DROP COLUMN tenant_id;
+-- ALTER TABLE organization_payment_methods DROP COLUMN tenant_id;
+-- ALTER TABLE organization_members DROP COLUMN tenant_id;
+-- ALTER TABLE organizations DROP COLUMN tenant_id;
+-- ALTER TABLE user_attributions DROP INDEX idx_user_attributions_tenant_registered_at, DROP COLUMN tenant_id;
+-- ALTER TABLE security_webhook_deliveries DROP INDEX idx_security_webhook_deliveries_tenant_due, DROP COLUMN tenant_id;
+-- ALTER TABLE security_webhooks DROP COLUMN tenant_id;
+-- ALTER TABLE order_completion_checklists DROP COLUMN tenant_id;
+-- ALTER TABLE checklist_templates
+--     DROP INDEX uk_checklist_templates_tenant_version,
+--     ADD UNIQUE KEY uk_checklist_templates_version (category, version),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE oauth_authorization_codes DROP COLUMN tenant_id;
+-- ALTER TABLE oauth_consents DROP COLUMN tenant_id;
+-- ALTER TABLE credit_transactions
+--     DROP INDEX uk_credit_transactions_tenant_event,
+--     ADD UNIQUE KEY uk_credit_transactions_event (kind, reference),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE credit_grants
+--     DROP INDEX idx_credit_grants_tenant_expires_at,
+--     DROP INDEX uk_credit_grants_tenant_reference,
+--     ADD UNIQUE KEY uk_credit_grants_reference (reference),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE order_fees DROP COLUMN tenant_id;
+-- ALTER TABLE fee_schedules
+--     DROP INDEX uk_fee_schedules_tenant_version,
+--     ADD UNIQUE KEY uk_fee_schedules_version (market, category, version),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE journal_lines DROP INDEX idx_journal_lines_tenant_account, DROP COLUMN tenant_id;
+-- ALTER TABLE journal_entries
+--     DROP INDEX uk_journal_entries_tenant_event,
+--     ADD UNIQUE KEY uk_journal_entries_event (transaction_type, reference),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE payout_methods DROP COLUMN tenant_id;
+-- ALTER TABLE saved_payment_methods DROP COLUMN tenant_id;
+-- ALTER TABLE payment_risk_assessments DROP INDEX idx_payment_risk_assessments_tenant_review, DROP COLUMN tenant_id;
+-- ALTER TABLE payment_disputes DROP COLUMN tenant_id;
+-- ALTER TABLE payouts DROP INDEX idx_payouts_tenant_worker_status, DROP COLUMN tenant_id;
+-- ALTER TABLE payment_timeline DROP COLUMN tenant_id;
+-- ALTER TABLE payments DROP INDEX idx_payments_tenant_customer, DROP COLUMN tenant_id;
+-- ALTER TABLE campaign_deliveries DROP INDEX idx_campaign_deliveries_tenant_campaign, DROP COLUMN tenant_id;
+-- ALTER TABLE campaigns DROP INDEX idx_campaigns_tenant_status, DROP COLUMN tenant_id;
+-- ALTER TABLE worker_service_areas DROP INDEX idx_worker_service_areas_tenant_worker, DROP COLUMN tenant_id;
+-- ALTER TABLE business_closures
+--     DROP INDEX uk_business_closures_tenant_market_date,
+--     ADD UNIQUE KEY uk_business_closures_market_date (market, closure_date),
+--     DROP COLUMN tenant_id;
+-- ALTER TABLE notification_preferences DROP COLUMN tenant_id;
+-- ALTER TABLE scheduled_messages DROP INDEX idx_scheduled_messages_tenant_status, DROP COLUMN tenant_id;
@@ -1,12 +1,15 @@
 //! Authentication and authorization configuration
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::secret::SecretString;
 
 /// JWT authentication configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct JwtConfig {
     /// JWT secret key for signing tokens
-    pub secret: String,
+    pub secret: SecretString,
 
     /// Access token expiry time in seconds
     pub access_token_expiry: i64,
@@ -37,7 +40,7 @@ pub struct JwtConfig {
 impl Default for JwtConfig {
     fn default() -> Self {
         Self {
-            secret: String::from("your-secret-key-change-in-production"),
+            secret: SecretString::from("your-secret-key-change-in-production"),
             access_token_expiry: 900,     // 15 minutes
             refresh_token_expiry: 604800,  // 7 days
             issuer: String::from("renoveasy"),
@@ -53,7 +56,7 @@ impl JwtConfig {
     /// Create a new JWT configuration with secret
     pub fn new(secret: impl Into<String>) -> Self {
         Self {
-            secret: secret.into(),
+            secret: SecretString::new(secret),
             ..Default::default()
         }
     }
@@ -72,7 +75,7 @@ impl JwtConfig {
 
     /// Check if using default secret (security warning)
     pub fn is_using_default_secret(&self) -> bool {
-        self.secret == "your-secret-key-change-in-production"
+        self.secret.expose_secret() == "your-secret-key-change-in-production"
     }
 }
 
@@ -83,7 +86,7 @@ pub struct OAuth2Config {
     pub client_id: String,
 
     /// OAuth2 client secret
-    pub client_secret: String,
+    pub client_secret: SecretString,
 
     /// Authorization URL
     pub auth_url: String,
@@ -99,6 +102,43 @@ pub struct OAuth2Config {
     pub scopes: Vec<String>,
 }
 
+/// Single sign-on of administrators through an external OIDC provider
+///
+/// Administrators sign in with their corporate identity (e.g. Okta or
+/// Azure AD) instead of a phone-based account. Their group memberships
+/// are mapped to internal admin roles; members of no mapped group are
+/// refused.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AdminSsoConfig {
+    /// Issuer URL; the discovery document is read from `/.well-known/openid-configuration`
+    pub issuer_url: String,
+
+    /// Client ID registered with the provider
+    pub client_id: String,
+
+    /// Client secret registered with the provider
+    pub client_secret: SecretString,
+
+    /// Admin portal URL the provider redirects to after sign-in
+    pub redirect_uri: String,
+
+    /// Scopes requested from the provider
+    #[serde(default = "default_sso_scopes")]
+    pub scopes: Vec<String>,
+
+    /// ID token claim listing the user's groups
+    #[serde(default = "default_groups_claim")]
+    pub groups_claim: String,
+
+    /// Provider group name or ID to internal admin role
+    #[serde(default)]
+    pub role_mappings: HashMap<String, String>,
+
+    /// Lifetime of an admin session in minutes; admins sign in again afterwards
+    #[serde(default = "default_admin_session_minutes")]
+    pub session_minutes: i64,
+}
+
 /// Session configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SessionConfig {
@@ -131,6 +171,43 @@ impl Default for SessionConfig {
     }
 }
 
+/// Peppers used to hash phone numbers
+///
+/// Phone hashes are HMAC-SHA256 digests keyed with server-side peppers. The
+/// peppers form an append-only keyring: the pepper at position `n` (from 1)
+/// is hash version `n`, and each version hashes the digest of the version
+/// before it, so stored rows can be brought to a new version without the
+/// phone number. To rotate, append a pepper and never remove or reorder
+/// existing ones; users are found under any version, and rows are rehashed
+/// when users sign in and by the background rehash job. Rows written before
+/// peppering was enabled are found as long as `accept_unpeppered` is set.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PhoneHashConfig {
+    /// Peppers, oldest first; phone hashes fall back to bare SHA-256 without any
+    #[serde(default)]
+    pub peppers: Vec<SecretString>,
+
+    /// Whether lookups also accept bare SHA-256 hashes
+    #[serde(default = "default_accept_unpeppered")]
+    pub accept_unpeppered: bool,
+}
+
+impl PhoneHashConfig {
+    /// Version of the hashes written now; 0 for bare SHA-256
+    pub fn current_version(&self) -> u16 {
+        self.peppers.len() as u16
+    }
+}
+
+impl Default for PhoneHashConfig {
+    fn default() -> Self {
+        Self {
+            peppers: Vec::new(),
+            accept_unpeppered: default_accept_unpeppered(),
+        }
+    }
+}
+
 /// Complete authentication configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AuthConfig {
@@ -144,6 +221,14 @@ pub struct AuthConfig {
     /// OAuth2 providers (optional)
     #[serde(default)]
     pub oauth2: Option<OAuth2Providers>,
+
+    /// Admin single sign-on (optional)
+    #[serde(default)]
+    pub admin_sso: Option<AdminSsoConfig>,
+
+    /// Phone number hashing
+    #[serde(default)]
+    pub phone_hash: PhoneHashConfig,
 }
 
 impl AuthConfig {
@@ -166,7 +251,7 @@ impl AuthConfig {
 
         Self {
             jwt: JwtConfig {
-                secret: jwt_secret,
+                secret: SecretString::from(jwt_secret),
                 access_token_expiry,
                 refresh_token_expiry,
                 issuer: String::from("renoveasy"),
@@ -179,12 +264,14 @@ impl AuthConfig {
             },
             session: SessionConfig::default(),
             oauth2: None,
+            admin_sso: None,
+            phone_hash: PhoneHashConfig::default(),
         }
     }
 
     /// Get JWT secret (backward compatibility)
     pub fn jwt_secret(&self) -> &str {
-        &self.jwt.secret
+        self.jwt.secret.expose_secret()
     }
 
     /// Get access token expiry in seconds (backward compatibility)
@@ -204,6 +291,8 @@ impl Default for AuthConfig {
             jwt: JwtConfig::default(),
             session: SessionConfig::default(),
             oauth2: None,
+            admin_sso: None,
+            phone_hash: PhoneHashConfig::default(),
         }
     }
 }
@@ -225,6 +314,22 @@ fn default_algorithm() -> String {
     String::from("RS256")
 }
 
+fn default_accept_unpeppered() -> bool {
+    true
+}
+
 fn default_http_only() -> bool {
     true
 }
+
+fn default_sso_scopes() -> Vec<String> {
+    ["openid", "email", "profile"].iter().map(|s| s.to_string()).collect()
+}
+
+fn default_groups_claim() -> String {
+    String::from("groups")
+}
+
+fn default_admin_session_minutes() -> i64 {
+    60
+}
@@ -2,11 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::secret::SecretString;
+
 /// Redis cache configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CacheConfig {
     /// Redis connection URL
-    pub url: String,
+    pub url: SecretString,
 
     /// Maximum number of connections in the pool
     pub max_connections: u32,
@@ -21,7 +23,8 @@ pub struct CacheConfig {
     #[serde(default = "default_ttl")]
     pub default_ttl: u64,
 
-    /// Enable cache key prefix
+    /// Namespace put in front of every Redis key and pub/sub channel as
+    /// `{key_prefix}:`, e.g. `staging` or `production`
     #[serde(default)]
     pub key_prefix: Option<String>,
 
@@ -32,12 +35,22 @@ pub struct CacheConfig {
     /// Enable cache statistics
     #[serde(default)]
     pub enable_stats: bool,
+
+    /// Sentinel endpoints (e.g. `redis://10.0.0.1:26379`); when set, the
+    /// master is resolved through them and `url` only supplies the password
+    /// and database of the master
+    #[serde(default)]
+    pub sentinels: Vec<String>,
+
+    /// Name of the master monitored by the sentinels
+    #[serde(default)]
+    pub sentinel_master: Option<String>,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
-            url: String::from("redis://localhost:6379"),
+            url: SecretString::from("redis://localhost:6379"),
             max_connections: 10,
             connection_timeout: 5,
             response_timeout: 5,
@@ -45,6 +58,8 @@ impl Default for CacheConfig {
             key_prefix: None,
             database: 0,
             enable_stats: false,
+            sentinels: Vec::new(),
+            sentinel_master: None,
         }
     }
 }
@@ -59,24 +74,38 @@ impl CacheConfig {
             .parse()
             .unwrap_or(10);
 
+        let key_prefix = std::env::var("REDIS_KEY_PREFIX")
+            .ok()
+            .and_then(|prefix| Self::parse_key_prefix(&prefix));
+
         Self {
-            url,
+            url: SecretString::from(url),
             max_connections,
+            key_prefix,
             ..Default::default()
         }
     }
 
+    /// Parse a key prefix, None if blank
+    ///
+    /// A trailing `:` is dropped since one is added between the prefix
+    /// and the key.
+    pub fn parse_key_prefix(value: &str) -> Option<String> {
+        let prefix = value.trim().trim_end_matches(':');
+        (!prefix.is_empty()).then(|| prefix.to_string())
+    }
+
     /// Create a new cache configuration with URL
     pub fn new(url: impl Into<String>) -> Self {
         Self {
-            url: url.into(),
+            url: SecretString::new(url),
             ..Default::default()
         }
     }
 
     /// Get Redis URL (backward compatibility)
     pub fn redis_url(&self) -> &str {
-        &self.url
+        self.url.expose_secret()
     }
 
     /// Get pool size (backward compatibility)
@@ -101,6 +130,31 @@ impl CacheConfig {
         self
     }
 
+    /// Resolve the master through sentinels instead of connecting to `url`
+    pub fn with_sentinels<I, S>(mut self, master: impl Into<String>, sentinels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sentinel_master = Some(master.into());
+        self.sentinels = sentinels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether the master is resolved through sentinels
+    pub fn uses_sentinel(&self) -> bool {
+        !self.sentinels.is_empty()
+    }
+
+    /// Check that sentinels come with the name of their master
+    pub fn validate_sentinels(&self) -> Result<(), String> {
+        let has_master = matches!(&self.sentinel_master, Some(master) if !master.trim().is_empty());
+        if self.uses_sentinel() && !has_master {
+            return Err("Redis sentinels require the name of their master".to_string());
+        }
+        Ok(())
+    }
+
     /// Generate a cache key with prefix
     pub fn make_key(&self, key: &str) -> String {
         match &self.key_prefix {
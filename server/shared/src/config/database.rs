@@ -2,11 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::secret::SecretString;
+
 /// Database configuration for MySQL/PostgreSQL connections
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     /// Database connection URL
-    pub url: String,
+    pub url: SecretString,
 
     /// Maximum number of connections in the pool
     pub max_connections: u32,
@@ -27,18 +29,44 @@ pub struct DatabaseConfig {
     /// Slow query threshold in milliseconds
     #[serde(default = "default_slow_query_threshold")]
     pub slow_query_threshold: u64,
+
+    /// TLS settings; unset options keep whatever the URL specifies
+    #[serde(default)]
+    pub tls: DatabaseTlsConfig,
+
+    /// Regional databases holding the users of some countries; users of
+    /// other countries stay in this database
+    #[serde(default)]
+    pub shards: Vec<DatabaseShardConfig>,
+}
+
+/// A regional database holding the users of some countries
+///
+/// Pool settings other than the URL are those of the primary database.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatabaseShardConfig {
+    /// Name of the shard, as used in logs
+    pub name: String,
+
+    /// Database connection URL
+    pub url: SecretString,
+
+    /// Country calling codes, such as `+86`, of the users on this shard
+    pub country_codes: Vec<String>,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
-            url: String::from("mysql://localhost:3306/renoveasy"),
+            url: SecretString::from("mysql://localhost:3306/renoveasy"),
             max_connections: 10,
             connect_timeout: 30,
             idle_timeout: 600,
             max_lifetime: 1800,
             enable_logging: false,
             slow_query_threshold: default_slow_query_threshold(),
+            tls: DatabaseTlsConfig::default(),
+            shards: Vec::new(),
         }
     }
 }
@@ -58,9 +86,10 @@ impl DatabaseConfig {
             .unwrap_or(30);
 
         Self {
-            url,
+            url: SecretString::from(url),
             max_connections,
             connect_timeout,
+            tls: DatabaseTlsConfig::from_env(),
             ..Default::default()
         }
     }
@@ -68,7 +97,7 @@ impl DatabaseConfig {
     /// Create a new database configuration with URL
     pub fn new(url: impl Into<String>) -> Self {
         Self {
-            url: url.into(),
+            url: SecretString::new(url),
             ..Default::default()
         }
     }
@@ -85,9 +114,175 @@ impl DatabaseConfig {
         self
     }
 
+    /// Set the TLS settings
+    pub fn with_tls(mut self, tls: DatabaseTlsConfig) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Add a regional database for the users of some countries
+    pub fn with_shard(mut self, name: impl Into<String>, url: impl Into<String>, country_codes: &[&str]) -> Self {
+        self.shards.push(DatabaseShardConfig {
+            name: name.into(),
+            url: SecretString::new(url),
+            country_codes: country_codes.iter().map(|code| code.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Configuration of the pool connecting to a shard
+    pub fn for_shard(&self, shard: &DatabaseShardConfig) -> Self {
+        Self {
+            url: shard.url.clone(),
+            shards: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Check that no country is assigned to more than one shard
+    pub fn validate_shards(&self) -> Result<(), String> {
+        let mut assigned = std::collections::HashMap::new();
+        for shard in &self.shards {
+            if shard.country_codes.is_empty() {
+                return Err(format!("Database shard {} has no country codes", shard.name));
+            }
+            for code in &shard.country_codes {
+                if let Some(other) = assigned.insert(code.as_str(), shard.name.as_str()) {
+                    return Err(format!(
+                        "Country code {} is assigned to database shards {} and {}",
+                        code, other, shard.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Check if this is a production database
     pub fn is_production(&self) -> bool {
-        !self.url.contains("localhost") && !self.url.contains("127.0.0.1")
+        let url = self.url.expose_secret();
+        !url.contains("localhost") && !url.contains("127.0.0.1")
+    }
+}
+
+/// Whether and how database connections use TLS, as MySQL's `--ssl-mode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabaseSslMode {
+    /// Never use TLS
+    Disabled,
+    /// Use TLS if the server supports it
+    Preferred,
+    /// Require TLS without verifying the server certificate
+    Required,
+    /// Require TLS and verify the server certificate against the CA
+    VerifyCa,
+    /// Also verify that the certificate matches the server host name
+    VerifyIdentity,
+}
+
+impl DatabaseSslMode {
+    /// Convert to string representation used in configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::Preferred => "preferred",
+            Self::Required => "required",
+            Self::VerifyCa => "verify_ca",
+            Self::VerifyIdentity => "verify_identity",
+        }
+    }
+
+    /// Parse from string representation, accepting MySQL's spelling
+    /// (e.g. `VERIFY_CA`) and dashes (e.g. `verify-ca`)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "disabled" => Some(Self::Disabled),
+            "preferred" => Some(Self::Preferred),
+            "required" => Some(Self::Required),
+            "verify_ca" => Some(Self::VerifyCa),
+            "verify_identity" => Some(Self::VerifyIdentity),
+            _ => None,
+        }
+    }
+
+    /// Whether the server certificate is verified
+    pub fn verifies_server(&self) -> bool {
+        matches!(self, Self::VerifyCa | Self::VerifyIdentity)
+    }
+}
+
+/// TLS settings for database connections
+///
+/// Paths point to PEM files. Options left unset fall back to the URL's
+/// query parameters (e.g. `?ssl-mode=required`), then to the driver
+/// default of `preferred`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DatabaseTlsConfig {
+    /// TLS mode
+    #[serde(default)]
+    pub ssl_mode: Option<DatabaseSslMode>,
+
+    /// CA certificate the server certificate is verified against
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+
+    /// Client certificate, for servers that authenticate clients by certificate
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+
+    /// Private key of the client certificate
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+}
+
+impl DatabaseTlsConfig {
+    /// Create from environment variables
+    ///
+    /// Reads `DATABASE_SSL_MODE`, `DATABASE_SSL_CA`, `DATABASE_SSL_CERT`
+    /// and `DATABASE_SSL_KEY`. An unknown mode is kept unset here and
+    /// rejected when the API configuration is loaded.
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+
+        Self {
+            ssl_mode: var("DATABASE_SSL_MODE").and_then(|mode| DatabaseSslMode::from_str(&mode)),
+            ca_cert_path: var("DATABASE_SSL_CA"),
+            client_cert_path: var("DATABASE_SSL_CERT"),
+            client_key_path: var("DATABASE_SSL_KEY"),
+        }
+    }
+
+    /// Check that the settings can be used together
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(mode) = self.ssl_mode.filter(DatabaseSslMode::verifies_server) {
+            if self.ca_cert_path.is_none() {
+                return Err(format!(
+                    "Database SSL mode {} requires a CA certificate (DATABASE_SSL_CA)",
+                    mode.as_str()
+                ));
+            }
+        }
+
+        if self.client_cert_path.is_some() != self.client_key_path.is_some() {
+            return Err(
+                "Database client certificate and key (DATABASE_SSL_CERT, DATABASE_SSL_KEY) must be set together"
+                    .to_string(),
+            );
+        }
+
+        if self.ssl_mode == Some(DatabaseSslMode::Disabled)
+            && (self.ca_cert_path.is_some() || self.client_cert_path.is_some())
+        {
+            return Err("Database certificates are configured but SSL mode is disabled".to_string());
+        }
+
+        Ok(())
     }
 }
 
@@ -6,24 +6,33 @@
 //! - `database` - Database connection and pool configuration
 //! - `environment` - Environment detection and logging configuration
 //! - `rate_limit` - Rate limiting for APIs, SMS, and authentication
+//! - `secret` - Credentials kept out of logs and cleared from memory
 //! - `server` - HTTP server, CORS, and TLS configuration
+//! - `sms` - Per-market SMS sender and branding, per-provider throughput and
+//!   daily spending budget
 
 pub mod auth;
 pub mod cache;
 pub mod database;
 pub mod environment;
 pub mod rate_limit;
+pub mod secret;
 pub mod server;
+pub mod sms;
 
 use serde::{Deserialize, Serialize};
 
 // Re-export commonly used types
-pub use auth::{AuthConfig, JwtConfig, SessionConfig};
+pub use auth::{AdminSsoConfig, AuthConfig, JwtConfig, PhoneHashConfig, SessionConfig};
 pub use cache::{CacheConfig, CacheStrategyConfig, CacheType};
-pub use database::DatabaseConfig;
+pub use database::{DatabaseConfig, DatabaseShardConfig, DatabaseSslMode, DatabaseTlsConfig};
 pub use environment::{Environment, LoggingConfig, MonitoringConfig};
-pub use rate_limit::RateLimitConfig;
-pub use server::{CorsConfig, ServerConfig, TlsConfig};
+pub use rate_limit::{ProofOfWorkConfig, RateLimitAlgorithm, RateLimitConfig};
+pub use secret::SecretString;
+pub use server::{CorsConfig, LoadSheddingConfig, ServerConfig, TenancyConfig, TlsConfig, UnknownFieldMode};
+pub use sms::{
+    SmsBudgetConfig, SmsMarketConfig, SmsOverBudgetAction, SmsProviderLimits, SmsRate, SmsRoute,
+};
 
 /// Complete application configuration combining all sub-configurations
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -70,6 +79,8 @@ impl Default for AppConfig {
                 jwt: JwtConfig::default(),
                 session: SessionConfig::default(),
                 oauth2: None,
+                admin_sso: None,
+                phone_hash: Default::default(),
             },
             cache: CacheStrategyConfig::default(),
             rate_limit: RateLimitConfig::default(),
@@ -91,6 +102,8 @@ impl AppConfig {
                 jwt: JwtConfig::default(),
                 session: SessionConfig::default(),
                 oauth2: None,
+                admin_sso: None,
+                phone_hash: Default::default(),
             },
             cache: CacheStrategyConfig::default(),
             rate_limit: RateLimitConfig::development(),
@@ -114,6 +127,8 @@ impl AppConfig {
                     ..Default::default()
                 },
                 oauth2: None,
+                admin_sso: None,
+                phone_hash: Default::default(),
             },
             cache: CacheStrategyConfig::default(),
             rate_limit: RateLimitConfig::production(),
@@ -10,6 +10,10 @@ pub struct RateLimitConfig {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
 
+    /// How requests are counted against the limits
+    #[serde(default)]
+    pub algorithm: RateLimitAlgorithm,
+
     /// SMS rate limits
     pub sms: SmsRateLimits,
 
@@ -19,11 +23,55 @@ pub struct RateLimitConfig {
     /// Authentication rate limits
     pub auth: AuthRateLimits,
 
+    /// Proof-of-work challenges required before sending a code
+    #[serde(default)]
+    pub proof_of_work: ProofOfWorkConfig,
+
     /// Custom endpoint limits
     #[serde(default)]
     pub custom_limits: HashMap<String, EndpointLimit>,
 }
 
+/// Algorithm counting requests against a limit over a window
+///
+/// Fixed windows are the cheapest but let a client spend its limit at the
+/// end of one window and again at the start of the next, doubling what it
+/// gets through in a short burst. The sliding window log and the token
+/// bucket never allow more than the limit within any window-long period.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    /// Count requests per calendar window, e.g. per clock hour
+    FixedWindow,
+    /// Keep the timestamp of every request made during the last window
+    #[default]
+    SlidingWindowLog,
+    /// Refill a bucket holding up to the limit at limit per window
+    TokenBucket,
+}
+
+impl RateLimitAlgorithm {
+    /// Convert to string representation used in configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FixedWindow => "fixed_window",
+            Self::SlidingWindowLog => "sliding_window_log",
+            Self::TokenBucket => "token_bucket",
+        }
+    }
+
+    /// Parse from string representation, accepting dashes (e.g. `token-bucket`)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "fixed_window" => Some(Self::FixedWindow),
+            "sliding_window_log" | "sliding_window" => Some(Self::SlidingWindowLog),
+            "token_bucket" => Some(Self::TokenBucket),
+            _ => None,
+        }
+    }
+}
+
 /// SMS-specific rate limits
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SmsRateLimits {
@@ -125,6 +173,36 @@ impl Default for AuthRateLimits {
     }
 }
 
+/// Proof-of-work challenge configuration
+///
+/// Clients solving a challenge spend CPU time on every SMS they request,
+/// which makes pumping large volumes of messages expensive while costing a
+/// single user well under a second.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProofOfWorkConfig {
+    /// Require a solved challenge before sending a code
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Leading zero bits the solution hash must have
+    #[serde(default = "default_pow_difficulty")]
+    pub difficulty: u8,
+
+    /// How long a challenge can be solved, in seconds
+    #[serde(default = "default_pow_challenge_ttl")]
+    pub challenge_ttl_seconds: u64,
+}
+
+impl Default for ProofOfWorkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            difficulty: default_pow_difficulty(),
+            challenge_ttl_seconds: default_pow_challenge_ttl(),
+        }
+    }
+}
+
 /// Custom endpoint rate limit
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EndpointLimit {
@@ -146,9 +224,11 @@ impl Default for RateLimitConfig {
     fn default() -> Self {
         Self {
             enabled: default_enabled(),
+            algorithm: RateLimitAlgorithm::default(),
             sms: SmsRateLimits::default(),
             api: ApiRateLimits::default(),
             auth: AuthRateLimits::default(),
+            proof_of_work: ProofOfWorkConfig::default(),
             custom_limits: HashMap::new(),
         }
     }
@@ -175,6 +255,7 @@ impl RateLimitConfig {
     pub fn development() -> Self {
         Self {
             enabled: true,
+            algorithm: RateLimitAlgorithm::default(),
             sms: SmsRateLimits {
                 per_phone_per_hour: 10,
                 per_phone_per_day: 50,
@@ -190,13 +271,20 @@ impl RateLimitConfig {
                 login_per_user_per_hour: 50,
                 ..Default::default()
             },
+            proof_of_work: ProofOfWorkConfig::default(),
             custom_limits: HashMap::new(),
         }
     }
 
     /// Create a production configuration (stricter limits)
     pub fn production() -> Self {
-        Self::default()
+        Self {
+            proof_of_work: ProofOfWorkConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Self::default()
+        }
     }
 }
 
@@ -219,3 +307,11 @@ fn default_burst_window() -> u64 {
 fn default_failed_attempts_threshold() -> u32 {
     5
 }
+
+fn default_pow_difficulty() -> u8 {
+    18
+}
+
+fn default_pow_challenge_ttl() -> u64 {
+    120  // 2 minutes
+}
@@ -1,6 +1,7 @@
 //! Server configuration module
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// HTTP server configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -38,6 +39,61 @@ pub struct ServerConfig {
     /// TLS configuration
     #[serde(default)]
     pub tls: Option<TlsConfig>,
+
+    /// Seconds to keep serving after reporting not-ready on shutdown, so
+    /// load balancers stop routing new requests first
+    #[serde(default = "default_drain_delay")]
+    pub drain_delay_seconds: u64,
+
+    /// Seconds in-flight requests get to finish once shutdown proceeds
+    #[serde(default = "default_shutdown_timeout")]
+    pub shutdown_timeout_seconds: u64,
+
+    /// How request bodies with fields the server does not know are handled
+    #[serde(default)]
+    pub unknown_field_mode: UnknownFieldMode,
+
+    /// Percentage of users (0-100) routed to the candidate implementation of
+    /// each canary, by canary name; unlisted canaries route nobody
+    #[serde(default)]
+    pub canary_rollouts: HashMap<String, u8>,
+
+    /// Rejection of low-priority requests while the instance is overloaded
+    #[serde(default)]
+    pub load_shedding: LoadSheddingConfig,
+
+    /// Resolution of the white-labeled marketplace (tenant) a request is for
+    #[serde(default)]
+    pub tenancy: TenancyConfig,
+}
+
+/// Tenant resolution configuration
+///
+/// Requests are served for the tenant their hostname is mapped to; unmapped
+/// hosts are served for the default tenant. When the header is enabled, a
+/// trusted proxy may select any configured tenant with `X-Tenant-ID` instead.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TenancyConfig {
+    /// Tenant ID by hostname, without port
+    #[serde(default)]
+    pub hosts: HashMap<String, String>,
+
+    /// Whether the `X-Tenant-ID` header selects the tenant; only enable
+    /// behind a proxy that strips the header from client requests
+    #[serde(default)]
+    pub header_enabled: bool,
+}
+
+/// Handling of JSON request fields a request schema does not declare
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownFieldMode {
+    /// Ignore unknown fields after logging and counting them, so clients
+    /// built against an older or newer schema keep working
+    #[default]
+    Compatible,
+    /// Reject requests with unknown fields, to surface schema drift in staging
+    Strict,
 }
 
 impl Default for ServerConfig {
@@ -52,6 +108,12 @@ impl Default for ServerConfig {
             enable_http2: false,
             enable_compression: default_enable_compression(),
             tls: None,
+            drain_delay_seconds: default_drain_delay(),
+            shutdown_timeout_seconds: default_shutdown_timeout(),
+            unknown_field_mode: UnknownFieldMode::default(),
+            canary_rollouts: HashMap::new(),
+            load_shedding: LoadSheddingConfig::default(),
+            tenancy: TenancyConfig::default(),
         }
     }
 }
@@ -111,6 +173,68 @@ impl ServerConfig {
     pub fn is_tls_enabled(&self) -> bool {
         self.tls.is_some()
     }
+
+    /// Percentage of users routed to the candidate implementation of a canary
+    pub fn canary_percentage(&self, canary: &str) -> u8 {
+        self.canary_rollouts.get(canary).copied().unwrap_or(0).min(100)
+    }
+}
+
+/// Load shedding configuration
+///
+/// The instance is overloaded when any health signal exceeds its limit,
+/// and critically overloaded when one exceeds twice its limit. Overloaded
+/// instances reject low-priority requests; critically overloaded instances
+/// reject every request except those to protected paths.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoadSheddingConfig {
+    /// Whether requests are shed at all
+    #[serde(default = "default_load_shedding_enabled")]
+    pub enabled: bool,
+
+    /// How often health signals are sampled in milliseconds
+    #[serde(default = "default_sample_interval_ms")]
+    pub sample_interval_ms: u64,
+
+    /// Event loop lag above which the instance is overloaded, in milliseconds
+    #[serde(default = "default_max_event_loop_lag_ms")]
+    pub max_event_loop_lag_ms: u64,
+
+    /// Share of database connections in use (0-100) above which the
+    /// instance is overloaded
+    #[serde(default = "default_max_db_pool_utilization")]
+    pub max_db_pool_utilization_percent: u8,
+
+    /// Redis round-trip time above which the instance is overloaded, in milliseconds
+    #[serde(default = "default_max_redis_latency_ms")]
+    pub max_redis_latency_ms: u64,
+
+    /// Path prefixes of low-priority requests, shed first
+    #[serde(default = "default_low_priority_paths")]
+    pub low_priority_paths: Vec<String>,
+
+    /// Path prefixes of requests that are never shed
+    #[serde(default = "default_protected_paths")]
+    pub protected_paths: Vec<String>,
+
+    /// Seconds clients are asked to wait before retrying a shed request
+    #[serde(default = "default_shed_retry_after")]
+    pub retry_after_seconds: u64,
+}
+
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_load_shedding_enabled(),
+            sample_interval_ms: default_sample_interval_ms(),
+            max_event_loop_lag_ms: default_max_event_loop_lag_ms(),
+            max_db_pool_utilization_percent: default_max_db_pool_utilization(),
+            max_redis_latency_ms: default_max_redis_latency_ms(),
+            low_priority_paths: default_low_priority_paths(),
+            protected_paths: default_protected_paths(),
+            retry_after_seconds: default_shed_retry_after(),
+        }
+    }
 }
 
 /// TLS/SSL configuration
@@ -133,6 +257,50 @@ pub struct TlsConfig {
     /// Minimum TLS version (e.g., "1.2", "1.3")
     #[serde(default = "default_min_tls_version")]
     pub min_version: String,
+
+    /// Path prefixes that require a verified client certificate
+    /// (e.g. "/api/v1/admin", "/metrics"); other routes accept any client
+    #[serde(default)]
+    pub client_auth_paths: Vec<String>,
+
+    /// SHA-256 fingerprints of client certificates mapped to the service
+    /// identity they authenticate
+    #[serde(default)]
+    pub client_identities: HashMap<String, String>,
+}
+
+impl TlsConfig {
+    /// Check if a request path requires a client certificate
+    pub fn requires_client_cert(&self, path: &str) -> bool {
+        self.verify_client
+            && self.client_auth_paths.iter().any(|prefix| {
+                path == prefix
+                    || path
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('/') || prefix.ends_with('/'))
+            })
+    }
+
+    /// Find the service identity of a certificate fingerprint
+    ///
+    /// Fingerprints are compared as lower-case hex with any `:` separators removed.
+    pub fn identity_for(&self, fingerprint: &str) -> Option<&str> {
+        let fingerprint = normalize_fingerprint(fingerprint);
+        self.client_identities
+            .iter()
+            .find(|(known, _)| normalize_fingerprint(known) == fingerprint)
+            .map(|(_, identity)| identity.as_str())
+    }
+}
+
+/// Normalize a certificate fingerprint to lower-case hex without separators
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| *c != ':')
+        .collect::<String>()
+        .trim()
+        .to_ascii_lowercase()
 }
 
 impl Default for TlsConfig {
@@ -143,6 +311,8 @@ impl Default for TlsConfig {
             ca_path: None,
             verify_client: false,
             min_version: default_min_tls_version(),
+            client_auth_paths: Vec::new(),
+            client_identities: HashMap::new(),
         }
     }
 }
@@ -224,6 +394,55 @@ fn default_enable_compression() -> bool {
     true
 }
 
+fn default_drain_delay() -> u64 {
+    15  // 15 seconds
+}
+
+fn default_shutdown_timeout() -> u64 {
+    30  // 30 seconds
+}
+
+fn default_load_shedding_enabled() -> bool {
+    true
+}
+
+fn default_sample_interval_ms() -> u64 {
+    500
+}
+
+fn default_max_event_loop_lag_ms() -> u64 {
+    100
+}
+
+fn default_max_db_pool_utilization() -> u8 {
+    90
+}
+
+fn default_max_redis_latency_ms() -> u64 {
+    50
+}
+
+fn default_low_priority_paths() -> Vec<String> {
+    vec![
+        String::from("/api/v1/search"),
+        String::from("/api/v1/analytics"),
+    ]
+}
+
+fn default_protected_paths() -> Vec<String> {
+    vec![
+        String::from("/api/v1/auth"),
+        String::from("/api/v1/payments"),
+        String::from("/api/v1/payouts"),
+        String::from("/api/v1/webhooks"),
+        String::from("/health"),
+    ]
+}
+
+fn default_shed_retry_after() -> u64 {
+    5
+}
+
 fn default_min_tls_version() -> String {
     String::from("1.2")
 }
@@ -0,0 +1,503 @@
+//! SMS market, routing, provider throughput and spending configuration module
+
+use serde::{Deserialize, Serialize};
+
+/// Sender and branding settings for one market
+///
+/// Markets are keyed by dialing code (e.g. `+61`), matching the country
+/// codes used elsewhere for market-specific behaviour.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SmsMarketConfig {
+    /// Dialing code of the market, such as `+61` or `+86`
+    pub country_code: String,
+
+    /// Alphanumeric sender ID, short code or long number messages come from
+    #[serde(default)]
+    pub sender_id: Option<String>,
+
+    /// Namespace of the provider-registered templates used in this market
+    #[serde(default)]
+    pub templates_namespace: Option<String>,
+
+    /// Text appended to every message, such as an opt-out notice
+    #[serde(default)]
+    pub compliance_footer: Option<String>,
+
+    /// Whether messages to this market use these settings
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl SmsMarketConfig {
+    /// Create an enabled market with a sender ID
+    pub fn new(country_code: impl Into<String>, sender_id: impl Into<String>) -> Self {
+        Self {
+            country_code: country_code.into(),
+            sender_id: Some(sender_id.into()),
+            templates_namespace: None,
+            compliance_footer: None,
+            enabled: true,
+        }
+    }
+
+    /// Load the markets listed in `SMS_MARKETS` from environment variables
+    ///
+    /// `SMS_MARKETS` is a comma-separated list of dialing codes. Each market
+    /// reads `SMS_MARKET_<digits>_SENDER_ID`, `_TEMPLATES_NAMESPACE`,
+    /// `_COMPLIANCE_FOOTER` and `_ENABLED`, e.g. `SMS_MARKET_61_SENDER_ID`.
+    pub fn list_from_env() -> Vec<Self> {
+        let markets = std::env::var("SMS_MARKETS").unwrap_or_default();
+
+        markets
+            .split(',')
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(|code| {
+                let digits = code.trim_start_matches('+');
+                let var = |name: &str| {
+                    std::env::var(format!("SMS_MARKET_{}_{}", digits, name))
+                        .ok()
+                        .filter(|value| !value.trim().is_empty())
+                };
+
+                Self {
+                    country_code: format!("+{}", digits),
+                    sender_id: var("SENDER_ID"),
+                    templates_namespace: var("TEMPLATES_NAMESPACE"),
+                    compliance_footer: var("COMPLIANCE_FOOTER"),
+                    enabled: var("ENABLED")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(true),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a phone number in E.164 format belongs to this market
+    pub fn matches(&self, phone: &str) -> bool {
+        phone.starts_with(&self.country_code)
+    }
+
+    /// Validate the market settings
+    ///
+    /// Enabled markets must have a sender configured; disabled markets are
+    /// only checked for a well-formed dialing code.
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_dialing_code(&self.country_code) {
+            return Err(format!(
+                "SMS market '{}' must be a dialing code such as +61",
+                self.country_code
+            ));
+        }
+
+        if self.enabled && self.sender_id.as_deref().is_none_or(|id| id.trim().is_empty()) {
+            return Err(format!(
+                "SMS market {} is enabled but has no sender ID configured",
+                self.country_code
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a string is a dialing code such as `+61`
+fn is_dialing_code(code: &str) -> bool {
+    let digits = code.strip_prefix('+').unwrap_or_default();
+    !digits.is_empty() && digits.len() <= 3 && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Provider the messages to one country are sent through
+///
+/// Routes are keyed by dialing code (e.g. `+86`); the route without a
+/// dialing code is the default for every other destination. Providers are
+/// named as in `SMS_PROVIDER` (e.g. `twilio`, `failover`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SmsRoute {
+    /// Dialing code of the destination, such as `+86`; None for the default route
+    #[serde(default)]
+    pub country_code: Option<String>,
+
+    /// Provider the messages are sent through
+    pub provider: String,
+}
+
+impl SmsRoute {
+    /// Create a route for the destinations with a dialing code
+    pub fn new(country_code: impl Into<String>, provider: impl Into<String>) -> Self {
+        Self {
+            country_code: Some(country_code.into()),
+            provider: provider.into(),
+        }
+    }
+
+    /// Create the route for destinations without a route of their own
+    pub fn fallback(provider: impl Into<String>) -> Self {
+        Self {
+            country_code: None,
+            provider: provider.into(),
+        }
+    }
+
+    /// Parse a rule such as `+86 -> aliyun` or `default -> failover`
+    pub fn parse(rule: &str) -> Option<Self> {
+        let (destination, provider) = rule.split_once("->")?;
+        let (destination, provider) = (destination.trim(), provider.trim());
+        if destination.is_empty() || provider.is_empty() {
+            return None;
+        }
+
+        Some(if destination.eq_ignore_ascii_case("default") {
+            Self::fallback(provider)
+        } else {
+            Self::new(destination, provider)
+        })
+    }
+
+    /// Load the rules listed in `SMS_ROUTES` from environment variables
+    ///
+    /// `SMS_ROUTES` is a comma-separated list of rules, e.g.
+    /// `+86 -> aliyun, +61 -> twilio, default -> failover`. Malformed rules
+    /// are skipped.
+    pub fn list_from_env() -> Vec<Self> {
+        std::env::var("SMS_ROUTES")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(Self::parse)
+            .collect()
+    }
+
+    /// Route of a phone number in E.164 format
+    ///
+    /// The route with the longest matching dialing code wins, so `+1` and
+    /// `+1268` can coexist; numbers without one take the default route.
+    pub fn resolve<'a>(routes: &'a [Self], phone: &str) -> Option<&'a Self> {
+        routes
+            .iter()
+            .filter(|route| route.country_code.as_deref().is_some_and(|code| phone.starts_with(code)))
+            .max_by_key(|route| route.country_code.as_deref().map_or(0, str::len))
+            .or_else(|| routes.iter().find(|route| route.country_code.is_none()))
+    }
+
+    /// Validate a list of routes
+    ///
+    /// Every dialing code, and the default, may be routed only once.
+    pub fn validate_all(routes: &[Self]) -> Result<(), String> {
+        for (index, route) in routes.iter().enumerate() {
+            route.validate()?;
+            if routes[..index].iter().any(|other| other.country_code == route.country_code) {
+                return Err(format!(
+                    "SMS route for {} is configured more than once",
+                    route.country_code.as_deref().unwrap_or("default")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate the route
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(ref country_code) = self.country_code {
+            if !is_dialing_code(country_code) {
+                return Err(format!(
+                    "SMS route '{}' must be a dialing code such as +61",
+                    country_code
+                ));
+            }
+        }
+
+        if self.provider.trim().is_empty() {
+            return Err(format!(
+                "SMS route for {} has no provider",
+                self.country_code.as_deref().unwrap_or("default")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Throughput limits of one SMS provider
+///
+/// Sends over the limits wait in a bounded queue instead of reaching the
+/// provider and being throttled. Providers are keyed by the name used in
+/// `SMS_PROVIDER` (e.g. `twilio`, `aws-sns`).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SmsProviderLimits {
+    /// Provider the limits apply to
+    pub provider: String,
+
+    /// Maximum number of sends in flight at once
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+
+    /// Sustained number of sends started per second
+    #[serde(default = "default_rate_per_second")]
+    pub rate_per_second: f64,
+
+    /// Number of sends that may start at once after an idle period
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+
+    /// Maximum number of sends waiting per priority before new ones are rejected
+    #[serde(default = "default_max_queue_depth")]
+    pub max_queue_depth: usize,
+}
+
+fn default_max_concurrent() -> usize {
+    10
+}
+
+fn default_rate_per_second() -> f64 {
+    10.0
+}
+
+fn default_burst() -> u32 {
+    20
+}
+
+fn default_max_queue_depth() -> usize {
+    1000
+}
+
+impl SmsProviderLimits {
+    /// Create limits for a provider with the default values
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            max_concurrent: default_max_concurrent(),
+            rate_per_second: default_rate_per_second(),
+            burst: default_burst(),
+            max_queue_depth: default_max_queue_depth(),
+        }
+    }
+
+    /// Load the providers listed in `SMS_PROVIDER_LIMITS` from environment variables
+    ///
+    /// `SMS_PROVIDER_LIMITS` is a comma-separated list of provider names. Each
+    /// provider reads `SMS_PROVIDER_<NAME>_MAX_CONCURRENT`, `_RATE_PER_SECOND`,
+    /// `_BURST` and `_MAX_QUEUE_DEPTH`, e.g. `SMS_PROVIDER_AWS_SNS_BURST`.
+    pub fn list_from_env() -> Vec<Self> {
+        let providers = std::env::var("SMS_PROVIDER_LIMITS").unwrap_or_default();
+
+        providers
+            .split(',')
+            .map(str::trim)
+            .filter(|provider| !provider.is_empty())
+            .map(|provider| {
+                let name = provider.to_uppercase().replace('-', "_");
+                let var = |suffix: &str| {
+                    std::env::var(format!("SMS_PROVIDER_{}_{}", name, suffix)).ok()
+                };
+                let defaults = Self::new(provider);
+
+                Self {
+                    max_concurrent: var("MAX_CONCURRENT")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(defaults.max_concurrent),
+                    rate_per_second: var("RATE_PER_SECOND")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(defaults.rate_per_second),
+                    burst: var("BURST")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(defaults.burst),
+                    max_queue_depth: var("MAX_QUEUE_DEPTH")
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(defaults.max_queue_depth),
+                    ..defaults
+                }
+            })
+            .collect()
+    }
+
+    /// Validate the provider limits
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_concurrent == 0 || self.burst == 0 || self.max_queue_depth == 0 {
+            return Err(format!(
+                "SMS provider {} limits must allow at least one send in flight, in a burst and in the queue",
+                self.provider
+            ));
+        }
+
+        if !self.rate_per_second.is_finite() || self.rate_per_second <= 0.0 {
+            return Err(format!(
+                "SMS provider {} rate must be a positive number of sends per second",
+                self.provider
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// What happens to a message once the daily SMS budget is spent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsOverBudgetAction {
+    /// Refuse every message until the next UTC day
+    #[default]
+    Refuse,
+    /// Keep sending through the cheapest provider of each destination only
+    Downgrade,
+}
+
+impl SmsOverBudgetAction {
+    /// Convert to string representation used in configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Refuse => "refuse",
+            Self::Downgrade => "downgrade",
+        }
+    }
+
+    /// Parse from string representation
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "refuse" => Some(Self::Refuse),
+            "downgrade" => Some(Self::Downgrade),
+            _ => None,
+        }
+    }
+}
+
+/// Estimated cost of a message sent through a provider to a destination
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SmsRate {
+    /// Provider name used in `SMS_PROVIDER` (e.g. `twilio`, `aws-sns`)
+    pub provider: String,
+
+    /// Dialing prefix of the destination, such as `+61`; `+` matches every number
+    pub prefix: String,
+
+    /// Cost of one message in millionths of the budget currency
+    pub cost_micros: u64,
+}
+
+/// SMS spending estimates and daily budget
+///
+/// Costs are estimated from the rate with the longest prefix matching the
+/// destination, for the provider sending the message. Messages without a
+/// matching rate cost nothing against the budget.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SmsBudgetConfig {
+    /// ISO 4217 currency of the rates and budget
+    #[serde(default = "default_budget_currency")]
+    pub currency: String,
+
+    /// Spend allowed per UTC day in millionths of the currency; None for no cap
+    #[serde(default)]
+    pub daily_budget_micros: Option<u64>,
+
+    /// What happens to messages once the budget is spent
+    #[serde(default)]
+    pub over_budget: SmsOverBudgetAction,
+
+    /// Estimated cost per provider and destination prefix
+    #[serde(default)]
+    pub rates: Vec<SmsRate>,
+}
+
+fn default_budget_currency() -> String {
+    "USD".to_string()
+}
+
+impl Default for SmsBudgetConfig {
+    fn default() -> Self {
+        Self {
+            currency: default_budget_currency(),
+            daily_budget_micros: None,
+            over_budget: SmsOverBudgetAction::default(),
+            rates: Vec::new(),
+        }
+    }
+}
+
+/// Convert an amount in currency units, such as `0.0515`, to millionths
+fn parse_micros(value: &str) -> Option<u64> {
+    let amount: f64 = value.trim().parse().ok()?;
+    (amount.is_finite() && amount >= 0.0).then(|| (amount * 1_000_000.0).round() as u64)
+}
+
+impl SmsBudgetConfig {
+    /// Load the budget from environment variables
+    ///
+    /// - `SMS_DAILY_BUDGET`: spend allowed per UTC day, e.g. `50.00`
+    /// - `SMS_OVER_BUDGET`: `refuse` (default) or `downgrade`
+    /// - `SMS_COST_CURRENCY`: currency of the budget and rates (default: `USD`)
+    /// - `SMS_RATES`: comma-separated `provider:prefix:cost` entries, e.g.
+    ///   `twilio:+61:0.0515,twilio:+:0.08,aws-sns:+61:0.0386`
+    ///
+    /// Malformed rate entries are skipped.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let rates = std::env::var("SMS_RATES").unwrap_or_default();
+
+        Self {
+            currency: std::env::var("SMS_COST_CURRENCY").unwrap_or(defaults.currency),
+            daily_budget_micros: std::env::var("SMS_DAILY_BUDGET")
+                .ok()
+                .and_then(|value| parse_micros(&value)),
+            over_budget: std::env::var("SMS_OVER_BUDGET")
+                .ok()
+                .and_then(|value| SmsOverBudgetAction::from_str(&value))
+                .unwrap_or(defaults.over_budget),
+            rates: rates
+                .split(',')
+                .filter_map(|entry| {
+                    let mut parts = entry.trim().splitn(3, ':');
+                    let provider = parts.next()?.trim();
+                    let prefix = parts.next()?.trim();
+                    let cost_micros = parse_micros(parts.next()?)?;
+                    (!provider.is_empty()).then(|| SmsRate {
+                        provider: provider.to_string(),
+                        prefix: prefix.to_string(),
+                        cost_micros,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Rate of a provider for a destination, by longest matching prefix
+    pub fn rate(&self, provider: &str, phone_number: &str) -> Option<&SmsRate> {
+        self.rates
+            .iter()
+            .filter(|rate| rate.provider == provider && phone_number.starts_with(&rate.prefix))
+            .max_by_key(|rate| rate.prefix.len())
+    }
+
+    /// Lowest cost of any provider for a destination
+    pub fn cheapest_cost(&self, phone_number: &str) -> Option<u64> {
+        let mut providers: Vec<&str> = self.rates.iter().map(|rate| rate.provider.as_str()).collect();
+        providers.sort_unstable();
+        providers.dedup();
+
+        providers
+            .into_iter()
+            .filter_map(|provider| self.rate(provider, phone_number))
+            .map(|rate| rate.cost_micros)
+            .min()
+    }
+
+    /// Validate the budget and rates
+    pub fn validate(&self) -> Result<(), String> {
+        if self.currency.len() != 3 || !self.currency.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(format!("SMS cost currency {} is not an ISO 4217 code", self.currency));
+        }
+
+        if let Some(rate) = self.rates.iter().find(|rate| !rate.prefix.starts_with('+')) {
+            return Err(format!(
+                "SMS rate prefix {} of provider {} must start with '+'",
+                rate.prefix, rate.provider
+            ));
+        }
+
+        Ok(())
+    }
+}